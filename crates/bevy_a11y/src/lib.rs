@@ -66,14 +66,23 @@ extern crate alloc;
 use alloc::sync::Arc;
 use core::sync::atomic::{AtomicBool, Ordering};
 
-use accesskit::Node;
-use bevy_app::Plugin;
+use accesskit::{Live, Node};
+use bevy_app::{Plugin, PostUpdate};
 use bevy_derive::{Deref, DerefMut};
-use bevy_ecs::{component::Component, message::Message, resource::Resource, schedule::SystemSet};
+use bevy_ecs::{
+    component::Component,
+    lifecycle::RemovedComponents,
+    message::Message,
+    query::{Added, Changed, Or, Without},
+    resource::Resource,
+    schedule::{IntoScheduleConfigs, SystemSet},
+    system::Query,
+};
 
 #[cfg(feature = "bevy_reflect")]
 use {
-    bevy_ecs::reflect::ReflectResource, bevy_reflect::std_traits::ReflectDefault,
+    bevy_ecs::reflect::{ReflectComponent, ReflectResource},
+    bevy_reflect::std_traits::ReflectDefault,
     bevy_reflect::Reflect,
 };
 
@@ -243,6 +252,62 @@ impl From<Node> for AccessibilityNode {
     }
 }
 
+/// Marks an entity with an [`AccessibilityNode`] as a live region.
+///
+/// Assistive technologies announce changes to the content of live regions, such as their value or
+/// label, without the user having to move the focus to them. This is useful for dynamic text like
+/// status messages, notifications or score counters.
+///
+/// For `bevy_ui` text, add this component next to a `Label`.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Default, Debug, PartialEq, Clone)
+)]
+#[cfg_attr(
+    all(feature = "bevy_reflect", feature = "serialize"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum LiveRegion {
+    /// Changes are announced when the user is idle, without interrupting the current speech.
+    #[default]
+    Polite,
+    /// Changes are announced immediately, interrupting the current speech. Use sparingly, for
+    /// time-sensitive information like errors.
+    Assertive,
+}
+
+impl From<LiveRegion> for Live {
+    fn from(live_region: LiveRegion) -> Self {
+        match live_region {
+            LiveRegion::Polite => Live::Polite,
+            LiveRegion::Assertive => Live::Assertive,
+        }
+    }
+}
+
+/// Copies the [`LiveRegion`] of entities to their [`AccessibilityNode`].
+fn update_live_regions(
+    mut live_regions: Query<
+        (&LiveRegion, &mut AccessibilityNode),
+        Or<(Changed<LiveRegion>, Added<AccessibilityNode>)>,
+    >,
+    mut removed_live_regions: RemovedComponents<LiveRegion>,
+    mut nodes: Query<&mut AccessibilityNode, Without<LiveRegion>>,
+) {
+    for (live_region, mut node) in &mut live_regions {
+        node.set_live((*live_region).into());
+    }
+
+    for entity in removed_live_regions.read() {
+        if let Ok(mut node) = nodes.get_mut(entity) {
+            node.clear_live();
+        }
+    }
+}
+
 /// A system set relating to accessibility.
 ///
 /// Helps run accessibility updates all at once.
@@ -271,6 +336,8 @@ pub enum AccessibilitySystems {
 /// - no assistive technologies have requested accessibility information yet,
 ///   and
 /// - Bevy's ECS will manage updates to the accessibility tree.
+///
+/// It also keeps the [`AccessibilityNode`]s of [`LiveRegion`]s up to date.
 #[derive(Default)]
 pub struct AccessibilityPlugin;
 
@@ -278,6 +345,10 @@ impl Plugin for AccessibilityPlugin {
     fn build(&self, app: &mut bevy_app::App) {
         app.init_resource::<AccessibilityRequested>()
             .init_resource::<ManageAccessibilityUpdates>()
-            .allow_ambiguous_component::<AccessibilityNode>();
+            .allow_ambiguous_component::<AccessibilityNode>()
+            .add_systems(
+                PostUpdate,
+                update_live_regions.before(AccessibilitySystems::Update),
+            );
     }
 }
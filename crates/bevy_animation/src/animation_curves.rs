@@ -84,7 +84,7 @@
 //! [`animated_field`]: crate::animated_field
 
 use core::{
-    any::TypeId,
+    any::{Any, TypeId},
     fmt::{self, Debug, Formatter},
     marker::PhantomData,
 };
@@ -389,6 +389,10 @@ where
             });
         Ok(())
     }
+
+    fn curve_mut(&mut self) -> Option<&mut dyn Any> {
+        Some(&mut self.curve)
+    }
 }
 
 impl<A: Animatable> AnimationCurveEvaluator for AnimatableCurveEvaluator<A> {
@@ -599,11 +603,22 @@ pub trait AnimationCurve: Debug + Send + Sync + 'static {
         weight: f32,
         graph_node: AnimationNodeIndex,
     ) -> Result<(), AnimationEvaluationError>;
+
+    /// Returns a mutable reference to the underlying curve that produces the
+    /// animated values, if this animation curve exposes one.
+    ///
+    /// This lets editors modify the keyframes of a curve after it has been
+    /// type-erased, by downcasting the returned value to the concrete curve
+    /// type, such as [`AnimatableKeyframeCurve`]. See
+    /// [`AnimationClip::edit_curve`](crate::AnimationClip::edit_curve).
+    fn curve_mut(&mut self) -> Option<&mut dyn Any> {
+        None
+    }
 }
 
 /// The [`EvaluatorId`] is used to look up the [`AnimationCurveEvaluator`] for an [`AnimatableProperty`].
 /// For a given animated property, this ID should always be the same to allow things like animation blending to occur.
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub enum EvaluatorId<'a> {
     /// Corresponds to a specific field on a specific component type.
     /// The `TypeId` should correspond to the component type, and the `usize`
@@ -750,6 +765,77 @@ where
             core: UnevenCore::new(keyframes)?,
         })
     }
+
+    /// Returns the number of keyframes of this curve.
+    #[inline]
+    pub fn keyframe_count(&self) -> usize {
+        self.core.times.len()
+    }
+
+    /// Returns the time and the value of each keyframe, in chronological
+    /// order.
+    pub fn keyframes(&self) -> impl Iterator<Item = (f32, &T)> {
+        self.core.times.iter().copied().zip(&self.core.samples)
+    }
+
+    /// Returns a mutable reference to the value of the keyframe at `index`.
+    pub fn keyframe_value_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.core.samples.get_mut(index)
+    }
+
+    /// Inserts a keyframe at the given `time`, replacing the value of the
+    /// keyframe already at that time, if any, and returns its index.
+    ///
+    /// Returns `None` and leaves the curve unchanged if `time` isn't finite.
+    pub fn insert_keyframe(&mut self, time: f32, value: T) -> Option<usize> {
+        if !time.is_finite() {
+            return None;
+        }
+        let index = self.core.times.partition_point(|&t| t < time);
+        if self.core.times.get(index) == Some(&time) {
+            self.core.samples[index] = value;
+        } else {
+            self.core.times.insert(index, time);
+            self.core.samples.insert(index, value);
+        }
+        Some(index)
+    }
+
+    /// Removes the keyframe at `index`, and returns its time and value.
+    ///
+    /// A keyframe curve always has at least two keyframes, so this returns
+    /// `None` and leaves the curve unchanged if there are only two keyframes
+    /// left, or if `index` is out of bounds.
+    pub fn remove_keyframe(&mut self, index: usize) -> Option<(f32, T)> {
+        if index >= self.keyframe_count() || self.keyframe_count() <= 2 {
+            return None;
+        }
+        Some((
+            self.core.times.remove(index),
+            self.core.samples.remove(index),
+        ))
+    }
+
+    /// Moves the keyframe at `index` to the given `time`, and returns its new
+    /// index. The keyframe replaces the keyframe already at that time, if any.
+    ///
+    /// Returns `None` and leaves the curve unchanged if `index` is out of
+    /// bounds, if `time` isn't finite, or if the keyframe would replace
+    /// another one when there are only two keyframes left.
+    pub fn move_keyframe(&mut self, index: usize, time: f32) -> Option<usize> {
+        if index >= self.keyframe_count() || !time.is_finite() {
+            return None;
+        }
+        if self.core.times[index] == time {
+            return Some(index);
+        }
+        if self.keyframe_count() <= 2 && self.core.times.contains(&time) {
+            return None;
+        }
+        self.core.times.remove(index);
+        let value = self.core.samples.remove(index);
+        self.insert_keyframe(time, value)
+    }
 }
 
 fn inconsistent<P>() -> AnimationEvaluationError
@@ -806,4 +892,26 @@ mod tests {
         let _ = AnimatedField::new_unchecked("1", |b: &mut B| &mut b.1);
         let _ = AnimatedField::new_unchecked("2", |b: &mut B| &mut b.2);
     }
+
+    #[test]
+    fn test_edit_keyframes() {
+        let mut curve = AnimatableKeyframeCurve::new([(0.0, 0.0), (1.0, 1.0)]).unwrap();
+        assert_eq!(curve.insert_keyframe(0.5, 2.0), Some(1));
+        assert_eq!(curve.insert_keyframe(0.5, 3.0), Some(1));
+        assert_eq!(curve.insert_keyframe(f32::NAN, 3.0), None);
+        assert_eq!(curve.sample_clamped(0.25), 1.5);
+
+        // Moving the first keyframe after the last one extends the domain
+        assert_eq!(curve.move_keyframe(0, 2.0), Some(2));
+        assert_eq!(curve.domain(), Interval::new(0.5, 2.0).unwrap());
+        assert_eq!(curve.sample_clamped(1.5), 0.5);
+
+        assert_eq!(curve.remove_keyframe(1), Some((1.0, 1.0)));
+        assert_eq!(curve.remove_keyframe(0), None);
+        assert_eq!(curve.move_keyframe(0, 2.0), None);
+        assert_eq!(
+            curve.keyframes().collect::<Vec<_>>(),
+            [(0.5, &3.0), (2.0, &0.0)]
+        );
+    }
 }
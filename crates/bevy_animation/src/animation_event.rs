@@ -6,6 +6,8 @@ use bevy_ecs::{
     observer::{CachedObservers, TriggerContext},
     world::DeferredWorld,
 };
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use serde::{Deserialize, Serialize};
 
 /// An [`Event`] that an [`AnimationPlayer`](crate::AnimationPlayer) or an [`AnimationTargetId`](crate::AnimationTargetId) can trigger when playing an [`AnimationClip`](crate::AnimationClip).
 ///
@@ -60,3 +62,58 @@ unsafe impl<E: AnimationEvent + for<'a> Event<Trigger<'a> = AnimationEventTrigge
         }
     }
 }
+
+/// An event authored at a keyframe of an [`AnimationClip`](crate::AnimationClip), such as a
+/// footstep or the frame an attack hits.
+///
+/// Unlike other [`AnimationEvent`]s, these events are described by data rather than by a type, so
+/// they can be created by tools and asset loaders. They are added with
+/// [`AnimationClip::add_keyframe_event`](crate::AnimationClip::add_keyframe_event), and observed
+/// like any other animation event:
+///
+/// ```
+/// # use bevy_animation::KeyframeEvent;
+/// # use bevy_ecs::observer::On;
+/// fn play_footstep(event: On<KeyframeEvent>) {
+///     // Ignore the footsteps of animations that are barely blended in.
+///     if event.name == "footstep" && event.weight > 0.5 {
+///         // ...
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[reflect(Clone, Debug, PartialEq)]
+pub struct KeyframeEvent {
+    /// The name of the event.
+    pub name: String,
+    /// The data attached to the event.
+    pub payload: KeyframeEventPayload,
+    /// The time of the event in the clip, in seconds.
+    pub time: f32,
+    /// The weight of the clip in the animation graph when the event occurred, which can be used
+    /// to ignore the events of clips that are blended out.
+    pub weight: f32,
+}
+
+impl Event for KeyframeEvent {
+    type Trigger<'a> = AnimationEventTrigger;
+}
+
+impl AnimationEvent for KeyframeEvent {}
+
+/// The data attached to a [`KeyframeEvent`].
+#[derive(Clone, Debug, Default, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Clone, Debug, Default, PartialEq)]
+pub enum KeyframeEventPayload {
+    /// No data.
+    #[default]
+    None,
+    /// A boolean.
+    Bool(bool),
+    /// An integer.
+    Int(i64),
+    /// A number.
+    Float(f32),
+    /// A string.
+    String(String),
+}
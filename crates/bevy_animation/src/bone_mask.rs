@@ -0,0 +1,142 @@
+//! Per-joint weights, which limit animation graph nodes to a part of the body.
+
+use std::io;
+
+use bevy_asset::{io::Reader, Asset, AssetLoader, LoadContext};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
+use ron::de::SpannedError;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::AnimationTargetId;
+
+/// The weight of each joint of a skeleton in a node of an
+/// [`AnimationGraph`](crate::graph::AnimationGraph).
+///
+/// A bone mask is set on a node with
+/// [`AnimationGraphNode::set_bone_mask`](crate::graph::AnimationGraphNode::set_bone_mask),
+/// and scales the weight of the node for each joint it animates. For example,
+/// an upper-body "aim" layer can be limited to the spine and the arms, so that
+/// the legs keep playing the locomotion layer:
+///
+/// ```
+/// # use bevy_animation::{bone_mask::BoneMask, AnimationTargetId};
+/// # use bevy_ecs::name::Name;
+/// let hips = AnimationTargetId::from_name(&Name::new("Hips"));
+/// let spine = AnimationTargetId::from_names([Name::new("Hips"), Name::new("Spine")].iter());
+/// let mask = BoneMask::default().with_weight(spine, 1.0);
+/// assert_eq!(mask.weight(spine), 1.0);
+/// assert_eq!(mask.weight(hips), 0.0);
+/// ```
+///
+/// Unlike mask groups, which either allow or prevent a node and its
+/// descendants from animating a joint, bone masks only apply to the node
+/// they're set on, and can partially blend joints, which smooths the
+/// transition between the masked and unmasked parts of the body.
+///
+/// Bone masks can be loaded from `.bonemask.ron` files with the
+/// [`BoneMaskAssetLoader`].
+#[derive(Asset, Clone, Debug, Reflect, Serialize, Deserialize)]
+#[reflect(Clone, Debug, Default)]
+pub struct BoneMask {
+    /// The weight of the joints that aren't in [`BoneMask::weights`].
+    ///
+    /// This defaults to 0.0, so that only the joints added to the mask are
+    /// animated.
+    pub default_weight: f32,
+    /// The weight of each joint.
+    pub weights: HashMap<AnimationTargetId, f32>,
+}
+
+impl Default for BoneMask {
+    fn default() -> Self {
+        Self {
+            default_weight: 0.0,
+            weights: HashMap::default(),
+        }
+    }
+}
+
+impl BoneMask {
+    /// Creates a bone mask giving the same weight to all the joints.
+    pub fn new(default_weight: f32) -> Self {
+        Self {
+            default_weight,
+            weights: HashMap::default(),
+        }
+    }
+
+    /// Returns this bone mask with the weight of the `target` joint set.
+    pub fn with_weight(mut self, target: AnimationTargetId, weight: f32) -> Self {
+        self.set_weight(target, weight);
+        self
+    }
+
+    /// Returns this bone mask with the weight of all the `targets` joints set.
+    pub fn with_weights(
+        mut self,
+        targets: impl IntoIterator<Item = AnimationTargetId>,
+        weight: f32,
+    ) -> Self {
+        for target in targets {
+            self.set_weight(target, weight);
+        }
+        self
+    }
+
+    /// Sets the weight of the `target` joint.
+    pub fn set_weight(&mut self, target: AnimationTargetId, weight: f32) -> &mut Self {
+        self.weights.insert(target, weight);
+        self
+    }
+
+    /// Returns the weight of the `target` joint.
+    pub fn weight(&self, target: AnimationTargetId) -> f32 {
+        self.weights
+            .get(&target)
+            .copied()
+            .unwrap_or(self.default_weight)
+    }
+}
+
+/// An [`AssetLoader`] that can load [`BoneMask`]s as assets.
+///
+/// The canonical extension for [`BoneMask`]s is `.bonemask.ron`.
+#[derive(Default)]
+pub struct BoneMaskAssetLoader;
+
+/// Errors that can occur when deserializing bone masks from RON.
+#[derive(Error, Debug)]
+pub enum BoneMaskLoadError {
+    /// An I/O error occurred.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// An error occurred in RON deserialization, and the location of the error
+    /// is supplied.
+    #[error(transparent)]
+    SpannedRon(#[from] SpannedError),
+}
+
+impl AssetLoader for BoneMaskAssetLoader {
+    type Asset = BoneMask;
+
+    type Settings = ();
+
+    type Error = BoneMaskLoadError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _: &Self::Settings,
+        _: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["bonemask.ron"]
+    }
+}
@@ -0,0 +1,323 @@
+//! Compressed storage for the keyframes of animation curves.
+//!
+//! Skeletal animations store a translation, rotation and scale track for each
+//! bone, which adds up quickly for large animation sets. The curves in this
+//! module store their keyframes quantized to 16 bits per component, and
+//! decompress them on the fly when they are sampled. Tracks whose keyframes
+//! don't change are stored as a single value.
+//!
+//! Curves are compressed with [`AnimationCompression`], which asset loaders can
+//! apply to the tracks they load.
+
+use core::f32::consts::SQRT_2;
+
+use bevy_math::{
+    curve::{
+        cores::{InterpolationDatum, UnevenCore, UnevenCoreError},
+        ConstantCurve, Curve, Interval, UnevenSampleAutoCurve,
+    },
+    ops, Quat, Vec3,
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    animation_curves::{AnimatableCurve, AnimatableProperty},
+    VariableCurve,
+};
+
+/// The largest value of a component quantized to 15 bits.
+const MAX_15_BITS: u16 = (1 << 15) - 1;
+
+/// Settings for compressing the linearly interpolated tracks of animation
+/// clips.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Clone, Debug, Default, PartialEq)]
+pub struct AnimationCompression {
+    /// Tracks whose keyframes all stay within this distance of the first
+    /// keyframe, for each component, are replaced by a constant value.
+    ///
+    /// Set to a negative value to keep all the tracks.
+    pub constant_tolerance: f32,
+    /// Whether to quantize translations and scales to 16 bits per component.
+    ///
+    /// The precision depends on the range of each track: a track moving over
+    /// 10 meters is precise to about 0.1 millimeters.
+    pub quantize_vectors: bool,
+    /// Whether to quantize rotations to 48 bits, which is precise to about
+    /// 0.005 degrees.
+    pub quantize_rotations: bool,
+}
+
+impl Default for AnimationCompression {
+    fn default() -> Self {
+        Self {
+            constant_tolerance: 1e-5,
+            quantize_vectors: true,
+            quantize_rotations: true,
+        }
+    }
+}
+
+impl AnimationCompression {
+    /// Creates a [`VariableCurve`] animating the translation or the scale
+    /// `property`, linearly interpolated between the given keyframes, and
+    /// compressed according to these settings.
+    pub fn compress_vec3<P>(
+        &self,
+        property: P,
+        keyframes: impl IntoIterator<Item = (f32, Vec3)>,
+    ) -> Result<VariableCurve, UnevenCoreError>
+    where
+        P: AnimatableProperty<Property = Vec3> + Clone,
+    {
+        let core = UnevenCore::new(keyframes)?;
+        let first = core.samples[0];
+        if core
+            .samples
+            .iter()
+            .all(|sample| sample.abs_diff_eq(first, self.constant_tolerance))
+        {
+            let curve = ConstantCurve::new(core.domain(), first);
+            Ok(VariableCurve::new(AnimatableCurve::new(property, curve)))
+        } else if self.quantize_vectors {
+            let curve = QuantizedVec3Curve::from_core(core);
+            Ok(VariableCurve::new(AnimatableCurve::new(property, curve)))
+        } else {
+            let curve = UnevenSampleAutoCurve::new(core.times.into_iter().zip(core.samples))?;
+            Ok(VariableCurve::new(AnimatableCurve::new(property, curve)))
+        }
+    }
+
+    /// Creates a [`VariableCurve`] animating the rotation `property`,
+    /// spherically interpolated between the given keyframes, and compressed
+    /// according to these settings.
+    pub fn compress_rotation<P>(
+        &self,
+        property: P,
+        keyframes: impl IntoIterator<Item = (f32, Quat)>,
+    ) -> Result<VariableCurve, UnevenCoreError>
+    where
+        P: AnimatableProperty<Property = Quat> + Clone,
+    {
+        let core = UnevenCore::new(keyframes)?;
+        let first = core.samples[0];
+        if core.samples.iter().all(|sample| {
+            // `q` and `-q` are the same rotation
+            sample.abs_diff_eq(first, self.constant_tolerance)
+                || (-*sample).abs_diff_eq(first, self.constant_tolerance)
+        }) {
+            let curve = ConstantCurve::new(core.domain(), first);
+            Ok(VariableCurve::new(AnimatableCurve::new(property, curve)))
+        } else if self.quantize_rotations {
+            let curve = QuantizedRotationCurve::from_core(core);
+            Ok(VariableCurve::new(AnimatableCurve::new(property, curve)))
+        } else {
+            let curve = UnevenSampleAutoCurve::new(core.times.into_iter().zip(core.samples))?;
+            Ok(VariableCurve::new(AnimatableCurve::new(property, curve)))
+        }
+    }
+}
+
+/// A linearly interpolated [`Vec3`] curve, whose keyframes are quantized to 16
+/// bits per component within the range of the curve.
+#[derive(Clone, Debug, Reflect)]
+#[reflect(Clone)]
+pub struct QuantizedVec3Curve {
+    core: UnevenCore<[u16; 3]>,
+    /// The smallest value of the curve.
+    min: Vec3,
+    /// The difference between two consecutive quantized values.
+    step: Vec3,
+}
+
+impl QuantizedVec3Curve {
+    /// Creates a [`QuantizedVec3Curve`] from keyframes. If the curve could not
+    /// be constructed from the given data, an error is returned.
+    pub fn new(keyframes: impl IntoIterator<Item = (f32, Vec3)>) -> Result<Self, UnevenCoreError> {
+        Ok(Self::from_core(UnevenCore::new(keyframes)?))
+    }
+
+    fn from_core(core: UnevenCore<Vec3>) -> Self {
+        let (min, max) = core.samples.iter().fold(
+            (Vec3::splat(f32::INFINITY), Vec3::splat(f32::NEG_INFINITY)),
+            |(min, max), &sample| (min.min(sample), max.max(sample)),
+        );
+        let step = (max - min) / u16::MAX as f32;
+        let samples = core
+            .samples
+            .iter()
+            .map(|&sample| {
+                let quantized = ((sample - min) / step).round();
+                // Components that don't change have a step of zero
+                let quantized = Vec3::select(step.cmpeq(Vec3::ZERO), Vec3::ZERO, quantized);
+                quantized.to_array().map(|component| component as u16)
+            })
+            .collect();
+        Self {
+            core: UnevenCore {
+                times: core.times,
+                samples,
+            },
+            min,
+            step,
+        }
+    }
+
+    fn decode(&self, quantized: &[u16; 3]) -> Vec3 {
+        self.min + Vec3::from_array(quantized.map(f32::from)) * self.step
+    }
+}
+
+impl Curve<Vec3> for QuantizedVec3Curve {
+    #[inline]
+    fn domain(&self) -> Interval {
+        self.core.domain()
+    }
+
+    #[inline]
+    fn sample_clamped(&self, t: f32) -> Vec3 {
+        match self.core.sample_interp(t) {
+            InterpolationDatum::Exact(sample)
+            | InterpolationDatum::LeftTail(sample)
+            | InterpolationDatum::RightTail(sample) => self.decode(sample),
+            InterpolationDatum::Between(start, end, s) => {
+                self.decode(start).lerp(self.decode(end), s)
+            }
+        }
+    }
+
+    #[inline]
+    fn sample_unchecked(&self, t: f32) -> Vec3 {
+        self.sample_clamped(t)
+    }
+}
+
+/// A spherically interpolated rotation curve, whose keyframes are quantized
+/// to 48 bits.
+///
+/// The rotations are stored with the "smallest three" method: the largest
+/// component of each quaternion is left out and recomputed from the three
+/// others, which are quantized to 15 bits each.
+#[derive(Clone, Debug, Reflect)]
+#[reflect(Clone)]
+pub struct QuantizedRotationCurve {
+    core: UnevenCore<[u16; 3]>,
+}
+
+impl QuantizedRotationCurve {
+    /// Creates a [`QuantizedRotationCurve`] from keyframes. If the curve could
+    /// not be constructed from the given data, an error is returned.
+    pub fn new(keyframes: impl IntoIterator<Item = (f32, Quat)>) -> Result<Self, UnevenCoreError> {
+        Ok(Self::from_core(UnevenCore::new(keyframes)?))
+    }
+
+    fn from_core(core: UnevenCore<Quat>) -> Self {
+        Self {
+            core: UnevenCore {
+                samples: core.samples.into_iter().map(encode_rotation).collect(),
+                times: core.times,
+            },
+        }
+    }
+}
+
+impl Curve<Quat> for QuantizedRotationCurve {
+    #[inline]
+    fn domain(&self) -> Interval {
+        self.core.domain()
+    }
+
+    #[inline]
+    fn sample_clamped(&self, t: f32) -> Quat {
+        match self.core.sample_interp(t) {
+            InterpolationDatum::Exact(sample)
+            | InterpolationDatum::LeftTail(sample)
+            | InterpolationDatum::RightTail(sample) => decode_rotation(*sample),
+            InterpolationDatum::Between(start, end, s) => {
+                decode_rotation(*start).slerp(decode_rotation(*end), s)
+            }
+        }
+    }
+
+    #[inline]
+    fn sample_unchecked(&self, t: f32) -> Quat {
+        self.sample_clamped(t)
+    }
+}
+
+/// Quantizes the three smallest components of the rotation, and stores the
+/// index of the largest one in the top bits of the first two.
+fn encode_rotation(rotation: Quat) -> [u16; 3] {
+    let components = rotation.normalize().to_array();
+    let largest = (0..4)
+        .max_by(|&a, &b| components[a].abs().total_cmp(&components[b].abs()))
+        .unwrap();
+    // `q` and `-q` are the same rotation, so the largest component can be
+    // assumed to be positive.
+    let sign = components[largest].signum();
+
+    let mut encoded = [0; 3];
+    for (quantized, index) in encoded.iter_mut().zip((0..4).filter(|&i| i != largest)) {
+        // The other components are within [-1/√2, 1/√2]
+        let normalized = (components[index] * sign * SQRT_2 + 1.0) * 0.5;
+        *quantized = (normalized.clamp(0.0, 1.0) * MAX_15_BITS as f32).round() as u16;
+    }
+    encoded[0] |= (largest as u16 & 1) << 15;
+    encoded[1] |= (largest as u16 >> 1) << 15;
+    encoded
+}
+
+fn decode_rotation(encoded: [u16; 3]) -> Quat {
+    let largest = (encoded[0] >> 15 | (encoded[1] >> 15) << 1) as usize;
+
+    let mut components = [0.0; 4];
+    for (quantized, index) in encoded.iter().zip((0..4).filter(|&i| i != largest)) {
+        let normalized = (quantized & MAX_15_BITS) as f32 / MAX_15_BITS as f32;
+        components[index] = (normalized * 2.0 - 1.0) / SQRT_2;
+    }
+    let length_squared: f32 = components
+        .iter()
+        .map(|component| component * component)
+        .sum();
+    components[largest] = ops::sqrt((1.0 - length_squared).max(0.0));
+    Quat::from_array(components).normalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{QuantizedRotationCurve, QuantizedVec3Curve};
+    use bevy_math::{curve::Curve, Quat, Vec3};
+
+    #[test]
+    fn quantized_vec3_precision() {
+        let keyframes = [
+            (0.0, Vec3::new(-5.0, 1.0, 0.0)),
+            (0.5, Vec3::new(2.5, 1.0, 0.25)),
+            (1.0, Vec3::new(5.0, 1.0, 0.1)),
+        ];
+        let curve = QuantizedVec3Curve::new(keyframes).unwrap();
+        for (time, value) in keyframes {
+            assert!(curve.sample_clamped(time).abs_diff_eq(value, 1e-4));
+        }
+        assert!(curve
+            .sample_clamped(0.25)
+            .abs_diff_eq(Vec3::new(-1.25, 1.0, 0.125), 1e-4));
+    }
+
+    #[test]
+    fn quantized_rotation_precision() {
+        let keyframes: Vec<_> = (0..16)
+            .map(|i| {
+                let t = i as f32 * 0.5;
+                let rotation = Quat::from_euler(bevy_math::EulerRot::YXZ, t, -2.0 * t, 0.3 * t);
+                (t, rotation)
+            })
+            .collect();
+        let curve = QuantizedRotationCurve::new(keyframes.iter().copied()).unwrap();
+        for (time, rotation) in keyframes {
+            let sampled = curve.sample_clamped(time);
+            assert!(sampled.angle_between(rotation) < 1e-3);
+        }
+    }
+}
@@ -30,7 +30,9 @@ use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 use thiserror::Error;
 
-use crate::{AnimationClip, AnimationTargetId};
+use crate::{
+    bone_mask::BoneMask, state_machine::AnimationStateMachine, AnimationClip, AnimationTargetId,
+};
 
 /// A graph structure that describes how animation clips are to be blended
 /// together.
@@ -43,12 +45,15 @@ use crate::{AnimationClip, AnimationTargetId};
 /// the root and blends the animations together in a bottom-up fashion to
 /// produce the final pose.
 ///
-/// There are three types of nodes: *blend nodes*, *add nodes*, and *clip
-/// nodes*, all of which can have an associated weight. Blend nodes and add
-/// nodes have no associated animation clip and combine the animations of their
-/// children according to those children's weights. Clip nodes specify an
-/// animation clip to play. When a graph is created, it starts with only a
-/// single blend node, the root node.
+/// There are five types of nodes: *blend nodes*, *add nodes*, *state machine
+/// nodes*, *clip nodes*, and *additive clip nodes*, all of which can have an
+/// associated weight. Blend nodes and add nodes have no associated animation
+/// clip and combine the animations of their children according to those
+/// children's weights. State machine nodes play one of their children at a
+/// time, and crossfade between them when the conditions of their transitions
+/// hold. Clip nodes specify an animation clip to play, and additive clip nodes
+/// play the difference between a clip and a reference pose. When a graph is
+/// created, it starts with only a single blend node, the root node.
 ///
 /// For example, consider the following graph:
 ///
@@ -160,13 +165,15 @@ pub type AnimationNodeIndex = NodeIndex<u32>;
 /// An individual node within an animation graph.
 ///
 /// The [`AnimationGraphNode::node_type`] field specifies the type of node: one
-/// of a *clip node*, a *blend node*, or an *add node*. Clip nodes, the leaves
-/// of the graph, contain animation clips to play. Blend and add nodes describe
-/// how to combine their children to produce a final animation.
+/// of a *clip node*, an *additive clip node*, a *blend node*, an *add node*, or
+/// a *state machine node*. Clip and additive clip nodes, the leaves of the
+/// graph, contain animation clips to play. Blend, add, and state machine nodes
+/// describe how to combine their children to produce a final animation.
 #[derive(Clone, Reflect, Debug)]
 #[reflect(Clone)]
 pub struct AnimationGraphNode {
-    /// Animation node data specific to the type of node (clip, blend, or add).
+    /// Animation node data specific to the type of node (clip, additive clip,
+    /// blend, add, or state machine).
     ///
     /// In the case of clip nodes, this contains the actual animation clip
     /// associated with the node.
@@ -199,9 +206,17 @@ pub struct AnimationGraphNode {
     /// [Add]: AnimationNodeType::Add
     /// [active animation weight]: crate::ActiveAnimation::weight
     pub weight: f32,
+
+    /// The weight of each joint in this node, which scales [`Self::weight`]
+    /// for the joints it animates.
+    ///
+    /// Unlike [`Self::mask`], this only applies to this node, and not to its
+    /// descendants.
+    pub bone_mask: Option<Handle<BoneMask>>,
 }
 
-/// Animation node data specific to the type of node (clip, blend, or add).
+/// Animation node data specific to the type of node (clip, additive clip,
+/// blend, add, or state machine).
 ///
 /// In the case of clip nodes, this contains the actual animation clip
 /// associated with the node.
@@ -213,6 +228,22 @@ pub enum AnimationNodeType {
     /// These are always the leaves of the graph.
     Clip(Handle<AnimationClip>),
 
+    /// An *additive clip node*, which plays the difference between an
+    /// animation clip and a reference pose.
+    ///
+    /// The reference pose is sampled from another clip and subtracted from the
+    /// animation, so that clips authored as full poses can be layered on top of
+    /// any other animation: recoil, breathing, or hit reactions on top of
+    /// locomotion, for example. Additive clip nodes should be children of [add
+    /// nodes], after the base layer, so that the difference is scaled by their
+    /// weight and added to it.
+    ///
+    /// Only the properties animated by both the clip and the reference pose
+    /// are affected. Like clip nodes, these are always the leaves of the graph.
+    ///
+    /// [add nodes]: AnimationNodeType::Add
+    AdditiveClip(AdditiveClip),
+
     /// A *blend node*, which blends its children according to their weights.
     ///
     /// The weights of all the children of this node are normalized to 1.0.
@@ -232,6 +263,34 @@ pub enum AnimationNodeType {
     /// top of a running animation to produce an animation of a character
     /// attacking while running.
     Add,
+
+    /// A *state machine node*, which plays one of its children at a time and
+    /// crossfades between them.
+    ///
+    /// Its children are blended like those of a blend node, weighted by the
+    /// crossfade between the states. The current state and the parameters of
+    /// the transitions are stored in the [`AnimationStates`] component of each
+    /// animation player.
+    ///
+    /// [`AnimationStates`]: crate::state_machine::AnimationStates
+    StateMachine(AnimationStateMachine),
+}
+
+/// The animation clip and reference pose of an [additive clip node].
+///
+/// [additive clip node]: AnimationNodeType::AdditiveClip
+#[derive(Clone, Reflect, Debug)]
+#[reflect(Clone)]
+pub struct AdditiveClip {
+    /// The animation clip to play.
+    pub clip: Handle<AnimationClip>,
+    /// The animation clip that the reference pose is sampled from.
+    ///
+    /// This is often the clip itself, or the idle animation of the character.
+    pub reference: Handle<AnimationClip>,
+    /// The time in the [`AdditiveClip::reference`] clip of the reference pose,
+    /// in seconds.
+    pub reference_time: f32,
 }
 
 /// An [`AssetLoader`] that can load [`AnimationGraph`]s as assets.
@@ -400,6 +459,9 @@ pub struct SerializedAnimationGraphNode {
     pub mask: AnimationMask,
     /// Corresponds to the `weight` field on [`AnimationGraphNode`].
     pub weight: f32,
+    /// Corresponds to the `bone_mask` field on [`AnimationGraphNode`].
+    #[serde(default)]
+    pub bone_mask: Option<AssetPath<'static>>,
 }
 
 /// A version of [`AnimationNodeType`] suitable for serializing as part of a
@@ -408,10 +470,21 @@ pub struct SerializedAnimationGraphNode {
 pub enum SerializedAnimationNodeType {
     /// Corresponds to [`AnimationNodeType::Clip`].
     Clip(AssetPath<'static>),
+    /// Corresponds to [`AnimationNodeType::AdditiveClip`].
+    AdditiveClip {
+        /// Corresponds to [`AdditiveClip::clip`].
+        clip: AssetPath<'static>,
+        /// Corresponds to [`AdditiveClip::reference`].
+        reference: AssetPath<'static>,
+        /// Corresponds to [`AdditiveClip::reference_time`].
+        reference_time: f32,
+    },
     /// Corresponds to [`AnimationNodeType::Blend`].
     Blend,
     /// Corresponds to [`AnimationNodeType::Add`].
     Add,
+    /// Corresponds to [`AnimationNodeType::StateMachine`].
+    StateMachine(AnimationStateMachine),
 }
 
 /// The type of an animation mask bitfield.
@@ -477,6 +550,7 @@ impl AnimationGraph {
             node_type: AnimationNodeType::Clip(clip),
             mask: 0,
             weight,
+            bone_mask: None,
         });
         self.graph.add_edge(parent, node_index, ());
         node_index
@@ -497,6 +571,38 @@ impl AnimationGraph {
             node_type: AnimationNodeType::Clip(clip),
             mask,
             weight,
+            bone_mask: None,
+        });
+        self.graph.add_edge(parent, node_index, ());
+        node_index
+    }
+
+    /// Adds an [`AnimationClip`] to the animation graph as an additive clip
+    /// with the given weight, and returns its index.
+    ///
+    /// The pose at `reference_time` (in seconds) in the `reference` clip is
+    /// subtracted from the clip, which can then be layered on top of other
+    /// animations. The node should be placed under an [add node], after the
+    /// base layer. It will have no mask.
+    ///
+    /// [add node]: AnimationNodeType::Add
+    pub fn add_additive_clip(
+        &mut self,
+        clip: Handle<AnimationClip>,
+        reference: Handle<AnimationClip>,
+        reference_time: f32,
+        weight: f32,
+        parent: AnimationNodeIndex,
+    ) -> AnimationNodeIndex {
+        let node_index = self.graph.add_node(AnimationGraphNode {
+            node_type: AnimationNodeType::AdditiveClip(AdditiveClip {
+                clip,
+                reference,
+                reference_time,
+            }),
+            mask: 0,
+            weight,
+            bone_mask: None,
         });
         self.graph.add_edge(parent, node_index, ());
         node_index
@@ -536,6 +642,7 @@ impl AnimationGraph {
             node_type: AnimationNodeType::Blend,
             mask: 0,
             weight,
+            bone_mask: None,
         });
         self.graph.add_edge(parent, node_index, ());
         node_index
@@ -559,6 +666,7 @@ impl AnimationGraph {
             node_type: AnimationNodeType::Blend,
             mask,
             weight,
+            bone_mask: None,
         });
         self.graph.add_edge(parent, node_index, ());
         node_index
@@ -580,6 +688,7 @@ impl AnimationGraph {
             node_type: AnimationNodeType::Add,
             mask: 0,
             weight,
+            bone_mask: None,
         });
         self.graph.add_edge(parent, node_index, ());
         node_index
@@ -603,11 +712,48 @@ impl AnimationGraph {
             node_type: AnimationNodeType::Add,
             mask,
             weight,
+            bone_mask: None,
         });
         self.graph.add_edge(parent, node_index, ());
         node_index
     }
 
+    /// Adds a state machine node to the animation graph with the given weight
+    /// and returns its index.
+    ///
+    /// The state machine node will be placed under the supplied `parent` node.
+    /// It has no states: add its children to the graph, then declare them as
+    /// states with [`AnimationGraph::state_machine_mut`]. The state machine
+    /// node will have no mask.
+    pub fn add_state_machine(
+        &mut self,
+        weight: f32,
+        parent: AnimationNodeIndex,
+    ) -> AnimationNodeIndex {
+        let node_index = self.graph.add_node(AnimationGraphNode {
+            node_type: AnimationNodeType::StateMachine(AnimationStateMachine::default()),
+            mask: 0,
+            weight,
+            bone_mask: None,
+        });
+        self.graph.add_edge(parent, node_index, ());
+        node_index
+    }
+
+    /// Returns the states and transitions of the state machine node with the
+    /// given index.
+    ///
+    /// If the node doesn't exist or isn't a state machine node, returns `None`.
+    pub fn state_machine_mut(
+        &mut self,
+        state_machine: AnimationNodeIndex,
+    ) -> Option<&mut AnimationStateMachine> {
+        match &mut self.graph.node_weight_mut(state_machine)?.node_type {
+            AnimationNodeType::StateMachine(state_machine) => Some(state_machine),
+            _ => None,
+        }
+    }
+
     /// Adds an edge from the edge `from` to `to`, making `to` a child of
     /// `from`.
     ///
@@ -672,6 +818,20 @@ impl AnimationGraph {
 }
 
 impl AnimationGraphNode {
+    /// Sets the [`BoneMask`] of this node, which scales its weight for each
+    /// joint it animates.
+    pub fn set_bone_mask(&mut self, bone_mask: Handle<BoneMask>) -> &mut Self {
+        self.bone_mask = Some(bone_mask);
+        self
+    }
+
+    /// Removes the [`BoneMask`] of this node, so that it animates all the
+    /// joints with its weight.
+    pub fn remove_bone_mask(&mut self) -> &mut Self {
+        self.bone_mask = None;
+        self
+    }
+
     /// Masks out the mask groups specified by the given `mask` bitfield.
     ///
     /// A 1 in bit position N causes this function to mask out mask group N, and
@@ -731,6 +891,7 @@ impl Default for AnimationGraphNode {
             node_type: Default::default(),
             mask: 0,
             weight: 1.0,
+            bone_mask: None,
         }
     }
 }
@@ -777,11 +938,27 @@ impl AssetLoader for AnimationGraphAssetLoader {
                     SerializedAnimationNodeType::Clip(ref path) => {
                         AnimationNodeType::Clip(load_context.load(path.clone()))
                     }
+                    SerializedAnimationNodeType::AdditiveClip {
+                        ref clip,
+                        ref reference,
+                        reference_time,
+                    } => AnimationNodeType::AdditiveClip(AdditiveClip {
+                        clip: load_context.load(clip.clone()),
+                        reference: load_context.load(reference.clone()),
+                        reference_time,
+                    }),
                     SerializedAnimationNodeType::Blend => AnimationNodeType::Blend,
                     SerializedAnimationNodeType::Add => AnimationNodeType::Add,
+                    SerializedAnimationNodeType::StateMachine(ref state_machine) => {
+                        AnimationNodeType::StateMachine(state_machine.clone())
+                    }
                 },
                 mask: serialized_node.mask,
                 weight: serialized_node.weight,
+                bone_mask: serialized_node
+                    .bone_mask
+                    .as_ref()
+                    .map(|path| load_context.load(path.clone())),
             });
         }
         for edge in serialized_animation_graph.graph.raw_edges() {
@@ -812,16 +989,39 @@ impl TryFrom<AnimationGraph> for SerializedAnimationGraph {
             animation_graph.graph.edge_count(),
         );
         for node in animation_graph.graph.node_weights() {
+            let bone_mask = match node.bone_mask {
+                Some(ref bone_mask) => match bone_mask.path() {
+                    Some(path) => Some(path.clone()),
+                    None => return Err(NonPathHandleError),
+                },
+                None => None,
+            };
             serialized_graph.add_node(SerializedAnimationGraphNode {
                 weight: node.weight,
                 mask: node.mask,
+                bone_mask,
                 node_type: match node.node_type {
                     AnimationNodeType::Clip(ref clip) => match clip.path() {
                         Some(path) => SerializedAnimationNodeType::Clip(path.clone()),
                         None => return Err(NonPathHandleError),
                     },
+                    AnimationNodeType::AdditiveClip(ref additive_clip) => {
+                        match (additive_clip.clip.path(), additive_clip.reference.path()) {
+                            (Some(clip), Some(reference)) => {
+                                SerializedAnimationNodeType::AdditiveClip {
+                                    clip: clip.clone(),
+                                    reference: reference.clone(),
+                                    reference_time: additive_clip.reference_time,
+                                }
+                            }
+                            _ => return Err(NonPathHandleError),
+                        }
+                    }
                     AnimationNodeType::Blend => SerializedAnimationNodeType::Blend,
                     AnimationNodeType::Add => SerializedAnimationNodeType::Add,
+                    AnimationNodeType::StateMachine(ref state_machine) => {
+                        SerializedAnimationNodeType::StateMachine(state_machine.clone())
+                    }
                 },
             });
         }
@@ -838,7 +1038,7 @@ impl TryFrom<AnimationGraph> for SerializedAnimationGraph {
 
 /// Error for when only path [`Handle`]s are supported.
 #[derive(Error, Debug)]
-#[error("AnimationGraph contains a handle to an AnimationClip that does not correspond to an asset path")]
+#[error("AnimationGraph contains a handle to an AnimationClip or a BoneMask that does not correspond to an asset path")]
 pub struct NonPathHandleError;
 
 /// A system that creates, updates, and removes [`ThreadedAnimationGraph`]
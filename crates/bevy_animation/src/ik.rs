@@ -0,0 +1,476 @@
+//! Inverse kinematics.
+//!
+//! Inverse kinematics constraints adjust the pose evaluated by the
+//! [`AnimationPlayer`](crate::AnimationPlayer) so that the end of a chain of
+//! bones reaches a target, for example to plant feet on uneven ground or to
+//! place hands on a ledge. They run after the animations are applied and before
+//! transforms are propagated.
+
+use bevy_ecs::{
+    component::Component, entity::Entity, hierarchy::ChildOf, query::With,
+    reflect::ReflectComponent, system::Query,
+};
+use bevy_math::{ops, Quat, Vec3};
+use bevy_reflect::Reflect;
+use bevy_transform::components::{GlobalTransform, Transform};
+use smallvec::SmallVec;
+
+/// The minimum distance used by the solvers, to avoid degenerate chains.
+const EPSILON: f32 = 1e-4;
+
+/// Rotates the two parent bones of this entity so that it reaches a target.
+///
+/// Place this component on the end of the chain, for example a foot or a hand.
+/// Its parent, the knee or elbow, and grandparent, the hip or shoulder, are
+/// rotated analytically, which is fast and exact. For longer chains, use a
+/// [`FabrikChain`].
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Clone, Debug)]
+pub struct TwoBoneIkConstraint {
+    /// The entity whose position the end of the chain reaches.
+    pub target: Entity,
+    /// An entity the middle joint bends towards, to control the direction of
+    /// the knee or elbow.
+    ///
+    /// Without a pole, the chain keeps bending in the plane of the animated
+    /// pose.
+    pub pole: Option<Entity>,
+    /// How much the constraint overrides the animated pose, from `0.0` to
+    /// `1.0`.
+    pub weight: f32,
+}
+
+impl TwoBoneIkConstraint {
+    /// Creates a [`TwoBoneIkConstraint`] reaching the given target, with full
+    /// weight and no pole.
+    pub fn new(target: Entity) -> Self {
+        Self {
+            target,
+            pole: None,
+            weight: 1.0,
+        }
+    }
+
+    /// Sets the entity the middle joint bends towards.
+    pub fn with_pole(mut self, pole: Entity) -> Self {
+        self.pole = Some(pole);
+        self
+    }
+
+    /// Sets how much the constraint overrides the animated pose.
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+/// Rotates the bones above this entity so that it reaches a target, using the
+/// FABRIK algorithm.
+///
+/// Place this component on the end of the chain. The chain is made of the
+/// given number of bones above it, for example a spine or a tail. FABRIK
+/// handles chains of any length, but only approximates the solution; prefer a
+/// [`TwoBoneIkConstraint`] for limbs.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Clone, Debug)]
+pub struct FabrikChain {
+    /// The entity whose position the end of the chain reaches.
+    pub target: Entity,
+    /// An entity the joints of the chain bend towards.
+    pub pole: Option<Entity>,
+    /// How much the constraint overrides the animated pose, from `0.0` to
+    /// `1.0`.
+    pub weight: f32,
+    /// The number of bones in the chain. The root of the chain is the ancestor
+    /// of this entity at this depth.
+    pub bones: usize,
+    /// The maximum number of iterations of the solver.
+    pub iterations: u32,
+    /// The distance to the target under which the solver stops iterating.
+    pub tolerance: f32,
+}
+
+impl FabrikChain {
+    /// Creates a [`FabrikChain`] of the given number of bones reaching the
+    /// given target, with full weight and no pole.
+    pub fn new(target: Entity, bones: usize) -> Self {
+        Self {
+            target,
+            pole: None,
+            weight: 1.0,
+            bones,
+            iterations: 10,
+            tolerance: 1e-3,
+        }
+    }
+
+    /// Sets the entity the joints of the chain bend towards.
+    pub fn with_pole(mut self, pole: Entity) -> Self {
+        self.pole = Some(pole);
+        self
+    }
+
+    /// Sets how much the constraint overrides the animated pose.
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+/// A system that applies the [`TwoBoneIkConstraint`] and [`FabrikChain`]
+/// components to the animated pose.
+pub fn apply_ik_constraints(
+    two_bone_constraints: Query<(Entity, &TwoBoneIkConstraint)>,
+    fabrik_chains: Query<(Entity, &FabrikChain)>,
+    mut transforms: Query<&mut Transform>,
+    parents: Query<&ChildOf, With<Transform>>,
+) {
+    for (entity, constraint) in &two_bone_constraints {
+        if constraint.weight <= 0.0 {
+            continue;
+        }
+        let Some(chain) = chain(entity, 2, &parents) else {
+            continue;
+        };
+        let Some(target) = world_transform(constraint.target, &transforms, &parents) else {
+            continue;
+        };
+        let pole = constraint
+            .pole
+            .and_then(|pole| world_transform(pole, &transforms, &parents))
+            .map(|pole| pole.translation());
+        let Some(joints) = world_joints(&chain, &transforms, &parents) else {
+            continue;
+        };
+
+        let [(a, root_rotation), (b, middle_rotation), (c, _)] = joints[..] else {
+            continue;
+        };
+        let (root_delta, middle_delta) = solve_two_bone(a, b, c, target.translation(), pole);
+        rotate_joint(
+            &mut transforms,
+            chain[0],
+            root_rotation,
+            root_delta,
+            constraint.weight,
+        );
+        rotate_joint(
+            &mut transforms,
+            chain[1],
+            middle_rotation,
+            middle_delta,
+            constraint.weight,
+        );
+    }
+
+    for (entity, fabrik_chain) in &fabrik_chains {
+        if fabrik_chain.weight <= 0.0 || fabrik_chain.bones == 0 {
+            continue;
+        }
+        let Some(chain) = chain(entity, fabrik_chain.bones, &parents) else {
+            continue;
+        };
+        let Some(target) = world_transform(fabrik_chain.target, &transforms, &parents) else {
+            continue;
+        };
+        let pole = fabrik_chain
+            .pole
+            .and_then(|pole| world_transform(pole, &transforms, &parents))
+            .map(|pole| pole.translation());
+        let Some(joints) = world_joints(&chain, &transforms, &parents) else {
+            continue;
+        };
+
+        let mut positions: SmallVec<[Vec3; 8]> =
+            joints.iter().map(|(position, _)| *position).collect();
+        solve_fabrik(
+            &mut positions,
+            target.translation(),
+            pole,
+            fabrik_chain.iterations,
+            fabrik_chain.tolerance,
+        );
+
+        // Rotate each joint from the root down, so that it points towards the
+        // new position of the next joint. `ancestors_delta` is the rotation
+        // that the joint inherits from the joints above it.
+        let mut ancestors_delta = Quat::IDENTITY;
+        for (index, &joint) in chain[..chain.len() - 1].iter().enumerate() {
+            let (position, rotation) = joints[index];
+            let old_direction = ancestors_delta * (joints[index + 1].0 - position);
+            let new_direction = positions[index + 1] - positions[index];
+            let (Some(old_direction), Some(new_direction)) =
+                (old_direction.try_normalize(), new_direction.try_normalize())
+            else {
+                continue;
+            };
+            let delta = Quat::from_rotation_arc(old_direction, new_direction);
+            rotate_joint(
+                &mut transforms,
+                joint,
+                rotation,
+                ancestors_delta.inverse() * delta * ancestors_delta,
+                fabrik_chain.weight,
+            );
+            ancestors_delta = delta * ancestors_delta;
+        }
+    }
+}
+
+/// Returns the entities of the chain of the given number of bones ending at
+/// `end`, from the root of the chain to `end`.
+fn chain(
+    end: Entity,
+    bones: usize,
+    parents: &Query<&ChildOf, With<Transform>>,
+) -> Option<SmallVec<[Entity; 8]>> {
+    let mut chain = SmallVec::with_capacity(bones + 1);
+    chain.push(end);
+    let mut entity = end;
+    for _ in 0..bones {
+        entity = parents.get(entity).ok()?.parent();
+        chain.push(entity);
+    }
+    chain.reverse();
+    Some(chain)
+}
+
+/// Computes the world transform of the entity from the local transforms of its
+/// ancestors.
+///
+/// The [`GlobalTransform`] of the entity can't be used, since it's only
+/// updated from the animated pose when transforms are propagated.
+fn world_transform(
+    entity: Entity,
+    transforms: &Query<&mut Transform>,
+    parents: &Query<&ChildOf, With<Transform>>,
+) -> Option<GlobalTransform> {
+    let mut world_transform = GlobalTransform::from(*transforms.get(entity).ok()?);
+    let mut entity = entity;
+    while let Ok(child_of) = parents.get(entity) {
+        entity = child_of.parent();
+        let Ok(transform) = transforms.get(entity) else {
+            break;
+        };
+        world_transform = GlobalTransform::from(*transform) * world_transform;
+    }
+    Some(world_transform)
+}
+
+/// Returns the world position and rotation of each joint of the chain.
+fn world_joints(
+    chain: &[Entity],
+    transforms: &Query<&mut Transform>,
+    parents: &Query<&ChildOf, With<Transform>>,
+) -> Option<SmallVec<[(Vec3, Quat); 8]>> {
+    let mut world_transform = world_transform(chain[0], transforms, parents)?;
+    let mut joints = SmallVec::with_capacity(chain.len());
+    for (index, &joint) in chain.iter().enumerate() {
+        if index > 0 {
+            world_transform = world_transform * GlobalTransform::from(*transforms.get(joint).ok()?);
+        }
+        let (_, rotation, translation) = world_transform.to_scale_rotation_translation();
+        joints.push((translation, rotation));
+    }
+    Some(joints)
+}
+
+/// Applies a world-space rotation to a joint whose world rotation is
+/// `world_rotation`, blended with its animated rotation by `weight`.
+fn rotate_joint(
+    transforms: &mut Query<&mut Transform>,
+    joint: Entity,
+    world_rotation: Quat,
+    delta: Quat,
+    weight: f32,
+) {
+    let Ok(mut transform) = transforms.get_mut(joint) else {
+        return;
+    };
+    let solved =
+        (transform.rotation * world_rotation.inverse() * delta * world_rotation).normalize();
+    transform.rotation = transform.rotation.slerp(solved, weight.min(1.0));
+}
+
+/// Solves a two-bone chain going from `a` to `b` to `c` so that `c` reaches
+/// `target`.
+///
+/// Returns the world-space rotations to apply to the root joint `a` and to the
+/// middle joint `b`. The rotation of `b` is expressed before the rotation of
+/// `a` is applied.
+fn solve_two_bone(a: Vec3, b: Vec3, c: Vec3, target: Vec3, pole: Option<Vec3>) -> (Quat, Quat) {
+    let upper_length = a.distance(b);
+    let lower_length = b.distance(c);
+    if upper_length < EPSILON || lower_length < EPSILON {
+        return (Quat::IDENTITY, Quat::IDENTITY);
+    }
+    let target_distance = a
+        .distance(target)
+        .min(upper_length + lower_length - EPSILON)
+        .max(EPSILON);
+
+    // Bend the middle joint so that the end is at the distance of the target,
+    // by the law of cosines.
+    let current_angle = (a - b).angle_between(c - b);
+    let cos_angle = (upper_length * upper_length + lower_length * lower_length
+        - target_distance * target_distance)
+        / (2.0 * upper_length * lower_length);
+    let desired_angle = ops::acos(cos_angle.clamp(-1.0, 1.0));
+    let bend_axis = (a - b)
+        .cross(c - b)
+        .try_normalize()
+        .or_else(|| pole.and_then(|pole| (a - b).cross(pole - b).try_normalize()))
+        .unwrap_or_else(|| (a - b).any_orthonormal_vector());
+    let middle_delta = Quat::from_axis_angle(bend_axis, desired_angle - current_angle);
+    let bent_end = b + middle_delta * (c - b);
+
+    // Rotate the whole chain so that the end points towards the target.
+    let (Some(end_direction), Some(target_direction)) =
+        ((bent_end - a).try_normalize(), (target - a).try_normalize())
+    else {
+        return (Quat::IDENTITY, middle_delta);
+    };
+    let mut root_delta = Quat::from_rotation_arc(end_direction, target_direction);
+
+    // Twist the chain around its axis so that the middle joint points towards
+    // the pole.
+    if let Some(pole) = pole {
+        let middle = a + root_delta * (b - a);
+        root_delta = twist_towards(middle - a, pole - a, target_direction) * root_delta;
+    }
+
+    (root_delta, middle_delta)
+}
+
+/// Returns the rotation around `axis` that brings `from` the closest to `to`.
+fn twist_towards(from: Vec3, to: Vec3, axis: Vec3) -> Quat {
+    let from = from.reject_from_normalized(axis);
+    let to = to.reject_from_normalized(axis);
+    if from.length_squared() < EPSILON * EPSILON || to.length_squared() < EPSILON * EPSILON {
+        return Quat::IDENTITY;
+    }
+    let angle = from.angle_between(to);
+    let sign = axis.dot(from.cross(to)).signum();
+    Quat::from_axis_angle(axis, sign * angle)
+}
+
+/// Moves the joints of a chain so that the last one reaches `target`, keeping
+/// the first one in place and the distances between the joints.
+fn solve_fabrik(
+    joints: &mut [Vec3],
+    target: Vec3,
+    pole: Option<Vec3>,
+    iterations: u32,
+    tolerance: f32,
+) {
+    let lengths: SmallVec<[f32; 8]> = joints
+        .windows(2)
+        .map(|pair| pair[0].distance(pair[1]))
+        .collect();
+    let root = joints[0];
+    let end = joints.len() - 1;
+
+    if root.distance(target) >= lengths.iter().sum::<f32>() {
+        // The target is out of reach: stretch the chain towards it.
+        for index in 0..end {
+            let direction = (target - joints[index]).normalize_or_zero();
+            joints[index + 1] = joints[index] + direction * lengths[index];
+        }
+    } else {
+        for _ in 0..iterations {
+            if joints[end].distance(target) <= tolerance {
+                break;
+            }
+
+            // Backward pass, from the end to the root.
+            joints[end] = target;
+            for index in (0..end).rev() {
+                let direction = (joints[index] - joints[index + 1]).normalize_or_zero();
+                joints[index] = joints[index + 1] + direction * lengths[index];
+            }
+
+            // Forward pass, from the root to the end.
+            joints[0] = root;
+            for index in 0..end {
+                let direction = (joints[index + 1] - joints[index]).normalize_or_zero();
+                joints[index + 1] = joints[index] + direction * lengths[index];
+            }
+        }
+    }
+
+    // Rotate each inner joint around the line between its neighbors, which
+    // keeps the lengths of the bones, so that it points towards the pole.
+    if let Some(pole) = pole {
+        for index in 1..end {
+            let previous = joints[index - 1];
+            let Some(axis) = (joints[index + 1] - previous).try_normalize() else {
+                continue;
+            };
+            let twist = twist_towards(joints[index] - previous, pole - previous, axis);
+            joints[index] = previous + twist * (joints[index] - previous);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_ik_constraints, FabrikChain, TwoBoneIkConstraint};
+    use bevy_ecs::{prelude::*, system::RunSystemOnce};
+    use bevy_math::Vec3;
+    use bevy_transform::{components::Transform, helper::TransformHelper};
+
+    /// Spawns a chain of bones of length 1 along the Y axis, returning its end.
+    fn spawn_chain(world: &mut World, bones: usize) -> Entity {
+        let mut entity = world.spawn(Transform::default()).id();
+        for _ in 0..bones {
+            entity = world
+                .spawn((Transform::from_xyz(0.0, 1.0, 0.0), ChildOf(entity)))
+                .id();
+        }
+        entity
+    }
+
+    fn end_position(world: &mut World, end: Entity) -> Vec3 {
+        world
+            .run_system_once(move |helper: TransformHelper| {
+                helper.compute_global_transform(end).unwrap().translation()
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn two_bone_reaches_target() {
+        let mut world = World::new();
+        let target_position = Vec3::new(1.0, 1.2, 0.5);
+        let target = world
+            .spawn(Transform::from_translation(target_position))
+            .id();
+        let pole = world.spawn(Transform::from_xyz(0.0, 1.0, 5.0)).id();
+        let end = spawn_chain(&mut world, 2);
+        world
+            .entity_mut(end)
+            .insert(TwoBoneIkConstraint::new(target).with_pole(pole));
+
+        world.run_system_once(apply_ik_constraints).unwrap();
+
+        assert!(end_position(&mut world, end).distance(target_position) < 1e-3);
+        // The knee bends towards the pole.
+        let middle = world.get::<ChildOf>(end).unwrap().parent();
+        assert!(end_position(&mut world, middle).z > 0.0);
+    }
+
+    #[test]
+    fn fabrik_reaches_target() {
+        let mut world = World::new();
+        let target_position = Vec3::new(2.0, 1.0, 0.0);
+        let target = world
+            .spawn(Transform::from_translation(target_position))
+            .id();
+        let end = spawn_chain(&mut world, 4);
+        world.entity_mut(end).insert(FabrikChain::new(target, 4));
+
+        world.run_system_once(apply_ik_constraints).unwrap();
+
+        assert!(end_position(&mut world, end).distance(target_position) < 1e-2);
+    }
+}
@@ -11,10 +11,15 @@ extern crate alloc;
 
 pub mod animatable;
 pub mod animation_curves;
+pub mod bone_mask;
+pub mod compression;
 pub mod gltf_curves;
 pub mod graph;
+pub mod ik;
 #[cfg(feature = "bevy_mesh")]
 mod morph;
+pub mod recording;
+pub mod state_machine;
 pub mod transition;
 
 mod animation_event;
@@ -29,7 +34,7 @@ use core::{
     hash::{Hash, Hasher},
     iter, slice,
 };
-use graph::AnimationNodeType;
+use graph::{AdditiveClip, AnimationNodeType};
 use prelude::AnimationCurveEvaluator;
 
 use crate::{
@@ -38,7 +43,7 @@ use crate::{
 };
 
 use bevy_app::{AnimationSystems, App, Plugin, PostUpdate};
-use bevy_asset::{Asset, AssetApp, AssetEventSystems, Assets};
+use bevy_asset::{Asset, AssetApp, AssetEvent, AssetEventSystems, Assets};
 use bevy_ecs::{prelude::*, world::EntityMutExcept};
 use bevy_math::FloatOrd;
 use bevy_platform::{collections::HashMap, hash::NoOpHash};
@@ -57,14 +62,17 @@ use uuid::Uuid;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        animatable::*, animation_curves::*, graph::*, transition::*, AnimationClip,
-        AnimationPlayer, AnimationPlugin, VariableCurve,
+        animatable::*, animation_curves::*, bone_mask::*, graph::*, ik::*, recording::*,
+        state_machine::*, transition::*, AnimationClip, AnimationPlayer, AnimationPlugin,
+        VariableCurve,
     };
 }
 
 use crate::{
     animation_curves::AnimationCurve,
+    bone_mask::{BoneMask, BoneMaskAssetLoader},
     graph::{AnimationGraph, AnimationGraphAssetLoader, AnimationNodeIndex},
+    state_machine::{advance_state_machines, AnimationStates},
     transition::{advance_transitions, expire_completed_transitions},
 };
 use alloc::sync::Arc;
@@ -317,6 +325,72 @@ impl AnimationClip {
             .push(variable_curve);
     }
 
+    /// Edits the curve animating the property identified by `evaluator_id` on
+    /// the given target, if that curve has the type `C`, and returns the
+    /// result of `edit`.
+    ///
+    /// This is how the keyframes of an
+    /// [`AnimatableKeyframeCurve`](animation_curves::AnimatableKeyframeCurve) are
+    /// inserted, removed and moved at runtime. If the edit moves the end of the
+    /// curve, the duration of this clip is updated to cover its curves and
+    /// events again. Players sample the edited curve as soon as they're next
+    /// updated.
+    ///
+    /// Returns `None` if this clip doesn't animate the property on the target,
+    /// or if the curve isn't a `C`.
+    ///
+    /// ```
+    /// # use bevy_animation::{animated_field, animation_curves::*, AnimationClip, AnimationTargetId};
+    /// # use bevy_math::Vec3;
+    /// # use bevy_transform::components::Transform;
+    /// # let target_id = AnimationTargetId::from_iter(["Hips"]);
+    /// # let mut clip = AnimationClip::default();
+    /// # let keyframes = AnimatableKeyframeCurve::new([(0.0, Vec3::ZERO), (1.0, Vec3::X)]).unwrap();
+    /// # clip.add_curve_to_target(
+    /// #     target_id,
+    /// #     AnimatableCurve::new(animated_field!(Transform::translation), keyframes),
+    /// # );
+    /// clip.edit_curve(
+    ///     target_id,
+    ///     &animated_field!(Transform::translation).evaluator_id(),
+    ///     |curve: &mut AnimatableKeyframeCurve<Vec3>| curve.insert_keyframe(2.0, Vec3::Y),
+    /// );
+    /// assert_eq!(clip.duration(), 2.0);
+    /// ```
+    pub fn edit_curve<C, R>(
+        &mut self,
+        target_id: AnimationTargetId,
+        evaluator_id: &EvaluatorId,
+        edit: impl FnOnce(&mut C) -> R,
+    ) -> Option<R>
+    where
+        C: 'static,
+    {
+        let curve = self
+            .curves
+            .get_mut(&target_id)?
+            .iter_mut()
+            .find(|curve| curve.0.evaluator_id() == *evaluator_id)?;
+        let old_end = curve.0.domain().end();
+        let result = edit(curve.0.curve_mut()?.downcast_mut::<C>()?);
+        let new_end = curve.0.domain().end();
+
+        if new_end.is_finite() && new_end > self.duration {
+            self.duration = new_end;
+        } else if new_end != old_end && old_end == self.duration {
+            // This curve used to end the clip, which may now be shorter
+            self.duration = self
+                .curves
+                .values()
+                .flatten()
+                .map(|curve| curve.0.domain().end())
+                .chain(self.events.values().flatten().map(|event| event.time))
+                .filter(|end| end.is_finite())
+                .fold(0.0, f32::max);
+        }
+        Some(result)
+    }
+
     /// Add an [`EntityEvent`] with no [`AnimationTargetId`].
     ///
     /// The `event` will be cloned and triggered on the [`AnimationPlayer`] entity once the `time` (in seconds)
@@ -353,10 +427,68 @@ impl AnimationClip {
         );
     }
 
+    /// Add a [`KeyframeEvent`] with no [`AnimationTargetId`].
+    ///
+    /// The event will be triggered on the [`AnimationPlayer`] entity once the `time` (in seconds)
+    /// is reached in the animation, with the given `name` and `payload`.
+    ///
+    /// See also [`add_keyframe_event_to_target`](Self::add_keyframe_event_to_target).
+    pub fn add_keyframe_event(
+        &mut self,
+        time: f32,
+        name: impl Into<String>,
+        payload: KeyframeEventPayload,
+    ) {
+        let name = name.into();
+        self.add_event_fn(
+            time,
+            move |commands: &mut Commands, target: Entity, time: f32, weight: f32| {
+                let event = KeyframeEvent {
+                    name: name.clone(),
+                    payload: payload.clone(),
+                    time,
+                    weight,
+                };
+                commands.trigger_with(event, AnimationEventTrigger { target });
+            },
+        );
+    }
+
+    /// Add a [`KeyframeEvent`] with an [`AnimationTargetId`].
+    ///
+    /// The event will be triggered on the entity matching the target once the `time` (in seconds)
+    /// is reached in the animation, with the given `name` and `payload`.
+    ///
+    /// Use [`add_keyframe_event`](Self::add_keyframe_event) instead if you don't have a specific
+    /// target.
+    pub fn add_keyframe_event_to_target(
+        &mut self,
+        target_id: AnimationTargetId,
+        time: f32,
+        name: impl Into<String>,
+        payload: KeyframeEventPayload,
+    ) {
+        let name = name.into();
+        self.add_event_fn_to_target(
+            target_id,
+            time,
+            move |commands: &mut Commands, target: Entity, time: f32, weight: f32| {
+                let event = KeyframeEvent {
+                    name: name.clone(),
+                    payload: payload.clone(),
+                    time,
+                    weight,
+                };
+                commands.trigger_with(event, AnimationEventTrigger { target });
+            },
+        );
+    }
+
     /// Add an event function with no [`AnimationTargetId`] to this [`AnimationClip`].
     ///
     /// The `func` will trigger on the [`AnimationPlayer`] entity once the `time` (in seconds)
-    /// is reached in the animation.
+    /// is reached in the animation. It receives the time of the event and the weight of the clip
+    /// in the animation graph.
     ///
     /// For a simpler [`EntityEvent`]-based alternative, see [`AnimationClip::add_event`].
     /// See also [`add_event_to_target`](Self::add_event_to_target).
@@ -423,7 +555,7 @@ impl AnimationClip {
 }
 
 /// Repetition behavior of an animation.
-#[derive(Reflect, Debug, PartialEq, Eq, Copy, Clone, Default)]
+#[derive(Reflect, Debug, PartialEq, Eq, Copy, Clone, Default, Serialize, Deserialize)]
 #[reflect(Clone, Default)]
 pub enum RepeatAnimation {
     /// The animation will finish after running once.
@@ -937,9 +1069,14 @@ fn trigger_untargeted_animation_events(
     mut commands: Commands,
     clips: Res<Assets<AnimationClip>>,
     graphs: Res<Assets<AnimationGraph>>,
-    players: Query<(Entity, &AnimationPlayer, &AnimationGraphHandle)>,
+    players: Query<(
+        Entity,
+        &AnimationPlayer,
+        &AnimationGraphHandle,
+        Option<&AnimationStates>,
+    )>,
 ) {
-    for (entity, player, graph_id) in &players {
+    for (entity, player, graph_id, states) in &players {
         // The graph might not have loaded yet. Safely bail.
         let Some(graph) = graphs.get(graph_id) else {
             return;
@@ -950,14 +1087,18 @@ fn trigger_untargeted_animation_events(
                 continue;
             }
 
-            let Some(clip) = graph
-                .get(*index)
-                .and_then(|node| match &node.node_type {
-                    AnimationNodeType::Clip(handle) => Some(handle),
-                    AnimationNodeType::Blend | AnimationNodeType::Add => None,
-                })
-                .and_then(|id| clips.get(id))
-            else {
+            let Some(node) = graph.get(*index) else {
+                continue;
+            };
+            let Some(clip) = (match &node.node_type {
+                AnimationNodeType::Clip(handle)
+                | AnimationNodeType::AdditiveClip(AdditiveClip { clip: handle, .. }) => {
+                    clips.get(handle)
+                }
+                AnimationNodeType::Blend
+                | AnimationNodeType::Add
+                | AnimationNodeType::StateMachine(_) => None,
+            }) else {
                 continue;
             };
 
@@ -967,8 +1108,11 @@ fn trigger_untargeted_animation_events(
                 continue;
             };
 
+            let weight = active_animation.weight
+                * node.weight
+                * states.map_or(1.0, |states| states.node_weight(*index));
             for TimedAnimationEvent { time, event } in triggered_events.iter() {
-                event.trigger(&mut commands, entity, *time, active_animation.weight);
+                event.trigger(&mut commands, entity, *time, weight);
             }
         }
     }
@@ -1002,7 +1146,11 @@ pub fn advance_animations(
                 if let Some(active_animation) = active_animations.get_mut(&node_index) {
                     // Tick the animation if necessary.
                     if !active_animation.paused
-                        && let AnimationNodeType::Clip(ref clip_handle) = node.node_type
+                        && let AnimationNodeType::Clip(ref clip_handle)
+                        | AnimationNodeType::AdditiveClip(AdditiveClip {
+                            clip: ref clip_handle,
+                            ..
+                        }) = node.node_type
                         && let Some(clip) = animation_clips.get(clip_handle)
                     {
                         active_animation.update(delta_seconds, clip.duration);
@@ -1012,6 +1160,53 @@ pub fn advance_animations(
         });
 }
 
+/// A system that keeps the animations playing an edited [`AnimationClip`]
+/// within the duration of the clip.
+///
+/// Edited curves are sampled again by [`animate_targets`] like any other
+/// curve, but an edit can shorten a clip, leaving the animations that play it
+/// past its end. Those animations are moved back to the end of the clip,
+/// without triggering the events in between.
+pub fn resample_edited_animations(
+    mut clip_events: MessageReader<AssetEvent<AnimationClip>>,
+    animation_clips: Res<Assets<AnimationClip>>,
+    animation_graphs: Res<Assets<AnimationGraph>>,
+    mut players: Query<(&mut AnimationPlayer, &AnimationGraphHandle)>,
+) {
+    let edited_clips: Vec<_> = clip_events
+        .read()
+        .filter_map(|event| match *event {
+            AssetEvent::Modified { id } => Some(id),
+            _ => None,
+        })
+        .collect();
+    if edited_clips.is_empty() {
+        return;
+    }
+
+    for (mut player, graph_handle) in &mut players {
+        let Some(animation_graph) = animation_graphs.get(graph_handle) else {
+            continue;
+        };
+        for (node_index, active_animation) in player.active_animations.iter_mut() {
+            let Some(node) = animation_graph.get(*node_index) else {
+                continue;
+            };
+            if let AnimationNodeType::Clip(ref clip_handle)
+            | AnimationNodeType::AdditiveClip(AdditiveClip {
+                clip: ref clip_handle,
+                ..
+            }) = node.node_type
+                && edited_clips.contains(&clip_handle.id())
+                && let Some(clip) = animation_clips.get(clip_handle)
+                && active_animation.seek_time > clip.duration
+            {
+                active_animation.set_seek_time(clip.duration);
+            }
+        }
+    }
+}
+
 /// A type alias for [`EntityMutExcept`] as used in animation.
 pub type AnimationEntityMut<'w, 's> = EntityMutExcept<
     'w,
@@ -1021,6 +1216,7 @@ pub type AnimationEntityMut<'w, 's> = EntityMutExcept<
         AnimatedBy,
         AnimationPlayer,
         AnimationGraphHandle,
+        AnimationStates,
     ),
 >;
 
@@ -1030,8 +1226,13 @@ pub fn animate_targets(
     par_commands: ParallelCommands,
     clips: Res<Assets<AnimationClip>>,
     graphs: Res<Assets<AnimationGraph>>,
+    bone_masks: Res<Assets<BoneMask>>,
     threaded_animation_graphs: Res<ThreadedAnimationGraphs>,
-    players: Query<(&AnimationPlayer, &AnimationGraphHandle)>,
+    players: Query<(
+        &AnimationPlayer,
+        &AnimationGraphHandle,
+        Option<&AnimationStates>,
+    )>,
     mut targets: Query<(Entity, &AnimationTargetId, &AnimatedBy, AnimationEntityMut)>,
     animation_evaluation_state: Local<ThreadLocal<RefCell<AnimationEvaluationState>>>,
 ) {
@@ -1039,9 +1240,9 @@ pub fn animate_targets(
     targets
         .par_iter_mut()
         .for_each(|(entity, &target_id, &AnimatedBy(player_id), entity_mut)| {
-            let (animation_player, animation_graph_id) =
-                if let Ok((player, graph_handle)) = players.get(player_id) {
-                    (player, graph_handle.id())
+            let (animation_player, animation_graph_id, animation_states) =
+                if let Ok((player, graph_handle, states)) = players.get(player_id) {
+                    (player, graph_handle.id(), states)
                 } else {
                     trace!(
                         "Either an animation player {} or a graph was missing for the target \
@@ -1081,8 +1282,21 @@ pub fn animate_targets(
                     continue;
                 };
 
+                // The states of state machines are weighted by their crossfade.
+                let node_weight = animation_graph_node.weight
+                    * animation_states
+                        .map_or(1.0, |states| states.node_weight(animation_graph_node_index));
+
+                // Bone masks weight the node for each animation target.
+                let bone_mask_weight = animation_graph_node
+                    .bone_mask
+                    .as_ref()
+                    .and_then(|bone_mask| bone_masks.get(bone_mask))
+                    .map_or(1.0, |bone_mask| bone_mask.weight(target_id));
+                let node_weight = node_weight * bone_mask_weight;
+
                 match animation_graph_node.node_type {
-                    AnimationNodeType::Blend => {
+                    AnimationNodeType::Blend | AnimationNodeType::StateMachine(_) => {
                         // This is a blend node.
                         for edge_index in threaded_animation_graph.sorted_edge_ranges
                             [animation_graph_node_index.index()]
@@ -1095,10 +1309,9 @@ pub fn animate_targets(
                             }
                         }
 
-                        if let Err(err) = evaluation_state.push_blend_register_all(
-                            animation_graph_node.weight,
-                            animation_graph_node_index,
-                        ) {
+                        if let Err(err) = evaluation_state
+                            .push_blend_register_all(node_weight, animation_graph_node_index)
+                        {
                             warn!("Animation blending failed: {:?}", err);
                         }
                     }
@@ -1116,15 +1329,18 @@ pub fn animate_targets(
                             }
                         }
 
-                        if let Err(err) = evaluation_state.push_blend_register_all(
-                            animation_graph_node.weight,
-                            animation_graph_node_index,
-                        ) {
+                        if let Err(err) = evaluation_state
+                            .push_blend_register_all(node_weight, animation_graph_node_index)
+                        {
                             warn!("Animation blending failed: {:?}", err);
                         }
                     }
 
-                    AnimationNodeType::Clip(ref animation_clip_handle) => {
+                    AnimationNodeType::Clip(ref animation_clip_handle)
+                    | AnimationNodeType::AdditiveClip(AdditiveClip {
+                        clip: ref animation_clip_handle,
+                        ..
+                    }) => {
                         // This is a clip node.
                         let Some(active_animation) = animation_player
                             .active_animations
@@ -1136,6 +1352,7 @@ pub fn animate_targets(
                         // If the weight is zero or the current animation target is
                         // masked out, stop here.
                         if active_animation.weight == 0.0
+                            || bone_mask_weight == 0.0
                             || (target_mask
                                 & threaded_animation_graph.computed_masks
                                     [animation_graph_node_index.index()])
@@ -1148,6 +1365,8 @@ pub fn animate_targets(
                             continue;
                         };
 
+                        let weight = active_animation.weight * node_weight;
+
                         if !active_animation.paused {
                             // Trigger all animation events that occurred this tick, if any.
                             if let Some(triggered_events) = TriggeredEvents::from_animation(
@@ -1160,12 +1379,7 @@ pub fn animate_targets(
                                     for TimedAnimationEvent { time, event } in
                                         triggered_events.iter()
                                     {
-                                        event.trigger(
-                                            &mut commands,
-                                            entity,
-                                            *time,
-                                            active_animation.weight,
-                                        );
+                                        event.trigger(&mut commands, entity, *time, weight);
                                     }
                                 });
                             }
@@ -1175,7 +1389,21 @@ pub fn animate_targets(
                             continue;
                         };
 
-                        let weight = active_animation.weight * animation_graph_node.weight;
+                        // Additive clips are applied relative to their reference pose.
+                        let reference = match animation_graph_node.node_type {
+                            AnimationNodeType::AdditiveClip(ref additive_clip) => {
+                                let Some(reference_clip) = clips.get(&additive_clip.reference)
+                                else {
+                                    continue;
+                                };
+                                Some((
+                                    reference_clip.curves_for_target(target_id),
+                                    additive_clip.reference_time,
+                                ))
+                            }
+                            _ => None,
+                        };
+
                         let seek_time = active_animation.seek_time;
 
                         for curve in curves {
@@ -1193,17 +1421,41 @@ pub fn animate_targets(
                                     curve.0.create_evaluator()
                                 });
 
+                            let result = match reference {
+                                None => AnimationCurve::apply(
+                                    &*curve.0,
+                                    curve_evaluator,
+                                    seek_time,
+                                    weight,
+                                    animation_graph_node_index,
+                                ),
+                                Some((reference_curves, reference_time)) => {
+                                    // Properties that aren't part of the
+                                    // reference pose have no difference to add.
+                                    let Some(reference_curve) =
+                                        reference_curves.into_iter().flatten().find(|reference| {
+                                            reference.0.evaluator_id() == curve_evaluator_id
+                                        })
+                                    else {
+                                        continue;
+                                    };
+                                    apply_additive_curve(
+                                        &*curve.0,
+                                        &*reference_curve.0,
+                                        curve_evaluator,
+                                        seek_time,
+                                        reference_time,
+                                        weight,
+                                        animation_graph_node_index,
+                                    )
+                                }
+                            };
+
                             evaluation_state
                                 .current_evaluators
                                 .insert(curve_evaluator_id);
 
-                            if let Err(err) = AnimationCurve::apply(
-                                &*curve.0,
-                                curve_evaluator,
-                                seek_time,
-                                weight,
-                                animation_graph_node_index,
-                            ) {
+                            if let Err(err) = result {
                                 warn!("Animation application failed: {:?}", err);
                             }
                         }
@@ -1217,6 +1469,27 @@ pub fn animate_targets(
         });
 }
 
+/// Pushes the difference between `curve` at time `t` and `reference` at time
+/// `reference_time` onto the evaluation stack of the `curve_evaluator`.
+///
+/// The difference is computed with the additive blending of the evaluator, by
+/// adding the reference pose with a weight of -1 to the sampled value.
+fn apply_additive_curve(
+    curve: &dyn AnimationCurve,
+    reference: &dyn AnimationCurve,
+    curve_evaluator: &mut dyn AnimationCurveEvaluator,
+    t: f32,
+    reference_time: f32,
+    weight: f32,
+    graph_node: AnimationNodeIndex,
+) -> Result<(), AnimationEvaluationError> {
+    reference.apply(curve_evaluator, reference_time, -1.0, graph_node)?;
+    curve_evaluator.add(graph_node)?;
+    curve.apply(curve_evaluator, t, 1.0, graph_node)?;
+    curve_evaluator.add(graph_node)?;
+    curve_evaluator.push_blend_register(weight, graph_node)
+}
+
 /// Adds animation support to an app
 #[derive(Default)]
 pub struct AnimationPlugin;
@@ -1225,15 +1498,20 @@ impl Plugin for AnimationPlugin {
     fn build(&self, app: &mut App) {
         app.init_asset::<AnimationClip>()
             .init_asset::<AnimationGraph>()
+            .init_asset::<BoneMask>()
             .init_asset_loader::<AnimationGraphAssetLoader>()
+            .init_asset_loader::<BoneMaskAssetLoader>()
             .register_asset_reflect::<AnimationClip>()
             .register_asset_reflect::<AnimationGraph>()
+            .register_asset_reflect::<BoneMask>()
             .init_resource::<ThreadedAnimationGraphs>()
             .add_systems(
                 PostUpdate,
                 (
                     graph::thread_animation_graphs.before(AssetEventSystems),
+                    advance_state_machines,
                     advance_transitions,
+                    resample_edited_animations,
                     advance_animations,
                     // TODO: `animate_targets` can animate anything, so
                     // ambiguity testing currently considers it ambiguous with
@@ -1247,6 +1525,8 @@ impl Plugin for AnimationPlugin {
                         .ambiguous_with_all(),
                     #[cfg(not(feature = "bevy_mesh"))]
                     animate_targets.ambiguous_with_all(),
+                    ik::apply_ik_constraints,
+                    recording::record_animations,
                     trigger_untargeted_animation_events,
                     expire_completed_transitions,
                 )
@@ -1656,6 +1936,267 @@ mod tests {
         assert_triggered_events_with(&active_animation, &clip, [0.3, 0.2]);
     }
 
+    #[test]
+    fn test_keyframe_events() {
+        #[derive(Resource, Default)]
+        struct Events(Vec<KeyframeEvent>);
+
+        let mut clip = AnimationClip::default();
+        clip.add_keyframe_event(0.4, "footstep", KeyframeEventPayload::Int(1));
+        assert_eq!(0.4, clip.duration);
+
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        world.init_resource::<Events>();
+        world.add_observer(|event: On<KeyframeEvent>, mut events: ResMut<Events>| {
+            events.0.push(event.event().clone());
+        });
+
+        let TimedAnimationEvent { time, event } = &clip.events[&AnimationEventTarget::Root][0];
+        event.trigger(&mut world.commands(), entity, *time, 0.25);
+        world.flush();
+
+        assert_eq!(
+            world.resource::<Events>().0,
+            [KeyframeEvent {
+                name: "footstep".into(),
+                payload: KeyframeEventPayload::Int(1),
+                time: 0.4,
+                weight: 0.25,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_additive_clip() {
+        use crate::{animated_field, prelude::*};
+        use bevy_app::TaskPoolPlugin;
+        use bevy_asset::{AssetPlugin, Handle};
+        use bevy_math::{
+            curve::{ConstantCurve, Interval},
+            Quat, Vec3,
+        };
+        use bevy_time::TimePlugin;
+        use bevy_transform::components::Transform;
+
+        let target_id = AnimationTargetId::from_name(&Name::new("target"));
+        let clip = |translation: Vec3, rotation: Quat| {
+            let mut clip = AnimationClip::default();
+            clip.add_curve_to_target(
+                target_id,
+                AnimatableCurve::new(
+                    animated_field!(Transform::translation),
+                    ConstantCurve::new(Interval::UNIT, translation),
+                ),
+            );
+            clip.add_curve_to_target(
+                target_id,
+                AnimatableCurve::new(
+                    animated_field!(Transform::rotation),
+                    ConstantCurve::new(Interval::UNIT, rotation),
+                ),
+            );
+            clip
+        };
+
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            TimePlugin,
+            AnimationPlugin,
+        ));
+
+        let mut clips = app.world_mut().resource_mut::<Assets<AnimationClip>>();
+        let base: Handle<AnimationClip> = clips.add(clip(Vec3::X, Quat::IDENTITY));
+        let recoil = clips.add(clip(Vec3::new(3.0, 2.0, 0.0), Quat::from_rotation_y(1.5)));
+        let reference = clips.add(clip(Vec3::new(3.0, 0.0, 0.0), Quat::from_rotation_y(0.5)));
+
+        let mut graph = AnimationGraph::new();
+        let add = graph.add_additive_blend(1.0, graph.root);
+        let base = graph.add_clip(base, 1.0, add);
+        let recoil = graph.add_additive_clip(recoil, reference, 0.0, 0.5, add);
+        let graph = app
+            .world_mut()
+            .resource_mut::<Assets<AnimationGraph>>()
+            .add(graph);
+
+        let mut player = AnimationPlayer::default();
+        player.play(base).repeat();
+        player.play(recoil).repeat();
+        let player = app
+            .world_mut()
+            .spawn((player, AnimationGraphHandle(graph)))
+            .id();
+        let target = app
+            .world_mut()
+            .spawn((target_id, AnimatedBy(player), Transform::default()))
+            .id();
+
+        for _ in 0..3 {
+            app.update();
+        }
+
+        let transform = app.world().get::<Transform>(target).unwrap();
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(1.0, 1.0, 0.0), 1e-5));
+        assert!(transform
+            .rotation
+            .abs_diff_eq(Quat::from_rotation_y(0.5), 1e-5));
+    }
+
+    #[test]
+    fn test_bone_mask() {
+        use crate::{animated_field, prelude::*};
+        use bevy_app::TaskPoolPlugin;
+        use bevy_asset::AssetPlugin;
+        use bevy_math::{
+            curve::{ConstantCurve, Interval},
+            Vec3,
+        };
+        use bevy_time::TimePlugin;
+        use bevy_transform::components::Transform;
+
+        let arm = AnimationTargetId::from_name(&Name::new("arm"));
+        let leg = AnimationTargetId::from_name(&Name::new("leg"));
+        let clip = |translation: Vec3| {
+            let mut clip = AnimationClip::default();
+            for target_id in [arm, leg] {
+                clip.add_curve_to_target(
+                    target_id,
+                    AnimatableCurve::new(
+                        animated_field!(Transform::translation),
+                        ConstantCurve::new(Interval::UNIT, translation),
+                    ),
+                );
+            }
+            clip
+        };
+
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            TimePlugin,
+            AnimationPlugin,
+        ));
+
+        let mut clips = app.world_mut().resource_mut::<Assets<AnimationClip>>();
+        let walk = clips.add(clip(Vec3::X));
+        let aim = clips.add(clip(Vec3::Y));
+        let upper_body = app
+            .world_mut()
+            .resource_mut::<Assets<BoneMask>>()
+            .add(BoneMask::default().with_weight(arm, 1.0));
+
+        let mut graph = AnimationGraph::new();
+        let walk = graph.add_clip(walk, 1.0, graph.root);
+        let aim = graph.add_clip(aim, 1.0, graph.root);
+        graph[aim].set_bone_mask(upper_body);
+        let graph = app
+            .world_mut()
+            .resource_mut::<Assets<AnimationGraph>>()
+            .add(graph);
+
+        let mut player = AnimationPlayer::default();
+        player.play(walk).repeat();
+        player.play(aim).repeat();
+        let player = app
+            .world_mut()
+            .spawn((player, AnimationGraphHandle(graph)))
+            .id();
+        let [arm, leg] = [arm, leg].map(|target_id| {
+            app.world_mut()
+                .spawn((target_id, AnimatedBy(player), Transform::default()))
+                .id()
+        });
+
+        for _ in 0..3 {
+            app.update();
+        }
+
+        let translation = |entity| app.world().get::<Transform>(entity).unwrap().translation;
+        assert!(translation(arm).abs_diff_eq(Vec3::new(0.5, 0.5, 0.0), 1e-5));
+        assert!(translation(leg).abs_diff_eq(Vec3::X, 1e-5));
+    }
+
+    #[test]
+    fn test_edit_curve() {
+        use crate::{animated_field, prelude::*};
+        use bevy_app::TaskPoolPlugin;
+        use bevy_asset::AssetPlugin;
+        use bevy_math::Vec3;
+        use bevy_time::TimePlugin;
+        use bevy_transform::components::Transform;
+
+        let target_id = AnimationTargetId::from_name(&Name::new("target"));
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_target(
+            target_id,
+            AnimatableCurve::new(
+                animated_field!(Transform::translation),
+                AnimatableKeyframeCurve::new([(0.0, Vec3::ZERO), (2.0, Vec3::X * 2.0)]).unwrap(),
+            ),
+        );
+
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            TimePlugin,
+            AnimationPlugin,
+        ));
+
+        let clip = app
+            .world_mut()
+            .resource_mut::<Assets<AnimationClip>>()
+            .add(clip);
+        let (graph, node_index) = AnimationGraph::from_clip(clip.clone());
+        let graph = app
+            .world_mut()
+            .resource_mut::<Assets<AnimationGraph>>()
+            .add(graph);
+
+        let mut player = AnimationPlayer::default();
+        player.play(node_index).seek_to(1.5).pause();
+        let player = app
+            .world_mut()
+            .spawn((player, AnimationGraphHandle(graph)))
+            .id();
+        let target = app
+            .world_mut()
+            .spawn((target_id, AnimatedBy(player), Transform::default()))
+            .id();
+
+        for _ in 0..3 {
+            app.update();
+        }
+        let translation = |app: &App| app.world().get::<Transform>(target).unwrap().translation;
+        assert!(translation(&app).abs_diff_eq(Vec3::X * 1.5, 1e-5));
+
+        // Shorten the clip to one second, which moves the player back to its end
+        let mut clips = app.world_mut().resource_mut::<Assets<AnimationClip>>();
+        let clip = clips.get_mut(&clip).unwrap();
+        let removed = clip.edit_curve(
+            target_id,
+            &animated_field!(Transform::translation).evaluator_id(),
+            |curve: &mut AnimatableKeyframeCurve<Vec3>| {
+                curve.insert_keyframe(1.0, Vec3::Y);
+                curve.remove_keyframe(2)
+            },
+        );
+        assert_eq!(removed, Some(Some((2.0, Vec3::X * 2.0))));
+        assert_eq!(clip.duration(), 1.0);
+
+        for _ in 0..2 {
+            app.update();
+        }
+        let player = app.world().get::<AnimationPlayer>(player).unwrap();
+        assert_eq!(player.animation(node_index).unwrap().seek_time(), 1.0);
+        assert!(translation(&app).abs_diff_eq(Vec3::Y, 1e-5));
+    }
+
     #[test]
     fn test_animation_node_index_as_key_of_dynamic_map() {
         let mut map = DynamicMap::default();
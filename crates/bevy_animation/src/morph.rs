@@ -7,7 +7,10 @@ use crate::{
 use bevy_math::curve::{iterable::IterableCurve, Interval};
 use bevy_mesh::morph::MorphWeights;
 use bevy_reflect::{FromReflect, Reflect, Reflectable};
-use core::{any::TypeId, fmt::Debug};
+use core::{
+    any::{Any, TypeId},
+    fmt::Debug,
+};
 
 /// This type allows an [`IterableCurve`] valued in `f32` to be used as an [`AnimationCurve`]
 /// that animates [morph weights].
@@ -104,6 +107,10 @@ where
             .push((weight, graph_node));
         Ok(())
     }
+
+    fn curve_mut(&mut self) -> Option<&mut dyn Any> {
+        Some(&mut self.0)
+    }
 }
 
 impl WeightsCurveEvaluator {
@@ -0,0 +1,289 @@
+//! Recording the pose of entity hierarchies into animation clips.
+//!
+//! An [`AnimationRecorder`] samples the [`Transform`] of an entity and its
+//! descendants every frame, after animations and inverse kinematics are
+//! applied. The recording can then be turned into an [`AnimationClip`], and
+//! played back like any other clip: to replay a ghost, to bake procedural
+//! motion, or to create clips from a physics ragdoll.
+
+use core::time::Duration;
+
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    hierarchy::Children,
+    name::Name,
+    reflect::ReflectComponent,
+    system::{Query, Res},
+};
+use bevy_math::{
+    curve::{ConstantCurve, Interval, UnevenSampleAutoCurve},
+    Quat, Vec3,
+};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
+use bevy_time::Time;
+use bevy_transform::components::Transform;
+
+use crate::{
+    animated_field,
+    animation_curves::{
+        AnimatableCurve, AnimatableProperty, AnimatedField, AnimationCompatibleCurve,
+    },
+    AnimationClip, AnimationTargetId,
+};
+
+/// Records the [`Transform`] of this entity and its descendants over time.
+///
+/// Each entity is recorded under its [`AnimationTargetId`], or, if it doesn't
+/// have one, under an ID computed from the [`Name`]s of the entities from this
+/// one to it, the same way the glTF loader does. Entities without either are
+/// skipped, along with their descendants.
+///
+/// ```
+/// # use bevy_animation::{recording::AnimationRecorder, AnimationClip};
+/// # use bevy_asset::Assets;
+/// # use bevy_ecs::prelude::*;
+/// fn save_ghost(
+///     recorders: Query<&AnimationRecorder>,
+///     mut clips: ResMut<Assets<AnimationClip>>,
+/// ) {
+///     for recorder in &recorders {
+///         let ghost = clips.add(recorder.to_clip());
+///         // ...
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Clone, Debug, Default)]
+pub struct AnimationRecorder {
+    /// The minimum time between two recorded poses.
+    ///
+    /// Poses are recorded every frame when this is zero.
+    pub interval: Duration,
+    paused: bool,
+    /// The time since the recording started, paused time excluded.
+    elapsed: Duration,
+    /// The time of the last recorded pose.
+    last_sample: Option<Duration>,
+    tracks: HashMap<AnimationTargetId, RecordedTrack>,
+}
+
+/// The recorded poses of a single animation target.
+#[derive(Clone, Debug, Default, Reflect)]
+struct RecordedTrack {
+    times: Vec<f32>,
+    translations: Vec<Vec3>,
+    rotations: Vec<Quat>,
+    scales: Vec<Vec3>,
+}
+
+impl AnimationRecorder {
+    /// Creates a recorder that records a pose every frame.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a recorder that records a pose at most once per `interval`.
+    pub fn with_interval(interval: Duration) -> Self {
+        Self {
+            interval,
+            ..Self::default()
+        }
+    }
+
+    /// Pauses the recording. Time doesn't advance in the recording until it's
+    /// resumed.
+    pub fn pause(&mut self) -> &mut Self {
+        self.paused = true;
+        self
+    }
+
+    /// Resumes a paused recording.
+    pub fn resume(&mut self) -> &mut Self {
+        self.paused = false;
+        self
+    }
+
+    /// Returns true if the recording is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Returns the duration of the recording.
+    pub fn elapsed(&self) -> Duration {
+        self.last_sample.unwrap_or_default()
+    }
+
+    /// Discards the recorded poses, and starts the recording over.
+    pub fn clear(&mut self) -> &mut Self {
+        self.elapsed = Duration::ZERO;
+        self.last_sample = None;
+        self.tracks.clear();
+        self
+    }
+
+    /// Creates an [`AnimationClip`] from the recorded poses.
+    ///
+    /// The translation, rotation and scale of each recorded entity are
+    /// linearly interpolated between the poses. The clip can be played back
+    /// on any hierarchy whose entities have the same [`AnimationTargetId`]s.
+    pub fn to_clip(&self) -> AnimationClip {
+        let mut clip = AnimationClip::default();
+        for (&target_id, track) in &self.tracks {
+            add_track(
+                &mut clip,
+                target_id,
+                animated_field!(Transform::translation),
+                &track.times,
+                &track.translations,
+            );
+            add_track(
+                &mut clip,
+                target_id,
+                animated_field!(Transform::rotation),
+                &track.times,
+                &track.rotations,
+            );
+            add_track(
+                &mut clip,
+                target_id,
+                animated_field!(Transform::scale),
+                &track.times,
+                &track.scales,
+            );
+        }
+        clip
+    }
+
+    fn record(&mut self, target_id: AnimationTargetId, time: f32, transform: &Transform) {
+        let track = self.tracks.entry(target_id).or_default();
+        track.times.push(time);
+        track.translations.push(transform.translation);
+        track.rotations.push(transform.rotation);
+        track.scales.push(transform.scale);
+    }
+}
+
+/// Adds a curve animating `property` to the clip, or a constant curve if only
+/// one pose was recorded.
+fn add_track<P, T>(
+    clip: &mut AnimationClip,
+    target_id: AnimationTargetId,
+    property: P,
+    times: &[f32],
+    values: &[T],
+) where
+    P: AnimatableProperty<Property = T> + Clone,
+    T: Clone,
+    UnevenSampleAutoCurve<T>: AnimationCompatibleCurve<T>,
+    ConstantCurve<T>: AnimationCompatibleCurve<T>,
+{
+    match UnevenSampleAutoCurve::new(times.iter().copied().zip(values.iter().cloned())) {
+        Ok(curve) => clip.add_curve_to_target(target_id, AnimatableCurve::new(property, curve)),
+        Err(_) => {
+            if let Some(value) = values.first() {
+                let curve = ConstantCurve::new(Interval::EVERYWHERE, value.clone());
+                clip.add_curve_to_target(target_id, AnimatableCurve::new(property, curve));
+            }
+        }
+    }
+}
+
+/// Records the pose of the hierarchies of the [`AnimationRecorder`]s.
+pub fn record_animations(
+    mut recorders: Query<(Entity, &mut AnimationRecorder)>,
+    entities: Query<(&Transform, Option<&AnimationTargetId>, Option<&Name>)>,
+    children: Query<&Children>,
+    time: Res<Time>,
+) {
+    for (root, mut recorder) in &mut recorders {
+        if recorder.paused {
+            continue;
+        }
+        // The first pose is recorded at time zero
+        if recorder.last_sample.is_some() {
+            recorder.elapsed += time.delta();
+        }
+        if recorder
+            .last_sample
+            .is_some_and(|last_sample| recorder.elapsed - last_sample < recorder.interval)
+        {
+            continue;
+        }
+        let elapsed = recorder.elapsed;
+        recorder.last_sample = Some(elapsed);
+
+        let mut stack = vec![(root, Vec::<&Name>::new())];
+        while let Some((entity, mut path)) = stack.pop() {
+            let Ok((transform, target_id, name)) = entities.get(entity) else {
+                continue;
+            };
+            if let Some(name) = name {
+                path.push(name);
+            }
+            let target_id = match (target_id, name) {
+                (Some(&target_id), _) => target_id,
+                (None, Some(_)) => AnimationTargetId::from_names(path.iter().copied()),
+                (None, None) => continue,
+            };
+            recorder.record(target_id, elapsed.as_secs_f32(), transform);
+
+            for &child in children.get(entity).into_iter().flatten() {
+                stack.push((child, path.clone()));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{record_animations, AnimationRecorder};
+    use crate::{AnimationClip, AnimationTargetId};
+    use bevy_app::{App, PostUpdate};
+    use bevy_ecs::name::Name;
+    use bevy_math::Vec3;
+    use bevy_time::{TimePlugin, TimeUpdateStrategy};
+    use bevy_transform::components::Transform;
+    use core::time::Duration;
+
+    #[test]
+    fn record_hierarchy() {
+        let mut app = App::new();
+        app.add_plugins(TimePlugin)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )))
+            .add_systems(PostUpdate, record_animations);
+
+        let hand = app
+            .world_mut()
+            .spawn((Name::new("Hand"), Transform::default()))
+            .id();
+        let root = app
+            .world_mut()
+            .spawn((
+                Name::new("Root"),
+                Transform::default(),
+                AnimationRecorder::new(),
+            ))
+            .add_child(hand)
+            .id();
+
+        for i in 0..5 {
+            app.world_mut()
+                .get_mut::<Transform>(hand)
+                .unwrap()
+                .translation = Vec3::X * i as f32;
+            app.update();
+        }
+
+        let recorder = app.world().get::<AnimationRecorder>(root).unwrap();
+        let clip: AnimationClip = recorder.to_clip();
+        assert_eq!(recorder.elapsed(), Duration::from_millis(400));
+        assert_eq!(clip.duration(), 0.4);
+
+        let hand_id = AnimationTargetId::from_names([Name::new("Root"), Name::new("Hand")].iter());
+        assert_eq!(clip.curves_for_target(hand_id).map(Vec::len), Some(3));
+    }
+}
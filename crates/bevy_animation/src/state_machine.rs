@@ -0,0 +1,642 @@
+//! Animation state machines.
+//!
+//! A state machine node in an [`AnimationGraph`] plays one of its children at
+//! a time, its *current state*, and crossfades to another child when the
+//! conditions of one of its transitions hold. The conditions are evaluated
+//! against the parameters of the [`AnimationStates`] component on the same
+//! entity as the [`AnimationPlayer`].
+//!
+//! ```
+//! # use bevy_animation::prelude::*;
+//! # use bevy_asset::Handle;
+//! # use core::time::Duration;
+//! # let (idle_clip, run_clip) = (Handle::default(), Handle::default());
+//! let mut graph = AnimationGraph::new();
+//! let locomotion = graph.add_state_machine(1.0, graph.root);
+//! let idle = graph.add_clip(idle_clip, 1.0, locomotion);
+//! let run = graph.add_clip(run_clip, 1.0, locomotion);
+//!
+//! graph
+//!     .state_machine_mut(locomotion)
+//!     .unwrap()
+//!     .add_state(AnimationState::new("idle", idle).repeat())
+//!     .add_state(AnimationState::new("run", run).repeat())
+//!     .add_transition(
+//!         "idle",
+//!         "run",
+//!         [AnimationCondition::greater("speed", 0.1)],
+//!         Duration::from_millis(200),
+//!     )
+//!     .add_transition(
+//!         "run",
+//!         "idle",
+//!         [AnimationCondition::less("speed", 0.1)],
+//!         Duration::from_millis(300),
+//!     );
+//!
+//! // On the entity with the `AnimationPlayer`, every frame:
+//! let mut states = AnimationStates::default();
+//! states.set_float("speed", 4.0);
+//! ```
+
+use bevy_asset::Assets;
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::EntityEvent,
+    reflect::ReflectComponent,
+    system::{Commands, Query, Res},
+};
+use bevy_platform::collections::{HashMap, HashSet};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::Time;
+use core::time::Duration;
+use petgraph::Direction;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    graph::{AnimationGraph, AnimationGraphHandle, AnimationNodeIndex, AnimationNodeType},
+    ActiveAnimation, AnimationPlayer, RepeatAnimation,
+};
+
+/// The states and transitions of a state machine node in an
+/// [`AnimationGraph`].
+///
+/// Each state plays a child of the state machine node. The first state is the
+/// initial state.
+#[derive(Clone, Debug, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Clone, Default)]
+pub struct AnimationStateMachine {
+    /// The states of the state machine.
+    pub states: Vec<AnimationState>,
+    /// The transitions between the states, checked in order. The first
+    /// transition whose conditions hold is taken.
+    pub transitions: Vec<AnimationStateTransition>,
+}
+
+/// A named state of an [`AnimationStateMachine`].
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize)]
+#[reflect(Clone)]
+pub struct AnimationState {
+    /// The name of the state, used by transitions and events.
+    pub name: String,
+    /// The node played while in this state, a child of the state machine node.
+    pub node: AnimationNodeIndex,
+    /// The repetition behavior of the animation clips of the state.
+    pub repeat: RepeatAnimation,
+}
+
+/// A transition between two states of an [`AnimationStateMachine`].
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize)]
+#[reflect(Clone)]
+pub struct AnimationStateTransition {
+    /// The state this transition leaves, or `None` to leave any other state.
+    pub from: Option<String>,
+    /// The state this transition enters.
+    pub to: String,
+    /// The conditions that must all hold for the transition to be taken.
+    pub conditions: Vec<AnimationCondition>,
+    /// The duration of the crossfade between the two states.
+    pub duration: Duration,
+}
+
+/// A condition of an [`AnimationStateTransition`], evaluated against the
+/// parameters of the [`AnimationStates`] component.
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Clone, PartialEq)]
+pub enum AnimationCondition {
+    /// The boolean parameter has the given value. Unset parameters are
+    /// `false`.
+    Bool {
+        /// The name of the parameter.
+        parameter: String,
+        /// The expected value.
+        value: bool,
+    },
+    /// The float parameter is greater than the given value. Unset parameters
+    /// are `0.0`.
+    Greater {
+        /// The name of the parameter.
+        parameter: String,
+        /// The value to compare against.
+        value: f32,
+    },
+    /// The float parameter is less than the given value. Unset parameters are
+    /// `0.0`.
+    Less {
+        /// The name of the parameter.
+        parameter: String,
+        /// The value to compare against.
+        value: f32,
+    },
+    /// The trigger parameter is set. Taking the transition resets it.
+    Trigger(String),
+    /// All the animation clips of the current state have finished.
+    Finished,
+}
+
+impl AnimationCondition {
+    /// Creates a condition that holds when the boolean parameter has the given
+    /// value.
+    pub fn bool(parameter: impl Into<String>, value: bool) -> Self {
+        Self::Bool {
+            parameter: parameter.into(),
+            value,
+        }
+    }
+
+    /// Creates a condition that holds when the float parameter is greater than
+    /// the given value.
+    pub fn greater(parameter: impl Into<String>, value: f32) -> Self {
+        Self::Greater {
+            parameter: parameter.into(),
+            value,
+        }
+    }
+
+    /// Creates a condition that holds when the float parameter is less than
+    /// the given value.
+    pub fn less(parameter: impl Into<String>, value: f32) -> Self {
+        Self::Less {
+            parameter: parameter.into(),
+            value,
+        }
+    }
+
+    /// Creates a condition that holds when the trigger parameter is set.
+    pub fn trigger(parameter: impl Into<String>) -> Self {
+        Self::Trigger(parameter.into())
+    }
+}
+
+impl AnimationState {
+    /// Creates a state playing the given node once.
+    pub fn new(name: impl Into<String>, node: AnimationNodeIndex) -> Self {
+        Self {
+            name: name.into(),
+            node,
+            repeat: RepeatAnimation::Never,
+        }
+    }
+
+    /// Sets the animation clips of the state to repeat forever.
+    pub fn repeat(mut self) -> Self {
+        self.repeat = RepeatAnimation::Forever;
+        self
+    }
+}
+
+impl AnimationStateMachine {
+    /// Adds a state to the state machine.
+    ///
+    /// The node of the state must be a child of the state machine node.
+    pub fn add_state(&mut self, state: AnimationState) -> &mut Self {
+        self.states.push(state);
+        self
+    }
+
+    /// Adds a transition from the state named `from` to the state named `to`,
+    /// taken when all the `conditions` hold.
+    pub fn add_transition(
+        &mut self,
+        from: impl Into<String>,
+        to: impl Into<String>,
+        conditions: impl IntoIterator<Item = AnimationCondition>,
+        duration: Duration,
+    ) -> &mut Self {
+        self.transitions.push(AnimationStateTransition {
+            from: Some(from.into()),
+            to: to.into(),
+            conditions: conditions.into_iter().collect(),
+            duration,
+        });
+        self
+    }
+
+    /// Adds a transition from any other state to the state named `to`, taken
+    /// when all the `conditions` hold.
+    pub fn add_transition_from_any(
+        &mut self,
+        to: impl Into<String>,
+        conditions: impl IntoIterator<Item = AnimationCondition>,
+        duration: Duration,
+    ) -> &mut Self {
+        self.transitions.push(AnimationStateTransition {
+            from: None,
+            to: to.into(),
+            conditions: conditions.into_iter().collect(),
+            duration,
+        });
+        self
+    }
+
+    /// Returns the index of the state with the given name.
+    pub fn state_index(&self, name: &str) -> Option<usize> {
+        self.states.iter().position(|state| state.name == name)
+    }
+}
+
+/// The parameters and current states of the state machines of an
+/// [`AnimationGraph`].
+///
+/// Place this component on the same entity as the [`AnimationPlayer`] and
+/// [`AnimationGraphHandle`]. It takes responsibility for starting and stopping
+/// the animations of the states, and for their weights. Without it, state
+/// machine nodes behave like blend nodes.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default, Clone)]
+pub struct AnimationStates {
+    bools: HashMap<String, bool>,
+    floats: HashMap<String, f32>,
+    triggers: HashSet<String>,
+    machines: HashMap<AnimationNodeIndex, ActiveStateMachine>,
+    /// The weight of the node of each state, used when evaluating the graph.
+    node_weights: HashMap<AnimationNodeIndex, f32>,
+}
+
+/// The current state of a state machine, and the weights of its states.
+#[derive(Clone, Debug, Reflect)]
+#[reflect(Clone)]
+struct ActiveStateMachine {
+    current: usize,
+    weights: Vec<f32>,
+    /// The duration of the crossfade to the current state, in seconds.
+    fade_duration: f32,
+    /// A transition requested with [`AnimationStates::play`].
+    requested: Option<(String, Duration)>,
+}
+
+impl AnimationStates {
+    /// Sets the value of a boolean parameter.
+    pub fn set_bool(&mut self, parameter: impl Into<String>, value: bool) -> &mut Self {
+        self.bools.insert(parameter.into(), value);
+        self
+    }
+
+    /// Returns the value of a boolean parameter, or `false` if it isn't set.
+    pub fn bool(&self, parameter: &str) -> bool {
+        self.bools.get(parameter).copied().unwrap_or_default()
+    }
+
+    /// Sets the value of a float parameter.
+    pub fn set_float(&mut self, parameter: impl Into<String>, value: f32) -> &mut Self {
+        self.floats.insert(parameter.into(), value);
+        self
+    }
+
+    /// Returns the value of a float parameter, or `0.0` if it isn't set.
+    pub fn float(&self, parameter: &str) -> f32 {
+        self.floats.get(parameter).copied().unwrap_or_default()
+    }
+
+    /// Sets a trigger parameter. It stays set until a transition conditioned
+    /// on it is taken, or until it is reset.
+    pub fn set_trigger(&mut self, parameter: impl Into<String>) -> &mut Self {
+        self.triggers.insert(parameter.into());
+        self
+    }
+
+    /// Resets a trigger parameter.
+    pub fn reset_trigger(&mut self, parameter: &str) -> &mut Self {
+        self.triggers.remove(parameter);
+        self
+    }
+
+    /// Returns `true` if the trigger parameter is set.
+    pub fn is_triggered(&self, parameter: &str) -> bool {
+        self.triggers.contains(parameter)
+    }
+
+    /// Crossfades the state machine to the state named `state` over the given
+    /// duration, regardless of its transitions.
+    ///
+    /// The state is entered the next time the state machines are updated.
+    pub fn play(
+        &mut self,
+        state_machine: AnimationNodeIndex,
+        state: impl Into<String>,
+        duration: Duration,
+    ) -> &mut Self {
+        if let Some(machine) = self.machines.get_mut(&state_machine) {
+            machine.requested = Some((state.into(), duration));
+        } else {
+            // The state machine starts in the requested state
+            self.machines.insert(
+                state_machine,
+                ActiveStateMachine {
+                    current: usize::MAX,
+                    weights: Vec::new(),
+                    fade_duration: 0.0,
+                    requested: Some((state.into(), duration)),
+                },
+            );
+        }
+        self
+    }
+
+    /// Returns the index of the current state of the state machine, in
+    /// [`AnimationStateMachine::states`].
+    ///
+    /// Returns `None` if the state machine hasn't started yet.
+    pub fn current_state(&self, state_machine: AnimationNodeIndex) -> Option<usize> {
+        self.machines
+            .get(&state_machine)
+            .map(|machine| machine.current)
+            .filter(|current| *current != usize::MAX)
+    }
+
+    /// Returns the weight of the node in the evaluation of the graph: its
+    /// crossfade weight if it's the node of a state, `1.0` otherwise.
+    pub(crate) fn node_weight(&self, node: AnimationNodeIndex) -> f32 {
+        self.node_weights.get(&node).copied().unwrap_or(1.0)
+    }
+
+    fn conditions_hold(
+        &self,
+        conditions: &[AnimationCondition],
+        player: &AnimationPlayer,
+        clips: &[AnimationNodeIndex],
+    ) -> bool {
+        conditions.iter().all(|condition| match condition {
+            AnimationCondition::Bool { parameter, value } => self.bool(parameter) == *value,
+            AnimationCondition::Greater { parameter, value } => self.float(parameter) > *value,
+            AnimationCondition::Less { parameter, value } => self.float(parameter) < *value,
+            AnimationCondition::Trigger(parameter) => self.is_triggered(parameter),
+            AnimationCondition::Finished => clips.iter().all(|clip| {
+                player
+                    .animation(*clip)
+                    .is_none_or(ActiveAnimation::is_finished)
+            }),
+        })
+    }
+
+    /// Advances the state machines of the graph by `delta` seconds, taking
+    /// the transitions whose conditions hold.
+    ///
+    /// `on_transition` is called with the state machine node, the state that
+    /// was left if any, and the state that was entered.
+    fn advance(
+        &mut self,
+        graph: &AnimationGraph,
+        player: &mut AnimationPlayer,
+        delta: f32,
+        mut on_transition: impl FnMut(AnimationNodeIndex, Option<&str>, &str),
+    ) {
+        for node in graph.nodes() {
+            let AnimationNodeType::StateMachine(ref state_machine) = graph[node].node_type else {
+                continue;
+            };
+            if state_machine.states.is_empty() {
+                continue;
+            }
+
+            let mut machine = self
+                .machines
+                .remove(&node)
+                .unwrap_or_else(|| ActiveStateMachine {
+                    current: usize::MAX,
+                    weights: Vec::new(),
+                    fade_duration: 0.0,
+                    requested: Some((state_machine.states[0].name.clone(), Duration::ZERO)),
+                });
+            // Restart the state machine if its states changed
+            if machine.weights.len() != state_machine.states.len() {
+                machine.weights = vec![0.0; state_machine.states.len()];
+                if machine.current >= state_machine.states.len() {
+                    machine.current = usize::MAX;
+                    machine.requested.get_or_insert_with(|| {
+                        (state_machine.states[0].name.clone(), Duration::ZERO)
+                    });
+                }
+            }
+
+            // Find the transition to take, if any
+            let current = state_machine.states.get(machine.current);
+            let transition = match machine.requested.take() {
+                // A state machine that hasn't started falls back to its
+                // initial state
+                Some((state, duration)) => state_machine
+                    .state_index(&state)
+                    .or(current.is_none().then_some(0))
+                    .map(|state| (state, duration)),
+                None => {
+                    let current = current.expect("a started state machine has a current state");
+                    let clips = state_clips(graph, current.node);
+                    state_machine
+                        .transitions
+                        .iter()
+                        .find(|transition| {
+                            let leaves_current = match &transition.from {
+                                Some(from) => *from == current.name,
+                                None => transition.to != current.name,
+                            };
+                            leaves_current
+                                && self.conditions_hold(&transition.conditions, player, &clips)
+                        })
+                        .and_then(|transition| {
+                            for condition in &transition.conditions {
+                                if let AnimationCondition::Trigger(parameter) = condition {
+                                    self.triggers.remove(parameter);
+                                }
+                            }
+                            state_machine
+                                .state_index(&transition.to)
+                                .map(|state| (state, transition.duration))
+                        })
+                }
+            };
+
+            if let Some((next, duration)) = transition {
+                let next_state = &state_machine.states[next];
+                on_transition(
+                    node,
+                    current.map(|state| state.name.as_str()),
+                    &next_state.name,
+                );
+                for clip in state_clips(graph, next_state.node) {
+                    player.start(clip).set_repeat(next_state.repeat);
+                }
+                machine.current = next;
+                machine.fade_duration = duration.as_secs_f32();
+            }
+
+            // Fade in the current state, and fade out the others so the
+            // weights add up to 1
+            let previous_weights = machine.weights.clone();
+            let current_weight = if machine.fade_duration > 0.0 {
+                (machine.weights[machine.current] + delta / machine.fade_duration).min(1.0)
+            } else {
+                1.0
+            };
+            let other_weights =
+                machine.weights.iter().sum::<f32>() - machine.weights[machine.current];
+            machine.weights[machine.current] = current_weight;
+            let remaining_weight = 1.0 - current_weight;
+            for (index, weight) in machine.weights.iter_mut().enumerate() {
+                if index == machine.current {
+                    continue;
+                }
+                *weight = if other_weights > 0.0 {
+                    *weight * remaining_weight / other_weights
+                } else {
+                    0.0
+                };
+                // Stop the animations of the states that finished fading out
+                if *weight == 0.0 && previous_weights[index] > 0.0 {
+                    for clip in state_clips(graph, state_machine.states[index].node) {
+                        player.stop(clip);
+                    }
+                }
+            }
+
+            for (state, weight) in state_machine.states.iter().zip(&machine.weights) {
+                self.node_weights.insert(state.node, *weight);
+            }
+            self.machines.insert(node, machine);
+        }
+    }
+}
+
+/// Returns the clip nodes played by a state: the node itself if it's a clip,
+/// or the clips below it.
+///
+/// The clips of nested state machines are managed by those state machines, and
+/// aren't included.
+fn state_clips(graph: &AnimationGraph, node: AnimationNodeIndex) -> Vec<AnimationNodeIndex> {
+    let mut clips = Vec::new();
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+        match graph[node].node_type {
+            AnimationNodeType::Clip(_) | AnimationNodeType::AdditiveClip(_) => clips.push(node),
+            AnimationNodeType::Blend | AnimationNodeType::Add => {
+                stack.extend(graph.graph.neighbors_directed(node, Direction::Outgoing));
+            }
+            AnimationNodeType::StateMachine(_) => {}
+        }
+    }
+    clips
+}
+
+/// Triggered on the entity with the [`AnimationPlayer`] when a state machine
+/// enters a state, at the start of the crossfade.
+#[derive(EntityEvent, Clone, Debug)]
+pub struct AnimationStateEntered {
+    /// The entity with the [`AnimationPlayer`].
+    pub entity: Entity,
+    /// The state machine node.
+    pub state_machine: AnimationNodeIndex,
+    /// The name of the state that was entered.
+    pub state: String,
+}
+
+/// Triggered on the entity with the [`AnimationPlayer`] when a state machine
+/// leaves a state, at the start of the crossfade.
+#[derive(EntityEvent, Clone, Debug)]
+pub struct AnimationStateExited {
+    /// The entity with the [`AnimationPlayer`].
+    pub entity: Entity,
+    /// The state machine node.
+    pub state_machine: AnimationNodeIndex,
+    /// The name of the state that was left.
+    pub state: String,
+}
+
+/// A system that takes the transitions of the state machines whose conditions
+/// hold, and crossfades between their states.
+pub fn advance_state_machines(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut AnimationStates,
+        &mut AnimationPlayer,
+        &AnimationGraphHandle,
+    )>,
+    graphs: Res<Assets<AnimationGraph>>,
+    time: Res<Time>,
+) {
+    for (entity, mut states, mut player, graph_handle) in &mut query {
+        let Some(graph) = graphs.get(graph_handle) else {
+            continue;
+        };
+        states.advance(
+            graph,
+            &mut player,
+            time.delta_secs(),
+            |state_machine, exited, entered| {
+                if let Some(exited) = exited {
+                    commands.trigger(AnimationStateExited {
+                        entity,
+                        state_machine,
+                        state: exited.into(),
+                    });
+                }
+                commands.trigger(AnimationStateEntered {
+                    entity,
+                    state_machine,
+                    state: entered.into(),
+                });
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AnimationCondition, AnimationState, AnimationStates};
+    use crate::{graph::AnimationGraph, AnimationPlayer};
+    use bevy_asset::Handle;
+    use core::time::Duration;
+
+    #[test]
+    fn crossfade_between_states() {
+        let mut graph = AnimationGraph::new();
+        let machine = graph.add_state_machine(1.0, graph.root);
+        let idle = graph.add_clip(Handle::default(), 1.0, machine);
+        let jump = graph.add_clip(Handle::default(), 1.0, machine);
+        graph
+            .state_machine_mut(machine)
+            .unwrap()
+            .add_state(AnimationState::new("idle", idle).repeat())
+            .add_state(AnimationState::new("jump", jump))
+            .add_transition(
+                "idle",
+                "jump",
+                [AnimationCondition::trigger("jump")],
+                Duration::from_secs(1),
+            );
+
+        let mut states = AnimationStates::default();
+        let mut player = AnimationPlayer::default();
+        let mut transitions = Vec::new();
+        let mut advance = |states: &mut AnimationStates, delta| {
+            states.advance(&graph, &mut player, delta, |_, exited, entered| {
+                transitions.push((exited.map(ToString::to_string), entered.to_string()));
+            });
+        };
+
+        advance(&mut states, 0.1);
+        assert_eq!(states.current_state(machine), Some(0));
+        assert_eq!(states.node_weight(idle), 1.0);
+
+        states.set_trigger("jump");
+        advance(&mut states, 0.25);
+        assert_eq!(states.current_state(machine), Some(1));
+        assert!(!states.is_triggered("jump"));
+        assert_eq!(states.node_weight(jump), 0.25);
+        assert_eq!(states.node_weight(idle), 0.75);
+
+        advance(&mut states, 1.0);
+        assert_eq!(states.node_weight(jump), 1.0);
+        assert_eq!(states.node_weight(idle), 0.0);
+
+        assert_eq!(
+            transitions,
+            [
+                (None, "idle".to_string()),
+                (Some("idle".to_string()), "jump".to_string())
+            ]
+        );
+        assert!(!player.is_playing_animation(idle));
+        assert!(player.is_playing_animation(jump));
+    }
+}
@@ -1086,6 +1086,71 @@ impl App {
             .try_register_required_components_with::<T, R>(constructor)
     }
 
+    /// Registers the given component `R` as a [required component] for `T`, replacing the
+    /// constructor of `R` if `T` already requires it directly, for example through
+    /// `#[require(...)]`.
+    ///
+    /// This allows plugins to change the defaults of components they don't own.
+    /// For the non-panicking version, see [`App::try_override_required_components_with`].
+    ///
+    /// Note that requirements must currently be registered before `T` is inserted into the world
+    /// for the first time. Commonly, this is done in plugins. This limitation may be fixed in the future.
+    ///
+    /// [required component]: Component#required-components
+    ///
+    /// # Panics
+    ///
+    /// Panics if `R` (possibly indirectly) requires `T`, or if `T` has ever been added
+    /// on an entity before the registration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_app::App;
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component, Default, PartialEq, Eq, Debug)]
+    /// enum Faction {
+    ///     #[default]
+    ///     Neutral,
+    ///     Hostile,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// #[require(Faction)]
+    /// struct Enemy;
+    ///
+    /// # let mut app = App::new();
+    /// // Whenever `Enemy` is inserted, it will now be `Faction::Hostile` by default.
+    /// app.override_required_components_with::<Enemy, Faction>(|| Faction::Hostile);
+    /// ```
+    pub fn override_required_components_with<T: Component, R: Component>(
+        &mut self,
+        constructor: fn() -> R,
+    ) -> &mut Self {
+        self.world_mut()
+            .override_required_components_with::<T, R>(constructor);
+        self
+    }
+
+    /// Tries to register the given component `R` as a [required component] for `T`, replacing the
+    /// constructor of `R` if `T` already requires it directly.
+    ///
+    /// For the panicking version, see [`App::override_required_components_with`].
+    ///
+    /// [required component]: Component#required-components
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RequiredComponentsError`] if `R` (possibly indirectly) requires `T`, or if `T`
+    /// has ever been added on an entity before the registration.
+    pub fn try_override_required_components_with<T: Component, R: Component>(
+        &mut self,
+        constructor: fn() -> R,
+    ) -> Result<(), RequiredComponentsError> {
+        self.world_mut()
+            .try_override_required_components_with::<T, R>(constructor)
+    }
+
     /// Registers a component type as "disabling",
     /// using [default query filters](bevy_ecs::entity_disabling::DefaultQueryFilters) to exclude entities with the component from queries.
     ///
@@ -1,6 +1,7 @@
-use crate::{App, Plugin};
+use crate::{App, Plugin, PreUpdate};
 
 use alloc::string::ToString;
+use bevy_ecs::lifecycle::apply_hook_tasks;
 use bevy_platform::sync::Arc;
 use bevy_tasks::{AsyncComputeTaskPool, ComputeTaskPool, IoTaskPool, TaskPoolBuilder};
 use core::fmt::Debug;
@@ -29,12 +30,14 @@ pub struct TaskPoolPlugin {
 }
 
 impl Plugin for TaskPoolPlugin {
-    fn build(&self, _app: &mut App) {
+    fn build(&self, app: &mut App) {
         // Setup the default bevy task pools
         self.task_pool_options.create_default_pools();
 
+        app.add_systems(PreUpdate, apply_hook_tasks);
+
         #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
-        _app.add_systems(Last, tick_global_task_pools);
+        app.add_systems(Last, tick_global_task_pools);
     }
 }
 
@@ -0,0 +1,114 @@
+//! Representation of assets present in an Aseprite file
+
+use core::time::Duration;
+
+use bevy_asset::{Asset, Handle};
+use bevy_image::{Image, TextureAtlasLayout};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::TypePath;
+
+/// Representation of a loaded Aseprite file.
+#[derive(Asset, Debug, TypePath)]
+pub struct Aseprite {
+    /// The texture containing every frame of the file.
+    pub image: Handle<Image>,
+    /// The layout of the frames in [`Aseprite::image`], where the index of each frame in the file
+    /// is its index in the atlas.
+    pub layout: Handle<TextureAtlasLayout>,
+    /// The duration of each frame.
+    pub frame_durations: Vec<Duration>,
+    /// All animations loaded from the tags of the file.
+    pub animations: Vec<Handle<AsepriteAnimation>>,
+    /// The animations loaded from the tags of the file, by tag name.
+    pub named_animations: HashMap<Box<str>, Handle<AsepriteAnimation>>,
+}
+
+/// A frame of an [`AsepriteAnimation`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AsepriteAnimationFrame {
+    /// The index of the frame in the texture atlas of the file.
+    pub index: usize,
+    /// How long the frame is displayed.
+    pub duration: Duration,
+}
+
+/// An animation loaded from an Aseprite tag.
+///
+/// The frames are listed in playback order, with the direction and repeat count of the tag applied:
+/// a ping-pong tag that plays twice lists its frames forward, then backward.
+///
+/// ```
+/// # use bevy_aseprite::AsepriteAnimation;
+/// # use bevy_image::TextureAtlas;
+/// # use core::time::Duration;
+/// fn update_atlas(animation: &AsepriteAnimation, elapsed: Duration, atlas: &mut TextureAtlas) {
+///     if let Some(index) = animation.frame_at(elapsed) {
+///         atlas.index = index;
+///     }
+/// }
+/// ```
+#[derive(Asset, Clone, Debug, TypePath)]
+pub struct AsepriteAnimation {
+    /// The frames of the animation, in playback order.
+    pub frames: Vec<AsepriteAnimationFrame>,
+    /// Whether the animation starts over once its last frame has been displayed.
+    pub looping: bool,
+}
+
+impl AsepriteAnimation {
+    /// Returns the duration of a single playback of the animation.
+    pub fn duration(&self) -> Duration {
+        self.frames.iter().map(|frame| frame.duration).sum()
+    }
+
+    /// Returns whether a non-looping animation has displayed all its frames after `elapsed`.
+    pub fn is_finished(&self, elapsed: Duration) -> bool {
+        !self.looping && elapsed >= self.duration()
+    }
+
+    /// Returns the atlas index of the frame displayed after `elapsed`, or [`None`] if the animation
+    /// doesn't have any frames.
+    ///
+    /// Non-looping animations keep displaying their last frame once finished.
+    pub fn frame_at(&self, elapsed: Duration) -> Option<usize> {
+        let duration = self.duration();
+        let mut elapsed = elapsed;
+        if self.looping && !duration.is_zero() {
+            elapsed = Duration::from_nanos((elapsed.as_nanos() % duration.as_nanos()) as u64);
+        }
+        let mut end = Duration::ZERO;
+        for frame in &self.frames {
+            end += frame.duration;
+            if elapsed < end {
+                return Some(frame.index);
+            }
+        }
+        self.frames.last().map(|frame| frame.index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_at() {
+        let frame = |index, milliseconds| AsepriteAnimationFrame {
+            index,
+            duration: Duration::from_millis(milliseconds),
+        };
+        let mut animation = AsepriteAnimation {
+            frames: vec![frame(3, 100), frame(4, 50), frame(3, 100)],
+            looping: true,
+        };
+        assert_eq!(animation.duration(), Duration::from_millis(250));
+        assert_eq!(animation.frame_at(Duration::ZERO), Some(3));
+        assert_eq!(animation.frame_at(Duration::from_millis(120)), Some(4));
+        assert_eq!(animation.frame_at(Duration::from_millis(370)), Some(4));
+        assert!(!animation.is_finished(Duration::from_secs(10)));
+
+        animation.looping = false;
+        assert_eq!(animation.frame_at(Duration::from_millis(370)), Some(3));
+        assert!(animation.is_finished(Duration::from_millis(250)));
+    }
+}
@@ -0,0 +1,697 @@
+//! Parsing and compositing of Aseprite files, following the
+//! [file format specification](https://github.com/aseprite/aseprite/blob/main/docs/ase-file-specs.md).
+
+use std::io::Read;
+
+use flate2::read::ZlibDecoder;
+use thiserror::Error;
+
+/// An error that occurs when parsing an Aseprite file.
+#[derive(Error, Debug)]
+pub enum AsepriteParseError {
+    /// The contents of the file are invalid.
+    #[error("invalid Aseprite file: {0}")]
+    Invalid(String),
+    /// The file uses a color depth other than RGBA, grayscale or indexed.
+    #[error("unsupported Aseprite color depth {0}")]
+    UnsupportedColorDepth(u16),
+}
+
+fn invalid(message: impl Into<String>) -> AsepriteParseError {
+    AsepriteParseError::Invalid(message.into())
+}
+
+const HEADER_SIZE: usize = 128;
+const FILE_MAGIC: u16 = 0xA5E0;
+const FRAME_MAGIC: u16 = 0xF1FA;
+
+/// The largest width and height of a canvas, which bounds the memory used to render a frame.
+const MAX_CANVAS_SIZE: u16 = 8192;
+
+const CHUNK_OLD_PALETTE: u16 = 0x0004;
+const CHUNK_LAYER: u16 = 0x2004;
+const CHUNK_CEL: u16 = 0x2005;
+const CHUNK_TAGS: u16 = 0x2018;
+const CHUNK_PALETTE: u16 = 0x2019;
+
+/// The header flag indicating that the opacity of layers is valid.
+const FLAG_LAYER_OPACITY: u32 = 1;
+/// The layer flag indicating that the layer is visible.
+const LAYER_VISIBLE: u16 = 1;
+
+/// How the pixels of a file are stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ColorDepth {
+    Rgba,
+    Grayscale,
+    Indexed,
+}
+
+impl ColorDepth {
+    fn bytes_per_pixel(self) -> usize {
+        match self {
+            ColorDepth::Rgba => 4,
+            ColorDepth::Grayscale => 2,
+            ColorDepth::Indexed => 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Layer {
+    pub name: String,
+    pub visible: bool,
+    pub is_group: bool,
+    /// The depth of the layer in the group hierarchy, where top-level layers are at level 0.
+    pub child_level: u16,
+    pub opacity: u8,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum CelContent {
+    Image {
+        width: u16,
+        height: u16,
+        /// The pixels of the image, in the color depth of the file.
+        pixels: Vec<u8>,
+    },
+    /// The cel reuses the cel of the same layer in another frame.
+    Linked(u16),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Cel {
+    pub layer: usize,
+    pub x: i16,
+    pub y: i16,
+    pub opacity: u8,
+    /// Moves the cel in front of (or behind) the cels of other layers.
+    pub z_index: i16,
+    pub content: CelContent,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Frame {
+    /// The duration of the frame in milliseconds.
+    pub duration: u16,
+    pub cels: Vec<Cel>,
+}
+
+/// The direction in which the frames of a [`Tag`] are played.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Direction {
+    Forward,
+    Reverse,
+    PingPong,
+    PingPongReverse,
+}
+
+/// A named range of frames, which is loaded as an animation.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Tag {
+    pub name: String,
+    pub from: u16,
+    pub to: u16,
+    pub direction: Direction,
+    /// How many times the frames are played, where `0` means forever.
+    pub repeat: u16,
+}
+
+/// A parsed Aseprite file.
+#[derive(Clone, Debug)]
+pub(crate) struct AsepriteFile {
+    pub width: u16,
+    pub height: u16,
+    pub color_depth: ColorDepth,
+    /// The palette index of transparent pixels in indexed files.
+    pub transparent_index: u8,
+    /// Whether the opacity of layers is valid, which is the case for files saved by recent versions.
+    pub layer_opacity: bool,
+    pub layers: Vec<Layer>,
+    pub frames: Vec<Frame>,
+    pub tags: Vec<Tag>,
+    /// The colors of indexed files, as RGBA.
+    pub palette: Vec<[u8; 4]>,
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, count: usize) -> Result<&'a [u8], AsepriteParseError> {
+        let bytes = self
+            .position
+            .checked_add(count)
+            .and_then(|end| self.bytes.get(self.position..end))
+            .ok_or_else(|| invalid("unexpected end of file"))?;
+        self.position += count;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, count: usize) -> Result<(), AsepriteParseError> {
+        self.bytes(count).map(|_| ())
+    }
+
+    fn byte(&mut self) -> Result<u8, AsepriteParseError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn word(&mut self) -> Result<u16, AsepriteParseError> {
+        Ok(u16::from_le_bytes(self.bytes(2)?.try_into().unwrap()))
+    }
+
+    fn short(&mut self) -> Result<i16, AsepriteParseError> {
+        Ok(i16::from_le_bytes(self.bytes(2)?.try_into().unwrap()))
+    }
+
+    fn dword(&mut self) -> Result<u32, AsepriteParseError> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn string(&mut self) -> Result<String, AsepriteParseError> {
+        let length = self.word()? as usize;
+        Ok(String::from_utf8_lossy(self.bytes(length)?).into_owned())
+    }
+}
+
+/// Parses the bytes of an Aseprite file.
+pub(crate) fn parse(bytes: &[u8]) -> Result<AsepriteFile, AsepriteParseError> {
+    let mut header = Reader { bytes, position: 0 };
+    let _file_size = header.dword()?;
+    if header.word()? != FILE_MAGIC {
+        return Err(invalid("not an Aseprite file"));
+    }
+    let frame_count = header.word()?;
+    let width = header.word()?;
+    let height = header.word()?;
+    let color_depth = match header.word()? {
+        32 => ColorDepth::Rgba,
+        16 => ColorDepth::Grayscale,
+        8 => ColorDepth::Indexed,
+        depth => return Err(AsepriteParseError::UnsupportedColorDepth(depth)),
+    };
+    if width == 0 || height == 0 {
+        return Err(invalid("empty canvas"));
+    }
+    if width > MAX_CANVAS_SIZE || height > MAX_CANVAS_SIZE {
+        return Err(invalid(format!(
+            "canvas of {width}x{height} pixels is larger than {MAX_CANVAS_SIZE}x{MAX_CANVAS_SIZE}"
+        )));
+    }
+    let flags = header.dword()?;
+    // The deprecated speed and two reserved fields.
+    header.skip(10)?;
+    let transparent_index = header.byte()?;
+
+    let mut file = AsepriteFile {
+        width,
+        height,
+        color_depth,
+        transparent_index,
+        layer_opacity: flags & FLAG_LAYER_OPACITY != 0,
+        layers: Vec::new(),
+        frames: Vec::new(),
+        tags: Vec::new(),
+        palette: Vec::new(),
+    };
+    let mut old_palette = Vec::new();
+    let mut reader = Reader {
+        bytes,
+        position: HEADER_SIZE,
+    };
+    for _ in 0..frame_count {
+        let frame_start = reader.position;
+        let frame_end = frame_start
+            .checked_add(reader.dword()? as usize)
+            .filter(|&end| end <= bytes.len())
+            .ok_or_else(|| invalid("frame larger than the file"))?;
+        if reader.word()? != FRAME_MAGIC {
+            return Err(invalid("invalid frame magic number"));
+        }
+        let old_chunk_count = reader.word()?;
+        let duration = reader.word()?;
+        reader.skip(2)?;
+        let chunk_count = match reader.dword()? {
+            0 => old_chunk_count as u32,
+            count => count,
+        };
+
+        let mut frame = Frame {
+            duration,
+            cels: Vec::new(),
+        };
+        for _ in 0..chunk_count {
+            let chunk_start = reader.position;
+            let chunk_end = chunk_start
+                .checked_add(reader.dword()? as usize)
+                .filter(|&end| end <= frame_end && end >= chunk_start + 6)
+                .ok_or_else(|| invalid("invalid chunk size"))?;
+            let kind = reader.word()?;
+            // Chunks can't read past their end.
+            let mut chunk = Reader {
+                bytes: &bytes[..chunk_end],
+                position: reader.position,
+            };
+            match kind {
+                CHUNK_LAYER => file.layers.push(chunk.layer()?),
+                CHUNK_CEL => {
+                    if let Some(cel) = chunk.cel(color_depth, width as usize * height as usize)? {
+                        frame.cels.push(cel);
+                    }
+                }
+                CHUNK_TAGS => file.tags.extend(chunk.tags()?),
+                CHUNK_PALETTE => chunk.palette(&mut file.palette)?,
+                CHUNK_OLD_PALETTE => chunk.old_palette(&mut old_palette)?,
+                // Other chunks, such as slices and user data, are ignored.
+                _ => {}
+            }
+            reader.position = chunk_end;
+        }
+        reader.position = frame_end;
+        file.frames.push(frame);
+    }
+    // Old palette chunks are only used by files that don't have a new one.
+    if file.palette.is_empty() {
+        file.palette = old_palette;
+    }
+    Ok(file)
+}
+
+impl Reader<'_> {
+    fn layer(&mut self) -> Result<Layer, AsepriteParseError> {
+        let flags = self.word()?;
+        let kind = self.word()?;
+        let child_level = self.word()?;
+        // The default width and height, and the blend mode.
+        self.skip(6)?;
+        let opacity = self.byte()?;
+        self.skip(3)?;
+        let name = self.string()?;
+        Ok(Layer {
+            name,
+            visible: flags & LAYER_VISIBLE != 0,
+            is_group: kind == 1,
+            child_level,
+            opacity,
+        })
+    }
+
+    /// Reads a cel, or returns [`None`] for tilemap cels, which aren't supported.
+    ///
+    /// Cels with more pixels than the canvas are rejected.
+    fn cel(
+        &mut self,
+        color_depth: ColorDepth,
+        canvas_pixels: usize,
+    ) -> Result<Option<Cel>, AsepriteParseError> {
+        let layer = self.word()? as usize;
+        let x = self.short()?;
+        let y = self.short()?;
+        let opacity = self.byte()?;
+        let kind = self.word()?;
+        let z_index = self.short()?;
+        self.skip(5)?;
+        let content = match kind {
+            0 | 2 => {
+                let cel_width = self.word()?;
+                let cel_height = self.word()?;
+                let cel_pixels = cel_width as usize * cel_height as usize;
+                if cel_pixels > canvas_pixels {
+                    return Err(invalid("cel larger than the canvas"));
+                }
+                let length = cel_pixels * color_depth.bytes_per_pixel();
+                let pixels = if kind == 0 {
+                    self.bytes(length)?.to_vec()
+                } else {
+                    let mut pixels = Vec::new();
+                    ZlibDecoder::new(&self.bytes[self.position..])
+                        .take(length as u64)
+                        .read_to_end(&mut pixels)
+                        .map_err(|err| invalid(format!("invalid compressed cel: {err}")))?;
+                    if pixels.len() != length {
+                        return Err(invalid("compressed cel smaller than its size"));
+                    }
+                    pixels
+                };
+                CelContent::Image {
+                    width: cel_width,
+                    height: cel_height,
+                    pixels,
+                }
+            }
+            1 => CelContent::Linked(self.word()?),
+            _ => return Ok(None),
+        };
+        Ok(Some(Cel {
+            layer,
+            x,
+            y,
+            opacity,
+            z_index,
+            content,
+        }))
+    }
+
+    fn tags(&mut self) -> Result<Vec<Tag>, AsepriteParseError> {
+        let count = self.word()?;
+        self.skip(8)?;
+        let mut tags = Vec::new();
+        for _ in 0..count {
+            let from = self.word()?;
+            let to = self.word()?;
+            let direction = match self.byte()? {
+                1 => Direction::Reverse,
+                2 => Direction::PingPong,
+                3 => Direction::PingPongReverse,
+                _ => Direction::Forward,
+            };
+            let repeat = self.word()?;
+            // Reserved bytes and the deprecated color.
+            self.skip(10)?;
+            let name = self.string()?;
+            tags.push(Tag {
+                name,
+                from,
+                to,
+                direction,
+                repeat,
+            });
+        }
+        Ok(tags)
+    }
+
+    fn palette(&mut self, palette: &mut Vec<[u8; 4]>) -> Result<(), AsepriteParseError> {
+        let size = self.dword()? as usize;
+        let first = self.dword()? as usize;
+        let last = self.dword()? as usize;
+        self.skip(8)?;
+        if first > last || last >= size {
+            return Err(invalid("invalid palette range"));
+        }
+        // Pixels are indexed by bytes, so larger palettes can't be used.
+        palette.resize(size.min(256), [0; 4]);
+        for index in first..=last {
+            let flags = self.word()?;
+            let color = self.bytes(4)?.try_into().unwrap();
+            if flags & 1 != 0 {
+                self.string()?;
+            }
+            if let Some(entry) = palette.get_mut(index) {
+                *entry = color;
+            }
+        }
+        Ok(())
+    }
+
+    fn old_palette(&mut self, palette: &mut Vec<[u8; 4]>) -> Result<(), AsepriteParseError> {
+        let packets = self.word()?;
+        let mut index = 0;
+        for _ in 0..packets {
+            index += self.byte()? as usize;
+            let count = match self.byte()? {
+                0 => 256,
+                count => count as usize,
+            };
+            for _ in 0..count {
+                let [r, g, b] = self.bytes(3)?.try_into().unwrap();
+                if index < 256 {
+                    if palette.len() <= index {
+                        palette.resize(index + 1, [0; 4]);
+                    }
+                    palette[index] = [r, g, b, 255];
+                }
+                index += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl AsepriteFile {
+    /// Returns whether each layer is visible, taking the visibility of its groups into account.
+    fn visible_layers(&self) -> Vec<bool> {
+        let mut groups: Vec<(u16, bool)> = Vec::new();
+        self.layers
+            .iter()
+            .map(|layer| {
+                while groups
+                    .last()
+                    .is_some_and(|(level, _)| *level >= layer.child_level)
+                {
+                    groups.pop();
+                }
+                let visible = layer.visible && groups.last().is_none_or(|(_, visible)| *visible);
+                groups.push((layer.child_level, visible));
+                visible
+            })
+            .collect()
+    }
+
+    /// Converts a pixel in the color depth of the file to RGBA.
+    fn rgba(&self, pixel: &[u8]) -> [u8; 4] {
+        match self.color_depth {
+            ColorDepth::Rgba => pixel.try_into().unwrap(),
+            ColorDepth::Grayscale => [pixel[0], pixel[0], pixel[0], pixel[1]],
+            ColorDepth::Indexed if pixel[0] == self.transparent_index => [0; 4],
+            ColorDepth::Indexed => self
+                .palette
+                .get(pixel[0] as usize)
+                .copied()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Composites the visible cels of a frame, returning the RGBA pixels of the canvas.
+    ///
+    /// All layers are blended with the normal blend mode.
+    pub(crate) fn render_frame(&self, frame: usize, include_hidden_layers: bool) -> Vec<u8> {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let mut canvas = vec![0; width * height * 4];
+        let visible_layers = self.visible_layers();
+
+        let mut cels = self.frames[frame]
+            .cels
+            .iter()
+            .filter_map(|cel| {
+                let layer = self.layers.get(cel.layer)?;
+                if layer.is_group || !(include_hidden_layers || visible_layers[cel.layer]) {
+                    return None;
+                }
+                // Linked cels use the image and position of the cel they link to.
+                let image = match &cel.content {
+                    CelContent::Linked(frame) => self
+                        .frames
+                        .get(*frame as usize)?
+                        .cels
+                        .iter()
+                        .find(|linked| linked.layer == cel.layer)?,
+                    CelContent::Image { .. } => cel,
+                };
+                let opacity = if self.layer_opacity {
+                    cel.opacity as u32 * layer.opacity as u32 / 255
+                } else {
+                    cel.opacity as u32
+                };
+                Some((image, opacity as f32 / 255.0))
+            })
+            .collect::<Vec<_>>();
+        // Cels are ordered by layer, offset by their z-index, and ties are broken by the z-index.
+        cels.sort_by_key(|(cel, _)| (cel.layer as i64 + cel.z_index as i64, cel.z_index));
+
+        let bytes_per_pixel = self.color_depth.bytes_per_pixel();
+        for (cel, opacity) in cels {
+            let CelContent::Image {
+                width: cel_width,
+                height: cel_height,
+                pixels,
+            } = &cel.content
+            else {
+                continue;
+            };
+            for row in 0..*cel_height as usize {
+                let y = cel.y as isize + row as isize;
+                if y < 0 || y >= height as isize {
+                    continue;
+                }
+                for column in 0..*cel_width as usize {
+                    let x = cel.x as isize + column as isize;
+                    if x < 0 || x >= width as isize {
+                        continue;
+                    }
+                    let source = (row * *cel_width as usize + column) * bytes_per_pixel;
+                    let source = self.rgba(&pixels[source..source + bytes_per_pixel]);
+                    let target = (y as usize * width + x as usize) * 4;
+                    blend(&mut canvas[target..target + 4], source, opacity);
+                }
+            }
+        }
+        canvas
+    }
+}
+
+/// Blends `source` over `target` with the normal blend mode, where colors aren't premultiplied.
+fn blend(target: &mut [u8], source: [u8; 4], opacity: f32) {
+    let source_alpha = source[3] as f32 / 255.0 * opacity;
+    if source_alpha <= 0.0 {
+        return;
+    }
+    let target_alpha = target[3] as f32 / 255.0;
+    let alpha = source_alpha + target_alpha * (1.0 - source_alpha);
+    for channel in 0..3 {
+        let color = (source[channel] as f32 * source_alpha
+            + target[channel] as f32 * target_alpha * (1.0 - source_alpha))
+            / alpha;
+        target[channel] = color.round() as u8;
+    }
+    target[3] = (alpha * 255.0).round() as u8;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{write::ZlibEncoder, Compression};
+
+    use super::*;
+
+    fn chunk(kind: u16, data: &[u8]) -> Vec<u8> {
+        let mut chunk = ((data.len() + 6) as u32).to_le_bytes().to_vec();
+        chunk.extend_from_slice(&kind.to_le_bytes());
+        chunk.extend_from_slice(data);
+        chunk
+    }
+
+    fn string(name: &str) -> Vec<u8> {
+        let mut bytes = (name.len() as u16).to_le_bytes().to_vec();
+        bytes.extend_from_slice(name.as_bytes());
+        bytes
+    }
+
+    fn layer(name: &str, flags: u16, opacity: u8) -> Vec<u8> {
+        let mut data = flags.to_le_bytes().to_vec();
+        data.extend_from_slice(&[0; 10]);
+        data.extend_from_slice(&[opacity, 0, 0, 0]);
+        data.extend_from_slice(&string(name));
+        chunk(CHUNK_LAYER, &data)
+    }
+
+    /// A cel at the origin of `layer`, whose content follows its header.
+    fn cel(layer: u16, kind: u16, content: &[u8]) -> Vec<u8> {
+        let mut data = layer.to_le_bytes().to_vec();
+        data.extend_from_slice(&[0, 0, 0, 0, 255]);
+        data.extend_from_slice(&kind.to_le_bytes());
+        data.extend_from_slice(&[0; 7]);
+        data.extend_from_slice(content);
+        chunk(CHUNK_CEL, &data)
+    }
+
+    fn frame(duration: u16, chunks: &[Vec<u8>]) -> Vec<u8> {
+        let data = chunks.concat();
+        let mut frame = ((data.len() + 16) as u32).to_le_bytes().to_vec();
+        frame.extend_from_slice(&FRAME_MAGIC.to_le_bytes());
+        frame.extend_from_slice(&0u16.to_le_bytes());
+        frame.extend_from_slice(&duration.to_le_bytes());
+        frame.extend_from_slice(&[0, 0]);
+        frame.extend_from_slice(&(chunks.len() as u32).to_le_bytes());
+        frame.extend_from_slice(&data);
+        frame
+    }
+
+    /// The header of an RGBA file.
+    fn header(frame_count: u16, width: u16, height: u16) -> Vec<u8> {
+        let mut bytes = vec![0; HEADER_SIZE];
+        bytes[4..6].copy_from_slice(&FILE_MAGIC.to_le_bytes());
+        bytes[6..8].copy_from_slice(&frame_count.to_le_bytes());
+        bytes[8..10].copy_from_slice(&width.to_le_bytes());
+        bytes[10..12].copy_from_slice(&height.to_le_bytes());
+        bytes[12..14].copy_from_slice(&32u16.to_le_bytes());
+        bytes[14..18].copy_from_slice(&FLAG_LAYER_OPACITY.to_le_bytes());
+        bytes
+    }
+
+    /// The content of a compressed cel.
+    fn compressed(width: u16, height: u16, pixels: &[u8]) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(pixels).unwrap();
+        let mut content = [width.to_le_bytes(), height.to_le_bytes()].concat();
+        content.extend_from_slice(&encoder.finish().unwrap());
+        content
+    }
+
+    #[test]
+    fn parse_and_render() {
+        let mut bytes = header(2, 2, 1);
+
+        // A red pixel and a transparent one, compressed.
+        let compressed = compressed(2, 1, &[255, 0, 0, 255, 0, 0, 0, 0]);
+        // A half-transparent blue pixel over both.
+        let mut raw = [2u16.to_le_bytes(), 1u16.to_le_bytes()].concat();
+        raw.extend_from_slice(&[0, 0, 255, 255, 0, 0, 255, 255]);
+
+        let mut tags = 1u16.to_le_bytes().to_vec();
+        tags.extend_from_slice(&[0; 8]);
+        tags.extend_from_slice(&[0, 0, 1, 0, 2, 0, 0]);
+        tags.extend_from_slice(&[0; 10]);
+        tags.extend_from_slice(&string("idle"));
+
+        bytes.extend(frame(
+            100,
+            &[
+                layer("Background", LAYER_VISIBLE, 255),
+                layer("Hidden", 0, 255),
+                layer("Blue", LAYER_VISIBLE, 128),
+                chunk(CHUNK_TAGS, &tags),
+                cel(0, 2, &compressed),
+                cel(1, 0, &raw),
+                cel(2, 0, &raw),
+            ],
+        ));
+        bytes.extend(frame(50, &[cel(0, 1, &0u16.to_le_bytes())]));
+
+        let file = parse(&bytes).unwrap();
+        assert_eq!((file.width, file.height), (2, 1));
+        assert_eq!(file.layers.len(), 3);
+        assert_eq!(file.frames[1].duration, 50);
+        assert_eq!(
+            file.tags,
+            [Tag {
+                name: "idle".into(),
+                from: 0,
+                to: 1,
+                direction: Direction::PingPong,
+                repeat: 0,
+            }]
+        );
+
+        assert_eq!(
+            file.render_frame(0, false),
+            [127, 0, 128, 255, 0, 0, 255, 128]
+        );
+        // The second frame links to the background of the first one.
+        assert_eq!(file.render_frame(1, false), [255, 0, 0, 255, 0, 0, 0, 0]);
+        assert_eq!(file.render_frame(1, true), file.render_frame(1, false));
+        assert_eq!(file.render_frame(0, true)[0..4], [0, 0, 255, 255]);
+
+        assert!(parse(&bytes[..bytes.len() - 10]).is_err());
+    }
+    #[test]
+    fn reject_oversized_dimensions() {
+        assert!(matches!(
+            parse(&header(0, u16::MAX, u16::MAX)),
+            Err(AsepriteParseError::Invalid(_))
+        ));
+
+        // A compressed cel with more pixels than the canvas.
+        let mut bytes = header(1, 2, 1);
+        bytes.extend(frame(
+            100,
+            &[
+                layer("Background", LAYER_VISIBLE, 255),
+                cel(0, 2, &compressed(3, 1, &[255; 12])),
+            ],
+        ));
+        assert!(matches!(parse(&bytes), Err(AsepriteParseError::Invalid(_))));
+    }
+}
@@ -0,0 +1,55 @@
+//! Labels that can be used to load part of an Aseprite file
+
+use bevy_asset::AssetPath;
+
+/// Labels that can be used to load part of an Aseprite file
+///
+/// You can use [`AsepriteAssetLabel::from_asset`] to add it to an asset path
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_asset::prelude::*;
+/// # use bevy_image::Image;
+/// # use bevy_aseprite::prelude::*;
+///
+/// fn load_aseprite_image(asset_server: Res<AssetServer>) {
+///     let image: Handle<Image> = asset_server.load(AsepriteAssetLabel::Image.from_asset("sprites/player.aseprite"));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsepriteAssetLabel {
+    /// `Image`: the frames of the file as a Bevy [`Image`](bevy_image::Image)
+    Image,
+    /// `Layout`: the position of each frame in the image as a Bevy
+    /// [`TextureAtlasLayout`](bevy_image::TextureAtlasLayout)
+    Layout,
+    /// `Animation{}`: Aseprite tag as an [`AsepriteAnimation`](crate::AsepriteAnimation)
+    Animation(usize),
+}
+
+impl core::fmt::Display for AsepriteAssetLabel {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AsepriteAssetLabel::Image => f.write_str("Image"),
+            AsepriteAssetLabel::Layout => f.write_str("Layout"),
+            AsepriteAssetLabel::Animation(index) => f.write_str(&format!("Animation{index}")),
+        }
+    }
+}
+
+impl AsepriteAssetLabel {
+    /// Add this label to an asset path
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_asset::prelude::*;
+    /// # use bevy_aseprite::prelude::*;
+    ///
+    /// fn load_aseprite_animation(asset_server: Res<AssetServer>) {
+    ///     let run: Handle<AsepriteAnimation> = asset_server.load(AsepriteAssetLabel::Animation(0).from_asset("sprites/player.aseprite"));
+    /// }
+    /// ```
+    pub fn from_asset(&self, path: impl Into<AssetPath<'static>>) -> AssetPath<'static> {
+        path.into().with_label(self.to_string())
+    }
+}
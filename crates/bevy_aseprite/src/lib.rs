@@ -0,0 +1,78 @@
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![forbid(unsafe_code)]
+#![doc(
+    html_logo_url = "https://bevy.org/assets/icon.png",
+    html_favicon_url = "https://bevy.org/assets/icon.png"
+)]
+
+//! Plugin providing an [`AssetLoader`](bevy_asset::AssetLoader) and type definitions
+//! for loading [Aseprite](https://www.aseprite.org) files in Bevy.
+//!
+//! Loading a `.aseprite` or `.ase` file composites the visible layers of each frame into a texture
+//! atlas, so sprite sheets don't need to be exported from Aseprite first.
+//!
+//! # Quick Start
+//!
+//! Here's how to spawn a sprite that displays the first frame of a file
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_asset::prelude::*;
+//! # use bevy_image::{Image, TextureAtlas, TextureAtlasLayout};
+//! # use bevy_aseprite::prelude::*;
+//!
+//! fn load_player(asset_server: Res<AssetServer>) {
+//!     let path = "sprites/player.aseprite";
+//!     let image: Handle<Image> = asset_server.load(AsepriteAssetLabel::Image.from_asset(path));
+//!     let layout: Handle<TextureAtlasLayout> =
+//!         asset_server.load(AsepriteAssetLabel::Layout.from_asset(path));
+//!     // `Sprite::from_atlas_image(image, TextureAtlas { layout, index: 0 })` displays the first frame.
+//! }
+//! ```
+//!
+//! Each tag of the file is loaded as an [`AsepriteAnimation`], which lists the atlas index and
+//! duration of each frame in playback order. The animations are also available by tag name in
+//! [`Aseprite::named_animations`].
+//!
+//! # Supported features
+//!
+//! RGBA, grayscale and indexed files are supported, along with layer groups, hidden layers, layer
+//! and cel opacity, linked cels and cel z-indices. All layers are blended with the normal blend
+//! mode, and tilemap layers, slices and user data are ignored.
+
+mod assets;
+mod file;
+mod label;
+mod loader;
+
+use bevy_app::prelude::*;
+use bevy_asset::AssetApp;
+use bevy_image::TextureAtlasPlugin;
+
+/// The Aseprite prelude.
+///
+/// This includes the most common types in this crate, re-exported for your convenience.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{
+        assets::{Aseprite, AsepriteAnimation},
+        label::AsepriteAssetLabel,
+    };
+}
+
+pub use {assets::*, file::AsepriteParseError, label::AsepriteAssetLabel, loader::*};
+
+/// Adds support for Aseprite file loading to the app.
+#[derive(Default)]
+pub struct AsepritePlugin;
+
+impl Plugin for AsepritePlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<TextureAtlasPlugin>() {
+            app.add_plugins(TextureAtlasPlugin);
+        }
+        app.init_asset::<Aseprite>()
+            .init_asset::<AsepriteAnimation>()
+            .init_asset_loader::<AsepriteLoader>();
+    }
+}
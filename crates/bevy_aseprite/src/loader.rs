@@ -0,0 +1,251 @@
+use core::time::Duration;
+
+use bevy_asset::{io::Reader, AssetLoader, LoadContext, RenderAssetUsages};
+use bevy_image::{Image, ImageSampler, TextureAtlasLayout};
+use bevy_math::UVec2;
+use bevy_platform::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::warn;
+use wgpu_types::{Extent3d, TextureDimension, TextureFormat};
+
+use crate::{
+    file::{parse, AsepriteParseError, Direction, Tag},
+    Aseprite, AsepriteAnimation, AsepriteAnimationFrame, AsepriteAssetLabel,
+};
+
+/// An error that occurs when loading an Aseprite file.
+#[derive(Error, Debug)]
+pub enum AsepriteError {
+    /// Failed to read the file.
+    #[error("failed to read Aseprite file: {0}")]
+    Io(#[from] std::io::Error),
+    /// Failed to parse the file.
+    #[error(transparent)]
+    Parse(#[from] AsepriteParseError),
+    /// The texture atlas of the frames of the file would be too large.
+    #[error("texture atlas of {}x{} pixels is too large", .0.x, .0.y)]
+    AtlasTooLarge(UVec2),
+}
+
+/// The largest number of pixels in the texture atlas of a file.
+const MAX_ATLAS_PIXELS: u64 = 1 << 28;
+
+/// Loads Aseprite files (`.aseprite` and `.ase`) as an [`Aseprite`] asset.
+///
+/// The frames of the file are composited into a single texture atlas, and each tag is loaded as an
+/// [`AsepriteAnimation`].
+#[derive(Default)]
+pub struct AsepriteLoader;
+
+/// Specifies optional settings for processing Aseprite files at load time.
+///
+/// To use, load the asset with [`AssetServer::load_with_settings`](bevy_asset::AssetServer::load_with_settings):
+///
+/// ```no_run
+/// # use bevy_asset::{AssetServer, Handle};
+/// # use bevy_aseprite::*;
+/// # use bevy_image::ImageSampler;
+/// # let asset_server: AssetServer = panic!();
+/// let aseprite_handle: Handle<Aseprite> = asset_server.load_with_settings(
+///     "sprites/player.aseprite",
+///     |s: &mut AsepriteLoaderSettings| {
+///         s.sampler = ImageSampler::nearest();
+///     },
+/// );
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AsepriteLoaderSettings {
+    /// The sampler of the texture atlas.
+    pub sampler: ImageSampler,
+    /// Where the texture atlas is retained.
+    pub asset_usage: RenderAssetUsages,
+    /// If true, hidden layers are composited into the frames as well.
+    pub include_hidden_layers: bool,
+    /// The number of transparent pixels between frames in the texture atlas, which prevents
+    /// neighboring frames from bleeding into each other when filtered.
+    pub padding: u32,
+}
+
+impl Default for AsepriteLoaderSettings {
+    fn default() -> Self {
+        Self {
+            sampler: ImageSampler::Default,
+            asset_usage: RenderAssetUsages::default(),
+            include_hidden_layers: false,
+            padding: 0,
+        }
+    }
+}
+
+impl AssetLoader for AsepriteLoader {
+    type Asset = Aseprite;
+    type Settings = AsepriteLoaderSettings;
+    type Error = AsepriteError;
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        settings: &AsepriteLoaderSettings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Aseprite, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file = parse(&bytes)?;
+
+        // Frames are laid out in a grid that is roughly square.
+        let frame_count = file.frames.len() as u32;
+        let columns = frame_count.max(1).isqrt();
+        let columns = if columns * columns < frame_count {
+            columns + 1
+        } else {
+            columns
+        };
+        let rows = frame_count.div_ceil(columns).max(1);
+        let frame_size = UVec2::new(file.width as u32, file.height as u32);
+        let mut layout = TextureAtlasLayout::from_grid(
+            frame_size,
+            columns,
+            rows,
+            Some(UVec2::splat(settings.padding)),
+            None,
+        );
+        layout.textures.truncate(file.frames.len());
+
+        if layout.size.x as u64 * layout.size.y as u64 > MAX_ATLAS_PIXELS {
+            return Err(AsepriteError::AtlasTooLarge(layout.size));
+        }
+
+        let atlas_width = layout.size.x as usize;
+        let mut data = vec![0; atlas_width * layout.size.y as usize * 4];
+        let row_length = file.width as usize * 4;
+        for (frame, rect) in layout.textures.iter().enumerate() {
+            let pixels = file.render_frame(frame, settings.include_hidden_layers);
+            for (row, pixels) in pixels.chunks_exact(row_length).enumerate() {
+                let start = ((rect.min.y as usize + row) * atlas_width + rect.min.x as usize) * 4;
+                data[start..start + row_length].copy_from_slice(pixels);
+            }
+        }
+        let mut image = Image::new(
+            Extent3d {
+                width: layout.size.x,
+                height: layout.size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            settings.asset_usage,
+        );
+        image.sampler = settings.sampler.clone();
+
+        let frame_durations = file
+            .frames
+            .iter()
+            .map(|frame| Duration::from_millis(frame.duration as u64))
+            .collect::<Vec<_>>();
+        let mut animations = Vec::new();
+        let mut named_animations = HashMap::default();
+        for tag in &file.tags {
+            if tag.from > tag.to || tag.to as usize >= frame_durations.len() {
+                warn!(
+                    "Skipping tag {} of {}, whose frames are out of range",
+                    tag.name,
+                    load_context.path()
+                );
+                continue;
+            }
+            let (frames, looping) = tag_frames(tag);
+            let animation = AsepriteAnimation {
+                frames: frames
+                    .into_iter()
+                    .map(|index| AsepriteAnimationFrame {
+                        index,
+                        duration: frame_durations[index],
+                    })
+                    .collect(),
+                looping,
+            };
+            let label = AsepriteAssetLabel::Animation(animations.len());
+            let handle = load_context.add_labeled_asset(label.to_string(), animation);
+            named_animations.insert(tag.name.as_str().into(), handle.clone());
+            animations.push(handle);
+        }
+
+        Ok(Aseprite {
+            image: load_context.add_labeled_asset(AsepriteAssetLabel::Image.to_string(), image),
+            layout: load_context.add_labeled_asset(AsepriteAssetLabel::Layout.to_string(), layout),
+            frame_durations,
+            animations,
+            named_animations,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["aseprite", "ase"]
+    }
+}
+
+/// Returns the frames played by a tag in order, and whether they loop.
+///
+/// Tags that repeat forever play a single cycle in a loop, while tags with a repeat count play each
+/// pass once. Consecutive ping-pong passes share the frame they turn around at.
+fn tag_frames(tag: &Tag) -> (Vec<usize>, bool) {
+    let forward = (tag.from as usize..=tag.to as usize).collect::<Vec<_>>();
+    let backward = forward.iter().rev().copied().collect::<Vec<_>>();
+    let (first, second, ping_pong) = match tag.direction {
+        Direction::Forward => (&forward, &forward, false),
+        Direction::Reverse => (&backward, &backward, false),
+        Direction::PingPong => (&forward, &backward, true),
+        Direction::PingPongReverse => (&backward, &forward, true),
+    };
+    let mut frames = first.clone();
+    if tag.repeat == 0 {
+        // A ping-pong cycle doesn't repeat the frames it turns around at.
+        if ping_pong && second.len() > 2 {
+            frames.extend_from_slice(&second[1..second.len() - 1]);
+        }
+        return (frames, true);
+    }
+    for pass in 1..tag.repeat {
+        let frames_of_pass = if pass % 2 == 1 { second } else { first };
+        let skip = if ping_pong { 1 } else { 0 };
+        frames.extend_from_slice(&frames_of_pass[skip.min(frames_of_pass.len())..]);
+    }
+    (frames, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_frames_follow_direction_and_repeat() {
+        let tag = |direction, repeat| Tag {
+            name: "run".into(),
+            from: 2,
+            to: 4,
+            direction,
+            repeat,
+        };
+        assert_eq!(
+            tag_frames(&tag(Direction::Forward, 0)),
+            (vec![2, 3, 4], true)
+        );
+        assert_eq!(
+            tag_frames(&tag(Direction::Reverse, 0)),
+            (vec![4, 3, 2], true)
+        );
+        assert_eq!(
+            tag_frames(&tag(Direction::PingPong, 0)),
+            (vec![2, 3, 4, 3], true)
+        );
+        assert_eq!(
+            tag_frames(&tag(Direction::Forward, 2)),
+            (vec![2, 3, 4, 2, 3, 4], false)
+        );
+        assert_eq!(
+            tag_frames(&tag(Direction::PingPongReverse, 3)),
+            (vec![4, 3, 2, 3, 4, 3, 2], false)
+        );
+    }
+}
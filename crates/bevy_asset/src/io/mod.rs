@@ -12,10 +12,13 @@ pub mod embedded;
 pub mod file;
 pub mod memory;
 pub mod processor_gated;
+pub mod throttled;
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
 #[cfg(any(feature = "http", feature = "https"))]
 pub mod web;
+#[cfg(feature = "zip")]
+pub mod zip;
 
 #[cfg(test)]
 pub mod gated;
@@ -1,5 +1,9 @@
 use crate::{
-    io::{processor_gated::ProcessorGatedReader, AssetSourceEvent, AssetWatcher},
+    io::{
+        processor_gated::ProcessorGatedReader,
+        throttled::{Throttle, ThrottledAssetReader},
+        AssetSourceEvent, AssetWatcher,
+    },
     processor::ProcessingState,
 };
 use alloc::{
@@ -146,6 +150,8 @@ pub struct AssetSourceBuilder {
     pub watch_warning: Option<&'static str>,
     /// The warning message to display when watching a processed asset fails.
     pub processed_watch_warning: Option<&'static str>,
+    /// The maximum average number of bytes per second read from this source, if any.
+    pub bandwidth_limit: Option<u64>,
 }
 
 impl AssetSourceBuilder {
@@ -162,6 +168,7 @@ impl AssetSourceBuilder {
             processed_watcher: None,
             watch_warning: None,
             processed_watch_warning: None,
+            bandwidth_limit: None,
         }
     }
 
@@ -173,18 +180,29 @@ impl AssetSourceBuilder {
         watch: bool,
         watch_processed: bool,
     ) -> AssetSource {
-        let reader = self.reader.as_mut()();
+        let mut reader = self.reader.as_mut()();
         let writer = self.writer.as_mut().and_then(|w| w(false));
         let processed_writer = self.processed_writer.as_mut().and_then(|w| w(true));
+        let mut processed_reader = self.processed_reader.as_mut().map(|r| r());
+        if let Some(bandwidth_limit) = self.bandwidth_limit {
+            // The unprocessed and processed readers share the bandwidth of the source.
+            let throttle = Arc::new(Throttle::new(bandwidth_limit));
+            reader = Box::new(ThrottledAssetReader::with_throttle(
+                reader,
+                throttle.clone(),
+            ));
+            processed_reader = processed_reader.map(|processed_reader| {
+                Box::new(ThrottledAssetReader::with_throttle(
+                    processed_reader,
+                    throttle,
+                )) as Box<dyn ErasedAssetReader>
+            });
+        }
         let mut source = AssetSource {
             id: id.clone(),
             reader,
             writer,
-            processed_reader: self
-                .processed_reader
-                .as_mut()
-                .map(|r| r())
-                .map(Into::<Arc<_>>::into),
+            processed_reader: processed_reader.map(Into::<Arc<_>>::into),
             ungated_processed_reader: None,
             processed_writer,
             event_receiver: None,
@@ -297,6 +315,15 @@ impl AssetSourceBuilder {
         self
     }
 
+    /// Limits the rate at which bytes are read from this source to `bytes_per_second` on average. This is mostly
+    /// useful for network sources, to keep background loads from saturating the player's connection.
+    ///
+    /// Reads that exceed the limit are delayed. Up to one second worth of bytes can be read at once.
+    pub fn with_bandwidth_limit(mut self, bytes_per_second: u64) -> Self {
+        self.bandwidth_limit = Some(bytes_per_second);
+        self
+    }
+
     /// Returns a builder containing the "platform default source" for the given `path` and `processed_path`.
     /// For most platforms, this will use [`FileAssetReader`](crate::io::file::FileAssetReader) / [`FileAssetWriter`](crate::io::file::FileAssetWriter),
     /// but some platforms (such as Android) have their own default readers / writers / watchers.
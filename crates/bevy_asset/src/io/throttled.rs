@@ -0,0 +1,348 @@
+use crate::io::{
+    AssetReader, AssetReaderError, AsyncSeekForward, ErasedAssetReader, PathStream, Reader,
+};
+use alloc::{boxed::Box, sync::Arc, task::Wake, vec::Vec};
+use bevy_platform::{
+    sync::{Mutex, PoisonError},
+    time::Instant,
+};
+use core::{
+    future::poll_fn,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+use futures_io::AsyncRead;
+use std::path::Path;
+
+/// Limits the rate at which bytes are read, shared by all the readers of an asset source.
+///
+/// This is a token bucket that holds up to one second worth of bytes. Reads can put the bucket in "debt", in which
+/// case further reads wait until the debt is paid back. This means readers that download assets before returning
+/// them (such as the web asset readers) are limited on average too.
+pub(crate) struct Throttle {
+    bytes_per_second: u64,
+    state: Mutex<ThrottleState>,
+    waiters: Arc<ThrottleWaiters>,
+}
+
+struct ThrottleState {
+    /// The number of bytes that can be read right now. Negative if more bytes than allowed were read.
+    available: i64,
+    last_refill: Instant,
+}
+
+impl Throttle {
+    pub(crate) fn new(bytes_per_second: u64) -> Self {
+        let bytes_per_second = bytes_per_second.max(1);
+        Self {
+            bytes_per_second,
+            state: Mutex::new(ThrottleState {
+                available: bytes_per_second.try_into().unwrap_or(i64::MAX),
+                last_refill: Instant::now(),
+            }),
+            waiters: Arc::default(),
+        }
+    }
+
+    /// Returns [`Poll::Ready`] once bytes can be read, or schedules `cx` to be woken up once they can.
+    fn poll_ready(&self, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let now = Instant::now();
+        let refilled = now
+            .saturating_duration_since(state.last_refill)
+            .as_secs_f64()
+            * self.bytes_per_second as f64;
+        // Only move the refill time forward by whole bytes, so frequent polls don't lose the fractions.
+        if refilled >= 1.0 {
+            let max = self.bytes_per_second.try_into().unwrap_or(i64::MAX);
+            state.available = state.available.saturating_add(refilled as i64).min(max);
+            state.last_refill = now;
+        }
+        if state.available > 0 {
+            return Poll::Ready(());
+        }
+        let missing = state.available.unsigned_abs() + 1;
+        let wait = Duration::from_secs_f64(missing as f64 / self.bytes_per_second as f64);
+        self.waiters.register(cx.waker(), now + wait);
+        Poll::Pending
+    }
+
+    fn consume(&self, bytes: usize) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.available = state
+            .available
+            .saturating_sub(bytes.try_into().unwrap_or(i64::MAX));
+    }
+}
+
+/// The tasks waiting for a [`Throttle`] to allow reads again.
+///
+/// A single timer is scheduled for all of them, and waking it wakes every waiting task.
+#[derive(Default)]
+struct ThrottleWaiters {
+    state: Mutex<ThrottleWaitersState>,
+}
+
+#[derive(Default)]
+struct ThrottleWaitersState {
+    wakers: Vec<Waker>,
+    /// The deadline of the scheduled timer, if any.
+    deadline: Option<Instant>,
+}
+
+impl ThrottleWaiters {
+    /// Registers `waker` to be woken up once `deadline` is reached, scheduling a timer if none is pending.
+    fn register(self: &Arc<Self>, waker: &Waker, deadline: Instant) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if !state.wakers.iter().any(|other| other.will_wake(waker)) {
+            state.wakers.push(waker.clone());
+        }
+        // Tasks woken up by an earlier timer schedule a new one if they still need to wait.
+        if state.deadline.is_none() {
+            state.deadline = Some(deadline);
+            wake_at(deadline, Waker::from(self.clone()));
+        }
+    }
+}
+
+impl Wake for ThrottleWaiters {
+    fn wake(self: Arc<Self>) {
+        let wakers = {
+            let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+            state.deadline = None;
+            core::mem::take(&mut state.wakers)
+        };
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn wake_at(deadline: Instant, waker: Waker) {
+    crate::io::wasm::wake_after(deadline.saturating_duration_since(Instant::now()), waker);
+}
+
+/// Wakes `waker` once `deadline` is reached, using a timer thread shared by every throttled source.
+#[cfg(not(target_arch = "wasm32"))]
+fn wake_at(deadline: Instant, waker: Waker) {
+    timer::TIMER.schedule(deadline, waker);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod timer {
+    use alloc::collections::BinaryHeap;
+    use bevy_platform::{sync::LazyLock, time::Instant};
+    use core::{cmp::Ordering, task::Waker};
+    use std::sync::{Condvar, Mutex, PoisonError};
+
+    pub(super) static TIMER: LazyLock<Timer> = LazyLock::new(|| {
+        std::thread::Builder::new()
+            .name("bevy_asset throttle timer".into())
+            .spawn(|| TIMER.run())
+            .expect("failed to spawn the asset throttle timer thread");
+        Timer {
+            queue: Mutex::new(BinaryHeap::new()),
+            condvar: Condvar::new(),
+        }
+    });
+
+    /// A queue of wakers to wake at a deadline, served by a single thread.
+    pub(super) struct Timer {
+        queue: Mutex<BinaryHeap<TimerEntry>>,
+        condvar: Condvar,
+    }
+
+    struct TimerEntry {
+        deadline: Instant,
+        waker: Waker,
+    }
+
+    // Entries are ordered by reverse deadline, so the heap yields the earliest deadline first.
+    impl Ord for TimerEntry {
+        fn cmp(&self, other: &Self) -> Ordering {
+            other.deadline.cmp(&self.deadline)
+        }
+    }
+
+    impl PartialOrd for TimerEntry {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl PartialEq for TimerEntry {
+        fn eq(&self, other: &Self) -> bool {
+            self.deadline == other.deadline
+        }
+    }
+
+    impl Eq for TimerEntry {}
+
+    impl Timer {
+        pub(super) fn schedule(&self, deadline: Instant, waker: Waker) {
+            let mut queue = self.queue.lock().unwrap_or_else(PoisonError::into_inner);
+            queue.push(TimerEntry { deadline, waker });
+            self.condvar.notify_one();
+        }
+
+        fn run(&self) {
+            let mut queue = self.queue.lock().unwrap_or_else(PoisonError::into_inner);
+            loop {
+                let now = Instant::now();
+                match queue.peek() {
+                    None => {
+                        queue = self
+                            .condvar
+                            .wait(queue)
+                            .unwrap_or_else(PoisonError::into_inner);
+                    }
+                    Some(entry) if entry.deadline <= now => {
+                        let entry = queue.pop().unwrap();
+                        drop(queue);
+                        entry.waker.wake();
+                        queue = self.queue.lock().unwrap_or_else(PoisonError::into_inner);
+                    }
+                    Some(entry) => {
+                        let timeout = entry.deadline.saturating_duration_since(now);
+                        queue = self
+                            .condvar
+                            .wait_timeout(queue, timeout)
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .0;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// An [`AssetReader`] that limits the rate at which bytes are read from the wrapped reader.
+///
+/// This is used to implement [`AssetSourceBuilder::with_bandwidth_limit`](crate::io::AssetSourceBuilder::with_bandwidth_limit).
+pub struct ThrottledAssetReader {
+    reader: Box<dyn ErasedAssetReader>,
+    throttle: Arc<Throttle>,
+}
+
+impl ThrottledAssetReader {
+    /// Creates a new [`ThrottledAssetReader`] that reads at most `bytes_per_second` (on average) from `reader`.
+    pub fn new(reader: Box<dyn ErasedAssetReader>, bytes_per_second: u64) -> Self {
+        Self::with_throttle(reader, Arc::new(Throttle::new(bytes_per_second)))
+    }
+
+    /// Creates a new [`ThrottledAssetReader`] that shares `throttle` with other readers.
+    pub(crate) fn with_throttle(
+        reader: Box<dyn ErasedAssetReader>,
+        throttle: Arc<Throttle>,
+    ) -> Self {
+        Self { reader, throttle }
+    }
+
+    async fn throttled<'a>(
+        &'a self,
+        reader: impl Future<Output = Result<Box<dyn Reader + 'a>, AssetReaderError>>,
+    ) -> Result<ThrottledReader<'a>, AssetReaderError> {
+        // Don't start new reads while the source is in debt.
+        poll_fn(|cx| self.throttle.poll_ready(cx)).await;
+        Ok(ThrottledReader {
+            reader: reader.await?,
+            throttle: &self.throttle,
+        })
+    }
+}
+
+impl AssetReader for ThrottledAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.throttled(self.reader.read(path)).await
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.throttled(self.reader.read_meta(path)).await
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        self.reader.read_directory(path).await
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        self.reader.is_directory(path).await
+    }
+}
+
+/// A [`Reader`] that waits for its [`Throttle`] before reading.
+struct ThrottledReader<'a> {
+    reader: Box<dyn Reader + 'a>,
+    throttle: &'a Throttle,
+}
+
+impl AsyncRead for ThrottledReader<'_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<futures_io::Result<usize>> {
+        if self.throttle.poll_ready(cx).is_pending() {
+            return Poll::Pending;
+        }
+        let result = Pin::new(&mut self.reader).poll_read(cx, buf);
+        if let Poll::Ready(Ok(bytes)) = result {
+            self.throttle.consume(bytes);
+        }
+        result
+    }
+}
+
+impl AsyncSeekForward for ThrottledReader<'_> {
+    fn poll_seek_forward(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        offset: u64,
+    ) -> Poll<futures_io::Result<u64>> {
+        Pin::new(&mut self.reader).poll_seek_forward(cx, offset)
+    }
+}
+
+impl Reader for ThrottledReader<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::memory::{Dir, MemoryAssetReader};
+    use alloc::{vec, vec::Vec};
+    use bevy_tasks::block_on;
+
+    #[test]
+    fn throttled_reads_wait_for_bandwidth() {
+        let dir = Dir::default();
+        dir.insert_asset(Path::new("a.bin"), vec![0; 100]);
+        dir.insert_asset(Path::new("b.bin"), vec![0; 100]);
+        let reader = ThrottledAssetReader::new(Box::new(MemoryAssetReader { root: dir }), 1000);
+
+        let read = |path: &str| {
+            block_on(async {
+                let mut bytes = Vec::new();
+                AssetReader::read(&reader, Path::new(path))
+                    .await
+                    .unwrap()
+                    .read_to_end(&mut bytes)
+                    .await
+                    .unwrap();
+                bytes.len()
+            })
+        };
+
+        // The first second worth of bytes is available right away, then the debt is paid back at 1000 bytes/s.
+        let start = Instant::now();
+        assert_eq!(read("a.bin"), 100);
+        assert!(start.elapsed() < Duration::from_millis(50));
+        reader.throttle.consume(1000);
+        let start = Instant::now();
+        assert_eq!(read("b.bin"), 100);
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+}
@@ -91,6 +91,49 @@ impl HttpWasmAssetReader {
     }
 }
 
+/// Calls `callback` after `duration` using the `setTimeout` function of the JS global scope. Returns `false` if the
+/// timeout couldn't be set.
+fn set_timeout(callback: &js_sys::Function, duration: core::time::Duration) -> bool {
+    let timeout = i32::try_from(duration.as_millis()).unwrap_or(i32::MAX);
+    let global: Global = js_sys::global().unchecked_into();
+    let result = if !global.window().is_undefined() {
+        let window: web_sys::Window = global.unchecked_into();
+        window.set_timeout_with_callback_and_timeout_and_arguments_0(callback, timeout)
+    } else if !global.worker().is_undefined() {
+        let worker: web_sys::WorkerGlobalScope = global.unchecked_into();
+        worker.set_timeout_with_callback_and_timeout_and_arguments_0(callback, timeout)
+    } else {
+        Err(JsValue::UNDEFINED)
+    };
+    result.is_ok()
+}
+
+/// Waits for `duration` using the `setTimeout` function of the JS global scope.
+// Used by [`WebAssetReader`](crate::web::WebAssetReader) to wait between retries.
+#[cfg(any(feature = "http", feature = "https"))]
+pub(crate) async fn sleep(duration: core::time::Duration) {
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        if !set_timeout(&resolve, duration) {
+            // Don't wait at all rather than waiting forever.
+            let _ = resolve.call0(&JsValue::UNDEFINED);
+        }
+    });
+    let _ = JsFuture::from(promise).await;
+}
+
+/// Wakes `waker` after `duration` using the `setTimeout` function of the JS global scope.
+// Used by [`ThrottledAssetReader`](crate::io::throttled::ThrottledAssetReader) to wait for bandwidth.
+pub(crate) fn wake_after(duration: core::time::Duration, waker: core::task::Waker) {
+    let wake = wasm_bindgen::closure::Closure::once_into_js({
+        let waker = waker.clone();
+        move || waker.wake()
+    });
+    if !set_timeout(wake.unchecked_ref(), duration) {
+        // Don't wait at all rather than waiting forever.
+        waker.wake();
+    }
+}
+
 impl AssetReader for HttpWasmAssetReader {
     async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
         let path = self.root_path.join(path);
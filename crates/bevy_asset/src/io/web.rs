@@ -6,6 +6,7 @@ use crate::{AssetApp, AssetPlugin};
 use alloc::boxed::Box;
 use bevy_app::{App, Plugin};
 use bevy_tasks::ConditionalSendFuture;
+use core::time::Duration;
 use std::path::{Path, PathBuf};
 use tracing::warn;
 
@@ -38,6 +39,7 @@ use tracing::warn;
 /// App::new()
 ///     .add_plugins(DefaultPlugins.set(WebAssetPlugin {
 ///         silence_startup_warning: true,
+///         ..Default::default()
 ///     }))
 /// #   .add_systems(Startup, setup).run();
 /// # }
@@ -47,6 +49,13 @@ use tracing::warn;
 /// # }
 /// ```
 ///
+/// Requests that fail because of a network error or a transient server error (such as `503 Service Unavailable`)
+/// are retried according to [`WebAssetPlugin::retry_policy`].
+///
+/// When the `web_asset_cache` feature is enabled on native platforms, downloaded assets are cached on disk. Cached
+/// assets are revalidated with the server using their `ETag`, so they are only downloaded again when they change,
+/// and are used as a fallback when the server can't be reached. On the web, caching is left to the browser.
+///
 /// By default, `ureq`'s HTTP compression is disabled. To enable gzip and brotli decompression, add
 /// the following dependency and features to your Cargo.toml. This will improve bandwidth
 /// utilization when its supported by the server.
@@ -58,6 +67,8 @@ use tracing::warn;
 #[derive(Default)]
 pub struct WebAssetPlugin {
     pub silence_startup_warning: bool,
+    /// How requests that fail with a transient error are retried.
+    pub retry_policy: WebAssetRetryPolicy,
 }
 
 impl Plugin for WebAssetPlugin {
@@ -71,34 +82,116 @@ impl Plugin for WebAssetPlugin {
             warn!("WebAssetPlugin must be added before AssetPlugin for it to work!");
         }
         #[cfg(feature = "http")]
-        app.register_asset_source(
-            "http",
-            AssetSourceBuilder::new(move || Box::new(WebAssetReader::Http))
-                .with_processed_reader(move || Box::new(WebAssetReader::Http)),
-        );
+        {
+            let reader = WebAssetReader {
+                scheme: WebAssetScheme::Http,
+                retry_policy: self.retry_policy.clone(),
+            };
+            let processed_reader = reader.clone();
+            app.register_asset_source(
+                "http",
+                AssetSourceBuilder::new(move || Box::new(reader.clone()))
+                    .with_processed_reader(move || Box::new(processed_reader.clone())),
+            );
+        }
 
         #[cfg(feature = "https")]
-        app.register_asset_source(
-            "https",
-            AssetSourceBuilder::new(move || Box::new(WebAssetReader::Https))
-                .with_processed_reader(move || Box::new(WebAssetReader::Https)),
-        );
+        {
+            let reader = WebAssetReader {
+                scheme: WebAssetScheme::Https,
+                retry_policy: self.retry_policy.clone(),
+            };
+            let processed_reader = reader.clone();
+            app.register_asset_source(
+                "https",
+                AssetSourceBuilder::new(move || Box::new(reader.clone()))
+                    .with_processed_reader(move || Box::new(processed_reader.clone())),
+            );
+        }
     }
 }
 
-/// Asset reader that treats paths as urls to load assets from.
-pub enum WebAssetReader {
+/// Controls how a [`WebAssetReader`] retries requests that failed with a transient error.
+///
+/// Network errors and the `408 Request Timeout`, `429 Too Many Requests` and `5xx` HTTP statuses are retried. Every
+/// retry waits twice as long as the previous one, starting from [`initial_backoff`](Self::initial_backoff) and
+/// capped at [`max_backoff`](Self::max_backoff).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WebAssetRetryPolicy {
+    /// The maximum number of times a request is retried. A value of `0` disables retries.
+    pub max_retries: u32,
+    /// The delay before the first retry.
+    pub initial_backoff: Duration,
+    /// The maximum delay between two retries.
+    pub max_backoff: Duration,
+}
+
+impl WebAssetRetryPolicy {
+    /// A policy that never retries requests.
+    pub const NONE: Self = Self {
+        max_retries: 0,
+        initial_backoff: Duration::ZERO,
+        max_backoff: Duration::ZERO,
+    };
+
+    /// Returns the delay before the given retry, starting from `0`.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff)
+    }
+
+    /// Returns whether a request that failed with `error` should be retried.
+    pub fn is_retryable(error: &AssetReaderError) -> bool {
+        match error {
+            AssetReaderError::NotFound(_) => false,
+            AssetReaderError::Io(_) => true,
+            AssetReaderError::HttpError(code) => matches!(code, 408 | 429 | 500..=599),
+        }
+    }
+}
+
+impl Default for WebAssetRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(4),
+        }
+    }
+}
+
+/// The scheme used by a [`WebAssetReader`] to build urls.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WebAssetScheme {
     /// Unencrypted connections.
     Http,
     /// Use TLS for setting up connections.
     Https,
 }
 
+/// Asset reader that treats paths as urls to load assets from.
+#[derive(Clone, Debug)]
+pub struct WebAssetReader {
+    /// The scheme used to build urls from asset paths.
+    pub scheme: WebAssetScheme,
+    /// How requests that fail with a transient error are retried.
+    pub retry_policy: WebAssetRetryPolicy,
+}
+
 impl WebAssetReader {
+    /// Creates a new [`WebAssetReader`] for `scheme`, using the default [`WebAssetRetryPolicy`].
+    pub fn new(scheme: WebAssetScheme) -> Self {
+        Self {
+            scheme,
+            retry_policy: WebAssetRetryPolicy::default(),
+        }
+    }
+
     fn make_uri(&self, path: &Path) -> PathBuf {
-        let prefix = match self {
-            Self::Http => "http://",
-            Self::Https => "https://",
+        let prefix = match self.scheme {
+            WebAssetScheme::Http => "http://",
+            WebAssetScheme::Https => "https://",
         };
         PathBuf::from(prefix).join(path)
     }
@@ -110,20 +203,79 @@ impl WebAssetReader {
     }
 }
 
+/// Fetches `path`, retrying transient failures according to `retry_policy`.
+async fn fetch_with_retries<T, F: ConditionalSendFuture<Output = Result<T, AssetReaderError>>>(
+    path: &Path,
+    retry_policy: &WebAssetRetryPolicy,
+    mut fetch: impl FnMut() -> F,
+) -> Result<T, AssetReaderError> {
+    let mut retry = 0;
+    loop {
+        match fetch().await {
+            Err(err)
+                if retry < retry_policy.max_retries && WebAssetRetryPolicy::is_retryable(&err) =>
+            {
+                let backoff = retry_policy.backoff(retry);
+                warn!(
+                    "Failed to load asset {}: {err}. Retrying in {backoff:?}.",
+                    path.display()
+                );
+                sleep(backoff).await;
+                retry += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
 #[cfg(target_arch = "wasm32")]
-async fn get<'a>(path: PathBuf) -> Result<Box<dyn Reader>, AssetReaderError> {
+async fn sleep(duration: Duration) {
+    crate::io::wasm::sleep(duration).await;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn sleep(duration: Duration) {
+    blocking::unblock(move || std::thread::sleep(duration)).await;
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn get<'a>(
+    path: PathBuf,
+    retry_policy: &WebAssetRetryPolicy,
+) -> Result<Box<dyn Reader>, AssetReaderError> {
     use crate::io::wasm::HttpWasmAssetReader;
 
-    HttpWasmAssetReader::new("")
-        .fetch_bytes(path)
+    // The browser takes care of caching, including revalidating cached responses.
+    let reader = HttpWasmAssetReader::new("");
+    fetch_with_retries(&path, retry_policy, || reader.fetch_bytes(path.clone()))
         .await
         .map(|r| Box::new(r) as Box<dyn Reader>)
 }
 
+/// An asset stored in the web asset cache.
+#[cfg(not(target_arch = "wasm32"))]
+struct CachedAsset {
+    data: alloc::vec::Vec<u8>,
+    /// The `ETag` the server returned with `data`, used to check whether the asset has changed.
+    etag: Option<alloc::string::String>,
+}
+
+/// The result of a successful request.
+#[cfg(not(target_arch = "wasm32"))]
+enum Fetched {
+    /// The server returned the asset, along with its `ETag` if it has one.
+    Data(alloc::vec::Vec<u8>, Option<alloc::string::String>),
+    /// The cached version of the asset is still up to date.
+    NotModified,
+}
+
 #[cfg(not(target_arch = "wasm32"))]
-async fn get(path: PathBuf) -> Result<Box<dyn Reader>, AssetReaderError> {
+async fn get(
+    path: PathBuf,
+    retry_policy: &WebAssetRetryPolicy,
+) -> Result<Box<dyn Reader>, AssetReaderError> {
     use crate::io::VecReader;
-    use alloc::{borrow::ToOwned, boxed::Box, vec::Vec};
+    use alloc::{borrow::ToOwned, boxed::Box, string::String};
     use bevy_platform::sync::LazyLock;
     use blocking::unblock;
     use std::io::{self, BufReader, Read};
@@ -134,10 +286,11 @@ async fn get(path: PathBuf) -> Result<Box<dyn Reader>, AssetReaderError> {
         )
     })?;
 
-    #[cfg(all(not(target_arch = "wasm32"), feature = "web_asset_cache"))]
-    if let Some(data) = web_asset_cache::try_load_from_cache(str_path).await? {
-        return Ok(Box::new(VecReader::new(data)));
-    }
+    #[cfg(feature = "web_asset_cache")]
+    let cached = web_asset_cache::try_load_from_cache(str_path).await?;
+    #[cfg(not(feature = "web_asset_cache"))]
+    let cached: Option<CachedAsset> = None;
+
     use ureq::tls::{RootCerts, TlsConfig};
     use ureq::Agent;
 
@@ -152,39 +305,70 @@ async fn get(path: PathBuf) -> Result<Box<dyn Reader>, AssetReaderError> {
             .new_agent()
     });
 
-    let uri = str_path.to_owned();
-    // Use [`unblock`] to run the http request on a separately spawned thread as to not block bevy's
-    // async executor.
-    let response = unblock(|| AGENT.get(uri).call()).await;
-
-    match response {
-        Ok(mut response) => {
-            let mut reader = BufReader::new(response.body_mut().with_config().reader());
-
-            let mut buffer = Vec::new();
-            reader.read_to_end(&mut buffer)?;
+    let etag = cached.as_ref().and_then(|cached| cached.etag.clone());
+    let fetch = || {
+        let uri = str_path.to_owned();
+        let etag = etag.clone();
+        let path = path.clone();
+        // Use [`unblock`] to run the http request on a separately spawned thread as to not block bevy's
+        // async executor.
+        unblock(move || {
+            let mut request = AGENT.get(uri);
+            if let Some(etag) = etag {
+                request = request.header("If-None-Match", etag);
+            }
+            match request.call() {
+                Ok(response) if response.status() == 304 => Ok(Fetched::NotModified),
+                Ok(mut response) => {
+                    let etag = response
+                        .headers()
+                        .get("ETag")
+                        .and_then(|etag| etag.to_str().ok())
+                        .map(String::from);
+                    let mut reader = BufReader::new(response.body_mut().with_config().reader());
+
+                    let mut buffer = alloc::vec::Vec::new();
+                    reader.read_to_end(&mut buffer)?;
+                    Ok(Fetched::Data(buffer, etag))
+                }
+                // ureq considers all >=400 status codes as errors
+                Err(ureq::Error::StatusCode(code)) => {
+                    if code == 404 {
+                        Err(AssetReaderError::NotFound(path))
+                    } else {
+                        Err(AssetReaderError::HttpError(code))
+                    }
+                }
+                Err(err) => Err(AssetReaderError::Io(
+                    io::Error::other(std::format!(
+                        "unexpected error while loading asset {}: {}",
+                        path.display(),
+                        err
+                    ))
+                    .into(),
+                )),
+            }
+        })
+    };
 
-            #[cfg(all(not(target_arch = "wasm32"), feature = "web_asset_cache"))]
-            web_asset_cache::save_to_cache(str_path, &buffer).await?;
+    match (fetch_with_retries(&path, retry_policy, fetch).await, cached) {
+        (Ok(Fetched::Data(buffer, _etag)), _) => {
+            #[cfg(feature = "web_asset_cache")]
+            web_asset_cache::save_to_cache(str_path, &buffer, _etag.as_deref()).await?;
 
             Ok(Box::new(VecReader::new(buffer)))
         }
-        // ureq considers all >=400 status codes as errors
-        Err(ureq::Error::StatusCode(code)) => {
-            if code == 404 {
-                Err(AssetReaderError::NotFound(path))
-            } else {
-                Err(AssetReaderError::HttpError(code))
-            }
+        (Ok(Fetched::NotModified), Some(cached)) => Ok(Box::new(VecReader::new(cached.data))),
+        // We only send `If-None-Match` when the asset is cached.
+        (Ok(Fetched::NotModified), None) => Err(AssetReaderError::HttpError(304)),
+        (Err(err), Some(cached)) if WebAssetRetryPolicy::is_retryable(&err) => {
+            warn!(
+                "Failed to load asset {}: {err}. Using the cached version instead.",
+                path.display()
+            );
+            Ok(Box::new(VecReader::new(cached.data)))
         }
-        Err(err) => Err(AssetReaderError::Io(
-            io::Error::other(std::format!(
-                "unexpected error while loading asset {}: {}",
-                path.display(),
-                err
-            ))
-            .into(),
-        )),
+        (Err(err), _) => Err(err),
     }
 }
 
@@ -193,12 +377,12 @@ impl AssetReader for WebAssetReader {
         &'a self,
         path: &'a Path,
     ) -> impl ConditionalSendFuture<Output = Result<Box<dyn Reader>, AssetReaderError>> {
-        get(self.make_uri(path))
+        get(self.make_uri(path), &self.retry_policy)
     }
 
     async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<Box<dyn Reader>, AssetReaderError> {
         let uri = self.make_meta_uri(path);
-        get(uri).await
+        get(uri, &self.retry_policy).await
     }
 
     async fn is_directory<'a>(&'a self, _path: &'a Path) -> Result<bool, AssetReaderError> {
@@ -213,20 +397,20 @@ impl AssetReader for WebAssetReader {
     }
 }
 
-/// A naive implementation of a cache for assets downloaded from the web that never invalidates.
+/// A simple cache for assets downloaded from the web. Entries are stored along with their `ETag`, which is used to
+/// revalidate them with the server. Entries without an `ETag` are downloaded again on every load.
 /// `ureq` currently does not support caching, so this is a simple workaround.
 /// It should eventually be replaced by `http-cache` or similar, see [tracking issue](https://github.com/06chaynes/http-cache/issues/91)
 #[cfg(all(not(target_arch = "wasm32"), feature = "web_asset_cache"))]
 mod web_asset_cache {
     use alloc::string::String;
-    use alloc::vec::Vec;
     use core::hash::{Hash, Hasher};
     use futures_lite::AsyncWriteExt;
     use std::collections::hash_map::DefaultHasher;
     use std::io;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
 
-    use crate::io::Reader;
+    use super::CachedAsset;
 
     const CACHE_DIR: &str = ".web-asset-cache";
 
@@ -236,21 +420,28 @@ mod web_asset_cache {
         std::format!("{:x}", hasher.finish())
     }
 
-    pub async fn try_load_from_cache(url: &str) -> Result<Option<Vec<u8>>, io::Error> {
+    fn etag_path(cache_path: &Path) -> PathBuf {
+        cache_path.with_extension("etag")
+    }
+
+    pub async fn try_load_from_cache(url: &str) -> Result<Option<CachedAsset>, io::Error> {
         let filename = url_to_hash(url);
         let cache_path = PathBuf::from(CACHE_DIR).join(&filename);
 
         if cache_path.exists() {
-            let mut file = async_fs::File::open(&cache_path).await?;
-            let mut buffer = Vec::new();
-            file.read_to_end(&mut buffer).await?;
-            Ok(Some(buffer))
+            let data = async_fs::read(&cache_path).await?;
+            let etag = async_fs::read_to_string(etag_path(&cache_path)).await.ok();
+            Ok(Some(CachedAsset { data, etag }))
         } else {
             Ok(None)
         }
     }
 
-    pub async fn save_to_cache(url: &str, data: &[u8]) -> Result<(), io::Error> {
+    pub async fn save_to_cache(
+        url: &str,
+        data: &[u8],
+        etag: Option<&str>,
+    ) -> Result<(), io::Error> {
         let filename = url_to_hash(url);
         let cache_path = PathBuf::from(CACHE_DIR).join(&filename);
 
@@ -259,6 +450,14 @@ mod web_asset_cache {
         let mut cache_file = async_fs::File::create(&cache_path).await?;
         cache_file.write_all(data).await?;
 
+        let etag_path = etag_path(&cache_path);
+        match etag {
+            Some(etag) => async_fs::write(etag_path, etag).await?,
+            None => {
+                async_fs::remove_file(etag_path).await.ok();
+            }
+        }
+
         Ok(())
     }
 }
@@ -270,7 +469,7 @@ mod tests {
     #[test]
     fn make_http_uri() {
         assert_eq!(
-            WebAssetReader::Http
+            WebAssetReader::new(WebAssetScheme::Http)
                 .make_uri(Path::new("example.com/favicon.png"))
                 .to_str()
                 .unwrap(),
@@ -281,7 +480,7 @@ mod tests {
     #[test]
     fn make_https_uri() {
         assert_eq!(
-            WebAssetReader::Https
+            WebAssetReader::new(WebAssetScheme::Https)
                 .make_uri(Path::new("example.com/favicon.png"))
                 .to_str()
                 .unwrap(),
@@ -292,7 +491,7 @@ mod tests {
     #[test]
     fn make_http_meta_uri() {
         assert_eq!(
-            WebAssetReader::Http
+            WebAssetReader::new(WebAssetScheme::Http)
                 .make_meta_uri(Path::new("example.com/favicon.png"))
                 .to_str()
                 .unwrap(),
@@ -303,7 +502,7 @@ mod tests {
     #[test]
     fn make_https_meta_uri() {
         assert_eq!(
-            WebAssetReader::Https
+            WebAssetReader::new(WebAssetScheme::Https)
                 .make_meta_uri(Path::new("example.com/favicon.png"))
                 .to_str()
                 .unwrap(),
@@ -314,11 +513,41 @@ mod tests {
     #[test]
     fn make_https_without_extension_meta_uri() {
         assert_eq!(
-            WebAssetReader::Https
+            WebAssetReader::new(WebAssetScheme::Https)
                 .make_meta_uri(Path::new("example.com/favicon"))
                 .to_str()
                 .unwrap(),
             "https://example.com/favicon.meta"
         );
     }
+
+    #[test]
+    fn retry_backoff_doubles_until_max() {
+        let policy = WebAssetRetryPolicy {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(500),
+        };
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(2), Duration::from_millis(400));
+        assert_eq!(policy.backoff(3), Duration::from_millis(500));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn only_transient_errors_are_retryable() {
+        assert!(!WebAssetRetryPolicy::is_retryable(
+            &AssetReaderError::NotFound("example.com/favicon.png".into())
+        ));
+        assert!(!WebAssetRetryPolicy::is_retryable(
+            &AssetReaderError::HttpError(403)
+        ));
+        assert!(WebAssetRetryPolicy::is_retryable(
+            &AssetReaderError::HttpError(429)
+        ));
+        assert!(WebAssetRetryPolicy::is_retryable(
+            &AssetReaderError::HttpError(503)
+        ));
+    }
 }
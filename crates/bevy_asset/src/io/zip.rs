@@ -0,0 +1,463 @@
+use crate::io::{get_meta_path, AssetReader, AssetReaderError, PathStream, Reader, VecReader};
+use alloc::{borrow::ToOwned, boxed::Box, sync::Arc, vec::Vec};
+use bevy_platform::{
+    collections::{HashMap, HashSet},
+    sync::{Mutex, PoisonError},
+};
+use std::{
+    fs::File,
+    io::{self, BufReader, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+use zip::{result::ZipError, ZipArchive};
+
+/// A reader of archive bytes that can be read from at random offsets.
+trait ArchiveRead: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> ArchiveRead for T {}
+
+/// A handle on the bytes of a zip archive.
+///
+/// Every read from the archive uses its own handle, so reads don't wait on each other while entries are decompressed.
+enum ArchiveSource {
+    /// An archive on the filesystem. Every handle opens the file when it's first read from.
+    File {
+        path: Arc<Path>,
+        file: Option<BufReader<File>>,
+        position: u64,
+    },
+    /// An archive read from a single reader, shared by every handle. The reader is only locked while reading raw
+    /// bytes from it.
+    Shared {
+        reader: Arc<Mutex<Box<dyn ArchiveRead>>>,
+        position: u64,
+    },
+}
+
+impl ArchiveSource {
+    fn file(&mut self) -> io::Result<&mut BufReader<File>> {
+        let Self::File {
+            path,
+            file,
+            position,
+        } = self
+        else {
+            unreachable!("only file sources have a file");
+        };
+        if file.is_none() {
+            let mut reader = BufReader::new(File::open(&**path)?);
+            reader.seek(SeekFrom::Start(*position))?;
+            *file = Some(reader);
+        }
+        Ok(file.as_mut().unwrap())
+    }
+}
+
+impl Clone for ArchiveSource {
+    fn clone(&self) -> Self {
+        match self {
+            Self::File { path, position, .. } => Self::File {
+                path: path.clone(),
+                file: None,
+                position: *position,
+            },
+            Self::Shared { reader, position } => Self::Shared {
+                reader: reader.clone(),
+                position: *position,
+            },
+        }
+    }
+}
+
+impl Read for ArchiveSource {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = match self {
+            Self::File { .. } => self.file()?.read(buf)?,
+            Self::Shared { reader, position } => {
+                let mut reader = reader.lock().unwrap_or_else(PoisonError::into_inner);
+                reader.seek(SeekFrom::Start(*position))?;
+                reader.read(buf)?
+            }
+        };
+        match self {
+            Self::File { position, .. } | Self::Shared { position, .. } => {
+                *position += read as u64;
+            }
+        }
+        Ok(read)
+    }
+}
+
+impl Seek for ArchiveSource {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_position = match self {
+            Self::File { .. } => self.file()?.seek(pos)?,
+            Self::Shared { reader, position } => match pos {
+                SeekFrom::Start(offset) => offset,
+                SeekFrom::Current(offset) => {
+                    position.checked_add_signed(offset).ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            "invalid seek to a negative position",
+                        )
+                    })?
+                }
+                SeekFrom::End(_) => reader
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .seek(pos)?,
+            },
+        };
+        match self {
+            Self::File { position, .. } | Self::Shared { position, .. } => {
+                *position = new_position;
+            }
+        }
+        Ok(new_position)
+    }
+}
+
+/// An [`AssetReader`] that reads assets from a zip archive, which allows packing many small asset files into a few
+/// bundles when shipping a game.
+///
+/// The archive's central directory is indexed when the reader is created, so assets are read without scanning the
+/// archive. Entries can be stored uncompressed or compressed with deflate, and can optionally be encrypted (using
+/// either AES or the legacy `ZipCrypto` scheme), in which case the password must be provided with
+/// [`ZipAssetReader::with_password`].
+///
+/// Like any other [`AssetReader`], it is mounted by registering it as an [`AssetSource`](crate::io::AssetSource):
+///
+/// ```no_run
+/// # use bevy_app::App;
+/// # use bevy_asset::{io::{zip::ZipAssetReader, AssetSourceBuilder}, AssetApp};
+/// # let mut app = App::new();
+/// let reader = ZipAssetReader::open("assets/levels.zip").unwrap();
+/// // Assets can now be loaded from paths like "levels://forest/map.ron".
+/// app.register_asset_source(
+///     "levels",
+///     AssetSourceBuilder::new(move || Box::new(reader.clone())),
+/// );
+/// ```
+///
+/// Asset meta files are read from the archive as well, next to the asset they belong to.
+#[derive(Clone)]
+pub struct ZipAssetReader {
+    index: Arc<ZipIndex>,
+    password: Option<Arc<[u8]>>,
+}
+
+struct ZipIndex {
+    /// The indexed archive, cloned into a new handle for every read.
+    archive: ZipArchive<ArchiveSource>,
+    /// Maps the path of every file in the archive to its index in the archive.
+    files: HashMap<PathBuf, usize>,
+    /// Maps the path of every directory in the archive (including implicit ones) to its direct children.
+    directories: HashMap<PathBuf, HashSet<PathBuf>>,
+}
+
+impl ZipAssetReader {
+    /// Creates a new [`ZipAssetReader`] for the zip archive read from `reader`, indexing its entries.
+    ///
+    /// Reads from the archive share `reader`, which is locked while raw bytes are read from it. Prefer
+    /// [`ZipAssetReader::open`] for archives on the filesystem, which lets every read use its own file handle.
+    pub fn new(reader: impl Read + Seek + Send + 'static) -> Result<Self, ZipError> {
+        Self::from_source(ArchiveSource::Shared {
+            reader: Arc::new(Mutex::new(Box::new(reader))),
+            position: 0,
+        })
+    }
+
+    /// Creates a new [`ZipAssetReader`] for the zip archive at `path` on the filesystem.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ZipError> {
+        Self::from_source(ArchiveSource::File {
+            path: path.as_ref().into(),
+            file: None,
+            position: 0,
+        })
+    }
+
+    fn from_source(source: ArchiveSource) -> Result<Self, ZipError> {
+        let mut archive = ZipArchive::new(source)?;
+        let mut files = <HashMap<_, _>>::default();
+        let mut directories = <HashMap<_, HashSet<_>>>::default();
+        directories.insert(PathBuf::new(), HashSet::default());
+        for index in 0..archive.len() {
+            // Reading the raw entry doesn't require decrypting it.
+            let entry = archive.by_index_raw(index)?;
+            // Entries with unsafe paths (such as absolute paths or paths containing "..") are skipped.
+            let Some(path) = entry.enclosed_name() else {
+                continue;
+            };
+            let is_dir = entry.is_dir();
+            // Register the entry with its parent directory, and any missing ancestor with its own parent, as
+            // archives don't necessarily contain entries for directories.
+            let mut child = path.clone();
+            while let Some(parent) = child.parent().map(Path::to_path_buf) {
+                let parent_exists = directories.contains_key(&parent);
+                directories.entry(parent.clone()).or_default().insert(child);
+                if parent_exists {
+                    break;
+                }
+                child = parent;
+            }
+            if is_dir {
+                directories.entry(path).or_default();
+            } else {
+                files.insert(path, index);
+            }
+        }
+        Ok(Self {
+            index: Arc::new(ZipIndex {
+                archive,
+                files,
+                directories,
+            }),
+            password: None,
+        })
+    }
+
+    /// Sets the password used to decrypt encrypted entries of the archive.
+    pub fn with_password(mut self, password: impl AsRef<[u8]>) -> Self {
+        self.password = Some(password.as_ref().into());
+        self
+    }
+
+    /// Returns the paths of every file in the archive.
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.index.files.keys().map(PathBuf::as_path)
+    }
+
+    async fn read_file(&self, path: &Path) -> Result<VecReader, AssetReaderError> {
+        let Some(&index) = self.index.files.get(path) else {
+            return Err(AssetReaderError::NotFound(path.to_owned()));
+        };
+        let mut archive = self.index.archive.clone();
+        let password = self.password.clone();
+        let path = path.to_owned();
+        let read = move || {
+            let entry = match &password {
+                Some(password) => archive.by_index_decrypt(index, password),
+                None => archive.by_index(index),
+            };
+            let mut entry = entry.map_err(|err| zip_error_to_io(&path, err))?;
+            // The size in the entry header is not trusted for preallocation,
+            // as a forged archive could claim an arbitrarily large entry.
+            let mut bytes = Vec::new();
+            entry.read_to_end(&mut bytes)?;
+            Ok(VecReader::new(bytes))
+        };
+        // Decompressing and decrypting entries is run on a separate thread, so as not to block the async executor.
+        #[cfg(not(target_arch = "wasm32"))]
+        return blocking::unblock(read).await;
+        #[cfg(target_arch = "wasm32")]
+        read()
+    }
+}
+
+fn zip_error_to_io(path: &Path, err: ZipError) -> io::Error {
+    match err {
+        ZipError::Io(err) => err,
+        err => io::Error::other(std::format!(
+            "failed to read {} from zip archive: {err}",
+            path.display()
+        )),
+    }
+}
+
+impl AssetReader for ZipAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.read_file(path).await
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.read_file(&get_meta_path(path)).await
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        let Some(children) = self.index.directories.get(path) else {
+            return Err(AssetReaderError::NotFound(path.to_owned()));
+        };
+        let children = children
+            .iter()
+            // filter out meta files as they are not considered assets
+            .filter(|child| child.extension().is_none_or(|ext| ext != "meta"))
+            .cloned()
+            .collect::<Vec<_>>();
+        let stream: Box<PathStream> = Box::new(futures_lite::stream::iter(children));
+        Ok(stream)
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        Ok(self.index.directories.contains_key(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{string::String, vec};
+    use bevy_tasks::block_on;
+    use futures_lite::{AsyncReadExt, StreamExt};
+    use std::io::{Cursor, Write};
+    use zip::{write::SimpleFileOptions, AesMode, CompressionMethod, ZipWriter};
+
+    fn create_archive(password: Option<&str>) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let mut options =
+            SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        if let Some(password) = password {
+            options = options.with_aes_encryption(AesMode::Aes256, password);
+        }
+        for (path, contents) in [
+            ("root.txt", "root"),
+            ("root.txt.meta", "root meta"),
+            ("textures/grass.txt", "grass"),
+            ("textures/sky/clouds.txt", "clouds"),
+        ] {
+            writer.start_file(path, options).unwrap();
+            writer.write_all(contents.as_bytes()).unwrap();
+        }
+        writer.add_directory("empty", options).unwrap();
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn read_to_string(reader: &ZipAssetReader, path: &str) -> Result<String, AssetReaderError> {
+        block_on(async {
+            let mut reader = reader.read(Path::new(path)).await?;
+            let mut text = String::new();
+            reader.read_to_string(&mut text).await?;
+            Ok(text)
+        })
+    }
+
+    fn read_directory(reader: &ZipAssetReader, path: &str) -> Vec<PathBuf> {
+        let mut children = block_on(async {
+            reader
+                .read_directory(Path::new(path))
+                .await
+                .unwrap()
+                .collect::<Vec<_>>()
+                .await
+        });
+        children.sort();
+        children
+    }
+
+    #[test]
+    fn reads_files_and_directories() {
+        let reader = ZipAssetReader::new(Cursor::new(create_archive(None))).unwrap();
+
+        assert_eq!(read_to_string(&reader, "root.txt").unwrap(), "root");
+        assert_eq!(
+            read_to_string(&reader, "textures/sky/clouds.txt").unwrap(),
+            "clouds"
+        );
+        assert!(matches!(
+            read_to_string(&reader, "missing.txt"),
+            Err(AssetReaderError::NotFound(_))
+        ));
+        let meta = block_on(async {
+            let mut reader = reader.read_meta(Path::new("root.txt")).await.unwrap();
+            let mut text = String::new();
+            reader.read_to_string(&mut text).await.unwrap();
+            text
+        });
+        assert_eq!(meta, "root meta");
+
+        assert_eq!(
+            read_directory(&reader, ""),
+            vec![
+                PathBuf::from("empty"),
+                PathBuf::from("root.txt"),
+                PathBuf::from("textures")
+            ]
+        );
+        assert_eq!(
+            read_directory(&reader, "textures"),
+            vec![
+                PathBuf::from("textures/grass.txt"),
+                PathBuf::from("textures/sky")
+            ]
+        );
+        assert!(block_on(reader.is_directory(Path::new("textures/sky"))).unwrap());
+        assert!(block_on(reader.is_directory(Path::new("empty"))).unwrap());
+        assert!(!block_on(reader.is_directory(Path::new("root.txt"))).unwrap());
+    }
+
+    #[test]
+    fn ignores_forged_entry_size() {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Stored)
+            .large_file(true);
+        writer.start_file("forged.txt", options).unwrap();
+        writer.write_all(b"forged").unwrap();
+        let mut archive = writer.finish().unwrap().into_inner();
+
+        // Claim a huge uncompressed size in the ZIP64 extra field of the central directory entry.
+        let central = archive
+            .windows(4)
+            .rposition(|bytes| bytes == b"PK\x01\x02")
+            .unwrap();
+        let name_len = u16::from_le_bytes([archive[central + 28], archive[central + 29]]) as usize;
+        let extra = central + 46 + name_len;
+        assert_eq!(archive[extra..extra + 2], [0x01, 0x00]);
+        archive[extra + 4..extra + 12].copy_from_slice(&u64::MAX.to_le_bytes());
+
+        let reader = ZipAssetReader::new(Cursor::new(archive)).unwrap();
+        assert_eq!(read_to_string(&reader, "forged.txt").unwrap(), "forged");
+    }
+
+    #[test]
+    fn reads_encrypted_files() {
+        let archive = create_archive(Some("hunter2"));
+
+        let reader = ZipAssetReader::new(Cursor::new(archive.clone()))
+            .unwrap()
+            .with_password("hunter2");
+        assert_eq!(
+            read_to_string(&reader, "textures/grass.txt").unwrap(),
+            "grass"
+        );
+
+        let reader = ZipAssetReader::new(Cursor::new(archive))
+            .unwrap()
+            .with_password("wrong");
+        assert!(matches!(
+            read_to_string(&reader, "textures/grass.txt"),
+            Err(AssetReaderError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn reads_files_concurrently() {
+        let path = std::env::temp_dir().join(std::format!(
+            "bevy_asset_zip_reader_{}.zip",
+            std::process::id()
+        ));
+        std::fs::write(&path, create_archive(None)).unwrap();
+        let reader = ZipAssetReader::open(&path).unwrap();
+
+        let (grass, clouds) = block_on(futures_lite::future::zip(
+            async {
+                let mut text = String::new();
+                let mut file = reader.read(Path::new("textures/grass.txt")).await?;
+                file.read_to_string(&mut text).await?;
+                Ok::<_, AssetReaderError>(text)
+            },
+            async {
+                let mut text = String::new();
+                let mut file = reader.read(Path::new("textures/sky/clouds.txt")).await?;
+                file.read_to_string(&mut text).await?;
+                Ok::<_, AssetReaderError>(text)
+            },
+        ));
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(grass.unwrap(), "grass");
+        assert_eq!(clouds.unwrap(), "clouds");
+    }
+}
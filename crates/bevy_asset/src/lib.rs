@@ -135,8 +135,9 @@
 //! After the loader is implemented, it needs to be registered with the [`AssetServer`] using [`App::register_asset_loader`](AssetApp::register_asset_loader).
 //! Once your asset type is loaded, you can use it in your game like any other asset type!
 //!
-//! If you want to save your assets back to disk, you should implement [`AssetSaver`](saver::AssetSaver) as well.
+//! If you want to save your assets back to disk, you should implement [`AssetSaver`] as well.
 //! This trait mirrors [`AssetLoader`] in structure, and works in tandem with [`AssetWriter`](io::AssetWriter), which mirrors [`AssetReader`](io::AssetReader).
+//! Savers registered with [`App::register_asset_saver`](AssetApp::register_asset_saver) can be used to save assets created at runtime with [`AssetServer::save`].
 
 #![expect(missing_docs, reason = "Not all docs are written yet, see #3492.")]
 #![cfg_attr(docsrs, feature(doc_cfg))]
@@ -209,6 +210,7 @@ pub use uuid;
 use crate::{
     io::{embedded::EmbeddedAssetRegistry, AssetSourceBuilder, AssetSourceBuilders, AssetSourceId},
     processor::{AssetProcessor, Process},
+    saver::AssetSaver,
 };
 use alloc::{
     string::{String, ToString},
@@ -261,6 +263,11 @@ pub struct AssetPlugin {
     /// Approved folders are [`AssetPlugin::file_path`] and the folder of each
     /// [`AssetSource`](io::AssetSource). Subfolders within these folders are also valid.
     pub unapproved_path_mode: UnapprovedPathMode,
+    /// The maximum number of asset loads that can run at the same time, or [`None`] if it is unlimited.
+    ///
+    /// When the limit is reached, loads are queued and started in [`LoadPriority`] order.
+    /// See [`AssetServer::set_max_concurrent_loads`].
+    pub max_concurrent_loads: Option<usize>,
 }
 
 /// Determines how to react to attempts to load assets not inside the approved folders.
@@ -340,6 +347,7 @@ impl Default for AssetPlugin {
             use_asset_processor_override: None,
             meta_check: AssetMetaCheck::default(),
             unapproved_path_mode: UnapprovedPathMode::default(),
+            max_concurrent_loads: None,
         }
     }
 }
@@ -414,6 +422,9 @@ impl Plugin for AssetPlugin {
                 }
             }
         }
+        app.world()
+            .resource::<AssetServer>()
+            .set_max_concurrent_loads(self.max_concurrent_loads);
         app.insert_resource(embedded)
             .init_asset::<LoadedFolder>()
             .init_asset::<LoadedUntypedAsset>()
@@ -552,6 +563,19 @@ impl VisitAssetDependencies for HashSet<UntypedHandle> {
 pub trait AssetApp {
     /// Registers the given `loader` in the [`App`]'s [`AssetServer`].
     fn register_asset_loader<L: AssetLoader>(&mut self, loader: L) -> &mut Self;
+    /// Registers the given `saver` in the [`App`]'s [`AssetServer`], enabling [`AssetServer::save`] for its asset type.
+    fn register_asset_saver<S: AssetSaver>(&mut self, saver: S) -> &mut Self
+    where
+        S::Asset: Clone;
+    /// Registers the given `saver` in the [`App`]'s [`AssetServer`] with the given `settings`, enabling
+    /// [`AssetServer::save`] for its asset type.
+    fn register_asset_saver_with_settings<S: AssetSaver>(
+        &mut self,
+        saver: S,
+        settings: S::Settings,
+    ) -> &mut Self
+    where
+        S::Asset: Clone;
     /// Registers the given `processor` in the [`App`]'s [`AssetProcessor`].
     fn register_asset_processor<P: Process>(&mut self, processor: P) -> &mut Self;
     /// Registers the given [`AssetSourceBuilder`] with the given `id`.
@@ -595,6 +619,28 @@ impl AssetApp for App {
         self
     }
 
+    fn register_asset_saver<S: AssetSaver>(&mut self, saver: S) -> &mut Self
+    where
+        S::Asset: Clone,
+    {
+        self.world().resource::<AssetServer>().register_saver(saver);
+        self
+    }
+
+    fn register_asset_saver_with_settings<S: AssetSaver>(
+        &mut self,
+        saver: S,
+        settings: S::Settings,
+    ) -> &mut Self
+    where
+        S::Asset: Clone,
+    {
+        self.world()
+            .resource::<AssetServer>()
+            .register_saver_with_settings(saver, settings);
+        self
+    }
+
     fn register_asset_processor<P: Process>(&mut self, processor: P) -> &mut Self {
         if let Some(asset_processor) = self.world().get_resource::<AssetProcessor>() {
             asset_processor.register_processor(processor);
@@ -710,14 +756,15 @@ mod tests {
         handle::Handle,
         io::{
             gated::{GateOpener, GatedReader},
-            memory::{Dir, MemoryAssetReader},
+            memory::{Dir, MemoryAssetReader, MemoryAssetWriter},
             AssetReader, AssetReaderError, AssetSourceBuilder, AssetSourceEvent, AssetSourceId,
             AssetWatcher, Reader,
         },
         loader::{AssetLoader, LoadContext},
+        saver::AssetSaver,
         Asset, AssetApp, AssetEvent, AssetId, AssetLoadError, AssetLoadFailedEvent, AssetPath,
-        AssetPlugin, AssetServer, Assets, InvalidGenerationError, LoadState, UnapprovedPathMode,
-        UntypedHandle,
+        AssetPlugin, AssetServer, Assets, InvalidGenerationError, LoadState, SaveAssetError,
+        UnapprovedPathMode, UntypedHandle,
     };
     use alloc::{
         boxed::Box,
@@ -741,11 +788,12 @@ mod tests {
     };
     use bevy_reflect::TypePath;
     use core::time::Duration;
+    use futures_lite::AsyncWriteExt;
     use serde::{Deserialize, Serialize};
     use std::path::{Path, PathBuf};
     use thiserror::Error;
 
-    #[derive(Asset, TypePath, Debug, Default)]
+    #[derive(Asset, TypePath, Debug, Default, Clone)]
     pub struct CoolText {
         pub text: String,
         pub embedded: String,
@@ -1542,6 +1590,166 @@ mod tests {
         );
     }
 
+    #[test]
+    fn labels_of_loaded_asset() {
+        let dir = Dir::default();
+        let path = "a.cool.ron";
+        dir.insert_asset_text(
+            Path::new(path),
+            r#"
+(
+    text: "a",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: ["foo", "bar"],
+)"#,
+        );
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader);
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let handle: Handle<CoolText> = asset_server.load(path);
+        assert!(asset_server.labels(&handle).is_empty());
+
+        gate_opener.open(path);
+        run_app_until(&mut app, |_| asset_server.is_loaded(&handle).then_some(()));
+        assert_eq!(
+            asset_server.labels(&handle),
+            [Box::from("bar"), Box::from("foo")]
+        );
+    }
+
+    #[test]
+    fn dependency_graph() {
+        let dir = Dir::default();
+        let a_path = "a.cool.ron";
+        let b_path = "b.cool.ron";
+        dir.insert_asset_text(
+            Path::new(a_path),
+            r#"
+(
+    text: "a",
+    dependencies: ["b.cool.ron"],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#,
+        );
+        dir.insert_asset_text(
+            Path::new(b_path),
+            r#"
+(
+    text: "b",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#,
+        );
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader);
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a_handle: Handle<CoolText> = asset_server.load(a_path);
+        let a_id = a_handle.id();
+
+        gate_opener.open(a_path);
+        run_app_until(&mut app, |_| asset_server.is_loaded(a_id).then_some(()));
+        let b_id = asset_server.get_path_id(b_path).unwrap();
+        let graph = asset_server.dependency_graph();
+        let a_node = graph.get(a_id).unwrap();
+        assert_eq!(a_node.path, Some(AssetPath::from(a_path)));
+        assert_eq!(a_node.dependencies, [b_id]);
+        assert_eq!(a_node.loading_dependencies, [b_id]);
+        assert!(graph.get(b_id).unwrap().load_state.is_loading());
+        assert_eq!(graph.dependents(b_id).collect::<Vec<_>>(), [a_id.untyped()]);
+
+        gate_opener.open(b_path);
+        run_app_until(&mut app, |_| {
+            asset_server.is_loaded_with_dependencies(a_id).then_some(())
+        });
+        let graph = asset_server.dependency_graph();
+        assert!(graph.get(a_id).unwrap().loading_dependencies.is_empty());
+        assert!(graph.get(b_id).unwrap().load_state.is_loaded());
+        assert_eq!(graph.recursive_dependencies(a_id), [b_id]);
+    }
+
+    #[test]
+    fn load_file_outside_asset_sources() {
+        struct TomlLoader;
+
+        impl AssetLoader for TomlLoader {
+            type Asset = CoolText;
+            type Settings = ();
+            type Error = std::io::Error;
+
+            async fn load(
+                &self,
+                reader: &mut dyn Reader,
+                _settings: &Self::Settings,
+                _load_context: &mut LoadContext<'_>,
+            ) -> Result<Self::Asset, Self::Error> {
+                let mut bytes = Vec::new();
+                reader.read_to_end(&mut bytes).await?;
+                Ok(CoolText {
+                    text: String::from_utf8_lossy(&bytes).into(),
+                    ..Default::default()
+                })
+            }
+
+            fn extensions(&self) -> &[&str] {
+                &["toml"]
+            }
+        }
+
+        let file_app = |mode| {
+            let mut app = App::new();
+            app.add_plugins((
+                TaskPoolPlugin::default(),
+                AssetPlugin {
+                    unapproved_path_mode: mode,
+                    ..Default::default()
+                },
+            ))
+            .init_asset::<CoolText>()
+            .register_asset_loader(TomlLoader);
+            app
+        };
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+
+        // Absolute paths are unapproved, so they can't be loaded when they're forbidden.
+        let app = file_app(UnapprovedPathMode::Forbid);
+        let asset_server = app.world().resource::<AssetServer>();
+        assert!(matches!(
+            bevy_tasks::block_on(asset_server.load_file_override_async(&path)),
+            Err(AssetLoadError::UnapprovedPath { .. })
+        ));
+
+        let mut app = file_app(UnapprovedPathMode::Deny);
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        assert!(matches!(
+            bevy_tasks::block_on(asset_server.load_file_async(&path)),
+            Err(AssetLoadError::UnapprovedPath { .. })
+        ));
+
+        let handle = asset_server.load_file_override(&path);
+        run_app_until(&mut app, |world| {
+            let loaded = world
+                .resource::<Assets<crate::LoadedUntypedAsset>>()
+                .get(&handle)?;
+            let text = get::<CoolText>(world, loaded.handle.id().typed())?;
+            assert!(text.text.contains("name = \"bevy_asset\""));
+            Some(())
+        });
+
+        let handle = asset_server.load_file_override(path.with_extension("missing"));
+        run_app_until(&mut app, |_| {
+            asset_server.load_state(&handle).is_failed().then_some(())
+        });
+    }
+
     #[test]
     fn manual_asset_management() {
         let dir = Dir::default();
@@ -2670,4 +2878,100 @@ mod tests {
         // assert_eq!(get_started_load_count(app.world()), 1);
         assert_eq!(get_started_load_count(app.world()), 2);
     }
+
+    #[test]
+    fn save_runtime_asset() {
+        struct CoolTextSaver;
+
+        #[derive(Default, Serialize, Deserialize)]
+        struct CoolTextSaverSettings {
+            suffix: String,
+        }
+
+        impl AssetSaver for CoolTextSaver {
+            type Asset = CoolText;
+            type Settings = CoolTextSaverSettings;
+            type OutputLoader = CoolTextLoader;
+            type Error = std::io::Error;
+
+            async fn save(
+                &self,
+                writer: &mut crate::io::Writer,
+                asset: crate::saver::SavedAsset<'_, Self::Asset>,
+                settings: &Self::Settings,
+            ) -> Result<(), Self::Error> {
+                let ron = CoolTextRon {
+                    text: format!("{}{}", asset.text, settings.suffix),
+                    dependencies: vec![],
+                    embedded_dependencies: vec![],
+                    sub_texts: vec![],
+                };
+                writer
+                    .write_all(ron::ser::to_string(&ron).unwrap().as_bytes())
+                    .await
+            }
+        }
+
+        let dir = Dir::default();
+        let reader_dir = dir.clone();
+        let writer_dir = dir.clone();
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSourceBuilder::new(move || {
+                Box::new(MemoryAssetReader {
+                    root: reader_dir.clone(),
+                })
+            })
+            .with_writer(move |_| {
+                Some(Box::new(MemoryAssetWriter {
+                    root: writer_dir.clone(),
+                }))
+            }),
+        )
+        .add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+        .init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader)
+        .register_asset_saver_with_settings(
+            CoolTextSaver,
+            CoolTextSaverSettings {
+                suffix: "!".to_string(),
+            },
+        );
+
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<CoolText>>()
+            .add(CoolText {
+                text: "procedural".to_string(),
+                ..Default::default()
+            });
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let saved = asset_server.save(&handle, "saved.cool.ron");
+        let missing_saver = asset_server.save(
+            &app.world_mut()
+                .resource_mut::<Assets<SubText>>()
+                .add(SubText {
+                    text: "no saver".to_string(),
+                }),
+            "sub.txt",
+        );
+
+        let saved_path = Path::new("saved.cool.ron");
+        run_app_until(&mut app, |_| dir.get_metadata(saved_path).map(|_| ()));
+        assert!(bevy_tasks::block_on(saved).is_ok());
+        assert!(matches!(
+            bevy_tasks::block_on(missing_saver),
+            Err(SaveAssetError::MissingSaver(_))
+        ));
+
+        // The saved asset can be loaded back.
+        let loaded: Handle<CoolText> = asset_server.load("saved.cool.ron");
+        run_app_until(&mut app, |world| {
+            let text = &world.resource::<Assets<CoolText>>().get(&loaded)?.text;
+            assert_eq!(text, "procedural!");
+            Some(())
+        });
+    }
 }
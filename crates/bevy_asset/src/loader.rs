@@ -189,6 +189,13 @@ impl<A: Asset> LoadedAsset<A> {
     pub fn iter_labels(&self) -> impl Iterator<Item = &str> {
         self.labeled_assets.keys().map(|s| &**s)
     }
+
+    /// Iterate over all "labeled assets" in the loaded asset, along with their labels.
+    pub fn labeled_assets(&self) -> impl Iterator<Item = (&str, &ErasedLoadedAsset)> {
+        self.labeled_assets
+            .iter()
+            .map(|(label, labeled)| (&**label, &labeled.asset))
+    }
 }
 
 impl<A: Asset> From<A> for LoadedAsset<A> {
@@ -251,6 +258,13 @@ impl ErasedLoadedAsset {
         self.labeled_assets.keys().map(|s| &**s)
     }
 
+    /// Iterate over all "labeled assets" in the loaded asset, along with their labels.
+    pub fn labeled_assets(&self) -> impl Iterator<Item = (&str, &ErasedLoadedAsset)> {
+        self.labeled_assets
+            .iter()
+            .map(|(label, labeled)| (&**label, &labeled.asset))
+    }
+
     /// Cast this loaded asset as the given type. If the type does not match,
     /// the original type-erased asset is returned.
     pub fn downcast<A: Asset>(mut self) -> Result<LoadedAsset<A>, ErasedLoadedAsset> {
@@ -507,6 +521,25 @@ impl<'a> LoadContext<'a> {
         Ok(bytes)
     }
 
+    /// Returns `true` if the asset at the given path can be read.
+    ///
+    /// Unlike [`LoadContext::read_asset_bytes`], the asset isn't read, nor added to the loader
+    /// dependencies of this asset.
+    pub async fn asset_exists<'b>(&self, path: impl Into<AssetPath<'b>>) -> bool {
+        let path = path.into();
+        let Ok(source) = self.asset_server.get_source(path.source()) else {
+            return false;
+        };
+        let asset_reader = match self.asset_server.mode() {
+            AssetServerMode::Unprocessed => source.reader(),
+            AssetServerMode::Processed => match source.processed_reader() {
+                Ok(asset_reader) => asset_reader,
+                Err(_) => return false,
+            },
+        };
+        asset_reader.read(path.path()).await.is_ok()
+    }
+
     /// Returns a handle to an asset of type `A` with the label `label`. This [`LoadContext`] must produce an asset of the
     /// given type and the given label or the dependencies of this asset will never be considered "fully loaded". However you
     /// can call this method before _or_ after adding the labeled asset.
@@ -5,7 +5,7 @@ use crate::{
     io::Reader,
     meta::{meta_transform_settings, AssetMetaDyn, MetaTransform, Settings},
     Asset, AssetLoadError, AssetPath, ErasedAssetLoader, ErasedLoadedAsset, Handle, LoadContext,
-    LoadDirectError, LoadedAsset, LoadedUntypedAsset, UntypedHandle,
+    LoadDirectError, LoadPriority, LoadedAsset, LoadedUntypedAsset, UntypedHandle,
 };
 use alloc::{borrow::ToOwned, boxed::Box, sync::Arc};
 use core::any::TypeId;
@@ -310,6 +310,7 @@ impl NestedLoader<'_, '_, StaticTyped, Deferred> {
                 self.meta_transform,
                 (),
                 true,
+                LoadPriority::Normal,
             )
         } else {
             self.load_context
@@ -83,6 +83,11 @@ pub struct ProcessedInfo {
     pub full_hash: AssetHash,
     /// Information about the "process dependencies" used to process this asset.
     pub process_dependencies: Vec<ProcessDependencyInfo>,
+    /// Information about the additional assets written while processing this asset.
+    ///
+    /// See [`ProcessContext::write_output`](crate::processor::ProcessContext::write_output).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outputs: Vec<ProcessedOutputInfo>,
 }
 
 /// Information about a dependency used to process an asset. This is used to determine whether an asset's "process dependency"
@@ -91,6 +96,33 @@ pub struct ProcessedInfo {
 pub struct ProcessDependencyInfo {
     pub full_hash: AssetHash,
     pub path: AssetPath<'static>,
+    /// Whether this is a "deferred" dependency: an asset that is referenced by a handle in the processed asset, but whose
+    /// value isn't used to process it. For these, `full_hash` stores the dependency's own [`ProcessedInfo::hash`], as the
+    /// processed asset doesn't change when the dependencies of the dependency do.
+    #[serde(default, skip_serializing_if = "core::ops::Not::not")]
+    pub deferred: bool,
+}
+
+impl ProcessDependencyInfo {
+    /// Returns the hash of `processed_info` that is compared against [`ProcessDependencyInfo::full_hash`] to
+    /// determine whether this dependency has changed.
+    pub fn live_hash(&self, processed_info: &ProcessedInfo) -> AssetHash {
+        if self.deferred {
+            processed_info.hash
+        } else {
+            processed_info.full_hash
+        }
+    }
+}
+
+/// Information about an additional asset written while processing another asset. Outputs are owned by the asset that
+/// produced them: they are removed when it is removed, and are reprocessed whenever it is.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProcessedOutputInfo {
+    /// A hash of the output asset bytes and its .meta data.
+    pub full_hash: AssetHash,
+    /// The path of the output asset.
+    pub path: AssetPath<'static>,
 }
 
 /// This is a minimal counterpart to [`AssetMeta`] that exists to speed up (or enable) serialization in cases where the whole [`AssetMeta`] isn't
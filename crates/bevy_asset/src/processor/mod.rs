@@ -36,6 +36,18 @@
 //! - [`Process`]: a flexible low-level API for processing assets in arbitrary ways.
 //!
 //! In most cases, [`LoadTransformAndSave`] should be sufficient.
+//!
+//! Third-party processors should be written against these traits, along with [`AssetLoader`](crate::AssetLoader),
+//! [`AssetSaver`](crate::saver::AssetSaver) and [`ProcessContext`]. The remaining processor types, such as
+//! [`ErasedProcessor`] and the transaction log, are implementation details of the [`AssetProcessor`].
+//!
+//! # Multiple outputs
+//!
+//! A single source asset can be processed into multiple assets, for example a scene file into its meshes, materials and
+//! animations. A [`Process`] implementation writes its "main" asset to the provided writer, and any additional asset with
+//! [`ProcessContext::write_output`]. Outputs are tracked as part of the source asset's [`ProcessedInfo`]: they are rewritten
+//! when the source asset (or one of its process dependencies) changes, and removed when they are no longer written or the
+//! source asset is removed. Other assets can depend on outputs while processing, like any other processed asset.
 
 mod log;
 mod process;
@@ -51,7 +63,7 @@ use crate::{
     },
     meta::{
         get_asset_hash, get_full_asset_hash, AssetAction, AssetActionMinimal, AssetHash, AssetMeta,
-        AssetMetaDyn, AssetMetaMinimal, ProcessedInfo, ProcessedInfoMinimal,
+        AssetMetaDyn, AssetMetaMinimal, ProcessedInfo, ProcessedInfoMinimal, ProcessedOutputInfo,
     },
     AssetLoadError, AssetMetaCheck, AssetPath, AssetServer, AssetServerMode, DeserializeMetaError,
     MissingAssetLoaderForExtensionError, UnapprovedPathMode, WriteDefaultMetaError,
@@ -649,6 +661,12 @@ impl AssetProcessor {
             let _write_lock = info.file_transaction_lock.write();
             self.remove_processed_asset_and_meta(source, asset_path.path())
                 .await;
+            if let Some(processed_info) = &info.processed_info {
+                for output in &processed_info.outputs {
+                    self.remove_processed_asset_and_meta(source, output.path.path())
+                        .await;
+                }
+            }
         }
         infos.remove(&asset_path).await;
     }
@@ -832,6 +850,9 @@ impl AssetProcessor {
                 asset_infos.get_or_insert(AssetPath::from(path).with_source(source.id()));
             }
 
+            // Paths without a source asset, which are removed unless they are the output of another asset.
+            let mut orphan_paths = Vec::new();
+            let mut outputs = Vec::new();
             for path in processed_paths {
                 let mut dependencies = Vec::new();
                 let asset_path = AssetPath::from(path).with_source(source.id());
@@ -851,6 +872,9 @@ impl AssetProcessor {
                                         {
                                             dependencies.push(process_dependency_info.path.clone());
                                         }
+                                        for output in &processed_info.outputs {
+                                            outputs.push((asset_path.clone(), output.clone()));
+                                        }
                                     }
                                     info.processed_info = minimal.processed_info;
                                 }
@@ -868,15 +892,27 @@ impl AssetProcessor {
                         }
                     }
                 } else {
-                    trace!("Removing processed data for non-existent asset {asset_path}");
-                    self.remove_processed_asset_and_meta(source, asset_path.path())
-                        .await;
+                    orphan_paths.push(asset_path.clone());
                 }
 
                 for dependency in dependencies {
                     asset_infos.add_dependent(&dependency, asset_path.clone());
                 }
             }
+
+            for (owner, output) in outputs {
+                if asset_infos.is_available_output(&output.path, &owner) {
+                    asset_infos.insert_output(&owner, &output);
+                }
+            }
+
+            for asset_path in orphan_paths {
+                if asset_infos.get(&asset_path).is_none() {
+                    trace!("Removing processed data for non-existent asset {asset_path}");
+                    self.remove_processed_asset_and_meta(source, asset_path.path())
+                        .await;
+                }
+            }
         }
 
         self.data
@@ -1040,13 +1076,15 @@ impl AssetProcessor {
             hash: new_hash,
             full_hash: new_hash,
             process_dependencies: Vec::new(),
+            outputs: Vec::new(),
         };
 
-        {
+        let old_outputs = {
             let infos = self.data.processing_state.asset_infos.read().await;
-            if let Some(current_processed_info) = infos
+            let current_processed_info = infos
                 .get(asset_path)
-                .and_then(|i| i.processed_info.as_ref())
+                .and_then(|i| i.processed_info.as_ref());
+            if let Some(current_processed_info) = current_processed_info
                 && current_processed_info.hash == new_hash
             {
                 let mut dependency_changed = false;
@@ -1054,7 +1092,12 @@ impl AssetProcessor {
                     let live_hash = infos
                         .get(&current_dep_info.path)
                         .and_then(|i| i.processed_info.as_ref())
-                        .map(|i| i.full_hash);
+                        .map(|i| current_dep_info.live_hash(i));
+                    // Deferred dependencies that haven't been processed are recorded with a default hash
+                    let live_hash = match live_hash {
+                        None if current_dep_info.deferred => Some(AssetHash::default()),
+                        live_hash => live_hash,
+                    };
                     if live_hash != Some(current_dep_info.full_hash) {
                         dependency_changed = true;
                         break;
@@ -1064,7 +1107,10 @@ impl AssetProcessor {
                     return Ok(ProcessResult::SkippedNotChanged);
                 }
             }
-        }
+            current_processed_info
+                .map(|info| info.outputs.clone())
+                .unwrap_or_default()
+        };
 
         // Note: this lock must remain alive until all processed asset and meta writes have finished (or failed)
         // See ProcessedAssetInfo::file_transaction_lock docs for more info
@@ -1081,8 +1127,13 @@ impl AssetProcessor {
         if let Some(processor) = processor {
             let mut writer = processed_writer.write(path).await.map_err(writer_err)?;
             let mut processed_meta = {
-                let mut context =
-                    ProcessContext::new(self, asset_path, &asset_bytes, &mut new_processed_info);
+                let mut context = ProcessContext::new(
+                    self,
+                    processed_writer,
+                    asset_path,
+                    &asset_bytes,
+                    &mut new_processed_info,
+                );
                 processor
                     .process(&mut context, source_meta, &mut *writer)
                     .await?
@@ -1123,6 +1174,16 @@ impl AssetProcessor {
                 .await
                 .map_err(writer_err)?;
         }
+        for old_output in old_outputs {
+            if !new_processed_info
+                .outputs
+                .iter()
+                .any(|output| output.path == old_output.path)
+            {
+                self.remove_processed_asset_and_meta(source, old_output.path.path())
+                    .await;
+            }
+        }
         self.log_end_processing(asset_path).await;
 
         Ok(ProcessResult::Processed(new_processed_info))
@@ -1432,6 +1493,9 @@ pub(crate) struct ProcessorAssetInfo {
     processed_info: Option<ProcessedInfo>,
     /// Paths of assets that depend on this asset when they are being processed.
     dependents: HashSet<AssetPath<'static>>,
+    /// The path of the asset that wrote this asset as one of its outputs, if any. Outputs have no source asset, and
+    /// share the `file_transaction_lock` of their owner.
+    owner: Option<AssetPath<'static>>,
     status: Option<ProcessStatus>,
     /// A lock that controls read/write access to processed asset files. The lock is shared for both the asset bytes and the meta bytes.
     /// _This lock must be locked whenever a read or write to processed assets occurs_
@@ -1456,6 +1520,7 @@ impl Default for ProcessorAssetInfo {
         Self {
             processed_info: Default::default(),
             dependents: Default::default(),
+            owner: None,
             file_transaction_lock: Default::default(),
             status: None,
             status_sender,
@@ -1510,6 +1575,39 @@ impl ProcessorAssetInfos {
         self.infos.get_mut(asset_path)
     }
 
+    /// Returns `true` if `asset_path` can be written as an output of `owner`, which is the case if it is not a source
+    /// asset nor the output of another asset.
+    pub(crate) fn is_available_output(
+        &self,
+        asset_path: &AssetPath<'static>,
+        owner: &AssetPath<'static>,
+    ) -> bool {
+        self.get(asset_path)
+            .is_none_or(|info| info.owner.as_ref() == Some(owner))
+    }
+
+    /// Inserts the info of an output of `owner`, which must exist.
+    fn insert_output(
+        &mut self,
+        owner: &AssetPath<'static>,
+        output: &ProcessedOutputInfo,
+    ) -> &mut ProcessorAssetInfo {
+        let file_transaction_lock = self
+            .get(owner)
+            .expect("owner info should exist")
+            .file_transaction_lock
+            .clone();
+        let info = self.get_or_insert(output.path.clone());
+        info.owner = Some(owner.clone());
+        info.file_transaction_lock = file_transaction_lock;
+        info.processed_info = Some(ProcessedInfo {
+            hash: output.full_hash,
+            full_hash: output.full_hash,
+            ..Default::default()
+        });
+        info
+    }
+
     fn add_dependent(&mut self, asset_path: &AssetPath<'static>, dependent: AssetPath<'static>) {
         if let Some(info) = self.get_mut(asset_path) {
             info.dependents.insert(dependent);
@@ -1538,17 +1636,45 @@ impl ProcessorAssetInfos {
                     .get_mut(&asset_path)
                     .and_then(|i| i.processed_info.take());
                 if let Some(old_processed_info) = old_processed_info {
+                    for old_output in &old_processed_info.outputs {
+                        if !processed_info
+                            .outputs
+                            .iter()
+                            .any(|output| output.path == old_output.path)
+                        {
+                            Box::pin(self.remove(&old_output.path)).await;
+                        }
+                    }
                     self.clear_dependencies(&asset_path, old_processed_info);
                 }
 
                 // populate new dependents
+                let mut dependency_changed = false;
                 for process_dependency_info in &processed_info.process_dependencies {
                     self.add_dependent(&process_dependency_info.path, asset_path.to_owned());
+                    // A dependency might have finished processing after it was read, but before this asset was
+                    // registered as its dependent. In that case, it didn't queue this asset for reprocessing.
+                    if let Some(dependency_info) = self
+                        .get(&process_dependency_info.path)
+                        .and_then(|info| info.processed_info.as_ref())
+                    {
+                        dependency_changed |= process_dependency_info.live_hash(dependency_info)
+                            != process_dependency_info.full_hash;
+                    }
                 }
-                let info = self.get_or_insert(asset_path);
+                let outputs = processed_info.outputs.clone();
+                let info = self.get_or_insert(asset_path.clone());
                 info.processed_info = Some(processed_info);
                 info.update_status(ProcessStatus::Processed).await;
-                let dependents = info.dependents.iter().cloned().collect::<Vec<_>>();
+                let mut dependents = info.dependents.iter().cloned().collect::<Vec<_>>();
+                for output in &outputs {
+                    let info = self.insert_output(&asset_path, output);
+                    info.update_status(ProcessStatus::Processed).await;
+                    dependents.extend(info.dependents.iter().cloned());
+                }
+                if dependency_changed {
+                    dependents.push(asset_path);
+                }
                 for path in dependents {
                     let _ = reprocess_sender
                         .send((path.source().clone_owned(), path.path().to_owned()))
@@ -1565,6 +1691,8 @@ impl ProcessorAssetInfos {
                 // If "block until latest state is reflected" is required, we can easily add a less granular
                 // "block until first pass finished" mode
                 info.update_status(ProcessStatus::Processed).await;
+                self.update_output_status(&asset_path, ProcessStatus::Processed)
+                    .await;
             }
             Ok(ProcessResult::Ignored) => {
                 debug!("Skipping processing (ignored) \"{}\"", asset_path);
@@ -1593,10 +1721,17 @@ impl ProcessorAssetInfos {
                         hash: AssetHash::default(),
                         full_hash: AssetHash::default(),
                         process_dependencies: vec![],
+                        outputs: info
+                            .processed_info
+                            .take()
+                            .map(|info| info.outputs)
+                            .unwrap_or_default(),
                     });
                     self.add_dependent(dependency.path(), asset_path.to_owned());
                 }
 
+                self.update_output_status(&asset_path, ProcessStatus::Failed)
+                    .await;
                 let info = self.get_mut(&asset_path).expect("info should exist");
                 info.update_status(ProcessStatus::Failed).await;
             }
@@ -1608,6 +1743,9 @@ impl ProcessorAssetInfos {
         let info = self.infos.remove(asset_path);
         if let Some(info) = info {
             if let Some(processed_info) = info.processed_info {
+                for output in &processed_info.outputs {
+                    Box::pin(self.remove(&output.path)).await;
+                }
                 self.clear_dependencies(asset_path, processed_info);
             }
             // Tell all listeners this asset does not exist
@@ -1651,6 +1789,12 @@ impl ProcessorAssetInfos {
                     .insert(old.clone(), core::mem::take(&mut info.dependents));
             }
             if let Some(processed_info) = &info.processed_info {
+                // Outputs keep their path, but are now owned by the new path.
+                for output in &processed_info.outputs {
+                    if let Some(info) = self.infos.get_mut(&output.path) {
+                        info.owner = Some(new.clone());
+                    }
+                }
                 // Update "dependent" lists for this asset's "process dependencies" to use new path.
                 for dep in &processed_info.process_dependencies {
                     if let Some(info) = self.infos.get_mut(&dep.path) {
@@ -1671,6 +1815,7 @@ impl ProcessorAssetInfos {
             let dependents: Vec<AssetPath<'static>> = {
                 let new_info = self.get_or_insert(new.clone());
                 new_info.processed_info = info.processed_info;
+                new_info.owner = info.owner;
                 new_info.status = info.status;
                 // Ensure things waiting on the new path are informed of the status of this asset
                 if let Some(status) = new_info.status {
@@ -1694,6 +1839,24 @@ impl ProcessorAssetInfos {
         }
     }
 
+    /// Updates the status of every output of `asset_path`.
+    async fn update_output_status(
+        &mut self,
+        asset_path: &AssetPath<'static>,
+        status: ProcessStatus,
+    ) {
+        let outputs = self
+            .get(asset_path)
+            .and_then(|info| info.processed_info.as_ref())
+            .map(|info| info.outputs.clone())
+            .unwrap_or_default();
+        for output in outputs {
+            if let Some(info) = self.get_mut(&output.path) {
+                info.update_status(status).await;
+            }
+        }
+    }
+
     fn clear_dependencies(&mut self, asset_path: &AssetPath<'static>, removed_info: ProcessedInfo) {
         for old_load_dep in removed_info.process_dependencies {
             if let Some(info) = self.infos.get_mut(&old_load_dep.path) {
@@ -1,9 +1,12 @@
 use crate::{
     io::{
-        AssetReaderError, AssetWriterError, MissingAssetWriterError,
+        AssetReaderError, AssetWriterError, ErasedAssetWriter, MissingAssetWriterError,
         MissingProcessedAssetReaderError, MissingProcessedAssetWriterError, SliceReader, Writer,
     },
-    meta::{AssetAction, AssetMeta, AssetMetaDyn, ProcessDependencyInfo, ProcessedInfo, Settings},
+    meta::{
+        get_asset_hash, AssetAction, AssetMeta, AssetMetaDyn, ProcessDependencyInfo, ProcessedInfo,
+        ProcessedOutputInfo, Settings,
+    },
     processor::AssetProcessor,
     saver::{AssetSaver, SavedAsset},
     transformer::{AssetTransformer, IdentityAssetTransformer, TransformedAsset},
@@ -14,6 +17,7 @@ use alloc::{
     borrow::ToOwned,
     boxed::Box,
     string::{String, ToString},
+    vec::Vec,
 };
 use bevy_tasks::{BoxedFuture, ConditionalSendFuture};
 use core::marker::PhantomData;
@@ -158,6 +162,12 @@ pub enum ProcessError {
     AssetTransformError(Box<dyn core::error::Error + Send + Sync + 'static>),
     #[error("Assets without extensions are not supported.")]
     ExtensionRequired,
+    #[error("The output path '{0}' is invalid. Outputs must be written to a path in the same asset source, without a label.")]
+    #[from(ignore)]
+    InvalidOutputPath(String),
+    #[error("The output '{path}' conflicts with another asset")]
+    #[from(ignore)]
+    OutputConflict { path: AssetPath<'static> },
 }
 
 impl<Loader, Transformer, Saver> Process for LoadTransformAndSave<Loader, Transformer, Saver>
@@ -279,6 +289,7 @@ pub struct ProcessContext<'a> {
     ///
     /// [`AssetServer`]: crate::server::AssetServer
     processor: &'a AssetProcessor,
+    processed_writer: &'a dyn ErasedAssetWriter,
     path: &'a AssetPath<'static>,
     asset_bytes: &'a [u8],
 }
@@ -286,12 +297,14 @@ pub struct ProcessContext<'a> {
 impl<'a> ProcessContext<'a> {
     pub(crate) fn new(
         processor: &'a AssetProcessor,
+        processed_writer: &'a dyn ErasedAssetWriter,
         path: &'a AssetPath<'static>,
         asset_bytes: &'a [u8],
         new_processed_info: &'a mut ProcessedInfo,
     ) -> Self {
         Self {
             processor,
+            processed_writer,
             path,
             asset_bytes,
             new_processed_info,
@@ -302,6 +315,10 @@ impl<'a> ProcessContext<'a> {
     /// This will take the "load dependencies" (asset values used when loading with `L`]) and
     /// register them as "process dependencies" because they are asset values required to process the
     /// current asset.
+    ///
+    /// The "deferred" dependencies of the loaded asset (handles it holds, such as the textures referenced by a material)
+    /// are registered as "process dependencies" too. This ensures the current asset is reprocessed (and hot-reloaded)
+    /// whenever any of its inputs change.
     pub async fn load_source_asset<L: AssetLoader>(
         &mut self,
         meta: AssetMeta<L, ()>,
@@ -319,11 +336,156 @@ impl<'a> ProcessContext<'a> {
                 .push(ProcessDependencyInfo {
                     full_hash: *full_hash,
                     path: path.to_owned(),
+                    deferred: false,
                 });
         }
+        self.add_deferred_process_dependencies(&loaded_asset).await;
         Ok(loaded_asset)
     }
 
+    /// Registers the deferred dependencies of `loaded_asset` (and its labeled assets) as "process dependencies". Their
+    /// values aren't read while processing, so their current hash is recorded rather than waiting for them to be
+    /// processed. Dependencies that haven't been processed yet are recorded with a default hash, which is corrected by
+    /// reprocessing this asset once they finish.
+    async fn add_deferred_process_dependencies(&mut self, loaded_asset: &ErasedLoadedAsset) {
+        let dependency_paths = {
+            let server_infos = self.processor.server.read_infos();
+            let mut paths = Vec::new();
+            let dependencies = loaded_asset.dependencies.iter().chain(
+                loaded_asset
+                    .labeled_assets
+                    .values()
+                    .flat_map(|labeled| labeled.asset.dependencies.iter()),
+            );
+            for index in dependencies {
+                let Some(path) = server_infos
+                    .get(*index)
+                    .and_then(|info| info.path.as_ref())
+                    .map(AssetPath::without_label)
+                else {
+                    continue;
+                };
+                if path == self.path.without_label() || paths.contains(&path) {
+                    continue;
+                }
+                paths.push(path.into_owned());
+            }
+            paths
+        };
+        let processor_infos = self
+            .processor
+            .data
+            .processing_state
+            .asset_infos
+            .read()
+            .await;
+        for path in dependency_paths {
+            if self
+                .new_processed_info
+                .process_dependencies
+                .iter()
+                .any(|dependency| dependency.path == path)
+            {
+                continue;
+            }
+            let full_hash = processor_infos
+                .get(&path)
+                .and_then(|info| info.processed_info.as_ref())
+                .map(|info| info.hash)
+                .unwrap_or_default();
+            self.new_processed_info
+                .process_dependencies
+                .push(ProcessDependencyInfo {
+                    full_hash,
+                    path,
+                    deferred: true,
+                });
+        }
+    }
+
+    /// Saves `asset` with `saver` as an additional output of the asset being processed, and returns its path.
+    ///
+    /// This allows a single source asset to be processed into multiple assets, for example a scene file into its meshes,
+    /// materials and animations. The output is written to `path`, resolved relative to the asset being processed (see
+    /// [`AssetPath::resolve_embed`]), and can then be loaded like any other processed asset using [`AssetSaver::OutputLoader`].
+    ///
+    /// Outputs are owned by the asset being processed:
+    /// - they are only available once it has finished processing,
+    /// - they are rewritten whenever it is reprocessed, and outputs that are no longer written are removed,
+    /// - they are removed when it is removed,
+    /// - assets that depend on them during processing are reprocessed when they change.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProcessError::InvalidOutputPath`] if `path` is not in the same asset source or has a label, and
+    /// [`ProcessError::OutputConflict`] if it refers to a source asset or to an output of another asset.
+    pub async fn write_output<S: AssetSaver>(
+        &mut self,
+        path: &str,
+        saver: &S,
+        asset: SavedAsset<'_, S::Asset>,
+        settings: &S::Settings,
+    ) -> Result<AssetPath<'static>, ProcessError> {
+        let output_path = self
+            .path
+            .resolve_embed(path)
+            .ok()
+            .filter(|output_path| {
+                output_path.source() == self.path.source() && output_path.label().is_none()
+            })
+            .ok_or_else(|| ProcessError::InvalidOutputPath(path.to_string()))?;
+        let conflicts = self
+            .new_processed_info
+            .outputs
+            .iter()
+            .any(|output| output.path == output_path)
+            || !self
+                .processor
+                .data
+                .processing_state
+                .asset_infos
+                .read()
+                .await
+                .is_available_output(&output_path, self.path);
+        if conflicts {
+            return Err(ProcessError::OutputConflict { path: output_path });
+        }
+
+        let mut bytes = Vec::new();
+        let loader_settings = saver
+            .save(&mut bytes, asset, settings)
+            .await
+            .map_err(|error| ProcessError::AssetSaveError(error.into()))?;
+        let mut meta = AssetMeta::<S::OutputLoader, ()>::new(AssetAction::Load {
+            loader: core::any::type_name::<S::OutputLoader>().to_string(),
+            settings: loader_settings,
+        });
+        let full_hash = get_asset_hash(&AssetMetaDyn::serialize(&meta), &bytes);
+        meta.processed_info = Some(ProcessedInfo {
+            hash: full_hash,
+            full_hash,
+            ..Default::default()
+        });
+
+        let writer_err = |err| ProcessError::AssetWriterError {
+            path: output_path.clone(),
+            err,
+        };
+        self.processed_writer
+            .write_bytes(output_path.path(), &bytes)
+            .await
+            .map_err(writer_err)?;
+        self.processed_writer
+            .write_meta_bytes(output_path.path(), &AssetMetaDyn::serialize(&meta))
+            .await
+            .map_err(writer_err)?;
+        self.new_processed_info.outputs.push(ProcessedOutputInfo {
+            full_hash,
+            path: output_path.clone(),
+        });
+        Ok(output_path)
+    }
+
     /// The path of the asset being processed.
     #[inline]
     pub fn path(&self) -> &AssetPath<'static> {
@@ -1,6 +1,7 @@
 use alloc::{
     boxed::Box,
     collections::BTreeMap,
+    format,
     string::{String, ToString},
     sync::Arc,
     vec,
@@ -28,9 +29,10 @@ use crate::{
         AssetReader, AssetReaderError, AssetSourceBuilder, AssetSourceEvent, AssetSourceId,
         AssetWatcher, PathStream, Reader,
     },
+    meta::{AssetAction, AssetMeta},
     processor::{
-        AssetProcessor, LoadTransformAndSave, LogEntry, ProcessorState, ProcessorTransactionLog,
-        ProcessorTransactionLogFactory,
+        AssetProcessor, LoadTransformAndSave, LogEntry, Process, ProcessContext, ProcessError,
+        ProcessorState, ProcessorTransactionLog, ProcessorTransactionLogFactory,
     },
     saver::AssetSaver,
     tests::{run_app_until, CoolText, CoolTextLoader, CoolTextRon, SubText},
@@ -1261,6 +1263,118 @@ fn nested_loads_of_processed_asset_reprocesses_on_reload() {
     assert_eq!(get_process_count(), 7);
 }
 
+#[test]
+fn deferred_dependencies_of_processed_asset_reprocess_on_change() {
+    let AppWithProcessor {
+        mut app,
+        source_gate,
+        default_source_dirs:
+            ProcessingDirs {
+                source: source_dir,
+                processed: processed_dir,
+                source_event_sender: source_events,
+            },
+        ..
+    } = create_app_with_asset_processor(&[]);
+
+    struct CountProcessing(Arc<Mutex<HashMap<String, u32>>>);
+
+    impl MutateAsset<CoolText> for CountProcessing {
+        fn mutate(&self, asset: &mut CoolText) {
+            *self
+                .0
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .entry(asset.text.clone())
+                .or_default() += 1;
+        }
+    }
+
+    let process_counts = Arc::new(Mutex::new(HashMap::<String, u32>::default()));
+
+    type CoolTextProcessor = LoadTransformAndSave<
+        CoolTextLoader,
+        RootAssetTransformer<CountProcessing, CoolText>,
+        CoolTextSaver,
+    >;
+    app.init_asset::<CoolText>()
+        .register_asset_loader(CoolTextLoader)
+        .register_asset_processor(CoolTextProcessor::new(
+            RootAssetTransformer::new(CountProcessing(process_counts.clone())),
+            CoolTextSaver,
+        ))
+        .set_default_asset_processor::<CoolTextProcessor>("cool.ron");
+
+    let guard = source_gate.write_blocking();
+
+    // The material only holds a handle to the texture, so processing it doesn't read the texture.
+    let material_ron = r#"(
+    text: "material",
+    dependencies: [
+        "texture.cool.ron",
+    ],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+    source_dir.insert_asset_text(Path::new("material.cool.ron"), material_ron);
+    source_dir.insert_asset_text(
+        Path::new("texture.cool.ron"),
+        &serialize_as_cool_text("texture"),
+    );
+    source_dir.insert_asset_text(
+        Path::new("unrelated.cool.ron"),
+        &serialize_as_cool_text("unrelated"),
+    );
+
+    run_app_until_finished_processing(&mut app, guard);
+
+    assert_eq!(
+        read_asset_as_string(&processed_dir, Path::new("material.cool.ron")),
+        material_ron
+    );
+
+    let get_process_counts = || {
+        process_counts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    };
+    let initial_counts = get_process_counts();
+
+    // Changing the texture should reprocess the material that references it, but nothing else.
+    let guard = source_gate.write_blocking();
+
+    source_dir.insert_asset_text(
+        Path::new("texture.cool.ron"),
+        &serialize_as_cool_text("texture changed"),
+    );
+    source_events
+        .send_blocking(AssetSourceEvent::ModifiedAsset("texture.cool.ron".into()))
+        .unwrap();
+
+    run_app_until_finished_processing(&mut app, guard);
+
+    assert_eq!(
+        read_asset_as_string(&processed_dir, Path::new("texture.cool.ron")),
+        serialize_as_cool_text("texture changed")
+    );
+    let counts = get_process_counts();
+    assert_eq!(counts["texture changed"], 1);
+    assert_eq!(counts["material"], initial_counts["material"] + 1);
+    assert_eq!(counts["unrelated"], initial_counts["unrelated"]);
+
+    // Sending a modify event for the material without changing anything should do **nothing**.
+    let guard = source_gate.write_blocking();
+
+    source_events
+        .send_blocking(AssetSourceEvent::ModifiedAsset("material.cool.ron".into()))
+        .unwrap();
+
+    run_app_until_finished_processing(&mut app, guard);
+
+    assert_eq!(get_process_counts(), counts);
+}
+
 #[test]
 fn clears_invalid_data_from_processed_dir() {
     let AppWithProcessor {
@@ -1517,3 +1631,126 @@ fn only_reprocesses_wrong_hash_on_startup() {
         serialize_as_cool_text("dep_changed processed DIFFERENT processed")
     );
 }
+
+#[test]
+fn processor_writes_and_cleans_up_outputs() {
+    let AppWithProcessor {
+        mut app,
+        source_gate,
+        default_source_dirs:
+            ProcessingDirs {
+                source: source_dir,
+                processed: processed_dir,
+                source_event_sender: source_events,
+            },
+        ..
+    } = create_app_with_asset_processor(&[]);
+
+    /// Writes every comma-separated part of the text as its own output.
+    struct SplitCoolText;
+
+    impl Process for SplitCoolText {
+        type Settings = ();
+        type OutputLoader = CoolTextLoader;
+
+        async fn process(
+            &self,
+            context: &mut ProcessContext<'_>,
+            _meta: AssetMeta<(), Self>,
+            writer: &mut crate::io::Writer,
+        ) -> Result<(), ProcessError> {
+            let loaded = context
+                .load_source_asset(AssetMeta::<CoolTextLoader, ()>::new(AssetAction::Load {
+                    loader: core::any::type_name::<CoolTextLoader>().to_string(),
+                    settings: (),
+                }))
+                .await?;
+            let text = loaded.get::<CoolText>().unwrap().text.clone();
+            for part in text.split(',') {
+                let output = TransformedAsset {
+                    value: CoolText {
+                        text: part.to_string(),
+                        embedded: String::new(),
+                        dependencies: vec![],
+                        sub_texts: vec![],
+                    },
+                    labeled_assets: Default::default(),
+                };
+                context
+                    .write_output(
+                        &format!("parts/{part}.cool.ron"),
+                        &CoolTextSaver,
+                        crate::saver::SavedAsset::from_transformed(&output),
+                        &(),
+                    )
+                    .await?;
+            }
+            CoolTextSaver
+                .save(
+                    writer,
+                    crate::saver::SavedAsset::from_loaded(&loaded).unwrap(),
+                    &(),
+                )
+                .await
+                .map_err(|error| ProcessError::AssetSaveError(error.into()))
+        }
+    }
+
+    app.init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader)
+        .register_asset_processor(SplitCoolText)
+        .set_default_asset_processor::<SplitCoolText>("cool.ron");
+
+    let guard = source_gate.write_blocking();
+
+    let path = Path::new("abc.cool.ron");
+    source_dir.insert_asset_text(path, &serialize_as_cool_text("a,b"));
+
+    run_app_until_finished_processing(&mut app, guard);
+
+    let a = Path::new("parts/a.cool.ron");
+    let b = Path::new("parts/b.cool.ron");
+    let c = Path::new("parts/c.cool.ron");
+    assert_eq!(
+        read_asset_as_string(&processed_dir, a),
+        serialize_as_cool_text("a")
+    );
+    assert_eq!(
+        read_asset_as_string(&processed_dir, b),
+        serialize_as_cool_text("b")
+    );
+    assert!(processed_dir.get_metadata(a).is_some());
+
+    // Outputs that are no longer written are removed when the source is reprocessed.
+    let guard = source_gate.write_blocking();
+
+    source_dir.insert_asset_text(path, &serialize_as_cool_text("a,c"));
+    source_events
+        .send_blocking(AssetSourceEvent::ModifiedAsset(path.into()))
+        .unwrap();
+
+    run_app_until_finished_processing(&mut app, guard);
+
+    assert!(processed_dir.get_asset(a).is_some());
+    assert!(processed_dir.get_asset(b).is_none());
+    assert!(processed_dir.get_metadata(b).is_none());
+    assert_eq!(
+        read_asset_as_string(&processed_dir, c),
+        serialize_as_cool_text("c")
+    );
+
+    // Outputs are removed along with their source. Removals don't restart processing, so wait for
+    // the files to be removed instead.
+    source_dir.remove_asset(path);
+    source_events
+        .send_blocking(AssetSourceEvent::RemovedAsset(path.into()))
+        .unwrap();
+
+    run_app_until(&mut app, |_| {
+        processed_dir.get_asset(c).is_none().then_some(())
+    });
+
+    assert!(processed_dir.get_asset(path).is_none());
+    assert!(processed_dir.get_asset(a).is_none());
+}
@@ -1,12 +1,16 @@
 use crate::{
-    io::Writer, meta::Settings, transformer::TransformedAsset, Asset, AssetLoader,
-    ErasedLoadedAsset, Handle, LabeledAsset, UntypedHandle,
+    io::Writer,
+    meta::{AssetAction, AssetMeta, AssetMetaDyn, Settings},
+    transformer::TransformedAsset,
+    Asset, AssetLoader, Assets, ErasedLoadedAsset, Handle, LabeledAsset, SaveAssetError,
+    UntypedAssetId, UntypedHandle,
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, string::ToString, sync::Arc, vec::Vec};
 use atomicow::CowArc;
+use bevy_ecs::world::World;
 use bevy_platform::collections::HashMap;
 use bevy_tasks::{BoxedFuture, ConditionalSendFuture};
-use core::{borrow::Borrow, hash::Hash, ops::Deref};
+use core::{any::TypeId, borrow::Borrow, hash::Hash, ops::Deref};
 use serde::{Deserialize, Serialize};
 
 /// Saves an [`Asset`] of a given [`AssetSaver::Asset`] type. [`AssetSaver::OutputLoader`] will then be used to load the saved asset
@@ -75,6 +79,91 @@ impl<S: AssetSaver> ErasedAssetSaver for S {
     }
 }
 
+/// Reads the asset with the given id from the [`World`], returning a future saving it to the asset bytes and the
+/// meta bytes.
+type SaveFromWorld = dyn Fn(
+        &World,
+        UntypedAssetId,
+    )
+        -> Result<BoxedFuture<'static, Result<(Vec<u8>, Vec<u8>), SaveAssetError>>, SaveAssetError>
+    + Send
+    + Sync;
+
+struct RegisteredSaver {
+    output_loader: &'static str,
+    save: Box<SaveFromWorld>,
+}
+
+/// The [`AssetSaver`]s registered with [`AssetServer::register_saver`](crate::AssetServer::register_saver),
+/// used to save assets at runtime.
+#[derive(Default)]
+pub(crate) struct AssetSavers {
+    savers: HashMap<TypeId, Vec<RegisteredSaver>>,
+}
+
+impl AssetSavers {
+    pub(crate) fn push<S: AssetSaver>(&mut self, saver: S, settings: S::Settings)
+    where
+        S::Asset: Clone,
+    {
+        let saver = Arc::new(saver);
+        let settings = Arc::new(settings);
+        let save = move |world: &World, id: UntypedAssetId| {
+            // The asset is cloned so the saver can run in the background without borrowing the world.
+            let asset = world
+                .get_resource::<Assets<S::Asset>>()
+                .and_then(|assets| assets.get(id.typed_debug_checked::<S::Asset>()))
+                .ok_or(SaveAssetError::MissingAsset(id))?
+                .clone();
+            let saver = saver.clone();
+            let settings = settings.clone();
+            let future: BoxedFuture<'static, _> = Box::pin(async move {
+                let labeled_assets = HashMap::default();
+                let saved_asset = SavedAsset {
+                    value: &asset,
+                    labeled_assets: &labeled_assets,
+                };
+                let mut bytes = Vec::new();
+                let loader_settings = saver
+                    .save(&mut bytes, saved_asset, &settings)
+                    .await
+                    .map_err(|error| SaveAssetError::AssetSaverError(error.into()))?;
+                let meta = AssetMeta::<S::OutputLoader, ()>::new(AssetAction::Load {
+                    loader: core::any::type_name::<S::OutputLoader>().to_string(),
+                    settings: loader_settings,
+                });
+                Ok((bytes, AssetMetaDyn::serialize(&meta)))
+            });
+            Ok(future)
+        };
+        self.savers
+            .entry(TypeId::of::<S::Asset>())
+            .or_default()
+            .push(RegisteredSaver {
+                output_loader: core::any::type_name::<S::OutputLoader>(),
+                save: Box::new(save),
+            });
+    }
+
+    /// Saves the asset `id` with the saver whose [`AssetSaver::OutputLoader`] has the type name `output_loader`,
+    /// falling back to the most recently registered saver for the asset type.
+    pub(crate) fn save(
+        &self,
+        world: &World,
+        id: UntypedAssetId,
+        output_loader: Option<&str>,
+    ) -> Option<
+        Result<BoxedFuture<'static, Result<(Vec<u8>, Vec<u8>), SaveAssetError>>, SaveAssetError>,
+    > {
+        let savers = self.savers.get(&id.type_id())?;
+        let saver = savers
+            .iter()
+            .rfind(|saver| Some(saver.output_loader) == output_loader)
+            .or(savers.last())?;
+        Some((saver.save)(world, id))
+    }
+}
+
 /// An [`Asset`] (and any labeled "sub assets") intended to be saved.
 pub struct SavedAsset<'a, A: Asset> {
     value: &'a A,
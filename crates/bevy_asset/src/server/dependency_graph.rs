@@ -0,0 +1,92 @@
+use crate::{
+    AssetPath, DependencyLoadState, LoadState, RecursiveDependencyLoadState, UntypedAssetId,
+};
+use alloc::vec::Vec;
+use bevy_platform::collections::{HashMap, HashSet};
+
+/// A snapshot of the assets tracked by the [`AssetServer`](crate::AssetServer) and the
+/// dependencies between them, returned by
+/// [`AssetServer::dependency_graph`](crate::AssetServer::dependency_graph).
+///
+/// This is meant for diagnostic tools and editors, for example to show which dependencies an asset
+/// is still waiting on, or everything that loading an asset pulls in.
+#[derive(Clone, Debug, Default)]
+pub struct AssetDependencyGraph {
+    pub(crate) nodes: HashMap<UntypedAssetId, AssetDependencyNode>,
+}
+
+/// An asset in an [`AssetDependencyGraph`].
+#[derive(Clone, Debug)]
+pub struct AssetDependencyNode {
+    /// The path of the asset, if it has one.
+    pub path: Option<AssetPath<'static>>,
+    /// The load state of the asset.
+    pub load_state: LoadState,
+    /// The load state of the asset's direct dependencies.
+    pub dependency_load_state: DependencyLoadState,
+    /// The load state of all of the asset's dependencies.
+    pub recursive_dependency_load_state: RecursiveDependencyLoadState,
+    /// The direct dependencies of the asset. This is empty until the asset is loaded.
+    pub dependencies: Vec<UntypedAssetId>,
+    /// The direct dependencies that haven't loaded yet.
+    pub loading_dependencies: Vec<UntypedAssetId>,
+    /// The direct dependencies that failed to load.
+    pub failed_dependencies: Vec<UntypedAssetId>,
+}
+
+impl AssetDependencyGraph {
+    /// Returns the asset with the given `id`, if it's tracked by the asset server.
+    pub fn get(&self, id: impl Into<UntypedAssetId>) -> Option<&AssetDependencyNode> {
+        self.nodes.get(&id.into())
+    }
+
+    /// Returns an iterator over all of the assets in the graph.
+    pub fn iter(&self) -> impl Iterator<Item = (UntypedAssetId, &AssetDependencyNode)> {
+        self.nodes.iter().map(|(id, node)| (*id, node))
+    }
+
+    /// Returns the number of assets in the graph.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if there are no assets in the graph.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns an iterator over the assets that directly depend on the asset with the given `id`.
+    pub fn dependents(
+        &self,
+        id: impl Into<UntypedAssetId>,
+    ) -> impl Iterator<Item = UntypedAssetId> {
+        let id = id.into();
+        self.nodes
+            .iter()
+            .filter(move |(_, node)| node.dependencies.contains(&id))
+            .map(|(id, _)| *id)
+    }
+
+    /// Returns the direct and indirect dependencies of the asset with the given `id`, in
+    /// depth-first order.
+    pub fn recursive_dependencies(&self, id: impl Into<UntypedAssetId>) -> Vec<UntypedAssetId> {
+        let id = id.into();
+        let mut visited = <HashSet<_>>::default();
+        visited.insert(id);
+        let mut dependencies = Vec::new();
+        let mut stack = Vec::new();
+        if let Some(node) = self.nodes.get(&id) {
+            stack.extend(node.dependencies.iter().rev());
+        }
+        while let Some(dependency) = stack.pop() {
+            if !visited.insert(dependency) {
+                continue;
+            }
+            dependencies.push(dependency);
+            if let Some(node) = self.nodes.get(&dependency) {
+                stack.extend(node.dependencies.iter().rev());
+            }
+        }
+        dependencies
+    }
+}
@@ -1,8 +1,8 @@
 use crate::{
     meta::{AssetHash, MetaTransform},
-    Asset, AssetHandleProvider, AssetIndex, AssetLoadError, AssetPath, DependencyLoadState,
-    ErasedAssetIndex, ErasedLoadedAsset, Handle, InternalAssetEvent, LoadState,
-    RecursiveDependencyLoadState, StrongHandle, UntypedHandle,
+    Asset, AssetDependencyGraph, AssetDependencyNode, AssetHandleProvider, AssetIndex,
+    AssetLoadError, AssetPath, DependencyLoadState, ErasedAssetIndex, ErasedLoadedAsset, Handle,
+    InternalAssetEvent, LoadState, RecursiveDependencyLoadState, StrongHandle, UntypedHandle,
 };
 use alloc::{
     borrow::ToOwned,
@@ -27,6 +27,10 @@ pub(crate) struct AssetInfo {
     pub(crate) load_state: LoadState,
     pub(crate) dep_load_state: DependencyLoadState,
     pub(crate) rec_dep_load_state: RecursiveDependencyLoadState,
+    /// The direct dependencies of this asset. This is set using the value from [`LoadedAsset`].
+    ///
+    /// [`LoadedAsset`]: crate::loader::LoadedAsset
+    dependencies: HashSet<ErasedAssetIndex>,
     loading_dependencies: HashSet<ErasedAssetIndex>,
     failed_dependencies: HashSet<ErasedAssetIndex>,
     loading_rec_dependencies: HashSet<ErasedAssetIndex>,
@@ -40,6 +44,8 @@ pub(crate) struct AssetInfo {
     ///
     /// [`LoadedAsset`]: crate::loader::LoadedAsset
     loader_dependencies: HashMap<AssetPath<'static>, AssetHash>,
+    /// The labels of the sub-assets that were added while loading this asset, sorted.
+    pub(crate) labels: Vec<Box<str>>,
     /// The number of handle drops to skip for this asset.
     /// See usage (and comments) in `get_or_create_path_handle` for context.
     handle_drops_to_skip: usize,
@@ -55,11 +61,13 @@ impl AssetInfo {
             load_state: LoadState::NotLoaded,
             dep_load_state: DependencyLoadState::NotLoaded,
             rec_dep_load_state: RecursiveDependencyLoadState::NotLoaded,
+            dependencies: HashSet::default(),
             loading_dependencies: HashSet::default(),
             failed_dependencies: HashSet::default(),
             loading_rec_dependencies: HashSet::default(),
             failed_rec_dependencies: HashSet::default(),
             loader_dependencies: HashMap::default(),
+            labels: Vec::new(),
             dependents_waiting_on_load: HashSet::default(),
             dependents_waiting_on_recursive_dep_load: HashSet::default(),
             handle_drops_to_skip: 0,
@@ -297,6 +305,29 @@ impl AssetInfos {
         self.infos.get(&index)
     }
 
+    /// Returns a snapshot of the tracked assets and their dependencies.
+    pub(crate) fn dependency_graph(&self) -> AssetDependencyGraph {
+        let ids =
+            |indices: &HashSet<ErasedAssetIndex>| indices.iter().copied().map(Into::into).collect();
+        let nodes = self
+            .infos
+            .iter()
+            .map(|(index, info)| {
+                let node = AssetDependencyNode {
+                    path: info.path.clone(),
+                    load_state: info.load_state.clone(),
+                    dependency_load_state: info.dep_load_state.clone(),
+                    recursive_dependency_load_state: info.rec_dep_load_state.clone(),
+                    dependencies: ids(&info.dependencies),
+                    loading_dependencies: ids(&info.loading_dependencies),
+                    failed_dependencies: ids(&info.failed_dependencies),
+                };
+                ((*index).into(), node)
+            })
+            .collect();
+        AssetDependencyGraph { nodes }
+    }
+
     pub(crate) fn contains_key(&self, index: ErasedAssetIndex) -> bool {
         self.infos.contains_key(&index)
     }
@@ -406,7 +437,9 @@ impl AssetInfos {
     ) {
         // Process all the labeled assets first so that they don't get skipped due to the "parent"
         // not having its handle alive.
-        for (_, asset) in loaded_asset.labeled_assets {
+        let mut labels = Vec::with_capacity(loaded_asset.labeled_assets.len());
+        for (label, asset) in loaded_asset.labeled_assets {
+            labels.push(Box::from(&*label));
             let UntypedHandle::Strong(handle) = &asset.handle else {
                 unreachable!("Labeled assets are always strong handles");
             };
@@ -426,8 +459,10 @@ impl AssetInfos {
             return;
         }
 
+        labels.sort_unstable();
         loaded_asset.value.insert(loaded_asset_index.index, world);
-        let mut loading_deps = loaded_asset.dependencies;
+        let dependencies = loaded_asset.dependencies;
+        let mut loading_deps = dependencies.clone();
         let mut failed_deps = <HashSet<_>>::default();
         let mut dep_error = None;
         let mut loading_rec_deps = loading_deps.clone();
@@ -523,6 +558,7 @@ impl AssetInfos {
             let info = self
                 .get_mut(loaded_asset_index)
                 .expect("Asset info should always exist at this point");
+            info.dependencies = dependencies;
             info.loading_dependencies = loading_deps;
             info.failed_dependencies = failed_deps;
             info.loading_rec_dependencies = loading_rec_deps;
@@ -530,6 +566,7 @@ impl AssetInfos {
             info.load_state = LoadState::Loaded;
             info.dep_load_state = dep_load_state;
             info.rec_dep_load_state = rec_dep_load_state.clone();
+            info.labels = labels;
             if watching_for_changes {
                 info.loader_dependencies = loaded_asset.loader_dependencies;
             }
@@ -1,12 +1,14 @@
+mod dependency_graph;
 mod info;
 mod loaders;
+mod scheduler;
 
 use crate::{
     folder::LoadedFolder,
     io::{
         AssetReaderError, AssetSource, AssetSourceEvent, AssetSourceId, AssetSources,
         AssetWriterError, ErasedAssetReader, MissingAssetSourceError, MissingAssetWriterError,
-        MissingProcessedAssetReaderError, Reader,
+        MissingProcessedAssetReaderError, Reader, VecReader,
     },
     loader::{AssetLoader, ErasedAssetLoader, LoadContext, LoadedAsset},
     meta::{
@@ -14,6 +16,7 @@ use crate::{
         MetaTransform, Settings,
     },
     path::AssetPath,
+    saver::{AssetSaver, AssetSavers},
     Asset, AssetEvent, AssetHandleProvider, AssetId, AssetIndex, AssetLoadFailedEvent,
     AssetMetaCheck, Assets, DeserializeMetaError, ErasedAssetIndex, ErasedLoadedAsset, Handle,
     LoadedUntypedAsset, UnapprovedPathMode, UntypedAssetId, UntypedAssetLoadFailedEvent,
@@ -39,10 +42,14 @@ use either::Either;
 use futures_lite::{FutureExt, StreamExt};
 use info::*;
 use loaders::*;
+use scheduler::*;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 use tracing::{error, info};
 
+pub use dependency_graph::*;
+pub use scheduler::LoadPriority;
+
 /// Loads and tracks the state of [`Asset`] values from a configured [`AssetReader`](crate::io::AssetReader).
 /// This can be used to kick off new asset loads and retrieve their current load states.
 ///
@@ -67,6 +74,8 @@ pub struct AssetServer {
 pub(crate) struct AssetServerData {
     pub(crate) infos: RwLock<AssetInfos>,
     pub(crate) loaders: Arc<RwLock<AssetLoaders>>,
+    savers: RwLock<AssetSavers>,
+    load_scheduler: LoadScheduler,
     asset_event_sender: Sender<InternalAssetEvent>,
     asset_event_receiver: Receiver<InternalAssetEvent>,
     sources: Arc<AssetSources>,
@@ -144,6 +153,8 @@ impl AssetServer {
                 asset_event_sender,
                 asset_event_receiver,
                 loaders,
+                savers: Default::default(),
+                load_scheduler: Default::default(),
                 infos: RwLock::new(infos),
                 unapproved_path_mode,
             }),
@@ -196,6 +207,28 @@ impl AssetServer {
         self.write_loaders().push(loader);
     }
 
+    /// Registers a new [`AssetSaver`], used by [`AssetServer::save`] to save assets of type [`AssetSaver::Asset`]
+    /// with the default [`AssetSaver::Settings`].
+    pub fn register_saver<S: AssetSaver>(&self, saver: S)
+    where
+        S::Asset: Clone,
+    {
+        self.register_saver_with_settings(saver, S::Settings::default());
+    }
+
+    /// Registers a new [`AssetSaver`], used by [`AssetServer::save`] to save assets of type [`AssetSaver::Asset`]
+    /// with the given `settings`.
+    pub fn register_saver_with_settings<S: AssetSaver>(&self, saver: S, settings: S::Settings)
+    where
+        S::Asset: Clone,
+    {
+        self.data
+            .savers
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(saver, settings);
+    }
+
     /// Registers a new [`Asset`] type. [`Asset`] types must be registered before assets of that type can be loaded.
     pub fn register_asset<A: Asset>(&self, assets: &Assets<A>) {
         self.register_handle_provider(assets.get_handle_provider());
@@ -358,7 +391,7 @@ impl AssetServer {
     /// The asset load will fail and an error will be printed to the logs if the asset stored at `path` is not of type `A`.
     #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
     pub fn load<'a, A: Asset>(&self, path: impl Into<AssetPath<'a>>) -> Handle<A> {
-        self.load_with_meta_transform(path, None, (), false)
+        self.load_with_meta_transform(path, None, (), false, LoadPriority::Normal)
     }
 
     /// Same as [`load`](AssetServer::load), but you can load assets from unapproved paths
@@ -367,7 +400,7 @@ impl AssetServer {
     ///
     /// See [`UnapprovedPathMode`] and [`AssetPath::is_unapproved`]
     pub fn load_override<'a, A: Asset>(&self, path: impl Into<AssetPath<'a>>) -> Handle<A> {
-        self.load_with_meta_transform(path, None, (), true)
+        self.load_with_meta_transform(path, None, (), true, LoadPriority::Normal)
     }
 
     /// Begins loading an [`Asset`] of type `A` stored at `path` while holding a guard item.
@@ -391,7 +424,7 @@ impl AssetServer {
         path: impl Into<AssetPath<'a>>,
         guard: G,
     ) -> Handle<A> {
-        self.load_with_meta_transform(path, None, guard, false)
+        self.load_with_meta_transform(path, None, guard, false, LoadPriority::Normal)
     }
 
     /// Same as [`load`](AssetServer::load_acquire), but you can load assets from unapproved paths
@@ -404,7 +437,7 @@ impl AssetServer {
         path: impl Into<AssetPath<'a>>,
         guard: G,
     ) -> Handle<A> {
-        self.load_with_meta_transform(path, None, guard, true)
+        self.load_with_meta_transform(path, None, guard, true, LoadPriority::Normal)
     }
 
     /// Begins loading an [`Asset`] of type `A` stored at `path`. The given `settings` function will override the asset's
@@ -421,6 +454,7 @@ impl AssetServer {
             Some(loader_settings_meta_transform(settings)),
             (),
             false,
+            LoadPriority::Normal,
         )
     }
 
@@ -439,6 +473,7 @@ impl AssetServer {
             Some(loader_settings_meta_transform(settings)),
             (),
             true,
+            LoadPriority::Normal,
         )
     }
 
@@ -463,6 +498,7 @@ impl AssetServer {
             Some(loader_settings_meta_transform(settings)),
             guard,
             false,
+            LoadPriority::Normal,
         )
     }
 
@@ -487,15 +523,72 @@ impl AssetServer {
             Some(loader_settings_meta_transform(settings)),
             guard,
             true,
+            LoadPriority::Normal,
         )
     }
 
+    /// Begins loading an [`Asset`] of type `A` stored at `path` with the given [`LoadPriority`].
+    ///
+    /// When the number of concurrent loads is limited (see [`AssetServer::set_max_concurrent_loads`]), queued loads
+    /// with a higher priority are started first. This can be used to load the assets the player can see before
+    /// assets that are streamed in the background.
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_with_priority<'a, A: Asset>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        priority: LoadPriority,
+    ) -> Handle<A> {
+        self.load_with_meta_transform(path, None, (), false, priority)
+    }
+
+    /// Begins loading an [`Asset`] of type `A` stored at `path` with the given [`LoadPriority`]. The given `settings`
+    /// function will override the asset's [`AssetLoader`] settings, like [`load_with_settings`](AssetServer::load_with_settings).
+    ///
+    /// See [`AssetServer::load_with_priority`] for how priorities are used.
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_with_settings_and_priority<'a, A: Asset, S: Settings>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        settings: impl Fn(&mut S) + Send + Sync + 'static,
+        priority: LoadPriority,
+    ) -> Handle<A> {
+        self.load_with_meta_transform(
+            path,
+            Some(loader_settings_meta_transform(settings)),
+            (),
+            false,
+            priority,
+        )
+    }
+
+    /// Returns the maximum number of asset loads that can run at the same time, or [`None`] if it is unlimited.
+    pub fn max_concurrent_loads(&self) -> Option<usize> {
+        self.data.load_scheduler.max_concurrent_loads()
+    }
+
+    /// Sets the maximum number of asset loads that can run at the same time (at least one), or [`None`] to make it
+    /// unlimited, which is the default.
+    ///
+    /// When the limit is reached, new loads are queued, and started in [`LoadPriority`] order as running loads finish.
+    /// Immediate nested loads performed by an [`AssetLoader`] aren't limited, as the outer load is already running.
+    pub fn set_max_concurrent_loads(&self, max_concurrent_loads: Option<usize>) {
+        self.data
+            .load_scheduler
+            .set_max_concurrent_loads(max_concurrent_loads);
+    }
+
+    /// Returns the number of asset loads waiting to run because the maximum number of concurrent loads was reached.
+    pub fn queued_loads(&self) -> usize {
+        self.data.load_scheduler.queued_loads()
+    }
+
     pub(crate) fn load_with_meta_transform<'a, A: Asset, G: Send + Sync + 'static>(
         &self,
         path: impl Into<AssetPath<'a>>,
         meta_transform: Option<MetaTransform>,
         guard: G,
         override_unapproved: bool,
+        priority: LoadPriority,
     ) -> Handle<A> {
         let path = path.into().into_owned();
 
@@ -517,7 +610,7 @@ impl AssetServer {
         );
 
         if should_load {
-            self.spawn_load_task(handle.clone().untyped(), path, infos, guard);
+            self.spawn_load_task(handle.clone().untyped(), path, infos, guard, priority);
         }
 
         handle
@@ -541,7 +634,7 @@ impl AssetServer {
         );
 
         if should_load {
-            self.spawn_load_task(handle.clone(), path, infos, guard);
+            self.spawn_load_task(handle.clone(), path, infos, guard, LoadPriority::Normal);
         }
 
         handle
@@ -553,6 +646,7 @@ impl AssetServer {
         path: AssetPath<'static>,
         mut infos: RwLockWriteGuard<AssetInfos>,
         guard: G,
+        priority: LoadPriority,
     ) {
         infos.stats.started_load_tasks += 1;
 
@@ -563,6 +657,7 @@ impl AssetServer {
         let owned_handle = handle.clone();
         let server = self.clone();
         let task = IoTaskPool::get().spawn(async move {
+            let _permit = server.data.load_scheduler.start(priority).await;
             if let Err(err) = server
                 .load_internal(Some(owned_handle), path, false, None)
                 .await
@@ -632,6 +727,7 @@ impl AssetServer {
 
         let server = self.clone();
         let task = IoTaskPool::get().spawn(async move {
+            let _permit = server.data.load_scheduler.start(LoadPriority::Normal).await;
             let path_clone = path.clone();
             match server
                 .load_internal(None, path, false, None)
@@ -692,6 +788,143 @@ impl AssetServer {
         self.load_unknown_type_with_meta_transform(path, None)
     }
 
+    /// Asynchronously loads the file at the given filesystem `path`, which doesn't need to be in
+    /// an [`AssetSource`], such as a file dropped onto a window. The [`AssetLoader`] is picked
+    /// using the file's extension, like [`AssetServer::load_untyped_async`].
+    ///
+    /// Like any other asset path, `path` is subject to [`UnapprovedPathMode`]: absolute paths and
+    /// paths leaving the current folder are unapproved, so loading them fails with
+    /// [`AssetLoadError::UnapprovedPath`] unless the mode is [`Allow`](UnapprovedPathMode::Allow).
+    /// Use [`AssetServer::load_file_override_async`] to load them when the mode is
+    /// [`Deny`](UnapprovedPathMode::Deny). Dependencies of the file are resolved relative to its
+    /// path, so they're also subject to [`UnapprovedPathMode`] when the file isn't in an approved
+    /// folder.
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub async fn load_file_async(
+        &self,
+        path: impl Into<PathBuf>,
+    ) -> Result<UntypedHandle, AssetLoadError> {
+        self.load_file_internal(path.into(), false).await
+    }
+
+    /// Same as [`load_file_async`](AssetServer::load_file_async), but you can load files from
+    /// unapproved paths if [`AssetPlugin::unapproved_path_mode`](super::AssetPlugin::unapproved_path_mode)
+    /// is [`Deny`](UnapprovedPathMode::Deny).
+    ///
+    /// See [`UnapprovedPathMode`] and [`AssetPath::is_unapproved`]
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub async fn load_file_override_async(
+        &self,
+        path: impl Into<PathBuf>,
+    ) -> Result<UntypedHandle, AssetLoadError> {
+        self.load_file_internal(path.into(), true).await
+    }
+
+    async fn load_file_internal(
+        &self,
+        path: PathBuf,
+        override_unapproved: bool,
+    ) -> Result<UntypedHandle, AssetLoadError> {
+        let asset_path = AssetPath::from_path_buf(path.clone());
+        if asset_path.is_unapproved() {
+            match (&self.data.unapproved_path_mode, override_unapproved) {
+                (UnapprovedPathMode::Allow, _) | (UnapprovedPathMode::Deny, true) => {}
+                (UnapprovedPathMode::Deny, false) | (UnapprovedPathMode::Forbid, _) => {
+                    return Err(AssetLoadError::UnapprovedPath { path: asset_path });
+                }
+            }
+        }
+        let loader = self.get_path_asset_loader(&asset_path).await?;
+        let bytes = async_fs::read(&path).await.map_err(|error| {
+            if error.kind() == std::io::ErrorKind::NotFound {
+                AssetReaderError::NotFound(path)
+            } else {
+                error.into()
+            }
+        })?;
+        let meta = loader.default_meta();
+        let loaded_asset = self
+            .load_with_meta_loader_and_reader(
+                &asset_path,
+                &*meta,
+                &*loader,
+                &mut VecReader::new(bytes),
+                true,
+                false,
+            )
+            .await?;
+        Ok(self.load_asset_untyped(Some(asset_path), loaded_asset))
+    }
+
+    /// Loads the file at the given filesystem `path`, which doesn't need to be in an
+    /// [`AssetSource`], such as a file dropped onto a window. The method returns a handle to a
+    /// [`LoadedUntypedAsset`], like [`AssetServer::load_untyped`].
+    ///
+    /// See [`AssetServer::load_file_async`] for details.
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the assets"]
+    pub fn load_file(&self, path: impl Into<PathBuf>) -> Handle<LoadedUntypedAsset> {
+        self.load_file_with_override(path.into(), false)
+    }
+
+    /// Same as [`load_file`](AssetServer::load_file), but you can load files from unapproved paths
+    /// if [`AssetPlugin::unapproved_path_mode`](super::AssetPlugin::unapproved_path_mode)
+    /// is [`Deny`](UnapprovedPathMode::Deny).
+    ///
+    /// See [`UnapprovedPathMode`] and [`AssetPath::is_unapproved`]
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the assets"]
+    pub fn load_file_override(&self, path: impl Into<PathBuf>) -> Handle<LoadedUntypedAsset> {
+        self.load_file_with_override(path.into(), true)
+    }
+
+    fn load_file_with_override(
+        &self,
+        path: PathBuf,
+        override_unapproved: bool,
+    ) -> Handle<LoadedUntypedAsset> {
+        let mut infos = self.write_infos();
+        let handle = infos.create_loading_handle_untyped(
+            TypeId::of::<LoadedUntypedAsset>(),
+            core::any::type_name::<LoadedUntypedAsset>(),
+        );
+        infos.stats.started_load_tasks += 1;
+
+        // drop the lock on `AssetInfos` before spawning a task that may block on it in single-threaded
+        #[cfg(any(target_arch = "wasm32", not(feature = "multi_threaded")))]
+        drop(infos);
+
+        // `create_loading_handle_untyped` always returns a Strong variant, so this is safe.
+        let index = (&handle).try_into().unwrap();
+
+        let server = self.clone();
+        let task = IoTaskPool::get().spawn(async move {
+            let _permit = server.data.load_scheduler.start(LoadPriority::Normal).await;
+            let asset_path = AssetPath::from_path_buf(path.clone());
+            match server.load_file_internal(path, override_unapproved).await {
+                Ok(handle) => server.send_asset_event(InternalAssetEvent::Loaded {
+                    index,
+                    loaded_asset: LoadedAsset::new_with_dependencies(LoadedUntypedAsset { handle })
+                        .into(),
+                }),
+                Err(err) => {
+                    error!("{err}");
+                    server.send_asset_event(InternalAssetEvent::Failed {
+                        index,
+                        path: asset_path,
+                        error: err,
+                    });
+                }
+            }
+        });
+
+        #[cfg(not(any(target_arch = "wasm32", not(feature = "multi_threaded"))))]
+        infos.pending_tasks.insert(index, task);
+
+        #[cfg(any(target_arch = "wasm32", not(feature = "multi_threaded")))]
+        task.detach();
+
+        handle.typed_debug_checked()
+    }
+
     /// Performs an async asset load.
     ///
     /// `input_handle` must only be [`Some`] if `should_load` was true when retrieving
@@ -1365,6 +1598,31 @@ impl AssetServer {
         Some(info.path.as_ref()?.clone())
     }
 
+    /// Returns a snapshot of the dependency graph of all assets tracked by this [`AssetServer`],
+    /// including their load states.
+    ///
+    /// Dependencies are only known once an asset has loaded, so assets that are still loading don't
+    /// have any yet.
+    pub fn dependency_graph(&self) -> AssetDependencyGraph {
+        self.read_infos().dependency_graph()
+    }
+
+    /// Returns the labels of the sub-assets that were added while loading the asset with the given
+    /// `id`, such as `Mesh0/Primitive0` for a glTF file, sorted.
+    ///
+    /// This is empty until the asset is loaded. Combine the labels with the asset's path using
+    /// [`AssetPath::with_label`] to load the sub-assets.
+    pub fn labels(&self, id: impl Into<UntypedAssetId>) -> Vec<Box<str>> {
+        let Ok(index) = id.into().try_into() else {
+            // Always say we don't have Uuid assets.
+            return Vec::new();
+        };
+        self.read_infos()
+            .get(index)
+            .map(|info| info.labels.clone())
+            .unwrap_or_default()
+    }
+
     /// Returns the [`AssetServerMode`] this server is currently in.
     pub fn mode(&self) -> AssetServerMode {
         self.data.mode
@@ -1675,6 +1933,83 @@ impl AssetServer {
         }
     }
 
+    /// Saves the asset `id` to `path` using a registered [`AssetSaver`], along with a meta file describing how to
+    /// load it back. This makes it possible to export assets created at runtime, such as procedural content,
+    /// screenshots or edited levels.
+    ///
+    /// If multiple savers are registered for the asset type, the one whose [`AssetSaver::OutputLoader`] is the
+    /// [`AssetLoader`] for the extension of `path` is used. Otherwise, the most recently registered saver is used.
+    ///
+    /// The asset is cloned from [`Assets`] the next time [`handle_internal_asset_events`] runs, which is part of
+    /// [`PreUpdate`](bevy_app::PreUpdate). It is then saved and written to the [`AssetWriter`](crate::io::AssetWriter)
+    /// of the source of `path` in the background, on the [`IoTaskPool`]. The returned future completes once the asset
+    /// has been written, and can be ignored if the result is not needed.
+    pub fn save<'a, A: Asset>(
+        &self,
+        id: impl Into<AssetId<A>>,
+        path: impl Into<AssetPath<'a>>,
+    ) -> impl Future<Output = Result<(), SaveAssetError>> + 'static {
+        let (sender, receiver) = async_channel::bounded(1);
+        self.send_asset_event(InternalAssetEvent::Save {
+            id: id.into().untyped(),
+            path: path.into().into_owned(),
+            sender,
+        });
+        async move {
+            receiver
+                .recv()
+                .await
+                .unwrap_or(Err(SaveAssetError::Cancelled))
+        }
+    }
+
+    /// Saves the asset `id` from `world`, then writes it to `path` in the background.
+    fn save_from_world(
+        &self,
+        world: &World,
+        id: UntypedAssetId,
+        path: AssetPath<'static>,
+        sender: async_channel::Sender<Result<(), SaveAssetError>>,
+    ) {
+        let output_loader = match self.read_loaders().get_by_path(&path) {
+            Some(MaybeAssetLoader::Ready(loader)) => Some(loader.type_name()),
+            _ => None,
+        };
+        let saved = self
+            .data
+            .savers
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .save(world, id, output_loader)
+            .unwrap_or_else(|| Err(SaveAssetError::MissingSaver(path.clone())));
+        let server = self.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                let result = async {
+                    let (bytes, meta_bytes) = saved?.await?;
+                    let writer = server.get_source(path.source())?.writer()?;
+                    let writer_err = |err| SaveAssetError::AssetWriterError {
+                        path: path.clone(),
+                        err,
+                    };
+                    writer
+                        .write_bytes(path.path(), &bytes)
+                        .await
+                        .map_err(writer_err)?;
+                    writer
+                        .write_meta_bytes(path.path(), &meta_bytes)
+                        .await
+                        .map_err(writer_err)
+                }
+                .await;
+                if let Err(err) = &result {
+                    error!("Failed to save asset {path}: {err}");
+                }
+                let _ = sender.send(result).await;
+            })
+            .detach();
+    }
+
     /// Writes the default loader meta file for the provided `path`.
     ///
     /// This function only generates meta files that simply load the path directly. To generate a
@@ -1751,6 +2086,9 @@ pub fn handle_internal_asset_events(world: &mut World) {
                         }
                     }
                 }
+                InternalAssetEvent::Save { id, path, sender } => {
+                    server.save_from_world(world, id, path, sender);
+                }
                 InternalAssetEvent::Failed { index, path, error } => {
                     infos.process_asset_fail(index, error.clone());
 
@@ -1898,6 +2236,11 @@ pub(crate) enum InternalAssetEvent {
         path: AssetPath<'static>,
         error: AssetLoadError,
     },
+    Save {
+        id: UntypedAssetId,
+        path: AssetPath<'static>,
+        sender: async_channel::Sender<Result<(), SaveAssetError>>,
+    },
 }
 
 /// The load state of an asset.
@@ -2052,6 +2395,9 @@ pub enum AssetLoadError {
     #[error("Asset '{path}' is configured to be ignored. It cannot be loaded.")]
     #[from(ignore)]
     CannotLoadIgnoredAsset { path: AssetPath<'static> },
+    #[error("Asset path '{path}' is unapproved. See UnapprovedPathMode for details.")]
+    #[from(ignore)]
+    UnapprovedPath { path: AssetPath<'static> },
     #[error("Failed to load asset '{path}', asset loader '{loader_name}' panicked")]
     AssetLoaderPanic {
         path: AssetPath<'static>,
@@ -2165,6 +2511,28 @@ pub enum WaitForAssetError {
     DependencyFailed(Arc<AssetLoadError>),
 }
 
+/// An error that occurs when saving an asset with [`AssetServer::save`].
+#[derive(Error, Debug)]
+pub enum SaveAssetError {
+    #[error("No AssetSaver is registered for the asset saved to '{0}'")]
+    MissingSaver(AssetPath<'static>),
+    #[error("The asset {0} does not exist")]
+    MissingAsset(UntypedAssetId),
+    #[error(transparent)]
+    MissingAssetSource(#[from] MissingAssetSourceError),
+    #[error(transparent)]
+    MissingAssetWriter(#[from] MissingAssetWriterError),
+    #[error("Encountered an AssetWriter error for '{path}': {err}")]
+    AssetWriterError {
+        path: AssetPath<'static>,
+        err: AssetWriterError,
+    },
+    #[error("Encountered an error while saving the asset: {0}")]
+    AssetSaverError(Box<dyn core::error::Error + Send + Sync + 'static>),
+    #[error("The asset server was dropped before the asset was saved")]
+    Cancelled,
+}
+
 #[derive(Error, Debug)]
 pub enum WriteDefaultMetaError {
     #[error(transparent)]
@@ -0,0 +1,209 @@
+use alloc::collections::BinaryHeap;
+use async_channel::{Receiver, Sender};
+use bevy_platform::sync::{Mutex, MutexGuard, PoisonError};
+use core::cmp::Ordering;
+
+/// The priority of an asset load, used by the [`AssetServer`](crate::AssetServer) to decide which loads to start first
+/// when the number of concurrent loads is limited (see [`AssetServer::set_max_concurrent_loads`]).
+///
+/// Loads with the same priority are started in the order they were requested.
+///
+/// [`AssetServer::set_max_concurrent_loads`]: crate::AssetServer::set_max_concurrent_loads
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LoadPriority {
+    /// Loads that aren't needed soon, such as streaming in the next area of a level in the background.
+    Low,
+    /// The priority of loads that don't specify one.
+    #[default]
+    Normal,
+    /// Loads that are needed as soon as possible, such as assets that are visible to the player.
+    High,
+}
+
+/// Limits the number of asset loads running at the same time, starting queued loads in [`LoadPriority`] order.
+#[derive(Default)]
+pub(crate) struct LoadScheduler {
+    state: Mutex<LoadSchedulerState>,
+}
+
+#[derive(Default)]
+struct LoadSchedulerState {
+    /// The maximum number of loads running at the same time. If [`None`], loads are never queued.
+    max_concurrent_loads: Option<usize>,
+    /// The number of loads currently running.
+    running: usize,
+    /// The loads waiting to run.
+    queue: BinaryHeap<QueuedLoad>,
+    /// The number of loads that have been queued, used to run loads with the same priority in order.
+    queued_count: u64,
+}
+
+impl LoadSchedulerState {
+    fn has_capacity(&self) -> bool {
+        self.max_concurrent_loads
+            .is_none_or(|max_concurrent_loads| self.running < max_concurrent_loads)
+    }
+
+    /// Starts queued loads until there is no capacity left.
+    fn start_queued_loads(&mut self) {
+        while self.has_capacity() {
+            let Some(load) = self.queue.pop() else {
+                break;
+            };
+            // If the load was cancelled while it was queued, the receiver no longer exists.
+            if load.start_sender.try_send(()).is_ok() {
+                self.running += 1;
+            }
+        }
+    }
+}
+
+struct QueuedLoad {
+    priority: LoadPriority,
+    index: u64,
+    start_sender: Sender<()>,
+}
+
+impl Ord for QueuedLoad {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Higher priorities first, then earlier loads first.
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl PartialOrd for QueuedLoad {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for QueuedLoad {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl Eq for QueuedLoad {}
+
+impl LoadScheduler {
+    pub(crate) fn max_concurrent_loads(&self) -> Option<usize> {
+        self.lock().max_concurrent_loads
+    }
+
+    pub(crate) fn set_max_concurrent_loads(&self, max_concurrent_loads: Option<usize>) {
+        let mut state = self.lock();
+        // Always allow at least one load to run, otherwise loads would never finish.
+        state.max_concurrent_loads = max_concurrent_loads.map(|max| max.max(1));
+        state.start_queued_loads();
+    }
+
+    /// Returns the number of loads waiting to run.
+    pub(crate) fn queued_loads(&self) -> usize {
+        self.lock().queue.len()
+    }
+
+    /// Waits until a load with the given `priority` is allowed to run. The load is considered running until the
+    /// returned [`LoadPermit`] is dropped.
+    pub(crate) async fn start(&self, priority: LoadPriority) -> LoadPermit<'_> {
+        let start_receiver = {
+            let mut state = self.lock();
+            if state.queue.is_empty() && state.has_capacity() {
+                state.running += 1;
+                return LoadPermit { scheduler: self };
+            }
+            let (start_sender, start_receiver) = async_channel::bounded(1);
+            let index = state.queued_count;
+            state.queued_count += 1;
+            state.queue.push(QueuedLoad {
+                priority,
+                index,
+                start_sender,
+            });
+            QueuedLoadReceiver {
+                scheduler: self,
+                receiver: start_receiver,
+            }
+        };
+        // The sender is only dropped after sending, so this can't fail.
+        let _ = start_receiver.receiver.recv().await;
+        start_receiver.into_permit()
+    }
+
+    fn finish(&self) {
+        let mut state = self.lock();
+        state.running -= 1;
+        state.start_queued_loads();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, LoadSchedulerState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Releases the running load if the queued load is dropped (cancelled) after it was started, but before it could
+/// observe it.
+struct QueuedLoadReceiver<'a> {
+    scheduler: &'a LoadScheduler,
+    receiver: Receiver<()>,
+}
+
+impl<'a> QueuedLoadReceiver<'a> {
+    fn into_permit(self) -> LoadPermit<'a> {
+        let scheduler = self.scheduler;
+        // The start signal has been received, so dropping `self` won't release the load.
+        drop(self);
+        LoadPermit { scheduler }
+    }
+}
+
+impl Drop for QueuedLoadReceiver<'_> {
+    fn drop(&mut self) {
+        // Closing the channel ensures the scheduler can't start this load after this check.
+        self.receiver.close();
+        if self.receiver.try_recv().is_ok() {
+            self.scheduler.finish();
+        }
+    }
+}
+
+/// Marks a load as running. The next queued load is started when this is dropped.
+pub(crate) struct LoadPermit<'a> {
+    scheduler: &'a LoadScheduler,
+}
+
+impl Drop for LoadPermit<'_> {
+    fn drop(&mut self) {
+        self.scheduler.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_tasks::block_on;
+    use futures_lite::future::poll_once;
+
+    #[test]
+    fn queued_loads_start_in_priority_order() {
+        let scheduler = LoadScheduler::default();
+        scheduler.set_max_concurrent_loads(Some(1));
+
+        let running = block_on(scheduler.start(LoadPriority::Normal));
+        let mut low = core::pin::pin!(scheduler.start(LoadPriority::Low));
+        let mut high = core::pin::pin!(scheduler.start(LoadPriority::High));
+        assert!(block_on(poll_once(&mut low)).is_none());
+        assert!(block_on(poll_once(&mut high)).is_none());
+        assert_eq!(scheduler.queued_loads(), 2);
+
+        drop(running);
+        assert!(block_on(poll_once(&mut low)).is_none());
+        let high = block_on(poll_once(&mut high)).unwrap();
+        assert_eq!(scheduler.queued_loads(), 1);
+
+        drop(high);
+        assert!(block_on(poll_once(&mut low)).is_some());
+        assert_eq!(scheduler.queued_loads(), 0);
+    }
+}
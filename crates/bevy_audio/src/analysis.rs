@@ -0,0 +1,437 @@
+use crate::{AudioBus, AudioBuses, AudioSink, AudioSinkPlayback, SpatialAudioSink};
+use alloc::{collections::VecDeque, sync::Arc};
+use bevy_ecs::{entity::EntityHashMap, prelude::*};
+use bevy_math::ops;
+use bevy_platform::collections::HashMap;
+use bevy_reflect::prelude::*;
+use core::{f32::consts::TAU, time::Duration};
+use rodio::{source::SeekError, Source};
+use std::sync::Mutex;
+
+/// Analyzes the sound played by this entity, publishing its [`AudioSpectrum`] in the
+/// [`AudioAnalysis`] resource every frame.
+///
+/// The component must be present when the sound starts playing. Buses can be analyzed with
+/// [`AudioBusSettings::analyze`](crate::AudioBusSettings::analyze).
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Clone, Debug, Default)]
+pub struct AudioAnalyzer;
+
+/// The amplitude and frequency content of a sound over the last few milliseconds.
+///
+/// Amplitudes are linear, a full scale sine wave has an amplitude of `1.0` in its frequency band.
+#[derive(Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Clone, Debug, Default, PartialEq)]
+pub struct AudioSpectrum {
+    /// The root mean square amplitude of the sound.
+    pub rms: f32,
+    /// The peak amplitude of the sound.
+    pub peak: f32,
+    /// The amplitude of each frequency band, from the lowest to the highest frequency.
+    ///
+    /// See [`AudioAnalysis::band_frequencies`] for the frequencies of the bands.
+    pub bands: Vec<f32>,
+}
+
+/// The analysis of the sounds with an [`AudioAnalyzer`] and of the buses with
+/// [`AudioBusSettings::analyze`](crate::AudioBusSettings::analyze) enabled, updated every frame.
+///
+/// This can drive music visualizers or effects reacting to the beat:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_audio::{AudioAnalysis, AudioBus};
+/// fn pulse(analysis: Res<AudioAnalysis>) {
+///     if let Some(spectrum) = analysis.bus(AudioBus::MUSIC) {
+///         let bass = spectrum.bands.first().copied().unwrap_or_default();
+///         // Scale something with `bass`
+///     }
+/// }
+/// ```
+///
+/// The sounds are analyzed after their effects and the volume of their buses, but before the
+/// volume of their sink and spatial audio. The spectrum of a bus combines the spectra of the
+/// sounds routed through it and its child buses.
+#[derive(Resource, Debug, Clone)]
+pub struct AudioAnalysis {
+    /// The number of frequency bands, logarithmically spaced between 20 Hz and 20 kHz.
+    pub band_count: usize,
+    buses: HashMap<AudioBus, AudioSpectrum>,
+    sounds: EntityHashMap<AudioSpectrum>,
+}
+
+impl Default for AudioAnalysis {
+    fn default() -> Self {
+        Self {
+            band_count: 16,
+            buses: HashMap::default(),
+            sounds: EntityHashMap::default(),
+        }
+    }
+}
+
+impl AudioAnalysis {
+    /// The lowest frequency of the bands, in hertz.
+    const MIN_FREQUENCY: f32 = 20.0;
+    /// The highest frequency of the bands, in hertz.
+    const MAX_FREQUENCY: f32 = 20_000.0;
+
+    /// Returns the spectrum of the bus, if it is analyzed.
+    pub fn bus(&self, bus: AudioBus) -> Option<&AudioSpectrum> {
+        self.buses.get(&bus)
+    }
+
+    /// Returns the spectrum of the sound played by the entity, if it has an [`AudioAnalyzer`].
+    pub fn sound(&self, entity: Entity) -> Option<&AudioSpectrum> {
+        self.sounds.get(&entity)
+    }
+
+    /// Returns the lower and upper frequencies of each band, in hertz.
+    pub fn band_frequencies(&self) -> impl Iterator<Item = (f32, f32)> + '_ {
+        (0..self.band_count).map(|band| (self.band_edge(band), self.band_edge(band + 1)))
+    }
+
+    fn band_edge(&self, edge: usize) -> f32 {
+        Self::MIN_FREQUENCY
+            * ops::powf(
+                Self::MAX_FREQUENCY / Self::MIN_FREQUENCY,
+                edge as f32 / self.band_count.max(1) as f32,
+            )
+    }
+}
+
+/// The number of samples analyzed every frame, a power of two.
+const ANALYSIS_WINDOW: usize = 2048;
+/// The number of samples a source buffers before sending them to the analysis.
+const ANALYSIS_BATCH: usize = 256;
+
+/// The latest samples of an analyzed sound, shared with the audio thread.
+#[derive(Debug, Clone)]
+pub(crate) struct AnalysisTap {
+    buffer: Arc<Mutex<TapBuffer>>,
+    /// The bus the sound is routed through.
+    bus: AudioBus,
+    /// Whether the sound has an [`AudioAnalyzer`].
+    analyzer: bool,
+}
+
+#[derive(Debug, Default)]
+struct TapBuffer {
+    samples: VecDeque<f32>,
+    sample_rate: u32,
+}
+
+impl AnalysisTap {
+    pub(crate) fn new(bus: AudioBus, analyzer: bool) -> Self {
+        Self {
+            buffer: Arc::default(),
+            bus,
+            analyzer,
+        }
+    }
+
+    fn spectrum(&self, band_edges: &[f32]) -> AudioSpectrum {
+        let Ok(buffer) = self.buffer.lock() else {
+            return AudioSpectrum::default();
+        };
+        analyze(&buffer.samples, buffer.sample_rate, band_edges)
+    }
+}
+
+/// Sends the samples of a source, downmixed to mono, to an [`AnalysisTap`] if there is one.
+pub(crate) struct AnalysisSource<S> {
+    input: S,
+    buffer: Option<Arc<Mutex<TapBuffer>>>,
+    batch: Vec<f32>,
+    frame_sum: f32,
+    channel: u16,
+}
+
+impl<S> AnalysisSource<S>
+where
+    S: Source<Item = f32>,
+{
+    pub(crate) fn new(input: S, tap: Option<&AnalysisTap>) -> Self {
+        Self {
+            input,
+            buffer: tap.map(|tap| tap.buffer.clone()),
+            batch: Vec::new(),
+            frame_sum: 0.0,
+            channel: 0,
+        }
+    }
+
+    fn flush(&mut self) {
+        let Some(buffer) = &self.buffer else {
+            return;
+        };
+        // Don't block the audio thread, the samples will be sent with the next batch instead
+        let Ok(mut buffer) = buffer.try_lock() else {
+            if self.batch.len() >= ANALYSIS_WINDOW {
+                self.batch.drain(..self.batch.len() - ANALYSIS_WINDOW);
+            }
+            return;
+        };
+        buffer.sample_rate = self.input.sample_rate();
+        buffer.samples.extend(self.batch.drain(..));
+        let excess = buffer.samples.len().saturating_sub(ANALYSIS_WINDOW);
+        buffer.samples.drain(..excess);
+    }
+}
+
+impl<S> Iterator for AnalysisSource<S>
+where
+    S: Source<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let sample = self.input.next()?;
+        if self.buffer.is_none() {
+            return Some(sample);
+        }
+        let channels = self.input.channels().max(1);
+
+        self.frame_sum += sample;
+        self.channel += 1;
+        if self.channel >= channels {
+            self.batch.push(self.frame_sum / channels as f32);
+            self.frame_sum = 0.0;
+            self.channel = 0;
+            if self.batch.len() >= ANALYSIS_BATCH {
+                self.flush();
+            }
+        }
+
+        Some(sample)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<S> Source for AnalysisSource<S>
+where
+    S: Source<Item = f32>,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        self.input.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.input.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.input.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)
+    }
+}
+
+/// Computes the spectrum of the latest [`ANALYSIS_WINDOW`] samples.
+fn analyze(samples: &VecDeque<f32>, sample_rate: u32, band_edges: &[f32]) -> AudioSpectrum {
+    let bands = band_edges.len().saturating_sub(1);
+    if samples.is_empty() || sample_rate == 0 {
+        return AudioSpectrum {
+            bands: vec![0.0; bands],
+            ..Default::default()
+        };
+    }
+
+    let rms =
+        ops::sqrt(samples.iter().map(|sample| sample * sample).sum::<f32>() / samples.len() as f32);
+    let peak = samples
+        .iter()
+        .fold(0.0f32, |peak, sample| peak.max(ops::abs(*sample)));
+
+    // Hann window, the missing samples at the start of a sound are treated as silence
+    let mut real = vec![0.0; ANALYSIS_WINDOW];
+    let mut imaginary = vec![0.0; ANALYSIS_WINDOW];
+    let offset = ANALYSIS_WINDOW - samples.len();
+    let mut window_energy = 0.0;
+    for (index, value) in real.iter_mut().enumerate() {
+        let window = 0.5 - 0.5 * ops::cos(TAU * index as f32 / ANALYSIS_WINDOW as f32);
+        window_energy += window * window;
+        if index >= offset {
+            *value = samples[index - offset] * window;
+        }
+    }
+    fft(&mut real, &mut imaginary);
+
+    // The energy of a sine wave of amplitude `a` is spread over a few bins, summing to
+    // `a² * ANALYSIS_WINDOW * window_energy / 4`
+    let scale = 4.0 / (ANALYSIS_WINDOW as f32 * window_energy);
+    let bin_width = sample_rate as f32 / ANALYSIS_WINDOW as f32;
+    let mut band_energy = vec![0.0; bands];
+    for bin in 1..ANALYSIS_WINDOW / 2 {
+        let frequency = bin as f32 * bin_width;
+        let Some(band) = band_edges
+            .windows(2)
+            .position(|edges| frequency >= edges[0] && frequency < edges[1])
+        else {
+            continue;
+        };
+        band_energy[band] += real[bin] * real[bin] + imaginary[bin] * imaginary[bin];
+    }
+
+    AudioSpectrum {
+        rms,
+        peak,
+        bands: band_energy
+            .into_iter()
+            .map(|energy| ops::sqrt(energy * scale))
+            .collect(),
+    }
+}
+
+/// An in-place radix-2 fast Fourier transform. The length of the buffers must be a power of two.
+fn fft(real: &mut [f32], imaginary: &mut [f32]) {
+    let len = real.len();
+    debug_assert!(len.is_power_of_two() && imaginary.len() == len);
+
+    // Bit-reversal permutation
+    let mut reversed = 0;
+    for index in 1..len {
+        let mut bit = len >> 1;
+        while reversed & bit != 0 {
+            reversed ^= bit;
+            bit >>= 1;
+        }
+        reversed |= bit;
+        if index < reversed {
+            real.swap(index, reversed);
+            imaginary.swap(index, reversed);
+        }
+    }
+
+    let mut size = 2;
+    while size <= len {
+        let (sin, cos) = ops::sin_cos(-TAU / size as f32);
+        for start in (0..len).step_by(size) {
+            let (mut twiddle_real, mut twiddle_imaginary) = (1.0, 0.0);
+            for index in start..start + size / 2 {
+                let other = index + size / 2;
+                let product_real =
+                    real[other] * twiddle_real - imaginary[other] * twiddle_imaginary;
+                let product_imaginary =
+                    real[other] * twiddle_imaginary + imaginary[other] * twiddle_real;
+                real[other] = real[index] - product_real;
+                imaginary[other] = imaginary[index] - product_imaginary;
+                real[index] += product_real;
+                imaginary[index] += product_imaginary;
+                (twiddle_real, twiddle_imaginary) = (
+                    twiddle_real * cos - twiddle_imaginary * sin,
+                    twiddle_real * sin + twiddle_imaginary * cos,
+                );
+            }
+        }
+        size *= 2;
+    }
+}
+
+/// Publishes the spectra of the analyzed sounds and buses in the [`AudioAnalysis`] resource.
+pub(crate) fn update_audio_analysis(
+    mut analysis: ResMut<AudioAnalysis>,
+    buses: Res<AudioBuses>,
+    sinks: Query<(Entity, AnyOf<(&AudioSink, &SpatialAudioSink)>)>,
+) {
+    let band_edges: Vec<f32> = (0..=analysis.band_count)
+        .map(|edge| analysis.band_edge(edge))
+        .collect();
+    let analysis = analysis.as_mut();
+    analysis.sounds.clear();
+    analysis.buses.clear();
+
+    // Spectra of the buses, as sums of squared amplitudes
+    for (bus, settings) in buses.iter() {
+        if settings.analyze {
+            analysis.buses.insert(
+                bus,
+                AudioSpectrum {
+                    bands: vec![0.0; analysis.band_count],
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    for (entity, (sink, spatial_sink)) in &sinks {
+        let (tap, paused) = match (sink, spatial_sink) {
+            (Some(sink), _) => (sink.analysis.as_ref(), sink.is_paused()),
+            (_, Some(sink)) => (sink.analysis.as_ref(), sink.is_paused()),
+            (None, None) => continue,
+        };
+        let Some(tap) = tap else {
+            continue;
+        };
+        let spectrum = if paused {
+            analyze(&VecDeque::new(), 0, &band_edges)
+        } else {
+            tap.spectrum(&band_edges)
+        };
+
+        for bus in buses.path(tap.bus) {
+            if let Some(bus_spectrum) = analysis.buses.get_mut(&bus) {
+                bus_spectrum.rms += spectrum.rms * spectrum.rms;
+                bus_spectrum.peak = bus_spectrum.peak.max(spectrum.peak);
+                for (bus_band, band) in bus_spectrum.bands.iter_mut().zip(&spectrum.bands) {
+                    *bus_band += band * band;
+                }
+            }
+        }
+        if tap.analyzer {
+            analysis.sounds.insert(entity, spectrum);
+        }
+    }
+
+    for spectrum in analysis.buses.values_mut() {
+        spectrum.rms = ops::sqrt(spectrum.rms);
+        for band in &mut spectrum.bands {
+            *band = ops::sqrt(*band);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{analyze, AudioAnalysis, ANALYSIS_WINDOW};
+    use alloc::collections::VecDeque;
+    use bevy_math::ops;
+    use core::f32::consts::TAU;
+
+    #[test]
+    fn sine_is_in_its_band() {
+        let analysis = AudioAnalysis::default();
+        let band_edges: Vec<f32> = (0..=analysis.band_count)
+            .map(|edge| analysis.band_edge(edge))
+            .collect();
+        let sample_rate = 44100;
+        let frequency = 1000.0;
+        let samples: VecDeque<f32> = (0..ANALYSIS_WINDOW)
+            .map(|index| 0.5 * ops::sin(TAU * frequency * index as f32 / sample_rate as f32))
+            .collect();
+
+        let spectrum = analyze(&samples, sample_rate, &band_edges);
+        let band = analysis
+            .band_frequencies()
+            .position(|(low, high)| frequency >= low && frequency < high)
+            .unwrap();
+
+        assert!(ops::abs(spectrum.peak - 0.5) < 0.01);
+        assert!(ops::abs(spectrum.rms - 0.5 / core::f32::consts::SQRT_2) < 0.01);
+        assert!(ops::abs(spectrum.bands[band] - 0.5) < 0.05, "{spectrum:?}");
+        for (index, amplitude) in spectrum.bands.iter().enumerate() {
+            if index.abs_diff(band) > 1 {
+                assert!(*amplitude < 0.01, "{spectrum:?}");
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{AudioSource, Decodable, Volume};
+use crate::{AudioBus, AudioSource, AudioTime, Decodable, Hrtf, Volume};
 use bevy_asset::{Asset, Handle};
 use bevy_ecs::prelude::*;
 use bevy_math::Vec3;
@@ -37,6 +37,8 @@ pub struct PlaybackSettings {
     pub mode: PlaybackMode,
     /// Volume to play at.
     pub volume: Volume,
+    /// The mixer bus the audio is routed through, see [`AudioBuses`](crate::AudioBuses).
+    pub bus: AudioBus,
     /// Speed to play at.
     pub speed: f32,
     /// Create the sink in paused state.
@@ -52,8 +54,8 @@ pub struct PlaybackSettings {
     ///
     /// See also: [`SpatialListener`].
     ///
-    /// Note: Bevy does not currently support HRTF or any other high-quality 3D sound rendering
-    /// features. Spatial audio is implemented via simple left-right stereo panning.
+    /// By default, spatial audio is implemented via simple left-right stereo panning. Set
+    /// [`SpatialListener::hrtf`] to render it binaurally instead.
     pub spatial: bool,
     /// Optional scale factor applied to the positions of this audio source and the listener,
     /// overriding the default value configured on [`AudioPlugin::default_spatial_scale`](crate::AudioPlugin::default_spatial_scale).
@@ -68,6 +70,10 @@ pub struct PlaybackSettings {
     ///
     /// If the playback mode is set to `Loop`, each loop will last for this duration.
     pub duration: Option<core::time::Duration>,
+    /// The time on the [`AudioClock`](crate::AudioClock) at which playback should start, on the
+    /// exact sample. If set to `None`, or if the time has already passed, it will play as soon as
+    /// the audio is loaded.
+    pub start_time: Option<AudioTime>,
 }
 
 impl Default for PlaybackSettings {
@@ -87,6 +93,7 @@ impl PlaybackSettings {
     pub const ONCE: PlaybackSettings = PlaybackSettings {
         mode: PlaybackMode::Once,
         volume: Volume::Linear(1.0),
+        bus: AudioBus::MASTER,
         speed: 1.0,
         paused: false,
         muted: false,
@@ -94,6 +101,7 @@ impl PlaybackSettings {
         spatial_scale: None,
         start_position: None,
         duration: None,
+        start_time: None,
     };
 
     /// Will play the associated audio source in a loop.
@@ -132,6 +140,12 @@ impl PlaybackSettings {
         self
     }
 
+    /// Helper to route the audio through the given mixer bus.
+    pub const fn with_bus(mut self, bus: AudioBus) -> Self {
+        self.bus = bus;
+        self
+    }
+
     /// Helper to set the speed from start of playback.
     pub const fn with_speed(mut self, speed: f32) -> Self {
         self.speed = speed;
@@ -161,6 +175,12 @@ impl PlaybackSettings {
         self.duration = Some(duration);
         self
     }
+
+    /// Helper to start playback at a precise time on the [`AudioClock`](crate::AudioClock).
+    pub const fn play_at(mut self, start_time: AudioTime) -> Self {
+        self.start_time = Some(start_time);
+        self
+    }
 }
 
 /// Settings for the listener for spatial audio sources.
@@ -175,6 +195,15 @@ pub struct SpatialListener {
     pub left_ear_offset: Vec3,
     /// Right ear position relative to the [`GlobalTransform`](bevy_transform::prelude::GlobalTransform).
     pub right_ear_offset: Vec3,
+    /// The head-related transfer function used to render spatial audio binaurally.
+    ///
+    /// If `None`, spatial audio is panned between the left and right channels, which only gives
+    /// a sense of the horizontal direction of sounds. A [`Hrtf`] also conveys their elevation and
+    /// whether they are in front or behind the listener, but is only accurate on headphones.
+    ///
+    /// Sounds wait for the asset to be loaded before starting to play. Changing this field only
+    /// affects sounds that start playing afterwards.
+    pub hrtf: Option<Handle<Hrtf>>,
 }
 
 impl Default for SpatialListener {
@@ -192,8 +221,15 @@ impl SpatialListener {
         SpatialListener {
             left_ear_offset: Vec3::X * gap / -2.0,
             right_ear_offset: Vec3::X * gap / 2.0,
+            hrtf: None,
         }
     }
+
+    /// Renders spatial audio binaurally with the given [`Hrtf`].
+    pub fn with_hrtf(mut self, hrtf: Handle<Hrtf>) -> Self {
+        self.hrtf = Some(hrtf);
+        self
+    }
 }
 
 /// A scale factor applied to the positions of audio sources and listeners for
@@ -1,10 +1,12 @@
 use crate::{
-    AudioPlayer, Decodable, DefaultSpatialScale, GlobalVolume, PlaybackMode, PlaybackSettings,
-    SpatialAudioSink, SpatialListener,
+    analysis::AnalysisTap, bus::AppendSource, effects::AudioEffectsHandle,
+    occlusion::occlusion_handle, sinks::SpatialSinkBackend, AudioAnalyzer, AudioBus, AudioBuses,
+    AudioClock, AudioEffects, AudioOcclusion, AudioPlayer, Decodable, DefaultSpatialScale,
+    GlobalVolume, Hrtf, PlaybackMode, PlaybackSettings, SpatialAudioSink, SpatialListener,
 };
-use bevy_asset::{Asset, Assets};
+use bevy_asset::{Asset, Assets, Handle};
 use bevy_ecs::{prelude::*, system::SystemParam};
-use bevy_math::Vec3;
+use bevy_math::{Quat, Vec3};
 use bevy_transform::prelude::GlobalTransform;
 use rodio::{OutputStream, OutputStreamHandle, Sink, Source, SpatialSink};
 use tracing::warn;
@@ -28,11 +30,16 @@ pub(crate) struct AudioOutput {
     stream_handle: Option<OutputStreamHandle>,
 }
 
-impl Default for AudioOutput {
-    fn default() -> Self {
+impl FromWorld for AudioOutput {
+    fn from_world(world: &mut World) -> Self {
         if let Ok((stream, stream_handle)) = OutputStream::try_default() {
             // We leak `OutputStream` to prevent the audio from stopping.
             core::mem::forget(stream);
+            // Played for as long as the output exists, to drive the audio clock
+            let clock = world.get_resource_or_init::<AudioClock>();
+            if let Err(err) = stream_handle.play_raw(clock.source()) {
+                warn!("Error starting the audio clock: {err:?}");
+            }
             Self {
                 stream_handle: Some(stream_handle),
             }
@@ -82,6 +89,23 @@ impl<'w, 's> EarPositions<'w, 's> {
         (left_ear, right_ear)
     }
 
+    /// Gets the rotation of the listener, used to render sounds with a [`Hrtf`].
+    pub(crate) fn rotation(&self) -> Quat {
+        self.query
+            .iter()
+            .next()
+            .map(|(_, transform, _)| transform.rotation())
+            .unwrap_or_default()
+    }
+
+    /// Gets the [`Hrtf`] of the listener, if it renders spatial audio binaurally.
+    pub(crate) fn hrtf(&self) -> Option<&Handle<Hrtf>> {
+        self.query
+            .iter()
+            .next()
+            .and_then(|(_, _, settings)| settings.hrtf.as_ref())
+    }
+
     pub(crate) fn multiple_listeners(&self) -> bool {
         self.query.iter().len() > 1
     }
@@ -98,12 +122,18 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
     audio_output: Res<AudioOutput>,
     audio_sources: Res<Assets<Source>>,
     global_volume: Res<GlobalVolume>,
+    buses: Res<AudioBuses>,
+    clock: Res<AudioClock>,
+    hrtfs: Res<Assets<Hrtf>>,
     query_nonplaying: Query<
         (
             Entity,
             &AudioPlayer<Source>,
             &PlaybackSettings,
             Option<&GlobalTransform>,
+            Option<&AudioEffects>,
+            Option<&AudioOcclusion>,
+            Has<AudioAnalyzer>,
         ),
         (Without<AudioSink>, Without<SpatialAudioSink>),
     >,
@@ -118,11 +148,48 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
         return;
     };
 
-    for (entity, source_handle, settings, maybe_emitter_transform) in &query_nonplaying {
+    for (
+        entity,
+        source_handle,
+        settings,
+        maybe_emitter_transform,
+        maybe_effects,
+        maybe_occlusion,
+        analyzer,
+    ) in &query_nonplaying
+    {
         let Some(audio_source) = audio_sources.get(&source_handle.0) else {
             continue;
         };
+        let hrtf = if settings.spatial
+            && let Some(hrtf) = ear_positions.hrtf()
+        {
+            // Wait for the HRTF rather than playing the sound with panning
+            let Some(hrtf) = hrtfs.get(hrtf) else {
+                continue;
+            };
+            Some(hrtf)
+        } else {
+            None
+        };
         // audio data is available (has loaded), begin playback and insert sink component
+        let effects = maybe_effects.map(|effects| AudioEffectsHandle::new(&effects.0));
+        let occlusion = settings.spatial.then(|| occlusion_handle(maybe_occlusion));
+        let sound_effects = || effects.iter().chain(&occlusion);
+        let route = buses
+            .route(settings.bus, sound_effects())
+            .unwrap_or_else(|| {
+                warn!(
+                    "{:?} doesn't exist, routing the audio through the master bus instead.",
+                    settings.bus
+                );
+                buses.route(AudioBus::MASTER, sound_effects()).unwrap()
+            })
+            .starting_at(&clock, settings.start_time);
+        let analysis = (analyzer || buses.is_analyzed(settings.bus))
+            .then(|| AnalysisTap::new(settings.bus, analyzer));
+        let route = route.analyzed_by(analysis.clone());
+
         if settings.spatial {
             let (left_ear, right_ear) = ear_positions.get();
 
@@ -138,70 +205,71 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
             let scale = settings.spatial_scale.unwrap_or(default_spatial_scale.0).0;
 
             let emitter_translation = if let Some(emitter_transform) = maybe_emitter_transform {
-                (emitter_transform.translation() * scale).into()
+                emitter_transform.translation() * scale
             } else {
                 warn!("Spatial AudioPlayer with no GlobalTransform component. Using zero.");
-                Vec3::ZERO.into()
+                Vec3::ZERO
             };
 
-            let sink = match SpatialSink::try_new(
-                stream_handle,
-                emitter_translation,
-                (left_ear * scale).into(),
-                (right_ear * scale).into(),
-            ) {
-                Ok(sink) => sink,
+            let sink = if let Some(hrtf) = hrtf {
+                Sink::try_new(stream_handle).map(|sink| {
+                    SpatialSinkBackend::hrtf(
+                        sink,
+                        hrtf.clone(),
+                        emitter_translation,
+                        left_ear * scale,
+                        right_ear * scale,
+                        ear_positions.rotation(),
+                    )
+                })
+            } else {
+                SpatialSink::try_new(
+                    stream_handle,
+                    emitter_translation.into(),
+                    (left_ear * scale).into(),
+                    (right_ear * scale).into(),
+                )
+                .map(SpatialSinkBackend::Panning)
+            };
+            let mut sink = match sink {
+                Ok(sink) => SpatialAudioSink::from_backend(sink),
                 Err(err) => {
                     warn!("Error creating spatial sink: {err:?}");
                     continue;
                 }
             };
 
-            let decoder = audio_source.decoder();
-
             match settings.mode {
-                PlaybackMode::Loop => match (settings.start_position, settings.duration) {
-                    // custom start position and duration
-                    (Some(start_position), Some(duration)) => sink.append(
-                        decoder
-                            .skip_duration(start_position)
-                            .take_duration(duration)
-                            .repeat_infinite(),
-                    ),
-
-                    // custom start position
-                    (Some(start_position), None) => {
-                        sink.append(decoder.skip_duration(start_position).repeat_infinite());
-                    }
-
-                    // custom duration
-                    (None, Some(duration)) => {
-                        sink.append(decoder.take_duration(duration).repeat_infinite());
-                    }
-
-                    // full clip
-                    (None, None) => sink.append(decoder.repeat_infinite()),
-                },
+                PlaybackMode::Loop => sink.append_source(route.apply(
+                    audio_source.looping_decoder(settings.start_position, settings.duration),
+                )),
                 PlaybackMode::Once | PlaybackMode::Despawn | PlaybackMode::Remove => {
+                    let decoder = audio_source.decoder();
                     match (settings.start_position, settings.duration) {
-                        (Some(start_position), Some(duration)) => sink.append(
-                            decoder
-                                .skip_duration(start_position)
-                                .take_duration(duration),
+                        (Some(start_position), Some(duration)) => sink.append_source(
+                            route.apply(
+                                decoder
+                                    .skip_duration(start_position)
+                                    .take_duration(duration),
+                            ),
                         ),
 
                         (Some(start_position), None) => {
-                            sink.append(decoder.skip_duration(start_position));
+                            sink.append_source(route.apply(decoder.skip_duration(start_position)));
                         }
 
-                        (None, Some(duration)) => sink.append(decoder.take_duration(duration)),
+                        (None, Some(duration)) => {
+                            sink.append_source(route.apply(decoder.take_duration(duration)));
+                        }
 
-                        (None, None) => sink.append(decoder),
+                        (None, None) => sink.append_source(route.apply(decoder)),
                     }
                 }
             }
 
-            let mut sink = SpatialAudioSink::new(sink);
+            sink.effects = effects;
+            sink.analysis = analysis;
+            sink.occlusion = occlusion.unwrap();
 
             if settings.muted {
                 sink.mute();
@@ -234,51 +302,37 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
                 }
             };
 
-            let decoder = audio_source.decoder();
-
             match settings.mode {
-                PlaybackMode::Loop => match (settings.start_position, settings.duration) {
-                    // custom start position and duration
-                    (Some(start_position), Some(duration)) => sink.append(
-                        decoder
-                            .skip_duration(start_position)
-                            .take_duration(duration)
-                            .repeat_infinite(),
-                    ),
-
-                    // custom start position
-                    (Some(start_position), None) => {
-                        sink.append(decoder.skip_duration(start_position).repeat_infinite());
-                    }
-
-                    // custom duration
-                    (None, Some(duration)) => {
-                        sink.append(decoder.take_duration(duration).repeat_infinite());
-                    }
-
-                    // full clip
-                    (None, None) => sink.append(decoder.repeat_infinite()),
-                },
+                PlaybackMode::Loop => sink.append_source(route.apply(
+                    audio_source.looping_decoder(settings.start_position, settings.duration),
+                )),
                 PlaybackMode::Once | PlaybackMode::Despawn | PlaybackMode::Remove => {
+                    let decoder = audio_source.decoder();
                     match (settings.start_position, settings.duration) {
-                        (Some(start_position), Some(duration)) => sink.append(
-                            decoder
-                                .skip_duration(start_position)
-                                .take_duration(duration),
+                        (Some(start_position), Some(duration)) => sink.append_source(
+                            route.apply(
+                                decoder
+                                    .skip_duration(start_position)
+                                    .take_duration(duration),
+                            ),
                         ),
 
                         (Some(start_position), None) => {
-                            sink.append(decoder.skip_duration(start_position));
+                            sink.append_source(route.apply(decoder.skip_duration(start_position)));
                         }
 
-                        (None, Some(duration)) => sink.append(decoder.take_duration(duration)),
+                        (None, Some(duration)) => {
+                            sink.append_source(route.apply(decoder.take_duration(duration)));
+                        }
 
-                        (None, None) => sink.append(decoder),
+                        (None, None) => sink.append_source(route.apply(decoder)),
                     }
                 }
             }
 
             let mut sink = AudioSink::new(sink);
+            sink.effects = effects;
+            sink.analysis = analysis;
 
             if settings.muted {
                 sink.mute();
@@ -400,10 +454,12 @@ pub(crate) fn update_listener_positions(
     }
 
     let (left_ear, right_ear) = ear_positions.get();
+    let rotation = ear_positions.rotation();
 
     for (sink, settings) in emitters.iter_mut() {
         let scale = settings.spatial_scale.unwrap_or(default_spatial_scale.0).0;
 
         sink.set_ears_position(left_ear * scale, right_ear * scale);
+        sink.set_listener_rotation(rotation);
     }
 }
@@ -1,6 +1,9 @@
+use crate::{loop_points::LoopPointsSource, LoopPoints};
 use alloc::sync::Arc;
 use bevy_asset::{io::Reader, Asset, AssetLoader, LoadContext};
 use bevy_reflect::TypePath;
+use core::time::Duration;
+use rodio::Source as _;
 use std::io::Cursor;
 
 /// A source of audio data
@@ -18,6 +21,15 @@ pub struct AudioSource {
     /// If the format used is not enabled,
     /// then this will panic with an `UnrecognizedFormat` error.
     pub bytes: Arc<[u8]>,
+    /// The region repeated when the audio is played with [`PlaybackMode::Loop`](crate::PlaybackMode::Loop).
+    ///
+    /// If set to `None`, the whole audio is repeated. The [`AudioLoader`] reads the loop points
+    /// from the metadata of WAV and Ogg Vorbis files, see [`LoopPoints::from_metadata`].
+    ///
+    /// When looping with loop points, [`PlaybackSettings::start_position`](crate::PlaybackSettings::start_position)
+    /// only applies to the first pass, and [`PlaybackSettings::duration`](crate::PlaybackSettings::duration)
+    /// is ignored.
+    pub loop_points: Option<LoopPoints>,
 }
 
 impl AsRef<[u8]> for AudioSource {
@@ -51,6 +63,7 @@ impl AssetLoader for AudioLoader {
         let mut bytes = Vec::new();
         reader.read_to_end(&mut bytes).await?;
         Ok(AudioSource {
+            loop_points: LoopPoints::from_metadata(&bytes),
             bytes: bytes.into(),
         })
     }
@@ -92,6 +105,55 @@ pub trait Decodable: Send + Sync + 'static {
 
     /// Build and return a [`Self::Decoder`] of the implementing type
     fn decoder(&self) -> Self::Decoder;
+
+    /// Build and return a [`rodio::Source`] that plays the audio in a loop.
+    ///
+    /// Each loop starts at `start_position` and plays at most `duration` of the audio, as in
+    /// [`PlaybackSettings`](crate::PlaybackSettings).
+    ///
+    /// The default implementation repeats [`Self::decoder`] with
+    /// [`Source::repeat_infinite`](rodio::Source::repeat_infinite), which keeps the samples
+    /// decoded during the first loop in memory. Types with long audio can override it to decode
+    /// the audio again for each loop instead.
+    fn looping_decoder(
+        &self,
+        start_position: Option<Duration>,
+        duration: Option<Duration>,
+    ) -> Box<dyn rodio::Source<Item = Self::DecoderItem> + Send> {
+        repeat_decoder(self.decoder(), start_position, duration)
+    }
+}
+
+/// Repeats the decoder as described in [`Decodable::looping_decoder`].
+fn repeat_decoder<D>(
+    decoder: D,
+    start_position: Option<Duration>,
+    duration: Option<Duration>,
+) -> Box<dyn rodio::Source<Item = D::Item> + Send>
+where
+    D: rodio::Source + Send + 'static,
+    D::Item: rodio::Sample + Send + Sync,
+{
+    match (start_position, duration) {
+        // custom start position and duration
+        (Some(start_position), Some(duration)) => Box::new(
+            decoder
+                .skip_duration(start_position)
+                .take_duration(duration)
+                .repeat_infinite(),
+        ),
+
+        // custom start position
+        (Some(start_position), None) => {
+            Box::new(decoder.skip_duration(start_position).repeat_infinite())
+        }
+
+        // custom duration
+        (None, Some(duration)) => Box::new(decoder.take_duration(duration).repeat_infinite()),
+
+        // full clip
+        (None, None) => Box::new(decoder.repeat_infinite()),
+    }
 }
 
 impl Decodable for AudioSource {
@@ -101,6 +163,21 @@ impl Decodable for AudioSource {
     fn decoder(&self) -> Self::Decoder {
         rodio::Decoder::new(Cursor::new(self.clone())).unwrap()
     }
+
+    fn looping_decoder(
+        &self,
+        start_position: Option<Duration>,
+        duration: Option<Duration>,
+    ) -> Box<dyn rodio::Source<Item = Self::DecoderItem> + Send> {
+        match self.loop_points {
+            Some(loop_points) => Box::new(LoopPointsSource::new(
+                self.decoder(),
+                loop_points,
+                start_position,
+            )),
+            None => repeat_decoder(self.decoder(), start_position, duration),
+        }
+    }
 }
 
 /// A trait that allows adding a custom audio source to the object.
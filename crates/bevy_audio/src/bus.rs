@@ -0,0 +1,404 @@
+use crate::{
+    analysis::{AnalysisSource, AnalysisTap},
+    clock::ScheduledSource,
+    effects::{AudioEffectsHandle, EffectsSource},
+    AudioClock, AudioEffect, AudioTime, Volume,
+};
+use alloc::sync::Arc;
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+use core::{
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
+};
+use rodio::{Sample, Sink, Source};
+
+/// Identifies a mixer bus in the [`AudioBuses`] resource.
+///
+/// Every sound is routed through a bus, selected with [`PlaybackSettings::bus`](crate::PlaybackSettings::bus).
+/// The default buses are [`AudioBus::MASTER`], and its children [`AudioBus::MUSIC`], [`AudioBus::SFX`]
+/// and [`AudioBus::VOICE`]. More buses can be created with [`AudioBuses::add`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Clone, Debug, PartialEq, Hash)]
+pub struct AudioBus(u32);
+
+impl AudioBus {
+    /// The root bus, every other bus is routed through it.
+    pub const MASTER: Self = Self(0);
+    /// The default bus for music.
+    pub const MUSIC: Self = Self(1);
+    /// The default bus for sound effects.
+    pub const SFX: Self = Self(2);
+    /// The default bus for dialogue.
+    pub const VOICE: Self = Self(3);
+}
+
+impl Default for AudioBus {
+    fn default() -> Self {
+        Self::MASTER
+    }
+}
+
+/// The settings of a bus in the [`AudioBuses`] resource.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Clone, Debug, PartialEq, Default)]
+pub struct AudioBusSettings {
+    /// The volume of the bus, multiplied with the volume of its parent buses.
+    pub volume: Volume,
+    /// Silences the bus and all of its child buses.
+    pub muted: bool,
+    /// While any bus is soloed, only sounds routed through a soloed bus can be heard.
+    ///
+    /// Sounds routed directly to [`AudioBus::MASTER`], which includes sounds without an
+    /// [`AudioBus`] component, are not affected by soloing.
+    pub solo: bool,
+    /// Publishes the spectrum of the bus in the [`AudioAnalysis`](crate::AudioAnalysis) resource.
+    ///
+    /// Only sounds that start playing while this is enabled are analyzed.
+    pub analyze: bool,
+}
+
+impl Default for AudioBusSettings {
+    fn default() -> Self {
+        Self {
+            volume: Volume::Linear(1.0),
+            muted: false,
+            solo: false,
+            analyze: false,
+        }
+    }
+}
+
+/// A tree of mixer buses, used to control the volume of groups of sounds.
+///
+/// Changes to the settings of a bus are applied to all sounds routed through the bus and its child
+/// buses, including sounds that are already playing. This is independent of the volume of each
+/// [`AudioSink`](crate::AudioSink), so a settings menu can expose a volume slider per bus without
+/// keeping track of the sounds that are playing.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_audio::{AudioBus, AudioBuses, Volume};
+/// fn set_music_volume(mut buses: ResMut<AudioBuses>) {
+///     buses.settings_mut(AudioBus::MUSIC).volume = Volume::Linear(0.5);
+/// }
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct AudioBuses {
+    buses: Vec<AudioBusState>,
+}
+
+#[derive(Debug, Clone)]
+struct AudioBusState {
+    parent: Option<AudioBus>,
+    settings: AudioBusSettings,
+    effects: Vec<AudioEffect>,
+    gain: AudioBusGain,
+    effects_handle: AudioEffectsHandle,
+    /// The gain applied by the [`MusicPlayer`](crate::MusicPlayer) while it ducks the bus.
+    ducking: f32,
+}
+
+impl AudioBusState {
+    fn new(parent: Option<AudioBus>, settings: AudioBusSettings) -> Self {
+        Self {
+            parent,
+            settings,
+            effects: Vec::new(),
+            gain: AudioBusGain::default(),
+            effects_handle: AudioEffectsHandle::default(),
+            ducking: 1.0,
+        }
+    }
+}
+
+impl Default for AudioBuses {
+    fn default() -> Self {
+        let mut buses = Self {
+            buses: vec![AudioBusState::new(None, AudioBusSettings::default())],
+        };
+        buses.add(AudioBus::MASTER);
+        buses.add(AudioBus::MASTER);
+        buses.add(AudioBus::MASTER);
+        buses
+    }
+}
+
+impl AudioBuses {
+    /// Adds a new bus routed through the `parent` bus.
+    pub fn add(&mut self, parent: AudioBus) -> AudioBus {
+        self.add_with_settings(parent, AudioBusSettings::default())
+    }
+
+    /// Adds a new bus with the given settings, routed through the `parent` bus.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `parent` doesn't belong to this resource.
+    pub fn add_with_settings(&mut self, parent: AudioBus, settings: AudioBusSettings) -> AudioBus {
+        assert!(
+            (parent.0 as usize) < self.buses.len(),
+            "{parent:?} doesn't exist"
+        );
+        let bus = AudioBus(self.buses.len() as u32);
+        self.buses.push(AudioBusState::new(Some(parent), settings));
+        self.update_gains();
+        bus
+    }
+
+    /// Returns the parent of the bus, or `None` for [`AudioBus::MASTER`].
+    pub fn parent(&self, bus: AudioBus) -> Option<AudioBus> {
+        self.state(bus).parent
+    }
+
+    /// Returns the settings of the bus.
+    pub fn settings(&self, bus: AudioBus) -> &AudioBusSettings {
+        &self.state(bus).settings
+    }
+
+    /// Returns the settings of the bus for mutation.
+    pub fn settings_mut(&mut self, bus: AudioBus) -> &mut AudioBusSettings {
+        &mut self.buses[bus.0 as usize].settings
+    }
+
+    /// Returns the effects applied to the sounds routed through the bus.
+    pub fn effects(&self, bus: AudioBus) -> &[AudioEffect] {
+        &self.state(bus).effects
+    }
+
+    /// Returns the effects applied to the sounds routed through the bus for mutation.
+    ///
+    /// Sounds routed through a child bus go through the effects of the child bus first. Each sound
+    /// is processed separately, so a [`AudioEffect::Compressor`] reacts to the volume of
+    /// individual sounds rather than to the volume of the bus.
+    pub fn effects_mut(&mut self, bus: AudioBus) -> &mut Vec<AudioEffect> {
+        &mut self.buses[bus.0 as usize].effects
+    }
+
+    /// Iterates over the buses, starting with [`AudioBus::MASTER`].
+    pub fn iter(&self) -> impl Iterator<Item = (AudioBus, &AudioBusSettings)> {
+        self.buses
+            .iter()
+            .enumerate()
+            .map(|(index, state)| (AudioBus(index as u32), &state.settings))
+    }
+
+    /// Returns the volume applied to the sounds routed through the bus, taking into account its
+    /// parent buses, muting, soloing and ducking.
+    pub fn effective_volume(&self, bus: AudioBus) -> Volume {
+        let any_solo = self.buses.iter().any(|state| state.settings.solo);
+
+        let mut volume = Volume::Linear(1.0);
+        let mut soloed = false;
+        let mut next = Some(bus);
+        while let Some(bus) = next {
+            let state = self.state(bus);
+            if state.settings.muted {
+                return Volume::SILENT;
+            }
+            volume *= state.settings.volume * Volume::Linear(state.ducking);
+            soloed |= state.settings.solo;
+            next = state.parent;
+        }
+
+        // Sounds routed directly to the master bus aren't part of any group that could be soloed.
+        if any_solo && !soloed && bus != AudioBus::MASTER {
+            Volume::SILENT
+        } else {
+            volume
+        }
+    }
+
+    /// Iterates over the bus and its parents, up to [`AudioBus::MASTER`].
+    pub(crate) fn path(&self, bus: AudioBus) -> impl Iterator<Item = AudioBus> + '_ {
+        core::iter::successors(Some(bus), |bus| self.state(*bus).parent)
+    }
+
+    /// Returns `true` if the bus or one of its parents is analyzed.
+    pub(crate) fn is_analyzed(&self, bus: AudioBus) -> bool {
+        self.path(bus).any(|bus| self.state(bus).settings.analyze)
+    }
+
+    /// Returns the gain applied to the bus while it is ducked.
+    pub(crate) fn ducking(&self, bus: AudioBus) -> f32 {
+        self.state(bus).ducking
+    }
+
+    /// Sets the gain applied to the bus while it is ducked, and updates the gains of the buses.
+    pub(crate) fn set_ducking(&mut self, bus: AudioBus, gain: f32) {
+        self.buses[bus.0 as usize].ducking = gain;
+        self.update_gains();
+    }
+
+    /// Returns the route through the bus and its parents, for a sound with the given effects.
+    pub(crate) fn route<'a>(
+        &self,
+        bus: AudioBus,
+        sound_effects: impl IntoIterator<Item = &'a AudioEffectsHandle>,
+    ) -> Option<AudioRoute> {
+        let gain = self.buses.get(bus.0 as usize)?.gain.clone();
+        let mut effects: Vec<_> = sound_effects.into_iter().cloned().collect();
+        let mut next = Some(bus);
+        while let Some(bus) = next {
+            let state = self.state(bus);
+            effects.push(state.effects_handle.clone());
+            next = state.parent;
+        }
+        Some(AudioRoute {
+            gain,
+            effects,
+            schedule: None,
+            analysis: None,
+        })
+    }
+
+    fn state(&self, bus: AudioBus) -> &AudioBusState {
+        &self.buses[bus.0 as usize]
+    }
+
+    fn update_gains(&self) {
+        for (index, state) in self.buses.iter().enumerate() {
+            state
+                .gain
+                .set(self.effective_volume(AudioBus(index as u32)).to_linear());
+        }
+    }
+}
+
+/// Applies the settings and effects of the [`AudioBuses`] to the sounds that are playing.
+pub(crate) fn update_audio_buses(buses: Res<AudioBuses>) {
+    if buses.is_changed() {
+        buses.update_gains();
+        for state in &buses.buses {
+            state.effects_handle.set(&state.effects);
+        }
+    }
+}
+
+/// The effects and gain applied to a sound, from the sound itself and the buses it is routed through.
+pub(crate) struct AudioRoute {
+    gain: AudioBusGain,
+    effects: Vec<AudioEffectsHandle>,
+    schedule: Option<(AudioClock, AudioTime)>,
+    analysis: Option<AnalysisTap>,
+}
+
+impl AudioRoute {
+    /// Delays the sound until the clock reaches `start_time`, if there is one.
+    pub(crate) fn starting_at(mut self, clock: &AudioClock, start_time: Option<AudioTime>) -> Self {
+        self.schedule = start_time.map(|start_time| (clock.clone(), start_time));
+        self
+    }
+
+    /// Sends the processed sound to the analysis tap, if there is one.
+    pub(crate) fn analyzed_by(mut self, analysis: Option<AnalysisTap>) -> Self {
+        self.analysis = analysis;
+        self
+    }
+
+    /// Wraps the source so it is processed by the effects and scaled by the gain of the route.
+    pub(crate) fn apply<S>(&self, source: S) -> impl Source<Item = f32> + Send + 'static
+    where
+        S: Source + Send + 'static,
+        S::Item: Sample + Send,
+    {
+        let gain = self.gain.clone();
+        let source = ScheduledSource::new(
+            EffectsSource::new(source, self.effects.clone()),
+            self.schedule.clone(),
+        )
+        .amplify(gain.get())
+        .periodic_access(BUS_GAIN_UPDATE_PERIOD, move |source| {
+            source.set_factor(gain.get());
+        });
+        AnalysisSource::new(source, self.analysis.as_ref())
+    }
+}
+
+/// How often playing sounds check for changes to the gain of their bus.
+const BUS_GAIN_UPDATE_PERIOD: Duration = Duration::from_millis(5);
+
+/// The linear gain of a bus, shared with the sounds routed through it.
+#[derive(Debug, Clone)]
+pub(crate) struct AudioBusGain(Arc<AtomicU32>);
+
+impl Default for AudioBusGain {
+    fn default() -> Self {
+        Self(Arc::new(AtomicU32::new(1.0f32.to_bits())))
+    }
+}
+
+impl AudioBusGain {
+    fn get(&self) -> f32 {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    fn set(&self, gain: f32) {
+        self.0.store(gain.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// A sink that sounds can be appended to once they have been routed with [`AudioRoute::apply`].
+pub(crate) trait AppendSource {
+    fn append_source(&self, source: impl Source<Item = f32> + Send + 'static);
+}
+
+impl AppendSource for Sink {
+    fn append_source(&self, source: impl Source<Item = f32> + Send + 'static) {
+        self.append(source);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AudioBus, AudioBuses};
+    use crate::Volume;
+
+    #[test]
+    fn bus_volume_is_hierarchical() {
+        let mut buses = AudioBuses::default();
+        let ambience = buses.add(AudioBus::SFX);
+
+        buses.settings_mut(AudioBus::MASTER).volume = Volume::Linear(0.5);
+        buses.settings_mut(AudioBus::SFX).volume = Volume::Linear(0.5);
+
+        assert_eq!(buses.effective_volume(ambience), Volume::Linear(0.25));
+        assert_eq!(buses.effective_volume(AudioBus::MUSIC), Volume::Linear(0.5));
+
+        buses.settings_mut(AudioBus::SFX).muted = true;
+        assert_eq!(buses.effective_volume(ambience), Volume::SILENT);
+        assert_eq!(buses.effective_volume(AudioBus::MUSIC), Volume::Linear(0.5));
+    }
+
+    #[test]
+    fn solo_silences_other_buses() {
+        let mut buses = AudioBuses::default();
+        let ambience = buses.add(AudioBus::SFX);
+
+        buses.settings_mut(AudioBus::SFX).solo = true;
+
+        assert_eq!(buses.effective_volume(ambience), Volume::Linear(1.0));
+        assert_eq!(buses.effective_volume(AudioBus::SFX), Volume::Linear(1.0));
+        assert_eq!(buses.effective_volume(AudioBus::MUSIC), Volume::SILENT);
+
+        buses.settings_mut(AudioBus::SFX).solo = false;
+        assert_eq!(buses.effective_volume(AudioBus::MUSIC), Volume::Linear(1.0));
+    }
+
+    #[test]
+    fn solo_does_not_affect_master_sounds() {
+        let mut buses = AudioBuses::default();
+        buses.settings_mut(AudioBus::MASTER).volume = Volume::Linear(0.5);
+
+        buses.settings_mut(AudioBus::SFX).solo = true;
+        assert_eq!(
+            buses.effective_volume(AudioBus::MASTER),
+            Volume::Linear(0.5)
+        );
+        assert_eq!(buses.effective_volume(AudioBus::MUSIC), Volume::SILENT);
+
+        // Muting the master bus still silences its sounds.
+        buses.settings_mut(AudioBus::MASTER).muted = true;
+        assert_eq!(buses.effective_volume(AudioBus::MASTER), Volume::SILENT);
+    }
+}
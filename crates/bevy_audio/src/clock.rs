@@ -0,0 +1,288 @@
+use alloc::sync::Arc;
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+use core::{
+    ops::{Add, AddAssign, Sub},
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+use rodio::{source::SeekError, Source};
+
+/// A point in time on the [`AudioClock`].
+///
+/// Sounds can be scheduled to start at a precise [`AudioTime`] with
+/// [`PlaybackSettings::play_at`](crate::PlaybackSettings::play_at).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+#[reflect(Clone, Debug, Default, PartialEq, Hash)]
+pub struct AudioTime(Duration);
+
+impl AudioTime {
+    /// The start of the [`AudioClock`].
+    pub const ZERO: Self = Self(Duration::ZERO);
+
+    /// Creates an [`AudioTime`] from the time elapsed since the start of the [`AudioClock`].
+    pub const fn from_duration(elapsed: Duration) -> Self {
+        Self(elapsed)
+    }
+
+    /// Returns the time elapsed since the start of the [`AudioClock`].
+    pub const fn elapsed(self) -> Duration {
+        self.0
+    }
+
+    /// Returns the duration from `earlier` to this time, or zero if `earlier` is later.
+    pub fn saturating_duration_since(self, earlier: Self) -> Duration {
+        self.0.saturating_sub(earlier.0)
+    }
+}
+
+impl Add<Duration> for AudioTime {
+    type Output = Self;
+
+    fn add(self, rhs: Duration) -> Self {
+        Self(self.0 + rhs)
+    }
+}
+
+impl AddAssign<Duration> for AudioTime {
+    fn add_assign(&mut self, rhs: Duration) {
+        self.0 += rhs;
+    }
+}
+
+impl Sub<Duration> for AudioTime {
+    type Output = Self;
+
+    fn sub(self, rhs: Duration) -> Self {
+        Self(self.0.saturating_sub(rhs))
+    }
+}
+
+/// The clock of the audio output, counting the samples that have been sent to the audio device.
+///
+/// Unlike [`Time`](bevy_time::Time), this clock is not
+/// subject to the jitter of the frame rate, and sounds scheduled with
+/// [`PlaybackSettings::play_at`](crate::PlaybackSettings::play_at) start on the exact sample.
+/// This makes it suitable for rhythm games and for layering music:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_asset::Handle;
+/// # use bevy_audio::{AudioClock, AudioPlayer, AudioSource, PlaybackSettings, Tempo};
+/// fn play_on_next_beat(mut commands: Commands, clock: Res<AudioClock>, drum: Handle<AudioSource>) {
+///     let tempo = Tempo::new(120.0);
+///     commands.spawn((
+///         AudioPlayer(drum),
+///         PlaybackSettings::ONCE.play_at(tempo.next_beat(clock.now()) + tempo.beats(1.0)),
+///     ));
+/// }
+/// ```
+///
+/// The audio device requests samples in batches, so [`AudioClock::now`] advances in steps of
+/// a few milliseconds. Schedule sounds far enough in advance to account for it. The clock doesn't
+/// advance if there is no audio device.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct AudioClock(Arc<AtomicU64>);
+
+impl AudioClock {
+    /// The sample rate the clock counts at.
+    const SAMPLE_RATE: u32 = 48_000;
+
+    /// Returns the current time of the clock.
+    pub fn now(&self) -> AudioTime {
+        let samples = self.0.load(Ordering::Acquire);
+        AudioTime(Duration::from_nanos(
+            samples * 1_000_000_000 / Self::SAMPLE_RATE as u64,
+        ))
+    }
+
+    /// Returns a silent source that advances the clock as it is played.
+    pub(crate) fn source(&self) -> ClockSource {
+        ClockSource(self.0.clone())
+    }
+}
+
+/// A tempo, used to schedule sounds on musical beats.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Clone, Debug, PartialEq)]
+pub struct Tempo {
+    /// The number of beats per minute.
+    pub bpm: f32,
+}
+
+impl Tempo {
+    /// Creates a [`Tempo`] with the given number of beats per minute.
+    pub const fn new(bpm: f32) -> Self {
+        Self { bpm }
+    }
+
+    /// Returns the duration of the given number of beats.
+    pub fn beats(&self, beats: f32) -> Duration {
+        Duration::from_secs_f64(beats.max(0.0) as f64 * 60.0 / self.bpm as f64)
+    }
+
+    /// Returns the first beat at or after `time`, counting beats from the start of the
+    /// [`AudioClock`].
+    pub fn next_beat(&self, time: AudioTime) -> AudioTime {
+        let beat = self.beats(1.0).as_nanos();
+        if beat == 0 {
+            return time;
+        }
+        let elapsed = time.0.as_nanos();
+        let beats = elapsed.div_ceil(beat);
+        AudioTime(Duration::from_nanos((beats * beat) as u64))
+    }
+}
+
+/// A silent source counting the samples it outputs, played continuously to drive the [`AudioClock`].
+pub(crate) struct ClockSource(Arc<AtomicU64>);
+
+impl Iterator for ClockSource {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        self.0.fetch_add(1, Ordering::Release);
+        Some(0.0)
+    }
+}
+
+impl Source for ClockSource {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        AudioClock::SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// Delays a source until the [`AudioClock`] reaches its start time.
+///
+/// The delay is measured when the source is first played, so that it doesn't depend on when the
+/// sound was queued.
+pub(crate) struct ScheduledSource<S> {
+    input: S,
+    /// The clock and start time of the source, if it is scheduled.
+    schedule: Option<(AudioClock, AudioTime)>,
+    /// The number of silent samples left before the input starts, once the delay is known.
+    remaining: Option<u64>,
+}
+
+impl<S> ScheduledSource<S>
+where
+    S: Source<Item = f32>,
+{
+    pub(crate) fn new(input: S, schedule: Option<(AudioClock, AudioTime)>) -> Self {
+        Self {
+            input,
+            schedule,
+            remaining: None,
+        }
+    }
+}
+
+impl<S> Iterator for ScheduledSource<S>
+where
+    S: Source<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let remaining = self.remaining.get_or_insert_with(|| {
+            let Some((clock, start)) = &self.schedule else {
+                return 0;
+            };
+            let delay = start.saturating_duration_since(clock.now());
+            let frames = (delay.as_secs_f64() * self.input.sample_rate() as f64).round() as u64;
+            frames * self.input.channels() as u64
+        });
+        if *remaining > 0 {
+            *remaining -= 1;
+            return Some(0.0);
+        }
+        self.input.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<S> Source for ScheduledSource<S>
+where
+    S: Source<Item = f32>,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        match self.remaining {
+            Some(remaining) if remaining > 0 => Some(remaining as usize),
+            Some(_) => self.input.current_frame_len(),
+            // The delay is only known once playback starts
+            None => Some(self.input.channels() as usize),
+        }
+    }
+
+    fn channels(&self) -> u16 {
+        self.input.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.input.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        // Seeking starts the sound immediately
+        self.remaining = Some(0);
+        self.input.try_seek(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AudioClock, AudioTime, ScheduledSource, Tempo};
+    use core::time::Duration;
+    use rodio::buffer::SamplesBuffer;
+
+    #[test]
+    fn next_beat() {
+        let tempo = Tempo::new(120.0);
+        assert_eq!(tempo.beats(2.0), Duration::from_secs(1));
+        assert_eq!(tempo.next_beat(AudioTime::ZERO), AudioTime::ZERO);
+        assert_eq!(
+            tempo.next_beat(AudioTime::from_duration(Duration::from_millis(600))),
+            AudioTime::from_duration(Duration::from_secs(1))
+        );
+    }
+
+    #[test]
+    fn scheduled_source_starts_on_time() {
+        let clock = AudioClock::default();
+        let mut clock_source = clock.source();
+        // Advance the clock by 10ms
+        for _ in 0..480 {
+            clock_source.next();
+        }
+
+        let start = AudioTime::from_duration(Duration::from_millis(20));
+        let source = ScheduledSource::new(
+            SamplesBuffer::new(2, 1000, vec![1.0; 4]),
+            Some((clock, start)),
+        );
+        let samples: Vec<f32> = source.collect();
+        // 10ms of silence at 1 kHz, for both channels
+        assert_eq!(samples.len(), 24);
+        assert!(samples[..20].iter().all(|sample| *sample == 0.0));
+        assert!(samples[20..].iter().all(|sample| *sample == 1.0));
+    }
+}
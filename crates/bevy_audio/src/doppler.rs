@@ -0,0 +1,204 @@
+use crate::{DefaultSpatialScale, PlaybackSettings, SpatialAudioSink, SpatialListener};
+use alloc::sync::Arc;
+use bevy_ecs::prelude::*;
+use bevy_math::Vec3;
+use bevy_reflect::prelude::*;
+use bevy_time::Time;
+use bevy_transform::prelude::GlobalTransform;
+use core::{
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
+};
+use rodio::Source;
+
+/// Configures the Doppler effect, the change of pitch of spatial sounds moving relative to the
+/// [`SpatialListener`].
+///
+/// Velocities are measured after applying the [`SpatialScale`](crate::SpatialScale) of the sound.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource, Debug, Default, Clone, PartialEq)]
+pub struct DopplerSettings {
+    /// Exaggerates or reduces the Doppler effect. `0.0` disables it, `1.0` is physically accurate.
+    pub factor: f32,
+    /// The speed of sound, in scaled units per second.
+    ///
+    /// Relative speeds are limited to half of the speed of sound to avoid extreme pitches, for
+    /// example when an emitter is teleported.
+    pub speed_of_sound: f32,
+}
+
+impl Default for DopplerSettings {
+    fn default() -> Self {
+        Self {
+            factor: 1.0,
+            speed_of_sound: 343.0,
+        }
+    }
+}
+
+impl DopplerSettings {
+    /// Returns the pitch multiplier of a sound emitted at `emitter` and heard at `listener`,
+    /// moving at the given velocities.
+    pub fn pitch(
+        &self,
+        emitter: Vec3,
+        emitter_velocity: Vec3,
+        listener: Vec3,
+        listener_velocity: Vec3,
+    ) -> f32 {
+        if self.factor <= 0.0 || self.speed_of_sound <= 0.0 {
+            return 1.0;
+        }
+        let Some(direction) = (emitter - listener).try_normalize() else {
+            return 1.0;
+        };
+
+        let max_speed = 0.5 * self.speed_of_sound / self.factor;
+        // Speed of the listener towards the emitter, and of the emitter away from the listener
+        let listener_speed = listener_velocity
+            .dot(direction)
+            .clamp(-max_speed, max_speed);
+        let emitter_speed = emitter_velocity.dot(direction).clamp(-max_speed, max_speed);
+
+        (self.speed_of_sound + self.factor * listener_speed)
+            / (self.speed_of_sound + self.factor * emitter_speed)
+    }
+}
+
+/// The velocity of a spatial emitter or of the [`SpatialListener`], used for the Doppler effect.
+///
+/// Without this component, the velocity is measured from the movement of the
+/// [`GlobalTransform`] between frames. Add it to use the velocity of a physics engine instead,
+/// which avoids spikes when entities are teleported.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, Clone, PartialEq)]
+pub struct AudioVelocity(pub Vec3);
+
+/// The Doppler pitch of a spatial sound, shared with the audio thread.
+#[derive(Debug, Clone)]
+pub(crate) struct DopplerShift(Arc<AtomicU32>);
+
+impl Default for DopplerShift {
+    fn default() -> Self {
+        Self(Arc::new(AtomicU32::new(1.0f32.to_bits())))
+    }
+}
+
+impl DopplerShift {
+    fn get(&self) -> f32 {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    fn set(&self, pitch: f32) {
+        self.0.store(pitch.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Wraps the source so its pitch follows the Doppler shift.
+    pub(crate) fn apply(
+        &self,
+        source: impl Source<Item = f32> + Send + 'static,
+    ) -> impl Source<Item = f32> + Send + 'static {
+        let shift = self.clone();
+        source
+            .speed(shift.get())
+            .periodic_access(DOPPLER_UPDATE_PERIOD, move |source| {
+                source.set_factor(shift.get());
+            })
+    }
+}
+
+/// How often playing sounds check for changes to their Doppler shift.
+const DOPPLER_UPDATE_PERIOD: Duration = Duration::from_millis(5);
+
+/// The last position of a spatial emitter, used to measure its velocity.
+#[derive(Debug, Default)]
+pub(crate) struct DopplerState {
+    pub(crate) shift: DopplerShift,
+    last_position: Option<Vec3>,
+}
+
+impl DopplerState {
+    /// Returns the velocity of the entity at the given position, either from its
+    /// [`AudioVelocity`] or from the distance it moved since the last frame.
+    fn velocity(&mut self, position: Vec3, velocity: Option<&AudioVelocity>, delta: f32) -> Vec3 {
+        let last_position = self.last_position.replace(position);
+        match (velocity, last_position) {
+            (Some(velocity), _) => velocity.0,
+            (None, Some(last_position)) if delta > 0.0 => (position - last_position) / delta,
+            (None, _) => Vec3::ZERO,
+        }
+    }
+}
+
+/// Updates the pitch of spatial sounds from the velocities of their emitters and of the listener.
+pub(crate) fn update_doppler_shift(
+    mut emitters: Query<(
+        &GlobalTransform,
+        &mut SpatialAudioSink,
+        &PlaybackSettings,
+        Option<&AudioVelocity>,
+    )>,
+    listener: Query<(&GlobalTransform, Option<&AudioVelocity>), With<SpatialListener>>,
+    mut listener_state: Local<DopplerState>,
+    settings: Res<DopplerSettings>,
+    default_spatial_scale: Res<DefaultSpatialScale>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_secs();
+    let (listener_position, listener_velocity) = listener
+        .iter()
+        .next()
+        .map(|(transform, velocity)| {
+            let position = transform.translation();
+            (position, listener_state.velocity(position, velocity, delta))
+        })
+        .unwrap_or_default();
+
+    for (transform, mut sink, playback_settings, velocity) in &mut emitters {
+        let scale = playback_settings
+            .spatial_scale
+            .unwrap_or(default_spatial_scale.0)
+            .0;
+        // The state is internal to the sink, don't trigger change detection for users
+        let doppler = &mut sink.bypass_change_detection().doppler;
+        let position = transform.translation();
+        let velocity = doppler.velocity(position, velocity, delta);
+        doppler.shift.set(settings.pitch(
+            position * scale,
+            velocity * scale,
+            listener_position * scale,
+            listener_velocity * scale,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DopplerSettings;
+    use bevy_math::Vec3;
+
+    #[test]
+    fn approaching_sounds_are_higher() {
+        let settings = DopplerSettings::default();
+        let emitter = Vec3::new(100.0, 0.0, 0.0);
+
+        let approaching = settings.pitch(emitter, Vec3::NEG_X * 30.0, Vec3::ZERO, Vec3::ZERO);
+        let receding = settings.pitch(emitter, Vec3::X * 30.0, Vec3::ZERO, Vec3::ZERO);
+        let passing = settings.pitch(emitter, Vec3::Y * 30.0, Vec3::ZERO, Vec3::ZERO);
+        assert!(approaching > 1.0);
+        assert!(receding < 1.0);
+        assert_eq!(passing, 1.0);
+
+        let listener_approaching = settings.pitch(emitter, Vec3::ZERO, Vec3::ZERO, Vec3::X * 30.0);
+        assert!(listener_approaching > 1.0);
+
+        let disabled = DopplerSettings {
+            factor: 0.0,
+            ..settings
+        };
+        assert_eq!(
+            disabled.pitch(emitter, Vec3::NEG_X * 30.0, Vec3::ZERO, Vec3::ZERO),
+            1.0
+        );
+    }
+}
@@ -0,0 +1,731 @@
+use crate::{AudioSink, SpatialAudioSink, Volume};
+use alloc::sync::Arc;
+use bevy_ecs::prelude::*;
+use bevy_math::ops;
+use bevy_reflect::prelude::*;
+use core::{
+    f32::consts::TAU,
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
+};
+use rodio::{source::SeekError, Sample, Source};
+use std::sync::Mutex;
+
+/// An effect processing the samples of a sound.
+///
+/// Effects are attached to a sound with the [`AudioEffects`] component, or to every sound routed
+/// through a bus with [`AudioBuses::effects_mut`](crate::AudioBuses::effects_mut).
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum AudioEffect {
+    /// Changes the volume of the sound, smoothly so it can be automated without clicks.
+    Gain {
+        /// The volume the sound is scaled by.
+        volume: Volume,
+    },
+    /// Attenuates frequencies above the cutoff frequency, making the sound muffled.
+    LowPass {
+        /// The cutoff frequency, in hertz.
+        cutoff_frequency: f32,
+        /// The resonance of the filter around the cutoff frequency.
+        ///
+        /// [`AudioEffect::DEFAULT_Q`] gives a flat response.
+        q: f32,
+    },
+    /// Attenuates frequencies below the cutoff frequency, making the sound thin.
+    HighPass {
+        /// The cutoff frequency, in hertz.
+        cutoff_frequency: f32,
+        /// The resonance of the filter around the cutoff frequency.
+        ///
+        /// [`AudioEffect::DEFAULT_Q`] gives a flat response.
+        q: f32,
+    },
+    /// Adds reverberation to the sound, simulating the reflections of an enclosed space.
+    Reverb {
+        /// The volume of the reverberated sound mixed with the original sound.
+        send: Volume,
+        /// The size of the simulated room, between `0.0` and `1.0`. Larger rooms have a longer tail.
+        room_size: f32,
+        /// How much high frequencies are absorbed by the room, between `0.0` and `1.0`.
+        damping: f32,
+    },
+    /// Reduces the volume of the sound when it is louder than the threshold.
+    Compressor {
+        /// The volume above which the sound is compressed.
+        threshold: Volume,
+        /// How much the volume above the threshold is reduced, a ratio of `4.0` turns a 4 dB
+        /// increase above the threshold into a 1 dB increase.
+        ratio: f32,
+        /// How quickly the compressor reacts when the sound gets louder than the threshold.
+        attack: Duration,
+        /// How quickly the compressor recovers when the sound gets quieter.
+        release: Duration,
+    },
+}
+
+impl AudioEffect {
+    /// The resonance of a Butterworth filter, which has no peak at the cutoff frequency.
+    pub const DEFAULT_Q: f32 = core::f32::consts::FRAC_1_SQRT_2;
+
+    /// A [`AudioEffect::Gain`] with the given volume.
+    pub const fn gain(volume: Volume) -> Self {
+        Self::Gain { volume }
+    }
+
+    /// A [`AudioEffect::LowPass`] filter with the given cutoff frequency in hertz.
+    pub const fn low_pass(cutoff_frequency: f32) -> Self {
+        Self::LowPass {
+            cutoff_frequency,
+            q: Self::DEFAULT_Q,
+        }
+    }
+
+    /// A [`AudioEffect::HighPass`] filter with the given cutoff frequency in hertz.
+    pub const fn high_pass(cutoff_frequency: f32) -> Self {
+        Self::HighPass {
+            cutoff_frequency,
+            q: Self::DEFAULT_Q,
+        }
+    }
+
+    /// A medium sized [`AudioEffect::Reverb`] with the given send volume.
+    pub const fn reverb(send: Volume) -> Self {
+        Self::Reverb {
+            send,
+            room_size: 0.5,
+            damping: 0.5,
+        }
+    }
+
+    /// A [`AudioEffect::Compressor`] with the given threshold and ratio.
+    pub const fn compressor(threshold: Volume, ratio: f32) -> Self {
+        Self::Compressor {
+            threshold,
+            ratio,
+            attack: Duration::from_millis(10),
+            release: Duration::from_millis(100),
+        }
+    }
+}
+
+/// A chain of [`AudioEffect`]s applied in order to the sound played by this entity.
+///
+/// The component must be present when the sound starts playing. Its effects and their parameters
+/// can then be changed at any time, for example to gradually muffle sounds when the player dives
+/// underwater:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_audio::{AudioEffect, AudioEffects};
+/// fn dive(mut query: Query<&mut AudioEffects>, depth: f32) {
+///     for mut effects in &mut query {
+///         effects.0 = vec![AudioEffect::low_pass(20_000.0 / (1.0 + depth))];
+///     }
+/// }
+/// ```
+///
+/// Effects are applied before the effects of the [`AudioBus`](crate::AudioBus) the sound is
+/// routed through.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Clone, Debug, Default, PartialEq)]
+pub struct AudioEffects(pub Vec<AudioEffect>);
+
+/// Sends changes of [`AudioEffects`] to the sounds that are playing.
+pub(crate) fn update_audio_effects(
+    query: Query<
+        (&AudioEffects, Option<&AudioSink>, Option<&SpatialAudioSink>),
+        Changed<AudioEffects>,
+    >,
+) {
+    for (effects, sink, spatial_sink) in &query {
+        let handle = sink
+            .and_then(|sink| sink.effects.as_ref())
+            .or_else(|| spatial_sink.and_then(|sink| sink.effects.as_ref()));
+        if let Some(handle) = handle {
+            handle.set(&effects.0);
+        }
+    }
+}
+
+/// Effects shared between the ECS and the sounds they are applied to.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AudioEffectsHandle(Arc<SharedEffects>);
+
+#[derive(Debug, Default)]
+struct SharedEffects {
+    generation: AtomicU32,
+    effects: Mutex<Vec<AudioEffect>>,
+}
+
+impl AudioEffectsHandle {
+    pub(crate) fn new(effects: &[AudioEffect]) -> Self {
+        let handle = Self::default();
+        handle.set(effects);
+        handle
+    }
+
+    pub(crate) fn set(&self, effects: &[AudioEffect]) {
+        let mut shared = self.0.effects.lock().unwrap();
+        if shared.as_slice() != effects {
+            shared.clear();
+            shared.extend_from_slice(effects);
+            self.0.generation.fetch_add(1, Ordering::Release);
+        }
+    }
+}
+
+/// How often playing sounds check for changes to their effects.
+const EFFECTS_UPDATE_PERIOD: Duration = Duration::from_millis(5);
+
+/// Applies chains of effects to a source, in order.
+pub(crate) struct EffectsSource<S> {
+    input: S,
+    chains: Vec<EffectChain>,
+    channels: u16,
+    sample_rate: u32,
+    channel: u16,
+    samples_until_update: u32,
+}
+
+impl<S> EffectsSource<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    pub(crate) fn new(input: S, handles: Vec<AudioEffectsHandle>) -> Self {
+        let mut source = Self {
+            channels: input.channels(),
+            sample_rate: input.sample_rate(),
+            input,
+            chains: handles
+                .into_iter()
+                .map(|handle| EffectChain {
+                    handle,
+                    generation: None,
+                    processors: Vec::new(),
+                })
+                .collect(),
+            channel: 0,
+            samples_until_update: 0,
+        };
+        source.update(true);
+        source
+    }
+
+    fn update(&mut self, format_changed: bool) {
+        for chain in &mut self.chains {
+            chain.update(self.channels, self.sample_rate, format_changed);
+        }
+        self.samples_until_update = (EFFECTS_UPDATE_PERIOD.as_secs_f32()
+            * self.sample_rate as f32
+            * self.channels as f32) as u32;
+    }
+}
+
+impl<S> Iterator for EffectsSource<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.channel == 0 {
+            let channels = self.input.channels();
+            let sample_rate = self.input.sample_rate();
+            let format_changed = channels != self.channels || sample_rate != self.sample_rate;
+            if format_changed || self.samples_until_update == 0 {
+                self.channels = channels;
+                self.sample_rate = sample_rate;
+                self.update(format_changed);
+            }
+        }
+
+        let mut sample = self.input.next()?.to_f32();
+        for chain in &mut self.chains {
+            for processor in &mut chain.processors {
+                sample = processor.process(sample, self.channel as usize);
+            }
+        }
+
+        self.channel = (self.channel + 1) % self.channels.max(1);
+        self.samples_until_update = self.samples_until_update.saturating_sub(1);
+        Some(sample)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<S> Source for EffectsSource<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        self.input.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.input.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.input.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.channel = 0;
+        self.update(true);
+        Ok(())
+    }
+}
+
+struct EffectChain {
+    handle: AudioEffectsHandle,
+    /// The generation of the effects the processors were created from.
+    generation: Option<u32>,
+    processors: Vec<EffectProcessor>,
+}
+
+impl EffectChain {
+    fn update(&mut self, channels: u16, sample_rate: u32, format_changed: bool) {
+        if format_changed {
+            // The state of the effects depends on the format, so they have to be recreated
+            self.processors.clear();
+            self.generation = None;
+        }
+
+        let generation = self.handle.0.generation.load(Ordering::Acquire);
+        if self.generation == Some(generation) {
+            return;
+        }
+        // Don't block the audio thread, the effects will be updated on the next attempt instead.
+        let Ok(effects) = self.handle.0.effects.try_lock() else {
+            return;
+        };
+        self.generation = Some(generation);
+
+        self.processors.truncate(effects.len());
+        for (index, effect) in effects.iter().enumerate() {
+            match self.processors.get_mut(index) {
+                // Keep the state of the effect so changing its parameters doesn't cause clicks
+                Some(processor) => {
+                    if !processor.set_effect(effect, sample_rate) {
+                        *processor = EffectProcessor::new(effect, channels, sample_rate);
+                    }
+                }
+                None => self
+                    .processors
+                    .push(EffectProcessor::new(effect, channels, sample_rate)),
+            }
+        }
+    }
+}
+
+enum EffectProcessor {
+    Gain(Gain),
+    Biquad(Biquad),
+    Reverb(Reverb),
+    Compressor(Compressor),
+}
+
+impl EffectProcessor {
+    fn new(effect: &AudioEffect, channels: u16, sample_rate: u32) -> Self {
+        let channels = channels.max(1) as usize;
+        let mut processor = match effect {
+            AudioEffect::Gain { volume } => Self::Gain(Gain::new(*volume, sample_rate)),
+            AudioEffect::LowPass { .. } | AudioEffect::HighPass { .. } => {
+                Self::Biquad(Biquad::new(channels))
+            }
+            AudioEffect::Reverb { .. } => Self::Reverb(Reverb::new(channels, sample_rate)),
+            AudioEffect::Compressor { .. } => Self::Compressor(Compressor::new(channels)),
+        };
+        processor.set_effect(effect, sample_rate);
+        processor
+    }
+
+    /// Updates the parameters of the processor, returns `false` if the effect needs a different processor.
+    fn set_effect(&mut self, effect: &AudioEffect, sample_rate: u32) -> bool {
+        match (self, *effect) {
+            (Self::Gain(gain), AudioEffect::Gain { volume }) => gain.set(volume),
+            (
+                Self::Biquad(biquad),
+                AudioEffect::LowPass {
+                    cutoff_frequency,
+                    q,
+                },
+            ) => biquad.set_low_pass(cutoff_frequency, q, sample_rate),
+            (
+                Self::Biquad(biquad),
+                AudioEffect::HighPass {
+                    cutoff_frequency,
+                    q,
+                },
+            ) => biquad.set_high_pass(cutoff_frequency, q, sample_rate),
+            (
+                Self::Reverb(reverb),
+                AudioEffect::Reverb {
+                    send,
+                    room_size,
+                    damping,
+                },
+            ) => reverb.set(send, room_size, damping),
+            (
+                Self::Compressor(compressor),
+                AudioEffect::Compressor {
+                    threshold,
+                    ratio,
+                    attack,
+                    release,
+                },
+            ) => compressor.set(threshold, ratio, attack, release, sample_rate),
+            _ => return false,
+        }
+        true
+    }
+
+    fn process(&mut self, sample: f32, channel: usize) -> f32 {
+        match self {
+            Self::Gain(gain) => gain.process(sample, channel),
+            Self::Biquad(biquad) => biquad.process(sample, channel),
+            Self::Reverb(reverb) => reverb.process(sample, channel),
+            Self::Compressor(compressor) => compressor.process(sample, channel),
+        }
+    }
+}
+
+/// A gain smoothly following its target, to avoid clicks when it changes.
+struct Gain {
+    target: f32,
+    current: f32,
+    /// How much of the distance to the target is covered per frame.
+    smoothing: f32,
+}
+
+impl Gain {
+    /// Time constant of the smoothing.
+    const SMOOTHING_TIME: f32 = 0.005;
+
+    fn new(volume: Volume, sample_rate: u32) -> Self {
+        let target = volume.to_linear();
+        Self {
+            target,
+            current: target,
+            smoothing: 1.0 - ops::exp(-1.0 / (Self::SMOOTHING_TIME * sample_rate as f32)),
+        }
+    }
+
+    fn set(&mut self, volume: Volume) {
+        self.target = volume.to_linear();
+    }
+
+    fn process(&mut self, sample: f32, channel: usize) -> f32 {
+        if channel == 0 {
+            self.current += (self.target - self.current) * self.smoothing;
+        }
+        sample * self.current
+    }
+}
+
+/// A second order filter, see <https://www.w3.org/TR/audio-eq-cookbook/>.
+struct Biquad {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    /// The state of the filter for each channel.
+    state: Vec<[f32; 2]>,
+}
+
+impl Biquad {
+    fn new(channels: usize) -> Self {
+        Self {
+            b0: 1.0,
+            b1: 0.0,
+            b2: 0.0,
+            a1: 0.0,
+            a2: 0.0,
+            state: vec![[0.0; 2]; channels],
+        }
+    }
+
+    fn set_low_pass(&mut self, cutoff_frequency: f32, q: f32, sample_rate: u32) {
+        let (cos, alpha) = Self::cos_alpha(cutoff_frequency, q, sample_rate);
+        self.set(
+            [(1.0 - cos) / 2.0, 1.0 - cos, (1.0 - cos) / 2.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        );
+    }
+
+    fn set_high_pass(&mut self, cutoff_frequency: f32, q: f32, sample_rate: u32) {
+        let (cos, alpha) = Self::cos_alpha(cutoff_frequency, q, sample_rate);
+        self.set(
+            [(1.0 + cos) / 2.0, -(1.0 + cos), (1.0 + cos) / 2.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        );
+    }
+
+    fn cos_alpha(cutoff_frequency: f32, q: f32, sample_rate: u32) -> (f32, f32) {
+        let sample_rate = sample_rate as f32;
+        let cutoff_frequency = cutoff_frequency.clamp(10.0, 0.49 * sample_rate);
+        let (sin, cos) = ops::sin_cos(TAU * cutoff_frequency / sample_rate);
+        (cos, sin / (2.0 * q.max(0.01)))
+    }
+
+    fn set(&mut self, [b0, b1, b2]: [f32; 3], [a0, a1, a2]: [f32; 3]) {
+        self.b0 = b0 / a0;
+        self.b1 = b1 / a0;
+        self.b2 = b2 / a0;
+        self.a1 = a1 / a0;
+        self.a2 = a2 / a0;
+    }
+
+    fn process(&mut self, sample: f32, channel: usize) -> f32 {
+        // Transposed direct form II
+        let [z1, z2] = &mut self.state[channel];
+        let output = self.b0 * sample + *z1;
+        *z1 = self.b1 * sample - self.a1 * output + *z2;
+        *z2 = self.b2 * sample - self.a2 * output;
+        output
+    }
+}
+
+/// A reverb based on Freeverb, made of parallel comb filters followed by all-pass filters.
+struct Reverb {
+    send: f32,
+    feedback: f32,
+    damping: f32,
+    channels: Vec<ReverbChannel>,
+}
+
+struct ReverbChannel {
+    combs: Vec<DelayLine>,
+    comb_filters: Vec<f32>,
+    all_passes: Vec<DelayLine>,
+}
+
+struct DelayLine {
+    buffer: Vec<f32>,
+    position: usize,
+}
+
+impl DelayLine {
+    fn new(length: usize) -> Self {
+        Self {
+            buffer: vec![0.0; length.max(1)],
+            position: 0,
+        }
+    }
+
+    fn read(&self) -> f32 {
+        self.buffer[self.position]
+    }
+
+    fn write(&mut self, value: f32) {
+        self.buffer[self.position] = value;
+        self.position = (self.position + 1) % self.buffer.len();
+    }
+}
+
+impl Reverb {
+    /// Delays of the comb filters at 44.1 kHz.
+    const COMB_DELAYS: [usize; 4] = [1116, 1188, 1277, 1356];
+    /// Delays of the all-pass filters at 44.1 kHz.
+    const ALL_PASS_DELAYS: [usize; 2] = [556, 441];
+    /// Offset of the delays of odd channels, to decorrelate stereo channels.
+    const STEREO_SPREAD: usize = 23;
+    /// Scales the input to keep the sum of the comb filters in range.
+    const INPUT_GAIN: f32 = 0.015;
+
+    fn new(channels: usize, sample_rate: u32) -> Self {
+        let scale = sample_rate as f32 / 44100.0;
+        let delay = |delay: usize, channel: usize| {
+            DelayLine::new(((delay + (channel % 2) * Self::STEREO_SPREAD) as f32 * scale) as usize)
+        };
+        Self {
+            send: 0.0,
+            feedback: 0.0,
+            damping: 0.0,
+            channels: (0..channels)
+                .map(|channel| ReverbChannel {
+                    combs: Self::COMB_DELAYS
+                        .iter()
+                        .map(|&comb| delay(comb, channel))
+                        .collect(),
+                    comb_filters: vec![0.0; Self::COMB_DELAYS.len()],
+                    all_passes: Self::ALL_PASS_DELAYS
+                        .iter()
+                        .map(|&all_pass| delay(all_pass, channel))
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    fn set(&mut self, send: Volume, room_size: f32, damping: f32) {
+        self.send = send.to_linear();
+        self.feedback = 0.7 + 0.28 * room_size.clamp(0.0, 1.0);
+        self.damping = 0.4 * damping.clamp(0.0, 1.0);
+    }
+
+    fn process(&mut self, sample: f32, channel: usize) -> f32 {
+        let ReverbChannel {
+            combs,
+            comb_filters,
+            all_passes,
+        } = &mut self.channels[channel];
+
+        let input = sample * Self::INPUT_GAIN;
+        let mut wet = 0.0;
+        for (comb, filter) in combs.iter_mut().zip(comb_filters.iter_mut()) {
+            let output = comb.read();
+            *filter = output * (1.0 - self.damping) + *filter * self.damping;
+            comb.write(input + *filter * self.feedback);
+            wet += output;
+        }
+        for all_pass in all_passes {
+            let delayed = all_pass.read();
+            all_pass.write(wet + delayed * 0.5);
+            wet = delayed - wet;
+        }
+
+        sample + wet * self.send
+    }
+}
+
+/// A feed-forward compressor with a peak envelope follower per channel.
+struct Compressor {
+    threshold: f32,
+    /// The exponent applied to the ratio between the threshold and the envelope.
+    slope: f32,
+    attack: f32,
+    release: f32,
+    envelopes: Vec<f32>,
+}
+
+impl Compressor {
+    fn new(channels: usize) -> Self {
+        Self {
+            threshold: 1.0,
+            slope: 0.0,
+            attack: 0.0,
+            release: 0.0,
+            envelopes: vec![0.0; channels],
+        }
+    }
+
+    fn set(
+        &mut self,
+        threshold: Volume,
+        ratio: f32,
+        attack: Duration,
+        release: Duration,
+        sample_rate: u32,
+    ) {
+        let smoothing = |duration: Duration| {
+            let samples = duration.as_secs_f32() * sample_rate as f32;
+            if samples > 0.0 {
+                ops::exp(-1.0 / samples)
+            } else {
+                0.0
+            }
+        };
+        self.threshold = threshold.to_linear();
+        self.slope = 1.0 - 1.0 / ratio.max(1.0);
+        self.attack = smoothing(attack);
+        self.release = smoothing(release);
+    }
+
+    fn process(&mut self, sample: f32, channel: usize) -> f32 {
+        let envelope = &mut self.envelopes[channel];
+        let level = ops::abs(sample);
+        let smoothing = if level > *envelope {
+            self.attack
+        } else {
+            self.release
+        };
+        *envelope = level + smoothing * (*envelope - level);
+
+        if *envelope > self.threshold && self.threshold > 0.0 {
+            sample * ops::powf(self.threshold / *envelope, self.slope)
+        } else {
+            sample
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AudioEffect, AudioEffectsHandle, EffectsSource};
+    use crate::Volume;
+    use bevy_math::ops;
+    use core::f32::consts::TAU;
+    use rodio::buffer::SamplesBuffer;
+
+    fn sine(frequency: f32, sample_rate: u32, amplitude: f32) -> SamplesBuffer<f32> {
+        let samples = (0..sample_rate)
+            .map(|i| amplitude * ops::sin(TAU * frequency * i as f32 / sample_rate as f32))
+            .collect::<Vec<_>>();
+        SamplesBuffer::new(1, sample_rate, samples)
+    }
+
+    /// The peak amplitude of the second half of the samples, once filters have settled.
+    fn peak(samples: impl Iterator<Item = f32>) -> f32 {
+        let samples = samples.collect::<Vec<_>>();
+        samples[samples.len() / 2..]
+            .iter()
+            .fold(0.0, |peak, sample| ops::abs(*sample).max(peak))
+    }
+
+    fn apply(effect: AudioEffect, input: SamplesBuffer<f32>) -> f32 {
+        peak(EffectsSource::new(
+            input,
+            vec![AudioEffectsHandle::new(&[effect])],
+        ))
+    }
+
+    #[test]
+    fn low_pass_attenuates_high_frequencies() {
+        let low = apply(AudioEffect::low_pass(1000.0), sine(100.0, 44100, 1.0));
+        let high = apply(AudioEffect::low_pass(1000.0), sine(10000.0, 44100, 1.0));
+        assert!(low > 0.95, "{low}");
+        assert!(high < 0.05, "{high}");
+    }
+
+    #[test]
+    fn high_pass_attenuates_low_frequencies() {
+        let low = apply(AudioEffect::high_pass(1000.0), sine(100.0, 44100, 1.0));
+        let high = apply(AudioEffect::high_pass(1000.0), sine(10000.0, 44100, 1.0));
+        assert!(low < 0.05, "{low}");
+        assert!(high > 0.95, "{high}");
+    }
+
+    #[test]
+    fn compressor_reduces_loud_sounds() {
+        let effect = AudioEffect::compressor(Volume::Linear(0.25), 4.0);
+        let quiet = apply(effect, sine(100.0, 44100, 0.2));
+        let loud = apply(effect, sine(100.0, 44100, 1.0));
+        assert!(ops::abs(quiet - 0.2) < 0.01, "{quiet}");
+        assert!(loud < 0.5, "{loud}");
+    }
+
+    #[test]
+    fn effects_can_change_during_playback() {
+        let handle = AudioEffectsHandle::new(&[]);
+        let mut source = EffectsSource::new(sine(10000.0, 44100, 1.0), vec![handle.clone()]);
+        let before = peak(source.by_ref().take(4410));
+        handle.set(&[AudioEffect::low_pass(1000.0)]);
+        let after = peak(source);
+        assert!(before > 0.95, "{before}");
+        assert!(after < 0.05, "{after}");
+    }
+}
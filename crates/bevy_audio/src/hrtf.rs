@@ -0,0 +1,529 @@
+use alloc::sync::Arc;
+use bevy_asset::Asset;
+use bevy_math::{ops, Quat, Vec3};
+use bevy_reflect::TypePath;
+use core::{
+    f32::consts::{FRAC_PI_2, PI},
+    time::Duration,
+};
+use rodio::{source::SeekError, Source};
+use std::sync::Mutex;
+
+/// The speed of sound in air, in meters per second.
+const SPEED_OF_SOUND: f32 = 343.0;
+
+/// A head-related transfer function, describing how a sound coming from a given direction is
+/// filtered by the head and ears of the listener before reaching each eardrum.
+///
+/// Assign it to a [`SpatialListener`](crate::SpatialListener) to render spatial audio binaurally
+/// instead of panning it between the left and right channels. This gives headphone users cues
+/// about the elevation of sounds and whether they are in front or behind them.
+///
+/// The transfer function is stored as a set of head-related impulse responses, one per ear for
+/// each measured direction. Sounds are convolved with the impulse responses of the directions
+/// closest to them. [`Hrtf::spherical_head`] synthesizes a generic set, measured sets can be
+/// used with [`Hrtf::new`].
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct Hrtf {
+    sample_rate: u32,
+    ir_length: usize,
+    points: Arc<[HrtfPoint]>,
+}
+
+/// The impulse responses of a [`Hrtf`] for a sound coming from a single direction.
+#[derive(Clone, Debug)]
+pub struct HrtfPoint {
+    /// The direction of the sound, in the space of the listener: `-Z` is forward, `X` is right
+    /// and `Y` is up.
+    pub direction: Vec3,
+    /// The impulse response for the left ear.
+    pub left: Vec<f32>,
+    /// The impulse response for the right ear.
+    pub right: Vec<f32>,
+}
+
+impl Default for Hrtf {
+    fn default() -> Self {
+        Self::spherical_head(44100, Self::DEFAULT_HEAD_RADIUS)
+    }
+}
+
+impl Hrtf {
+    /// The radius of an average adult head, in meters.
+    pub const DEFAULT_HEAD_RADIUS: f32 = 0.0875;
+
+    /// Creates a [`Hrtf`] from impulse responses sampled at the given sample rate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `points` is empty, or if the impulse responses don't all have the same length.
+    pub fn new(sample_rate: u32, points: impl IntoIterator<Item = HrtfPoint>) -> Self {
+        let points: Arc<[HrtfPoint]> = points
+            .into_iter()
+            .map(|point| HrtfPoint {
+                direction: point.direction.normalize_or(Vec3::NEG_Z),
+                ..point
+            })
+            .collect();
+        assert!(!points.is_empty(), "A HRTF needs at least one direction");
+        let ir_length = points[0].left.len();
+        assert!(
+            points
+                .iter()
+                .all(|point| point.left.len() == ir_length && point.right.len() == ir_length),
+            "All impulse responses of a HRTF must have the same length"
+        );
+        Self {
+            sample_rate,
+            ir_length,
+            points,
+        }
+    }
+
+    /// Synthesizes a [`Hrtf`] from a model of a spherical head with the given radius in meters.
+    ///
+    /// The model combines the interaural time difference, the shadowing of the far ear by the
+    /// head and echoes from the outer ear that depend on the elevation of the sound, see
+    /// "An Efficient HRTF Model for 3-D Sound" by C. Phillip Brown and Richard O. Duda.
+    pub fn spherical_head(sample_rate: u32, head_radius: f32) -> Self {
+        let ir_length = (0.003 * sample_rate as f32) as usize;
+        let mut points = Vec::new();
+        for elevation in (-40..=90).step_by(10) {
+            let elevation = (elevation as f32).to_radians();
+            let count = ((36.0 * ops::cos(elevation)).round() as usize).max(1);
+            for index in 0..count {
+                let azimuth = index as f32 / count as f32 * 2.0 * PI;
+                let (sin_elevation, cos_elevation) = ops::sin_cos(elevation);
+                let direction = Vec3::new(
+                    ops::sin(azimuth) * cos_elevation,
+                    sin_elevation,
+                    -ops::cos(azimuth) * cos_elevation,
+                );
+                points.push(HrtfPoint {
+                    direction,
+                    left: spherical_head_ir(
+                        direction,
+                        Vec3::NEG_X,
+                        head_radius,
+                        sample_rate,
+                        ir_length,
+                    ),
+                    right: spherical_head_ir(
+                        direction,
+                        Vec3::X,
+                        head_radius,
+                        sample_rate,
+                        ir_length,
+                    ),
+                });
+            }
+        }
+        Self::new(sample_rate, points)
+    }
+
+    /// The sample rate of the impulse responses.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// The impulse responses of each direction.
+    pub fn points(&self) -> &[HrtfPoint] {
+        &self.points
+    }
+
+    /// Returns the impulse responses resampled to the given sample rate.
+    fn resampled(&self, sample_rate: u32) -> Self {
+        if sample_rate == self.sample_rate {
+            return self.clone();
+        }
+        let ratio = self.sample_rate as f32 / sample_rate as f32;
+        let ir_length = ((self.ir_length as f32 / ratio) as usize).max(1);
+        let resample = |ir: &[f32]| {
+            (0..ir_length)
+                .map(|index| {
+                    let position = index as f32 * ratio;
+                    let previous = ir.get(position as usize).copied().unwrap_or(0.0);
+                    let next = ir.get(position as usize + 1).copied().unwrap_or(0.0);
+                    // Keep the energy of the impulse response constant
+                    (previous + (next - previous) * position.fract()) * ratio
+                })
+                .collect()
+        };
+        Self {
+            sample_rate,
+            ir_length,
+            points: self
+                .points
+                .iter()
+                .map(|point| HrtfPoint {
+                    direction: point.direction,
+                    left: resample(&point.left),
+                    right: resample(&point.right),
+                })
+                .collect(),
+        }
+    }
+
+    /// Writes the impulse responses for a sound coming from the given direction, blending the
+    /// closest directions of the set.
+    fn interpolate(&self, direction: Vec3, gain: f32, left: &mut [f32], right: &mut [f32]) {
+        const BLENDED_POINTS: usize = 3;
+
+        let mut closest: [(f32, Option<&HrtfPoint>); BLENDED_POINTS] = [(-2.0, None); 3];
+        for point in self.points.iter() {
+            let similarity = point.direction.dot(direction);
+            if let Some(index) = closest.iter().position(|(best, _)| similarity > *best) {
+                closest.copy_within(index..BLENDED_POINTS - 1, index + 1);
+                closest[index] = (similarity, Some(point));
+            }
+        }
+
+        let weights = closest.map(|(similarity, point)| {
+            point.map_or(0.0, |_| {
+                1.0 / (ops::acos(similarity.clamp(-1.0, 1.0)) + 1e-3)
+            })
+        });
+        let total: f32 = weights.iter().sum();
+
+        left.fill(0.0);
+        right.fill(0.0);
+        for ((_, point), weight) in closest.iter().zip(weights) {
+            let Some(point) = point else {
+                continue;
+            };
+            let weight = gain * weight / total;
+            for (output, input) in left.iter_mut().zip(&point.left) {
+                *output += weight * input;
+            }
+            for (output, input) in right.iter_mut().zip(&point.right) {
+                *output += weight * input;
+            }
+        }
+    }
+}
+
+/// Synthesizes the impulse response of a spherical head for the ear facing `ear_axis`.
+fn spherical_head_ir(
+    direction: Vec3,
+    ear_axis: Vec3,
+    head_radius: f32,
+    sample_rate: u32,
+    ir_length: usize,
+) -> Vec<f32> {
+    /// Reflection coefficients of the echoes of the outer ear.
+    const PINNA_REFLECTIONS: [f32; 5] = [0.5, -1.0, 0.5, -0.25, 0.25];
+    /// Amplitudes of the elevation dependent part of the echo delays, in samples at 44.1 kHz.
+    const PINNA_AMPLITUDES: [f32; 5] = [1.0, 5.0, 5.0, 5.0, 5.0];
+    /// Constant part of the echo delays, in samples at 44.1 kHz.
+    const PINNA_OFFSETS: [f32; 5] = [2.0, 4.0, 7.0, 11.0, 13.0];
+    /// Scales the elevation of the echo delays.
+    const PINNA_SCALES: [f32; 5] = [1.0, 0.5, 0.5, 0.5, 0.5];
+
+    let sample_rate = sample_rate as f32;
+    let head_delay = head_radius / SPEED_OF_SOUND;
+
+    // Angle between the direction of the sound and the axis of the ear
+    let incidence = ops::acos(direction.dot(ear_axis).clamp(-1.0, 1.0));
+    let elevation = ops::asin(direction.y.clamp(-1.0, 1.0));
+    // Azimuth relative to the front of the ear, between -PI and PI
+    let azimuth = ops::atan2(direction.dot(ear_axis), -direction.z);
+
+    // Pinna: the direct sound followed by echoes whose delay depends on the elevation
+    let mut pinna = vec![0.0; ir_length];
+    pinna[0] = 1.0;
+    for index in 0..PINNA_REFLECTIONS.len() {
+        let delay = PINNA_AMPLITUDES[index]
+            * ops::cos(azimuth / 2.0)
+            * ops::sin(PINNA_SCALES[index] * (FRAC_PI_2 - elevation))
+            + PINNA_OFFSETS[index];
+        let delay = delay * sample_rate / 44100.0;
+        add_delayed(&mut pinna, delay, PINNA_REFLECTIONS[index]);
+    }
+
+    // Head shadow: a one-pole, one-zero filter boosting high frequencies towards the ear and
+    // attenuating them away from it, discretized with the bilinear transform
+    let alpha = 1.05 + 0.95 * ops::cos(incidence * 180.0 / 150.0);
+    let time_constant = head_delay / 2.0;
+    let k = 2.0 * sample_rate;
+    let (b0, b1) = (
+        alpha * time_constant * k + 1.0,
+        1.0 - alpha * time_constant * k,
+    );
+    let (a0, a1) = (time_constant * k + 1.0, 1.0 - time_constant * k);
+    let mut previous_input = 0.0;
+    let mut previous_output = 0.0;
+    for sample in &mut pinna {
+        let output = (b0 * *sample + b1 * previous_input - a1 * previous_output) / a0;
+        previous_input = *sample;
+        previous_output = output;
+        *sample = output;
+    }
+
+    // Interaural time difference, relative to a sound reaching the near side of the head
+    let delay = if incidence < FRAC_PI_2 {
+        head_delay * (1.0 - ops::cos(incidence))
+    } else {
+        head_delay * (1.0 + incidence - FRAC_PI_2)
+    };
+
+    let mut ir = vec![0.0; ir_length];
+    for (index, sample) in pinna.iter().enumerate() {
+        add_delayed(&mut ir, index as f32 + delay * sample_rate, *sample);
+    }
+    // The echoes of the outer ear add energy, keep the loudness of frontal sounds close to the input
+    for sample in &mut ir {
+        *sample *= 0.5;
+    }
+    ir
+}
+
+/// Adds an impulse at a fractional delay, using linear interpolation.
+fn add_delayed(ir: &mut [f32], delay: f32, value: f32) {
+    let index = delay.max(0.0) as usize;
+    let fract = delay.max(0.0).fract();
+    if let Some(sample) = ir.get_mut(index) {
+        *sample += value * (1.0 - fract);
+    }
+    if let Some(sample) = ir.get_mut(index + 1) {
+        *sample += value * fract;
+    }
+}
+
+/// The positions of a sound rendered with a [`Hrtf`] and of its listener, shared with the audio thread.
+#[derive(Clone, Debug)]
+pub(crate) struct HrtfPositions(Arc<Mutex<HrtfPositionsInner>>);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct HrtfPositionsInner {
+    emitter: Vec3,
+    left_ear: Vec3,
+    right_ear: Vec3,
+    rotation: Quat,
+}
+
+impl HrtfPositions {
+    pub(crate) fn new(emitter: Vec3, left_ear: Vec3, right_ear: Vec3, rotation: Quat) -> Self {
+        Self(Arc::new(Mutex::new(HrtfPositionsInner {
+            emitter,
+            left_ear,
+            right_ear,
+            rotation,
+        })))
+    }
+
+    pub(crate) fn set_emitter(&self, emitter: Vec3) {
+        self.0.lock().unwrap().emitter = emitter;
+    }
+
+    pub(crate) fn set_ears(&self, left_ear: Vec3, right_ear: Vec3) {
+        let mut positions = self.0.lock().unwrap();
+        positions.left_ear = left_ear;
+        positions.right_ear = right_ear;
+    }
+
+    pub(crate) fn set_rotation(&self, rotation: Quat) {
+        self.0.lock().unwrap().rotation = rotation;
+    }
+}
+
+/// Number of frames between updates of the direction of the sound, over which the impulse
+/// responses are crossfaded.
+const HRTF_BLOCK_FRAMES: usize = 64;
+
+/// Renders a source binaurally, convolving it with the impulse responses of a [`Hrtf`].
+///
+/// The source is downmixed to mono and the output is stereo.
+pub(crate) struct HrtfSource<S> {
+    input: S,
+    hrtf: Hrtf,
+    positions: HrtfPositions,
+    last_positions: Option<HrtfPositionsInner>,
+    /// The last samples of the input, stored twice so the most recent ones are contiguous.
+    history: Vec<f32>,
+    history_position: usize,
+    /// The impulse responses for the left and right ears.
+    current: [Vec<f32>; 2],
+    /// The impulse responses being faded out.
+    previous: [Vec<f32>; 2],
+    fade_frames: usize,
+    block_frames: usize,
+    pending_right: Option<f32>,
+}
+
+impl<S> HrtfSource<S>
+where
+    S: Source<Item = f32>,
+{
+    pub(crate) fn new(input: S, hrtf: &Hrtf, positions: HrtfPositions) -> Self {
+        let hrtf = hrtf.resampled(input.sample_rate());
+        let ir_length = hrtf.ir_length;
+        Self {
+            input,
+            hrtf,
+            positions,
+            last_positions: None,
+            history: vec![0.0; 2 * ir_length],
+            history_position: 0,
+            current: [vec![0.0; ir_length], vec![0.0; ir_length]],
+            previous: [vec![0.0; ir_length], vec![0.0; ir_length]],
+            fade_frames: 0,
+            block_frames: 0,
+            pending_right: None,
+        }
+    }
+
+    fn update_impulse_responses(&mut self) {
+        // Don't block the audio thread, the direction will be updated in the next block instead.
+        let Ok(positions) = self.positions.0.try_lock().map(|positions| *positions) else {
+            return;
+        };
+        if self.last_positions == Some(positions) {
+            return;
+        }
+        let first_update = self.last_positions.is_none();
+        self.last_positions = Some(positions);
+
+        let offset = positions.emitter - (positions.left_ear + positions.right_ear) / 2.0;
+        let distance_squared = offset.length_squared();
+        let direction = (positions.rotation.inverse() * offset).normalize_or(Vec3::NEG_Z);
+        let gain = (1.0 / distance_squared).min(1.0);
+
+        core::mem::swap(&mut self.previous, &mut self.current);
+        let [left, right] = &mut self.current;
+        self.hrtf.interpolate(direction, gain, left, right);
+        self.fade_frames = if first_update { 0 } else { HRTF_BLOCK_FRAMES };
+    }
+
+    fn convolve(history: &[f32], [left, right]: &[Vec<f32>; 2]) -> (f32, f32) {
+        // The history is in chronological order, the impulse responses start with the most recent sample
+        let left = history.iter().rev().zip(left).map(|(x, h)| x * h).sum();
+        let right = history.iter().rev().zip(right).map(|(x, h)| x * h).sum();
+        (left, right)
+    }
+}
+
+impl<S> Iterator for HrtfSource<S>
+where
+    S: Source<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if let Some(right) = self.pending_right.take() {
+            return Some(right);
+        }
+
+        if self.block_frames == 0 {
+            self.update_impulse_responses();
+            self.block_frames = HRTF_BLOCK_FRAMES;
+        }
+        self.block_frames -= 1;
+
+        let channels = self.input.channels().max(1);
+        let mut sample = 0.0;
+        for _ in 0..channels {
+            sample += self.input.next()?;
+        }
+        sample /= channels as f32;
+
+        let ir_length = self.hrtf.ir_length;
+        self.history[self.history_position] = sample;
+        self.history[self.history_position + ir_length] = sample;
+        self.history_position = (self.history_position + 1) % ir_length;
+        let history = &self.history[self.history_position..self.history_position + ir_length];
+
+        let (mut left, mut right) = Self::convolve(history, &self.current);
+        if self.fade_frames > 0 {
+            let (previous_left, previous_right) = Self::convolve(history, &self.previous);
+            let fade = self.fade_frames as f32 / HRTF_BLOCK_FRAMES as f32;
+            left += (previous_left - left) * fade;
+            right += (previous_right - right) * fade;
+            self.fade_frames -= 1;
+        }
+
+        self.pending_right = Some(right);
+        Some(left)
+    }
+}
+
+impl<S> Source for HrtfSource<S>
+where
+    S: Source<Item = f32>,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        2
+    }
+
+    fn sample_rate(&self) -> u32 {
+        // The sample rate of the input can vary slightly, for example with the Doppler effect.
+        // The impulse responses are resampled once, as the error is inaudible.
+        self.input.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.history.fill(0.0);
+        self.pending_right = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Hrtf, HrtfPositions, HrtfSource};
+    use bevy_math::{ops, Quat, Vec3};
+    use rodio::buffer::SamplesBuffer;
+
+    /// Renders a second of white-ish noise coming from the given position, returning the energy of each ear.
+    fn render(emitter: Vec3) -> (f32, f32) {
+        let mut seed = 1u32;
+        let samples = (0..44100)
+            .map(|_| {
+                seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+                (seed >> 8) as f32 / (1 << 24) as f32 - 0.5
+            })
+            .collect::<Vec<_>>();
+        let positions = HrtfPositions::new(
+            emitter,
+            Vec3::new(-0.1, 0.0, 0.0),
+            Vec3::new(0.1, 0.0, 0.0),
+            Quat::IDENTITY,
+        );
+        let source = HrtfSource::new(
+            SamplesBuffer::new(1, 44100, samples),
+            &Hrtf::default(),
+            positions,
+        );
+        let output = source.collect::<Vec<_>>();
+        output.chunks(2).fold((0.0, 0.0), |(left, right), frame| {
+            (left + frame[0] * frame[0], right + frame[1] * frame[1])
+        })
+    }
+
+    #[test]
+    fn far_ear_is_quieter() {
+        let (left, right) = render(Vec3::X);
+        assert!(right > 2.0 * left, "{left} {right}");
+
+        let (left, right) = render(Vec3::NEG_X);
+        assert!(left > 2.0 * right, "{left} {right}");
+
+        let (left, right) = render(Vec3::NEG_Z);
+        assert!(ops::abs(left - right) < 0.01 * left, "{left} {right}");
+    }
+
+    #[test]
+    fn elevation_changes_the_spectrum() {
+        let (front, _) = render(Vec3::NEG_Z);
+        let (above, _) = render(Vec3::Y);
+        assert!(ops::abs(front - above) > 0.01 * front, "{front} {above}");
+    }
+}
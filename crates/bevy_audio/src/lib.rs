@@ -29,11 +29,21 @@
 
 extern crate alloc;
 
+mod analysis;
 mod audio;
 mod audio_output;
 mod audio_source;
+mod bus;
+mod clock;
+mod doppler;
+mod effects;
+mod hrtf;
+mod loop_points;
+mod music;
+mod occlusion;
 mod pitch;
 mod sinks;
+mod streaming_source;
 mod volume;
 
 /// The audio prelude.
@@ -42,14 +52,25 @@ mod volume;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        AudioPlayer, AudioSink, AudioSinkPlayback, AudioSource, Decodable, GlobalVolume, Pitch,
-        PlaybackSettings, SpatialAudioSink, SpatialListener,
+        AudioBus, AudioBuses, AudioEffect, AudioEffects, AudioOcclusion, AudioPlayer, AudioSink,
+        AudioSinkPlayback, AudioSource, Decodable, GlobalVolume, Hrtf, MusicPlayer, Pitch,
+        PlaybackSettings, SpatialAudioSink, SpatialListener, StreamingAudioSource,
     };
 }
 
+pub use analysis::{AudioAnalysis, AudioAnalyzer, AudioSpectrum};
 pub use audio::*;
 pub use audio_source::*;
+pub use bus::{AudioBus, AudioBusSettings, AudioBuses};
+pub use clock::{AudioClock, AudioTime, Tempo};
+pub use doppler::{AudioVelocity, DopplerSettings};
+pub use effects::{AudioEffect, AudioEffects};
+pub use hrtf::{Hrtf, HrtfPoint};
+pub use loop_points::LoopPoints;
+pub use music::{MusicDucking, MusicPlayer, MusicTrack};
+pub use occlusion::AudioOcclusion;
 pub use pitch::*;
+pub use streaming_source::*;
 pub use volume::*;
 
 pub use rodio::{cpal::Sample as CpalSample, source::Source, Sample};
@@ -76,21 +97,41 @@ pub struct AudioPlugin {
     /// The scale factor applied to the positions of audio sources and listeners for
     /// spatial audio.
     pub default_spatial_scale: SpatialScale,
+    /// Configures the Doppler effect of spatial audio.
+    pub doppler: DopplerSettings,
 }
 
 impl Plugin for AudioPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(self.global_volume)
             .insert_resource(DefaultSpatialScale(self.default_spatial_scale))
+            .insert_resource(self.doppler)
             .configure_sets(
                 PostUpdate,
                 AudioPlaybackSystems
                     .run_if(audio_output_available)
                     .after(TransformSystems::Propagate), // For spatial audio transforms
             )
+            .init_resource::<AudioBuses>()
+            .init_resource::<AudioClock>()
+            .init_resource::<AudioAnalysis>()
+            .init_resource::<MusicPlayer>()
+            .init_asset::<Hrtf>()
             .add_systems(
                 PostUpdate,
-                (update_emitter_positions, update_listener_positions).in_set(AudioPlaybackSystems),
+                (
+                    (
+                        update_emitter_positions,
+                        update_listener_positions,
+                        doppler::update_doppler_shift,
+                    )
+                        .in_set(AudioPlaybackSystems),
+                    music::update_music_player.before(AudioPlaybackSystems),
+                    bus::update_audio_buses,
+                    effects::update_audio_effects,
+                    occlusion::update_audio_occlusion,
+                    analysis::update_audio_analysis,
+                ),
             )
             .init_resource::<AudioOutput>();
 
@@ -98,6 +139,8 @@ impl Plugin for AudioPlugin {
         {
             app.add_audio_source::<AudioSource>();
             app.init_asset_loader::<AudioLoader>();
+            app.add_audio_source::<StreamingAudioSource>();
+            app.init_asset_loader::<StreamingAudioLoader>();
         }
 
         app.add_audio_source::<Pitch>();
@@ -0,0 +1,325 @@
+use bevy_reflect::prelude::*;
+use core::time::Duration;
+use rodio::{source::SeekError, Sample, Source};
+
+/// The region of an [`AudioSource`](crate::AudioSource) that is repeated when it is played with
+/// [`PlaybackMode::Loop`](crate::PlaybackMode::Loop).
+///
+/// The audio before `start` is only played once, so music with an intro can transition into its
+/// loop without a gap. Positions are in sample frames, a frame containing one sample per channel,
+/// so that loops are sample-accurate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Clone, Debug, PartialEq, Hash)]
+pub struct LoopPoints {
+    /// The first frame of the loop.
+    pub start: u64,
+    /// The frame after the last frame of the loop, or `None` to loop until the end of the audio.
+    pub end: Option<u64>,
+}
+
+impl LoopPoints {
+    /// Creates [`LoopPoints`] repeating the frames from `start` until the end of the audio.
+    pub const fn from_start(start: u64) -> Self {
+        Self { start, end: None }
+    }
+
+    /// Creates [`LoopPoints`] repeating the frames from `start` to `end`, excluded.
+    pub const fn new(start: u64, end: u64) -> Self {
+        Self {
+            start,
+            end: Some(end),
+        }
+    }
+
+    /// Reads the loop points stored in the metadata of a WAV or Ogg Vorbis file.
+    ///
+    /// WAV files store loops in their `smpl` chunk, the first one is used. Ogg Vorbis files store
+    /// them as `LOOPSTART` and either `LOOPEND` or `LOOPLENGTH` comments, as written by most
+    /// music tools and game engines.
+    pub fn from_metadata(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WAVE") {
+            wav_loop_points(bytes)
+        } else if bytes.starts_with(b"OggS") {
+            vorbis_loop_points(bytes)
+        } else {
+            None
+        }
+    }
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    let bytes = bytes.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes(bytes.try_into().ok()?))
+}
+
+/// Reads the first loop of the `smpl` chunk of a WAV file.
+fn wav_loop_points(bytes: &[u8]) -> Option<LoopPoints> {
+    /// The size of the fields of the `smpl` chunk before its loops.
+    const SAMPLER_HEADER: usize = 36;
+
+    let mut offset = 12;
+    while offset + 8 <= bytes.len() {
+        let id = &bytes[offset..offset + 4];
+        let size = read_u32(bytes, offset + 4)? as usize;
+        let data = offset + 8;
+        if id == b"smpl" {
+            let loop_count = read_u32(bytes, data + 28)?;
+            if loop_count == 0 {
+                return None;
+            }
+            let first_loop = data + SAMPLER_HEADER;
+            let start = read_u32(bytes, first_loop + 8)?;
+            // The end of the loop is inclusive
+            let end = read_u32(bytes, first_loop + 12)?;
+            return (end >= start).then(|| LoopPoints::new(start as u64, end as u64 + 1));
+        }
+        // Chunks are padded to an even size
+        offset = data + size + (size & 1);
+    }
+    None
+}
+
+/// Reads the `LOOPSTART`, `LOOPEND` and `LOOPLENGTH` comments of an Ogg Vorbis file.
+fn vorbis_loop_points(bytes: &[u8]) -> Option<LoopPoints> {
+    /// The start of the comment header packet.
+    const COMMENT_HEADER: &[u8] = b"\x03vorbis";
+
+    let header = bytes
+        .windows(COMMENT_HEADER.len())
+        .position(|window| window == COMMENT_HEADER)?;
+    let mut offset = header + COMMENT_HEADER.len();
+    let vendor_length = read_u32(bytes, offset)? as usize;
+    offset += 4 + vendor_length;
+    let comment_count = read_u32(bytes, offset)?;
+    offset += 4;
+
+    let (mut start, mut end, mut length) = (None, None, None);
+    for _ in 0..comment_count {
+        let comment_length = read_u32(bytes, offset)? as usize;
+        let comment = bytes.get(offset + 4..offset + 4 + comment_length)?;
+        offset += 4 + comment_length;
+
+        let Ok(comment) = core::str::from_utf8(comment) else {
+            continue;
+        };
+        let Some((key, value)) = comment.split_once('=') else {
+            continue;
+        };
+        let value = value.trim().parse::<u64>().ok();
+        match key.to_ascii_uppercase().as_str() {
+            "LOOPSTART" => start = value,
+            "LOOPEND" => end = value,
+            "LOOPLENGTH" => length = value,
+            _ => {}
+        }
+    }
+
+    let start = start?;
+    Some(LoopPoints {
+        start,
+        end: end.or(length.map(|length| start + length)),
+    })
+}
+
+/// Plays a source until the end of its loop, then repeats the loop forever.
+///
+/// The samples of the loop are kept in memory while they are decoded for the first time, so the
+/// source is only decoded once.
+pub(crate) struct LoopPointsSource<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    /// The source, until the end of the loop is reached for the first time.
+    input: Option<S>,
+    loop_samples: Vec<S::Item>,
+    /// The position in the source, in samples, during the first pass.
+    position: u64,
+    /// The samples before this position are decoded but not played.
+    skip_until: u64,
+    start: u64,
+    end: Option<u64>,
+    /// The position in `loop_samples` once the loop is repeated.
+    replay_position: usize,
+    channels: u16,
+    sample_rate: u32,
+}
+
+impl<S> LoopPointsSource<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    /// Plays `input` from `start_position`, repeating the region between the loop points.
+    pub(crate) fn new(input: S, loop_points: LoopPoints, start_position: Option<Duration>) -> Self {
+        let channels = input.channels();
+        let sample_rate = input.sample_rate();
+        let to_samples = |frames: u64| frames * channels as u64;
+        let skip_frames = start_position
+            .map(|position| (position.as_secs_f64() * sample_rate as f64) as u64)
+            .unwrap_or(0);
+        Self {
+            input: Some(input),
+            loop_samples: Vec::new(),
+            position: 0,
+            skip_until: to_samples(skip_frames),
+            start: to_samples(loop_points.start),
+            end: loop_points.end.map(to_samples),
+            replay_position: 0,
+            channels,
+            sample_rate,
+        }
+    }
+
+    /// Reads the next sample of the first pass, or returns `None` once the end of the loop is reached.
+    fn next_from_input(&mut self) -> Option<S::Item> {
+        let input = self.input.as_mut()?;
+        if self.end.is_some_and(|end| self.position >= end) {
+            self.input = None;
+            return None;
+        }
+        let Some(sample) = input.next() else {
+            self.input = None;
+            return None;
+        };
+        if self.position >= self.start {
+            self.loop_samples.push(sample);
+        }
+        self.position += 1;
+        Some(sample)
+    }
+}
+
+impl<S> Iterator for LoopPointsSource<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    type Item = S::Item;
+
+    fn next(&mut self) -> Option<S::Item> {
+        while self.input.is_some() {
+            let position = self.position;
+            match self.next_from_input() {
+                Some(_) if position < self.skip_until => continue,
+                Some(sample) => return Some(sample),
+                None => break,
+            }
+        }
+
+        // The loop may not contain a whole number of frames if the source ended early
+        let frames_len =
+            self.loop_samples.len() - self.loop_samples.len() % self.channels.max(1) as usize;
+        if frames_len == 0 {
+            return None;
+        }
+        if self.replay_position >= frames_len {
+            self.replay_position = 0;
+        }
+        let sample = self.loop_samples[self.replay_position];
+        self.replay_position += 1;
+        Some(sample)
+    }
+}
+
+impl<S> Source for LoopPointsSource<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        // Seeking is supported within the loop once it has been decoded
+        let frame = (pos.as_secs_f64() * self.sample_rate as f64) as u64;
+        let sample = frame * self.channels as u64;
+        if self.input.is_some() || sample < self.start || self.loop_samples.is_empty() {
+            return Err(SeekError::NotSupported {
+                underlying_source: core::any::type_name::<Self>(),
+            });
+        }
+        self.replay_position = ((sample - self.start) % self.loop_samples.len() as u64) as usize;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LoopPoints, LoopPointsSource};
+    use core::time::Duration;
+    use rodio::buffer::SamplesBuffer;
+
+    #[test]
+    fn intro_then_loop() {
+        let samples: Vec<i16> = (0..10).collect();
+        let source = LoopPointsSource::new(
+            SamplesBuffer::new(1, 10, samples.clone()),
+            LoopPoints::new(4, 7),
+            None,
+        );
+        let played: Vec<i16> = source.take(13).collect();
+        assert_eq!(played, [0, 1, 2, 3, 4, 5, 6, 4, 5, 6, 4, 5, 6]);
+
+        let source = LoopPointsSource::new(
+            SamplesBuffer::new(1, 10, samples),
+            LoopPoints::from_start(8),
+            Some(Duration::from_millis(500)),
+        );
+        let played: Vec<i16> = source.take(7).collect();
+        assert_eq!(played, [5, 6, 7, 8, 9, 8, 9]);
+    }
+
+    #[test]
+    fn wav_metadata() {
+        let mut smpl = vec![0u8; 36 + 24];
+        smpl[28..32].copy_from_slice(&1u32.to_le_bytes());
+        smpl[36 + 8..36 + 12].copy_from_slice(&1000u32.to_le_bytes());
+        smpl[36 + 12..36 + 16].copy_from_slice(&4999u32.to_le_bytes());
+
+        let mut wav = b"RIFF\0\0\0\0WAVE".to_vec();
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&3u32.to_le_bytes());
+        wav.extend_from_slice(&[0, 0, 0, 0]);
+        wav.extend_from_slice(b"smpl");
+        wav.extend_from_slice(&(smpl.len() as u32).to_le_bytes());
+        wav.extend_from_slice(&smpl);
+
+        assert_eq!(
+            LoopPoints::from_metadata(&wav),
+            Some(LoopPoints::new(1000, 5000))
+        );
+    }
+
+    #[test]
+    fn vorbis_metadata() {
+        let mut ogg = b"OggS".to_vec();
+        ogg.extend_from_slice(b"\x03vorbis");
+        ogg.extend_from_slice(&4u32.to_le_bytes());
+        ogg.extend_from_slice(b"test");
+        let comments = ["TITLE=Theme", "LOOPSTART=44100", "LOOPLENGTH=88200"];
+        ogg.extend_from_slice(&(comments.len() as u32).to_le_bytes());
+        for comment in comments {
+            ogg.extend_from_slice(&(comment.len() as u32).to_le_bytes());
+            ogg.extend_from_slice(comment.as_bytes());
+        }
+
+        assert_eq!(
+            LoopPoints::from_metadata(&ogg),
+            Some(LoopPoints::new(44100, 132300))
+        );
+    }
+}
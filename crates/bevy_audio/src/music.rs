@@ -0,0 +1,360 @@
+use crate::{
+    AudioBus, AudioBuses, AudioPlayer, AudioSink, AudioSinkPlayback, AudioSource, PlaybackSettings,
+    SpatialAudioSink, Volume,
+};
+use alloc::collections::VecDeque;
+use bevy_asset::Handle;
+use bevy_ecs::prelude::*;
+use bevy_math::ops;
+use bevy_time::Time;
+use core::{f32::consts::FRAC_PI_2, time::Duration};
+
+/// Plays music tracks one after the other, crossfading between them and ducking the music while
+/// dialogue plays.
+///
+/// Requests are applied at the end of the frame:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_asset::Handle;
+/// # use bevy_audio::{AudioSource, MusicPlayer};
+/// # use core::time::Duration;
+/// fn enter_battle(mut music: ResMut<MusicPlayer>, battle_theme: Handle<AudioSource>) {
+///     music.play(battle_theme, Duration::from_secs(2));
+/// }
+/// ```
+///
+/// The tracks are played by entities with a [`MusicTrack`] component, routed through
+/// [`MusicPlayer::bus`].
+#[derive(Resource, Debug)]
+pub struct MusicPlayer {
+    /// The bus the tracks are routed through.
+    pub bus: AudioBus,
+    /// Adds the tracks that finish playing back at the end of the queue, so the playlist repeats.
+    pub repeat: bool,
+    /// Lowers the volume of [`MusicPlayer::bus`] while sounds routed through another bus play.
+    pub ducking: Option<MusicDucking>,
+    queue: VecDeque<Handle<AudioSource>>,
+    current: Option<(Entity, Handle<AudioSource>)>,
+    requests: Vec<MusicRequest>,
+    /// The bus that was last ducked, restored if [`MusicPlayer::bus`] changes.
+    ducked_bus: Option<AudioBus>,
+}
+
+impl Default for MusicPlayer {
+    fn default() -> Self {
+        Self {
+            bus: AudioBus::MUSIC,
+            repeat: false,
+            ducking: None,
+            queue: VecDeque::new(),
+            current: None,
+            requests: Vec::new(),
+            ducked_bus: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum MusicRequest {
+    Play {
+        track: Handle<AudioSource>,
+        crossfade: Duration,
+    },
+    Skip {
+        crossfade: Duration,
+    },
+    Stop {
+        fade_out: Duration,
+    },
+}
+
+impl MusicPlayer {
+    /// Plays the track right away, crossfading from the current track over the given duration.
+    ///
+    /// The queue is kept, and continues after this track.
+    pub fn play(&mut self, track: Handle<AudioSource>, crossfade: Duration) {
+        self.requests.push(MusicRequest::Play { track, crossfade });
+    }
+
+    /// Adds the track at the end of the queue. It starts when the tracks before it finish, or
+    /// right away if no track is playing.
+    pub fn queue(&mut self, track: Handle<AudioSource>) {
+        self.queue.push_back(track);
+    }
+
+    /// Crossfades from the current track to the next track in the queue over the given duration.
+    pub fn skip(&mut self, crossfade: Duration) {
+        self.requests.push(MusicRequest::Skip { crossfade });
+    }
+
+    /// Fades out the current track over the given duration, and clears the queue.
+    pub fn stop(&mut self, fade_out: Duration) {
+        self.requests.push(MusicRequest::Stop { fade_out });
+    }
+
+    /// Returns the track that is playing, or being faded in.
+    pub fn current(&self) -> Option<&Handle<AudioSource>> {
+        self.current.as_ref().map(|(_, track)| track)
+    }
+
+    /// Returns the tracks waiting in the queue.
+    pub fn queued(&self) -> impl ExactSizeIterator<Item = &Handle<AudioSource>> {
+        self.queue.iter()
+    }
+
+    /// Removes the tracks waiting in the queue.
+    pub fn clear_queue(&mut self) {
+        self.queue.clear();
+    }
+
+    /// Fades out the current track and starts `next`, if there is one.
+    fn transition(
+        &mut self,
+        commands: &mut Commands,
+        tracks: &mut Query<(Entity, &mut MusicTrack, Option<&mut AudioSink>)>,
+        next: Option<Handle<AudioSource>>,
+        crossfade: Duration,
+    ) {
+        if let Some((entity, _)) = self.current.take()
+            && let Ok((_, mut track, _)) = tracks.get_mut(entity)
+        {
+            let gain = track.gain();
+            track.fade = Some(Fade::new(FadeDirection::Out { from: gain }, crossfade));
+        }
+
+        if let Some(next) = next {
+            let entity = commands
+                .spawn((
+                    AudioPlayer(next.clone()),
+                    PlaybackSettings::ONCE
+                        .with_bus(self.bus)
+                        .with_volume(Volume::SILENT),
+                    MusicTrack {
+                        fade: Some(Fade::new(FadeDirection::In, crossfade)),
+                    },
+                ))
+                .id();
+            self.current = Some((entity, next));
+        }
+    }
+}
+
+/// Settings for lowering the volume of the music while other sounds play, so that dialogue can
+/// be heard over it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MusicDucking {
+    /// The bus whose sounds trigger the ducking.
+    pub trigger: AudioBus,
+    /// The volume of the music while it is ducked.
+    pub volume: Volume,
+    /// How long the music takes to be ducked when a sound starts.
+    pub attack: Duration,
+    /// How long the music takes to recover after the sounds stop.
+    pub release: Duration,
+}
+
+impl Default for MusicDucking {
+    fn default() -> Self {
+        Self {
+            trigger: AudioBus::VOICE,
+            volume: Volume::Decibels(-12.0),
+            attack: Duration::from_millis(100),
+            release: Duration::from_millis(500),
+        }
+    }
+}
+
+/// Marks an entity playing a track of the [`MusicPlayer`].
+///
+/// Its volume is controlled by the [`MusicPlayer`], and it is despawned when the track finishes or
+/// is faded out.
+#[derive(Component, Debug)]
+pub struct MusicTrack {
+    fade: Option<Fade>,
+}
+
+impl MusicTrack {
+    /// Returns the gain of the track, following an equal power curve during fades.
+    fn gain(&self) -> f32 {
+        let Some(fade) = &self.fade else {
+            return 1.0;
+        };
+        let progress = if fade.duration.is_zero() {
+            1.0
+        } else {
+            (fade.elapsed.as_secs_f32() / fade.duration.as_secs_f32()).min(1.0)
+        };
+        match fade.direction {
+            FadeDirection::In => ops::sin(progress * FRAC_PI_2),
+            FadeDirection::Out { from } => from * ops::cos(progress * FRAC_PI_2),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Fade {
+    direction: FadeDirection,
+    elapsed: Duration,
+    duration: Duration,
+}
+
+impl Fade {
+    fn new(direction: FadeDirection, duration: Duration) -> Self {
+        Self {
+            direction,
+            elapsed: Duration::ZERO,
+            duration,
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum FadeDirection {
+    In,
+    Out { from: f32 },
+}
+
+/// Applies the requests of the [`MusicPlayer`], fades its tracks and ducks its bus.
+pub(crate) fn update_music_player(
+    mut commands: Commands,
+    mut player: ResMut<MusicPlayer>,
+    mut tracks: Query<(Entity, &mut MusicTrack, Option<&mut AudioSink>)>,
+    sounds: Query<(&PlaybackSettings, AnyOf<(&AudioSink, &SpatialAudioSink)>), Without<MusicTrack>>,
+    mut buses: ResMut<AudioBuses>,
+    time: Res<Time>,
+) {
+    let player = player.as_mut();
+
+    for request in core::mem::take(&mut player.requests) {
+        match request {
+            MusicRequest::Play { track, crossfade } => {
+                player.transition(&mut commands, &mut tracks, Some(track), crossfade);
+            }
+            MusicRequest::Skip { crossfade } => {
+                if player.repeat
+                    && let Some((_, track)) = &player.current
+                {
+                    player.queue.push_back(track.clone());
+                }
+                let next = player.queue.pop_front();
+                player.transition(&mut commands, &mut tracks, next, crossfade);
+            }
+            MusicRequest::Stop { fade_out } => {
+                player.queue.clear();
+                player.transition(&mut commands, &mut tracks, None, fade_out);
+            }
+        }
+    }
+
+    // Start the next track when the current one finishes
+    let finished = match &player.current {
+        Some((entity, _)) => tracks
+            .get(*entity)
+            .is_ok_and(|(.., sink)| sink.is_some_and(AudioSinkPlayback::empty)),
+        None => true,
+    };
+    if finished {
+        if let Some((entity, track)) = player.current.take() {
+            commands.entity(entity).despawn();
+            if player.repeat {
+                player.queue.push_back(track);
+            }
+        }
+        if let Some(next) = player.queue.pop_front() {
+            player.transition(&mut commands, &mut tracks, Some(next), Duration::ZERO);
+        }
+    }
+
+    for (entity, mut track, sink) in &mut tracks {
+        // Wait for the track to start playing before fading it
+        let Some(mut sink) = sink else {
+            continue;
+        };
+        if let Some(fade) = &mut track.fade {
+            fade.elapsed += time.delta();
+        }
+        sink.set_volume(Volume::Linear(track.gain()));
+
+        match &track.fade {
+            Some(fade) if fade.is_finished() => match fade.direction {
+                FadeDirection::In => track.fade = None,
+                FadeDirection::Out { .. } => commands.entity(entity).despawn(),
+            },
+            _ => {}
+        }
+    }
+
+    // Only the gains need to be updated, the settings of the buses didn't change
+    let buses = buses.bypass_change_detection();
+    if let Some(ducked_bus) = player.ducked_bus.replace(player.bus)
+        && ducked_bus != player.bus
+    {
+        buses.set_ducking(ducked_bus, 1.0);
+    }
+
+    let (target, duration) = match &player.ducking {
+        Some(ducking)
+            if sounds.iter().any(|(settings, (sink, spatial_sink))| {
+                let playing = match (sink, spatial_sink) {
+                    (Some(sink), _) => !sink.is_paused() && !sink.empty(),
+                    (_, Some(sink)) => !sink.is_paused() && !sink.empty(),
+                    (None, None) => false,
+                };
+                playing && buses.path(settings.bus).any(|bus| bus == ducking.trigger)
+            }) =>
+        {
+            (ducking.volume.to_linear(), ducking.attack)
+        }
+        Some(ducking) => (1.0, ducking.release),
+        None => (1.0, Duration::ZERO),
+    };
+    let current = buses.ducking(player.bus);
+    if current != target {
+        let step = if duration.is_zero() {
+            1.0
+        } else {
+            time.delta_secs() / duration.as_secs_f32()
+        };
+        let gain = if current < target {
+            (current + step).min(target)
+        } else {
+            (current - step).max(target)
+        };
+        buses.set_ducking(player.bus, gain);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Fade, FadeDirection, MusicTrack};
+    use bevy_math::ops;
+    use core::time::Duration;
+
+    #[test]
+    fn crossfade_keeps_power() {
+        let duration = Duration::from_secs(2);
+        let mut fading_in = MusicTrack {
+            fade: Some(Fade::new(FadeDirection::In, duration)),
+        };
+        let mut fading_out = MusicTrack {
+            fade: Some(Fade::new(FadeDirection::Out { from: 1.0 }, duration)),
+        };
+        assert_eq!(fading_in.gain(), 0.0);
+        assert_eq!(fading_out.gain(), 1.0);
+
+        for _ in 0..4 {
+            for track in [&mut fading_in, &mut fading_out] {
+                track.fade.as_mut().unwrap().elapsed += Duration::from_millis(500);
+            }
+            let power = fading_in.gain() * fading_in.gain() + fading_out.gain() * fading_out.gain();
+            assert!(ops::abs(power - 1.0) < 1e-5);
+        }
+        assert!(fading_in.fade.as_ref().unwrap().is_finished());
+        assert!(fading_out.gain() < 1e-5);
+    }
+}
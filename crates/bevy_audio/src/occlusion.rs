@@ -0,0 +1,135 @@
+use crate::{effects::AudioEffectsHandle, AudioEffect, SpatialAudioSink, Volume};
+use bevy_ecs::prelude::*;
+use bevy_math::ops;
+use bevy_reflect::prelude::*;
+
+/// How much the sound of a spatial emitter is blocked by the geometry between it and the
+/// [`SpatialListener`](crate::SpatialListener).
+///
+/// Bevy doesn't know about the geometry of the world: this component is meant to be updated by a
+/// gameplay system, for example by casting rays from the listener to the emitter. Blocked sounds
+/// are muffled with a low-pass filter and made quieter.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_audio::AudioOcclusion;
+/// # fn walls_between_listener_and(emitter: Entity) -> usize { 0 }
+/// fn occlude(mut query: Query<(Entity, &mut AudioOcclusion)>) {
+///     for (entity, mut occlusion) in &mut query {
+///         let walls = walls_between_listener_and(entity);
+///         occlusion.occlusion = (walls as f32 * 0.5).min(1.0);
+///     }
+/// }
+/// ```
+///
+/// The occlusion is applied after the [`AudioEffects`](crate::AudioEffects) of the sound, and
+/// only to sounds played with [`PlaybackSettings::spatial`](crate::PlaybackSettings::spatial).
+/// Unlike [`AudioEffects`](crate::AudioEffects), it can be added after the sound starts playing.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Clone, Debug, Default, PartialEq)]
+pub struct AudioOcclusion {
+    /// How much the emitter is fully enclosed from the listener, for example in another room,
+    /// between `0.0` and `1.0`.
+    ///
+    /// Both the sound and its reflections are blocked, so the sound is heavily attenuated and
+    /// muffled.
+    pub occlusion: f32,
+    /// How much the direct path between the emitter and the listener is blocked, for example by
+    /// a pillar, between `0.0` and `1.0`.
+    ///
+    /// The sound still reaches the listener by going around the obstacle, so it is muffled but
+    /// only slightly attenuated.
+    pub obstruction: f32,
+}
+
+impl AudioOcclusion {
+    /// The attenuation of a fully occluded sound, in decibels.
+    const OCCLUSION_ATTENUATION: f32 = 24.0;
+    /// The attenuation of a fully obstructed sound, in decibels.
+    const OBSTRUCTION_ATTENUATION: f32 = 6.0;
+    /// The cutoff frequency of the low-pass filter of sounds that aren't blocked.
+    const MAX_CUTOFF_FREQUENCY: f32 = 20_000.0;
+    /// The cutoff frequency of the low-pass filter of fully blocked sounds.
+    const MIN_CUTOFF_FREQUENCY: f32 = 400.0;
+
+    /// Creates an [`AudioOcclusion`] with the given occlusion and obstruction.
+    pub const fn new(occlusion: f32, obstruction: f32) -> Self {
+        Self {
+            occlusion,
+            obstruction,
+        }
+    }
+
+    /// Returns the effects simulating the occlusion and obstruction of the sound.
+    pub fn effects(&self) -> Vec<AudioEffect> {
+        let occlusion = self.occlusion.clamp(0.0, 1.0);
+        let obstruction = self.obstruction.clamp(0.0, 1.0);
+        if occlusion == 0.0 && obstruction == 0.0 {
+            return Vec::new();
+        }
+
+        let attenuation =
+            occlusion * Self::OCCLUSION_ATTENUATION + obstruction * Self::OBSTRUCTION_ATTENUATION;
+        // Both block high frequencies, the cutoff frequency is interpolated on a logarithmic scale
+        let muffling = 1.0 - (1.0 - occlusion) * (1.0 - obstruction);
+        let cutoff_frequency = Self::MAX_CUTOFF_FREQUENCY
+            * ops::powf(
+                Self::MIN_CUTOFF_FREQUENCY / Self::MAX_CUTOFF_FREQUENCY,
+                muffling,
+            );
+
+        vec![
+            AudioEffect::gain(Volume::Decibels(-attenuation)),
+            AudioEffect::low_pass(cutoff_frequency),
+        ]
+    }
+}
+
+/// Sends changes of [`AudioOcclusion`] to the spatial sounds that are playing.
+pub(crate) fn update_audio_occlusion(
+    query: Query<(&AudioOcclusion, &SpatialAudioSink), Changed<AudioOcclusion>>,
+    sinks: Query<&SpatialAudioSink>,
+    mut removed: RemovedComponents<AudioOcclusion>,
+) {
+    for (occlusion, sink) in &query {
+        sink.occlusion.set(&occlusion.effects());
+    }
+    for entity in removed.read() {
+        if let Ok(sink) = sinks.get(entity) {
+            sink.occlusion.set(&[]);
+        }
+    }
+}
+
+/// Creates the occlusion effects of a spatial sound that starts playing.
+pub(crate) fn occlusion_handle(occlusion: Option<&AudioOcclusion>) -> AudioEffectsHandle {
+    AudioEffectsHandle::new(&occlusion.map(AudioOcclusion::effects).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AudioOcclusion;
+    use crate::AudioEffect;
+    use bevy_math::ops;
+
+    fn cutoff_frequency(occlusion: AudioOcclusion) -> f32 {
+        match occlusion.effects()[..] {
+            [_, AudioEffect::LowPass {
+                cutoff_frequency, ..
+            }] => cutoff_frequency,
+            _ => panic!("Expected a low-pass filter"),
+        }
+    }
+
+    #[test]
+    fn blocked_sounds_are_muffled() {
+        assert!(AudioOcclusion::default().effects().is_empty());
+
+        let obstructed = cutoff_frequency(AudioOcclusion::new(0.0, 0.5));
+        let occluded = cutoff_frequency(AudioOcclusion::new(0.5, 0.5));
+        let fully_occluded = cutoff_frequency(AudioOcclusion::new(1.0, 0.0));
+        assert!(obstructed < AudioOcclusion::MAX_CUTOFF_FREQUENCY);
+        assert!(occluded < obstructed);
+        assert!(ops::abs(fully_occluded - AudioOcclusion::MIN_CUTOFF_FREQUENCY) < 1.0);
+    }
+}
@@ -1,10 +1,17 @@
-use crate::Volume;
+use crate::{
+    analysis::AnalysisTap,
+    bus::AppendSource,
+    doppler::DopplerState,
+    effects::AudioEffectsHandle,
+    hrtf::{HrtfPositions, HrtfSource},
+    Hrtf, Volume,
+};
 use bevy_ecs::component::Component;
-use bevy_math::Vec3;
+use bevy_math::{Quat, Vec3};
 use bevy_transform::prelude::Transform;
 use core::time::Duration;
 pub use rodio::source::SeekError;
-use rodio::{Sink, SpatialSink};
+use rodio::{Sink, Source, SpatialSink};
 
 /// Common interactions with an audio sink.
 pub trait AudioSinkPlayback {
@@ -151,6 +158,12 @@ pub struct AudioSink {
     /// user's intended volume setting, even if the underlying sink's volume is
     /// 0.
     pub(crate) managed_volume: Option<Volume>,
+
+    /// The effects of the sound, if it was played with [`AudioEffects`](crate::AudioEffects).
+    pub(crate) effects: Option<AudioEffectsHandle>,
+
+    /// The latest samples of the sound, if it is analyzed, see [`AudioAnalysis`](crate::AudioAnalysis).
+    pub(crate) analysis: Option<AnalysisTap>,
 }
 
 impl AudioSink {
@@ -159,6 +172,8 @@ impl AudioSink {
         Self {
             sink,
             managed_volume: None,
+            effects: None,
+            analysis: None,
         }
     }
 }
@@ -241,7 +256,7 @@ impl AudioSinkPlayback for AudioSink {
 /// that source is unchanged, that translates to the audio restarting.
 #[derive(Component)]
 pub struct SpatialAudioSink {
-    pub(crate) sink: SpatialSink,
+    pub(crate) sink: SpatialSinkBackend,
 
     /// Managed volume allows the sink to be muted without losing the user's
     /// intended volume setting.
@@ -255,16 +270,41 @@ pub struct SpatialAudioSink {
     /// user's intended volume setting, even if the underlying sink's volume is
     /// 0.
     pub(crate) managed_volume: Option<Volume>,
+
+    /// The effects of the sound, if it was played with [`AudioEffects`](crate::AudioEffects).
+    pub(crate) effects: Option<AudioEffectsHandle>,
+
+    /// The latest samples of the sound, if it is analyzed, see [`AudioAnalysis`](crate::AudioAnalysis).
+    pub(crate) analysis: Option<AnalysisTap>,
+
+    /// The effects simulating the [`AudioOcclusion`](crate::AudioOcclusion) of the sound.
+    pub(crate) occlusion: AudioEffectsHandle,
+
+    /// The Doppler shift of the sound, see [`DopplerSettings`](crate::DopplerSettings).
+    pub(crate) doppler: DopplerState,
 }
 
 impl SpatialAudioSink {
     /// Create a new spatial audio sink.
     pub fn new(sink: SpatialSink) -> Self {
+        Self::from_backend(SpatialSinkBackend::Panning(sink))
+    }
+
+    pub(crate) fn from_backend(sink: SpatialSinkBackend) -> Self {
         Self {
             sink,
             managed_volume: None,
+            effects: None,
+            analysis: None,
+            occlusion: AudioEffectsHandle::default(),
+            doppler: DopplerState::default(),
         }
     }
+
+    /// Returns `true` if the sound is rendered binaurally with a [`Hrtf`].
+    pub fn is_binaural(&self) -> bool {
+        matches!(self.sink, SpatialSinkBackend::Hrtf { .. })
+    }
 }
 
 impl AudioSinkPlayback for SpatialAudioSink {
@@ -336,8 +376,15 @@ impl AudioSinkPlayback for SpatialAudioSink {
 impl SpatialAudioSink {
     /// Set the two ears position.
     pub fn set_ears_position(&self, left_position: Vec3, right_position: Vec3) {
-        self.sink.set_left_ear_position(left_position.to_array());
-        self.sink.set_right_ear_position(right_position.to_array());
+        match &self.sink {
+            SpatialSinkBackend::Panning(sink) => {
+                sink.set_left_ear_position(left_position.to_array());
+                sink.set_right_ear_position(right_position.to_array());
+            }
+            SpatialSinkBackend::Hrtf { positions, .. } => {
+                positions.set_ears(left_position, right_position);
+            }
+        }
     }
 
     /// Set the listener position, with an ear on each side separated by `gap`.
@@ -346,11 +393,148 @@ impl SpatialAudioSink {
             position.translation + position.left() * gap / 2.0,
             position.translation + position.right() * gap / 2.0,
         );
+        self.set_listener_rotation(position.rotation);
+    }
+
+    /// Set the rotation of the listener, used to find the direction of the emitter when the
+    /// sound is rendered with a [`Hrtf`].
+    ///
+    /// Panned sounds only depend on the position of the ears.
+    pub fn set_listener_rotation(&self, rotation: Quat) {
+        if let SpatialSinkBackend::Hrtf { positions, .. } = &self.sink {
+            positions.set_rotation(rotation);
+        }
     }
 
     /// Set the emitter position.
     pub fn set_emitter_position(&self, position: Vec3) {
-        self.sink.set_emitter_position(position.to_array());
+        match &self.sink {
+            SpatialSinkBackend::Panning(sink) => sink.set_emitter_position(position.to_array()),
+            SpatialSinkBackend::Hrtf { positions, .. } => positions.set_emitter(position),
+        }
+    }
+}
+
+impl AppendSource for SpatialAudioSink {
+    fn append_source(&self, source: impl Source<Item = f32> + Send + 'static) {
+        self.sink.append(self.doppler.shift.apply(source));
+    }
+}
+
+/// How a [`SpatialAudioSink`] renders its sounds.
+pub(crate) enum SpatialSinkBackend {
+    /// Pans the sound between the left and right channels.
+    Panning(SpatialSink),
+    /// Convolves the sound with the impulse responses of a [`Hrtf`].
+    Hrtf {
+        sink: Sink,
+        hrtf: Hrtf,
+        positions: HrtfPositions,
+    },
+}
+
+impl SpatialSinkBackend {
+    pub(crate) fn hrtf(
+        sink: Sink,
+        hrtf: Hrtf,
+        emitter: Vec3,
+        left_ear: Vec3,
+        right_ear: Vec3,
+        rotation: Quat,
+    ) -> Self {
+        Self::Hrtf {
+            sink,
+            hrtf,
+            positions: HrtfPositions::new(emitter, left_ear, right_ear, rotation),
+        }
+    }
+
+    pub(crate) fn append(&self, source: impl Source<Item = f32> + Send + 'static) {
+        match self {
+            Self::Panning(sink) => sink.append(source),
+            Self::Hrtf {
+                sink,
+                hrtf,
+                positions,
+            } => sink.append(HrtfSource::new(source, hrtf, positions.clone())),
+        }
+    }
+
+    fn volume(&self) -> f32 {
+        match self {
+            Self::Panning(sink) => sink.volume(),
+            Self::Hrtf { sink, .. } => sink.volume(),
+        }
+    }
+
+    fn set_volume(&self, volume: f32) {
+        match self {
+            Self::Panning(sink) => sink.set_volume(volume),
+            Self::Hrtf { sink, .. } => sink.set_volume(volume),
+        }
+    }
+
+    fn speed(&self) -> f32 {
+        match self {
+            Self::Panning(sink) => sink.speed(),
+            Self::Hrtf { sink, .. } => sink.speed(),
+        }
+    }
+
+    fn set_speed(&self, speed: f32) {
+        match self {
+            Self::Panning(sink) => sink.set_speed(speed),
+            Self::Hrtf { sink, .. } => sink.set_speed(speed),
+        }
+    }
+
+    fn play(&self) {
+        match self {
+            Self::Panning(sink) => sink.play(),
+            Self::Hrtf { sink, .. } => sink.play(),
+        }
+    }
+
+    fn get_pos(&self) -> Duration {
+        match self {
+            Self::Panning(sink) => sink.get_pos(),
+            Self::Hrtf { sink, .. } => sink.get_pos(),
+        }
+    }
+
+    fn try_seek(&self, pos: Duration) -> Result<(), SeekError> {
+        match self {
+            Self::Panning(sink) => sink.try_seek(pos),
+            Self::Hrtf { sink, .. } => sink.try_seek(pos),
+        }
+    }
+
+    fn pause(&self) {
+        match self {
+            Self::Panning(sink) => sink.pause(),
+            Self::Hrtf { sink, .. } => sink.pause(),
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        match self {
+            Self::Panning(sink) => sink.is_paused(),
+            Self::Hrtf { sink, .. } => sink.is_paused(),
+        }
+    }
+
+    fn stop(&self) {
+        match self {
+            Self::Panning(sink) => sink.stop(),
+            Self::Hrtf { sink, .. } => sink.stop(),
+        }
+    }
+
+    pub(crate) fn empty(&self) -> bool {
+        match self {
+            Self::Panning(sink) => sink.empty(),
+            Self::Hrtf { sink, .. } => sink.empty(),
+        }
     }
 }
 
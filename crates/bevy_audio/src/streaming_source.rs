@@ -0,0 +1,440 @@
+use crate::Decodable;
+use alloc::sync::Arc;
+use bevy_asset::{io::Reader, Asset, AssetLoader, LoadContext};
+use bevy_reflect::TypePath;
+use core::time::Duration;
+use rodio::{decoder::DecoderError, Source};
+use std::io::Cursor;
+use tracing::warn;
+
+/// The number of frames, made of one sample per channel, decoded at once by a [`StreamingDecoder`].
+const CHUNK_FRAMES: usize = 2048;
+
+/// The number of decoded chunks a [`StreamingDecoder`] keeps ahead of playback.
+#[cfg(not(target_arch = "wasm32"))]
+const BUFFERED_CHUNKS: usize = 8;
+
+type Sample = <rodio::Decoder<Cursor<StreamingAudioSource>> as Iterator>::Item;
+
+/// A source of audio data that is decoded while it plays.
+///
+/// Unlike [`AudioSource`](crate::AudioSource), samples are decoded in small chunks on the
+/// [`IoTaskPool`](bevy_tasks::IoTaskPool) shortly before they're played, and only a few chunks
+/// are kept in memory at a time. This makes it suited to long tracks like music and voice-over,
+/// while short sound effects are better served by [`AudioSource`](crate::AudioSource).
+///
+/// Streaming sources are loaded from the same file formats as [`AudioSource`](crate::AudioSource),
+/// by requesting this asset type:
+///
+/// ```no_run
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_asset::AssetServer;
+/// # use bevy_audio::{AudioPlayer, PlaybackSettings, StreamingAudioSource};
+/// fn play_music(asset_server: Res<AssetServer>, mut commands: Commands) {
+///     commands.spawn((
+///         AudioPlayer::<StreamingAudioSource>(asset_server.load("music.ogg")),
+///         PlaybackSettings::LOOP,
+///     ));
+/// }
+/// ```
+#[derive(Asset, Debug, Clone, TypePath)]
+pub struct StreamingAudioSource {
+    /// Raw data of the audio source, which must be in one of the formats supported by
+    /// [`AudioSource`](crate::AudioSource).
+    pub bytes: Arc<[u8]>,
+}
+
+impl AsRef<[u8]> for StreamingAudioSource {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// Loads files as [`StreamingAudioSource`] [`Assets`](bevy_asset::Assets)
+///
+/// This supports the same formats as [`AudioLoader`](crate::AudioLoader), and fails if the format of
+/// the file isn't recognized.
+#[derive(Default)]
+pub struct StreamingAudioLoader;
+
+impl AssetLoader for StreamingAudioLoader {
+    type Asset = StreamingAudioSource;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<StreamingAudioSource, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let source = StreamingAudioSource {
+            bytes: bytes.into(),
+        };
+        // Check the format now, as the decoder is created when the source starts playing.
+        rodio::Decoder::new(Cursor::new(source.clone()))
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+        Ok(source)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[
+            #[cfg(feature = "mp3")]
+            "mp3",
+            #[cfg(feature = "flac")]
+            "flac",
+            #[cfg(feature = "wav")]
+            "wav",
+            #[cfg(feature = "vorbis")]
+            "oga",
+            #[cfg(feature = "vorbis")]
+            "ogg",
+            #[cfg(feature = "vorbis")]
+            "spx",
+        ]
+    }
+}
+
+impl Decodable for StreamingAudioSource {
+    type DecoderItem = Sample;
+    type Decoder = StreamingDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        // The format is checked by the loader, as for `AudioSource`.
+        StreamingDecoder::new(self.clone(), None).unwrap()
+    }
+
+    fn looping_decoder(
+        &self,
+        start_position: Option<Duration>,
+        duration: Option<Duration>,
+    ) -> Box<dyn Source<Item = Self::DecoderItem> + Send> {
+        // Decode the source again for each loop, rather than keeping all of its samples. The next
+        // loop starts decoding when a loop starts playing, so that it's ready when the loop ends.
+        let source = self.clone();
+        let mut next = Some(StreamingDecoder::spawn(source.clone(), start_position));
+        let loops = core::iter::from_fn(move || {
+            let mut decoder = match next.take()? {
+                Ok(decoder) => decoder,
+                Err(error) => {
+                    warn!("Failed to decode streaming audio source: {error}");
+                    return None;
+                }
+            };
+            decoder.wait_for_first_chunk();
+            next = Some(StreamingDecoder::spawn(source.clone(), start_position));
+            Some(match duration {
+                Some(duration) => Box::new(decoder.take_duration(duration))
+                    as Box<dyn Source<Item = Sample> + Send>,
+                None => Box::new(decoder),
+            })
+        });
+        Box::new(rodio::source::from_iter(loops))
+    }
+}
+
+/// The [`rodio::Source`] of a [`StreamingAudioSource`].
+///
+/// Samples are decoded in chunks by a task on the [`IoTaskPool`](bevy_tasks::IoTaskPool), which
+/// stops when the decoder is dropped. The first chunk is decoded before playback starts, but as the
+/// decoder is played on the audio thread, it never waits for the following chunks: it plays silence
+/// until a chunk is decoded instead. On the web, chunks are decoded when they're needed.
+pub struct StreamingDecoder {
+    /// The decoded chunks, and whether each one is the last chunk of the source.
+    #[cfg(not(target_arch = "wasm32"))]
+    chunks: async_channel::Receiver<(Vec<Sample>, bool)>,
+    #[cfg(target_arch = "wasm32")]
+    decoder: rodio::Decoder<Cursor<StreamingAudioSource>>,
+    chunk: Vec<Sample>,
+    position: usize,
+    /// Whether [`Self::chunk`] is the last chunk of the source, so that its end isn't mistaken for
+    /// a chunk that hasn't been decoded yet.
+    last_chunk: bool,
+    /// The number of samples of silence left to play before the next chunk, so that silence is
+    /// always played in whole frames.
+    silence: usize,
+    channels: u16,
+    sample_rate: u32,
+    total_duration: Option<Duration>,
+}
+
+impl StreamingDecoder {
+    /// Starts decoding `source` from `start_position`, or from its start if it's `None`.
+    ///
+    /// This waits for the first chunk to be decoded, so that playback doesn't start with silence.
+    /// Returns an error if the format of the source isn't supported.
+    pub fn new(
+        source: StreamingAudioSource,
+        start_position: Option<Duration>,
+    ) -> Result<Self, DecoderError> {
+        let mut decoder = Self::spawn(source, start_position)?;
+        decoder.wait_for_first_chunk();
+        Ok(decoder)
+    }
+
+    /// Starts decoding `source` like [`Self::new`], without waiting for the first chunk.
+    fn spawn(
+        source: StreamingAudioSource,
+        start_position: Option<Duration>,
+    ) -> Result<Self, DecoderError> {
+        let mut decoder = rodio::Decoder::new(Cursor::new(source))?;
+        let channels = decoder.channels();
+        let sample_rate = decoder.sample_rate();
+        let total_duration = decoder.total_duration();
+
+        #[cfg(target_arch = "wasm32")]
+        if let Some(start_position) = start_position {
+            skip_to(&mut decoder, start_position);
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let chunks = {
+            let (sender, chunks) = async_channel::bounded(BUFFERED_CHUNKS);
+            bevy_tasks::IoTaskPool::get()
+                .spawn(async move {
+                    if let Some(start_position) = start_position {
+                        skip_to(&mut decoder, start_position);
+                    }
+                    let chunk_length = CHUNK_FRAMES * channels as usize;
+                    let mut samples = decoder.peekable();
+                    loop {
+                        let chunk: Vec<_> = samples.by_ref().take(chunk_length).collect();
+                        let last = samples.peek().is_none();
+                        // Sending fails once the decoder has been dropped.
+                        if chunk.is_empty() || sender.send((chunk, last)).await.is_err() || last {
+                            return;
+                        }
+                    }
+                })
+                .detach();
+            chunks
+        };
+
+        Ok(Self {
+            #[cfg(not(target_arch = "wasm32"))]
+            chunks,
+            #[cfg(target_arch = "wasm32")]
+            decoder,
+            chunk: Vec::new(),
+            position: 0,
+            last_chunk: false,
+            silence: 0,
+            channels,
+            sample_rate,
+            total_duration,
+        })
+    }
+
+    /// Waits until the first chunk of samples is decoded, if playback hasn't started yet.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn wait_for_first_chunk(&mut self) {
+        if self.chunk.is_empty() {
+            // If the source is empty, the channel is closed and the decoder ends when played.
+            if let Ok((chunk, last)) = self.chunks.recv_blocking() {
+                self.chunk = chunk;
+                self.position = 0;
+                self.last_chunk = last;
+            }
+        }
+    }
+
+    /// Chunks are decoded when they're needed on the web, so there is nothing to wait for.
+    #[cfg(target_arch = "wasm32")]
+    fn wait_for_first_chunk(&mut self) {}
+
+    /// Returns the next chunk of samples if it has been decoded.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn next_chunk(&mut self) -> NextChunk {
+        match self.chunks.try_recv() {
+            Ok((chunk, last)) => {
+                self.last_chunk = last;
+                NextChunk::Ready(chunk)
+            }
+            Err(async_channel::TryRecvError::Empty) => NextChunk::Pending,
+            // The channel is closed without sending a chunk if the source is empty.
+            Err(async_channel::TryRecvError::Closed) => NextChunk::End,
+        }
+    }
+
+    /// Returns the next chunk of samples.
+    #[cfg(target_arch = "wasm32")]
+    fn next_chunk(&mut self) -> NextChunk {
+        let chunk_length = CHUNK_FRAMES * self.channels as usize;
+        let chunk: Vec<_> = self.decoder.by_ref().take(chunk_length).collect();
+        if chunk.is_empty() {
+            NextChunk::End
+        } else {
+            NextChunk::Ready(chunk)
+        }
+    }
+}
+
+/// The result of [`StreamingDecoder::next_chunk`].
+enum NextChunk {
+    /// The chunk has been decoded.
+    Ready(Vec<Sample>),
+    /// The chunk is still being decoded.
+    #[cfg_attr(
+        target_arch = "wasm32",
+        expect(
+            dead_code,
+            reason = "chunks are decoded when they're needed on the web"
+        )
+    )]
+    Pending,
+    /// All the samples of the source have been played.
+    End,
+}
+
+/// Moves `decoder` to `position`, decoding and dropping samples if it can't seek.
+fn skip_to(decoder: &mut rodio::Decoder<Cursor<StreamingAudioSource>>, position: Duration) {
+    if decoder.try_seek(position).is_ok() {
+        return;
+    }
+    let frames = (position.as_secs_f64() * decoder.sample_rate() as f64) as usize;
+    let samples = frames * decoder.channels() as usize;
+    decoder.by_ref().take(samples).for_each(drop);
+}
+
+impl Iterator for StreamingDecoder {
+    type Item = Sample;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.silence > 0 {
+            self.silence -= 1;
+            return Some(Sample::default());
+        }
+        if self.position == self.chunk.len() {
+            if self.last_chunk {
+                return None;
+            }
+            match self.next_chunk() {
+                NextChunk::Ready(chunk) => {
+                    self.chunk = chunk;
+                    self.position = 0;
+                }
+                NextChunk::Pending => {
+                    self.silence = self.channels as usize - 1;
+                    return Some(Sample::default());
+                }
+                NextChunk::End => return None,
+            }
+        }
+        let sample = self.chunk[self.position];
+        self.position += 1;
+        Some(sample)
+    }
+}
+
+impl Source for StreamingDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.total_duration
+    }
+}
+
+#[cfg(all(test, feature = "wav"))]
+mod tests {
+    use super::{StreamingAudioSource, CHUNK_FRAMES};
+    use crate::Decodable;
+    use bevy_tasks::{IoTaskPool, TaskPool};
+    use core::time::Duration;
+
+    /// The length of the test source, which spans several chunks.
+    const LENGTH: usize = 3 * CHUNK_FRAMES + 100;
+
+    /// A mono 16-bit WAV file whose samples count up from 1, so that they're never silent.
+    fn counting_source() -> StreamingAudioSource {
+        let data_size = (LENGTH * 2) as u32;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_size).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        // PCM, 1 channel, 44100 Hz, 88200 bytes per second, 2 bytes per frame, 16 bits per sample.
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&44100u32.to_le_bytes());
+        bytes.extend_from_slice(&88200u32.to_le_bytes());
+        bytes.extend_from_slice(&2u16.to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_size.to_le_bytes());
+        for sample in 1..=LENGTH as i16 {
+            bytes.extend_from_slice(&sample.to_le_bytes());
+        }
+        StreamingAudioSource {
+            bytes: bytes.into(),
+        }
+    }
+
+    /// Collects `count` samples, dropping the silence played while later chunks are decoded.
+    fn collect_samples(samples: impl Iterator<Item = i16>, count: usize) -> Vec<i16> {
+        samples.filter(|&sample| sample != 0).take(count).collect()
+    }
+
+    /// The samples of the source from `start` to `end`, inclusive.
+    fn counting(start: usize, end: usize) -> Vec<i16> {
+        (start as i16..=end as i16).collect()
+    }
+
+    #[test]
+    fn plays_chunks_in_order_until_the_end() {
+        IoTaskPool::get_or_init(TaskPool::default);
+        let mut decoder = counting_source().decoder();
+
+        // Playback doesn't start with silence, as the first chunk is decoded ahead of time.
+        let first_chunk: Vec<_> = decoder.by_ref().take(CHUNK_FRAMES).collect();
+        assert_eq!(first_chunk, counting(1, CHUNK_FRAMES));
+        let samples = collect_samples(decoder.by_ref(), LENGTH - CHUNK_FRAMES);
+        assert_eq!(samples, counting(CHUNK_FRAMES + 1, LENGTH));
+        // The end of the source isn't mistaken for a chunk that hasn't been decoded yet.
+        assert_eq!(decoder.next(), None);
+        assert_eq!(decoder.next(), None);
+    }
+
+    #[test]
+    fn loops_from_the_start() {
+        IoTaskPool::get_or_init(TaskPool::default);
+        let mut decoder = counting_source().looping_decoder(None, None);
+
+        for _ in 0..2 {
+            // Each loop starts right away, without silence.
+            let first_chunk: Vec<_> = decoder.by_ref().take(CHUNK_FRAMES).collect();
+            assert_eq!(first_chunk, counting(1, CHUNK_FRAMES));
+            let samples = collect_samples(decoder.by_ref(), LENGTH - CHUNK_FRAMES);
+            assert_eq!(samples, counting(CHUNK_FRAMES + 1, LENGTH));
+        }
+    }
+
+    #[test]
+    fn seeks_without_silence() {
+        IoTaskPool::get_or_init(TaskPool::default);
+        let start_position = Duration::from_secs(1) * CHUNK_FRAMES as u32 / 44100;
+        let mut decoder = counting_source().looping_decoder(Some(start_position), None);
+
+        for _ in 0..2 {
+            let first_chunk: Vec<_> = decoder.by_ref().take(CHUNK_FRAMES).collect();
+            assert!(first_chunk[0] > 1);
+            assert!(first_chunk.windows(2).all(|pair| pair[1] == pair[0] + 1));
+            // Skip to the end of the loop.
+            collect_samples(
+                decoder.by_ref(),
+                LENGTH - first_chunk[0] as usize + 1 - CHUNK_FRAMES,
+            );
+        }
+    }
+}
@@ -18,9 +18,8 @@ use bevy_app::{App, Plugin, RunFixedMainLoop, RunFixedMainLoopSystems};
 use bevy_camera::Camera;
 use bevy_ecs::prelude::*;
 use bevy_input::keyboard::KeyCode;
-use bevy_input::mouse::{
-    AccumulatedMouseMotion, AccumulatedMouseScroll, MouseButton, MouseScrollUnit,
-};
+use bevy_input::mouse::{AccumulatedMouseScroll, MouseButton, MouseScrollUnit};
+use bevy_input::mouse_processing::ProcessedMouseMotion;
 use bevy_input::ButtonInput;
 use bevy_log::info;
 use bevy_math::{EulerRot, Quat, StableInterpolate, Vec2, Vec3};
@@ -70,6 +69,9 @@ const RADIANS_PER_DOT: f32 = 1.0 / 180.0;
 #[require(FreeCameraState)]
 pub struct FreeCamera {
     /// Multiplier for pitch and yaw rotation speed.
+    ///
+    /// This applies on top of the sensitivity of the
+    /// [`MouseMotionSettings`](bevy_input::mouse_processing::MouseMotionSettings).
     pub sensitivity: f32,
     /// [`KeyCode`] for forward translation.
     pub key_forward: KeyCode,
@@ -194,7 +196,7 @@ impl Default for FreeCameraState {
 pub fn run_freecamera_controller(
     time: Res<Time<Real>>,
     mut windows: Query<(&Window, &mut CursorOptions)>,
-    accumulated_mouse_motion: Res<AccumulatedMouseMotion>,
+    mouse_motion: Res<ProcessedMouseMotion>,
     accumulated_mouse_scroll: Res<AccumulatedMouseScroll>,
     mouse_button_input: Res<ButtonInput<MouseButton>>,
     key_input: Res<ButtonInput<KeyCode>>,
@@ -324,12 +326,11 @@ pub fn run_freecamera_controller(
     }
 
     // Handle mouse input
-    if accumulated_mouse_motion.delta != Vec2::ZERO && cursor_grab {
+    if mouse_motion.delta != Vec2::ZERO && cursor_grab {
         // Apply look update
-        state.pitch = (state.pitch
-            - accumulated_mouse_motion.delta.y * RADIANS_PER_DOT * config.sensitivity)
+        state.pitch = (state.pitch - mouse_motion.delta.y * RADIANS_PER_DOT * config.sensitivity)
             .clamp(-PI / 2., PI / 2.);
-        state.yaw -= accumulated_mouse_motion.delta.x * RADIANS_PER_DOT * config.sensitivity;
+        state.yaw -= mouse_motion.delta.x * RADIANS_PER_DOT * config.sensitivity;
         transform.rotation = Quat::from_euler(EulerRot::ZYX, 0.0, state.yaw, state.pitch);
     }
 }
@@ -13,7 +13,7 @@ use bevy_render::{
     },
     renderer::RenderContext,
     texture::{FallbackImage, GpuImage},
-    view::{ViewTarget, ViewUniformOffset, ViewUniforms},
+    view::{screenshot::copy_hdr_screenshot, ViewTarget, ViewUniformOffset, ViewUniforms},
 };
 
 use super::{get_lut_bindings, Tonemapping};
@@ -34,7 +34,7 @@ impl ViewNode for TonemappingNode {
 
     fn run(
         &self,
-        _graph: &mut RenderGraphContext,
+        graph: &mut RenderGraphContext,
         render_context: &mut RenderContext,
         (view_uniform_offset, target, view_tonemapping_pipeline, tonemapping): QueryItem<
             Self::ViewQuery,
@@ -49,6 +49,14 @@ impl ViewNode for TonemappingNode {
         let view_uniforms = &view_uniforms_resource.uniforms;
         let view_uniforms_id = view_uniforms.buffer().unwrap().id();
 
+        // Capture the color buffer before it is tonemapped.
+        copy_hdr_screenshot(
+            world,
+            graph.view_entity(),
+            target,
+            render_context.command_encoder(),
+        );
+
         if *tonemapping == Tonemapping::None {
             return Ok(());
         }
@@ -0,0 +1,672 @@
+//! Recording of [`Message`]s and deterministic replay into another [`App`].
+//!
+//! Add the [`EventRecorderPlugin`] to an app to capture every message of the types registered with
+//! [`RecordMessageAppExt::record_message`], along with the index of the frame it was read on.
+//! Keyboard, mouse, touch, pen and gamepad input is recorded by default. The resulting
+//! [`EventRecording`] can be saved as [`ron`] and later fed to the [`EventReplayPlugin`] of a
+//! fresh [`App`], which writes each message back on the same frame.
+//!
+//! This is useful to attach reproducible input to bug reports, or to drive integration tests
+//! from real gameplay sessions: a recording saved to an `.events.ron` file can be loaded as an
+//! asset with [`EventReplayPlugin::load`], and replayed into a headless app whose
+//! [`ButtonInput`](bevy_input::ButtonInput), [`Touches`](bevy_input::touch::Touches) and
+//! [`Gamepad`](bevy_input::gamepad::Gamepad) states then evolve as they did in the recorded
+//! session. Replays are only deterministic if the app itself is: consider configuring a fixed
+//! [`TimeUpdateStrategy`](bevy_time::TimeUpdateStrategy) in both apps.
+//!
+//! ```
+//! # use bevy_app::prelude::*;
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_reflect::Reflect;
+//! # use bevy_dev_tools::event_recording::*;
+//! #[derive(Message, Reflect, Clone, Debug, PartialEq)]
+//! struct Jump(f32);
+//!
+//! let mut app = App::new();
+//! app.add_plugins(EventRecorderPlugin).record_message::<Jump>();
+//! app.world_mut().write_message(Jump(2.0));
+//! app.update();
+//!
+//! let registry = app.world().resource::<AppTypeRegistry>().read();
+//! let saved = app.world().resource::<EventRecording>().to_ron(&registry).unwrap();
+//! let recording = EventRecording::from_ron(&saved, &registry).unwrap();
+//! # drop(registry);
+//!
+//! let mut replay = App::new();
+//! replay
+//!     .add_plugins(EventReplayPlugin::new(recording))
+//!     .record_message::<Jump>();
+//! replay.update();
+//! ```
+
+use core::fmt;
+
+use bevy_app::prelude::*;
+use bevy_asset::{
+    io::Reader, Asset, AssetApp, AssetLoader, AssetPath, AssetServer, Assets, Handle, LoadContext,
+};
+use bevy_ecs::{
+    message::{Message, MessageUpdateSystems},
+    prelude::*,
+};
+use bevy_input::{
+    gamepad::{GamepadConnectionEvent, RawGamepadEvent},
+    keyboard::KeyboardInput,
+    mouse::{MouseButtonInput, MouseMotion, MouseWheel},
+    pen::PenInput,
+    touch::TouchInput,
+};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{
+    serde::{ReflectDeserializer, ReflectSerializer},
+    FromReflect, GetTypeRegistration, PartialReflect, TypeInfo, TypePath, TypeRegistry,
+    TypeRegistryArc,
+};
+use ron::error::SpannedError;
+use serde::{
+    de::{DeserializeSeed, SeqAccess, Visitor},
+    ser::SerializeSeq,
+    Deserializer, Serialize, Serializer,
+};
+use thiserror::Error;
+use tracing::warn;
+
+/// Records the registered [`Message`] types into the [`EventRecording`] resource.
+///
+/// Keyboard, mouse, touch, pen and gamepad input messages are registered by default; register
+/// additional types with [`RecordMessageAppExt::record_message`].
+#[derive(Default)]
+pub struct EventRecorderPlugin;
+
+impl Plugin for EventRecorderPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EventRecording>()
+            .init_resource::<RecordingFrame>()
+            .add_systems(
+                Last,
+                advance_recording_frame.after(EventRecordingSystems::Record),
+            );
+        register_input_messages(app);
+    }
+}
+
+/// Writes the messages of an [`EventRecording`] back into the app, on the frame they were
+/// recorded on.
+///
+/// Every message type in the recording must be registered with
+/// [`RecordMessageAppExt::record_message`] in the replaying app.
+///
+/// Gamepads are replayed on new entities, spawned when the recording first mentions them. Other
+/// entities referenced by the messages, such as the window receiving keyboard input, are replayed
+/// as is.
+pub struct EventReplayPlugin {
+    source: ReplaySource,
+}
+
+enum ReplaySource {
+    Recording(EventRecording),
+    Asset(AssetPath<'static>),
+}
+
+impl EventReplayPlugin {
+    /// Creates a plugin replaying the given `recording`.
+    pub fn new(recording: EventRecording) -> Self {
+        Self {
+            source: ReplaySource::Recording(recording),
+        }
+    }
+
+    /// Creates a plugin loading the recording at `path` with the [`AssetServer`], and replaying it
+    /// once it's loaded.
+    ///
+    /// The first recorded frame is replayed on the frame the recording finishes loading. This
+    /// registers the [`EventRecording`] asset and its [`EventRecordingLoader`], so the
+    /// [`AssetPlugin`](bevy_asset::AssetPlugin) must be added before this plugin.
+    pub fn load(path: impl Into<AssetPath<'static>>) -> Self {
+        Self {
+            source: ReplaySource::Asset(path.into()),
+        }
+    }
+}
+
+impl Plugin for EventReplayPlugin {
+    fn build(&self, app: &mut App) {
+        let (recording, handle) = match &self.source {
+            ReplaySource::Recording(recording) => (Some(recording.clone()), None),
+            ReplaySource::Asset(path) => {
+                if !app.world().contains_resource::<Assets<EventRecording>>() {
+                    app.init_asset::<EventRecording>()
+                        .init_asset_loader::<EventRecordingLoader>();
+                }
+                let handle = app.world().resource::<AssetServer>().load(path.clone());
+                (None, Some(handle))
+            }
+        };
+        app.insert_resource(EventReplay {
+            recording,
+            handle,
+            next: 0,
+            frame: 0,
+        })
+        .init_resource::<ReplayedGamepads>()
+        .add_systems(
+            First,
+            replay_messages
+                .after(MessageUpdateSystems)
+                .in_set(EventRecordingSystems::Replay),
+        );
+        register_input_messages(app);
+    }
+}
+
+/// System sets used by the [`EventRecorderPlugin`] and [`EventReplayPlugin`].
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EventRecordingSystems {
+    /// Reads messages into the [`EventRecording`], in [`Last`].
+    Record,
+    /// Writes the replayed messages, in [`First`].
+    Replay,
+}
+
+/// Extension trait registering [`Message`] types for recording and replay.
+pub trait RecordMessageAppExt {
+    /// Records messages of type `M` when the [`EventRecorderPlugin`] is added, and allows
+    /// replaying them with the [`EventReplayPlugin`].
+    ///
+    /// This also registers `M` in the [`AppTypeRegistry`], which is used to (de)serialize recordings.
+    fn record_message<M>(&mut self) -> &mut Self
+    where
+        M: Message + FromReflect + TypePath + GetTypeRegistration;
+}
+
+impl RecordMessageAppExt for App {
+    fn record_message<M>(&mut self) -> &mut Self
+    where
+        M: Message + FromReflect + TypePath + GetTypeRegistration,
+    {
+        register_message::<M>(self, write_reflected::<M>)
+    }
+}
+
+/// Registers `M` for recording, replaying its messages with `write`.
+fn register_message<M>(app: &mut App, write: MessageWriterFn) -> &mut App
+where
+    M: Message + FromReflect + TypePath + GetTypeRegistration,
+{
+    let already_registered = app
+        .world_mut()
+        .get_resource_or_init::<ReplayableMessages>()
+        .writers
+        .insert(M::type_path(), write)
+        .is_some();
+    if already_registered {
+        return app;
+    }
+
+    app.add_message::<M>().register_type::<M>().add_systems(
+        Last,
+        record_messages::<M>
+            .run_if(resource_exists::<EventRecording>)
+            .in_set(EventRecordingSystems::Record),
+    )
+}
+
+fn register_input_messages(app: &mut App) {
+    app.record_message::<KeyboardInput>()
+        .record_message::<MouseButtonInput>()
+        .record_message::<MouseMotion>()
+        .record_message::<MouseWheel>()
+        .record_message::<TouchInput>()
+        .record_message::<PenInput>();
+    register_message::<GamepadConnectionEvent>(app, write_gamepad_connection);
+    register_message::<RawGamepadEvent>(app, write_raw_gamepad_event);
+}
+
+/// A message captured by the [`EventRecorderPlugin`].
+#[derive(Debug)]
+pub struct RecordedMessage {
+    /// The index of the frame the message was read on, starting at `0`.
+    pub frame: u32,
+    /// The reflected message.
+    pub message: Box<dyn PartialReflect>,
+}
+
+impl Clone for RecordedMessage {
+    fn clone(&self) -> Self {
+        Self {
+            frame: self.frame,
+            message: self.message.to_dynamic(),
+        }
+    }
+}
+
+/// The messages captured by the [`EventRecorderPlugin`], in the order they were read.
+///
+/// Recordings saved with [`EventRecording::to_ron`] can be loaded as assets by the
+/// [`EventRecordingLoader`].
+#[derive(Resource, Asset, TypePath, Default, Debug, Clone)]
+pub struct EventRecording {
+    /// The recorded messages, sorted by frame.
+    pub messages: Vec<RecordedMessage>,
+}
+
+impl EventRecording {
+    /// Serializes the recording to a [`ron`] string.
+    ///
+    /// Every recorded message type must be registered in the `registry`.
+    pub fn to_ron(&self, registry: &TypeRegistry) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(
+            &RecordingSerializer {
+                recording: self,
+                registry,
+            },
+            ron::ser::PrettyConfig::default(),
+        )
+    }
+
+    /// Deserializes a recording from a [`ron`] string created by [`EventRecording::to_ron`].
+    ///
+    /// Every recorded message type must be registered in the `registry`.
+    pub fn from_ron(ron: &str, registry: &TypeRegistry) -> Result<Self, ron::Error> {
+        let mut deserializer = ron::de::Deserializer::from_str(ron)?;
+        let messages = RecordingDeserializer { registry }.deserialize(&mut deserializer)?;
+        deserializer.end()?;
+        Ok(Self { messages })
+    }
+}
+
+/// Asset loader for [`EventRecording`]s saved with [`EventRecording::to_ron`] (`.events.ron`).
+///
+/// Every recorded message type must be registered in the [`AppTypeRegistry`] when the loader is
+/// created.
+#[derive(Debug)]
+pub struct EventRecordingLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for EventRecordingLoader {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            type_registry: world.resource::<AppTypeRegistry>().0.clone(),
+        }
+    }
+}
+
+/// Possible errors that can be produced by the [`EventRecordingLoader`].
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum EventRecordingLoaderError {
+    /// An [IO Error](std::io::Error).
+    #[error("Error while trying to read the recording file: {0}")]
+    Io(#[from] std::io::Error),
+    /// A [RON Error](ron::error::SpannedError).
+    #[error("Could not parse RON: {0}")]
+    RonSpannedError(#[from] SpannedError),
+}
+
+impl AssetLoader for EventRecordingLoader {
+    type Asset = EventRecording;
+    type Settings = ();
+    type Error = EventRecordingLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+        let messages = RecordingDeserializer {
+            registry: &self.type_registry.read(),
+        }
+        .deserialize(&mut deserializer)
+        .map_err(|e| deserializer.span_error(e))?;
+        deserializer.end().map_err(|e| deserializer.span_error(e))?;
+        Ok(EventRecording { messages })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["events.ron"]
+    }
+}
+
+struct RecordingSerializer<'a> {
+    recording: &'a EventRecording,
+    registry: &'a TypeRegistry,
+}
+
+impl Serialize for RecordingSerializer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.recording.messages.len()))?;
+        for recorded in &self.recording.messages {
+            seq.serialize_element(&(
+                recorded.frame,
+                ReflectSerializer::new(&*recorded.message, self.registry),
+            ))?;
+        }
+        seq.end()
+    }
+}
+
+struct RecordingDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'de> DeserializeSeed<'de> for RecordingDeserializer<'_> {
+    type Value = Vec<RecordedMessage>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for RecordingDeserializer<'_> {
+    type Value = Vec<RecordedMessage>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of recorded messages")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut messages = Vec::new();
+        while let Some(recorded) = seq.next_element_seed(RecordedMessageDeserializer {
+            registry: self.registry,
+        })? {
+            messages.push(recorded);
+        }
+        Ok(messages)
+    }
+}
+
+struct RecordedMessageDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'de> DeserializeSeed<'de> for RecordedMessageDeserializer<'_> {
+    type Value = RecordedMessage;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_tuple(2, self)
+    }
+}
+
+impl<'de> Visitor<'de> for RecordedMessageDeserializer<'_> {
+    type Value = RecordedMessage;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a `(frame, message)` tuple")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        use serde::de::Error;
+
+        let frame = seq
+            .next_element::<u32>()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
+        let message = seq
+            .next_element_seed(ReflectDeserializer::new(self.registry))?
+            .ok_or_else(|| Error::invalid_length(1, &self))?;
+        Ok(RecordedMessage { frame, message })
+    }
+}
+
+/// The index of the frame currently being recorded.
+#[derive(Resource, Default)]
+struct RecordingFrame(u32);
+
+/// The recording being replayed by the [`EventReplayPlugin`].
+#[derive(Resource)]
+struct EventReplay {
+    /// The replayed recording, or `None` while it's loading.
+    recording: Option<EventRecording>,
+    handle: Option<Handle<EventRecording>>,
+    /// The index of the next message to replay.
+    next: usize,
+    frame: u32,
+}
+
+/// The gamepad entities spawned by the [`EventReplayPlugin`], keyed by the recorded entity.
+#[derive(Resource, Default)]
+struct ReplayedGamepads(HashMap<Entity, Entity>);
+
+/// Writes a reflected message into the world, returning `false` if it has the wrong type.
+type MessageWriterFn = fn(&mut World, &dyn PartialReflect) -> bool;
+
+/// Functions writing a reflected message into the world, keyed by message type path.
+#[derive(Resource, Default)]
+struct ReplayableMessages {
+    writers: HashMap<&'static str, MessageWriterFn>,
+}
+
+fn write_reflected<M: Message + FromReflect>(
+    world: &mut World,
+    message: &dyn PartialReflect,
+) -> bool {
+    let Some(message) = M::from_reflect(message) else {
+        return false;
+    };
+    world.write_message(message);
+    true
+}
+
+fn write_gamepad_connection(world: &mut World, message: &dyn PartialReflect) -> bool {
+    let Some(mut event) = GamepadConnectionEvent::from_reflect(message) else {
+        return false;
+    };
+    event.gamepad = replayed_gamepad(world, event.gamepad);
+    world.write_message(event);
+    true
+}
+
+fn write_raw_gamepad_event(world: &mut World, message: &dyn PartialReflect) -> bool {
+    let Some(mut event) = RawGamepadEvent::from_reflect(message) else {
+        return false;
+    };
+    let gamepad = match &mut event {
+        RawGamepadEvent::Connection(event) => &mut event.gamepad,
+        RawGamepadEvent::Button(event) => &mut event.gamepad,
+        RawGamepadEvent::Axis(event) => &mut event.gamepad,
+    };
+    *gamepad = replayed_gamepad(world, *gamepad);
+    world.write_message(event);
+    true
+}
+
+/// Returns the entity replaying the `recorded` gamepad, spawning it the first time.
+fn replayed_gamepad(world: &mut World, recorded: Entity) -> Entity {
+    if let Some(&replayed) = world
+        .get_resource_or_init::<ReplayedGamepads>()
+        .0
+        .get(&recorded)
+    {
+        return replayed;
+    }
+    let replayed = world.spawn_empty().id();
+    world
+        .resource_mut::<ReplayedGamepads>()
+        .0
+        .insert(recorded, replayed);
+    replayed
+}
+
+fn record_messages<M: Message + FromReflect>(
+    mut reader: MessageReader<M>,
+    frame: Res<RecordingFrame>,
+    mut recording: ResMut<EventRecording>,
+) {
+    for message in reader.read() {
+        recording.messages.push(RecordedMessage {
+            frame: frame.0,
+            message: message.to_dynamic(),
+        });
+    }
+}
+
+fn advance_recording_frame(mut frame: ResMut<RecordingFrame>) {
+    frame.0 += 1;
+}
+
+fn replay_messages(world: &mut World) {
+    world.resource_scope(|world, mut replay: Mut<EventReplay>| {
+        let replay = &mut *replay;
+        if replay.recording.is_none() {
+            replay.recording = replay.handle.as_ref().and_then(|handle| {
+                world
+                    .get_resource::<Assets<EventRecording>>()?
+                    .get(handle)
+                    .cloned()
+            });
+        }
+        let Some(recording) = &replay.recording else {
+            return;
+        };
+        world.resource_scope(|world, writers: Mut<ReplayableMessages>| {
+            while let Some(recorded) = recording.messages.get(replay.next) {
+                if recorded.frame > replay.frame {
+                    break;
+                }
+                replay.next += 1;
+
+                let type_path = recorded
+                    .message
+                    .get_represented_type_info()
+                    .map(TypeInfo::type_path)
+                    .unwrap_or_else(|| recorded.message.reflect_type_path());
+                let written = writers
+                    .writers
+                    .get(type_path)
+                    .is_some_and(|write| write(world, &*recorded.message));
+                if !written {
+                    warn!("Unable to replay message of type `{type_path}`: it was not registered with `record_message`");
+                }
+            }
+        });
+        replay.frame += 1;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Message, bevy_reflect::Reflect, Clone, Debug, PartialEq)]
+    struct Jump(f32);
+
+    #[derive(Resource, Default)]
+    struct Jumps(Vec<(u32, f32)>);
+
+    fn count_jumps(
+        mut frame: Local<u32>,
+        mut reader: MessageReader<Jump>,
+        mut jumps: ResMut<Jumps>,
+    ) {
+        for jump in reader.read() {
+            jumps.0.push((*frame, jump.0));
+        }
+        *frame += 1;
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let mut app = App::new();
+        app.add_plugins(EventRecorderPlugin)
+            .record_message::<Jump>();
+        app.update();
+        app.world_mut().write_message(Jump(1.0));
+        app.update();
+        app.update();
+        app.world_mut().write_message(Jump(2.0));
+        app.world_mut().write_message(Jump(3.0));
+        app.update();
+
+        let saved = {
+            let registry = app.world().resource::<AppTypeRegistry>().read();
+            app.world()
+                .resource::<EventRecording>()
+                .to_ron(&registry)
+                .unwrap()
+        };
+
+        let mut replay = App::new();
+        replay
+            .init_resource::<Jumps>()
+            .record_message::<Jump>()
+            .add_systems(Update, count_jumps);
+        let recording = {
+            let registry = replay.world().resource::<AppTypeRegistry>().read();
+            EventRecording::from_ron(&saved, &registry).unwrap()
+        };
+        assert_eq!(recording.messages.len(), 3);
+        replay.add_plugins(EventReplayPlugin::new(recording));
+        for _ in 0..4 {
+            replay.update();
+        }
+
+        assert_eq!(
+            replay.world().resource::<Jumps>().0,
+            [(1, 1.0), (3, 2.0), (3, 3.0)]
+        );
+    }
+
+    #[test]
+    fn replay_input_into_headless_app() {
+        use bevy_input::{
+            gamepad::{
+                Gamepad, GamepadButton, GamepadConnection, GamepadConnectionEvent,
+                RawGamepadButtonChangedEvent,
+            },
+            keyboard::{Key, KeyCode},
+            ButtonInput, ButtonState, InputPlugin,
+        };
+
+        let mut app = App::new();
+        app.add_plugins((InputPlugin, EventRecorderPlugin));
+        let window = app.world_mut().spawn_empty().id();
+        let gamepad = app.world_mut().spawn_empty().id();
+        app.world_mut().write_message(KeyboardInput {
+            key_code: KeyCode::Space,
+            logical_key: Key::Space,
+            state: ButtonState::Pressed,
+            text: None,
+            repeat: false,
+            window,
+        });
+        // Gamepad backends send both messages when a gamepad connects
+        let connection = GamepadConnectionEvent::new(
+            gamepad,
+            GamepadConnection::Connected {
+                name: "Recorded gamepad".into(),
+                vendor_id: None,
+                product_id: None,
+            },
+        );
+        app.world_mut()
+            .write_message(RawGamepadEvent::Connection(connection.clone()));
+        app.world_mut().write_message(connection);
+        app.update();
+        app.world_mut()
+            .write_message(RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(
+                gamepad,
+                GamepadButton::South,
+                1.0,
+            )));
+        app.update();
+        let recording = app.world().resource::<EventRecording>().clone();
+        assert_eq!(recording.messages.len(), 4);
+
+        let mut replay = App::new();
+        replay.add_plugins((InputPlugin, EventReplayPlugin::new(recording)));
+        replay.update();
+        assert!(replay
+            .world()
+            .resource::<ButtonInput<KeyCode>>()
+            .pressed(KeyCode::Space));
+        replay.update();
+        let mut gamepads = replay.world_mut().query::<(Entity, &Gamepad)>();
+        let (replayed, replayed_gamepad) = gamepads.single(replay.world()).unwrap();
+        assert_ne!(replayed, gamepad);
+        assert!(replayed_gamepad.pressed(GamepadButton::South));
+    }
+}
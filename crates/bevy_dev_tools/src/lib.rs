@@ -11,6 +11,9 @@
 #[cfg(feature = "bevy_ci_testing")]
 pub mod ci_testing;
 
+#[cfg(feature = "bevy_event_recording")]
+pub mod event_recording;
+
 mod easy_screenshot;
 pub mod fps_overlay;
 pub mod frame_time_graph;
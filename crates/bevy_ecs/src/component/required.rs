@@ -462,6 +462,60 @@ impl Components {
         Ok(())
     }
 
+    /// Registers the given component `R` as required by `T`, replacing the constructor of `R` if it
+    /// is already directly required by `T`.
+    ///
+    /// # Safety
+    ///
+    /// - the given component IDs `required` and `requiree` must be valid in `self`;
+    /// - the given component ID `required` must be valid for the component type `R`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RequiredComponentsError`] if the `requiree` component is already a (possibly indirect)
+    /// required component for the `required` component.
+    pub(crate) unsafe fn override_required_components<R: Component>(
+        &mut self,
+        requiree: ComponentId,
+        required: ComponentId,
+        constructor: fn() -> R,
+    ) -> Result<(), RequiredComponentsError> {
+        // SAFETY: The caller ensures that the `requiree` is valid.
+        let required_components = unsafe {
+            self.get_required_components_mut(requiree)
+                .debug_checked_unwrap()
+        };
+
+        let Some(required_component) = required_components.direct.get_mut(&required) else {
+            // SAFETY: The caller's guarantees are the same.
+            return unsafe { self.register_required_components(requiree, required, constructor) };
+        };
+
+        // Replacing the constructor keeps the set of required components unchanged, so only the
+        // constructors stored in `all` need to be updated, for `requiree` and every component requiring it.
+        // SAFETY: the caller guarantees that `required` is valid for type `R` in `self`
+        required_component.constructor =
+            unsafe { RequiredComponentConstructor::new(required, constructor) };
+
+        // SAFETY: The caller ensures that the `requiree` is valid.
+        let requiree_required_by = unsafe { self.get_required_by(requiree).debug_checked_unwrap() };
+        let requirees = [requiree]
+            .into_iter()
+            .chain(requiree_required_by.iter().copied())
+            .collect::<Vec<_>>();
+
+        // `required_by` is ordered such that components are rebuilt after the components they depend on.
+        for indirect_requiree in requirees {
+            // SAFETY: `indirect_requiree` comes from `self` so it must be valid.
+            self.required_components_scope(indirect_requiree, |this, required_components| {
+                // SAFETY: `required_components` comes from `self`, so all its components must have be valid in `self`.
+                unsafe { required_components.rebuild_inherited_required_components(this) };
+            });
+        }
+
+        Ok(())
+    }
+
     /// Temporarily take out the [`RequiredComponents`] of the component with id `component_id`
     /// and runs the given closure with mutable access to `self` and the given [`RequiredComponents`].
     ///
@@ -1294,6 +1348,41 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn runtime_required_components_override_existing() {
+        #[derive(Component, Default)]
+        #[require(Y(1))]
+        struct X;
+
+        #[derive(Component, Default, Debug, PartialEq, Eq)]
+        struct Y(u32);
+
+        #[derive(Component)]
+        #[require(X)]
+        struct Z;
+
+        #[derive(Component, Default, Debug, PartialEq, Eq)]
+        struct W;
+
+        let mut world = World::new();
+        world.override_required_components_with::<X, Y>(|| Y(2));
+        // Overriding a requirement that doesn't exist yet registers it.
+        world.override_required_components_with::<X, W>(|| W);
+        assert!(matches!(
+            world.try_override_required_components_with::<Y, Z>(|| Z),
+            Err(RequiredComponentsError::CyclicRequirement(_, _))
+        ));
+
+        let x = world.spawn(X).id();
+        assert_eq!(world.entity(x).get::<Y>(), Some(&Y(2)));
+        assert!(world.entity(x).contains::<W>());
+
+        // The override is also used when `X` is itself required.
+        let z = world.spawn(Z).id();
+        assert_eq!(world.entity(z).get::<Y>(), Some(&Y(2)));
+        assert!(world.entity(z).contains::<W>());
+    }
+
     #[test]
     fn required_components_bundle_priority() {
         #[derive(Component, PartialEq, Eq, Clone, Copy, Debug)]
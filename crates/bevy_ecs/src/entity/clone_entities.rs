@@ -4,11 +4,12 @@ use crate::{
     change_detection::MaybeLocation,
     component::{Component, ComponentCloneBehavior, ComponentCloneFn, ComponentId, ComponentInfo},
     entity::{hash_map::EntityHashMap, Entity, EntityAllocator, EntityMapper},
+    hierarchy::Children,
     query::DebugCheckedUnwrap,
     relationship::RelationshipHookMode,
     world::World,
 };
-use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
+use alloc::{boxed::Box, collections::VecDeque, vec, vec::Vec};
 use bevy_platform::collections::{hash_map::Entry, HashMap, HashSet};
 use bevy_ptr::{Ptr, PtrMut};
 use bevy_utils::prelude::DebugName;
@@ -497,6 +498,69 @@ impl EntityCloner {
         target
     }
 
+    /// Clones `source` into `target` and all of the descendants of `source` into newly spawned
+    /// children of `target`, using the stored configuration.
+    ///
+    /// Every entity of the hierarchy is spawned before any component is cloned, so entity references
+    /// between members of the hierarchy (such as targets or joints) point to their clones, regardless
+    /// of the order in which entities are cloned. This applies to components implementing
+    /// [`MapEntities`](crate::entity::MapEntities) and, if an [`AppTypeRegistry`](crate::reflect::AppTypeRegistry)
+    /// exists, to every [`Entity`] field of mutable components registered with
+    /// [`ReflectComponent`](crate::reflect::ReflectComponent). References to entities outside of the
+    /// hierarchy are left untouched, which means `target` receives the parent of `source`.
+    ///
+    /// The hierarchy is defined by [`Children`], and [`EntityCloner::linked_cloning`] is ignored.
+    ///
+    /// ```
+    /// # use bevy_ecs::{prelude::*, entity::EntityCloner};
+    /// #[derive(Component, Clone)]
+    /// struct LookAt(#[entities] Entity);
+    ///
+    /// let mut world = World::new();
+    /// let root = world.spawn_empty().id();
+    /// let eye = world.spawn(ChildOf(root)).id();
+    /// world.spawn((ChildOf(root), LookAt(eye)));
+    ///
+    /// let clone = world.spawn_empty().id();
+    /// EntityCloner::default().clone_hierarchy(&mut world, root, clone);
+    /// let children = world.get::<Children>(clone).unwrap();
+    /// let (eye_clone, looker_clone) = (children[0], children[1]);
+    /// assert_eq!(world.get::<LookAt>(looker_clone).unwrap().0, eye_clone);
+    /// ```
+    #[track_caller]
+    pub fn clone_hierarchy(&mut self, world: &mut World, source: Entity, target: Entity) {
+        let mut sources = vec![source];
+        let mut index = 0;
+        while let Some(&entity) = sources.get(index) {
+            if let Some(children) = world.get::<Children>(entity) {
+                sources.extend(children.iter());
+            }
+            index += 1;
+        }
+
+        let mut mapper = EntityHashMap::<Entity>::new();
+        mapper.set_mapped(source, target);
+        for &entity in &sources[1..] {
+            mapper.set_mapped(entity, world.spawn_empty().id());
+        }
+
+        // Descendants are cloned explicitly, and must not be spawned a second time.
+        let linked_cloning = core::mem::replace(&mut self.state.linked_cloning, false);
+        for &entity in &sources {
+            self.clone_entity_mapped(world, entity, &mut mapper);
+        }
+        self.state.linked_cloning = linked_cloning;
+
+        #[cfg(feature = "bevy_reflect")]
+        {
+            let targets: Vec<Entity> = sources
+                .iter()
+                .map(|&entity| mapper.get_mapped(entity))
+                .collect();
+            map_reflected_component_entities(world, &targets, &mut mapper);
+        }
+    }
+
     /// Clones the entity into whatever entity `mapper` chooses for it.
     #[track_caller]
     pub fn clone_entity_mapped(
@@ -1453,6 +1517,49 @@ mod private {
 
 use private::{FilterableId, FilterableIds, Marker};
 
+/// Maps the [`Entity`] fields of all reflected, mutable components of `targets`.
+#[cfg(feature = "bevy_reflect")]
+fn map_reflected_component_entities(
+    world: &mut World,
+    targets: &[Entity],
+    mapper: &mut dyn EntityMapper,
+) {
+    use crate::{
+        change_detection::DetectChangesMut,
+        reflect::{map_reflected_entities, AppTypeRegistry, ReflectComponent},
+    };
+
+    let Some(registry) = world.get_resource::<AppTypeRegistry>().cloned() else {
+        return;
+    };
+    let registry = registry.read();
+    for &target in targets {
+        let components = world
+            .entity(target)
+            .archetype()
+            .components()
+            .iter()
+            .filter_map(|&id| {
+                let info = world.components().get_info(id)?;
+                // Relationships are already mapped, and must not be modified without their hooks.
+                if !info.mutable() || info.relationship_accessor().is_some() {
+                    return None;
+                }
+                registry.get_type_data::<ReflectComponent>(info.type_id()?)
+            })
+            .collect::<Vec<_>>();
+        for reflect_component in components {
+            let mut entity = world.entity_mut(target);
+            if let Some(mut component) = reflect_component.reflect_mut(&mut entity) {
+                map_reflected_entities(
+                    component.bypass_change_detection().as_partial_reflect_mut(),
+                    mapper,
+                );
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1729,6 +1836,61 @@ mod tests {
             );
             assert!(world.resource::<FromWorldCalled>().0);
         }
+
+        #[test]
+        fn clone_hierarchy_maps_reflected_entities() {
+            #[derive(Reflect, Clone, PartialEq, Debug)]
+            enum Target {
+                None,
+                Entity(Entity),
+            }
+
+            // No `#[entities]` or `MapEntities` implementation.
+            #[derive(Component, Reflect, Clone, PartialEq, Debug)]
+            #[reflect(Component)]
+            struct Joint {
+                target: Target,
+                chain: Vec<Entity>,
+            }
+
+            let mut world = World::new();
+            let registry = AppTypeRegistry::default();
+            registry.write().register::<Joint>();
+            world.insert_resource(registry);
+
+            let outside = world.spawn_empty().id();
+            let root = world.spawn_empty().id();
+            let a = world.spawn(ChildOf(root)).id();
+            let b = world.spawn(ChildOf(a)).id();
+            world.entity_mut(a).insert(Joint {
+                target: Target::Entity(b),
+                chain: vec![root, outside],
+            });
+            world.entity_mut(b).insert(Joint {
+                target: Target::None,
+                chain: vec![a],
+            });
+
+            let root_clone = world.spawn_empty().id();
+            EntityCloner::default().clone_hierarchy(&mut world, root, root_clone);
+            let a_clone = world.get::<Children>(root_clone).unwrap()[0];
+            let b_clone = world.get::<Children>(a_clone).unwrap()[0];
+            assert_eq!(
+                world.get::<Joint>(a_clone),
+                Some(&Joint {
+                    target: Target::Entity(b_clone),
+                    chain: vec![root_clone, outside],
+                })
+            );
+            assert_eq!(
+                world.get::<Joint>(b_clone),
+                Some(&Joint {
+                    target: Target::None,
+                    chain: vec![a_clone],
+                })
+            );
+            assert_eq!(world.get::<Joint>(a).unwrap().target, Target::Entity(b));
+        }
     }
 
     #[test]
@@ -2179,6 +2341,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn clone_hierarchy() {
+        #[derive(Component, Clone, PartialEq, Debug)]
+        struct Target(#[entities] Entity);
+
+        let mut world = World::new();
+        let parent = world.spawn_empty().id();
+        let root = world.spawn(ChildOf(parent)).id();
+        let child1 = world.spawn(ChildOf(root)).id();
+        let child2 = world.spawn(ChildOf(root)).id();
+        let grandchild = world.spawn((ChildOf(child2), Target(parent))).id();
+        // `child1` references an entity that is cloned after it.
+        world.entity_mut(child1).insert(Target(grandchild));
+
+        let root_clone = world.spawn_empty().id();
+        EntityCloner::default().clone_hierarchy(&mut world, root, root_clone);
+
+        assert_eq!(world.get::<ChildOf>(root_clone), Some(&ChildOf(parent)));
+        let children = world.get::<Children>(root_clone).unwrap().to_vec();
+        assert_eq!(children.len(), 2);
+        assert!(!children.contains(&child1) && !children.contains(&child2));
+        let grandchildren = world.get::<Children>(children[1]).unwrap().to_vec();
+        assert_eq!(grandchildren.len(), 1);
+        assert_ne!(grandchildren[0], grandchild);
+
+        assert_eq!(
+            world.get::<Target>(children[0]),
+            Some(&Target(grandchildren[0]))
+        );
+        assert_eq!(world.get::<Target>(grandchildren[0]), Some(&Target(parent)));
+        assert_eq!(
+            world.get::<Children>(root).unwrap().deref(),
+            &[child1, child2]
+        );
+        assert_eq!(world.get::<Target>(child1), Some(&Target(grandchild)));
+    }
+
     #[test]
     fn cloning_with_required_components_preserves_existing() {
         #[derive(Component, Clone, PartialEq, Debug, Default)]
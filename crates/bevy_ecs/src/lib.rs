@@ -157,7 +157,7 @@ mod tests {
         entity::{Entity, EntityMapper, EntityNotSpawnedError},
         entity_disabling::DefaultQueryFilters,
         prelude::Or,
-        query::{Added, Changed, FilteredAccess, QueryFilter, With, Without},
+        query::{Added, ArchetypeChanged, Changed, FilteredAccess, QueryFilter, With, Without},
         resource::Resource,
         world::{error::EntityDespawnError, EntityMut, EntityRef, Mut, World},
     };
@@ -1243,6 +1243,56 @@ mod tests {
         assert_eq!(get_changed(&mut world), vec![e1]);
     }
 
+    #[test]
+    fn archetype_changed_query() {
+        let mut world = World::default();
+        let e1 = world.spawn((A(0), B(0))).id();
+        let e2 = world.spawn((A(0), B(0))).id();
+        let e3 = world.spawn((A(0), C)).id();
+
+        fn get_changed(world: &mut World) -> HashSet<Entity> {
+            world
+                .query_filtered::<Entity, ArchetypeChanged<A>>()
+                .iter(world)
+                .collect::<HashSet<Entity>>()
+        }
+        assert_eq!(get_changed(&mut world), [e1, e2, e3].into_iter().collect());
+        world.clear_trackers();
+        assert!(get_changed(&mut world).is_empty());
+
+        // changing one entity yields every entity of its archetype, and no other
+        world.get_mut::<A>(e1).unwrap().0 = 1;
+        assert_eq!(get_changed(&mut world), [e1, e2].into_iter().collect());
+        world.clear_trackers();
+
+        world.get_mut::<A>(e3).unwrap().0 = 1;
+        assert_eq!(get_changed(&mut world), [e3].into_iter().collect());
+    }
+
+    #[test]
+    fn archetype_changed_query_sparse() {
+        let mut world = World::default();
+        let e1 = world.spawn((SparseStored(0), A(0))).id();
+        let e2 = world.spawn((SparseStored(0), A(0))).id();
+        let e3 = world.spawn(SparseStored(0)).id();
+        world.clear_trackers();
+
+        fn get_changed(world: &mut World) -> HashSet<Entity> {
+            world
+                .query_filtered::<Entity, ArchetypeChanged<SparseStored>>()
+                .iter(world)
+                .collect::<HashSet<Entity>>()
+        }
+        assert!(get_changed(&mut world).is_empty());
+
+        world.get_mut::<SparseStored>(e2).unwrap().0 = 1;
+        assert_eq!(get_changed(&mut world), [e1, e2].into_iter().collect());
+        world.clear_trackers();
+
+        world.get_mut::<SparseStored>(e3).unwrap().0 = 1;
+        assert_eq!(get_changed(&mut world), [e3].into_iter().collect());
+    }
+
     #[test]
     fn resource() {
         use crate::resource::Resource;
@@ -53,23 +53,29 @@ use crate::{
     change_detection::{MaybeLocation, Tick},
     component::{Component, ComponentId, ComponentIdFor},
     entity::Entity,
+    error::HandleError,
     event::{EntityComponentsTrigger, EntityEvent, EventKey},
     message::{
         Message, MessageCursor, MessageId, MessageIterator, MessageIteratorWithId, Messages,
     },
     query::FilteredAccessSet,
     relationship::RelationshipHookMode,
+    resource::Resource,
     storage::SparseSet,
-    system::{Local, ReadOnlySystemParam, SystemMeta, SystemParam},
+    system::{Command, Local, ReadOnlySystemParam, SystemMeta, SystemParam},
     world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld, World},
 };
 
+use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
+use bevy_platform::{cell::SyncCell, collections::HashMap};
+use bevy_tasks::{futures::check_ready, AsyncComputeTaskPool, Task, TaskPool};
 use derive_more::derive::Into;
 
 #[cfg(feature = "bevy_reflect")]
 use bevy_reflect::Reflect;
 use core::{
     fmt::Debug,
+    future::{poll_fn, Future},
     iter,
     marker::PhantomData,
     ops::{Deref, DerefMut},
@@ -102,6 +108,9 @@ pub struct HookContext {
 /// to clean up resources when a component is removed,
 /// or to keep hierarchical data structures across entities in sync.
 ///
+/// Hooks run synchronously. Slow work can be moved to an asynchronous task with
+/// [`DeferredWorld::spawn_hook_task`], whose result is applied to the world as a command.
+///
 /// This information is stored in the [`ComponentInfo`](crate::component::ComponentInfo) of the associated component.
 ///
 /// There are two ways of configuring hooks for a component:
@@ -314,6 +323,160 @@ impl ComponentHooks {
     }
 }
 
+// The single-threaded task pool requires the task and its output to be `Sync`, which `SyncCell`
+// provides for any `Send` value.
+type HookTaskOutput = SyncCell<Box<dyn FnOnce(&mut World) + Send>>;
+
+/// A [`Resource`] storing the asynchronous tasks spawned by component hooks with
+/// [`DeferredWorld::spawn_hook_task`].
+///
+/// Tasks are stored per entity and component, in the order they were spawned. They are polled by
+/// [`World::apply_hook_tasks`], which the `TaskPoolPlugin` of `bevy_app` runs every frame.
+#[derive(Resource, Default)]
+pub struct HookTasks {
+    queues: HashMap<(Entity, ComponentId), VecDeque<(u64, Task<HookTaskOutput>)>>,
+    next_index: u64,
+}
+
+impl HookTasks {
+    /// Returns the number of tasks that have not been applied yet.
+    pub fn len(&self) -> usize {
+        self.queues.values().map(VecDeque::len).sum()
+    }
+
+    /// Returns `true` if there are no pending tasks.
+    pub fn is_empty(&self) -> bool {
+        self.queues.is_empty()
+    }
+
+    /// Returns the number of pending tasks spawned by the hooks of `component_id` on `entity`.
+    pub fn pending(&self, entity: Entity, component_id: ComponentId) -> usize {
+        self.queues
+            .get(&(entity, component_id))
+            .map_or(0, VecDeque::len)
+    }
+
+    fn push(&mut self, key: (Entity, ComponentId), task: Task<HookTaskOutput>) {
+        let index = self.next_index;
+        self.next_index += 1;
+        self.queues.entry(key).or_default().push_back((index, task));
+    }
+
+    /// Removes the completed tasks that are ready to be applied, in the order they were spawned.
+    ///
+    /// A task is only ready once every task spawned before it for the same entity and component
+    /// has completed.
+    fn take_ready(&mut self) -> Vec<(u64, HookTaskOutput)> {
+        let mut ready = Vec::new();
+        self.queues.retain(|_, queue| {
+            while let Some((index, task)) = queue.front_mut() {
+                let Some(output) = check_ready(task) else {
+                    break;
+                };
+                ready.push((*index, output));
+                queue.pop_front();
+            }
+            !queue.is_empty()
+        });
+        ready.sort_unstable_by_key(|(index, _)| *index);
+        ready
+    }
+}
+
+impl<'w> DeferredWorld<'w> {
+    /// Spawns an asynchronous task from a component hook, whose output is a [`Command`] applied to
+    /// the [`World`] once the task completes.
+    ///
+    /// Hooks must run synchronously, which makes them unsuitable for slow work such as loading a
+    /// file or querying a database. This moves such work to the [`AsyncComputeTaskPool`], and
+    /// lets its result re-enter the ECS through a command.
+    ///
+    /// The command is queued by [`World::apply_hook_tasks`], and is guaranteed to:
+    /// - be applied after the insertion or removal that triggered the hook,
+    /// - be applied after the commands of every task previously spawned for the same
+    ///   [`HookContext::entity`] and [`HookContext::component_id`], even if those tasks complete later.
+    ///
+    /// The command is applied even if the component has since been removed, or the entity despawned,
+    /// so it should check for them if needed.
+    ///
+    /// ```
+    /// # use bevy_ecs::{prelude::*, lifecycle::HookContext, world::DeferredWorld};
+    /// #[derive(Component)]
+    /// #[component(on_add = load_texture)]
+    /// struct TexturePath(&'static str);
+    ///
+    /// #[derive(Component)]
+    /// struct Texture(Vec<u8>);
+    ///
+    /// fn load_texture(mut world: DeferredWorld, context: HookContext) {
+    ///     let path = world.get::<TexturePath>(context.entity).unwrap().0;
+    ///     world.spawn_hook_task(context, async move {
+    ///         // Read the file without blocking the hook.
+    ///         let bytes = path.as_bytes().to_vec();
+    ///         move |world: &mut World| {
+    ///             if let Ok(mut entity) = world.get_entity_mut(context.entity) {
+    ///                 entity.insert(Texture(bytes));
+    ///             }
+    ///         }
+    ///     });
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn(TexturePath("player.png")).id();
+    /// while !world.resource::<bevy_ecs::lifecycle::HookTasks>().is_empty() {
+    ///     world.apply_hook_tasks();
+    /// }
+    /// assert!(world.entity(entity).contains::<Texture>());
+    /// ```
+    pub fn spawn_hook_task<C: Command<T> + HandleError<T>, T: 'static>(
+        &mut self,
+        context: HookContext,
+        future: impl Future<Output = C> + Send + 'static,
+    ) {
+        let mut future = SyncCell::new(Box::pin(future));
+        let task = AsyncComputeTaskPool::get_or_init(TaskPool::default).spawn(async move {
+            let command = poll_fn(move |cx| future.get().as_mut().poll(cx)).await;
+            SyncCell::new(
+                Box::new(move |world: &mut World| world.commands().queue(command))
+                    as Box<dyn FnOnce(&mut World) + Send>,
+            )
+        });
+        let key = (context.entity, context.component_id);
+        if let Some(mut tasks) = self.get_resource_mut::<HookTasks>() {
+            tasks.push(key, task);
+        } else {
+            // The resource cannot be inserted from a `DeferredWorld`, but the command is applied
+            // before any task could be polled, so the order of tasks is preserved.
+            self.commands().queue(move |world: &mut World| {
+                world.get_resource_or_init::<HookTasks>().push(key, task);
+            });
+        }
+    }
+}
+
+impl World {
+    /// Applies the commands of the tasks spawned by [`DeferredWorld::spawn_hook_task`] that have
+    /// completed, in the order the tasks were spawned.
+    ///
+    /// Tasks that completed before a task spawned earlier for the same entity and component are
+    /// kept until that task completes.
+    pub fn apply_hook_tasks(&mut self) {
+        let Some(mut tasks) = self.get_resource_mut::<HookTasks>() else {
+            return;
+        };
+        let ready = tasks.take_ready();
+        for (_, apply) in ready {
+            SyncCell::to_inner(apply)(self);
+        }
+        self.flush();
+    }
+}
+
+/// A system that calls [`World::apply_hook_tasks`].
+pub fn apply_hook_tasks(world: &mut World) {
+    world.apply_hook_tasks();
+}
+
 /// [`EventKey`] for [`Add`]
 pub const ADD: EventKey = EventKey(ComponentId::new(0));
 /// [`EventKey`] for [`Insert`]
@@ -641,3 +804,52 @@ unsafe impl<'a> SystemParam for &'a RemovedComponentMessages {
         world.removed_components()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use bevy_tasks::futures_lite::future::yield_now;
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<&'static str>);
+
+    #[derive(Component)]
+    #[component(on_insert = on_insert, on_remove = on_remove)]
+    struct Slow;
+
+    fn on_insert(mut world: DeferredWorld, context: HookContext) {
+        world.spawn_hook_task(context, async {
+            // Complete after the task spawned by `on_remove`.
+            for _ in 0..100 {
+                yield_now().await;
+            }
+            |world: &mut World| world.resource_mut::<Log>().0.push("insert")
+        });
+    }
+
+    fn on_remove(mut world: DeferredWorld, context: HookContext) {
+        world.spawn_hook_task(context, async {
+            |world: &mut World| world.resource_mut::<Log>().0.push("remove")
+        });
+    }
+
+    #[test]
+    fn hook_tasks_apply_in_spawn_order() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let entity = world.spawn(Slow).id();
+        world.entity_mut(entity).remove::<Slow>();
+        assert_eq!(
+            world
+                .resource::<HookTasks>()
+                .pending(entity, world.component_id::<Slow>().unwrap()),
+            2
+        );
+
+        while !world.resource::<HookTasks>().is_empty() {
+            world.apply_hook_tasks();
+        }
+        assert_eq!(world.resource::<Log>().0, vec!["insert", "remove"]);
+    }
+}
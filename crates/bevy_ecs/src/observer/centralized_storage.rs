@@ -9,11 +9,16 @@
 //!     - These are split by target type, in order to allow for different lookup strategies.
 //!     - [`CachedComponentObservers`] is one of these maps, which contains observers that are specifically targeted at a component.
 
+use alloc::{collections::BinaryHeap, vec::Vec};
 use bevy_platform::collections::HashMap;
+use core::{cmp::Reverse, ops::Deref};
 
 use crate::{
-    archetype::ArchetypeFlags, component::ComponentId, entity::EntityHashMap, event::EventKey,
-    observer::ObserverRunner,
+    archetype::ArchetypeFlags,
+    component::ComponentId,
+    entity::{Entity, EntityHashMap},
+    event::EventKey,
+    observer::{ObserverOrder, ObserverRunner},
 };
 
 /// An internal lookup table tracking all of the observers in the world.
@@ -144,8 +149,195 @@ impl CachedObservers {
     }
 }
 
-/// Map between an observer entity and its [`ObserverRunner`]
-pub type ObserverMap = EntityHashMap<ObserverRunner>;
+/// Map between an observer entity and its [`ObserverRunner`].
+///
+/// This dereferences to the underlying [`EntityHashMap`], while iterating the map itself yields
+/// observers in the order they should run, as defined by their [`ObserverOrder`]: observers with a
+/// higher [priority](ObserverOrder::priority) run first, and [`before`](ObserverOrder::before) and
+/// [`after`](ObserverOrder::after) constraints between observers in the same map are always
+/// respected. Among observers with the same priority, the earliest registered runs first.
+///
+/// When none of the observers has an ordering constraint, they run in an unspecified order.
+#[derive(Default, Debug, Clone)]
+pub struct ObserverMap {
+    runners: EntityHashMap<ObserverRunner>,
+    /// The ordering of each observer, with the sequence number it was inserted with.
+    orders: EntityHashMap<(u64, ObserverOrder)>,
+    next_sequence: u64,
+    /// The number of observers with a non-default [`ObserverOrder`].
+    ordered: usize,
+    /// The cached run order, only computed while some observers have ordering constraints.
+    run_order: Vec<(Entity, ObserverRunner)>,
+}
+
+impl Deref for ObserverMap {
+    type Target = EntityHashMap<ObserverRunner>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.runners
+    }
+}
+
+impl ObserverMap {
+    /// Iterates over the observers and their runners, in run order.
+    pub fn iter(&self) -> ObserverMapIter<'_> {
+        if self.ordered == 0 {
+            ObserverMapIter::Unordered(self.runners.iter())
+        } else {
+            ObserverMapIter::Ordered(self.run_order.iter())
+        }
+    }
+
+    /// Inserts the `observer`, replacing any previous entry for it.
+    ///
+    /// A replaced observer keeps its registration order among observers with the same priority.
+    /// The run order is only recomputed while some observers have ordering constraints.
+    pub(crate) fn insert(
+        &mut self,
+        observer: Entity,
+        runner: ObserverRunner,
+        order: ObserverOrder,
+    ) {
+        let sequence = match self.orders.get(&observer) {
+            Some(&(sequence, _)) => sequence,
+            None => {
+                self.next_sequence += 1;
+                self.next_sequence - 1
+            }
+        };
+        self.remove(&observer);
+        if !order.is_default() {
+            self.ordered += 1;
+        }
+        self.runners.insert(observer, runner);
+        self.orders.insert(observer, (sequence, order));
+        if self.ordered > 0 {
+            self.sort();
+        }
+    }
+
+    /// Removes the `observer`, returning its runner if it was present.
+    pub(crate) fn remove(&mut self, observer: &Entity) -> Option<ObserverRunner> {
+        let runner = self.runners.remove(observer)?;
+        if let Some((_, order)) = self.orders.remove(observer)
+            && !order.is_default()
+        {
+            self.ordered -= 1;
+        }
+        if self.ordered == 0 {
+            self.run_order.clear();
+        } else {
+            // Removing an observer never invalidates the order of the remaining ones.
+            self.run_order.retain(|(entity, _)| entity != observer);
+        }
+        Some(runner)
+    }
+
+    /// Topologically sorts the observers using their `before` / `after` constraints, picking the
+    /// highest priority among the runnable observers at each step, and caches the result.
+    ///
+    /// Constraints forming a cycle are ignored for the observers involved in it.
+    fn sort(&mut self) {
+        let mut nodes: Vec<(Entity, u64, &ObserverOrder)> = self
+            .orders
+            .iter()
+            .map(|(&observer, (sequence, order))| (observer, *sequence, order))
+            .collect();
+        nodes.sort_unstable_by_key(|&(_, sequence, _)| sequence);
+        let index_of: EntityHashMap<usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, &(observer, ..))| (observer, i))
+            .collect();
+
+        // `outgoing[i]` lists the observers that must run after observer `i`.
+        let mut outgoing: Vec<Vec<usize>> = (0..nodes.len()).map(|_| Vec::new()).collect();
+        let mut incoming = Vec::from_iter(core::iter::repeat_n(0usize, nodes.len()));
+        for (i, (_, _, order)) in nodes.iter().enumerate() {
+            for other in &order.before {
+                if let Some(&j) = index_of.get(other) {
+                    outgoing[i].push(j);
+                    incoming[j] += 1;
+                }
+            }
+            for other in &order.after {
+                if let Some(&j) = index_of.get(other) {
+                    outgoing[j].push(i);
+                    incoming[i] += 1;
+                }
+            }
+        }
+
+        // Ready observers, highest priority first, then earliest registered.
+        let key = |i: usize| (nodes[i].2.priority, Reverse(i));
+        let mut ready: BinaryHeap<_> = (0..nodes.len())
+            .filter(|&i| incoming[i] == 0)
+            .map(key)
+            .collect();
+        let mut is_placed = Vec::from_iter(core::iter::repeat_n(false, nodes.len()));
+        self.run_order.clear();
+        while self.run_order.len() < nodes.len() {
+            // If nothing is ready, the remaining constraints form a cycle: fall back to priority.
+            let next = ready.pop().map(|(_, Reverse(i))| i).or_else(|| {
+                (0..nodes.len())
+                    .filter(|&i| !is_placed[i])
+                    .max_by_key(|&i| key(i))
+            });
+            let Some(next) = next else {
+                break;
+            };
+            if is_placed[next] {
+                continue;
+            }
+            is_placed[next] = true;
+            let observer = nodes[next].0;
+            self.run_order.push((observer, self.runners[&observer]));
+            for &after in &outgoing[next] {
+                incoming[after] = incoming[after].saturating_sub(1);
+                if incoming[after] == 0 && !is_placed[after] {
+                    ready.push(key(after));
+                }
+            }
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a ObserverMap {
+    type Item = (&'a Entity, &'a ObserverRunner);
+    type IntoIter = ObserverMapIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the observers of an [`ObserverMap`], in run order.
+pub enum ObserverMapIter<'a> {
+    /// Iterates over observers without ordering constraints.
+    Unordered(bevy_platform::collections::hash_map::Iter<'a, Entity, ObserverRunner>),
+    /// Iterates over the cached run order.
+    Ordered(core::slice::Iter<'a, (Entity, ObserverRunner)>),
+}
+
+impl<'a> Iterator for ObserverMapIter<'a> {
+    type Item = (&'a Entity, &'a ObserverRunner);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Unordered(iter) => iter.next(),
+            Self::Ordered(iter) => iter.next().map(|(observer, runner)| (observer, runner)),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Unordered(iter) => iter.size_hint(),
+            Self::Ordered(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl ExactSizeIterator for ObserverMapIter<'_> {}
 
 /// Collection of [`ObserverRunner`] for [`Observer`](crate::observer::Observer) registered to a particular event targeted at a specific component.
 ///
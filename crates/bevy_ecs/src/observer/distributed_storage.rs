@@ -187,6 +187,22 @@ use crate::prelude::ReflectComponent;
 /// This allows hooks to act as constructors and destructors for components,
 /// as they always have the first and final say in the component's lifecycle.
 ///
+/// ## Observer ordering
+///
+/// When several observers watch the same event, their relative order can be controlled with
+/// [`Observer::with_priority`], [`Observer::before`] and [`Observer::after`]:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # let mut world = World::default();
+/// # #[derive(Event)]
+/// # struct Attack;
+/// let apply_damage = world.add_observer(|_: On<Attack>| { /* ... */ }).id();
+/// world.spawn(Observer::new(|_: On<Attack>| { /* check for death */ }).after(apply_damage));
+/// ```
+///
+/// See [`ObserverOrder`] for the exact rules.
+///
 /// ## Observer re-targeting
 ///
 /// Currently, [observers cannot be retargeted after spawning](https://github.com/bevyengine/bevy/issues/19587):
@@ -205,6 +221,7 @@ pub struct Observer {
     pub(crate) error_handler: Option<ErrorHandler>,
     pub(crate) system: Box<dyn AnyNamedSystem>,
     pub(crate) descriptor: ObserverDescriptor,
+    pub(crate) order: ObserverOrder,
     pub(crate) last_trigger_id: u32,
     pub(crate) despawned_watched_entities: u32,
     pub(crate) runner: ObserverRunner,
@@ -229,6 +246,7 @@ impl Observer {
         Self {
             system,
             descriptor: Default::default(),
+            order: Default::default(),
             hook_on_add: hook_on_add::<E, B, I::System>,
             error_handler: None,
             runner: observer_system_runner::<E, B, I::System>,
@@ -242,6 +260,7 @@ impl Observer {
         Self {
             system: Box::new(IntoSystem::into_system(|| {})),
             descriptor: Default::default(),
+            order: Default::default(),
             hook_on_add: |mut world, hook_context| {
                 let default_error_handler = world.default_error_handler();
                 world.commands().queue(move |world: &mut World| {
@@ -319,6 +338,38 @@ impl Observer {
         self
     }
 
+    /// Sets the priority of this observer. When an event is triggered, observers with a higher
+    /// priority run before observers with a lower one. Observers default to a priority of `0`.
+    ///
+    /// Note that if this is called _after_ an [`Observer`] is spawned, it will produce no effects.
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.order.priority = priority;
+        self
+    }
+
+    /// Runs this observer before the given `observer` entity whenever both are triggered by the same event.
+    ///
+    /// This takes precedence over [priorities](Observer::with_priority).
+    /// Note that if this is called _after_ an [`Observer`] is spawned, it will produce no effects.
+    pub fn before(mut self, observer: Entity) -> Self {
+        self.order.before.push(observer);
+        self
+    }
+
+    /// Runs this observer after the given `observer` entity whenever both are triggered by the same event.
+    ///
+    /// This takes precedence over [priorities](Observer::with_priority).
+    /// Note that if this is called _after_ an [`Observer`] is spawned, it will produce no effects.
+    pub fn after(mut self, observer: Entity) -> Self {
+        self.order.after.push(observer);
+        self
+    }
+
+    /// Returns the [`ObserverOrder`] for this [`Observer`].
+    pub fn order(&self) -> &ObserverOrder {
+        &self.order
+    }
+
     /// Returns the [`ObserverDescriptor`] for this [`Observer`].
     pub fn descriptor(&self) -> &ObserverDescriptor {
         &self.descriptor
@@ -412,6 +463,45 @@ impl ObserverDescriptor {
     }
 }
 
+/// Controls the order in which an [`Observer`] runs relative to other observers of the same event.
+///
+/// Ordering applies between observers that are stored together in an [`ObserverMap`](crate::observer::ObserverMap):
+/// observers watching the same event with the same kind of target (global, entity, component or entity and component).
+/// Observers of different kinds keep their fixed relative order (see [`Trigger`](crate::event::Trigger)).
+#[derive(Default, Clone, Debug)]
+pub struct ObserverOrder {
+    /// Observers with a higher priority run first.
+    pub(super) priority: i32,
+
+    /// The observers this observer must run before.
+    pub(super) before: Vec<Entity>,
+
+    /// The observers this observer must run after.
+    pub(super) after: Vec<Entity>,
+}
+
+impl ObserverOrder {
+    /// Returns `true` if the observer has the default priority and no ordering constraints.
+    pub(super) fn is_default(&self) -> bool {
+        self.priority == 0 && self.before.is_empty() && self.after.is_empty()
+    }
+
+    /// Returns the priority of the observer.
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    /// Returns the observers this observer must run before.
+    pub fn before(&self) -> &[Entity] {
+        &self.before
+    }
+
+    /// Returns the observers this observer must run after.
+    pub fn after(&self) -> &[Entity] {
+        &self.after
+    }
+}
+
 /// A [`ComponentHook`] used by [`Observer`] to handle its [`on-add`](`crate::lifecycle::ComponentHooks::on_add`).
 ///
 /// This function exists separate from [`Observer`] to allow [`Observer`] to have its type parameters
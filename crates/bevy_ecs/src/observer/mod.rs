@@ -132,14 +132,20 @@ impl World {
             let cache = observers.get_observers_mut(event_key);
 
             if descriptor.components.is_empty() && descriptor.entities.is_empty() {
-                cache
-                    .global_observers
-                    .insert(observer_entity, observer_state.runner);
+                cache.global_observers.insert(
+                    observer_entity,
+                    observer_state.runner,
+                    observer_state.order.clone(),
+                );
             } else if descriptor.components.is_empty() {
                 // Observer is not targeting any components so register it as an entity observer
                 for &watched_entity in &observer_state.descriptor.entities {
                     let map = cache.entity_observers.entry(watched_entity).or_default();
-                    map.insert(observer_entity, observer_state.runner);
+                    map.insert(
+                        observer_entity,
+                        observer_state.runner,
+                        observer_state.order.clone(),
+                    );
                 }
             } else {
                 // Register observer for each watched component
@@ -156,9 +162,11 @@ impl World {
                             });
                     if descriptor.entities.is_empty() {
                         // Register for all triggers targeting the component
-                        observers
-                            .global_observers
-                            .insert(observer_entity, observer_state.runner);
+                        observers.global_observers.insert(
+                            observer_entity,
+                            observer_state.runner,
+                            observer_state.order.clone(),
+                        );
                     } else {
                         // Register for each watched entity
                         for &watched_entity in &descriptor.entities {
@@ -166,7 +174,11 @@ impl World {
                                 .entity_component_observers
                                 .entry(watched_entity)
                                 .or_default();
-                            map.insert(observer_entity, observer_state.runner);
+                            map.insert(
+                                observer_entity,
+                                observer_state.runner,
+                                observer_state.order.clone(),
+                            );
                         }
                     }
                 }
@@ -1059,6 +1071,80 @@ mod tests {
         assert_eq!(vec!["a", "a"], world.resource::<Order>().0);
     }
 
+    #[test]
+    fn observer_priority() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        world.spawn(
+            Observer::new(|_: On<EventA>, mut res: ResMut<Order>| res.observed("low"))
+                .with_priority(-1),
+        );
+        world.add_observer(|_: On<EventA>, mut res: ResMut<Order>| res.observed("default"));
+        world.spawn(
+            Observer::new(|_: On<EventA>, mut res: ResMut<Order>| res.observed("high"))
+                .with_priority(10),
+        );
+        world.add_observer(|_: On<EventA>, mut res: ResMut<Order>| res.observed("default 2"));
+
+        world.trigger(EventA);
+        assert_eq!(
+            vec!["high", "default", "default 2", "low"],
+            world.resource::<Order>().0
+        );
+    }
+
+    #[test]
+    fn observer_before_after() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        let damage = world
+            .spawn(
+                Observer::new(|_: On<Add, A>, mut res: ResMut<Order>| res.observed("damage"))
+                    .with_priority(-10),
+            )
+            .id();
+        world.spawn(
+            Observer::new(|_: On<Add, A>, mut res: ResMut<Order>| res.observed("death"))
+                .with_priority(10)
+                .after(damage),
+        );
+        world.spawn(
+            Observer::new(|_: On<Add, A>, mut res: ResMut<Order>| res.observed("armor"))
+                .before(damage),
+        );
+
+        world.spawn(A);
+        assert_eq!(
+            vec!["armor", "damage", "death"],
+            world.resource::<Order>().0
+        );
+    }
+
+    #[test]
+    fn observer_map_reinsert_keeps_registration_order() {
+        use crate::observer::{ObserverMap, ObserverOrder, TriggerContext};
+        use bevy_ptr::PtrMut;
+
+        unsafe fn noop(_: DeferredWorld, _: Entity, _: &TriggerContext, _: PtrMut, _: PtrMut) {}
+        let order = || ObserverOrder {
+            priority: 1,
+            ..Default::default()
+        };
+
+        let mut world = World::new();
+        let [a, b, c] = [(); 3].map(|_| world.spawn_empty().id());
+        let mut map = ObserverMap::default();
+        for observer in [a, b, c] {
+            map.insert(observer, noop, order());
+        }
+        map.insert(a, noop, order());
+
+        let run_order: Vec<_> = map.iter().map(|(&observer, _)| observer).collect();
+        assert_eq!(run_order, [a, b, c]);
+    }
+
     #[test]
     fn unregister_global_observer() {
         let mut world = World::new();
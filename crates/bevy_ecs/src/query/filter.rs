@@ -19,6 +19,7 @@ use variadics_please::all_tuples;
 ///   [`With`] and [`Without`] filters can be applied to check if the queried entity does or does not contain a particular component.
 /// - **Change detection filters.**
 ///   [`Added`] and [`Changed`] filters can be applied to detect component changes to an entity.
+///   [`ArchetypeChanged`] detects changes to any entity of an archetype.
 /// - **Spawned filter.**
 ///   [`Spawned`] filter can be applied to check if the queried entity was spawned recently.
 /// - **`QueryFilter` tuples.**
@@ -1097,6 +1098,191 @@ unsafe impl<T: Component> QueryFilter for Changed<T> {
     }
 }
 
+/// A filter on a component that retains every entity of an archetype if the component was added
+/// or mutably dereferenced on *any* entity of that archetype since the system last ran.
+///
+/// This answers "did anything in this archetype change?" at table granularity. It is useful for
+/// systems that rebuild derived data per archetype (such as acceleration structures or batches),
+/// which can early-out on [`Query::is_empty`](crate::system::Query::is_empty) when nothing
+/// changed, or rebuild the whole archetype's data when something did.
+///
+/// Unlike [`Changed`], the change ticks are only inspected once when the query moves to a new
+/// table (or archetype, for sparse set components), so rows of an unchanged table are rejected
+/// without reading their ticks.
+///
+/// **Note** that simply *mutably dereferencing* a component is considered a change ([`DerefMut`](std::ops::DerefMut)).
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ecs::component::Component;
+/// # use bevy_ecs::query::ArchetypeChanged;
+/// # use bevy_ecs::system::Query;
+/// #
+/// # #[derive(Component)]
+/// # struct Transform;
+/// # #[derive(Component)]
+/// # struct Aabb;
+///
+/// fn rebuild_bvh(query: Query<&Aabb, ArchetypeChanged<Transform>>) {
+///     if query.is_empty() {
+///         // Nothing moved, keep the previous tree.
+///         return;
+///     }
+///     for aabb in &query {
+///         // Rebuild the tree for every archetype that had a moving entity.
+///     }
+/// }
+///
+/// # bevy_ecs::system::assert_is_system(rebuild_bvh);
+/// ```
+pub struct ArchetypeChanged<T>(PhantomData<T>);
+
+#[doc(hidden)]
+pub struct ArchetypeChangedFetch<'w, T: Component> {
+    // Can be `None` when the component has never been inserted
+    sparse_set: Option<&'w ComponentSparseSet>,
+    changed: bool,
+    last_run: Tick,
+    this_run: Tick,
+    marker: PhantomData<T>,
+}
+
+impl<T: Component> Clone for ArchetypeChangedFetch<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            sparse_set: self.sparse_set,
+            changed: self.changed,
+            last_run: self.last_run,
+            this_run: self.this_run,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// SAFETY:
+/// `set_table` and `set_archetype` access a single component's ticks in a readonly way.
+/// This is sound because `update_component_access` adds read access for that component and panics when appropriate.
+/// `update_component_access` doesn't add a filter, archetypes without the component are rejected
+/// by `matches_component_set`, which returns whether the set contains that component.
+unsafe impl<T: Component> WorldQuery for ArchetypeChanged<T> {
+    type Fetch<'w> = ArchetypeChangedFetch<'w, T>;
+    type State = ComponentId;
+
+    fn shrink_fetch<'wlong: 'wshort, 'wshort>(fetch: Self::Fetch<'wlong>) -> Self::Fetch<'wshort> {
+        ArchetypeChangedFetch {
+            sparse_set: fetch.sparse_set,
+            changed: fetch.changed,
+            last_run: fetch.last_run,
+            this_run: fetch.this_run,
+            marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    unsafe fn init_fetch<'w, 's>(
+        world: UnsafeWorldCell<'w>,
+        &id: &'s ComponentId,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        ArchetypeChangedFetch {
+            sparse_set: match T::STORAGE_TYPE {
+                StorageType::Table => None,
+                // SAFETY: The underlying type associated with `component_id` is `T`,
+                // which we are allowed to access since we registered it in `update_component_access`.
+                StorageType::SparseSet => unsafe { world.storages().sparse_sets.get(id) },
+            },
+            changed: false,
+            last_run,
+            this_run,
+            marker: PhantomData,
+        }
+    }
+
+    const IS_DENSE: bool = {
+        match T::STORAGE_TYPE {
+            StorageType::Table => true,
+            StorageType::SparseSet => false,
+        }
+    };
+
+    #[inline]
+    unsafe fn set_archetype<'w, 's>(
+        fetch: &mut Self::Fetch<'w>,
+        component_id: &'s ComponentId,
+        archetype: &'w Archetype,
+        table: &'w Table,
+    ) {
+        if Self::IS_DENSE {
+            // SAFETY: `set_archetype`'s safety rules are a super set of the `set_table`'s ones.
+            unsafe {
+                Self::set_table(fetch, component_id, table);
+            }
+        } else {
+            fetch.changed = fetch.sparse_set.is_some_and(|sparse_set| {
+                archetype.entities().iter().any(|entity| {
+                    sparse_set
+                        .get_changed_tick(entity.id())
+                        .is_some_and(|tick| {
+                            // SAFETY: We have read access to the ticks of `T`, so no mutable reference to them exists.
+                            unsafe { tick.read() }.is_newer_than(fetch.last_run, fetch.this_run)
+                        })
+                })
+            });
+        }
+    }
+
+    #[inline]
+    unsafe fn set_table<'w, 's>(
+        fetch: &mut Self::Fetch<'w>,
+        &component_id: &'s ComponentId,
+        table: &'w Table,
+    ) {
+        // SAFETY: We have read access to the ticks of `T`, so no mutable reference to them exists.
+        fetch.changed =
+            unsafe { table.is_column_changed(component_id, fetch.last_run, fetch.this_run) };
+    }
+
+    #[inline]
+    fn update_component_access(&id: &ComponentId, access: &mut FilteredAccess) {
+        if access.access().has_component_write(id) {
+            panic!("$state_name<{}> conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.", DebugName::type_name::<T>());
+        }
+        access.add_component_read(id);
+    }
+
+    fn init_state(world: &mut World) -> ComponentId {
+        world.register_component::<T>()
+    }
+
+    fn get_state(components: &Components) -> Option<ComponentId> {
+        components.component_id::<T>()
+    }
+
+    fn matches_component_set(
+        &id: &ComponentId,
+        set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        set_contains_id(id)
+    }
+}
+
+// SAFETY: WorldQuery impl performs only read access on ticks
+unsafe impl<T: Component> QueryFilter for ArchetypeChanged<T> {
+    const IS_ARCHETYPAL: bool = false;
+
+    #[inline(always)]
+    unsafe fn filter_fetch(
+        _state: &Self::State,
+        fetch: &mut Self::Fetch<'_>,
+        _entity: Entity,
+        _table_row: TableRow,
+    ) -> bool {
+        fetch.changed
+    }
+}
+
 /// A filter that only retains results the first time after the entity has been spawned.
 ///
 /// A common use for this filter is one-time initialization.
@@ -2,10 +2,14 @@ use super::{QueryData, QueryFilter, ReadOnlyQueryData};
 use crate::{
     archetype::{Archetype, ArchetypeEntity, Archetypes},
     bundle::Bundle,
-    change_detection::Tick,
-    entity::{ContainsEntity, Entities, Entity, EntityEquivalent, EntitySet, EntitySetIterator},
+    change_detection::{DetectChanges, Ref, Tick},
+    entity::{
+        ContainsEntity, Entities, Entity, EntityEquivalent, EntityHashSet, EntitySet,
+        EntitySetIterator,
+    },
     query::{ArchetypeFilter, ArchetypeQueryData, DebugCheckedUnwrap, QueryState, StorageId},
     storage::{Table, TableRow, Tables},
+    system::Query,
     world::{
         unsafe_world_cell::UnsafeWorldCell, EntityMut, EntityMutExcept, EntityRef, EntityRefExcept,
         FilteredEntityMut, FilteredEntityRef,
@@ -992,11 +996,94 @@ impl<'w, 's, D: ReadOnlyQueryData, F: QueryFilter> Clone for QueryIter<'w, 's, D
     }
 }
 
+/// The cached sort order used by [`Query::iter_sorted_by_key`](crate::system::Query::iter_sorted_by_key).
+///
+/// The cache stores the key of every entity matched by the query, in sorted order. On each call,
+/// only the entities whose sorting component changed, or that started or stopped matching the
+/// query, have their key recomputed, and the existing order is merged with theirs instead of being
+/// rebuilt from scratch.
+///
+/// A cache should only be used with a single query and key function, which is easily achieved by
+/// storing it in a [`Local`](crate::system::Local).
+pub struct QuerySortCache<K> {
+    entries: Vec<(K, Entity)>,
+    cached: EntityHashSet,
+    seen: EntityHashSet,
+    dirty: Vec<(K, Entity)>,
+    last_update: Option<Tick>,
+}
+
+impl<K> Default for QuerySortCache<K> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            cached: EntityHashSet::default(),
+            seen: EntityHashSet::default(),
+            dirty: Vec::new(),
+            last_update: None,
+        }
+    }
+}
+
+impl<K: Ord> QuerySortCache<K> {
+    /// Returns the cached entities in sorted order.
+    pub fn entities(&self) -> impl ExactSizeIterator<Item = Entity> + DoubleEndedIterator + '_ {
+        self.entries.iter().map(|(_, entity)| *entity)
+    }
+
+    /// Clears the cache, forcing every key to be recomputed on the next use.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.cached.clear();
+        self.last_update = None;
+    }
+
+    /// Updates the cache from the `(entity, key component)` pairs currently matched by the query.
+    pub(crate) fn update<'a, C: 'a>(
+        &mut self,
+        items: impl Iterator<Item = (Entity, Ref<'a, C>)>,
+        mut key: impl FnMut(&C) -> K,
+        this_run: Tick,
+    ) {
+        self.seen.clear();
+        self.dirty.clear();
+        for (entity, component) in items {
+            self.seen.insert(entity);
+            let changed = self.last_update.is_none_or(|last_update| {
+                component
+                    .last_changed()
+                    .is_newer_than(last_update, this_run)
+            });
+            if changed {
+                // Stale entries are dropped below, and re-added with their new key.
+                self.cached.remove(&entity);
+            }
+            if !self.cached.contains(&entity) {
+                self.dirty.push((key(&component), entity));
+            }
+        }
+
+        if !self.dirty.is_empty() || self.seen.len() != self.cached.len() {
+            let (cached, seen) = (&self.cached, &self.seen);
+            self.entries
+                .retain(|(_, entity)| cached.contains(entity) && seen.contains(entity));
+            self.dirty.sort_by(|(a, _), (b, _)| a.cmp(b));
+            self.entries.append(&mut self.dirty);
+            // Both halves are sorted, which the stable sort merges in linear time.
+            self.entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            core::mem::swap(&mut self.cached, &mut self.seen);
+        }
+
+        self.last_update = Some(this_run);
+    }
+}
+
 /// An [`Iterator`] over sorted query results of a [`Query`](crate::system::Query).
 ///
 /// This struct is created by the [`QueryIter::sort`], [`QueryIter::sort_unstable`],
 /// [`QueryIter::sort_by`], [`QueryIter::sort_unstable_by`], [`QueryIter::sort_by_key`],
-/// [`QueryIter::sort_unstable_by_key`], and [`QueryIter::sort_by_cached_key`] methods.
+/// [`QueryIter::sort_unstable_by_key`], and [`QueryIter::sort_by_cached_key`] methods,
+/// as well as [`Query::iter_sorted_by_key`](crate::system::Query::iter_sorted_by_key).
 pub struct QuerySortedIter<'w, 's, D: QueryData, F: QueryFilter, I>
 where
     I: Iterator<Item = Entity>,
@@ -1152,6 +1239,70 @@ impl<'w, 's, D: QueryData, F: QueryFilter, I: Iterator<Item = Entity>> Debug
     }
 }
 
+/// An [`Iterator`] over pairs of query items, where the second item belongs to the entity that the
+/// first item refers to.
+///
+/// Items of the first query whose key entity does not match the second query are skipped.
+///
+/// This struct is created by the [`Query::join_on`](crate::system::Query::join_on) and
+/// [`Query::join_on_mut`](crate::system::Query::join_on_mut) methods.
+pub struct QueryJoinOnIter<'w, 's, 'jw, 'js, D: QueryData, F: QueryFilter, J, G, K>
+where
+    J: ReadOnlyQueryData,
+    G: QueryFilter,
+{
+    iter: QueryIter<'w, 's, D, F>,
+    other: Query<'jw, 'js, J, G>,
+    key: K,
+}
+
+impl<'w, 's, 'jw, 'js, D: QueryData, F: QueryFilter, J, G, K>
+    QueryJoinOnIter<'w, 's, 'jw, 'js, D, F, J, G, K>
+where
+    J: ReadOnlyQueryData,
+    G: QueryFilter,
+    K: FnMut(&D::Item<'w, 's>) -> Entity,
+{
+    pub(crate) fn new(iter: QueryIter<'w, 's, D, F>, other: Query<'jw, 'js, J, G>, key: K) -> Self {
+        Self { iter, other, key }
+    }
+}
+
+impl<'w, 's, 'jw, 'js, D: QueryData, F: QueryFilter, J, G, K> Iterator
+    for QueryJoinOnIter<'w, 's, 'jw, 'js, D, F, J, G, K>
+where
+    J: ReadOnlyQueryData,
+    G: QueryFilter,
+    K: FnMut(&D::Item<'w, 's>) -> Entity,
+{
+    type Item = (D::Item<'w, 's>, J::Item<'jw, 'js>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for item in self.iter.by_ref() {
+            let entity = (self.key)(&item);
+            if let Ok(joined) = self.other.get_inner(entity) {
+                return Some((item, joined));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, max_size) = self.iter.size_hint();
+        (0, max_size)
+    }
+}
+
+impl<'w, 's, 'jw, 'js, D: QueryData, F: QueryFilter, J, G, K> FusedIterator
+    for QueryJoinOnIter<'w, 's, 'jw, 'js, D, F, J, G, K>
+where
+    J: ReadOnlyQueryData,
+    G: QueryFilter,
+    K: FnMut(&D::Item<'w, 's>) -> Entity,
+{
+}
+
 /// An [`Iterator`] over the query items generated from an iterator of [`Entity`]s.
 ///
 /// Items are returned in the order of the provided iterator.
@@ -3046,4 +3197,69 @@ mod tests {
             while query.fetch_next().is_some() {}
         }
     }
+
+    #[test]
+    fn query_iter_sorted_by_key_cached() {
+        use super::QuerySortCache;
+        use crate::resource::Resource;
+        use crate::system::{Local, Query, ResMut, RunSystemOnce};
+
+        #[derive(Component)]
+        struct Key(u32);
+
+        #[derive(Resource, Default)]
+        struct Sorted {
+            order: Vec<u32>,
+            key_calls: usize,
+        }
+
+        fn sort_system(
+            query: Query<&Key>,
+            mut cache: Local<QuerySortCache<u32>>,
+            mut sorted: ResMut<Sorted>,
+        ) {
+            let mut key_calls = 0;
+            sorted.order = query
+                .iter_sorted_by_key(&mut cache, |key: &Key| {
+                    key_calls += 1;
+                    key.0
+                })
+                .map(|key| key.0)
+                .collect();
+            sorted.key_calls = key_calls;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Sorted>();
+        let entities: Vec<Entity> = [5, 1, 4, 2, 3]
+            .into_iter()
+            .map(|key| world.spawn(Key(key)).id())
+            .collect();
+        let mut schedule = crate::schedule::Schedule::default();
+        schedule.add_systems(sort_system);
+
+        schedule.run(&mut world);
+        let sorted = world.resource::<Sorted>();
+        assert_eq!(sorted.order, [1, 2, 3, 4, 5]);
+        assert_eq!(sorted.key_calls, 5);
+
+        schedule.run(&mut world);
+        let sorted = world.resource::<Sorted>();
+        assert_eq!(sorted.order, [1, 2, 3, 4, 5]);
+        assert_eq!(sorted.key_calls, 0);
+
+        world.get_mut::<Key>(entities[0]).unwrap().0 = 0;
+        world.despawn(entities[2]);
+        world.spawn(Key(6));
+        schedule.run(&mut world);
+        let sorted = world.resource::<Sorted>();
+        assert_eq!(sorted.order, [0, 1, 2, 3, 6]);
+        assert_eq!(sorted.key_calls, 2);
+
+        // Without a cache, every call sorts from scratch.
+        world.run_system_once(sort_system).unwrap();
+        let sorted = world.resource::<Sorted>();
+        assert_eq!(sorted.order, [0, 1, 2, 3, 6]);
+        assert_eq!(sorted.key_calls, 5);
+    }
 }
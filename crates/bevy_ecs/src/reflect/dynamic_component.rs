@@ -0,0 +1,551 @@
+//! Components defined at runtime, whose values are described through reflection.
+
+use alloc::{borrow::Cow, boxed::Box, string::String};
+use core::{alloc::Layout, any::TypeId, mem::needs_drop, ptr::NonNull};
+
+use bevy_platform::collections::HashMap;
+use bevy_ptr::OwningPtr;
+use bevy_reflect::{
+    FromReflect, FromType, PartialReflect, Reflect, ReflectFromPtr, ReflectFromReflect, TypeInfo,
+    TypePath, TypeRegistration, Typed,
+};
+use thiserror::Error;
+
+use crate::{
+    change_detection::Mut,
+    component::{ComponentCloneBehavior, ComponentDescriptor, ComponentId, StorageType},
+    entity::{ComponentCloneCtx, SourceComponent},
+    resource::Resource,
+    world::{EntityWorldMut, World},
+};
+
+/// A [`Resource`] storing the reflection data of every component registered with
+/// [`World::register_dynamic_component`] or [`World::register_dynamic_component_with_layout`].
+///
+/// Dynamic components are regular components as far as the ECS is concerned: they have their own
+/// [`ComponentId`], are stored in tables or sparse sets, take part in change detection and run
+/// their [hooks](crate::lifecycle::ComponentHooks) and observers. Unlike components defined by a
+/// Rust type, many dynamic components can share the same value type, which makes them suitable
+/// for scripting and modding, where component names are only known at runtime.
+///
+/// Dynamic components can be queried with [`QueryBuilder`](crate::query::QueryBuilder) using
+/// their [`ComponentId`], and read through reflection with [`EntityWorldMut::get_dynamic`].
+#[derive(Resource, Default)]
+pub struct DynamicComponentRegistry {
+    components: HashMap<ComponentId, DynamicComponentInfo>,
+}
+
+impl DynamicComponentRegistry {
+    /// Returns the [`DynamicComponentInfo`] of the dynamic component with the given `id`.
+    pub fn get(&self, id: ComponentId) -> Option<&DynamicComponentInfo> {
+        self.components.get(&id)
+    }
+
+    /// Returns `true` if `id` refers to a dynamic component.
+    pub fn contains(&self, id: ComponentId) -> bool {
+        self.components.contains_key(&id)
+    }
+
+    /// Iterates over all dynamic components and their [`DynamicComponentInfo`].
+    pub fn iter(&self) -> impl Iterator<Item = (ComponentId, &DynamicComponentInfo)> {
+        self.components.iter().map(|(id, info)| (*id, info))
+    }
+}
+
+/// Reflection data of a dynamic component, stored in the [`DynamicComponentRegistry`].
+#[derive(Clone)]
+pub struct DynamicComponentInfo {
+    type_info: &'static TypeInfo,
+    layout: Layout,
+    from_ptr: ReflectFromPtr,
+    from_reflect: ReflectFromReflect,
+}
+
+impl DynamicComponentInfo {
+    fn new<T: FromReflect + Typed + TypePath>() -> Self {
+        Self {
+            type_info: T::type_info(),
+            layout: Layout::new::<T>(),
+            from_ptr: <ReflectFromPtr as FromType<T>>::from_type(),
+            from_reflect: <ReflectFromReflect as FromType<T>>::from_type(),
+        }
+    }
+
+    fn from_registration(
+        layout: Layout,
+        registration: &TypeRegistration,
+    ) -> Result<Self, DynamicComponentError> {
+        let missing = |type_data| DynamicComponentError::MissingTypeData {
+            type_path: registration.type_info().type_path(),
+            type_data,
+        };
+        Ok(Self {
+            type_info: registration.type_info(),
+            layout,
+            from_ptr: registration
+                .data::<ReflectFromPtr>()
+                .ok_or_else(|| missing("ReflectFromPtr"))?
+                .clone(),
+            from_reflect: registration
+                .data::<ReflectFromReflect>()
+                .ok_or_else(|| missing("ReflectFromReflect"))?
+                .clone(),
+        })
+    }
+
+    /// The [`TypeInfo`] of the values stored in this component.
+    pub fn type_info(&self) -> &'static TypeInfo {
+        self.type_info
+    }
+
+    /// The [`TypeId`] of the values stored in this component.
+    pub fn type_id(&self) -> TypeId {
+        self.type_info.type_id()
+    }
+
+    /// The [`Layout`] of the values stored in this component.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// The [`ReflectFromPtr`] used to reflect the values stored in this component.
+    pub fn from_ptr(&self) -> &ReflectFromPtr {
+        &self.from_ptr
+    }
+
+    /// Converts `value` to the value type of the component and inserts it as the component `id`.
+    ///
+    /// Returns `false` if `value` cannot be converted.
+    fn insert(
+        &self,
+        entity: &mut EntityWorldMut,
+        id: ComponentId,
+        value: &dyn PartialReflect,
+    ) -> bool {
+        let Some(value) = self.from_reflect.from_reflect(value) else {
+            return false;
+        };
+        debug_assert_eq!(Layout::for_value(&*value), self.layout);
+        let data = Box::into_raw(value).cast::<u8>();
+        // SAFETY:
+        // - `data` comes from a `Box`, so it is non-null, aligned and points to an owned value.
+        // - `id` was registered with the layout and drop function of the value type, which is the
+        //   type produced by `from_reflect`.
+        unsafe { entity.insert_by_id(id, OwningPtr::new(NonNull::new_unchecked(data))) };
+        if self.layout.size() > 0 {
+            // SAFETY: The value was moved into the component, so its allocation is freed without
+            // dropping it. Zero-sized values were never allocated.
+            unsafe { alloc::alloc::dealloc(data, self.layout) };
+        }
+        true
+    }
+}
+
+fn clone_from_reflect<T: FromReflect>(source: &SourceComponent, ctx: &mut ComponentCloneCtx) {
+    // SAFETY: This clone function is only used for components registered with the layout of `T`.
+    let value = unsafe { source.ptr().deref::<T>() };
+    let Some(value) = T::from_reflect(value) else {
+        return;
+    };
+    OwningPtr::make(value, |ptr| {
+        // SAFETY: `ptr` points to an owned `T`, which matches the component's type.
+        unsafe { ctx.write_target_component_ptr(ptr.as_ref()) };
+    });
+}
+
+/// Clones a component registered with [`World::register_dynamic_component_with_layout`].
+///
+/// Without a Rust type to convert to, the value is cloned through reflection once the other
+/// components have been cloned, while the source entity still has the component.
+fn clone_dynamic(_source: &SourceComponent, ctx: &mut ComponentCloneCtx) {
+    let id = ctx.component_id();
+    let (source, target) = (ctx.source(), ctx.target());
+    ctx.queue_deferred(move |world, _| {
+        let Some(info) = world
+            .get_resource::<DynamicComponentRegistry>()
+            .and_then(|registry| registry.get(id))
+            .cloned()
+        else {
+            return;
+        };
+        let Some(value) = world
+            .get_entity(source)
+            .ok()
+            .and_then(|source| source.get_by_id(id).ok())
+            .and_then(|ptr| {
+                // SAFETY: `from_ptr` was created for the value type of the component `id`.
+                unsafe { info.from_ptr.as_reflect(ptr) }
+                    .reflect_clone()
+                    .ok()
+            })
+        else {
+            return;
+        };
+        if let Ok(mut target) = world.get_entity_mut(target) {
+            info.insert(&mut target, id, value.as_partial_reflect());
+        }
+    });
+}
+
+/// An error returned when interacting with a dynamic component through reflection.
+#[derive(Error, Debug)]
+pub enum DynamicComponentError {
+    /// The component was not registered with [`World::register_dynamic_component`] or
+    /// [`World::register_dynamic_component_with_layout`].
+    #[error("Component {0:?} is not a dynamic component")]
+    NotDynamic(ComponentId),
+    /// The provided value could not be converted to the value type of the component.
+    #[error("Value of type `{actual}` cannot be converted to `{expected}`")]
+    MismatchedType {
+        /// The type path of the component's value type.
+        expected: &'static str,
+        /// The type path of the provided value.
+        actual: String,
+    },
+    /// The [`TypeRegistration`] of the component's value type lacks some required type data.
+    #[error("Type `{type_path}` is missing the `{type_data}` type data")]
+    MissingTypeData {
+        /// The type path of the component's value type.
+        type_path: &'static str,
+        /// The name of the missing type data.
+        type_data: &'static str,
+    },
+}
+
+impl World {
+    /// Registers a new component at runtime, storing values of the reflected type `T`.
+    ///
+    /// Each call registers a distinct component, even for the same `T`: the component is
+    /// identified by the returned [`ComponentId`] rather than by `T`. Insert values with
+    /// [`EntityWorldMut::insert_dynamic`], query it with [`QueryBuilder`](crate::query::QueryBuilder),
+    /// and attach hooks with [`World::register_component_hooks_by_id`].
+    ///
+    /// The reflection data of the component is stored in the [`DynamicComponentRegistry`] resource.
+    ///
+    /// ```
+    /// # use bevy_ecs::{prelude::*, component::StorageType};
+    /// let mut world = World::new();
+    /// let health = world.register_dynamic_component::<f32>("Health", StorageType::Table);
+    ///
+    /// let mut entity = world.spawn_empty();
+    /// entity.insert_dynamic(health, &100.0f32).unwrap();
+    /// let value = entity.get_dynamic(health).unwrap();
+    /// assert_eq!(value.downcast_ref::<f32>(), Some(&100.0));
+    /// ```
+    pub fn register_dynamic_component<T: FromReflect + Typed + TypePath>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        storage_type: StorageType,
+    ) -> ComponentId {
+        // SAFETY:
+        // - the drop function matches `Layout::new::<T>()`
+        // - `T: Reflect` implies `T: Send + Sync`
+        // - no relationship accessor is provided
+        let descriptor = unsafe {
+            ComponentDescriptor::new_with_layout(
+                name,
+                storage_type,
+                Layout::new::<T>(),
+                needs_drop::<T>().then_some(|ptr: OwningPtr<'_>| ptr.drop_as::<T>()),
+                true,
+                ComponentCloneBehavior::Custom(clone_from_reflect::<T>),
+                None,
+            )
+        };
+        let id = self.register_component_with_descriptor(descriptor);
+        self.get_resource_or_init::<DynamicComponentRegistry>()
+            .components
+            .insert(id, DynamicComponentInfo::new::<T>());
+        id
+    }
+
+    /// Registers a new component at runtime, storing values of the type described by
+    /// `registration`.
+    ///
+    /// Unlike [`World::register_dynamic_component`], the value type doesn't need to be known at
+    /// compile time, which lets a scripting or modding layer create components for any type found
+    /// in a [`TypeRegistry`](bevy_reflect::TypeRegistry). The registration must provide the
+    /// [`ReflectFromPtr`] and [`ReflectFromReflect`] type data, which are registered by
+    /// `#[derive(Reflect)]`.
+    ///
+    /// Values are cloned through reflection when entities are cloned.
+    ///
+    /// ```
+    /// # use bevy_ecs::{prelude::*, component::StorageType};
+    /// # use bevy_reflect::{GetTypeRegistration, Reflect};
+    /// # use core::alloc::Layout;
+    /// #[derive(Reflect)]
+    /// struct Health(f32);
+    ///
+    /// // In a scripting layer, the registration would come from the `AppTypeRegistry`.
+    /// let registration = Health::get_type_registration();
+    ///
+    /// let mut world = World::new();
+    /// // SAFETY: `Health` has this layout and doesn't need to be dropped.
+    /// let health = unsafe {
+    ///     world.register_dynamic_component_with_layout(
+    ///         "Health",
+    ///         StorageType::Table,
+    ///         Layout::new::<Health>(),
+    ///         None,
+    ///         &registration,
+    ///     )
+    /// }
+    /// .unwrap();
+    ///
+    /// let mut entity = world.spawn_empty();
+    /// entity.insert_dynamic(health, &Health(100.0)).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DynamicComponentError::MissingTypeData`] if `registration` lacks
+    /// [`ReflectFromPtr`] or [`ReflectFromReflect`].
+    ///
+    /// # Safety
+    ///
+    /// - `layout` must be the layout of the type described by `registration`.
+    /// - `drop` must be usable on a pointer to a value of that type, and must be `Some` if the type
+    ///   needs to be dropped.
+    pub unsafe fn register_dynamic_component_with_layout(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        storage_type: StorageType,
+        layout: Layout,
+        drop: Option<for<'a> unsafe fn(OwningPtr<'a>)>,
+        registration: &TypeRegistration,
+    ) -> Result<ComponentId, DynamicComponentError> {
+        let info = DynamicComponentInfo::from_registration(layout, registration)?;
+        // SAFETY:
+        // - the caller guarantees that the drop function matches `layout`
+        // - reflected types are `Send + Sync`
+        // - no relationship accessor is provided
+        let descriptor = unsafe {
+            ComponentDescriptor::new_with_layout(
+                name,
+                storage_type,
+                layout,
+                drop,
+                true,
+                ComponentCloneBehavior::Custom(clone_dynamic),
+                None,
+            )
+        };
+        let id = self.register_component_with_descriptor(descriptor);
+        self.get_resource_or_init::<DynamicComponentRegistry>()
+            .components
+            .insert(id, info);
+        Ok(id)
+    }
+}
+
+impl<'w> EntityWorldMut<'w> {
+    /// Inserts the dynamic component `id`, converting `value` to the component's value type
+    /// using [`FromReflect`].
+    ///
+    /// This will overwrite any previous value of the component, running hooks and observers and
+    /// updating change detection ticks exactly like [`EntityWorldMut::insert`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DynamicComponentError`] if `id` is not a dynamic component or if `value` cannot be
+    /// converted.
+    ///
+    /// # Panics
+    ///
+    /// If the entity has been despawned while this `EntityWorldMut` is still alive.
+    pub fn insert_dynamic(
+        &mut self,
+        id: ComponentId,
+        value: &dyn PartialReflect,
+    ) -> Result<&mut Self, DynamicComponentError> {
+        let info = self.dynamic_component_info(id)?;
+        if info.insert(self, id, value) {
+            Ok(self)
+        } else {
+            Err(DynamicComponentError::MismatchedType {
+                expected: info.type_info.type_path(),
+                actual: String::from(value.reflect_type_path()),
+            })
+        }
+    }
+
+    /// Gets a reflected reference to the value of the dynamic component `id`.
+    ///
+    /// Returns `None` if the entity does not have the component or if `id` is not a dynamic component.
+    ///
+    /// # Panics
+    ///
+    /// If the entity has been despawned while this `EntityWorldMut` is still alive.
+    pub fn get_dynamic(&self, id: ComponentId) -> Option<&dyn Reflect> {
+        let info = self.dynamic_component_info(id).ok()?;
+        let ptr = self.get_by_id(id).ok()?;
+        // SAFETY: `from_ptr` was created for the value type of the component `id`.
+        Some(unsafe { info.from_ptr.as_reflect(ptr) })
+    }
+
+    /// Gets a reflected mutable reference to the value of the dynamic component `id`.
+    ///
+    /// Returns `None` if the entity does not have the component or if `id` is not a dynamic component.
+    ///
+    /// # Panics
+    ///
+    /// If the entity has been despawned while this `EntityWorldMut` is still alive.
+    pub fn get_dynamic_mut(&mut self, id: ComponentId) -> Option<Mut<'_, dyn Reflect>> {
+        let info = self.dynamic_component_info(id).ok()?;
+        let value = self.get_mut_by_id(id).ok()?;
+        // SAFETY: `from_ptr` was created for the value type of the component `id`.
+        Some(value.map_unchanged(|ptr| unsafe { info.from_ptr.as_reflect_mut(ptr) }))
+    }
+
+    fn dynamic_component_info(
+        &self,
+        id: ComponentId,
+    ) -> Result<DynamicComponentInfo, DynamicComponentError> {
+        self.world()
+            .get_resource::<DynamicComponentRegistry>()
+            .and_then(|registry| registry.get(id))
+            .cloned()
+            .ok_or(DynamicComponentError::NotDynamic(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        change_detection::DetectChanges, entity::Entity, lifecycle::HookContext,
+        query::QueryBuilder, world::DeferredWorld,
+    };
+    use bevy_reflect::{DynamicStruct, GetTypeRegistration};
+
+    #[derive(Reflect, Debug, PartialEq, Default)]
+    struct Stats {
+        health: f32,
+        armor: u32,
+    }
+
+    #[test]
+    fn insert_and_query_dynamic_component() {
+        let mut world = World::new();
+        let stats = world.register_dynamic_component::<Stats>("Stats", StorageType::Table);
+        let other = world.register_dynamic_component::<Stats>("Other", StorageType::SparseSet);
+        assert_ne!(stats, other);
+
+        let mut value = DynamicStruct::default();
+        value.insert("health", 10.0f32);
+        value.insert("armor", 2u32);
+
+        let entity = world
+            .spawn_empty()
+            .insert_dynamic(stats, &value)
+            .unwrap()
+            .id();
+        world.spawn_empty().insert_dynamic(other, &value).unwrap();
+
+        let mut query = QueryBuilder::<Entity>::new(&mut world)
+            .ref_id(stats)
+            .build();
+        assert_eq!(query.iter(&world).collect::<alloc::vec::Vec<_>>(), [entity]);
+
+        let mut entity = world.entity_mut(entity);
+        assert_eq!(
+            entity.get_dynamic(stats).unwrap().downcast_ref::<Stats>(),
+            Some(&Stats {
+                health: 10.0,
+                armor: 2
+            })
+        );
+        assert!(entity.get_dynamic(other).is_none());
+        assert!(matches!(
+            entity.insert_dynamic(stats, &1u32),
+            Err(DynamicComponentError::MismatchedType { .. })
+        ));
+    }
+
+    #[test]
+    fn dynamic_component_from_registration() {
+        #[derive(Reflect, Debug, PartialEq)]
+        struct Name(String);
+
+        let mut world = World::new();
+        // SAFETY: The layout and drop function are the ones of `Name`.
+        let name = unsafe {
+            world.register_dynamic_component_with_layout(
+                "Name",
+                StorageType::Table,
+                Layout::new::<Name>(),
+                Some(|ptr: OwningPtr<'_>| ptr.drop_as::<Name>()),
+                &Name::get_type_registration(),
+            )
+        }
+        .unwrap();
+
+        let entity = world
+            .spawn_empty()
+            .insert_dynamic(name, &Name(String::from("Ferris")))
+            .unwrap()
+            .id();
+        let clone = world.spawn_empty().id();
+        world.entity_mut(entity).clone_with_opt_out(clone, |_| {});
+
+        for entity in [entity, clone] {
+            assert_eq!(
+                world
+                    .entity_mut(entity)
+                    .get_dynamic(name)
+                    .unwrap()
+                    .downcast_ref::<Name>(),
+                Some(&Name(String::from("Ferris")))
+            );
+        }
+
+        let mut registration = TypeRegistration::of::<Name>();
+        registration.insert(<ReflectFromPtr as FromType<Name>>::from_type());
+        // SAFETY: The layout and drop function are the ones of `Name`.
+        let result = unsafe {
+            world.register_dynamic_component_with_layout(
+                "Name",
+                StorageType::Table,
+                Layout::new::<Name>(),
+                Some(|ptr: OwningPtr<'_>| ptr.drop_as::<Name>()),
+                &registration,
+            )
+        };
+        assert!(matches!(
+            result,
+            Err(DynamicComponentError::MissingTypeData { .. })
+        ));
+    }
+
+    #[test]
+    fn dynamic_component_change_detection_and_hooks() {
+        #[derive(Resource, Default)]
+        struct Added(usize);
+
+        let mut world = World::new();
+        world.init_resource::<Added>();
+        let stats = world.register_dynamic_component::<Stats>("Stats", StorageType::Table);
+        world.register_component_hooks_by_id(stats).unwrap().on_add(
+            |mut world: DeferredWorld, _: HookContext| {
+                world.resource_mut::<Added>().0 += 1;
+            },
+        );
+
+        let entity = world.spawn_empty().id();
+        world
+            .entity_mut(entity)
+            .insert_dynamic(stats, &Stats::default())
+            .unwrap();
+        assert_eq!(world.resource::<Added>().0, 1);
+
+        world.clear_trackers();
+        let mut entity = world.entity_mut(entity);
+        assert!(!entity.get_mut_by_id(stats).unwrap().is_changed());
+        entity.get_dynamic_mut(stats).unwrap().apply(&Stats {
+            health: 1.0,
+            armor: 0,
+        });
+        assert!(entity.get_mut_by_id(stats).unwrap().is_changed());
+    }
+}
@@ -1,5 +1,5 @@
-use crate::entity::{EntityMapper, MapEntities};
-use bevy_reflect::{FromReflect, FromType, PartialReflect};
+use crate::entity::{Entity, EntityMapper, MapEntities};
+use bevy_reflect::{FromReflect, FromType, PartialReflect, ReflectMut};
 
 /// For a specific type of value, this maps any fields with values of type [`Entity`] to a new world.
 ///
@@ -36,3 +36,64 @@ impl<C: FromReflect + MapEntities> FromType<C> for ReflectMapEntities {
         }
     }
 }
+
+/// Maps every [`Entity`] found in the fields of a reflected value, without requiring the value's
+/// type to implement [`MapEntities`].
+///
+/// The value is walked recursively through its structs, tuples, lists, arrays, enums and map values.
+/// Entities used as map keys or stored in sets are left untouched, as they cannot be mutated in place.
+pub fn map_reflected_entities(reflected: &mut dyn PartialReflect, mapper: &mut dyn EntityMapper) {
+    if let Some(entity) = reflected.try_downcast_mut::<Entity>() {
+        *entity = mapper.get_mapped(*entity);
+        return;
+    }
+    match reflected.reflect_mut() {
+        ReflectMut::Struct(value) => {
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_at_mut(index) {
+                    map_reflected_entities(field, mapper);
+                }
+            }
+        }
+        ReflectMut::TupleStruct(value) => {
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_mut(index) {
+                    map_reflected_entities(field, mapper);
+                }
+            }
+        }
+        ReflectMut::Tuple(value) => {
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_mut(index) {
+                    map_reflected_entities(field, mapper);
+                }
+            }
+        }
+        ReflectMut::List(value) => {
+            for index in 0..value.len() {
+                if let Some(item) = value.get_mut(index) {
+                    map_reflected_entities(item, mapper);
+                }
+            }
+        }
+        ReflectMut::Array(value) => {
+            for index in 0..value.len() {
+                if let Some(item) = value.get_mut(index) {
+                    map_reflected_entities(item, mapper);
+                }
+            }
+        }
+        ReflectMut::Enum(value) => {
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_at_mut(index) {
+                    map_reflected_entities(field, mapper);
+                }
+            }
+        }
+        ReflectMut::Map(value) => value.retain(&mut |_, item| {
+            map_reflected_entities(item, mapper);
+            true
+        }),
+        _ => {}
+    }
+}
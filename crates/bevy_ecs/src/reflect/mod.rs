@@ -13,6 +13,7 @@ use bevy_reflect::{
 
 mod bundle;
 mod component;
+mod dynamic_component;
 mod entity_commands;
 mod from_world;
 mod map_entities;
@@ -21,9 +22,12 @@ mod resource;
 use bevy_utils::prelude::DebugName;
 pub use bundle::{ReflectBundle, ReflectBundleFns};
 pub use component::{ReflectComponent, ReflectComponentFns};
+pub use dynamic_component::{
+    DynamicComponentError, DynamicComponentInfo, DynamicComponentRegistry,
+};
 pub use entity_commands::ReflectCommandExt;
 pub use from_world::{ReflectFromWorld, ReflectFromWorldFns};
-pub use map_entities::ReflectMapEntities;
+pub use map_entities::{map_reflected_entities, ReflectMapEntities};
 pub use resource::{ReflectResource, ReflectResourceFns};
 
 /// A [`Resource`] storing [`TypeRegistry`] for
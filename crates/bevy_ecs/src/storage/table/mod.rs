@@ -482,6 +482,29 @@ impl Table {
         })
     }
 
+    /// Returns `true` if the component matching `component_id` was added or changed on any row
+    /// of this table after `last_run`.
+    ///
+    /// This only reads the column's change ticks, which are stored contiguously, and stops at the
+    /// first changed row. Returns `false` if the component does not belong to the table.
+    ///
+    /// # Safety
+    /// - No mutable reference to the change ticks of the column may exist for the duration of the call.
+    pub unsafe fn is_column_changed(
+        &self,
+        component_id: ComponentId,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> bool {
+        self.get_changed_ticks_slice_for(component_id)
+            .is_some_and(|ticks| {
+                ticks
+                    .iter()
+                    // SAFETY: The caller ensures there is no mutable access to the ticks.
+                    .any(|tick| unsafe { tick.read() }.is_newer_than(last_run, this_run))
+            })
+    }
+
     /// Fetches a read-only reference to the [`Column`] for a given [`Component`] within the table.
     ///
     /// Returns `None` if the corresponding component does not belong to the table.
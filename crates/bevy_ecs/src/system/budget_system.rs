@@ -0,0 +1,247 @@
+use alloc::vec::Vec;
+use bevy_platform::time::Instant;
+use bevy_utils::prelude::DebugName;
+use core::time::Duration;
+use log::warn;
+
+use crate::{
+    change_detection::{CheckChangeTicks, Tick},
+    event::Event,
+    query::{Access, FilteredAccessSet},
+    schedule::InternedSystemSet,
+    system::{input::SystemIn, RunSystemError, System, SystemParamValidationError},
+    world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld, World},
+};
+
+use super::{IntoSystem, ReadOnlySystem, SystemStateFlags};
+
+/// An [`Event`] triggered when a system wrapped with [`IntoSystem::with_budget`] runs for longer
+/// than its budget.
+///
+/// The event is triggered when the system's deferred buffers are applied, which is usually at the
+/// next sync point of the schedule.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, system::SystemBudgetExceeded};
+/// fn report_slow_systems(event: On<SystemBudgetExceeded>) {
+///     // Forward the overflow to a profiler, a diagnostic overlay, etc.
+///     let _ = (&event.system, event.elapsed, event.budget);
+/// }
+///
+/// let mut world = World::new();
+/// world.add_observer(report_slow_systems);
+/// ```
+#[derive(Event, Debug, Clone)]
+pub struct SystemBudgetExceeded {
+    /// The name of the system that exceeded its budget.
+    pub system: DebugName,
+    /// The budget of the system.
+    pub budget: Duration,
+    /// How long the system ran for, excluding the application of its deferred buffers.
+    pub elapsed: Duration,
+    /// The number of archetypes containing at least one component accessed by the system.
+    ///
+    /// A large number of archetypes is a common cause of slow queries, as each of them has to be
+    /// visited separately.
+    pub archetype_count: usize,
+}
+
+/// A [`System`] that measures the run time of `S` and triggers [`SystemBudgetExceeded`] when it
+/// exceeds its budget.
+///
+/// See [`IntoSystem::with_budget`] for details.
+pub struct BudgetedSystem<S> {
+    system: S,
+    budget: Duration,
+    access: Access,
+    overflow: Option<Duration>,
+    reported: bool,
+}
+
+impl<S: System> BudgetedSystem<S> {
+    /// Wraps the given system with the given time budget.
+    pub fn new<M>(system: impl IntoSystem<S::In, S::Out, M, System = S>, budget: Duration) -> Self {
+        Self {
+            system: IntoSystem::into_system(system),
+            budget,
+            access: Access::default(),
+            overflow: None,
+            reported: false,
+        }
+    }
+
+    /// Returns the time budget of the system.
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    /// Sets the time budget of the system.
+    pub fn set_budget(&mut self, budget: Duration) {
+        self.budget = budget;
+    }
+
+    fn report_overflow(&mut self, world: &World) -> Option<SystemBudgetExceeded> {
+        let elapsed = self.overflow.take()?;
+        let archetype_count = world
+            .archetypes()
+            .iter()
+            .filter(|archetype| {
+                archetype
+                    .components()
+                    .iter()
+                    .any(|&id| self.access.has_component_read(id))
+            })
+            .count();
+        let event = SystemBudgetExceeded {
+            system: self.system.name(),
+            budget: self.budget,
+            elapsed,
+            archetype_count,
+        };
+        // Only the first overflow is logged, to avoid flooding the logs every frame.
+        if !self.reported {
+            self.reported = true;
+            warn!(
+                "System `{}` took {:?}, exceeding its budget of {:?} ({} archetypes accessed)",
+                event.system, event.elapsed, event.budget, event.archetype_count
+            );
+        }
+        Some(event)
+    }
+}
+
+impl<S: System> System for BudgetedSystem<S> {
+    type In = S::In;
+    type Out = S::Out;
+
+    fn name(&self) -> DebugName {
+        self.system.name()
+    }
+
+    #[inline]
+    fn flags(&self) -> SystemStateFlags {
+        self.system.flags()
+    }
+
+    unsafe fn run_unsafe(
+        &mut self,
+        input: SystemIn<'_, Self>,
+        world: UnsafeWorldCell,
+    ) -> Result<Self::Out, RunSystemError> {
+        let start = Instant::now();
+        // SAFETY: `system.run_unsafe` has the same invariants as `self.run_unsafe`.
+        let out = unsafe { self.system.run_unsafe(input, world) };
+        let elapsed = start.elapsed();
+        if elapsed > self.budget {
+            self.overflow = Some(
+                self.overflow
+                    .map_or(elapsed, |overflow| overflow.max(elapsed)),
+            );
+        }
+        out
+    }
+
+    #[cfg(feature = "hotpatching")]
+    #[inline]
+    fn refresh_hotpatch(&mut self) {
+        self.system.refresh_hotpatch();
+    }
+
+    fn apply_deferred(&mut self, world: &mut World) {
+        self.system.apply_deferred(world);
+        if let Some(event) = self.report_overflow(world) {
+            world.trigger(event);
+        }
+    }
+
+    fn queue_deferred(&mut self, mut world: DeferredWorld) {
+        self.system.queue_deferred(world.reborrow());
+        if let Some(event) = self.report_overflow(&world) {
+            world.commands().trigger(event);
+        }
+    }
+
+    #[inline]
+    unsafe fn validate_param_unsafe(
+        &mut self,
+        world: UnsafeWorldCell,
+    ) -> Result<(), SystemParamValidationError> {
+        // SAFETY: Delegate to other `System` implementations.
+        unsafe { self.system.validate_param_unsafe(world) }
+    }
+
+    fn initialize(&mut self, world: &mut World) -> FilteredAccessSet {
+        let access = self.system.initialize(world);
+        self.access = access.combined_access().clone();
+        access
+    }
+
+    fn check_change_tick(&mut self, check: CheckChangeTicks) {
+        self.system.check_change_tick(check);
+    }
+
+    fn default_system_sets(&self) -> Vec<InternedSystemSet> {
+        self.system.default_system_sets()
+    }
+
+    fn get_last_run(&self) -> Tick {
+        self.system.get_last_run()
+    }
+
+    fn set_last_run(&mut self, last_run: Tick) {
+        self.system.set_last_run(last_run);
+    }
+}
+
+// SAFETY: The inner system is read-only.
+unsafe impl<S: ReadOnlySystem> ReadOnlySystem for BudgetedSystem<S> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        observer::On,
+        prelude::{Component, Query, ResMut, Resource, Schedule},
+    };
+
+    #[derive(Resource, Default)]
+    struct Overflows(Vec<SystemBudgetExceeded>);
+
+    #[derive(Component)]
+    struct A;
+
+    #[derive(Component)]
+    struct B;
+
+    fn slow_system(_: Query<&A>) {
+        std::thread::sleep(Duration::from_millis(5));
+    }
+
+    #[test]
+    fn budget_overflow_is_reported() {
+        let mut world = World::new();
+        world.init_resource::<Overflows>();
+        world.add_observer(
+            |event: On<SystemBudgetExceeded>, mut overflows: ResMut<Overflows>| {
+                overflows.0.push(event.clone());
+            },
+        );
+        world.spawn(A);
+        world.spawn((A, B));
+        world.spawn(B);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((
+            slow_system.with_budget(Duration::from_millis(1)),
+            slow_system.with_budget(Duration::from_secs(60)),
+        ));
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        let overflows = &world.resource::<Overflows>().0;
+        assert_eq!(overflows.len(), 2);
+        assert_eq!(overflows[0].budget, Duration::from_millis(1));
+        assert!(overflows[0].elapsed >= Duration::from_millis(5));
+        assert_eq!(overflows[0].archetype_count, 2);
+    }
+}
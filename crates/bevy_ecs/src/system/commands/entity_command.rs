@@ -296,6 +296,16 @@ pub fn clone_with_opt_in(
     }
 }
 
+/// An [`EntityCommand`] that clones an entity onto another entity, and its
+/// descendants onto new children of that entity.
+///
+/// See [`EntityCloner::clone_hierarchy`](crate::entity::EntityCloner::clone_hierarchy) for details.
+pub fn clone_hierarchy(target: Entity) -> impl EntityCommand {
+    move |mut entity: EntityWorldMut| {
+        entity.clone_hierarchy(target);
+    }
+}
+
 /// An [`EntityCommand`] that clones the specified components of an entity
 /// and inserts them into another entity.
 pub fn clone_components<B: Bundle>(target: Entity) -> impl EntityCommand {
@@ -2246,6 +2246,39 @@ impl<'a> EntityCommands<'a> {
         }
     }
 
+    /// Spawns a clone of this entity and all of its descendants, returning the
+    /// [`EntityCommands`] of the clone.
+    ///
+    /// Entity references between members of the hierarchy are remapped to their clones.
+    /// See [`EntityCloner::clone_hierarchy`](crate::entity::EntityCloner::clone_hierarchy) for details.
+    ///
+    /// # Note
+    ///
+    /// If the original entity does not exist when this command is applied,
+    /// the returned entity will have no components.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component, Clone)]
+    /// struct Turret;
+    ///
+    /// fn duplicate_turret(mut commands: Commands, turret: Single<Entity, With<Turret>>) {
+    ///     // Clones the turret along with its children.
+    ///     commands.entity(*turret).clone_hierarchy();
+    /// }
+    /// # bevy_ecs::system::assert_is_system(duplicate_turret);
+    /// ```
+    pub fn clone_hierarchy(&mut self) -> EntityCommands<'_> {
+        let entity_clone = self.commands().spawn_empty().id();
+        self.queue(entity_command::clone_hierarchy(entity_clone));
+        EntityCommands {
+            commands: self.commands_mut().reborrow(),
+            entity: entity_clone,
+        }
+    }
+
     /// Clones the specified components of this entity and inserts them into another entity.
     ///
     /// Components can only be cloned if they implement
@@ -120,6 +120,7 @@
 //! [`Vec<P>`]: alloc::vec::Vec
 
 mod adapter_system;
+mod budget_system;
 mod builder;
 mod combinator;
 mod commands;
@@ -135,9 +136,10 @@ mod system_name;
 mod system_param;
 mod system_registry;
 
-use core::any::TypeId;
+use core::{any::TypeId, time::Duration};
 
 pub use adapter_system::*;
+pub use budget_system::*;
 pub use builder::*;
 pub use combinator::*;
 pub use commands::*;
@@ -228,6 +230,31 @@ pub trait IntoSystem<In: SystemInput, Out, Marker>: Sized {
         IntoAdapterSystem::new(f, self)
     }
 
+    /// Measures how long this system takes to run, triggering a [`SystemBudgetExceeded`] event
+    /// whenever it runs for longer than `budget`.
+    ///
+    /// This is an opt-in tool to catch performance regressions early: the event reports how long
+    /// the system ran for, and how many archetypes contain the components it accesses. The first
+    /// overflow of each system is also logged as a warning.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use core::time::Duration;
+    /// # #[derive(Component)]
+    /// # struct Velocity;
+    /// fn movement(query: Query<&Velocity>) {
+    ///     // ...
+    /// }
+    ///
+    /// # let mut schedule = Schedule::default();
+    /// schedule.add_systems(movement.with_budget(Duration::from_millis(2)));
+    /// ```
+    fn with_budget(self, budget: Duration) -> BudgetedSystem<Self::System> {
+        BudgetedSystem::new(self, budget)
+    }
+
     /// Passes a mutable reference to `value` as input to the system each run,
     /// turning it into a system that takes no input.
     ///
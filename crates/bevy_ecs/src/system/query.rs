@@ -2,12 +2,14 @@ use bevy_utils::prelude::DebugName;
 
 use crate::{
     batching::BatchingStrategy,
-    change_detection::Tick,
+    change_detection::{Ref, Tick},
+    component::Component,
     entity::{Entity, EntityEquivalent, EntitySet, UniqueEntityArray},
     query::{
         DebugCheckedUnwrap, NopWorldQuery, QueryCombinationIter, QueryData, QueryEntityError,
-        QueryFilter, QueryIter, QueryManyIter, QueryManyUniqueIter, QueryParIter, QueryParManyIter,
-        QueryParManyUniqueIter, QuerySingleError, QueryState, ROQueryItem, ReadOnlyQueryData,
+        QueryFilter, QueryIter, QueryJoinOnIter, QueryManyIter, QueryManyUniqueIter, QueryParIter,
+        QueryParManyIter, QueryParManyUniqueIter, QuerySingleError, QuerySortCache,
+        QuerySortedIter, QueryState, ROQueryItem, ReadOnlyQueryData,
     },
     world::unsafe_world_cell::UnsafeWorldCell,
 };
@@ -708,6 +710,218 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
         self.reborrow().into_iter()
     }
 
+    /// Returns an [`Iterator`] over the read-only query items, sorted by a key computed from the
+    /// component `C` of each entity.
+    ///
+    /// Unlike [`QueryIter::sort_by_key`], the sort order is kept in `cache` between calls. Keys are
+    /// only recomputed for entities whose `C` changed or that started matching the query since the
+    /// last call, and these are merged into the existing order, so sorting a large, mostly static
+    /// set of entities every frame is cheap. The sort is stable with respect to the cached order.
+    ///
+    /// The same `cache` must only be used with this query and the same `key` function, typically by
+    /// storing it in a [`Local`](crate::system::Local).
+    ///
+    /// Like [`Changed`](crate::query::Changed), changes to `C` made by the system that owns the
+    /// cache are not detected on its next run, so such changes should not affect the key.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if the query does not have read access to `C`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::query::QuerySortCache;
+    /// #[derive(Component)]
+    /// struct Depth(u32);
+    ///
+    /// #[derive(Component)]
+    /// struct Name(&'static str);
+    ///
+    /// fn draw_in_order(
+    ///     query: Query<(&Name, &Depth)>,
+    ///     mut cache: Local<QuerySortCache<u32>>,
+    /// ) {
+    ///     for (name, _) in query.iter_sorted_by_key(&mut cache, |depth: &Depth| depth.0) {
+    ///         // Drawn from back to front.
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(draw_in_order);
+    /// ```
+    ///
+    /// [`QueryIter::sort_by_key`]: crate::query::QueryIter::sort_by_key
+    pub fn iter_sorted_by_key<'a, C: Component, K: Ord>(
+        &'a self,
+        cache: &'a mut QuerySortCache<K>,
+        key: impl FnMut(&C) -> K,
+    ) -> QuerySortedIter<'a, 's, D::ReadOnly, F, impl Iterator<Item = Entity> + 'a> {
+        let lens_state = self
+            .state
+            .transmute_filtered::<(Entity, Ref<C>), F>(self.world);
+        // SAFETY:
+        // - `self.world` has permission to access the required components, and the lens only
+        //   reads components that `self.state` already accesses.
+        // - The lens was created from `self.state`, so it matches `self.world`.
+        let lens = unsafe {
+            lens_state.query_unchecked_manual_with_ticks(self.world, self.last_run, self.this_run)
+        };
+        cache.update(lens.into_iter(), key, self.this_run);
+
+        // SAFETY:
+        // - `self.world` has permission to read the components of `self.state`.
+        // - `self.world` was used to initialize `self.state`.
+        // - The cache contains each entity at most once.
+        unsafe {
+            QuerySortedIter::new(
+                self.world,
+                self.state.as_readonly(),
+                cache.entities(),
+                self.last_run,
+                self.this_run,
+            )
+        }
+    }
+
+    /// Returns an [`Iterator`] over pairs of read-only items, joining each item of this query with
+    /// the item of `other` on the entity returned by `key`.
+    ///
+    /// The key is typically an [`Entity`] field or a [`Relationship`](crate::relationship::Relationship),
+    /// such as the target of a projectile or the [`ChildOf`](crate::hierarchy::ChildOf) parent.
+    /// Items whose key entity does not match `other` are skipped.
+    ///
+    /// Unlike [`join`](Self::join), which combines the data of two queries on the same entity,
+    /// this pairs items of different entities, without per-item [`get`](Self::get) calls in user code.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Name(&'static str);
+    /// fn print_parents(children: Query<(&Name, &ChildOf)>, parents: Query<&Name>) {
+    ///     for ((name, _), parent_name) in children.join_on(&parents, |(_, child_of)| child_of.parent()) {
+    ///         println!("{} is a child of {}", name.0, parent_name.0);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(print_parents);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// - [`join_on_mut`](Self::join_on_mut) to get mutable items from this query.
+    /// - [`par_join_on_mut`](Self::par_join_on_mut) to process pairs in parallel.
+    pub fn join_on<'a, 'jw, 'js, J: QueryData, G: QueryFilter>(
+        &'a self,
+        other: &'a Query<'jw, 'js, J, G>,
+        key: impl FnMut(&ROQueryItem<'a, 's, D>) -> Entity,
+    ) -> QueryJoinOnIter<
+        'a,
+        's,
+        'a,
+        'js,
+        D::ReadOnly,
+        F,
+        J::ReadOnly,
+        G,
+        impl FnMut(&ROQueryItem<'a, 's, D>) -> Entity,
+    > {
+        QueryJoinOnIter::new(self.iter(), other.as_readonly(), key)
+    }
+
+    /// Returns an [`Iterator`] over pairs of items, joining each mutable item of this query with
+    /// the read-only item of `other` on the entity returned by `key`.
+    ///
+    /// Items whose key entity does not match `other` are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Position(f32);
+    /// # #[derive(Component)]
+    /// # struct Projectile;
+    /// #[derive(Component)]
+    /// struct Target(Entity);
+    ///
+    /// fn home_in(
+    ///     mut projectiles: Query<(&mut Position, &Target), With<Projectile>>,
+    ///     targets: Query<&Position, Without<Projectile>>,
+    /// ) {
+    ///     for ((mut position, _), target) in projectiles.join_on_mut(&targets, |(_, target)| target.0) {
+    ///         position.0 += (target.0 - position.0) * 0.1;
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(home_in);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// - [`join_on`](Self::join_on) for read-only items.
+    /// - [`par_join_on_mut`](Self::par_join_on_mut) to process pairs in parallel.
+    pub fn join_on_mut<'a, 'jw, 'js, J: QueryData, G: QueryFilter>(
+        &'a mut self,
+        other: &'a Query<'jw, 'js, J, G>,
+        key: impl FnMut(&D::Item<'a, 's>) -> Entity,
+    ) -> QueryJoinOnIter<
+        'a,
+        's,
+        'a,
+        'js,
+        D,
+        F,
+        J::ReadOnly,
+        G,
+        impl FnMut(&D::Item<'a, 's>) -> Entity,
+    > {
+        QueryJoinOnIter::new(self.iter_mut(), other.as_readonly(), key)
+    }
+
+    /// Runs `func` in parallel on pairs of items, joining each mutable item of this query with the
+    /// read-only item of `other` on the entity returned by `key`.
+    ///
+    /// This is the parallel equivalent of [`join_on_mut`](Self::join_on_mut), and uses
+    /// [`par_iter_mut`](Self::par_iter_mut) under the hood.
+    /// Items whose key entity does not match `other` are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Position(f32);
+    /// # #[derive(Component)]
+    /// # struct Projectile;
+    /// #[derive(Component)]
+    /// struct Target(Entity);
+    ///
+    /// fn home_in(
+    ///     mut projectiles: Query<(&mut Position, &Target), With<Projectile>>,
+    ///     targets: Query<&Position, Without<Projectile>>,
+    /// ) {
+    ///     projectiles.par_join_on_mut(
+    ///         &targets,
+    ///         |(_, target)| target.0,
+    ///         |(mut position, _), target| position.0 += (target.0 - position.0) * 0.1,
+    ///     );
+    /// }
+    /// # bevy_ecs::system::assert_is_system(home_in);
+    /// ```
+    pub fn par_join_on_mut<J: QueryData, G: QueryFilter>(
+        &mut self,
+        other: &Query<'_, '_, J, G>,
+        key: impl Fn(&D::Item<'_, 's>) -> Entity + Send + Sync,
+        func: impl Fn(D::Item<'_, 's>, ROQueryItem<'_, '_, J>) + Send + Sync,
+    ) {
+        let other = other.as_readonly();
+        self.par_iter_mut().for_each(|item| {
+            if let Ok(joined) = other.get(key(&item)) {
+                func(item, joined);
+            }
+        });
+    }
+
     /// Returns a [`QueryCombinationIter`] over all combinations of `K` read-only query items without repetition.
     ///
     /// This iterator is always guaranteed to return results from each unique pair of matching entities.
@@ -2744,7 +2958,7 @@ impl<'a, 'w, 's, D: QueryData, F: QueryFilter> IntoIterator for &'a mut Populate
 
 #[cfg(test)]
 mod tests {
-    use crate::{prelude::*, query::QueryEntityError};
+    use crate::{prelude::*, query::QueryEntityError, system::RunSystemOnce};
     use alloc::vec::Vec;
 
     #[test]
@@ -2788,4 +3002,64 @@ mod tests {
             QueryEntityError::AliasedMutability(entities[9])
         );
     }
+
+    #[test]
+    fn join_on_related_entity() {
+        #[derive(Component, Debug, PartialEq)]
+        struct Position(i32);
+
+        #[derive(Component)]
+        struct Target(Entity);
+
+        fn follow(
+            mut followers: Query<(&mut Position, &Target)>,
+            targets: Query<&Position, Without<Target>>,
+        ) {
+            for ((mut position, _), target) in
+                followers.join_on_mut(&targets, |(_, target)| target.0)
+            {
+                position.0 = target.0;
+            }
+        }
+
+        fn follow_par(
+            mut followers: Query<(&mut Position, &Target)>,
+            targets: Query<&Position, Without<Target>>,
+        ) {
+            followers.par_join_on_mut(
+                &targets,
+                |(_, target)| target.0,
+                |(mut position, _), target| position.0 = target.0 * 2,
+            );
+        }
+
+        let mut world = World::new();
+        let a = world.spawn(Position(1)).id();
+        let b = world.spawn(Position(2)).id();
+        let despawned = world.spawn_empty().id();
+        world.despawn(despawned);
+        let follows_a = world.spawn((Position(0), Target(a))).id();
+        let follows_b = world.spawn((Position(0), Target(b))).id();
+        let follows_none = world.spawn((Position(0), Target(despawned))).id();
+
+        world.run_system_once(follow).unwrap();
+        assert_eq!(world.get::<Position>(follows_a), Some(&Position(1)));
+        assert_eq!(world.get::<Position>(follows_b), Some(&Position(2)));
+        assert_eq!(world.get::<Position>(follows_none), Some(&Position(0)));
+
+        world.run_system_once(follow_par).unwrap();
+        assert_eq!(world.get::<Position>(follows_a), Some(&Position(2)));
+        assert_eq!(world.get::<Position>(follows_b), Some(&Position(4)));
+        assert_eq!(world.get::<Position>(follows_none), Some(&Position(0)));
+
+        let mut query = world.query::<(&Position, &Target)>();
+        let mut targets = world.query_filtered::<&Position, Without<Target>>();
+        let (followers, targets) = (query.query(&world), targets.query(&world));
+        let joined: Vec<_> = followers
+            .join_on(&targets, |(_, target)| target.0)
+            .map(|((position, _), target)| (position.0, target.0))
+            .collect();
+        assert_eq!(joined.len(), 2);
+        assert!(joined.contains(&(2, 1)) && joined.contains(&(4, 2)));
+    }
 }
@@ -1993,6 +1993,25 @@ impl<'w> EntityWorldMut<'w> {
         entity_clone
     }
 
+    /// Clones this entity onto `target`, and all of its descendants onto newly spawned children
+    /// of `target`, remapping entity references within the hierarchy to their clones.
+    ///
+    /// See [`EntityCloner::clone_hierarchy`] for details.
+    ///
+    /// # Panics
+    ///
+    /// - If this entity has been despawned while this `EntityWorldMut` is still alive.
+    /// - If the target entity does not exist.
+    pub fn clone_hierarchy(&mut self, target: Entity) -> &mut Self {
+        self.assert_not_despawned();
+
+        EntityCloner::default().clone_hierarchy(self.world, self.entity, target);
+
+        self.world.flush();
+        self.update_location();
+        self
+    }
+
     /// Clones the specified components of this entity and inserts them into another entity.
     ///
     /// Components can only be cloned if they implement
@@ -567,6 +567,83 @@ impl World {
         }
     }
 
+    /// Registers the given component `R` as a [required component] for `T`, replacing the
+    /// constructor of `R` if `T` already requires it directly, for example through
+    /// `#[require(...)]`.
+    ///
+    /// This allows plugins to change the defaults of components they don't own.
+    /// For the non-panicking version, see [`World::try_override_required_components_with`].
+    ///
+    /// Note that requirements must currently be registered before `T` is inserted into the world
+    /// for the first time. This limitation may be fixed in the future.
+    ///
+    /// [required component]: Component#required-components
+    ///
+    /// # Panics
+    ///
+    /// Panics if `R` (possibly indirectly) requires `T`, or if `T` has ever been added
+    /// on an entity before the registration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component, Default, PartialEq, Eq, Debug)]
+    /// enum Faction {
+    ///     #[default]
+    ///     Neutral,
+    ///     Hostile,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// #[require(Faction)]
+    /// struct Enemy;
+    ///
+    /// # let mut world = World::default();
+    /// world.override_required_components_with::<Enemy, Faction>(|| Faction::Hostile);
+    ///
+    /// let id = world.spawn(Enemy).id();
+    /// assert_eq!(&Faction::Hostile, world.entity(id).get::<Faction>().unwrap());
+    /// ```
+    pub fn override_required_components_with<T: Component, R: Component>(
+        &mut self,
+        constructor: fn() -> R,
+    ) {
+        self.try_override_required_components_with::<T, R>(constructor)
+            .unwrap();
+    }
+
+    /// Tries to register the given component `R` as a [required component] for `T`, replacing the
+    /// constructor of `R` if `T` already requires it directly.
+    ///
+    /// For the panicking version, see [`World::override_required_components_with`].
+    ///
+    /// [required component]: Component#required-components
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RequiredComponentsError`] if `R` (possibly indirectly) requires `T`, or if `T`
+    /// has ever been added on an entity before the registration.
+    pub fn try_override_required_components_with<T: Component, R: Component>(
+        &mut self,
+        constructor: fn() -> R,
+    ) -> Result<(), RequiredComponentsError> {
+        let requiree = self.register_component::<T>();
+
+        // TODO: Remove this panic and update archetype edges accordingly when required components are added
+        if self.archetypes().component_index().contains_key(&requiree) {
+            return Err(RequiredComponentsError::ArchetypeExists(requiree));
+        }
+
+        let required = self.register_component::<R>();
+
+        // SAFETY: We just created the `required` and `requiree` components.
+        unsafe {
+            self.components
+                .override_required_components::<R>(requiree, required, constructor)
+        }
+    }
+
     /// Retrieves the [required components](RequiredComponents) for the given component type, if it exists.
     pub fn get_required_components<C: Component>(&self) -> Option<&RequiredComponents> {
         let id = self.components().valid_component_id::<C>()?;
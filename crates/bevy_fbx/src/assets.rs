@@ -0,0 +1,29 @@
+//! Representation of assets present in an FBX file
+
+#[cfg(feature = "bevy_animation")]
+use bevy_animation::AnimationClip;
+use bevy_asset::{Asset, Handle};
+use bevy_mesh::Mesh;
+use bevy_pbr::StandardMaterial;
+use bevy_platform::collections::HashMap;
+use bevy_reflect::TypePath;
+use bevy_scene::Scene;
+
+/// Representation of a loaded FBX file.
+#[derive(Asset, Debug, TypePath)]
+pub struct Fbx {
+    /// The models of the FBX file, with the root models under a single root entity.
+    pub scene: Handle<Scene>,
+    /// All meshes loaded from the FBX file.
+    pub meshes: Vec<Handle<Mesh>>,
+    /// All materials loaded from the FBX file.
+    pub materials: Vec<Handle<StandardMaterial>>,
+    /// The materials loaded from FBX `Material` objects, by name.
+    pub named_materials: HashMap<Box<str>, Handle<StandardMaterial>>,
+    /// All animation stacks loaded from the FBX file.
+    #[cfg(feature = "bevy_animation")]
+    pub animations: Vec<Handle<AnimationClip>>,
+    /// The animation stacks of the FBX file, by name.
+    #[cfg(feature = "bevy_animation")]
+    pub named_animations: HashMap<Box<str>, Handle<AnimationClip>>,
+}
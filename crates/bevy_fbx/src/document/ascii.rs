@@ -0,0 +1,322 @@
+//! Parsing of the ASCII format of FBX files.
+
+use super::{Document, FbxParseError, Node, Property};
+
+/// Nodes nested deeper than this are rejected, so that invalid files can't overflow the stack.
+const MAX_DEPTH: usize = 64;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token<'a> {
+    Punct(char),
+    Word(&'a str),
+    Integer(i64),
+    Number(f64),
+    String(String),
+    Eof,
+}
+
+/// Splits the text of a file into tokens, along with the line they start at.
+fn tokenize(text: &str) -> Result<Vec<(Token<'_>, usize)>, FbxParseError> {
+    let bytes = text.as_bytes();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut index = 0;
+    while index < bytes.len() {
+        let byte = bytes[index];
+        let start = index;
+        let token = match byte {
+            b'\n' => {
+                line += 1;
+                index += 1;
+                continue;
+            }
+            _ if byte.is_ascii_whitespace() => {
+                index += 1;
+                continue;
+            }
+            b';' => {
+                while index < bytes.len() && bytes[index] != b'\n' {
+                    index += 1;
+                }
+                continue;
+            }
+            b'{' | b'}' | b':' | b',' | b'*' => {
+                index += 1;
+                Token::Punct(byte as char)
+            }
+            b'"' => {
+                index += 1;
+                while index < bytes.len() && bytes[index] != b'"' {
+                    if bytes[index] == b'\n' {
+                        line += 1;
+                    }
+                    index += 1;
+                }
+                if index == bytes.len() {
+                    return Err(FbxParseError::InvalidAscii {
+                        line,
+                        message: "unterminated string".into(),
+                    });
+                }
+                index += 1;
+                // Quotes are escaped as XML entities.
+                Token::String(text[start + 1..index - 1].replace("&quot;", "\""))
+            }
+            b'-' | b'+' | b'.' | b'0'..=b'9' => {
+                while index < bytes.len()
+                    && matches!(bytes[index], b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+                {
+                    index += 1;
+                }
+                let number = &text[start..index];
+                match number.parse::<i64>() {
+                    Ok(integer) => Token::Integer(integer),
+                    Err(_) => {
+                        Token::Number(number.parse().map_err(|_| FbxParseError::InvalidAscii {
+                            line,
+                            message: format!("invalid number {number}"),
+                        })?)
+                    }
+                }
+            }
+            _ => {
+                while index < bytes.len()
+                    && !bytes[index].is_ascii_whitespace()
+                    && !matches!(bytes[index], b'{' | b'}' | b':' | b',' | b';' | b'"')
+                {
+                    index += 1;
+                }
+                Token::Word(&text[start..index])
+            }
+        };
+        tokens.push((token, line));
+    }
+    tokens.push((Token::Eof, line));
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<(Token<'a>, usize)>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    /// Returns the current token, which is the last one ([`Token::Eof`]) past the end.
+    fn current(&self) -> &(Token<'a>, usize) {
+        &self.tokens[self.position.min(self.tokens.len() - 1)]
+    }
+
+    fn peek(&self) -> &Token<'a> {
+        &self.current().0
+    }
+
+    fn line(&self) -> usize {
+        self.current().1
+    }
+
+    fn next(&mut self) -> Token<'a> {
+        let token = self.peek().clone();
+        self.position += 1;
+        token
+    }
+
+    fn error(&self, message: impl Into<String>) -> FbxParseError {
+        FbxParseError::InvalidAscii {
+            line: self.line(),
+            message: message.into(),
+        }
+    }
+
+    fn expect(&mut self, punct: char) -> Result<(), FbxParseError> {
+        if self.next() == Token::Punct(punct) {
+            Ok(())
+        } else {
+            self.position -= 1;
+            Err(self.error(format!("expected `{punct}`")))
+        }
+    }
+
+    /// Parses nodes until the end of the enclosing braces, or the end of the file.
+    fn nodes(&mut self, depth: usize) -> Result<Vec<Node>, FbxParseError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("nodes are nested too deeply"));
+        }
+        let mut nodes = Vec::new();
+        loop {
+            match self.next() {
+                Token::Word(name) => nodes.push(self.node(name, depth)?),
+                Token::Punct('}') if depth > 0 => return Ok(nodes),
+                Token::Eof if depth == 0 => return Ok(nodes),
+                _ => {
+                    self.position -= 1;
+                    return Err(self.error("expected a node name"));
+                }
+            }
+        }
+    }
+
+    fn node(&mut self, name: &str, depth: usize) -> Result<Node, FbxParseError> {
+        let line = self.line();
+        self.expect(':')?;
+        let mut properties = Vec::new();
+        // The first property is on the same line as the name, and a trailing comma continues the list.
+        if self.line() == line && !matches!(self.peek(), Token::Punct('{') | Token::Eof) {
+            properties.push(self.property()?);
+            while self.peek() == &Token::Punct(',') {
+                self.next();
+                properties.push(self.property()?);
+            }
+        }
+        let children = if self.peek() == &Token::Punct('{') {
+            self.next();
+            self.nodes(depth + 1)?
+        } else {
+            Vec::new()
+        };
+        Ok(Node {
+            name: name.into(),
+            properties,
+            children,
+        })
+    }
+
+    fn property(&mut self) -> Result<Property, FbxParseError> {
+        Ok(match self.next() {
+            Token::Integer(value) => Property::Integer(value),
+            Token::Number(value) => Property::Number(value),
+            Token::String(value) => Property::String(value),
+            Token::Word(value) => Property::String(value.into()),
+            // Arrays are written as `*count { a: values }`.
+            Token::Punct('*') => {
+                let Token::Integer(_) = self.next() else {
+                    return Err(self.error("expected an array length"));
+                };
+                self.expect('{')?;
+                if self.next() != Token::Word("a") {
+                    return Err(self.error("expected `a`"));
+                }
+                self.expect(':')?;
+                let mut integers = Vec::new();
+                let mut numbers = Vec::new();
+                loop {
+                    match self.next() {
+                        Token::Integer(value) => {
+                            integers.push(value);
+                            numbers.push(value as f64);
+                        }
+                        Token::Number(value) => numbers.push(value),
+                        Token::Punct(',') => {}
+                        Token::Punct('}') => break,
+                        _ => return Err(self.error("expected a number")),
+                    }
+                }
+                if integers.len() == numbers.len() {
+                    Property::Integers(integers)
+                } else {
+                    Property::Numbers(numbers)
+                }
+            }
+            _ => {
+                self.position -= 1;
+                return Err(self.error("expected a property"));
+            }
+        })
+    }
+}
+
+pub(super) fn parse(text: &str) -> Result<Document, FbxParseError> {
+    let mut parser = Parser {
+        tokens: tokenize(text)?,
+        position: 0,
+    };
+    let nodes = parser.nodes(0)?;
+    let version = nodes
+        .iter()
+        .find(|node| node.name == "FBXHeaderExtension")
+        .and_then(|header| header.value("FBXVersion"))
+        .and_then(Property::as_i64)
+        .and_then(|version| u32::try_from(version).ok());
+    match version {
+        Some(version) if version < 7000 => Err(FbxParseError::UnsupportedVersion(version)),
+        // Files without a header are assumed to use the current version.
+        version => Ok(Document {
+            version: version.unwrap_or(7400),
+            nodes,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ascii() {
+        let document = parse(
+            r#"; FBX 7.4.0 project file
+FBXHeaderExtension:  {
+	FBXVersion: 7400
+}
+Objects:  {
+	Geometry: 140, "Geometry::Cube", "Mesh" {
+		Vertices: *6 {
+			a: -1,-1,1,
+			1.5,-1,1
+		}
+		PolygonVertexIndex: *3 {
+			a: 0,1,-2
+		}
+	}
+	Model: 1, "Model::Cube", "Mesh" {
+		Properties70:  {
+			P: "Lcl Translation", "Lcl Translation", "", "A",1,2,
+				3
+		}
+		Shading: T
+		Culling: "CullingOff"
+	}
+}
+"#,
+        )
+        .unwrap();
+        assert_eq!(document.version, 7400);
+        let objects = document.node("Objects").unwrap();
+        let geometry = objects.child("Geometry").unwrap();
+        assert_eq!(
+            geometry.properties,
+            [
+                Property::Integer(140),
+                Property::String("Geometry::Cube".into()),
+                Property::String("Mesh".into()),
+            ]
+        );
+        assert_eq!(
+            geometry.value("Vertices"),
+            Some(&Property::Numbers(vec![-1.0, -1.0, 1.0, 1.5, -1.0, 1.0]))
+        );
+        assert_eq!(
+            geometry.value("PolygonVertexIndex"),
+            Some(&Property::Integers(vec![0, 1, -2]))
+        );
+        let model = objects.child("Model").unwrap();
+        assert_eq!(
+            model.property70("Lcl Translation"),
+            Some(
+                &[
+                    Property::Integer(1),
+                    Property::Integer(2),
+                    Property::Integer(3)
+                ][..]
+            )
+        );
+        assert_eq!(model.value("Shading"), Some(&Property::String("T".into())));
+        assert_eq!(model.children.len(), 3);
+
+        let error = parse("Objects: {\n  Model: 1, {\n}").unwrap_err();
+        assert!(matches!(error, FbxParseError::InvalidAscii { line: 2, .. }));
+        assert!(matches!(
+            parse("FBXHeaderExtension: {\n FBXVersion: 6100\n}"),
+            Err(FbxParseError::UnsupportedVersion(6100))
+        ));
+    }
+}
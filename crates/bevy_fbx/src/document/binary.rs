@@ -0,0 +1,269 @@
+//! Parsing of the binary format of FBX files.
+
+use std::io::Read;
+
+use flate2::read::ZlibDecoder;
+
+use super::{Document, FbxParseError, Node, Property, BINARY_MAGIC};
+
+/// Nodes nested deeper than this are rejected, so that invalid files can't overflow the stack.
+const MAX_DEPTH: usize = 64;
+
+fn error(message: impl Into<String>) -> FbxParseError {
+    FbxParseError::InvalidBinary(message.into())
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    /// Whether node records use 64-bit offsets, which is the case from version 7.5.
+    wide: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, count: usize) -> Result<&'a [u8], FbxParseError> {
+        let bytes = self
+            .position
+            .checked_add(count)
+            .and_then(|end| self.bytes.get(self.position..end))
+            .ok_or_else(|| error("unexpected end of file"))?;
+        self.position += count;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], FbxParseError> {
+        Ok(self.bytes(N)?.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8, FbxParseError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, FbxParseError> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    /// Reads an offset or count of a node record, whose size depends on the version.
+    fn record_field(&mut self) -> Result<usize, FbxParseError> {
+        let value = if self.wide {
+            self.array().map(u64::from_le_bytes)?
+        } else {
+            self.u32()? as u64
+        };
+        usize::try_from(value).map_err(|_| error("record field out of range"))
+    }
+
+    /// Reads a list of nodes, which ends with a null record or at the end of the file.
+    fn nodes(&mut self, depth: usize) -> Result<Vec<Node>, FbxParseError> {
+        if depth > MAX_DEPTH {
+            return Err(error("nodes are nested too deeply"));
+        }
+        let mut nodes = Vec::new();
+        while self.position < self.bytes.len() {
+            let end = self.record_field()?;
+            let property_count = self.record_field()?;
+            let _property_list_length = self.record_field()?;
+            let name_length = self.u8()? as usize;
+            if end == 0 {
+                // A null record ends the list.
+                break;
+            }
+            if end <= self.position || end > self.bytes.len() {
+                return Err(error("invalid node end offset"));
+            }
+            let name = String::from_utf8_lossy(self.bytes(name_length)?).into_owned();
+            // Each property takes at least two bytes, which bounds the allocation.
+            let mut properties = Vec::with_capacity(property_count.min(end - self.position));
+            for _ in 0..property_count {
+                properties.push(self.property()?);
+            }
+            let children = if self.position < end {
+                self.nodes(depth + 1)?
+            } else {
+                Vec::new()
+            };
+            self.position = end;
+            nodes.push(Node {
+                name,
+                properties,
+                children,
+            });
+        }
+        Ok(nodes)
+    }
+
+    fn property(&mut self) -> Result<Property, FbxParseError> {
+        Ok(match self.u8()? {
+            b'Y' => Property::Integer(self.array().map(i16::from_le_bytes)? as i64),
+            b'C' => Property::Bool(self.u8()? != 0),
+            b'I' => Property::Integer(self.array().map(i32::from_le_bytes)? as i64),
+            b'L' => Property::Integer(self.array().map(i64::from_le_bytes)?),
+            b'F' => Property::Number(self.array().map(f32::from_le_bytes)? as f64),
+            b'D' => Property::Number(self.array().map(f64::from_le_bytes)?),
+            b'S' => {
+                let length = self.u32()? as usize;
+                Property::String(String::from_utf8_lossy(self.bytes(length)?).into_owned())
+            }
+            b'R' => {
+                let length = self.u32()? as usize;
+                Property::Raw(self.bytes(length)?.to_vec())
+            }
+            b'f' => Property::Numbers(self.elements(4, |bytes| {
+                f32::from_le_bytes(bytes.try_into().unwrap()) as f64
+            })?),
+            b'd' => Property::Numbers(
+                self.elements(8, |bytes| f64::from_le_bytes(bytes.try_into().unwrap()))?,
+            ),
+            b'i' => Property::Integers(self.elements(4, |bytes| {
+                i32::from_le_bytes(bytes.try_into().unwrap()) as i64
+            })?),
+            b'l' => Property::Integers(
+                self.elements(8, |bytes| i64::from_le_bytes(bytes.try_into().unwrap()))?,
+            ),
+            b'b' => Property::Integers(self.elements(1, |bytes| (bytes[0] != 0) as i64)?),
+            code => return Err(error(format!("unknown property type {:?}", code as char))),
+        })
+    }
+
+    /// Reads an array property, which may be compressed with zlib.
+    fn elements<T>(
+        &mut self,
+        size: usize,
+        read: impl Fn(&[u8]) -> T,
+    ) -> Result<Vec<T>, FbxParseError> {
+        let count = self.u32()? as usize;
+        let encoding = self.u32()?;
+        let compressed_length = self.u32()? as usize;
+        let length = count
+            .checked_mul(size)
+            .ok_or_else(|| error("array too large"))?;
+        let data = self.bytes(compressed_length)?;
+        let decompressed;
+        let data = match encoding {
+            0 => data
+                .get(..length)
+                .ok_or_else(|| error("array larger than its data"))?,
+            1 => {
+                let mut bytes = Vec::new();
+                ZlibDecoder::new(data)
+                    .take(length as u64)
+                    .read_to_end(&mut bytes)
+                    .map_err(|err| error(format!("invalid compressed array: {err}")))?;
+                if bytes.len() != length {
+                    return Err(error("compressed array smaller than its length"));
+                }
+                decompressed = bytes;
+                &decompressed
+            }
+            _ => return Err(error(format!("unknown array encoding {encoding}"))),
+        };
+        Ok(data.chunks_exact(size).map(read).collect())
+    }
+}
+
+pub(super) fn parse(bytes: &[u8]) -> Result<Document, FbxParseError> {
+    // The magic is followed by two bytes and the version.
+    let header_length = BINARY_MAGIC.len() + 2;
+    let version = bytes
+        .get(header_length..header_length + 4)
+        .map(|version| u32::from_le_bytes(version.try_into().unwrap()))
+        .ok_or_else(|| error("missing version"))?;
+    if version < 7000 {
+        return Err(FbxParseError::UnsupportedVersion(version));
+    }
+    let mut reader = Reader {
+        bytes,
+        position: header_length + 4,
+        wide: version >= 7500,
+    };
+    let nodes = reader.nodes(0)?;
+    Ok(Document { version, nodes })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{write::ZlibEncoder, Compression};
+
+    use super::*;
+
+    /// Writes a node record with 32-bit offsets.
+    fn write_node(
+        output: &mut Vec<u8>,
+        name: &str,
+        properties: &[&[u8]],
+        children: &[&dyn Fn(&mut Vec<u8>)],
+    ) {
+        let start = output.len();
+        output.extend_from_slice(&[0; 12]);
+        output.push(name.len() as u8);
+        output.extend_from_slice(name.as_bytes());
+        let properties_start = output.len();
+        for property in properties {
+            output.extend_from_slice(property);
+        }
+        let properties_length = output.len() - properties_start;
+        if !children.is_empty() {
+            for child in children {
+                child(output);
+            }
+            output.extend_from_slice(&[0; 13]);
+        }
+        let header = [output.len(), properties.len(), properties_length];
+        for (index, value) in header.into_iter().enumerate() {
+            output[start + index * 4..start + index * 4 + 4]
+                .copy_from_slice(&(value as u32).to_le_bytes());
+        }
+    }
+
+    #[test]
+    fn parse_binary() {
+        let mut bytes = BINARY_MAGIC.to_vec();
+        bytes.extend_from_slice(&[0x1A, 0]);
+        bytes.extend_from_slice(&7400u32.to_le_bytes());
+
+        // A compressed array of doubles.
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        for value in [1.0f64, 2.0, 3.0] {
+            encoder.write_all(&value.to_le_bytes()).unwrap();
+        }
+        let compressed = encoder.finish().unwrap();
+        let mut vertices = vec![b'd'];
+        vertices.extend_from_slice(&3u32.to_le_bytes());
+        vertices.extend_from_slice(&1u32.to_le_bytes());
+        vertices.extend_from_slice(&(compressed.len() as u32).to_le_bytes());
+        vertices.extend_from_slice(&compressed);
+
+        let mut name = vec![b'S'];
+        name.extend_from_slice(&11u32.to_le_bytes());
+        name.extend_from_slice(b"Cube\0\x01Model");
+
+        write_node(
+            &mut bytes,
+            "Objects",
+            &[],
+            &[
+                &|output| write_node(output, "Model", &[&name], &[]),
+                &|output| write_node(output, "Vertices", &[&vertices], &[]),
+            ],
+        );
+        bytes.extend_from_slice(&[0; 13]);
+
+        let document = parse(&bytes).unwrap();
+        assert_eq!(document.version, 7400);
+        let objects = document.node("Objects").unwrap();
+        assert_eq!(objects.children.len(), 2);
+        assert_eq!(
+            objects.value("Model"),
+            Some(&Property::String("Cube\0\x01Model".into()))
+        );
+        assert_eq!(
+            objects.value("Vertices"),
+            Some(&Property::Numbers(vec![1.0, 2.0, 3.0]))
+        );
+
+        // Truncated files are errors, not panics.
+        assert!(parse(&bytes[..bytes.len() - 30]).is_err());
+    }
+}
@@ -0,0 +1,158 @@
+//! An in-memory representation of an FBX document, parsed from binary or ASCII files.
+//!
+//! FBX files are trees of nodes, where each node has a name, a list of properties and child nodes.
+//! This module only parses that tree: interpreting the nodes as objects and connections is done by
+//! the loader.
+
+mod ascii;
+mod binary;
+
+use thiserror::Error;
+
+/// An error that occurs when parsing an FBX file.
+#[derive(Error, Debug)]
+pub enum FbxParseError {
+    /// The contents of a binary FBX file are invalid.
+    #[error("invalid binary FBX: {0}")]
+    InvalidBinary(String),
+    /// The text of an ASCII FBX file is invalid.
+    #[error("invalid ASCII FBX at line {line}: {message}")]
+    InvalidAscii {
+        /// The line at which the error occurred.
+        line: usize,
+        /// A description of the error.
+        message: String,
+    },
+    /// The file was written with a version of FBX that isn't supported.
+    #[error("unsupported FBX version {0}, only versions 7.0 and later are supported")]
+    UnsupportedVersion(u32),
+}
+
+/// A property of a [`Node`].
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Property {
+    Bool(bool),
+    Integer(i64),
+    Number(f64),
+    String(String),
+    Raw(Vec<u8>),
+    Integers(Vec<i64>),
+    Numbers(Vec<f64>),
+}
+
+impl Property {
+    pub(crate) fn as_i64(&self) -> Option<i64> {
+        match self {
+            Property::Bool(value) => Some(*value as i64),
+            Property::Integer(value) => Some(*value),
+            Property::Number(value) => Some(*value as i64),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_f64(&self) -> Option<f64> {
+        match self {
+            Property::Bool(value) => Some(*value as u8 as f64),
+            Property::Integer(value) => Some(*value as f64),
+            Property::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Property::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the values of an array property, converted to `f64`.
+    pub(crate) fn to_f64s(&self) -> Option<Vec<f64>> {
+        match self {
+            Property::Numbers(values) => Some(values.clone()),
+            Property::Integers(values) => Some(values.iter().map(|&value| value as f64).collect()),
+            _ => None,
+        }
+    }
+
+    /// Returns the values of an array property, converted to `i64`.
+    pub(crate) fn to_i64s(&self) -> Option<Vec<i64>> {
+        match self {
+            Property::Integers(values) => Some(values.clone()),
+            Property::Numbers(values) => Some(values.iter().map(|&value| value as i64).collect()),
+            _ => None,
+        }
+    }
+}
+
+/// A node of an FBX document.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Node {
+    pub name: String,
+    pub properties: Vec<Property>,
+    pub children: Vec<Node>,
+}
+
+impl Node {
+    /// Returns the first child named `name`.
+    pub(crate) fn child(&self, name: &str) -> Option<&Node> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    /// Returns the children named `name`.
+    pub(crate) fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Node> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    /// Returns the first property of the first child named `name`.
+    pub(crate) fn value(&self, name: &str) -> Option<&Property> {
+        self.child(name)?.properties.first()
+    }
+
+    /// Returns the properties of the `Properties70` child, by name.
+    ///
+    /// Each property is a `P` node whose first four properties are its name, type, label and flags,
+    /// followed by its values.
+    pub(crate) fn properties70(&self) -> impl Iterator<Item = (&str, &[Property])> {
+        self.child("Properties70")
+            .into_iter()
+            .flat_map(|properties| properties.children_named("P"))
+            .filter_map(|property| {
+                let name = property.properties.first()?.as_str()?;
+                Some((name, property.properties.get(4..).unwrap_or_default()))
+            })
+    }
+
+    /// Returns the values of the property `name` of the `Properties70` child.
+    pub(crate) fn property70(&self, name: &str) -> Option<&[Property]> {
+        self.properties70()
+            .find(|(property, _)| *property == name)
+            .map(|(_, values)| values)
+    }
+}
+
+/// A parsed FBX document.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Document {
+    /// The version of the file, such as `7400` for FBX 7.4.
+    pub version: u32,
+    /// The top-level nodes of the file, such as `GlobalSettings`, `Objects` and `Connections`.
+    pub nodes: Vec<Node>,
+}
+
+impl Document {
+    pub(crate) fn node(&self, name: &str) -> Option<&Node> {
+        self.nodes.iter().find(|node| node.name == name)
+    }
+}
+
+const BINARY_MAGIC: &[u8] = b"Kaydara FBX Binary  \0";
+
+/// Parses an FBX file, which can either be in the binary or ASCII format.
+pub(crate) fn parse(bytes: &[u8]) -> Result<Document, FbxParseError> {
+    if bytes.starts_with(BINARY_MAGIC) {
+        binary::parse(bytes)
+    } else {
+        ascii::parse(&String::from_utf8_lossy(bytes))
+    }
+}
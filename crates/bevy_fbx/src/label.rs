@@ -0,0 +1,69 @@
+//! Labels that can be used to load part of an FBX file
+
+use bevy_asset::AssetPath;
+
+/// Labels that can be used to load part of an FBX file
+///
+/// You can use [`FbxAssetLabel::from_asset`] to add it to an asset path
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_asset::prelude::*;
+/// # use bevy_scene::prelude::*;
+/// # use bevy_fbx::prelude::*;
+///
+/// fn load_fbx_scene(asset_server: Res<AssetServer>) {
+///     let fbx_scene: Handle<Scene> = asset_server.load(FbxAssetLabel::Scene.from_asset("models/Fox/Fox.fbx"));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FbxAssetLabel {
+    /// `Scene`: the FBX models as a Bevy [`Scene`](bevy_scene::Scene)
+    Scene,
+    /// `Mesh{}`: a mesh of an FBX `Geometry` as a Bevy [`Mesh`](bevy_mesh::Mesh).
+    ///
+    /// A geometry results in several meshes if its polygons use different materials.
+    Mesh(usize),
+    /// `Material{}`: FBX material as a Bevy [`StandardMaterial`](bevy_pbr::StandardMaterial)
+    Material(usize),
+    /// `Texture{}`: a texture embedded in the FBX file as a Bevy [`Image`](bevy_image::prelude::Image)
+    Texture(usize),
+    /// `Animation{}`: FBX animation stack as a Bevy [`AnimationClip`](bevy_animation::AnimationClip)
+    Animation(usize),
+    /// `InverseBindMatrices{}`: the inverse bind matrices of an FBX skin as a Bevy
+    /// [`SkinnedMeshInverseBindposes`](bevy_mesh::skinning::SkinnedMeshInverseBindposes)
+    InverseBindMatrices(usize),
+}
+
+impl core::fmt::Display for FbxAssetLabel {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FbxAssetLabel::Scene => f.write_str("Scene"),
+            FbxAssetLabel::Mesh(index) => f.write_str(&format!("Mesh{index}")),
+            FbxAssetLabel::Material(index) => f.write_str(&format!("Material{index}")),
+            FbxAssetLabel::Texture(index) => f.write_str(&format!("Texture{index}")),
+            FbxAssetLabel::Animation(index) => f.write_str(&format!("Animation{index}")),
+            FbxAssetLabel::InverseBindMatrices(index) => {
+                f.write_str(&format!("InverseBindMatrices{index}"))
+            }
+        }
+    }
+}
+
+impl FbxAssetLabel {
+    /// Add this label to an asset path
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_asset::prelude::*;
+    /// # use bevy_scene::prelude::*;
+    /// # use bevy_fbx::prelude::*;
+    ///
+    /// fn load_fbx_scene(asset_server: Res<AssetServer>) {
+    ///     let fbx_scene: Handle<Scene> = asset_server.load(FbxAssetLabel::Scene.from_asset("models/Fox/Fox.fbx"));
+    /// }
+    /// ```
+    pub fn from_asset(&self, path: impl Into<AssetPath<'static>>) -> AssetPath<'static> {
+        path.into().with_label(self.to_string())
+    }
+}
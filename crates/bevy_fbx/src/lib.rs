@@ -0,0 +1,101 @@
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![forbid(unsafe_code)]
+#![doc(
+    html_logo_url = "https://bevy.org/assets/icon.png",
+    html_favicon_url = "https://bevy.org/assets/icon.png"
+)]
+
+//! Plugin providing an [`AssetLoader`](bevy_asset::AssetLoader) and type definitions
+//! for loading [FBX](https://en.wikipedia.org/wiki/FBX) files in Bevy.
+//!
+//! Both the binary and ASCII formats are supported, from FBX 7.0 (2011) onwards.
+//!
+//! # Quick Start
+//!
+//! Here's how to spawn an FBX scene
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_asset::prelude::*;
+//! # use bevy_scene::prelude::*;
+//! # use bevy_transform::prelude::*;
+//! # use bevy_fbx::prelude::*;
+//!
+//! fn spawn_fbx(mut commands: Commands, asset_server: Res<AssetServer>) {
+//!     commands.spawn((
+//!         // This is equivalent to "models/Fox/Fox.fbx#Scene"
+//!         SceneRoot(asset_server.load(FbxAssetLabel::Scene.from_asset("models/Fox/Fox.fbx"))),
+//!         // You can use the transform to give it a position
+//!         Transform::from_xyz(2.0, 0.0, -5.0),
+//!     ));
+//! }
+//! ```
+//!
+//! # Supported features
+//!
+//! The loader converts the following parts of a file:
+//!
+//! - `Model` objects, with their transform (including pivots, offsets and pre and post rotations),
+//!   visibility and name.
+//! - `Geometry` objects, including normals, two texture coordinate sets, vertex colors and per-polygon
+//!   materials. Polygons are triangulated as fans.
+//! - `Skin` deformers, as a [`SkinnedMesh`](bevy_mesh::skinning::SkinnedMesh) of up to four joints
+//!   per vertex.
+//! - Lambert and Phong `Material` objects, with diffuse, normal and emissive textures that are either
+//!   embedded in the file or stored next to it.
+//! - Animation stacks, as [`AnimationClip`](bevy_animation::AnimationClip)s of the model transforms
+//!   when the `bevy_animation` feature is enabled. Only the first layer of each stack is loaded, and
+//!   curves are sampled at their keys with linear interpolation.
+//! - The axes and unit of the file, which can be disabled in [`FbxLoaderSettings`].
+//!
+//! Blend shapes, cameras and lights are ignored.
+
+mod assets;
+mod document;
+mod label;
+mod loader;
+
+extern crate alloc;
+
+use tracing::warn;
+
+use bevy_app::prelude::*;
+use bevy_asset::AssetApp;
+use bevy_image::{CompressedImageFormatSupport, CompressedImageFormats};
+
+/// The FBX prelude.
+///
+/// This includes the most common types in this crate, re-exported for your convenience.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{assets::Fbx, label::FbxAssetLabel};
+}
+
+pub use {assets::*, document::FbxParseError, label::FbxAssetLabel, loader::*};
+
+/// Adds support for FBX file loading to the app.
+#[derive(Default)]
+pub struct FbxPlugin;
+
+impl Plugin for FbxPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Fbx>()
+            .preregister_asset_loader::<FbxLoader>(&["fbx"]);
+    }
+
+    fn finish(&self, app: &mut App) {
+        let supported_compressed_formats = if let Some(resource) =
+            app.world().get_resource::<CompressedImageFormatSupport>()
+        {
+            resource.0
+        } else {
+            warn!("CompressedImageFormatSupport resource not found. It should either be initialized in finish() of \
+            RenderPlugin, or manually if not using the RenderPlugin or the WGPU backend.");
+            CompressedImageFormats::NONE
+        };
+
+        app.register_asset_loader(FbxLoader {
+            supported_compressed_formats,
+        });
+    }
+}
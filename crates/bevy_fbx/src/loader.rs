@@ -0,0 +1,1457 @@
+use alloc::collections::BTreeMap;
+
+#[cfg(feature = "bevy_animation")]
+use bevy_animation::{
+    animated_field,
+    animation_curves::{AnimatableCurve, AnimatedField},
+    AnimatedBy, AnimationClip, AnimationPlayer, AnimationTargetId,
+};
+use bevy_asset::{
+    io::Reader, AssetLoader, Handle, LoadContext, ParseAssetPathError, RenderAssetUsages,
+};
+use bevy_camera::visibility::Visibility;
+use bevy_color::{Alpha, Color, LinearRgba};
+use bevy_ecs::{entity::Entity, hierarchy::ChildOf, name::Name, world::World};
+use bevy_image::{
+    CompressedImageFormats, Image, ImageAddressMode, ImageLoaderSettings, ImageSampler,
+    ImageSamplerDescriptor, ImageType, TextureError,
+};
+#[cfg(feature = "bevy_animation")]
+use bevy_math::curve::UnevenSampleAutoCurve;
+use bevy_math::{Mat3, Mat4, Quat, Vec3};
+use bevy_mesh::{
+    skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+    Indices, Mesh, Mesh3d, PrimitiveTopology, VertexAttributeValues,
+};
+use bevy_pbr::{MeshMaterial3d, StandardMaterial, MAX_JOINTS};
+use bevy_platform::collections::HashMap;
+use bevy_render::alpha::AlphaMode;
+use bevy_scene::Scene;
+use bevy_transform::components::Transform;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::warn;
+
+use crate::{
+    document::{parse, Document, FbxParseError, Node, Property},
+    Fbx, FbxAssetLabel,
+};
+
+/// The number of FBX time units in a second.
+#[cfg(feature = "bevy_animation")]
+const TICKS_PER_SECOND: f64 = 46_186_158_000.0;
+
+/// An error that occurs when loading an FBX file.
+#[derive(Error, Debug)]
+pub enum FbxError {
+    /// Failed to read the file.
+    #[error("failed to read FBX file: {0}")]
+    Io(#[from] std::io::Error),
+    /// Failed to parse the file.
+    #[error(transparent)]
+    Parse(#[from] FbxParseError),
+    /// Failed to decode an embedded texture.
+    #[error("failed to load texture {0}: {1}")]
+    Texture(String, TextureError),
+    /// A texture path is invalid.
+    #[error("invalid texture path {0}: {1}")]
+    InvalidTexturePath(String, ParseAssetPathError),
+}
+
+/// Loads FBX files (binary or ASCII, version 7.0 and later) as a [`Fbx`] asset.
+pub struct FbxLoader {
+    /// The compressed texture formats supported by the GPU, used to decode embedded textures.
+    pub supported_compressed_formats: CompressedImageFormats,
+}
+
+/// Specifies optional settings for processing FBX files at load time.
+///
+/// To use, load the asset with [`AssetServer::load_with_settings`](bevy_asset::AssetServer::load_with_settings):
+///
+/// ```no_run
+/// # use bevy_asset::{AssetServer, Handle};
+/// # use bevy_fbx::*;
+/// # let asset_server: AssetServer = panic!();
+/// let fbx_handle: Handle<Fbx> = asset_server.load_with_settings(
+///     "my.fbx",
+///     |s: &mut FbxLoaderSettings| {
+///         s.load_animations = false;
+///     },
+/// );
+/// ```
+#[derive(Serialize, Deserialize)]
+pub struct FbxLoaderSettings {
+    /// If empty, the meshes will be skipped.
+    ///
+    /// Otherwise, meshes will be loaded and retained in RAM/VRAM according to the active flags.
+    pub load_meshes: RenderAssetUsages,
+    /// If empty, the materials will be skipped.
+    ///
+    /// Otherwise, materials will be loaded and retained in RAM/VRAM according to the active flags.
+    pub load_materials: RenderAssetUsages,
+    /// If true, the loader will load each animation stack (or take) as an `AnimationClip` asset, and
+    /// add `AnimationTarget` and `AnimationPlayer` components to the scene. Requires the `bevy_animation` feature.
+    pub load_animations: bool,
+    /// If true, the axes of the file are converted to Bevy's coordinate system, where `Y` is up and `Z`
+    /// points towards the viewer.
+    pub convert_coordinates: bool,
+    /// If true, files are scaled by their `UnitScaleFactor` so that one unit is one meter.
+    pub convert_units: bool,
+}
+
+impl Default for FbxLoaderSettings {
+    fn default() -> Self {
+        Self {
+            load_meshes: RenderAssetUsages::default(),
+            load_materials: RenderAssetUsages::default(),
+            load_animations: true,
+            convert_coordinates: true,
+            convert_units: true,
+        }
+    }
+}
+
+impl AssetLoader for FbxLoader {
+    type Asset = Fbx;
+    type Settings = FbxLoaderSettings;
+    type Error = FbxError;
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        settings: &FbxLoaderSettings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Fbx, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let document = parse(&bytes)?;
+        if document.version > 7700 {
+            warn!(
+                "FBX version {} is newer than the latest supported version 7.7, {} may not load correctly",
+                document.version,
+                load_context.path()
+            );
+        }
+        let objects = Objects::new(&document);
+        DocumentLoader {
+            document: &document,
+            objects,
+            settings,
+            load_context,
+            supported_compressed_formats: self.supported_compressed_formats,
+            meshes: Vec::new(),
+            materials: Vec::new(),
+            named_materials: HashMap::default(),
+            material_handles: HashMap::default(),
+            default_material: None,
+            geometries: HashMap::default(),
+            textures: HashMap::default(),
+            texture_count: 0,
+            skin_count: 0,
+            models: HashMap::default(),
+            skinned_primitives: Vec::new(),
+        }
+        .load()
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["fbx"]
+    }
+}
+
+/// A connection between two objects, or between an object and a property of another object.
+struct Connection<'a> {
+    object: i64,
+    property: Option<&'a str>,
+}
+
+/// The objects of a document, indexed by id, and the connections between them.
+struct Objects<'a> {
+    /// The objects, in the order they appear in the file.
+    objects: Vec<(i64, &'a Node)>,
+    by_id: HashMap<i64, &'a Node>,
+    children: HashMap<i64, Vec<Connection<'a>>>,
+    parents: HashMap<i64, Vec<Connection<'a>>>,
+}
+
+impl<'a> Objects<'a> {
+    fn new(document: &'a Document) -> Self {
+        let objects = document
+            .node("Objects")
+            .into_iter()
+            .flat_map(|objects| &objects.children)
+            .filter_map(|object| Some((object.properties.first()?.as_i64()?, object)))
+            .collect::<Vec<_>>();
+        let by_id = objects.iter().copied().collect();
+        let mut children = HashMap::<_, Vec<_>>::default();
+        let mut parents = HashMap::<_, Vec<_>>::default();
+        for connection in document
+            .node("Connections")
+            .into_iter()
+            .flat_map(|connections| connections.children_named("C"))
+        {
+            let [_, child, parent, ..] = connection.properties.as_slice() else {
+                continue;
+            };
+            let (Some(child), Some(parent)) = (child.as_i64(), parent.as_i64()) else {
+                continue;
+            };
+            let property = connection.properties.get(3).and_then(Property::as_str);
+            children.entry(parent).or_default().push(Connection {
+                object: child,
+                property,
+            });
+            parents.entry(child).or_default().push(Connection {
+                object: parent,
+                property,
+            });
+        }
+        Self {
+            objects,
+            by_id,
+            children,
+            parents,
+        }
+    }
+
+    /// Returns the objects of kind `kind` (such as `Model`) in `connections` of `object`, in connection order.
+    fn connected(
+        &self,
+        connections: &HashMap<i64, Vec<Connection<'a>>>,
+        object: i64,
+        kind: &str,
+    ) -> Vec<(i64, &'a Node, Option<&'a str>)> {
+        connections
+            .get(&object)
+            .into_iter()
+            .flatten()
+            .filter_map(|connection| {
+                let node = *self.by_id.get(&connection.object)?;
+                (node.name == kind).then_some((connection.object, node, connection.property))
+            })
+            .collect()
+    }
+
+    /// Returns the objects of kind `kind` connected to `object` as its children.
+    fn children_of(&self, object: i64, kind: &str) -> Vec<(i64, &'a Node, Option<&'a str>)> {
+        self.connected(&self.children, object, kind)
+    }
+
+    /// Returns the objects of kind `kind` that `object` is connected to as a child.
+    fn parents_of(&self, object: i64, kind: &str) -> Vec<(i64, &'a Node, Option<&'a str>)> {
+        self.connected(&self.parents, object, kind)
+    }
+}
+
+/// Returns the name of an object.
+///
+/// Binary files store names as `Name\0\x01Class`, while ASCII files store them as `Class::Name`.
+fn object_name(object: &Node) -> &str {
+    let name = object
+        .properties
+        .get(1)
+        .and_then(Property::as_str)
+        .unwrap_or_default();
+    if let Some((name, _)) = name.split_once("\0\x01") {
+        name
+    } else if let Some((_, name)) = name.split_once("::") {
+        name
+    } else {
+        name
+    }
+}
+
+/// Returns the class of an object, such as `Mesh` or `LimbNode` for models.
+fn object_class(object: &Node) -> &str {
+    object
+        .properties
+        .get(2)
+        .and_then(Property::as_str)
+        .unwrap_or_default()
+}
+
+fn vec3(values: &[Property]) -> Option<Vec3> {
+    match values {
+        [x, y, z, ..] => Some(Vec3::new(
+            x.as_f64()? as f32,
+            y.as_f64()? as f32,
+            z.as_f64()? as f32,
+        )),
+        _ => None,
+    }
+}
+
+fn matrix(values: &[f64]) -> Option<Mat4> {
+    // FBX matrices are stored in column-major order.
+    let values: [f64; 16] = values.try_into().ok()?;
+    Some(Mat4::from_cols_array(&values.map(|value| value as f32)))
+}
+
+/// Returns the rotation of Euler angles in degrees, where `order` is the FBX `RotationOrder`.
+fn euler_rotation(degrees: Vec3, order: i64) -> Quat {
+    let axes: &[u8; 3] = match order {
+        1 => b"XZY",
+        2 => b"YZX",
+        3 => b"YXZ",
+        4 => b"ZXY",
+        5 => b"ZYX",
+        // Spheric XYZ is interpolated differently, but uses the same rotation.
+        _ => b"XYZ",
+    };
+    // The first axis is applied first.
+    axes.iter().fold(Quat::IDENTITY, |rotation, axis| {
+        let axis_rotation = match axis {
+            b'X' => Quat::from_rotation_x(degrees.x.to_radians()),
+            b'Y' => Quat::from_rotation_y(degrees.y.to_radians()),
+            _ => Quat::from_rotation_z(degrees.z.to_radians()),
+        };
+        axis_rotation * rotation
+    })
+}
+
+/// The transform properties of a `Model`, which are combined into its local transform.
+#[derive(Clone, Copy)]
+struct ModelTransform {
+    translation: Vec3,
+    /// Euler angles in degrees.
+    rotation: Vec3,
+    scaling: Vec3,
+    rotation_order: i64,
+    pre_rotation: Vec3,
+    post_rotation: Vec3,
+    rotation_offset: Vec3,
+    rotation_pivot: Vec3,
+    scaling_offset: Vec3,
+    scaling_pivot: Vec3,
+}
+
+impl ModelTransform {
+    fn new(model: &Node) -> Self {
+        let vector =
+            |name: &str, default: Vec3| model.property70(name).and_then(vec3).unwrap_or(default);
+        Self {
+            translation: vector("Lcl Translation", Vec3::ZERO),
+            rotation: vector("Lcl Rotation", Vec3::ZERO),
+            scaling: vector("Lcl Scaling", Vec3::ONE),
+            rotation_order: model
+                .property70("RotationOrder")
+                .and_then(<[Property]>::first)
+                .and_then(Property::as_i64)
+                .unwrap_or(0),
+            pre_rotation: vector("PreRotation", Vec3::ZERO),
+            post_rotation: vector("PostRotation", Vec3::ZERO),
+            rotation_offset: vector("RotationOffset", Vec3::ZERO),
+            rotation_pivot: vector("RotationPivot", Vec3::ZERO),
+            scaling_offset: vector("ScalingOffset", Vec3::ZERO),
+            scaling_pivot: vector("ScalingPivot", Vec3::ZERO),
+        }
+    }
+
+    /// Returns the local transform matrix, following the FBX SDK definition:
+    /// `T * Roff * Rp * Rpre * R * Rpost^-1 * Rp^-1 * Soff * Sp * S * Sp^-1`.
+    fn matrix(&self) -> Mat4 {
+        // Pre and post rotations always use the XYZ order.
+        let pre_rotation = euler_rotation(self.pre_rotation, 0);
+        let post_rotation = euler_rotation(self.post_rotation, 0);
+        let rotation = euler_rotation(self.rotation, self.rotation_order);
+        Mat4::from_translation(self.translation + self.rotation_offset + self.rotation_pivot)
+            * Mat4::from_quat(pre_rotation * rotation * post_rotation.inverse())
+            * Mat4::from_translation(
+                -self.rotation_pivot + self.scaling_offset + self.scaling_pivot,
+            )
+            * Mat4::from_scale(self.scaling)
+            * Mat4::from_translation(-self.scaling_pivot)
+    }
+}
+
+/// Returns the transform of a model's geometry relative to the model, which doesn't affect its children.
+fn geometric_transform(model: &Node) -> Transform {
+    let vector =
+        |name: &str, default: Vec3| model.property70(name).and_then(vec3).unwrap_or(default);
+    Transform {
+        translation: vector("GeometricTranslation", Vec3::ZERO),
+        rotation: euler_rotation(vector("GeometricRotation", Vec3::ZERO), 0),
+        scale: vector("GeometricScaling", Vec3::ONE),
+    }
+}
+
+/// How the values of a layer element are mapped to the polygons of a geometry.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mapping {
+    /// One value for each corner of each polygon.
+    PolygonVertex,
+    /// One value for each control point.
+    Vertex,
+    /// One value for each polygon.
+    Polygon,
+    /// One value for the whole geometry.
+    AllSame,
+}
+
+/// A layer element of a geometry, such as its normals or texture coordinates.
+struct LayerElement {
+    values: Vec<f64>,
+    width: usize,
+    indices: Option<Vec<i64>>,
+    mapping: Mapping,
+}
+
+impl LayerElement {
+    /// Reads the layer element `element` (such as `LayerElementUV`) with the given index, whose values are
+    /// stored in the `values` child and indexed by `indices`.
+    fn new(
+        geometry: &Node,
+        element: &str,
+        layer: i64,
+        values: &str,
+        indices: &str,
+        width: usize,
+    ) -> Option<Self> {
+        let node = geometry.children_named(element).find(|node| {
+            node.properties
+                .first()
+                .and_then(Property::as_i64)
+                .unwrap_or(0)
+                == layer
+        })?;
+        let mapping = match node
+            .value("MappingInformationType")
+            .and_then(Property::as_str)
+        {
+            Some("ByPolygonVertex") => Mapping::PolygonVertex,
+            Some("ByVertex" | "ByVertice" | "ByControlPoint") => Mapping::Vertex,
+            Some("ByPolygon") => Mapping::Polygon,
+            Some("AllSame") => Mapping::AllSame,
+            mapping => {
+                warn!("Unsupported {element} mapping {mapping:?}");
+                return None;
+            }
+        };
+        let indices = match node
+            .value("ReferenceInformationType")
+            .and_then(Property::as_str)
+        {
+            Some("IndexToDirect" | "Index") => Some(node.value(indices)?.to_i64s()?),
+            _ => None,
+        };
+        Some(Self {
+            values: node.value(values)?.to_f64s()?,
+            width,
+            indices,
+            mapping,
+        })
+    }
+
+    /// Returns the value for a corner of a polygon, or [`None`] if the element doesn't have a value for it.
+    fn get(&self, polygon: usize, control_point: usize, polygon_vertex: usize) -> Option<&[f64]> {
+        let element = match self.mapping {
+            Mapping::PolygonVertex => polygon_vertex,
+            Mapping::Vertex => control_point,
+            Mapping::Polygon => polygon,
+            Mapping::AllSame => 0,
+        };
+        let element = match &self.indices {
+            Some(indices) => usize::try_from(*indices.get(element)?).ok()?,
+            None => element,
+        };
+        self.values
+            .get(element * self.width..(element + 1) * self.width)
+    }
+}
+
+/// An animation curve of a single component, such as the `X` translation.
+#[cfg(feature = "bevy_animation")]
+struct Curve {
+    /// The key times, in FBX time units.
+    times: Vec<i64>,
+    values: Vec<f64>,
+}
+
+#[cfg(feature = "bevy_animation")]
+impl Curve {
+    fn new(curve: &Node) -> Option<Self> {
+        let times = curve.value("KeyTime")?.to_i64s()?;
+        let values = curve.value("KeyValueFloat")?.to_f64s()?;
+        (times.len() == values.len() && !times.is_empty()).then_some(Self { times, values })
+    }
+
+    /// Returns the value at `time`, interpolating linearly between keys.
+    fn sample(&self, time: i64) -> f64 {
+        let next = self.times.partition_point(|&key| key <= time);
+        if next == 0 {
+            return self.values[0];
+        }
+        if next == self.times.len() {
+            return self.values[next - 1];
+        }
+        let (start, end) = (self.times[next - 1], self.times[next]);
+        let t = (time - start) as f64 / (end - start) as f64;
+        self.values[next - 1] + (self.values[next] - self.values[next - 1]) * t
+    }
+}
+
+/// The animated properties of a model in an animation stack.
+#[cfg(feature = "bevy_animation")]
+#[derive(Default)]
+struct ModelAnimation {
+    /// The curves of the `X`, `Y` and `Z` components of the translation, rotation and scaling.
+    curves: [[Option<Curve>; 3]; 3],
+}
+
+#[cfg(feature = "bevy_animation")]
+impl ModelAnimation {
+    fn times(&self) -> Vec<i64> {
+        let mut times = self
+            .curves
+            .iter()
+            .flatten()
+            .flatten()
+            .flat_map(|curve| curve.times.iter().copied())
+            .collect::<Vec<_>>();
+        times.sort_unstable();
+        times.dedup();
+        times
+    }
+
+    /// Returns the local transform of the model at `time`.
+    fn sample(&self, transform: &ModelTransform, time: i64) -> Transform {
+        let mut transform = *transform;
+        let properties = [
+            &mut transform.translation,
+            &mut transform.rotation,
+            &mut transform.scaling,
+        ];
+        for (property, curves) in properties.into_iter().zip(&self.curves) {
+            for (component, curve) in curves.iter().enumerate() {
+                if let Some(curve) = curve {
+                    property[component] = curve.sample(time) as f32;
+                }
+            }
+        }
+        Transform::from_matrix(transform.matrix())
+    }
+}
+
+/// The meshes loaded from a `Geometry` object, which can be shared by several models.
+#[derive(Clone, Default)]
+struct LoadedGeometry {
+    /// The mesh of each material slot used by the geometry.
+    primitives: Vec<(usize, Handle<Mesh>)>,
+    /// The inverse bind matrices and joint models of the geometry's skin.
+    skin: Option<(Handle<SkinnedMeshInverseBindposes>, Vec<i64>)>,
+}
+
+/// The `Skin` deformer of a geometry.
+struct LoadedSkin {
+    inverse_bindposes: Handle<SkinnedMeshInverseBindposes>,
+    /// The ids of the joint models.
+    joints: Vec<i64>,
+    /// The four most important joints of each control point, and their weights.
+    influences: Vec<([u16; 4], [f32; 4])>,
+}
+
+/// A model spawned in the scene.
+struct SpawnedModel {
+    entity: Entity,
+    /// The names of the model and its ancestors, starting from the root.
+    #[cfg_attr(
+        not(feature = "bevy_animation"),
+        expect(dead_code, reason = "only used to animate models")
+    )]
+    path: Vec<Name>,
+    #[cfg_attr(
+        not(feature = "bevy_animation"),
+        expect(dead_code, reason = "only used to animate models")
+    )]
+    transform: ModelTransform,
+}
+
+/// The vertices of a mesh being built.
+#[derive(Default)]
+struct MeshBuilder {
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    uvs: [Vec<[f32; 2]>; 2],
+    colors: Vec<[f32; 4]>,
+    joint_indices: Vec<[u16; 4]>,
+    joint_weights: Vec<[f32; 4]>,
+    indices: Vec<u32>,
+    /// Maps a control point and its attributes to the vertex created for them.
+    vertices: HashMap<[u32; 12], u32>,
+}
+
+/// Converts a [`Document`] to Bevy assets.
+struct DocumentLoader<'a, 'b, 'c> {
+    document: &'a Document,
+    objects: Objects<'a>,
+    settings: &'a FbxLoaderSettings,
+    load_context: &'b mut LoadContext<'c>,
+    supported_compressed_formats: CompressedImageFormats,
+    meshes: Vec<Handle<Mesh>>,
+    materials: Vec<Handle<StandardMaterial>>,
+    named_materials: HashMap<Box<str>, Handle<StandardMaterial>>,
+    /// The handle of each `Material` object, and whether it has a normal map.
+    material_handles: HashMap<i64, (Handle<StandardMaterial>, bool)>,
+    default_material: Option<Handle<StandardMaterial>>,
+    geometries: HashMap<i64, LoadedGeometry>,
+    textures: HashMap<(i64, bool), Handle<Image>>,
+    texture_count: usize,
+    skin_count: usize,
+    models: HashMap<i64, SpawnedModel>,
+    /// The mesh entities to skin once all models are spawned, with their inverse bind matrices and joint models.
+    skinned_primitives: Vec<(Entity, Handle<SkinnedMeshInverseBindposes>, Vec<i64>)>,
+}
+
+impl<'a> DocumentLoader<'a, '_, '_> {
+    fn load(mut self) -> Result<Fbx, FbxError> {
+        if !self.settings.load_materials.is_empty() {
+            let materials = self
+                .objects
+                .objects
+                .iter()
+                .filter(|(_, object)| object.name == "Material")
+                .copied()
+                .collect::<Vec<_>>();
+            for (id, object) in materials {
+                let (material, normal_mapped) = self.load_material(id, object)?;
+                let handle = self.add_material(material);
+                self.named_materials
+                    .insert(object_name(object).into(), handle.clone());
+                self.material_handles.insert(id, (handle, normal_mapped));
+            }
+        }
+
+        let mut world = World::default();
+        let root = world
+            .spawn((self.root_transform(), Visibility::default()))
+            .id();
+        let root_models = self
+            .objects
+            .objects
+            .iter()
+            .filter(|(id, object)| {
+                object.name == "Model" && self.objects.parents_of(*id, "Model").is_empty()
+            })
+            .copied()
+            .collect::<Vec<_>>();
+        for (id, model) in root_models {
+            self.spawn_model(&mut world, id, model, root, &[]);
+        }
+
+        let models = core::mem::take(&mut self.models);
+        for (primitive, inverse_bindposes, joint_models) in
+            core::mem::take(&mut self.skinned_primitives)
+        {
+            let Some(joints) = joint_models
+                .iter()
+                .map(|joint| models.get(joint).map(|model| model.entity))
+                .collect::<Option<Vec<_>>>()
+            else {
+                warn!("Skipping a skin whose joints aren't part of the scene");
+                continue;
+            };
+            world.entity_mut(primitive).insert(SkinnedMesh {
+                inverse_bindposes,
+                joints,
+            });
+        }
+
+        #[cfg(feature = "bevy_animation")]
+        let animations = if self.settings.load_animations {
+            self.load_animations(&mut world, root, &models)
+        } else {
+            Vec::new()
+        };
+
+        let scene = self
+            .load_context
+            .add_labeled_asset(FbxAssetLabel::Scene.to_string(), Scene::new(world));
+        Ok(Fbx {
+            scene,
+            meshes: self.meshes,
+            materials: self.materials,
+            named_materials: self.named_materials,
+            #[cfg(feature = "bevy_animation")]
+            named_animations: animations.iter().cloned().collect(),
+            #[cfg(feature = "bevy_animation")]
+            animations: animations.into_iter().map(|(_, handle)| handle).collect(),
+        })
+    }
+
+    /// Returns the transform of the scene root, which converts the axes and units of the file.
+    fn root_transform(&self) -> Transform {
+        let global_settings = self.document.node("GlobalSettings");
+        let setting = |name: &str| {
+            global_settings?
+                .property70(name)?
+                .first()
+                .and_then(Property::as_f64)
+        };
+        let mut matrix = Mat4::IDENTITY;
+        if self.settings.convert_coordinates {
+            let axis = |axis: &str, sign: &str, default: usize| {
+                let index = setting(axis).map_or(default, |index| index as usize);
+                let mut vector = Vec3::ZERO;
+                vector[index.min(2)] = if setting(sign).unwrap_or(1.0) < 0.0 {
+                    -1.0
+                } else {
+                    1.0
+                };
+                vector
+            };
+            // The rows map the right, up and front axes of the file to Bevy's X, Y and Z axes.
+            let axes = Mat3::from_cols(
+                axis("CoordAxis", "CoordAxisSign", 0),
+                axis("UpAxis", "UpAxisSign", 1),
+                axis("FrontAxis", "FrontAxisSign", 2),
+            )
+            .transpose();
+            if axes.determinant() == 0.0 {
+                warn!("Ignoring invalid axes in the FBX global settings");
+            } else {
+                matrix = Mat4::from_mat3(axes);
+            }
+        }
+        if self.settings.convert_units
+            && let Some(centimeters_per_unit) = setting("UnitScaleFactor")
+        {
+            matrix *= Mat4::from_scale(Vec3::splat(centimeters_per_unit as f32 / 100.0));
+        }
+        Transform::from_matrix(matrix)
+    }
+
+    fn add_material(&mut self, material: StandardMaterial) -> Handle<StandardMaterial> {
+        let label = FbxAssetLabel::Material(self.materials.len());
+        let handle = self
+            .load_context
+            .add_labeled_asset(label.to_string(), material);
+        self.materials.push(handle.clone());
+        handle
+    }
+
+    /// Spawns a model and its descendants, where `path` contains the names of the model's ancestors.
+    fn spawn_model(
+        &mut self,
+        world: &mut World,
+        id: i64,
+        model: &'a Node,
+        parent: Entity,
+        path: &[Name],
+    ) {
+        // Models with several parents or cycles are only spawned once.
+        if self.models.contains_key(&id) {
+            return;
+        }
+        let name = Name::new(object_name(model).to_string());
+        let transform = ModelTransform::new(model);
+        let visibility = match model
+            .property70("Visibility")
+            .and_then(<[Property]>::first)
+            .and_then(Property::as_f64)
+        {
+            Some(0.0) => Visibility::Hidden,
+            _ => Visibility::Inherited,
+        };
+        let entity = world
+            .spawn((
+                name.clone(),
+                Transform::from_matrix(transform.matrix()),
+                visibility,
+                ChildOf(parent),
+            ))
+            .id();
+        let mut path = path.to_vec();
+        path.push(name);
+        self.models.insert(
+            id,
+            SpawnedModel {
+                entity,
+                path: path.clone(),
+                transform,
+            },
+        );
+
+        let geometry = self.objects.children_of(id, "Geometry").into_iter().next();
+        if let Some((geometry_id, geometry, _)) = geometry
+            && object_class(model) == "Mesh"
+            && !self.settings.load_meshes.is_empty()
+        {
+            // Material slots of the geometry index the materials connected to the model.
+            let materials = self
+                .objects
+                .children_of(id, "Material")
+                .into_iter()
+                .filter_map(|(material, _, _)| self.material_handles.get(&material).cloned())
+                .collect::<Vec<_>>();
+            let needs_tangents = materials.iter().any(|(_, normal_mapped)| *normal_mapped);
+            let loaded = match self.geometries.get(&geometry_id) {
+                Some(loaded) => loaded.clone(),
+                None => {
+                    let loaded = self.load_geometry(geometry_id, geometry, needs_tangents);
+                    self.geometries.insert(geometry_id, loaded.clone());
+                    loaded
+                }
+            };
+            let geometric_transform = geometric_transform(model);
+            for (slot, mesh) in loaded.primitives {
+                let mut primitive =
+                    world.spawn((Mesh3d(mesh), geometric_transform, ChildOf(entity)));
+                let material = match materials.get(slot) {
+                    Some((material, _)) => Some(material.clone()),
+                    None => self.default_material(),
+                };
+                if let Some(material) = material {
+                    primitive.insert(MeshMaterial3d(material));
+                }
+                if let Some((inverse_bindposes, joints)) = &loaded.skin {
+                    self.skinned_primitives.push((
+                        primitive.id(),
+                        inverse_bindposes.clone(),
+                        joints.clone(),
+                    ));
+                }
+            }
+        }
+
+        for (child_id, child, _) in self.objects.children_of(id, "Model") {
+            self.spawn_model(world, child_id, child, entity, &path);
+        }
+    }
+
+    /// Returns the material of meshes without a material for their slot.
+    fn default_material(&mut self) -> Option<Handle<StandardMaterial>> {
+        if self.settings.load_materials.is_empty() {
+            return None;
+        }
+        if self.default_material.is_none() {
+            self.default_material = Some(self.add_material(StandardMaterial::default()));
+        }
+        self.default_material.clone()
+    }
+
+    /// Loads a `Material` object, returning whether it has a normal map.
+    fn load_material(
+        &mut self,
+        id: i64,
+        material: &'a Node,
+    ) -> Result<(StandardMaterial, bool), FbxError> {
+        let color = |name: &str| material.property70(name).and_then(vec3);
+        let factor = |name: &str| {
+            material
+                .property70(name)
+                .and_then(<[Property]>::first)
+                .and_then(Property::as_f64)
+        };
+        let diffuse = color("DiffuseColor")
+            .or_else(|| color("Diffuse"))
+            .unwrap_or(Vec3::splat(0.8))
+            * factor("DiffuseFactor").unwrap_or(1.0) as f32;
+        let emissive = color("EmissiveColor").unwrap_or(Vec3::ZERO)
+            * factor("EmissiveFactor").unwrap_or(1.0) as f32;
+        let opacity = factor("Opacity")
+            .or_else(|| {
+                let transparency = factor("TransparencyFactor")?;
+                let color = color("TransparentColor").unwrap_or(Vec3::ONE);
+                Some(1.0 - transparency * (color.element_sum() / 3.0) as f64)
+            })
+            .unwrap_or(1.0)
+            .clamp(0.0, 1.0) as f32;
+
+        let mut standard_material = StandardMaterial {
+            base_color: Color::linear_rgba(diffuse.x, diffuse.y, diffuse.z, opacity),
+            emissive: LinearRgba::rgb(emissive.x, emissive.y, emissive.z),
+            metallic: 0.0,
+            alpha_mode: if opacity < 1.0 {
+                AlphaMode::Blend
+            } else {
+                AlphaMode::Opaque
+            },
+            ..Default::default()
+        };
+        // Phong shininess is converted to a roughness with the same highlight size.
+        if let Some(shininess) = factor("Shininess").or_else(|| factor("ShininessExponent")) {
+            standard_material.perceptual_roughness =
+                ((2.0 / (shininess.max(0.0) + 2.0)).sqrt() as f32).sqrt();
+        }
+
+        let mut normal_mapped = false;
+        for (texture_id, texture, property) in self.objects.children_of(id, "Texture") {
+            match property {
+                Some("DiffuseColor") => {
+                    standard_material.base_color_texture =
+                        self.load_texture(texture_id, texture, true)?;
+                    // The texture replaces the diffuse color.
+                    standard_material.base_color = Color::WHITE.with_alpha(opacity);
+                }
+                Some("EmissiveColor") => {
+                    standard_material.emissive_texture =
+                        self.load_texture(texture_id, texture, true)?;
+                    standard_material.emissive = LinearRgba::WHITE;
+                }
+                Some("NormalMap") => {
+                    standard_material.normal_map_texture =
+                        self.load_texture(texture_id, texture, false)?;
+                    normal_mapped = standard_material.normal_map_texture.is_some();
+                }
+                // The opacity is read from the alpha channel of the base color texture.
+                Some("TransparentColor" | "TransparencyFactor") => {
+                    standard_material.alpha_mode = AlphaMode::Blend;
+                }
+                _ => {}
+            }
+        }
+        Ok((standard_material, normal_mapped))
+    }
+
+    /// Loads the image of a `Texture` object, either embedded in the file or next to it.
+    fn load_texture(
+        &mut self,
+        id: i64,
+        texture: &'a Node,
+        is_srgb: bool,
+    ) -> Result<Option<Handle<Image>>, FbxError> {
+        if let Some(handle) = self.textures.get(&(id, is_srgb)) {
+            return Ok(Some(handle.clone()));
+        }
+        let file_name = |name: &str| {
+            texture
+                .value(name)
+                .and_then(Property::as_str)
+                .filter(|file| !file.is_empty())
+                .map(|file| file.replace('\\', "/"))
+        };
+        let Some(file) = file_name("RelativeFilename").or_else(|| file_name("FileName")) else {
+            return Ok(None);
+        };
+        let address_mode = |name: &str| match texture
+            .property70(name)
+            .and_then(<[Property]>::first)
+            .and_then(Property::as_i64)
+        {
+            Some(1) => ImageAddressMode::ClampToEdge,
+            _ => ImageAddressMode::Repeat,
+        };
+        let sampler = ImageSamplerDescriptor {
+            address_mode_u: address_mode("WrapModeU"),
+            address_mode_v: address_mode("WrapModeV"),
+            ..ImageSamplerDescriptor::linear()
+        };
+
+        let embedded =
+            self.objects
+                .children_of(id, "Video")
+                .into_iter()
+                .find_map(|(_, video, _)| match video.value("Content") {
+                    Some(Property::Raw(content)) if !content.is_empty() => Some(content),
+                    _ => None,
+                });
+        let handle = if let Some(content) = embedded {
+            let extension = file.rsplit('.').next().unwrap_or_default().to_lowercase();
+            let image = Image::from_buffer(
+                content,
+                ImageType::Extension(&extension),
+                self.supported_compressed_formats,
+                is_srgb,
+                ImageSampler::Descriptor(sampler),
+                self.settings.load_materials,
+            )
+            .map_err(|err| FbxError::Texture(file.clone(), err))?;
+            let label = FbxAssetLabel::Texture(self.texture_count);
+            self.texture_count += 1;
+            self.load_context
+                .add_labeled_asset(label.to_string(), image)
+        } else {
+            // Absolute paths point to the machine the file was authored on, so only their file name is kept.
+            let is_absolute = file.starts_with('/') || file.as_bytes().get(1) == Some(&b':');
+            let relative = if is_absolute {
+                file.rsplit('/').next().unwrap_or_default()
+            } else {
+                &file
+            };
+            let path = self
+                .load_context
+                .path()
+                .resolve_embed(relative)
+                .map_err(|err| FbxError::InvalidTexturePath(file.clone(), err))?;
+            self.load_context
+                .loader()
+                .with_settings(move |settings: &mut ImageLoaderSettings| {
+                    settings.is_srgb = is_srgb;
+                    settings.sampler = ImageSampler::Descriptor(sampler.clone());
+                })
+                .load(path)
+        };
+        self.textures.insert((id, is_srgb), handle.clone());
+        Ok(Some(handle))
+    }
+
+    /// Loads a `Geometry` object as a mesh for each material slot used by its polygons.
+    fn load_geometry(
+        &mut self,
+        id: i64,
+        geometry: &'a Node,
+        needs_tangents: bool,
+    ) -> LoadedGeometry {
+        let name = object_name(geometry);
+        let (Some(control_points), Some(polygon_vertices)) = (
+            geometry.value("Vertices").and_then(Property::to_f64s),
+            geometry
+                .value("PolygonVertexIndex")
+                .and_then(Property::to_i64s),
+        ) else {
+            warn!("Geometry {name} is missing its vertices or polygons");
+            return LoadedGeometry::default();
+        };
+        let control_point_count = control_points.len() / 3;
+
+        let normals = LayerElement::new(
+            geometry,
+            "LayerElementNormal",
+            0,
+            "Normals",
+            "NormalsIndex",
+            3,
+        );
+        let uvs = [0, 1]
+            .map(|layer| LayerElement::new(geometry, "LayerElementUV", layer, "UV", "UVIndex", 2));
+        let colors = LayerElement::new(geometry, "LayerElementColor", 0, "Colors", "ColorIndex", 4);
+        // Material slots are indexed directly, even when the reference type is `IndexToDirect`.
+        let material_slots = geometry.child("LayerElementMaterial").and_then(|element| {
+            let slots = element.value("Materials")?.to_i64s()?;
+            let by_polygon = element
+                .value("MappingInformationType")
+                .and_then(Property::as_str)
+                == Some("ByPolygon");
+            Some((slots, by_polygon))
+        });
+        let skin = self.load_skin(id, control_point_count);
+
+        let mut builders = BTreeMap::<usize, MeshBuilder>::new();
+        let mut polygon = 0;
+        let mut polygon_start = 0;
+        for (polygon_vertex, &index) in polygon_vertices.iter().enumerate() {
+            // The last vertex of each polygon is stored as `-index - 1`.
+            if index >= 0 {
+                continue;
+            }
+            let corners = polygon_start..polygon_vertex + 1;
+            polygon_start = polygon_vertex + 1;
+            let slot = material_slots
+                .as_ref()
+                .and_then(|(slots, by_polygon)| slots.get(if *by_polygon { polygon } else { 0 }))
+                .map_or(0, |&slot| slot.max(0) as usize);
+            let builder = builders.entry(slot).or_default();
+            let mut face = Vec::with_capacity(corners.len());
+            for corner in corners {
+                let control_point = polygon_vertices[corner];
+                let control_point = if control_point < 0 {
+                    !control_point
+                } else {
+                    control_point
+                } as usize;
+                if control_point >= control_point_count {
+                    continue;
+                }
+                let normal = layer_value(&normals, polygon, control_point, corner, &[0.0; 3]);
+                let uv = [
+                    layer_value(&uvs[0], polygon, control_point, corner, &[0.0; 2]),
+                    layer_value(&uvs[1], polygon, control_point, corner, &[0.0; 2]),
+                ];
+                let color = layer_value(&colors, polygon, control_point, corner, &[1.0; 4]);
+                let mut key = [0; 12];
+                key[0] = control_point as u32;
+                for (key, value) in key[1..]
+                    .iter_mut()
+                    .zip(normal.iter().chain(uv[0]).chain(uv[1]).chain(color))
+                {
+                    *key = (*value as f32).to_bits();
+                }
+                let vertex = *builder.vertices.entry(key).or_insert_with(|| {
+                    let position = &control_points[control_point * 3..control_point * 3 + 3];
+                    builder.positions.push([
+                        position[0] as f32,
+                        position[1] as f32,
+                        position[2] as f32,
+                    ]);
+                    if normals.is_some() {
+                        builder.normals.push([
+                            normal[0] as f32,
+                            normal[1] as f32,
+                            normal[2] as f32,
+                        ]);
+                    }
+                    for (values, (element, uv)) in builder.uvs.iter_mut().zip(uvs.iter().zip(uv)) {
+                        // FBX texture coordinates start at the bottom of textures.
+                        if element.is_some() {
+                            values.push([uv[0] as f32, 1.0 - uv[1] as f32]);
+                        }
+                    }
+                    if colors.is_some() {
+                        builder
+                            .colors
+                            .push(core::array::from_fn(|index| color[index] as f32));
+                    }
+                    if let Some(skin) = &skin {
+                        let (joints, weights) = skin.influences[control_point];
+                        builder.joint_indices.push(joints);
+                        builder.joint_weights.push(weights);
+                    }
+                    (builder.positions.len() - 1) as u32
+                });
+                face.push(vertex);
+            }
+            // Polygons are triangulated as fans.
+            for index in 1..face.len().saturating_sub(1) {
+                builder
+                    .indices
+                    .extend([face[0], face[index], face[index + 1]]);
+            }
+            polygon += 1;
+        }
+
+        let mut loaded = LoadedGeometry {
+            skin: skin.map(|skin| (skin.inverse_bindposes, skin.joints)),
+            ..Default::default()
+        };
+        for (slot, builder) in builders {
+            if builder.indices.is_empty() {
+                continue;
+            }
+            let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, self.settings.load_meshes);
+            mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, builder.positions);
+            let [uv_0, uv_1] = builder.uvs;
+            if !uv_0.is_empty() {
+                mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uv_0);
+            }
+            if !uv_1.is_empty() {
+                mesh.insert_attribute(Mesh::ATTRIBUTE_UV_1, uv_1);
+            }
+            if !builder.colors.is_empty() {
+                mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, builder.colors);
+            }
+            if !builder.joint_indices.is_empty() {
+                mesh.insert_attribute(
+                    Mesh::ATTRIBUTE_JOINT_INDEX,
+                    VertexAttributeValues::Uint16x4(builder.joint_indices),
+                );
+                mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT, builder.joint_weights);
+            }
+            mesh.insert_indices(Indices::U32(builder.indices));
+            if builder.normals.is_empty() {
+                mesh.compute_smooth_normals();
+            } else {
+                mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, builder.normals);
+            }
+            if needs_tangents
+                && mesh.contains_attribute(Mesh::ATTRIBUTE_UV_0)
+                && let Err(err) = mesh.generate_tangents()
+            {
+                warn!("Failed to generate vertex tangents for {name}: {err}");
+            }
+
+            let label = FbxAssetLabel::Mesh(self.meshes.len());
+            let handle = self.load_context.add_labeled_asset(label.to_string(), mesh);
+            self.meshes.push(handle.clone());
+            loaded.primitives.push((slot, handle));
+        }
+        loaded
+    }
+
+    /// Loads the `Skin` deformer of a geometry.
+    fn load_skin(&mut self, geometry_id: i64, control_point_count: usize) -> Option<LoadedSkin> {
+        let (skin_id, _, _) = self
+            .objects
+            .children_of(geometry_id, "Deformer")
+            .into_iter()
+            .find(|(_, deformer, _)| object_class(deformer) == "Skin")?;
+        let mut inverse_bindposes = Vec::new();
+        let mut joints = Vec::new();
+        let mut influences = vec![Vec::<(u16, f32)>::new(); control_point_count];
+        for (cluster_id, cluster, _) in self.objects.children_of(skin_id, "Deformer") {
+            if object_class(cluster) != "Cluster" {
+                continue;
+            }
+            let Some((joint, _, _)) = self
+                .objects
+                .children_of(cluster_id, "Model")
+                .into_iter()
+                .next()
+            else {
+                continue;
+            };
+            let Ok(joint_index) = u16::try_from(joints.len()) else {
+                break;
+            };
+            // The bind pose maps the mesh at bind time to the space of the joint at bind time.
+            let transform = cluster
+                .value("Transform")
+                .and_then(Property::to_f64s)
+                .and_then(|values| matrix(&values))
+                .unwrap_or(Mat4::IDENTITY);
+            let transform_link = cluster
+                .value("TransformLink")
+                .and_then(Property::to_f64s)
+                .and_then(|values| matrix(&values))
+                .unwrap_or(Mat4::IDENTITY);
+            inverse_bindposes.push(transform_link.inverse() * transform);
+            joints.push(joint);
+
+            let indices = cluster
+                .value("Indexes")
+                .and_then(Property::to_i64s)
+                .unwrap_or_default();
+            let weights = cluster
+                .value("Weights")
+                .and_then(Property::to_f64s)
+                .unwrap_or_default();
+            for (&index, &weight) in indices.iter().zip(&weights) {
+                if let Some(influences) = usize::try_from(index)
+                    .ok()
+                    .and_then(|index| influences.get_mut(index))
+                {
+                    influences.push((joint_index, weight as f32));
+                }
+            }
+        }
+        if joints.is_empty() {
+            return None;
+        }
+        if joints.len() > MAX_JOINTS {
+            warn!(
+                "The FBX skin of {} has {} joints, but the maximum supported is {}",
+                self.objects
+                    .by_id
+                    .get(&geometry_id)
+                    .map(|geometry| object_name(geometry))
+                    .unwrap_or_default(),
+                joints.len(),
+                MAX_JOINTS
+            );
+        }
+
+        // Only the four joints with the largest weights are kept, and their weights are normalized.
+        let influences = influences
+            .into_iter()
+            .map(|mut influences| {
+                influences.sort_by(|a, b| b.1.total_cmp(&a.1));
+                influences.truncate(4);
+                let total = influences.iter().map(|(_, weight)| weight).sum::<f32>();
+                let mut joints = [0; 4];
+                let mut weights = [0.0; 4];
+                if total > 0.0 {
+                    for (index, (joint, weight)) in influences.into_iter().enumerate() {
+                        joints[index] = joint;
+                        weights[index] = weight / total;
+                    }
+                } else {
+                    // Control points without weights follow the first joint.
+                    weights[0] = 1.0;
+                }
+                (joints, weights)
+            })
+            .collect();
+
+        let label = FbxAssetLabel::InverseBindMatrices(self.skin_count);
+        self.skin_count += 1;
+        let inverse_bindposes = self.load_context.add_labeled_asset(
+            label.to_string(),
+            SkinnedMeshInverseBindposes::from(inverse_bindposes),
+        );
+        Some(LoadedSkin {
+            inverse_bindposes,
+            joints,
+            influences,
+        })
+    }
+
+    /// Loads each `AnimationStack` object as an animation clip, along with its name.
+    #[cfg(feature = "bevy_animation")]
+    fn load_animations(
+        &mut self,
+        world: &mut World,
+        root: Entity,
+        models: &HashMap<i64, SpawnedModel>,
+    ) -> Vec<(Box<str>, Handle<AnimationClip>)> {
+        let mut animations = Vec::new();
+        let mut animated_models = BTreeMap::new();
+        let stacks = self
+            .objects
+            .objects
+            .iter()
+            .filter(|(_, object)| object.name == "AnimationStack")
+            .copied()
+            .collect::<Vec<_>>();
+        for (stack_id, stack) in stacks {
+            let layers = self.objects.children_of(stack_id, "AnimationLayer");
+            let Some(&(layer_id, _, _)) = layers.first() else {
+                continue;
+            };
+            if layers.len() > 1 {
+                warn!(
+                    "Animation stack {} has several layers, only the first one is loaded",
+                    object_name(stack)
+                );
+            }
+
+            let mut model_animations = BTreeMap::<i64, ModelAnimation>::new();
+            for (curve_node_id, _, _) in self.objects.children_of(layer_id, "AnimationCurveNode") {
+                for (model_id, _, property) in self.objects.parents_of(curve_node_id, "Model") {
+                    let property = match property {
+                        Some("Lcl Translation") => 0,
+                        Some("Lcl Rotation") => 1,
+                        Some("Lcl Scaling") => 2,
+                        _ => continue,
+                    };
+                    let animation = model_animations.entry(model_id).or_default();
+                    for (_, curve, component) in
+                        self.objects.children_of(curve_node_id, "AnimationCurve")
+                    {
+                        let component = match component {
+                            Some("d|X") => 0,
+                            Some("d|Y") => 1,
+                            Some("d|Z") => 2,
+                            _ => continue,
+                        };
+                        animation.curves[property][component] = Curve::new(curve);
+                    }
+                }
+            }
+
+            let start = stack
+                .property70("LocalStart")
+                .and_then(<[Property]>::first)
+                .and_then(Property::as_i64);
+            let mut clip = AnimationClip::default();
+            for (model_id, animation) in model_animations {
+                let Some(model) = models.get(&model_id) else {
+                    continue;
+                };
+                let times = animation.times();
+                if times.len() < 2 {
+                    continue;
+                }
+                let start = start.unwrap_or(times[0]);
+                let samples = times
+                    .iter()
+                    .map(|&time| {
+                        (
+                            ((time - start) as f64 / TICKS_PER_SECOND) as f32,
+                            animation.sample(&model.transform, time),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                let target = AnimationTargetId::from_names(model.path.iter());
+                let curves = (
+                    UnevenSampleAutoCurve::new(
+                        samples
+                            .iter()
+                            .map(|(time, transform)| (*time, transform.translation)),
+                    ),
+                    UnevenSampleAutoCurve::new(
+                        samples
+                            .iter()
+                            .map(|(time, transform)| (*time, transform.rotation)),
+                    ),
+                    UnevenSampleAutoCurve::new(
+                        samples
+                            .iter()
+                            .map(|(time, transform)| (*time, transform.scale)),
+                    ),
+                );
+                let (Ok(translations), Ok(rotations), Ok(scales)) = curves else {
+                    warn!("Invalid animation keys for {}", model.path.last().unwrap());
+                    continue;
+                };
+                clip.add_curve_to_target(
+                    target,
+                    AnimatableCurve::new(animated_field!(Transform::translation), translations),
+                );
+                clip.add_curve_to_target(
+                    target,
+                    AnimatableCurve::new(animated_field!(Transform::rotation), rotations),
+                );
+                clip.add_curve_to_target(
+                    target,
+                    AnimatableCurve::new(animated_field!(Transform::scale), scales),
+                );
+                animated_models.insert(model_id, (model.entity, target));
+            }
+
+            let label = FbxAssetLabel::Animation(animations.len());
+            let handle = self.load_context.add_labeled_asset(label.to_string(), clip);
+            animations.push((object_name(stack).into(), handle));
+        }
+
+        if !animated_models.is_empty() {
+            world.entity_mut(root).insert(AnimationPlayer::default());
+            for (entity, target) in animated_models.into_values() {
+                world.entity_mut(entity).insert((target, AnimatedBy(root)));
+            }
+        }
+        animations
+    }
+}
+
+/// Returns the value of a layer element for a corner of a polygon, or `default` if there isn't any.
+fn layer_value<'a>(
+    element: &'a Option<LayerElement>,
+    polygon: usize,
+    control_point: usize,
+    polygon_vertex: usize,
+    default: &'a [f64],
+) -> &'a [f64] {
+    element
+        .as_ref()
+        .and_then(|element| element.get(polygon, control_point, polygon_vertex))
+        .unwrap_or(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::parse;
+
+    #[test]
+    fn model_transform() {
+        let document = parse(
+            br#"Objects:  {
+	Model: 1, "Model::Arm", "LimbNode" {
+		Properties70:  {
+			P: "Lcl Translation", "Lcl Translation", "", "A",1,2,3
+			P: "Lcl Rotation", "Lcl Rotation", "", "A",0,90,0
+			P: "PreRotation", "Vector3D", "Vector", "",-90,0,0
+			P: "RotationOrder", "enum", "", "",5
+		}
+	}
+}
+"#,
+        )
+        .unwrap();
+        let model = document.node("Objects").unwrap().child("Model").unwrap();
+        let transform = Transform::from_matrix(ModelTransform::new(model).matrix());
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(1.0, 2.0, 3.0), 1e-5));
+        let expected = Quat::from_rotation_x(-core::f32::consts::FRAC_PI_2)
+            * Quat::from_rotation_y(core::f32::consts::FRAC_PI_2);
+        assert!(transform.rotation.abs_diff_eq(expected, 1e-5));
+
+        // The first axis of the rotation order is applied first.
+        let rotation = euler_rotation(Vec3::new(10.0, 20.0, 30.0), 0);
+        let expected = Quat::from_rotation_z(30f32.to_radians())
+            * Quat::from_rotation_y(20f32.to_radians())
+            * Quat::from_rotation_x(10f32.to_radians());
+        assert!(rotation.abs_diff_eq(expected, 1e-5));
+    }
+
+    #[cfg(feature = "bevy_animation")]
+    #[test]
+    fn sample_curve() {
+        let curve = Curve {
+            times: vec![0, 10, 20],
+            values: vec![0.0, 1.0, 3.0],
+        };
+        assert_eq!(curve.sample(-5), 0.0);
+        assert_eq!(curve.sample(15), 2.0);
+        assert_eq!(curve.sample(30), 3.0);
+    }
+}
@@ -0,0 +1,270 @@
+use bevy_app::{Plugin, PreUpdate};
+use bevy_ecs::{
+    bundle::Bundle,
+    children,
+    component::Component,
+    entity::Entity,
+    hierarchy::{ChildOf, Children},
+    lifecycle::RemovedComponents,
+    query::{Added, Changed, Has, Or, With},
+    reflect::ReflectComponent,
+    schedule::IntoScheduleConfigs,
+    spawn::SpawnRelated,
+    system::{Commands, Query},
+};
+use bevy_input_focus::tab_navigation::TabIndex;
+use bevy_picking::{hover::Hovered, PickingSystems};
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
+use bevy_ui::{
+    widget::Text, AlignItems, BorderRadius, Checked, InteractionDisabled, JustifyContent, Node,
+    UiRect, Val,
+};
+use bevy_ui_widgets::{Dropdown, DropdownOption, DropdownPopup, DropdownValue};
+
+use crate::{
+    constants::{fonts, size},
+    cursor::EntityCursor,
+    font_styles::InheritableFont,
+    handle_or_path::HandleOrPath,
+    rounded_corners::RoundedCorners,
+    theme::{ThemeBackgroundColor, ThemeFontColor, ThemedText},
+    tokens,
+};
+
+/// Parameters for the dropdown template, passed to [`dropdown`] function.
+#[derive(Default)]
+pub struct DropdownProps {
+    /// The labels of the options that can be picked.
+    pub options: Vec<String>,
+    /// The index of the option that is initially selected.
+    pub value: usize,
+}
+
+/// Marker for dropdowns styled by feathers.
+#[derive(Component, Default, Clone, Reflect)]
+#[reflect(Component, Clone, Default)]
+struct DropdownStyle;
+
+/// Marker for the text showing the selected option of a dropdown.
+#[derive(Component, Default, Clone, Reflect)]
+#[reflect(Component, Clone, Default)]
+struct DropdownLabel;
+
+/// Template function to spawn a dropdown.
+///
+/// # Arguments
+/// * `props` - construction properties for the dropdown.
+/// * `overrides` - a bundle of components that are merged in with the normal dropdown components.
+///
+/// # Emitted events
+/// * [`bevy_ui_widgets::ValueChange<usize>`] with the index of the option when an option is
+///   picked. Add the [`bevy_ui_widgets::dropdown_self_update`] observer to the dropdown to update
+///   its value automatically.
+///
+///  These events can be disabled by adding an [`bevy_ui::InteractionDisabled`] component to the entity
+pub fn dropdown<B: Bundle>(props: DropdownProps, overrides: B) -> impl Bundle {
+    let label = props.options.get(props.value).cloned().unwrap_or_default();
+    (
+        Node {
+            height: size::ROW_HEIGHT,
+            justify_content: JustifyContent::SpaceBetween,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(8.0),
+            padding: UiRect::axes(Val::Px(8.0), Val::Px(0.)),
+            flex_grow: 1.0,
+            border_radius: RoundedCorners::All.to_border_radius(4.0),
+            ..Default::default()
+        },
+        Dropdown {
+            options: props.options,
+        },
+        DropdownValue(props.value),
+        DropdownStyle,
+        Hovered::default(),
+        EntityCursor::System(bevy_window::SystemCursorIcon::Pointer),
+        TabIndex(0),
+        ThemeBackgroundColor(tokens::BUTTON_BG),
+        ThemeFontColor(tokens::BUTTON_TEXT),
+        InheritableFont {
+            font: HandleOrPath::Path(fonts::REGULAR.to_owned()),
+            font_size: 14.0,
+        },
+        overrides,
+        children![
+            (Text::new(label), ThemedText, DropdownLabel),
+            (Text::new("\u{25be}"), ThemedText),
+        ],
+    )
+}
+
+fn update_dropdown_labels(
+    q_dropdowns: Query<
+        (&Dropdown, &DropdownValue, &Children),
+        (
+            With<DropdownStyle>,
+            Or<(Changed<Dropdown>, Changed<DropdownValue>)>,
+        ),
+    >,
+    mut q_labels: Query<&mut Text, With<DropdownLabel>>,
+) {
+    for (dropdown, value, children) in q_dropdowns.iter() {
+        let label = dropdown.option(value.0).unwrap_or_default();
+        for child in children.iter() {
+            if let Ok(mut text) = q_labels.get_mut(child)
+                && text.0 != label
+            {
+                text.0 = label.to_owned();
+            }
+        }
+    }
+}
+
+fn update_dropdown_styles(
+    q_dropdowns: Query<
+        (
+            Entity,
+            Has<InteractionDisabled>,
+            &Hovered,
+            &ThemeBackgroundColor,
+        ),
+        (
+            With<DropdownStyle>,
+            Or<(Changed<Hovered>, Added<InteractionDisabled>)>,
+        ),
+    >,
+    mut commands: Commands,
+) {
+    for (dropdown_ent, disabled, hovered, bg_color) in q_dropdowns.iter() {
+        set_dropdown_styles(dropdown_ent, disabled, hovered.0, bg_color, &mut commands);
+    }
+}
+
+fn update_dropdown_styles_remove(
+    q_dropdowns: Query<
+        (
+            Entity,
+            Has<InteractionDisabled>,
+            &Hovered,
+            &ThemeBackgroundColor,
+        ),
+        With<DropdownStyle>,
+    >,
+    mut removed_disabled: RemovedComponents<InteractionDisabled>,
+    mut commands: Commands,
+) {
+    removed_disabled.read().for_each(|ent| {
+        if let Ok((dropdown_ent, disabled, hovered, bg_color)) = q_dropdowns.get(ent) {
+            set_dropdown_styles(dropdown_ent, disabled, hovered.0, bg_color, &mut commands);
+        }
+    });
+}
+
+fn set_dropdown_styles(
+    dropdown_ent: Entity,
+    disabled: bool,
+    hovered: bool,
+    bg_color: &ThemeBackgroundColor,
+    commands: &mut Commands,
+) {
+    let bg_token = match (disabled, hovered) {
+        (true, _) => tokens::BUTTON_BG_DISABLED,
+        (false, true) => tokens::BUTTON_BG_HOVER,
+        (false, false) => tokens::BUTTON_BG,
+    };
+
+    let font_color_token = match disabled {
+        true => tokens::BUTTON_TEXT_DISABLED,
+        false => tokens::BUTTON_TEXT,
+    };
+
+    let cursor_shape = match disabled {
+        true => bevy_window::SystemCursorIcon::NotAllowed,
+        false => bevy_window::SystemCursorIcon::Pointer,
+    };
+
+    if bg_color.0 != bg_token {
+        commands
+            .entity(dropdown_ent)
+            .insert(ThemeBackgroundColor(bg_token));
+    }
+
+    commands.entity(dropdown_ent).insert((
+        ThemeFontColor(font_color_token),
+        EntityCursor::System(cursor_shape),
+    ));
+}
+
+/// Styles the popup menus spawned by feathers dropdowns.
+fn style_dropdown_popups(
+    mut q_popups: Query<(Entity, &mut Node, &ChildOf), Added<DropdownPopup>>,
+    q_dropdowns: Query<(), With<DropdownStyle>>,
+    mut commands: Commands,
+) {
+    for (popup_ent, mut node, ChildOf(dropdown)) in q_popups.iter_mut() {
+        if !q_dropdowns.contains(*dropdown) {
+            continue;
+        }
+        node.padding = UiRect::all(Val::Px(4.0));
+        node.border_radius = BorderRadius::all(Val::Px(4.0));
+        commands
+            .entity(popup_ent)
+            .insert(ThemeBackgroundColor(tokens::DROPDOWN_MENU_BG));
+    }
+}
+
+/// Styles the options of the popup menus spawned by feathers dropdowns.
+fn update_dropdown_option_styles(
+    mut q_options: Query<
+        (Entity, &mut Node, &ChildOf, Has<Checked>, &Hovered),
+        (With<DropdownOption>, Changed<Hovered>),
+    >,
+    q_popups: Query<&ChildOf, With<DropdownPopup>>,
+    q_dropdowns: Query<(), With<DropdownStyle>>,
+    mut commands: Commands,
+) {
+    for (option_ent, mut node, ChildOf(popup), checked, hovered) in q_options.iter_mut() {
+        if !q_popups
+            .get(*popup)
+            .is_ok_and(|ChildOf(dropdown)| q_dropdowns.contains(*dropdown))
+        {
+            continue;
+        }
+        let padding = UiRect::axes(Val::Px(8.0), Val::Px(4.0));
+        if node.padding != padding {
+            node.padding = padding;
+            node.border_radius = BorderRadius::all(Val::Px(3.0));
+        }
+
+        let bg_token = match hovered.0 {
+            true => tokens::DROPDOWN_ITEM_BG_HOVER,
+            false => tokens::DROPDOWN_ITEM_BG,
+        };
+        let font_color_token = match checked {
+            true => tokens::DROPDOWN_ITEM_TEXT_SELECTED,
+            false => tokens::DROPDOWN_ITEM_TEXT,
+        };
+        commands.entity(option_ent).insert((
+            ThemeBackgroundColor(bg_token),
+            ThemeFontColor(font_color_token),
+        ));
+    }
+}
+
+/// Plugin which registers the systems for updating the dropdown styles.
+pub struct DropdownPlugin;
+
+impl Plugin for DropdownPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_systems(
+            PreUpdate,
+            (
+                update_dropdown_labels,
+                update_dropdown_styles,
+                update_dropdown_styles_remove,
+                style_dropdown_popups,
+                update_dropdown_option_styles,
+            )
+                .in_set(PickingSystems::Last),
+        );
+    }
+}
@@ -6,7 +6,10 @@ mod checkbox;
 mod color_plane;
 mod color_slider;
 mod color_swatch;
+mod dropdown;
+mod progress_bar;
 mod radio;
+mod scroll_view;
 mod slider;
 mod toggle_switch;
 mod virtual_keyboard;
@@ -18,7 +21,10 @@ pub use color_slider::{
     color_slider, ColorChannel, ColorSlider, ColorSliderPlugin, ColorSliderProps, SliderBaseColor,
 };
 pub use color_swatch::{color_swatch, ColorSwatch, ColorSwatchFg, ColorSwatchValue};
+pub use dropdown::{dropdown, DropdownPlugin, DropdownProps};
+pub use progress_bar::{progress_bar, ProgressBarPlugin, ProgressBarProps};
 pub use radio::{radio, RadioPlugin};
+pub use scroll_view::{scroll_view, ScrollViewPlugin, ScrollViewProps};
 pub use slider::{slider, SliderPlugin, SliderProps};
 pub use toggle_switch::{toggle_switch, ToggleSwitchPlugin};
 pub use virtual_keyboard::{virtual_keyboard, VirtualKeyPressed};
@@ -40,7 +46,10 @@ impl Plugin for ControlsPlugin {
             ColorPlanePlugin,
             ColorSliderPlugin,
             ColorSwatchPlugin,
+            DropdownPlugin,
+            ProgressBarPlugin,
             RadioPlugin,
+            ScrollViewPlugin,
             SliderPlugin,
             ToggleSwitchPlugin,
         ));
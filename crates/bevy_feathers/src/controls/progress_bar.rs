@@ -0,0 +1,120 @@
+use bevy_app::{Plugin, Update};
+use bevy_ecs::{
+    bundle::Bundle,
+    children,
+    component::Component,
+    hierarchy::Children,
+    query::With,
+    reflect::ReflectComponent,
+    spawn::SpawnRelated,
+    system::{Query, Res},
+};
+use bevy_math::ops;
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
+use bevy_time::Time;
+use bevy_ui::{BorderRadius, Node, Overflow, PositionType, Val};
+use bevy_ui_widgets::{ProgressBar, ProgressBarFill};
+
+use crate::{theme::ThemeBackgroundColor, tokens};
+
+/// Height of the progress bar, in logical pixels.
+const PROGRESS_BAR_HEIGHT: f32 = 6.0;
+
+/// Fraction of the track covered by the fill of an indeterminate progress bar.
+const INDETERMINATE_WIDTH: f32 = 0.3;
+
+/// Duration of a sweep of the fill of an indeterminate progress bar, in seconds.
+const INDETERMINATE_PERIOD: f32 = 1.5;
+
+/// Parameters for the progress bar template, passed to [`progress_bar`] function.
+#[derive(Default)]
+pub struct ProgressBarProps {
+    /// The fraction of the task that is complete, from 0 to 1, or `None` if it is unknown.
+    pub progress: Option<f32>,
+}
+
+/// Marker for progress bars styled by feathers.
+#[derive(Component, Default, Clone, Reflect)]
+#[reflect(Component, Clone, Default)]
+struct ProgressBarStyle;
+
+/// Template function to spawn a progress bar.
+///
+/// To show the progress of a task, update the [`ProgressBar`] component of the spawned entity.
+/// Indeterminate progress bars show a bar sweeping across the track.
+///
+/// # Arguments
+/// * `props` - construction properties for the progress bar.
+/// * `overrides` - a bundle of components that are merged in with the normal progress bar
+///   components.
+pub fn progress_bar<B: Bundle>(props: ProgressBarProps, overrides: B) -> impl Bundle {
+    (
+        Node {
+            height: Val::Px(PROGRESS_BAR_HEIGHT),
+            flex_grow: 1.0,
+            overflow: Overflow::clip(),
+            border_radius: BorderRadius::all(Val::Px(PROGRESS_BAR_HEIGHT * 0.5)),
+            ..Default::default()
+        },
+        ProgressBar {
+            progress: props.progress,
+        },
+        ProgressBarStyle,
+        ThemeBackgroundColor(tokens::PROGRESS_BAR_BG),
+        overrides,
+        children![(
+            Node {
+                position_type: PositionType::Absolute,
+                height: Val::Percent(100.),
+                width: Val::Percent(0.),
+                border_radius: BorderRadius::all(Val::Px(PROGRESS_BAR_HEIGHT * 0.5)),
+                ..Default::default()
+            },
+            ProgressBarFill,
+            ThemeBackgroundColor(tokens::PROGRESS_BAR_FILL),
+        )],
+    )
+}
+
+fn update_progress_bar_fills(
+    q_progress_bars: Query<(&ProgressBar, &Children), With<ProgressBarStyle>>,
+    mut q_fills: Query<&mut Node, With<ProgressBarFill>>,
+    time: Res<Time>,
+) {
+    // Position of the fill of indeterminate progress bars, sweeping from just outside the left
+    // edge of the track to just outside the right edge.
+    let t = ops::rem_euclid(time.elapsed_secs(), INDETERMINATE_PERIOD) / INDETERMINATE_PERIOD;
+    let indeterminate_left =
+        Val::Percent((t * (1. + INDETERMINATE_WIDTH) - INDETERMINATE_WIDTH) * 100.);
+
+    for (progress_bar, children) in q_progress_bars.iter() {
+        // The headless widget sets the width of the fill from the progress.
+        let left = match progress_bar.progress {
+            Some(_) => Val::Px(0.),
+            None => indeterminate_left,
+        };
+        for child in children.iter() {
+            let Ok(mut node) = q_fills.get_mut(child) else {
+                continue;
+            };
+            if progress_bar.progress.is_none() {
+                let width = Val::Percent(INDETERMINATE_WIDTH * 100.);
+                if node.width != width {
+                    node.width = width;
+                }
+            }
+            if node.left != left {
+                node.left = left;
+            }
+        }
+    }
+}
+
+/// Plugin which registers the systems for updating the progress bar styles.
+pub struct ProgressBarPlugin;
+
+impl Plugin for ProgressBarPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_systems(Update, update_progress_bar_fills);
+    }
+}
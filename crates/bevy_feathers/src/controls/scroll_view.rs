@@ -0,0 +1,169 @@
+use bevy_app::{Plugin, PreUpdate};
+use bevy_ecs::{
+    bundle::Bundle,
+    children,
+    component::Component,
+    entity::Entity,
+    hierarchy::{ChildOf, Children},
+    query::{Added, With},
+    reflect::ReflectComponent,
+    spawn::{SpawnRelated, SpawnableList},
+    system::Query,
+};
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
+use bevy_ui::{
+    BorderRadius, Display, GridPlacement, Node, Overflow, OverflowAxis, PositionType,
+    RepeatedGridTrack, Val,
+};
+use bevy_ui_widgets::{ControlOrientation, CoreScrollbarThumb, ScrollView, Scrollbar};
+
+use crate::{theme::ThemeBackgroundColor, tokens};
+
+/// Width of the scrollbars, in logical pixels.
+const SCROLLBAR_WIDTH: f32 = 8.0;
+
+/// Minimum length of the scrollbar thumbs, in logical pixels.
+const MIN_THUMB_LENGTH: f32 = 16.0;
+
+/// Parameters for the scroll view template, passed to [`scroll_view`] function.
+pub struct ScrollViewProps {
+    /// Which axes the content can be scrolled along. Only [`OverflowAxis::Scroll`] axes get a
+    /// scrollbar.
+    pub overflow: Overflow,
+}
+
+impl Default for ScrollViewProps {
+    fn default() -> Self {
+        Self {
+            overflow: Overflow::scroll_y(),
+        }
+    }
+}
+
+/// Marker for the scrollbars of a feathers scroll view, which target their sibling
+/// [`ScrollView`].
+#[derive(Component, Default, Clone, Reflect)]
+#[reflect(Component, Clone, Default)]
+struct ScrollViewScrollbar;
+
+/// Template function to spawn a scroll view with scrollbars.
+///
+/// The scroll view is a grid containing the scrolling content area, a vertical scrollbar to its
+/// right and a horizontal scrollbar beneath it.
+///
+/// # Arguments
+/// * `props` - construction properties for the scroll view.
+/// * `overrides` - a bundle of components that are merged in with the normal components of the
+///   outer frame, such as a [`Node`] to set the size of the scroll view.
+/// * `children` - a [`SpawnableList`] of the content elements to scroll.
+pub fn scroll_view<C: SpawnableList<ChildOf> + Send + Sync + 'static, B: Bundle>(
+    props: ScrollViewProps,
+    overrides: B,
+    children: C,
+) -> impl Bundle {
+    let display = |axis: OverflowAxis| {
+        if axis == OverflowAxis::Scroll {
+            Display::Block
+        } else {
+            Display::None
+        }
+    };
+
+    (
+        Node {
+            display: Display::Grid,
+            grid_template_columns: vec![RepeatedGridTrack::flex(1, 1.), RepeatedGridTrack::auto(1)],
+            grid_template_rows: vec![RepeatedGridTrack::flex(1, 1.), RepeatedGridTrack::auto(1)],
+            ..Default::default()
+        },
+        overrides,
+        children![
+            (
+                Node {
+                    grid_row: GridPlacement::start(1),
+                    grid_column: GridPlacement::start(1),
+                    overflow: props.overflow,
+                    ..Default::default()
+                },
+                ScrollView::default(),
+                Children::spawn(children),
+            ),
+            (
+                Node {
+                    display: display(props.overflow.y),
+                    grid_row: GridPlacement::start(1),
+                    grid_column: GridPlacement::start(2),
+                    width: Val::Px(SCROLLBAR_WIDTH),
+                    border_radius: BorderRadius::all(Val::Px(SCROLLBAR_WIDTH * 0.5)),
+                    ..Default::default()
+                },
+                Scrollbar::new(
+                    Entity::PLACEHOLDER,
+                    ControlOrientation::Vertical,
+                    MIN_THUMB_LENGTH
+                ),
+                ScrollViewScrollbar,
+                ThemeBackgroundColor(tokens::SCROLLBAR_BG),
+                children![scrollbar_thumb()],
+            ),
+            (
+                Node {
+                    display: display(props.overflow.x),
+                    grid_row: GridPlacement::start(2),
+                    grid_column: GridPlacement::start(1),
+                    height: Val::Px(SCROLLBAR_WIDTH),
+                    border_radius: BorderRadius::all(Val::Px(SCROLLBAR_WIDTH * 0.5)),
+                    ..Default::default()
+                },
+                Scrollbar::new(
+                    Entity::PLACEHOLDER,
+                    ControlOrientation::Horizontal,
+                    MIN_THUMB_LENGTH
+                ),
+                ScrollViewScrollbar,
+                ThemeBackgroundColor(tokens::SCROLLBAR_BG),
+                children![scrollbar_thumb()],
+            ),
+        ],
+    )
+}
+
+fn scrollbar_thumb() -> impl Bundle {
+    (
+        Node {
+            position_type: PositionType::Absolute,
+            border_radius: BorderRadius::all(Val::Px(SCROLLBAR_WIDTH * 0.5)),
+            ..Default::default()
+        },
+        CoreScrollbarThumb,
+        ThemeBackgroundColor(tokens::SCROLLBAR_THUMB),
+    )
+}
+
+/// Points newly spawned scrollbars at the [`ScrollView`] they were spawned next to.
+fn connect_scrollbars(
+    mut q_scrollbars: Query<(&mut Scrollbar, &ChildOf), Added<ScrollViewScrollbar>>,
+    q_children: Query<&Children>,
+    q_scroll_views: Query<(), With<ScrollView>>,
+) {
+    for (mut scrollbar, ChildOf(frame)) in q_scrollbars.iter_mut() {
+        let Ok(siblings) = q_children.get(*frame) else {
+            continue;
+        };
+        if let Some(scroll_view) = siblings
+            .iter()
+            .find(|sibling| q_scroll_views.contains(*sibling))
+        {
+            scrollbar.target = scroll_view;
+        }
+    }
+}
+
+/// Plugin which registers the systems for updating the scroll view.
+pub struct ScrollViewPlugin;
+
+impl Plugin for ScrollViewPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_systems(PreUpdate, connect_scrollbars);
+    }
+}
@@ -96,6 +96,18 @@ pub fn create_dark_theme() -> ThemeProps {
                 palette::LIGHT_GRAY_2.with_alpha(0.3),
             ),
             (tokens::COLOR_PLANE_BG, palette::GRAY_1),
+            // Scroll View
+            (tokens::SCROLLBAR_BG, palette::GRAY_1),
+            (tokens::SCROLLBAR_THUMB, palette::GRAY_3),
+            // Dropdown
+            (tokens::DROPDOWN_MENU_BG, palette::GRAY_2),
+            (tokens::DROPDOWN_ITEM_BG, palette::GRAY_2),
+            (tokens::DROPDOWN_ITEM_BG_HOVER, palette::GRAY_3),
+            (tokens::DROPDOWN_ITEM_TEXT, palette::LIGHT_GRAY_1),
+            (tokens::DROPDOWN_ITEM_TEXT_SELECTED, palette::WHITE),
+            // Progress Bar
+            (tokens::PROGRESS_BAR_BG, palette::GRAY_1),
+            (tokens::PROGRESS_BAR_FILL, palette::ACCENT),
         ]),
     }
 }
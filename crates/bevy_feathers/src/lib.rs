@@ -39,7 +39,7 @@ use crate::{
 mod alpha_pattern;
 pub mod constants;
 pub mod controls;
-pub mod cursor;
+pub use bevy_picking::cursor;
 pub mod dark_theme;
 pub mod font_styles;
 pub mod handle_or_path;
@@ -66,9 +66,12 @@ impl Plugin for FeathersPlugin {
         embedded_asset!(app, "assets/shaders/alpha_pattern.wgsl");
         embedded_asset!(app, "assets/shaders/color_plane.wgsl");
 
+        if !app.is_plugin_added::<CursorIconPlugin>() {
+            app.add_plugins(CursorIconPlugin);
+        }
+
         app.add_plugins((
             ControlsPlugin,
-            CursorIconPlugin,
             HierarchyPropagatePlugin::<TextColor, With<ThemedText>>::new(PostUpdate),
             HierarchyPropagatePlugin::<TextFont, With<ThemedText>>::new(PostUpdate),
             UiMaterialPlugin::<AlphaPatternMaterial>::default(),
@@ -142,3 +142,32 @@ pub const SWITCH_SLIDE_DISABLED: ThemeToken =
 
 /// Color plane frame background
 pub const COLOR_PLANE_BG: ThemeToken = ThemeToken::new_static("feathers.colorplane.bg");
+
+// Scroll View
+
+/// Scrollbar track
+pub const SCROLLBAR_BG: ThemeToken = ThemeToken::new_static("feathers.scrollbar.bg");
+/// Scrollbar thumb
+pub const SCROLLBAR_THUMB: ThemeToken = ThemeToken::new_static("feathers.scrollbar.thumb");
+
+// Dropdown
+
+/// Dropdown popup menu background
+pub const DROPDOWN_MENU_BG: ThemeToken = ThemeToken::new_static("feathers.dropdown.menu.bg");
+/// Dropdown option background
+pub const DROPDOWN_ITEM_BG: ThemeToken = ThemeToken::new_static("feathers.dropdown.item.bg");
+/// Dropdown option background (hovered)
+pub const DROPDOWN_ITEM_BG_HOVER: ThemeToken =
+    ThemeToken::new_static("feathers.dropdown.item.bg.hover");
+/// Dropdown option text
+pub const DROPDOWN_ITEM_TEXT: ThemeToken = ThemeToken::new_static("feathers.dropdown.item.txt");
+/// Dropdown option text (selected)
+pub const DROPDOWN_ITEM_TEXT_SELECTED: ThemeToken =
+    ThemeToken::new_static("feathers.dropdown.item.txt.selected");
+
+// Progress Bar
+
+/// Progress bar track
+pub const PROGRESS_BAR_BG: ThemeToken = ThemeToken::new_static("feathers.progressbar.bg");
+/// Progress bar fill
+pub const PROGRESS_BAR_FILL: ThemeToken = ThemeToken::new_static("feathers.progressbar.fill");
@@ -1,36 +1,71 @@
 use crate::{
     converter::{convert_axis, convert_button},
+    mapping::{
+        GamepadElementCode, GamepadGuid, GamepadRemappings, UnmappedGamepadConnected,
+        UnmappedGamepadElement, UnmappedGamepadInput,
+    },
     Gilrs, GilrsGamepads,
 };
+use bevy_ecs::entity::Entity;
 use bevy_ecs::message::MessageWriter;
 use bevy_ecs::prelude::Commands;
-use bevy_ecs::system::ResMut;
+use bevy_ecs::system::{Res, ResMut};
 use bevy_input::gamepad::{
-    GamepadConnection, GamepadConnectionEvent, RawGamepadAxisChangedEvent,
-    RawGamepadButtonChangedEvent, RawGamepadEvent,
+    GamepadConnection, GamepadConnectionEvent, GamepadHapticCapabilities,
+    RawGamepadAxisChangedEvent, RawGamepadButtonChangedEvent, RawGamepadEvent,
 };
-use gilrs::{ev::filter::axis_dpad_to_button, EventType, Filter};
+use gilrs::{ev::filter::axis_dpad_to_button, EventType, Filter, MappingSource};
+
+/// gilrs emulates the envelope of the rumbles for all the gamepads supporting
+/// force feedback, but doesn't support adaptive triggers.
+pub(crate) fn haptic_capabilities(gamepad: &gilrs::Gamepad) -> GamepadHapticCapabilities {
+    GamepadHapticCapabilities {
+        rumble: gamepad.is_ff_supported(),
+        rumble_envelopes: gamepad.is_ff_supported(),
+        trigger_effects: false,
+    }
+}
+
+pub(crate) fn connection_event(entity: Entity, gamepad: &gilrs::Gamepad) -> GamepadConnectionEvent {
+    GamepadConnectionEvent::new(
+        entity,
+        GamepadConnection::Connected {
+            name: gamepad.name().to_string(),
+            vendor_id: gamepad.vendor_id(),
+            product_id: gamepad.product_id(),
+        },
+    )
+}
+
+/// Returns `true` if the gamepad has neither an SDL mapping nor remappings.
+pub(crate) fn is_unmapped(gamepad: &gilrs::Gamepad, remappings: &GamepadRemappings) -> bool {
+    gamepad.mapping_source() == MappingSource::None
+        && remappings.get(GamepadGuid(gamepad.uuid())).is_none()
+}
 
 pub fn gilrs_event_startup_system(
     mut commands: Commands,
     mut gilrs: ResMut<Gilrs>,
     mut gamepads: ResMut<GilrsGamepads>,
+    remappings: Res<GamepadRemappings>,
     mut events: MessageWriter<GamepadConnectionEvent>,
+    mut unmapped_events: MessageWriter<UnmappedGamepadConnected>,
 ) {
     gilrs.with(|gilrs| {
         for (id, gamepad) in gilrs.gamepads() {
             // Create entity and add to mapping
-            let entity = commands.spawn_empty().id();
+            let guid = GamepadGuid(gamepad.uuid());
+            let entity = commands.spawn((haptic_capabilities(&gamepad), guid)).id();
             gamepads.id_to_entity.insert(id, entity);
             gamepads.entity_to_id.insert(entity, id);
-            events.write(GamepadConnectionEvent {
-                gamepad: entity,
-                connection: GamepadConnection::Connected {
+            events.write(connection_event(entity, &gamepad));
+            if is_unmapped(&gamepad, &remappings) {
+                unmapped_events.write(UnmappedGamepadConnected {
+                    gamepad: entity,
+                    guid,
                     name: gamepad.name().to_string(),
-                    vendor_id: gamepad.vendor_id(),
-                    product_id: gamepad.product_id(),
-                },
-            });
+                });
+            }
         }
     });
 }
@@ -39,10 +74,13 @@ pub fn gilrs_event_system(
     mut commands: Commands,
     mut gilrs: ResMut<Gilrs>,
     mut gamepads: ResMut<GilrsGamepads>,
+    remappings: Res<GamepadRemappings>,
     mut events: MessageWriter<RawGamepadEvent>,
     mut connection_events: MessageWriter<GamepadConnectionEvent>,
     mut button_events: MessageWriter<RawGamepadButtonChangedEvent>,
     mut axis_event: MessageWriter<RawGamepadAxisChangedEvent>,
+    mut unmapped_events: MessageWriter<UnmappedGamepadConnected>,
+    mut unmapped_inputs: MessageWriter<UnmappedGamepadInput>,
 ) {
     gilrs.with(|gilrs| {
         while let Some(gilrs_event) = gilrs.next_event().filter_ev(&axis_dpad_to_button, gilrs) {
@@ -56,17 +94,21 @@ pub fn gilrs_event_system(
                         gamepads.entity_to_id.insert(entity, gilrs_event.id);
                         entity
                     });
+                    let guid = GamepadGuid(pad.uuid());
+                    commands
+                        .entity(entity)
+                        .insert((haptic_capabilities(&pad), guid));
 
-                    let event = GamepadConnectionEvent::new(
-                        entity,
-                        GamepadConnection::Connected {
-                            name: pad.name().to_string(),
-                            vendor_id: pad.vendor_id(),
-                            product_id: pad.product_id(),
-                        },
-                    );
+                    let event = connection_event(entity, &pad);
                     events.write(event.clone().into());
                     connection_events.write(event);
+                    if is_unmapped(&pad, &remappings) {
+                        unmapped_events.write(UnmappedGamepadConnected {
+                            gamepad: entity,
+                            guid,
+                            name: pad.name().to_string(),
+                        });
+                    }
                 }
                 EventType::Disconnected => {
                     let gamepad = gamepads
@@ -79,15 +121,25 @@ pub fn gilrs_event_system(
                     events.write(event.clone().into());
                     connection_events.write(event);
                 }
-                EventType::ButtonChanged(gilrs_button, raw_value, _) => {
-                    let Some(button) = convert_button(gilrs_button) else {
-                        continue;
-                    };
+                EventType::ButtonChanged(gilrs_button, raw_value, code) => {
                     let gamepad = gamepads
                         .id_to_entity
                         .get(&gilrs_event.id)
                         .copied()
                         .expect("mapping should exist from connection");
+                    let guid = GamepadGuid(gilrs.gamepad(gilrs_event.id).uuid());
+                    let code = GamepadElementCode(code.into_u32());
+                    let Some(button) = remappings
+                        .button(guid, code)
+                        .or_else(|| convert_button(gilrs_button))
+                    else {
+                        unmapped_inputs.write(UnmappedGamepadInput {
+                            gamepad,
+                            element: UnmappedGamepadElement::Button(code),
+                            value: raw_value,
+                        });
+                        continue;
+                    };
                     events.write(
                         RawGamepadButtonChangedEvent::new(gamepad, button, raw_value).into(),
                     );
@@ -95,15 +147,28 @@ pub fn gilrs_event_system(
                         gamepad, button, raw_value,
                     ));
                 }
-                EventType::AxisChanged(gilrs_axis, raw_value, _) => {
-                    let Some(axis) = convert_axis(gilrs_axis) else {
-                        continue;
-                    };
+                EventType::AxisChanged(gilrs_axis, raw_value, code) => {
                     let gamepad = gamepads
                         .id_to_entity
                         .get(&gilrs_event.id)
                         .copied()
                         .expect("mapping should exist from connection");
+                    let guid = GamepadGuid(gilrs.gamepad(gilrs_event.id).uuid());
+                    let code = GamepadElementCode(code.into_u32());
+                    let Some(axis) = remappings
+                        .axis(guid, code)
+                        .or_else(|| convert_axis(gilrs_axis))
+                    else {
+                        // The dpad axes are converted to buttons by the `axis_dpad_to_button` filter
+                        if gilrs_axis == gilrs::Axis::Unknown {
+                            unmapped_inputs.write(UnmappedGamepadInput {
+                                gamepad,
+                                element: UnmappedGamepadElement::Axis(code),
+                                value: raw_value,
+                            });
+                        }
+                        continue;
+                    };
                     events.write(RawGamepadAxisChangedEvent::new(gamepad, axis, raw_value).into());
                     axis_event.write(RawGamepadAxisChangedEvent::new(gamepad, axis, raw_value));
                 }
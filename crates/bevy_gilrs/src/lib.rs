@@ -9,11 +9,21 @@
 //!
 //! This crate is built on top of [GilRs](gilrs), a library
 //! that handles abstracting over platform-specific gamepad APIs.
+//!
+//! Gamepads are recognized with the SDL game controller mappings of gilrs. More mappings can be
+//! added at runtime to the [`GamepadMappingDb`], and the buttons and axes of the gamepads that
+//! aren't recognized can be remapped with the [`GamepadRemappings`].
 
 mod converter;
 mod gilrs_system;
+mod mapping;
 mod rumble;
 
+pub use mapping::{
+    GamepadElementCode, GamepadGuid, GamepadMappingDb, GamepadRemap, GamepadRemappings,
+    UnmappedGamepadConnected, UnmappedGamepadElement, UnmappedGamepadInput,
+};
+
 #[cfg(not(target_arch = "wasm32"))]
 use bevy_platform::cell::SyncCell;
 
@@ -25,9 +35,9 @@ use bevy_ecs::entity::EntityHashMap;
 use bevy_ecs::prelude::*;
 use bevy_input::InputSystems;
 use bevy_platform::collections::HashMap;
-use gilrs::GilrsBuilder;
 use gilrs_system::{gilrs_event_startup_system, gilrs_event_system};
-use rumble::{play_gilrs_rumble, RunningRumbleEffects};
+use mapping::reload_gamepad_mappings;
+use rumble::{handle_gilrs_trigger_effects, play_gilrs_rumble, RunningRumbleEffects};
 use tracing::error;
 
 #[cfg(target_arch = "wasm32")]
@@ -89,9 +99,14 @@ pub struct RumbleSystems;
 
 impl Plugin for GilrsPlugin {
     fn build(&self, app: &mut App) {
-        match GilrsBuilder::new()
-            .with_default_filters(false)
-            .set_update_state(false)
+        app.init_resource::<GamepadRemappings>()
+            .add_message::<UnmappedGamepadConnected>()
+            .add_message::<UnmappedGamepadInput>();
+        match app
+            .world_mut()
+            .get_resource_or_init::<GamepadMappingDb>()
+            .bypass_change_detection()
+            .gilrs_builder()
             .build()
         {
             Ok(gilrs) => {
@@ -107,8 +122,16 @@ impl Plugin for GilrsPlugin {
                 app.init_resource::<GilrsGamepads>();
                 app.init_resource::<RunningRumbleEffects>()
                     .add_systems(PreStartup, gilrs_event_startup_system)
-                    .add_systems(PreUpdate, gilrs_event_system.before(InputSystems))
-                    .add_systems(PostUpdate, play_gilrs_rumble.in_set(RumbleSystems));
+                    .add_systems(
+                        PreUpdate,
+                        (reload_gamepad_mappings, gilrs_event_system)
+                            .chain()
+                            .before(InputSystems),
+                    )
+                    .add_systems(
+                        PostUpdate,
+                        (play_gilrs_rumble, handle_gilrs_trigger_effects).in_set(RumbleSystems),
+                    );
             }
             Err(err) => error!("Failed to start Gilrs. {}", err),
         }
@@ -0,0 +1,283 @@
+//! Runtime gamepad mappings: SDL game controller mappings added while the app runs, and
+//! remappings of the buttons and axes of gamepads that gilrs doesn't recognize.
+use crate::{
+    gilrs_system::{connection_event, haptic_capabilities, is_unmapped},
+    rumble::RunningRumbleEffects,
+    Gilrs, GilrsGamepads,
+};
+use bevy_ecs::prelude::*;
+use bevy_input::gamepad::{
+    GamepadAxis, GamepadButton, GamepadConnection, GamepadConnectionEvent, RawGamepadEvent,
+};
+use bevy_platform::collections::HashMap;
+use core::fmt;
+use gilrs::GilrsBuilder;
+use tracing::error;
+
+/// The GUID identifying a gamepad model, as used by SDL game controller mappings.
+///
+/// This component is inserted on the gamepad entities when they connect. It is displayed in the
+/// format of the GUIDs at the start of the SDL mapping strings.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GamepadGuid(pub [u8; 16]);
+
+impl fmt::Display for GamepadGuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+/// A button or an axis of a gamepad, identified by its code on the current platform.
+///
+/// Codes aren't portable: the same button has different codes on different platforms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GamepadElementCode(pub u32);
+
+/// The SDL game controller mappings used to recognize gamepads, in addition to the mappings
+/// included in gilrs and those of the `SDL_GAMECONTROLLERCONFIG` environment variable.
+///
+/// Mappings added before the [`GilrsPlugin`](crate::GilrsPlugin) is built are used right away.
+/// Mappings added later are applied in the next frame by reconnecting the gamepads: their
+/// entities are kept, but their pressed buttons are released.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct GamepadMappingDb {
+    mappings: String,
+    pending: bool,
+}
+
+impl GamepadMappingDb {
+    /// Adds SDL game controller mappings, one per line, in the format of the
+    /// [SDL_GameControllerDB](https://github.com/mdqinc/SDL_GameControllerDB).
+    ///
+    /// Mappings for other platforms are ignored.
+    pub fn add_sdl_mappings(&mut self, mappings: &str) {
+        self.mappings.push_str(mappings);
+        self.mappings.push('\n');
+        self.pending = true;
+    }
+
+    /// Returns the SDL game controller mappings that were added.
+    pub fn sdl_mappings(&self) -> &str {
+        &self.mappings
+    }
+
+    /// Returns the builder of a gilrs context using the mappings, marking them as applied.
+    pub(crate) fn gilrs_builder(&mut self) -> GilrsBuilder {
+        self.pending = false;
+        GilrsBuilder::new()
+            .with_default_filters(false)
+            .set_update_state(false)
+            .add_mappings(&self.mappings)
+    }
+}
+
+/// The buttons and axes of a gamepad model, remapped by their [`GamepadElementCode`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GamepadRemap {
+    buttons: HashMap<GamepadElementCode, GamepadButton>,
+    axes: HashMap<GamepadElementCode, GamepadAxis>,
+}
+
+impl GamepadRemap {
+    /// Returns the button the element is remapped to, if any.
+    pub fn button(&self, code: GamepadElementCode) -> Option<GamepadButton> {
+        self.buttons.get(&code).copied()
+    }
+
+    /// Returns the axis the element is remapped to, if any.
+    pub fn axis(&self, code: GamepadElementCode) -> Option<GamepadAxis> {
+        self.axes.get(&code).copied()
+    }
+
+    /// An iterator visiting every remapped button, in arbitrary order.
+    pub fn buttons(&self) -> impl Iterator<Item = (GamepadElementCode, GamepadButton)> + '_ {
+        self.buttons.iter().map(|(code, button)| (*code, *button))
+    }
+
+    /// An iterator visiting every remapped axis, in arbitrary order.
+    pub fn axes(&self) -> impl Iterator<Item = (GamepadElementCode, GamepadAxis)> + '_ {
+        self.axes.iter().map(|(code, axis)| (*code, *axis))
+    }
+}
+
+/// Remappings of the buttons and axes of gamepad models, which take precedence over the mappings
+/// of gilrs.
+///
+/// This lets players fix the layout of gamepads that aren't recognized: ask the player to press
+/// the button to remap, and remap the element of the next [`UnmappedGamepadInput`] event. The
+/// remappings apply to all the gamepads with the same [`GamepadGuid`], and can be saved by the app
+/// to be restored in later sessions on the same platform.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct GamepadRemappings {
+    remaps: HashMap<GamepadGuid, GamepadRemap>,
+}
+
+impl GamepadRemappings {
+    /// Remaps an element of a gamepad model to a button.
+    pub fn remap_button(
+        &mut self,
+        guid: GamepadGuid,
+        code: GamepadElementCode,
+        button: GamepadButton,
+    ) {
+        let remap = self.remaps.entry(guid).or_default();
+        remap.axes.remove(&code);
+        remap.buttons.insert(code, button);
+    }
+
+    /// Remaps an element of a gamepad model to an axis.
+    pub fn remap_axis(&mut self, guid: GamepadGuid, code: GamepadElementCode, axis: GamepadAxis) {
+        let remap = self.remaps.entry(guid).or_default();
+        remap.buttons.remove(&code);
+        remap.axes.insert(code, axis);
+    }
+
+    /// Returns the remappings of a gamepad model.
+    pub fn get(&self, guid: GamepadGuid) -> Option<&GamepadRemap> {
+        self.remaps.get(&guid)
+    }
+
+    /// Removes the remappings of a gamepad model, returning them.
+    pub fn remove(&mut self, guid: GamepadGuid) -> Option<GamepadRemap> {
+        self.remaps.remove(&guid)
+    }
+
+    pub(crate) fn button(
+        &self,
+        guid: GamepadGuid,
+        code: GamepadElementCode,
+    ) -> Option<GamepadButton> {
+        self.remaps.get(&guid)?.button(code)
+    }
+
+    pub(crate) fn axis(&self, guid: GamepadGuid, code: GamepadElementCode) -> Option<GamepadAxis> {
+        self.remaps.get(&guid)?.axis(code)
+    }
+}
+
+/// Sent when a gamepad that has neither an SDL mapping nor a [`GamepadRemap`] connects.
+///
+/// Most of the buttons and axes of such a gamepad are reported as [`UnmappedGamepadInput`] events
+/// until they are remapped with the [`GamepadRemappings`] resource, or an SDL mapping is added to the
+/// [`GamepadMappingDb`].
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+pub struct UnmappedGamepadConnected {
+    /// The gamepad entity.
+    pub gamepad: Entity,
+    /// The GUID of the gamepad model.
+    pub guid: GamepadGuid,
+    /// The name of the gamepad reported by the platform.
+    pub name: String,
+}
+
+/// A button or an axis that isn't mapped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnmappedGamepadElement {
+    /// A button, which can be remapped with [`GamepadRemappings::remap_button`].
+    Button(GamepadElementCode),
+    /// An axis, which can be remapped with [`GamepadRemappings::remap_axis`].
+    Axis(GamepadElementCode),
+}
+
+/// Sent when a button or an axis of a gamepad that isn't mapped changes.
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+pub struct UnmappedGamepadInput {
+    /// The gamepad entity.
+    pub gamepad: Entity,
+    /// The element that changed.
+    pub element: UnmappedGamepadElement,
+    /// The value of the element, from 0.0 to 1.0 for buttons and from -1.0 to 1.0 for axes.
+    pub value: f32,
+}
+
+/// Rebuilds the gilrs context when SDL mappings are added to the [`GamepadMappingDb`].
+///
+/// As the gamepad ids of the new context may differ, the connected gamepads are matched to their
+/// entities by model, and reconnected to reset their state.
+pub(crate) fn reload_gamepad_mappings(
+    mut commands: Commands,
+    mut mapping_db: ResMut<GamepadMappingDb>,
+    remappings: Res<GamepadRemappings>,
+    mut gilrs: ResMut<Gilrs>,
+    mut gamepads: ResMut<GilrsGamepads>,
+    mut running_rumbles: ResMut<RunningRumbleEffects>,
+    mut events: MessageWriter<RawGamepadEvent>,
+    mut connection_events: MessageWriter<GamepadConnectionEvent>,
+    mut unmapped_events: MessageWriter<UnmappedGamepadConnected>,
+) {
+    if !mapping_db.pending {
+        return;
+    }
+    let new_gilrs = match mapping_db.gilrs_builder().build() {
+        Ok(new_gilrs) => new_gilrs,
+        Err(err) => {
+            error!("Failed to reload gamepad mappings. {}", err);
+            return;
+        }
+    };
+
+    // Stop the rumbles of the previous context
+    *running_rumbles = RunningRumbleEffects::default();
+    let previous_gamepads = core::mem::take(&mut *gamepads);
+    gilrs.with(|gilrs| {
+        let mut previous: Vec<_> = previous_gamepads
+            .id_to_entity
+            .iter()
+            .filter(|(id, _)| gilrs.connected_gamepad(**id).is_some())
+            .map(|(id, entity)| (*entity, GamepadGuid(gilrs.gamepad(*id).uuid())))
+            .collect();
+        *gilrs = new_gilrs;
+
+        for (id, pad) in gilrs.gamepads() {
+            let guid = GamepadGuid(pad.uuid());
+            let entity = match previous.iter().position(|(_, previous)| *previous == guid) {
+                Some(index) => {
+                    let (entity, _) = previous.swap_remove(index);
+                    let event =
+                        GamepadConnectionEvent::new(entity, GamepadConnection::Disconnected);
+                    events.write(event.clone().into());
+                    connection_events.write(event);
+                    entity
+                }
+                None => commands.spawn_empty().id(),
+            };
+            gamepads.id_to_entity.insert(id, entity);
+            gamepads.entity_to_id.insert(entity, id);
+            commands
+                .entity(entity)
+                .insert((haptic_capabilities(&pad), guid));
+
+            let event = connection_event(entity, &pad);
+            events.write(event.clone().into());
+            connection_events.write(event);
+            if is_unmapped(&pad, &remappings) {
+                unmapped_events.write(UnmappedGamepadConnected {
+                    gamepad: entity,
+                    guid,
+                    name: pad.name().into(),
+                });
+            }
+        }
+
+        // Gamepads the new context didn't find
+        for (entity, _) in previous {
+            let event = GamepadConnectionEvent::new(entity, GamepadConnection::Disconnected);
+            events.write(event.clone().into());
+            connection_events.write(event);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GamepadGuid;
+
+    #[test]
+    fn guid_in_sdl_format() {
+        let guid = GamepadGuid([
+            0x03, 0x00, 0x00, 0x00, 0x5e, 0x04, 0x00, 0x00, 0x8e, 0x02, 0x00, 0x00, 0x14, 0x01,
+            0x00, 0x00,
+        ]);
+        assert_eq!(guid.to_string(), "030000005e0400008e02000014010000");
+    }
+}
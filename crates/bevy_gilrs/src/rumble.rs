@@ -1,13 +1,16 @@
 //! Handle user specified rumble request events.
 use crate::{Gilrs, GilrsGamepads};
 use bevy_ecs::prelude::{MessageReader, Res, ResMut, Resource};
-use bevy_input::gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest};
+use bevy_input::gamepad::{
+    GamepadMotorRumble, GamepadRumbleEnvelope, GamepadRumbleIntensity, GamepadRumblePattern,
+    GamepadRumbleRequest, GamepadTriggerEffectRequest,
+};
 use bevy_platform::cell::SyncCell;
 use bevy_platform::collections::HashMap;
 use bevy_time::{Real, Time};
 use core::time::Duration;
 use gilrs::{
-    ff::{self, BaseEffect, BaseEffectType, Repeat, Replay},
+    ff::{self, BaseEffect, BaseEffectType, Envelope, Repeat, Replay, Ticks},
     GamepadId,
 };
 use thiserror::Error;
@@ -79,6 +82,53 @@ fn get_base_effects(
     effects
 }
 
+/// Converts the rumble of a motor in a pattern, whose [`BaseEffectType`] is
+/// `kind`, to a gilrs effect.
+fn get_motor_base_effect(rumble: GamepadMotorRumble, kind: BaseEffectType) -> BaseEffect {
+    let play_for = Ticks::from(rumble.duration);
+    BaseEffect {
+        kind,
+        scheduling: Replay {
+            after: rumble.delay.into(),
+            play_for,
+            ..Default::default()
+        },
+        envelope: to_gilrs_envelope(rumble.envelope, play_for),
+    }
+}
+
+/// gilrs requires the attack and the fade of an envelope to be shorter than
+/// the effect, so they're shortened to leave at least one tick of sustain.
+fn to_gilrs_envelope(envelope: GamepadRumbleEnvelope, play_for: Ticks) -> Envelope {
+    let one_tick = Ticks::from_ms(1);
+    let attack_length = Ticks::from(envelope.attack).min(play_for - one_tick);
+    let fade_length = Ticks::from(envelope.decay).min(play_for - one_tick - attack_length);
+    Envelope {
+        attack_length,
+        attack_level: envelope.attack_level,
+        fade_length,
+        fade_level: envelope.decay_level,
+    }
+}
+
+fn get_pattern_base_effects(pattern: GamepadRumblePattern) -> Vec<BaseEffect> {
+    let strong = pattern.strong_motor.map(|rumble| {
+        let magnitude = to_gilrs_magnitude(rumble.intensity);
+        (rumble, BaseEffectType::Strong { magnitude })
+    });
+    let weak = pattern.weak_motor.map(|rumble| {
+        let magnitude = to_gilrs_magnitude(rumble.intensity);
+        (rumble, BaseEffectType::Weak { magnitude })
+    });
+    [strong, weak]
+        .into_iter()
+        .flatten()
+        // gilrs can't play effects shorter than a tick
+        .filter(|(rumble, _)| rumble.intensity > 0. && !rumble.duration.is_zero())
+        .map(|(rumble, kind)| get_motor_base_effect(rumble, kind))
+        .collect()
+}
+
 fn handle_rumble_request(
     running_rumbles: &mut RunningRumbleEffects,
     gilrs: &mut gilrs::Gilrs,
@@ -120,6 +170,28 @@ fn handle_rumble_request(
                 effect: SyncCell::new(effect),
             });
         }
+        GamepadRumbleRequest::AddPattern { pattern, .. } => {
+            let effects = get_pattern_base_effects(pattern);
+            if effects.is_empty() {
+                return Ok(());
+            }
+
+            let duration = pattern.duration();
+            let mut effect_builder = ff::EffectBuilder::new();
+            for effect in effects {
+                effect_builder.add_effect(effect);
+            }
+            effect_builder.repeat(Repeat::For(duration.into()));
+
+            let effect = effect_builder.gamepads(&[gamepad_id]).finish(gilrs)?;
+            effect.play()?;
+
+            let gamepad_rumbles = running_rumbles.rumbles.entry(gamepad_id).or_default();
+            gamepad_rumbles.push(RunningRumble {
+                deadline: current_time + duration,
+                effect: SyncCell::new(effect),
+            });
+        }
     }
 
     Ok(())
@@ -164,9 +236,25 @@ pub(crate) fn play_gilrs_rumble(
     });
 }
 
+/// gilrs doesn't support adaptive triggers, so trigger effect requests are
+/// only logged.
+pub(crate) fn handle_gilrs_trigger_effects(
+    mut requests: MessageReader<GamepadTriggerEffectRequest>,
+) {
+    for request in requests.read() {
+        debug!(
+            "Tried to set a trigger effect on {:?}, but gilrs doesn't support adaptive triggers",
+            request.gamepad
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::to_gilrs_magnitude;
+    use super::{get_pattern_base_effects, to_gilrs_envelope, to_gilrs_magnitude};
+    use bevy_input::gamepad::{GamepadMotorRumble, GamepadRumbleEnvelope, GamepadRumblePattern};
+    use core::time::Duration;
+    use gilrs::ff::{BaseEffectType, Ticks};
 
     #[test]
     fn magnitude_conversion() {
@@ -182,4 +270,34 @@ mod tests {
         assert_eq!(to_gilrs_magnitude(-1.0), 0);
         assert_eq!(to_gilrs_magnitude(-0.1), 0);
     }
+
+    #[test]
+    fn envelope_fits_in_effect() {
+        let play_for = Ticks::from(Duration::from_millis(200));
+        let envelope = to_gilrs_envelope(
+            GamepadRumbleEnvelope::new(Duration::from_millis(150), Duration::from_millis(150)),
+            play_for,
+        );
+        assert_eq!(
+            envelope.attack_length,
+            Ticks::from(Duration::from_millis(150))
+        );
+        assert!(envelope.attack_length + envelope.fade_length < play_for);
+    }
+
+    #[test]
+    fn pattern_drives_each_motor() {
+        let pattern = GamepadRumblePattern {
+            strong_motor: Some(GamepadMotorRumble::new(1.0, Duration::from_millis(100))),
+            weak_motor: Some(GamepadMotorRumble::new(0.0, Duration::from_millis(100))),
+        };
+        let effects = get_pattern_base_effects(pattern);
+        assert_eq!(effects.len(), 1);
+        assert_eq!(
+            effects[0].kind,
+            BaseEffectType::Strong {
+                magnitude: u16::MAX
+            }
+        );
+    }
 }
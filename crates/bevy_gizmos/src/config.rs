@@ -256,6 +256,11 @@ pub struct GizmoMeshConfig {
     ///
     /// Gizmos will only be rendered to cameras with intersecting layers.
     pub render_layers: RenderLayers,
+    /// Draw the gizmo on top of all other geometry, without testing or writing depth.
+    ///
+    /// This setting only affects 3D, where it's used for gizmos drawn with
+    /// [`GizmoDepthMode::AlwaysOnTop`](crate::depth::GizmoDepthMode::AlwaysOnTop).
+    pub always_on_top: bool,
     /// Handle of the gizmo asset.
     pub handle: Handle<GizmoAsset>,
 }
@@ -0,0 +1,221 @@
+//! Additional [`GizmoBuffer`] Functions -- Depth
+//!
+//! Includes the implementation of [`GizmoBuffer::depth_mode`],
+//! and assorted support items.
+
+use core::{
+    mem,
+    ops::{Deref, DerefMut},
+};
+
+use bevy_color::{Alpha, LinearRgba};
+use bevy_math::Vec3;
+use bevy_reflect::Reflect;
+
+use crate::{gizmos::GizmoBuffer, prelude::GizmoConfigGroup};
+
+/// How gizmos drawn with [`GizmoBuffer::depth_mode`] are occluded by other geometry, overriding
+/// the [`depth_bias`](crate::config::GizmoConfig::depth_bias) of their group.
+///
+/// This has no effect in 2D, where gizmos are always drawn on top.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum GizmoDepthMode {
+    /// The gizmos are hidden behind other geometry.
+    DepthTested,
+    /// The gizmos are drawn on top of all other geometry.
+    AlwaysOnTop,
+    /// The gizmos are drawn normally where they're visible, and with their alpha multiplied by
+    /// `alpha` where they're hidden behind other geometry.
+    FadeOccluded {
+        /// The factor of the alpha of the hidden parts of the gizmos, from `0.0` to `1.0`.
+        alpha: f32,
+    },
+}
+
+impl<Config, Clear> GizmoBuffer<Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Returns a [`GizmoBuffer`] whose gizmos are occluded according to `mode`, rather than the
+    /// [`depth_bias`](crate::config::GizmoConfig::depth_bias) of their group.
+    ///
+    /// This lets a single gizmo group mix hints seen through walls with gizmos hidden behind them.
+    /// The gizmos are added to this buffer once the builder is dropped. Depth modes are only
+    /// applied to [`Gizmos`](crate::gizmos::Gizmos), not to retained
+    /// [`GizmoAsset`](crate::GizmoAsset)s.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::{GREEN, RED};
+    /// fn system(mut gizmos: Gizmos) {
+    ///     // Seen through walls.
+    ///     gizmos
+    ///         .depth_mode(GizmoDepthMode::AlwaysOnTop)
+    ///         .sphere(Vec3::ZERO, 1., RED);
+    ///
+    ///     // Faded behind walls.
+    ///     let mut faded = gizmos.depth_mode(GizmoDepthMode::FadeOccluded { alpha: 0.2 });
+    ///     faded.line(Vec3::ZERO, Vec3::X, GREEN);
+    ///     faded.line(Vec3::ZERO, Vec3::Y, GREEN);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn depth_mode(&mut self, mode: GizmoDepthMode) -> DepthModeBuilder<'_, Config, Clear> {
+        let buffer = GizmoBuffer {
+            enabled: self.enabled,
+            ..Default::default()
+        };
+        DepthModeBuilder {
+            gizmos: self,
+            mode,
+            buffer,
+        }
+    }
+}
+
+/// A builder returned by [`GizmoBuffer::depth_mode`].
+///
+/// It dereferences to the [`GizmoBuffer`] the gizmos are drawn to.
+pub struct DepthModeBuilder<'a, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    gizmos: &'a mut GizmoBuffer<Config, Clear>,
+    mode: GizmoDepthMode,
+    buffer: GizmoBuffer<Config, Clear>,
+}
+
+impl<Config, Clear> Deref for DepthModeBuilder<'_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    type Target = GizmoBuffer<Config, Clear>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.buffer
+    }
+}
+
+impl<Config, Clear> DerefMut for DepthModeBuilder<'_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.buffer
+    }
+}
+
+impl<Config, Clear> Drop for DepthModeBuilder<'_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+        let buffer = &mut self.buffer;
+        let lines = GizmoLines {
+            list_positions: mem::take(&mut buffer.list_positions),
+            list_colors: mem::take(&mut buffer.list_colors),
+            strip_positions: mem::take(&mut buffer.strip_positions),
+            strip_colors: mem::take(&mut buffer.strip_colors),
+            triangle_positions: mem::take(&mut buffer.triangle_positions),
+            triangle_colors: mem::take(&mut buffer.triangle_colors),
+        };
+
+        // Gizmos drawn with a nested depth mode keep it.
+        let depth_lines = &mut self.gizmos.depth_lines;
+        depth_lines.append(&buffer.depth_lines);
+        depth_lines.append_with_mode(&lines, self.mode);
+
+        for mut text in buffer.texts.drain(..) {
+            text.depth_mode.get_or_insert(self.mode);
+            self.gizmos.texts.push(text);
+        }
+    }
+}
+
+/// Line and triangle vertices, in the same layout as those of a [`GizmoBuffer`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct GizmoLines {
+    pub(crate) list_positions: Vec<Vec3>,
+    pub(crate) list_colors: Vec<LinearRgba>,
+    pub(crate) strip_positions: Vec<Vec3>,
+    pub(crate) strip_colors: Vec<LinearRgba>,
+    pub(crate) triangle_positions: Vec<Vec3>,
+    pub(crate) triangle_colors: Vec<LinearRgba>,
+}
+
+impl GizmoLines {
+    pub(crate) fn append(&mut self, other: &Self) {
+        self.list_positions.extend(other.list_positions.iter());
+        self.list_colors.extend(other.list_colors.iter());
+        self.strip_positions.extend(other.strip_positions.iter());
+        self.strip_colors.extend(other.strip_colors.iter());
+        self.triangle_positions
+            .extend(other.triangle_positions.iter());
+        self.triangle_colors.extend(other.triangle_colors.iter());
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.list_positions.clear();
+        self.list_colors.clear();
+        self.strip_positions.clear();
+        self.strip_colors.clear();
+        self.triangle_positions.clear();
+        self.triangle_colors.clear();
+    }
+
+    fn faded(&self, alpha: f32) -> Self {
+        let fade = |color: &LinearRgba| color.with_alpha(color.alpha * alpha);
+        Self {
+            list_positions: self.list_positions.clone(),
+            list_colors: self.list_colors.iter().map(fade).collect(),
+            strip_positions: self.strip_positions.clone(),
+            strip_colors: self.strip_colors.iter().map(fade).collect(),
+            triangle_positions: self.triangle_positions.clone(),
+            triangle_colors: self.triangle_colors.iter().map(fade).collect(),
+        }
+    }
+}
+
+/// The lines drawn with a [`GizmoDepthMode`], rendered apart from the other lines of their group.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct GizmoDepthLines {
+    /// The lines hidden behind other geometry.
+    pub(crate) depth_tested: GizmoLines,
+    /// The lines drawn on top of all other geometry.
+    pub(crate) always_on_top: GizmoLines,
+}
+
+impl GizmoDepthLines {
+    pub(crate) fn append(&mut self, other: &Self) {
+        self.depth_tested.append(&other.depth_tested);
+        self.always_on_top.append(&other.always_on_top);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.depth_tested.clear();
+        self.always_on_top.clear();
+    }
+
+    /// Appends `lines` drawn with `mode`.
+    pub(crate) fn append_with_mode(&mut self, lines: &GizmoLines, mode: GizmoDepthMode) {
+        match mode {
+            GizmoDepthMode::DepthTested => self.depth_tested.append(lines),
+            GizmoDepthMode::AlwaysOnTop => self.always_on_top.append(lines),
+            GizmoDepthMode::FadeOccluded { alpha } => {
+                self.depth_tested.append(lines);
+                self.always_on_top.append(&lines.faded(alpha));
+            }
+        }
+    }
+}
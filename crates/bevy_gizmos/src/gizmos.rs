@@ -16,7 +16,7 @@ use bevy_ecs::{
         Deferred, ReadOnlySystemParam, Res, SystemBuffer, SystemMeta, SystemParam,
         SystemParamValidationError,
     },
-    world::{unsafe_world_cell::UnsafeWorldCell, World},
+    world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld, World},
 };
 use bevy_math::{bounding::Aabb3d, Isometry2d, Isometry3d, Vec2, Vec3};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
@@ -25,7 +25,10 @@ use bevy_utils::default;
 
 use crate::{
     config::{DefaultGizmoConfigGroup, GizmoConfigGroup, GizmoConfigStore},
+    depth::GizmoDepthLines,
     prelude::GizmoConfig,
+    retained::RetainedGizmoQueue,
+    text::GizmoText,
 };
 
 /// Storage of gizmo primitives.
@@ -35,6 +38,10 @@ pub struct GizmoStorage<Config, Clear> {
     pub(crate) list_colors: Vec<LinearRgba>,
     pub(crate) strip_positions: Vec<Vec3>,
     pub(crate) strip_colors: Vec<LinearRgba>,
+    pub(crate) triangle_positions: Vec<Vec3>,
+    pub(crate) triangle_colors: Vec<LinearRgba>,
+    pub(crate) texts: Vec<GizmoText>,
+    pub(crate) depth_lines: GizmoDepthLines,
     marker: PhantomData<(Config, Clear)>,
 }
 
@@ -45,6 +52,10 @@ impl<Config, Clear> Default for GizmoStorage<Config, Clear> {
             list_colors: default(),
             strip_positions: default(),
             strip_colors: default(),
+            triangle_positions: default(),
+            triangle_colors: default(),
+            texts: default(),
+            depth_lines: default(),
             marker: PhantomData,
         }
     }
@@ -64,6 +75,29 @@ where
         self.list_colors.extend(other.list_colors.iter());
         self.strip_positions.extend(other.strip_positions.iter());
         self.strip_colors.extend(other.strip_colors.iter());
+        self.triangle_positions
+            .extend(other.triangle_positions.iter());
+        self.triangle_colors.extend(other.triangle_colors.iter());
+        self.texts.extend(other.texts.iter().cloned());
+        self.depth_lines.append(&other.depth_lines);
+    }
+
+    pub(crate) fn append_buffer<OtherConfig, OtherClear>(
+        &mut self,
+        buffer: &GizmoBuffer<OtherConfig, OtherClear>,
+    ) where
+        OtherConfig: GizmoConfigGroup,
+        OtherClear: 'static + Send + Sync,
+    {
+        self.list_positions.extend(buffer.list_positions.iter());
+        self.list_colors.extend(buffer.list_colors.iter());
+        self.strip_positions.extend(buffer.strip_positions.iter());
+        self.strip_colors.extend(buffer.strip_colors.iter());
+        self.triangle_positions
+            .extend(buffer.triangle_positions.iter());
+        self.triangle_colors.extend(buffer.triangle_colors.iter());
+        self.texts.extend(buffer.texts.iter().cloned());
+        self.depth_lines.append(&buffer.depth_lines);
     }
 
     pub(crate) fn swap<OtherConfig, OtherClear>(
@@ -74,6 +108,10 @@ where
         mem::swap(&mut self.list_colors, &mut other.list_colors);
         mem::swap(&mut self.strip_positions, &mut other.strip_positions);
         mem::swap(&mut self.strip_colors, &mut other.strip_colors);
+        mem::swap(&mut self.triangle_positions, &mut other.triangle_positions);
+        mem::swap(&mut self.triangle_colors, &mut other.triangle_colors);
+        mem::swap(&mut self.texts, &mut other.texts);
+        mem::swap(&mut self.depth_lines, &mut other.depth_lines);
     }
 
     /// Clear this gizmo storage of any requested gizmos.
@@ -82,6 +120,10 @@ where
         self.list_colors.clear();
         self.strip_positions.clear();
         self.strip_colors.clear();
+        self.triangle_positions.clear();
+        self.triangle_colors.clear();
+        self.texts.clear();
+        self.depth_lines.clear();
     }
 }
 
@@ -105,6 +147,8 @@ pub struct Swap<Clear>(PhantomData<Clear>);
 /// Gizmos should be spawned before the [`Last`](bevy_app::Last) schedule
 /// to ensure they are drawn.
 ///
+/// To keep gizmos drawn for longer, such as marks of one-shot events, see [`Gizmos::retain`].
+///
 /// To set up your own clearing context (useful for custom scheduling similar
 /// to [`FixedMain`](bevy_app::FixedMain)):
 ///
@@ -146,6 +190,7 @@ where
     Clear: 'static + Send + Sync,
 {
     buffer: Deferred<'s, GizmoBuffer<Config, Clear>>,
+    pub(crate) retained: Deferred<'s, RetainedGizmoQueue<Config>>,
     /// The currently used [`GizmoConfig`]
     pub config: &'w GizmoConfig,
     /// The currently used [`GizmoConfigGroup`]
@@ -176,6 +221,7 @@ where
 
 type GizmosState<Config, Clear> = (
     Deferred<'static, GizmoBuffer<Config, Clear>>,
+    Deferred<'static, RetainedGizmoQueue<Config>>,
     Res<'static, GizmoConfigStore>,
 );
 #[doc(hidden)]
@@ -224,6 +270,10 @@ where
         GizmosState::<Config, Clear>::apply(&mut state.state, system_meta, world);
     }
 
+    fn queue(state: &mut Self::State, system_meta: &SystemMeta, world: DeferredWorld) {
+        GizmosState::<Config, Clear>::queue(&mut state.state, system_meta, world);
+    }
+
     #[inline]
     unsafe fn validate_param(
         state: &mut Self::State,
@@ -244,7 +294,7 @@ where
         change_tick: Tick,
     ) -> Self::Item<'w, 's> {
         // SAFETY: Delegated to existing `SystemParam` implementations.
-        let (mut f0, f1) = unsafe {
+        let (mut f0, f1, f2) = unsafe {
             GizmosState::<Config, Clear>::get_param(
                 &mut state.state,
                 system_meta,
@@ -256,11 +306,12 @@ where
         // Accessing the GizmoConfigStore in every API call reduces performance significantly.
         // Implementing SystemParam manually allows us to cache whether the config is currently enabled.
         // Having this available allows for cheap early returns when gizmos are disabled.
-        let (config, config_ext) = f1.into_inner().config::<Config>();
+        let (config, config_ext) = f2.into_inner().config::<Config>();
         f0.enabled = config.enabled;
 
         Gizmos {
             buffer: f0,
+            retained: f1,
             config,
             config_ext,
         }
@@ -277,6 +328,7 @@ where
     Config: GizmoConfigGroup,
     Clear: 'static + Send + Sync,
     Deferred<'s, GizmoBuffer<Config, Clear>>: ReadOnlySystemParam,
+    Deferred<'s, RetainedGizmoQueue<Config>>: ReadOnlySystemParam,
     Res<'w, GizmoConfigStore>: ReadOnlySystemParam,
 {
 }
@@ -298,6 +350,16 @@ where
     pub strip_positions: Vec<Vec3>,
     /// The colors of line strip vertices.
     pub strip_colors: Vec<LinearRgba>,
+    /// The positions of filled triangle vertices, three per triangle.
+    pub triangle_positions: Vec<Vec3>,
+    /// The colors of filled triangle vertices.
+    pub triangle_colors: Vec<LinearRgba>,
+    /// The text labels, drawn facing the camera by [`Gizmos`].
+    #[reflect(ignore, clone)]
+    pub(crate) texts: Vec<GizmoText>,
+    /// The lines drawn with a [`GizmoDepthMode`](crate::depth::GizmoDepthMode).
+    #[reflect(ignore, clone)]
+    pub(crate) depth_lines: GizmoDepthLines,
     #[reflect(ignore, clone)]
     pub(crate) marker: PhantomData<(Config, Clear)>,
 }
@@ -314,6 +376,10 @@ where
             list_colors: Vec::new(),
             strip_positions: Vec::new(),
             strip_colors: Vec::new(),
+            triangle_positions: Vec::new(),
+            triangle_colors: Vec::new(),
+            texts: Vec::new(),
+            depth_lines: GizmoDepthLines::default(),
             marker: PhantomData,
         }
     }
@@ -329,6 +395,10 @@ pub struct GizmoBufferView<'a> {
     pub strip_positions: &'a Vec<Vec3>,
     /// Vertex colors for line-strip topology.
     pub strip_colors: &'a Vec<LinearRgba>,
+    /// Vertex positions for triangle-list topology.
+    pub triangle_positions: &'a Vec<Vec3>,
+    /// Vertex colors for triangle-list topology.
+    pub triangle_colors: &'a Vec<LinearRgba>,
 }
 
 impl<Config, Clear> SystemBuffer for GizmoBuffer<Config, Clear>
@@ -342,6 +412,13 @@ where
         storage.list_colors.append(&mut self.list_colors);
         storage.strip_positions.append(&mut self.strip_positions);
         storage.strip_colors.append(&mut self.strip_colors);
+        storage
+            .triangle_positions
+            .append(&mut self.triangle_positions);
+        storage.triangle_colors.append(&mut self.triangle_colors);
+        storage.texts.append(&mut self.texts);
+        storage.depth_lines.append(&self.depth_lines);
+        self.depth_lines.clear();
     }
 }
 
@@ -356,6 +433,10 @@ where
         self.list_colors.clear();
         self.strip_positions.clear();
         self.strip_colors.clear();
+        self.triangle_positions.clear();
+        self.triangle_colors.clear();
+        self.texts.clear();
+        self.depth_lines.clear();
     }
 
     /// Read-only view into the buffers data.
@@ -365,6 +446,8 @@ where
             list_colors,
             strip_positions,
             strip_colors,
+            triangle_positions,
+            triangle_colors,
             ..
         } = self;
         GizmoBufferView {
@@ -372,6 +455,8 @@ where
             list_colors,
             strip_positions,
             strip_colors,
+            triangle_positions,
+            triangle_colors,
         }
     }
     /// Draw a line in 3D from `start` to `end`.
@@ -29,14 +29,20 @@ pub mod circles;
 pub mod config;
 pub mod cross;
 pub mod curves;
+pub mod depth;
 pub mod gizmos;
 pub mod grid;
 pub mod primitives;
 pub mod retained;
 pub mod rounded_box;
+pub mod screen;
+pub mod solid;
+pub mod text;
 
 #[cfg(feature = "bevy_light")]
 pub mod light;
+#[cfg(feature = "bevy_picking")]
+pub mod transform_gizmo;
 
 /// The gizmos prelude.
 ///
@@ -51,35 +57,52 @@ pub mod prelude {
             DefaultGizmoConfigGroup, GizmoConfig, GizmoConfigGroup, GizmoConfigStore,
             GizmoLineConfig, GizmoLineJoint, GizmoLineStyle,
         },
+        depth::GizmoDepthMode,
         gizmos::Gizmos,
         primitives::{dim2::GizmoPrimitive2d, dim3::GizmoPrimitive3d},
-        retained::Gizmo,
+        retained::{Gizmo, RetainedGizmoId},
+        screen::{ScreenGizmoConfigGroup, ScreenGizmos},
         AppGizmoBuilder, GizmoAsset,
     };
 
     #[doc(hidden)]
     #[cfg(feature = "bevy_light")]
     pub use crate::light::{LightGizmoColor, LightGizmoConfigGroup, ShowLightGizmo};
+
+    #[doc(hidden)]
+    #[cfg(feature = "bevy_picking")]
+    pub use crate::transform_gizmo::{
+        TransformGizmo, TransformGizmoAxis, TransformGizmoConfigGroup, TransformGizmoDelta,
+        TransformGizmoDrag, TransformGizmoMode, TransformGizmoSpace,
+    };
 }
 
 use bevy_app::{App, FixedFirst, FixedLast, Last, Plugin, RunFixedMainLoop};
 use bevy_asset::{Asset, AssetApp, Assets, Handle};
+use bevy_camera::Camera;
 use bevy_ecs::{
+    query::Without,
     resource::Resource,
     schedule::{IntoScheduleConfigs, SystemSet},
-    system::{Res, ResMut},
+    system::{Query, Res, ResMut},
 };
+use bevy_math::Vec3;
 use bevy_reflect::TypePath;
+use bevy_transform::components::GlobalTransform;
 
-use crate::{config::ErasedGizmoConfigGroup, gizmos::GizmoBuffer};
+use crate::{config::ErasedGizmoConfigGroup, depth::GizmoLines, gizmos::GizmoBuffer};
 
 use bevy_time::Fixed;
 use bevy_utils::TypeIdMap;
 use config::{DefaultGizmoConfigGroup, GizmoConfig, GizmoConfigGroup, GizmoConfigStore};
-use core::{any::TypeId, marker::PhantomData, mem};
+use core::{any::TypeId, mem};
 use gizmos::{GizmoStorage, Swap};
 #[cfg(feature = "bevy_light")]
 use light::LightGizmoPlugin;
+use retained::{draw_retained_gizmos, RetainedGizmos};
+use screen::{ScreenGizmoCamera, ScreenGizmoConfigGroup, ScreenGizmoPlugin};
+#[cfg(feature = "bevy_picking")]
+use transform_gizmo::TransformGizmoPlugin;
 
 /// A [`Plugin`] that provides an immediate mode drawing api for visual debugging.
 #[derive(Default)]
@@ -92,10 +115,13 @@ impl Plugin for GizmoPlugin {
             // We insert the Resource GizmoConfigStore into the world implicitly here if it does not exist.
             .init_gizmo_group::<DefaultGizmoConfigGroup>();
 
-        app.add_plugins(aabb::AabbGizmoPlugin);
+        app.add_plugins((aabb::AabbGizmoPlugin, ScreenGizmoPlugin));
 
         #[cfg(feature = "bevy_light")]
         app.add_plugins(LightGizmoPlugin);
+
+        #[cfg(feature = "bevy_picking")]
+        app.add_plugins(TransformGizmoPlugin);
     }
 }
 
@@ -129,11 +155,15 @@ impl AppGizmoBuilder for App {
         let mut handles = self.world_mut().get_resource_or_init::<GizmoHandles>();
 
         handles.handles.insert(TypeId::of::<Config>(), None);
+        handles
+            .depth_mode_handles
+            .insert(TypeId::of::<Config>(), GizmoDepthModeHandles::default());
 
         // These handles are safe to mutate in any order
         self.allow_ambiguous_resource::<GizmoHandles>();
 
         self.init_resource::<GizmoStorage<Config, ()>>()
+            .init_resource::<RetainedGizmos<Config>>()
             .init_resource::<GizmoStorage<Config, Fixed>>()
             .init_resource::<GizmoStorage<Config, Swap<Fixed>>>()
             .add_systems(
@@ -152,6 +182,7 @@ impl AppGizmoBuilder for App {
                 Last,
                 (
                     propagate_gizmos::<Config, Fixed>.before(GizmoMeshSystems),
+                    draw_retained_gizmos::<Config>.before(GizmoMeshSystems),
                     update_gizmo_meshes::<Config>.in_set(GizmoMeshSystems),
                 ),
             );
@@ -182,6 +213,7 @@ impl AppGizmoBuilder for App {
 #[derive(Resource, Default)]
 pub struct GizmoHandles {
     handles: TypeIdMap<Option<Handle<GizmoAsset>>>,
+    depth_mode_handles: TypeIdMap<GizmoDepthModeHandles>,
 }
 
 impl GizmoHandles {
@@ -189,6 +221,22 @@ impl GizmoHandles {
     pub fn handles(&self) -> &TypeIdMap<Option<Handle<GizmoAsset>>> {
         &self.handles
     }
+
+    /// The handles to the gizmo assets of the gizmos drawn with a [`GizmoDepthMode`](depth::GizmoDepthMode) by each gizmo
+    /// configuration group.
+    pub fn depth_mode_handles(&self) -> &TypeIdMap<GizmoDepthModeHandles> {
+        &self.depth_mode_handles
+    }
+}
+
+/// The handles to the gizmo assets of the gizmos drawn with a [`GizmoDepthMode`](depth::GizmoDepthMode) by a gizmo
+/// configuration group, which override the depth bias of the group.
+#[derive(Clone, Debug, Default)]
+pub struct GizmoDepthModeHandles {
+    /// The gizmos hidden behind other geometry.
+    pub depth_tested: Option<Handle<GizmoAsset>>,
+    /// The gizmos drawn on top of all other geometry.
+    pub always_on_top: Option<Handle<GizmoAsset>>,
 }
 
 /// Start a new gizmo clearing context.
@@ -267,33 +315,113 @@ fn update_gizmo_meshes<Config: GizmoConfigGroup>(
     mut gizmo_assets: ResMut<Assets<GizmoAsset>>,
     mut handles: ResMut<GizmoHandles>,
     mut storage: ResMut<GizmoStorage<Config, ()>>,
+    cameras: Query<(&Camera, &GlobalTransform), Without<ScreenGizmoCamera>>,
 ) {
-    if storage.list_positions.is_empty() && storage.strip_positions.is_empty() {
-        handles.handles.insert(TypeId::of::<Config>(), None);
-    } else if let Some(handle) = handles.handles.get_mut(&TypeId::of::<Config>()) {
-        if let Some(handle) = handle {
-            let gizmo = gizmo_assets.get_mut(handle.id()).unwrap();
-
-            gizmo.buffer.list_positions = mem::take(&mut storage.list_positions);
-            gizmo.buffer.list_colors = mem::take(&mut storage.list_colors);
-            gizmo.buffer.strip_positions = mem::take(&mut storage.strip_positions);
-            gizmo.buffer.strip_colors = mem::take(&mut storage.strip_colors);
+    if !storage.texts.is_empty() {
+        // Text labels face the camera drawn last, which usually renders to the window. Screen
+        // gizmos are drawn with the Y axis pointing down.
+        let (right, up) = if TypeId::of::<Config>() == TypeId::of::<ScreenGizmoConfigGroup>() {
+            (Vec3::X, Vec3::NEG_Y)
         } else {
-            let gizmo = GizmoAsset {
-                config_ty: TypeId::of::<Config>(),
-                buffer: GizmoBuffer {
-                    enabled: true,
-                    list_positions: mem::take(&mut storage.list_positions),
-                    list_colors: mem::take(&mut storage.list_colors),
-                    strip_positions: mem::take(&mut storage.strip_positions),
-                    strip_colors: mem::take(&mut storage.strip_colors),
-                    marker: PhantomData,
-                },
-            };
-
-            *handle = Some(gizmo_assets.add(gizmo));
+            cameras
+                .iter()
+                .filter(|(camera, _)| camera.is_active)
+                .max_by_key(|(camera, _)| camera.order)
+                .map_or((Vec3::X, Vec3::Y), |(_, transform)| {
+                    (*transform.right(), *transform.up())
+                })
+        };
+        let GizmoStorage {
+            list_positions,
+            list_colors,
+            texts,
+            depth_lines,
+            ..
+        } = &mut *storage;
+        for text in texts.drain(..) {
+            if let Some(depth_mode) = text.depth_mode {
+                let mut lines = GizmoLines::default();
+                text.draw(right, up, &mut lines.list_positions, &mut lines.list_colors);
+                depth_lines.append_with_mode(&lines, depth_mode);
+            } else {
+                text.draw(right, up, list_positions, list_colors);
+            }
         }
     }
+
+    let config_ty = TypeId::of::<Config>();
+    if let Some(handle) = handles.handles.get_mut(&config_ty) {
+        let lines = GizmoLines {
+            list_positions: mem::take(&mut storage.list_positions),
+            list_colors: mem::take(&mut storage.list_colors),
+            strip_positions: mem::take(&mut storage.strip_positions),
+            strip_colors: mem::take(&mut storage.strip_colors),
+            triangle_positions: mem::take(&mut storage.triangle_positions),
+            triangle_colors: mem::take(&mut storage.triangle_colors),
+        };
+        update_gizmo_asset(&mut gizmo_assets, handle, config_ty, lines);
+    }
+
+    let depth_lines = mem::take(&mut storage.depth_lines);
+    let depth_mode_handles = handles.depth_mode_handles.entry(config_ty).or_default();
+    update_gizmo_asset(
+        &mut gizmo_assets,
+        &mut depth_mode_handles.depth_tested,
+        config_ty,
+        depth_lines.depth_tested,
+    );
+    update_gizmo_asset(
+        &mut gizmo_assets,
+        &mut depth_mode_handles.always_on_top,
+        config_ty,
+        depth_lines.always_on_top,
+    );
+}
+
+/// Moves `lines` to the gizmo asset of `handle`, adding the asset if needed, or removes the asset
+/// if there are no lines or triangles.
+fn update_gizmo_asset(
+    gizmo_assets: &mut Assets<GizmoAsset>,
+    handle: &mut Option<Handle<GizmoAsset>>,
+    config_ty: TypeId,
+    lines: GizmoLines,
+) {
+    let GizmoLines {
+        list_positions,
+        list_colors,
+        strip_positions,
+        strip_colors,
+        triangle_positions,
+        triangle_colors,
+    } = lines;
+
+    if list_positions.is_empty() && strip_positions.is_empty() && triangle_positions.is_empty() {
+        *handle = None;
+    } else if let Some(handle) = handle {
+        let gizmo = gizmo_assets.get_mut(handle.id()).unwrap();
+
+        gizmo.buffer.list_positions = list_positions;
+        gizmo.buffer.list_colors = list_colors;
+        gizmo.buffer.strip_positions = strip_positions;
+        gizmo.buffer.strip_colors = strip_colors;
+        gizmo.buffer.triangle_positions = triangle_positions;
+        gizmo.buffer.triangle_colors = triangle_colors;
+    } else {
+        let gizmo = GizmoAsset {
+            config_ty,
+            buffer: GizmoBuffer {
+                list_positions,
+                list_colors,
+                strip_positions,
+                strip_colors,
+                triangle_positions,
+                triangle_colors,
+                ..Default::default()
+            },
+        };
+
+        *handle = Some(gizmo_assets.add(gizmo));
+    }
 }
 
 /// A collection of gizmos.
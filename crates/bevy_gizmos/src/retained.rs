@@ -1,15 +1,26 @@
 //! This module is for 'retained' alternatives to the 'immediate mode' [`Gizmos`](crate::gizmos::Gizmos) system parameter.
 
-use core::ops::{Deref, DerefMut};
+use core::{
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
 
 use bevy_asset::Handle;
-use bevy_ecs::{component::Component, reflect::ReflectComponent};
+use bevy_ecs::{
+    component::Component,
+    reflect::ReflectComponent,
+    resource::Resource,
+    system::{Res, ResMut, SystemBuffer, SystemMeta},
+    world::{DeferredWorld, World},
+};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::Time;
 use bevy_transform::components::Transform;
 
 use crate::{
-    config::{ErasedGizmoConfigGroup, GizmoLineConfig},
-    gizmos::GizmoBuffer,
+    config::{ErasedGizmoConfigGroup, GizmoConfigGroup, GizmoLineConfig},
+    gizmos::{GizmoBuffer, GizmoStorage, Gizmos},
     GizmoAsset,
 };
 
@@ -83,3 +94,363 @@ pub struct Gizmo {
     /// You would set this value to a negative number close to 0.
     pub depth_bias: f32,
 }
+
+/// The identifier of gizmos retained with [`Gizmos::retain`], used to clear them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RetainedGizmoId(u64);
+
+impl RetainedGizmoId {
+    fn next() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl<Config, Clear> Gizmos<'_, '_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Returns a [`GizmoBuffer`] whose gizmos are drawn every frame until they're cleared, or
+    /// until their [`lifetime`](RetainedGizmoBuilder::lifetime) has elapsed.
+    ///
+    /// The gizmos are retained once the builder is dropped, and identified by its
+    /// [`id`](RetainedGizmoBuilder::id) to clear them with [`Gizmos::clear_retained`].
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::{RED, YELLOW};
+    /// # use core::time::Duration;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     // Mark a hit for two seconds.
+    ///     gizmos
+    ///         .retain()
+    ///         .lifetime(Duration::from_secs(2))
+    ///         .sphere(Vec3::ZERO, 0.1, RED);
+    ///
+    ///     // Draw a path until it's cleared.
+    ///     let mut path = gizmos.retain();
+    ///     path.linestrip([Vec3::ZERO, Vec3::X, Vec3::Y], YELLOW);
+    ///     let id = path.id();
+    ///     drop(path);
+    ///
+    ///     gizmos.clear_retained(id);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn retain(&mut self) -> RetainedGizmoBuilder<'_, Config> {
+        let enabled = self.enabled;
+        RetainedGizmoBuilder {
+            queue: &mut self.retained,
+            id: RetainedGizmoId::next(),
+            lifetime: None,
+            buffer: GizmoBuffer {
+                enabled,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Clears the gizmos retained with the given `id`.
+    pub fn clear_retained(&mut self, id: RetainedGizmoId) {
+        self.retained.commands.push(RetainedGizmoCommand::Clear(id));
+    }
+
+    /// Clears all the gizmos retained with [`Gizmos::retain`] for this [`GizmoConfigGroup`].
+    pub fn clear_all_retained(&mut self) {
+        self.retained.commands.push(RetainedGizmoCommand::ClearAll);
+    }
+}
+
+/// A builder returned by [`Gizmos::retain`].
+///
+/// It dereferences to the [`GizmoBuffer`] the retained gizmos are drawn to.
+pub struct RetainedGizmoBuilder<'a, Config>
+where
+    Config: GizmoConfigGroup,
+{
+    queue: &'a mut RetainedGizmoQueue<Config>,
+    id: RetainedGizmoId,
+    lifetime: Option<Duration>,
+    buffer: GizmoBuffer<Config, ()>,
+}
+
+impl<Config> RetainedGizmoBuilder<'_, Config>
+where
+    Config: GizmoConfigGroup,
+{
+    /// Set how long the gizmos are drawn for, measured with the default [`Time`].
+    ///
+    /// The gizmos are always drawn at least once. By default, they're drawn until they're cleared.
+    pub fn lifetime(mut self, lifetime: Duration) -> Self {
+        self.lifetime = Some(lifetime);
+        self
+    }
+
+    /// The identifier of the retained gizmos.
+    pub fn id(&self) -> RetainedGizmoId {
+        self.id
+    }
+}
+
+impl<Config> Deref for RetainedGizmoBuilder<'_, Config>
+where
+    Config: GizmoConfigGroup,
+{
+    type Target = GizmoBuffer<Config, ()>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.buffer
+    }
+}
+
+impl<Config> DerefMut for RetainedGizmoBuilder<'_, Config>
+where
+    Config: GizmoConfigGroup,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.buffer
+    }
+}
+
+impl<Config> Drop for RetainedGizmoBuilder<'_, Config>
+where
+    Config: GizmoConfigGroup,
+{
+    fn drop(&mut self) {
+        if !self.buffer.enabled {
+            return;
+        }
+        let mut storage = GizmoStorage::default();
+        storage.append_buffer(&self.buffer);
+        self.queue
+            .commands
+            .push(RetainedGizmoCommand::Retain(Box::new(RetainedGizmo {
+                id: self.id,
+                remaining: self.lifetime,
+                storage,
+            })));
+    }
+}
+
+/// Gizmos retained with [`Gizmos::retain`], drawn every frame until they're cleared or expire.
+#[derive(Resource)]
+pub struct RetainedGizmos<Config: GizmoConfigGroup> {
+    gizmos: Vec<RetainedGizmo<Config>>,
+}
+
+impl<Config: GizmoConfigGroup> Default for RetainedGizmos<Config> {
+    fn default() -> Self {
+        Self { gizmos: Vec::new() }
+    }
+}
+
+impl<Config: GizmoConfigGroup> RetainedGizmos<Config> {
+    /// Returns `true` if the gizmos retained with `id` are still drawn.
+    pub fn contains(&self, id: RetainedGizmoId) -> bool {
+        self.gizmos.iter().any(|gizmo| gizmo.id == id)
+    }
+
+    /// Returns the identifiers of the retained gizmos.
+    pub fn ids(&self) -> impl Iterator<Item = RetainedGizmoId> + '_ {
+        self.gizmos.iter().map(|gizmo| gizmo.id)
+    }
+
+    /// Clears the gizmos retained with `id`.
+    pub fn clear(&mut self, id: RetainedGizmoId) {
+        self.gizmos.retain(|gizmo| gizmo.id != id);
+    }
+
+    /// Clears all the retained gizmos.
+    pub fn clear_all(&mut self) {
+        self.gizmos.clear();
+    }
+}
+
+struct RetainedGizmo<Config> {
+    id: RetainedGizmoId,
+    /// The time left until the gizmos expire, or `None` if they're drawn until cleared.
+    remaining: Option<Duration>,
+    storage: GizmoStorage<Config, ()>,
+}
+
+enum RetainedGizmoCommand<Config> {
+    Retain(Box<RetainedGizmo<Config>>),
+    Clear(RetainedGizmoId),
+    ClearAll,
+}
+
+/// The changes to the [`RetainedGizmos`] made by a [`Gizmos`] system parameter.
+#[doc(hidden)]
+pub struct RetainedGizmoQueue<Config> {
+    commands: Vec<RetainedGizmoCommand<Config>>,
+}
+
+impl<Config> Default for RetainedGizmoQueue<Config> {
+    fn default() -> Self {
+        Self {
+            commands: Vec::new(),
+        }
+    }
+}
+
+impl<Config: GizmoConfigGroup> SystemBuffer for RetainedGizmoQueue<Config> {
+    fn apply(&mut self, system_meta: &SystemMeta, world: &mut World) {
+        self.queue(system_meta, world.into());
+    }
+
+    // Also applied when queued, so that gizmos retained by observers and one-shot systems aren't lost.
+    fn queue(&mut self, _system_meta: &SystemMeta, mut world: DeferredWorld) {
+        if self.commands.is_empty() {
+            return;
+        }
+        let mut retained = world.resource_mut::<RetainedGizmos<Config>>();
+        for command in self.commands.drain(..) {
+            match command {
+                RetainedGizmoCommand::Retain(gizmo) => retained.gizmos.push(*gizmo),
+                RetainedGizmoCommand::Clear(id) => retained.clear(id),
+                RetainedGizmoCommand::ClearAll => retained.clear_all(),
+            }
+        }
+    }
+}
+
+/// Draws the [`RetainedGizmos`], and clears those that expired.
+pub(crate) fn draw_retained_gizmos<Config: GizmoConfigGroup>(
+    time: Option<Res<Time>>,
+    mut retained: ResMut<RetainedGizmos<Config>>,
+    mut storage: ResMut<GizmoStorage<Config, ()>>,
+) {
+    let delta = time.map(|time| time.delta()).unwrap_or_default();
+    retained.gizmos.retain_mut(|gizmo| {
+        storage.append_storage(&gizmo.storage);
+        match &mut gizmo.remaining {
+            Some(remaining) if *remaining <= delta => false,
+            Some(remaining) => {
+                *remaining -= delta;
+                true
+            }
+            None => true,
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{DefaultGizmoConfigGroup, GizmoConfigStore};
+    use bevy_color::palettes::basic::RED;
+    use bevy_ecs::system::In;
+    use bevy_math::Vec3;
+
+    type Storage = GizmoStorage<DefaultGizmoConfigGroup, ()>;
+
+    /// The duration of a frame.
+    const FRAME: Duration = Duration::from_millis(100);
+
+    fn world() -> World {
+        let mut world = World::new();
+        let mut store = GizmoConfigStore::default();
+        store.register::<DefaultGizmoConfigGroup>();
+        world.insert_resource(store);
+        world.init_resource::<Time>();
+        world.init_resource::<RetainedGizmos<DefaultGizmoConfigGroup>>();
+        world.init_resource::<Storage>();
+        world
+    }
+
+    /// Retains a line drawn for `lifetime`, or until it's cleared if it's `None`.
+    fn retain_line(world: &mut World, lifetime: Option<Duration>) -> RetainedGizmoId {
+        fn retain(In(lifetime): In<Option<Duration>>, mut gizmos: Gizmos) -> RetainedGizmoId {
+            let mut builder = gizmos.retain();
+            if let Some(lifetime) = lifetime {
+                builder = builder.lifetime(lifetime);
+            }
+            builder.line(Vec3::ZERO, Vec3::X, RED);
+            builder.id()
+        }
+        world.run_system_cached_with(retain, lifetime).unwrap()
+    }
+
+    /// Advances the time by `delta` and draws the retained gizmos, returning the number of lines
+    /// drawn.
+    fn draw(world: &mut World, delta: Duration) -> usize {
+        world.resource_mut::<Time>().advance_by(delta);
+        world.resource_mut::<Storage>().clear();
+        world
+            .run_system_cached(draw_retained_gizmos::<DefaultGizmoConfigGroup>)
+            .unwrap();
+        world.resource::<Storage>().list_positions.len() / 2
+    }
+
+    fn is_retained(world: &World, id: RetainedGizmoId) -> bool {
+        world
+            .resource::<RetainedGizmos<DefaultGizmoConfigGroup>>()
+            .contains(id)
+    }
+
+    #[test]
+    fn retained_gizmos_expire_after_their_lifetime() {
+        let mut world = world();
+        let id = retain_line(&mut world, Some(3 * FRAME));
+        let forever = retain_line(&mut world, None);
+
+        assert_eq!(draw(&mut world, FRAME), 2);
+        assert_eq!(draw(&mut world, FRAME), 2);
+        assert_eq!(draw(&mut world, FRAME), 2);
+        assert!(!is_retained(&world, id));
+        assert_eq!(draw(&mut world, FRAME), 1);
+        assert!(is_retained(&world, forever));
+    }
+
+    #[test]
+    fn retained_gizmos_with_zero_lifetime_are_drawn_once() {
+        let mut world = world();
+        retain_line(&mut world, Some(Duration::ZERO));
+
+        // Even if no time passed since the gizmos were retained.
+        assert_eq!(draw(&mut world, Duration::ZERO), 1);
+        assert_eq!(draw(&mut world, Duration::ZERO), 0);
+        assert_eq!(draw(&mut world, FRAME), 0);
+    }
+
+    #[test]
+    fn clear_retained_gizmos_by_id() {
+        let mut world = world();
+        let cleared = retain_line(&mut world, None);
+        let kept = retain_line(&mut world, Some(10 * FRAME));
+        assert_eq!(draw(&mut world, FRAME), 2);
+
+        world
+            .run_system_cached_with(
+                |In(id): In<RetainedGizmoId>, mut gizmos: Gizmos| gizmos.clear_retained(id),
+                cleared,
+            )
+            .unwrap();
+        assert!(!is_retained(&world, cleared));
+        assert!(is_retained(&world, kept));
+        assert_eq!(draw(&mut world, FRAME), 1);
+    }
+
+    #[test]
+    fn clear_all_retained_gizmos() {
+        let mut world = world();
+        retain_line(&mut world, None);
+        retain_line(&mut world, Some(10 * FRAME));
+        assert_eq!(draw(&mut world, FRAME), 2);
+
+        world
+            .run_system_cached(|mut gizmos: Gizmos| gizmos.clear_all_retained())
+            .unwrap();
+        assert_eq!(draw(&mut world, FRAME), 0);
+        assert_eq!(
+            world
+                .resource::<RetainedGizmos<DefaultGizmoConfigGroup>>()
+                .ids()
+                .count(),
+            0
+        );
+    }
+}
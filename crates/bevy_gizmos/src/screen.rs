@@ -0,0 +1,156 @@
+//! Gizmos drawn in screen space, in the pixel coordinates of the window.
+//!
+//! Includes the [`ScreenGizmoConfigGroup`], the [`ScreenGizmos`] system parameter drawing with it,
+//! and the [`ScreenGizmoCamera`] they're drawn by.
+
+use core::{any::TypeId, f32::consts::PI};
+
+use bevy_app::{App, Last, Plugin, Startup};
+use bevy_camera::{
+    visibility::RenderLayers, Camera, Camera2d, CameraOutputMode, ClearColorConfig,
+    OrthographicProjection, Projection,
+};
+use bevy_color::Color;
+use bevy_ecs::{
+    component::Component,
+    query::With,
+    reflect::ReflectComponent,
+    schedule::IntoScheduleConfigs,
+    system::{Commands, Query, Res},
+};
+use bevy_math::{Quat, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_transform::components::Transform;
+use wgpu_types::BlendState;
+
+use crate::{
+    config::{GizmoConfig, GizmoConfigGroup, GizmoConfigStore},
+    gizmos::Gizmos,
+    AppGizmoBuilder, GizmoHandles, GizmoMeshSystems,
+};
+
+/// The [`RenderLayers`] layer screen gizmos are drawn on by default.
+///
+/// It's reserved for the [`ScreenGizmoCamera`], which only renders this layer. Another layer can
+/// be used by changing the [`render_layers`](GizmoConfig::render_layers) of the
+/// [`ScreenGizmoConfigGroup`].
+pub const DEFAULT_SCREEN_GIZMO_RENDER_LAYER: usize = 31;
+
+/// A [`Plugin`] that draws the gizmos of the [`ScreenGizmoConfigGroup`] on top of all cameras.
+pub struct ScreenGizmoPlugin;
+
+impl Plugin for ScreenGizmoPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_gizmo_config(
+            ScreenGizmoConfigGroup,
+            GizmoConfig {
+                render_layers: RenderLayers::layer(DEFAULT_SCREEN_GIZMO_RENDER_LAYER),
+                ..Default::default()
+            },
+        )
+        .add_systems(Startup, spawn_screen_gizmo_camera)
+        .add_systems(Last, update_screen_gizmo_camera.after(GizmoMeshSystems));
+    }
+}
+
+/// The [`GizmoConfigGroup`] of gizmos drawn in the pixel coordinates of the primary window.
+///
+/// The coordinates are in logical pixels, with the origin at the top left corner of the window and
+/// the Y axis pointing down, like `Window::cursor_position`. Gizmos are drawn with the 2D methods
+/// of [`Gizmos`], on top of everything rendered by the other cameras, regardless of where these
+/// cameras are. As the Y axis points down, angles go clockwise.
+///
+/// See [`ScreenGizmos`].
+#[derive(Clone, Default, Reflect, GizmoConfigGroup)]
+#[reflect(Clone, Default)]
+pub struct ScreenGizmoConfigGroup;
+
+/// A [`SystemParam`](bevy_ecs::system::SystemParam) for drawing gizmos in the pixel coordinates
+/// of the primary window, on top of all cameras.
+///
+/// See [`ScreenGizmoConfigGroup`] for the coordinate system.
+///
+/// # Example
+/// ```
+/// # use bevy_gizmos::prelude::*;
+/// # use bevy_math::prelude::*;
+/// # use bevy_color::palettes::basic::{RED, WHITE};
+/// fn system(mut screen_gizmos: ScreenGizmos) {
+///     // A selection rectangle from the pixel at (100, 50) to the one at (300, 200).
+///     let selection = Rect::new(100., 50., 300., 200.);
+///     screen_gizmos.rect_2d(selection.center(), selection.size(), WHITE);
+///
+///     // A marker 20 pixels from the top left corner.
+///     screen_gizmos.circle_2d(Vec2::splat(20.), 8., RED);
+/// }
+/// # bevy_ecs::system::assert_is_system(system);
+/// ```
+pub type ScreenGizmos<'w, 's, Clear = ()> = Gizmos<'w, 's, ScreenGizmoConfigGroup, Clear>;
+
+/// Marks the camera drawing the gizmos of the [`ScreenGizmoConfigGroup`].
+///
+/// It's spawned by the [`ScreenGizmoPlugin`] with the highest [`order`](Camera::order), and is
+/// only active while there are screen gizmos to draw. Its [`RenderLayers`] follow the
+/// [`render_layers`](GizmoConfig::render_layers) of the group. Its
+/// [`target`](Camera::target) can be changed to draw the screen gizmos to another window.
+///
+/// When `bevy_ui` is enabled, this camera doesn't render any UI, so it's never picked as the
+/// default UI camera.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default, Debug, Clone)]
+pub struct ScreenGizmoCamera;
+
+fn spawn_screen_gizmo_camera(mut commands: Commands) {
+    commands.spawn((
+        ScreenGizmoCamera,
+        Camera2d,
+        Camera {
+            order: isize::MAX,
+            is_active: false,
+            // The gizmos are drawn on a transparent texture, blended on top of the output of the
+            // other cameras, whatever their HDR and MSAA settings are.
+            clear_color: ClearColorConfig::Custom(Color::NONE),
+            output_mode: CameraOutputMode::Write {
+                blend_state: Some(BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                clear_color: ClearColorConfig::None,
+            },
+            msaa_writeback: false,
+            ..Default::default()
+        },
+        Projection::Orthographic(OrthographicProjection {
+            // One unit per logical pixel, from the top left corner of the window.
+            viewport_origin: Vec2::new(0., 1.),
+            ..OrthographicProjection::default_2d()
+        }),
+        // Flips the Y axis to point down.
+        Transform::from_rotation(Quat::from_rotation_x(PI)),
+        RenderLayers::layer(DEFAULT_SCREEN_GIZMO_RENDER_LAYER),
+    ));
+}
+
+/// Activates the [`ScreenGizmoCamera`]s while there are screen gizmos to draw, and keeps their
+/// [`RenderLayers`] in sync with the [`ScreenGizmoConfigGroup`].
+fn update_screen_gizmo_camera(
+    config_store: Res<GizmoConfigStore>,
+    handles: Res<GizmoHandles>,
+    mut cameras: Query<(&mut Camera, &mut RenderLayers), With<ScreenGizmoCamera>>,
+) {
+    let (config, _) = config_store.config::<ScreenGizmoConfigGroup>();
+    let config_ty = TypeId::of::<ScreenGizmoConfigGroup>();
+    let is_active = handles.handles.get(&config_ty).is_some_and(Option::is_some)
+        || handles
+            .depth_mode_handles
+            .get(&config_ty)
+            .is_some_and(|handles| {
+                handles.depth_tested.is_some() || handles.always_on_top.is_some()
+            });
+
+    for (mut camera, mut render_layers) in &mut cameras {
+        if camera.is_active != is_active {
+            camera.is_active = is_active;
+        }
+        if *render_layers != config.render_layers {
+            *render_layers = config.render_layers.clone();
+        }
+    }
+}
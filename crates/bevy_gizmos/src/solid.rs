@@ -0,0 +1,507 @@
+//! Additional [`GizmoBuffer`] Functions -- Solid shapes
+//!
+//! Includes the implementation of [`GizmoBuffer::solid_triangle`], [`GizmoBuffer::solid_cube`],
+//! [`GizmoBuffer::solid_sphere`], [`GizmoBuffer::solid_capsule`], [`GizmoBuffer::solid_cone`]
+//! and [`GizmoBuffer::solid_arrow`], and assorted support items.
+//!
+//! Solid shapes are filled with flat-shaded triangles, lit from the camera. They're drawn in 3D
+//! only, and culled from behind, so transparent shapes only show their front faces. Like lines,
+//! they're hidden behind other geometry according to the
+//! [`depth_bias`](crate::config::GizmoConfig::depth_bias) of their group, or the
+//! [`depth_mode`](GizmoBuffer::depth_mode) they're drawn with.
+
+use core::f32::consts::{FRAC_PI_2, TAU};
+
+use bevy_color::{Color, LinearRgba};
+use bevy_math::{ops, Dir3, Isometry3d, Quat, Vec2, Vec3};
+use bevy_transform::TransformPoint;
+
+use crate::{circles::DEFAULT_CIRCLE_RESOLUTION, gizmos::GizmoBuffer, prelude::GizmoConfigGroup};
+
+impl<Config, Clear> GizmoBuffer<Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Draw a filled triangle in 3D with the vertices `a`, `b` and `c`.
+    ///
+    /// The triangle is only visible from the side its vertices appear counterclockwise from.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::GREEN;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.solid_triangle(Vec3::ZERO, Vec3::X, Vec3::Y, GREEN);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn solid_triangle(&mut self, a: Vec3, b: Vec3, c: Vec3, color: impl Into<Color>) {
+        if !self.enabled {
+            return;
+        }
+        self.triangle_positions.extend([a, b, c]);
+        self.add_triangle_color(color, 3);
+    }
+
+    /// Draw a filled cube in 3D, with the given `transform` applied to a unit cube centered at
+    /// the origin.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_transform::prelude::*;
+    /// # use bevy_color::{palettes::basic::GREEN, Alpha};
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.solid_cube(Transform::IDENTITY, GREEN.with_alpha(0.3));
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn solid_cube(&mut self, transform: impl TransformPoint, color: impl Into<Color>) {
+        if !self.enabled {
+            return;
+        }
+        for axis in 0..3 {
+            for sign in [-1., 1.] {
+                // `u` and `v` follow `axis` in cyclic order, so `u × v` points along it.
+                let corner = |u: f32, v: f32| {
+                    let mut point = Vec3::ZERO;
+                    point[axis] = sign;
+                    point[(axis + 1) % 3] = u;
+                    point[(axis + 2) % 3] = v;
+                    transform.transform_point(point * 0.5)
+                };
+                let [a, b, c, d] = [
+                    corner(-1., -1.),
+                    corner(1., -1.),
+                    corner(1., 1.),
+                    corner(-1., 1.),
+                ];
+                if sign > 0. {
+                    self.triangle_positions.extend([a, b, c, a, c, d]);
+                } else {
+                    self.triangle_positions.extend([a, c, b, a, d, c]);
+                }
+            }
+        }
+        self.add_triangle_color(color, 36);
+    }
+
+    /// Draw a filled sphere in 3D with the given `isometry` applied.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::{palettes::basic::RED, Alpha};
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.solid_sphere(Isometry3d::IDENTITY, 1., RED.with_alpha(0.3));
+    ///
+    ///     // The sphere has 32 segments around its axis by default.
+    ///     // You may want to increase this for larger spheres.
+    ///     gizmos
+    ///         .solid_sphere(Isometry3d::IDENTITY, 5., RED)
+    ///         .resolution(64);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn solid_sphere(
+        &mut self,
+        isometry: impl Into<Isometry3d>,
+        radius: f32,
+        color: impl Into<Color>,
+    ) -> SolidSphereBuilder<'_, Config, Clear> {
+        SolidSphereBuilder {
+            gizmos: self,
+            isometry: isometry.into(),
+            radius,
+            color: color.into(),
+            resolution: DEFAULT_CIRCLE_RESOLUTION,
+        }
+    }
+
+    /// Draw a filled capsule in 3D with the given `isometry` applied.
+    ///
+    /// If `isometry == Isometry3d::IDENTITY` then the capsule is centered at the origin, and its
+    /// cylinder of height `2 * half_length` is aligned with the Y axis.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::{palettes::basic::BLUE, Alpha};
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.solid_capsule(Isometry3d::IDENTITY, 0.5, 1., BLUE.with_alpha(0.3));
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn solid_capsule(
+        &mut self,
+        isometry: impl Into<Isometry3d>,
+        radius: f32,
+        half_length: f32,
+        color: impl Into<Color>,
+    ) -> SolidCapsuleBuilder<'_, Config, Clear> {
+        SolidCapsuleBuilder {
+            gizmos: self,
+            isometry: isometry.into(),
+            radius,
+            half_length,
+            color: color.into(),
+            resolution: DEFAULT_CIRCLE_RESOLUTION,
+        }
+    }
+
+    /// Draw a filled cone in 3D with the given `isometry` applied.
+    ///
+    /// If `isometry == Isometry3d::IDENTITY` then the cone is centered at the origin, with its
+    /// base at `-height / 2` and its tip at `height / 2` on the Y axis.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::{palettes::basic::YELLOW, Alpha};
+    /// fn system(mut gizmos: Gizmos) {
+    ///     // A cone pointing along the X axis.
+    ///     gizmos.solid_cone(
+    ///         Isometry3d::new(Vec3::X * 2., Quat::from_rotation_z(-FRAC_PI_2)),
+    ///         1.,
+    ///         4.,
+    ///         YELLOW.with_alpha(0.2),
+    ///     );
+    /// }
+    /// # use core::f32::consts::FRAC_PI_2;
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn solid_cone(
+        &mut self,
+        isometry: impl Into<Isometry3d>,
+        radius: f32,
+        height: f32,
+        color: impl Into<Color>,
+    ) -> SolidConeBuilder<'_, Config, Clear> {
+        SolidConeBuilder {
+            gizmos: self,
+            isometry: isometry.into(),
+            radius,
+            height,
+            color: color.into(),
+            resolution: DEFAULT_CIRCLE_RESOLUTION,
+        }
+    }
+
+    /// Draw a filled arrow in 3D, from `start` to `end`.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::GREEN;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.solid_arrow(Vec3::ZERO, Vec3::ONE, GREEN);
+    ///
+    ///     // The tip is a tenth of the length of the arrow by default.
+    ///     gizmos
+    ///         .solid_arrow(Vec3::ZERO, Vec3::Y * 2., GREEN)
+    ///         .with_tip_length(0.5)
+    ///         .with_radius(0.05);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn solid_arrow(
+        &mut self,
+        start: Vec3,
+        end: Vec3,
+        color: impl Into<Color>,
+    ) -> SolidArrowBuilder<'_, Config, Clear> {
+        let tip_length = (end - start).length() / 10.;
+        SolidArrowBuilder {
+            gizmos: self,
+            start,
+            end,
+            color: color.into(),
+            tip_length,
+            radius: tip_length / 8.,
+            resolution: DEFAULT_SOLID_ARROW_RESOLUTION,
+        }
+    }
+
+    #[inline]
+    fn add_triangle_color(&mut self, color: impl Into<Color>, count: usize) {
+        let linear_color = LinearRgba::from(color.into());
+        self.triangle_colors
+            .extend(core::iter::repeat_n(linear_color, count));
+    }
+
+    /// Fills the surface swept by revolving `profile` around the Y axis, with `isometry` applied.
+    ///
+    /// The profile is given as `(radius, height)` points from the bottom of the surface to the
+    /// top, and closes the surface where it meets the axis.
+    fn solid_of_revolution(
+        &mut self,
+        isometry: Isometry3d,
+        profile: &[Vec2],
+        resolution: u32,
+        color: Color,
+    ) {
+        let resolution = resolution.max(3);
+        let point = |profile_point: Vec2, index: u32| {
+            let angle = TAU * index as f32 / resolution as f32;
+            let (sin, cos) = ops::sin_cos(angle);
+            isometry
+                * Vec3::new(
+                    profile_point.x * cos,
+                    profile_point.y,
+                    profile_point.x * sin,
+                )
+        };
+
+        let vertex_count = self.triangle_positions.len();
+        for edge in profile.windows(2) {
+            let (bottom, top) = (edge[0], edge[1]);
+            for index in 0..resolution {
+                let a = point(bottom, index);
+                let b = point(bottom, index + 1);
+                let c = point(top, index + 1);
+                let d = point(top, index);
+                // Skip the triangles that collapse where the profile meets the axis.
+                if bottom.x > 0. {
+                    self.triangle_positions.extend([a, c, b]);
+                }
+                if top.x > 0. {
+                    self.triangle_positions.extend([a, d, c]);
+                }
+            }
+        }
+
+        let vertex_count = self.triangle_positions.len() - vertex_count;
+        self.add_triangle_color(color, vertex_count);
+    }
+}
+
+/// The default number of segments around the axis of a [`GizmoBuffer::solid_arrow`].
+const DEFAULT_SOLID_ARROW_RESOLUTION: u32 = 12;
+
+/// Returns the profile of an arc of a circle of `radius` centered at `height` on the axis, from
+/// `start` to `end` radians above the horizontal, in `segments` segments.
+fn arc_profile(
+    radius: f32,
+    height: f32,
+    start: f32,
+    end: f32,
+    segments: u32,
+) -> impl Iterator<Item = Vec2> {
+    (0..=segments).map(move |index| {
+        let angle = start + (end - start) * index as f32 / segments as f32;
+        let (sin, cos) = ops::sin_cos(angle);
+        // The points on the axis must not cross it due to rounding.
+        Vec2::new((radius * cos).max(0.), height + radius * sin)
+    })
+}
+
+/// A builder returned by [`GizmoBuffer::solid_sphere`].
+pub struct SolidSphereBuilder<'a, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    gizmos: &'a mut GizmoBuffer<Config, Clear>,
+    isometry: Isometry3d,
+    radius: f32,
+    color: Color,
+    resolution: u32,
+}
+
+impl<Config, Clear> SolidSphereBuilder<'_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Set the number of segments around the axis of the sphere, which also has half as many
+    /// rings.
+    pub fn resolution(mut self, resolution: u32) -> Self {
+        self.resolution = resolution;
+        self
+    }
+}
+
+impl<Config, Clear> Drop for SolidSphereBuilder<'_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+        let rings = (self.resolution / 2).max(2);
+        let profile: Vec<_> = arc_profile(self.radius, 0., -FRAC_PI_2, FRAC_PI_2, rings).collect();
+        self.gizmos
+            .solid_of_revolution(self.isometry, &profile, self.resolution, self.color);
+    }
+}
+
+/// A builder returned by [`GizmoBuffer::solid_capsule`].
+pub struct SolidCapsuleBuilder<'a, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    gizmos: &'a mut GizmoBuffer<Config, Clear>,
+    isometry: Isometry3d,
+    radius: f32,
+    half_length: f32,
+    color: Color,
+    resolution: u32,
+}
+
+impl<Config, Clear> SolidCapsuleBuilder<'_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Set the number of segments around the axis of the capsule, which also has a quarter as
+    /// many rings in each hemisphere.
+    pub fn resolution(mut self, resolution: u32) -> Self {
+        self.resolution = resolution;
+        self
+    }
+}
+
+impl<Config, Clear> Drop for SolidCapsuleBuilder<'_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+        let rings = (self.resolution / 4).max(1);
+        let bottom = arc_profile(self.radius, -self.half_length, -FRAC_PI_2, 0., rings);
+        let top = arc_profile(self.radius, self.half_length, 0., FRAC_PI_2, rings);
+        let profile: Vec<_> = bottom.chain(top).collect();
+        self.gizmos
+            .solid_of_revolution(self.isometry, &profile, self.resolution, self.color);
+    }
+}
+
+/// A builder returned by [`GizmoBuffer::solid_cone`].
+pub struct SolidConeBuilder<'a, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    gizmos: &'a mut GizmoBuffer<Config, Clear>,
+    isometry: Isometry3d,
+    radius: f32,
+    height: f32,
+    color: Color,
+    resolution: u32,
+}
+
+impl<Config, Clear> SolidConeBuilder<'_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Set the number of segments around the axis of the cone.
+    pub fn resolution(mut self, resolution: u32) -> Self {
+        self.resolution = resolution;
+        self
+    }
+}
+
+impl<Config, Clear> Drop for SolidConeBuilder<'_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+        let half_height = self.height / 2.;
+        let profile = [
+            Vec2::new(0., -half_height),
+            Vec2::new(self.radius, -half_height),
+            Vec2::new(0., half_height),
+        ];
+        self.gizmos
+            .solid_of_revolution(self.isometry, &profile, self.resolution, self.color);
+    }
+}
+
+/// A builder returned by [`GizmoBuffer::solid_arrow`].
+pub struct SolidArrowBuilder<'a, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    gizmos: &'a mut GizmoBuffer<Config, Clear>,
+    start: Vec3,
+    end: Vec3,
+    color: Color,
+    tip_length: f32,
+    radius: f32,
+    resolution: u32,
+}
+
+impl<Config, Clear> SolidArrowBuilder<'_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Change the length of the tip to be `length`.
+    /// The default tip length is [length of the arrow]/10.
+    pub fn with_tip_length(mut self, length: f32) -> Self {
+        self.tip_length = length;
+        self
+    }
+
+    /// Change the radius of the shaft to be `radius`. The tip is four times as wide.
+    /// The default radius is [length of the arrow]/80.
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Set the number of segments around the axis of the arrow.
+    pub fn resolution(mut self, resolution: u32) -> Self {
+        self.resolution = resolution;
+        self
+    }
+}
+
+impl<Config, Clear> Drop for SolidArrowBuilder<'_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+        let Ok((direction, length)) = Dir3::new_and_length(self.end - self.start) else {
+            return;
+        };
+        let tip_start = length - self.tip_length.clamp(0., length);
+        let profile = [
+            Vec2::ZERO,
+            Vec2::new(self.radius, 0.),
+            Vec2::new(self.radius, tip_start),
+            Vec2::new(self.radius * 4., tip_start),
+            Vec2::new(0., length),
+        ];
+        let isometry = Isometry3d::new(self.start, Quat::from_rotation_arc(Vec3::Y, *direction));
+        self.gizmos
+            .solid_of_revolution(isometry, &profile, self.resolution, self.color);
+    }
+}
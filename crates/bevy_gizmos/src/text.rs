@@ -0,0 +1,278 @@
+//! Additional [`GizmoBuffer`] Functions -- Text
+//!
+//! Includes the implementation of [`GizmoBuffer::text`],
+//! and assorted support items.
+
+use bevy_color::{Color, LinearRgba};
+use bevy_math::Vec3;
+
+use crate::{depth::GizmoDepthMode, gizmos::GizmoBuffer, prelude::GizmoConfigGroup};
+
+/// The default height of the capital letters of text, in world units.
+const DEFAULT_TEXT_SIZE: f32 = 0.25;
+
+impl<Config, Clear> GizmoBuffer<Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Draw a text label in 3D, centered on `position` and facing the camera.
+    ///
+    /// The text is drawn with lines, in a built-in stroke font covering printable ASCII.
+    /// Other characters are drawn as `?`, and `\n` starts a new line.
+    ///
+    /// The labels face the active camera with the highest [`order`](bevy_camera::Camera::order),
+    /// as it's placed when the gizmos are drawn. They're only drawn by [`Gizmos`](crate::gizmos::Gizmos),
+    /// not by retained [`GizmoAsset`](crate::GizmoAsset)s.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::{WHITE, YELLOW};
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.text(Vec3::Y, "label", WHITE);
+    ///
+    ///     // The capital letters are 0.25 units high by default.
+    ///     gizmos
+    ///         .text(Vec3::ZERO, format!("speed: {:.1}", 4.2), YELLOW)
+    ///         .size(0.5);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn text(
+        &mut self,
+        position: Vec3,
+        text: impl Into<String>,
+        color: impl Into<Color>,
+    ) -> TextBuilder<'_, Config, Clear> {
+        TextBuilder {
+            gizmos: self,
+            position,
+            text: text.into(),
+            color: color.into(),
+            size: DEFAULT_TEXT_SIZE,
+        }
+    }
+}
+
+/// A builder returned by [`GizmoBuffer::text`].
+pub struct TextBuilder<'a, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    gizmos: &'a mut GizmoBuffer<Config, Clear>,
+    position: Vec3,
+    text: String,
+    color: Color,
+    size: f32,
+}
+
+impl<Config, Clear> TextBuilder<'_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Set the height of the capital letters of the text, in world units.
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+}
+
+impl<Config, Clear> Drop for TextBuilder<'_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    fn drop(&mut self) {
+        if !self.gizmos.enabled || self.text.is_empty() {
+            return;
+        }
+        self.gizmos.texts.push(GizmoText {
+            position: self.position,
+            text: core::mem::take(&mut self.text),
+            color: self.color.into(),
+            size: self.size,
+            depth_mode: None,
+        });
+    }
+}
+
+/// A text label, laid out once the camera it faces is known.
+#[derive(Debug, Clone)]
+pub(crate) struct GizmoText {
+    position: Vec3,
+    text: String,
+    color: LinearRgba,
+    size: f32,
+    /// The depth mode the text is drawn with, if it's drawn with [`GizmoBuffer::depth_mode`].
+    pub(crate) depth_mode: Option<GizmoDepthMode>,
+}
+
+impl GizmoText {
+    /// Appends the line segments drawing the text, in the plane spanned by `right` and `up`, to
+    /// `positions` and `colors`.
+    pub(crate) fn draw(
+        &self,
+        right: Vec3,
+        up: Vec3,
+        positions: &mut Vec<Vec3>,
+        colors: &mut Vec<LinearRgba>,
+    ) {
+        let unit = self.size / CAP_HEIGHT;
+        let line_count = self.text.lines().count().max(1) as f32;
+        // The text is centered between the top of the first line and the baseline of the last.
+        let height = CAP_HEIGHT + (line_count - 1.) * LINE_HEIGHT;
+        let segment_count = positions.len();
+
+        for (line_index, line) in self.text.lines().enumerate() {
+            let baseline = height / 2. - CAP_HEIGHT - line_index as f32 * LINE_HEIGHT;
+            let width = line.chars().count() as f32 * ADVANCE - (ADVANCE - GLYPH_WIDTH);
+            let mut left = -width / 2.;
+            for character in line.chars() {
+                for stroke in glyph(character).split(' ') {
+                    let points = stroke.as_bytes().chunks_exact(2).map(|point| {
+                        let x = left + f32::from(point[0] - b'0');
+                        let y = baseline + f32::from(point[1] - b'0') - BASELINE;
+                        self.position + (right * x + up * y) * unit
+                    });
+                    let mut previous = None;
+                    for point in points {
+                        if let Some(previous) = previous {
+                            positions.extend([previous, point]);
+                        }
+                        previous = Some(point);
+                    }
+                }
+                left += ADVANCE;
+            }
+        }
+
+        let vertex_count = positions.len() - segment_count;
+        colors.extend(core::iter::repeat_n(self.color, vertex_count));
+    }
+}
+
+/// The width of the glyphs, in font units.
+const GLYPH_WIDTH: f32 = 4.;
+/// The horizontal distance between the glyphs of a line, in font units.
+const ADVANCE: f32 = 6.;
+/// The height of the baseline above the bottom of the descenders, in font units.
+const BASELINE: f32 = 2.;
+/// The height of the capital letters, in font units.
+const CAP_HEIGHT: f32 = 6.;
+/// The vertical distance between the baselines of the lines, in font units.
+const LINE_HEIGHT: f32 = 10.;
+
+/// Returns the strokes of the glyph of `character`.
+///
+/// The strokes are separated by spaces, and each is a polyline of points written as two digits,
+/// `x` from `0` to `4` and `y` from `0` to `8`, with the baseline at `y = 2`.
+fn glyph(character: char) -> &'static str {
+    let index = (character as usize).wrapping_sub(' ' as usize);
+    GLYPHS
+        .get(index)
+        .unwrap_or(&GLYPHS['?' as usize - ' ' as usize])
+}
+
+/// The glyphs of the printable ASCII characters, starting from the space.
+const GLYPHS: [&str; 95] = [
+    "",                           // ' '
+    "2824 2223",                  // '!'
+    "1817 3837",                  // '"'
+    "1812 3832 0646 0444",        // '#'
+    "470705454303 2822",          // '$'
+    "0248 0818170708 3343423233", // '%'
+    "4217283704022244",           // '&'
+    "2827",                       // '\''
+    "38272332",                   // '('
+    "18272312",                   // ')'
+    "2723 0644 0446",             // '*'
+    "2723 0545",                  // '+'
+    "232211",                     // ','
+    "0545",                       // '-'
+    "2223",                       // '.'
+    "0248",                       // '/'
+    "0848420208 0248",            // '0'
+    "172822 1232",                // '1'
+    "084845050242",               // '2'
+    "08484202 1545",              // '3'
+    "32380444",                   // '4'
+    "480805454202",               // '5'
+    "480802424505",               // '6'
+    "084812",                     // '7'
+    "0848420208 0545",            // '8'
+    "024248080545",               // '9'
+    "2526 2223",                  // ':'
+    "2526 232211",                // ';'
+    "470543",                     // '<'
+    "0646 0444",                  // '='
+    "074503",                     // '>'
+    "07183847462524 2223",        // '?'
+    "34141636334348080242",       // '@'
+    "0206284642 0545",            // 'A'
+    "02083847463505 3544433202",  // 'B'
+    "48080242",                   // 'C'
+    "02082846442202",             // 'D'
+    "48080242 0535",              // 'E'
+    "480802 0535",                // 'F'
+    "480802424525",               // 'G'
+    "0208 4248 0545",             // 'H'
+    "2822 1838 1232",             // 'I'
+    "48420204",                   // 'J'
+    "0208 4804 1542",             // 'K'
+    "080242",                     // 'L'
+    "0208254842",                 // 'M'
+    "02084248",                   // 'N'
+    "0848420208",                 // 'O'
+    "0208484505",                 // 'P'
+    "0848420208 2441",            // 'Q'
+    "0208484505 2542",            // 'R'
+    "480805454202",               // 'S'
+    "0848 2822",                  // 'T'
+    "08024248",                   // 'U'
+    "082248",                     // 'V'
+    "0812253248",                 // 'W'
+    "0842 0248",                  // 'X'
+    "082548 2522",                // 'Y'
+    "08480242",                   // 'Z'
+    "38282232",                   // '['
+    "0842",                       // '\\'
+    "18282212",                   // ']'
+    "162836",                     // '^'
+    "0141",                       // '_'
+    "1827",                       // '`'
+    "064642020444",               // 'a'
+    "0802424606",                 // 'b'
+    "46060242",                   // 'c'
+    "4842020646",                 // 'd'
+    "044446060242",               // 'e'
+    "482822 1636",                // 'f'
+    "430306464000",               // 'g'
+    "0802 064642",                // 'h'
+    "2622 2728",                  // 'i'
+    "262000 2728",                // 'j'
+    "0802 4604 2542",             // 'k'
+    "282232",                     // 'l'
+    "02064642 2622",              // 'm'
+    "02064642",                   // 'n'
+    "0646420206",                 // 'o'
+    "0006464202",                 // 'p'
+    "4046060242",                 // 'q'
+    "0206 051646",                // 'r'
+    "460604444202",               // 's'
+    "282242 0646",                // 't'
+    "06024246",                   // 'u'
+    "062246",                     // 'v'
+    "0612243246",                 // 'w'
+    "0642 0246",                  // 'x'
+    "060343 464000",              // 'y'
+    "06460242",                   // 'z'
+    "38282615242232",             // '{'
+    "2821",                       // '|'
+    "18282635242212",             // '}'
+    "05163445",                   // '~'
+];
@@ -0,0 +1,548 @@
+//! A module adding interactive gizmos to translate, rotate and scale entities, dragged with
+//! [`bevy_picking`].
+//!
+//! Add the [`TransformGizmo`] component to an entity to show the handles of its gizmo. Dragging a
+//! handle with the primary pointer button changes the [`Transform`] of the entity, and triggers a
+//! [`TransformGizmoDrag`] event with the change.
+//!
+//! The handles are hit tested by a picking backend, so a picking input plugin, such as the
+//! [`DefaultPickingPlugins`](bevy_picking::DefaultPickingPlugins), is needed to drag them.
+
+use bevy_app::{Plugin, PostUpdate, PreUpdate};
+use bevy_camera::Camera;
+use bevy_color::{
+    palettes::basic::{BLUE, LIME, RED, YELLOW},
+    Color,
+};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::EntityEvent,
+    hierarchy::ChildOf,
+    message::MessageWriter,
+    observer::On,
+    query::Without,
+    reflect::ReflectComponent,
+    schedule::IntoScheduleConfigs,
+    system::{Commands, Query, Res},
+};
+use bevy_math::{ops, primitives::InfinitePlane3d, Dir3, Isometry3d, Quat, Ray3d, Vec3};
+use bevy_picking::{
+    backend::{
+        ray::{RayId, RayMap},
+        HitData, PointerHits,
+    },
+    events::{Drag, DragEnd, DragStart, Pointer},
+    pointer::{PointerButton, PointerId},
+    PickingSystems,
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_transform::{
+    components::{GlobalTransform, Transform},
+    TransformSystems,
+};
+
+use crate::{
+    config::{GizmoConfig, GizmoConfigGroup, GizmoConfigStore},
+    gizmos::Gizmos,
+    screen::ScreenGizmoCamera,
+    AppGizmoBuilder,
+};
+
+/// A [`Plugin`] that provides [`TransformGizmo`]s, dragged with [`bevy_picking`].
+pub struct TransformGizmoPlugin;
+
+impl Plugin for TransformGizmoPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.insert_gizmo_config(
+            TransformGizmoConfigGroup::default(),
+            GizmoConfig {
+                // The handles are drawn on top of the entities they manipulate.
+                depth_bias: -1.0,
+                ..Default::default()
+            },
+        )
+        .add_systems(PreUpdate, update_hits.in_set(PickingSystems::Backend))
+        .add_systems(
+            PostUpdate,
+            draw_transform_gizmos.after(TransformSystems::Propagate),
+        )
+        .add_observer(start_drag)
+        .add_observer(drag)
+        .add_observer(end_drag);
+    }
+}
+
+/// The [`GizmoConfigGroup`] used to configure [`TransformGizmo`]s.
+#[derive(Clone, Reflect, GizmoConfigGroup)]
+#[reflect(Clone, Default)]
+pub struct TransformGizmoConfigGroup {
+    /// The length of the handles, in world units.
+    ///
+    /// Defaults to `1.0`.
+    pub size: f32,
+    /// How close to a handle the pointer hovers it, as a fraction of [`Self::size`].
+    ///
+    /// Defaults to `0.08`.
+    pub hit_radius: f32,
+    /// The colors of the handles of the X, Y and Z axes.
+    ///
+    /// Defaults to red, green and blue.
+    pub axis_colors: [Color; 3],
+    /// The color of the hovered or dragged handle.
+    ///
+    /// Defaults to yellow.
+    pub active_color: Color,
+}
+
+impl Default for TransformGizmoConfigGroup {
+    fn default() -> Self {
+        Self {
+            size: 1.0,
+            hit_radius: 0.08,
+            axis_colors: [RED.into(), LIME.into(), BLUE.into()],
+            active_color: YELLOW.into(),
+        }
+    }
+}
+
+/// What a [`TransformGizmo`] changes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Clone, Default, Debug, PartialEq)]
+pub enum TransformGizmoMode {
+    /// The handles move the entity along an axis.
+    #[default]
+    Translate,
+    /// The handles rotate the entity around an axis.
+    Rotate,
+    /// The handles scale the entity along one of its local axes.
+    Scale,
+}
+
+/// The axes the handles of a [`TransformGizmo`] are aligned with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Clone, Default, Debug, PartialEq)]
+pub enum TransformGizmoSpace {
+    /// The axes of the world.
+    #[default]
+    World,
+    /// The axes of the entity, following its rotation.
+    ///
+    /// The handles of [`TransformGizmoMode::Scale`] always use the axes of the entity.
+    Local,
+}
+
+/// An axis of a [`TransformGizmo`], which has a handle for each axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum TransformGizmoAxis {
+    /// The X axis.
+    X,
+    /// The Y axis.
+    Y,
+    /// The Z axis.
+    Z,
+}
+
+impl TransformGizmoAxis {
+    /// All the axes.
+    pub const ALL: [Self; 3] = [Self::X, Self::Y, Self::Z];
+
+    /// Returns the unit vector of the axis.
+    pub fn unit(self) -> Vec3 {
+        match self {
+            Self::X => Vec3::X,
+            Self::Y => Vec3::Y,
+            Self::Z => Vec3::Z,
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            Self::X => 0,
+            Self::Y => 1,
+            Self::Z => 2,
+        }
+    }
+}
+
+/// Add this [`Component`] to an entity to show a gizmo translating, rotating or scaling it when
+/// its handles are dragged.
+///
+/// The entity must have a [`Transform`] and a [`GlobalTransform`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_gizmos::transform_gizmo::{TransformGizmo, TransformGizmoMode, TransformGizmoSpace};
+/// # use bevy_transform::components::Transform;
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         Transform::default(),
+///         TransformGizmo::new(TransformGizmoMode::Rotate).with_space(TransformGizmoSpace::Local),
+///     ));
+/// }
+/// # bevy_ecs::system::assert_is_system(setup);
+/// ```
+#[derive(Component, Reflect, Clone, Debug, Default)]
+#[reflect(Component, Default, Debug)]
+pub struct TransformGizmo {
+    /// What the handles change.
+    pub mode: TransformGizmoMode,
+    /// The axes the handles are aligned with.
+    pub space: TransformGizmoSpace,
+    #[reflect(ignore, clone)]
+    hovered: Option<TransformGizmoAxis>,
+    #[reflect(ignore, clone)]
+    dragged: Option<GizmoDrag>,
+}
+
+impl TransformGizmo {
+    /// Creates a gizmo changing its entity in the given `mode`.
+    pub fn new(mode: TransformGizmoMode) -> Self {
+        Self {
+            mode,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the gizmo with its handles aligned with the axes of `space`.
+    pub fn with_space(mut self, space: TransformGizmoSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// Returns the axis of the handle hovered by a pointer, if any.
+    pub fn hovered_axis(&self) -> Option<TransformGizmoAxis> {
+        self.hovered
+    }
+
+    /// Returns the axis of the dragged handle, if any.
+    pub fn dragged_axis(&self) -> Option<TransformGizmoAxis> {
+        self.dragged.as_ref().map(|drag| drag.axis)
+    }
+
+    /// Returns the world space direction of `axis`, for an entity at `transform`.
+    fn direction(&self, transform: &GlobalTransform, axis: TransformGizmoAxis) -> Vec3 {
+        if self.space == TransformGizmoSpace::Local || self.mode == TransformGizmoMode::Scale {
+            transform.rotation() * axis.unit()
+        } else {
+            axis.unit()
+        }
+    }
+}
+
+/// The state of a dragged handle of a [`TransformGizmo`].
+#[derive(Clone, Debug)]
+struct GizmoDrag {
+    axis: TransformGizmoAxis,
+    pointer: PointerId,
+    camera: Entity,
+    origin: Vec3,
+    direction: Vec3,
+    /// The point under the pointer on the axis, or on the plane of rotation, when last dragged.
+    point: Vec3,
+}
+
+/// A change made by dragging a handle of a [`TransformGizmo`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum TransformGizmoDelta {
+    /// A translation, in world space.
+    Translation(Vec3),
+    /// A rotation around the origin of the entity, in world space.
+    Rotation(Quat),
+    /// A factor of the scale of the entity, along each of its local axes.
+    Scale(Vec3),
+}
+
+/// An [`EntityEvent`] triggered when a handle of the [`TransformGizmo`] of an entity is dragged,
+/// after its [`Transform`] is changed.
+///
+/// The delta is the change since the previous drag event, so that undo stacks or network
+/// replication can record the changes of the entity.
+#[derive(EntityEvent, Clone, Debug)]
+pub struct TransformGizmoDrag {
+    /// The entity of the gizmo.
+    pub entity: Entity,
+    /// The axis of the dragged handle.
+    pub axis: TransformGizmoAxis,
+    /// The change made to the entity.
+    pub delta: TransformGizmoDelta,
+}
+
+/// Hit tests the handles of the [`TransformGizmo`]s, and sends [`PointerHits`] for them.
+fn update_hits(
+    ray_map: Res<RayMap>,
+    // Screen gizmos are drawn by a camera on top of all others, which doesn't see these gizmos.
+    cameras: Query<&Camera, Without<ScreenGizmoCamera>>,
+    config_store: Res<GizmoConfigStore>,
+    mut gizmos: Query<(Entity, &mut TransformGizmo, &GlobalTransform)>,
+    mut pointer_hits_writer: MessageWriter<PointerHits>,
+) {
+    let (gizmo_config, config) = config_store.config::<TransformGizmoConfigGroup>();
+    let mut hovered: Vec<(Entity, TransformGizmoAxis, f32)> = Vec::new();
+
+    if gizmo_config.enabled {
+        for (&ray_id, &ray) in ray_map.iter() {
+            let Ok(camera) = cameras.get(ray_id.camera) else {
+                continue;
+            };
+            let mut picks = Vec::new();
+            for (entity, gizmo, transform) in &gizmos {
+                let Some((axis, depth, position)) = hit_handles(gizmo, transform, ray, config)
+                else {
+                    continue;
+                };
+                picks.push((
+                    entity,
+                    HitData::new(ray_id.camera, depth, Some(position), None),
+                ));
+                match hovered.iter_mut().find(|(hovered, ..)| *hovered == entity) {
+                    Some(hovered) if hovered.2 <= depth => {}
+                    Some(hovered) => *hovered = (entity, axis, depth),
+                    None => hovered.push((entity, axis, depth)),
+                }
+            }
+            if !picks.is_empty() {
+                // The handles are drawn on top of the other entities seen by the camera.
+                let order = camera.order as f32 + 0.25;
+                pointer_hits_writer.write(PointerHits::new(ray_id.pointer, picks, order));
+            }
+        }
+    }
+
+    for (entity, mut gizmo, _) in &mut gizmos {
+        let axis = hovered
+            .iter()
+            .find(|(hovered, ..)| *hovered == entity)
+            .map(|&(_, axis, _)| axis);
+        if gizmo.hovered != axis {
+            gizmo.hovered = axis;
+        }
+    }
+}
+
+/// Returns the axis of the handle of `gizmo` closest to `ray`, with the distance along the ray and
+/// the point of the hit, if the ray hits a handle.
+fn hit_handles(
+    gizmo: &TransformGizmo,
+    transform: &GlobalTransform,
+    ray: Ray3d,
+    config: &TransformGizmoConfigGroup,
+) -> Option<(TransformGizmoAxis, f32, Vec3)> {
+    let origin = transform.translation();
+    let hit_radius = config.size * config.hit_radius;
+    TransformGizmoAxis::ALL
+        .into_iter()
+        .filter_map(|axis| {
+            let direction = gizmo.direction(transform, axis);
+            let point = match gizmo.mode {
+                TransformGizmoMode::Translate | TransformGizmoMode::Scale => {
+                    let along = closest_on_axis(ray, origin, direction)?;
+                    origin + direction * along.clamp(0.0, config.size)
+                }
+                TransformGizmoMode::Rotate => {
+                    let point = intersect_plane(ray, origin, direction)?;
+                    origin + (point - origin).normalize_or_zero() * config.size
+                }
+            };
+            let depth = (point - ray.origin).dot(*ray.direction);
+            let distance = point.distance(ray.get_point(depth));
+            (depth > 0.0 && distance <= hit_radius).then_some((axis, depth, point))
+        })
+        .min_by(|(_, a, _), (_, b, _)| a.total_cmp(b))
+}
+
+/// Returns the distance along the axis at `origin` in `direction` of its closest point to `ray`,
+/// or `None` if they're parallel.
+fn closest_on_axis(ray: Ray3d, origin: Vec3, direction: Vec3) -> Option<f32> {
+    let offset = origin - ray.origin;
+    let cos = direction.dot(*ray.direction);
+    let denominator = 1.0 - cos * cos;
+    if denominator < 1e-6 {
+        return None;
+    }
+    Some((cos * offset.dot(*ray.direction) - offset.dot(direction)) / denominator)
+}
+
+/// Returns the intersection of `ray` with the plane through `origin` with the given `normal`.
+fn intersect_plane(ray: Ray3d, origin: Vec3, normal: Vec3) -> Option<Vec3> {
+    let normal = Dir3::new(normal).ok()?;
+    let distance = ray.intersect_plane(origin, InfinitePlane3d::new(normal))?;
+    Some(ray.get_point(distance))
+}
+
+/// Returns the point under `ray` dragging a handle of `mode`: on the axis when translating or
+/// scaling, or on the plane of rotation when rotating.
+fn drag_point(mode: TransformGizmoMode, ray: Ray3d, origin: Vec3, direction: Vec3) -> Option<Vec3> {
+    match mode {
+        TransformGizmoMode::Translate | TransformGizmoMode::Scale => {
+            Some(origin + direction * closest_on_axis(ray, origin, direction)?)
+        }
+        TransformGizmoMode::Rotate => intersect_plane(ray, origin, direction),
+    }
+}
+
+fn start_drag(
+    mut drag_start: On<Pointer<DragStart>>,
+    ray_map: Res<RayMap>,
+    mut gizmos: Query<(&mut TransformGizmo, &GlobalTransform)>,
+) {
+    let Ok((mut gizmo, transform)) = gizmos.get_mut(drag_start.entity) else {
+        return;
+    };
+    let Some(axis) = gizmo.hovered else {
+        return;
+    };
+    if drag_start.button != PointerButton::Primary || gizmo.dragged.is_some() {
+        return;
+    }
+    let camera = drag_start.hit.camera;
+    let Some(&ray) = ray_map.map.get(&RayId::new(camera, drag_start.pointer_id)) else {
+        return;
+    };
+
+    let origin = transform.translation();
+    let direction = gizmo.direction(transform, axis);
+    let Some(point) = drag_point(gizmo.mode, ray, origin, direction) else {
+        return;
+    };
+    gizmo.dragged = Some(GizmoDrag {
+        axis,
+        pointer: drag_start.pointer_id,
+        camera,
+        origin,
+        direction,
+        point,
+    });
+    drag_start.propagate(false);
+}
+
+fn drag(
+    mut drag: On<Pointer<Drag>>,
+    ray_map: Res<RayMap>,
+    mut gizmos: Query<(&mut TransformGizmo, &mut Transform, Option<&ChildOf>)>,
+    parents: Query<&GlobalTransform>,
+    mut commands: Commands,
+) {
+    let Ok((mut gizmo, mut transform, child_of)) = gizmos.get_mut(drag.entity) else {
+        return;
+    };
+    let mode = gizmo.mode;
+    let Some(dragged) = gizmo
+        .dragged
+        .as_mut()
+        .filter(|dragged| dragged.pointer == drag.pointer_id)
+    else {
+        return;
+    };
+    drag.propagate(false);
+    let Some(&ray) = ray_map
+        .map
+        .get(&RayId::new(dragged.camera, drag.pointer_id))
+    else {
+        return;
+    };
+    let Some(point) = drag_point(mode, ray, dragged.origin, dragged.direction) else {
+        return;
+    };
+
+    // The translations and rotations are made in world space, and applied in the space of the
+    // parent of the entity.
+    let parent = child_of
+        .and_then(|child_of| parents.get(child_of.parent()).ok())
+        .copied()
+        .unwrap_or_default();
+    let delta = match mode {
+        TransformGizmoMode::Translate => {
+            let delta = point - dragged.point;
+            transform.translation += parent.affine().inverse().transform_vector3(delta);
+            TransformGizmoDelta::Translation(delta)
+        }
+        TransformGizmoMode::Rotate => {
+            let from = dragged.point - dragged.origin;
+            let to = point - dragged.origin;
+            let angle = ops::atan2(dragged.direction.dot(from.cross(to)), from.dot(to));
+            let delta = Quat::from_axis_angle(dragged.direction, angle);
+            let parent_rotation = parent.rotation();
+            transform.rotation =
+                (parent_rotation.inverse() * delta * parent_rotation * transform.rotation)
+                    .normalize();
+            TransformGizmoDelta::Rotation(delta)
+        }
+        TransformGizmoMode::Scale => {
+            let from = (dragged.point - dragged.origin).dot(dragged.direction);
+            let to = (point - dragged.origin).dot(dragged.direction);
+            if from.abs() < f32::EPSILON {
+                return;
+            }
+            let mut delta = Vec3::ONE;
+            delta[dragged.axis.index()] = to / from;
+            transform.scale *= delta;
+            TransformGizmoDelta::Scale(delta)
+        }
+    };
+    dragged.point = point;
+
+    commands.trigger(TransformGizmoDrag {
+        entity: drag.entity,
+        axis: dragged.axis,
+        delta,
+    });
+}
+
+fn end_drag(mut drag_end: On<Pointer<DragEnd>>, mut gizmos: Query<&mut TransformGizmo>) {
+    let Ok(mut gizmo) = gizmos.get_mut(drag_end.entity) else {
+        return;
+    };
+    if gizmo
+        .dragged
+        .as_ref()
+        .is_some_and(|dragged| dragged.pointer == drag_end.pointer_id)
+    {
+        gizmo.dragged = None;
+        drag_end.propagate(false);
+    }
+}
+
+fn draw_transform_gizmos(
+    query: Query<(&TransformGizmo, &GlobalTransform)>,
+    mut gizmos: Gizmos<TransformGizmoConfigGroup>,
+) {
+    let size = gizmos.config_ext.size;
+    let axis_colors = gizmos.config_ext.axis_colors;
+    let active_color = gizmos.config_ext.active_color;
+    for (gizmo, transform) in &query {
+        let origin = transform.translation();
+        let active_axis = gizmo.dragged_axis().or(gizmo.hovered);
+        for axis in TransformGizmoAxis::ALL {
+            let direction = gizmo.direction(transform, axis);
+            let color = if active_axis == Some(axis) {
+                active_color
+            } else {
+                axis_colors[axis.index()]
+            };
+            let end = origin + direction * size;
+            match gizmo.mode {
+                TransformGizmoMode::Translate => {
+                    gizmos.arrow(origin, end, color);
+                }
+                TransformGizmoMode::Rotate => {
+                    let rotation = Quat::from_rotation_arc(Vec3::Z, direction);
+                    gizmos
+                        .circle(Isometry3d::new(origin, rotation), size, color)
+                        .resolution(64);
+                }
+                TransformGizmoMode::Scale => {
+                    gizmos.line(origin, end, color);
+                    gizmos.cube(
+                        Transform::from_translation(end)
+                            .with_rotation(transform.rotation())
+                            .with_scale(Vec3::splat(size * 0.1)),
+                        color,
+                    );
+                }
+            }
+        }
+    }
+}
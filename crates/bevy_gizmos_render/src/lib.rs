@@ -51,10 +51,11 @@ use {
         render_asset::{PrepareAssetError, RenderAsset, RenderAssetPlugin, RenderAssets},
         render_phase::{PhaseItem, RenderCommand, RenderCommandResult, TrackedRenderPass},
         render_resource::{
-            binding_types::uniform_buffer, BindGroup, BindGroupEntries, BindGroupLayoutEntries,
-            Buffer, BufferInitDescriptor, BufferUsages, ShaderStages, ShaderType, VertexFormat,
+            binding_types::{storage_buffer_read_only_sized, uniform_buffer},
+            BindGroup, BindGroupEntries, BindGroupLayoutEntries, Buffer, BufferInitDescriptor,
+            BufferUsages, ShaderStages, ShaderType, VertexFormat,
         },
-        renderer::RenderDevice,
+        renderer::{RenderDevice, RenderQueue},
         sync_world::{MainEntity, TemporaryRenderEntity},
         Extract, ExtractSchedule, Render, RenderApp, RenderStartup, RenderSystems,
     },
@@ -82,6 +83,7 @@ impl Plugin for GizmoRenderPlugin {
             use bevy_asset::embedded_asset;
             embedded_asset!(app, "lines.wgsl");
             embedded_asset!(app, "line_joints.wgsl");
+            embedded_asset!(app, "triangles.wgsl");
         }
 
         app.add_plugins(UniformComponentPlugin::<LineGizmoUniform>::default())
@@ -115,7 +117,10 @@ impl Plugin for GizmoRenderPlugin {
     }
 }
 
-fn init_line_gizmo_uniform_bind_group_layout(mut commands: Commands) {
+fn init_line_gizmo_uniform_bind_group_layout(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+) {
     let line_layout = BindGroupLayoutDescriptor::new(
         "LineGizmoUniform layout",
         &BindGroupLayoutEntries::single(
@@ -127,6 +132,28 @@ fn init_line_gizmo_uniform_bind_group_layout(mut commands: Commands) {
     commands.insert_resource(LineGizmoUniformBindgroupLayout {
         layout: line_layout,
     });
+
+    // Lines are read from storage buffers in the vertex shader where they're supported, and from
+    // per-instance vertex buffers otherwise, such as on WebGL2.
+    let storage_layout =
+        (render_device.limits().max_storage_buffers_per_shader_stage >= 2).then(|| {
+            BindGroupLayoutDescriptor::new(
+                "LineGizmoStorage layout",
+                &BindGroupLayoutEntries::sequential(
+                    ShaderStages::VERTEX,
+                    (
+                        // positions
+                        storage_buffer_read_only_sized(false, None),
+                        // colors
+                        storage_buffer_read_only_sized(false, None),
+                    ),
+                ),
+            )
+        });
+
+    commands.insert_resource(LineGizmoStorageBindgroupLayout {
+        layout: storage_layout,
+    });
 }
 
 fn extract_gizmo_data(
@@ -147,10 +174,6 @@ fn extract_gizmo_data(
             continue;
         }
 
-        let Some(handle) = handle else {
-            continue;
-        };
-
         let joints_resolution = if let GizmoLineJoint::Round(resolution) = config.line.joints {
             resolution
         } else {
@@ -173,30 +196,47 @@ fn extract_gizmo_data(
             (1.0, 1.0)
         };
 
-        commands.spawn((
-            LineGizmoUniform {
-                world_from_local: Affine3::from(&Affine3A::IDENTITY).to_transpose(),
-                line_width: config.line.width,
-                depth_bias: config.depth_bias,
-                joints_resolution,
-                gap_scale,
-                line_scale,
-                #[cfg(feature = "webgl")]
-                _padding: Default::default(),
-            },
-            #[cfg(any(feature = "bevy_pbr", feature = "bevy_sprite_render"))]
-            GizmoMeshConfig {
-                line_perspective: config.line.perspective,
-                line_style: config.line.style,
-                line_joints: config.line.joints,
-                render_layers: config.render_layers.clone(),
-                handle: handle.clone(),
-            },
-            // The immediate mode API does not have a main world entity to refer to,
-            // but we do need MainEntity on this render entity for the systems to find it.
-            MainEntity::from(Entity::PLACEHOLDER),
-            TemporaryRenderEntity,
-        ));
+        // The gizmos drawn with a depth mode override the depth bias of their group.
+        let depth_mode_handles = handles.depth_mode_handles().get(group_type_id);
+        let gizmos = [
+            handle
+                .as_ref()
+                .map(|handle| (handle, config.depth_bias, false)),
+            depth_mode_handles
+                .and_then(|handles| handles.depth_tested.as_ref())
+                .map(|handle| (handle, 0.0, false)),
+            depth_mode_handles
+                .and_then(|handles| handles.always_on_top.as_ref())
+                .map(|handle| (handle, 0.0, true)),
+        ];
+
+        for (handle, depth_bias, always_on_top) in gizmos.into_iter().flatten() {
+            commands.spawn((
+                LineGizmoUniform {
+                    world_from_local: Affine3::from(&Affine3A::IDENTITY).to_transpose(),
+                    line_width: config.line.width,
+                    depth_bias,
+                    joints_resolution,
+                    gap_scale,
+                    line_scale,
+                    #[cfg(feature = "webgl")]
+                    _padding: Default::default(),
+                },
+                #[cfg(any(feature = "bevy_pbr", feature = "bevy_sprite_render"))]
+                GizmoMeshConfig {
+                    line_perspective: config.line.perspective,
+                    line_style: config.line.style,
+                    line_joints: config.line.joints,
+                    render_layers: config.render_layers.clone(),
+                    always_on_top,
+                    handle: handle.clone(),
+                },
+                // The immediate mode API does not have a main world entity to refer to,
+                // but we do need MainEntity on this render entity for the systems to find it.
+                MainEntity::from(Entity::PLACEHOLDER),
+                TemporaryRenderEntity,
+            ));
+        }
     }
 }
 
@@ -220,61 +260,166 @@ struct GpuLineGizmo {
     list_position_buffer: Buffer,
     list_color_buffer: Buffer,
     list_vertex_count: u32,
+    /// The bind group of the list buffers, if the lines are read from storage buffers.
+    list_bind_group: Option<BindGroup>,
     strip_position_buffer: Buffer,
     strip_color_buffer: Buffer,
     strip_vertex_count: u32,
+    /// The bind group of the strip buffers, if the lines are read from storage buffers.
+    strip_bind_group: Option<BindGroup>,
+    triangle_position_buffer: Buffer,
+    triangle_color_buffer: Buffer,
+    triangle_vertex_count: u32,
 }
 
 impl RenderAsset for GpuLineGizmo {
     type SourceAsset = GizmoAsset;
-    type Param = SRes<RenderDevice>;
+    type Param = (
+        SRes<RenderDevice>,
+        SRes<RenderQueue>,
+        SRes<PipelineCache>,
+        SRes<LineGizmoStorageBindgroupLayout>,
+    );
 
     fn prepare_asset(
         gizmo: Self::SourceAsset,
         _: AssetId<Self::SourceAsset>,
-        render_device: &mut SystemParamItem<Self::Param>,
-        _: Option<&Self>,
+        (render_device, render_queue, pipeline_cache, storage_layout): &mut SystemParamItem<
+            Self::Param,
+        >,
+        previous_asset: Option<&Self>,
     ) -> Result<Self, PrepareAssetError<Self::SourceAsset>> {
-        let list_position_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
-            usage: BufferUsages::VERTEX,
-            label: Some("LineGizmo Position Buffer"),
-            contents: cast_slice(&gizmo.buffer().list_positions),
-        });
+        let storage_layout = storage_layout.layout.as_ref();
+        let usage = if storage_layout.is_some() {
+            BufferUsages::VERTEX | BufferUsages::STORAGE | BufferUsages::COPY_DST
+        } else {
+            BufferUsages::VERTEX | BufferUsages::COPY_DST
+        };
+        let buffer = gizmo.buffer();
+
+        let write_buffer = |previous: Option<&Buffer>, label: &'static str, contents: &[u8]| {
+            write_line_gizmo_buffer(
+                render_device,
+                render_queue,
+                previous,
+                usage,
+                label,
+                contents,
+            )
+        };
 
-        let list_color_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
-            usage: BufferUsages::VERTEX,
-            label: Some("LineGizmo Color Buffer"),
-            contents: cast_slice(&gizmo.buffer().list_colors),
-        });
+        let list_position_buffer = write_buffer(
+            previous_asset.map(|previous| &previous.list_position_buffer),
+            "LineGizmo Position Buffer",
+            cast_slice(&buffer.list_positions),
+        );
+        let list_color_buffer = write_buffer(
+            previous_asset.map(|previous| &previous.list_color_buffer),
+            "LineGizmo Color Buffer",
+            cast_slice(&buffer.list_colors),
+        );
+        let strip_position_buffer = write_buffer(
+            previous_asset.map(|previous| &previous.strip_position_buffer),
+            "LineGizmo Strip Position Buffer",
+            cast_slice(&buffer.strip_positions),
+        );
+        let strip_color_buffer = write_buffer(
+            previous_asset.map(|previous| &previous.strip_color_buffer),
+            "LineGizmo Strip Color Buffer",
+            cast_slice(&buffer.strip_colors),
+        );
 
-        let strip_position_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
-            usage: BufferUsages::VERTEX,
-            label: Some("LineGizmo Strip Position Buffer"),
-            contents: cast_slice(&gizmo.buffer().strip_positions),
-        });
+        let triangle_position_buffer = write_buffer(
+            previous_asset.map(|previous| &previous.triangle_position_buffer),
+            "LineGizmo Triangle Position Buffer",
+            cast_slice(&buffer.triangle_positions),
+        );
+        let triangle_color_buffer = write_buffer(
+            previous_asset.map(|previous| &previous.triangle_color_buffer),
+            "LineGizmo Triangle Color Buffer",
+            cast_slice(&buffer.triangle_colors),
+        );
 
-        let strip_color_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
-            usage: BufferUsages::VERTEX,
-            label: Some("LineGizmo Strip Color Buffer"),
-            contents: cast_slice(&gizmo.buffer().strip_colors),
-        });
+        let list_vertex_count = buffer.list_positions.len() as u32;
+        let strip_vertex_count = buffer.strip_positions.len() as u32;
+
+        // Empty buffers can't be bound, but nothing is drawn from them anyway.
+        let create_bind_group =
+            |label: &'static str, vertex_count: u32, positions: &Buffer, colors: &Buffer| {
+                let layout = storage_layout.filter(|_| vertex_count > 0)?;
+                Some(render_device.create_bind_group(
+                    label,
+                    &pipeline_cache.get_bind_group_layout(layout),
+                    &BindGroupEntries::sequential((
+                        positions.as_entire_binding(),
+                        colors.as_entire_binding(),
+                    )),
+                ))
+            };
 
         Ok(GpuLineGizmo {
+            list_bind_group: create_bind_group(
+                "LineGizmo bindgroup",
+                list_vertex_count,
+                &list_position_buffer,
+                &list_color_buffer,
+            ),
+            strip_bind_group: create_bind_group(
+                "LineGizmo Strip bindgroup",
+                strip_vertex_count,
+                &strip_position_buffer,
+                &strip_color_buffer,
+            ),
             list_position_buffer,
             list_color_buffer,
-            list_vertex_count: gizmo.buffer().list_positions.len() as u32,
+            list_vertex_count,
             strip_position_buffer,
             strip_color_buffer,
-            strip_vertex_count: gizmo.buffer().strip_positions.len() as u32,
+            strip_vertex_count,
+            triangle_position_buffer,
+            triangle_color_buffer,
+            triangle_vertex_count: buffer.triangle_positions.len() as u32,
         })
     }
 }
 
+/// Writes `contents` to the `previous` buffer of a gizmo if it's large enough to hold them, and to
+/// a new buffer otherwise.
+///
+/// Gizmos are usually redrawn every frame, so this saves reallocating their buffers each time.
+fn write_line_gizmo_buffer(
+    render_device: &RenderDevice,
+    render_queue: &RenderQueue,
+    previous: Option<&Buffer>,
+    usage: BufferUsages,
+    label: &'static str,
+    contents: &[u8],
+) -> Buffer {
+    match previous {
+        Some(buffer) if buffer.size() >= contents.len() as u64 => {
+            render_queue.write_buffer(buffer, 0, contents);
+            buffer.clone()
+        }
+        _ => render_device.create_buffer_with_data(&BufferInitDescriptor {
+            usage,
+            label: Some(label),
+            contents,
+        }),
+    }
+}
+
 #[derive(Resource)]
 struct LineGizmoUniformBindgroupLayout {
     layout: BindGroupLayoutDescriptor,
 }
 
+/// The layout of the storage buffers the vertex shader reads lines from, if the render device
+/// supports them.
+#[derive(Resource)]
+struct LineGizmoStorageBindgroupLayout {
+    layout: Option<BindGroupLayoutDescriptor>,
+}
+
 #[derive(Resource)]
 struct LineGizmoUniformBindgroup {
     bindgroup: BindGroup,
@@ -358,6 +503,25 @@ impl<P: PhaseItem, const STRIP: bool> RenderCommand<P> for DrawLineGizmo<STRIP>
         }
 
         let instances = if STRIP {
+            vertex_count - 1
+        } else {
+            vertex_count / 2
+        };
+
+        let bind_group = if STRIP {
+            &line_gizmo.strip_bind_group
+        } else {
+            &line_gizmo.list_bind_group
+        };
+
+        // The vertex shader reads the lines from the storage buffers itself.
+        if let Some(bind_group) = bind_group {
+            pass.set_bind_group(2, bind_group, &[]);
+            pass.draw(0..6, 0..instances);
+            return RenderCommandResult::Success;
+        }
+
+        if STRIP {
             let item_size = VertexFormat::Float32x3.size();
             let buffer_size = line_gizmo.strip_position_buffer.size() - item_size;
 
@@ -369,14 +533,10 @@ impl<P: PhaseItem, const STRIP: bool> RenderCommand<P> for DrawLineGizmo<STRIP>
 
             pass.set_vertex_buffer(2, line_gizmo.strip_color_buffer.slice(..buffer_size));
             pass.set_vertex_buffer(3, line_gizmo.strip_color_buffer.slice(item_size..));
-
-            vertex_count - 1
         } else {
             pass.set_vertex_buffer(0, line_gizmo.list_position_buffer.slice(..));
             pass.set_vertex_buffer(1, line_gizmo.list_color_buffer.slice(..));
-
-            vertex_count / 2
-        };
+        }
 
         pass.draw(0..6, 0..instances);
 
@@ -455,6 +615,43 @@ impl<P: PhaseItem> RenderCommand<P> for DrawLineJointGizmo {
     }
 }
 
+#[cfg(feature = "bevy_pbr")]
+struct DrawTriangleGizmo;
+
+#[cfg(feature = "bevy_pbr")]
+impl<P: PhaseItem> RenderCommand<P> for DrawTriangleGizmo {
+    type Param = SRes<RenderAssets<GpuLineGizmo>>;
+    type ViewQuery = ();
+    type ItemQuery = Read<GizmoMeshConfig>;
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        _view: ROQueryItem<'w, '_, Self::ViewQuery>,
+        config: Option<ROQueryItem<'w, '_, Self::ItemQuery>>,
+        line_gizmos: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(config) = config else {
+            return RenderCommandResult::Skip;
+        };
+        let Some(line_gizmo) = line_gizmos.into_inner().get(&config.handle) else {
+            return RenderCommandResult::Skip;
+        };
+
+        if line_gizmo.triangle_vertex_count < 3 {
+            return RenderCommandResult::Success;
+        }
+
+        pass.set_vertex_buffer(0, line_gizmo.triangle_position_buffer.slice(..));
+        pass.set_vertex_buffer(1, line_gizmo.triangle_color_buffer.slice(..));
+
+        pass.draw(0..line_gizmo.triangle_vertex_count, 0..1);
+
+        RenderCommandResult::Success
+    }
+}
+
 fn line_gizmo_vertex_buffer_layouts(strip: bool) -> Vec<VertexBufferLayout> {
     use VertexFormat::*;
     let mut position_layout = VertexBufferLayout {
@@ -509,6 +706,31 @@ fn line_gizmo_vertex_buffer_layouts(strip: bool) -> Vec<VertexBufferLayout> {
     }
 }
 
+#[cfg(feature = "bevy_pbr")]
+fn triangle_gizmo_vertex_buffer_layouts() -> Vec<VertexBufferLayout> {
+    use VertexFormat::*;
+    vec![
+        VertexBufferLayout {
+            array_stride: Float32x3.size(),
+            step_mode: VertexStepMode::Vertex,
+            attributes: vec![VertexAttribute {
+                format: Float32x3,
+                offset: 0,
+                shader_location: 0,
+            }],
+        },
+        VertexBufferLayout {
+            array_stride: Float32x4.size(),
+            step_mode: VertexStepMode::Vertex,
+            attributes: vec![VertexAttribute {
+                format: Float32x4,
+                offset: 0,
+                shader_location: 1,
+            }],
+        },
+    ]
+}
+
 fn line_joint_gizmo_vertex_buffer_layouts() -> Vec<VertexBufferLayout> {
     use VertexFormat::*;
     let mut position_layout = VertexBufferLayout {
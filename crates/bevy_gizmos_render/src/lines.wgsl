@@ -19,6 +19,16 @@ struct LineGizmoUniform {
 
 @group(1) @binding(0) var<uniform> line_gizmo: LineGizmoUniform;
 
+#ifdef VERTEX_PULLING
+// The positions are tightly packed `vec3`s, which have a stride of 16 bytes in storage buffers.
+@group(2) @binding(0) var<storage> vertex_positions: array<f32>;
+@group(2) @binding(1) var<storage> vertex_colors: array<vec4<f32>>;
+
+struct VertexInput {
+    @builtin(instance_index) instance: u32,
+    @builtin(vertex_index) index: u32,
+};
+#else
 struct VertexInput {
     @location(0) position_a: vec3<f32>,
     @location(1) position_b: vec3<f32>,
@@ -26,6 +36,39 @@ struct VertexInput {
     @location(3) color_b: vec4<f32>,
     @builtin(vertex_index) index: u32,
 };
+#endif
+
+struct LineSegment {
+    position_a: vec3<f32>,
+    position_b: vec3<f32>,
+    color_a: vec4<f32>,
+    color_b: vec4<f32>,
+};
+
+#ifdef VERTEX_PULLING
+fn vertex_position(index: u32) -> vec3<f32> {
+    return vec3(
+        vertex_positions[index * 3u],
+        vertex_positions[index * 3u + 1u],
+        vertex_positions[index * 3u + 2u]
+    );
+}
+#endif
+
+// Each instance draws the segment between two consecutive vertices.
+fn line_segment(vertex: VertexInput) -> LineSegment {
+#ifdef VERTEX_PULLING
+#ifdef STRIP
+    let a = vertex.instance;
+#else
+    let a = vertex.instance * 2u;
+#endif
+    let b = a + 1u;
+    return LineSegment(vertex_position(a), vertex_position(b), vertex_colors[a], vertex_colors[b]);
+#else
+    return LineSegment(vertex.position_a, vertex.position_b, vertex.color_a, vertex.color_b);
+#endif
+}
 
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
@@ -47,12 +90,13 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
         vec2(0.5, 0.)
     );
     let position = positions[vertex.index];
+    let segment = line_segment(vertex);
 
     let world_from_local = affine3_to_square(line_gizmo.world_from_local);
 
     // algorithm based on https://wwwtyro.net/2019/11/18/instanced-lines.html
-    var clip_a = view.clip_from_world * world_from_local * vec4(vertex.position_a, 1.);
-    var clip_b = view.clip_from_world * world_from_local * vec4(vertex.position_b, 1.);
+    var clip_a = view.clip_from_world * world_from_local * vec4(segment.position_a, 1.);
+    var clip_b = view.clip_from_world * world_from_local * vec4(segment.position_b, 1.);
 
     // Manual near plane clipping to avoid errors when doing the perspective divide inside this shader.
     clip_a = clip_near_plane(clip_a, clip_b);
@@ -66,7 +110,7 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
     let y_basis = normalize(screen_b - screen_a);
     let x_basis = vec2(-y_basis.y, y_basis.x);
 
-    var color = mix(vertex.color_a, vertex.color_b, position.y);
+    var color = mix(segment.color_a, segment.color_b, position.y);
 
     var line_width = line_gizmo.line_width;
     var alpha = 1.;
@@ -80,13 +124,13 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
     let pos1 = view.view_from_clip * vec4(0, 1, 0, 1); // Top of the screen
     let near_clipping_plane_height = length(pos0.xyz - pos1.xyz);
 
-    // We can't use vertex.position_X because we may have changed the clip positions with clip_near_plane
+    // We can't use segment.position_X because we may have changed the clip positions with clip_near_plane
     let position_a = view.world_from_clip * clip_a;
     let position_b = view.world_from_clip * clip_b;
     let world_distance = length(position_a.xyz - position_b.xyz);
 
     // Offset to compensate for moved clip positions. If removed dots on lines will slide when position a is ofscreen.
-    let clipped_offset = length(position_a.xyz - vertex.position_a);
+    let clipped_offset = length(position_a.xyz - segment.position_a);
 
     uv = (clipped_offset + position.y * world_distance) * resolution.y / near_clipping_plane_height / line_gizmo.line_width;
 #else
@@ -1,7 +1,8 @@
 use crate::{
     init_line_gizmo_uniform_bind_group_layout, line_gizmo_vertex_buffer_layouts,
     line_joint_gizmo_vertex_buffer_layouts, DrawLineGizmo, DrawLineJointGizmo, GizmoRenderSystems,
-    GpuLineGizmo, LineGizmoUniformBindgroupLayout, SetLineGizmoBindGroup,
+    GpuLineGizmo, LineGizmoStorageBindgroupLayout, LineGizmoUniformBindgroupLayout,
+    SetLineGizmoBindGroup,
 };
 use bevy_app::{App, Plugin};
 use bevy_asset::{load_embedded_asset, AssetServer, Handle};
@@ -79,6 +80,8 @@ impl Plugin for LineGizmo2dPlugin {
 struct LineGizmoPipeline {
     mesh_pipeline: Mesh2dPipeline,
     uniform_layout: BindGroupLayoutDescriptor,
+    /// The layout of the storage buffers lines are read from, if they're supported.
+    storage_layout: Option<BindGroupLayoutDescriptor>,
     shader: Handle<Shader>,
 }
 
@@ -86,11 +89,13 @@ fn init_line_gizmo_pipelines(
     mut commands: Commands,
     mesh_2d_pipeline: Res<Mesh2dPipeline>,
     uniform_bind_group_layout: Res<LineGizmoUniformBindgroupLayout>,
+    storage_bind_group_layout: Res<LineGizmoStorageBindgroupLayout>,
     asset_server: Res<AssetServer>,
 ) {
     commands.insert_resource(LineGizmoPipeline {
         mesh_pipeline: mesh_2d_pipeline.clone(),
         uniform_layout: uniform_bind_group_layout.layout.clone(),
+        storage_layout: storage_bind_group_layout.layout.clone(),
         shader: load_embedded_asset!(asset_server.as_ref(), "lines.wgsl"),
     });
     commands.insert_resource(LineJointGizmoPipeline {
@@ -117,16 +122,28 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
             TextureFormat::bevy_default()
         };
 
-        let shader_defs = vec![
+        let mut shader_defs = vec![
             #[cfg(feature = "webgl")]
             "SIXTEEN_BYTE_ALIGNMENT".into(),
         ];
 
-        let layout = vec![
+        let mut layout = vec![
             self.mesh_pipeline.view_layout.clone(),
             self.uniform_layout.clone(),
         ];
 
+        // Without vertex buffers, the vertex shader reads the lines from storage buffers.
+        let buffers = if let Some(storage_layout) = &self.storage_layout {
+            layout.push(storage_layout.clone());
+            shader_defs.push("VERTEX_PULLING".into());
+            if key.strip {
+                shader_defs.push("STRIP".into());
+            }
+            Vec::new()
+        } else {
+            line_gizmo_vertex_buffer_layouts(key.strip)
+        };
+
         let fragment_entry_point = match key.line_style {
             GizmoLineStyle::Solid => "fragment_solid",
             GizmoLineStyle::Dotted => "fragment_dotted",
@@ -138,7 +155,7 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
             vertex: VertexState {
                 shader: self.shader.clone(),
                 shader_defs: shader_defs.clone(),
-                buffers: line_gizmo_vertex_buffer_layouts(key.strip),
+                buffers,
                 ..default()
             },
             fragment: Some(FragmentState {
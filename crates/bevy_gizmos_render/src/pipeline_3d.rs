@@ -1,7 +1,8 @@
 use crate::{
     init_line_gizmo_uniform_bind_group_layout, line_gizmo_vertex_buffer_layouts,
-    line_joint_gizmo_vertex_buffer_layouts, DrawLineGizmo, DrawLineJointGizmo, GizmoRenderSystems,
-    GpuLineGizmo, LineGizmoUniformBindgroupLayout, SetLineGizmoBindGroup,
+    line_joint_gizmo_vertex_buffer_layouts, triangle_gizmo_vertex_buffer_layouts, DrawLineGizmo,
+    DrawLineJointGizmo, DrawTriangleGizmo, GizmoRenderSystems, GpuLineGizmo,
+    LineGizmoStorageBindgroupLayout, LineGizmoUniformBindgroupLayout, SetLineGizmoBindGroup,
 };
 use bevy_app::{App, Plugin};
 use bevy_asset::{load_embedded_asset, AssetServer, Handle};
@@ -48,8 +49,10 @@ impl Plugin for LineGizmo3dPlugin {
             .add_render_command::<Transparent3d, DrawLineGizmo3d>()
             .add_render_command::<Transparent3d, DrawLineGizmo3dStrip>()
             .add_render_command::<Transparent3d, DrawLineJointGizmo3d>()
+            .add_render_command::<Transparent3d, DrawTriangleGizmo3d>()
             .init_resource::<SpecializedRenderPipelines<LineGizmoPipeline>>()
             .init_resource::<SpecializedRenderPipelines<LineJointGizmoPipeline>>()
+            .init_resource::<SpecializedRenderPipelines<TriangleGizmoPipeline>>()
             .configure_sets(
                 Render,
                 GizmoRenderSystems::QueueLineGizmos3d.in_set(RenderSystems::Queue),
@@ -60,7 +63,11 @@ impl Plugin for LineGizmo3dPlugin {
             )
             .add_systems(
                 Render,
-                (queue_line_gizmos_3d, queue_line_joint_gizmos_3d)
+                (
+                    queue_line_gizmos_3d,
+                    queue_line_joint_gizmos_3d,
+                    queue_triangle_gizmos_3d,
+                )
                     .in_set(GizmoRenderSystems::QueueLineGizmos3d)
                     .after(prepare_assets::<GpuLineGizmo>),
             );
@@ -71,6 +78,8 @@ impl Plugin for LineGizmo3dPlugin {
 struct LineGizmoPipeline {
     mesh_pipeline: MeshPipeline,
     uniform_layout: BindGroupLayoutDescriptor,
+    /// The layout of the storage buffers lines are read from, if they're supported.
+    storage_layout: Option<BindGroupLayoutDescriptor>,
     shader: Handle<Shader>,
 }
 
@@ -78,11 +87,13 @@ fn init_line_gizmo_pipelines(
     mut commands: Commands,
     mesh_pipeline: Res<MeshPipeline>,
     uniform_bind_group_layout: Res<LineGizmoUniformBindgroupLayout>,
+    storage_bind_group_layout: Res<LineGizmoStorageBindgroupLayout>,
     asset_server: Res<AssetServer>,
 ) {
     commands.insert_resource(LineGizmoPipeline {
         mesh_pipeline: mesh_pipeline.clone(),
         uniform_layout: uniform_bind_group_layout.layout.clone(),
+        storage_layout: storage_bind_group_layout.layout.clone(),
         shader: load_embedded_asset!(asset_server.as_ref(), "lines.wgsl"),
     });
     commands.insert_resource(LineJointGizmoPipeline {
@@ -90,6 +101,11 @@ fn init_line_gizmo_pipelines(
         uniform_layout: uniform_bind_group_layout.layout.clone(),
         shader: load_embedded_asset!(asset_server.as_ref(), "line_joints.wgsl"),
     });
+    commands.insert_resource(TriangleGizmoPipeline {
+        mesh_pipeline: mesh_pipeline.clone(),
+        uniform_layout: uniform_bind_group_layout.layout.clone(),
+        shader: load_embedded_asset!(asset_server.as_ref(), "triangles.wgsl"),
+    });
 }
 
 #[derive(PartialEq, Eq, Hash, Clone)]
@@ -98,6 +114,7 @@ struct LineGizmoPipelineKey {
     strip: bool,
     perspective: bool,
     line_style: GizmoLineStyle,
+    always_on_top: bool,
 }
 
 impl SpecializedRenderPipeline for LineGizmoPipeline {
@@ -123,7 +140,19 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
             .mesh_pipeline
             .get_view_layout(key.view_key.into())
             .clone();
-        let layout = vec![view_layout.main_layout.clone(), self.uniform_layout.clone()];
+        let mut layout = vec![view_layout.main_layout.clone(), self.uniform_layout.clone()];
+
+        // Without vertex buffers, the vertex shader reads the lines from storage buffers.
+        let buffers = if let Some(storage_layout) = &self.storage_layout {
+            layout.push(storage_layout.clone());
+            shader_defs.push("VERTEX_PULLING".into());
+            if key.strip {
+                shader_defs.push("STRIP".into());
+            }
+            Vec::new()
+        } else {
+            line_gizmo_vertex_buffer_layouts(key.strip)
+        };
 
         let fragment_entry_point = match key.line_style {
             GizmoLineStyle::Solid => "fragment_solid",
@@ -136,7 +165,7 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
             vertex: VertexState {
                 shader: self.shader.clone(),
                 shader_defs: shader_defs.clone(),
-                buffers: line_gizmo_vertex_buffer_layouts(key.strip),
+                buffers,
                 ..default()
             },
             fragment: Some(FragmentState {
@@ -150,13 +179,7 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
                 })],
             }),
             layout,
-            depth_stencil: Some(DepthStencilState {
-                format: CORE_3D_DEPTH_FORMAT,
-                depth_write_enabled: true,
-                depth_compare: CompareFunction::Greater,
-                stencil: StencilState::default(),
-                bias: DepthBiasState::default(),
-            }),
+            depth_stencil: Some(line_gizmo_depth_stencil_state(key.always_on_top)),
             multisample: MultisampleState {
                 count: key.view_key.msaa_samples(),
                 mask: !0,
@@ -180,6 +203,7 @@ struct LineJointGizmoPipelineKey {
     view_key: MeshPipelineKey,
     perspective: bool,
     joints: GizmoLineJoint,
+    always_on_top: bool,
 }
 
 impl SpecializedRenderPipeline for LineJointGizmoPipeline {
@@ -235,13 +259,7 @@ impl SpecializedRenderPipeline for LineJointGizmoPipeline {
                 ..default()
             }),
             layout,
-            depth_stencil: Some(DepthStencilState {
-                format: CORE_3D_DEPTH_FORMAT,
-                depth_write_enabled: true,
-                depth_compare: CompareFunction::Greater,
-                stencil: StencilState::default(),
-                bias: DepthBiasState::default(),
-            }),
+            depth_stencil: Some(line_gizmo_depth_stencil_state(key.always_on_top)),
             multisample: MultisampleState {
                 count: key.view_key.msaa_samples(),
                 mask: !0,
@@ -253,6 +271,89 @@ impl SpecializedRenderPipeline for LineJointGizmoPipeline {
     }
 }
 
+#[derive(Clone, Resource)]
+struct TriangleGizmoPipeline {
+    mesh_pipeline: MeshPipeline,
+    uniform_layout: BindGroupLayoutDescriptor,
+    shader: Handle<Shader>,
+}
+
+#[derive(PartialEq, Eq, Hash, Clone)]
+struct TriangleGizmoPipelineKey {
+    view_key: MeshPipelineKey,
+    always_on_top: bool,
+}
+
+impl SpecializedRenderPipeline for TriangleGizmoPipeline {
+    type Key = TriangleGizmoPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let shader_defs = vec![
+            #[cfg(feature = "webgl")]
+            "SIXTEEN_BYTE_ALIGNMENT".into(),
+        ];
+
+        let format = if key.view_key.contains(MeshPipelineKey::HDR) {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+
+        let view_layout = self
+            .mesh_pipeline
+            .get_view_layout(key.view_key.into())
+            .clone();
+        let layout = vec![view_layout.main_layout.clone(), self.uniform_layout.clone()];
+
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: self.shader.clone(),
+                shader_defs: shader_defs.clone(),
+                buffers: triangle_gizmo_vertex_buffer_layouts(),
+                ..default()
+            },
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs,
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+                ..default()
+            }),
+            layout,
+            primitive: PrimitiveState {
+                cull_mode: Some(Face::Back),
+                ..default()
+            },
+            depth_stencil: Some(line_gizmo_depth_stencil_state(key.always_on_top)),
+            multisample: MultisampleState {
+                count: key.view_key.msaa_samples(),
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            label: Some("TriangleGizmo 3d Pipeline".into()),
+            ..default()
+        }
+    }
+}
+
+fn line_gizmo_depth_stencil_state(always_on_top: bool) -> DepthStencilState {
+    DepthStencilState {
+        format: CORE_3D_DEPTH_FORMAT,
+        // Gizmos drawn on top don't hide the gizmos hidden behind other geometry.
+        depth_write_enabled: !always_on_top,
+        depth_compare: if always_on_top {
+            CompareFunction::Always
+        } else {
+            CompareFunction::Greater
+        },
+        stencil: StencilState::default(),
+        bias: DepthBiasState::default(),
+    }
+}
+
 type DrawLineGizmo3d = (
     SetItemPipeline,
     SetMeshViewBindGroup<0>,
@@ -271,6 +372,12 @@ type DrawLineJointGizmo3d = (
     SetLineGizmoBindGroup<1>,
     DrawLineJointGizmo,
 );
+type DrawTriangleGizmo3d = (
+    SetItemPipeline,
+    SetMeshViewBindGroup<0>,
+    SetLineGizmoBindGroup<1>,
+    DrawTriangleGizmo,
+);
 
 fn queue_line_gizmos_3d(
     draw_functions: Res<DrawFunctions<Transparent3d>>,
@@ -354,6 +461,7 @@ fn queue_line_gizmos_3d(
                         strip: false,
                         perspective: config.line_perspective,
                         line_style: config.line_style,
+                        always_on_top: config.always_on_top,
                     },
                 );
                 transparent_phase.add(Transparent3d {
@@ -376,6 +484,7 @@ fn queue_line_gizmos_3d(
                         strip: true,
                         perspective: config.line_perspective,
                         line_style: config.line_style,
+                        always_on_top: config.always_on_top,
                     },
                 );
                 transparent_phase.add(Transparent3d {
@@ -470,6 +579,100 @@ fn queue_line_joint_gizmos_3d(
                     view_key,
                     perspective: config.line_perspective,
                     joints: config.line_joints,
+                    always_on_top: config.always_on_top,
+                },
+            );
+
+            transparent_phase.add(Transparent3d {
+                entity: (entity, *main_entity),
+                draw_function,
+                pipeline,
+                distance: 0.,
+                batch_range: 0..1,
+                extra_index: PhaseItemExtraIndex::None,
+                indexed: true,
+            });
+        }
+    }
+}
+
+fn queue_triangle_gizmos_3d(
+    draw_functions: Res<DrawFunctions<Transparent3d>>,
+    pipeline: Res<TriangleGizmoPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<TriangleGizmoPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    line_gizmos: Query<(Entity, &MainEntity, &GizmoMeshConfig)>,
+    line_gizmo_assets: Res<RenderAssets<GpuLineGizmo>>,
+    mut transparent_render_phases: ResMut<ViewSortedRenderPhases<Transparent3d>>,
+    views: Query<(
+        &ExtractedView,
+        &Msaa,
+        Option<&RenderLayers>,
+        (
+            Has<NormalPrepass>,
+            Has<DepthPrepass>,
+            Has<MotionVectorPrepass>,
+            Has<DeferredPrepass>,
+        ),
+    )>,
+) {
+    let draw_function = draw_functions
+        .read()
+        .get_id::<DrawTriangleGizmo3d>()
+        .unwrap();
+
+    for (
+        view,
+        msaa,
+        render_layers,
+        (normal_prepass, depth_prepass, motion_vector_prepass, deferred_prepass),
+    ) in &views
+    {
+        let Some(transparent_phase) = transparent_render_phases.get_mut(&view.retained_view_entity)
+        else {
+            continue;
+        };
+
+        let render_layers = render_layers.unwrap_or_default();
+
+        let mut view_key = MeshPipelineKey::from_msaa_samples(msaa.samples())
+            | MeshPipelineKey::from_hdr(view.hdr);
+
+        if normal_prepass {
+            view_key |= MeshPipelineKey::NORMAL_PREPASS;
+        }
+
+        if depth_prepass {
+            view_key |= MeshPipelineKey::DEPTH_PREPASS;
+        }
+
+        if motion_vector_prepass {
+            view_key |= MeshPipelineKey::MOTION_VECTOR_PREPASS;
+        }
+
+        if deferred_prepass {
+            view_key |= MeshPipelineKey::DEFERRED_PREPASS;
+        }
+
+        for (entity, main_entity, config) in &line_gizmos {
+            if !config.render_layers.intersects(render_layers) {
+                continue;
+            }
+
+            let Some(line_gizmo) = line_gizmo_assets.get(&config.handle) else {
+                continue;
+            };
+
+            if line_gizmo.triangle_vertex_count < 3 {
+                continue;
+            }
+
+            let pipeline = pipelines.specialize(
+                &pipeline_cache,
+                &pipeline,
+                TriangleGizmoPipelineKey {
+                    view_key,
+                    always_on_top: config.always_on_top,
                 },
             );
 
@@ -0,0 +1,596 @@
+//! Exporting entities to binary glTF (`.glb`) files.
+
+use bevy_asset::{AssetId, Assets};
+use bevy_color::{ColorToComponents, LinearRgba};
+use bevy_ecs::{
+    entity::Entity,
+    hierarchy::{ChildOf, Children},
+    name::Name,
+    world::World,
+};
+use bevy_mesh::{Indices, Mesh, Mesh3d, PrimitiveTopology, VertexAttributeValues};
+use bevy_pbr::{MeshMaterial3d, StandardMaterial};
+use bevy_platform::collections::{HashMap, HashSet};
+use bevy_render::alpha::AlphaMode;
+use bevy_scene::Scene;
+use bevy_transform::components::Transform;
+use serde_json::{json, Map, Value};
+use thiserror::Error;
+
+/// An error that occurs when exporting a glTF file with a [`GltfExporter`].
+#[derive(Error, Debug)]
+pub enum GltfExportError {
+    /// A mesh used by an exported entity isn't loaded.
+    #[error("mesh {0} is not loaded")]
+    MissingMesh(AssetId<Mesh>),
+    /// A mesh has a vertex attribute with a format that can't be exported.
+    #[error("mesh {mesh} has an unsupported format for its {attribute} attribute")]
+    UnsupportedAttributeFormat {
+        /// The mesh with the attribute.
+        mesh: AssetId<Mesh>,
+        /// The name of the glTF attribute.
+        attribute: &'static str,
+    },
+    /// Failed to serialize the glTF JSON.
+    #[error("failed to serialize glTF JSON: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Exports entities with meshes, materials and transforms to a binary glTF (`.glb`) file, so that
+/// procedurally generated or edited content can be opened in other tools.
+///
+/// Every entity with a [`Mesh3d`] is exported as a glTF node, along with its ancestors so that the
+/// hierarchy (and the [`Transform`] of each entity) is preserved. Entity [`Name`]s are used as node
+/// names, and [`StandardMaterial`]s are exported as glTF PBR materials. Textures, skins and
+/// animations aren't exported.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_gltf::GltfExporter;
+/// fn export(world: &mut World) {
+///     let glb = GltfExporter::from_world(world).export_world(world).unwrap();
+///     // Write `glb` to a file...
+/// }
+/// ```
+pub struct GltfExporter<'a> {
+    meshes: &'a Assets<Mesh>,
+    materials: &'a Assets<StandardMaterial>,
+}
+
+impl<'a> GltfExporter<'a> {
+    /// Creates a new [`GltfExporter`] that reads mesh and material data from the given assets.
+    pub fn new(meshes: &'a Assets<Mesh>, materials: &'a Assets<StandardMaterial>) -> Self {
+        Self { meshes, materials }
+    }
+
+    /// Creates a new [`GltfExporter`] that reads mesh and material data from the assets in `world`.
+    pub fn from_world(world: &'a World) -> Self {
+        Self::new(
+            world.resource::<Assets<Mesh>>(),
+            world.resource::<Assets<StandardMaterial>>(),
+        )
+    }
+
+    /// Exports every entity of `world` that has a mesh (and their ancestors) to a `.glb` file.
+    pub fn export_world(&self, world: &World) -> Result<Vec<u8>, GltfExportError> {
+        let meshes = world
+            .try_query::<(Entity, &Mesh3d)>()
+            .map(|mut query| query.iter(world).map(|(entity, _)| entity).collect())
+            .unwrap_or_default();
+        self.export(world, meshes)
+    }
+
+    /// Exports every entity of the `scene` that has a mesh (and their ancestors) to a `.glb` file.
+    pub fn export_scene(&self, scene: &Scene) -> Result<Vec<u8>, GltfExportError> {
+        self.export_world(&scene.world)
+    }
+
+    /// Exports the hierarchies of the given `roots` in `world` to a `.glb` file.
+    ///
+    /// Descendants of the roots without a mesh (nor descendants with a mesh) aren't exported.
+    pub fn export_entities(
+        &self,
+        world: &World,
+        roots: impl IntoIterator<Item = Entity>,
+    ) -> Result<Vec<u8>, GltfExportError> {
+        let mut meshes = Vec::new();
+        let mut stack = roots.into_iter().collect::<Vec<_>>();
+        while let Some(entity) = stack.pop() {
+            let Ok(entity) = world.get_entity(entity) else {
+                continue;
+            };
+            if entity.contains::<Mesh3d>() {
+                meshes.push(entity.id());
+            }
+            if let Some(children) = entity.get::<Children>() {
+                stack.extend(children.iter());
+            }
+        }
+        self.export(world, meshes)
+    }
+
+    fn export(&self, world: &World, meshes: Vec<Entity>) -> Result<Vec<u8>, GltfExportError> {
+        // Export the ancestors of the meshes as well, so their transforms are preserved.
+        let mut entities = HashSet::default();
+        for entity in meshes {
+            let mut entity = Some(entity);
+            while let Some(current) = entity {
+                if !entities.insert(current) {
+                    break;
+                }
+                entity = world.get::<ChildOf>(current).map(ChildOf::parent);
+            }
+        }
+        let mut roots = entities
+            .iter()
+            .copied()
+            .filter(|&entity| world.get::<ChildOf>(entity).is_none())
+            .collect::<Vec<_>>();
+        roots.sort();
+
+        let mut builder = GlbBuilder::default();
+        let root_nodes = roots
+            .into_iter()
+            .map(|root| self.export_node(&mut builder, world, &entities, root))
+            .collect::<Result<Vec<_>, _>>()?;
+        builder.finish(root_nodes)
+    }
+
+    /// Adds the node of `entity` and its exported descendants, returning its index.
+    fn export_node(
+        &self,
+        builder: &mut GlbBuilder,
+        world: &World,
+        entities: &HashSet<Entity>,
+        entity: Entity,
+    ) -> Result<usize, GltfExportError> {
+        let mut node = Map::new();
+        if let Some(name) = world.get::<Name>(entity) {
+            node.insert("name".into(), json!(name.as_str()));
+        }
+        let transform = world.get::<Transform>(entity).copied().unwrap_or_default();
+        if transform.translation != Transform::IDENTITY.translation {
+            node.insert(
+                "translation".into(),
+                json!(transform.translation.to_array()),
+            );
+        }
+        if transform.rotation != Transform::IDENTITY.rotation {
+            node.insert("rotation".into(), json!(transform.rotation.to_array()));
+        }
+        if transform.scale != Transform::IDENTITY.scale {
+            node.insert("scale".into(), json!(transform.scale.to_array()));
+        }
+        if let Some(mesh) = world.get::<Mesh3d>(entity) {
+            let material = world
+                .get::<MeshMaterial3d<StandardMaterial>>(entity)
+                .map(|material| material.id());
+            let mesh = self.export_mesh(builder, mesh.id(), material)?;
+            node.insert("mesh".into(), json!(mesh));
+        }
+
+        let children = world
+            .get::<Children>(entity)
+            .into_iter()
+            .flat_map(|children| children.iter().copied())
+            .filter(|child| entities.contains(child))
+            .map(|child| self.export_node(builder, world, entities, child))
+            .collect::<Result<Vec<_>, _>>()?;
+        if !children.is_empty() {
+            node.insert("children".into(), json!(children));
+        }
+
+        builder.nodes.push(Value::Object(node));
+        Ok(builder.nodes.len() - 1)
+    }
+
+    /// Adds the glTF mesh for the given mesh and material, returning its index.
+    fn export_mesh(
+        &self,
+        builder: &mut GlbBuilder,
+        mesh_id: AssetId<Mesh>,
+        material_id: Option<AssetId<StandardMaterial>>,
+    ) -> Result<usize, GltfExportError> {
+        // glTF meshes contain their material, so each combination is a different glTF mesh.
+        if let Some(&index) = builder.mesh_indices.get(&(mesh_id, material_id)) {
+            return Ok(index);
+        }
+        let mesh = self
+            .meshes
+            .get(mesh_id)
+            .ok_or(GltfExportError::MissingMesh(mesh_id))?;
+
+        let mut attributes = Map::new();
+        for (name, attribute) in [
+            ("POSITION", Mesh::ATTRIBUTE_POSITION),
+            ("NORMAL", Mesh::ATTRIBUTE_NORMAL),
+            ("TANGENT", Mesh::ATTRIBUTE_TANGENT),
+            ("TEXCOORD_0", Mesh::ATTRIBUTE_UV_0),
+            ("TEXCOORD_1", Mesh::ATTRIBUTE_UV_1),
+            ("COLOR_0", Mesh::ATTRIBUTE_COLOR),
+        ] {
+            let Some(values) = mesh.attribute(attribute) else {
+                continue;
+            };
+            let accessor = match values {
+                VertexAttributeValues::Float32x2(values) => {
+                    builder.add_accessor(values.as_flattened(), "VEC2", values.len(), None)
+                }
+                VertexAttributeValues::Float32x3(values) => {
+                    // The bounds of the positions are required by glTF.
+                    let bounds = (name == "POSITION").then(|| bounds(values));
+                    builder.add_accessor(values.as_flattened(), "VEC3", values.len(), bounds)
+                }
+                VertexAttributeValues::Float32x4(values) => {
+                    builder.add_accessor(values.as_flattened(), "VEC4", values.len(), None)
+                }
+                _ => {
+                    return Err(GltfExportError::UnsupportedAttributeFormat {
+                        mesh: mesh_id,
+                        attribute: name,
+                    })
+                }
+            };
+            attributes.insert(name.into(), json!(accessor));
+        }
+
+        let mut primitive = Map::new();
+        primitive.insert("attributes".into(), Value::Object(attributes));
+        primitive.insert("mode".into(), json!(mode(mesh.primitive_topology())));
+        if let Some(indices) = mesh.indices() {
+            primitive.insert("indices".into(), json!(builder.add_indices(indices)));
+        }
+        if let Some(material) = material_id.and_then(|id| self.export_material(builder, id)) {
+            primitive.insert("material".into(), json!(material));
+        }
+
+        builder.meshes.push(json!({ "primitives": [primitive] }));
+        let index = builder.meshes.len() - 1;
+        builder.mesh_indices.insert((mesh_id, material_id), index);
+        Ok(index)
+    }
+
+    /// Adds the glTF material for the given material, returning its index.
+    fn export_material(
+        &self,
+        builder: &mut GlbBuilder,
+        id: AssetId<StandardMaterial>,
+    ) -> Option<usize> {
+        if let Some(&index) = builder.material_indices.get(&id) {
+            return Some(index);
+        }
+        let material = self.materials.get(id)?;
+
+        let mut gltf_material = Map::new();
+        gltf_material.insert(
+            "pbrMetallicRoughness".into(),
+            json!({
+                "baseColorFactor": LinearRgba::from(material.base_color).to_f32_array(),
+                "metallicFactor": material.metallic,
+                "roughnessFactor": material.perceptual_roughness,
+            }),
+        );
+        let mut extensions = Map::new();
+        // Emissive factors are limited to 1 in glTF, brighter colors are scaled with an extension.
+        let emissive = material.emissive.to_f32_array_no_alpha();
+        let emissive_strength = emissive.into_iter().fold(1.0, f32::max);
+        if emissive != [0.0; 3] {
+            gltf_material.insert(
+                "emissiveFactor".into(),
+                json!(emissive.map(|channel| channel / emissive_strength)),
+            );
+        }
+        if emissive_strength > 1.0 {
+            extensions.insert(
+                "KHR_materials_emissive_strength".into(),
+                json!({ "emissiveStrength": emissive_strength }),
+            );
+        }
+        match material.alpha_mode {
+            AlphaMode::Opaque => {}
+            AlphaMode::Mask(cutoff) => {
+                gltf_material.insert("alphaMode".into(), json!("MASK"));
+                gltf_material.insert("alphaCutoff".into(), json!(cutoff));
+            }
+            // glTF only supports blending, but the other modes look similar enough.
+            _ => {
+                gltf_material.insert("alphaMode".into(), json!("BLEND"));
+            }
+        }
+        if material.double_sided {
+            gltf_material.insert("doubleSided".into(), json!(true));
+        }
+        if material.unlit {
+            extensions.insert("KHR_materials_unlit".into(), json!({}));
+        }
+        if !extensions.is_empty() {
+            builder.extensions_used.extend(extensions.keys().cloned());
+            gltf_material.insert("extensions".into(), Value::Object(extensions));
+        }
+
+        builder.materials.push(Value::Object(gltf_material));
+        let index = builder.materials.len() - 1;
+        builder.material_indices.insert(id, index);
+        Some(index)
+    }
+}
+
+/// Returns the glTF primitive mode of the topology.
+fn mode(topology: PrimitiveTopology) -> u32 {
+    match topology {
+        PrimitiveTopology::PointList => 0,
+        PrimitiveTopology::LineList => 1,
+        PrimitiveTopology::LineStrip => 3,
+        PrimitiveTopology::TriangleList => 4,
+        PrimitiveTopology::TriangleStrip => 5,
+    }
+}
+
+/// Returns the component-wise minimum and maximum of the values.
+fn bounds(values: &[[f32; 3]]) -> (Vec<f32>, Vec<f32>) {
+    let mut min = [f32::INFINITY; 3];
+    let mut max = [f32::NEG_INFINITY; 3];
+    for value in values {
+        for ((min, max), value) in min.iter_mut().zip(&mut max).zip(value) {
+            *min = min.min(*value);
+            *max = max.max(*value);
+        }
+    }
+    (min.to_vec(), max.to_vec())
+}
+
+const GLB_MAGIC: &[u8; 4] = b"glTF";
+const GLB_VERSION: u32 = 2;
+const GLB_JSON_CHUNK: &[u8; 4] = b"JSON";
+const GLB_BIN_CHUNK: &[u8; 4] = b"BIN\0";
+
+const FLOAT: u32 = 5126;
+const UNSIGNED_SHORT: u32 = 5123;
+const UNSIGNED_INT: u32 = 5125;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+/// Accumulates the glTF JSON objects and binary data of an export.
+#[derive(Default)]
+struct GlbBuilder {
+    nodes: Vec<Value>,
+    meshes: Vec<Value>,
+    materials: Vec<Value>,
+    accessors: Vec<Value>,
+    buffer_views: Vec<Value>,
+    extensions_used: HashSet<String>,
+    buffer: Vec<u8>,
+    mesh_indices: HashMap<(AssetId<Mesh>, Option<AssetId<StandardMaterial>>), usize>,
+    material_indices: HashMap<AssetId<StandardMaterial>, usize>,
+}
+
+impl GlbBuilder {
+    /// Adds a buffer view for `bytes`, returning its index.
+    fn add_buffer_view(&mut self, bytes: impl IntoIterator<Item = u8>, target: u32) -> usize {
+        // Accessors must be aligned to the size of their components.
+        self.buffer.resize(self.buffer.len().next_multiple_of(4), 0);
+        let offset = self.buffer.len();
+        self.buffer.extend(bytes);
+        self.buffer_views.push(json!({
+            "buffer": 0,
+            "byteOffset": offset,
+            "byteLength": self.buffer.len() - offset,
+            "target": target,
+        }));
+        self.buffer_views.len() - 1
+    }
+
+    /// Adds an accessor for float vertex data, returning its index.
+    fn add_accessor(
+        &mut self,
+        values: &[f32],
+        accessor_type: &str,
+        count: usize,
+        bounds: Option<(Vec<f32>, Vec<f32>)>,
+    ) -> usize {
+        let bytes = values.iter().flat_map(|value| value.to_le_bytes());
+        let buffer_view = self.add_buffer_view(bytes, ARRAY_BUFFER);
+        let mut accessor = json!({
+            "bufferView": buffer_view,
+            "componentType": FLOAT,
+            "count": count,
+            "type": accessor_type,
+        });
+        if let Some((min, max)) = bounds {
+            accessor["min"] = json!(min);
+            accessor["max"] = json!(max);
+        }
+        self.accessors.push(accessor);
+        self.accessors.len() - 1
+    }
+
+    /// Adds an accessor for vertex indices, returning its index.
+    fn add_indices(&mut self, indices: &Indices) -> usize {
+        let (buffer_view, component_type) = match indices {
+            Indices::U16(indices) => (
+                self.add_buffer_view(
+                    indices.iter().flat_map(|index| index.to_le_bytes()),
+                    ELEMENT_ARRAY_BUFFER,
+                ),
+                UNSIGNED_SHORT,
+            ),
+            Indices::U32(indices) => (
+                self.add_buffer_view(
+                    indices.iter().flat_map(|index| index.to_le_bytes()),
+                    ELEMENT_ARRAY_BUFFER,
+                ),
+                UNSIGNED_INT,
+            ),
+        };
+        self.accessors.push(json!({
+            "bufferView": buffer_view,
+            "componentType": component_type,
+            "count": indices.len(),
+            "type": "SCALAR",
+        }));
+        self.accessors.len() - 1
+    }
+
+    /// Writes the `.glb` file, with a single scene containing the given root nodes.
+    fn finish(mut self, roots: Vec<usize>) -> Result<Vec<u8>, GltfExportError> {
+        self.buffer.resize(self.buffer.len().next_multiple_of(4), 0);
+
+        let mut root = json!({
+            "asset": { "version": "2.0", "generator": "Bevy" },
+            "scene": 0,
+            "scenes": [{ "nodes": roots }],
+            "nodes": self.nodes,
+        });
+        for (key, values) in [
+            ("meshes", self.meshes),
+            ("materials", self.materials),
+            ("accessors", self.accessors),
+            ("bufferViews", self.buffer_views),
+        ] {
+            if !values.is_empty() {
+                root[key] = Value::Array(values);
+            }
+        }
+        if !self.buffer.is_empty() {
+            root["buffers"] = json!([{ "byteLength": self.buffer.len() }]);
+        }
+        if !self.extensions_used.is_empty() {
+            let mut extensions_used = self.extensions_used.into_iter().collect::<Vec<_>>();
+            extensions_used.sort();
+            root["extensionsUsed"] = json!(extensions_used);
+        }
+
+        let mut json = serde_json::to_vec(&root)?;
+        // Chunks must be aligned to 4 bytes, the JSON chunk is padded with spaces.
+        json.resize(json.len().next_multiple_of(4), b' ');
+
+        let mut chunks = vec![(GLB_JSON_CHUNK, json)];
+        if !self.buffer.is_empty() {
+            chunks.push((GLB_BIN_CHUNK, self.buffer));
+        }
+        let length = 12 + chunks.iter().map(|(_, data)| 8 + data.len()).sum::<usize>();
+
+        let mut glb = Vec::with_capacity(length);
+        glb.extend_from_slice(GLB_MAGIC);
+        glb.extend_from_slice(&GLB_VERSION.to_le_bytes());
+        glb.extend_from_slice(&(length as u32).to_le_bytes());
+        for (chunk_type, data) in chunks {
+            glb.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            glb.extend_from_slice(chunk_type);
+            glb.extend_from_slice(&data);
+        }
+        Ok(glb)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_asset::RenderAssetUsages;
+    use bevy_color::Color;
+    use bevy_math::{primitives::Cuboid, Vec3};
+
+    fn parse_glb(glb: &[u8]) -> (Value, &[u8]) {
+        assert_eq!(&glb[0..4], GLB_MAGIC);
+        assert_eq!(
+            u32::from_le_bytes(glb[8..12].try_into().unwrap()) as usize,
+            glb.len()
+        );
+        let json_length = u32::from_le_bytes(glb[12..16].try_into().unwrap()) as usize;
+        assert_eq!(&glb[16..20], GLB_JSON_CHUNK);
+        let json = serde_json::from_slice(&glb[20..20 + json_length]).unwrap();
+        let bin = &glb[20 + json_length..];
+        assert_eq!(&bin[4..8], GLB_BIN_CHUNK);
+        (json, &bin[8..])
+    }
+
+    #[test]
+    fn export_hierarchy() {
+        let mut world = World::new();
+        let mut meshes = Assets::<Mesh>::default();
+        let mut materials = Assets::<StandardMaterial>::default();
+        let mesh = meshes.add(
+            Mesh::from(Cuboid::default())
+                .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, vec![[1.0; 4]; 24]),
+        );
+        let material = materials.add(StandardMaterial {
+            base_color: Color::linear_rgb(1.0, 0.0, 0.0),
+            emissive: LinearRgba::rgb(4.0, 2.0, 0.0),
+            unlit: true,
+            ..Default::default()
+        });
+        let line = meshes.add(
+            Mesh::new(PrimitiveTopology::LineList, RenderAssetUsages::default())
+                .with_inserted_attribute(
+                    Mesh::ATTRIBUTE_POSITION,
+                    vec![[0.0, 0.0, 0.0], [1.0, 2.0, 3.0]],
+                ),
+        );
+
+        let root = world
+            .spawn((Name::new("Root"), Transform::from_xyz(1.0, 2.0, 3.0)))
+            .id();
+        world.spawn((
+            ChildOf(root),
+            Transform::from_scale(Vec3::splat(2.0)),
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(material.clone()),
+        ));
+        world.spawn((ChildOf(root), Mesh3d(mesh), MeshMaterial3d(material)));
+        world.spawn((ChildOf(root), Name::new("Empty")));
+        world.spawn(Mesh3d(line));
+
+        let glb = GltfExporter::new(&meshes, &materials)
+            .export_world(&world)
+            .unwrap();
+        let (json, bin) = parse_glb(&glb);
+
+        assert_eq!(json["scenes"][0]["nodes"].as_array().unwrap().len(), 2);
+        let nodes = json["nodes"].as_array().unwrap();
+        let root = nodes.iter().find(|node| node["name"] == "Root").unwrap();
+        assert_eq!(root["translation"], json!([1.0, 2.0, 3.0]));
+        // The child without a mesh isn't exported.
+        let children = root["children"].as_array().unwrap();
+        assert_eq!(children.len(), 2);
+        // Both children share the same mesh and material, so they share the glTF mesh.
+        let cube = &nodes[children[0].as_u64().unwrap() as usize]["mesh"];
+        assert_eq!(&nodes[children[1].as_u64().unwrap() as usize]["mesh"], cube);
+
+        let meshes = json["meshes"].as_array().unwrap();
+        assert_eq!(meshes.len(), 2);
+        let cube_index = cube.as_u64().unwrap() as usize;
+        let cube = &meshes[cube_index]["primitives"][0];
+        assert_eq!(cube["mode"], 4);
+        assert_eq!(cube["material"], 0);
+        let position =
+            &json["accessors"][cube["attributes"]["POSITION"].as_u64().unwrap() as usize];
+        assert_eq!(position["count"], 24);
+        assert_eq!(position["min"], json!([-0.5, -0.5, -0.5]));
+        assert!(cube["attributes"]["COLOR_0"].is_u64());
+        let line = &meshes[1 - cube_index]["primitives"][0];
+        assert_eq!(line["mode"], 1);
+        assert!(line.get("indices").is_none());
+
+        let material = &json["materials"][0];
+        assert_eq!(
+            material["pbrMetallicRoughness"]["baseColorFactor"],
+            json!([1.0, 0.0, 0.0, 1.0])
+        );
+        assert_eq!(material["emissiveFactor"], json!([1.0, 0.5, 0.0]));
+        assert_eq!(
+            material["extensions"]["KHR_materials_emissive_strength"]["emissiveStrength"],
+            4.0
+        );
+        assert_eq!(
+            json["extensionsUsed"],
+            json!(["KHR_materials_emissive_strength", "KHR_materials_unlit"])
+        );
+
+        assert_eq!(json["buffers"][0]["byteLength"], bin.len());
+        for view in json["bufferViews"].as_array().unwrap() {
+            let offset = view["byteOffset"].as_u64().unwrap() as usize;
+            assert_eq!(offset % 4, 0);
+            assert!(offset + view["byteLength"].as_u64().unwrap() as usize <= bin.len());
+        }
+    }
+}
@@ -129,6 +129,7 @@
 
 mod assets;
 mod convert_coordinates;
+mod exporter;
 mod label;
 mod loader;
 mod vertex_attributes;
@@ -155,7 +156,7 @@ pub mod prelude {
     pub use crate::{assets::Gltf, assets::GltfExtras, label::GltfAssetLabel};
 }
 
-pub use {assets::*, label::GltfAssetLabel, loader::*};
+pub use {assets::*, exporter::*, label::GltfAssetLabel, loader::*};
 
 // Has to store an Arc<Mutex<...>> as there is no other way to mutate fields of asset loaders.
 /// Stores default [`ImageSamplerDescriptor`] in main world.
@@ -218,6 +218,13 @@ pub struct GltfLoaderSettings {
     ///
     /// If `None`, uses the global default set by [`GltfPlugin::use_model_forward_direction`](crate::GltfPlugin::use_model_forward_direction).
     pub use_model_forward_direction: Option<bool>,
+    /// If set, the linearly interpolated translation, rotation and scale
+    /// tracks of the animations are compressed with these settings, which
+    /// reduces their memory usage at the cost of some precision.
+    ///
+    /// If `None`, the tracks are loaded without compression.
+    #[cfg(feature = "bevy_animation")]
+    pub animation_compression: Option<bevy_animation::compression::AnimationCompression>,
 }
 
 impl Default for GltfLoaderSettings {
@@ -232,6 +239,8 @@ impl Default for GltfLoaderSettings {
             default_sampler: None,
             override_sampler: false,
             use_model_forward_direction: None,
+            #[cfg(feature = "bevy_animation")]
+            animation_compression: None,
         }
     }
 }
@@ -339,18 +348,21 @@ impl GltfLoader {
                                 } else {
                                     match interpolation {
                                         gltf::animation::Interpolation::Linear => {
-                                            UnevenSampleAutoCurve::new(
-                                                keyframe_timestamps.into_iter().zip(translations),
-                                            )
-                                            .ok()
-                                            .map(
-                                                |curve| {
-                                                    VariableCurve::new(AnimatableCurve::new(
-                                                        translation_property,
-                                                        curve,
-                                                    ))
-                                                },
-                                            )
+                                            let keyframes =
+                                                keyframe_timestamps.into_iter().zip(translations);
+                                            match settings.animation_compression {
+                                                Some(compression) => compression
+                                                    .compress_vec3(translation_property, keyframes)
+                                                    .ok(),
+                                                None => UnevenSampleAutoCurve::new(keyframes)
+                                                    .ok()
+                                                    .map(|curve| {
+                                                        VariableCurve::new(AnimatableCurve::new(
+                                                            translation_property,
+                                                            curve,
+                                                        ))
+                                                    }),
+                                            }
                                         }
                                         gltf::animation::Interpolation::Step => {
                                             SteppedKeyframeCurve::new(
@@ -405,18 +417,21 @@ impl GltfLoader {
                                 } else {
                                     match interpolation {
                                         gltf::animation::Interpolation::Linear => {
-                                            UnevenSampleAutoCurve::new(
-                                                keyframe_timestamps.into_iter().zip(rotations),
-                                            )
-                                            .ok()
-                                            .map(
-                                                |curve| {
-                                                    VariableCurve::new(AnimatableCurve::new(
-                                                        rotation_property,
-                                                        curve,
-                                                    ))
-                                                },
-                                            )
+                                            let keyframes =
+                                                keyframe_timestamps.into_iter().zip(rotations);
+                                            match settings.animation_compression {
+                                                Some(compression) => compression
+                                                    .compress_rotation(rotation_property, keyframes)
+                                                    .ok(),
+                                                None => UnevenSampleAutoCurve::new(keyframes)
+                                                    .ok()
+                                                    .map(|curve| {
+                                                        VariableCurve::new(AnimatableCurve::new(
+                                                            rotation_property,
+                                                            curve,
+                                                        ))
+                                                    }),
+                                            }
                                         }
                                         gltf::animation::Interpolation::Step => {
                                             SteppedKeyframeCurve::new(
@@ -461,18 +476,21 @@ impl GltfLoader {
                                 } else {
                                     match interpolation {
                                         gltf::animation::Interpolation::Linear => {
-                                            UnevenSampleAutoCurve::new(
-                                                keyframe_timestamps.into_iter().zip(scales),
-                                            )
-                                            .ok()
-                                            .map(
-                                                |curve| {
-                                                    VariableCurve::new(AnimatableCurve::new(
-                                                        scale_property,
-                                                        curve,
-                                                    ))
-                                                },
-                                            )
+                                            let keyframes =
+                                                keyframe_timestamps.into_iter().zip(scales);
+                                            match settings.animation_compression {
+                                                Some(compression) => compression
+                                                    .compress_vec3(scale_property, keyframes)
+                                                    .ok(),
+                                                None => UnevenSampleAutoCurve::new(keyframes)
+                                                    .ok()
+                                                    .map(|curve| {
+                                                        VariableCurve::new(AnimatableCurve::new(
+                                                            scale_property,
+                                                            curve,
+                                                        ))
+                                                    }),
+                                            }
                                         }
                                         gltf::animation::Interpolation::Step => {
                                             SteppedKeyframeCurve::new(
@@ -0,0 +1,192 @@
+use crate::{HidContext, HidSettings, OpenHidDevice};
+use alloc::ffi::CString;
+use bevy_ecs::prelude::*;
+use bevy_input::hid::{
+    HidDeviceConnection, HidDeviceConnectionEvent, HidDeviceDescriptor, HidInputReport,
+    HidOutputReport,
+};
+use bevy_platform::{cell::SyncCell, time::Instant};
+use tracing::{debug, warn};
+
+/// The size of the buffer the input reports are read into, larger than any report of a full-speed
+/// USB device.
+const MAX_REPORT_SIZE: usize = 4096;
+
+fn descriptor(info: &hidapi::DeviceInfo) -> HidDeviceDescriptor {
+    HidDeviceDescriptor {
+        vendor_id: info.vendor_id(),
+        product_id: info.product_id(),
+        usage_page: info.usage_page(),
+        usage: info.usage(),
+        product_name: info.product_string().map(Into::into),
+        manufacturer_name: info.manufacturer_string().map(Into::into),
+        serial_number: info.serial_number().map(Into::into),
+    }
+}
+
+/// Refreshes the connected devices when due, and sends the input reports of the opened devices.
+pub fn hid_device_system(
+    mut commands: Commands,
+    mut context: ResMut<HidContext>,
+    settings: Res<HidSettings>,
+    mut connection_events: MessageWriter<HidDeviceConnectionEvent>,
+    mut reports: MessageWriter<HidInputReport>,
+    mut buffer: Local<Vec<u8>>,
+) {
+    let context = &mut *context;
+    let now = Instant::now();
+    let refresh_due = context
+        .last_refresh
+        .is_none_or(|last_refresh| now - last_refresh >= settings.refresh_interval);
+    if refresh_due || settings.is_changed() {
+        context.last_refresh = Some(now);
+        refresh_devices(&mut commands, context, &settings, &mut connection_events);
+    }
+
+    buffer.resize(MAX_REPORT_SIZE, 0);
+    let mut lost = Vec::new();
+    for (path, open) in &mut context.devices {
+        loop {
+            match open.device.get().read(&mut buffer) {
+                Ok(0) => break,
+                Ok(len) => {
+                    reports.write(HidInputReport {
+                        device: open.entity,
+                        data: buffer[..len].to_vec(),
+                    });
+                }
+                Err(err) => {
+                    warn!("Failed to read HID device {}. {}", open.entity, err);
+                    lost.push(path.clone());
+                    break;
+                }
+            }
+        }
+    }
+    // Lost devices are opened again at the next refresh if they are still connected
+    for path in lost {
+        disconnect(context, &path, &mut connection_events);
+    }
+}
+
+/// Opens the new devices matching the settings, and closes the removed devices and those that
+/// don't match the settings anymore.
+fn refresh_devices(
+    commands: &mut Commands,
+    context: &mut HidContext,
+    settings: &HidSettings,
+    connection_events: &mut MessageWriter<HidDeviceConnectionEvent>,
+) {
+    let api = context.api.get();
+    if let Err(err) = api.refresh_devices() {
+        warn!("Failed to refresh the HID devices. {}", err);
+        return;
+    }
+    let found: Vec<(CString, HidDeviceDescriptor)> = api
+        .device_list()
+        .map(|info| (info.path().to_owned(), descriptor(info)))
+        .filter(|(_, descriptor)| settings.matches(descriptor))
+        .collect();
+
+    let removed: Vec<CString> = context
+        .devices
+        .keys()
+        .filter(|path| !found.iter().any(|(found, _)| found == *path))
+        .cloned()
+        .collect();
+    for path in removed {
+        disconnect(context, &path, connection_events);
+    }
+    context
+        .failed
+        .retain(|path| found.iter().any(|(found, _)| found == path));
+
+    for (path, descriptor) in found {
+        if context.devices.contains_key(&path) || context.failed.contains(&path) {
+            continue;
+        }
+        let device = match context.api.get().open_path(&path) {
+            Ok(device) => device,
+            Err(err) => {
+                warn!(
+                    "Failed to open HID device {:?}. {}",
+                    descriptor.product_name, err
+                );
+                context.failed.push(path);
+                continue;
+            }
+        };
+        if let Err(err) = device.set_blocking_mode(false) {
+            warn!(
+                "Failed to open HID device {:?}. {}",
+                descriptor.product_name, err
+            );
+            context.failed.push(path);
+            continue;
+        }
+
+        let entity = match context
+            .disconnected
+            .iter()
+            .position(|(disconnected, _)| *disconnected == descriptor)
+        {
+            Some(index) => context.disconnected.swap_remove(index).1,
+            None => commands.spawn_empty().id(),
+        };
+        debug!("Opened HID device {entity}: {:?}", descriptor);
+        connection_events.write(HidDeviceConnectionEvent {
+            device: entity,
+            connection: HidDeviceConnection::Connected(descriptor.clone()),
+        });
+        context.devices.insert(
+            path,
+            OpenHidDevice {
+                entity,
+                descriptor,
+                device: SyncCell::new(device),
+            },
+        );
+    }
+}
+
+/// Closes a device, keeping its entity in case it reconnects.
+fn disconnect(
+    context: &mut HidContext,
+    path: &CString,
+    connection_events: &mut MessageWriter<HidDeviceConnectionEvent>,
+) {
+    let Some(open) = context.devices.remove(path) else {
+        return;
+    };
+    connection_events.write(HidDeviceConnectionEvent {
+        device: open.entity,
+        connection: HidDeviceConnection::Disconnected,
+    });
+    context.disconnected.push((open.descriptor, open.entity));
+}
+
+/// Sends the requested output reports to the opened devices.
+pub fn hid_output_system(
+    mut context: ResMut<HidContext>,
+    mut requests: MessageReader<HidOutputReport>,
+) {
+    for request in requests.read() {
+        let Some(open) = context
+            .devices
+            .values_mut()
+            .find(|open| open.entity == request.device)
+        else {
+            warn!(
+                "Tried to send an output report to HID device {}, which isn't connected.",
+                request.device
+            );
+            continue;
+        };
+        if let Err(err) = open.device.get().write(&request.data) {
+            warn!(
+                "Failed to send an output report to HID device {}. {}",
+                request.device, err
+            );
+        }
+    }
+}
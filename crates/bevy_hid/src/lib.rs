@@ -0,0 +1,206 @@
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![forbid(unsafe_code)]
+#![doc(
+    html_logo_url = "https://bevy.org/assets/icon.png",
+    html_favicon_url = "https://bevy.org/assets/icon.png"
+)]
+
+//! Raw HID device input for Bevy.
+//!
+//! This crate is built on top of [hidapi](hidapi), and sends the input reports of HID devices like
+//! flight sticks, racing wheels and custom hardware as [`HidInputReport`] events of `bevy_input`.
+//! It is supported on Windows, macOS and Linux.
+//!
+//! Only the devices matching the [`HidDeviceFilter`]s of the [`HidSettings`] are opened. By default,
+//! these are joysticks, multi-axis controllers and simulation controls: gamepads are left to
+//! `bevy_gilrs`, and keyboards and mice can't be opened on most platforms.
+//!
+//! [`HidInputReport`]: bevy_input::hid::HidInputReport
+
+extern crate alloc;
+
+mod hid_system;
+
+use alloc::ffi::CString;
+use bevy_app::{App, Plugin, PostUpdate, PreUpdate};
+use bevy_ecs::prelude::*;
+use bevy_input::{hid::HidDeviceDescriptor, InputSystems};
+use bevy_platform::{cell::SyncCell, collections::HashMap, time::Instant};
+use core::time::Duration;
+use hid_system::{hid_device_system, hid_output_system};
+use tracing::error;
+
+/// The HID input prelude.
+///
+/// This includes the most common types in this crate, re-exported for your convenience.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{HidDeviceFilter, HidPlugin, HidSettings};
+}
+
+/// Selects HID devices by their [`HidDeviceDescriptor`].
+///
+/// Fields left to [`None`] match any device.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct HidDeviceFilter {
+    /// The USB vendor ID of the devices.
+    pub vendor_id: Option<u16>,
+    /// The USB product ID of the devices.
+    pub product_id: Option<u16>,
+    /// The usage page of the top-level collection of the devices.
+    pub usage_page: Option<u16>,
+    /// The usage of the top-level collection of the devices.
+    pub usage: Option<u16>,
+}
+
+impl HidDeviceFilter {
+    /// Joysticks, such as flight sticks.
+    pub const JOYSTICK: Self = Self::usage(0x01, 0x04);
+
+    /// Multi-axis controllers, such as 3D mice.
+    pub const MULTI_AXIS_CONTROLLER: Self = Self::usage(0x01, 0x08);
+
+    /// Simulation controls, such as racing wheels, pedals and flight yokes.
+    pub const SIMULATION_CONTROLS: Self = Self {
+        vendor_id: None,
+        product_id: None,
+        usage_page: Some(0x02),
+        usage: None,
+    };
+
+    /// Matches the devices whose top-level collection has the given usage.
+    pub const fn usage(usage_page: u16, usage: u16) -> Self {
+        Self {
+            vendor_id: None,
+            product_id: None,
+            usage_page: Some(usage_page),
+            usage: Some(usage),
+        }
+    }
+
+    /// Matches the devices with the given USB vendor and product IDs.
+    pub const fn product(vendor_id: u16, product_id: u16) -> Self {
+        Self {
+            vendor_id: Some(vendor_id),
+            product_id: Some(product_id),
+            usage_page: None,
+            usage: None,
+        }
+    }
+
+    /// Returns `true` if the device matches the filter.
+    pub fn matches(&self, descriptor: &HidDeviceDescriptor) -> bool {
+        [
+            (self.vendor_id, descriptor.vendor_id),
+            (self.product_id, descriptor.product_id),
+            (self.usage_page, descriptor.usage_page),
+            (self.usage, descriptor.usage),
+        ]
+        .into_iter()
+        .all(|(filter, value)| filter.is_none_or(|filter| filter == value))
+    }
+}
+
+/// Settings of the HID devices opened by the [`HidPlugin`].
+///
+/// They can be changed at runtime: devices that don't match the filters anymore are closed at the
+/// next refresh of the devices.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct HidSettings {
+    /// The filters selecting the devices to open. A device is opened if it matches any of them.
+    pub filters: Vec<HidDeviceFilter>,
+    /// How often the list of connected devices is refreshed.
+    pub refresh_interval: Duration,
+}
+
+impl HidSettings {
+    /// Returns `true` if the device matches any of the filters.
+    pub fn matches(&self, descriptor: &HidDeviceDescriptor) -> bool {
+        self.filters.iter().any(|filter| filter.matches(descriptor))
+    }
+}
+
+impl Default for HidSettings {
+    fn default() -> Self {
+        Self {
+            filters: vec![
+                HidDeviceFilter::JOYSTICK,
+                HidDeviceFilter::MULTI_AXIS_CONTROLLER,
+                HidDeviceFilter::SIMULATION_CONTROLS,
+            ],
+            refresh_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// An opened HID device.
+struct OpenHidDevice {
+    entity: Entity,
+    descriptor: HidDeviceDescriptor,
+    device: SyncCell<hidapi::HidDevice>,
+}
+
+/// The hidapi context and the devices it opened.
+#[derive(Resource)]
+pub(crate) struct HidContext {
+    api: SyncCell<hidapi::HidApi>,
+    /// The opened devices, by path.
+    devices: HashMap<CString, OpenHidDevice>,
+    /// The entities of the disconnected devices, reused if they reconnect.
+    disconnected: Vec<(HidDeviceDescriptor, Entity)>,
+    /// The devices that couldn't be opened, which aren't retried until they reconnect.
+    failed: Vec<CString>,
+    last_refresh: Option<Instant>,
+}
+
+/// Plugin that opens HID devices and sends their input reports as events.
+///
+/// This plugin isn't needed for gamepads, which are handled by `bevy_gilrs`. The devices it opens
+/// can be configured with the [`HidSettings`] resource.
+#[derive(Default)]
+pub struct HidPlugin;
+
+impl Plugin for HidPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HidSettings>();
+        match hidapi::HidApi::new_without_enumerate() {
+            Ok(api) => {
+                app.insert_resource(HidContext {
+                    api: SyncCell::new(api),
+                    devices: HashMap::default(),
+                    disconnected: Vec::new(),
+                    failed: Vec::new(),
+                    last_refresh: None,
+                })
+                .add_systems(PreUpdate, hid_device_system.before(InputSystems))
+                .add_systems(PostUpdate, hid_output_system);
+            }
+            Err(err) => error!("Failed to start hidapi. {}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HidDeviceFilter, HidSettings};
+    use bevy_input::hid::HidDeviceDescriptor;
+
+    #[test]
+    fn default_filters() {
+        let settings = HidSettings::default();
+        let device = |usage_page, usage| HidDeviceDescriptor {
+            vendor_id: 0x044f,
+            product_id: 0xb10a,
+            usage_page,
+            usage,
+            ..Default::default()
+        };
+        assert!(settings.matches(&device(0x01, 0x04)));
+        assert!(settings.matches(&device(0x02, 0xbb)));
+        // Gamepads and keyboards
+        assert!(!settings.matches(&device(0x01, 0x05)));
+        assert!(!settings.matches(&device(0x01, 0x06)));
+
+        assert!(HidDeviceFilter::product(0x044f, 0xb10a).matches(&device(0xff00, 0x01)));
+    }
+}
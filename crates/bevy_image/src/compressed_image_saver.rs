@@ -1,11 +1,111 @@
 use crate::{Image, ImageFormat, ImageFormatSetting, ImageLoader, ImageLoaderSettings};
 
 use bevy_asset::saver::{AssetSaver, SavedAsset};
+use core::num::NonZeroU8;
 use futures_lite::AsyncWriteExt;
+use ktx2::{
+    ChannelTypeQualifiers, ColorModel, ColorPrimaries, DataFormatFlags, DfdBlockHeaderBasic,
+    DfdHeader, Header, Index, LevelIndex, SampleInformation, TransferFunction,
+};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use wgpu_types::TextureFormat;
 
+/// An [`AssetSaver`] that compresses images into GPU-friendly Basis Universal textures when processing assets, so that
+/// they don't need to be shipped (and uploaded to the GPU) uncompressed.
+///
+/// By default, images are compressed to UASTC, which has a high quality, and stored in a KTX2 container. ETC1S
+/// has a lower quality but results in much smaller files, which are stored as `.basis` files. Both are transcoded
+/// to a format supported by the GPU when loaded. The compression can be configured per asset in its `.meta` file,
+/// using [`CompressedImageSaverSettings`].
 pub struct CompressedImageSaver;
 
+/// Settings for the [`CompressedImageSaver`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CompressedImageSaverSettings {
+    /// The Basis Universal format to compress the image to.
+    pub format: CompressedImageFormat,
+    /// The quality of the compression. Higher qualities take longer to compress.
+    pub quality: CompressedImageQuality,
+    /// The color space of the image data.
+    pub color_space: CompressedImageColorSpace,
+    /// Whether to generate mipmaps for the image.
+    pub generate_mipmaps: bool,
+}
+
+impl Default for CompressedImageSaverSettings {
+    fn default() -> Self {
+        Self {
+            format: CompressedImageFormat::default(),
+            quality: CompressedImageQuality::default(),
+            color_space: CompressedImageColorSpace::default(),
+            generate_mipmaps: true,
+        }
+    }
+}
+
+/// The Basis Universal format used by the [`CompressedImageSaver`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompressedImageFormat {
+    /// High quality compression, roughly 8 bits per pixel. Saved as a KTX2 file.
+    #[default]
+    Uastc,
+    /// Low quality compression with much smaller files, roughly 1 to 2 bits per pixel. Saved as a `.basis` file.
+    Etc1s,
+}
+
+/// The quality of the compression done by the [`CompressedImageSaver`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompressedImageQuality {
+    /// The fastest compression, with the most visible artifacts.
+    VeryLow,
+    /// Fast compression, with some visible artifacts.
+    Low,
+    /// A balance between compression time and quality.
+    #[default]
+    Medium,
+    /// Slow compression, with few visible artifacts.
+    High,
+    /// The slowest compression, with the best quality.
+    VeryHigh,
+}
+
+impl CompressedImageQuality {
+    /// The UASTC packing level, from 0 (fastest) to 4 (slowest).
+    fn uastc_quality_level(self) -> u32 {
+        match self {
+            CompressedImageQuality::VeryLow => 0,
+            CompressedImageQuality::Low => 1,
+            CompressedImageQuality::Medium => 2,
+            CompressedImageQuality::High => 3,
+            CompressedImageQuality::VeryHigh => 4,
+        }
+    }
+
+    /// The ETC1S quality level, from 1 (lowest) to 255 (highest).
+    fn etc1s_quality_level(self) -> u32 {
+        match self {
+            CompressedImageQuality::VeryLow => 1,
+            CompressedImageQuality::Low => 64,
+            CompressedImageQuality::Medium => 128,
+            CompressedImageQuality::High => 192,
+            CompressedImageQuality::VeryHigh => 255,
+        }
+    }
+}
+
+/// The color space of the image compressed by the [`CompressedImageSaver`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompressedImageColorSpace {
+    /// Use the color space of the loaded image, which is determined by the [`ImageLoaderSettings`] of the source.
+    #[default]
+    Auto,
+    /// The image contains colors, in the sRGB color space.
+    Srgb,
+    /// The image contains linear data, such as normal maps.
+    Linear,
+}
+
 #[non_exhaustive]
 #[derive(Debug, Error)]
 pub enum CompressedImageSaverError {
@@ -13,12 +113,16 @@ pub enum CompressedImageSaverError {
     Io(#[from] std::io::Error),
     #[error("Cannot compress an uninitialized image")]
     UninitializedImage,
+    #[error("Cannot compress an image with format {0:?}")]
+    UnsupportedFormat(TextureFormat),
+    #[error("Failed to compress image: {0}")]
+    CompressionFailed(String),
 }
 
 impl AssetSaver for CompressedImageSaver {
     type Asset = Image;
 
-    type Settings = ();
+    type Settings = CompressedImageSaverSettings;
     type OutputLoader = ImageLoader;
     type Error = CompressedImageSaverError;
 
@@ -26,46 +130,55 @@ impl AssetSaver for CompressedImageSaver {
         &self,
         writer: &mut bevy_asset::io::Writer,
         image: SavedAsset<'_, Self::Asset>,
-        _settings: &Self::Settings,
+        settings: &Self::Settings,
     ) -> Result<ImageLoaderSettings, Self::Error> {
-        let is_srgb = image.texture_descriptor.format.is_srgb();
+        let is_srgb = match settings.color_space {
+            CompressedImageColorSpace::Auto => image.texture_descriptor.format.is_srgb(),
+            CompressedImageColorSpace::Srgb => true,
+            CompressedImageColorSpace::Linear => false,
+        };
 
-        let compressed_basis_data = {
-            let mut compressor_params = basis_universal::CompressorParams::new();
-            compressor_params.set_basis_format(basis_universal::BasisTextureFormat::UASTC4x4);
-            compressor_params.set_generate_mipmaps(true);
-            let color_space = if is_srgb {
-                basis_universal::ColorSpace::Srgb
-            } else {
-                basis_universal::ColorSpace::Linear
-            };
-            compressor_params.set_color_space(color_space);
-            compressor_params.set_uastc_quality_level(basis_universal::UASTC_QUALITY_DEFAULT);
-
-            let mut source_image = compressor_params.source_image_mut(0);
-            let size = image.size();
-            let Some(ref data) = image.data else {
-                return Err(CompressedImageSaverError::UninitializedImage);
-            };
-            source_image.init(data, size.x, size.y, 4);
-
-            let mut compressor = basis_universal::Compressor::new(4);
-            #[expect(
-                unsafe_code,
-                reason = "The basis-universal compressor cannot be interacted with except through unsafe functions"
-            )]
-            // SAFETY: the CompressorParams are "valid" to the best of our knowledge. The basis-universal
-            // library bindings note that invalid params might produce undefined behavior.
-            unsafe {
-                compressor.init(&compressor_params);
-                compressor.process().unwrap();
+        // The compressor expects 8-bit RGBA data. The color space is passed to the compressor separately, so
+        // the data is converted without changing whether it is sRGB or linear.
+        let converted;
+        let source = match image.texture_descriptor.format {
+            TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => image.get(),
+            format => {
+                let rgba_format = if format.is_srgb() {
+                    TextureFormat::Rgba8UnormSrgb
+                } else {
+                    TextureFormat::Rgba8Unorm
+                };
+                converted = image
+                    .convert(rgba_format)
+                    .ok_or(CompressedImageSaverError::UnsupportedFormat(format))?;
+                &converted
             }
-            compressor.basis_file().to_vec()
         };
+        let Some(ref data) = source.data else {
+            return Err(CompressedImageSaverError::UninitializedImage);
+        };
+        let size = source.size();
+        let has_alpha = data.chunks_exact(4).any(|pixel| pixel[3] != u8::MAX);
+
+        let compressed_basis_data = compress_basis(data, size.x, size.y, settings, is_srgb)?;
 
-        writer.write_all(&compressed_basis_data).await?;
+        let format = match settings.format {
+            CompressedImageFormat::Uastc => {
+                let levels = uastc_levels_from_basis(&compressed_basis_data)
+                    .map_err(CompressedImageSaverError::CompressionFailed)?;
+                writer
+                    .write_all(&uastc_ktx2(size.x, size.y, &levels, has_alpha, is_srgb))
+                    .await?;
+                ImageFormat::Ktx2
+            }
+            CompressedImageFormat::Etc1s => {
+                writer.write_all(&compressed_basis_data).await?;
+                ImageFormat::Basis
+            }
+        };
         Ok(ImageLoaderSettings {
-            format: ImageFormatSetting::Format(ImageFormat::Basis),
+            format: ImageFormatSetting::Format(format),
             is_srgb,
             sampler: image.sampler.clone(),
             asset_usage: image.asset_usage,
@@ -74,3 +187,239 @@ impl AssetSaver for CompressedImageSaver {
         })
     }
 }
+
+/// Compresses 8-bit RGBA data to a `.basis` file containing a single image.
+fn compress_basis(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    settings: &CompressedImageSaverSettings,
+    is_srgb: bool,
+) -> Result<Vec<u8>, CompressedImageSaverError> {
+    let mut compressor_params = basis_universal::CompressorParams::new();
+    match settings.format {
+        CompressedImageFormat::Uastc => {
+            compressor_params.set_basis_format(basis_universal::BasisTextureFormat::UASTC4x4);
+            compressor_params.set_uastc_quality_level(settings.quality.uastc_quality_level());
+        }
+        CompressedImageFormat::Etc1s => {
+            compressor_params.set_basis_format(basis_universal::BasisTextureFormat::ETC1S);
+            compressor_params.set_etc1s_quality_level(settings.quality.etc1s_quality_level());
+        }
+    }
+    compressor_params.set_generate_mipmaps(settings.generate_mipmaps);
+    let color_space = if is_srgb {
+        basis_universal::ColorSpace::Srgb
+    } else {
+        basis_universal::ColorSpace::Linear
+    };
+    compressor_params.set_color_space(color_space);
+
+    let mut source_image = compressor_params.source_image_mut(0);
+    source_image.init(data, width, height, 4);
+
+    let mut compressor = basis_universal::Compressor::new(4);
+    #[expect(
+        unsafe_code,
+        reason = "The basis-universal compressor cannot be interacted with except through unsafe functions"
+    )]
+    // SAFETY: the CompressorParams are "valid" to the best of our knowledge. The basis-universal
+    // library bindings note that invalid params might produce undefined behavior.
+    unsafe {
+        compressor.init(&compressor_params);
+        compressor
+            .process()
+            .map_err(|error| CompressedImageSaverError::CompressionFailed(format!("{error:?}")))?;
+    }
+    Ok(compressor.basis_file().to_vec())
+}
+
+/// The size of a UASTC block, which covers 4x4 pixels.
+const UASTC_BLOCK_SIZE: usize = 16;
+
+/// Extracts the UASTC blocks of each mip level from a `.basis` file containing a single UASTC image.
+///
+/// Unlike ETC1S slices, UASTC slices are stored as raw blocks, so they can be copied as-is into a KTX2 file.
+fn uastc_levels_from_basis(basis: &[u8]) -> Result<Vec<Vec<u8>>, String> {
+    /// The size of the `.basis` file header.
+    const HEADER_SIZE: usize = 77;
+    /// The size of a slice description.
+    const SLICE_DESC_SIZE: usize = 23;
+
+    let read = |offset: usize, len: usize| -> Result<u32, String> {
+        let bytes = basis
+            .get(offset..offset + len)
+            .ok_or_else(|| "Unexpected end of basis file".to_string())?;
+        Ok(bytes
+            .iter()
+            .rev()
+            .fold(0, |value, &byte| (value << 8) | u32::from(byte)))
+    };
+    if basis.len() < HEADER_SIZE || read(0, 2)? != u32::from_le_bytes([b's', b'B', 0, 0]) {
+        return Err("Invalid basis file header".to_string());
+    }
+    let total_slices = read(14, 3)? as usize;
+    let slice_desc_offset = read(65, 4)? as usize;
+
+    let mut levels = Vec::new();
+    for slice in 0..total_slices {
+        let desc = slice_desc_offset + slice * SLICE_DESC_SIZE;
+        let (image_index, level_index) = (read(desc, 3)?, read(desc + 3, 1)? as usize);
+        if image_index != 0 {
+            return Err("Basis files with multiple images are not supported".to_string());
+        }
+        let (offset, size) = (read(desc + 13, 4)? as usize, read(desc + 17, 4)? as usize);
+        let data = basis
+            .get(offset..offset + size)
+            .ok_or_else(|| format!("Slice {slice} is out of bounds"))?;
+        if level_index != levels.len() || size % UASTC_BLOCK_SIZE != 0 {
+            return Err(format!("Slice {slice} is not a valid UASTC mip level"));
+        }
+        levels.push(data.to_vec());
+    }
+    Ok(levels)
+}
+
+/// Writes the UASTC blocks of each mip level of an image to a KTX2 file.
+fn uastc_ktx2(
+    width: u32,
+    height: u32,
+    levels: &[Vec<u8>],
+    has_alpha: bool,
+    is_srgb: bool,
+) -> Vec<u8> {
+    let dfd_block_size =
+        DfdHeader::LENGTH + DfdBlockHeaderBasic::LENGTH + SampleInformation::LENGTH;
+    let dfd_offset = Header::LENGTH + levels.len() * LevelIndex::LENGTH;
+    let dfd_length = 4 + dfd_block_size;
+
+    // Level data is stored from the smallest to the largest mip level, with each level aligned to the block size.
+    let mut level_index = vec![None; levels.len()];
+    let mut level_data = Vec::new();
+    let mut offset = dfd_offset + dfd_length;
+    for (level, data) in levels.iter().enumerate().rev() {
+        let padding = offset.next_multiple_of(UASTC_BLOCK_SIZE) - offset;
+        level_data.resize(level_data.len() + padding, 0);
+        offset += padding;
+        level_index[level] = Some(LevelIndex {
+            byte_offset: offset as u64,
+            byte_length: data.len() as u64,
+            uncompressed_byte_length: data.len() as u64,
+        });
+        level_data.extend_from_slice(data);
+        offset += data.len();
+    }
+
+    let header = Header {
+        // UASTC doesn't have a Vulkan format, so it is described by the data format descriptor instead.
+        format: None,
+        type_size: 1,
+        pixel_width: width,
+        pixel_height: height,
+        pixel_depth: 0,
+        layer_count: 0,
+        face_count: 1,
+        level_count: levels.len() as u32,
+        supercompression_scheme: None,
+        index: Index {
+            dfd_byte_offset: dfd_offset as u32,
+            dfd_byte_length: dfd_length as u32,
+            kvd_byte_offset: 0,
+            kvd_byte_length: 0,
+            sgd_byte_offset: 0,
+            sgd_byte_length: 0,
+        },
+    };
+    let block_dimension = NonZeroU8::new(4).unwrap();
+    let dfd_header = DfdBlockHeaderBasic {
+        color_model: Some(ColorModel::UASTC),
+        color_primaries: Some(ColorPrimaries::BT709),
+        transfer_function: Some(if is_srgb {
+            TransferFunction::SRGB
+        } else {
+            TransferFunction::Linear
+        }),
+        flags: DataFormatFlags::STRAIGHT_ALPHA,
+        texel_block_dimensions: [
+            block_dimension,
+            block_dimension,
+            NonZeroU8::MIN,
+            NonZeroU8::MIN,
+        ],
+        bytes_planes: [UASTC_BLOCK_SIZE as u8, 0, 0, 0, 0, 0, 0, 0],
+    };
+    let sample = SampleInformation {
+        bit_offset: 0,
+        bit_length: NonZeroU8::new(128).unwrap(),
+        // The UASTC RGB and RGBA channel types.
+        channel_type: if has_alpha { 3 } else { 0 },
+        channel_type_qualifiers: ChannelTypeQualifiers::empty(),
+        sample_positions: [0; 4],
+        lower: 0,
+        upper: u32::MAX,
+    };
+
+    let mut ktx2 = Vec::with_capacity(offset);
+    ktx2.extend_from_slice(&header.as_bytes());
+    for level in level_index.into_iter().flatten() {
+        ktx2.extend_from_slice(&level.as_bytes());
+    }
+    ktx2.extend_from_slice(&(dfd_length as u32).to_le_bytes());
+    ktx2.extend_from_slice(&DfdHeader::BASIC.as_bytes(dfd_block_size as u16));
+    ktx2.extend_from_slice(&dfd_header.as_bytes());
+    ktx2.extend_from_slice(&sample.as_bytes());
+    ktx2.extend_from_slice(&level_data);
+    ktx2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ktx2_get_texture_format, DataFormat, TextureError, TranscodeFormat};
+
+    #[test]
+    fn uastc_ktx2_round_trip() {
+        // An 8x4 image with 2 mip levels.
+        let levels = vec![vec![1; 2 * UASTC_BLOCK_SIZE], vec![2; UASTC_BLOCK_SIZE]];
+        let ktx2 = uastc_ktx2(8, 4, &levels, true, true);
+
+        let reader = ktx2::Reader::new(&ktx2).unwrap();
+        let header = reader.header();
+        assert_eq!((header.pixel_width, header.pixel_height), (8, 4));
+        assert_eq!(header.level_count, 2);
+        for (level, expected) in reader.levels().zip(&levels) {
+            assert_eq!(level.data, expected.as_slice());
+            let offset = level.data.as_ptr() as usize - ktx2.as_ptr() as usize;
+            assert_eq!(offset % UASTC_BLOCK_SIZE, 0);
+        }
+        assert!(matches!(
+            ktx2_get_texture_format(&reader, true),
+            Err(TextureError::FormatRequiresTranscodingError(
+                TranscodeFormat::Uastc(DataFormat::Rgba)
+            ))
+        ));
+    }
+
+    #[test]
+    fn uastc_levels_from_encoder_output() {
+        // An opaque 8x8 gradient, which has 4 mip levels of 4, 1, 1 and 1 blocks.
+        let data: Vec<u8> = (0..64u8)
+            .flat_map(|pixel| [pixel * 4, 255 - pixel * 4, 128, u8::MAX])
+            .collect();
+        let settings = CompressedImageSaverSettings {
+            quality: CompressedImageQuality::VeryLow,
+            ..Default::default()
+        };
+        let basis = compress_basis(&data, 8, 8, &settings, true).unwrap();
+
+        let levels = uastc_levels_from_basis(&basis).unwrap();
+        let sizes: Vec<_> = levels.iter().map(Vec::len).collect();
+        assert_eq!(sizes, [4, 1, 1, 1].map(|blocks| blocks * UASTC_BLOCK_SIZE));
+
+        let ktx2 = uastc_ktx2(8, 8, &levels, false, true);
+        let reader = ktx2::Reader::new(&ktx2).unwrap();
+        for (level, expected) in reader.levels().zip(&levels) {
+            assert_eq!(level.data, expected.as_slice());
+        }
+    }
+}
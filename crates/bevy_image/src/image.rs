@@ -105,11 +105,13 @@ impl Plugin for ImagePlugin {
                 bevy_asset::transformer::IdentityAssetTransformer<Image>,
                 crate::CompressedImageSaver,
             >>(crate::CompressedImageSaver.into());
-            processor.set_default_processor::<bevy_asset::processor::LoadTransformAndSave<
-                ImageLoader,
-                bevy_asset::transformer::IdentityAssetTransformer<Image>,
-                crate::CompressedImageSaver,
-            >>("png");
+            for extension in ["png", "jpg", "jpeg"] {
+                processor.set_default_processor::<bevy_asset::processor::LoadTransformAndSave<
+                    ImageLoader,
+                    bevy_asset::transformer::IdentityAssetTransformer<Image>,
+                    crate::CompressedImageSaver,
+                >>(extension);
+            }
         }
 
         app.preregister_asset_loader::<ImageLoader>(ImageLoader::SUPPORTED_FILE_EXTENSIONS);
@@ -1163,6 +1165,7 @@ impl Image {
     /// supported as input and output:
     /// - `TextureFormat::R8Unorm`
     /// - `TextureFormat::Rg8Unorm`
+    /// - `TextureFormat::Rgba8Unorm`
     /// - `TextureFormat::Rgba8UnormSrgb`
     ///
     /// To get [`Image`] as a [`image::DynamicImage`] see:
@@ -1179,6 +1182,9 @@ impl Image {
                     image::DynamicImage::ImageLumaA8(img.into_luma_alpha8()),
                     false,
                 )),
+                TextureFormat::Rgba8Unorm => {
+                    Some((image::DynamicImage::ImageRgba8(img.into_rgba8()), false))
+                }
                 TextureFormat::Rgba8UnormSrgb => {
                     Some((image::DynamicImage::ImageRgba8(img.into_rgba8()), true))
                 }
@@ -168,6 +168,8 @@ impl Image {
     /// - `TextureFormat::Rg8Unorm`
     /// - `TextureFormat::Rgba8UnormSrgb`
     /// - `TextureFormat::Bgra8UnormSrgb`
+    /// - `TextureFormat::Rgba16Float`
+    /// - `TextureFormat::Rgba32Float`
     ///
     /// To convert [`Image`] to a different format see: [`Image::convert`].
     pub fn try_into_dynamic(self) -> Result<DynamicImage, IntoDynamicImageError> {
@@ -198,6 +200,24 @@ impl Image {
                 })
                 .map(DynamicImage::ImageRgba8)
             }
+            // This format is used for HDR rendering
+            // This conversion is added here to support HDR screenshots
+            TextureFormat::Rgba16Float => ImageBuffer::from_raw(
+                width,
+                height,
+                data.chunks_exact(2)
+                    .map(|bytes| half::f16::from_le_bytes([bytes[0], bytes[1]]).to_f32())
+                    .collect(),
+            )
+            .map(DynamicImage::ImageRgba32F),
+            TextureFormat::Rgba32Float => ImageBuffer::from_raw(
+                width,
+                height,
+                data.chunks_exact(4)
+                    .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+                    .collect(),
+            )
+            .map(DynamicImage::ImageRgba32F),
             // Throw and error if conversion isn't supported
             texture_format => return Err(IntoDynamicImageError::UnsupportedFormat(texture_format)),
         }
@@ -241,4 +261,26 @@ mod test {
         // NOTE: Fails if `is_srgb = false` or the dynamic image is of the type rgb8.
         assert_eq!(initial, image.try_into_dynamic().unwrap());
     }
+
+    #[test]
+    fn hdr_conversion() {
+        // Check to see if HDR values are preserved when converting to a dynamic image.
+        let pixel = [4.5, 0.25, 1000.0, 1.0]
+            .into_iter()
+            .flat_map(|value| half::f16::from_f32(value).to_le_bytes())
+            .collect();
+        let image = Image::new(
+            Extent3d::default(),
+            TextureDimension::D2,
+            pixel,
+            TextureFormat::Rgba16Float,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+
+        let dyn_img = image.try_into_dynamic().unwrap();
+        assert_eq!(
+            dyn_img.as_rgba32f().unwrap().get_pixel(0, 0).0,
+            [4.5, 0.25, 1000.0, 1.0]
+        );
+    }
 }
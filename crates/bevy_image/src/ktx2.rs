@@ -194,7 +194,10 @@ pub fn ktx2_buffer_to_image(
                                 let slice_parameters = SliceParametersUastc {
                                     num_blocks_x,
                                     num_blocks_y,
-                                    has_alpha: false,
+                                    has_alpha: matches!(
+                                        data_format,
+                                        DataFormat::Rgba | DataFormat::Rrrg
+                                    ),
                                     original_width: level_width,
                                     original_height: level_height,
                                 };
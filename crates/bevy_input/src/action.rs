@@ -0,0 +1,648 @@
+//! Mapping of raw inputs to named actions and axes.
+//!
+//! Games usually don't care which key or button was pressed, but which action
+//! the player asked for: jump, fire, open the menu. The [`InputMap`] resource
+//! binds named actions and axes to keyboard keys, mouse buttons and motion,
+//! gamepad buttons and sticks, and touches, and the [`ActionState`] resource
+//! holds the state of those actions, updated every frame.
+//!
+//! Bindings are grouped in contexts, such as "gameplay" and "menu", which can
+//! be enabled and disabled at runtime, and can be changed at any time to let
+//! players rebind their controls. With the `serialize` feature, the
+//! [`InputMap`] can be saved and loaded along with the other settings of the
+//! game.
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    gamepad::{Gamepad, GamepadAxis, GamepadButton},
+    keyboard::KeyCode,
+    mouse::{AccumulatedMouseScroll, MouseButton},
+    mouse_processing::ProcessedMouseMotion,
+    touch::Touches,
+    ButtonInput,
+};
+#[cfg(feature = "bevy_reflect")]
+use bevy_ecs::reflect::ReflectResource;
+use bevy_ecs::{
+    resource::Resource,
+    system::{Query, Res, ResMut},
+};
+use bevy_math::Vec2;
+use bevy_platform::collections::{HashMap, HashSet};
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// An input that can trigger an action.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Hash, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum InputBinding {
+    /// A key of the keyboard.
+    ///
+    /// Keys are identified by their physical location, so bindings are saved independently of
+    /// the keyboard layout. Use the
+    /// [`KeyboardLayout`](crate::keyboard_layout::KeyboardLayout) to display the label of the key
+    /// under the current layout.
+    Key(KeyCode),
+    /// A button of the mouse.
+    MouseButton(MouseButton),
+    /// A button of any gamepad.
+    GamepadButton(GamepadButton),
+    /// Any finger touching the screen.
+    Touch,
+}
+
+impl From<KeyCode> for InputBinding {
+    fn from(key: KeyCode) -> Self {
+        Self::Key(key)
+    }
+}
+
+impl From<MouseButton> for InputBinding {
+    fn from(button: MouseButton) -> Self {
+        Self::MouseButton(button)
+    }
+}
+
+impl From<GamepadButton> for InputBinding {
+    fn from(button: GamepadButton) -> Self {
+        Self::GamepadButton(button)
+    }
+}
+
+impl InputBinding {
+    /// Returns the first input that was just pressed, if any.
+    ///
+    /// This is meant for rebinding screens, which wait for the player to press
+    /// the input they want to bind to an action.
+    pub fn just_pressed<'a>(
+        keys: &ButtonInput<KeyCode>,
+        mouse_buttons: &ButtonInput<MouseButton>,
+        gamepads: impl IntoIterator<Item = &'a Gamepad>,
+    ) -> Option<Self> {
+        keys.get_just_pressed()
+            .next()
+            .map(|&key| Self::Key(key))
+            .or_else(|| {
+                mouse_buttons
+                    .get_just_pressed()
+                    .next()
+                    .map(|&button| Self::MouseButton(button))
+            })
+            .or_else(|| {
+                gamepads.into_iter().find_map(|gamepad| {
+                    gamepad
+                        .get_just_pressed()
+                        .next()
+                        .map(|&button| Self::GamepadButton(button))
+                })
+            })
+    }
+}
+
+/// A direction of a two-dimensional input, such as the motion of the mouse.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Hash, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum MotionAxis {
+    /// The horizontal axis.
+    X,
+    /// The vertical axis.
+    Y,
+}
+
+impl MotionAxis {
+    fn value(self, motion: Vec2) -> f32 {
+        match self {
+            MotionAxis::X => motion.x,
+            MotionAxis::Y => motion.y,
+        }
+    }
+}
+
+/// An input that can move an axis.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Hash, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum AxisBinding {
+    /// Two inputs, which move the axis to -1.0 and 1.0 respectively while
+    /// they're pressed.
+    Buttons {
+        /// The input that moves the axis to -1.0.
+        negative: InputBinding,
+        /// The input that moves the axis to 1.0.
+        positive: InputBinding,
+    },
+    /// An axis of any gamepad, such as a stick.
+    GamepadAxis(GamepadAxis),
+    /// The motion of the mouse, in the units of [`ProcessedMouseMotion`].
+    MouseMotion(MotionAxis),
+    /// The scrolling of the mouse wheel, in the units of
+    /// [`AccumulatedMouseScroll`].
+    MouseScroll(MotionAxis),
+    /// The motion of the first finger touching the screen, in logical pixels.
+    TouchMotion(MotionAxis),
+}
+
+impl From<GamepadAxis> for AxisBinding {
+    fn from(axis: GamepadAxis) -> Self {
+        Self::GamepadAxis(axis)
+    }
+}
+
+/// The bindings of a group of actions and axes, which are enabled and
+/// disabled together.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct InputContext {
+    /// Whether the actions and axes of this context are updated.
+    ///
+    /// The actions of a disabled context are released, and its axes are zero.
+    #[cfg_attr(feature = "serialize", serde(default = "enabled_by_default"))]
+    pub enabled: bool,
+    /// The inputs bound to each action.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub actions: HashMap<String, Vec<InputBinding>>,
+    /// The inputs bound to each axis.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub axes: HashMap<String, Vec<AxisBinding>>,
+}
+
+#[cfg(feature = "serialize")]
+fn enabled_by_default() -> bool {
+    true
+}
+
+impl Default for InputContext {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            actions: HashMap::default(),
+            axes: HashMap::default(),
+        }
+    }
+}
+
+/// The bindings of the named actions and axes of the game, grouped in
+/// [`InputContext`]s.
+///
+/// The state of the actions and axes is stored in the [`ActionState`]
+/// resource, which is updated from this map every frame.
+///
+/// ```
+/// # use bevy_input::{action::{AxisBinding, InputMap}, gamepad::{GamepadAxis, GamepadButton}, keyboard::KeyCode};
+/// let mut input_map = InputMap::default();
+/// input_map
+///     .bind_action("gameplay", "jump", KeyCode::Space)
+///     .bind_action("gameplay", "jump", GamepadButton::South)
+///     .bind_axis(
+///         "gameplay",
+///         "move",
+///         AxisBinding::Buttons {
+///             negative: KeyCode::KeyA.into(),
+///             positive: KeyCode::KeyD.into(),
+///         },
+///     )
+///     .bind_axis("gameplay", "move", GamepadAxis::LeftStickX)
+///     .bind_action("menu", "confirm", KeyCode::Enter);
+///
+/// // Only the gameplay actions are updated until the menu is opened
+/// input_map.set_context_enabled("menu", false);
+/// ```
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, Resource, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct InputMap {
+    /// The contexts, by name.
+    pub contexts: HashMap<String, InputContext>,
+}
+
+impl InputMap {
+    /// Returns the context with the given name, if it exists.
+    pub fn context(&self, context: &str) -> Option<&InputContext> {
+        self.contexts.get(context)
+    }
+
+    /// Returns the context with the given name, creating it if it doesn't
+    /// exist.
+    pub fn context_mut(&mut self, context: &str) -> &mut InputContext {
+        self.contexts.entry(context.into()).or_default()
+    }
+
+    /// Enables or disables the context with the given name.
+    pub fn set_context_enabled(&mut self, context: &str, enabled: bool) -> &mut Self {
+        self.context_mut(context).enabled = enabled;
+        self
+    }
+
+    /// Returns true if the context with the given name exists and is enabled.
+    pub fn is_context_enabled(&self, context: &str) -> bool {
+        self.context(context).is_some_and(|context| context.enabled)
+    }
+
+    /// Binds an input to an action of a context. An action can be bound to
+    /// several inputs, and is pressed while any of them is.
+    pub fn bind_action(
+        &mut self,
+        context: &str,
+        action: &str,
+        binding: impl Into<InputBinding>,
+    ) -> &mut Self {
+        let binding = binding.into();
+        let bindings = self
+            .context_mut(context)
+            .actions
+            .entry(action.into())
+            .or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+        self
+    }
+
+    /// Binds an input to an axis of a context. An axis can be bound to several
+    /// inputs, and its value is the sum of their values.
+    pub fn bind_axis(
+        &mut self,
+        context: &str,
+        axis: &str,
+        binding: impl Into<AxisBinding>,
+    ) -> &mut Self {
+        let binding = binding.into();
+        let bindings = self
+            .context_mut(context)
+            .axes
+            .entry(axis.into())
+            .or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+        self
+    }
+
+    /// Removes an input from the bindings of an action. Returns true if the
+    /// input was bound to the action.
+    pub fn unbind_action(
+        &mut self,
+        context: &str,
+        action: &str,
+        binding: impl Into<InputBinding>,
+    ) -> bool {
+        let binding = binding.into();
+        self.contexts
+            .get_mut(context)
+            .and_then(|context| context.actions.get_mut(action))
+            .is_some_and(|bindings| remove_binding(bindings, &binding))
+    }
+
+    /// Removes an input from the bindings of an axis. Returns true if the input
+    /// was bound to the axis.
+    pub fn unbind_axis(
+        &mut self,
+        context: &str,
+        axis: &str,
+        binding: impl Into<AxisBinding>,
+    ) -> bool {
+        let binding = binding.into();
+        self.contexts
+            .get_mut(context)
+            .and_then(|context| context.axes.get_mut(axis))
+            .is_some_and(|bindings| remove_binding(bindings, &binding))
+    }
+
+    /// Replaces the bindings of an action with a single input, as rebinding
+    /// screens usually do.
+    pub fn rebind_action(
+        &mut self,
+        context: &str,
+        action: &str,
+        binding: impl Into<InputBinding>,
+    ) -> &mut Self {
+        self.context_mut(context)
+            .actions
+            .insert(action.into(), Vec::from([binding.into()]));
+        self
+    }
+
+    /// Returns the inputs bound to an action of a context.
+    pub fn action_bindings(&self, context: &str, action: &str) -> &[InputBinding] {
+        self.context(context)
+            .and_then(|context| context.actions.get(action))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the inputs bound to an axis of a context.
+    pub fn axis_bindings(&self, context: &str, axis: &str) -> &[AxisBinding] {
+        self.context(context)
+            .and_then(|context| context.axes.get(axis))
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+fn remove_binding<T: PartialEq>(bindings: &mut Vec<T>, binding: &T) -> bool {
+    let len = bindings.len();
+    bindings.retain(|bound| bound != binding);
+    bindings.len() != len
+}
+
+/// The state of the actions and axes of the [`InputMap`].
+///
+/// An action bound in several enabled contexts is pressed if it's pressed in
+/// any of them, and the value of an axis is the sum of its values in all of
+/// them.
+///
+/// ```
+/// # use bevy_ecs::system::Res;
+/// # use bevy_input::action::ActionState;
+/// fn jump(actions: Res<ActionState>) {
+///     if actions.just_pressed("jump") {
+///         // ...
+///     }
+///     let speed = actions.axis("move") * 5.0;
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Default)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, Resource, Clone)
+)]
+pub struct ActionState {
+    pressed: HashSet<String>,
+    just_pressed: HashSet<String>,
+    just_released: HashSet<String>,
+    axes: HashMap<String, f32>,
+}
+
+impl ActionState {
+    /// Returns true if the action is pressed.
+    pub fn pressed(&self, action: &str) -> bool {
+        self.pressed.contains(action)
+    }
+
+    /// Returns true if the action was pressed this frame.
+    pub fn just_pressed(&self, action: &str) -> bool {
+        self.just_pressed.contains(action)
+    }
+
+    /// Returns true if the action was released this frame.
+    pub fn just_released(&self, action: &str) -> bool {
+        self.just_released.contains(action)
+    }
+
+    /// Returns the value of the axis, or zero if it isn't bound in any enabled
+    /// context.
+    pub fn axis(&self, axis: &str) -> f32 {
+        self.axes.get(axis).copied().unwrap_or_default()
+    }
+
+    /// An iterator visiting every pressed action in arbitrary order.
+    pub fn get_pressed(&self) -> impl Iterator<Item = &str> {
+        self.pressed.iter().map(String::as_str)
+    }
+
+    /// Releases all the actions and resets the axes, for example when the
+    /// game is paused.
+    pub fn reset_all(&mut self) {
+        self.pressed.clear();
+        self.just_pressed.clear();
+        self.just_released.clear();
+        self.axes.clear();
+    }
+}
+
+/// The raw inputs that actions and axes can be bound to.
+struct RawInputs<'a> {
+    keys: &'a ButtonInput<KeyCode>,
+    mouse_buttons: &'a ButtonInput<MouseButton>,
+    gamepads: Vec<&'a Gamepad>,
+    touches: &'a Touches,
+    mouse_motion: Vec2,
+    mouse_scroll: Vec2,
+}
+
+impl RawInputs<'_> {
+    fn pressed(&self, binding: InputBinding) -> bool {
+        match binding {
+            InputBinding::Key(key) => self.keys.pressed(key),
+            InputBinding::MouseButton(button) => self.mouse_buttons.pressed(button),
+            InputBinding::GamepadButton(button) => {
+                self.gamepads.iter().any(|gamepad| gamepad.pressed(button))
+            }
+            InputBinding::Touch => self.touches.iter().next().is_some(),
+        }
+    }
+
+    fn axis(&self, binding: AxisBinding) -> f32 {
+        match binding {
+            AxisBinding::Buttons { negative, positive } => {
+                f32::from(u8::from(self.pressed(positive)))
+                    - f32::from(u8::from(self.pressed(negative)))
+            }
+            AxisBinding::GamepadAxis(axis) => self
+                .gamepads
+                .iter()
+                .filter_map(|gamepad| gamepad.get(axis))
+                .sum(),
+            AxisBinding::MouseMotion(axis) => axis.value(self.mouse_motion),
+            AxisBinding::MouseScroll(axis) => axis.value(self.mouse_scroll),
+            AxisBinding::TouchMotion(axis) => self
+                .touches
+                .iter()
+                .next()
+                .map_or(0.0, |touch| axis.value(touch.delta())),
+        }
+    }
+}
+
+/// Updates the [`ActionState`] resource from the [`InputMap`] and the raw
+/// inputs.
+pub fn action_state_system(
+    input_map: Res<InputMap>,
+    mut action_state: ResMut<ActionState>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    touches: Res<Touches>,
+    mouse_motion: Res<ProcessedMouseMotion>,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+) {
+    let inputs = RawInputs {
+        keys: &keys,
+        mouse_buttons: &mouse_buttons,
+        gamepads: gamepads.iter().collect(),
+        touches: &touches,
+        mouse_motion: mouse_motion.delta,
+        mouse_scroll: mouse_scroll.delta,
+    };
+
+    let mut pressed = HashSet::default();
+    let mut axes = HashMap::<String, f32>::default();
+    for context in input_map
+        .contexts
+        .values()
+        .filter(|context| context.enabled)
+    {
+        for (action, bindings) in &context.actions {
+            if bindings.iter().any(|&binding| inputs.pressed(binding)) {
+                pressed.insert(action.clone());
+            }
+        }
+        for (axis, bindings) in &context.axes {
+            let value: f32 = bindings.iter().map(|&binding| inputs.axis(binding)).sum();
+            *axes.entry(axis.clone()).or_default() += value;
+        }
+    }
+
+    let action_state = &mut *action_state;
+    action_state.just_pressed = pressed.difference(&action_state.pressed).cloned().collect();
+    action_state.just_released = action_state.pressed.difference(&pressed).cloned().collect();
+    action_state.pressed = pressed;
+    action_state.axes = axes;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{action_state_system, ActionState, AxisBinding, InputMap};
+    use crate::{
+        gamepad::Gamepad,
+        keyboard::KeyCode,
+        mouse::{AccumulatedMouseScroll, MouseButton},
+        mouse_processing::ProcessedMouseMotion,
+        touch::Touches,
+        ButtonInput,
+    };
+    use bevy_ecs::{schedule::Schedule, world::World};
+
+    fn setup() -> (World, Schedule) {
+        let mut world = World::new();
+        world.init_resource::<InputMap>();
+        world.init_resource::<ActionState>();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<ButtonInput<MouseButton>>();
+        world.init_resource::<Touches>();
+        world.init_resource::<ProcessedMouseMotion>();
+        world.init_resource::<AccumulatedMouseScroll>();
+        world.spawn(Gamepad::default());
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(action_state_system);
+        (world, schedule)
+    }
+
+    #[test]
+    fn actions_follow_bindings() {
+        let (mut world, mut schedule) = setup();
+        world
+            .resource_mut::<InputMap>()
+            .bind_action("gameplay", "jump", KeyCode::Space)
+            .bind_action("gameplay", "jump", MouseButton::Right)
+            .bind_axis(
+                "gameplay",
+                "move",
+                AxisBinding::Buttons {
+                    negative: KeyCode::KeyA.into(),
+                    positive: KeyCode::KeyD.into(),
+                },
+            );
+
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Space);
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyA);
+        schedule.run(&mut world);
+        let actions = world.resource::<ActionState>();
+        assert!(actions.pressed("jump"));
+        assert!(actions.just_pressed("jump"));
+        assert_eq!(actions.axis("move"), -1.0);
+
+        // Holding a second binding keeps the action pressed
+        world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Right);
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(KeyCode::Space);
+        schedule.run(&mut world);
+        let actions = world.resource::<ActionState>();
+        assert!(actions.pressed("jump"));
+        assert!(!actions.just_pressed("jump"));
+
+        world
+            .resource_mut::<InputMap>()
+            .set_context_enabled("gameplay", false);
+        schedule.run(&mut world);
+        let actions = world.resource::<ActionState>();
+        assert!(!actions.pressed("jump"));
+        assert!(actions.just_released("jump"));
+        assert_eq!(actions.axis("move"), 0.0);
+    }
+
+    #[test]
+    fn rebind_action() {
+        let (mut world, mut schedule) = setup();
+        let mut input_map = world.resource_mut::<InputMap>();
+        input_map
+            .bind_action("gameplay", "fire", KeyCode::KeyF)
+            .bind_action("gameplay", "fire", MouseButton::Left)
+            .rebind_action("gameplay", "fire", KeyCode::KeyE);
+        assert_eq!(
+            input_map.action_bindings("gameplay", "fire"),
+            [KeyCode::KeyE.into()]
+        );
+        assert!(input_map.unbind_action("gameplay", "fire", KeyCode::KeyE));
+        assert!(!input_map.unbind_action("gameplay", "fire", KeyCode::KeyE));
+
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyE);
+        schedule.run(&mut world);
+        assert!(!world.resource::<ActionState>().pressed("fire"));
+    }
+}
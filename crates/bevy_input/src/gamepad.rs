@@ -371,7 +371,7 @@ pub enum ButtonSettingsError {
     derive(Reflect),
     reflect(Debug, Component, Default)
 )]
-#[require(GamepadSettings)]
+#[require(GamepadSettings, GamepadHapticCapabilities)]
 pub struct Gamepad {
     /// The USB vendor ID as assigned by the USB-IF, if available.
     pub(crate) vendor_id: Option<u16>,
@@ -1745,6 +1745,155 @@ impl GamepadRumbleIntensity {
     }
 }
 
+/// How the intensity of a rumble ramps up and down over its duration.
+///
+/// The rumble starts at [`attack_level`](Self::attack_level) times its
+/// intensity, ramps up to its full intensity over the
+/// [`attack`](Self::attack), sustains it, and ramps down to
+/// [`decay_level`](Self::decay_level) times its intensity over the
+/// [`decay`](Self::decay) at the end of the rumble.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, PartialEq, Clone)
+)]
+pub struct GamepadRumbleEnvelope {
+    /// How long the intensity takes to ramp up at the start of the rumble.
+    pub attack: Duration,
+    /// The intensity at the start of the rumble, relative to its full
+    /// intensity.
+    pub attack_level: f32,
+    /// How long the intensity takes to ramp down at the end of the rumble.
+    pub decay: Duration,
+    /// The intensity at the end of the rumble, relative to its full intensity.
+    pub decay_level: f32,
+}
+
+impl GamepadRumbleEnvelope {
+    /// An envelope that keeps the rumble at its full intensity.
+    pub const FLAT: Self = GamepadRumbleEnvelope {
+        attack: Duration::ZERO,
+        attack_level: 1.0,
+        decay: Duration::ZERO,
+        decay_level: 1.0,
+    };
+
+    /// Creates an envelope that ramps up from zero over `attack`, and back
+    /// down to zero over `decay`.
+    pub const fn new(attack: Duration, decay: Duration) -> Self {
+        Self {
+            attack,
+            attack_level: 0.0,
+            decay,
+            decay_level: 0.0,
+        }
+    }
+}
+
+impl Default for GamepadRumbleEnvelope {
+    fn default() -> Self {
+        Self::FLAT
+    }
+}
+
+/// The rumble of a single motor in a [`GamepadRumblePattern`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+pub struct GamepadMotorRumble {
+    /// The full intensity of the rumble.
+    ///
+    /// Ranges from `0.0` to `1.0`.
+    pub intensity: f32,
+    /// How long after the start of the pattern the motor starts rumbling.
+    pub delay: Duration,
+    /// How long the motor rumbles, attack and decay included.
+    pub duration: Duration,
+    /// How the intensity ramps up and down.
+    pub envelope: GamepadRumbleEnvelope,
+}
+
+impl GamepadMotorRumble {
+    /// Creates a rumble at the given intensity, which starts immediately and
+    /// lasts for `duration`.
+    pub const fn new(intensity: f32, duration: Duration) -> Self {
+        Self {
+            intensity,
+            delay: Duration::ZERO,
+            duration,
+            envelope: GamepadRumbleEnvelope::FLAT,
+        }
+    }
+
+    /// Returns this rumble, starting `delay` after the start of the pattern.
+    pub const fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Returns this rumble with the given envelope.
+    pub const fn with_envelope(mut self, envelope: GamepadRumbleEnvelope) -> Self {
+        self.envelope = envelope;
+        self
+    }
+
+    /// Returns the time from the start of the pattern to the end of this
+    /// rumble.
+    pub fn end(&self) -> Duration {
+        self.delay + self.duration
+    }
+}
+
+/// A rumble that drives each motor of a gamepad separately, played with
+/// [`GamepadRumbleRequest::AddPattern`].
+///
+/// ```
+/// # use bevy_input::gamepad::{GamepadMotorRumble, GamepadRumbleEnvelope, GamepadRumblePattern};
+/// # use core::time::Duration;
+/// // A heavy hit: a sharp jolt of the weak motor, followed by a fading thud
+/// let pattern = GamepadRumblePattern {
+///     weak_motor: Some(GamepadMotorRumble::new(1.0, Duration::from_millis(80))),
+///     strong_motor: Some(
+///         GamepadMotorRumble::new(0.8, Duration::from_millis(400))
+///             .with_delay(Duration::from_millis(50))
+///             .with_envelope(GamepadRumbleEnvelope::new(
+///                 Duration::ZERO,
+///                 Duration::from_millis(300),
+///             )),
+///     ),
+/// };
+/// assert_eq!(pattern.duration(), Duration::from_millis(450));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, PartialEq, Clone)
+)]
+pub struct GamepadRumblePattern {
+    /// The rumble of the strong motor, if any.
+    pub strong_motor: Option<GamepadMotorRumble>,
+    /// The rumble of the weak motor, if any.
+    pub weak_motor: Option<GamepadMotorRumble>,
+}
+
+impl GamepadRumblePattern {
+    /// Returns the time from the start of the pattern to the end of the last
+    /// motor rumble.
+    pub fn duration(&self) -> Duration {
+        [self.strong_motor, self.weak_motor]
+            .iter()
+            .flatten()
+            .map(GamepadMotorRumble::end)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
 /// An event that controls force-feedback rumbling of a [`Gamepad`] [`entity`](Entity).
 ///
 /// # Notes
@@ -1795,6 +1944,18 @@ pub enum GamepadRumbleRequest {
         /// The gamepad to rumble.
         gamepad: Entity,
     },
+    /// Add a rumble pattern to the given gamepad, which drives each motor
+    /// separately and shapes their intensity over time.
+    ///
+    /// Patterns add up with the other rumbles, like [`GamepadRumbleRequest::Add`].
+    /// Gamepads whose [`GamepadHapticCapabilities::rumble_envelopes`] is false
+    /// play each motor at its full intensity instead.
+    AddPattern {
+        /// The rumble of each motor.
+        pattern: GamepadRumblePattern,
+        /// The gamepad to rumble.
+        gamepad: Entity,
+    },
     /// Stop all running rumbles on the given [`Entity`].
     Stop {
         /// The gamepad to stop rumble.
@@ -1806,11 +1967,134 @@ impl GamepadRumbleRequest {
     /// Get the [`Entity`] associated with this request.
     pub fn gamepad(&self) -> Entity {
         match self {
-            Self::Add { gamepad, .. } | Self::Stop { gamepad } => *gamepad,
+            Self::Add { gamepad, .. }
+            | Self::AddPattern { gamepad, .. }
+            | Self::Stop { gamepad } => *gamepad,
         }
     }
 }
 
+/// One of the two analog triggers of a gamepad.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Hash, PartialEq, Clone)
+)]
+pub enum GamepadTrigger {
+    /// The left trigger, [`GamepadButton::LeftTrigger2`].
+    Left,
+    /// The right trigger, [`GamepadButton::RightTrigger2`].
+    Right,
+}
+
+impl GamepadTrigger {
+    /// Returns the button of this trigger.
+    pub fn button(self) -> GamepadButton {
+        match self {
+            Self::Left => GamepadButton::LeftTrigger2,
+            Self::Right => GamepadButton::RightTrigger2,
+        }
+    }
+}
+
+/// The resistance an adaptive trigger opposes to being pressed.
+///
+/// Positions and strengths range from `0.0` to `1.0`, where a position of
+/// `0.0` is the released trigger, and `1.0` the fully pressed one.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, PartialEq, Clone)
+)]
+pub enum GamepadTriggerEffect {
+    /// The trigger moves freely.
+    #[default]
+    Off,
+    /// The trigger resists from `start` to the end of its travel.
+    Resistance {
+        /// The position where the resistance starts.
+        start: f32,
+        /// How hard the trigger resists.
+        strength: f32,
+    },
+    /// The trigger resists from `start` to `end`, then gives way, like the
+    /// trigger of a gun.
+    Weapon {
+        /// The position where the resistance starts.
+        start: f32,
+        /// The position where the trigger gives way.
+        end: f32,
+        /// How hard the trigger resists.
+        strength: f32,
+    },
+    /// The trigger vibrates from `start` to the end of its travel.
+    Vibration {
+        /// The position where the vibration starts.
+        start: f32,
+        /// The amplitude of the vibration.
+        amplitude: f32,
+        /// The frequency of the vibration, in hertz.
+        frequency: f32,
+    },
+}
+
+/// An event that sets the effect of an adaptive trigger of a [`Gamepad`]
+/// [`entity`](Entity).
+///
+/// The effect stays on until another effect is set on the trigger. Does
+/// nothing if the gamepad, platform or input backend doesn't support trigger
+/// effects, which [`GamepadHapticCapabilities::trigger_effects`] tells.
+#[derive(Message, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+pub struct GamepadTriggerEffectRequest {
+    /// The gamepad whose trigger is set.
+    pub gamepad: Entity,
+    /// The trigger to set the effect of.
+    pub trigger: GamepadTrigger,
+    /// The effect of the trigger.
+    pub effect: GamepadTriggerEffect,
+}
+
+/// The haptic effects supported by a [`Gamepad`].
+///
+/// Input backends set this component when a gamepad connects. It defaults to
+/// no support at all, which is also what gamepads get when the backend can't
+/// tell.
+///
+/// ```
+/// # use bevy_input::gamepad::{Gamepad, GamepadHapticCapabilities};
+/// # use bevy_ecs::prelude::{Entity, Query};
+/// fn rumbling_gamepads(gamepads: Query<(Entity, &GamepadHapticCapabilities)>) {
+///     for (gamepad, capabilities) in &gamepads {
+///         if capabilities.rumble {
+///             // ...
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, Component, PartialEq, Clone)
+)]
+pub struct GamepadHapticCapabilities {
+    /// Whether the gamepad can play [`GamepadRumbleRequest`]s.
+    pub rumble: bool,
+    /// Whether the gamepad shapes the intensity of the rumbles with their
+    /// [`GamepadRumbleEnvelope`].
+    pub rumble_envelopes: bool,
+    /// Whether the gamepad has adaptive triggers, which can play
+    /// [`GamepadTriggerEffectRequest`]s.
+    pub trigger_effects: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
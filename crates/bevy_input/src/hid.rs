@@ -0,0 +1,187 @@
+//! Raw input from HID devices, like flight sticks, racing wheels, pedals and custom hardware.
+//!
+//! Devices that don't fit the keyboard, mouse and gamepad abstractions of `bevy_input` are exposed
+//! as [`HidDevice`] entities, whose reports are sent as [`HidInputReport`] events. The reports are
+//! the raw bytes sent by the device: parsing them is left to the app, which can recognize the
+//! device by its [`HidDeviceDescriptor`].
+//!
+//! The events are sent by a backend, such as the one of the `bevy_hid` crate.
+
+use alloc::{string::String, vec::Vec};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    message::{Message, MessageReader},
+    name::Name,
+    system::Commands,
+};
+use log::{info, warn};
+
+#[cfg(feature = "bevy_reflect")]
+use {bevy_ecs::reflect::ReflectComponent, bevy_reflect::Reflect};
+
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// Describes a HID device.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Hash, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct HidDeviceDescriptor {
+    /// The USB vendor ID of the device.
+    pub vendor_id: u16,
+    /// The USB product ID of the device.
+    pub product_id: u16,
+    /// The usage page of the top-level collection of the device, such as `0x01` for generic desktop
+    /// controls or `0x02` for simulation controls.
+    pub usage_page: u16,
+    /// The usage of the top-level collection of the device within its usage page, such as `0x04`
+    /// for a joystick.
+    pub usage: u16,
+    /// The name of the product, if the device reports it.
+    pub product_name: Option<String>,
+    /// The name of the manufacturer, if the device reports it.
+    pub manufacturer_name: Option<String>,
+    /// The serial number of the device, if it reports one.
+    ///
+    /// Can be used to tell identical devices apart across sessions.
+    pub serial_number: Option<String>,
+}
+
+/// A connected HID device.
+///
+/// ## Usage
+///
+/// The entity of the device is spawned by the backend, and this component is inserted and removed
+/// by the [`hid_device_connection_system`] when the device connects and disconnects.
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Component, PartialEq, Clone)
+)]
+pub struct HidDevice {
+    /// The descriptor of the device.
+    pub descriptor: HidDeviceDescriptor,
+}
+
+/// The connection status of a HID device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum HidDeviceConnection {
+    /// The device is connected.
+    Connected(HidDeviceDescriptor),
+    /// The device is disconnected.
+    Disconnected,
+}
+
+/// A HID device has been connected or disconnected.
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct HidDeviceConnectionEvent {
+    /// The device whose connection status changed.
+    pub device: Entity,
+    /// The change in the device's connection.
+    pub connection: HidDeviceConnection,
+}
+
+/// An input report sent by a HID device.
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct HidInputReport {
+    /// The device that sent the report.
+    pub device: Entity,
+    /// The bytes of the report.
+    ///
+    /// If the device uses numbered reports, the first byte is the report ID.
+    pub data: Vec<u8>,
+}
+
+/// A request to send an output report to a HID device, for instance to light its LEDs or drive its
+/// force feedback.
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct HidOutputReport {
+    /// The device to send the report to.
+    pub device: Entity,
+    /// The bytes of the report.
+    ///
+    /// The first byte must be the report ID, or `0` if the device doesn't use numbered reports.
+    pub data: Vec<u8>,
+}
+
+/// Handles [`HidDeviceConnectionEvent`]s, inserting and removing the [`HidDevice`] component.
+///
+/// Entities are left alive when their device disconnects, to preserve the state the app attached to
+/// them in case the device reconnects.
+pub fn hid_device_connection_system(
+    mut commands: Commands,
+    mut connection_events: MessageReader<HidDeviceConnectionEvent>,
+) {
+    for connection_event in connection_events.read() {
+        let id = connection_event.device;
+        let Ok(mut device) = commands.get_entity(id) else {
+            warn!("HID device {id} removed before handling connection event.");
+            continue;
+        };
+        match &connection_event.connection {
+            HidDeviceConnection::Connected(descriptor) => {
+                if let Some(product_name) = &descriptor.product_name {
+                    device.insert(Name::new(product_name.clone()));
+                }
+                device.insert(HidDevice {
+                    descriptor: descriptor.clone(),
+                });
+                info!("HID device {id} connected.");
+            }
+            HidDeviceConnection::Disconnected => {
+                device.remove::<HidDevice>();
+                info!("HID device {id} disconnected.");
+            }
+        }
+    }
+}
@@ -0,0 +1,313 @@
+//! Labels of the physical keys under the keyboard layout of the user.
+//!
+//! Games usually bind actions to physical [`KeyCode`]s, so that bindings follow the location of
+//! the keys rather than the characters printed on them, and can be saved independently of the
+//! layout. Binding UIs however should show the characters the user sees on their keyboard:
+//! [`KeyCode::KeyW`] is labeled "Z" on an AZERTY keyboard.
+//!
+//! The [`KeyboardLayout`] resource provides these labels. As the platform layer doesn't report
+//! the keyboard layout, the labels are learned from the [`KeyboardInput`] events: whenever a key
+//! is pressed without modifiers, the logical key it produced becomes its label. Keys that were
+//! never pressed are labeled as on a US keyboard. When a key produces a different character than
+//! before, which happens when the user switches their layout, the learned labels are discarded and
+//! a [`KeyboardLayoutChanged`] event is sent.
+
+use alloc::format;
+
+use bevy_ecs::{
+    message::{Message, MessageReader, MessageWriter},
+    resource::Resource,
+    system::{Res, ResMut},
+};
+use bevy_platform::collections::HashMap;
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::Reflect;
+
+#[cfg(not(feature = "smol_str"))]
+use alloc::string::String as SmolStr;
+
+#[cfg(feature = "smol_str")]
+use smol_str::SmolStr;
+
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+use crate::{
+    keyboard::{Key, KeyCode, KeyboardInput},
+    ButtonInput, ButtonState,
+};
+
+/// The modifier keys that change the character produced by the other keys.
+const MODIFIERS: [KeyCode; 8] = [
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::AltLeft,
+    KeyCode::AltRight,
+    KeyCode::SuperLeft,
+    KeyCode::SuperRight,
+];
+
+/// The labels of the physical keys under the current keyboard layout.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_input::{keyboard::KeyCode, keyboard_layout::KeyboardLayout};
+/// fn show_bindings(layout: Res<KeyboardLayout>) {
+///     // "W" on a QWERTY keyboard, "Z" on an AZERTY keyboard
+///     let forward = layout.label(KeyCode::KeyW);
+/// }
+/// ```
+///
+/// See the [module documentation](self) for how the labels are learned.
+#[derive(Debug, Clone, Default, Resource)]
+pub struct KeyboardLayout {
+    labels: HashMap<KeyCode, SmolStr>,
+}
+
+impl KeyboardLayout {
+    /// Returns the label of the key under the current layout.
+    ///
+    /// Character keys that weren't pressed yet are labeled as on a US keyboard, and other keys
+    /// are labeled with their name, such as "Space" or "Left Shift".
+    pub fn label(&self, key_code: KeyCode) -> SmolStr {
+        self.labels
+            .get(&key_code)
+            .cloned()
+            .unwrap_or_else(|| default_label(key_code))
+    }
+
+    /// Returns the label learned for the key under the current layout, if it was pressed since
+    /// the layout last changed.
+    pub fn learned_label(&self, key_code: KeyCode) -> Option<&str> {
+        self.labels.get(&key_code).map(|label| &**label)
+    }
+
+    /// Sets the label of the key under the current layout.
+    ///
+    /// This can be used to provide the labels of keys that weren't pressed yet, if they can be
+    /// queried from the platform.
+    pub fn set_label(&mut self, key_code: KeyCode, label: impl Into<SmolStr>) {
+        self.labels.insert(key_code, label.into());
+    }
+
+    /// Forgets the learned labels, returning to the labels of a US keyboard.
+    pub fn clear(&mut self) {
+        self.labels.clear();
+    }
+
+    /// Learns the label of a key pressed without modifiers, returning `true` if it changed.
+    fn learn(&mut self, key_code: KeyCode, logical_key: &Key) -> bool {
+        let Some(label) = logical_label(logical_key) else {
+            return false;
+        };
+        match self.labels.get(&key_code) {
+            Some(learned) if *learned == label => false,
+            Some(_) => {
+                self.labels.clear();
+                self.labels.insert(key_code, label);
+                true
+            }
+            None => {
+                self.labels.insert(key_code, label);
+                false
+            }
+        }
+    }
+}
+
+/// Sent when the keys produce different characters than before, because the user switched their
+/// keyboard layout.
+///
+/// The [`KeyboardLayout`] resource forgets the labels it learned before the change, so binding UIs
+/// should refresh the labels they display.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct KeyboardLayoutChanged {
+    /// The key whose character revealed the change.
+    pub key_code: KeyCode,
+}
+
+/// Learns the labels of the [`KeyboardLayout`] from the latest [`KeyboardInput`] events.
+pub fn keyboard_layout_system(
+    mut layout: ResMut<KeyboardLayout>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut keyboard_input_reader: MessageReader<KeyboardInput>,
+    mut layout_changed_writer: MessageWriter<KeyboardLayoutChanged>,
+) {
+    if keys.any_pressed(MODIFIERS) {
+        keyboard_input_reader.clear();
+        return;
+    }
+    for event in keyboard_input_reader.read() {
+        if event.state == ButtonState::Pressed
+            && !event.repeat
+            && layout.learn(event.key_code, &event.logical_key)
+        {
+            layout_changed_writer.write(KeyboardLayoutChanged {
+                key_code: event.key_code,
+            });
+        }
+    }
+}
+
+/// Returns the label of the character or dead key produced by a key.
+fn logical_label(logical_key: &Key) -> Option<SmolStr> {
+    let character = match logical_key {
+        Key::Character(character) => character,
+        Key::Dead(Some(character)) => {
+            return Some(SmolStr::from(&*character.encode_utf8(&mut [0; 4])));
+        }
+        _ => return None,
+    };
+    if character.chars().any(char::is_lowercase) {
+        Some(SmolStr::from(character.to_uppercase().as_str()))
+    } else {
+        Some(character.clone())
+    }
+}
+
+/// Returns the label of a key on a US keyboard.
+fn default_label(key_code: KeyCode) -> SmolStr {
+    let label = match key_code {
+        KeyCode::Backquote => "`",
+        KeyCode::Backslash => "\\",
+        KeyCode::BracketLeft => "[",
+        KeyCode::BracketRight => "]",
+        KeyCode::Comma => ",",
+        KeyCode::Digit0 => "0",
+        KeyCode::Digit1 => "1",
+        KeyCode::Digit2 => "2",
+        KeyCode::Digit3 => "3",
+        KeyCode::Digit4 => "4",
+        KeyCode::Digit5 => "5",
+        KeyCode::Digit6 => "6",
+        KeyCode::Digit7 => "7",
+        KeyCode::Digit8 => "8",
+        KeyCode::Digit9 => "9",
+        KeyCode::Equal => "=",
+        KeyCode::KeyA => "A",
+        KeyCode::KeyB => "B",
+        KeyCode::KeyC => "C",
+        KeyCode::KeyD => "D",
+        KeyCode::KeyE => "E",
+        KeyCode::KeyF => "F",
+        KeyCode::KeyG => "G",
+        KeyCode::KeyH => "H",
+        KeyCode::KeyI => "I",
+        KeyCode::KeyJ => "J",
+        KeyCode::KeyK => "K",
+        KeyCode::KeyL => "L",
+        KeyCode::KeyM => "M",
+        KeyCode::KeyN => "N",
+        KeyCode::KeyO => "O",
+        KeyCode::KeyP => "P",
+        KeyCode::KeyQ => "Q",
+        KeyCode::KeyR => "R",
+        KeyCode::KeyS => "S",
+        KeyCode::KeyT => "T",
+        KeyCode::KeyU => "U",
+        KeyCode::KeyV => "V",
+        KeyCode::KeyW => "W",
+        KeyCode::KeyX => "X",
+        KeyCode::KeyY => "Y",
+        KeyCode::KeyZ => "Z",
+        KeyCode::Minus => "-",
+        KeyCode::Period => ".",
+        KeyCode::Quote => "'",
+        KeyCode::Semicolon => ";",
+        KeyCode::Slash => "/",
+        KeyCode::AltLeft => "Left Alt",
+        KeyCode::AltRight => "Right Alt",
+        KeyCode::Backspace => "Backspace",
+        KeyCode::CapsLock => "Caps Lock",
+        KeyCode::ControlLeft => "Left Ctrl",
+        KeyCode::ControlRight => "Right Ctrl",
+        KeyCode::Enter => "Enter",
+        KeyCode::SuperLeft => "Left Super",
+        KeyCode::SuperRight => "Right Super",
+        KeyCode::ShiftLeft => "Left Shift",
+        KeyCode::ShiftRight => "Right Shift",
+        KeyCode::Space => "Space",
+        KeyCode::Tab => "Tab",
+        KeyCode::Delete => "Delete",
+        KeyCode::End => "End",
+        KeyCode::Home => "Home",
+        KeyCode::Insert => "Insert",
+        KeyCode::PageDown => "Page Down",
+        KeyCode::PageUp => "Page Up",
+        KeyCode::ArrowDown => "Down",
+        KeyCode::ArrowLeft => "Left",
+        KeyCode::ArrowRight => "Right",
+        KeyCode::ArrowUp => "Up",
+        KeyCode::Escape => "Esc",
+        _ => return SmolStr::from(format!("{key_code:?}").as_str()),
+    };
+    label.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{keyboard_layout_system, KeyboardLayout, KeyboardLayoutChanged};
+    use crate::{
+        keyboard::{Key, KeyCode, KeyboardInput},
+        ButtonInput, ButtonState,
+    };
+    use bevy_app::{App, Update};
+    use bevy_ecs::{entity::Entity, message::Messages};
+
+    fn press(app: &mut App, key_code: KeyCode, character: &str) {
+        app.world_mut().write_message(KeyboardInput {
+            key_code,
+            logical_key: Key::Character(character.into()),
+            state: ButtonState::Pressed,
+            text: Some(character.into()),
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+        app.update();
+    }
+
+    #[test]
+    fn learn_layout() {
+        let mut app = App::new();
+        app.add_message::<KeyboardInput>()
+            .add_message::<KeyboardLayoutChanged>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<KeyboardLayout>()
+            .add_systems(Update, keyboard_layout_system);
+
+        let layout = app.world().resource::<KeyboardLayout>();
+        assert_eq!(layout.label(KeyCode::KeyW), "W");
+        assert_eq!(layout.label(KeyCode::Space), "Space");
+
+        // AZERTY
+        press(&mut app, KeyCode::KeyW, "z");
+        press(&mut app, KeyCode::KeyQ, "a");
+        let layout = app.world().resource::<KeyboardLayout>();
+        assert_eq!(layout.label(KeyCode::KeyW), "Z");
+        assert_eq!(layout.learned_label(KeyCode::KeyA), None);
+        assert!(app
+            .world()
+            .resource::<Messages<KeyboardLayoutChanged>>()
+            .is_empty());
+
+        // Back to QWERTY
+        press(&mut app, KeyCode::KeyW, "w");
+        let layout = app.world().resource::<KeyboardLayout>();
+        assert_eq!(layout.label(KeyCode::KeyW), "W");
+        assert_eq!(layout.learned_label(KeyCode::KeyQ), None);
+        let changes = app.world().resource::<Messages<KeyboardLayoutChanged>>();
+        assert_eq!(changes.len(), 1);
+    }
+}
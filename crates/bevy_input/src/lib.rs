@@ -10,21 +10,27 @@
 //!
 //! # Supported input devices
 //!
-//! `bevy` currently supports keyboard, mouse, gamepad, and touch inputs.
+//! `bevy` currently supports keyboard, mouse, gamepad, touch, and pen inputs, as well as raw input
+//! from other HID devices.
 
 #[cfg(feature = "std")]
 extern crate std;
 
 extern crate alloc;
 
+pub mod action;
 mod axis;
 mod button_input;
 /// Common run conditions
 pub mod common_conditions;
 pub mod gamepad;
 pub mod gestures;
+pub mod hid;
 pub mod keyboard;
+pub mod keyboard_layout;
 pub mod mouse;
+pub mod mouse_processing;
+pub mod pen;
 pub mod touch;
 
 pub use axis::*;
@@ -36,32 +42,42 @@ pub use button_input::*;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
+        action::{ActionState, InputMap},
         gamepad::{Gamepad, GamepadAxis, GamepadButton, GamepadSettings},
         keyboard::KeyCode,
+        keyboard_layout::KeyboardLayout,
         mouse::MouseButton,
+        pen::{PenInput, Pens},
         touch::{TouchInput, Touches},
         Axis, ButtonInput,
     };
 }
 
+use action::{action_state_system, ActionState, InputMap};
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
 #[cfg(feature = "bevy_reflect")]
 use bevy_reflect::Reflect;
 use gestures::*;
+use hid::{
+    hid_device_connection_system, HidDeviceConnectionEvent, HidInputReport, HidOutputReport,
+};
 use keyboard::{keyboard_input_system, Key, KeyCode, KeyboardFocusLost, KeyboardInput};
+use keyboard_layout::{keyboard_layout_system, KeyboardLayout, KeyboardLayoutChanged};
 use mouse::{
     accumulate_mouse_motion_system, accumulate_mouse_scroll_system, mouse_button_input_system,
-    AccumulatedMouseMotion, AccumulatedMouseScroll, MouseButton, MouseButtonInput, MouseMotion,
-    MouseWheel,
+    pointing_devices_system, AccumulatedMouseMotion, AccumulatedMouseScroll, MouseButton,
+    MouseButtonInput, MouseMotion, MouseWheel, PointingDevices,
 };
+use mouse_processing::{process_mouse_motion_system, MouseMotionSettings, ProcessedMouseMotion};
+use pen::{pen_input_system, PenInput, Pens};
 use touch::{touch_screen_input_system, TouchInput, Touches};
 
 use gamepad::{
     gamepad_connection_system, gamepad_event_processing_system, GamepadAxisChangedEvent,
     GamepadButtonChangedEvent, GamepadButtonStateChangedEvent, GamepadConnectionEvent,
-    GamepadEvent, GamepadRumbleRequest, RawGamepadAxisChangedEvent, RawGamepadButtonChangedEvent,
-    RawGamepadEvent,
+    GamepadEvent, GamepadRumbleRequest, GamepadTriggerEffectRequest, RawGamepadAxisChangedEvent,
+    RawGamepadButtonChangedEvent, RawGamepadEvent,
 };
 
 #[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
@@ -84,6 +100,14 @@ impl Plugin for InputPlugin {
             .init_resource::<ButtonInput<KeyCode>>()
             .init_resource::<ButtonInput<Key>>()
             .add_systems(PreUpdate, keyboard_input_system.in_set(InputSystems))
+            .add_message::<KeyboardLayoutChanged>()
+            .init_resource::<KeyboardLayout>()
+            .add_systems(
+                PreUpdate,
+                keyboard_layout_system
+                    .after(keyboard_input_system)
+                    .in_set(InputSystems),
+            )
             // mouse
             .add_message::<MouseButtonInput>()
             .add_message::<MouseMotion>()
@@ -95,9 +119,19 @@ impl Plugin for InputPlugin {
                     mouse_button_input_system,
                     accumulate_mouse_motion_system,
                     accumulate_mouse_scroll_system,
+                    pointing_devices_system,
                 )
                     .in_set(InputSystems),
             )
+            .init_resource::<PointingDevices>()
+            .init_resource::<MouseMotionSettings>()
+            .init_resource::<ProcessedMouseMotion>()
+            .add_systems(
+                PreUpdate,
+                process_mouse_motion_system
+                    .after(accumulate_mouse_motion_system)
+                    .in_set(InputSystems),
+            )
             .add_message::<PinchGesture>()
             .add_message::<RotationGesture>()
             .add_message::<DoubleTapGesture>()
@@ -112,6 +146,7 @@ impl Plugin for InputPlugin {
             .add_message::<RawGamepadAxisChangedEvent>()
             .add_message::<RawGamepadButtonChangedEvent>()
             .add_message::<GamepadRumbleRequest>()
+            .add_message::<GamepadTriggerEffectRequest>()
             .init_resource::<AccumulatedMouseMotion>()
             .init_resource::<AccumulatedMouseScroll>()
             .add_systems(
@@ -122,10 +157,33 @@ impl Plugin for InputPlugin {
                 )
                     .in_set(InputSystems),
             )
+            // hid
+            .add_message::<HidDeviceConnectionEvent>()
+            .add_message::<HidInputReport>()
+            .add_message::<HidOutputReport>()
+            .add_systems(PreUpdate, hid_device_connection_system.in_set(InputSystems))
             // touch
             .add_message::<TouchInput>()
             .init_resource::<Touches>()
-            .add_systems(PreUpdate, touch_screen_input_system.in_set(InputSystems));
+            .add_systems(PreUpdate, touch_screen_input_system.in_set(InputSystems))
+            // pen
+            .add_message::<PenInput>()
+            .init_resource::<Pens>()
+            .add_systems(PreUpdate, pen_input_system.in_set(InputSystems))
+            // actions
+            .init_resource::<InputMap>()
+            .init_resource::<ActionState>()
+            .add_systems(
+                PreUpdate,
+                action_state_system
+                    .after(keyboard_input_system)
+                    .after(mouse_button_input_system)
+                    .after(process_mouse_motion_system)
+                    .after(accumulate_mouse_scroll_system)
+                    .after(gamepad_event_processing_system)
+                    .after(touch_screen_input_system)
+                    .in_set(InputSystems),
+            );
     }
 }
 
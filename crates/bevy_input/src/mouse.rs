@@ -9,6 +9,7 @@ use bevy_ecs::{
     system::ResMut,
 };
 use bevy_math::Vec2;
+use bevy_platform::collections::HashMap;
 #[cfg(feature = "bevy_reflect")]
 use {
     bevy_ecs::reflect::ReflectResource,
@@ -44,6 +45,8 @@ pub struct MouseButtonInput {
     pub state: ButtonState,
     /// Window that received the input.
     pub window: Entity,
+    /// The pointing device the button belongs to.
+    pub device: PointingDeviceId,
 }
 
 /// A button on a mouse device.
@@ -87,8 +90,8 @@ pub enum MouseButton {
 /// This represents raw, unfiltered physical motion.
 /// It is the translated version of [`DeviceEvent::MouseMotion`] from the `winit` crate.
 ///
-/// All pointing devices connected to a single machine at the same time can emit the event independently.
-/// However, the event data does not make it possible to distinguish which device it is referring to.
+/// All pointing devices connected to a single machine at the same time can emit the event independently,
+/// and are distinguished by their [`PointingDeviceId`].
 ///
 /// [`DeviceEvent::MouseMotion`]: https://docs.rs/winit/latest/winit/event/enum.DeviceEvent.html#variant.MouseMotion
 #[derive(Message, Debug, Clone, Copy, PartialEq)]
@@ -105,6 +108,38 @@ pub enum MouseButton {
 pub struct MouseMotion {
     /// The change in the position of the pointing device since the last event was sent.
     pub delta: Vec2,
+    /// The pointing device that moved.
+    pub device: PointingDeviceId,
+}
+
+/// Identifies the pointing device that sent a mouse event.
+///
+/// Each mouse, trackpad or other pointing device is given its own id in the order it first sends
+/// input, which makes it possible to tell the devices apart when several are connected to the same
+/// machine, for instance for local multiplayer. Events that don't come from a device, such as the
+/// events written by the app itself, use [`PointingDeviceId::DEFAULT`].
+///
+/// ## Note
+///
+/// Not every platform distinguishes the devices of every event: most desktop platforms report the
+/// [`MouseButtonInput`] and [`MouseWheel`] events for a single device shared by all the mice, and
+/// only the raw [`MouseMotion`] events have the id of the device that sent them.
+#[derive(Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, Hash, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PointingDeviceId(pub u64);
+
+impl PointingDeviceId {
+    /// The id of the events that don't come from a specific device.
+    pub const DEFAULT: Self = Self(0);
 }
 
 /// The scroll unit.
@@ -172,6 +207,88 @@ pub struct MouseWheel {
     pub y: f32,
     /// Window that received the input.
     pub window: Entity,
+    /// The pointing device that scrolled.
+    pub device: PointingDeviceId,
+}
+
+/// The state of each pointing device, for apps that let several mice be used at the same time.
+///
+/// Unlike [`ButtonInput<MouseButton>`] and [`AccumulatedMouseMotion`], which merge the input of
+/// all the devices, this resource keeps the buttons and the motion of each [`PointingDeviceId`]
+/// apart. Devices are added when they first send input.
+///
+/// ## Updating
+///
+/// The resource is updated inside of the [`pointing_devices_system`].
+#[derive(Resource, Debug, Clone, Default)]
+pub struct PointingDevices {
+    devices: HashMap<PointingDeviceId, PointingDevice>,
+}
+
+impl PointingDevices {
+    /// An iterator visiting every [`PointingDevice`] that sent input, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (PointingDeviceId, &PointingDevice)> + '_ {
+        self.devices.iter().map(|(id, device)| (*id, device))
+    }
+
+    /// Returns the [`PointingDevice`] corresponding to the `id` if it sent input.
+    pub fn get(&self, id: PointingDeviceId) -> Option<&PointingDevice> {
+        self.devices.get(&id)
+    }
+
+    /// Forgets all the devices.
+    pub fn clear(&mut self) {
+        self.devices.clear();
+    }
+}
+
+/// The state of a single pointing device, stored in the [`PointingDevices`] resource.
+#[derive(Debug, Clone, Default)]
+pub struct PointingDevice {
+    buttons: ButtonInput<MouseButton>,
+    motion: Vec2,
+}
+
+impl PointingDevice {
+    /// Returns the state of the buttons of the device.
+    #[inline]
+    pub fn buttons(&self) -> &ButtonInput<MouseButton> {
+        &self.buttons
+    }
+
+    /// Returns the sum of the [`MouseMotion`] of the device this frame.
+    #[inline]
+    pub fn motion(&self) -> Vec2 {
+        self.motion
+    }
+}
+
+/// Updates the [`PointingDevices`] resource with the latest [`MouseButtonInput`] and
+/// [`MouseMotion`] events.
+pub fn pointing_devices_system(
+    mut pointing_devices: ResMut<PointingDevices>,
+    mut mouse_button_input_events: MessageReader<MouseButtonInput>,
+    mut mouse_motion_events: MessageReader<MouseMotion>,
+) {
+    for device in pointing_devices
+        .bypass_change_detection()
+        .devices
+        .values_mut()
+    {
+        device.buttons.clear();
+        device.motion = Vec2::ZERO;
+    }
+    for event in mouse_motion_events.read() {
+        let device = pointing_devices.devices.entry(event.device).or_default();
+        device.motion += event.delta;
+    }
+    for event in mouse_button_input_events.read() {
+        let device = pointing_devices.devices.entry(event.device).or_default();
+        match event.state {
+            ButtonState::Pressed => device.buttons.press(event.button),
+            ButtonState::Released => device.buttons.release(event.button),
+        }
+    }
 }
 
 /// Updates the [`ButtonInput<MouseButton>`] resource with the latest [`MouseButtonInput`] events.
@@ -278,3 +395,56 @@ pub fn accumulate_mouse_scroll_system(
     accumulated_mouse_scroll.delta = delta;
     accumulated_mouse_scroll.unit = unit;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        pointing_devices_system, MouseButton, MouseButtonInput, MouseMotion, PointingDeviceId,
+        PointingDevices,
+    };
+    use crate::ButtonState;
+    use bevy_app::{App, Update};
+    use bevy_ecs::entity::Entity;
+    use bevy_math::Vec2;
+
+    #[test]
+    fn separate_pointing_devices() {
+        let mut app = App::new();
+        app.add_message::<MouseButtonInput>()
+            .add_message::<MouseMotion>()
+            .init_resource::<PointingDevices>()
+            .add_systems(Update, pointing_devices_system);
+
+        let (first, second) = (PointingDeviceId(1), PointingDeviceId(2));
+        app.world_mut().write_message(MouseMotion {
+            delta: Vec2::X,
+            device: first,
+        });
+        app.world_mut().write_message(MouseMotion {
+            delta: Vec2::Y,
+            device: second,
+        });
+        app.world_mut().write_message(MouseButtonInput {
+            button: MouseButton::Left,
+            state: ButtonState::Pressed,
+            window: Entity::PLACEHOLDER,
+            device: second,
+        });
+        app.update();
+
+        let devices = app.world().resource::<PointingDevices>();
+        assert_eq!(devices.iter().count(), 2);
+        let (first, second) = (devices.get(first).unwrap(), devices.get(second).unwrap());
+        assert_eq!(first.motion(), Vec2::X);
+        assert!(!first.buttons().pressed(MouseButton::Left));
+        assert_eq!(second.motion(), Vec2::Y);
+        assert!(second.buttons().just_pressed(MouseButton::Left));
+
+        app.update();
+        let devices = app.world().resource::<PointingDevices>();
+        let second = devices.get(PointingDeviceId(2)).unwrap();
+        assert_eq!(second.motion(), Vec2::ZERO);
+        assert!(second.buttons().pressed(MouseButton::Left));
+        assert!(!second.buttons().just_pressed(MouseButton::Left));
+    }
+}
@@ -0,0 +1,331 @@
+//! Processing of the mouse motion: sensitivity, acceleration and smoothing.
+//!
+//! The raw [`AccumulatedMouseMotion`] is processed according to the [`MouseMotionSettings`]
+//! resource into the [`ProcessedMouseMotion`] resource, which camera controllers and the
+//! [`ActionState`](crate::action::ActionState) read. This gives players the same mouse feel in the
+//! whole game, and lets the game expose these settings in a single place.
+
+use crate::{action::InputMap, mouse::AccumulatedMouseMotion};
+use alloc::string::String;
+use bevy_ecs::{
+    resource::Resource,
+    system::{Local, Res, ResMut},
+};
+use bevy_math::{ops, Vec2};
+use bevy_platform::collections::HashMap;
+use bevy_time::{Real, Time};
+use core::time::Duration;
+
+#[cfg(feature = "bevy_reflect")]
+use {
+    bevy_ecs::reflect::ReflectResource,
+    bevy_reflect::{std_traits::ReflectDefault, Reflect},
+};
+
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// An acceleration curve, which multiplies the mouse motion as the mouse moves faster.
+///
+/// The multiplier is `1 + (scale * speed) ^ exponent`, capped at `max_multiplier`, where the speed
+/// is in the units of [`AccumulatedMouseMotion`] per second.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct MouseAcceleration {
+    /// How fast the multiplier grows with the speed. The acceleration is disabled if it's zero.
+    pub scale: f32,
+    /// The shape of the curve: `1.0` is linear, and greater values leave slow motions
+    /// unaccelerated for longer.
+    pub exponent: f32,
+    /// The maximum multiplier.
+    pub max_multiplier: f32,
+}
+
+impl MouseAcceleration {
+    /// No acceleration.
+    pub const NONE: Self = Self {
+        scale: 0.0,
+        exponent: 1.0,
+        max_multiplier: 1.0,
+    };
+
+    /// Returns `true` if the curve accelerates the motion.
+    pub fn is_enabled(&self) -> bool {
+        self.scale > 0.0 && self.max_multiplier > 1.0
+    }
+
+    /// Returns the multiplier of the motion at the given speed.
+    pub fn multiplier(&self, speed: f32) -> f32 {
+        if !self.is_enabled() {
+            return 1.0;
+        }
+        (1.0 + ops::powf(self.scale * speed, self.exponent)).min(self.max_multiplier)
+    }
+}
+
+impl Default for MouseAcceleration {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+/// Settings of the processing of the mouse motion into the [`ProcessedMouseMotion`].
+///
+/// The default settings leave the motion unchanged.
+///
+/// ## Usage
+///
+/// ```
+/// # use bevy_input::mouse_processing::{MouseAcceleration, MouseMotionSettings};
+/// # use bevy_math::Vec2;
+/// # use core::time::Duration;
+/// let mut settings = MouseMotionSettings {
+///     // Invert the vertical axis
+///     sensitivity: Vec2::new(1.0, -1.0),
+///     acceleration: MouseAcceleration {
+///         scale: 0.001,
+///         exponent: 1.5,
+///         max_multiplier: 3.0,
+///     },
+///     smoothing: Duration::from_millis(20),
+///     ..Default::default()
+/// };
+/// // Slower aiming while the "aiming" context of the `InputMap` is enabled
+/// settings.context_sensitivity.insert("aiming".into(), 0.5);
+/// ```
+#[derive(Resource, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, Resource, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct MouseMotionSettings {
+    /// The multiplier of the motion on each axis. Negative values invert the axis.
+    pub sensitivity: Vec2,
+    /// The acceleration curve.
+    pub acceleration: MouseAcceleration,
+    /// The time constant of the exponential smoothing of the motion. Longer durations smooth the
+    /// motion more, at the cost of latency. The smoothing is disabled if it's zero.
+    pub smoothing: Duration,
+    /// Additional multipliers of the motion, applied while the [`InputMap`] context with the same
+    /// name is enabled.
+    pub context_sensitivity: HashMap<String, f32>,
+}
+
+impl MouseMotionSettings {
+    /// Returns the multiplier of the motion from the enabled contexts of the input map.
+    pub fn context_multiplier(&self, input_map: &InputMap) -> f32 {
+        self.context_sensitivity
+            .iter()
+            .filter(|(context, _)| input_map.is_context_enabled(context))
+            .map(|(_, sensitivity)| sensitivity)
+            .product()
+    }
+}
+
+impl Default for MouseMotionSettings {
+    fn default() -> Self {
+        Self {
+            sensitivity: Vec2::ONE,
+            acceleration: MouseAcceleration::NONE,
+            smoothing: Duration::ZERO,
+            context_sensitivity: HashMap::default(),
+        }
+    }
+}
+
+/// The mouse motion of the frame, processed according to the [`MouseMotionSettings`].
+///
+/// This resource is updated every frame by the [`process_mouse_motion_system`]. It's equal to the
+/// [`AccumulatedMouseMotion`] with the default settings.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, Resource, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct ProcessedMouseMotion {
+    /// The processed change in mouse position.
+    pub delta: Vec2,
+}
+
+/// The state of the acceleration and smoothing between frames.
+#[derive(Debug, Default)]
+pub struct MouseMotionProcessor {
+    /// The smoothed speed of the mouse, in units per second.
+    velocity: Vec2,
+}
+
+impl MouseMotionProcessor {
+    /// Processes the motion of a frame that lasted `delta_time`, or of a frame of unknown duration
+    /// if it's [`None`], which is neither accelerated nor smoothed.
+    fn process(
+        &mut self,
+        settings: &MouseMotionSettings,
+        delta: Vec2,
+        delta_time: Option<Duration>,
+    ) -> Vec2 {
+        let delta_secs = delta_time.map_or(0.0, |delta_time| delta_time.as_secs_f32());
+        if delta_secs <= 0.0 {
+            self.velocity = Vec2::ZERO;
+            return delta;
+        }
+
+        let mut delta = delta
+            * settings
+                .acceleration
+                .multiplier(delta.length() / delta_secs);
+        if !settings.smoothing.is_zero() {
+            let factor = 1.0 - ops::exp(-delta_secs / settings.smoothing.as_secs_f32());
+            self.velocity = self.velocity.lerp(delta / delta_secs, factor);
+            delta = self.velocity * delta_secs;
+        }
+        delta
+    }
+}
+
+/// Updates the [`ProcessedMouseMotion`] resource from the [`AccumulatedMouseMotion`] and the
+/// [`MouseMotionSettings`].
+///
+/// The duration of the frame is read from the [`Time<Real>`] resource, so that the mouse feel
+/// doesn't depend on the speed of the virtual time. The motion isn't accelerated nor smoothed
+/// without it.
+pub fn process_mouse_motion_system(
+    settings: Res<MouseMotionSettings>,
+    input_map: Res<InputMap>,
+    accumulated_mouse_motion: Res<AccumulatedMouseMotion>,
+    time: Option<Res<Time<Real>>>,
+    mut processed_mouse_motion: ResMut<ProcessedMouseMotion>,
+    mut processor: Local<MouseMotionProcessor>,
+) {
+    let mut delta = accumulated_mouse_motion.delta;
+    if settings.acceleration.is_enabled() || !settings.smoothing.is_zero() {
+        let delta_time = time.map(|time| time.delta());
+        delta = processor.process(&settings, delta, delta_time);
+    } else {
+        // Don't smooth the first frames with the motion from before the processing was disabled
+        *processor = MouseMotionProcessor::default();
+    }
+
+    delta *= settings.sensitivity * settings.context_multiplier(&input_map);
+    processed_mouse_motion.delta = delta;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        process_mouse_motion_system, MouseAcceleration, MouseMotionProcessor, MouseMotionSettings,
+        ProcessedMouseMotion,
+    };
+    use crate::{action::InputMap, mouse::AccumulatedMouseMotion};
+    use bevy_ecs::world::World;
+    use bevy_math::Vec2;
+    use bevy_platform::time::Instant;
+    use bevy_time::{Real, Time};
+    use core::time::Duration;
+
+    #[test]
+    fn acceleration_curve() {
+        let acceleration = MouseAcceleration {
+            scale: 0.01,
+            exponent: 2.0,
+            max_multiplier: 4.0,
+        };
+        assert_eq!(acceleration.multiplier(0.0), 1.0);
+        assert_eq!(acceleration.multiplier(100.0), 2.0);
+        assert_eq!(acceleration.multiplier(1000.0), 4.0);
+        assert_eq!(MouseAcceleration::NONE.multiplier(1000.0), 1.0);
+    }
+
+    #[test]
+    fn smoothing_spreads_motion() {
+        let settings = MouseMotionSettings {
+            smoothing: Duration::from_millis(50),
+            ..Default::default()
+        };
+        let frame = Some(Duration::from_millis(10));
+        let mut processor = MouseMotionProcessor::default();
+        assert_eq!(processor.process(&settings, Vec2::X, None), Vec2::X);
+
+        let first = processor.process(&settings, Vec2::new(10.0, 0.0), frame);
+        assert!(first.x > 0.0 && first.x < 10.0);
+        // The motion continues for a while after the mouse stops
+        let second = processor.process(&settings, Vec2::ZERO, frame);
+        assert!(second.x > 0.0 && second.x < first.x);
+    }
+
+    #[test]
+    fn system_uses_real_time() {
+        let mut world = World::new();
+        world.insert_resource(MouseMotionSettings {
+            acceleration: MouseAcceleration {
+                scale: 0.001,
+                exponent: 1.0,
+                max_multiplier: 4.0,
+            },
+            ..Default::default()
+        });
+        world.init_resource::<InputMap>();
+        world.init_resource::<ProcessedMouseMotion>();
+        world.insert_resource(AccumulatedMouseMotion {
+            delta: Vec2::new(10.0, 0.0),
+        });
+        let mut time = Time::<Real>::new(Instant::now());
+        time.update_with_duration(Duration::ZERO);
+        world.insert_resource(time);
+
+        let mut run = |frame: Duration| {
+            world
+                .resource_mut::<Time<Real>>()
+                .update_with_duration(frame);
+            world
+                .run_system_cached(process_mouse_motion_system)
+                .unwrap();
+            world.resource::<ProcessedMouseMotion>().delta.x
+        };
+        // 10 units in 10 milliseconds is 1000 units per second, which doubles the motion
+        assert!((run(Duration::from_millis(10)) - 20.0).abs() < 1e-3);
+        // 10 units in 100 milliseconds is 100 units per second
+        assert!((run(Duration::from_millis(100)) - 11.0).abs() < 1e-3);
+
+        // The motion isn't accelerated without a clock
+        world.remove_resource::<Time<Real>>();
+        world
+            .run_system_cached(process_mouse_motion_system)
+            .unwrap();
+        assert_eq!(world.resource::<ProcessedMouseMotion>().delta.x, 10.0);
+    }
+
+    #[test]
+    fn context_sensitivity() {
+        let mut settings = MouseMotionSettings::default();
+        settings.context_sensitivity.insert("aiming".into(), 0.5);
+        settings.context_sensitivity.insert("vehicle".into(), 2.0);
+
+        let mut input_map = InputMap::default();
+        assert_eq!(settings.context_multiplier(&input_map), 1.0);
+        input_map.set_context_enabled("aiming", true);
+        assert_eq!(settings.context_multiplier(&input_map), 0.5);
+        input_map.set_context_enabled("vehicle", true);
+        assert_eq!(settings.context_multiplier(&input_map), 1.0);
+    }
+}
@@ -0,0 +1,370 @@
+//! The pen and stylus input functionality.
+
+use bevy_ecs::{
+    entity::Entity,
+    message::{Message, MessageReader},
+    resource::Resource,
+    system::ResMut,
+};
+use bevy_math::Vec2;
+use bevy_platform::collections::{HashMap, HashSet};
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// A pen input event, sent when a pen or a stylus moves over or touches a tablet or a touchscreen.
+///
+/// ## Logic
+///
+/// A [`PenPhase::Entered`] event is sent when a pen comes within range of the surface, if the
+/// device can detect hovering pens. [`PenPhase::Down`] and [`PenPhase::Up`] events are sent when
+/// the tip of the pen touches and leaves the surface, and a [`PenPhase::Left`] event is sent when
+/// the pen goes out of range. In between, [`PenPhase::Moved`] events are sent when the pen moves,
+/// or when its pressure, tilt or buttons change.
+///
+/// Pens that can't be detected while hovering are sent a [`PenPhase::Left`] event right after
+/// they're lifted.
+///
+/// ## Note
+///
+/// With `winit`, pen input is currently only reported for the Apple Pencil on **iOS**, whose
+/// touches are sent as pen input instead of [`TouchInput`](crate::touch::TouchInput).
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PenInput {
+    /// The phase of the pen input.
+    pub phase: PenPhase,
+    /// The position of the pen on the window.
+    pub position: Vec2,
+    /// How hard the pen presses on the surface, from 0.0 to 1.0.
+    ///
+    /// This is 0.0 while the pen hovers over the surface, and 1.0 while it touches it if the
+    /// device isn't pressure sensitive.
+    pub pressure: f32,
+    /// The altitude (in radians) of the pen.
+    ///
+    /// A value of 0 radians indicates that the pen is parallel to the surface. The value of this
+    /// property is Pi/2 when the pen is perpendicular to the surface.
+    ///
+    /// May be [`None`] if the device doesn't report the tilt of the pen.
+    pub altitude_angle: Option<f32>,
+    /// The azimuth (in radians) of the pen: the angle from the x axis of the window to the
+    /// direction the pen points to, projected on the surface.
+    ///
+    /// May be [`None`] if the device doesn't report the orientation of the pen.
+    pub azimuth_angle: Option<f32>,
+    /// The buttons on the barrel of the pen that are pressed.
+    pub barrel_buttons: PenBarrelButtons,
+    /// Whether the pen is used as an eraser, usually by flipping it upside down.
+    pub eraser: bool,
+    /// The window entity registering the pen.
+    pub window: Entity,
+    /// The unique identifier of the pen.
+    pub id: u64,
+}
+
+/// A phase of a [`PenInput`].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Hash, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum PenPhase {
+    /// The pen came within range of the surface, and hovers over it.
+    Entered,
+    /// The tip of the pen touched the surface.
+    Down,
+    /// The pen moved, or its pressure, tilt or buttons changed.
+    Moved,
+    /// The tip of the pen left the surface.
+    Up,
+    /// The pen went out of range of the surface.
+    Left,
+    /// The system canceled the tracking of the pen.
+    ///
+    /// This occurs when the window loses focus.
+    Canceled,
+}
+
+/// The buttons on the barrel of a pen.
+#[derive(Debug, Default, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, Hash, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PenBarrelButtons {
+    /// Whether the primary barrel button, closest to the tip, is pressed.
+    pub primary: bool,
+    /// Whether the secondary barrel button is pressed.
+    pub secondary: bool,
+}
+
+/// A pen in range of a surface.
+///
+/// ## Usage
+///
+/// The data of the pen comes from the [`PenInput`] events, and is stored inside of the [`Pens`]
+/// resource.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pen {
+    id: u64,
+    previous_position: Vec2,
+    position: Vec2,
+    pressure: f32,
+    altitude_angle: Option<f32>,
+    azimuth_angle: Option<f32>,
+    barrel_buttons: PenBarrelButtons,
+    eraser: bool,
+    down: bool,
+}
+
+impl Pen {
+    /// Returns the `id` of the pen.
+    #[inline]
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the position of the pen in the previous frame.
+    #[inline]
+    pub fn previous_position(&self) -> Vec2 {
+        self.previous_position
+    }
+
+    /// Returns the current position of the pen.
+    #[inline]
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    /// The delta of the current `position` and the `previous_position`.
+    pub fn delta(&self) -> Vec2 {
+        self.position - self.previous_position
+    }
+
+    /// Returns the current pressure of the pen, from 0.0 to 1.0.
+    #[inline]
+    pub fn pressure(&self) -> f32 {
+        self.pressure
+    }
+
+    /// Returns the altitude (in radians) of the pen, if known.
+    ///
+    /// See [`PenInput::altitude_angle`].
+    #[inline]
+    pub fn altitude_angle(&self) -> Option<f32> {
+        self.altitude_angle
+    }
+
+    /// Returns the azimuth (in radians) of the pen, if known.
+    ///
+    /// See [`PenInput::azimuth_angle`].
+    #[inline]
+    pub fn azimuth_angle(&self) -> Option<f32> {
+        self.azimuth_angle
+    }
+
+    /// Returns the pressed barrel buttons of the pen.
+    #[inline]
+    pub fn barrel_buttons(&self) -> PenBarrelButtons {
+        self.barrel_buttons
+    }
+
+    /// Returns true if the pen is used as an eraser.
+    #[inline]
+    pub fn is_eraser(&self) -> bool {
+        self.eraser
+    }
+
+    /// Returns true if the tip of the pen touches the surface.
+    #[inline]
+    pub fn is_down(&self) -> bool {
+        self.down
+    }
+
+    fn update(&mut self, input: &PenInput) {
+        self.position = input.position;
+        self.pressure = input.pressure;
+        self.altitude_angle = input.altitude_angle;
+        self.azimuth_angle = input.azimuth_angle;
+        self.barrel_buttons = input.barrel_buttons;
+        self.eraser = input.eraser;
+    }
+}
+
+impl From<&PenInput> for Pen {
+    fn from(input: &PenInput) -> Pen {
+        Pen {
+            id: input.id,
+            previous_position: input.position,
+            position: input.position,
+            pressure: input.pressure,
+            altitude_angle: input.altitude_angle,
+            azimuth_angle: input.azimuth_angle,
+            barrel_buttons: input.barrel_buttons,
+            eraser: input.eraser,
+            down: false,
+        }
+    }
+}
+
+/// A collection of the [`Pen`]s in range of a surface.
+///
+/// ## Updating
+///
+/// The resource is updated inside of the [`pen_input_system`].
+#[derive(Debug, Clone, Default, Resource)]
+pub struct Pens {
+    pens: HashMap<u64, Pen>,
+    just_down: HashSet<u64>,
+    just_up: HashSet<u64>,
+}
+
+impl Pens {
+    /// An iterator visiting every [`Pen`] in range, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &Pen> + '_ {
+        self.pens.values()
+    }
+
+    /// Returns the [`Pen`] input corresponding to the `id` if it is in range.
+    pub fn get(&self, id: u64) -> Option<&Pen> {
+        self.pens.get(&id)
+    }
+
+    /// Returns `true` if the tip of the pen with the given `id` touched the surface this frame.
+    pub fn just_down(&self, id: u64) -> bool {
+        self.just_down.contains(&id)
+    }
+
+    /// Returns `true` if the tip of the pen with the given `id` left the surface this frame.
+    pub fn just_up(&self, id: u64) -> bool {
+        self.just_up.contains(&id)
+    }
+
+    /// Returns `true` if the tip of any pen touches the surface.
+    pub fn any_down(&self) -> bool {
+        self.pens.values().any(Pen::is_down)
+    }
+
+    /// Clears the `just_down` and `just_up` states, and forgets all the pens.
+    pub fn clear(&mut self) {
+        self.pens.clear();
+        self.just_down.clear();
+        self.just_up.clear();
+    }
+
+    /// Processes a [`PenInput`] event by updating the `pens`, `just_down` and `just_up` collections.
+    fn process_pen_event(&mut self, event: &PenInput) {
+        let pen = self
+            .pens
+            .entry(event.id)
+            .or_insert_with(|| Pen::from(event));
+        pen.update(event);
+        match event.phase {
+            PenPhase::Entered | PenPhase::Moved => {}
+            PenPhase::Down => {
+                pen.down = true;
+                self.just_down.insert(event.id);
+            }
+            PenPhase::Up => {
+                pen.down = false;
+                self.just_up.insert(event.id);
+            }
+            PenPhase::Left | PenPhase::Canceled => {
+                if pen.down {
+                    self.just_up.insert(event.id);
+                }
+                self.pens.remove(&event.id);
+            }
+        }
+    }
+}
+
+/// Updates the [`Pens`] resource with the latest [`PenInput`] events.
+///
+/// ## Differences
+///
+/// The main difference between the [`PenInput`] event and the [`Pens`] resource is that
+/// the latter has convenient functions like [`Pens::just_down`] and [`Pens::just_up`].
+pub fn pen_input_system(mut pens: ResMut<Pens>, mut pen_input_reader: MessageReader<PenInput>) {
+    if !pens.just_down.is_empty() {
+        pens.just_down.clear();
+    }
+    if !pens.just_up.is_empty() {
+        pens.just_up.clear();
+    }
+
+    if !pen_input_reader.is_empty() {
+        for pen in pens.pens.values_mut() {
+            pen.previous_position = pen.position;
+        }
+
+        for event in pen_input_reader.read() {
+            pens.process_pen_event(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PenBarrelButtons, PenInput, PenPhase, Pens};
+    use bevy_ecs::entity::Entity;
+    use bevy_math::Vec2;
+
+    fn pen_input(phase: PenPhase, position: Vec2, pressure: f32) -> PenInput {
+        PenInput {
+            phase,
+            position,
+            pressure,
+            altitude_angle: None,
+            azimuth_angle: None,
+            barrel_buttons: PenBarrelButtons::default(),
+            eraser: false,
+            window: Entity::PLACEHOLDER,
+            id: 1,
+        }
+    }
+
+    #[test]
+    fn pen_strokes() {
+        let mut pens = Pens::default();
+
+        pens.process_pen_event(&pen_input(PenPhase::Entered, Vec2::ZERO, 0.0));
+        assert!(!pens.get(1).unwrap().is_down());
+
+        pens.process_pen_event(&pen_input(PenPhase::Down, Vec2::ZERO, 0.5));
+        pens.process_pen_event(&pen_input(PenPhase::Moved, Vec2::X, 0.75));
+        let pen = pens.get(1).unwrap();
+        assert!(pens.just_down(1) && pen.is_down() && pens.any_down());
+        assert_eq!(pen.position(), Vec2::X);
+        assert_eq!(pen.pressure(), 0.75);
+
+        // A pen going out of range while down is lifted
+        pens.process_pen_event(&pen_input(PenPhase::Left, Vec2::X, 0.0));
+        assert!(pens.just_up(1));
+        assert!(pens.get(1).is_none());
+    }
+}
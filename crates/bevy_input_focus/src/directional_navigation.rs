@@ -57,6 +57,18 @@
 //! - **Precise control**: Define exact navigation flow, including non-obvious connections like looping edges
 //! - **Cross-layer navigation**: Connect elements across different UI layers or z-index levels
 //! - **Custom behavior**: Implement domain-specific navigation patterns (e.g., spreadsheet-style wrapping)
+//!
+//! # Keyboard Navigation
+//!
+//! To move the focus with the arrow keys, add the [`ArrowKeyNavigationPlugin`] along with the
+//! [`DirectionalNavigationPlugin`] and [`InputDispatchPlugin`](crate::InputDispatchPlugin).
+//! This will install a keyboard event observer on the primary window, in the same way as
+//! [tab navigation](crate::tab_navigation). Keyboard events bubble up from the focused entity to
+//! the window, so widgets which use the arrow keys themselves, such as sliders, should stop the
+//! propagation of the events they handle.
+//!
+//! Gamepads and input-action-mapping frameworks can call [`DirectionalNavigation::navigate`]
+//! directly instead.
 
 use alloc::vec::Vec;
 use bevy_app::prelude::*;
@@ -66,11 +78,16 @@ use bevy_ecs::{
     prelude::*,
     system::SystemParam,
 };
+use bevy_input::{
+    keyboard::{KeyCode, KeyboardInput},
+    ButtonState,
+};
 use bevy_math::{CompassOctant, Dir2, Vec2};
 use bevy_ui::{ComputedNode, UiGlobalTransform, UiSystems};
+use bevy_window::PrimaryWindow;
 use thiserror::Error;
 
-use crate::InputFocus;
+use crate::{FocusedInput, InputFocus, InputFocusVisible};
 
 #[cfg(feature = "bevy_reflect")]
 use bevy_reflect::{prelude::*, Reflect};
@@ -90,6 +107,52 @@ impl Plugin for DirectionalNavigationPlugin {
     }
 }
 
+/// Plugin for moving the focus between the entities of the [`DirectionalNavigationMap`] with the
+/// arrow keys.
+///
+/// This requires the [`DirectionalNavigationPlugin`] and the
+/// [`InputDispatchPlugin`](crate::InputDispatchPlugin).
+pub struct ArrowKeyNavigationPlugin;
+
+impl Plugin for ArrowKeyNavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_arrow_key_navigation);
+    }
+}
+
+fn setup_arrow_key_navigation(mut commands: Commands, window: Query<Entity, With<PrimaryWindow>>) {
+    for window in window.iter() {
+        commands.entity(window).observe(handle_arrow_key_navigation);
+    }
+}
+
+/// Observer function which moves the focus to the neighbor of the focused entity in the direction
+/// of the pressed arrow key.
+///
+/// Nothing happens if no entity is focused, or if the focused entity has no neighbor in that
+/// direction, in which case the event keeps propagating.
+pub fn handle_arrow_key_navigation(
+    mut event: On<FocusedInput<KeyboardInput>>,
+    mut nav: DirectionalNavigation,
+    mut visible: ResMut<InputFocusVisible>,
+) {
+    let key_event = &event.input;
+    if key_event.state != ButtonState::Pressed {
+        return;
+    }
+    let direction = match key_event.key_code {
+        KeyCode::ArrowUp => CompassOctant::North,
+        KeyCode::ArrowDown => CompassOctant::South,
+        KeyCode::ArrowLeft => CompassOctant::West,
+        KeyCode::ArrowRight => CompassOctant::East,
+        _ => return,
+    };
+    if nav.navigate(direction).is_ok() {
+        event.propagate(false);
+        visible.0 = true;
+    }
+}
+
 /// Marker component to enable automatic directional navigation graph generation.
 ///
 /// Simply add this component to your UI entities and the navigation graph will be
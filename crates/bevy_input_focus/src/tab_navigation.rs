@@ -14,6 +14,10 @@
 //! is determined by the [`TabGroup::order`] field, with lower orders being tabbed first. Modal tab groups
 //! are used for ui elements that should only tab within themselves, such as modal dialog boxes.
 //!
+//! The order of the entities within a tab group can also be set explicitly by adding a [`TabOrder`]
+//! component to the group, in which case the tab indices and hierarchy of its descendants are
+//! ignored.
+//!
 //! To enable automatic tabbing, add the
 //! [`TabNavigationPlugin`] and [`InputDispatchPlugin`](crate::InputDispatchPlugin) to your app.
 //! This will install a keyboard event observer on the primary window which automatically handles
@@ -98,6 +102,20 @@ impl TabGroup {
     }
 }
 
+/// A component which explicitly sets the order in which the entities of a [`TabGroup`] are
+/// focused by tab navigation, overriding their [`TabIndex`] and hierarchy order.
+///
+/// Only the listed entities can be reached by tabbing within the group. They should still be
+/// descendants of the group, so that navigation can find the group of the focused entity, and
+/// keep a [`TabIndex`] so that they can be focused by clicking on them.
+#[derive(Debug, Default, Component, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, Component, PartialEq, Clone)
+)]
+pub struct TabOrder(pub Vec<Entity>);
+
 /// A navigation action that users might take to navigate your user interface in a cyclic fashion.
 ///
 /// These values are consumed by the [`TabNavigation`] system param.
@@ -161,6 +179,8 @@ pub struct TabNavigation<'w, 's> {
         (Entity, Option<&'static TabIndex>, Option<&'static Children>),
         Without<TabGroup>,
     >,
+    // Query for explicit tab orders.
+    tab_order_query: Query<'w, 's, &'static TabOrder, With<TabGroup>>,
     // Query for parents.
     parent_query: Query<'w, 's, &'static ChildOf>,
 }
@@ -261,7 +281,9 @@ impl TabNavigation<'_, '_> {
         match tabgroup {
             Some((tg_entity, tg)) if tg.modal => {
                 // We're in a modal tab group, then gather all tab indices in that group.
-                if let Ok((_, _, children)) = self.tabgroup_query.get(tg_entity) {
+                if let Ok(tab_order) = self.tab_order_query.get(tg_entity) {
+                    self.gather_tab_order(&mut focusable, tab_order, 0);
+                } else if let Ok((_, _, children)) = self.tabgroup_query.get(tg_entity) {
                     for child in children.iter() {
                         self.gather_focusable(&mut focusable, *child, 0);
                     }
@@ -338,12 +360,33 @@ impl TabNavigation<'_, '_> {
             }
         } else if let Ok((_, tabgroup, children)) = self.tabgroup_query.get(parent) {
             if !tabgroup.modal {
-                for child in children.iter() {
-                    self.gather_focusable(out, *child, tab_group_idx);
+                if let Ok(tab_order) = self.tab_order_query.get(parent) {
+                    self.gather_tab_order(out, tab_order, tab_group_idx);
+                } else {
+                    for child in children.iter() {
+                        self.gather_focusable(out, *child, tab_group_idx);
+                    }
                 }
             }
         }
     }
+
+    /// Gather the entities of an explicit tab order, using their position as tab index.
+    fn gather_tab_order(
+        &self,
+        out: &mut Vec<(Entity, TabIndex, usize)>,
+        tab_order: &TabOrder,
+        tab_group_idx: usize,
+    ) {
+        out.extend(
+            tab_order
+                .0
+                .iter()
+                .filter(|entity| self.tabindex_query.contains(**entity))
+                .enumerate()
+                .map(|(index, entity)| (*entity, TabIndex(index as i32), tab_group_idx)),
+        );
+    }
 }
 
 /// Observer which sets focus to the nearest ancestor that has tab index, using bubbling.
@@ -464,6 +507,7 @@ pub fn handle_tab_navigation(
 
 #[cfg(test)]
 mod tests {
+    use alloc::vec;
     use bevy_ecs::system::SystemState;
 
     use super::*;
@@ -497,6 +541,39 @@ mod tests {
         assert_eq!(last_entity, Ok(tab_entity_2));
     }
 
+    #[test]
+    fn test_tab_navigation_with_tab_order() {
+        let mut app = App::new();
+        let world = app.world_mut();
+
+        let tab_group_entity = world.spawn(TabGroup::new(0)).id();
+        let tab_entity_1 = world.spawn((TabIndex(0), ChildOf(tab_group_entity))).id();
+        let tab_entity_2 = world.spawn((TabIndex(1), ChildOf(tab_group_entity))).id();
+        let tab_entity_3 = world.spawn((TabIndex(2), ChildOf(tab_group_entity))).id();
+        world
+            .entity_mut(tab_group_entity)
+            .insert(TabOrder(vec![tab_entity_3, tab_entity_1]));
+
+        let mut system_state: SystemState<TabNavigation> = SystemState::new(world);
+        let tab_navigation = system_state.get(world);
+
+        let first_entity = tab_navigation.navigate(&InputFocus::default(), NavAction::First);
+        assert_eq!(first_entity, Ok(tab_entity_3));
+
+        let next_entity =
+            tab_navigation.navigate(&InputFocus::from_entity(tab_entity_3), NavAction::Next);
+        assert_eq!(next_entity, Ok(tab_entity_1));
+
+        let next_entity =
+            tab_navigation.navigate(&InputFocus::from_entity(tab_entity_1), NavAction::Next);
+        assert_eq!(next_entity, Ok(tab_entity_3));
+
+        // Entities missing from the tab order are skipped.
+        let next_entity =
+            tab_navigation.navigate(&InputFocus::from_entity(tab_entity_2), NavAction::Next);
+        assert_eq!(next_entity, Ok(tab_entity_3));
+    }
+
     #[test]
     fn test_tab_navigation_between_groups_is_sorted_by_group() {
         let mut app = App::new();
@@ -70,10 +70,20 @@ plugin_group! {
         // compressed texture formats.
         #[cfg(feature = "bevy_gltf")]
         bevy_gltf:::GltfPlugin,
+        #[cfg(feature = "bevy_usd")]
+        bevy_usd:::UsdPlugin,
+        #[cfg(feature = "bevy_fbx")]
+        bevy_fbx:::FbxPlugin,
+        #[cfg(feature = "bevy_aseprite")]
+        bevy_aseprite:::AsepritePlugin,
+        #[cfg(feature = "bevy_tilemap_import")]
+        bevy_tilemap_import:::TilemapImportPlugin,
         #[cfg(feature = "bevy_audio")]
         bevy_audio:::AudioPlugin,
         #[cfg(feature = "bevy_gilrs")]
         bevy_gilrs:::GilrsPlugin,
+        #[cfg(feature = "bevy_hid")]
+        bevy_hid:::HidPlugin,
         #[cfg(feature = "bevy_animation")]
         bevy_animation:::AnimationPlugin,
         #[cfg(feature = "bevy_gizmos")]
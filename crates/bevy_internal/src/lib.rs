@@ -23,6 +23,8 @@ pub use bevy_animation as animation;
 #[cfg(feature = "bevy_anti_alias")]
 pub use bevy_anti_alias as anti_alias;
 pub use bevy_app as app;
+#[cfg(feature = "bevy_aseprite")]
+pub use bevy_aseprite as aseprite;
 #[cfg(feature = "bevy_asset")]
 pub use bevy_asset as asset;
 #[cfg(feature = "bevy_audio")]
@@ -41,6 +43,8 @@ pub use bevy_diagnostic as diagnostic;
 pub use bevy_ecs as ecs;
 #[cfg(feature = "bevy_feathers")]
 pub use bevy_feathers as feathers;
+#[cfg(feature = "bevy_fbx")]
+pub use bevy_fbx as fbx;
 #[cfg(feature = "bevy_gilrs")]
 pub use bevy_gilrs as gilrs;
 #[cfg(feature = "bevy_gizmos")]
@@ -49,6 +53,8 @@ pub use bevy_gizmos as gizmos;
 pub use bevy_gizmos_render as gizmos_render;
 #[cfg(feature = "bevy_gltf")]
 pub use bevy_gltf as gltf;
+#[cfg(feature = "bevy_hid")]
+pub use bevy_hid as hid;
 #[cfg(feature = "bevy_image")]
 pub use bevy_image as image;
 pub use bevy_input as input;
@@ -89,12 +95,16 @@ pub use bevy_state as state;
 pub use bevy_tasks as tasks;
 #[cfg(feature = "bevy_text")]
 pub use bevy_text as text;
+#[cfg(feature = "bevy_tilemap_import")]
+pub use bevy_tilemap_import as tilemap_import;
 pub use bevy_time as time;
 pub use bevy_transform as transform;
 #[cfg(feature = "bevy_ui")]
 pub use bevy_ui as ui;
 #[cfg(feature = "bevy_ui_render")]
 pub use bevy_ui_render as ui_render;
+#[cfg(feature = "bevy_usd")]
+pub use bevy_usd as usd;
 #[cfg(feature = "bevy_ui_widgets")]
 pub use bevy_ui_widgets as ui_widgets;
 pub use bevy_utils as utils;
@@ -91,6 +91,10 @@ pub use crate::gizmos::prelude::*;
 #[cfg(feature = "bevy_gilrs")]
 pub use crate::gilrs::*;
 
+#[doc(hidden)]
+#[cfg(feature = "bevy_hid")]
+pub use crate::hid::prelude::*;
+
 #[doc(hidden)]
 #[cfg(feature = "bevy_state")]
 pub use crate::state::prelude::*;
@@ -99,6 +103,22 @@ pub use crate::state::prelude::*;
 #[cfg(feature = "bevy_gltf")]
 pub use crate::gltf::prelude::*;
 
+#[doc(hidden)]
+#[cfg(feature = "bevy_usd")]
+pub use crate::usd::prelude::*;
+
+#[doc(hidden)]
+#[cfg(feature = "bevy_fbx")]
+pub use crate::fbx::prelude::*;
+
+#[doc(hidden)]
+#[cfg(feature = "bevy_aseprite")]
+pub use crate::aseprite::prelude::*;
+
+#[doc(hidden)]
+#[cfg(feature = "bevy_tilemap_import")]
+pub use crate::tilemap_import::prelude::*;
+
 #[doc(hidden)]
 #[cfg(feature = "bevy_picking")]
 pub use crate::picking::prelude::*;
@@ -1,4 +1,14 @@
-//! Provides a way to automatically set the mouse cursor based on hovered entity.
+//! Provides a way to automatically set the mouse cursor based on the hovered entity.
+//!
+//! Add an [`EntityCursor`] to any pickable entity, such as a UI node, a sprite or a mesh, and the
+//! cursor of the window will change to it while the mouse hovers over the entity or one of its
+//! descendants. Custom cursor images, including their hotspot, are supported with the
+//! `custom_cursor` feature.
+//!
+//! This is opt-in: add the [`CursorIconPlugin`] to enable it. Once added, the plugin owns the
+//! [`CursorIcon`] of the windows, so the cursor shown when nothing with an [`EntityCursor`] is
+//! hovered should be set with the [`DefaultCursor`] resource.
+use alloc::vec::Vec;
 use bevy_app::{App, Plugin, PreUpdate};
 use bevy_ecs::{
     component::Component,
@@ -10,12 +20,13 @@ use bevy_ecs::{
     schedule::IntoScheduleConfigs,
     system::{Commands, Query, Res},
 };
-use bevy_picking::{hover::HoverMap, pointer::PointerId, PickingSystems};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 #[cfg(feature = "custom_cursor")]
 use bevy_window::CustomCursor;
 use bevy_window::{CursorIcon, SystemCursorIcon, Window};
 
+use crate::{hover::HoverMap, pointer::PointerId, PickingSystems};
+
 /// A resource that specifies the cursor icon to be used when the mouse is not hovering over
 /// any other entity. This is used to set the default cursor icon for the window.
 #[derive(Resource, Debug, Clone, Default, Reflect)]
@@ -71,9 +82,10 @@ impl Default for EntityCursor {
 }
 
 /// System which updates the window cursor icon whenever the mouse hovers over an entity with
-/// a [`CursorIcon`] component. If no entity is hovered, the cursor icon is set to
-/// the cursor in the [`DefaultCursor`] resource.
-pub(crate) fn update_cursor(
+/// an [`EntityCursor`] component, or whose ancestors have one. If several hovered entities have a
+/// cursor, the closest one to the camera wins. If no such entity is hovered, the cursor icon is set
+/// to the cursor in the [`DefaultCursor`] resource.
+pub fn update_cursor(
     mut commands: Commands,
     hover_map: Option<Res<HoverMap>>,
     parent_query: Query<&ChildOf>,
@@ -82,15 +94,16 @@ pub(crate) fn update_cursor(
     r_default_cursor: Res<DefaultCursor>,
 ) {
     let cursor = hover_map
-        .and_then(|hover_map| match hover_map.get(&PointerId::Mouse) {
-            Some(hover_set) => hover_set.keys().find_map(|entity| {
+        .and_then(|hover_map| {
+            let mut hovered: Vec<_> = hover_map.get(&PointerId::Mouse)?.iter().collect();
+            hovered.sort_by(|(_, a), (_, b)| a.depth.total_cmp(&b.depth));
+            hovered.into_iter().find_map(|(entity, _)| {
                 cursor_query.get(*entity).ok().or_else(|| {
                     parent_query
                         .iter_ancestors(*entity)
                         .find_map(|e| cursor_query.get(e).ok())
                 })
-            }),
-            None => None,
+            })
         })
         .unwrap_or(&r_default_cursor.0);
 
@@ -105,6 +118,8 @@ pub(crate) fn update_cursor(
 }
 
 /// Plugin that supports automatically changing the cursor based on the hovered entity.
+///
+/// See the [module docs](self) for more details.
 pub struct CursorIconPlugin;
 
 impl Plugin for CursorIconPlugin {
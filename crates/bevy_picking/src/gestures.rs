@@ -0,0 +1,610 @@
+//! Recognition of touch gestures, dispatched to the entities under the fingers.
+//!
+//! The [`TouchGesturePlugin`] follows the fingers touching the screen through the [`Touches`]
+//! resource, and recognizes taps, long presses, swipes, pinches and rotations. Like the other
+//! [`Pointer`] events, gestures are sent to the entities hovered by the touch pointer, and bubble
+//! up the hierarchy:
+//!
+//! ```rust
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_picking::prelude::*;
+//! # #[derive(Component)]
+//! # struct Zoom(f32);
+//! # let mut world = World::default();
+//! world.spawn(Zoom(1.0)).observe(|pinch: On<Pointer<Pinch>>, mut zooms: Query<&mut Zoom>| {
+//!     if let Ok(mut zoom) = zooms.get_mut(pinch.entity) {
+//!         zoom.0 *= pinch.scale;
+//!     }
+//! });
+//! ```
+//!
+//! Two-finger gestures are sent to the entities under the finger that touched the screen first.
+//! A touch that takes part in a two-finger gesture, or that was long pressed, is neither a tap nor
+//! a swipe when it's lifted.
+
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::{prelude::*, system::SystemParam};
+use bevy_input::touch::Touches;
+use bevy_math::Vec2;
+use bevy_platform::{collections::HashMap, time::Instant};
+use bevy_reflect::prelude::*;
+
+use crate::{
+    backend::HitData,
+    events::{pointer_events, Pointer},
+    hover::{HoverMap, PreviousHoverMap},
+    pointer::{PointerId, PointerLocation},
+    PickingSystems,
+};
+
+/// Recognizes touch gestures, and sends them as [`Pointer`] events.
+///
+/// The thresholds of the gestures can be tuned with the [`TouchGestureSettings`] resource.
+#[derive(Default)]
+pub struct TouchGesturePlugin;
+
+impl Plugin for TouchGesturePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TouchGestureSettings>()
+            .add_message::<Pointer<Tap>>()
+            .add_message::<Pointer<LongPress>>()
+            .add_message::<Pointer<Swipe>>()
+            .add_message::<Pointer<Pinch>>()
+            .add_message::<Pointer<Rotate>>()
+            .add_systems(
+                PreUpdate,
+                touch_gesture_events
+                    .after(pointer_events)
+                    .in_set(PickingSystems::Hover),
+            );
+    }
+}
+
+/// The thresholds used to recognize touch gestures.
+#[derive(Copy, Clone, Debug, Resource, Reflect)]
+#[reflect(Resource, Default, Debug, Clone)]
+pub struct TouchGestureSettings {
+    /// The longest a finger can touch the screen for the touch to be a [`Tap`].
+    pub tap_max_duration: Duration,
+    /// The furthest, in logical pixels, a finger can move from where it touched the screen and
+    /// still [`Tap`] or [`LongPress`].
+    pub tap_max_distance: f32,
+    /// How long a finger must touch the screen without moving for a [`LongPress`].
+    pub long_press_duration: Duration,
+    /// The shortest distance, in logical pixels, a finger must move for a [`Swipe`].
+    pub swipe_min_distance: f32,
+    /// The lowest average speed, in logical pixels per second, of a [`Swipe`].
+    pub swipe_min_speed: f32,
+}
+
+impl Default for TouchGestureSettings {
+    fn default() -> Self {
+        Self {
+            tap_max_duration: Duration::from_millis(300),
+            tap_max_distance: 10.0,
+            long_press_duration: Duration::from_millis(500),
+            swipe_min_distance: 50.0,
+            swipe_min_speed: 300.0,
+        }
+    }
+}
+
+/// Fires when a finger briefly touches the [target entity](EntityEvent::event_target) without
+/// moving.
+#[derive(Clone, PartialEq, Debug, Reflect)]
+#[reflect(Clone, PartialEq)]
+pub struct Tap {
+    /// Information about the picking intersection.
+    pub hit: HitData,
+    /// How long the finger touched the screen.
+    pub duration: Duration,
+}
+
+/// Fires when a finger has touched the [target entity](EntityEvent::event_target) without moving
+/// for [`TouchGestureSettings::long_press_duration`], while it's still touching it.
+#[derive(Clone, PartialEq, Debug, Reflect)]
+#[reflect(Clone, PartialEq)]
+pub struct LongPress {
+    /// Information about the picking intersection.
+    pub hit: HitData,
+    /// How long the finger has touched the screen.
+    pub duration: Duration,
+}
+
+/// Fires when a finger quickly slides over the [target entity](EntityEvent::event_target) and is
+/// lifted.
+#[derive(Clone, PartialEq, Debug, Reflect)]
+#[reflect(Clone, PartialEq)]
+pub struct Swipe {
+    /// Information about the picking intersection.
+    pub hit: HitData,
+    /// The vector from where the finger touched the screen to where it was lifted, in logical
+    /// pixels.
+    pub distance: Vec2,
+    /// The average velocity of the finger, in logical pixels per second.
+    pub velocity: Vec2,
+}
+
+/// Fires while two fingers move apart or together over the
+/// [target entity](EntityEvent::event_target).
+#[derive(Clone, PartialEq, Debug, Reflect)]
+#[reflect(Clone, PartialEq)]
+pub struct Pinch {
+    /// The ratio of the distance between the fingers to their distance in the previous event.
+    ///
+    /// Values above `1.0` mean the fingers are moving apart, which usually zooms in.
+    pub scale: f32,
+    /// The ratio of the distance between the fingers to their distance when the second finger
+    /// touched the screen.
+    pub total_scale: f32,
+    /// How fast the scale changes, per second.
+    pub velocity: f32,
+    /// The point halfway between the fingers, in logical pixels.
+    pub center: Vec2,
+}
+
+/// Fires while two fingers turn around each other over the
+/// [target entity](EntityEvent::event_target).
+#[derive(Clone, PartialEq, Debug, Reflect)]
+#[reflect(Clone, PartialEq)]
+pub struct Rotate {
+    /// The angle, in radians, the fingers turned since the previous event.
+    ///
+    /// Positive angles turn from the x axis towards the y axis of the screen, which is clockwise
+    /// since the y axis points down.
+    pub delta: f32,
+    /// The angle, in radians, the fingers turned since the second finger touched the screen.
+    pub total: f32,
+    /// How fast the fingers turn, in radians per second.
+    pub velocity: f32,
+    /// The point halfway between the fingers, in logical pixels.
+    pub center: Vec2,
+}
+
+/// A gesture recognized by the [`GestureTracker`], before it's sent to the hit targets.
+#[derive(Clone, Debug, PartialEq)]
+enum Gesture {
+    Tap {
+        duration: Duration,
+    },
+    LongPress {
+        duration: Duration,
+    },
+    Swipe {
+        distance: Vec2,
+        velocity: Vec2,
+    },
+    Pinch {
+        scale: f32,
+        total_scale: f32,
+        velocity: f32,
+        center: Vec2,
+    },
+    Rotate {
+        delta: f32,
+        total: f32,
+        velocity: f32,
+        center: Vec2,
+    },
+}
+
+/// A finger touching the screen.
+#[derive(Debug)]
+struct TrackedTouch {
+    start: Instant,
+    start_position: Vec2,
+    /// The furthest the finger moved from its start position, which tells taps from drags.
+    max_distance: f32,
+    long_pressed: bool,
+    /// Whether the finger took part in a two-finger gesture.
+    multi_touch: bool,
+}
+
+/// Two fingers pinching or rotating.
+#[derive(Debug)]
+struct TwoFingerGesture {
+    touches: [u64; 2],
+    start_distance: f32,
+    last_vector: Vec2,
+    last_time: Instant,
+    total_rotation: f32,
+}
+
+/// Follows the fingers touching the screen, and recognizes their gestures.
+///
+/// This is the local state of [`touch_gesture_events`].
+#[derive(Debug, Default)]
+pub struct GestureTracker {
+    touches: HashMap<u64, TrackedTouch>,
+    two_fingers: Option<TwoFingerGesture>,
+}
+
+impl GestureTracker {
+    /// Updates the tracked touches, and returns the gestures recognized this frame along with
+    /// the touch that made them.
+    fn update(
+        &mut self,
+        touches: &Touches,
+        settings: &TouchGestureSettings,
+        now: Instant,
+    ) -> Vec<(u64, Gesture)> {
+        let mut gestures = Vec::new();
+
+        for touch in touches.iter_just_pressed() {
+            self.touches.insert(
+                touch.id(),
+                TrackedTouch {
+                    start: now,
+                    start_position: touch.start_position(),
+                    max_distance: 0.0,
+                    long_pressed: false,
+                    multi_touch: false,
+                },
+            );
+        }
+        if self.touches.len() > 1 {
+            for tracked in self.touches.values_mut() {
+                tracked.multi_touch = true;
+            }
+        }
+
+        for touch in touches.iter() {
+            let Some(tracked) = self.touches.get_mut(&touch.id()) else {
+                continue;
+            };
+            tracked.max_distance = tracked
+                .max_distance
+                .max(touch.position().distance(tracked.start_position));
+            let duration = now - tracked.start;
+            if !tracked.long_pressed
+                && !tracked.multi_touch
+                && tracked.max_distance <= settings.tap_max_distance
+                && duration >= settings.long_press_duration
+            {
+                tracked.long_pressed = true;
+                gestures.push((touch.id(), Gesture::LongPress { duration }));
+            }
+        }
+
+        self.update_two_fingers(touches, now, &mut gestures);
+
+        for touch in touches
+            .iter_just_released()
+            .chain(touches.iter_just_canceled())
+        {
+            let Some(tracked) = self.touches.remove(&touch.id()) else {
+                continue;
+            };
+            if touches.just_canceled(touch.id()) || tracked.long_pressed || tracked.multi_touch {
+                continue;
+            }
+            let duration = now - tracked.start;
+            let distance = touch.position() - tracked.start_position;
+            let max_distance = tracked.max_distance.max(distance.length());
+            if max_distance <= settings.tap_max_distance {
+                if duration <= settings.tap_max_duration {
+                    gestures.push((touch.id(), Gesture::Tap { duration }));
+                }
+            } else if distance.length() >= settings.swipe_min_distance {
+                let velocity = distance / duration.as_secs_f32().max(f32::EPSILON);
+                if velocity.length() >= settings.swipe_min_speed {
+                    gestures.push((touch.id(), Gesture::Swipe { distance, velocity }));
+                }
+            }
+        }
+
+        gestures
+    }
+
+    fn update_two_fingers(
+        &mut self,
+        touches: &Touches,
+        now: Instant,
+        gestures: &mut Vec<(u64, Gesture)>,
+    ) {
+        let mut pressed: Vec<_> = touches
+            .iter()
+            .filter_map(|touch| Some((self.touches.get(&touch.id())?.start, touch)))
+            .collect();
+        if pressed.len() != 2 {
+            self.two_fingers = None;
+            return;
+        }
+        pressed.sort_by_key(|(start, touch)| (*start, touch.id()));
+        let [(_, first), (_, second)] = [pressed[0], pressed[1]];
+        let ids = [first.id(), second.id()];
+        let vector = second.position() - first.position();
+        let center = first.position().midpoint(second.position());
+
+        let Some(two_fingers) = self
+            .two_fingers
+            .as_mut()
+            .filter(|two_fingers| two_fingers.touches == ids)
+        else {
+            self.two_fingers = Some(TwoFingerGesture {
+                touches: ids,
+                start_distance: vector.length(),
+                last_vector: vector,
+                last_time: now,
+                total_rotation: 0.0,
+            });
+            return;
+        };
+        if vector == two_fingers.last_vector
+            || vector == Vec2::ZERO
+            || two_fingers.last_vector == Vec2::ZERO
+        {
+            return;
+        }
+
+        let elapsed = (now - two_fingers.last_time).as_secs_f32();
+        let per_second = |change: f32| {
+            if elapsed > 0.0 {
+                change / elapsed
+            } else {
+                0.0
+            }
+        };
+
+        let scale = vector.length() / two_fingers.last_vector.length();
+        if scale != 1.0 {
+            gestures.push((
+                ids[0],
+                Gesture::Pinch {
+                    scale,
+                    total_scale: vector.length() / two_fingers.start_distance,
+                    velocity: per_second(scale - 1.0),
+                    center,
+                },
+            ));
+        }
+        let delta = two_fingers.last_vector.angle_to(vector);
+        if delta != 0.0 {
+            two_fingers.total_rotation += delta;
+            gestures.push((
+                ids[0],
+                Gesture::Rotate {
+                    delta,
+                    total: two_fingers.total_rotation,
+                    velocity: per_second(delta),
+                    center,
+                },
+            ));
+        }
+        two_fingers.last_vector = vector;
+        two_fingers.last_time = now;
+    }
+}
+
+/// A helper system param for accessing the gesture event writers.
+#[derive(SystemParam)]
+pub struct GestureMessageWriters<'w> {
+    tap_events: MessageWriter<'w, Pointer<Tap>>,
+    long_press_events: MessageWriter<'w, Pointer<LongPress>>,
+    swipe_events: MessageWriter<'w, Pointer<Swipe>>,
+    pinch_events: MessageWriter<'w, Pointer<Pinch>>,
+    rotate_events: MessageWriter<'w, Pointer<Rotate>>,
+}
+
+/// Recognizes the gestures of the fingers touching the screen, and dispatches them to the
+/// entities hovered by their touch pointers.
+///
+/// Like [`Click`](crate::events::Click), gestures that end when a finger is lifted target the
+/// entities hovered in the previous frame, because touch pointers hover nothing on the frame they
+/// are released.
+pub fn touch_gesture_events(
+    // Input
+    touches: Res<Touches>,
+    settings: Res<TouchGestureSettings>,
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    hover_map: Res<HoverMap>,
+    previous_hover_map: Res<PreviousHoverMap>,
+    // Locals
+    mut tracker: Local<GestureTracker>,
+    // Output
+    mut commands: Commands,
+    mut message_writers: GestureMessageWriters,
+) {
+    let gestures = tracker.update(&touches, &settings, Instant::now());
+    for (touch_id, gesture) in gestures {
+        let pointer_id = PointerId::Touch(touch_id);
+        let Some(location) = pointers
+            .iter()
+            .find(|(id, _)| **id == pointer_id)
+            .and_then(|(_, location)| location.location())
+        else {
+            continue;
+        };
+        let hovered = hover_map
+            .get(&pointer_id)
+            .filter(|hovered| !hovered.is_empty())
+            .or_else(|| previous_hover_map.get(&pointer_id));
+
+        for (&entity, hit) in hovered.into_iter().flatten() {
+            let hit = hit.clone();
+            macro_rules! dispatch {
+                ($writer:ident, $event:expr) => {{
+                    let event = Pointer::new(pointer_id, location.clone(), $event, entity);
+                    commands.trigger(event.clone());
+                    message_writers.$writer.write(event);
+                }};
+            }
+            match gesture {
+                Gesture::Tap { duration } => dispatch!(tap_events, Tap { hit, duration }),
+                Gesture::LongPress { duration } => {
+                    dispatch!(long_press_events, LongPress { hit, duration });
+                }
+                Gesture::Swipe { distance, velocity } => dispatch!(
+                    swipe_events,
+                    Swipe {
+                        hit,
+                        distance,
+                        velocity
+                    }
+                ),
+                Gesture::Pinch {
+                    scale,
+                    total_scale,
+                    velocity,
+                    center,
+                } => dispatch!(
+                    pinch_events,
+                    Pinch {
+                        scale,
+                        total_scale,
+                        velocity,
+                        center
+                    }
+                ),
+                Gesture::Rotate {
+                    delta,
+                    total,
+                    velocity,
+                    center,
+                } => dispatch!(
+                    rotate_events,
+                    Rotate {
+                        delta,
+                        total,
+                        velocity,
+                        center
+                    }
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use bevy_ecs::{message::Messages, system::SystemId, world::World};
+    use bevy_input::touch::{touch_screen_input_system, TouchInput, TouchPhase, Touches};
+    use bevy_math::Vec2;
+    use bevy_platform::time::Instant;
+
+    use super::{Gesture, GestureTracker, TouchGestureSettings};
+
+    struct Harness {
+        world: World,
+        update_touches: SystemId,
+        tracker: GestureTracker,
+        start: Instant,
+    }
+
+    impl Harness {
+        fn new() -> Self {
+            let mut world = World::new();
+            world.init_resource::<Touches>();
+            world.init_resource::<Messages<TouchInput>>();
+            Self {
+                update_touches: world.register_system(touch_screen_input_system),
+                world,
+                tracker: GestureTracker::default(),
+                start: Instant::now(),
+            }
+        }
+
+        fn touch(&mut self, id: u64, phase: TouchPhase, position: Vec2) {
+            let window = self.world.spawn_empty().id();
+            self.world.write_message(TouchInput {
+                phase,
+                position,
+                window,
+                force: None,
+                id,
+            });
+        }
+
+        fn update(&mut self, millis: u64) -> Vec<Gesture> {
+            self.world.run_system(self.update_touches).unwrap();
+            self.world.resource_mut::<Messages<TouchInput>>().update();
+            let now = self.start + Duration::from_millis(millis);
+            let touches = self.world.resource::<Touches>();
+            self.tracker
+                .update(touches, &TouchGestureSettings::default(), now)
+                .into_iter()
+                .map(|(_, gesture)| gesture)
+                .collect()
+        }
+    }
+
+    #[test]
+    fn tap_and_long_press() {
+        let mut harness = Harness::new();
+        harness.touch(0, TouchPhase::Started, Vec2::ZERO);
+        assert_eq!(harness.update(0), []);
+        harness.touch(0, TouchPhase::Ended, Vec2::new(2.0, 0.0));
+        assert_eq!(
+            harness.update(100),
+            [Gesture::Tap {
+                duration: Duration::from_millis(100)
+            }]
+        );
+
+        harness.touch(1, TouchPhase::Started, Vec2::ZERO);
+        harness.update(200);
+        assert_eq!(
+            harness.update(800),
+            [Gesture::LongPress {
+                duration: Duration::from_millis(600)
+            }]
+        );
+        // A long press isn't a tap
+        harness.touch(1, TouchPhase::Ended, Vec2::ZERO);
+        assert_eq!(harness.update(900), []);
+    }
+
+    #[test]
+    fn swipe() {
+        let mut harness = Harness::new();
+        harness.touch(0, TouchPhase::Started, Vec2::ZERO);
+        harness.update(0);
+        harness.touch(0, TouchPhase::Moved, Vec2::new(200.0, 0.0));
+        harness.update(100);
+        harness.touch(0, TouchPhase::Ended, Vec2::new(200.0, 0.0));
+        assert_eq!(
+            harness.update(200),
+            [Gesture::Swipe {
+                distance: Vec2::new(200.0, 0.0),
+                velocity: Vec2::new(1000.0, 0.0),
+            }]
+        );
+    }
+
+    #[test]
+    fn pinch_and_rotate() {
+        let mut harness = Harness::new();
+        harness.touch(0, TouchPhase::Started, Vec2::new(-10.0, 0.0));
+        harness.touch(1, TouchPhase::Started, Vec2::new(10.0, 0.0));
+        assert_eq!(harness.update(0), []);
+
+        // The fingers move twice as far apart, and turn a quarter turn
+        harness.touch(0, TouchPhase::Moved, Vec2::new(0.0, -20.0));
+        harness.touch(1, TouchPhase::Moved, Vec2::new(0.0, 20.0));
+        let gestures = harness.update(500);
+        let [Gesture::Pinch {
+            scale,
+            total_scale,
+            velocity,
+            center,
+        }, Gesture::Rotate { delta, total, .. }] = gestures[..]
+        else {
+            panic!("expected a pinch and a rotation, got {gestures:?}");
+        };
+        assert!((scale - 2.0).abs() < 1e-5 && (total_scale - 2.0).abs() < 1e-5);
+        assert!((velocity - 2.0).abs() < 1e-4);
+        assert_eq!(center, Vec2::ZERO);
+        assert!((delta - core::f32::consts::FRAC_PI_2).abs() < 1e-5);
+        assert_eq!(delta, total);
+
+        // Fingers that pinched don't tap when they're lifted
+        harness.touch(0, TouchPhase::Ended, Vec2::new(0.0, -20.0));
+        harness.touch(1, TouchPhase::Ended, Vec2::new(0.0, 20.0));
+        assert_eq!(harness.update(600), []);
+    }
+}
@@ -1,6 +1,7 @@
 //! This module provides unsurprising default inputs to `bevy_picking` through [`PointerInput`].
-//! The included systems are responsible for sending  mouse and touch inputs to their
-//! respective `Pointer`s.
+//! The included systems are responsible for sending  mouse, touch and pen inputs to their
+//! respective `Pointer`s. Mouse input can either drive a single pointer that follows the cursor, or
+//! a pointer for each pointing device, for local multiplayer or setups with several mice.
 //!
 //! Because this has it's own plugin, it's easy to omit it, and provide your own inputs as
 //! needed. Because `Pointer`s aren't coupled to the underlying input hardware, you can easily mock
@@ -15,7 +16,8 @@ use bevy_app::prelude::*;
 use bevy_camera::RenderTarget;
 use bevy_ecs::prelude::*;
 use bevy_input::{
-    mouse::MouseWheel,
+    mouse::{MouseWheel, PointingDeviceId},
+    pen::{PenBarrelButtons, PenInput, PenPhase},
     prelude::*,
     touch::{TouchInput, TouchPhase},
     ButtonState,
@@ -23,7 +25,7 @@ use bevy_input::{
 use bevy_math::Vec2;
 use bevy_platform::collections::{HashMap, HashSet};
 use bevy_reflect::prelude::*;
-use bevy_window::{PrimaryWindow, WindowEvent, WindowRef};
+use bevy_window::{PrimaryWindow, Window, WindowEvent, WindowRef};
 use tracing::debug;
 
 use crate::pointer::{
@@ -41,7 +43,7 @@ pub mod prelude {
 
 #[derive(Copy, Clone, Resource, Debug, Reflect)]
 #[reflect(Resource, Default, Clone)]
-/// Settings for enabling and disabling updating mouse and touch inputs for picking
+/// Settings for enabling and disabling updating mouse, touch and pen inputs for picking
 ///
 /// ## Custom initialization
 /// ```
@@ -51,6 +53,8 @@ pub mod prelude {
 ///     .insert_resource(PointerInputSettings {
 ///         is_touch_enabled: false,
 ///         is_mouse_enabled: true,
+///         is_mouse_device_enabled: false,
+///         is_pen_enabled: true,
 ///     })
 ///     // or DefaultPlugins
 ///     .add_plugins(PointerInputPlugin);
@@ -60,6 +64,13 @@ pub struct PointerInputSettings {
     pub is_touch_enabled: bool,
     /// Should mouse inputs be updated?
     pub is_mouse_enabled: bool,
+    /// Should each pointing device drive its own pointer?
+    ///
+    /// See [`mouse_device_pick_events`]. Disable `is_mouse_enabled` as well if the pointer following
+    /// the cursor of the operating system shouldn't be used alongside them.
+    pub is_mouse_device_enabled: bool,
+    /// Should pen inputs be updated?
+    pub is_pen_enabled: bool,
 }
 
 impl PointerInputSettings {
@@ -67,9 +78,17 @@ impl PointerInputSettings {
         state.is_mouse_enabled
     }
 
+    fn is_mouse_device_enabled(state: Res<Self>) -> bool {
+        state.is_mouse_device_enabled
+    }
+
     fn is_touch_enabled(state: Res<Self>) -> bool {
         state.is_touch_enabled
     }
+
+    fn is_pen_enabled(state: Res<Self>) -> bool {
+        state.is_pen_enabled
+    }
 }
 
 impl Default for PointerInputSettings {
@@ -77,14 +96,16 @@ impl Default for PointerInputSettings {
         Self {
             is_touch_enabled: true,
             is_mouse_enabled: true,
+            is_mouse_device_enabled: false,
+            is_pen_enabled: true,
         }
     }
 }
 
-/// Adds mouse and touch inputs for picking pointers to your app. This is a default input plugin,
-/// that you can replace with your own plugin as needed.
+/// Adds mouse, touch and pen inputs for picking pointers to your app. This is a default input
+/// plugin, that you can replace with your own plugin as needed.
 ///
-/// Toggling mouse input or touch input can be done at runtime by modifying
+/// Toggling mouse input, touch input or pen input can be done at runtime by modifying
 /// [`PointerInputSettings`] resource.
 ///
 /// [`PointerInputSettings`] can be initialized with custom values, but will be
@@ -100,14 +121,19 @@ impl Plugin for PointerInputPlugin {
                 First,
                 (
                     mouse_pick_events.run_if(PointerInputSettings::is_mouse_enabled),
+                    mouse_device_pick_events.run_if(PointerInputSettings::is_mouse_device_enabled),
                     touch_pick_events.run_if(PointerInputSettings::is_touch_enabled),
+                    pen_pick_events.run_if(PointerInputSettings::is_pen_enabled),
                 )
                     .chain()
                     .in_set(PickingSystems::Input),
             )
             .add_systems(
                 Last,
-                deactivate_touch_pointers.run_if(PointerInputSettings::is_touch_enabled),
+                (
+                    deactivate_touch_pointers.run_if(PointerInputSettings::is_touch_enabled),
+                    deactivate_pen_pointers.run_if(PointerInputSettings::is_pen_enabled),
+                ),
             );
     }
 }
@@ -173,7 +199,9 @@ pub fn mouse_pick_events(
                 pointer_inputs.write(PointerInput::new(PointerId::Mouse, location, action));
             }
             WindowEvent::MouseWheel(event) => {
-                let MouseWheel { unit, x, y, window } = *event;
+                let MouseWheel {
+                    unit, x, y, window, ..
+                } = *event;
 
                 let location = Location {
                     target: match RenderTarget::Window(WindowRef::Entity(window))
@@ -194,6 +222,106 @@ pub fn mouse_pick_events(
     }
 }
 
+/// Sends the events of a pointer for each pointing device, to be processed by the core plugin
+///
+/// The operating system moves a single cursor for all the mice, so the pointer of each device
+/// instead starts at the center of the primary window when the device first moves, and is moved by
+/// the raw [`MouseMotion`] of the device within the bounds of the window.
+///
+/// Button and wheel events go to the pointer of their device. As most desktop platforms report
+/// these events for a single device shared by all the mice (see [`PointingDeviceId`]), the events
+/// of a device that never moved go to the pointer of the device that moved last.
+pub fn mouse_device_pick_events(
+    // Input
+    mut window_events: MessageReader<WindowEvent>,
+    primary_window: Query<(Entity, &Window), With<PrimaryWindow>>,
+    // Locals
+    mut positions: Local<HashMap<PointingDeviceId, Vec2>>,
+    mut last_moved: Local<Option<PointingDeviceId>>,
+    // Output
+    mut commands: Commands,
+    mut pointer_inputs: MessageWriter<PointerInput>,
+) {
+    let Ok((window_entity, window)) = primary_window.single() else {
+        window_events.clear();
+        return;
+    };
+    let Some(target) = RenderTarget::Window(WindowRef::Primary).normalize(Some(window_entity))
+    else {
+        window_events.clear();
+        return;
+    };
+
+    for window_event in window_events.read() {
+        let (device, action) = match window_event {
+            WindowEvent::MouseMotion(motion) => {
+                let position = positions.entry(motion.device).or_insert_with(|| {
+                    let position = window.size() / 2.0;
+                    let pointer = PointerId::MouseDevice(motion.device.0);
+                    debug!("Spawning pointer {:?}", pointer);
+                    commands.spawn((
+                        pointer,
+                        PointerLocation::new(Location {
+                            target: target.clone(),
+                            position,
+                        }),
+                    ));
+                    position
+                });
+                let last_position = *position;
+                *position = (last_position + motion.delta / window.scale_factor())
+                    .clamp(Vec2::ZERO, window.size());
+                *last_moved = Some(motion.device);
+                (
+                    motion.device,
+                    PointerAction::Move {
+                        delta: *position - last_position,
+                    },
+                )
+            }
+            WindowEvent::MouseButtonInput(input) => {
+                let button = match input.button {
+                    MouseButton::Left => PointerButton::Primary,
+                    MouseButton::Right => PointerButton::Secondary,
+                    MouseButton::Middle => PointerButton::Middle,
+                    MouseButton::Other(_) | MouseButton::Back | MouseButton::Forward => continue,
+                };
+                let action = match input.state {
+                    ButtonState::Pressed => PointerAction::Press(button),
+                    ButtonState::Released => PointerAction::Release(button),
+                };
+                (input.device, action)
+            }
+            WindowEvent::MouseWheel(event) => (
+                event.device,
+                PointerAction::Scroll {
+                    x: event.x,
+                    y: event.y,
+                    unit: event.unit,
+                },
+            ),
+            _ => continue,
+        };
+
+        let device = match positions.contains_key(&device) {
+            true => device,
+            false => match *last_moved {
+                Some(last_moved) => last_moved,
+                None => continue,
+            },
+        };
+        let location = Location {
+            target: target.clone(),
+            position: positions[&device],
+        };
+        pointer_inputs.write(PointerInput::new(
+            PointerId::MouseDevice(device.0),
+            location,
+            action,
+        ));
+    }
+}
+
 /// Sends touch pointer events to be consumed by the core plugin
 pub fn touch_pick_events(
     // Input
@@ -267,6 +395,115 @@ pub fn touch_pick_events(
     }
 }
 
+/// Sends pen pointer events to be consumed by the core plugin
+///
+/// The tip of the pen is the primary button of the pointer, and the primary and secondary barrel
+/// buttons are its secondary and middle buttons.
+pub fn pen_pick_events(
+    // Input
+    mut window_events: MessageReader<WindowEvent>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    // Locals
+    mut pen_cache: Local<HashMap<u64, (PenInput, bool)>>,
+    // Output
+    mut commands: Commands,
+    mut pointer_inputs: MessageWriter<PointerInput>,
+) {
+    for window_event in window_events.read() {
+        let WindowEvent::PenInput(pen) = window_event else {
+            continue;
+        };
+        let pointer = PointerId::Pen(pen.id);
+        let location = Location {
+            target: match RenderTarget::Window(WindowRef::Entity(pen.window))
+                .normalize(primary_window.single().ok())
+            {
+                Some(target) => target,
+                None => continue,
+            },
+            position: pen.position,
+        };
+
+        let (last_position, last_barrel_buttons, was_down) = match pen_cache.get(&pen.id) {
+            Some(&(last_pen, was_down)) => (last_pen.position, last_pen.barrel_buttons, was_down),
+            None => {
+                if let PenPhase::Left | PenPhase::Canceled = pen.phase {
+                    continue;
+                }
+                debug!("Spawning pointer {:?}", pointer);
+                commands.spawn((pointer, PointerLocation::new(location.clone())));
+                (pen.position, PenBarrelButtons::default(), false)
+            }
+        };
+
+        if pen.phase == PenPhase::Canceled {
+            pointer_inputs.write(PointerInput::new(pointer, location, PointerAction::Cancel));
+            pen_cache.remove(&pen.id);
+            continue;
+        }
+        if pen.position != last_position {
+            pointer_inputs.write(PointerInput::new(
+                pointer,
+                location.clone(),
+                PointerAction::Move {
+                    delta: pen.position - last_position,
+                },
+            ));
+        }
+
+        // Buttons held while the pen goes out of range are released
+        let (down, barrel_buttons) = match pen.phase {
+            PenPhase::Down => (true, pen.barrel_buttons),
+            PenPhase::Up => (false, pen.barrel_buttons),
+            PenPhase::Left => (false, PenBarrelButtons::default()),
+            PenPhase::Entered | PenPhase::Moved | PenPhase::Canceled => {
+                (was_down, pen.barrel_buttons)
+            }
+        };
+        if down != was_down {
+            let action = match down {
+                true => PointerAction::Press(PointerButton::Primary),
+                false => PointerAction::Release(PointerButton::Primary),
+            };
+            pointer_inputs.write(PointerInput::new(pointer, location.clone(), action));
+        }
+        write_barrel_buttons(
+            pointer,
+            &location,
+            last_barrel_buttons,
+            barrel_buttons,
+            &mut pointer_inputs,
+        );
+
+        if pen.phase == PenPhase::Left {
+            pen_cache.remove(&pen.id);
+        } else {
+            pen_cache.insert(pen.id, (*pen, down));
+        }
+    }
+}
+
+/// Sends the presses and releases of the pen barrel buttons that changed from `last` to `current`.
+fn write_barrel_buttons(
+    pointer: PointerId,
+    location: &Location,
+    last: PenBarrelButtons,
+    current: PenBarrelButtons,
+    pointer_inputs: &mut MessageWriter<PointerInput>,
+) {
+    for (button, was_pressed, pressed) in [
+        (PointerButton::Secondary, last.primary, current.primary),
+        (PointerButton::Middle, last.secondary, current.secondary),
+    ] {
+        let action = match (was_pressed, pressed) {
+            (false, true) => PointerAction::Press(button),
+            (true, false) => PointerAction::Release(button),
+            _ => continue,
+        };
+        pointer_inputs.write(PointerInput::new(pointer, location.clone(), action));
+    }
+}
+
 /// Deactivates unused touch pointers.
 ///
 /// Because each new touch gets assigned a new ID, we need to remove the pointers associated with
@@ -292,3 +529,26 @@ pub fn deactivate_touch_pointers(
         commands.entity(entity).despawn();
     }
 }
+
+/// Deactivates the pointers of pens that went out of range.
+pub fn deactivate_pen_pointers(
+    mut commands: Commands,
+    mut despawn_list: Local<HashSet<(Entity, PointerId)>>,
+    pointers: Query<(Entity, &PointerId)>,
+    mut pens: MessageReader<PenInput>,
+) {
+    for pen in pens.read() {
+        if let PenPhase::Left | PenPhase::Canceled = pen.phase {
+            for (entity, pointer) in &pointers {
+                if pointer.get_pen_id() == Some(pen.id) {
+                    despawn_list.insert((entity, *pointer));
+                }
+            }
+        }
+    }
+    // A hash set is used to prevent despawning the same entity twice.
+    for (entity, pointer) in despawn_list.drain() {
+        debug!("Despawning pointer {:?}", pointer);
+        commands.entity(entity).despawn();
+    }
+}
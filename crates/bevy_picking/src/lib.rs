@@ -157,7 +157,9 @@
 extern crate alloc;
 
 pub mod backend;
+pub mod cursor;
 pub mod events;
+pub mod gestures;
 pub mod hover;
 pub mod input;
 #[cfg(feature = "mesh_picking")]
@@ -182,8 +184,14 @@ pub mod prelude {
     };
     #[doc(hidden)]
     pub use crate::{
-        events::*, input::PointerInputPlugin, pointer::PointerButton, DefaultPickingPlugins,
-        InteractionPlugin, Pickable, PickingPlugin,
+        cursor::{CursorIconPlugin, DefaultCursor, EntityCursor},
+        events::*,
+        gestures::{
+            LongPress, Pinch, Rotate, Swipe, Tap, TouchGesturePlugin, TouchGestureSettings,
+        },
+        input::PointerInputPlugin,
+        pointer::PointerButton,
+        DefaultPickingPlugins, InteractionPlugin, Pickable, PickingPlugin,
     };
 }
 
@@ -275,8 +283,9 @@ pub enum PickingSystems {
     Last,
 }
 
-/// One plugin that contains the [`PointerInputPlugin`](input::PointerInputPlugin), [`PickingPlugin`]
-/// and the [`InteractionPlugin`], this is probably the plugin that will be most used.
+/// One plugin that contains the [`PointerInputPlugin`](input::PointerInputPlugin), [`PickingPlugin`],
+/// the [`InteractionPlugin`] and the [`TouchGesturePlugin`](gestures::TouchGesturePlugin), this is
+/// probably the plugin that will be most used.
 ///
 /// Note: for any of these plugins to work, they require a picking backend to be active,
 /// The picking backend is responsible to turn an input, into a [`PointerHits`](`crate::backend::PointerHits`)
@@ -290,6 +299,7 @@ impl PluginGroup for DefaultPickingPlugins {
             .add(input::PointerInputPlugin)
             .add(PickingPlugin)
             .add(InteractionPlugin)
+            .add(gestures::TouchGesturePlugin)
     }
 }
 
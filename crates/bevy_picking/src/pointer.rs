@@ -23,7 +23,8 @@ use core::{fmt::Debug, ops::Deref};
 
 use crate::backend::HitData;
 
-/// Identifies a unique pointer entity. `Mouse` and `Touch` pointers are automatically spawned.
+/// Identifies a unique pointer entity. `Mouse`, `MouseDevice`, `Touch` and `Pen` pointers are
+/// automatically spawned.
 ///
 /// This component is needed because pointers can be spawned and despawned, but they need to have a
 /// stable ID that persists regardless of the Entity they are associated with.
@@ -34,8 +35,18 @@ pub enum PointerId {
     /// The mouse pointer.
     #[default]
     Mouse,
+    /// The pointer of a single pointing device, numbered by the
+    /// [`PointingDeviceId`](bevy_input::mouse::PointingDeviceId) of its mouse events.
+    ///
+    /// These pointers are only used when
+    /// [`PointerInputSettings::is_mouse_device_enabled`](crate::input::PointerInputSettings::is_mouse_device_enabled)
+    /// is set.
+    MouseDevice(u64),
     /// A touch input, usually numbered by window touch events from `winit`.
     Touch(u64),
+    /// A pen or stylus input, numbered by the id of its [`PenInput`](bevy_input::pen::PenInput)
+    /// events.
+    Pen(u64),
     /// A custom, uniquely identified pointer. Useful for mocking inputs or implementing a software
     /// controlled cursor.
     #[reflect(ignore, clone)]
@@ -47,10 +58,18 @@ impl PointerId {
     pub fn is_touch(&self) -> bool {
         matches!(self, PointerId::Touch(_))
     }
+    /// Returns true if the pointer is a pen input.
+    pub fn is_pen(&self) -> bool {
+        matches!(self, PointerId::Pen(_))
+    }
     /// Returns true if the pointer is the mouse.
     pub fn is_mouse(&self) -> bool {
         matches!(self, PointerId::Mouse)
     }
+    /// Returns true if the pointer is a single pointing device.
+    pub fn is_mouse_device(&self) -> bool {
+        matches!(self, PointerId::MouseDevice(_))
+    }
     /// Returns true if the pointer is a custom input.
     pub fn is_custom(&self) -> bool {
         matches!(self, PointerId::Custom(_))
@@ -63,6 +82,22 @@ impl PointerId {
             None
         }
     }
+    /// Returns the device id if the pointer is a single pointing device.
+    pub fn get_mouse_device_id(&self) -> Option<u64> {
+        if let PointerId::MouseDevice(id) = self {
+            Some(*id)
+        } else {
+            None
+        }
+    }
+    /// Returns the pen id if the pointer is a pen input.
+    pub fn get_pen_id(&self) -> Option<u64> {
+        if let PointerId::Pen(id) = self {
+            Some(*id)
+        } else {
+            None
+        }
+    }
 }
 
 /// Holds a list of entities this pointer is currently interacting with, sorted from nearest to
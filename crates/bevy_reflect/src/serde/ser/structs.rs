@@ -41,19 +41,35 @@ impl<P: ReflectSerializerProcessor> Serialize for StructSerializer<'_, P> {
             .registry
             .get(type_info.type_id())
             .and_then(|registration| registration.data::<SerializationData>());
-        let ignored_len = serialization_data.map(SerializationData::len).unwrap_or(0);
-        let mut state = serializer.serialize_struct(
-            struct_info.type_path_table().ident().unwrap(),
-            self.struct_value.field_len() - ignored_len,
-        )?;
+        // The fields are looked up by name, since a dynamic struct may only hold some of the
+        // fields of the type it represents, in any order.
+        let is_skipped = |name: &str| {
+            serialization_data.is_some_and(|data| {
+                struct_info
+                    .index_of(name)
+                    .is_some_and(|index| data.is_field_skipped(index))
+            })
+        };
+        let len = (0..self.struct_value.field_len())
+            .filter_map(|index| self.struct_value.name_at(index))
+            .filter(|name| !is_skipped(name))
+            .count();
+        let mut state =
+            serializer.serialize_struct(struct_info.type_path_table().ident().unwrap(), len)?;
 
         for (index, value) in self.struct_value.iter_fields().enumerate() {
-            if serialization_data.is_some_and(|data| data.is_field_skipped(index)) {
+            let name = self.struct_value.name_at(index).unwrap_or_default();
+            if is_skipped(name) {
                 continue;
             }
-            let key = struct_info.field_at(index).unwrap().name();
+            let field = struct_info.field(name).ok_or_else(|| {
+                make_custom_error(format_args!(
+                    "unknown field `{name}` for `{}`",
+                    struct_info.type_path()
+                ))
+            })?;
             state.serialize_field(
-                key,
+                field.name(),
                 &TypedReflectSerializer::new_internal(value, self.registry, self.processor),
             )?;
         }
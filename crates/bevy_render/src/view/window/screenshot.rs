@@ -1,5 +1,6 @@
 use super::ExtractedWindows;
 use crate::{
+    camera::CameraRenderGraph,
     gpu_readback,
     render_asset::RenderAssets,
     render_resource::{
@@ -9,23 +10,36 @@ use crate::{
         SpecializedRenderPipelines, Texture, TextureUsages, TextureView, VertexState,
     },
     renderer::RenderDevice,
+    sync_world::RenderEntity,
     texture::{GpuImage, ManualTextureViews, OutputColorAttachment},
-    view::{prepare_view_attachments, prepare_view_targets, ViewTargetAttachments, WindowSurfaces},
+    view::{
+        prepare_view_attachments, prepare_view_targets, Hdr, Msaa, ViewTarget,
+        ViewTargetAttachments, WindowSurfaces,
+    },
     ExtractSchedule, MainWorld, Render, RenderApp, RenderStartup, RenderSystems,
 };
 use alloc::{borrow::Cow, sync::Arc};
-use bevy_app::{First, Plugin, Update};
-use bevy_asset::{embedded_asset, load_embedded_asset, AssetServer, Handle, RenderAssetUsages};
-use bevy_camera::{ManualTextureViewHandle, NormalizedRenderTarget, RenderTarget};
+use bevy_app::{First, Plugin, PostUpdate, Update};
+use bevy_asset::{
+    embedded_asset, load_embedded_asset, AssetServer, Assets, Handle, RenderAssetUsages,
+};
+use bevy_camera::{
+    visibility::{Layer, RenderLayers},
+    Camera, Camera2d, Camera3d, CameraUpdateSystems, ClearColorConfig, ManualTextureViewHandle,
+    NormalizedRenderTarget, Projection, RenderTarget,
+};
+use bevy_color::Color;
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     entity::EntityHashMap, message::message_update_system, prelude::*, system::SystemState,
 };
 use bevy_image::{Image, TextureFormatPixelInfo, ToExtents};
+use bevy_math::URect;
 use bevy_platform::collections::HashSet;
 use bevy_reflect::Reflect;
 use bevy_shader::Shader;
 use bevy_tasks::AsyncComputeTaskPool;
+use bevy_transform::{components::Transform, TransformSystems};
 use bevy_utils::default;
 use bevy_window::{PrimaryWindow, WindowRef};
 use core::ops::Deref;
@@ -37,7 +51,7 @@ use std::{
     },
 };
 use tracing::{error, info, warn};
-use wgpu::{CommandEncoder, Extent3d, TextureFormat};
+use wgpu::{CommandEncoder, Extent3d, Origin3d, TextureFormat};
 
 #[derive(EntityEvent, Reflect, Deref, DerefMut, Debug)]
 #[reflect(Debug)]
@@ -74,6 +88,111 @@ pub struct ScreenshotCaptured {
 #[reflect(Component, Debug)]
 pub struct Screenshot(pub RenderTarget);
 
+/// A component that signals to the renderer to capture the HDR color buffer of a camera this frame,
+/// before it is tonemapped.
+///
+/// This works like [`Screenshot`], but the captured image contains the linear color values of the
+/// scene rather than what is displayed on screen. The camera must have HDR enabled, otherwise the
+/// screenshot entity is despawned without being captured. The image will have the
+/// [`ViewTarget::TEXTURE_FORMAT_HDR`] format. Use [`save_to_disk`] with an `.exr` or
+/// `.hdr` path to inspect it outside of Bevy (this requires the `exr` or `hdr` feature).
+///
+/// # Usage
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::view::screenshot::{save_to_disk, HdrScreenshot};
+///
+/// fn take_hdr_screenshot(mut commands: Commands, camera: Entity) {
+///    commands.spawn(HdrScreenshot(camera))
+///       .observe(save_to_disk("screenshot.exr"));
+/// }
+/// ```
+#[derive(Component, Deref, DerefMut, Reflect, Debug)]
+#[reflect(Component, Debug)]
+pub struct HdrScreenshot(pub Entity);
+
+/// A component that restricts a [`Screenshot`] or an [`HdrScreenshot`] on the same entity to a
+/// rectangle of its target, in physical pixels from the top-left corner.
+///
+/// The part of the rectangle outside of the target is ignored. If the rectangle doesn't overlap the
+/// target, the screenshot is skipped and its entity is despawned.
+///
+/// # Usage
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::URect;
+/// # use bevy_render::view::screenshot::{save_to_disk, Screenshot, ScreenshotRegion};
+///
+/// fn take_minimap_screenshot(mut commands: Commands) {
+///    commands.spawn((
+///        Screenshot::primary_window(),
+///        ScreenshotRegion(URect::new(0, 0, 256, 256)),
+///    ))
+///    .observe(save_to_disk("minimap.png"));
+/// }
+/// ```
+#[derive(Component, Deref, DerefMut, Reflect, Debug, Clone, Copy)]
+#[reflect(Component, Debug, Clone)]
+pub struct ScreenshotRegion(pub URect);
+
+/// A component that captures only some entities seen by a camera, against a transparent
+/// background, for thumbnails and photo modes.
+///
+/// A copy of the camera renders the entities and their descendants to an image for a frame, which
+/// is captured like a [`Screenshot`]. During that frame, the entities are added to the
+/// [`RenderLayers`] layer [`IsolatedScreenshot::layer`], which the rest of the scene must not be
+/// on. Lights only light the entities if they are part of them.
+///
+/// # Usage
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::view::screenshot::{IsolatedScreenshot, ScreenshotCaptured};
+///
+/// fn take_thumbnail(mut commands: Commands, camera: Entity, item: Entity, light: Entity) {
+///    commands.spawn(IsolatedScreenshot::new(camera, [item, light]))
+///       .observe(|captured: On<ScreenshotCaptured>| {
+///           // The image has an alpha channel, which is transparent around the item.
+///           let thumbnail = &captured.image;
+///       });
+/// }
+/// ```
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component, Debug, Clone)]
+pub struct IsolatedScreenshot {
+    /// The camera the entities are seen by.
+    pub camera: Entity,
+    /// The captured entities, with their descendants.
+    pub entities: Vec<Entity>,
+    /// The render layer the entities are added to while they are captured.
+    pub layer: Layer,
+}
+
+impl IsolatedScreenshot {
+    /// The default [`IsolatedScreenshot::layer`].
+    pub const DEFAULT_LAYER: Layer = 31;
+
+    /// Capture the given entities, as seen by the given camera.
+    pub fn new(camera: Entity, entities: impl IntoIterator<Item = Entity>) -> Self {
+        Self {
+            camera,
+            entities: entities.into_iter().collect(),
+            layer: Self::DEFAULT_LAYER,
+        }
+    }
+}
+
+/// The state of an [`IsolatedScreenshot`] that is being captured.
+#[derive(Component)]
+struct IsolatedScreenshotState {
+    /// The copy of the camera rendering the entities.
+    camera: Entity,
+    /// The render layers the entities had before being captured.
+    render_layers: Vec<(Entity, Option<RenderLayers>)>,
+}
+
 /// A marker component that indicates that a screenshot is currently being captured.
 #[derive(Component, Default)]
 pub struct Capturing;
@@ -110,6 +229,9 @@ struct ScreenshotPreparedState {
     pub buffer: Buffer,
     pub bind_group: BindGroup,
     pub pipeline_id: CachedRenderPipelineId,
+    /// The origin of the captured region of the texture.
+    pub origin: Origin3d,
+    /// The size of the captured region of the texture.
     pub size: Extent3d,
 }
 
@@ -122,6 +244,30 @@ struct RenderScreenshotTargets(EntityHashMap<NormalizedRenderTarget>);
 #[derive(Resource, Deref, DerefMut, Default)]
 struct RenderScreenshotsPrepared(EntityHashMap<ScreenshotPreparedState>);
 
+/// The [`ScreenshotRegion`]s of the screenshots to capture, by screenshot entity.
+#[derive(Resource, Deref, DerefMut, Default)]
+struct RenderScreenshotRegions(EntityHashMap<ScreenshotRegion>);
+
+/// The screenshot entities of the [`HdrScreenshot`]s to capture, by render world camera entity.
+#[derive(Resource, Deref, DerefMut, Default)]
+struct RenderHdrScreenshotTargets(EntityHashMap<Entity>);
+
+struct HdrScreenshotPreparedState {
+    screenshot: Entity,
+    buffer: Buffer,
+    origin: Origin3d,
+    size: Extent3d,
+    format: TextureFormat,
+}
+
+#[derive(Resource, Deref, DerefMut, Default)]
+struct RenderHdrScreenshotsPrepared(EntityHashMap<HdrScreenshotPreparedState>);
+
+/// The screenshot entities that can't be captured, which are despawned during the next extraction
+/// so that they don't stay [`Capturing`] forever.
+#[derive(Resource, Deref, DerefMut, Default)]
+struct RenderFailedScreenshots(Vec<Entity>);
+
 #[derive(Resource, Deref, DerefMut)]
 struct RenderScreenshotsSender(Sender<(Entity, Image)>);
 
@@ -135,7 +281,13 @@ pub fn save_to_disk(path: impl AsRef<Path>) -> impl FnMut(On<ScreenshotCaptured>
                 Ok(format) => {
                     // discard the alpha channel which stores brightness values when HDR is enabled to make sure
                     // the screenshot looks right
-                    let img = dyn_img.to_rgb8();
+                    let img = match format {
+                        // keep the full range of the color values for HDR formats
+                        image::ImageFormat::OpenExr | image::ImageFormat::Hdr => {
+                            image::DynamicImage::ImageRgb32F(dyn_img.to_rgb32f())
+                        }
+                        _ => image::DynamicImage::ImageRgb8(dyn_img.to_rgb8()),
+                    };
                     #[cfg(not(target_arch = "wasm32"))]
                     match img.save_with_format(&path, format) {
                         Ok(_) => info!("Screenshot saved to {}", path.display()),
@@ -193,6 +345,107 @@ fn clear_screenshots(mut commands: Commands, screenshots: Query<Entity, With<Cap
     }
 }
 
+/// Spawns the copies of the cameras of the new [`IsolatedScreenshot`]s, and adds their entities to
+/// the layer of the copies.
+fn prepare_isolated_screenshots(
+    mut commands: Commands,
+    screenshots: Query<(Entity, &IsolatedScreenshot), Without<Screenshot>>,
+    cameras: Query<&Camera>,
+    children: Query<&Children>,
+    render_layers: Query<Option<&RenderLayers>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    for (entity, screenshot) in &screenshots {
+        let Ok(camera) = cameras.get(screenshot.camera) else {
+            warn!(
+                "Unknown camera for isolated screenshot, skipping: {}",
+                screenshot.camera
+            );
+            commands.entity(entity).despawn();
+            continue;
+        };
+        let Some(size) = camera.physical_target_size() else {
+            // The size of the target of the camera isn't known yet, try again next frame.
+            continue;
+        };
+
+        let image = images.add(Image::new_target_texture(
+            size.x,
+            size.y,
+            TextureFormat::Rgba8UnormSrgb,
+        ));
+        // Only the components needed to render are copied, so that the copy isn't mistaken for the
+        // camera by the queries of the app.
+        let isolated_camera = commands
+            .entity(screenshot.camera)
+            .clone_and_spawn_with_opt_in(|builder| {
+                builder.allow::<(
+                    CameraRenderGraph,
+                    Camera2d,
+                    Camera3d,
+                    Projection,
+                    Transform,
+                    ChildOf,
+                    Msaa,
+                    Hdr,
+                )>();
+            })
+            .insert((
+                Camera {
+                    target: RenderTarget::Image(image.clone().into()),
+                    clear_color: ClearColorConfig::Custom(Color::NONE),
+                    is_active: true,
+                    ..camera.clone()
+                },
+                RenderLayers::layer(screenshot.layer),
+            ))
+            .id();
+
+        let mut previous_render_layers = Vec::new();
+        for &root in &screenshot.entities {
+            for captured in core::iter::once(root).chain(children.iter_descendants(root)) {
+                let Ok(layers) = render_layers.get(captured) else {
+                    continue;
+                };
+                previous_render_layers.push((captured, layers.cloned()));
+                commands
+                    .entity(captured)
+                    .insert(layers.cloned().unwrap_or_default().with(screenshot.layer));
+            }
+        }
+
+        commands.entity(entity).insert((
+            Screenshot::image(image),
+            IsolatedScreenshotState {
+                camera: isolated_camera,
+                render_layers: previous_render_layers,
+            },
+        ));
+    }
+}
+
+/// Despawns the copies of the cameras of the [`IsolatedScreenshot`]s that were rendered, and
+/// restores the render layers of their entities.
+fn restore_isolated_screenshots(
+    mut commands: Commands,
+    screenshots: Query<(Entity, &IsolatedScreenshotState), With<Capturing>>,
+) {
+    for (entity, state) in &screenshots {
+        commands.entity(state.camera).despawn();
+        // In reverse, so that the first layers of entities captured multiple times are restored.
+        for (captured, layers) in state.render_layers.iter().rev() {
+            let Ok(mut captured) = commands.get_entity(*captured) else {
+                continue;
+            };
+            match layers {
+                Some(layers) => captured.insert(layers.clone()),
+                None => captured.remove::<RenderLayers>(),
+            };
+        }
+        commands.entity(entity).remove::<IsolatedScreenshotState>();
+    }
+}
+
 pub fn trigger_screenshots(
     mut commands: Commands,
     captured_screenshots: ResMut<CapturedScreenshots>,
@@ -212,24 +465,38 @@ fn extract_screenshots(
             SystemState<(
                 Commands,
                 Query<Entity, With<PrimaryWindow>>,
-                Query<(Entity, &Screenshot), Without<Capturing>>,
+                Query<(Entity, &Screenshot, Option<&ScreenshotRegion>), Without<Capturing>>,
+                Query<(Entity, &HdrScreenshot, Option<&ScreenshotRegion>), Without<Capturing>>,
+                Query<&RenderEntity>,
             )>,
         >,
     >,
+    mut hdr_targets: ResMut<RenderHdrScreenshotTargets>,
+    mut regions: ResMut<RenderScreenshotRegions>,
+    mut failed: ResMut<RenderFailedScreenshots>,
     mut seen_targets: Local<HashSet<NormalizedRenderTarget>>,
 ) {
     if system_state.is_none() {
         *system_state = Some(SystemState::new(&mut main_world));
     }
     let system_state = system_state.as_mut().unwrap();
-    let (mut commands, primary_window, screenshots) = system_state.get_mut(&mut main_world);
+    let (mut commands, primary_window, screenshots, hdr_screenshots, render_entities) =
+        system_state.get_mut(&mut main_world);
 
     targets.clear();
+    hdr_targets.clear();
+    regions.clear();
     seen_targets.clear();
 
+    for entity in failed.drain(..) {
+        if let Ok(mut entity) = commands.get_entity(entity) {
+            entity.despawn();
+        }
+    }
+
     let primary_window = primary_window.iter().next();
 
-    for (entity, screenshot) in screenshots.iter() {
+    for (entity, screenshot, region) in screenshots.iter() {
         let render_target = screenshot.0.clone();
         let Some(render_target) = render_target.normalize(primary_window) else {
             warn!(
@@ -249,6 +516,33 @@ fn extract_screenshots(
         }
         seen_targets.insert(render_target.clone());
         targets.insert(entity, render_target);
+        if let Some(region) = region {
+            regions.insert(entity, *region);
+        }
+        commands.entity(entity).insert(Capturing);
+    }
+
+    for (entity, hdr_screenshot, region) in hdr_screenshots.iter() {
+        let Ok(render_entity) = render_entities.get(hdr_screenshot.0) else {
+            warn!(
+                "Unknown camera for HDR screenshot, skipping: {}",
+                hdr_screenshot.0
+            );
+            continue;
+        };
+        if hdr_targets.contains_key(&**render_entity) {
+            warn!(
+                "Duplicate camera for HDR screenshot, skipping entity {}: {}",
+                entity, hdr_screenshot.0
+            );
+            // If we don't despawn the entity here, it will be captured again in the next frame
+            commands.entity(entity).despawn();
+            continue;
+        }
+        hdr_targets.insert(**render_entity, entity);
+        if let Some(region) = region {
+            regions.insert(entity, *region);
+        }
         commands.entity(entity).insert(Capturing);
     }
 
@@ -257,7 +551,9 @@ fn extract_screenshots(
 
 fn prepare_screenshots(
     targets: Res<RenderScreenshotTargets>,
+    regions: Res<RenderScreenshotRegions>,
     mut prepared: ResMut<RenderScreenshotsPrepared>,
+    mut failed: ResMut<RenderFailedScreenshots>,
     window_surfaces: Res<WindowSurfaces>,
     render_device: Res<RenderDevice>,
     screenshot_pipeline: Res<ScreenshotToScreenPipeline>,
@@ -282,14 +578,19 @@ fn prepare_screenshots(
                     height: surface_data.configuration.height,
                     ..default()
                 };
-                let (texture_view, state) = prepare_screenshot_state(
+                let Some((texture_view, state)) = prepare_screenshot_state(
+                    *entity,
                     size,
+                    regions.get(entity),
                     format,
                     &render_device,
                     &screenshot_pipeline,
                     &pipeline_cache,
                     &mut pipelines,
-                );
+                ) else {
+                    failed.push(*entity);
+                    continue;
+                };
                 prepared.insert(*entity, state);
                 view_target_attachments.insert(
                     target.clone(),
@@ -302,14 +603,19 @@ fn prepare_screenshots(
                     continue;
                 };
                 let format = gpu_image.texture_format;
-                let (texture_view, state) = prepare_screenshot_state(
+                let Some((texture_view, state)) = prepare_screenshot_state(
+                    *entity,
                     gpu_image.size,
+                    regions.get(entity),
                     format,
                     &render_device,
                     &screenshot_pipeline,
                     &pipeline_cache,
                     &mut pipelines,
-                );
+                ) else {
+                    failed.push(*entity);
+                    continue;
+                };
                 prepared.insert(*entity, state);
                 view_target_attachments.insert(
                     target.clone(),
@@ -326,14 +632,19 @@ fn prepare_screenshots(
                 };
                 let format = manual_texture_view.format;
                 let size = manual_texture_view.size.to_extents();
-                let (texture_view, state) = prepare_screenshot_state(
+                let Some((texture_view, state)) = prepare_screenshot_state(
+                    *entity,
                     size,
+                    regions.get(entity),
                     format,
                     &render_device,
                     &screenshot_pipeline,
                     &pipeline_cache,
                     &mut pipelines,
-                );
+                ) else {
+                    failed.push(*entity);
+                    continue;
+                };
                 prepared.insert(*entity, state);
                 view_target_attachments.insert(
                     target.clone(),
@@ -347,14 +658,106 @@ fn prepare_screenshots(
     }
 }
 
+fn prepare_hdr_screenshots(
+    targets: Res<RenderHdrScreenshotTargets>,
+    regions: Res<RenderScreenshotRegions>,
+    mut prepared: ResMut<RenderHdrScreenshotsPrepared>,
+    mut failed: ResMut<RenderFailedScreenshots>,
+    views: Query<&ViewTarget>,
+    render_device: Res<RenderDevice>,
+) {
+    prepared.clear();
+    for (&camera, &screenshot) in targets.iter() {
+        let Ok(view_target) = views.get(camera) else {
+            warn!(
+                "Camera for HDR screenshot isn't rendering, skipping: {}",
+                camera
+            );
+            failed.push(screenshot);
+            continue;
+        };
+        if !view_target.is_hdr() {
+            warn!(
+                "Camera for HDR screenshot doesn't have HDR enabled, skipping: {}",
+                camera
+            );
+            failed.push(screenshot);
+            continue;
+        }
+        let Some((origin, size)) = screenshot_region(
+            screenshot,
+            view_target.main_texture().size(),
+            regions.get(&screenshot),
+        ) else {
+            failed.push(screenshot);
+            continue;
+        };
+        let format = view_target.main_texture_format();
+        let buffer = render_device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("hdr-screenshot-transfer-buffer"),
+            size: gpu_readback::get_aligned_size(size, format.pixel_size().unwrap_or(0) as u32)
+                as u64,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        prepared.insert(
+            camera,
+            HdrScreenshotPreparedState {
+                screenshot,
+                buffer,
+                origin,
+                size,
+                format,
+            },
+        );
+    }
+}
+
+/// Returns the origin and size of the part of a texture of the given size that is captured by a
+/// screenshot, or `None` if its region doesn't overlap the texture.
+fn screenshot_region(
+    screenshot: Entity,
+    size: Extent3d,
+    region: Option<&ScreenshotRegion>,
+) -> Option<(Origin3d, Extent3d)> {
+    let Some(region) = region else {
+        return Some((Origin3d::ZERO, size));
+    };
+    let region = region.intersect(URect::new(0, 0, size.width, size.height));
+    if region.is_empty() {
+        warn!(
+            "Screenshot region is outside of its target, skipping: {}",
+            screenshot
+        );
+        return None;
+    }
+    Some((
+        Origin3d {
+            x: region.min.x,
+            y: region.min.y,
+            z: 0,
+        },
+        Extent3d {
+            width: region.width(),
+            height: region.height(),
+            depth_or_array_layers: 1,
+        },
+    ))
+}
+
+/// Creates the texture and buffer capturing a screenshot, or returns `None` if its region doesn't
+/// overlap the target.
 fn prepare_screenshot_state(
+    screenshot: Entity,
     size: Extent3d,
+    region: Option<&ScreenshotRegion>,
     format: TextureFormat,
     render_device: &RenderDevice,
     pipeline: &ScreenshotToScreenPipeline,
     pipeline_cache: &PipelineCache,
     pipelines: &mut SpecializedRenderPipelines<ScreenshotToScreenPipeline>,
-) -> (TextureView, ScreenshotPreparedState) {
+) -> Option<(TextureView, ScreenshotPreparedState)> {
+    let (origin, copy_size) = screenshot_region(screenshot, size, region)?;
     let texture = render_device.create_texture(&wgpu::TextureDescriptor {
         label: Some("screenshot-capture-rendertarget"),
         size,
@@ -370,7 +773,8 @@ fn prepare_screenshot_state(
     let texture_view = texture.create_view(&Default::default());
     let buffer = render_device.create_buffer(&wgpu::BufferDescriptor {
         label: Some("screenshot-transfer-buffer"),
-        size: gpu_readback::get_aligned_size(size, format.pixel_size().unwrap_or(0) as u32) as u64,
+        size: gpu_readback::get_aligned_size(copy_size, format.pixel_size().unwrap_or(0) as u32)
+            as u64,
         usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
         mapped_at_creation: false,
     });
@@ -381,16 +785,17 @@ fn prepare_screenshot_state(
     );
     let pipeline_id = pipelines.specialize(pipeline_cache, pipeline, format);
 
-    (
+    Some((
         texture_view,
         ScreenshotPreparedState {
             texture,
             buffer,
             bind_group,
             pipeline_id,
-            size,
+            origin,
+            size: copy_size,
         },
-    )
+    ))
 }
 
 pub struct ScreenshotPlugin;
@@ -407,7 +812,14 @@ impl Plugin for ScreenshotPlugin {
                     .after(message_update_system)
                     .before(ApplyDeferred),
             )
-            .add_systems(Update, trigger_screenshots);
+            .add_systems(Update, trigger_screenshots)
+            .add_systems(First, restore_isolated_screenshots)
+            .add_systems(
+                PostUpdate,
+                prepare_isolated_screenshots
+                    .before(CameraUpdateSystems)
+                    .before(TransformSystems::Propagate),
+            );
 
         let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
             return;
@@ -417,14 +829,21 @@ impl Plugin for ScreenshotPlugin {
             .insert_resource(RenderScreenshotsSender(tx))
             .init_resource::<RenderScreenshotTargets>()
             .init_resource::<RenderScreenshotsPrepared>()
+            .init_resource::<RenderScreenshotRegions>()
+            .init_resource::<RenderHdrScreenshotTargets>()
+            .init_resource::<RenderHdrScreenshotsPrepared>()
+            .init_resource::<RenderFailedScreenshots>()
             .init_resource::<SpecializedRenderPipelines<ScreenshotToScreenPipeline>>()
             .add_systems(RenderStartup, init_screenshot_to_screen_pipeline)
             .add_systems(ExtractSchedule, extract_screenshots.ambiguous_with_all())
             .add_systems(
                 Render,
-                prepare_screenshots
-                    .after(prepare_view_attachments)
-                    .before(prepare_view_targets)
+                (
+                    prepare_screenshots
+                        .after(prepare_view_attachments)
+                        .before(prepare_view_targets),
+                    prepare_hdr_screenshots.after(prepare_view_targets),
+                )
                     .in_set(RenderSystems::ManageViews),
             );
     }
@@ -498,8 +917,6 @@ pub(crate) fn submit_screenshot_commands(world: &World, encoder: &mut CommandEnc
                 let Some(window) = windows.get(&window) else {
                     continue;
                 };
-                let width = window.physical_width;
-                let height = window.physical_height;
                 let Some(texture_format) = window.swap_chain_texture_format else {
                     continue;
                 };
@@ -512,8 +929,6 @@ pub(crate) fn submit_screenshot_commands(world: &World, encoder: &mut CommandEnc
                     prepared,
                     pipelines,
                     entity,
-                    width,
-                    height,
                     texture_format,
                     &texture_view,
                 );
@@ -523,8 +938,6 @@ pub(crate) fn submit_screenshot_commands(world: &World, encoder: &mut CommandEnc
                     warn!("Unknown image for screenshot, skipping: {:?}", image);
                     continue;
                 };
-                let width = gpu_image.size.width;
-                let height = gpu_image.size.height;
                 let texture_format = gpu_image.texture_format;
                 let texture_view = gpu_image.texture_view.deref();
                 render_screenshot(
@@ -532,8 +945,6 @@ pub(crate) fn submit_screenshot_commands(world: &World, encoder: &mut CommandEnc
                     prepared,
                     pipelines,
                     entity,
-                    width,
-                    height,
                     texture_format,
                     texture_view,
                 );
@@ -546,8 +957,6 @@ pub(crate) fn submit_screenshot_commands(world: &World, encoder: &mut CommandEnc
                     );
                     continue;
                 };
-                let width = texture_view.size.x;
-                let height = texture_view.size.y;
                 let texture_format = texture_view.format;
                 let texture_view = texture_view.texture_view.deref();
                 render_screenshot(
@@ -555,8 +964,6 @@ pub(crate) fn submit_screenshot_commands(world: &World, encoder: &mut CommandEnc
                     prepared,
                     pipelines,
                     entity,
-                    width,
-                    height,
                     texture_format,
                     texture_view,
                 );
@@ -573,24 +980,20 @@ fn render_screenshot(
     prepared: &RenderScreenshotsPrepared,
     pipelines: &PipelineCache,
     entity: &Entity,
-    width: u32,
-    height: u32,
     texture_format: TextureFormat,
     texture_view: &wgpu::TextureView,
 ) {
     if let Some(prepared_state) = &prepared.get(entity) {
-        let extent = Extent3d {
-            width,
-            height,
-            depth_or_array_layers: 1,
-        };
         encoder.copy_texture_to_buffer(
-            prepared_state.texture.as_image_copy(),
+            wgpu::TexelCopyTextureInfo {
+                origin: prepared_state.origin,
+                ..prepared_state.texture.as_image_copy()
+            },
             wgpu::TexelCopyBufferInfo {
                 buffer: &prepared_state.buffer,
-                layout: gpu_readback::layout_data(extent, texture_format),
+                layout: gpu_readback::layout_data(prepared_state.size, texture_format),
             },
-            extent,
+            prepared_state.size,
         );
 
         if let Some(pipeline) = pipelines.get_render_pipeline(prepared_state.pipeline_id) {
@@ -616,23 +1019,66 @@ fn render_screenshot(
     }
 }
 
+/// Copies the HDR color buffer of `view` for its [`HdrScreenshot`], if one was requested this frame.
+///
+/// This is called by the render graph node that tonemaps the view, before tonemapping.
+pub fn copy_hdr_screenshot(
+    world: &World,
+    view: Entity,
+    target: &ViewTarget,
+    encoder: &mut CommandEncoder,
+) {
+    let Some(prepared_state) = world
+        .get_resource::<RenderHdrScreenshotsPrepared>()
+        .and_then(|prepared| prepared.get(&view))
+    else {
+        return;
+    };
+    encoder.copy_texture_to_buffer(
+        wgpu::TexelCopyTextureInfo {
+            origin: prepared_state.origin,
+            ..target.main_texture().as_image_copy()
+        },
+        wgpu::TexelCopyBufferInfo {
+            buffer: &prepared_state.buffer,
+            layout: gpu_readback::layout_data(prepared_state.size, prepared_state.format),
+        },
+        prepared_state.size,
+    );
+}
+
 pub(crate) fn collect_screenshots(world: &mut World) {
     #[cfg(feature = "trace")]
     let _span = tracing::info_span!("collect_screenshots").entered();
 
     let sender = world.resource::<RenderScreenshotsSender>().deref().clone();
     let prepared = world.resource::<RenderScreenshotsPrepared>();
+    let hdr_prepared = world.resource::<RenderHdrScreenshotsPrepared>();
 
-    for (entity, prepared) in prepared.iter() {
-        let entity = *entity;
+    let screenshots = prepared.iter().map(|(entity, prepared)| {
+        (
+            *entity,
+            &prepared.buffer,
+            prepared.size,
+            prepared.texture.format(),
+        )
+    });
+    let hdr_screenshots = hdr_prepared.values().map(|prepared| {
+        (
+            prepared.screenshot,
+            &prepared.buffer,
+            prepared.size,
+            prepared.format,
+        )
+    });
+    for (entity, buffer, size, texture_format) in screenshots.chain(hdr_screenshots) {
         let sender = sender.clone();
-        let width = prepared.size.width;
-        let height = prepared.size.height;
-        let texture_format = prepared.texture.format();
+        let width = size.width;
+        let height = size.height;
         let Ok(pixel_size) = texture_format.pixel_size() else {
             continue;
         };
-        let buffer = prepared.buffer.clone();
+        let buffer = buffer.clone();
 
         let finish = async move {
             let (tx, rx) = async_channel::bounded(1);
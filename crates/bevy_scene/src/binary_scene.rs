@@ -0,0 +1,563 @@
+//! A compact binary format for Bevy scenes (`.scn.bin`).
+
+use crate::{
+    scene_version,
+    serde::{SceneDeserializer, SceneSerializer, SceneValueDeserializer},
+    serialize_ron, DynamicEntity, DynamicScene,
+};
+use bevy_ecs::entity::Entity;
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{serde::TypedReflectSerializer, PartialReflect, TypeRegistration, TypeRegistry};
+use core::fmt::Formatter;
+use serde::{
+    de::{DeserializeSeed, Error, SeqAccess, Visitor},
+    ser::{SerializeSeq, SerializeTuple},
+    Deserializer, Serialize, Serializer,
+};
+use thiserror::Error;
+
+/// The bytes every binary scene starts with.
+pub const BINARY_SCENE_MAGIC: [u8; 4] = *b"BSCN";
+
+/// The version of the binary scene format written by [`DynamicScene::serialize_binary`].
+///
+/// This is the version of the layout of the format itself, unrelated to the version of the scenes
+/// of an app used by [`SceneMigration`](crate::SceneMigration)s.
+pub const BINARY_SCENE_FORMAT_VERSION: u8 = 1;
+
+/// Possible errors that can be produced when reading or writing binary scenes.
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum BinarySceneError {
+    /// The data doesn't start with [`BINARY_SCENE_MAGIC`].
+    #[error("The data isn't a binary scene")]
+    NotBinaryScene,
+    /// The binary scene was written with a later version of the format.
+    #[error("The binary scene format version {0} isn't supported")]
+    UnsupportedFormatVersion(u8),
+    /// A [Postcard Error](postcard::Error)
+    #[error("Could not read or write the binary scene: {0}")]
+    Postcard(#[from] postcard::Error),
+    /// A [RON Error](ron::error::SpannedError), when converting a RON scene.
+    #[error("Could not parse RON: {0}")]
+    RonSpannedError(#[from] ron::error::SpannedError),
+    /// A [RON Error](ron::Error), when converting a binary scene to RON.
+    #[error("Could not write RON: {0}")]
+    Ron(#[from] ron::Error),
+}
+
+impl DynamicScene {
+    /// Serialize this dynamic scene into the compact binary scene format (`.scn.bin`).
+    ///
+    /// The binary format is much smaller and faster to load than the RON format of
+    /// [`DynamicScene::serialize`] for large scenes, at the cost of being unreadable by humans.
+    /// It starts with a header listing the type paths of the resources and components of the
+    /// scene, which the values then refer to by index, and lays out the values with
+    /// [postcard](https://crates.io/crates/postcard) using their reflected type information.
+    ///
+    /// The [`SceneLoader`](crate::SceneLoader) loads scenes in both formats.
+    pub fn serialize_binary(&self, registry: &TypeRegistry) -> Result<Vec<u8>, BinarySceneError> {
+        let mut bytes = Vec::from(BINARY_SCENE_MAGIC);
+        bytes.push(BINARY_SCENE_FORMAT_VERSION);
+        let bytes = postcard::to_extend(&BinarySceneSerializer::new(self, registry), bytes)?;
+        Ok(bytes)
+    }
+
+    /// Deserialize a dynamic scene serialized with [`DynamicScene::serialize_binary`].
+    pub fn deserialize_binary(
+        bytes: &[u8],
+        registry: &TypeRegistry,
+    ) -> Result<DynamicScene, BinarySceneError> {
+        let Some(body) = bytes.strip_prefix(&BINARY_SCENE_MAGIC) else {
+            return Err(BinarySceneError::NotBinaryScene);
+        };
+        let Some((&format_version, body)) = body.split_first() else {
+            return Err(BinarySceneError::NotBinaryScene);
+        };
+        if format_version > BINARY_SCENE_FORMAT_VERSION {
+            return Err(BinarySceneError::UnsupportedFormatVersion(format_version));
+        }
+        let mut deserializer = postcard::Deserializer::from_bytes(body);
+        Ok(BinarySceneDeserializer { registry }.deserialize(&mut deserializer)?)
+    }
+}
+
+/// Returns `true` if `bytes` are a scene serialized with [`DynamicScene::serialize_binary`], rather
+/// than with [`DynamicScene::serialize`].
+pub fn is_binary_scene(bytes: &[u8]) -> bool {
+    bytes.starts_with(&BINARY_SCENE_MAGIC)
+}
+
+/// Converts a scene in the RON format (`.scn.ron`) to the binary format (`.scn.bin`).
+pub fn ron_scene_to_binary(
+    ron: &str,
+    registry: &TypeRegistry,
+) -> Result<Vec<u8>, BinarySceneError> {
+    let mut deserializer = ron::de::Deserializer::from_str(ron)?;
+    let scene = SceneDeserializer {
+        type_registry: registry,
+    }
+    .deserialize(&mut deserializer)
+    .map_err(|e| deserializer.span_error(e))?;
+    scene.serialize_binary(registry)
+}
+
+/// Converts a scene in the binary format (`.scn.bin`) to the RON format (`.scn.ron`).
+pub fn binary_scene_to_ron(
+    bytes: &[u8],
+    registry: &TypeRegistry,
+) -> Result<String, BinarySceneError> {
+    let scene = DynamicScene::deserialize_binary(bytes, registry)?;
+    Ok(serialize_ron(SceneSerializer::new(&scene, registry))?)
+}
+
+/// Serializer for the body of a binary scene, after its magic bytes and format version.
+struct BinarySceneSerializer<'a> {
+    scene: &'a DynamicScene,
+    registry: &'a TypeRegistry,
+    /// The type paths of the values of the scene, in the order of their indices.
+    type_paths: Vec<&'a str>,
+    /// The index of each type path in `type_paths`.
+    type_indices: HashMap<&'a str, u32>,
+}
+
+impl<'a> BinarySceneSerializer<'a> {
+    fn new(scene: &'a DynamicScene, registry: &'a TypeRegistry) -> Self {
+        let mut type_paths = Vec::new();
+        let mut type_indices = HashMap::default();
+        let values = scene
+            .resources
+            .iter()
+            .chain(scene.entities.iter().flat_map(|entity| &entity.components));
+        for value in values {
+            let type_path = value.get_represented_type_info().unwrap().type_path();
+            type_indices.entry(type_path).or_insert_with(|| {
+                type_paths.push(type_path);
+                type_paths.len() as u32 - 1
+            });
+        }
+        Self {
+            scene,
+            registry,
+            type_paths,
+            type_indices,
+        }
+    }
+}
+
+impl<'a> Serialize for BinarySceneSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_tuple(4)?;
+        state.serialize_element(&scene_version(self.registry))?;
+        state.serialize_element(&self.type_paths)?;
+        state.serialize_element(&BinaryValuesSerializer {
+            values: &self.scene.resources,
+            scene: self,
+        })?;
+        state.serialize_element(&BinaryEntitiesSerializer { scene: self })?;
+        state.end()
+    }
+}
+
+/// Serializes the entities of a binary scene, as a sequence of their ids and components.
+struct BinaryEntitiesSerializer<'a> {
+    scene: &'a BinarySceneSerializer<'a>,
+}
+
+impl<'a> Serialize for BinaryEntitiesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let entities = &self.scene.scene.entities;
+        let mut state = serializer.serialize_seq(Some(entities.len()))?;
+        for entity in entities {
+            state.serialize_element(&(
+                entity.entity,
+                BinaryValuesSerializer {
+                    values: &entity.components,
+                    scene: self.scene,
+                },
+            ))?;
+        }
+        state.end()
+    }
+}
+
+/// Serializes reflected values as a sequence of the indices of their types and their data.
+struct BinaryValuesSerializer<'a> {
+    values: &'a [Box<dyn PartialReflect>],
+    scene: &'a BinarySceneSerializer<'a>,
+}
+
+impl<'a> Serialize for BinaryValuesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.values.len()))?;
+        for value in self.values {
+            let type_path = value.get_represented_type_info().unwrap().type_path();
+            state.serialize_element(&(
+                self.scene.type_indices[type_path],
+                TypedReflectSerializer::new(value.as_partial_reflect(), self.scene.registry),
+            ))?;
+        }
+        state.end()
+    }
+}
+
+/// Deserializer for the body of a binary scene, after its magic bytes and format version.
+struct BinarySceneDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BinarySceneDeserializer<'a> {
+    type Value = DynamicScene;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(4, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for BinarySceneDeserializer<'a> {
+    type Value = DynamicScene;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("binary scene")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let version: u32 = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
+        let type_paths: Vec<&str> = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(1, &self))?;
+        let registrations = type_paths
+            .into_iter()
+            .map(|type_path| {
+                self.registry.get_with_type_path(type_path).ok_or_else(|| {
+                    Error::custom(format_args!("no registration found for type `{type_path}`"))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let values = BinaryValuesDeserializer {
+            registrations: &registrations,
+            registry: self.registry,
+            version,
+        };
+        let resources = seq
+            .next_element_seed(values)?
+            .ok_or_else(|| Error::invalid_length(2, &self))?;
+        let entities = seq
+            .next_element_seed(BinaryEntitiesDeserializer { values })?
+            .ok_or_else(|| Error::invalid_length(3, &self))?;
+
+        Ok(DynamicScene {
+            resources,
+            entities,
+        })
+    }
+}
+
+/// Deserializes the entities of a binary scene.
+#[derive(Clone, Copy)]
+struct BinaryEntitiesDeserializer<'a> {
+    values: BinaryValuesDeserializer<'a>,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BinaryEntitiesDeserializer<'a> {
+    type Value = Vec<DynamicEntity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for BinaryEntitiesDeserializer<'a> {
+    type Value = Vec<DynamicEntity>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("sequence of entities")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        // The length of the sequence is read from the data, so it can't be trusted for preallocation.
+        let mut entities = Vec::new();
+        while let Some(entity) = seq.next_element_seed(BinaryEntityDeserializer {
+            values: self.values,
+        })? {
+            entities.push(entity);
+        }
+        Ok(entities)
+    }
+}
+
+/// Deserializes an entity of a binary scene.
+struct BinaryEntityDeserializer<'a> {
+    values: BinaryValuesDeserializer<'a>,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BinaryEntityDeserializer<'a> {
+    type Value = DynamicEntity;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for BinaryEntityDeserializer<'a> {
+    type Value = DynamicEntity;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("entity")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let entity: Entity = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
+        let components = seq
+            .next_element_seed(self.values)?
+            .ok_or_else(|| Error::invalid_length(1, &self))?;
+        Ok(DynamicEntity { entity, components })
+    }
+}
+
+/// Deserializes the reflected values of a binary scene.
+#[derive(Clone, Copy)]
+struct BinaryValuesDeserializer<'a> {
+    /// The registrations of the types of the header of the scene, in the order of their indices.
+    registrations: &'a [&'a TypeRegistration],
+    registry: &'a TypeRegistry,
+    version: u32,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BinaryValuesDeserializer<'a> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for BinaryValuesDeserializer<'a> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("sequence of reflected values")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        // As with entities, the length of the sequence isn't trusted for preallocation.
+        let mut values = Vec::new();
+        while let Some(value) = seq.next_element_seed(BinaryValueDeserializer { values: self })? {
+            values.push(value);
+        }
+        Ok(values)
+    }
+}
+
+/// Deserializes a reflected value of a binary scene, preceded by the index of its type.
+struct BinaryValueDeserializer<'a> {
+    values: BinaryValuesDeserializer<'a>,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BinaryValueDeserializer<'a> {
+    type Value = Box<dyn PartialReflect>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for BinaryValueDeserializer<'a> {
+    type Value = Box<dyn PartialReflect>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("reflected value")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let index: u32 = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
+        let Some(registration) = self.values.registrations.get(index as usize) else {
+            return Err(Error::custom(format_args!(
+                "type index {index} is out of the header of the scene"
+            )));
+        };
+        seq.next_element_seed(SceneValueDeserializer {
+            registration,
+            registry: self.values.registry,
+            version: self.values.version,
+        })?
+        .ok_or_else(|| Error::invalid_length(1, &self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{
+        component::Component,
+        entity::Entity,
+        query::With,
+        reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+        resource::Resource,
+        world::World,
+    };
+    use bevy_reflect::Reflect;
+
+    use super::{
+        binary_scene_to_ron, is_binary_scene, ron_scene_to_binary, BinarySceneError,
+        BINARY_SCENE_FORMAT_VERSION, BINARY_SCENE_MAGIC,
+    };
+    use crate::{DynamicScene, DynamicSceneBuilder};
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Label {
+        text: String,
+        size: f32,
+    }
+
+    #[derive(Resource, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct Level(u8);
+
+    fn create_scene() -> (DynamicScene, AppTypeRegistry) {
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Health>();
+            registry.register::<Label>();
+            registry.register::<Level>();
+            registry.register::<String>();
+        }
+
+        let mut world = World::new();
+        world.insert_resource(registry.clone());
+        world.insert_resource(Level(3));
+        for i in 0..10 {
+            world.spawn((
+                Health(i),
+                Label {
+                    text: format!("Enemy {i}"),
+                    size: 12.0,
+                },
+            ));
+        }
+        world.spawn(Health(100));
+
+        let mut query = world.query_filtered::<Entity, With<Health>>();
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .extract_entities(query.iter(&world))
+            .allow_resource::<Level>()
+            .extract_resources()
+            .build();
+        (scene, registry)
+    }
+
+    #[test]
+    fn should_roundtrip_binary() {
+        let (scene, registry) = create_scene();
+        let registry = registry.read();
+
+        let bytes = scene.serialize_binary(&registry).unwrap();
+        assert!(is_binary_scene(&bytes));
+        // The type paths are only written once, in the header.
+        assert!(bytes.len() < scene.serialize(&registry).unwrap().len() / 4);
+
+        let deserialized = DynamicScene::deserialize_binary(&bytes, &registry).unwrap();
+        assert_eq!(
+            scene.serialize(&registry).unwrap(),
+            deserialized.serialize(&registry).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_convert_between_ron_and_binary() {
+        let (scene, registry) = create_scene();
+        let registry = registry.read();
+
+        let ron = scene.serialize(&registry).unwrap();
+        let bytes = ron_scene_to_binary(&ron, &registry).unwrap();
+        assert_eq!(bytes, scene.serialize_binary(&registry).unwrap());
+        assert_eq!(binary_scene_to_ron(&bytes, &registry).unwrap(), ron);
+    }
+
+    #[test]
+    fn should_reject_other_data() {
+        let (scene, registry) = create_scene();
+        let registry = registry.read();
+
+        let ron = scene.serialize(&registry).unwrap();
+        assert!(!is_binary_scene(ron.as_bytes()));
+        assert!(matches!(
+            DynamicScene::deserialize_binary(ron.as_bytes(), &registry),
+            Err(BinarySceneError::NotBinaryScene)
+        ));
+
+        let mut bytes = scene.serialize_binary(&registry).unwrap();
+        bytes[4] = u8::MAX;
+        assert!(matches!(
+            DynamicScene::deserialize_binary(&bytes, &registry),
+            Err(BinarySceneError::UnsupportedFormatVersion(u8::MAX))
+        ));
+    }
+    #[test]
+    fn should_reject_truncated_data() {
+        let (_, registry) = create_scene();
+        let registry = registry.read();
+
+        let mut bytes = Vec::from(BINARY_SCENE_MAGIC);
+        bytes.push(BINARY_SCENE_FORMAT_VERSION);
+        // The scene version, and an empty header.
+        bytes.extend_from_slice(&[0, 0]);
+        // A huge number of resources, whose data is missing.
+        bytes.extend_from_slice(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]);
+        assert!(matches!(
+            DynamicScene::deserialize_binary(&bytes, &registry),
+            Err(BinarySceneError::Postcard(_))
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{DynamicSceneBuilder, Scene, SceneSpawnError};
+use crate::{DynamicSceneBuilder, ReflectMapSceneEntities, Scene, SceneSpawnError};
 use bevy_asset::Asset;
 use bevy_ecs::reflect::{ReflectMapEntities, ReflectResource};
 use bevy_ecs::{
@@ -126,10 +126,27 @@ impl DynamicScene {
                     }
                 }
 
+                // If this component references entities in the scene through fields that its
+                // `MapEntities` doesn't map, update them to the entities in the world.
+                let mut cloned_component;
+                let partial_reflect_component = if let Some(map_scene_entities) =
+                    registration.data::<ReflectMapSceneEntities>()
+                {
+                    cloned_component =
+                        clone_reflect_value(component.as_partial_reflect(), registration);
+                    SceneEntityMapper::world_scope(entity_map, world, |_, mapper| {
+                        map_scene_entities
+                            .map_entities(cloned_component.as_partial_reflect_mut(), mapper);
+                    });
+                    cloned_component.as_partial_reflect()
+                } else {
+                    component.as_partial_reflect()
+                };
+
                 SceneEntityMapper::world_scope(entity_map, world, |world, mapper| {
                     reflect_component.apply_or_insert_mapped(
                         &mut world.entity_mut(entity),
-                        component.as_partial_reflect(),
+                        partial_reflect_component,
                         &type_registry,
                         mapper,
                         RelationshipHookMode::Skip,
@@ -168,6 +185,14 @@ impl DynamicScene {
                     map_entities.map_entities(cloned_resource.as_partial_reflect_mut(), mapper);
                 });
                 cloned_resource.as_partial_reflect()
+            } else if let Some(map_scene_entities) = registration.data::<ReflectMapSceneEntities>()
+            {
+                cloned_resource = clone_reflect_value(resource.as_partial_reflect(), registration);
+                SceneEntityMapper::world_scope(entity_map, world, |_, mapper| {
+                    map_scene_entities
+                        .map_entities(cloned_resource.as_partial_reflect_mut(), mapper);
+                });
+                cloned_resource.as_partial_reflect()
             } else {
                 resource.as_partial_reflect()
             };
@@ -198,7 +223,8 @@ impl DynamicScene {
     /// Serialize this dynamic scene into the official Bevy scene format (`.scn` / `.scn.ron`).
     ///
     /// The Bevy scene format is based on [Rusty Object Notation (RON)]. It describes the scene
-    /// in a human-friendly format. To deserialize the scene, use the [`SceneLoader`].
+    /// in a human-friendly format. To deserialize the scene, use the [`SceneLoader`]. Large scenes
+    /// are smaller and faster to load with [`DynamicScene::serialize_binary`].
     ///
     /// [`SceneLoader`]: crate::SceneLoader
     /// [Rusty Object Notation (RON)]: https://crates.io/crates/ron
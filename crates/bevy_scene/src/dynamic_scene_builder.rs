@@ -1,17 +1,19 @@
-use core::any::TypeId;
+use core::any::{Any, TypeId};
 
 use crate::reflect_utils::clone_reflect_value;
 use crate::{DynamicEntity, DynamicScene, SceneFilter};
 use alloc::collections::BTreeMap;
 use bevy_ecs::{
     component::{Component, ComponentId},
+    entity::EntityHashMap,
     entity_disabling::DefaultQueryFilters,
     prelude::Entity,
     reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
     resource::Resource,
     world::World,
 };
-use bevy_reflect::PartialReflect;
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{DynamicStruct, PartialReflect, ReflectRef, Struct};
 use bevy_utils::default;
 
 /// A [`DynamicScene`] builder, used to build a scene from a [`World`] by extracting some entities and resources.
@@ -23,6 +25,11 @@ use bevy_utils::default;
 /// This can be changed by [specifying a filter](DynamicSceneBuilder::with_component_filter) or by explicitly
 /// [allowing](DynamicSceneBuilder::allow_component)/[denying](DynamicSceneBuilder::deny_component) certain components.
 ///
+/// The components of specific entities can be filtered separately, by [specifying a filter for an
+/// entity](DynamicSceneBuilder::with_entity_component_filter) or by explicitly
+/// [allowing](DynamicSceneBuilder::allow_entity_component)/[denying](DynamicSceneBuilder::deny_entity_component)
+/// certain components of an entity.
+///
 /// Extraction happens immediately and uses the filter as it exists during the time of extraction.
 ///
 /// # Resource Extraction
@@ -34,6 +41,18 @@ use bevy_utils::default;
 ///
 /// Extraction happens immediately and uses the filter as it exists during the time of extraction.
 ///
+/// # Field Extraction
+///
+/// By default, components and resources are extracted whole. Only some of the fields of a type can be
+/// extracted by explicitly [allowing](DynamicSceneBuilder::allow_field)/[denying](DynamicSceneBuilder::deny_field)
+/// the fields at certain reflection paths, like `translation` or `translation.x`.
+///
+/// A value extracted without some of its fields is stored as a [`DynamicStruct`], so that spawning the
+/// scene only changes its extracted fields. Spawning it on an entity without the component, or in a
+/// world without the resource, fills the other fields from its reflected [`Default`] or
+/// [`FromWorld`](bevy_ecs::world::FromWorld). Such values can't be serialized in the
+/// [binary scene format](DynamicScene::serialize_binary), which relies on all the fields being present.
+///
 /// # Entity Order
 ///
 /// Extracted entities will always be stored in ascending order based on their [index](Entity::index).
@@ -62,7 +81,9 @@ pub struct DynamicSceneBuilder<'w> {
     extracted_resources: BTreeMap<ComponentId, Box<dyn PartialReflect>>,
     extracted_scene: BTreeMap<Entity, DynamicEntity>,
     component_filter: SceneFilter,
+    entity_component_filters: EntityHashMap<SceneFilter>,
     resource_filter: SceneFilter,
+    field_filters: HashMap<TypeId, FieldFilter>,
     original_world: &'w World,
 }
 
@@ -73,7 +94,9 @@ impl<'w> DynamicSceneBuilder<'w> {
             extracted_resources: default(),
             extracted_scene: default(),
             component_filter: SceneFilter::default(),
+            entity_component_filters: default(),
             resource_filter: SceneFilter::default(),
+            field_filters: default(),
             original_world: world,
         }
     }
@@ -85,6 +108,14 @@ impl<'w> DynamicSceneBuilder<'w> {
         self
     }
 
+    /// Specify a custom component [`SceneFilter`] to be used for `entity` instead of the builder's
+    /// component filter.
+    #[must_use]
+    pub fn with_entity_component_filter(mut self, entity: Entity, filter: SceneFilter) -> Self {
+        self.entity_component_filters.insert(entity, filter);
+        self
+    }
+
     /// Specify a custom resource [`SceneFilter`] to be used with this builder.
     #[must_use]
     pub fn with_resource_filter(mut self, filter: SceneFilter) -> Self {
@@ -158,6 +189,94 @@ impl<'w> DynamicSceneBuilder<'w> {
         self
     }
 
+    /// Allows the given component type, `T`, of `entity` to be included in the generated scene.
+    ///
+    /// If `entity` has no filter of its own, it starts from the builder's component filter.
+    ///
+    /// This is the inverse of [`deny_entity_component`](Self::deny_entity_component).
+    #[must_use]
+    pub fn allow_entity_component<T: Component>(mut self, entity: Entity) -> Self {
+        let filter = self.entity_component_filter(entity);
+        self.entity_component_filters
+            .insert(entity, filter.allow::<T>());
+        self
+    }
+
+    /// Denies the given component type, `T`, of `entity` from being included in the generated
+    /// scene.
+    ///
+    /// If `entity` has no filter of its own, it starts from the builder's component filter.
+    ///
+    /// This is the inverse of [`allow_entity_component`](Self::allow_entity_component).
+    #[must_use]
+    pub fn deny_entity_component<T: Component>(mut self, entity: Entity) -> Self {
+        let filter = self.entity_component_filter(entity);
+        self.entity_component_filters
+            .insert(entity, filter.deny::<T>());
+        self
+    }
+
+    /// Removes the component filter of `entity`, or returns a copy of the builder's component
+    /// filter if it has none.
+    fn entity_component_filter(&mut self, entity: Entity) -> SceneFilter {
+        self.entity_component_filters
+            .remove(&entity)
+            .unwrap_or_else(|| self.component_filter.clone())
+    }
+
+    /// Allows the field at the reflection path `path` of the component or resource type `T` to be
+    /// included in the generated scene, such as `translation` or `translation.x`.
+    ///
+    /// Once a field of `T` is allowed, only the allowed fields of `T` are included. Only the named
+    /// fields of structs can be filtered, other values are included whole.
+    ///
+    /// This method may be called multiple times for any number of fields.
+    ///
+    /// ```
+    /// # use bevy_scene::DynamicSceneBuilder;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+    /// #[derive(Component, Reflect, Default)]
+    /// #[reflect(Component, Default)]
+    /// struct Body {
+    ///     position: (f32, f32),
+    ///     velocity: (f32, f32),
+    /// }
+    ///
+    /// # let mut world = World::default();
+    /// # world.init_resource::<AppTypeRegistry>();
+    /// # let entity = world.spawn(Body::default()).id();
+    /// let scene = DynamicSceneBuilder::from_world(&world)
+    ///     .allow_field::<Body>("position")
+    ///     .extract_entity(entity)
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn allow_field<T: Any>(mut self, path: impl Into<String>) -> Self {
+        let filter = self.field_filters.entry(TypeId::of::<T>()).or_default();
+        let path = path.into();
+        filter.denied.retain(|denied| *denied != path);
+        filter.allowed.push(path);
+        self
+    }
+
+    /// Denies the field at the reflection path `path` of the component or resource type `T` from
+    /// being included in the generated scene, such as `rotation` or `translation.y`.
+    ///
+    /// Only the named fields of structs can be filtered.
+    ///
+    /// This method may be called multiple times for any number of fields.
+    ///
+    /// This is the inverse of [`allow_field`](Self::allow_field).
+    #[must_use]
+    pub fn deny_field<T: Any>(mut self, path: impl Into<String>) -> Self {
+        let filter = self.field_filters.entry(TypeId::of::<T>()).or_default();
+        let path = path.into();
+        filter.allowed.retain(|allowed| *allowed != path);
+        filter.denied.push(path);
+        self
+    }
+
     /// Allows the given resource type, `T`, to be included in the generated scene.
     ///
     /// This method may be called multiple times for any number of resources.
@@ -282,6 +401,10 @@ impl<'w> DynamicSceneBuilder<'w> {
                 components: Vec::new(),
             };
 
+            let component_filter = self
+                .entity_component_filters
+                .get(&entity)
+                .unwrap_or(&self.component_filter);
             let original_entity = self.original_world.entity(entity);
             for &component_id in original_entity.archetype().components().iter() {
                 let mut extract_and_push = || {
@@ -291,7 +414,7 @@ impl<'w> DynamicSceneBuilder<'w> {
                         .get_info(component_id)?
                         .type_id()?;
 
-                    let is_denied = self.component_filter.is_denied_by_id(type_id);
+                    let is_denied = component_filter.is_denied_by_id(type_id);
 
                     if is_denied {
                         // Component is either in the denylist or _not_ in the allowlist
@@ -304,8 +427,14 @@ impl<'w> DynamicSceneBuilder<'w> {
                         .data::<ReflectComponent>()?
                         .reflect(original_entity)?;
 
-                    let component =
-                        clone_reflect_value(component.as_partial_reflect(), type_registration);
+                    let component = match self.field_filters.get(&type_id) {
+                        Some(field_filter) => {
+                            field_filter.apply(component.as_partial_reflect(), "")?
+                        }
+                        None => {
+                            clone_reflect_value(component.as_partial_reflect(), type_registration)
+                        }
+                    };
 
                     entry.components.push(component);
                     Some(())
@@ -379,8 +508,10 @@ impl<'w> DynamicSceneBuilder<'w> {
                     .reflect(self.original_world)
                     .ok()?;
 
-                let resource =
-                    clone_reflect_value(resource.as_partial_reflect(), type_registration);
+                let resource = match self.field_filters.get(&type_id) {
+                    Some(field_filter) => field_filter.apply(resource.as_partial_reflect(), "")?,
+                    None => clone_reflect_value(resource.as_partial_reflect(), type_registration),
+                };
 
                 self.extracted_resources.insert(component_id, resource);
                 Some(())
@@ -393,6 +524,67 @@ impl<'w> DynamicSceneBuilder<'w> {
     }
 }
 
+/// The reflection paths of the fields of a type allowed in, or denied from, a scene.
+#[derive(Default)]
+struct FieldFilter {
+    allowed: Vec<String>,
+    denied: Vec<String>,
+}
+
+impl FieldFilter {
+    /// Returns the allowed fields of `value`, found at the reflection path `path`, or `None` if
+    /// none of them are allowed.
+    fn apply(&self, value: &dyn PartialReflect, path: &str) -> Option<Box<dyn PartialReflect>> {
+        if self.denied.iter().any(|denied| denied == path) {
+            return None;
+        }
+        let is_allowed =
+            self.allowed.is_empty() || self.allowed.iter().any(|allowed| is_within(path, allowed));
+        let has_denied_fields = self.denied.iter().any(|denied| is_within(denied, path));
+        let has_allowed_fields = self.allowed.iter().any(|allowed| is_within(allowed, path));
+        if is_allowed && !has_denied_fields {
+            return Some(
+                value
+                    .reflect_clone()
+                    .map(PartialReflect::into_partial_reflect)
+                    .unwrap_or_else(|_| value.to_dynamic()),
+            );
+        }
+        if !is_allowed && !has_allowed_fields {
+            return None;
+        }
+
+        let ReflectRef::Struct(value) = value.reflect_ref() else {
+            // Only the named fields of structs can be filtered.
+            return is_allowed.then(|| value.to_dynamic());
+        };
+        let mut filtered = DynamicStruct::default();
+        filtered.set_represented_type(value.get_represented_type_info());
+        for (index, field) in value.iter_fields().enumerate() {
+            let Some(name) = value.name_at(index) else {
+                continue;
+            };
+            let field_path = if path.is_empty() {
+                name.into()
+            } else {
+                format!("{path}.{name}")
+            };
+            if let Some(field) = self.apply(field, &field_path) {
+                filtered.insert_boxed(name, field);
+            }
+        }
+        (is_allowed || filtered.field_len() > 0).then(|| Box::new(filtered) as _)
+    }
+}
+
+/// Returns `true` if the reflection path `path` is `ancestor` or one of its fields.
+fn is_within(path: &str, ancestor: &str) -> bool {
+    ancestor.is_empty()
+        || path
+            .strip_prefix(ancestor)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
 #[cfg(test)]
 mod tests {
     use bevy_ecs::{
@@ -403,9 +595,10 @@ mod tests {
         world::World,
     };
 
-    use bevy_reflect::Reflect;
+    use bevy_reflect::{std_traits::ReflectDefault, Reflect, ReflectRef};
 
     use super::DynamicSceneBuilder;
+    use crate::SceneFilter;
 
     #[derive(Component, Reflect, Default, Eq, PartialEq, Debug)]
     #[reflect(Component)]
@@ -740,4 +933,106 @@ mod tests {
             .expect("resource should be concrete due to `FromReflect`")
             .is::<SomeResource>());
     }
+
+    #[test]
+    fn should_use_entity_component_filters() {
+        let mut world = World::default();
+        let atr = AppTypeRegistry::default();
+        {
+            let mut register = atr.write();
+            register.register::<ComponentA>();
+            register.register::<ComponentB>();
+        }
+        world.insert_resource(atr);
+
+        let entity_a = world.spawn((ComponentA, ComponentB)).id();
+        let entity_b = world.spawn((ComponentA, ComponentB)).id();
+        let entity_c = world.spawn((ComponentA, ComponentB)).id();
+
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .deny_component::<ComponentA>()
+            .allow_entity_component::<ComponentA>(entity_b)
+            .with_entity_component_filter(entity_c, SceneFilter::deny_all())
+            .extract_entities([entity_a, entity_b, entity_c].into_iter())
+            .build();
+
+        assert_eq!(scene.entities.len(), 3);
+        let components = |entity| {
+            &scene
+                .entities
+                .iter()
+                .find(|dynamic_entity| dynamic_entity.entity == entity)
+                .unwrap()
+                .components
+        };
+        assert_eq!(components(entity_a).len(), 1);
+        assert!(components(entity_a)[0].represents::<ComponentB>());
+        assert_eq!(components(entity_b).len(), 2);
+        assert!(components(entity_c).is_empty());
+    }
+
+    #[test]
+    fn should_extract_allowed_fields() {
+        #[derive(Reflect, Default, PartialEq, Debug)]
+        struct Vec2 {
+            x: f32,
+            y: f32,
+        }
+
+        #[derive(Component, Reflect, Default, PartialEq, Debug)]
+        #[reflect(Component, Default)]
+        struct Body {
+            position: Vec2,
+            velocity: Vec2,
+            mass: f32,
+        }
+
+        let mut world = World::default();
+        let atr = AppTypeRegistry::default();
+        atr.write().register::<Body>();
+        world.insert_resource(atr);
+
+        let body = Body {
+            position: Vec2 { x: 1.0, y: 2.0 },
+            velocity: Vec2 { x: 3.0, y: 4.0 },
+            mass: 5.0,
+        };
+        let entity = world.spawn(body).id();
+
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .allow_field::<Body>("position")
+            .allow_field::<Body>("velocity")
+            .deny_field::<Body>("velocity.y")
+            .extract_entity(entity)
+            .build();
+
+        let component = &scene.entities[0].components[0];
+        assert!(component.represents::<Body>());
+        let ReflectRef::Struct(body) = component.reflect_ref() else {
+            panic!("the filtered component should be a struct");
+        };
+        assert_eq!(body.field_len(), 2);
+        assert!(body.field("mass").is_none());
+        let ReflectRef::Struct(velocity) = body.field("velocity").unwrap().reflect_ref() else {
+            panic!("the filtered field should be a struct");
+        };
+        assert_eq!(velocity.field_len(), 1);
+        assert!(velocity.field("y").is_none());
+
+        // Spawning the scene leaves the fields that weren't extracted unchanged.
+        world.entity_mut(entity).insert(Body {
+            mass: 6.0,
+            ..Default::default()
+        });
+        let mut entity_map = [(entity, entity)].into_iter().collect();
+        scene.write_to_world(&mut world, &mut entity_map).unwrap();
+        assert_eq!(
+            world.get::<Body>(entity),
+            Some(&Body {
+                position: Vec2 { x: 1.0, y: 2.0 },
+                velocity: Vec2 { x: 3.0, y: 0.0 },
+                mass: 6.0,
+            })
+        );
+    }
 }
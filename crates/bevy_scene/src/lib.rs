@@ -12,25 +12,49 @@
 
 extern crate alloc;
 
+#[cfg(feature = "serialize")]
+mod binary_scene;
 mod components;
 mod dynamic_scene;
 mod dynamic_scene_builder;
 mod reflect_utils;
+#[cfg(feature = "serialize")]
+mod save_game;
 mod scene;
+mod scene_entity_mapping;
 mod scene_filter;
 mod scene_loader;
+#[cfg(feature = "serialize")]
+mod scene_migration;
+mod scene_overrides;
+mod scene_parameters;
+mod scene_reconcile;
 mod scene_spawner;
+mod scene_streaming;
+mod scene_validation;
 
 #[cfg(feature = "serialize")]
 pub mod serde;
 
+#[cfg(feature = "serialize")]
+pub use binary_scene::*;
 pub use components::*;
 pub use dynamic_scene::*;
 pub use dynamic_scene_builder::*;
+#[cfg(feature = "serialize")]
+pub use save_game::*;
 pub use scene::*;
+pub use scene_entity_mapping::*;
 pub use scene_filter::*;
 pub use scene_loader::*;
+#[cfg(feature = "serialize")]
+pub use scene_migration::*;
+pub use scene_overrides::*;
+pub use scene_parameters::*;
+pub use scene_reconcile::SceneHotReload;
 pub use scene_spawner::*;
+pub use scene_streaming::*;
+pub use scene_validation::*;
 
 /// The scene prelude.
 ///
@@ -38,15 +62,22 @@ pub use scene_spawner::*;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        DynamicScene, DynamicSceneBuilder, DynamicSceneRoot, Scene, SceneFilter, SceneRoot,
-        SceneSpawner,
+        DynamicScene, DynamicSceneBuilder, DynamicSceneRoot, Scene, SceneEntityMappingApp,
+        SceneFilter, SceneOverrides, SceneParameters, SceneRoot, SceneSpawner, SceneStreamingCell,
+        SceneStreamingGrid, SceneStreamingSource,
     };
+
+    #[cfg(feature = "serialize")]
+    #[doc(hidden)]
+    pub use crate::{SaveGame, SaveGameApp, SceneMigrationApp};
 }
 
 use bevy_app::prelude::*;
 
 #[cfg(feature = "serialize")]
-use {bevy_asset::AssetApp, bevy_ecs::schedule::IntoScheduleConfigs};
+use {
+    bevy_asset::AssetApp, bevy_ecs::schedule::IntoScheduleConfigs, bevy_transform::TransformSystems,
+};
 
 /// Plugin that provides scene functionality to an [`App`].
 #[derive(Default)]
@@ -59,7 +90,24 @@ impl Plugin for ScenePlugin {
             .init_asset::<Scene>()
             .init_asset_loader::<SceneLoader>()
             .init_resource::<SceneSpawner>()
-            .add_systems(SpawnScene, (scene_spawner, scene_spawner_system).chain());
+            .init_resource::<SceneStreaming>()
+            .add_observer(on_streaming_cell_ready)
+            .add_systems(
+                SpawnScene,
+                (
+                    scene_spawner,
+                    scene_spawner_system,
+                    apply_changed_scene_parameters,
+                    apply_changed_scene_overrides,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                PostUpdate,
+                (update_streaming_cells, update_streaming_grids)
+                    .chain()
+                    .after(TransformSystems::Propagate),
+            );
 
         // Register component hooks for DynamicSceneRoot
         app.world_mut()
@@ -1,4 +1,5 @@
-use bevy_reflect::{PartialReflect, ReflectFromReflect, TypeRegistration};
+use bevy_ecs::{entity::Entity, reflect::ReflectComponent, world::World};
+use bevy_reflect::{GetPath, PartialReflect, ReflectFromReflect, TypeRegistration};
 
 /// Attempts to clone a [`PartialReflect`] value using various methods.
 ///
@@ -23,3 +24,28 @@ pub(super) fn clone_reflect_value(
                 .unwrap_or_else(|| value.to_dynamic())
         })
 }
+
+/// Applies `value` to the field at the [reflection path](GetPath) `field` of a component of
+/// `entity`, or to the whole component if `field` is empty.
+///
+/// Returns a description of the error if the entity doesn't have the component, or if the field
+/// doesn't exist or has another type.
+pub(super) fn apply_component_field(
+    world: &mut World,
+    entity: Entity,
+    reflect_component: &ReflectComponent,
+    field: &str,
+    value: &dyn PartialReflect,
+) -> Result<(), String> {
+    let Some(mut component) = reflect_component.reflect_mut(world.entity_mut(entity)) else {
+        return Err("the entity doesn't have this component".into());
+    };
+    let target = if field.is_empty() {
+        component.as_partial_reflect_mut()
+    } else {
+        component
+            .reflect_path_mut(field)
+            .map_err(|err| err.to_string())?
+    };
+    target.try_apply(value).map_err(|err| err.to_string())
+}
@@ -0,0 +1,246 @@
+use bevy_app::App;
+use bevy_ecs::{
+    archetype::{Archetype, ArchetypeEntity},
+    entity::{Entity, EntityHashMap},
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    world::World,
+};
+use bevy_reflect::{FromType, GetTypeRegistration, Reflect, TypePath, TypeRegistry};
+use thiserror::Error;
+
+use crate::{BinarySceneError, DynamicScene, DynamicSceneBuilder, SceneFilter, SceneSpawnError};
+
+/// Type data marking a component or resource as part of the [`SaveGame`]s.
+///
+/// It's usually added with `#[reflect(Save)]`, or with [`SaveGameApp::register_saveable`] for the
+/// types of other crates.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::Reflect;
+/// # use bevy_scene::ReflectSave;
+/// #[derive(Component, Reflect)]
+/// #[reflect(Component, Save)]
+/// struct Inventory {
+///     gold: u32,
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ReflectSave;
+
+impl<T> FromType<T> for ReflectSave {
+    fn from_type() -> Self {
+        Self
+    }
+}
+
+/// Adds saveable types to an [`App`].
+pub trait SaveGameApp {
+    /// Registers `T`, a component or resource, and marks it as part of the [`SaveGame`]s.
+    fn register_saveable<T: Reflect + TypePath + GetTypeRegistration>(&mut self) -> &mut Self;
+}
+
+impl SaveGameApp for App {
+    fn register_saveable<T: Reflect + TypePath + GetTypeRegistration>(&mut self) -> &mut Self {
+        self.register_type::<T>()
+            .register_type_data::<T, ReflectSave>()
+    }
+}
+
+/// Possible errors that can be produced when saving or restoring a [`SaveGame`].
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum SaveGameError {
+    /// The save game couldn't be serialized or deserialized.
+    #[error(transparent)]
+    Serialization(#[from] BinarySceneError),
+    /// The save game couldn't be written to the world.
+    #[error(transparent)]
+    Spawn(#[from] SceneSpawnError),
+}
+
+/// A snapshot of the saveable components and resources of a world, marked with [`ReflectSave`].
+///
+/// The snapshot holds the entities with at least one saveable component, with only their saveable
+/// components. It's serialized in the [binary scene format](DynamicScene::serialize_binary), which
+/// records the version of the scenes of the app, so that the save games of older versions are
+/// converted by the [`SceneMigration`](crate::SceneMigration)s when they're read.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_scene::SaveGame;
+/// fn save(world: &mut World) {
+///     let save_game = SaveGame::capture(world);
+///     let registry = world.resource::<AppTypeRegistry>().read();
+///     let bytes = save_game.to_bytes(&registry).unwrap();
+///     // Write the bytes to a file...
+///     # let _ = bytes;
+/// }
+///
+/// fn load(world: &mut World, bytes: &[u8]) {
+///     let registry = world.resource::<AppTypeRegistry>().clone();
+///     let save_game = SaveGame::from_bytes(bytes, &registry.read()).unwrap();
+///     save_game.restore(world).unwrap();
+/// }
+/// ```
+pub struct SaveGame {
+    scene: DynamicScene,
+}
+
+impl SaveGame {
+    /// Captures the saveable components and resources of `world`.
+    pub fn capture(world: &World) -> Self {
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let mut components = SceneFilter::deny_all();
+        let mut resources = SceneFilter::deny_all();
+        for (registration, _) in registry.iter_with_data::<ReflectSave>() {
+            if registration.data::<ReflectComponent>().is_some() {
+                components = components.allow_by_id(registration.type_id());
+            }
+            if registration.data::<ReflectResource>().is_some() {
+                resources = resources.allow_by_id(registration.type_id());
+            }
+        }
+
+        let entities = saved_entities(world, &registry);
+        let scene = DynamicSceneBuilder::from_world(world)
+            .with_component_filter(components)
+            .with_resource_filter(resources)
+            .extract_entities(entities.into_iter())
+            .extract_resources()
+            .build();
+        Self { scene }
+    }
+
+    /// Restores the saveable components and resources of `world` to this snapshot.
+    ///
+    /// The entities of `world` with a saveable component are despawned, along with their
+    /// descendants, then the entities of the snapshot are spawned. The entities referenced by the
+    /// saved components and resources are mapped to the spawned entities, and the returned map
+    /// gives the spawned entity of each saved entity.
+    pub fn restore(&self, world: &mut World) -> Result<EntityHashMap<Entity>, SaveGameError> {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        for entity in saved_entities(world, &registry.read()) {
+            // The entity may have been despawned along with its parent.
+            let _ = world.try_despawn(entity);
+        }
+
+        let mut entity_map = EntityHashMap::default();
+        self.scene
+            .write_to_world_with(world, &mut entity_map, &registry)?;
+        Ok(entity_map)
+    }
+
+    /// Serializes this snapshot.
+    pub fn to_bytes(&self, registry: &TypeRegistry) -> Result<Vec<u8>, SaveGameError> {
+        Ok(self.scene.serialize_binary(registry)?)
+    }
+
+    /// Deserializes a snapshot serialized with [`SaveGame::to_bytes`].
+    pub fn from_bytes(bytes: &[u8], registry: &TypeRegistry) -> Result<Self, SaveGameError> {
+        Ok(Self {
+            scene: DynamicScene::deserialize_binary(bytes, registry)?,
+        })
+    }
+
+    /// Returns the saved entities and resources.
+    pub fn scene(&self) -> &DynamicScene {
+        &self.scene
+    }
+
+    /// Returns the saved entities and resources, consuming the snapshot.
+    pub fn into_scene(self) -> DynamicScene {
+        self.scene
+    }
+}
+
+/// Returns the entities of `world` with at least one saveable component.
+fn saved_entities(world: &World, registry: &TypeRegistry) -> Vec<Entity> {
+    let saved_components: Vec<_> = registry
+        .iter_with_data::<ReflectSave>()
+        .map(|(registration, _)| registration.type_id())
+        .filter_map(|type_id| world.components().get_id(type_id))
+        .collect();
+    world
+        .archetypes()
+        .iter()
+        .filter(|archetype| saved_components.iter().any(|&id| archetype.contains(id)))
+        .flat_map(Archetype::entities)
+        .map(ArchetypeEntity::id)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_ecs::{
+        component::Component,
+        entity::{Entity, MapEntities},
+        reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities, ReflectResource},
+        resource::Resource,
+    };
+    use bevy_reflect::Reflect;
+
+    use super::{ReflectSave, SaveGame, SaveGameApp};
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component, Save)]
+    struct Health(u32);
+
+    #[derive(Component, Reflect, MapEntities, Debug)]
+    #[reflect(Component, MapEntities, Save)]
+    struct Target(#[entities] Entity);
+
+    #[derive(Component, Reflect)]
+    #[reflect(Component)]
+    struct Transient;
+
+    #[derive(Resource, Reflect, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct Score(u32);
+
+    #[test]
+    fn save_and_restore() {
+        let mut app = App::new();
+        app.register_type::<Health>()
+            .register_type::<Target>()
+            .register_type::<Entity>()
+            .register_type::<Transient>()
+            .register_saveable::<Score>();
+        let world = app.world_mut();
+
+        let enemy = world.spawn((Health(10), Transient)).id();
+        let player = world.spawn((Health(100), Target(enemy))).id();
+        let transient = world.spawn(Transient).id();
+        world.insert_resource(Score(3));
+
+        let save_game = SaveGame::capture(world);
+        assert_eq!(save_game.scene().entities.len(), 2);
+        let bytes = {
+            let registry = world.resource::<AppTypeRegistry>().read();
+            save_game.to_bytes(&registry).unwrap()
+        };
+
+        world.entity_mut(player).insert(Health(1));
+        world.despawn(enemy);
+        world.insert_resource(Score(0));
+
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let save_game = SaveGame::from_bytes(&bytes, &registry.read()).unwrap();
+        let entity_map = save_game.restore(world).unwrap();
+
+        let restored_player = entity_map[&player];
+        let restored_enemy = entity_map[&enemy];
+        assert!(world.get_entity(player).is_err());
+        assert_eq!(world.get::<Health>(restored_player), Some(&Health(100)));
+        assert_eq!(world.get::<Health>(restored_enemy), Some(&Health(10)));
+        assert_eq!(
+            world.get::<Target>(restored_player).unwrap().0,
+            restored_enemy
+        );
+        // The components that aren't saveable aren't saved.
+        assert!(world.get::<Transient>(restored_enemy).is_none());
+        assert!(world.get::<Transient>(transient).is_some());
+        assert_eq!(world.resource::<Score>(), &Score(3));
+    }
+}
@@ -1,7 +1,7 @@
 use core::any::TypeId;
 
 use crate::reflect_utils::clone_reflect_value;
-use crate::{DynamicScene, SceneSpawnError};
+use crate::{DynamicScene, ReflectMapSceneEntities, SceneSpawnError};
 use bevy_asset::Asset;
 use bevy_ecs::{
     component::ComponentCloneBehavior,
@@ -145,7 +145,7 @@ impl Scene {
                             }
                         })?;
 
-                    let Some(component) = reflect_component
+                    let Some(mut component) = reflect_component
                         .reflect(self.world.entity(scene_entity.id()))
                         .map(|component| {
                             clone_reflect_value(component.as_partial_reflect(), registration)
@@ -156,6 +156,13 @@ impl Scene {
 
                     // If this component references entities in the scene,
                     // update them to the entities in the world.
+                    if let Some(map_scene_entities) = registration.data::<ReflectMapSceneEntities>()
+                    {
+                        SceneEntityMapper::world_scope(entity_map, world, |_, mapper| {
+                            map_scene_entities
+                                .map_entities(component.as_partial_reflect_mut(), mapper);
+                        });
+                    }
                     SceneEntityMapper::world_scope(entity_map, world, |world, mapper| {
                         reflect_component.apply_or_insert_mapped(
                             &mut world.entity_mut(entity),
@@ -0,0 +1,280 @@
+use alloc::sync::Arc;
+use core::any::TypeId;
+
+use bevy_app::App;
+use bevy_ecs::{
+    entity::{Entity, EntityMapper},
+    reflect::AppTypeRegistry,
+};
+use bevy_reflect::{
+    FromReflect, FromType, GetTypeRegistration, PartialReflect, ReflectMut, TypePath,
+};
+
+type MapSceneEntitiesFn = dyn Fn(&mut dyn PartialReflect, &mut dyn EntityMapper) + Send + Sync;
+
+/// Type data remapping the [`Entity`] fields of a component, or of a resource of a
+/// [`DynamicScene`](crate::DynamicScene), when it's spawned from a scene, so that it references
+/// the spawned entities rather than the entities of the scene.
+///
+/// The fields of components marked with `#[entities]` are already remapped through
+/// [`MapEntities`](bevy_ecs::entity::MapEntities). This covers the types whose fields aren't, such
+/// as the types of other crates, and must not be used for types whose fields are.
+///
+/// Adding `#[reflect(MapSceneEntities)]` to a type remaps all of its [`Entity`] fields, found with
+/// reflection, including in its collections and nested types. Custom remapping is registered with
+/// [`SceneEntityMappingApp::register_scene_entity_mapping`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::Reflect;
+/// # use bevy_scene::ReflectMapSceneEntities;
+/// #[derive(Component, Reflect)]
+/// #[reflect(Component, MapSceneEntities)]
+/// struct Squad {
+///     leader: Entity,
+///     members: Vec<Entity>,
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ReflectMapSceneEntities {
+    map_entities: Arc<MapSceneEntitiesFn>,
+}
+
+impl ReflectMapSceneEntities {
+    /// Creates type data remapping the entities of values of `T` with `map_entities`.
+    pub fn new<T: FromReflect>(
+        map_entities: impl Fn(&mut T, &mut dyn EntityMapper) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            map_entities: Arc::new(move |value, mapper| {
+                if let Some(value) = value.try_downcast_mut::<T>() {
+                    map_entities(value, mapper);
+                } else if let Some(mut concrete) = T::from_reflect(value) {
+                    map_entities(&mut concrete, mapper);
+                    value.apply(&concrete);
+                }
+            }),
+        }
+    }
+
+    /// Creates type data remapping all the [`Entity`] fields found with reflection.
+    pub fn reflected() -> Self {
+        Self {
+            map_entities: Arc::new(map_reflected_entities),
+        }
+    }
+
+    /// Remaps the entities of `value` with `mapper`.
+    pub fn map_entities(&self, value: &mut dyn PartialReflect, mapper: &mut dyn EntityMapper) {
+        (self.map_entities)(value, mapper);
+    }
+}
+
+impl<T> FromType<T> for ReflectMapSceneEntities {
+    fn from_type() -> Self {
+        Self::reflected()
+    }
+}
+
+/// Remaps all the [`Entity`] values found in `value` with reflection.
+fn map_reflected_entities(value: &mut dyn PartialReflect, mapper: &mut dyn EntityMapper) {
+    if let Some(entity) = value.try_downcast_mut::<Entity>() {
+        *entity = mapper.get_mapped(*entity);
+        return;
+    }
+    match value.reflect_mut() {
+        ReflectMut::Struct(value) => {
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_at_mut(index) {
+                    map_reflected_entities(field, mapper);
+                }
+            }
+        }
+        ReflectMut::TupleStruct(value) => {
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_mut(index) {
+                    map_reflected_entities(field, mapper);
+                }
+            }
+        }
+        ReflectMut::Tuple(value) => {
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_mut(index) {
+                    map_reflected_entities(field, mapper);
+                }
+            }
+        }
+        ReflectMut::List(value) => {
+            for index in 0..value.len() {
+                if let Some(item) = value.get_mut(index) {
+                    map_reflected_entities(item, mapper);
+                }
+            }
+        }
+        ReflectMut::Array(value) => {
+            for index in 0..value.len() {
+                if let Some(item) = value.get_mut(index) {
+                    map_reflected_entities(item, mapper);
+                }
+            }
+        }
+        ReflectMut::Map(value) => {
+            // The keys can't be changed in place, so the entries are reinserted.
+            for (mut key, mut item) in value.drain() {
+                map_reflected_entities(key.as_mut(), mapper);
+                map_reflected_entities(item.as_mut(), mapper);
+                value.insert_boxed(key, item);
+            }
+        }
+        ReflectMut::Set(value) => {
+            for mut item in value.drain() {
+                map_reflected_entities(item.as_mut(), mapper);
+                value.insert_boxed(item);
+            }
+        }
+        ReflectMut::Enum(value) => {
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_at_mut(index) {
+                    map_reflected_entities(field, mapper);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Adds remapping of the entities of components and resources spawned from scenes to an [`App`].
+pub trait SceneEntityMappingApp {
+    /// Registers `map_entities` to remap the [`Entity`] fields of `T` when it's spawned from a
+    /// scene, and registers `T` in the [`AppTypeRegistry`].
+    ///
+    /// See [`ReflectMapSceneEntities`].
+    ///
+    /// ```
+    /// # use bevy_app::App;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_reflect::Reflect;
+    /// # use bevy_scene::SceneEntityMappingApp;
+    /// #[derive(Component, Reflect)]
+    /// #[reflect(Component)]
+    /// struct Rope {
+    ///     ends: [Entity; 2],
+    /// }
+    ///
+    /// App::new().register_scene_entity_mapping::<Rope>(|rope, mapper| {
+    ///     rope.ends = rope.ends.map(|end| mapper.get_mapped(end));
+    /// });
+    /// ```
+    fn register_scene_entity_mapping<T>(
+        &mut self,
+        map_entities: impl Fn(&mut T, &mut dyn EntityMapper) + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        T: FromReflect + TypePath + GetTypeRegistration;
+}
+
+impl SceneEntityMappingApp for App {
+    fn register_scene_entity_mapping<T>(
+        &mut self,
+        map_entities: impl Fn(&mut T, &mut dyn EntityMapper) + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        T: FromReflect + TypePath + GetTypeRegistration,
+    {
+        {
+            let mut registry = self.world().resource::<AppTypeRegistry>().write();
+            registry.register::<T>();
+            registry
+                .get_mut(TypeId::of::<T>())
+                .unwrap()
+                .insert(ReflectMapSceneEntities::new(map_entities));
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_ecs::{
+        component::Component,
+        entity::{Entity, EntityHashMap},
+        reflect::{AppTypeRegistry, ReflectComponent},
+        world::World,
+    };
+    use bevy_platform::collections::HashMap;
+    use bevy_reflect::Reflect;
+
+    use super::{ReflectMapSceneEntities, SceneEntityMappingApp};
+    use crate::{DynamicSceneBuilder, Scene};
+
+    #[derive(Component, Reflect)]
+    #[reflect(Component, MapSceneEntities)]
+    struct Squad {
+        leader: Entity,
+        members: Vec<Entity>,
+        roles: HashMap<Entity, u32>,
+        next: Option<Entity>,
+    }
+
+    #[derive(Component, Reflect)]
+    #[reflect(Component)]
+    struct Rope {
+        ends: [Entity; 2],
+    }
+
+    #[test]
+    fn remap_scene_entities() {
+        let mut app = App::new();
+        app.register_type::<Squad>()
+            .register_type::<Entity>()
+            .register_type::<Vec<Entity>>()
+            .register_type::<HashMap<Entity, u32>>()
+            .register_type::<Option<Entity>>()
+            .register_type::<[Entity; 2]>()
+            .register_scene_entity_mapping::<Rope>(|rope, mapper| {
+                rope.ends = rope.ends.map(|end| mapper.get_mapped(end));
+            });
+        let registry = app.world().resource::<AppTypeRegistry>().clone();
+
+        let mut scene_world = World::new();
+        scene_world.insert_resource(registry.clone());
+        let a = scene_world.spawn_empty().id();
+        let b = scene_world.spawn(Rope { ends: [a, a] }).id();
+        scene_world.entity_mut(a).insert(Squad {
+            leader: b,
+            members: vec![a, b],
+            roles: [(b, 1)].into(),
+            next: Some(b),
+        });
+        let dynamic_scene = DynamicSceneBuilder::from_world(&scene_world)
+            .extract_entities([a, b].into_iter())
+            .build();
+        scene_world.remove_resource::<AppTypeRegistry>();
+        let scene = Scene::new(scene_world);
+
+        // Both kinds of scenes remap the entities.
+        let mut world = World::new();
+        world.insert_resource(registry.clone());
+        // Offset the entities of the world from those of the scene.
+        world.spawn_batch((0..10).map(|_| ()));
+        let mut dynamic_entity_map = EntityHashMap::default();
+        dynamic_scene
+            .write_to_world(&mut world, &mut dynamic_entity_map)
+            .unwrap();
+        let mut entity_map = EntityHashMap::default();
+        scene
+            .write_to_world_with(&mut world, &mut entity_map, &registry)
+            .unwrap();
+
+        for entity_map in [dynamic_entity_map, entity_map] {
+            let (a, b) = (entity_map[&a], entity_map[&b]);
+            let squad = world.get::<Squad>(a).unwrap();
+            assert_eq!(squad.leader, b);
+            assert_eq!(squad.members, [a, b]);
+            assert_eq!(squad.roles[&b], 1);
+            assert_eq!(squad.next, Some(b));
+            assert_eq!(world.get::<Rope>(b).unwrap().ends, [a, a]);
+        }
+    }
+}
@@ -7,14 +7,18 @@ use thiserror::Error;
 
 #[cfg(feature = "serialize")]
 use {
-    crate::{serde::SceneDeserializer, DynamicScene},
+    crate::{
+        is_binary_scene, serde::SceneDeserializer, BinarySceneError, DynamicScene, SceneValidation,
+    },
     bevy_asset::{io::Reader, AssetLoader, LoadContext},
-    serde::de::DeserializeSeed,
+    serde::{de::DeserializeSeed, Deserialize, Serialize},
+    tracing::warn,
 };
 
-/// Asset loader for a Bevy dynamic scene (`.scn` / `.scn.ron`).
+/// Asset loader for a Bevy dynamic scene (`.scn` / `.scn.ron` / `.scn.bin`).
 ///
-/// The loader handles assets serialized with [`DynamicScene::serialize`].
+/// The loader handles assets serialized with [`DynamicScene::serialize`] and
+/// [`DynamicScene::serialize_binary`], whatever their extension.
 #[derive(Debug)]
 pub struct SceneLoader {
     #[cfg_attr(
@@ -33,6 +37,24 @@ impl FromWorld for SceneLoader {
     }
 }
 
+/// Settings of the [`SceneLoader`].
+#[cfg(feature = "serialize")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SceneLoaderSettings {
+    /// Whether the loaded scenes are [validated](DynamicScene::validate), including whether the
+    /// assets they reference exist.
+    ///
+    /// The scenes with errors fail to load, and the warnings are logged.
+    pub validate: bool,
+}
+
+#[cfg(feature = "serialize")]
+impl Default for SceneLoaderSettings {
+    fn default() -> Self {
+        Self { validate: true }
+    }
+}
+
 /// Possible errors that can be produced by [`SceneLoader`]
 #[non_exhaustive]
 #[derive(Debug, Error)]
@@ -43,32 +65,61 @@ pub enum SceneLoaderError {
     /// A [RON Error](ron::error::SpannedError)
     #[error("Could not parse RON: {0}")]
     RonSpannedError(#[from] ron::error::SpannedError),
+    /// A [Binary Scene Error](BinarySceneError)
+    #[cfg(feature = "serialize")]
+    #[error("Could not read the binary scene: {0}")]
+    BinarySceneError(#[from] BinarySceneError),
+    /// The scene failed its [validation](DynamicScene::validate).
+    #[cfg(feature = "serialize")]
+    #[error("The scene is invalid: {0}")]
+    InvalidScene(SceneValidation),
 }
 
 #[cfg(feature = "serialize")]
 impl AssetLoader for SceneLoader {
     type Asset = DynamicScene;
-    type Settings = ();
+    type Settings = SceneLoaderSettings;
     type Error = SceneLoaderError;
 
     async fn load(
         &self,
         reader: &mut dyn Reader,
-        _settings: &(),
-        _load_context: &mut LoadContext<'_>,
+        settings: &SceneLoaderSettings,
+        load_context: &mut LoadContext<'_>,
     ) -> Result<Self::Asset, Self::Error> {
         let mut bytes = Vec::new();
         reader.read_to_end(&mut bytes).await?;
-        let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
-        let scene_deserializer = SceneDeserializer {
-            type_registry: &self.type_registry.read(),
+        let scene = if is_binary_scene(&bytes) {
+            DynamicScene::deserialize_binary(&bytes, &self.type_registry.read())?
+        } else {
+            let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+            let scene_deserializer = SceneDeserializer {
+                type_registry: &self.type_registry.read(),
+            };
+            scene_deserializer
+                .deserialize(&mut deserializer)
+                .map_err(|e| deserializer.span_error(e))?
         };
-        Ok(scene_deserializer
-            .deserialize(&mut deserializer)
-            .map_err(|e| deserializer.span_error(e))?)
+
+        if settings.validate {
+            let mut validation = scene.validate(&self.type_registry.read());
+            for asset_path in core::mem::take(&mut validation.asset_paths) {
+                if !load_context.asset_exists(&asset_path.path).await {
+                    validation.diagnostics.push(asset_path.clone().into_missing());
+                }
+                validation.asset_paths.push(asset_path);
+            }
+            for warning in validation.warnings() {
+                warn!("{}: {warning}", load_context.path());
+            }
+            if !validation.is_valid() {
+                return Err(SceneLoaderError::InvalidScene(validation));
+            }
+        }
+        Ok(scene)
     }
 
     fn extensions(&self) -> &[&str] {
-        &["scn", "scn.ron"]
+        &["scn", "scn.ron", "scn.bin"]
     }
 }
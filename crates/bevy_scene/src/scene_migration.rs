@@ -0,0 +1,209 @@
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use bevy_app::App;
+use bevy_ecs::reflect::AppTypeRegistry;
+use bevy_reflect::{FromReflect, GetTypeRegistration, PartialReflect, TypeRegistry};
+use core::any::TypeId;
+
+type MigrateFn = dyn Fn(&dyn PartialReflect) -> Option<Box<dyn PartialReflect>> + Send + Sync;
+
+/// Converts the serialized data of a type from one version of an app's scenes to a later one.
+///
+/// Scenes are saved along with the latest version any migration was registered for (see
+/// [`scene_version`]), and when a scene saved at an older version is loaded, the values of
+/// migrated types are deserialized as the type the migration converts from, then converted until
+/// they reach the current layout of the type.
+///
+/// Migrations are usually registered with [`SceneMigrationApp::register_scene_migration`].
+#[derive(Clone)]
+pub struct SceneMigration {
+    from_version: u32,
+    to_version: u32,
+    source_type_id: TypeId,
+    migrate: Arc<MigrateFn>,
+}
+
+impl SceneMigration {
+    /// Creates a migration from `from_version` to `to_version`, which converts values of `Old`,
+    /// the layout of the migrated type at `from_version`, using `migrate`.
+    ///
+    /// `New` is either the migrated type itself, or the type the next migration converts from.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from_version` isn't lower than `to_version`.
+    pub fn new<Old: FromReflect, New: PartialReflect>(
+        from_version: u32,
+        to_version: u32,
+        migrate: fn(Old) -> New,
+    ) -> Self {
+        assert!(
+            from_version < to_version,
+            "scene migrations must go from a version to a later one, got {from_version} -> {to_version}"
+        );
+        Self {
+            from_version,
+            to_version,
+            source_type_id: TypeId::of::<Old>(),
+            migrate: Arc::new(move |value| {
+                let old = Old::from_reflect(value)?;
+                Some(Box::new(migrate(old)))
+            }),
+        }
+    }
+
+    /// The version of the scenes this migration converts data from.
+    pub fn from_version(&self) -> u32 {
+        self.from_version
+    }
+
+    /// The version of the scenes this migration converts data to.
+    pub fn to_version(&self) -> u32 {
+        self.to_version
+    }
+
+    /// The [`TypeId`] of the type the data is deserialized as before running this migration.
+    pub fn source_type_id(&self) -> TypeId {
+        self.source_type_id
+    }
+
+    /// Converts `value`, returning `None` if it isn't a value of the type this migration converts
+    /// from.
+    pub fn migrate(&self, value: &dyn PartialReflect) -> Option<Box<dyn PartialReflect>> {
+        (self.migrate)(value)
+    }
+}
+
+/// Type data holding the [`SceneMigration`]s of a type, ordered by version.
+#[derive(Clone, Default)]
+pub struct ReflectSceneMigrations {
+    migrations: Vec<SceneMigration>,
+}
+
+impl ReflectSceneMigrations {
+    /// Adds a migration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the versions of `migration` overlap those of a migration that was already added.
+    pub fn add(&mut self, migration: SceneMigration) {
+        let index = self
+            .migrations
+            .partition_point(|other| other.from_version < migration.from_version);
+        let overlaps_previous =
+            index > 0 && self.migrations[index - 1].to_version > migration.from_version;
+        let overlaps_next = self
+            .migrations
+            .get(index)
+            .is_some_and(|next| next.from_version < migration.to_version);
+        assert!(
+            !overlaps_previous && !overlaps_next,
+            "scene migration {} -> {} overlaps another migration of the same type",
+            migration.from_version,
+            migration.to_version
+        );
+        self.migrations.insert(index, migration);
+    }
+
+    /// Returns the migrations, ordered by version.
+    pub fn iter(&self) -> impl Iterator<Item = &SceneMigration> {
+        self.migrations.iter()
+    }
+
+    /// Returns the migrations that bring data serialized at `version` up to date, in the order they
+    /// should run.
+    pub fn pending(&self, version: u32) -> &[SceneMigration] {
+        let index = self
+            .migrations
+            .partition_point(|migration| migration.to_version <= version);
+        &self.migrations[index..]
+    }
+}
+
+/// Returns the version scenes are serialized at with the types in `registry`.
+///
+/// This is the latest version any [`SceneMigration`] was registered for, or 0 if there are none.
+pub fn scene_version(registry: &TypeRegistry) -> u32 {
+    registry
+        .iter_with_data::<ReflectSceneMigrations>()
+        .filter_map(|(_, migrations)| migrations.iter().last())
+        .map(SceneMigration::to_version)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Adds [`SceneMigration`]s to an [`App`].
+pub trait SceneMigrationApp {
+    /// Registers a migration of the serialized data of `T` from `from_version` to `to_version`,
+    /// so that scenes saved before `T` was changed keep loading.
+    ///
+    /// `Old` is the layout of `T` at `from_version`, and `New` is either `T` itself, or the `Old`
+    /// type of the migration from `to_version`. Both `T` and `Old` are registered in the
+    /// [`AppTypeRegistry`].
+    ///
+    /// ```
+    /// # use bevy_app::App;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_reflect::Reflect;
+    /// # use bevy_scene::SceneMigrationApp;
+    /// #[derive(Component, Reflect)]
+    /// #[reflect(Component)]
+    /// struct Health {
+    ///     current: u32,
+    /// }
+    ///
+    /// // The layout of `Health` in scenes saved before version 1.
+    /// #[derive(Reflect)]
+    /// struct HealthV0 {
+    ///     hp: u32,
+    /// }
+    ///
+    /// App::new().register_scene_migration::<Health, HealthV0, Health>(0, 1, |old| Health {
+    ///     current: old.hp,
+    /// });
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from_version` isn't lower than `to_version`, or if the versions overlap those of
+    /// another migration of `T`.
+    fn register_scene_migration<T, Old, New>(
+        &mut self,
+        from_version: u32,
+        to_version: u32,
+        migrate: fn(Old) -> New,
+    ) -> &mut Self
+    where
+        T: GetTypeRegistration,
+        Old: FromReflect + GetTypeRegistration,
+        New: PartialReflect;
+}
+
+impl SceneMigrationApp for App {
+    fn register_scene_migration<T, Old, New>(
+        &mut self,
+        from_version: u32,
+        to_version: u32,
+        migrate: fn(Old) -> New,
+    ) -> &mut Self
+    where
+        T: GetTypeRegistration,
+        Old: FromReflect + GetTypeRegistration,
+        New: PartialReflect,
+    {
+        let migration = SceneMigration::new(from_version, to_version, migrate);
+        {
+            let mut registry = self.world().resource::<AppTypeRegistry>().write();
+            registry.register::<T>();
+            registry.register::<Old>();
+            let registration = registry.get_mut(TypeId::of::<T>()).unwrap();
+            if registration.data::<ReflectSceneMigrations>().is_none() {
+                registration.insert(ReflectSceneMigrations::default());
+            }
+            registration
+                .data_mut::<ReflectSceneMigrations>()
+                .unwrap()
+                .add(migration);
+        }
+        self
+    }
+}
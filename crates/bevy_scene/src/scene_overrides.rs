@@ -0,0 +1,290 @@
+use core::any::TypeId;
+
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    name::Name,
+    query::{Changed, Or, QueryState},
+    reflect::{AppTypeRegistry, ReflectComponent},
+    world::{Mut, World},
+};
+use bevy_reflect::PartialReflect;
+use bevy_utils::prelude::DebugName;
+use tracing::warn;
+
+use crate::{reflect_utils::apply_component_field, SceneInstance, SceneParameters, SceneSpawner};
+
+/// Changes to the components of the entities of a scene instance, applied on top of its scene.
+///
+/// Adding this component next to a [`SceneRoot`](crate::SceneRoot) or a
+/// [`DynamicSceneRoot`](crate::DynamicSceneRoot) lets one instance of a scene differ from the
+/// others, such as having a lamp of another color, without making a new scene asset. The entities
+/// of the instance are picked by their [`Name`].
+///
+/// The overrides are applied when the instance is spawned, including when it's respawned because
+/// its scene was hot reloaded, after its [`SceneParameters`] and before
+/// [`SceneInstanceReady`](crate::SceneInstanceReady) is triggered. They are applied again when this component changes, but the changes of the overrides
+/// that were removed are kept until the instance is respawned.
+///
+/// ```
+/// # use bevy_asset::Handle;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::Reflect;
+/// # use bevy_scene::{Scene, SceneOverrides, SceneRoot};
+/// # #[derive(Component, Reflect)]
+/// # #[reflect(Component)]
+/// # struct Lamp { intensity: f32 }
+/// fn spawn_dim_room(mut commands: Commands, room: Handle<Scene>) {
+///     commands.spawn((
+///         SceneRoot(room),
+///         SceneOverrides::default().with_field::<Lamp>("Ceiling Lamp", "intensity", 0.25f32),
+///     ));
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Default)]
+pub struct SceneOverrides {
+    overrides: Vec<SceneOverride>,
+}
+
+/// A change to a field of a component of the entities with a given name.
+#[derive(Debug)]
+struct SceneOverride {
+    entity: Name,
+    component: TypeId,
+    component_name: DebugName,
+    field: String,
+    value: Box<dyn PartialReflect>,
+}
+
+impl Clone for SceneOverride {
+    fn clone(&self) -> Self {
+        Self {
+            entity: self.entity.clone(),
+            component: self.component,
+            component_name: self.component_name.clone(),
+            field: self.field.clone(),
+            value: self.value.to_dynamic(),
+        }
+    }
+}
+
+impl SceneOverrides {
+    /// Overrides a field of the `C` component of the entities named `entity`.
+    ///
+    /// The field is given by a [reflection path](bevy_reflect::GetPath), such as `color` or
+    /// `translation.y`, and `value` must have the type of the field.
+    pub fn with_field<C: Component>(
+        mut self,
+        entity: impl Into<Name>,
+        field: impl Into<String>,
+        value: impl PartialReflect,
+    ) -> Self {
+        self.set_field::<C>(entity, field, value);
+        self
+    }
+
+    /// Overrides the whole `C` component of the entities named `entity`.
+    pub fn with_component<C: Component + PartialReflect>(
+        self,
+        entity: impl Into<Name>,
+        component: C,
+    ) -> Self {
+        self.with_field::<C>(entity, "", component)
+    }
+
+    /// Overrides a field of the `C` component of the entities named `entity`, replacing the
+    /// previous override of that field if any.
+    ///
+    /// See [`SceneOverrides::with_field`].
+    pub fn set_field<C: Component>(
+        &mut self,
+        entity: impl Into<Name>,
+        field: impl Into<String>,
+        value: impl PartialReflect,
+    ) {
+        let entity = entity.into();
+        let field = field.into();
+        let component = TypeId::of::<C>();
+        self.overrides.retain(|scene_override| {
+            scene_override.entity != entity
+                || scene_override.component != component
+                || scene_override.field != field
+        });
+        self.overrides.push(SceneOverride {
+            entity,
+            component,
+            component_name: DebugName::type_name::<C>(),
+            field,
+            value: Box::new(value),
+        });
+    }
+
+    /// Removes the overrides of the entities named `entity`.
+    pub fn remove_entity(&mut self, entity: &str) {
+        self.overrides
+            .retain(|scene_override| scene_override.entity.as_str() != entity);
+    }
+
+    /// Returns `true` if there are no overrides.
+    pub fn is_empty(&self) -> bool {
+        self.overrides.is_empty()
+    }
+}
+
+/// Applies the [`SceneOverrides`] of `root` to the `entities` of its scene instance.
+pub(crate) fn apply_scene_overrides(
+    world: &mut World,
+    root: Entity,
+    entities: impl IntoIterator<Item = Entity>,
+) {
+    let Some(overrides) = world
+        .get::<SceneOverrides>(root)
+        .filter(|overrides| !overrides.is_empty())
+        .cloned()
+    else {
+        return;
+    };
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+
+    for entity in entities {
+        let Some(name) = world.get::<Name>(entity).cloned() else {
+            continue;
+        };
+        for scene_override in overrides.overrides.iter().filter(|o| o.entity == name) {
+            let Some(reflect_component) =
+                registry.get_type_data::<ReflectComponent>(scene_override.component)
+            else {
+                warn!(
+                    "Can't override `{}` on `{}`: the component isn't registered with `#[reflect(Component)]`.",
+                    scene_override.component_name, name
+                );
+                continue;
+            };
+            let result = apply_component_field(
+                world,
+                entity,
+                reflect_component,
+                &scene_override.field,
+                scene_override.value.as_ref(),
+            );
+            if let Err(err) = result {
+                warn!(
+                    "Can't override `{}` of `{}` on `{}`: {}",
+                    scene_override.field, scene_override.component_name, name, err
+                );
+            }
+        }
+    }
+}
+
+/// System that applies the [`SceneOverrides`] that changed to their spawned scene instances.
+///
+/// The overrides are also applied again when the [`SceneParameters`] of the instance change, since
+/// the overrides take precedence over the parameters.
+pub fn apply_changed_scene_overrides(
+    world: &mut World,
+    roots: &mut QueryState<
+        (Entity, &SceneInstance),
+        Or<(Changed<SceneOverrides>, Changed<SceneParameters>)>,
+    >,
+) {
+    let roots: Vec<_> = roots
+        .iter(world)
+        .map(|(root, instance)| (root, **instance))
+        .collect();
+    if roots.is_empty() {
+        return;
+    }
+
+    world.resource_scope(|world, scene_spawner: Mut<SceneSpawner>| {
+        for (root, instance_id) in roots {
+            let entities: Vec<_> = scene_spawner.iter_instance_entities(instance_id).collect();
+            apply_scene_overrides(world, root, entities);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_asset::{AssetPlugin, Assets};
+    use bevy_ecs::{component::Component, name::Name, reflect::ReflectComponent, world::World};
+    use bevy_reflect::Reflect;
+
+    use crate::{Scene, ScenePlugin, SceneRoot};
+
+    use super::SceneOverrides;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Lamp {
+        intensity: f32,
+    }
+
+    fn lamp_intensities(app: &mut App) -> Vec<(String, f32)> {
+        let mut intensities: Vec<_> = app
+            .world_mut()
+            .query::<(&Name, &Lamp)>()
+            .iter(app.world())
+            .map(|(name, lamp)| (name.to_string(), lamp.intensity))
+            .collect();
+        intensities.sort_by(|a, b| a.0.cmp(&b.0));
+        intensities
+    }
+
+    #[test]
+    fn apply_scene_overrides() {
+        let mut app = App::new();
+        app.add_plugins((AssetPlugin::default(), ScenePlugin))
+            .register_type::<Lamp>()
+            .register_type::<Name>();
+
+        let mut scene_world = World::new();
+        scene_world.spawn((Name::new("Lamp"), Lamp { intensity: 1.0 }));
+        scene_world.spawn((Name::new("Other Lamp"), Lamp { intensity: 1.0 }));
+        let scene = app
+            .world_mut()
+            .resource_mut::<Assets<Scene>>()
+            .add(Scene::new(scene_world));
+
+        let root = app
+            .world_mut()
+            .spawn((
+                SceneRoot(scene.clone()),
+                SceneOverrides::default().with_field::<Lamp>("Lamp", "intensity", 0.5f32),
+            ))
+            .id();
+        app.update();
+        assert_eq!(
+            lamp_intensities(&mut app),
+            [("Lamp".into(), 0.5), ("Other Lamp".into(), 1.0)]
+        );
+
+        // The overrides are applied again when they change.
+        app.world_mut()
+            .get_mut::<SceneOverrides>(root)
+            .unwrap()
+            .set_field::<Lamp>("Other Lamp", "intensity", 2.0f32);
+        app.update();
+        assert_eq!(
+            lamp_intensities(&mut app),
+            [("Lamp".into(), 0.5), ("Other Lamp".into(), 2.0)]
+        );
+
+        // The overrides are applied again when the scene is reloaded.
+        for _ in 0..4 {
+            app.update();
+        }
+        app.world_mut()
+            .resource_mut::<Assets<Scene>>()
+            .get_mut(&scene)
+            .unwrap();
+        app.update();
+        app.update();
+        assert_eq!(
+            lamp_intensities(&mut app),
+            [("Lamp".into(), 0.5), ("Other Lamp".into(), 2.0)]
+        );
+    }
+}
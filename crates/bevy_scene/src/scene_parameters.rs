@@ -0,0 +1,325 @@
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{Changed, QueryState},
+    reflect::{AppTypeRegistry, ReflectComponent},
+    world::{Mut, World},
+};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{std_traits::ReflectDefault, PartialReflect, Reflect, TypePath};
+use tracing::warn;
+
+use crate::{reflect_utils::apply_component_field, SceneInstance, SceneSpawner};
+
+/// Binds the fields of the components of a scene entity to the named parameters of its scene.
+///
+/// A scene declares its parameters by adding this component to its entities, which makes reusable
+/// prefabs possible, such as a door whose key is picked by each instance. The values of the
+/// parameters are given by the [`SceneParameters`] of each instance, and the fields keep their value
+/// from the scene for the parameters that aren't given.
+///
+/// As a component of the scene, the bindings are saved and loaded with the scene, for instance in
+/// the entry of the door entity of a `.scn.ron` file:
+///
+/// ```ron
+/// "bevy_scene::scene_parameters::SceneParameterBindings": ([
+///     Field(parameter: "key_id", component: "my_game::Door", field: "key_id"),
+/// ]),
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Default, PartialEq, Eq)]
+#[reflect(Component, Debug, Default, PartialEq, Clone)]
+pub struct SceneParameterBindings(pub Vec<SceneParameterBinding>);
+
+impl SceneParameterBindings {
+    /// Binds a field of the `C` component of the entity to the parameter named `parameter`.
+    ///
+    /// The field is given by a [reflection path](bevy_reflect::GetPath), such as `key_id` or
+    /// `translation.y`. The whole component is set by the parameter if `field` is empty.
+    pub fn with_field<C: Component + TypePath>(
+        mut self,
+        parameter: impl Into<String>,
+        field: impl Into<String>,
+    ) -> Self {
+        self.0.push(SceneParameterBinding::Field {
+            parameter: parameter.into(),
+            component: C::type_path().to_string(),
+            field: field.into(),
+        });
+        self
+    }
+
+    /// Passes the parameter named `parameter` on to the parameter named `nested` of the scene
+    /// instanced by the entity, with its own [`SceneRoot`](crate::SceneRoot) or
+    /// [`DynamicSceneRoot`](crate::DynamicSceneRoot).
+    pub fn with_nested(mut self, parameter: impl Into<String>, nested: impl Into<String>) -> Self {
+        self.0.push(SceneParameterBinding::Nested {
+            parameter: parameter.into(),
+            nested: nested.into(),
+        });
+        self
+    }
+}
+
+/// A binding of a [`SceneParameterBindings`].
+#[derive(Reflect, Debug, Clone, PartialEq, Eq)]
+#[reflect(Debug, PartialEq, Clone)]
+pub enum SceneParameterBinding {
+    /// Sets a field of a component of the entity to the value of the parameter.
+    Field {
+        /// The name of the parameter.
+        parameter: String,
+        /// The [type path](TypePath) of the component.
+        component: String,
+        /// The [reflection path](bevy_reflect::GetPath) of the field in the component, or an empty
+        /// string for the whole component.
+        field: String,
+    },
+    /// Passes the value of the parameter on to a parameter of the scene instanced by the entity.
+    Nested {
+        /// The name of the parameter.
+        parameter: String,
+        /// The name of the parameter of the nested scene.
+        nested: String,
+    },
+}
+
+/// The values of the parameters of a scene instance.
+///
+/// Adding this component next to a [`SceneRoot`](crate::SceneRoot) or a
+/// [`DynamicSceneRoot`](crate::DynamicSceneRoot) sets the fields bound to the parameters by the
+/// [`SceneParameterBindings`] of the entities of the scene.
+///
+/// The parameters are applied when the instance is spawned, including when it's respawned because
+/// its scene was hot reloaded, before its [`SceneOverrides`](crate::SceneOverrides). They are
+/// applied again when this component changes.
+///
+/// ```
+/// # use bevy_asset::Handle;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_scene::{Scene, SceneParameters, SceneRoot};
+/// fn spawn_locked_door(mut commands: Commands, door: Handle<Scene>) {
+///     commands.spawn((
+///         SceneRoot(door),
+///         SceneParameters::default().with("key_id", 3u32),
+///     ));
+/// }
+/// ```
+#[derive(Component, Debug, Default)]
+pub struct SceneParameters {
+    values: HashMap<String, Box<dyn PartialReflect>>,
+}
+
+impl Clone for SceneParameters {
+    fn clone(&self) -> Self {
+        Self {
+            values: self
+                .values
+                .iter()
+                .map(|(name, value)| (name.clone(), value.to_dynamic()))
+                .collect(),
+        }
+    }
+}
+
+impl SceneParameters {
+    /// Sets the value of the parameter named `name`.
+    pub fn with(mut self, name: impl Into<String>, value: impl PartialReflect) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    /// Sets the value of the parameter named `name`, replacing its previous value if any.
+    pub fn set(&mut self, name: impl Into<String>, value: impl PartialReflect) {
+        self.values.insert(name.into(), Box::new(value));
+    }
+
+    /// Returns the value of the parameter named `name`, if it's set.
+    pub fn get(&self, name: &str) -> Option<&dyn PartialReflect> {
+        self.values.get(name).map(AsRef::as_ref)
+    }
+
+    /// Unsets the parameter named `name`, returning its value if it was set.
+    ///
+    /// The fields bound to the parameter keep their value until the instance is respawned.
+    pub fn remove(&mut self, name: &str) -> Option<Box<dyn PartialReflect>> {
+        self.values.remove(name)
+    }
+
+    /// Returns `true` if no parameter is set.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Applies the [`SceneParameters`] of `root` to the `entities` of its scene instance.
+pub(crate) fn apply_scene_parameters(
+    world: &mut World,
+    root: Entity,
+    entities: impl IntoIterator<Item = Entity>,
+) {
+    let Some(parameters) = world
+        .get::<SceneParameters>(root)
+        .filter(|parameters| !parameters.is_empty())
+        .cloned()
+    else {
+        return;
+    };
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+
+    for entity in entities {
+        let Some(bindings) = world.get::<SceneParameterBindings>(entity).cloned() else {
+            continue;
+        };
+        for binding in bindings.0 {
+            match binding {
+                SceneParameterBinding::Field {
+                    parameter,
+                    component,
+                    field,
+                } => {
+                    let Some(value) = parameters.get(&parameter) else {
+                        continue;
+                    };
+                    let Some(reflect_component) = registry
+                        .get_with_type_path(&component)
+                        .and_then(|registration| registration.data::<ReflectComponent>())
+                    else {
+                        warn!(
+                            "Can't set the scene parameter `{}`: the component `{}` isn't registered with `#[reflect(Component)]`.",
+                            parameter, component
+                        );
+                        continue;
+                    };
+                    if let Err(err) =
+                        apply_component_field(world, entity, reflect_component, &field, value)
+                    {
+                        warn!(
+                            "Can't set the scene parameter `{}` to `{}` of `{}`: {}",
+                            parameter, field, component, err
+                        );
+                    }
+                }
+                SceneParameterBinding::Nested { parameter, nested } => {
+                    let Some(value) = parameters.get(&parameter) else {
+                        continue;
+                    };
+                    let mut entity = world.entity_mut(entity);
+                    match entity.get_mut::<SceneParameters>() {
+                        Some(mut nested_parameters) => {
+                            nested_parameters.values.insert(nested, value.to_dynamic());
+                        }
+                        None => {
+                            let mut nested_parameters = SceneParameters::default();
+                            nested_parameters.values.insert(nested, value.to_dynamic());
+                            entity.insert(nested_parameters);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// System that applies the [`SceneParameters`] that changed to their spawned scene instances.
+pub fn apply_changed_scene_parameters(
+    world: &mut World,
+    roots: &mut QueryState<(Entity, &SceneInstance), Changed<SceneParameters>>,
+) {
+    let roots: Vec<_> = roots
+        .iter(world)
+        .map(|(root, instance)| (root, **instance))
+        .collect();
+    if roots.is_empty() {
+        return;
+    }
+
+    world.resource_scope(|world, scene_spawner: Mut<SceneSpawner>| {
+        for (root, instance_id) in roots {
+            let entities: Vec<_> = scene_spawner.iter_instance_entities(instance_id).collect();
+            apply_scene_parameters(world, root, entities);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_asset::{AssetPlugin, Assets};
+    use bevy_ecs::{component::Component, reflect::ReflectComponent, world::World};
+    use bevy_reflect::Reflect;
+
+    use crate::{Scene, ScenePlugin, SceneRoot};
+
+    use super::{SceneParameterBindings, SceneParameters};
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Door {
+        key_id: u32,
+    }
+
+    #[test]
+    fn apply_scene_parameters() {
+        let mut app = App::new();
+        app.add_plugins((AssetPlugin::default(), ScenePlugin))
+            .register_type::<Door>()
+            .register_type::<SceneParameterBindings>();
+
+        let mut door_world = World::new();
+        door_world.spawn((
+            Door { key_id: 0 },
+            SceneParameterBindings::default().with_field::<Door>("key_id", "key_id"),
+        ));
+        let door = app
+            .world_mut()
+            .resource_mut::<Assets<Scene>>()
+            .add(Scene::new(door_world));
+
+        // A house with a door, whose key is a parameter of the house.
+        let mut house_world = World::new();
+        house_world.spawn((
+            SceneRoot(door.clone()),
+            SceneParameterBindings::default().with_nested("front_key_id", "key_id"),
+        ));
+        let house = app
+            .world_mut()
+            .resource_mut::<Assets<Scene>>()
+            .add(Scene::new(house_world));
+
+        let root = app
+            .world_mut()
+            .spawn((
+                SceneRoot(door),
+                SceneParameters::default().with("key_id", 3u32),
+            ))
+            .id();
+        app.world_mut().spawn((
+            SceneRoot(house),
+            SceneParameters::default().with("front_key_id", 5u32),
+        ));
+        for _ in 0..3 {
+            app.update();
+        }
+
+        let key_ids = |app: &mut App| {
+            let mut key_ids: Vec<_> = app
+                .world_mut()
+                .query::<&Door>()
+                .iter(app.world())
+                .map(|door| door.key_id)
+                .collect();
+            key_ids.sort();
+            key_ids
+        };
+        assert_eq!(key_ids(&mut app), [3, 5]);
+
+        // The parameters are applied again when they change.
+        app.world_mut()
+            .get_mut::<SceneParameters>(root)
+            .unwrap()
+            .set("key_id", 7u32);
+        app.update();
+        assert_eq!(key_ids(&mut app), [5, 7]);
+    }
+}
@@ -0,0 +1,458 @@
+use core::any::TypeId;
+
+use bevy_asset::{AssetId, Assets};
+use bevy_ecs::{
+    component::{Component, ComponentCloneBehavior},
+    entity::{Entity, EntityHashMap, EntityHashSet, SceneEntityMapper},
+    entity_disabling::DefaultQueryFilters,
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities, ReflectResource},
+    relationship::RelationshipHookMode,
+    world::World,
+};
+use bevy_reflect::{
+    std_traits::ReflectDefault, PartialReflect, Reflect, TypeRegistration, TypeRegistry,
+};
+
+use crate::{
+    reflect_utils::clone_reflect_value, DynamicEntity, DynamicScene, ReflectMapSceneEntities,
+    Scene, SceneSpawnError,
+};
+
+/// How a scene instance is updated when its scene is hot reloaded.
+///
+/// Adding this component next to a [`SceneRoot`](crate::SceneRoot) or a
+/// [`DynamicSceneRoot`](crate::DynamicSceneRoot) picks the mode of its instance. It must be added
+/// before the instance is spawned for [`SceneHotReload::Reconcile`] to take effect.
+#[derive(Component, Reflect, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub enum SceneHotReload {
+    /// Despawns the entities of the instance and spawns the new scene, which loses the changes
+    /// made to the entities at runtime.
+    #[default]
+    Respawn,
+    /// Only applies the changes made to the scene asset, keeping the changes made at runtime.
+    ///
+    /// The scene is compared with its previous version: the entities and components added to the
+    /// scene are spawned and inserted, the ones removed from the scene are despawned and removed,
+    /// and the components and resources whose value changed in the scene are updated, unless their
+    /// value was changed at runtime too. The values are compared with
+    /// [`PartialReflect::reflect_partial_eq`], so the types without an implementation are always
+    /// considered changed at runtime.
+    ///
+    /// The entities of a [`Scene`] are matched by their order in the world of the scene, and the
+    /// entities of a [`DynamicScene`] by their id in the scene.
+    Reconcile,
+}
+
+/// Returns `true` if the scene instance spawned under `parent` is reconciled on hot reload.
+pub(crate) fn reconciles_on_reload(world: &World, parent: Option<Entity>) -> bool {
+    parent
+        .and_then(|parent| world.get::<SceneHotReload>(parent))
+        .is_some_and(|mode| *mode == SceneHotReload::Reconcile)
+}
+
+/// Copies the values of the [`Scene`] `id`, to be compared with the next version of the scene.
+pub(crate) fn snapshot_scene(world: &World, id: AssetId<Scene>) -> Option<DynamicScene> {
+    let scene = world.resource::<Assets<Scene>>().get(id)?;
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let registry = &*registry;
+    let dqf_id = scene
+        .world
+        .components()
+        .get_resource_id(TypeId::of::<DefaultQueryFilters>());
+
+    let resources = scene
+        .world
+        .storages()
+        .resources
+        .iter()
+        .filter(|(component_id, data)| Some(*component_id) != dqf_id && data.is_present())
+        .filter_map(|(component_id, _)| {
+            let type_id = scene.world.components().get_info(component_id)?.type_id()?;
+            let registration = registry.get(type_id)?;
+            let resource = registration
+                .data::<ReflectResource>()?
+                .reflect(&scene.world)
+                .ok()?;
+            Some(clone_reflect_value(
+                resource.as_partial_reflect(),
+                registration,
+            ))
+        })
+        .collect();
+
+    let entities = scene
+        .world
+        .archetypes()
+        .iter()
+        .flat_map(|archetype| {
+            archetype.entities().iter().map(move |scene_entity| {
+                let entity = scene.world.entity(scene_entity.id());
+                let components = archetype
+                    .iter_components()
+                    .filter_map(|component_id| {
+                        let info = scene.world.components().get_info(component_id)?;
+                        if matches!(*info.clone_behavior(), ComponentCloneBehavior::Ignore) {
+                            return None;
+                        }
+                        let registration = registry.get(info.type_id()?)?;
+                        let component = registration.data::<ReflectComponent>()?.reflect(entity)?;
+                        Some(clone_reflect_value(
+                            component.as_partial_reflect(),
+                            registration,
+                        ))
+                    })
+                    .collect();
+                DynamicEntity {
+                    entity: scene_entity.id(),
+                    components,
+                }
+            })
+        })
+        .collect();
+
+    Some(DynamicScene {
+        resources,
+        entities,
+    })
+}
+
+/// Copies the values of the [`DynamicScene`] `id`, to be compared with the next version of the
+/// scene.
+pub(crate) fn snapshot_dynamic_scene(
+    world: &World,
+    id: AssetId<DynamicScene>,
+) -> Option<DynamicScene> {
+    let scene = world.resource::<Assets<DynamicScene>>().get(id)?;
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let clone = |value: &dyn PartialReflect| match value
+        .get_represented_type_info()
+        .and_then(|type_info| registry.get(type_info.type_id()))
+    {
+        Some(registration) => clone_reflect_value(value, registration),
+        None => value.to_dynamic(),
+    };
+    Some(DynamicScene {
+        resources: scene
+            .resources
+            .iter()
+            .map(AsRef::as_ref)
+            .map(clone)
+            .collect(),
+        entities: scene
+            .entities
+            .iter()
+            .map(|entity| DynamicEntity {
+                entity: entity.entity,
+                components: entity
+                    .components
+                    .iter()
+                    .map(AsRef::as_ref)
+                    .map(clone)
+                    .collect(),
+            })
+            .collect(),
+    })
+}
+
+/// Updates the entities of a scene instance from the `old` version of its scene to the `new` one,
+/// keeping the changes made at runtime.
+///
+/// See [`SceneHotReload::Reconcile`].
+pub(crate) fn reconcile_instance(
+    world: &mut World,
+    old: &DynamicScene,
+    new: &DynamicScene,
+    entity_map: &mut EntityHashMap<Entity>,
+) -> Result<(), SceneSpawnError> {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+
+    // Despawn the entities removed from the scene.
+    let new_entities: EntityHashSet = new.entities.iter().map(|entity| entity.entity).collect();
+    for old_entity in &old.entities {
+        if !new_entities.contains(&old_entity.entity)
+            && let Some(entity) = entity_map.remove(&old_entity.entity)
+        {
+            // The entity may have been despawned at runtime, or along with its parent.
+            let _ = world.try_despawn(entity);
+        }
+    }
+
+    // Spawn the entities added to the scene before updating the components, which may reference
+    // them.
+    for new_entity in &new.entities {
+        entity_map
+            .entry(new_entity.entity)
+            .or_insert_with(|| world.spawn_empty().id());
+    }
+
+    for new_entity in &new.entities {
+        let entity = entity_map[&new_entity.entity];
+        if world.get_entity(entity).is_err() {
+            // The entity was despawned at runtime.
+            continue;
+        }
+        let old_components = old
+            .entities
+            .iter()
+            .find(|old_entity| old_entity.entity == new_entity.entity)
+            .map(|old_entity| old_entity.components.as_slice())
+            .unwrap_or_default();
+
+        for component in &new_entity.components {
+            let registration = registration(&registry, component.as_ref())?;
+            let reflect_component = registration.data::<ReflectComponent>().ok_or_else(|| {
+                SceneSpawnError::UnregisteredComponent {
+                    type_path: registration.type_info().type_path().to_string(),
+                }
+            })?;
+
+            if let Some(old_component) = find_value(old_components, registration.type_id()) {
+                let live = reflect_component.reflect(world.entity(entity));
+                if !is_authored_change(
+                    old_component,
+                    component.as_ref(),
+                    live,
+                    registration,
+                    entity_map,
+                ) {
+                    continue;
+                }
+            }
+
+            let mut component = clone_reflect_value(component.as_partial_reflect(), registration);
+            if let Some(map_scene_entities) = registration.data::<ReflectMapSceneEntities>() {
+                SceneEntityMapper::world_scope(entity_map, world, |_, mapper| {
+                    map_scene_entities.map_entities(component.as_partial_reflect_mut(), mapper);
+                });
+            }
+            SceneEntityMapper::world_scope(entity_map, world, |world, mapper| {
+                reflect_component.apply_or_insert_mapped(
+                    &mut world.entity_mut(entity),
+                    component.as_partial_reflect(),
+                    &registry,
+                    mapper,
+                    RelationshipHookMode::Skip,
+                );
+            });
+        }
+
+        // Remove the components removed from the scene.
+        for old_component in old_components {
+            let registration = registration(&registry, old_component.as_ref())?;
+            if find_value(&new_entity.components, registration.type_id()).is_none()
+                && let Some(reflect_component) = registration.data::<ReflectComponent>()
+            {
+                reflect_component.remove(&mut world.entity_mut(entity));
+            }
+        }
+    }
+
+    for resource in &new.resources {
+        let registration = registration(&registry, resource.as_ref())?;
+        let reflect_resource = registration.data::<ReflectResource>().ok_or_else(|| {
+            SceneSpawnError::UnregisteredResource {
+                type_path: registration.type_info().type_path().to_string(),
+            }
+        })?;
+
+        if let Some(old_resource) = find_value(&old.resources, registration.type_id()) {
+            let live = reflect_resource.reflect(&*world).ok();
+            if !is_authored_change(
+                old_resource,
+                resource.as_ref(),
+                live,
+                registration,
+                entity_map,
+            ) {
+                continue;
+            }
+        }
+
+        let mut resource = clone_reflect_value(resource.as_partial_reflect(), registration);
+        if let Some(map_entities) = registration.data::<ReflectMapEntities>() {
+            map_entities.map_entities(resource.as_partial_reflect_mut(), entity_map);
+        } else if let Some(map_scene_entities) = registration.data::<ReflectMapSceneEntities>() {
+            map_scene_entities.map_entities(resource.as_partial_reflect_mut(), entity_map);
+        }
+        reflect_resource.apply_or_insert(world, resource.as_partial_reflect(), &registry);
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if the value of a component or resource changed from `old` to `new` in the
+/// scene, and its `live` value in the world wasn't changed at runtime.
+fn is_authored_change(
+    old: &dyn PartialReflect,
+    new: &dyn PartialReflect,
+    live: Option<&dyn Reflect>,
+    registration: &TypeRegistration,
+    entity_map: &mut EntityHashMap<Entity>,
+) -> bool {
+    if old.reflect_partial_eq(new).unwrap_or(false) {
+        return false;
+    }
+    // The value was removed at runtime.
+    let Some(live) = live else {
+        return false;
+    };
+    // The live value references the entities of the instance rather than those of the scene.
+    let mut old = clone_reflect_value(old, registration);
+    if let Some(map_entities) = registration.data::<ReflectMapEntities>() {
+        map_entities.map_entities(old.as_partial_reflect_mut(), entity_map);
+    }
+    if let Some(map_scene_entities) = registration.data::<ReflectMapSceneEntities>() {
+        map_scene_entities.map_entities(old.as_partial_reflect_mut(), entity_map);
+    }
+    live.reflect_partial_eq(old.as_partial_reflect())
+        .unwrap_or(false)
+}
+
+/// Returns the value of the type `type_id` in `values`, if any.
+fn find_value(values: &[Box<dyn PartialReflect>], type_id: TypeId) -> Option<&dyn PartialReflect> {
+    values
+        .iter()
+        .find(|value| {
+            value
+                .get_represented_type_info()
+                .is_some_and(|type_info| type_info.type_id() == type_id)
+        })
+        .map(AsRef::as_ref)
+}
+
+/// Returns the registration of the type of `value`.
+fn registration<'a>(
+    registry: &'a TypeRegistry,
+    value: &dyn PartialReflect,
+) -> Result<&'a TypeRegistration, SceneSpawnError> {
+    let type_info =
+        value
+            .get_represented_type_info()
+            .ok_or_else(|| SceneSpawnError::NoRepresentedType {
+                type_path: value.reflect_type_path().to_string(),
+            })?;
+    registry
+        .get(type_info.type_id())
+        .ok_or_else(|| SceneSpawnError::UnregisteredButReflectedType {
+            type_path: type_info.type_path().to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_asset::{AssetPlugin, Assets};
+    use bevy_ecs::{component::Component, entity::Entity, reflect::ReflectComponent, world::World};
+    use bevy_reflect::Reflect;
+
+    use crate::{Scene, SceneHotReload, ScenePlugin, SceneRoot};
+
+    #[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+    #[reflect(Component, PartialEq)]
+    struct Lamp {
+        intensity: f32,
+        color: u32,
+    }
+
+    #[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+    #[reflect(Component, PartialEq)]
+    struct Broken;
+
+    fn lamps(app: &mut App) -> Vec<Lamp> {
+        let mut lamps: Vec<_> = app
+            .world_mut()
+            .query::<&Lamp>()
+            .iter(app.world())
+            .copied()
+            .collect();
+        lamps.sort_by_key(|lamp| lamp.color);
+        lamps
+    }
+
+    #[test]
+    fn reconcile_on_hot_reload() {
+        let mut app = App::new();
+        app.add_plugins((AssetPlugin::default(), ScenePlugin))
+            .register_type::<Lamp>()
+            .register_type::<Broken>();
+
+        let mut scene_world = World::new();
+        scene_world.spawn(Lamp {
+            intensity: 1.0,
+            color: 1,
+        });
+        scene_world.spawn(Lamp {
+            intensity: 1.0,
+            color: 2,
+        });
+        let scene = app
+            .world_mut()
+            .resource_mut::<Assets<Scene>>()
+            .add(Scene::new(scene_world));
+        app.world_mut()
+            .spawn((SceneRoot(scene.clone()), SceneHotReload::Reconcile));
+        // Let the scene asset events settle.
+        for _ in 0..4 {
+            app.update();
+        }
+
+        // The first lamp is changed at runtime, and broken.
+        let first_lamp = app
+            .world_mut()
+            .query::<(Entity, &Lamp)>()
+            .iter(app.world())
+            .find(|(_, lamp)| lamp.color == 1)
+            .unwrap()
+            .0;
+        app.world_mut().entity_mut(first_lamp).insert((
+            Lamp {
+                intensity: 0.5,
+                color: 1,
+            },
+            Broken,
+        ));
+
+        // Both lamps are made brighter in the scene, and a third one is added.
+        let mut scene_world = World::new();
+        scene_world.spawn(Lamp {
+            intensity: 2.0,
+            color: 1,
+        });
+        scene_world.spawn(Lamp {
+            intensity: 2.0,
+            color: 2,
+        });
+        scene_world.spawn(Lamp {
+            intensity: 2.0,
+            color: 3,
+        });
+        app.world_mut()
+            .resource_mut::<Assets<Scene>>()
+            .insert(&scene, Scene::new(scene_world))
+            .unwrap();
+        // The asset event is sent at the end of the frame.
+        app.update();
+        app.update();
+
+        assert_eq!(
+            lamps(&mut app),
+            [
+                Lamp {
+                    intensity: 0.5,
+                    color: 1
+                },
+                Lamp {
+                    intensity: 2.0,
+                    color: 2
+                },
+                Lamp {
+                    intensity: 2.0,
+                    color: 3
+                },
+            ]
+        );
+        assert!(app.world().get::<Broken>(first_lamp).is_some());
+    }
+}
@@ -15,7 +15,14 @@ use bevy_utils::prelude::DebugName;
 use thiserror::Error;
 use uuid::Uuid;
 
-use crate::{DynamicSceneRoot, SceneRoot};
+use crate::{
+    scene_overrides::apply_scene_overrides,
+    scene_parameters::apply_scene_parameters,
+    scene_reconcile::{
+        reconcile_instance, reconciles_on_reload, snapshot_dynamic_scene, snapshot_scene,
+    },
+    DynamicSceneRoot, SceneRoot,
+};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     change_detection::ResMut,
@@ -103,6 +110,11 @@ pub struct SceneSpawner {
     dynamic_scenes_to_despawn: Vec<AssetId<DynamicScene>>,
     instances_to_despawn: Vec<InstanceId>,
     instances_ready: Vec<(InstanceId, Option<Entity>)>,
+    /// The previous version of the scenes with instances reconciled on hot reload.
+    ///
+    /// See [`SceneHotReload::Reconcile`](crate::SceneHotReload::Reconcile).
+    scene_snapshots: HashMap<AssetId<Scene>, DynamicScene>,
+    dynamic_scene_snapshots: HashMap<AssetId<DynamicScene>, DynamicScene>,
 }
 
 /// Errors that can occur when spawning a scene.
@@ -357,20 +369,34 @@ impl SceneSpawner {
     ) -> Result<(), SceneSpawnError> {
         for id in scene_ids {
             if let Some(spawned_instances) = self.spawned_scenes.get(id) {
+                let old_snapshot = self.scene_snapshots.remove(id);
+                let new_snapshot = old_snapshot
+                    .as_ref()
+                    .and_then(|_| snapshot_scene(world, *id));
                 for instance_id in spawned_instances {
                     if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
-                        // Despawn the scene before respawning it. This is a very heavy operation,
-                        // but otherwise, entities may be left behind, or be left in an otherwise
-                        // invalid state (e.g., invalid relationships).
-                        Self::despawn_instance_internal(world, instance_info);
-                        Self::spawn_sync_internal(world, *id, &mut instance_info.entity_map)?;
+                        if let (Some(old), Some(new)) = (&old_snapshot, &new_snapshot)
+                            && reconciles_on_reload(world, instance_info.parent)
+                        {
+                            reconcile_instance(world, old, new, &mut instance_info.entity_map)?;
+                        } else {
+                            // Despawn the scene before respawning it. This is a very heavy
+                            // operation, but otherwise, entities may be left behind, or be left in
+                            // an otherwise invalid state (e.g., invalid relationships).
+                            Self::despawn_instance_internal(world, instance_info);
+                            Self::spawn_sync_internal(world, *id, &mut instance_info.entity_map)?;
+                        }
                         Self::set_scene_instance_parent_sync(world, instance_info);
+                        Self::apply_parameters_and_overrides_sync(world, instance_info);
                         // We trigger `SceneInstanceReady` events after processing all scenes
                         // SceneSpawner may not be available in the observer.
                         self.instances_ready
                             .push((*instance_id, instance_info.parent));
                     }
                 }
+                if let Some(new_snapshot) = new_snapshot {
+                    self.scene_snapshots.insert(*id, new_snapshot);
+                }
             }
         }
         Ok(())
@@ -387,20 +413,38 @@ impl SceneSpawner {
     ) -> Result<(), SceneSpawnError> {
         for id in scene_ids {
             if let Some(spawned_instances) = self.spawned_dynamic_scenes.get(id) {
+                let old_snapshot = self.dynamic_scene_snapshots.remove(id);
+                let new_snapshot = old_snapshot
+                    .as_ref()
+                    .and_then(|_| snapshot_dynamic_scene(world, *id));
                 for instance_id in spawned_instances {
                     if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
-                        // Despawn the scene before respawning it. This is a very heavy operation,
-                        // but otherwise, entities may be left behind, or be left in an otherwise
-                        // invalid state (e.g., invalid relationships).
-                        Self::despawn_instance_internal(world, instance_info);
-                        Self::spawn_dynamic_internal(world, *id, &mut instance_info.entity_map)?;
+                        if let (Some(old), Some(new)) = (&old_snapshot, &new_snapshot)
+                            && reconciles_on_reload(world, instance_info.parent)
+                        {
+                            reconcile_instance(world, old, new, &mut instance_info.entity_map)?;
+                        } else {
+                            // Despawn the scene before respawning it. This is a very heavy
+                            // operation, but otherwise, entities may be left behind, or be left in
+                            // an otherwise invalid state (e.g., invalid relationships).
+                            Self::despawn_instance_internal(world, instance_info);
+                            Self::spawn_dynamic_internal(
+                                world,
+                                *id,
+                                &mut instance_info.entity_map,
+                            )?;
+                        }
                         Self::set_scene_instance_parent_sync(world, instance_info);
+                        Self::apply_parameters_and_overrides_sync(world, instance_info);
                         // We trigger `SceneInstanceReady` events after processing all scenes
                         // SceneSpawner may not be available in the observer.
                         self.instances_ready
                             .push((*instance_id, instance_info.parent));
                     }
                 }
+                if let Some(new_snapshot) = new_snapshot {
+                    self.dynamic_scene_snapshots.insert(*id, new_snapshot);
+                }
             }
         }
         Ok(())
@@ -439,6 +483,14 @@ impl SceneSpawner {
                 Ok(_) => {
                     let instance_info = InstanceInfo { entity_map, parent };
                     Self::set_scene_instance_parent_sync(world, &instance_info);
+                    Self::apply_parameters_and_overrides_sync(world, &instance_info);
+
+                    if reconciles_on_reload(world, parent)
+                        && !self.dynamic_scene_snapshots.contains_key(&handle.id())
+                        && let Some(snapshot) = snapshot_dynamic_scene(world, handle.id())
+                    {
+                        self.dynamic_scene_snapshots.insert(handle.id(), snapshot);
+                    }
 
                     self.spawned_instances.insert(instance_id, instance_info);
                     let spawned = self.spawned_dynamic_scenes.entry(handle.id()).or_default();
@@ -464,6 +516,14 @@ impl SceneSpawner {
                 Ok(_) => {
                     let instance_info = InstanceInfo { entity_map, parent };
                     Self::set_scene_instance_parent_sync(world, &instance_info);
+                    Self::apply_parameters_and_overrides_sync(world, &instance_info);
+
+                    if reconciles_on_reload(world, parent)
+                        && !self.scene_snapshots.contains_key(&scene_handle.id())
+                        && let Some(snapshot) = snapshot_scene(world, scene_handle.id())
+                    {
+                        self.scene_snapshots.insert(scene_handle.id(), snapshot);
+                    }
 
                     self.spawned_instances.insert(instance_id, instance_info);
                     let spawned = self.spawned_scenes.entry(scene_handle.id()).or_default();
@@ -505,6 +565,13 @@ impl SceneSpawner {
         }
     }
 
+    fn apply_parameters_and_overrides_sync(world: &mut World, instance: &InstanceInfo) {
+        if let Some(parent) = instance.parent {
+            apply_scene_parameters(world, parent, instance.entity_map.values().copied());
+            apply_scene_overrides(world, parent, instance.entity_map.values().copied());
+        }
+    }
+
     fn trigger_scene_ready_events(&mut self, world: &mut World) {
         for (instance_id, parent) in self.instances_ready.drain(..) {
             if let Some(parent) = parent {
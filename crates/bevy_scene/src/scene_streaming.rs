@@ -0,0 +1,537 @@
+use bevy_asset::{AssetPath, AssetServer, Handle};
+use bevy_camera::visibility::Visibility;
+use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    component::Component,
+    entity::Entity,
+    event::EntityEvent,
+    hierarchy::ChildOf,
+    observer::On,
+    query::With,
+    reflect::{ReflectComponent, ReflectResource},
+    resource::Resource,
+    system::{Commands, Query, Res},
+};
+use bevy_math::{IRect, IVec2, Vec3, Vec3Swizzles};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_transform::components::{GlobalTransform, Transform};
+use tracing::warn;
+
+use crate::{DynamicScene, DynamicSceneRoot, SceneInstanceReady};
+
+/// The settings of the streaming of the [`SceneStreamingCell`]s.
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource, Default, Debug, PartialEq, Clone)]
+pub struct SceneStreaming {
+    /// The distance from a [`SceneStreamingSource`] under which the cells are loaded.
+    pub load_distance: f32,
+    /// The distance from every [`SceneStreamingSource`] over which the cells are unloaded.
+    ///
+    /// It should be greater than [`SceneStreaming::load_distance`], so that the cells on the edge
+    /// aren't loaded and unloaded over and over as the sources move around.
+    pub unload_distance: f32,
+    /// The maximum number of cells loading at the same time.
+    ///
+    /// The closest cells are loaded first.
+    pub max_loading_cells: usize,
+}
+
+impl Default for SceneStreaming {
+    fn default() -> Self {
+        Self {
+            load_distance: 100.0,
+            unload_distance: 120.0,
+            max_loading_cells: 4,
+        }
+    }
+}
+
+/// Marks an entity around which the [`SceneStreamingCell`]s are loaded, usually the camera.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_scene::SceneStreamingSource;
+/// # use bevy_transform::components::Transform;
+/// fn spawn_camera(mut commands: Commands) {
+///     commands.spawn((Transform::default(), SceneStreamingSource));
+/// }
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+#[require(Transform)]
+pub struct SceneStreamingSource;
+
+/// A region of the world whose [`DynamicScene`] is loaded and spawned when a
+/// [`SceneStreamingSource`] comes near, and despawned when they all go away.
+///
+/// The region is an axis-aligned box centered on the [`GlobalTransform`] of the entity, ignoring
+/// its rotation and scale. The scene is spawned in the background, as a child of the entity, and
+/// [`SceneCellLoaded`] is triggered on the entity once it's spawned. The distances and the number
+/// of cells loaded at once are set by the [`SceneStreaming`] resource.
+///
+/// Cells can be placed by hand, including in a scene, or around the sources by a
+/// [`SceneStreamingGrid`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::Vec3;
+/// # use bevy_scene::SceneStreamingCell;
+/// # use bevy_transform::components::Transform;
+/// fn spawn_cells(mut commands: Commands) {
+///     commands.spawn((
+///         SceneStreamingCell::new("world/village.scn.bin", Vec3::new(50.0, 20.0, 50.0)),
+///         Transform::from_xyz(200.0, 0.0, -80.0),
+///     ));
+/// }
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Default)]
+#[reflect(Component, Default, Debug, Clone)]
+#[require(Transform, Visibility)]
+pub struct SceneStreamingCell {
+    /// The path of the scene of the cell.
+    pub scene: AssetPath<'static>,
+    /// The half size of the region of the cell.
+    pub half_size: Vec3,
+    #[reflect(ignore, clone)]
+    streaming: CellStreaming,
+}
+
+/// The streaming state of a [`SceneStreamingCell`].
+#[derive(Debug, Clone, Default)]
+struct CellStreaming {
+    state: SceneStreamingCellState,
+    /// The scene while it's loading or loaded.
+    handle: Handle<DynamicScene>,
+    /// The child entity the scene is spawned on.
+    instance: Option<Entity>,
+}
+
+/// Whether the scene of a [`SceneStreamingCell`] is loaded.
+#[derive(Reflect, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[reflect(Default, Debug, PartialEq, Clone)]
+pub enum SceneStreamingCellState {
+    /// The scene isn't loaded.
+    #[default]
+    Unloaded,
+    /// The scene is being loaded and spawned.
+    Loading,
+    /// The scene is spawned.
+    Loaded,
+    /// The scene failed to load. It's loaded again once the cell is out of range and back.
+    Failed,
+}
+
+impl SceneStreamingCell {
+    /// Makes a cell streaming the scene at `scene`, over a region of the given half size.
+    pub fn new(scene: impl Into<AssetPath<'static>>, half_size: Vec3) -> Self {
+        Self {
+            scene: scene.into(),
+            half_size,
+            streaming: CellStreaming::default(),
+        }
+    }
+
+    /// Returns whether the scene of the cell is loaded.
+    pub fn state(&self) -> SceneStreamingCellState {
+        self.streaming.state
+    }
+}
+
+/// Returns the distance between `point` and the axis-aligned box of the given center and half size,
+/// or zero if `point` is inside.
+fn distance_to_box(center: Vec3, half_size: Vec3, point: Vec3) -> f32 {
+    ((point - center).abs() - half_size)
+        .max(Vec3::ZERO)
+        .length()
+}
+
+/// Triggered on a [`SceneStreamingCell`] when its scene is spawned.
+#[derive(EntityEvent, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Debug, PartialEq, Clone)]
+pub struct SceneCellLoaded {
+    /// The cell whose scene is spawned.
+    pub entity: Entity,
+}
+
+/// Triggered on a [`SceneStreamingCell`] when its scene is despawned because it went out of range.
+#[derive(EntityEvent, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Debug, PartialEq, Clone)]
+pub struct SceneCellUnloaded {
+    /// The cell whose scene is despawned.
+    pub entity: Entity,
+}
+
+/// A grid of [`SceneStreamingCell`]s on the XZ plane, spawned as children of the entity around the
+/// [`SceneStreamingSource`]s and despawned once they are unloaded.
+///
+/// The scene of each cell is found by replacing `{x}` and `{z}` by the coordinates of the cell in
+/// [`SceneStreamingGrid::scenes`], so that the cell `(2, -1)` of `world/{x}_{z}.scn.bin` is
+/// `world/2_-1.scn.bin`. The cell `(0, 0)` spans from the translation of the entity to `cell_size`
+/// along the X and Z axes, and the cells span all heights. The grid is axis-aligned, so the entity
+/// should not be rotated or scaled.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::IRect;
+/// # use bevy_scene::SceneStreamingGrid;
+/// fn spawn_world(mut commands: Commands) {
+///     commands.spawn(SceneStreamingGrid::new(
+///         "world/{x}_{z}.scn.bin",
+///         64.0,
+///         IRect::new(-16, -16, 15, 15),
+///     ));
+/// }
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Default)]
+#[reflect(Component, Default, Debug, Clone)]
+#[require(Transform, Visibility)]
+pub struct SceneStreamingGrid {
+    /// The path of the scenes of the cells, with `{x}` and `{z}` replaced by the coordinates of
+    /// each cell.
+    pub scenes: String,
+    /// The size of the cells along the X and Z axes.
+    pub cell_size: f32,
+    /// The coordinates of the cells that have a scene, `max` included.
+    pub cells: IRect,
+    #[reflect(ignore, clone)]
+    spawned_cells: HashMap<IVec2, Entity>,
+}
+
+impl SceneStreamingGrid {
+    /// Makes a grid of cells of size `cell_size` streaming the scenes at `scenes`, for the cells
+    /// whose coordinates are in `cells`.
+    pub fn new(scenes: impl Into<String>, cell_size: f32, cells: IRect) -> Self {
+        Self {
+            scenes: scenes.into(),
+            cell_size,
+            cells,
+            spawned_cells: HashMap::default(),
+        }
+    }
+
+    /// Returns the path of the scene of the cell at `cell`.
+    pub fn cell_scene(&self, cell: IVec2) -> String {
+        self.scenes
+            .replace("{x}", &cell.x.to_string())
+            .replace("{z}", &cell.y.to_string())
+    }
+
+    /// Returns the cell entity spawned for the cell at `cell`, if any.
+    pub fn cell_entity(&self, cell: IVec2) -> Option<Entity> {
+        self.spawned_cells.get(&cell).copied()
+    }
+}
+
+/// System that loads the [`SceneStreamingCell`]s that came in range of a [`SceneStreamingSource`],
+/// closest first, and unloads the ones that went out of range.
+pub fn update_streaming_cells(
+    mut commands: Commands,
+    streaming: Res<SceneStreaming>,
+    asset_server: Res<AssetServer>,
+    sources: Query<&GlobalTransform, With<SceneStreamingSource>>,
+    mut cells: Query<(Entity, &mut SceneStreamingCell, &GlobalTransform)>,
+) {
+    let mut loading_cells = 0;
+    let mut candidates = Vec::new();
+    for (entity, mut cell, transform) in &mut cells {
+        let center = transform.translation();
+        let distance = sources
+            .iter()
+            .map(|source| distance_to_box(center, cell.half_size, source.translation()))
+            .min_by(f32::total_cmp)
+            .unwrap_or(f32::INFINITY);
+
+        let state = cell.streaming.state;
+        if distance > streaming.unload_distance {
+            match state {
+                SceneStreamingCellState::Loading => {
+                    unload_cell(&mut commands, &mut cell, SceneStreamingCellState::Unloaded);
+                }
+                SceneStreamingCellState::Loaded => {
+                    unload_cell(&mut commands, &mut cell, SceneStreamingCellState::Unloaded);
+                    commands.trigger(SceneCellUnloaded { entity });
+                }
+                SceneStreamingCellState::Failed => {
+                    cell.streaming.state = SceneStreamingCellState::Unloaded;
+                }
+                SceneStreamingCellState::Unloaded => {}
+            }
+        } else if state == SceneStreamingCellState::Loading {
+            if asset_server.load_state(&cell.streaming.handle).is_failed() {
+                warn!(
+                    "Failed to load the scene `{}` of the streaming cell {}.",
+                    cell.scene, entity
+                );
+                unload_cell(&mut commands, &mut cell, SceneStreamingCellState::Failed);
+            } else {
+                loading_cells += 1;
+            }
+        } else if state == SceneStreamingCellState::Unloaded && distance <= streaming.load_distance
+        {
+            candidates.push((distance, entity));
+        }
+    }
+
+    candidates.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+    let available = streaming.max_loading_cells.saturating_sub(loading_cells);
+    for (_, entity) in candidates.into_iter().take(available) {
+        let Ok((_, mut cell, _)) = cells.get_mut(entity) else {
+            continue;
+        };
+        let handle = asset_server.load(cell.scene.clone());
+        let instance = commands
+            .spawn((DynamicSceneRoot(handle.clone()), ChildOf(entity)))
+            .id();
+        cell.streaming = CellStreaming {
+            state: SceneStreamingCellState::Loading,
+            handle,
+            instance: Some(instance),
+        };
+    }
+}
+
+/// Despawns the scene of `cell`, if any, and sets its state.
+fn unload_cell(
+    commands: &mut Commands,
+    cell: &mut SceneStreamingCell,
+    state: SceneStreamingCellState,
+) {
+    if let Some(instance) = cell.streaming.instance {
+        commands.entity(instance).try_despawn();
+    }
+    cell.streaming = CellStreaming {
+        state,
+        ..Default::default()
+    };
+}
+
+/// Observer that marks the [`SceneStreamingCell`]s whose scene is spawned as loaded.
+pub(crate) fn on_streaming_cell_ready(
+    ready: On<SceneInstanceReady>,
+    mut commands: Commands,
+    parents: Query<&ChildOf>,
+    mut cells: Query<&mut SceneStreamingCell>,
+) {
+    let Ok(ChildOf(entity)) = parents.get(ready.entity) else {
+        return;
+    };
+    let Ok(mut cell) = cells.get_mut(*entity) else {
+        return;
+    };
+    if cell.streaming.instance == Some(ready.entity)
+        && cell.streaming.state == SceneStreamingCellState::Loading
+    {
+        cell.streaming.state = SceneStreamingCellState::Loaded;
+        commands.trigger(SceneCellLoaded { entity: *entity });
+    }
+}
+
+/// System that spawns the cells of the [`SceneStreamingGrid`]s that came in range of a
+/// [`SceneStreamingSource`], and despawns the ones that were unloaded.
+pub fn update_streaming_grids(
+    mut commands: Commands,
+    streaming: Res<SceneStreaming>,
+    sources: Query<&GlobalTransform, With<SceneStreamingSource>>,
+    mut grids: Query<(Entity, &mut SceneStreamingGrid, &GlobalTransform)>,
+    cells: Query<&SceneStreamingCell>,
+) {
+    for (entity, mut grid, transform) in &mut grids {
+        if grid.cell_size <= 0.0 {
+            continue;
+        }
+        let origin = transform.translation();
+        let cell_size = grid.cell_size;
+        let half_size = Vec3::new(cell_size / 2.0, f32::INFINITY, cell_size / 2.0);
+        let cell_center =
+            |cell: IVec2| Vec3::new(cell.x as f32 + 0.5, 0.0, cell.y as f32 + 0.5) * cell_size;
+        let cell_distance = |cell: IVec2, point: Vec3| {
+            distance_to_box(origin + cell_center(cell), half_size, point)
+        };
+
+        // The spawned cells are bookkeeping, which shouldn't count as a change of the grid.
+        let grid = grid.bypass_change_detection();
+
+        // Despawn the cells that went out of range, once their scene is unloaded.
+        grid.spawned_cells.retain(|&cell, &mut cell_entity| {
+            let Ok(streaming_cell) = cells.get(cell_entity) else {
+                return false;
+            };
+            let unloaded = matches!(
+                streaming_cell.state(),
+                SceneStreamingCellState::Unloaded | SceneStreamingCellState::Failed
+            );
+            if unloaded
+                && sources.iter().all(|source| {
+                    cell_distance(cell, source.translation()) > streaming.unload_distance
+                })
+            {
+                commands.entity(cell_entity).despawn();
+                return false;
+            }
+            true
+        });
+
+        // Spawn the cells in range.
+        for source in &sources {
+            let position = source.translation() - origin;
+            let min = ((position.xz() - streaming.load_distance) / cell_size)
+                .floor()
+                .as_ivec2()
+                .max(grid.cells.min);
+            let max = ((position.xz() + streaming.load_distance) / cell_size)
+                .floor()
+                .as_ivec2()
+                .min(grid.cells.max);
+            for z in min.y..=max.y {
+                for x in min.x..=max.x {
+                    let cell = IVec2::new(x, z);
+                    if grid.spawned_cells.contains_key(&cell)
+                        || cell_distance(cell, source.translation()) > streaming.load_distance
+                    {
+                        continue;
+                    }
+                    let cell_entity = commands
+                        .spawn((
+                            SceneStreamingCell::new(grid.cell_scene(cell), half_size),
+                            Transform::from_translation(cell_center(cell)),
+                            ChildOf(entity),
+                        ))
+                        .id();
+                    grid.spawned_cells.insert(cell, cell_entity);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::{App, TaskPoolPlugin};
+    use bevy_asset::AssetPlugin;
+    use bevy_math::{IRect, IVec2, Vec3};
+    use bevy_transform::{components::Transform, TransformPlugin};
+
+    use crate::ScenePlugin;
+
+    use super::{
+        SceneStreaming, SceneStreamingCell, SceneStreamingCellState, SceneStreamingGrid,
+        SceneStreamingSource,
+    };
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            TransformPlugin,
+            ScenePlugin,
+        ))
+        .insert_resource(SceneStreaming {
+            load_distance: 6.0,
+            unload_distance: 20.0,
+            max_loading_cells: 1,
+        });
+        app
+    }
+
+    #[test]
+    fn load_closest_cells_first() {
+        let mut app = app();
+        let source = app
+            .world_mut()
+            .spawn((SceneStreamingSource, Transform::from_xyz(0.0, 0.0, 0.0)))
+            .id();
+        let far = app
+            .world_mut()
+            .spawn((
+                SceneStreamingCell::new("far.scn.ron", Vec3::ONE),
+                Transform::from_xyz(6.0, 0.0, 0.0),
+            ))
+            .id();
+        let near = app
+            .world_mut()
+            .spawn((
+                SceneStreamingCell::new("near.scn.ron", Vec3::ONE),
+                Transform::from_xyz(3.0, 0.0, 0.0),
+            ))
+            .id();
+        let out_of_range = app
+            .world_mut()
+            .spawn((
+                SceneStreamingCell::new("out_of_range.scn.ron", Vec3::ONE),
+                Transform::from_xyz(0.0, 0.0, 12.0),
+            ))
+            .id();
+        app.update();
+
+        let state = |app: &App, cell| app.world().get::<SceneStreamingCell>(cell).unwrap().state();
+        assert_eq!(state(&app, near), SceneStreamingCellState::Loading);
+        assert_eq!(state(&app, far), SceneStreamingCellState::Unloaded);
+        assert_eq!(state(&app, out_of_range), SceneStreamingCellState::Unloaded);
+
+        // The cells are unloaded once every source is out of range.
+        app.world_mut()
+            .entity_mut(source)
+            .insert(Transform::from_xyz(0.0, 0.0, 100.0));
+        app.update();
+        assert_eq!(state(&app, near), SceneStreamingCellState::Unloaded);
+    }
+
+    #[test]
+    fn spawn_grid_cells_around_sources() {
+        let mut app = app();
+        let source = app
+            .world_mut()
+            .spawn((SceneStreamingSource, Transform::from_xyz(5.0, 50.0, 5.0)))
+            .id();
+        let grid = app
+            .world_mut()
+            .spawn(SceneStreamingGrid::new(
+                "cells/{x}_{z}.scn.ron",
+                10.0,
+                IRect::new(-1, -1, 0, 1),
+            ))
+            .id();
+        app.update();
+
+        let grid_cells = |app: &App| {
+            let grid = app.world().get::<SceneStreamingGrid>(grid).unwrap();
+            let mut cells: Vec<_> = grid.spawned_cells.keys().copied().collect();
+            cells.sort_by_key(|cell| (cell.y, cell.x));
+            cells
+        };
+        // The corners are out of range, and there are no cells outside of the bounds of the grid.
+        assert_eq!(
+            grid_cells(&app),
+            [
+                IVec2::new(0, -1),
+                IVec2::new(-1, 0),
+                IVec2::new(0, 0),
+                IVec2::new(0, 1),
+            ]
+        );
+        let cell = app
+            .world()
+            .get::<SceneStreamingGrid>(grid)
+            .unwrap()
+            .cell_entity(IVec2::new(-1, 0))
+            .unwrap();
+        assert_eq!(
+            app.world()
+                .get::<SceneStreamingCell>(cell)
+                .unwrap()
+                .scene
+                .to_string(),
+            "cells/-1_0.scn.ron"
+        );
+
+        // The cells out of range are despawned once they are unloaded.
+        app.world_mut()
+            .entity_mut(source)
+            .insert(Transform::from_xyz(1000.0, 0.0, 0.0));
+        app.update();
+        assert!(grid_cells(&app).is_empty());
+        assert!(app.world().get_entity(cell).is_err());
+    }
+}
@@ -0,0 +1,440 @@
+use core::{any::TypeId, fmt};
+
+use bevy_asset::AssetPath;
+use bevy_ecs::{entity::Entity, reflect::ReflectComponent, reflect::ReflectResource, world::World};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{PartialReflect, ReflectRef, TypeRegistration, TypeRegistry};
+use thiserror::Error;
+
+use crate::DynamicScene;
+
+/// Where a component or resource of a scene is found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SceneLocation {
+    /// The components of an entity of the scene.
+    Entity(Entity),
+    /// The resources of the scene.
+    Resources,
+}
+
+impl fmt::Display for SceneLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Entity(entity) => write!(f, "entity {entity}"),
+            Self::Resources => write!(f, "the resources"),
+        }
+    }
+}
+
+/// A problem found in a scene by [`DynamicScene::validate`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum SceneDiagnostic {
+    /// A value doesn't represent a type.
+    #[error("{location} holds the value `{type_path}`, which doesn't represent a type")]
+    NoRepresentedType {
+        /// Where the value is found.
+        location: SceneLocation,
+        /// The type path of the value.
+        type_path: String,
+    },
+    /// A value is of a type that isn't registered.
+    #[error("{location} holds the type `{type_path}`, which isn't registered")]
+    UnregisteredType {
+        /// Where the value is found.
+        location: SceneLocation,
+        /// The type path of the value.
+        type_path: String,
+    },
+    /// An entity holds a value of a type that isn't registered as a component, with
+    /// [`ReflectComponent`].
+    #[error("{location} holds the type `{type_path}`, which isn't registered as a component")]
+    UnregisteredComponent {
+        /// Where the value is found.
+        location: SceneLocation,
+        /// The type path of the value.
+        type_path: String,
+    },
+    /// The resources hold a value of a type that isn't registered as a resource, with
+    /// [`ReflectResource`].
+    #[error("{location} holds the type `{type_path}`, which isn't registered as a resource")]
+    UnregisteredResource {
+        /// Where the value is found.
+        location: SceneLocation,
+        /// The type path of the value.
+        type_path: String,
+    },
+    /// An entity doesn't hold a reflected component required by one of its components, so it'll be
+    /// spawned with the value built by the requirement.
+    #[error(
+        "{location} doesn't hold the component `{required}` required by `{type_path}`, which will be spawned with its required value"
+    )]
+    MissingRequiredComponent {
+        /// Where the requiring component is found.
+        location: SceneLocation,
+        /// The type path of the requiring component.
+        type_path: String,
+        /// The type path of the required component.
+        required: String,
+    },
+    /// A value references an asset that doesn't exist.
+    #[error("{location} holds `{type_path}`, which references the missing asset `{path}`")]
+    MissingAsset {
+        /// Where the value is found.
+        location: SceneLocation,
+        /// The type path of the value.
+        type_path: String,
+        /// The path of the missing asset.
+        path: AssetPath<'static>,
+    },
+}
+
+impl SceneDiagnostic {
+    /// Returns `true` if the diagnostic prevents the scene from being spawned as it was authored,
+    /// or `false` if it's only a warning.
+    pub fn is_error(&self) -> bool {
+        !matches!(self, Self::MissingRequiredComponent { .. })
+    }
+}
+
+/// An [`AssetPath`] referenced by a component or resource of a scene.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SceneAssetPath {
+    /// Where the referencing value is found.
+    pub location: SceneLocation,
+    /// The type path of the referencing value.
+    pub type_path: String,
+    /// The referenced path.
+    pub path: AssetPath<'static>,
+}
+
+impl SceneAssetPath {
+    /// Returns the diagnostic reporting that the referenced asset is missing.
+    pub fn into_missing(self) -> SceneDiagnostic {
+        SceneDiagnostic::MissingAsset {
+            location: self.location,
+            type_path: self.type_path,
+            path: self.path,
+        }
+    }
+}
+
+/// The result of [`DynamicScene::validate`].
+///
+/// The validation can't tell whether the assets referenced by the scene exist on its own, so their
+/// paths are listed in [`SceneValidation::asset_paths`] to be checked with
+/// [`SceneValidation::check_asset_paths`]. The [`SceneLoader`](crate::SceneLoader) checks them
+/// when it validates the scenes it loads.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SceneValidation {
+    /// The problems found in the scene.
+    pub diagnostics: Vec<SceneDiagnostic>,
+    /// The asset paths referenced by the scene, found with reflection.
+    pub asset_paths: Vec<SceneAssetPath>,
+}
+
+impl SceneValidation {
+    /// Returns `true` if no error was found in the scene, ignoring the warnings.
+    pub fn is_valid(&self) -> bool {
+        !self.diagnostics.iter().any(SceneDiagnostic::is_error)
+    }
+
+    /// Returns the errors found in the scene.
+    pub fn errors(&self) -> impl Iterator<Item = &SceneDiagnostic> {
+        self.diagnostics.iter().filter(|diagnostic| diagnostic.is_error())
+    }
+
+    /// Returns the warnings found in the scene.
+    pub fn warnings(&self) -> impl Iterator<Item = &SceneDiagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| !diagnostic.is_error())
+    }
+
+    /// Adds a [`SceneDiagnostic::MissingAsset`] for each referenced asset path that doesn't pass
+    /// `exists`.
+    pub fn check_asset_paths(&mut self, mut exists: impl FnMut(&AssetPath<'static>) -> bool) {
+        for asset_path in &self.asset_paths {
+            if !exists(&asset_path.path) {
+                self.diagnostics.push(asset_path.clone().into_missing());
+            }
+        }
+    }
+}
+
+impl fmt::Display for SceneValidation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, error) in self.errors().enumerate() {
+            if index > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl DynamicScene {
+    /// Checks this scene against `registry`, before it's spawned.
+    ///
+    /// The validation reports the values of unknown types, or of types that can't be spawned as
+    /// components or resources, and warns of the reflected components required by the components
+    /// of an entity that the entity doesn't hold. It also lists the asset paths referenced by the
+    /// scene, which can be checked with [`SceneValidation::check_asset_paths`].
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_scene::DynamicScene;
+    /// # let mut world = World::new();
+    /// # world.init_resource::<AppTypeRegistry>();
+    /// let scene = DynamicScene::from_world(&world);
+    /// let validation = scene.validate(&world.resource::<AppTypeRegistry>().read());
+    /// if !validation.is_valid() {
+    ///     panic!("the scene is invalid: {validation}");
+    /// }
+    /// ```
+    pub fn validate(&self, registry: &TypeRegistry) -> SceneValidation {
+        let mut validation = SceneValidation::default();
+        // The required components are only known once the components are registered in a world.
+        let mut world = World::new();
+        let mut required_components = HashMap::<TypeId, Vec<TypeId>>::default();
+
+        for entity in &self.entities {
+            let location = SceneLocation::Entity(entity.entity);
+            let mut components = Vec::with_capacity(entity.components.len());
+            for component in &entity.components {
+                let Some(registration) =
+                    validate_registration(component.as_ref(), location, registry, &mut validation)
+                else {
+                    continue;
+                };
+                let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+                    validation
+                        .diagnostics
+                        .push(SceneDiagnostic::UnregisteredComponent {
+                            location,
+                            type_path: registration.type_info().type_path().to_string(),
+                        });
+                    continue;
+                };
+                required_components
+                    .entry(registration.type_id())
+                    .or_insert_with(|| {
+                        let id = reflect_component.register_component(&mut world);
+                        let components = world.components();
+                        components
+                            .get_info(id)
+                            .into_iter()
+                            .flat_map(|info| info.required_components().iter_ids())
+                            .filter_map(|id| components.get_info(id)?.type_id())
+                            .collect()
+                    });
+                components.push(registration);
+                find_asset_paths(component.as_ref(), location, &mut validation);
+            }
+
+            for registration in &components {
+                for required in &required_components[&registration.type_id()] {
+                    let is_held = components
+                        .iter()
+                        .any(|component| component.type_id() == *required);
+                    // Only the reflected components could have been held by the entity.
+                    if let Some(required) = registry.get(*required)
+                        && !is_held
+                        && required.data::<ReflectComponent>().is_some()
+                    {
+                        validation
+                            .diagnostics
+                            .push(SceneDiagnostic::MissingRequiredComponent {
+                                location,
+                                type_path: registration.type_info().type_path().to_string(),
+                                required: required.type_info().type_path().to_string(),
+                            });
+                    }
+                }
+            }
+        }
+
+        for resource in &self.resources {
+            let location = SceneLocation::Resources;
+            let Some(registration) =
+                validate_registration(resource.as_ref(), location, registry, &mut validation)
+            else {
+                continue;
+            };
+            if registration.data::<ReflectResource>().is_none() {
+                validation
+                    .diagnostics
+                    .push(SceneDiagnostic::UnregisteredResource {
+                        location,
+                        type_path: registration.type_info().type_path().to_string(),
+                    });
+                continue;
+            }
+            find_asset_paths(resource.as_ref(), location, &mut validation);
+        }
+
+        validation
+    }
+}
+
+/// Returns the registration of the type of `value`, or reports why it has none.
+fn validate_registration<'a>(
+    value: &dyn PartialReflect,
+    location: SceneLocation,
+    registry: &'a TypeRegistry,
+    validation: &mut SceneValidation,
+) -> Option<&'a TypeRegistration> {
+    let Some(type_info) = value.get_represented_type_info() else {
+        validation
+            .diagnostics
+            .push(SceneDiagnostic::NoRepresentedType {
+                location,
+                type_path: value.reflect_type_path().to_string(),
+            });
+        return None;
+    };
+    let registration = registry.get(type_info.type_id());
+    if registration.is_none() {
+        validation
+            .diagnostics
+            .push(SceneDiagnostic::UnregisteredType {
+                location,
+                type_path: type_info.type_path().to_string(),
+            });
+    }
+    registration
+}
+
+/// Adds the [`AssetPath`]s found in `value` with reflection to the referenced asset paths.
+fn find_asset_paths(
+    value: &dyn PartialReflect,
+    location: SceneLocation,
+    validation: &mut SceneValidation,
+) {
+    let type_path = value.reflect_type_path();
+    visit_asset_paths(value, &mut |path| {
+        validation.asset_paths.push(SceneAssetPath {
+            location,
+            type_path: type_path.to_string(),
+            path: path.clone(),
+        });
+    });
+}
+
+fn visit_asset_paths(value: &dyn PartialReflect, visit: &mut dyn FnMut(&AssetPath<'static>)) {
+    if let Some(path) = value.try_downcast_ref::<AssetPath<'static>>() {
+        visit(path);
+        return;
+    }
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => value
+            .iter_fields()
+            .for_each(|field| visit_asset_paths(field, visit)),
+        ReflectRef::TupleStruct(value) => value
+            .iter_fields()
+            .for_each(|field| visit_asset_paths(field, visit)),
+        ReflectRef::Tuple(value) => value
+            .iter_fields()
+            .for_each(|field| visit_asset_paths(field, visit)),
+        ReflectRef::List(value) => value.iter().for_each(|item| visit_asset_paths(item, visit)),
+        ReflectRef::Array(value) => value.iter().for_each(|item| visit_asset_paths(item, visit)),
+        ReflectRef::Map(value) => value.iter().for_each(|(key, item)| {
+            visit_asset_paths(key, visit);
+            visit_asset_paths(item, visit);
+        }),
+        ReflectRef::Set(value) => value.iter().for_each(|item| visit_asset_paths(item, visit)),
+        ReflectRef::Enum(value) => value
+            .iter_fields()
+            .for_each(|field| visit_asset_paths(field.value(), visit)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::AssetPath;
+    use bevy_ecs::{
+        component::Component,
+        reflect::{AppTypeRegistry, ReflectComponent},
+        world::World,
+    };
+    use bevy_reflect::{Reflect, TypeRegistry};
+
+    use super::{SceneDiagnostic, SceneLocation};
+    use crate::DynamicSceneBuilder;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    #[require(Collider)]
+    struct Body {
+        model: Option<AssetPath<'static>>,
+    }
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Collider;
+
+    #[derive(Component, Reflect)]
+    #[reflect(Component)]
+    struct Unregistered;
+
+    #[test]
+    fn validate_scene() {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Body>();
+            registry.register::<Collider>();
+            registry.register::<Unregistered>();
+            registry.register::<Option<AssetPath<'static>>>();
+        }
+        world.insert_resource(registry.clone());
+
+        let entity = world
+            .spawn((
+                Body {
+                    model: Some("models/missing.glb".into()),
+                },
+                Unregistered,
+            ))
+            .id();
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .deny_component::<Collider>()
+            .extract_entity(entity)
+            .build();
+
+        // Validate the scene against a registry missing one of its types.
+        let mut registry = TypeRegistry::default();
+        registry.register::<Body>();
+        registry.register::<Collider>();
+        let mut validation = scene.validate(&registry);
+        validation.check_asset_paths(|path| path.path().starts_with("models/found"));
+
+        let location = SceneLocation::Entity(entity);
+        assert!(!validation.is_valid());
+        assert_eq!(
+            validation.errors().cloned().collect::<Vec<_>>(),
+            [
+                SceneDiagnostic::UnregisteredType {
+                    location,
+                    type_path: "bevy_scene::scene_validation::tests::Unregistered".into(),
+                },
+                SceneDiagnostic::MissingAsset {
+                    location,
+                    type_path: "bevy_scene::scene_validation::tests::Body".into(),
+                    path: "models/missing.glb".into(),
+                },
+            ]
+        );
+        assert_eq!(
+            validation.warnings().cloned().collect::<Vec<_>>(),
+            [SceneDiagnostic::MissingRequiredComponent {
+                location,
+                type_path: "bevy_scene::scene_validation::tests::Body".into(),
+                required: "bevy_scene::scene_validation::tests::Collider".into(),
+            }]
+        );
+    }
+}
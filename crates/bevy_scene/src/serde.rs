@@ -1,6 +1,6 @@
 //! `serde` serialization and deserialization implementation for Bevy scenes.
 
-use crate::{DynamicEntity, DynamicScene};
+use crate::{scene_version, DynamicEntity, DynamicScene, ReflectSceneMigrations};
 use bevy_ecs::entity::Entity;
 use bevy_platform::collections::HashSet;
 use bevy_reflect::{
@@ -8,7 +8,7 @@ use bevy_reflect::{
         ReflectDeserializer, TypeRegistrationDeserializer, TypedReflectDeserializer,
         TypedReflectSerializer,
     },
-    PartialReflect, ReflectFromReflect, TypeRegistry,
+    PartialReflect, ReflectFromReflect, ReflectRef, TypeInfo, TypeRegistration, TypeRegistry,
 };
 use core::fmt::Formatter;
 use serde::{
@@ -19,6 +19,12 @@ use serde::{
 
 /// Name of the serialized scene struct type.
 pub const SCENE_STRUCT: &str = "Scene";
+/// Name of the serialized version field in a scene struct.
+///
+/// It's only present in scenes of apps that registered [`SceneMigration`]s.
+///
+/// [`SceneMigration`]: crate::SceneMigration
+pub const SCENE_VERSION: &str = "version";
 /// Name of the serialized resources field in a scene struct.
 pub const SCENE_RESOURCES: &str = "resources";
 /// Name of the serialized entities field in a scene struct.
@@ -78,7 +84,12 @@ impl<'a> Serialize for SceneSerializer<'a> {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct(SCENE_STRUCT, 2)?;
+        let version = scene_version(self.registry);
+        let mut state =
+            serializer.serialize_struct(SCENE_STRUCT, if version > 0 { 3 } else { 2 })?;
+        if version > 0 {
+            state.serialize_field(SCENE_VERSION, &version)?;
+        }
         state.serialize_field(
             SCENE_RESOURCES,
             &SceneMapSerializer {
@@ -197,6 +208,7 @@ impl<'a> Serialize for SceneMapSerializer<'a> {
 #[derive(Deserialize)]
 #[serde(field_identifier, rename_all = "lowercase")]
 enum SceneField {
+    Version,
     Resources,
     Entities,
 }
@@ -222,7 +234,7 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneDeserializer<'a> {
     {
         deserializer.deserialize_struct(
             SCENE_STRUCT,
-            &[SCENE_RESOURCES, SCENE_ENTITIES],
+            &[SCENE_VERSION, SCENE_RESOURCES, SCENE_ENTITIES],
             SceneVisitor {
                 type_registry: self.type_registry,
             },
@@ -245,15 +257,26 @@ impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
     where
         A: SeqAccess<'de>,
     {
+        // Formats that aren't self-describing can't skip fields, so the version is only expected
+        // from apps with migrations, whose scenes are always serialized with it.
+        let version = if scene_version(self.type_registry) > 0 {
+            seq.next_element()?
+                .ok_or_else(|| Error::missing_field(SCENE_VERSION))?
+        } else {
+            0
+        };
+
         let resources = seq
             .next_element_seed(SceneMapDeserializer {
                 registry: self.type_registry,
+                version,
             })?
             .ok_or_else(|| Error::missing_field(SCENE_RESOURCES))?;
 
         let entities = seq
             .next_element_seed(SceneEntitiesDeserializer {
                 type_registry: self.type_registry,
+                version,
             })?
             .ok_or_else(|| Error::missing_field(SCENE_ENTITIES))?;
 
@@ -267,16 +290,31 @@ impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
     where
         A: MapAccess<'de>,
     {
+        let mut version = None;
         let mut resources = None;
         let mut entities = None;
         while let Some(key) = map.next_key()? {
             match key {
+                SceneField::Version => {
+                    if version.is_some() {
+                        return Err(Error::duplicate_field(SCENE_VERSION));
+                    }
+                    // The version decides how the values are deserialized, so it has to be known
+                    // before reaching them.
+                    if resources.is_some() || entities.is_some() {
+                        return Err(Error::custom(format_args!(
+                            "`{SCENE_VERSION}` must come before `{SCENE_RESOURCES}` and `{SCENE_ENTITIES}`"
+                        )));
+                    }
+                    version = Some(map.next_value()?);
+                }
                 SceneField::Resources => {
                     if resources.is_some() {
                         return Err(Error::duplicate_field(SCENE_RESOURCES));
                     }
                     resources = Some(map.next_value_seed(SceneMapDeserializer {
                         registry: self.type_registry,
+                        version: version.unwrap_or(0),
                     })?);
                 }
                 SceneField::Entities => {
@@ -285,6 +323,7 @@ impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
                     }
                     entities = Some(map.next_value_seed(SceneEntitiesDeserializer {
                         type_registry: self.type_registry,
+                        version: version.unwrap_or(0),
                     })?);
                 }
             }
@@ -304,6 +343,10 @@ impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
 pub struct SceneEntitiesDeserializer<'a> {
     /// Type registry in which the component types used by the entities to deserialize are registered.
     pub type_registry: &'a TypeRegistry,
+    /// Version of the scene the entities were serialized at, which decides the [`SceneMigration`]s to run.
+    ///
+    /// [`SceneMigration`]: crate::SceneMigration
+    pub version: u32,
 }
 
 impl<'a, 'de> DeserializeSeed<'de> for SceneEntitiesDeserializer<'a> {
@@ -315,12 +358,14 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneEntitiesDeserializer<'a> {
     {
         deserializer.deserialize_map(SceneEntitiesVisitor {
             type_registry: self.type_registry,
+            version: self.version,
         })
     }
 }
 
 struct SceneEntitiesVisitor<'a> {
     pub type_registry: &'a TypeRegistry,
+    pub version: u32,
 }
 
 impl<'a, 'de> Visitor<'de> for SceneEntitiesVisitor<'a> {
@@ -339,6 +384,7 @@ impl<'a, 'de> Visitor<'de> for SceneEntitiesVisitor<'a> {
             let entity = map.next_value_seed(SceneEntityDeserializer {
                 entity,
                 type_registry: self.type_registry,
+                version: self.version,
             })?;
             entities.push(entity);
         }
@@ -353,6 +399,10 @@ pub struct SceneEntityDeserializer<'a> {
     pub entity: Entity,
     /// Type registry in which the component types used by the entity to deserialize are registered.
     pub type_registry: &'a TypeRegistry,
+    /// Version of the scene the entity was serialized at, which decides the [`SceneMigration`]s to run.
+    ///
+    /// [`SceneMigration`]: crate::SceneMigration
+    pub version: u32,
 }
 
 impl<'a, 'de> DeserializeSeed<'de> for SceneEntityDeserializer<'a> {
@@ -368,6 +418,7 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneEntityDeserializer<'a> {
             SceneEntityVisitor {
                 entity: self.entity,
                 registry: self.type_registry,
+                version: self.version,
             },
         )
     }
@@ -376,6 +427,7 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneEntityDeserializer<'a> {
 struct SceneEntityVisitor<'a> {
     pub entity: Entity,
     pub registry: &'a TypeRegistry,
+    pub version: u32,
 }
 
 impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
@@ -392,6 +444,7 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
         let components = seq
             .next_element_seed(SceneMapDeserializer {
                 registry: self.registry,
+                version: self.version,
             })?
             .ok_or_else(|| Error::missing_field(ENTITY_FIELD_COMPONENTS))?;
 
@@ -415,6 +468,7 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
 
                     components = Some(map.next_value_seed(SceneMapDeserializer {
                         registry: self.registry,
+                        version: self.version,
                     })?);
                 }
             }
@@ -434,6 +488,10 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
 pub struct SceneMapDeserializer<'a> {
     /// Type registry in which the types of the values to deserialize are registered.
     pub registry: &'a TypeRegistry,
+    /// Version of the scene the values were serialized at, which decides the [`SceneMigration`]s to run.
+    ///
+    /// [`SceneMigration`]: crate::SceneMigration
+    pub version: u32,
 }
 
 impl<'a, 'de> DeserializeSeed<'de> for SceneMapDeserializer<'a> {
@@ -445,12 +503,14 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneMapDeserializer<'a> {
     {
         deserializer.deserialize_map(SceneMapVisitor {
             registry: self.registry,
+            version: self.version,
         })
     }
 }
 
 struct SceneMapVisitor<'a> {
     pub registry: &'a TypeRegistry,
+    pub version: u32,
 }
 
 impl<'a, 'de> Visitor<'de> for SceneMapVisitor<'a> {
@@ -488,17 +548,11 @@ impl<'a, 'de> Visitor<'de> for SceneMapVisitor<'a> {
                 )));
             }
 
-            let value =
-                map.next_value_seed(TypedReflectDeserializer::new(registration, self.registry))?;
-
-            // Attempt to convert using FromReflect.
-            let value = self
-                .registry
-                .get(registration.type_id())
-                .and_then(|tr| tr.data::<ReflectFromReflect>())
-                .and_then(|fr| fr.from_reflect(value.as_partial_reflect()))
-                .map(PartialReflect::into_partial_reflect)
-                .unwrap_or(value);
+            let value = map.next_value_seed(SceneValueDeserializer {
+                registration,
+                registry: self.registry,
+                version: self.version,
+            })?;
 
             entries.push(value);
         }
@@ -507,12 +561,91 @@ impl<'a, 'de> Visitor<'de> for SceneMapVisitor<'a> {
     }
 }
 
+/// Handles deserialization of a value of a registered type, running the [`SceneMigration`]s of the
+/// type that are pending at the version of the scene.
+///
+/// [`SceneMigration`]: crate::SceneMigration
+pub struct SceneValueDeserializer<'a> {
+    /// Registration of the type of the value.
+    pub registration: &'a TypeRegistration,
+    /// Type registry in which the types used by the value to deserialize are registered.
+    pub registry: &'a TypeRegistry,
+    /// Version of the scene the value was serialized at, which decides the [`SceneMigration`]s to run.
+    ///
+    /// [`SceneMigration`]: crate::SceneMigration
+    pub version: u32,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneValueDeserializer<'a> {
+    type Value = Box<dyn PartialReflect>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let registration = self.registration;
+        let migrations = registration
+            .data::<ReflectSceneMigrations>()
+            .map(|migrations| migrations.pending(self.version))
+            .unwrap_or_default();
+        let value = match migrations.first() {
+            Some(first) => {
+                // Deserialize the value as it was laid out at the scene's version, then bring
+                // it up to date.
+                let Some(source) = self.registry.get(first.source_type_id()) else {
+                    return Err(Error::custom(format_args!(
+                        "no registration found for the type `{}` is migrated from",
+                        registration.type_info().type_path(),
+                    )));
+                };
+                let mut value = TypedReflectDeserializer::new(source, self.registry)
+                    .deserialize(deserializer)?;
+                for migration in migrations {
+                    value = migration
+                        .migrate(value.as_partial_reflect())
+                        .ok_or_else(|| {
+                            Error::custom(format_args!(
+                                "failed to migrate `{}` from version {} to {}",
+                                registration.type_info().type_path(),
+                                migration.from_version(),
+                                migration.to_version(),
+                            ))
+                        })?;
+                }
+                value
+            }
+            None => TypedReflectDeserializer::new(registration, self.registry)
+                .deserialize(deserializer)?,
+        };
+
+        // Values extracted without some of their fields are kept dynamic, so that spawning them
+        // leaves their other fields unchanged.
+        if let ReflectRef::Struct(dynamic_struct) = value.reflect_ref()
+            && let Some(TypeInfo::Struct(struct_info)) = dynamic_struct.get_represented_type_info()
+            && dynamic_struct.field_len() < struct_info.field_len()
+        {
+            return Ok(value);
+        }
+
+        // Attempt to convert using FromReflect.
+        let value = self
+            .registry
+            .get(registration.type_id())
+            .and_then(|tr| tr.data::<ReflectFromReflect>())
+            .and_then(|fr| fr.from_reflect(value.as_partial_reflect()))
+            .map(PartialReflect::into_partial_reflect)
+            .unwrap_or(value);
+        Ok(value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
         serde::{SceneDeserializer, SceneSerializer},
-        DynamicScene, DynamicSceneBuilder,
+        DynamicScene, DynamicSceneBuilder, SceneMigrationApp,
     };
+    use bevy_app::App;
     use bevy_ecs::{
         entity::{Entity, EntityHashMap},
         prelude::{Component, ReflectComponent, ReflectResource, Resource, World},
@@ -726,6 +859,102 @@ mod tests {
         assert_eq!(1, dst_world.query::<&Baz>().iter(&dst_world).count());
     }
 
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Health {
+        current: u32,
+        max: u32,
+    }
+
+    #[derive(Reflect)]
+    struct HealthV0 {
+        hp: u32,
+    }
+
+    #[derive(Reflect)]
+    struct HealthV1 {
+        current: u32,
+    }
+
+    #[test]
+    fn should_migrate_older_scenes() {
+        let mut app = App::new();
+        app.register_scene_migration::<Health, HealthV1, Health>(1, 2, |old| Health {
+            current: old.current,
+            max: old.current,
+        })
+        .register_scene_migration::<Health, HealthV0, HealthV1>(0, 1, |old| HealthV1 {
+            current: old.hp,
+        });
+        let type_registry = app.world().resource::<AppTypeRegistry>().clone();
+        let registry = type_registry.read();
+
+        let deserialize = |input: &str| {
+            let mut deserializer = ron::de::Deserializer::from_str(input).unwrap();
+            let scene = SceneDeserializer {
+                type_registry: &registry,
+            }
+            .deserialize(&mut deserializer)
+            .unwrap();
+            let health = scene.entities[0].components[0]
+                .try_downcast_ref::<Health>()
+                .unwrap();
+            (health.current, health.max)
+        };
+
+        // Scenes saved before the first migration have no version.
+        let input = r#"(
+  resources: {},
+  entities: {
+    4294967295: (
+      components: {
+        "bevy_scene::serde::tests::Health": (
+          hp: 5,
+        ),
+      },
+    ),
+  },
+)"#;
+        assert_eq!((5, 5), deserialize(input));
+
+        let input = r#"(
+  version: 1,
+  resources: {},
+  entities: {
+    4294967295: (
+      components: {
+        "bevy_scene::serde::tests::Health": (
+          current: 3,
+        ),
+      },
+    ),
+  },
+)"#;
+        assert_eq!((3, 3), deserialize(input));
+
+        // Scenes are saved at the latest version, and aren't migrated again.
+        let mut world = World::new();
+        world.insert_resource(type_registry.clone());
+        world.spawn(Health { current: 2, max: 4 });
+        let output = DynamicScene::from_world(&world)
+            .serialize(&registry)
+            .unwrap();
+        assert!(output.starts_with("(\n  version: 2,\n"));
+        assert_eq!((2, 4), deserialize(&output));
+
+        let input = r#"(
+  resources: {},
+  version: 1,
+  entities: {},
+)"#;
+        let mut deserializer = ron::de::Deserializer::from_str(input).unwrap();
+        assert!(SceneDeserializer {
+            type_registry: &registry,
+        }
+        .deserialize(&mut deserializer)
+        .is_err());
+    }
+
     fn roundtrip_ron(world: &World) -> (DynamicScene, DynamicScene) {
         let scene = DynamicScene::from_world(world);
         let registry = world.resource::<AppTypeRegistry>().read();
@@ -795,6 +1024,39 @@ mod tests {
         assert_eq!(&qux, world.query::<&Qux>().single(&world).unwrap());
     }
 
+    #[test]
+    fn should_roundtrip_filtered_fields() {
+        let mut world = create_world();
+        let entity = world
+            .spawn(MyComponent {
+                foo: [1, 2, 3],
+                bar: (1.0, 2.0),
+                baz: MyEnum::Unit,
+            })
+            .id();
+
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .allow_field::<MyComponent>("bar")
+            .extract_entity(entity)
+            .build();
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let serialized = scene.serialize(&registry).unwrap();
+        assert!(!serialized.contains("baz"));
+
+        let mut deserializer = ron::de::Deserializer::from_str(&serialized).unwrap();
+        let deserialized_scene = SceneDeserializer {
+            type_registry: &registry,
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+        assert_scene_eq(&scene, &deserialized_scene);
+
+        // The component only holds some of its fields, so it isn't converted with `FromReflect`.
+        let component = &deserialized_scene.entities[0].components[0];
+        assert!(component.represents::<MyComponent>());
+        assert!(component.try_as_reflect().is_none());
+    }
+
     #[test]
     fn should_roundtrip_postcard() {
         let mut world = create_world();
@@ -124,6 +124,12 @@ impl Default for TileData {
 pub struct TilemapChunkTileData(pub Vec<Option<TileData>>);
 
 fn on_insert_tilemap_chunk(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    // Chunks inserted in worlds without the plugin, such as the world of a scene, are initialized
+    // once they're spawned in the app.
+    if !world.contains_resource::<TilemapChunkMeshCache>() {
+        return;
+    }
+
     let Some(tilemap_chunk) = world.get::<TilemapChunk>(entity) else {
         warn!("TilemapChunk not found for tilemap chunk {}", entity);
         return;
@@ -1,7 +1,7 @@
 use alloc::sync::Arc;
 
-use bevy_asset::Asset;
-use bevy_reflect::TypePath;
+use bevy_asset::{Asset, Handle};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect, TypePath};
 use cosmic_text::skrifa::raw::ReadError;
 use cosmic_text::skrifa::FontRef;
 
@@ -17,6 +17,8 @@ use cosmic_text::skrifa::FontRef;
 /// by its style (e.g. italic), its weight (e.g. bold) and its stretch (e.g. condensed).
 ///
 /// Bevy currently loads a single font face as a single `Font` asset.
+///
+/// Variable fonts with a weight axis can be drawn at any weight, see [`FontWeight`].
 #[derive(Debug, TypePath, Clone, Asset)]
 pub struct Font {
     /// Content of a font file as bytes
@@ -32,3 +34,69 @@ impl Font {
         })
     }
 }
+
+/// An [`Asset`] listing fonts in order of preference.
+///
+/// When a [`TextFont`](crate::TextFont) has a [`FontStack`] as its
+/// [`fallbacks`](crate::TextFont::fallbacks), characters that its `font` has no glyph for are drawn
+/// with the first font of the stack that has one. This allows text to mix scripts that are rarely
+/// covered by a single font, for example with a Latin font followed by a CJK font and an emoji font.
+///
+/// Text isn't drawn until all the fonts of the stack are loaded.
+#[derive(Debug, TypePath, Clone, Default, Asset)]
+pub struct FontStack {
+    /// The fonts of the stack, in order of preference.
+    #[dependency]
+    pub fonts: Vec<Handle<Font>>,
+}
+
+impl FontStack {
+    /// Creates a [`FontStack`] from fonts in order of preference.
+    pub fn new(fonts: impl IntoIterator<Item = Handle<Font>>) -> Self {
+        Self {
+            fonts: fonts.into_iter().collect(),
+        }
+    }
+}
+
+/// The weight of a font, from 1 (thinnest) to 1000 (boldest).
+///
+/// Variable fonts with a `wght` axis are drawn at this weight, clamped to the range supported by
+/// the font. Other fonts are always drawn at their own weight, so a separate font file is needed
+/// for each weight.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+#[reflect(Default, Debug, Clone, PartialEq)]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    /// Thin weight (100).
+    pub const THIN: Self = Self(100);
+    /// Extra light weight (200).
+    pub const EXTRA_LIGHT: Self = Self(200);
+    /// Light weight (300).
+    pub const LIGHT: Self = Self(300);
+    /// Normal weight (400).
+    pub const NORMAL: Self = Self(400);
+    /// Medium weight (500).
+    pub const MEDIUM: Self = Self(500);
+    /// Semibold weight (600).
+    pub const SEMIBOLD: Self = Self(600);
+    /// Bold weight (700).
+    pub const BOLD: Self = Self(700);
+    /// Extra bold weight (800).
+    pub const EXTRA_BOLD: Self = Self(800);
+    /// Black weight (900).
+    pub const BLACK: Self = Self(900);
+}
+
+impl Default for FontWeight {
+    fn default() -> Self {
+        Self::NORMAL
+    }
+}
+
+impl From<FontWeight> for cosmic_text::Weight {
+    fn from(weight: FontWeight) -> Self {
+        cosmic_text::Weight(weight.0.clamp(1, 1000))
+    }
+}
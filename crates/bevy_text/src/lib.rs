@@ -38,6 +38,7 @@ mod font_atlas;
 mod font_atlas_set;
 mod font_loader;
 mod glyph;
+mod markup;
 mod pipeline;
 mod text;
 mod text_access;
@@ -49,6 +50,7 @@ pub use font_atlas::*;
 pub use font_atlas_set::*;
 pub use font_loader::*;
 pub use glyph::*;
+pub use markup::*;
 pub use pipeline::*;
 pub use text::*;
 pub use text_access::*;
@@ -59,8 +61,9 @@ pub use text_access::*;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        Font, Justify, LineBreak, Strikethrough, StrikethroughColor, TextColor, TextError,
-        TextFont, TextLayout, TextSpan, Underline, UnderlineColor,
+        Font, FontStack, FontWeight, Justify, LineBreak, Strikethrough, StrikethroughColor,
+        TextColor, TextError, TextFont, TextLayout, TextMarkup, TextSpan, Underline,
+        UnderlineColor,
     };
 }
 
@@ -86,15 +89,20 @@ pub struct Text2dUpdateSystems;
 impl Plugin for TextPlugin {
     fn build(&self, app: &mut App) {
         app.init_asset::<Font>()
+            .init_asset::<FontStack>()
             .init_asset_loader::<FontLoader>()
             .init_resource::<FontAtlasSet>()
             .init_resource::<TextPipeline>()
             .init_resource::<CosmicFontSystem>()
             .init_resource::<SwashCache>()
             .init_resource::<TextIterScratch>()
+            .init_resource::<MarkupIcons>()
             .add_systems(
                 PostUpdate,
-                free_unused_font_atlases_system.before(AssetEventSystems),
+                (
+                    free_unused_font_atlases_system.before(AssetEventSystems),
+                    update_markup_spans.before(Text2dUpdateSystems),
+                ),
             )
             .add_systems(Last, trim_cosmic_cache);
 
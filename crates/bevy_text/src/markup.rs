@@ -0,0 +1,392 @@
+use crate::{
+    Font, FontWeight, LineHeight, Strikethrough, TextColor, TextFont, TextSpan, Underline,
+};
+use bevy_asset::Handle;
+use bevy_color::{palettes::basic, Color, Srgba};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::EntityEvent,
+    hierarchy::Children,
+    query::{Changed, With},
+    reflect::ReflectComponent,
+    resource::Resource,
+    system::{Commands, Query, Res},
+};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use thiserror::Error;
+use tracing::warn;
+
+/// Rich text written with markup tags, which is parsed into the [`TextSpan`]s of a text entity.
+///
+/// Add this to an entity with a text root component such as `Text` or `Text2d`, whose own text
+/// should be left empty. The spans are spawned as children of the entity, and are respawned
+/// whenever the markup changes. Spans are styled with the [`TextFont`], [`TextColor`] and
+/// [`LineHeight`] of the entity, changed by the tags they're in:
+///
+/// - `[b]bold[/b]` draws text with a bold [`FontWeight`].
+/// - `[u]underlined[/u]` and `[s]struck through[/s]` add [`Underline`] and [`Strikethrough`].
+/// - `[color=red]` and `[color=#ff8000]` set the [`TextColor`] to one of the basic named colors or
+///   to a hex color.
+/// - `[size=24]` sets the font size.
+/// - `[link=id]click here[/link]` adds a [`TextLink`] to the spans, so that clicking them sends
+///   [`TextLinkClicked`].
+/// - `[icon=name]` inserts the [`MarkupIcon`] registered as `name` in [`MarkupIcons`].
+///
+/// `[[` is a literal `[`. See [`parse_markup`] for parsing markup without spawning spans.
+///
+/// ```
+/// # use bevy_ecs::world::World;
+/// # use bevy_text::TextMarkup;
+/// # let mut world = World::default();
+/// world.spawn(TextMarkup::new(
+///     "[b]Guard:[/b] The [color=yellow]key[/color] is in the [link=tower]old tower[/link].",
+/// ));
+/// ```
+#[derive(Component, Debug, Default, Clone, Deref, DerefMut, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct TextMarkup(pub String);
+
+impl TextMarkup {
+    /// Makes a new text markup component.
+    pub fn new(markup: impl Into<String>) -> Self {
+        Self(markup.into())
+    }
+}
+
+/// Marks the [`TextSpan`]s spawned from a [`TextMarkup`].
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component, Default, Debug, Clone)]
+pub struct MarkupSpan;
+
+/// A link on a text span, usually added with the `[link=...]` tag of [`TextMarkup`].
+///
+/// Clicking on the span sends [`TextLinkClicked`] to its text entity.
+#[derive(Component, Debug, Default, Clone, Deref, DerefMut, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct TextLink(pub String);
+
+/// A span with a [`TextLink`] was clicked.
+#[derive(Clone, Debug, PartialEq, EntityEvent)]
+pub struct TextLinkClicked {
+    /// The text entity containing the span.
+    pub entity: Entity,
+    /// The span that was clicked.
+    pub span: Entity,
+    /// The link of the span.
+    pub link: String,
+}
+
+/// An icon inserted in [`TextMarkup`] with the `[icon=name]` tag.
+///
+/// Icons are drawn as glyphs of a font, typically an icon font mapping its icons to characters in
+/// the Unicode private use area.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkupIcon {
+    /// The text of the icon.
+    pub text: String,
+    /// The font the icon is drawn with, or `None` to use the font of the text.
+    pub font: Option<Handle<Font>>,
+}
+
+impl MarkupIcon {
+    /// Makes an icon drawn as `text`.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            font: None,
+        }
+    }
+
+    /// Returns this icon drawn with `font`.
+    pub fn with_font(mut self, font: Handle<Font>) -> Self {
+        self.font = Some(font);
+        self
+    }
+}
+
+/// The icons that can be inserted in [`TextMarkup`], by name.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct MarkupIcons {
+    icons: HashMap<String, MarkupIcon>,
+}
+
+impl MarkupIcons {
+    /// Registers `icon` as `name`, replacing any icon with the same name.
+    pub fn insert(&mut self, name: impl Into<String>, icon: MarkupIcon) {
+        self.icons.insert(name.into(), icon);
+    }
+
+    /// Returns the icon registered as `name`.
+    pub fn get(&self, name: &str) -> Option<&MarkupIcon> {
+        self.icons.get(name)
+    }
+
+    /// Removes the icon registered as `name`, returning it.
+    pub fn remove(&mut self, name: &str) -> Option<MarkupIcon> {
+        self.icons.remove(name)
+    }
+}
+
+/// A segment of [`TextMarkup`] with a single style, returned by [`parse_markup`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkupSegment {
+    /// The content of the segment.
+    pub content: MarkupContent,
+    /// The style of the segment.
+    pub style: MarkupStyle,
+}
+
+/// The content of a [`MarkupSegment`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MarkupContent {
+    /// Text.
+    Text(String),
+    /// The name of a [`MarkupIcon`].
+    Icon(String),
+}
+
+/// The style of a [`MarkupSegment`], set by the tags it's in.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MarkupStyle {
+    /// The color of the text, or `None` to keep the color of the text entity.
+    pub color: Option<Color>,
+    /// If true, the text is bold.
+    pub bold: bool,
+    /// If true, the text is underlined.
+    pub underline: bool,
+    /// If true, the text is struck through.
+    pub strikethrough: bool,
+    /// The font size of the text, or `None` to keep the font size of the text entity.
+    pub font_size: Option<f32>,
+    /// The link of the text, if any.
+    pub link: Option<String>,
+}
+
+/// An error that occurs when parsing [`TextMarkup`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum MarkupError {
+    /// A tag isn't closed by a `]`.
+    #[error("tag at byte {0} isn't closed by a `]`")]
+    UnterminatedTag(usize),
+    /// The tag isn't supported.
+    #[error("unknown tag `[{0}]`")]
+    UnknownTag(String),
+    /// The value of a tag is invalid.
+    #[error("invalid value `{value}` for tag `[{tag}]`")]
+    InvalidValue {
+        /// The name of the tag.
+        tag: String,
+        /// The invalid value.
+        value: String,
+    },
+    /// A closing tag doesn't match the last open tag.
+    #[error("closing tag `[/{0}]` doesn't match an open tag")]
+    UnexpectedClosingTag(String),
+    /// A tag is never closed.
+    #[error("tag `[{0}]` is never closed")]
+    UnclosedTag(String),
+}
+
+/// Parses `markup` into segments of text with a single style, as described in [`TextMarkup`].
+///
+/// ```
+/// # use bevy_text::{parse_markup, MarkupContent};
+/// let segments = parse_markup("Press [b]Jump[/b]").unwrap();
+/// assert_eq!(segments[1].content, MarkupContent::Text("Jump".into()));
+/// assert!(segments[1].style.bold);
+/// ```
+pub fn parse_markup(markup: &str) -> Result<Vec<MarkupSegment>, MarkupError> {
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut style = MarkupStyle::default();
+    // The open tags, with the style from before each of them.
+    let mut open_tags: Vec<(&str, MarkupStyle)> = Vec::new();
+
+    let mut rest = markup;
+    while let Some(start) = rest.find('[') {
+        text.push_str(&rest[..start]);
+        let tag_start = markup.len() - rest.len() + start;
+        rest = &rest[start + 1..];
+        if let Some(after) = rest.strip_prefix('[') {
+            text.push('[');
+            rest = after;
+            continue;
+        }
+        let end = rest
+            .find(']')
+            .ok_or(MarkupError::UnterminatedTag(tag_start))?;
+        let tag = &rest[..end];
+        rest = &rest[end + 1..];
+
+        if !text.is_empty() {
+            segments.push(MarkupSegment {
+                content: MarkupContent::Text(core::mem::take(&mut text)),
+                style: style.clone(),
+            });
+        }
+
+        if let Some(name) = tag.strip_prefix('/') {
+            match open_tags.pop() {
+                Some((open, previous_style)) if open == name => style = previous_style,
+                _ => return Err(MarkupError::UnexpectedClosingTag(name.into())),
+            }
+            continue;
+        }
+
+        let (name, value) = match tag.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (tag, None),
+        };
+        let invalid_value = |value: &str| MarkupError::InvalidValue {
+            tag: name.into(),
+            value: value.into(),
+        };
+        let mut tag_style = style.clone();
+        match (name, value) {
+            ("icon", Some(icon)) => {
+                segments.push(MarkupSegment {
+                    content: MarkupContent::Icon(icon.into()),
+                    style: style.clone(),
+                });
+                continue;
+            }
+            ("b", None) => tag_style.bold = true,
+            ("u", None) => tag_style.underline = true,
+            ("s", None) => tag_style.strikethrough = true,
+            ("color", Some(color)) => {
+                tag_style.color = Some(parse_color(color).ok_or_else(|| invalid_value(color))?);
+            }
+            ("size", Some(size)) => {
+                let font_size = size
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|font_size| *font_size > 0.)
+                    .ok_or_else(|| invalid_value(size))?;
+                tag_style.font_size = Some(font_size);
+            }
+            ("link", Some(link)) => tag_style.link = Some(link.into()),
+            _ => return Err(MarkupError::UnknownTag(tag.into())),
+        }
+        open_tags.push((name, core::mem::replace(&mut style, tag_style)));
+    }
+
+    text.push_str(rest);
+    if let Some((name, _)) = open_tags.pop() {
+        return Err(MarkupError::UnclosedTag(name.into()));
+    }
+    if !text.is_empty() {
+        segments.push(MarkupSegment {
+            content: MarkupContent::Text(text),
+            style,
+        });
+    }
+    Ok(segments)
+}
+
+/// Parses a hex color, or the name of one of the [basic colors](basic).
+fn parse_color(color: &str) -> Option<Color> {
+    if color.starts_with('#') {
+        return Srgba::hex(color).ok().map(Color::from);
+    }
+    let color = match color.to_ascii_lowercase().as_str() {
+        "aqua" => basic::AQUA,
+        "black" => basic::BLACK,
+        "blue" => basic::BLUE,
+        "fuchsia" => basic::FUCHSIA,
+        "gray" | "grey" => basic::GRAY,
+        "green" => basic::GREEN,
+        "lime" => basic::LIME,
+        "maroon" => basic::MAROON,
+        "navy" => basic::NAVY,
+        "olive" => basic::OLIVE,
+        "purple" => basic::PURPLE,
+        "red" => basic::RED,
+        "silver" => basic::SILVER,
+        "teal" => basic::TEAL,
+        "white" => basic::WHITE,
+        "yellow" => basic::YELLOW,
+        _ => return None,
+    };
+    Some(color.into())
+}
+
+/// Respawns the [`TextSpan`]s of text entities whose [`TextMarkup`] changed.
+///
+/// If the markup can't be parsed, it's shown as plain text.
+pub fn update_markup_spans(
+    mut commands: Commands,
+    markup_query: Query<
+        (
+            Entity,
+            &TextMarkup,
+            &TextFont,
+            &TextColor,
+            &LineHeight,
+            Option<&Children>,
+        ),
+        Changed<TextMarkup>,
+    >,
+    markup_spans: Query<(), With<MarkupSpan>>,
+    icons: Res<MarkupIcons>,
+) {
+    for (entity, markup, text_font, text_color, line_height, children) in &markup_query {
+        for &child in children.into_iter().flatten() {
+            if markup_spans.contains(child) {
+                commands.entity(child).despawn();
+            }
+        }
+
+        let segments = parse_markup(markup).unwrap_or_else(|error| {
+            warn!("Failed to parse the markup of text entity {entity}: {error}");
+            vec![MarkupSegment {
+                content: MarkupContent::Text(markup.0.clone()),
+                style: MarkupStyle::default(),
+            }]
+        });
+
+        commands.entity(entity).with_children(|parent| {
+            for MarkupSegment { content, style } in segments {
+                let mut font = text_font.clone();
+                if style.bold {
+                    font.weight = FontWeight::BOLD;
+                }
+                if let Some(font_size) = style.font_size {
+                    font.font_size = font_size;
+                }
+                let text = match content {
+                    MarkupContent::Text(text) => text,
+                    MarkupContent::Icon(name) => {
+                        let Some(icon) = icons.get(&name) else {
+                            warn!("Text entity {entity} uses the unknown markup icon `{name}`");
+                            continue;
+                        };
+                        if let Some(icon_font) = &icon.font {
+                            font.font = icon_font.clone();
+                        }
+                        icon.text.clone()
+                    }
+                };
+
+                let mut span = parent.spawn((
+                    MarkupSpan,
+                    TextSpan(text),
+                    font,
+                    TextColor(style.color.unwrap_or(text_color.0)),
+                    *line_height,
+                ));
+                if style.underline {
+                    span.insert(Underline);
+                }
+                if style.strikethrough {
+                    span.insert(Strikethrough);
+                }
+                if let Some(link) = style.link {
+                    span.insert(TextLink(link));
+                }
+            }
+        });
+    }
+}
@@ -17,10 +17,13 @@ use cosmic_text::{Attrs, Buffer, Family, Metrics, Shaping, Wrap};
 
 use crate::{
     add_glyph_to_atlas, error::TextError, get_glyph_atlas_info, ComputedTextBlock, Font,
-    FontAtlasKey, FontAtlasSet, FontSmoothing, Justify, LineBreak, LineHeight, PositionedGlyph,
-    TextBounds, TextEntity, TextFont, TextLayout,
+    FontAtlasKey, FontAtlasSet, FontSmoothing, FontStack, Justify, LineBreak, LineHeight,
+    PositionedGlyph, TextBounds, TextEntity, TextFont, TextLayout,
 };
 
+/// The tag of the weight axis of variable fonts.
+const WEIGHT_AXIS: u32 = u32::from_be_bytes(*b"wght");
+
 /// A wrapper resource around a [`cosmic_text::FontSystem`]
 ///
 /// The font system is used to retrieve fonts and their information, including glyph outlines.
@@ -55,12 +58,16 @@ impl Default for SwashCache {
 /// Information about a font collected as part of preparing for text layout.
 #[derive(Clone)]
 pub struct FontFaceInfo {
+    /// The ID of the face in the font database
+    pub id: cosmic_text::fontdb::ID,
     /// Width class: <https://docs.microsoft.com/en-us/typography/opentype/spec/os2#uswidthclass>
     pub stretch: cosmic_text::fontdb::Stretch,
     /// Allows italic or oblique faces to be selected
     pub style: cosmic_text::fontdb::Style,
     /// The degree of blackness or stroke thickness
     pub weight: cosmic_text::fontdb::Weight,
+    /// Whether the font is a variable font with a weight axis
+    pub has_weight_axis: bool,
     /// Font family name
     pub family_name: Arc<str>,
 }
@@ -93,6 +100,7 @@ impl TextPipeline {
     pub fn update_buffer<'a>(
         &mut self,
         fonts: &Assets<Font>,
+        font_stacks: &Assets<FontStack>,
         text_spans: impl Iterator<Item = (Entity, usize, &'a str, &'a TextFont, Color, LineHeight)>,
         linebreak: LineBreak,
         justify: Justify,
@@ -129,7 +137,16 @@ impl TextPipeline {
                 continue;
             }
             // Return early if a font is not loaded yet.
-            if !fonts.contains(text_font.font.id()) {
+            let fallbacks = match &text_font.fallbacks {
+                Some(fallbacks) => font_stacks
+                    .get(fallbacks.id())
+                    .map(|font_stack| font_stack.fonts.as_slice()),
+                None => Some(&[][..]),
+            };
+            let Some(fallbacks) = fallbacks.filter(|fallbacks| {
+                fonts.contains(text_font.font.id())
+                    && fallbacks.iter().all(|font| fonts.contains(font.id()))
+            }) else {
                 spans.clear();
                 self.spans_buffer = spans
                     .into_iter()
@@ -145,7 +162,7 @@ impl TextPipeline {
                     .collect();
 
                 return Err(TextError::NoSuchFont);
-            }
+            };
 
             // Get max font size for use in cosmic Metrics.
             max_font_size = max_font_size.max(text_font.font_size);
@@ -153,7 +170,7 @@ impl TextPipeline {
 
             // Load Bevy fonts into cosmic-text's font system.
             let face_info = load_font_to_fontdb(
-                text_font,
+                text_font.font.id(),
                 font_system,
                 &mut self.map_handle_to_font_id,
                 fonts,
@@ -167,7 +184,64 @@ impl TextPipeline {
 
                 continue;
             }
-            spans.push((span_index, span, text_font, face_info, color, line_height));
+
+            if fallbacks.is_empty() {
+                spans.push((span_index, span, text_font, face_info, color, line_height));
+                continue;
+            }
+
+            // Split the span into runs that are each drawn with the first font that has glyphs
+            // for their characters.
+            let faces: Vec<FontFaceInfo> = core::iter::once(face_info)
+                .chain(fallbacks.iter().map(|font| {
+                    load_font_to_fontdb(
+                        font.id(),
+                        font_system,
+                        &mut self.map_handle_to_font_id,
+                        fonts,
+                    )
+                }))
+                .collect();
+            let faces_fonts: Vec<_> = faces
+                .iter()
+                .map(|face| font_system.get_font(face.id, face.weight))
+                .collect();
+            let mut run_start = 0;
+            let mut run_face = 0;
+            for (index, character) in span.char_indices() {
+                if continues_cluster(character) {
+                    continue;
+                }
+                let face = faces_fonts
+                    .iter()
+                    .position(|font| {
+                        font.as_ref()
+                            .is_some_and(|font| font.as_swash().charmap().map(character) != 0)
+                    })
+                    .unwrap_or(0);
+                if face != run_face {
+                    if index > run_start {
+                        spans.push((
+                            span_index,
+                            &span[run_start..index],
+                            text_font,
+                            faces[run_face].clone(),
+                            color,
+                            line_height,
+                        ));
+                    }
+                    run_start = index;
+                    run_face = face;
+                }
+            }
+            spans.push((
+                span_index,
+                &span[run_start..],
+                text_font,
+                faces[run_face].clone(),
+                color,
+                line_height,
+            ));
         }
 
         let mut metrics = Metrics::new(max_font_size, max_line_height).scale(scale_factor as f32);
@@ -257,6 +331,7 @@ impl TextPipeline {
         &mut self,
         layout_info: &mut TextLayoutInfo,
         fonts: &Assets<Font>,
+        font_stacks: &Assets<FontStack>,
         text_spans: impl Iterator<Item = (Entity, usize, &'a str, &'a TextFont, Color, LineHeight)>,
         scale_factor: f64,
         layout: &TextLayout,
@@ -291,6 +366,7 @@ impl TextPipeline {
 
         let update_result = self.update_buffer(
             fonts,
+            font_stacks,
             text_spans,
             layout.linebreak,
             layout.justify,
@@ -467,6 +543,7 @@ impl TextPipeline {
         &mut self,
         entity: Entity,
         fonts: &Assets<Font>,
+        font_stacks: &Assets<FontStack>,
         text_spans: impl Iterator<Item = (Entity, usize, &'a str, &'a TextFont, Color, LineHeight)>,
         scale_factor: f64,
         layout: &TextLayout,
@@ -481,6 +558,7 @@ impl TextPipeline {
 
         self.update_buffer(
             fonts,
+            font_stacks,
             text_spans,
             layout.linebreak,
             layout.justify,
@@ -536,6 +614,17 @@ pub struct TextLayoutInfo {
     pub size: Vec2,
 }
 
+impl TextLayoutInfo {
+    /// Returns the index in the [`ComputedTextBlock`] of the span drawn at `position`, relative to
+    /// the top left corner of the text layout in the same units as [`Self::run_geometry`].
+    pub fn span_at(&self, position: Vec2) -> Option<usize> {
+        self.run_geometry
+            .iter()
+            .find(|run| run.bounds.contains(position))
+            .map(|run| run.span_index)
+    }
+}
+
 /// Geometry of a text run used to render text decorations like background colors, strikethrough, and underline.
 /// A run in `bevy_text` is a contiguous sequence of glyphs on a line that share the same text attributes like font,
 /// font size, and line height.
@@ -615,41 +704,66 @@ impl TextMeasureInfo {
 
 /// Add the font to the cosmic text's `FontSystem`'s in-memory font database
 pub fn load_font_to_fontdb(
-    text_font: &TextFont,
+    font: AssetId<Font>,
     font_system: &mut cosmic_text::FontSystem,
     map_handle_to_font_id: &mut HashMap<AssetId<Font>, (cosmic_text::fontdb::ID, Arc<str>)>,
     fonts: &Assets<Font>,
 ) -> FontFaceInfo {
-    let font_handle = text_font.font.clone();
-    let (face_id, family_name) = map_handle_to_font_id
-        .entry(font_handle.id())
-        .or_insert_with(|| {
-            let font = fonts.get(font_handle.id()).expect(
-                "Tried getting a font that was not available, probably due to not being loaded yet",
-            );
-            let data = Arc::clone(&font.data);
-            let ids = font_system
-                .db_mut()
-                .load_font_source(cosmic_text::fontdb::Source::Binary(data));
+    let (face_id, family_name) = map_handle_to_font_id.entry(font).or_insert_with(|| {
+        let font = fonts.get(font).expect(
+            "Tried getting a font that was not available, probably due to not being loaded yet",
+        );
+        let data = Arc::clone(&font.data);
+        let ids = font_system
+            .db_mut()
+            .load_font_source(cosmic_text::fontdb::Source::Binary(data));
 
-            // TODO: it is assumed this is the right font face
-            let face_id = *ids.last().unwrap();
-            let face = font_system.db().face(face_id).unwrap();
+        // TODO: it is assumed this is the right font face
+        let face_id = *ids.last().unwrap();
+        let face = font_system.db().face(face_id).unwrap();
 
-            let family_name = Arc::from(face.families[0].0.as_str());
-            (face_id, family_name)
-        });
+        let family_name = Arc::from(face.families[0].0.as_str());
+        (face_id, family_name)
+    });
 
     let face = font_system.db().face(*face_id).unwrap();
+    let (stretch, style, weight) = (face.stretch, face.style, face.weight);
+    let has_weight_axis = font_system.get_font(*face_id, weight).is_some_and(|font| {
+        font.as_swash()
+            .variations()
+            .find_by_tag(WEIGHT_AXIS)
+            .is_some()
+    });
 
     FontFaceInfo {
-        stretch: face.stretch,
-        style: face.style,
-        weight: face.weight,
+        id: *face_id,
+        stretch,
+        style,
+        weight,
+        has_weight_axis,
         family_name: family_name.clone(),
     }
 }
 
+/// Returns whether a character continues the grapheme cluster before it, like joiners, variation
+/// selectors and combining marks, or doesn't need a glyph, like whitespace.
+///
+/// These characters are drawn with the font of the characters before them.
+fn continues_cluster(character: char) -> bool {
+    character.is_whitespace()
+        || character.is_control()
+        || matches!(
+            character,
+            '\u{0300}'..='\u{036F}'
+                | '\u{200C}'..='\u{200D}'
+                | '\u{20D0}'..='\u{20FF}'
+                | '\u{FE00}'..='\u{FE0F}'
+                | '\u{1F3FB}'..='\u{1F3FF}'
+                | '\u{E0020}'..='\u{E007F}'
+                | '\u{E0100}'..='\u{E01EF}'
+        )
+}
+
 /// Translates [`TextFont`] to [`Attrs`].
 fn get_attrs<'a>(
     span_index: usize,
@@ -664,7 +778,11 @@ fn get_attrs<'a>(
         .family(Family::Name(&face_info.family_name))
         .stretch(face_info.stretch)
         .style(face_info.style)
-        .weight(face_info.weight)
+        .weight(if face_info.has_weight_axis {
+            text_font.weight.into()
+        } else {
+            face_info.weight
+        })
         .metrics(
             Metrics {
                 font_size: text_font.font_size,
@@ -1,4 +1,4 @@
-use crate::{Font, TextLayoutInfo, TextSpanAccess, TextSpanComponent};
+use crate::{Font, FontStack, FontWeight, TextLayoutInfo, TextSpanAccess, TextSpanComponent};
 use bevy_asset::Handle;
 use bevy_color::Color;
 use bevy_derive::{Deref, DerefMut};
@@ -246,7 +246,8 @@ impl From<Justify> for cosmic_text::Align {
 }
 
 /// `TextFont` determines the style of a text span within a [`ComputedTextBlock`], specifically
-/// the font face, the font size, the line height, and the antialiasing method.
+/// the font face and its fallbacks, the font weight, the font size, the line height, and the
+/// antialiasing method.
 #[derive(Component, Clone, Debug, Reflect, PartialEq)]
 #[reflect(Component, Default, Debug, Clone)]
 pub struct TextFont {
@@ -258,6 +259,14 @@ pub struct TextFont {
     /// * otherwise no text will be rendered, unless a custom font is loaded into the default font
     ///   handle.
     pub font: Handle<Font>,
+    /// Fonts used, in order, for the characters that `font` has no glyph for.
+    ///
+    /// See [`FontStack`].
+    pub fallbacks: Option<Handle<FontStack>>,
+    /// The weight of variable fonts.
+    ///
+    /// This is ignored by fonts without a weight axis, see [`FontWeight`].
+    pub weight: FontWeight,
     /// The vertical height of rasterized glyphs in the font atlas in pixels.
     ///
     /// This is multiplied by the window scale factor and `UiScale`, but not the text entity
@@ -284,6 +293,18 @@ impl TextFont {
         self
     }
 
+    /// Returns this [`TextFont`] with the specified fallback fonts.
+    pub fn with_fallbacks(mut self, fallbacks: Handle<FontStack>) -> Self {
+        self.fallbacks = Some(fallbacks);
+        self
+    }
+
+    /// Returns this [`TextFont`] with the specified [`FontWeight`].
+    pub const fn with_weight(mut self, weight: FontWeight) -> Self {
+        self.weight = weight;
+        self
+    }
+
     /// Returns this [`TextFont`] with the specified font size.
     pub const fn with_font_size(mut self, font_size: f32) -> Self {
         self.font_size = font_size;
@@ -307,6 +328,8 @@ impl Default for TextFont {
     fn default() -> Self {
         Self {
             font: Default::default(),
+            fallbacks: None,
+            weight: FontWeight::NORMAL,
             font_size: 20.0,
             font_features: FontFeatures::default(),
             font_smoothing: Default::default(),
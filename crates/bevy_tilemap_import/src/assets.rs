@@ -0,0 +1,15 @@
+//! Representation of assets present in a Tiled or LDtk map
+
+use bevy_asset::{Asset, Handle};
+use bevy_image::Image;
+use bevy_reflect::TypePath;
+use bevy_scene::Scene;
+
+/// Representation of a loaded Tiled or LDtk map.
+#[derive(Asset, Debug, TypePath)]
+pub struct Tilemap {
+    /// The layers of the map, with the levels of an LDtk world under a single root entity.
+    pub scene: Handle<Scene>,
+    /// The tiles of each tileset used by the map, as an array texture with one layer per tile.
+    pub tilesets: Vec<Handle<Image>>,
+}
@@ -0,0 +1,146 @@
+//! Components added to the entities of loaded maps, for the parts of a map that aren't tiles.
+
+use bevy_color::Color;
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{component::Component, reflect::ReflectComponent};
+use bevy_math::{Rect, UVec2, Vec2};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+/// The value of a custom property of a map, layer, object or tile.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum MapProperty {
+    /// A boolean.
+    Bool(bool),
+    /// An integer, which is also used for references to other objects.
+    Int(i64),
+    /// A floating point number.
+    Float(f64),
+    /// A string, which is also used for file paths and enum values.
+    String(String),
+    /// A color.
+    Color(Color),
+    /// A point, in the coordinates of the map.
+    Point(Vec2),
+    /// A list of values.
+    Array(Vec<MapProperty>),
+    /// A value of a custom class.
+    Class {
+        /// The name of the class.
+        class: String,
+        /// The value of each member of the class that was set.
+        members: HashMap<String, MapProperty>,
+    },
+}
+
+/// The custom properties of a map, layer or object, by name.
+///
+/// Properties whose value is of a custom class are also inserted as a component when a component
+/// type with the same name as the class is registered, like the class of a [`MapObject`].
+#[derive(Component, Clone, Debug, Default, PartialEq, Deref, DerefMut, Reflect)]
+#[reflect(Component, Clone, Debug, Default, PartialEq)]
+pub struct MapProperties(pub HashMap<String, MapProperty>);
+
+/// The geometry of an object or a collider, relative to its origin.
+///
+/// Like everything in Bevy, the y axis points up, while it points down in map editors.
+#[derive(Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Clone, Debug, Default, PartialEq)]
+pub enum MapShape {
+    /// A rectangle.
+    Rectangle(Rect),
+    /// An ellipse, bounded by a rectangle.
+    Ellipse(Rect),
+    /// A closed polygon, with its vertices.
+    Polygon(Vec<Vec2>),
+    /// An open line, with its vertices.
+    Polyline(Vec<Vec2>),
+    /// A single point, at the origin.
+    #[default]
+    Point,
+}
+
+/// An object of an object layer, or an entity of an LDtk level.
+///
+/// The entity of the object is placed at the origin of the object, which is the top-left corner in
+/// Tiled (or the bottom-left corner for tile objects) and the pivot in LDtk. If a component type
+/// whose name matches the class of the object is registered and reflects [`Default`] and
+/// [`Component`], it's inserted as well, with the fields set from the custom properties of the
+/// object.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Clone, Debug, Default, PartialEq)]
+pub struct MapObject {
+    /// The class of the object, which is its identifier in LDtk. It may be empty.
+    pub class: String,
+    /// The shape of the object.
+    pub shape: MapShape,
+}
+
+/// A collision shape of a tile, as set in the tile collision editor of Tiled.
+#[derive(Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Clone, Debug, Default, PartialEq)]
+pub struct TileCollider {
+    /// The origin of the shape, relative to the entity of the chunk.
+    pub origin: Vec2,
+    /// The counterclockwise rotation of the shape around its origin, in radians.
+    pub rotation: f32,
+    /// The shape, relative to its origin.
+    pub shape: MapShape,
+}
+
+/// The collision shapes of the tiles of a chunk.
+#[derive(Component, Clone, Debug, Default, PartialEq, Deref, DerefMut, Reflect)]
+#[reflect(Component, Clone, Debug, Default, PartialEq)]
+pub struct TileColliders(pub Vec<TileCollider>);
+
+/// The values of an LDtk `IntGrid` layer, which are commonly used for collisions.
+///
+/// The entity of the layer is placed at the top-left corner of the grid.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Clone, Debug, Default, PartialEq)]
+pub struct IntGrid {
+    /// The number of cells in each direction.
+    pub size: UVec2,
+    /// The size of a cell.
+    pub cell_size: Vec2,
+    /// The value of each cell, row by row from the top, where 0 is an empty cell.
+    pub values: Vec<i32>,
+}
+
+impl IntGrid {
+    /// Returns the value of the cell at `position`, counting rows from the top, or 0 if it's out
+    /// of the grid.
+    pub fn get(&self, position: UVec2) -> i32 {
+        if position.x >= self.size.x || position.y >= self.size.y {
+            return 0;
+        }
+        self.values
+            .get((position.y * self.size.x + position.x) as usize)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns the center of the cell at `position`, relative to the entity of the layer.
+    pub fn cell_center(&self, position: UVec2) -> Vec2 {
+        (position.as_vec2() + 0.5) * self.cell_size * Vec2::new(1.0, -1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn int_grid() {
+        let grid = IntGrid {
+            size: UVec2::new(2, 2),
+            cell_size: Vec2::splat(16.0),
+            values: vec![0, 1, 2, 3],
+        };
+        assert_eq!(grid.get(UVec2::new(1, 0)), 1);
+        assert_eq!(grid.get(UVec2::new(0, 1)), 2);
+        assert_eq!(grid.get(UVec2::new(2, 0)), 0);
+        assert_eq!(grid.cell_center(UVec2::new(1, 1)), Vec2::new(24.0, -24.0));
+    }
+}
@@ -0,0 +1,52 @@
+//! Labels that can be used to load part of a Tiled or LDtk map
+
+use bevy_asset::AssetPath;
+
+/// Labels that can be used to load part of a Tiled or LDtk map
+///
+/// You can use [`TilemapAssetLabel::from_asset`] to add it to an asset path
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_asset::prelude::*;
+/// # use bevy_scene::prelude::*;
+/// # use bevy_tilemap_import::prelude::*;
+///
+/// fn spawn_level(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     commands.spawn(SceneRoot(asset_server.load(TilemapAssetLabel::Scene.from_asset("maps/level.tmx"))));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TilemapAssetLabel {
+    /// `Scene`: the layers of the map as a Bevy [`Scene`](bevy_scene::Scene)
+    Scene,
+    /// `Tileset{}`: the tiles of a tileset as an array texture, as a Bevy [`Image`](bevy_image::Image)
+    Tileset(usize),
+}
+
+impl core::fmt::Display for TilemapAssetLabel {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TilemapAssetLabel::Scene => f.write_str("Scene"),
+            TilemapAssetLabel::Tileset(index) => f.write_str(&format!("Tileset{index}")),
+        }
+    }
+}
+
+impl TilemapAssetLabel {
+    /// Add this label to an asset path
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_asset::prelude::*;
+    /// # use bevy_image::Image;
+    /// # use bevy_tilemap_import::prelude::*;
+    ///
+    /// fn load_tileset(asset_server: Res<AssetServer>) {
+    ///     let tileset: Handle<Image> = asset_server.load(TilemapAssetLabel::Tileset(0).from_asset("maps/level.ldtk"));
+    /// }
+    /// ```
+    pub fn from_asset(&self, path: impl Into<AssetPath<'static>>) -> AssetPath<'static> {
+        path.into().with_label(self.to_string())
+    }
+}
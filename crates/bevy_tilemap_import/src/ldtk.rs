@@ -0,0 +1,671 @@
+//! Loading of [LDtk](https://ldtk.io) projects, saved as `.ldtk` files.
+
+use bevy_asset::{io::Reader, AssetLoader, AssetPath, LoadContext};
+use bevy_color::Color;
+use bevy_ecs::{
+    entity::Entity,
+    reflect::AppTypeRegistry,
+    world::{FromWorld, World},
+};
+use bevy_math::{IVec2, Rect, UVec2, Vec2};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{TypeRegistry, TypeRegistryArc};
+use bevy_scene::Scene;
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::warn;
+
+use crate::{
+    loader::{
+        flip_y, layer_color, load_tileset, parse_color, resolve_path, LayerTile, MapBuilder,
+        TileGrid, TilesetGrid,
+    },
+    reflect::insert_properties,
+    IntGrid, MapObject, MapProperty, MapShape, Tilemap, TilemapAssetLabel, TilemapError,
+    TilemapLoaderSettings,
+};
+
+/// Loads [LDtk](https://ldtk.io) projects (`.ldtk`) as a [`Tilemap`].
+///
+/// Every level of the project is spawned at its position in the world, and levels saved in
+/// separate files (`.ldtkl`) are loaded along with the project.
+pub struct LdtkLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for LdtkLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        LdtkLoader {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Project {
+    defs: Definitions,
+    #[serde(default)]
+    levels: Vec<Level>,
+    /// The worlds of projects that have multiple worlds, in which case `levels` is empty.
+    #[serde(default)]
+    worlds: Vec<LdtkWorld>,
+    world_layout: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Definitions {
+    tilesets: Vec<TilesetDefinition>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TilesetDefinition {
+    uid: i64,
+    identifier: String,
+    /// The path of the image, which isn't set for the internal icons of LDtk.
+    rel_path: Option<String>,
+    tile_grid_size: u32,
+    #[serde(default)]
+    spacing: u32,
+    #[serde(default)]
+    padding: u32,
+    #[serde(rename = "__cWid")]
+    columns: u32,
+    #[serde(rename = "__cHei")]
+    rows: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LdtkWorld {
+    identifier: String,
+    levels: Vec<Level>,
+    world_layout: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Level {
+    identifier: String,
+    world_x: i32,
+    world_y: i32,
+    px_wid: i32,
+    px_hei: i32,
+    /// The layers, from the top down, which aren't set for levels saved in separate files.
+    layer_instances: Option<Vec<LayerInstance>>,
+    external_rel_path: Option<String>,
+    #[serde(default)]
+    field_instances: Vec<FieldInstance>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LayerInstance {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    #[serde(rename = "__type")]
+    kind: String,
+    #[serde(rename = "__cWid")]
+    columns: u32,
+    #[serde(rename = "__cHei")]
+    rows: u32,
+    #[serde(rename = "__gridSize")]
+    grid_size: u32,
+    #[serde(rename = "__opacity", default = "one")]
+    opacity: f32,
+    #[serde(rename = "__pxTotalOffsetX", default)]
+    offset_x: i32,
+    #[serde(rename = "__pxTotalOffsetY", default)]
+    offset_y: i32,
+    #[serde(rename = "__tilesetDefUid")]
+    tileset_uid: Option<i64>,
+    #[serde(default = "yes")]
+    visible: bool,
+    #[serde(default)]
+    int_grid_csv: Vec<i32>,
+    #[serde(default)]
+    auto_layer_tiles: Vec<TileInstance>,
+    #[serde(default)]
+    grid_tiles: Vec<TileInstance>,
+    #[serde(default)]
+    entity_instances: Vec<EntityInstance>,
+}
+
+#[derive(Deserialize)]
+struct TileInstance {
+    /// The position of the tile in the layer, in pixels.
+    px: [i32; 2],
+    /// The index of the tile in its tileset.
+    t: u32,
+    /// Whether the tile is flipped horizontally (1) or vertically (2).
+    #[serde(default)]
+    f: u8,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EntityInstance {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    #[serde(rename = "__pivot")]
+    pivot: [f32; 2],
+    #[serde(rename = "__tile")]
+    tile: Option<TilesetRect>,
+    /// The position of the pivot of the entity in the layer, in pixels.
+    px: [f32; 2],
+    width: f32,
+    height: f32,
+    #[serde(default)]
+    field_instances: Vec<FieldInstance>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TilesetRect {
+    tileset_uid: i64,
+    x: u32,
+    y: u32,
+}
+
+#[derive(Deserialize)]
+struct FieldInstance {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    #[serde(rename = "__type")]
+    kind: String,
+    #[serde(rename = "__value")]
+    value: Value,
+}
+
+fn one() -> f32 {
+    1.0
+}
+
+fn yes() -> bool {
+    true
+}
+
+impl AssetLoader for LdtkLoader {
+    type Asset = Tilemap;
+    type Settings = TilemapLoaderSettings;
+    type Error = TilemapError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        settings: &TilemapLoaderSettings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Tilemap, TilemapError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let project: Project = serde_json::from_slice(&bytes)?;
+
+        let mut builder = MapBuilder::new();
+        let mut tilesets = HashMap::default();
+        for tileset in &project.defs.tilesets {
+            let Some(rel_path) = &tileset.rel_path else {
+                continue;
+            };
+            let grid = TilesetGrid {
+                tile_size: UVec2::splat(tileset.tile_grid_size),
+                margin: tileset.padding,
+                spacing: tileset.spacing,
+                columns: tileset.columns,
+                count: tileset.columns * tileset.rows,
+            };
+            if grid.count > u16::MAX as u32 {
+                return Err(TilemapError::Invalid(format!(
+                    "tileset {} has more than {} tiles",
+                    tileset.identifier,
+                    u16::MAX
+                )));
+            }
+            let path = resolve_path(load_context, None, rel_path)?;
+            let index = builder.tilesets.len();
+            let handle = load_tileset(load_context, path, &grid, settings, index).await?;
+            builder.tilesets.push(handle);
+            tilesets.insert(tileset.uid, (index, grid));
+        }
+
+        let mut worlds = Vec::new();
+        if project.worlds.is_empty() {
+            worlds.push((None, project.world_layout, project.levels));
+        } else {
+            for world in project.worlds {
+                worlds.push((Some(world.identifier), world.world_layout, world.levels));
+            }
+        }
+        // Levels saved in separate files are read up front, since levels are spawned synchronously.
+        for (_, _, levels) in &mut worlds {
+            for level in levels {
+                if level.layer_instances.is_some() {
+                    continue;
+                }
+                let Some(external_rel_path) = &level.external_rel_path else {
+                    continue;
+                };
+                let path = resolve_path(load_context, None, external_rel_path)?;
+                let bytes = load_context.read_asset_bytes(path).await?;
+                *level = serde_json::from_slice(&bytes)?;
+            }
+        }
+
+        let registry = self.type_registry.read();
+        let name = load_context
+            .path()
+            .path()
+            .file_stem()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut spawner = LevelSpawner {
+            builder,
+            tilesets,
+            registry: &registry,
+            settings,
+            path: load_context.path(),
+            warned_flips: false,
+        };
+        let root = spawner.builder.spawn(None, &name, Vec2::ZERO, 0.0, true);
+        for (identifier, layout, levels) in &worlds {
+            let parent = match identifier {
+                Some(identifier) => {
+                    spawner
+                        .builder
+                        .spawn(Some(root), identifier, Vec2::ZERO, 0.0, true)
+                }
+                None => root,
+            };
+            let mut offset = IVec2::ZERO;
+            for level in levels {
+                // Levels of linear layouts are placed one after another.
+                let position = match layout.as_deref() {
+                    Some("LinearHorizontal") => {
+                        offset.x += level.px_wid;
+                        IVec2::new(offset.x - level.px_wid, 0)
+                    }
+                    Some("LinearVertical") => {
+                        offset.y += level.px_hei;
+                        IVec2::new(0, offset.y - level.px_hei)
+                    }
+                    _ => IVec2::new(level.world_x, level.world_y),
+                };
+                spawner.spawn_level(level, parent, position);
+            }
+        }
+
+        let MapBuilder {
+            world, tilesets, ..
+        } = spawner.builder;
+        let scene =
+            load_context.add_labeled_asset(TilemapAssetLabel::Scene.to_string(), Scene::new(world));
+        Ok(Tilemap { scene, tilesets })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ldtk"]
+    }
+}
+
+/// Converts the value of a field to a property, where points are converted from grid cells to
+/// the center of the cell, relative to the level.
+fn field_value(kind: &str, value: &Value, grid_size: f32) -> Option<MapProperty> {
+    if let Some(kind) = kind
+        .strip_prefix("Array<")
+        .and_then(|kind| kind.strip_suffix('>'))
+    {
+        let values = value.as_array()?;
+        return Some(MapProperty::Array(
+            values
+                .iter()
+                .filter_map(|value| field_value(kind, value, grid_size))
+                .collect(),
+        ));
+    }
+    Some(match kind {
+        "Int" => MapProperty::Int(value.as_i64()?),
+        "Float" => MapProperty::Float(value.as_f64()?),
+        "Bool" => MapProperty::Bool(value.as_bool()?),
+        "Color" => MapProperty::Color(parse_color(value.as_str()?)?),
+        "Point" => {
+            let cell = Vec2::new(value["cx"].as_f64()? as f32, value["cy"].as_f64()? as f32);
+            MapProperty::Point(flip_y((cell + 0.5) * grid_size))
+        }
+        "EntityRef" => MapProperty::String(value["entityIid"].as_str()?.into()),
+        // Strings, multiline strings, file paths and enums.
+        _ => MapProperty::String(value.as_str()?.into()),
+    })
+}
+
+/// Converts the fields of a level or entity to properties, skipping fields without a value.
+fn field_properties(fields: &[FieldInstance], grid_size: f32) -> HashMap<String, MapProperty> {
+    fields
+        .iter()
+        .filter_map(|field| {
+            let value = field_value(&field.kind, &field.value, grid_size)?;
+            Some((field.identifier.clone(), value))
+        })
+        .collect()
+}
+
+/// Returns the bounds of an entity, relative to its pivot.
+fn entity_bounds(entity: &EntityInstance) -> Rect {
+    let [pivot_x, pivot_y] = entity.pivot;
+    Rect::new(
+        -pivot_x * entity.width,
+        -(1.0 - pivot_y) * entity.height,
+        (1.0 - pivot_x) * entity.width,
+        pivot_y * entity.height,
+    )
+}
+
+/// Spawns the levels of a project.
+struct LevelSpawner<'a> {
+    builder: MapBuilder,
+    /// The index in the [`Tilemap`] and the layout of each tileset, by unique ID.
+    tilesets: HashMap<i64, (usize, TilesetGrid)>,
+    registry: &'a TypeRegistry,
+    settings: &'a TilemapLoaderSettings,
+    path: &'a AssetPath<'static>,
+    warned_flips: bool,
+}
+
+impl LevelSpawner<'_> {
+    /// Spawns a level with its top-left corner at `position`, in the pixels of the world.
+    fn spawn_level(&mut self, level: &Level, parent: Entity, position: IVec2) {
+        let entity = self.builder.spawn(
+            Some(parent),
+            &level.identifier,
+            flip_y(position.as_vec2()),
+            0.0,
+            true,
+        );
+        let grid_size = level
+            .layer_instances
+            .iter()
+            .flatten()
+            .map(|layer| layer.grid_size)
+            .next()
+            .unwrap_or(1) as f32;
+        let properties = field_properties(&level.field_instances, grid_size);
+        let mut level_entity = self.builder.world.entity_mut(entity);
+        insert_properties(&mut level_entity, "", properties, self.registry);
+
+        let layers = level.layer_instances.as_deref().unwrap_or_default();
+        for (index, layer) in layers.iter().enumerate() {
+            // Layers are listed from the top down.
+            self.spawn_layer(layer, entity, (layers.len() - index) as f32);
+        }
+    }
+
+    fn spawn_layer(&mut self, layer: &LayerInstance, level: Entity, z: f32) {
+        let entity = self.builder.spawn(
+            Some(level),
+            &layer.identifier,
+            flip_y(Vec2::new(layer.offset_x as f32, layer.offset_y as f32)),
+            z,
+            layer.visible,
+        );
+        let color = layer_color(Color::WHITE, layer.opacity, None);
+        let cell_size = layer.grid_size as f32;
+        if layer.kind == "IntGrid" {
+            self.builder.world.entity_mut(entity).insert(IntGrid {
+                size: UVec2::new(layer.columns, layer.rows),
+                cell_size: Vec2::splat(cell_size),
+                values: layer.int_grid_csv.clone(),
+            });
+        }
+
+        for instance in &layer.entity_instances {
+            let shape = MapShape::Rectangle(entity_bounds(instance));
+            let object = self.builder.spawn(
+                Some(entity),
+                &instance.identifier,
+                flip_y(Vec2::from(instance.px)),
+                0.0,
+                true,
+            );
+            let properties = field_properties(&instance.field_instances, cell_size);
+            let mut object_entity = self.builder.world.entity_mut(object);
+            object_entity.insert(MapObject {
+                class: instance.identifier.clone(),
+                shape: shape.clone(),
+            });
+            insert_properties(
+                &mut object_entity,
+                &instance.identifier,
+                properties,
+                self.registry,
+            );
+            if let (Some(rect), MapShape::Rectangle(bounds)) = (&instance.tile, shape) {
+                let Some((tileset, grid)) = self.tilesets.get(&rect.tileset_uid) else {
+                    continue;
+                };
+                let step = grid.tile_size + grid.spacing;
+                let cell = (UVec2::new(rect.x, rect.y).saturating_sub(UVec2::splat(grid.margin)))
+                    / step.max(UVec2::ONE);
+                let tile = LayerTile {
+                    tileset: *tileset,
+                    index: (cell.y * grid.columns + cell.x) as u16,
+                };
+                let size = bounds.size().round().as_uvec2();
+                self.builder
+                    .spawn_tile(object, tile, size, bounds.center(), color);
+            }
+        }
+
+        let tiles = if layer.grid_tiles.is_empty() {
+            &layer.auto_layer_tiles
+        } else {
+            &layer.grid_tiles
+        };
+        let tileset = layer
+            .tileset_uid
+            .and_then(|uid| self.tilesets.get(&uid))
+            .map(|(tileset, _)| *tileset);
+        let (Some(tileset), false) = (tileset, tiles.is_empty()) else {
+            return;
+        };
+        // Several tiles can be stacked in the same cell, so each additional tile is placed in the
+        // grid of a chunk layer above.
+        let size = UVec2::new(layer.columns, layer.rows);
+        let mut grids: Vec<TileGrid> = Vec::new();
+        for tile in tiles {
+            if tile.f != 0 && !self.warned_flips {
+                warn!(
+                    "Flipped tiles of {} aren't supported, and are drawn as is",
+                    self.path
+                );
+                self.warned_flips = true;
+            }
+            let cell = IVec2::from(tile.px).div_euclid(IVec2::splat(layer.grid_size.max(1) as i32));
+            let Ok(cell) = UVec2::try_from(cell) else {
+                continue;
+            };
+            let layer_tile = Some(LayerTile {
+                tileset,
+                index: tile.t as u16,
+            });
+            let slot = grids
+                .iter_mut()
+                .find_map(|grid| grid.get_mut(cell).filter(|slot| slot.is_none()));
+            match slot {
+                Some(slot) => *slot = layer_tile,
+                None => {
+                    let mut grid = TileGrid::new(IVec2::ZERO, size);
+                    if let Some(slot) = grid.get_mut(cell) {
+                        *slot = layer_tile;
+                        grids.push(grid);
+                    }
+                }
+            }
+        }
+        let tile_size = UVec2::splat(layer.grid_size);
+        for (index, grid) in grids.iter().enumerate() {
+            let parent = if index == 0 {
+                entity
+            } else {
+                // Stacked tiles are drawn slightly above, without reaching the next layer.
+                self.builder.spawn(
+                    Some(entity),
+                    "",
+                    Vec2::ZERO,
+                    index as f32 / (grids.len() + 1) as f32,
+                    true,
+                )
+            };
+            self.builder
+                .spawn_chunks(parent, grid, tile_size, color, self.settings);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{hierarchy::ChildOf, name::Name};
+    use bevy_sprite_render::TilemapChunkTileData;
+
+    use super::*;
+    use crate::MapProperties;
+
+    const LEVEL: &str = r##"{
+        "identifier": "Level_0",
+        "worldX": 256,
+        "worldY": 128,
+        "pxWid": 32,
+        "pxHei": 32,
+        "fieldInstances": [
+            { "__identifier": "music", "__type": "String", "__value": "cave.ogg" }
+        ],
+        "layerInstances": [
+            {
+                "__identifier": "Entities",
+                "__type": "Entities",
+                "__cWid": 2,
+                "__cHei": 2,
+                "__gridSize": 16,
+                "__opacity": 1,
+                "__pxTotalOffsetX": 0,
+                "__pxTotalOffsetY": 0,
+                "__tilesetDefUid": null,
+                "visible": true,
+                "entityInstances": [
+                    {
+                        "__identifier": "Player",
+                        "__pivot": [0.5, 1],
+                        "__tile": null,
+                        "px": [8, 16],
+                        "width": 16,
+                        "height": 16,
+                        "fieldInstances": [
+                            { "__identifier": "lives", "__type": "Int", "__value": 3 },
+                            { "__identifier": "target", "__type": "Point", "__value": { "cx": 1, "cy": 0 } },
+                            { "__identifier": "tags", "__type": "Array<LocalEnum.Tag>", "__value": ["Hero"] },
+                            { "__identifier": "unset", "__type": "Float", "__value": null }
+                        ]
+                    }
+                ]
+            },
+            {
+                "__identifier": "Collisions",
+                "__type": "IntGrid",
+                "__cWid": 2,
+                "__cHei": 2,
+                "__gridSize": 16,
+                "__opacity": 0.5,
+                "__pxTotalOffsetX": 0,
+                "__pxTotalOffsetY": 0,
+                "__tilesetDefUid": 7,
+                "visible": true,
+                "intGridCsv": [1, 0, 0, 1],
+                "autoLayerTiles": [
+                    { "px": [0, 0], "src": [0, 0], "f": 0, "t": 2, "d": [0], "a": 1 },
+                    { "px": [0, 0], "src": [16, 0], "f": 0, "t": 3, "d": [0], "a": 1 },
+                    { "px": [16, 16], "src": [0, 0], "f": 0, "t": 1, "d": [3], "a": 1 }
+                ]
+            }
+        ]
+    }"##;
+
+    #[test]
+    fn spawn_level() {
+        let level: Level = serde_json::from_str(LEVEL).unwrap();
+        let mut builder = MapBuilder::new();
+        builder.tilesets.push(Default::default());
+        let grid = TilesetGrid {
+            tile_size: UVec2::splat(16),
+            margin: 0,
+            spacing: 0,
+            columns: 4,
+            count: 4,
+        };
+        let registry = TypeRegistry::default();
+        let path = AssetPath::from("world.ldtk");
+        let mut spawner = LevelSpawner {
+            builder,
+            tilesets: HashMap::from_iter([(7, (0, grid))]),
+            registry: &registry,
+            settings: &TilemapLoaderSettings::default(),
+            path: &path,
+            warned_flips: false,
+        };
+        let root = spawner.builder.spawn(None, "world", Vec2::ZERO, 0.0, true);
+        spawner.spawn_level(&level, root, IVec2::new(level.world_x, level.world_y));
+        let world = &mut spawner.builder.world;
+
+        let (level, properties) = world
+            .query::<(&Name, &MapProperties)>()
+            .iter(world)
+            .find(|(name, _)| name.as_str() == "Level_0")
+            .unwrap();
+        assert_eq!(level.as_str(), "Level_0");
+        assert_eq!(properties["music"], MapProperty::String("cave.ogg".into()));
+
+        let (object, properties, transform) = world
+            .query::<(
+                &MapObject,
+                &MapProperties,
+                &bevy_transform::components::Transform,
+            )>()
+            .single(world)
+            .unwrap();
+        assert_eq!(object.class, "Player");
+        assert_eq!(
+            object.shape,
+            MapShape::Rectangle(Rect::new(-8.0, 0.0, 8.0, 16.0))
+        );
+        assert_eq!(transform.translation, Vec2::new(8.0, -16.0).extend(0.0));
+        assert_eq!(properties["lives"], MapProperty::Int(3));
+        assert_eq!(
+            properties["target"],
+            MapProperty::Point(Vec2::new(24.0, -8.0))
+        );
+        assert_eq!(
+            properties["tags"],
+            MapProperty::Array(vec![MapProperty::String("Hero".into())])
+        );
+        assert!(!properties.contains_key("unset"));
+
+        let int_grid = world.query::<&IntGrid>().single(world).unwrap();
+        assert_eq!(int_grid.get(UVec2::new(1, 1)), 1);
+
+        // The stacked tile is in a second chunk, above the first one.
+        let mut chunks = world
+            .query::<(&TilemapChunkTileData, &ChildOf)>()
+            .iter(world)
+            .map(|(tiles, child_of)| {
+                let indices = tiles
+                    .iter()
+                    .map(|tile| tile.map(|tile| tile.tileset_index))
+                    .collect::<Vec<_>>();
+                (indices, child_of.parent())
+            })
+            .collect::<Vec<_>>();
+        chunks.sort();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].0, [Some(2), None, None, Some(1)]);
+        assert_eq!(chunks[1].0, [Some(3), None, None, None]);
+        assert_ne!(chunks[0].1, chunks[1].1);
+    }
+}
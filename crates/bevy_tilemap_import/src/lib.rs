@@ -0,0 +1,114 @@
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![forbid(unsafe_code)]
+#![doc(
+    html_logo_url = "https://bevy.org/assets/icon.png",
+    html_favicon_url = "https://bevy.org/assets/icon.png"
+)]
+
+//! Plugin providing [`AssetLoader`](bevy_asset::AssetLoader)s and type definitions
+//! for loading [Tiled](https://www.mapeditor.org) and [LDtk](https://ldtk.io) maps in Bevy.
+//!
+//! Loading a `.tmx` or `.ldtk` file produces a [`Scene`](bevy_scene::Scene) with an entity for each
+//! layer of the map. Tile layers are split into chunks that are drawn with
+//! [`TilemapChunk`](bevy_sprite_render::TilemapChunk), and the tiles of each tileset are loaded as
+//! an array texture.
+//!
+//! # Quick Start
+//!
+//! Here's how to spawn a map
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_asset::prelude::*;
+//! # use bevy_scene::prelude::*;
+//! # use bevy_tilemap_import::prelude::*;
+//!
+//! fn spawn_map(mut commands: Commands, asset_server: Res<AssetServer>) {
+//!     commands.spawn(SceneRoot(
+//!         asset_server.load(TilemapAssetLabel::Scene.from_asset("maps/level.tmx")),
+//!     ));
+//! }
+//! ```
+//!
+//! The origin of the map is its top-left corner, and the y axis points up as everywhere in Bevy,
+//! so maps extend downwards from their origin.
+//!
+//! # Objects and collisions
+//!
+//! Objects of Tiled object layers and entities of LDtk levels are spawned with a [`MapObject`]
+//! component that holds their class and shape, and their custom properties are stored in
+//! [`MapProperties`]. When a component type is registered with the same name as the class of an
+//! object, or as the class of one of its properties, it's inserted as well with the fields set from
+//! the matching properties:
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_reflect::prelude::*;
+//! // Objects of class `Enemy`, or LDtk entities named `Enemy`, get this component.
+//! #[derive(Component, Reflect, Default)]
+//! #[reflect(Component, Default)]
+//! struct Enemy {
+//!     health: u32,
+//!     speed: f32,
+//! }
+//! ```
+//!
+//! Collision shapes of Tiled tiles are added to the chunks that contain them as [`TileColliders`],
+//! and LDtk `IntGrid` layers have an [`IntGrid`] component with the value of each cell.
+//!
+//! # Supported features
+//!
+//! Orthogonal Tiled maps are supported, including infinite maps, layer groups, external tilesets
+//! and object templates. Tile data can be stored as CSV or base64, optionally compressed with zlib
+//! or gzip. Image layers, image collection tilesets and flipped tiles aren't supported, and tiles
+//! are drawn at the size of the grid of the map.
+//!
+//! LDtk projects are supported, including multiple worlds and levels saved in separate files. Tiles
+//! that are stacked in the same cell are drawn with additional chunks, and flipped tiles aren't
+//! supported.
+//!
+//! Each tile of a tileset is a layer of its array texture, so the number of tiles of a tileset is
+//! limited by the maximum number of texture array layers of the device.
+
+extern crate alloc;
+
+mod assets;
+mod components;
+mod label;
+mod ldtk;
+mod loader;
+mod reflect;
+mod tiled;
+mod xml;
+
+use bevy_app::prelude::*;
+use bevy_asset::AssetApp;
+
+/// The tilemap import prelude.
+///
+/// This includes the most common types in this crate, re-exported for your convenience.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{
+        assets::Tilemap,
+        components::{IntGrid, MapObject, MapProperties, MapProperty, MapShape, TileColliders},
+        label::TilemapAssetLabel,
+    };
+}
+
+pub use {
+    assets::*, components::*, label::TilemapAssetLabel, ldtk::LdtkLoader, loader::*,
+    tiled::TiledLoader, xml::XmlError,
+};
+
+/// Adds support for Tiled and LDtk map loading to the app.
+#[derive(Default)]
+pub struct TilemapImportPlugin;
+
+impl Plugin for TilemapImportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Tilemap>()
+            .init_asset_loader::<TiledLoader>()
+            .init_asset_loader::<LdtkLoader>();
+    }
+}
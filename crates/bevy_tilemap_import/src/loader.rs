@@ -0,0 +1,518 @@
+use alloc::borrow::Cow;
+
+use bevy_asset::{
+    AssetPath, Handle, LoadContext, LoadDirectError, ParseAssetPathError, ReadAssetBytesError,
+    RenderAssetUsages,
+};
+use bevy_camera::visibility::Visibility;
+use bevy_color::{Color, ColorToComponents, LinearRgba};
+use bevy_ecs::{entity::Entity, hierarchy::ChildOf, name::Name, world::World};
+use bevy_image::{Image, ImageSampler};
+use bevy_math::{IVec2, UVec2, Vec2, Vec4};
+use bevy_platform::collections::HashMap;
+use bevy_sprite_render::{AlphaMode2d, TileData, TilemapChunk, TilemapChunkTileData};
+use bevy_transform::components::Transform;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use wgpu_types::{
+    Extent3d, TextureDimension, TextureFormat, TextureViewDescriptor, TextureViewDimension,
+};
+
+use crate::{xml::XmlError, TileCollider, TileColliders, TilemapAssetLabel};
+
+/// An error that occurs when loading a Tiled or LDtk map.
+#[derive(Error, Debug)]
+pub enum TilemapError {
+    /// Failed to read the file.
+    #[error("failed to read map: {0}")]
+    Io(#[from] std::io::Error),
+    /// The XML of a Tiled file is invalid.
+    #[error(transparent)]
+    Xml(#[from] XmlError),
+    /// The JSON of an LDtk file is invalid.
+    #[error("invalid LDtk file: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The contents of the map are invalid or unsupported.
+    #[error("invalid map: {0}")]
+    Invalid(String),
+    /// A file referenced by the map has an invalid path.
+    #[error("invalid path {0}: {1}")]
+    InvalidPath(String, ParseAssetPathError),
+    /// Failed to read a tileset, template or level that is stored in a separate file.
+    #[error("failed to read a file referenced by the map: {0}")]
+    ReadFile(#[from] ReadAssetBytesError),
+    /// Failed to load the image of a tileset.
+    #[error("failed to load tileset image: {0}")]
+    LoadImage(#[from] Box<LoadDirectError>),
+    /// The image of a tileset doesn't match the size of its tiles, or has an unsupported format.
+    #[error("tileset image {0} doesn't contain all the tiles of the tileset")]
+    InvalidTilesetImage(AssetPath<'static>),
+}
+
+/// Specifies optional settings for processing Tiled and LDtk maps at load time.
+///
+/// To use, load the asset with [`AssetServer::load_with_settings`](bevy_asset::AssetServer::load_with_settings):
+///
+/// ```no_run
+/// # use bevy_asset::{AssetServer, Handle};
+/// # use bevy_tilemap_import::*;
+/// # use bevy_image::ImageSampler;
+/// # let asset_server: AssetServer = panic!();
+/// let tilemap_handle: Handle<Tilemap> = asset_server.load_with_settings(
+///     "maps/level.tmx",
+///     |s: &mut TilemapLoaderSettings| {
+///         s.sampler = ImageSampler::nearest();
+///     },
+/// );
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TilemapLoaderSettings {
+    /// The size of the chunks that tile layers are split into, in tiles.
+    ///
+    /// Each chunk is rendered as a single mesh, so larger chunks mean fewer draw calls, while
+    /// smaller chunks are cheaper to update and cull.
+    pub chunk_size: UVec2,
+    /// The sampler of the tileset images.
+    pub sampler: ImageSampler,
+    /// Where the tileset images are retained.
+    pub asset_usage: RenderAssetUsages,
+}
+
+impl Default for TilemapLoaderSettings {
+    fn default() -> Self {
+        Self {
+            chunk_size: UVec2::splat(32),
+            sampler: ImageSampler::Default,
+            asset_usage: RenderAssetUsages::default(),
+        }
+    }
+}
+
+/// How the tiles of a tileset are laid out in its image.
+#[derive(Clone, Debug)]
+pub(crate) struct TilesetGrid {
+    pub tile_size: UVec2,
+    /// The number of pixels around the tiles.
+    pub margin: u32,
+    /// The number of pixels between tiles.
+    pub spacing: u32,
+    pub columns: u32,
+    pub count: u32,
+}
+
+impl TilesetGrid {
+    /// Returns the position of the top-left pixel of a tile.
+    pub(crate) fn tile_position(&self, tile: u32) -> UVec2 {
+        let step = self.tile_size + self.spacing;
+        UVec2::splat(self.margin) + UVec2::new(tile % self.columns, tile / self.columns) * step
+    }
+}
+
+/// Rearranges the tiles of a tileset image into an array texture with one layer per tile, which
+/// is what [`TilemapChunk`] expects. Returns `None` if a tile is outside of the image, or if its
+/// format can't be converted.
+pub(crate) fn tileset_array(
+    image: &Image,
+    grid: &TilesetGrid,
+    settings: &TilemapLoaderSettings,
+) -> Option<Image> {
+    let image = match image.texture_descriptor.format {
+        TextureFormat::Rgba8UnormSrgb | TextureFormat::Rgba8Unorm => Cow::Borrowed(image),
+        _ => Cow::Owned(image.convert(TextureFormat::Rgba8UnormSrgb)?),
+    };
+    let data = image.data.as_ref()?;
+    let (width, height) = (image.width(), image.height());
+    if grid.columns == 0 || grid.count == 0 || grid.tile_size.cmpeq(UVec2::ZERO).any() {
+        return None;
+    }
+    let row_length = grid.tile_size.x as usize * 4;
+    let mut tiles = Vec::with_capacity(row_length * (grid.tile_size.y * grid.count) as usize);
+    for tile in 0..grid.count {
+        let position = grid.tile_position(tile);
+        if position.x + grid.tile_size.x > width || position.y + grid.tile_size.y > height {
+            return None;
+        }
+        for row in position.y..position.y + grid.tile_size.y {
+            let start = (row as usize * width as usize + position.x as usize) * 4;
+            tiles.extend_from_slice(data.get(start..start + row_length)?);
+        }
+    }
+    let mut array = Image::new(
+        Extent3d {
+            width: grid.tile_size.x,
+            height: grid.tile_size.y * grid.count,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        tiles,
+        image.texture_descriptor.format,
+        settings.asset_usage,
+    );
+    array.reinterpret_stacked_2d_as_array(grid.count).ok()?;
+    // A single layer would be viewed as a 2D texture otherwise.
+    array.texture_view_descriptor = Some(TextureViewDescriptor {
+        dimension: Some(TextureViewDimension::D2Array),
+        ..Default::default()
+    });
+    array.sampler = settings.sampler.clone();
+    Some(array)
+}
+
+/// Loads the image of a tileset, and adds its tiles as an array texture labeled with
+/// [`TilemapAssetLabel::Tileset`].
+pub(crate) async fn load_tileset(
+    load_context: &mut LoadContext<'_>,
+    path: AssetPath<'static>,
+    grid: &TilesetGrid,
+    settings: &TilemapLoaderSettings,
+    index: usize,
+) -> Result<Handle<Image>, TilemapError> {
+    let image = load_context
+        .loader()
+        .immediate()
+        .load::<Image>(path.clone())
+        .await
+        .map_err(Box::new)?;
+    let array = tileset_array(image.get(), grid, settings)
+        .ok_or(TilemapError::InvalidTilesetImage(path))?;
+    Ok(load_context.add_labeled_asset(TilemapAssetLabel::Tileset(index).to_string(), array))
+}
+
+/// Resolves a path relative to the file being loaded, or to `base` if set.
+pub(crate) fn resolve_path(
+    load_context: &LoadContext,
+    base: Option<&AssetPath<'static>>,
+    path: &str,
+) -> Result<AssetPath<'static>, TilemapError> {
+    base.unwrap_or(load_context.path())
+        .resolve_embed(path)
+        .map_err(|err| TilemapError::InvalidPath(path.into(), err))
+}
+
+/// Parses a color written as `#RRGGBB` or `#AARRGGBB`, as in Tiled and LDtk.
+pub(crate) fn parse_color(text: &str) -> Option<Color> {
+    let hex = text.trim();
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    let value = u32::from_str_radix(hex, 16).ok()?;
+    let [alpha, red, green, blue] = match hex.len() {
+        6 => (value | 0xFF00_0000).to_be_bytes(),
+        8 => value.to_be_bytes(),
+        _ => return None,
+    };
+    Some(Color::srgba_u8(red, green, blue, alpha))
+}
+
+/// Converts a position in the coordinates of a map, where the y axis points down, to Bevy
+/// coordinates.
+pub(crate) fn flip_y(position: Vec2) -> Vec2 {
+    Vec2::new(position.x, -position.y)
+}
+
+/// A tile placed on a layer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct LayerTile {
+    /// The index of the tileset in the [`Tilemap`](crate::Tilemap).
+    pub tileset: usize,
+    /// The index of the tile in its tileset.
+    pub index: u16,
+}
+
+/// The tiles of a layer, in a grid whose rows go from the top down.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TileGrid {
+    /// The position of the top-left tile, in tiles. This may be negative in infinite maps.
+    pub origin: IVec2,
+    pub size: UVec2,
+    pub tiles: Vec<Option<LayerTile>>,
+}
+
+impl TileGrid {
+    pub(crate) fn new(origin: IVec2, size: UVec2) -> Self {
+        Self {
+            origin,
+            size,
+            tiles: vec![None; size.element_product() as usize],
+        }
+    }
+
+    pub(crate) fn get_mut(&mut self, position: UVec2) -> Option<&mut Option<LayerTile>> {
+        if position.x >= self.size.x || position.y >= self.size.y {
+            return None;
+        }
+        self.tiles
+            .get_mut((position.y * self.size.x + position.x) as usize)
+    }
+}
+
+/// Builds the world of the scene of a map.
+pub(crate) struct MapBuilder {
+    pub world: World,
+    pub tilesets: Vec<Handle<Image>>,
+    /// The collision shapes of tiles, by tileset and tile index, relative to the top-left corner
+    /// of the tile.
+    pub colliders: HashMap<(usize, u16), Vec<TileCollider>>,
+}
+
+impl MapBuilder {
+    pub(crate) fn new() -> Self {
+        let mut world = World::new();
+        // Scenes copy the components of an entity in the order of their IDs, and the tile data of a
+        // chunk must be present when its `TilemapChunk` is inserted.
+        world.register_component::<TilemapChunkTileData>();
+        world.register_component::<TilemapChunk>();
+        Self {
+            world,
+            tilesets: Vec::new(),
+            colliders: HashMap::default(),
+        }
+    }
+
+    /// Spawns an entity for a layer or an object, as a child of `parent` if set.
+    pub(crate) fn spawn(
+        &mut self,
+        parent: Option<Entity>,
+        name: &str,
+        translation: Vec2,
+        z: f32,
+        visible: bool,
+    ) -> Entity {
+        let mut entity = self.world.spawn((
+            Transform::from_translation(translation.extend(z)),
+            if visible {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            },
+        ));
+        if !name.is_empty() {
+            entity.insert(Name::new(name.to_owned()));
+        }
+        if let Some(parent) = parent {
+            entity.insert(ChildOf(parent));
+        }
+        entity.id()
+    }
+
+    /// Spawns the chunks of a tile layer as children of `layer`, which is placed at the top-left
+    /// corner of the tile at the origin of the map.
+    ///
+    /// A chunk can only use a single tileset, so an entity is spawned for each tileset used in each
+    /// chunk.
+    pub(crate) fn spawn_chunks(
+        &mut self,
+        layer: Entity,
+        grid: &TileGrid,
+        tile_size: UVec2,
+        color: Color,
+        settings: &TilemapLoaderSettings,
+    ) {
+        let chunk_size = settings.chunk_size.max(UVec2::ONE);
+        let chunk_count = (grid.size + chunk_size - 1) / chunk_size;
+        for chunk_y in 0..chunk_count.y {
+            for chunk_x in 0..chunk_count.x {
+                let start = UVec2::new(chunk_x, chunk_y) * chunk_size;
+                let size = chunk_size.min(grid.size - start);
+                let mut chunks: Vec<(usize, Vec<Option<TileData>>, Vec<TileCollider>)> = Vec::new();
+                let half_size = (size * tile_size).as_vec2() / 2.0;
+                for y in 0..size.y {
+                    for x in 0..size.x {
+                        let position = start + UVec2::new(x, y);
+                        let Some(tile) =
+                            grid.tiles[(position.y * grid.size.x + position.x) as usize]
+                        else {
+                            continue;
+                        };
+                        let chunk = match chunks
+                            .iter()
+                            .position(|(tileset, ..)| *tileset == tile.tileset)
+                        {
+                            Some(chunk) => chunk,
+                            None => {
+                                chunks.push((
+                                    tile.tileset,
+                                    vec![None; size.element_product() as usize],
+                                    Vec::new(),
+                                ));
+                                chunks.len() - 1
+                            }
+                        };
+                        let (_, tiles, colliders) = &mut chunks[chunk];
+                        tiles[(y * size.x + x) as usize] = Some(TileData {
+                            tileset_index: tile.index,
+                            color,
+                            visible: true,
+                        });
+                        let corner = (UVec2::new(x, y) * tile_size).as_vec2();
+                        let corner = Vec2::new(corner.x - half_size.x, half_size.y - corner.y);
+                        if let Some(shapes) = self.colliders.get(&(tile.tileset, tile.index)) {
+                            colliders.extend(shapes.iter().map(|collider| TileCollider {
+                                origin: corner + collider.origin,
+                                ..collider.clone()
+                            }));
+                        }
+                    }
+                }
+
+                let center =
+                    (grid.origin + start.as_ivec2()).as_vec2() * tile_size.as_vec2() + half_size;
+                for (tileset, tiles, colliders) in chunks {
+                    let mut entity = self.world.spawn((
+                        TilemapChunkTileData(tiles),
+                        TilemapChunk {
+                            chunk_size: size,
+                            tile_display_size: tile_size,
+                            tileset: self.tilesets[tileset].clone(),
+                            alpha_mode: AlphaMode2d::Blend,
+                        },
+                        Transform::from_translation(flip_y(center).extend(0.0)),
+                        Visibility::default(),
+                        ChildOf(layer),
+                    ));
+                    if !colliders.is_empty() {
+                        entity.insert(TileColliders(colliders));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl MapBuilder {
+    /// Spawns a chunk of a single tile as a child of `parent`, for objects that are drawn as a
+    /// tile, stretched to `size` and centered on `center`.
+    pub(crate) fn spawn_tile(
+        &mut self,
+        parent: Entity,
+        tile: LayerTile,
+        size: UVec2,
+        center: Vec2,
+        color: Color,
+    ) {
+        self.world.spawn((
+            TilemapChunkTileData(vec![Some(TileData {
+                tileset_index: tile.index,
+                color,
+                visible: true,
+            })]),
+            TilemapChunk {
+                chunk_size: UVec2::ONE,
+                tile_display_size: size,
+                tileset: self.tilesets[tile.tileset].clone(),
+                alpha_mode: AlphaMode2d::Blend,
+            },
+            Transform::from_translation(center.extend(0.0)),
+            Visibility::default(),
+            ChildOf(parent),
+        ));
+    }
+}
+
+/// Multiplies the color of the parent of a layer by the opacity and tint of the layer.
+pub(crate) fn layer_color(parent: Color, opacity: f32, tint: Option<Color>) -> Color {
+    let tint = tint.unwrap_or(Color::WHITE).to_linear().to_vec4()
+        * Vec4::new(1.0, 1.0, 1.0, opacity.clamp(0.0, 1.0));
+    LinearRgba::from_vec4(parent.to_linear().to_vec4() * tint).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colors() {
+        assert_eq!(parse_color("#ff0000"), Some(Color::srgb_u8(255, 0, 0)));
+        assert_eq!(
+            parse_color("#00112233"),
+            Some(Color::srgba_u8(0x11, 0x22, 0x33, 0))
+        );
+        assert_eq!(parse_color("red"), None);
+    }
+
+    #[test]
+    fn slice_tileset() {
+        // A 2x2 tileset of 1x1 tiles, with a margin of 1 and a spacing of 1.
+        let mut data = vec![0; 5 * 5 * 4];
+        for (tile, (x, y)) in [(1, 1), (3, 1), (1, 3), (3, 3)].into_iter().enumerate() {
+            data[(y * 5 + x) * 4] = tile as u8 + 1;
+        }
+        let image = Image::new(
+            Extent3d {
+                width: 5,
+                height: 5,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        );
+        let mut grid = TilesetGrid {
+            tile_size: UVec2::ONE,
+            margin: 1,
+            spacing: 1,
+            columns: 2,
+            count: 4,
+        };
+        let array = tileset_array(&image, &grid, &TilemapLoaderSettings::default()).unwrap();
+        assert_eq!(array.texture_descriptor.size.depth_or_array_layers, 4);
+        let data = array.data.unwrap();
+        assert_eq!(
+            data.chunks_exact(4)
+                .map(|pixel| pixel[0])
+                .collect::<Vec<_>>(),
+            [1, 2, 3, 4]
+        );
+
+        grid.count = 6;
+        assert!(tileset_array(&image, &grid, &TilemapLoaderSettings::default()).is_none());
+    }
+
+    #[test]
+    fn chunks() {
+        let mut builder = MapBuilder::new();
+        builder.tilesets = vec![Handle::default(), Handle::default()];
+        builder.colliders.insert(
+            (1, 0),
+            vec![TileCollider {
+                origin: Vec2::new(1.0, -1.0),
+                ..Default::default()
+            }],
+        );
+        let mut grid = TileGrid::new(IVec2::new(-1, 0), UVec2::new(3, 2));
+        *grid.get_mut(UVec2::new(0, 0)).unwrap() = Some(LayerTile {
+            tileset: 0,
+            index: 5,
+        });
+        *grid.get_mut(UVec2::new(2, 1)).unwrap() = Some(LayerTile {
+            tileset: 1,
+            index: 0,
+        });
+        let layer = builder.world.spawn_empty().id();
+        let settings = TilemapLoaderSettings {
+            chunk_size: UVec2::splat(2),
+            ..Default::default()
+        };
+        builder.spawn_chunks(layer, &grid, UVec2::splat(16), Color::WHITE, &settings);
+
+        let mut chunks = builder
+            .world
+            .query::<(&TilemapChunk, &TilemapChunkTileData, &Transform)>();
+        let mut chunks = chunks.iter(&builder.world).collect::<Vec<_>>();
+        chunks.sort_by(|a, b| a.2.translation.x.total_cmp(&b.2.translation.x));
+        assert_eq!(chunks.len(), 2);
+        let (chunk, tiles, transform) = chunks[0];
+        assert_eq!(chunk.chunk_size, UVec2::new(2, 2));
+        assert_eq!(tiles[0].unwrap().tileset_index, 5);
+        assert_eq!(tiles.iter().flatten().count(), 1);
+        assert_eq!(transform.translation, Vec2::new(0.0, -16.0).extend(0.0));
+        let (chunk, tiles, transform) = chunks[1];
+        assert_eq!(chunk.chunk_size, UVec2::new(1, 2));
+        assert_eq!(tiles[1].unwrap().tileset_index, 0);
+        assert_eq!(transform.translation, Vec2::new(24.0, -16.0).extend(0.0));
+
+        let mut colliders = builder.world.query::<&TileColliders>();
+        let colliders = colliders.single(&builder.world).unwrap();
+        // The tile is in the bottom row of the chunk.
+        assert_eq!(colliders[0].origin, Vec2::new(-8.0 + 1.0, 0.0 - 1.0));
+    }
+}
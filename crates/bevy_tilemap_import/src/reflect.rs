@@ -0,0 +1,196 @@
+//! Insertion of reflected components from the classes and custom properties of maps.
+
+use bevy_ecs::{reflect::ReflectComponent, world::EntityWorldMut};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{
+    std_traits::ReflectDefault, DynamicEnum, PartialReflect, ReflectMut, TypeRegistry,
+};
+use tracing::warn;
+
+use crate::{MapProperties, MapProperty};
+
+/// Inserts the custom properties of an entity as [`MapProperties`], along with the components
+/// of its class and of the properties whose value is of a custom class.
+pub(crate) fn insert_properties(
+    entity: &mut EntityWorldMut,
+    class: &str,
+    properties: HashMap<String, MapProperty>,
+    registry: &TypeRegistry,
+) {
+    insert_class_component(entity, class, &properties, registry);
+    for property in properties.values() {
+        if let MapProperty::Class { class, members } = property {
+            insert_class_component(entity, class, members, registry);
+        }
+    }
+    if !properties.is_empty() {
+        entity.insert(MapProperties(properties));
+    }
+}
+
+/// Inserts the component whose type is named `class`, with its fields set from `fields`.
+///
+/// The type may be named by its short or full type path. Classes without a matching component
+/// are skipped, since classes are often used for other purposes.
+pub(crate) fn insert_class_component(
+    entity: &mut EntityWorldMut,
+    class: &str,
+    fields: &HashMap<String, MapProperty>,
+    registry: &TypeRegistry,
+) {
+    if class.is_empty() {
+        return;
+    }
+    let Some(registration) = registry
+        .get_with_short_type_path(class)
+        .or_else(|| registry.get_with_type_path(class))
+    else {
+        return;
+    };
+    let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+        return;
+    };
+    let Some(reflect_default) = registration.data::<ReflectDefault>() else {
+        warn!(
+            "Skipping component {} of class {class}, which doesn't reflect `Default`",
+            registration.type_info().type_path()
+        );
+        return;
+    };
+    let mut component = reflect_default.default();
+    set_fields(component.as_partial_reflect_mut(), class, fields);
+    reflect_component.insert(entity, component.as_partial_reflect(), registry);
+}
+
+/// Sets the fields of a struct from the members of a class, skipping members without a field.
+fn set_fields(target: &mut dyn PartialReflect, class: &str, fields: &HashMap<String, MapProperty>) {
+    let ReflectMut::Struct(target) = target.reflect_mut() else {
+        if !fields.is_empty() {
+            warn!("Ignoring the properties of class {class}, which isn't a struct");
+        }
+        return;
+    };
+    for (name, value) in fields {
+        let Some(field) = target.field_mut(name) else {
+            continue;
+        };
+        if !set_value(field, value) {
+            warn!(
+                "Ignoring property {name} of class {class}, whose value {value:?} doesn't match the type {}",
+                field.reflect_type_path()
+            );
+        }
+    }
+}
+
+fn set<T: 'static>(target: &mut dyn PartialReflect, value: T) -> bool {
+    match target.try_downcast_mut::<T>() {
+        Some(target) => {
+            *target = value;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Sets an integer field of any type, as long as `value` fits in it.
+macro_rules! set_integer {
+    ($target:expr, $value:expr, $($ty:ty),*) => {
+        $(
+            if let Some(target) = $target.try_downcast_mut::<$ty>() {
+                return <$ty>::try_from($value).map(|value| *target = value).is_ok();
+            }
+        )*
+    };
+}
+
+/// Sets `target` to `value`, converting between numeric types. Returns `false` if the types don't
+/// match.
+fn set_value(target: &mut dyn PartialReflect, value: &MapProperty) -> bool {
+    match value {
+        MapProperty::Bool(value) => set(target, *value),
+        MapProperty::Int(value) => {
+            set_integer!(target, *value, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+            set(target, *value as f32) || set(target, *value as f64)
+        }
+        MapProperty::Float(value) => set(target, *value as f32) || set(target, *value),
+        MapProperty::String(value) => {
+            if let ReflectMut::Enum(target) = target.reflect_mut() {
+                // Enum values are set as unit variants.
+                return target
+                    .try_apply(&DynamicEnum::new(value.clone(), ()))
+                    .is_ok();
+            }
+            set(target, value.clone())
+        }
+        MapProperty::Color(value) => set(target, *value),
+        MapProperty::Point(value) => set(target, *value),
+        // Lists can't be built from their values without knowing the type of their items.
+        MapProperty::Array(_) => false,
+        MapProperty::Class { class, members } => {
+            set_fields(target, class, members);
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_color::Color;
+    use bevy_ecs::{component::Component, world::World};
+    use bevy_reflect::{prelude::ReflectDefault, Reflect};
+
+    use super::*;
+    use crate::loader::parse_color;
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component, Default)]
+    struct Enemy {
+        health: u32,
+        speed: f32,
+        kind: Kind,
+        tint: Color,
+    }
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    enum Kind {
+        #[default]
+        Walker,
+        Flyer,
+    }
+
+    #[test]
+    fn insert_component() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Enemy>();
+
+        let fields = HashMap::from_iter([
+            ("health".to_owned(), MapProperty::Int(20)),
+            ("speed".to_owned(), MapProperty::Float(1.5)),
+            ("kind".to_owned(), MapProperty::String("Flyer".into())),
+            (
+                "tint".to_owned(),
+                MapProperty::Color(parse_color("#80ff0000").unwrap()),
+            ),
+            ("unknown".to_owned(), MapProperty::Bool(true)),
+        ]);
+        let mut world = World::new();
+        let mut entity = world.spawn_empty();
+        insert_class_component(&mut entity, "Enemy", &fields, &registry);
+        let enemy = entity.get::<Enemy>().unwrap();
+        assert_eq!(enemy.health, 20);
+        assert_eq!(enemy.speed, 1.5);
+        assert_eq!(enemy.kind, Kind::Flyer);
+        assert_eq!(enemy.tint, Color::srgba_u8(255, 0, 0, 128));
+
+        // Negative values don't fit in unsigned fields.
+        let mut entity = world.spawn_empty();
+        let fields = HashMap::from_iter([("health".to_owned(), MapProperty::Int(-1))]);
+        insert_class_component(&mut entity, "Enemy", &fields, &registry);
+        assert_eq!(entity.get::<Enemy>(), Some(&Enemy::default()));
+
+        let mut entity = world.spawn_empty();
+        insert_class_component(&mut entity, "Unknown", &fields, &registry);
+        assert_eq!(entity.archetype().component_count(), 0);
+    }
+}
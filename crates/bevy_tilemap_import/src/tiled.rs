@@ -0,0 +1,826 @@
+//! Loading of [Tiled](https://www.mapeditor.org) maps, saved as `.tmx` files.
+
+use std::io::Read;
+
+use base64::Engine;
+use bevy_asset::{io::Reader, AssetLoader, AssetPath, LoadContext};
+use bevy_color::Color;
+use bevy_ecs::{
+    entity::Entity,
+    reflect::AppTypeRegistry,
+    world::{FromWorld, World},
+};
+use bevy_math::{IVec2, Quat, Rect, UVec2, Vec2};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{TypeRegistry, TypeRegistryArc};
+use bevy_scene::Scene;
+use bevy_transform::components::Transform;
+use flate2::read::{GzDecoder, ZlibDecoder};
+use tracing::warn;
+
+use crate::{
+    loader::{
+        flip_y, layer_color, load_tileset, parse_color, resolve_path, LayerTile, MapBuilder,
+        TileGrid, TilesetGrid,
+    },
+    reflect::insert_properties,
+    xml::{self, Element},
+    MapObject, MapProperty, MapShape, TileCollider, Tilemap, TilemapAssetLabel, TilemapError,
+    TilemapLoaderSettings,
+};
+
+/// The bits of a global tile ID that store whether the tile is flipped or rotated.
+const FLIP_FLAGS: u32 = 0xF000_0000;
+
+/// Loads [Tiled](https://www.mapeditor.org) maps (`.tmx`) as a [`Tilemap`].
+///
+/// External tilesets (`.tsx`) and object templates (`.tx`) are loaded along with the map, and the
+/// tileset images are loaded with the image loader of their format.
+pub struct TiledLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for TiledLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        TiledLoader {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+impl AssetLoader for TiledLoader {
+    type Asset = Tilemap;
+    type Settings = TilemapLoaderSettings;
+    type Error = TilemapError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        settings: &TilemapLoaderSettings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Tilemap, TilemapError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let map = xml::parse(&String::from_utf8_lossy(&bytes))?;
+        if map.name != "map" {
+            return Err(TilemapError::Invalid(format!(
+                "expected a `map` element, found `{}`",
+                map.name
+            )));
+        }
+        let orientation = map.attribute("orientation").unwrap_or("orthogonal");
+        if orientation != "orthogonal" {
+            return Err(TilemapError::Invalid(format!(
+                "unsupported orientation {orientation}, only orthogonal maps are supported"
+            )));
+        }
+        let (Some(tile_width), Some(tile_height)) =
+            (map.parse("tilewidth"), map.parse("tileheight"))
+        else {
+            return Err(TilemapError::Invalid("missing tile size".into()));
+        };
+
+        let mut builder = MapBuilder::new();
+        let mut tilesets = Vec::new();
+        for element in map.children_named("tileset") {
+            let first_gid = element
+                .parse("firstgid")
+                .ok_or_else(|| TilemapError::Invalid("missing tileset firstgid".into()))?;
+            let (tileset, source) = match element.attribute("source") {
+                Some(source) => {
+                    let path = resolve_path(load_context, None, source)?;
+                    let bytes = load_context.read_asset_bytes(path.clone()).await?;
+                    (xml::parse(&String::from_utf8_lossy(&bytes))?, Some(path))
+                }
+                None => (element.clone(), None),
+            };
+            tilesets.push(
+                load_tiled_tileset(
+                    &mut builder,
+                    load_context,
+                    &tileset,
+                    source,
+                    first_gid,
+                    settings,
+                )
+                .await?,
+            );
+        }
+
+        // Templates are read up front, since layers are spawned synchronously.
+        let mut templates = HashMap::default();
+        let mut sources = Vec::new();
+        collect_templates(&map, &mut sources);
+        for source in sources {
+            if templates.contains_key(source) {
+                continue;
+            }
+            let path = resolve_path(load_context, None, source)?;
+            let bytes = load_context.read_asset_bytes(path.clone()).await?;
+            let template = xml::parse(&String::from_utf8_lossy(&bytes))?;
+            let Some(object) = template.child("object").cloned() else {
+                return Err(TilemapError::Invalid(format!(
+                    "template {source} has no object"
+                )));
+            };
+            let tileset = match template.child("tileset") {
+                Some(tileset) => Some((
+                    tileset.parse("firstgid").unwrap_or(1),
+                    resolve_path(
+                        load_context,
+                        Some(&path),
+                        tileset.attribute("source").unwrap_or_default(),
+                    )?,
+                )),
+                None => None,
+            };
+            templates.insert(source.to_owned(), Template { object, tileset });
+        }
+
+        let registry = self.type_registry.read();
+        let name = load_context
+            .path()
+            .path()
+            .file_stem()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut spawner = LayerSpawner {
+            builder,
+            tilesets,
+            templates,
+            tile_size: UVec2::new(tile_width, tile_height),
+            registry: &registry,
+            settings,
+            path: load_context.path(),
+            z: 0.0,
+            warned_flips: false,
+        };
+        let root = spawner.builder.spawn(None, &name, Vec2::ZERO, 0.0, true);
+        let mut entity = spawner.builder.world.entity_mut(root);
+        insert_properties(&mut entity, class(&map), properties(&map), &registry);
+        spawner.spawn_layers(&map, root, 0.0, Color::WHITE)?;
+
+        let MapBuilder {
+            world, tilesets, ..
+        } = spawner.builder;
+        let scene =
+            load_context.add_labeled_asset(TilemapAssetLabel::Scene.to_string(), Scene::new(world));
+        Ok(Tilemap { scene, tilesets })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["tmx"]
+    }
+}
+
+/// A tileset of a map.
+struct Tileset {
+    first_gid: u32,
+    /// The index of the tileset in the [`Tilemap`], or `None` if it isn't supported.
+    index: Option<usize>,
+    tile_count: u32,
+    tile_size: UVec2,
+    /// The path of the file of the tileset, if it's external.
+    source: Option<AssetPath<'static>>,
+}
+
+/// An object template, which provides the default values of the objects that use it.
+struct Template {
+    object: Element,
+    /// The first global tile ID and the path of the tileset of the template, if it's a tile.
+    tileset: Option<(u32, AssetPath<'static>)>,
+}
+
+/// Loads the image and tile collision shapes of a tileset.
+async fn load_tiled_tileset(
+    builder: &mut MapBuilder,
+    load_context: &mut LoadContext<'_>,
+    tileset: &Element,
+    source: Option<AssetPath<'static>>,
+    first_gid: u32,
+    settings: &TilemapLoaderSettings,
+) -> Result<Tileset, TilemapError> {
+    let name = tileset.attribute("name").unwrap_or_default();
+    let tile_size = UVec2::new(
+        tileset.parse("tilewidth").unwrap_or(0),
+        tileset.parse("tileheight").unwrap_or(0),
+    );
+    let margin = tileset.parse("margin").unwrap_or(0);
+    let spacing = tileset.parse("spacing").unwrap_or(0);
+    let mut tileset_info = Tileset {
+        first_gid,
+        index: None,
+        tile_count: tileset.parse("tilecount").unwrap_or(0),
+        tile_size,
+        source: source.clone(),
+    };
+    let Some(image) = tileset.child("image") else {
+        warn!(
+            "Skipping tileset {name} of {}, image collection tilesets aren't supported",
+            load_context.path()
+        );
+        return Ok(tileset_info);
+    };
+    let image_path = resolve_path(
+        load_context,
+        source.as_ref(),
+        image.attribute("source").unwrap_or_default(),
+    )?;
+    // Older files don't store the number of columns and tiles.
+    let columns = tileset.parse("columns").unwrap_or_else(|| {
+        let width: u32 = image.parse("width").unwrap_or(0);
+        (width.saturating_sub(2 * margin) + spacing) / (tile_size.x + spacing).max(1)
+    });
+    if tileset_info.tile_count == 0 {
+        let height: u32 = image.parse("height").unwrap_or(0);
+        let rows = (height.saturating_sub(2 * margin) + spacing) / (tile_size.y + spacing).max(1);
+        tileset_info.tile_count = columns * rows;
+    }
+    if tileset_info.tile_count > u16::MAX as u32 {
+        return Err(TilemapError::Invalid(format!(
+            "tileset {name} has more than {} tiles",
+            u16::MAX
+        )));
+    }
+    let grid = TilesetGrid {
+        tile_size,
+        margin,
+        spacing,
+        columns,
+        count: tileset_info.tile_count,
+    };
+    let index = builder.tilesets.len();
+    let handle = load_tileset(load_context, image_path, &grid, settings, index).await?;
+    builder.tilesets.push(handle);
+    tileset_info.index = Some(index);
+
+    for tile in tileset.children_named("tile") {
+        let (Some(id), Some(group)) = (tile.parse::<u16>("id"), tile.child("objectgroup")) else {
+            continue;
+        };
+        let colliders = group
+            .children_named("object")
+            .map(|object| {
+                let (position, rotation, size) = object_placement(object);
+                TileCollider {
+                    origin: flip_y(position),
+                    rotation,
+                    shape: object_shape(object, size, false),
+                }
+            })
+            .collect::<Vec<_>>();
+        if !colliders.is_empty() {
+            builder.colliders.insert((index, id), colliders);
+        }
+    }
+    Ok(tileset_info)
+}
+
+/// Collects the paths of the templates used by the objects of a map.
+fn collect_templates<'a>(element: &'a Element, sources: &mut Vec<&'a str>) {
+    for child in &element.children {
+        match child.name.as_str() {
+            "object" => sources.extend(child.attribute("template")),
+            "objectgroup" | "group" => collect_templates(child, sources),
+            _ => {}
+        }
+    }
+}
+
+/// Returns the class of a map, layer or object, which was called its type before Tiled 1.9.
+fn class(element: &Element) -> &str {
+    element
+        .attribute("class")
+        .or_else(|| element.attribute("type"))
+        .unwrap_or_default()
+}
+
+/// Parses the custom properties of an element.
+fn properties(element: &Element) -> HashMap<String, MapProperty> {
+    element
+        .child("properties")
+        .into_iter()
+        .flat_map(|properties| properties.children_named("property"))
+        .filter_map(|property| {
+            let name = property.attribute("name")?;
+            // Multiline strings are stored as text.
+            let value = property.attribute("value").unwrap_or(&property.text);
+            let value = match property.attribute("type").unwrap_or("string") {
+                "bool" => MapProperty::Bool(value == "true"),
+                "int" | "object" => MapProperty::Int(value.parse().ok()?),
+                "float" => MapProperty::Float(value.parse().ok()?),
+                // Colors that aren't set are empty.
+                "color" => MapProperty::Color(parse_color(value).unwrap_or(Color::NONE)),
+                "class" => MapProperty::Class {
+                    class: property
+                        .attribute("propertytype")
+                        .unwrap_or_default()
+                        .into(),
+                    members: properties(property),
+                },
+                _ => MapProperty::String(value.into()),
+            };
+            Some((name.to_owned(), value))
+        })
+        .collect()
+}
+
+/// Returns the position, counterclockwise rotation in radians and size of an object.
+fn object_placement(object: &Element) -> (Vec2, f32, Vec2) {
+    let value = |name| object.parse::<f32>(name).unwrap_or(0.0);
+    (
+        Vec2::new(value("x"), value("y")),
+        -value("rotation").to_radians(),
+        Vec2::new(value("width"), value("height")),
+    )
+}
+
+/// Parses the points of a polygon or polyline.
+fn points(element: &Element) -> Vec<Vec2> {
+    element
+        .attribute("points")
+        .unwrap_or_default()
+        .split_whitespace()
+        .filter_map(|point| {
+            let (x, y) = point.split_once(',')?;
+            Some(flip_y(Vec2::new(x.parse().ok()?, y.parse().ok()?)))
+        })
+        .collect()
+}
+
+/// Returns the shape of an object, relative to its origin.
+///
+/// The origin is the top-left corner, except for tile objects whose origin is the bottom-left
+/// corner.
+fn object_shape(object: &Element, size: Vec2, is_tile: bool) -> MapShape {
+    let bounds = if is_tile {
+        Rect::from_corners(Vec2::ZERO, size)
+    } else {
+        Rect::from_corners(Vec2::ZERO, flip_y(size))
+    };
+    if object.child("ellipse").is_some() {
+        MapShape::Ellipse(bounds)
+    } else if object.child("point").is_some() {
+        MapShape::Point
+    } else if let Some(polygon) = object.child("polygon") {
+        MapShape::Polygon(points(polygon))
+    } else if let Some(polyline) = object.child("polyline") {
+        MapShape::Polyline(points(polyline))
+    } else {
+        MapShape::Rectangle(bounds)
+    }
+}
+
+/// Merges an object with its template, where the attributes, shape and properties of the object
+/// take precedence.
+fn apply_template(object: &Element, template: &Element) -> Element {
+    let mut merged = template.clone();
+    for (name, value) in &object.attributes {
+        match merged
+            .attributes
+            .iter_mut()
+            .find(|(other, _)| other == name)
+        {
+            Some((_, other)) => other.clone_from(value),
+            None => merged.attributes.push((name.clone(), value.clone())),
+        }
+    }
+    for child in &object.children {
+        let existing = merged
+            .children
+            .iter_mut()
+            .find(|other| other.name == child.name);
+        match existing {
+            Some(existing) if child.name == "properties" => {
+                for property in &child.children {
+                    let name = property.attribute("name");
+                    existing
+                        .children
+                        .retain(|other| other.attribute("name") != name);
+                    existing.children.push(property.clone());
+                }
+            }
+            Some(existing) => *existing = child.clone(),
+            None => merged.children.push(child.clone()),
+        }
+    }
+    merged
+}
+
+/// Decodes the global tile IDs of a `data` or `chunk` element.
+fn decode_tiles(
+    data: &Element,
+    encoding: Option<&str>,
+    compression: Option<&str>,
+) -> Result<Vec<u32>, TilemapError> {
+    let invalid = |message: &str| TilemapError::Invalid(format!("invalid tile data: {message}"));
+    match encoding {
+        None => Ok(data
+            .children_named("tile")
+            .map(|tile| tile.parse("gid").unwrap_or(0))
+            .collect()),
+        Some("csv") => data
+            .text
+            .split(',')
+            .map(|gid| {
+                gid.trim()
+                    .parse()
+                    .map_err(|_| invalid("expected a tile ID"))
+            })
+            .collect(),
+        Some("base64") => {
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(data.text.trim())
+                .map_err(|_| invalid("invalid base64"))?;
+            let bytes = match compression {
+                None | Some("") => bytes,
+                Some("zlib") => {
+                    let mut decompressed = Vec::new();
+                    ZlibDecoder::new(&bytes[..])
+                        .read_to_end(&mut decompressed)
+                        .map_err(|_| invalid("invalid zlib data"))?;
+                    decompressed
+                }
+                Some("gzip") => {
+                    let mut decompressed = Vec::new();
+                    GzDecoder::new(&bytes[..])
+                        .read_to_end(&mut decompressed)
+                        .map_err(|_| invalid("invalid gzip data"))?;
+                    decompressed
+                }
+                Some(compression) => {
+                    return Err(invalid(&format!("unsupported compression {compression}")))
+                }
+            };
+            Ok(bytes
+                .chunks_exact(4)
+                .map(|gid| u32::from_le_bytes(gid.try_into().unwrap()))
+                .collect())
+        }
+        Some(encoding) => Err(invalid(&format!("unsupported encoding {encoding}"))),
+    }
+}
+
+/// Decodes the tiles of a tile layer, which are split into chunks in infinite maps.
+fn layer_gids(layer: &Element) -> Result<(IVec2, UVec2, Vec<u32>), TilemapError> {
+    let Some(data) = layer.child("data") else {
+        return Ok((IVec2::ZERO, UVec2::ZERO, Vec::new()));
+    };
+    let encoding = data.attribute("encoding");
+    let compression = data.attribute("compression");
+    let chunks = data.children_named("chunk").collect::<Vec<_>>();
+    if chunks.is_empty() {
+        let size = UVec2::new(
+            layer.parse("width").unwrap_or(0),
+            layer.parse("height").unwrap_or(0),
+        );
+        return Ok((
+            IVec2::ZERO,
+            size,
+            decode_tiles(data, encoding, compression)?,
+        ));
+    }
+
+    let mut placed = Vec::new();
+    let (mut min, mut max) = (IVec2::MAX, IVec2::MIN);
+    for chunk in chunks {
+        let position = IVec2::new(chunk.parse("x").unwrap_or(0), chunk.parse("y").unwrap_or(0));
+        let size = UVec2::new(
+            chunk.parse("width").unwrap_or(0),
+            chunk.parse("height").unwrap_or(0),
+        );
+        min = min.min(position);
+        max = max.max(position + size.as_ivec2());
+        placed.push((position, size, decode_tiles(chunk, encoding, compression)?));
+    }
+    let size = (max - min).max(IVec2::ZERO).as_uvec2();
+    let mut gids = vec![0; size.element_product() as usize];
+    for (position, chunk_size, chunk_gids) in placed {
+        let offset = (position - min).as_uvec2();
+        for (index, gid) in chunk_gids.into_iter().enumerate() {
+            let local = UVec2::new(
+                index as u32 % chunk_size.x.max(1),
+                index as u32 / chunk_size.x.max(1),
+            );
+            if local.y < chunk_size.y {
+                let cell = offset + local;
+                gids[(cell.y * size.x + cell.x) as usize] = gid;
+            }
+        }
+    }
+    Ok((min, size, gids))
+}
+
+/// Spawns the layers and objects of a map.
+struct LayerSpawner<'a> {
+    builder: MapBuilder,
+    tilesets: Vec<Tileset>,
+    templates: HashMap<String, Template>,
+    tile_size: UVec2,
+    registry: &'a TypeRegistry,
+    settings: &'a TilemapLoaderSettings,
+    path: &'a AssetPath<'static>,
+    /// The depth of the last spawned layer, which increases by one for each layer.
+    z: f32,
+    warned_flips: bool,
+}
+
+impl LayerSpawner<'_> {
+    /// Returns the tileset and index of the tile with a global ID.
+    fn tile(&mut self, gid: u32) -> Option<(&Tileset, u16)> {
+        if gid & FLIP_FLAGS != 0 && !self.warned_flips {
+            warn!(
+                "Flipped and rotated tiles of {} aren't supported, and are drawn as is",
+                self.path
+            );
+            self.warned_flips = true;
+        }
+        let gid = gid & !FLIP_FLAGS;
+        if gid == 0 {
+            return None;
+        }
+        let tileset = self
+            .tilesets
+            .iter()
+            .rev()
+            .find(|tileset| tileset.first_gid <= gid)?;
+        let index = gid - tileset.first_gid;
+        (index < tileset.tile_count).then_some((tileset, index as u16))
+    }
+
+    /// Spawns the layers of a map or a group layer as children of `parent`.
+    fn spawn_layers(
+        &mut self,
+        parent_element: &Element,
+        parent: Entity,
+        parent_z: f32,
+        parent_color: Color,
+    ) -> Result<(), TilemapError> {
+        for element in &parent_element.children {
+            if !matches!(
+                element.name.as_str(),
+                "layer" | "objectgroup" | "group" | "imagelayer"
+            ) {
+                continue;
+            }
+            let name = element.attribute("name").unwrap_or_default();
+            if element.name == "imagelayer" {
+                warn!(
+                    "Skipping image layer {name} of {}, image layers aren't supported",
+                    self.path
+                );
+                continue;
+            }
+            self.z += 1.0;
+            let offset = Vec2::new(
+                element.parse("offsetx").unwrap_or(0.0),
+                element.parse("offsety").unwrap_or(0.0),
+            );
+            let layer = self.builder.spawn(
+                Some(parent),
+                name,
+                flip_y(offset),
+                self.z - parent_z,
+                element.attribute("visible") != Some("0"),
+            );
+            let mut entity = self.builder.world.entity_mut(layer);
+            insert_properties(
+                &mut entity,
+                class(element),
+                properties(element),
+                self.registry,
+            );
+            let color = layer_color(
+                parent_color,
+                element.parse("opacity").unwrap_or(1.0),
+                element.attribute("tintcolor").and_then(parse_color),
+            );
+            match element.name.as_str() {
+                "layer" => self.spawn_tile_layer(element, layer, color)?,
+                "objectgroup" => {
+                    for object in element.children_named("object") {
+                        self.spawn_object(object, layer, color);
+                    }
+                }
+                _ => self.spawn_layers(element, layer, self.z, color)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn spawn_tile_layer(
+        &mut self,
+        element: &Element,
+        layer: Entity,
+        color: Color,
+    ) -> Result<(), TilemapError> {
+        let (origin, size, gids) = layer_gids(element)?;
+        if gids.len() != size.element_product() as usize {
+            return Err(TilemapError::Invalid(format!(
+                "layer {} has {} tiles instead of {size}",
+                element.attribute("name").unwrap_or_default(),
+                gids.len(),
+            )));
+        }
+        let mut grid = TileGrid::new(origin, size);
+        for (tile, gid) in grid.tiles.iter_mut().zip(gids) {
+            *tile = self.tile(gid).and_then(|(tileset, index)| {
+                Some(LayerTile {
+                    tileset: tileset.index?,
+                    index,
+                })
+            });
+        }
+        self.builder
+            .spawn_chunks(layer, &grid, self.tile_size, color, self.settings);
+        Ok(())
+    }
+
+    fn spawn_object(&mut self, object: &Element, layer: Entity, color: Color) {
+        let merged = object
+            .attribute("template")
+            .and_then(|source| self.templates.get(source))
+            .map(|template| {
+                let mut merged = apply_template(object, &template.object);
+                // The tile of a template refers to the tileset of the template.
+                if let (None, Some(gid), Some((first_gid, source))) = (
+                    object.attribute("gid"),
+                    template.object.parse::<u32>("gid"),
+                    &template.tileset,
+                ) {
+                    let gid = self
+                        .tilesets
+                        .iter()
+                        .find(|tileset| tileset.source.as_ref() == Some(source))
+                        .and_then(|tileset| {
+                            let index = (gid & !FLIP_FLAGS).checked_sub(*first_gid)?;
+                            Some((tileset.first_gid + index) | (gid & FLIP_FLAGS))
+                        });
+                    merged_gid(&mut merged, gid);
+                }
+                merged
+            });
+        let object = merged.as_ref().unwrap_or(object);
+
+        let (position, rotation, size) = object_placement(object);
+        let tile = object
+            .parse::<u32>("gid")
+            .and_then(|gid| self.tile(gid))
+            .and_then(|(tileset, index)| {
+                let tile = LayerTile {
+                    tileset: tileset.index?,
+                    index,
+                };
+                Some((tile, tileset.tile_size))
+            });
+        let shape = object_shape(object, size, tile.is_some());
+        let class = class(object);
+        let entity = self.builder.spawn(
+            Some(layer),
+            object.attribute("name").unwrap_or_default(),
+            flip_y(position),
+            0.0,
+            object.attribute("visible") != Some("0"),
+        );
+        let mut entity = self.builder.world.entity_mut(entity);
+        entity.insert(MapObject {
+            class: class.into(),
+            shape,
+        });
+        entity
+            .get_mut::<Transform>()
+            .unwrap()
+            .rotate(Quat::from_rotation_z(rotation));
+        insert_properties(&mut entity, class, properties(object), self.registry);
+        let entity = entity.id();
+
+        // Tile objects are drawn from their bottom-left corner.
+        if let Some((tile, tile_size)) = tile {
+            let size = if size == Vec2::ZERO {
+                tile_size
+            } else {
+                size.round().as_uvec2()
+            };
+            self.builder
+                .spawn_tile(entity, tile, size, size.as_vec2() / 2.0, color);
+        }
+    }
+}
+
+/// Sets the global tile ID of an object merged with its template, or removes it if the tileset of
+/// the template isn't used by the map.
+fn merged_gid(object: &mut Element, gid: Option<u32>) {
+    object.attributes.retain(|(name, _)| name != "gid");
+    if let Some(gid) = gid {
+        object.attributes.push(("gid".into(), gid.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{write::ZlibEncoder, Compression};
+
+    use super::*;
+
+    #[test]
+    fn decode_layers() {
+        let layer = xml::parse(
+            r#"<layer width="3" height="2"><data encoding="csv">
+1,0,2,
+0,3,0
+</data></layer>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            layer_gids(&layer).unwrap(),
+            (IVec2::ZERO, UVec2::new(3, 2), vec![1, 0, 2, 0, 3, 0])
+        );
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        for gid in [1u32, 2, 3, 4 | 0x8000_0000] {
+            encoder.write_all(&gid.to_le_bytes()).unwrap();
+        }
+        let data = base64::engine::general_purpose::STANDARD.encode(encoder.finish().unwrap());
+        let layer = xml::parse(&format!(
+            r#"<layer><data encoding="base64" compression="zlib">
+ <chunk x="-2" y="0" width="2" height="2">{data}</chunk>
+ <chunk x="0" y="1" width="1" height="1">{}</chunk>
+</data></layer>"#,
+            base64::engine::general_purpose::STANDARD.encode({
+                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(&5u32.to_le_bytes()).unwrap();
+                encoder.finish().unwrap()
+            })
+        ))
+        .unwrap();
+        let (origin, size, gids) = layer_gids(&layer).unwrap();
+        assert_eq!(origin, IVec2::new(-2, 0));
+        assert_eq!(size, UVec2::new(3, 2));
+        assert_eq!(gids, [1, 2, 0, 3, 4 | 0x8000_0000, 5]);
+    }
+
+    #[test]
+    fn objects() {
+        let object = xml::parse(
+            r##"<object id="1" name="Door" type="Door" x="16" y="32" width="8" height="4" rotation="90">
+  <properties>
+   <property name="locked" type="bool" value="true"/>
+   <property name="key" type="class" propertytype="Key">
+    <properties><property name="color" type="color" value="#ff00ff00"/></properties>
+   </property>
+   <property name="note">Two
+lines</property>
+  </properties>
+ </object>"##,
+        )
+        .unwrap();
+        let (position, rotation, size) = object_placement(&object);
+        assert_eq!(position, Vec2::new(16.0, 32.0));
+        assert_eq!(rotation, -core::f32::consts::FRAC_PI_2);
+        assert_eq!(
+            object_shape(&object, size, false),
+            MapShape::Rectangle(Rect::new(0.0, -4.0, 8.0, 0.0))
+        );
+        assert_eq!(class(&object), "Door");
+        let values = properties(&object);
+        assert_eq!(values["locked"], MapProperty::Bool(true));
+        assert_eq!(values["note"], MapProperty::String("Two\nlines".into()));
+        let MapProperty::Class { class, members } = &values["key"] else {
+            panic!("expected a class property");
+        };
+        assert_eq!(class, "Key");
+        assert_eq!(
+            members["color"],
+            MapProperty::Color(Color::srgb_u8(0, 255, 0))
+        );
+
+        let polygon =
+            xml::parse(r#"<object x="1" y="2"><polygon points="0,0 4,0 4,-2"/></object>"#).unwrap();
+        assert_eq!(
+            object_shape(&polygon, Vec2::ZERO, false),
+            MapShape::Polygon(vec![Vec2::ZERO, Vec2::new(4.0, 0.0), Vec2::new(4.0, 2.0)])
+        );
+
+        let template = xml::parse(
+            r#"<object name="Chest" width="16" height="16"><properties><property name="gold" type="int" value="5"/><property name="open" type="bool" value="false"/></properties><ellipse/></object>"#,
+        )
+        .unwrap();
+        let instance = xml::parse(
+            r#"<object template="chest.tx" x="3" y="4"><properties><property name="gold" type="int" value="10"/></properties></object>"#,
+        )
+        .unwrap();
+        let merged = apply_template(&instance, &template);
+        assert_eq!(merged.attribute("name"), Some("Chest"));
+        assert_eq!(merged.attribute("x"), Some("3"));
+        let values = properties(&merged);
+        assert_eq!(values["gold"], MapProperty::Int(10));
+        assert_eq!(values["open"], MapProperty::Bool(false));
+        assert!(matches!(
+            object_shape(&merged, Vec2::splat(16.0), false),
+            MapShape::Ellipse(_)
+        ));
+    }
+}
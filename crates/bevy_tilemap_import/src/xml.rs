@@ -0,0 +1,289 @@
+//! A minimal XML parser, which is enough to read the files written by Tiled.
+//!
+//! Namespaces, doctypes and processing instructions are skipped, and only the predefined and
+//! numeric entities are decoded.
+
+use thiserror::Error;
+
+/// Elements nested deeper than this are rejected, so that invalid files can't overflow the stack.
+const MAX_DEPTH: usize = 64;
+
+/// An error that occurs when parsing an XML file.
+#[derive(Error, Debug)]
+#[error("invalid XML at line {line}: {message}")]
+pub struct XmlError {
+    /// The line at which the error occurred.
+    pub line: usize,
+    /// A description of the error.
+    pub message: String,
+}
+
+/// An element of an XML document.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Element>,
+    /// The text directly inside the element, with entities decoded.
+    pub text: String,
+}
+
+impl Element {
+    pub(crate) fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Parses the attribute `name`, returning `None` if it's missing or invalid.
+    pub(crate) fn parse<T: core::str::FromStr>(&self, name: &str) -> Option<T> {
+        self.attribute(name)?.trim().parse().ok()
+    }
+
+    /// Returns the first child named `name`.
+    pub(crate) fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    /// Returns the children named `name`.
+    pub(crate) fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: impl Into<String>) -> XmlError {
+        let consumed = &self.text[..self.position.min(self.text.len())];
+        XmlError {
+            line: consumed.matches('\n').count() + 1,
+            message: message.into(),
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.position..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skips past the next occurrence of `end`.
+    fn skip_past(&mut self, end: &str) -> Result<(), XmlError> {
+        match self.rest().find(end) {
+            Some(index) => {
+                self.position += index + end.len();
+                Ok(())
+            }
+            None => Err(self.error(format!("expected `{end}`"))),
+        }
+    }
+
+    /// Skips comments, processing instructions and doctypes.
+    fn skip_misc(&mut self) -> Result<(), XmlError> {
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if rest.starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if rest.starts_with("<!") {
+                self.skip_past(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<&'a str, XmlError> {
+        let rest = self.rest();
+        let length = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '/' | '>' | '='))
+            .unwrap_or(rest.len());
+        if length == 0 {
+            return Err(self.error("expected a name"));
+        }
+        self.position += length;
+        Ok(&rest[..length])
+    }
+
+    fn element(&mut self, depth: usize) -> Result<Element, XmlError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("elements are nested too deeply"));
+        }
+        if !self.rest().starts_with('<') {
+            return Err(self.error("expected an element"));
+        }
+        self.position += 1;
+        let mut element = Element {
+            name: self.name()?.into(),
+            ..Default::default()
+        };
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("/>") {
+                self.position += 2;
+                return Ok(element);
+            } else if rest.starts_with('>') {
+                self.position += 1;
+                break;
+            }
+            let name = self.name()?;
+            self.skip_whitespace();
+            if !self.rest().starts_with('=') {
+                return Err(self.error(format!("expected a value for attribute `{name}`")));
+            }
+            self.position += 1;
+            self.skip_whitespace();
+            let Some(quote @ ('"' | '\'')) = self.rest().chars().next() else {
+                return Err(self.error("expected a quoted attribute value"));
+            };
+            self.position += 1;
+            let Some(length) = self.rest().find(quote) else {
+                return Err(self.error("unterminated attribute value"));
+            };
+            let value = decode(&self.rest()[..length]).map_err(|message| self.error(message))?;
+            self.position += length + 1;
+            element.attributes.push((name.into(), value));
+        }
+
+        // Content, until the closing tag.
+        loop {
+            let rest = self.rest();
+            if rest.starts_with("</") {
+                self.position += 2;
+                let name = self.name()?;
+                if name != element.name {
+                    return Err(
+                        self.error(format!("expected `</{}>`, found `</{name}>`", element.name))
+                    );
+                }
+                self.skip_whitespace();
+                if !self.rest().starts_with('>') {
+                    return Err(self.error("expected `>`"));
+                }
+                self.position += 1;
+                return Ok(element);
+            } else if rest.starts_with("<![CDATA[") {
+                self.position += "<![CDATA[".len();
+                let Some(length) = self.rest().find("]]>") else {
+                    return Err(self.error("unterminated CDATA section"));
+                };
+                element.text.push_str(&self.rest()[..length]);
+                self.position += length + "]]>".len();
+            } else if rest.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if rest.starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if rest.starts_with('<') {
+                let child = self.element(depth + 1)?;
+                element.children.push(child);
+            } else if rest.is_empty() {
+                return Err(self.error(format!("unterminated element `{}`", element.name)));
+            } else {
+                let length = rest.find('<').unwrap_or(rest.len());
+                let text = decode(&rest[..length]).map_err(|message| self.error(message))?;
+                element.text.push_str(&text);
+                self.position += length;
+            }
+        }
+    }
+}
+
+/// Decodes the entities of a text or attribute value.
+fn decode(text: &str) -> Result<String, String> {
+    if !text.contains('&') {
+        return Ok(text.into());
+    }
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find(';') else {
+            return Err("unterminated entity".into());
+        };
+        let entity = &rest[..end];
+        let character = match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+        let Some(character) = character else {
+            return Err(format!("unknown entity `&{entity};`"));
+        };
+        decoded.push(character);
+        rest = &rest[end + 1..];
+    }
+    decoded.push_str(rest);
+    Ok(decoded)
+}
+
+/// Parses an XML document, returning its root element.
+pub(crate) fn parse(text: &str) -> Result<Element, XmlError> {
+    let mut parser = Parser {
+        // Skip the byte order mark.
+        text: text.strip_prefix('\u{feff}').unwrap_or(text),
+        position: 0,
+    };
+    parser.skip_misc()?;
+    let root = parser.element(0)?;
+    parser.skip_misc()?;
+    if !parser.rest().is_empty() {
+        return Err(parser.error("unexpected content after the root element"));
+    }
+    Ok(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_document() {
+        let root = parse(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- A comment -->
+<map version="1.10" width='2'>
+ <properties>
+  <property name="title" value="&lt;Level &amp; &#x31;&gt;"/>
+ </properties>
+ <data encoding="csv">
+1,2,
+3,4
+</data>
+</map>
+"#,
+        )
+        .unwrap();
+        assert_eq!(root.name, "map");
+        assert_eq!(root.attribute("version"), Some("1.10"));
+        assert_eq!(root.parse::<u32>("width"), Some(2));
+        assert_eq!(root.children.len(), 2);
+        let property = root.child("properties").unwrap().child("property").unwrap();
+        assert_eq!(property.attribute("value"), Some("<Level & 1>"));
+        assert_eq!(root.child("data").unwrap().text.trim(), "1,2,\n3,4");
+
+        let error = parse("<map>\n<layer></map>").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert!(parse("<map><layer/>").is_err());
+        assert!(parse("<map/><map/>").is_err());
+    }
+}
@@ -2,14 +2,14 @@ use crate::{
     experimental::UiChildren,
     prelude::{Button, Label},
     ui_transform::UiGlobalTransform,
-    widget::{ImageNode, TextUiReader},
+    widget::{ImageNode, Text, TextInput, TextInputValue, TextUiReader},
     ComputedNode,
 };
 use bevy_a11y::AccessibilityNode;
 use bevy_app::{App, Plugin, PostUpdate};
 use bevy_ecs::{
     prelude::{DetectChanges, Entity},
-    query::{Changed, Without},
+    query::{Changed, Or, With, Without},
     schedule::IntoScheduleConfigs,
     system::{Commands, Query},
     world::Ref,
@@ -113,7 +113,10 @@ fn image_changed(
 
 fn label_changed(
     mut commands: Commands,
-    mut query: Query<(Entity, Option<&mut AccessibilityNode>), Changed<Label>>,
+    mut query: Query<
+        (Entity, Option<&mut AccessibilityNode>),
+        (With<Label>, Or<(Changed<Label>, Changed<Text>)>),
+    >,
     mut text_reader: TextUiReader,
 ) {
     for (entity, accessible) in &mut query {
@@ -141,6 +144,37 @@ fn label_changed(
     }
 }
 
+fn text_input_changed(
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            &TextInput,
+            &TextInputValue,
+            Option<&mut AccessibilityNode>,
+        ),
+        Or<(Changed<TextInput>, Changed<TextInputValue>)>,
+    >,
+) {
+    for (entity, input, value, accessible) in &mut query {
+        let role = if input.multiline {
+            Role::MultilineTextInput
+        } else {
+            Role::TextInput
+        };
+        if let Some(mut accessible) = accessible {
+            accessible.set_role(role);
+            accessible.set_value(value.as_str());
+        } else {
+            let mut node = Node::new(role);
+            node.set_value(value.as_str());
+            commands
+                .entity(entity)
+                .try_insert(AccessibilityNode::from(node));
+        }
+    }
+}
+
 /// `AccessKit` integration for `bevy_ui`.
 pub(crate) struct AccessibilityPlugin;
 
@@ -157,6 +191,7 @@ impl Plugin for AccessibilityPlugin {
                 button_changed,
                 image_changed,
                 label_changed,
+                text_input_changed,
             ),
         );
     }
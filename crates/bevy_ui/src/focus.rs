@@ -1,6 +1,6 @@
 use crate::{
-    ui_transform::UiGlobalTransform, ComputedNode, ComputedUiTargetCamera, Node, OverrideClip,
-    UiStack,
+    ui_transform::UiGlobalTransform, CalculatedClip, ComputedNode, ComputedUiTargetCamera, Node,
+    OverrideClip, UiStack,
 };
 use bevy_camera::{visibility::InheritedVisibility, Camera, NormalizedRenderTarget};
 use bevy_ecs::{
@@ -351,13 +351,15 @@ pub fn clip_check_recursive(
 ) -> bool {
     if let Ok(child_of) = child_of_query.get(entity) {
         let parent = child_of.0;
-        if let Ok((computed_node, transform, node)) = clipping_query.get(parent)
-            && !computed_node
-                .resolve_clip_rect(node.overflow, node.overflow_clip_margin)
-                .contains(transform.inverse().transform_point2(point))
-        {
-            // The point is clipped and should be ignored by picking
-            return false;
+        if let Ok((computed_node, transform, node)) = clipping_query.get(parent) {
+            let clip = CalculatedClip {
+                clip: computed_node.resolve_clip_rect(node.overflow, node.overflow_clip_margin),
+                radius: computed_node.resolve_clip_radius(node.overflow, node.overflow_clip_margin),
+            };
+            if !clip.contains(transform.inverse().transform_point2(point)) {
+                // The point is clipped and should be ignored by picking
+                return false;
+            }
         }
         return clip_check_recursive(point, parent, clipping_query, child_of_query);
     }
@@ -0,0 +1,135 @@
+use bevy_ecs::entity::{Entity, EntityHashMap};
+use bevy_math::{Rect, Vec2};
+use taffy::style_helpers;
+
+use super::{convert, ui_surface::UiSurface, LayoutContext};
+use crate::{AnchorTarget, EdgeAnchor, EdgeConstraints, Node};
+
+/// Positions the children of a [`ConstraintLayout`](crate::ConstraintLayout) node by overriding their taffy styles
+/// with absolute positions and sizes resolved from their [`EdgeConstraints`].
+///
+/// Uses the layout from the last call to [`UiSurface::compute_layout`]. Returns `true` if any style was changed,
+/// in which case the layout needs to be computed again.
+pub(super) fn resolve_constraint_layout<'a>(
+    ui_surface: &mut UiSurface,
+    container: Entity,
+    children: impl Iterator<Item = (Entity, &'a Node, Option<&'a EdgeConstraints>)>,
+    layout_context: &LayoutContext,
+) -> bool {
+    let Ok((container_layout, _)) = ui_surface.get_layout(container, false) else {
+        return false;
+    };
+
+    // Absolutely positioned nodes are placed relative to the padding box of their parent
+    let border_offset = Vec2::new(container_layout.border.left, container_layout.border.top);
+    let container_size = Vec2::new(
+        container_layout.size.width - container_layout.border.left - container_layout.border.right,
+        container_layout.size.height - container_layout.border.top - container_layout.border.bottom,
+    )
+    .max(Vec2::ZERO);
+
+    let children: Vec<_> = children.collect();
+
+    // Start from the current layout of each child so anchors can refer to siblings that haven't been resolved yet
+    let mut rects = EntityHashMap::default();
+    for &(child, ..) in &children {
+        if let Ok((layout, _)) = ui_surface.get_layout(child, false) {
+            let min = Vec2::new(layout.location.x, layout.location.y) - border_offset;
+            let size = Vec2::new(layout.size.width, layout.size.height);
+            rects.insert(child, Rect::from_corners(min, min + size));
+        }
+    }
+
+    let mut changed = false;
+    for (child, node, constraints) in children {
+        let Some(&current_rect) = rects.get(&child) else {
+            continue;
+        };
+        let constraints = constraints.copied().unwrap_or_default();
+
+        let resolve = |anchor: Option<EdgeAnchor>, axis: usize| {
+            anchor.map(|anchor| {
+                let target = match anchor.target {
+                    AnchorTarget::Sibling(sibling) => rects.get(&sibling).copied(),
+                    AnchorTarget::Parent => None,
+                }
+                .unwrap_or(Rect::from_corners(Vec2::ZERO, container_size));
+                target.min[axis]
+                    + anchor.ratio * target.size()[axis]
+                    + anchor
+                        .offset
+                        .resolve(
+                            layout_context.scale_factor,
+                            container_size[axis],
+                            layout_context.physical_size,
+                        )
+                        .unwrap_or(0.)
+            })
+        };
+
+        let (x, width) = resolve_axis(
+            resolve(constraints.left, 0),
+            resolve(constraints.right, 0),
+            resolve(constraints.center_x, 0),
+            current_rect.width(),
+        );
+        let (y, height) = resolve_axis(
+            resolve(constraints.top, 1),
+            resolve(constraints.bottom, 1),
+            resolve(constraints.center_y, 1),
+            current_rect.height(),
+        );
+
+        let Some(&taffy_node) = ui_surface.entity_to_taffy.get(&child) else {
+            continue;
+        };
+        let has_measure = ui_surface.taffy.get_node_context(taffy_node.id).is_some();
+        let mut style = convert::from_node(node, layout_context, has_measure);
+        style.position = taffy::style::Position::Absolute;
+        style.inset = taffy::Rect {
+            left: style_helpers::length(x),
+            right: style_helpers::auto(),
+            top: style_helpers::length(y),
+            bottom: style_helpers::auto(),
+        };
+        style.margin = taffy::Rect::zero();
+        style.grid_row = Default::default();
+        style.grid_column = Default::default();
+        if let Some(width) = width {
+            style.size.width = style_helpers::length(width);
+        }
+        if let Some(height) = height {
+            style.size.height = style_helpers::length(height);
+        }
+
+        if ui_surface.taffy.style(taffy_node.id).ok() != Some(&style) {
+            ui_surface.taffy.set_style(taffy_node.id, style).unwrap();
+            changed = true;
+        }
+
+        let size = Vec2::new(
+            width.unwrap_or(current_rect.width()),
+            height.unwrap_or(current_rect.height()),
+        );
+        let min = Vec2::new(x, y);
+        rects.insert(child, Rect::from_corners(min, min + size));
+    }
+
+    changed
+}
+
+/// Returns the position of the node along an axis, and its length if it is stretched between its edges.
+fn resolve_axis(
+    start: Option<f32>,
+    end: Option<f32>,
+    center: Option<f32>,
+    length: f32,
+) -> (f32, Option<f32>) {
+    match (start, end, center) {
+        (Some(start), Some(end), _) => (start, Some((end - start).max(0.))),
+        (Some(start), None, _) => (start, None),
+        (None, Some(end), _) => (end - length, None),
+        (None, None, Some(center)) => (center - 0.5 * length, None),
+        (None, None, None) => (0., None),
+    }
+}
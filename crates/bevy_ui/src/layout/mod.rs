@@ -1,15 +1,16 @@
 use crate::{
     experimental::{UiChildren, UiRootNodes},
     ui_transform::{UiGlobalTransform, UiTransform},
-    ComputedNode, ComputedUiRenderTargetInfo, ContentSize, Display, IgnoreScroll, LayoutConfig,
-    Node, Outline, OverflowAxis, ScrollPosition,
+    ComputedNode, ComputedUiRenderTargetInfo, ConstraintLayout, ContentSize, Display,
+    EdgeConstraints, IgnoreScroll, LayoutConfig, LayoutRounding, Node, Outline, OverflowAxis,
+    ScrollPosition,
 };
 use bevy_ecs::{
     change_detection::{DetectChanges, DetectChangesMut},
     entity::Entity,
     hierarchy::Children,
     lifecycle::RemovedComponents,
-    query::Added,
+    query::{Added, With},
     system::{Query, ResMut},
     world::Ref,
 };
@@ -23,6 +24,7 @@ use bevy_text::ComputedTextBlock;
 
 use bevy_text::CosmicFontSystem;
 
+mod constraint;
 mod convert;
 pub mod debug;
 pub(crate) mod ui_surface;
@@ -61,6 +63,9 @@ impl Default for LayoutContext {
     }
 }
 
+/// The maximum number of times the layout of a UI tree is recomputed to resolve nested constraint layouts.
+const MAX_CONSTRAINT_LAYOUT_PASSES: usize = 4;
+
 #[derive(Debug, Error)]
 pub enum LayoutError {
     #[error("Invalid hierarchy")]
@@ -87,15 +92,19 @@ pub fn ui_layout_system(
         &mut UiGlobalTransform,
         &Node,
         Option<&LayoutConfig>,
+        Option<&LayoutRounding>,
         Option<&Outline>,
         Option<&ScrollPosition>,
         Option<&IgnoreScroll>,
     )>,
+    constraint_layout_query: Query<(), With<ConstraintLayout>>,
+    constraint_query: Query<(&Node, Option<&EdgeConstraints>)>,
     mut buffer_query: Query<&mut ComputedTextBlock>,
     mut font_system: ResMut<CosmicFontSystem>,
     mut removed_children: RemovedComponents<Children>,
     mut removed_content_sizes: RemovedComponents<ContentSize>,
     mut removed_nodes: RemovedComponents<Node>,
+    mut removed_constraint_layouts: RemovedComponents<ConstraintLayout>,
 ) {
     // When a `ContentSize` component is removed from an entity, we need to remove the measure from the corresponding taffy node.
     for entity in removed_content_sizes.read() {
@@ -121,6 +130,19 @@ pub fn ui_layout_system(
             }
         });
 
+    // Restore the styles of the children of nodes that no longer use a constraint layout
+    for entity in removed_constraint_layouts.read() {
+        for child in ui_children.iter_ui_children(entity) {
+            if let Ok((_, node, _, computed_target)) = node_query.get(child) {
+                let layout_context = LayoutContext::new(
+                    computed_target.scale_factor,
+                    computed_target.physical_size.as_vec2(),
+                );
+                ui_surface.upsert_node(&layout_context, child, &node, None);
+            }
+        }
+    }
+
     // update and remove children
     for entity in removed_children.read() {
         ui_surface.try_remove_children(entity);
@@ -171,6 +193,33 @@ pub fn ui_layout_system(
             &mut font_system,
         );
 
+        // Constraint layouts are resolved from the computed layout of their container and siblings,
+        // so the layout is computed again until it settles.
+        if !constraint_layout_query.is_empty() {
+            let layout_context = LayoutContext::new(
+                computed_target.scale_factor,
+                computed_target.physical_size.as_vec2(),
+            );
+            for _ in 0..MAX_CONSTRAINT_LAYOUT_PASSES {
+                if !resolve_constraint_layouts_recursive(
+                    ui_root_entity,
+                    &mut ui_surface,
+                    &ui_children,
+                    &constraint_layout_query,
+                    &constraint_query,
+                    &layout_context,
+                ) {
+                    break;
+                }
+                ui_surface.compute_layout(
+                    ui_root_entity,
+                    computed_target.physical_size,
+                    &mut buffer_query,
+                    &mut font_system,
+                );
+            }
+        }
+
         update_uinode_geometry_recursive(
             ui_root_entity,
             &mut ui_surface,
@@ -185,6 +234,39 @@ pub fn ui_layout_system(
         );
     }
 
+    fn resolve_constraint_layouts_recursive(
+        entity: Entity,
+        ui_surface: &mut UiSurface,
+        ui_children: &UiChildren,
+        constraint_layout_query: &Query<(), With<ConstraintLayout>>,
+        constraint_query: &Query<(&Node, Option<&EdgeConstraints>)>,
+        layout_context: &LayoutContext,
+    ) -> bool {
+        let mut changed = false;
+        if constraint_layout_query.contains(entity) {
+            changed |= constraint::resolve_constraint_layout(
+                ui_surface,
+                entity,
+                ui_children.iter_ui_children(entity).filter_map(|child| {
+                    let (node, constraints) = constraint_query.get(child).ok()?;
+                    Some((child, node, constraints))
+                }),
+                layout_context,
+            );
+        }
+        for child in ui_children.iter_ui_children(entity) {
+            changed |= resolve_constraint_layouts_recursive(
+                child,
+                ui_surface,
+                ui_children,
+                constraint_layout_query,
+                constraint_query,
+                layout_context,
+            );
+        }
+        changed
+    }
+
     // Returns the combined bounding box of the node and any of its overflowing children.
     fn update_uinode_geometry_recursive(
         entity: Entity,
@@ -198,6 +280,7 @@ pub fn ui_layout_system(
             &mut UiGlobalTransform,
             &Node,
             Option<&LayoutConfig>,
+            Option<&LayoutRounding>,
             Option<&Outline>,
             Option<&ScrollPosition>,
             Option<&IgnoreScroll>,
@@ -213,14 +296,20 @@ pub fn ui_layout_system(
             mut global_transform,
             style,
             maybe_layout_config,
+            maybe_layout_rounding,
             maybe_outline,
             maybe_scroll_position,
             maybe_scroll_sticky,
         )) = node_update_query.get_mut(entity)
         {
-            let use_rounding = maybe_layout_config
-                .map(|layout_config| layout_config.use_rounding)
-                .unwrap_or(inherited_use_rounding);
+            let layout_rounding = maybe_layout_rounding.copied().unwrap_or_default();
+            let use_rounding = match layout_rounding {
+                LayoutRounding::Always => true,
+                LayoutRounding::Never => false,
+                LayoutRounding::Inherit => maybe_layout_config
+                    .map(|layout_config| layout_config.use_rounding)
+                    .unwrap_or(inherited_use_rounding),
+            };
 
             let Ok((layout, unrounded_size)) = ui_surface.get_layout(entity, use_rounding) else {
                 return;
@@ -272,6 +361,12 @@ pub fn ui_layout_system(
             local_transform.translation += local_center;
             inherited_transform *= local_transform;
 
+            if layout_rounding == LayoutRounding::Always && !inherited_use_rounding {
+                // The parent isn't pixel aligned, so snap this node's top-left corner to the pixel grid.
+                let top_left = inherited_transform.transform_point2(-0.5 * layout_size);
+                inherited_transform.translation += top_left.round() - top_left;
+            }
+
             if inherited_transform != **global_transform {
                 *global_transform = inherited_transform.into();
             }
@@ -1059,6 +1154,139 @@ mod tests {
         }
     }
 
+    #[test]
+    fn layout_rounding_override() {
+        let mut app = setup_ui_test_app();
+        let world = app.world_mut();
+
+        let mut snapped = Entity::PLACEHOLDER;
+        let mut unsnapped = Entity::PLACEHOLDER;
+        let parent = world
+            .spawn((
+                Node {
+                    left: Val::Px(10.25),
+                    top: Val::Px(5.5),
+                    width: Val::Px(100.),
+                    height: Val::Px(50.),
+                    ..default()
+                },
+                LayoutRounding::Never,
+            ))
+            .with_children(|commands| {
+                snapped = commands
+                    .spawn((
+                        Node {
+                            width: Val::Px(20.),
+                            height: Val::Px(20.),
+                            ..default()
+                        },
+                        LayoutRounding::Always,
+                    ))
+                    .id();
+                unsnapped = commands
+                    .spawn(Node {
+                        width: Val::Px(20.),
+                        height: Val::Px(20.),
+                        ..default()
+                    })
+                    .id();
+            })
+            .id();
+
+        app.update();
+        let world = app.world();
+
+        let top_left = |entity: Entity| {
+            let node = world.get::<ComputedNode>(entity).unwrap();
+            let transform = world.get::<UiGlobalTransform>(entity).unwrap();
+            transform.translation - 0.5 * node.size()
+        };
+
+        assert_eq!(top_left(parent), Vec2::new(10.25, 5.5));
+        assert_eq!(top_left(snapped), Vec2::new(10., 6.));
+        assert_eq!(top_left(unsnapped), Vec2::new(30.25, 5.5));
+    }
+
+    #[test]
+    fn constraint_layout() {
+        let mut app = setup_ui_test_app();
+        let world = app.world_mut();
+
+        let container = world
+            .spawn((
+                Node {
+                    width: Val::Px(400.),
+                    height: Val::Px(100.),
+                    ..default()
+                },
+                ConstraintLayout,
+            ))
+            .id();
+        let pinned = world
+            .spawn((
+                Node {
+                    width: Val::Px(100.),
+                    height: Val::Px(20.),
+                    ..default()
+                },
+                EdgeConstraints {
+                    right: Some(EdgeAnchor::parent(1.).with_offset(Val::Px(-10.))),
+                    top: Some(EdgeAnchor::parent(0.).with_offset(Val::Px(10.))),
+                    ..default()
+                },
+                ChildOf(container),
+            ))
+            .id();
+        let stretched = world
+            .spawn((
+                Node {
+                    height: Val::Px(30.),
+                    ..default()
+                },
+                EdgeConstraints {
+                    left: Some(EdgeAnchor::sibling(pinned, 0.)),
+                    right: Some(EdgeAnchor::sibling(pinned, 1.)),
+                    top: Some(EdgeAnchor::sibling(pinned, 1.).with_offset(Val::Px(5.))),
+                    ..default()
+                },
+                ChildOf(container),
+            ))
+            .id();
+        let centered = world
+            .spawn((
+                Node {
+                    width: Val::Px(20.),
+                    height: Val::Px(20.),
+                    ..default()
+                },
+                EdgeConstraints::center(),
+                ChildOf(container),
+            ))
+            .id();
+
+        app.update();
+        let world = app.world();
+
+        let rect = |entity: Entity| {
+            let node = world.get::<ComputedNode>(entity).unwrap();
+            let transform = world.get::<UiGlobalTransform>(entity).unwrap();
+            Rect::from_center_size(transform.translation, node.size())
+        };
+
+        assert_eq!(
+            rect(pinned),
+            Rect::from_corners(Vec2::new(290., 10.), Vec2::new(390., 30.))
+        );
+        assert_eq!(
+            rect(stretched),
+            Rect::from_corners(Vec2::new(290., 35.), Vec2::new(390., 65.))
+        );
+        assert_eq!(
+            rect(centered),
+            Rect::from_corners(Vec2::new(190., 40.), Vec2::new(210., 60.))
+        );
+    }
+
     #[test]
     fn no_camera_ui() {
         let mut app = App::new();
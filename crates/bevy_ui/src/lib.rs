@@ -12,6 +12,7 @@
 
 pub mod interaction_states;
 pub mod measurement;
+pub mod transition;
 pub mod update;
 pub mod widget;
 
@@ -21,6 +22,8 @@ pub mod picking_backend;
 pub mod ui_transform;
 
 use bevy_derive::{Deref, DerefMut};
+#[cfg(feature = "bevy_gizmos")]
+use bevy_gizmos::screen::ScreenGizmoCamera;
 #[cfg(feature = "bevy_picking")]
 use bevy_picking::PickingSystems;
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
@@ -32,7 +35,9 @@ mod focus;
 mod geometry;
 mod layout;
 mod stack;
+mod theme;
 mod ui_node;
+mod world_surface;
 
 pub use focus::*;
 pub use geometry::*;
@@ -40,8 +45,11 @@ pub use gradients::*;
 pub use interaction_states::{Checkable, Checked, InteractionDisabled, Pressed};
 pub use layout::*;
 pub use measurement::*;
+pub use theme::*;
+pub use transition::*;
 pub use ui_node::*;
 pub use ui_transform::*;
+pub use world_surface::*;
 
 /// The UI prelude.
 ///
@@ -51,7 +59,9 @@ pub mod prelude {
     #[cfg(feature = "bevy_picking")]
     pub use crate::picking_backend::{UiPickingCamera, UiPickingPlugin, UiPickingSettings};
     #[doc(hidden)]
-    pub use crate::widget::{Text, TextShadow, TextUiReader, TextUiWriter};
+    pub use crate::widget::{
+        Text, TextInput, TextInputValue, TextShadow, TextUiReader, TextUiWriter,
+    };
     #[doc(hidden)]
     pub use {
         crate::{
@@ -59,8 +69,8 @@ pub mod prelude {
             gradients::*,
             ui_node::*,
             ui_transform::*,
-            widget::{Button, ImageNode, Label, NodeImageMode, ViewportNode},
-            Interaction, UiScale,
+            widget::{BorderImage, Button, ImageNode, Label, NodeImageMode, ViewportNode},
+            ActiveTheme, Classes, Interaction, Theme, UiScale, WorldUiSurface,
         },
         // `bevy_sprite` re-exports for texture slicing
         bevy_sprite::{BorderRect, SliceScaleMode, SpriteImageMode, TextureSlicer},
@@ -69,6 +79,7 @@ pub mod prelude {
 }
 
 use bevy_app::{prelude::*, AnimationSystems, HierarchyPropagatePlugin, PropagateSet};
+use bevy_asset::AssetApp;
 use bevy_camera::CameraUpdateSystems;
 use bevy_ecs::prelude::*;
 use bevy_input::InputSystems;
@@ -136,6 +147,8 @@ impl Plugin for UiPlugin {
         app.init_resource::<UiSurface>()
             .init_resource::<UiScale>()
             .init_resource::<UiStack>()
+            .init_resource::<ActiveTheme>()
+            .init_asset::<Theme>()
             .configure_sets(
                 PostUpdate,
                 (
@@ -162,17 +175,38 @@ impl Plugin for UiPlugin {
             .add_plugins(HierarchyPropagatePlugin::<ComputedUiRenderTargetInfo>::new(
                 PostUpdate,
             ))
+            .add_plugins((
+                UiTransitionPlugin::<Node>::default(),
+                UiTransitionPlugin::<BackgroundColor>::default(),
+                UiTransitionPlugin::<BorderColor>::default(),
+                UiTransitionPlugin::<UiTransform>::default(),
+            ))
             .add_systems(
                 PreUpdate,
                 ui_focus_system.in_set(UiSystems::Focus).after(InputSystems),
             );
 
+        #[cfg(feature = "serialize")]
+        app.register_asset_loader(ThemeLoader);
+
+        // The screen gizmo camera is drawn on top of everything, but doesn't render any UI.
+        #[cfg(feature = "bevy_gizmos")]
+        app.register_required_components_with::<ScreenGizmoCamera, UiLayerFilter>(|| {
+            UiLayerFilter::NONE
+        });
+
         #[cfg(feature = "bevy_picking")]
         app.add_plugins(picking_backend::UiPickingPlugin)
             .add_systems(
                 First,
-                widget::viewport_picking.in_set(PickingSystems::PostInput),
-            );
+                (widget::viewport_picking, world_ui_surface_pointer_input)
+                    .in_set(PickingSystems::PostInput),
+            )
+            .add_systems(
+                PreUpdate,
+                world_ui_surface_picking.in_set(PickingSystems::Backend),
+            )
+            .add_observer(widget::text_link_on_click);
 
         let ui_layout_system_config = ui_layout_system
             .in_set(UiSystems::Layout)
@@ -187,6 +221,11 @@ impl Plugin for UiPlugin {
             PostUpdate,
             (
                 propagate_ui_target_cameras.in_set(UiSystems::Prepare),
+                apply_theme_classes
+                    .in_set(UiSystems::Prepare)
+                    .before(update_transitions::<Node>)
+                    .before(update_transitions::<BackgroundColor>)
+                    .before(update_transitions::<BorderColor>),
                 ui_layout_system_config,
                 ui_stack_system
                     .in_set(UiSystems::Stack)
@@ -226,10 +265,12 @@ fn build_text_interop(app: &mut App) {
         PostUpdate,
         (
             (
+                widget::update_text_input_text,
                 bevy_text::detect_text_needs_rerender::<Text>,
                 widget::measure_text_system,
             )
                 .chain()
+                .after(bevy_text::update_markup_spans)
                 .in_set(UiSystems::Content)
                 // Text and Text2d are independent.
                 .ambiguous_with(bevy_text::detect_text_needs_rerender::<bevy_sprite::Text2d>)
@@ -248,6 +289,9 @@ fn build_text_interop(app: &mut App) {
                 .ambiguous_with(bevy_text::detect_text_needs_rerender::<bevy_sprite::Text2d>)
                 .ambiguous_with(bevy_sprite::update_text2d_layout)
                 .ambiguous_with(bevy_sprite::calculate_bounds_text2d),
+            widget::update_text_input_layout
+                .in_set(UiSystems::PostLayout)
+                .after(widget::text_system),
         ),
     );
 
@@ -2,7 +2,7 @@
 
 use crate::{
     experimental::{UiChildren, UiRootNodes},
-    ComputedNode, GlobalZIndex, ZIndex,
+    ComputedNode, GlobalZIndex, UiLayer, ZIndex,
 };
 use bevy_ecs::prelude::*;
 use bevy_platform::collections::HashSet;
@@ -37,16 +37,23 @@ impl ChildBufferCache {
 
 /// Generates the render stack for UI nodes.
 ///
-/// Create a list of root nodes from parentless entities and entities with a `GlobalZIndex` component.
+/// Create a list of root nodes from parentless entities and entities with a `GlobalZIndex` component,
+/// sorted by their `UiLayer`, `GlobalZIndex` and then `ZIndex`.
 /// Then build the `UiStack` from a walk of the existing layout trees starting from each root node,
 /// filtering branches by `Without<GlobalZIndex>`so that we don't revisit nodes.
 pub fn ui_stack_system(
     mut cache: Local<ChildBufferCache>,
-    mut root_nodes: Local<Vec<(Entity, (i32, i32))>>,
+    mut root_nodes: Local<Vec<(Entity, (i32, i32, i32))>>,
     mut visited_root_nodes: Local<HashSet<Entity>>,
     mut ui_stack: ResMut<UiStack>,
     ui_root_nodes: UiRootNodes,
-    root_node_query: Query<(Entity, Option<&GlobalZIndex>, Option<&ZIndex>)>,
+    root_node_query: Query<(
+        Entity,
+        Option<&UiLayer>,
+        Option<&GlobalZIndex>,
+        Option<&ZIndex>,
+    )>,
+    layer_query: Query<&UiLayer>,
     zindex_global_node_query: Query<(Entity, &GlobalZIndex, Option<&ZIndex>), With<ComputedNode>>,
     ui_children: UiChildren,
     zindex_query: Query<Option<&ZIndex>, (With<ComputedNode>, Without<GlobalZIndex>)>,
@@ -56,10 +63,13 @@ pub fn ui_stack_system(
     ui_stack.uinodes.clear();
     visited_root_nodes.clear();
 
-    for (id, maybe_global_zindex, maybe_zindex) in root_node_query.iter_many(ui_root_nodes.iter()) {
+    for (id, maybe_layer, maybe_global_zindex, maybe_zindex) in
+        root_node_query.iter_many(ui_root_nodes.iter())
+    {
         root_nodes.push((
             id,
             (
+                maybe_layer.map(|layer| layer.0).unwrap_or(0),
                 maybe_global_zindex.map(|zindex| zindex.0).unwrap_or(0),
                 maybe_zindex.map(|zindex| zindex.0).unwrap_or(0),
             ),
@@ -72,9 +82,18 @@ pub fn ui_stack_system(
             continue;
         }
 
+        // Nodes with a `GlobalZIndex` are drawn on the layer of their root node.
+        let mut root = id;
+        while let Some(parent) = ui_children.get_parent(root)
+            && ui_children.is_ui_node(parent)
+        {
+            root = parent;
+        }
+
         root_nodes.push((
             id,
             (
+                layer_query.get(root).map_or(0, |layer| layer.0),
                 global_zindex.0,
                 maybe_zindex.map(|zindex| zindex.0).unwrap_or(0),
             ),
@@ -139,7 +158,7 @@ mod tests {
         world::{CommandQueue, World},
     };
 
-    use crate::{GlobalZIndex, Node, UiStack, ZIndex};
+    use crate::{GlobalZIndex, Node, UiLayer, UiStack, ZIndex};
 
     use super::ui_stack_system;
 
@@ -338,4 +357,43 @@ mod tests {
             assert_eq!(*part, i..i + 1);
         }
     }
+
+    #[test]
+    fn test_ui_layer_is_ordered_before_global_zindex() {
+        let mut world = World::default();
+        world.init_resource::<UiStack>();
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        commands
+            .spawn((node_without_zindex("0"), UiLayer(1)))
+            .with_children(|builder| {
+                builder.spawn(node_with_global_zindex("0-0", -1));
+            });
+        commands.spawn(node_with_global_zindex("1", 5));
+        commands.spawn((node_with_zindex("2", -1), UiLayer(-1)));
+
+        queue.apply(&mut world);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(ui_stack_system);
+        schedule.run(&mut world);
+
+        let mut query = world.query::<&Label>();
+        let ui_stack = world.resource::<UiStack>();
+        let actual_result = ui_stack
+            .uinodes
+            .iter()
+            .map(|entity| query.get(&world, *entity).unwrap().clone())
+            .collect::<Vec<_>>();
+
+        let expected_result = vec![
+            (Label("2")),   // UiLayer(-1)
+            (Label("1")),   // GlobalZIndex(5)
+            (Label("0-0")), // UiLayer(1) inherited from its root, GlobalZIndex(-1)
+            (Label("0")),   // UiLayer(1)
+        ];
+
+        assert_eq!(actual_result, expected_result);
+    }
 }
@@ -0,0 +1,394 @@
+//! Style sheets for UI nodes.
+//!
+//! A [`Theme`] is an asset of named [`StyleClass`]es. Nodes list the classes they use in a
+//! [`Classes`] component, and the values of those classes are applied to the node's components
+//! before layout. Changing the [`ActiveTheme`], or editing the theme asset with hot reloading
+//! enabled, restyles every node that uses classes.
+//!
+//! With the `serialize` feature, themes can be loaded from `.theme.ron` files:
+//!
+//! ```ron
+//! (
+//!     classes: {
+//!         "panel": (
+//!             background_color: Some(Srgba((red: 0.1, green: 0.1, blue: 0.1, alpha: 1.0))),
+//!             padding: Some((left: Px(8.0), right: Px(8.0), top: Px(8.0), bottom: Px(8.0))),
+//!             border_radius: Some((
+//!                 top_left: Px(4.0),
+//!                 top_right: Px(4.0),
+//!                 bottom_right: Px(4.0),
+//!                 bottom_left: Px(4.0),
+//!             )),
+//!         ),
+//!         "title": (
+//!             font: Some("fonts/FiraSans-Bold.ttf"),
+//!             font_size: Some(24.0),
+//!         ),
+//!     },
+//! )
+//! ```
+
+use std::borrow::Cow;
+
+use bevy_asset::{Asset, AssetEvent, Assets, Handle};
+use bevy_color::Color;
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    change_detection::{DetectChanges, DetectChangesMut},
+    component::Component,
+    message::MessageReader,
+    reflect::{ReflectComponent, ReflectResource},
+    resource::Resource,
+    system::{Query, Res},
+    world::Ref,
+};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect, TypePath};
+use bevy_text::{Font, TextColor, TextFont};
+
+use crate::{BackgroundColor, BorderColor, BorderRadius, Node, UiRect};
+
+/// An asset of named [`StyleClass`]es, which are applied to UI nodes with [`Classes`].
+#[derive(Asset, TypePath, Debug, Clone, Default)]
+pub struct Theme {
+    /// The classes of the theme, by name.
+    pub classes: HashMap<String, StyleClass>,
+}
+
+impl Theme {
+    /// Returns the class with the given `name`, if the theme has one.
+    pub fn get(&self, name: &str) -> Option<&StyleClass> {
+        self.classes.get(name)
+    }
+
+    /// Adds a class to the theme, replacing any class with the same name.
+    pub fn insert(&mut self, name: impl Into<String>, class: StyleClass) -> &mut Self {
+        self.classes.insert(name.into(), class);
+        self
+    }
+
+    /// Returns the style of a node with the given classes. Classes later in the list override the
+    /// values of earlier classes. Names without a class in the theme are ignored.
+    pub fn resolve<'a>(&self, classes: impl IntoIterator<Item = &'a str>) -> StyleClass {
+        let mut style = StyleClass::default();
+        for class in classes.into_iter().filter_map(|name| self.get(name)) {
+            style.merge(class);
+        }
+        style
+    }
+}
+
+/// A set of style values applied to the UI nodes that use the class.
+///
+/// Values that are `None` are left unchanged.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StyleClass {
+    /// Sets the [`BackgroundColor`] of the node.
+    pub background_color: Option<Color>,
+    /// Sets every side of the [`BorderColor`] of the node.
+    pub border_color: Option<Color>,
+    /// Sets the [`TextColor`] of the node.
+    pub text_color: Option<Color>,
+    /// Sets [`Node::padding`].
+    pub padding: Option<UiRect>,
+    /// Sets [`Node::margin`].
+    pub margin: Option<UiRect>,
+    /// Sets [`Node::border`].
+    pub border: Option<UiRect>,
+    /// Sets [`Node::border_radius`].
+    pub border_radius: Option<BorderRadius>,
+    /// Sets the [`TextFont::font`] of the node.
+    pub font: Option<Handle<Font>>,
+    /// Sets the [`TextFont::font_size`] of the node.
+    pub font_size: Option<f32>,
+}
+
+impl StyleClass {
+    /// Overrides the values of this class with the values set by `other`.
+    pub fn merge(&mut self, other: &StyleClass) {
+        fn merge<T: Clone>(value: &mut Option<T>, other: &Option<T>) {
+            if other.is_some() {
+                value.clone_from(other);
+            }
+        }
+        merge(&mut self.background_color, &other.background_color);
+        merge(&mut self.border_color, &other.border_color);
+        merge(&mut self.text_color, &other.text_color);
+        merge(&mut self.padding, &other.padding);
+        merge(&mut self.margin, &other.margin);
+        merge(&mut self.border, &other.border);
+        merge(&mut self.border_radius, &other.border_radius);
+        merge(&mut self.font, &other.font);
+        merge(&mut self.font_size, &other.font_size);
+    }
+}
+
+/// The style classes used by a UI node, from the [`ActiveTheme`].
+///
+/// Classes later in the list override the values of earlier classes. The classes are applied when
+/// this component changes, and when the theme changes. Properties that none of the classes set
+/// are left unchanged, so they can still be set on the node directly.
+#[derive(Component, Debug, Clone, Default, PartialEq, Deref, DerefMut, Reflect)]
+#[reflect(Component, Default, Debug, Clone, PartialEq)]
+pub struct Classes(pub Vec<Cow<'static, str>>);
+
+impl Classes {
+    /// Creates a list of classes from a whitespace separated string, like `"panel dark"`.
+    pub fn new(classes: &str) -> Self {
+        Self(
+            classes
+                .split_whitespace()
+                .map(|class| Cow::Owned(class.into()))
+                .collect(),
+        )
+    }
+
+    /// Returns `true` if the list contains the class `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.iter().any(|class| class == name)
+    }
+
+    /// Adds the class `name` to the end of the list, if it isn't already in the list.
+    pub fn add(&mut self, name: impl Into<Cow<'static, str>>) {
+        let name = name.into();
+        if !self.contains(&name) {
+            self.0.push(name);
+        }
+    }
+
+    /// Removes the class `name` from the list.
+    pub fn remove(&mut self, name: &str) {
+        self.0.retain(|class| class != name);
+    }
+}
+
+impl From<&'static str> for Classes {
+    fn from(classes: &'static str) -> Self {
+        Self::new(classes)
+    }
+}
+
+/// The [`Theme`] used to resolve the [`Classes`] of UI nodes.
+#[derive(Resource, Debug, Clone, Default, Deref, DerefMut, Reflect)]
+#[reflect(Resource, Default, Debug, Clone)]
+pub struct ActiveTheme(pub Handle<Theme>);
+
+/// Applies the [`ActiveTheme`] to the nodes with [`Classes`].
+///
+/// Nodes are restyled when their classes change, and all nodes are restyled when the theme
+/// changes or is reloaded.
+pub fn apply_theme_classes(
+    active_theme: Res<ActiveTheme>,
+    themes: Res<Assets<Theme>>,
+    mut theme_events: MessageReader<AssetEvent<Theme>>,
+    mut query: Query<(
+        Ref<Classes>,
+        &mut Node,
+        &mut BackgroundColor,
+        &mut BorderColor,
+        Option<&mut TextColor>,
+        Option<&mut TextFont>,
+    )>,
+) {
+    let id = active_theme.id();
+    let theme_events = theme_events
+        .read()
+        .filter(|event| event.is_modified(id) || event.is_loaded_with_dependencies(id))
+        .count();
+    let theme_changed = active_theme.is_changed() || theme_events > 0;
+    let Some(theme) = themes.get(id) else {
+        return;
+    };
+
+    for (classes, mut node, mut background_color, mut border_color, text_color, text_font) in
+        &mut query
+    {
+        if !theme_changed && !classes.is_changed() {
+            continue;
+        }
+        let style = theme.resolve(classes.iter().map(AsRef::as_ref));
+
+        if let Some(color) = style.background_color {
+            background_color.set_if_neq(BackgroundColor(color));
+        }
+        if let Some(color) = style.border_color {
+            border_color.set_if_neq(BorderColor::all(color));
+        }
+        if let Some(padding) = style.padding
+            && node.padding != padding
+        {
+            node.padding = padding;
+        }
+        if let Some(margin) = style.margin
+            && node.margin != margin
+        {
+            node.margin = margin;
+        }
+        if let Some(border) = style.border
+            && node.border != border
+        {
+            node.border = border;
+        }
+        if let Some(border_radius) = style.border_radius
+            && node.border_radius != border_radius
+        {
+            node.border_radius = border_radius;
+        }
+        if let Some(mut text_color) = text_color
+            && let Some(color) = style.text_color
+        {
+            text_color.set_if_neq(TextColor(color));
+        }
+        if let Some(mut text_font) = text_font {
+            if let Some(font) = style.font
+                && text_font.font != font
+            {
+                text_font.font = font;
+            }
+            if let Some(font_size) = style.font_size
+                && text_font.font_size != font_size
+            {
+                text_font.font_size = font_size;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+mod loader {
+    use bevy_asset::{io::Reader, AssetLoader, LoadContext};
+    use bevy_color::Color;
+    use bevy_platform::collections::HashMap;
+    use bevy_reflect::TypePath;
+    use ron::de::SpannedError;
+    use serde::Deserialize;
+    use thiserror::Error;
+
+    use super::{StyleClass, Theme};
+    use crate::{BorderRadius, UiRect};
+
+    /// Loads [`Theme`] assets from `.theme.ron` files.
+    #[derive(Default, TypePath)]
+    pub struct ThemeLoader;
+
+    /// Errors that can occur when loading a [`Theme`].
+    #[derive(Error, Debug)]
+    pub enum ThemeLoaderError {
+        /// An I/O error occurred.
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        /// An error occurred in RON deserialization.
+        #[error(transparent)]
+        Ron(#[from] SpannedError),
+    }
+
+    /// The serialized form of a [`Theme`], with the fonts given by asset paths.
+    #[derive(Deserialize)]
+    struct SerializedTheme {
+        classes: HashMap<String, SerializedStyleClass>,
+    }
+
+    #[derive(Deserialize, Default)]
+    #[serde(default)]
+    struct SerializedStyleClass {
+        background_color: Option<Color>,
+        border_color: Option<Color>,
+        text_color: Option<Color>,
+        padding: Option<UiRect>,
+        margin: Option<UiRect>,
+        border: Option<UiRect>,
+        border_radius: Option<BorderRadius>,
+        font: Option<String>,
+        font_size: Option<f32>,
+    }
+
+    impl AssetLoader for ThemeLoader {
+        type Asset = Theme;
+        type Settings = ();
+        type Error = ThemeLoaderError;
+
+        async fn load(
+            &self,
+            reader: &mut dyn Reader,
+            _settings: &(),
+            load_context: &mut LoadContext<'_>,
+        ) -> Result<Self::Asset, Self::Error> {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let theme: SerializedTheme = ron::de::from_bytes(&bytes)?;
+
+            let classes = theme
+                .classes
+                .into_iter()
+                .map(|(name, class)| {
+                    let class = StyleClass {
+                        background_color: class.background_color,
+                        border_color: class.border_color,
+                        text_color: class.text_color,
+                        padding: class.padding,
+                        margin: class.margin,
+                        border: class.border,
+                        border_radius: class.border_radius,
+                        font: class.font.map(|path| load_context.load(path)),
+                        font_size: class.font_size,
+                    };
+                    (name, class)
+                })
+                .collect();
+            Ok(Theme { classes })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["theme.ron"]
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+pub use loader::{ThemeLoader, ThemeLoaderError};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Val;
+
+    #[test]
+    fn later_classes_override_earlier_classes() {
+        let mut theme = Theme::default();
+        theme
+            .insert(
+                "panel",
+                StyleClass {
+                    background_color: Some(Color::BLACK),
+                    padding: Some(UiRect::all(Val::Px(4.))),
+                    ..Default::default()
+                },
+            )
+            .insert(
+                "highlighted",
+                StyleClass {
+                    background_color: Some(Color::WHITE),
+                    ..Default::default()
+                },
+            );
+
+        let style = theme.resolve(["panel", "missing", "highlighted"]);
+        assert_eq!(style.background_color, Some(Color::WHITE));
+        assert_eq!(style.padding, Some(UiRect::all(Val::Px(4.))));
+        assert_eq!(style.border, None);
+
+        let style = theme.resolve(["highlighted", "panel"]);
+        assert_eq!(style.background_color, Some(Color::BLACK));
+    }
+
+    #[test]
+    fn classes_from_string() {
+        let mut classes = Classes::new("panel  dark");
+        assert!(classes.contains("panel"));
+        assert!(classes.contains("dark"));
+        classes.add("dark");
+        classes.add("wide");
+        classes.remove("panel");
+        assert_eq!(classes.0, ["dark", "wide"]);
+    }
+}
@@ -0,0 +1,483 @@
+//! Animated transitions between the values of UI style components.
+//!
+//! Adding a [`Transition<C>`] to a UI node makes changes to its `C` component animate smoothly,
+//! instead of applying instantly. Any change to the component starts a transition: changes made
+//! by your own systems, as well as changes made by [`StateStyles<C>`] when the node is hovered,
+//! pressed or disabled.
+//!
+//! Transitions are supported for [`Node`], [`BackgroundColor`], [`BorderColor`] and
+//! [`UiTransform`] by the [`UiPlugin`](crate::UiPlugin). Other components can be animated by
+//! implementing [`Transitionable`] for them and adding a [`UiTransitionPlugin`] for them.
+
+use core::{marker::PhantomData, time::Duration};
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_color::{Color, Mix, Oklaba};
+use bevy_ecs::{
+    change_detection::{DetectChanges, DetectChangesMut},
+    component::{Component, Mutable},
+    entity::{Entity, EntityHashSet},
+    lifecycle::RemovedComponents,
+    schedule::IntoScheduleConfigs,
+    system::{Query, Res},
+    world::Ref,
+};
+use bevy_math::{
+    curve::{Curve, EaseFunction},
+    FloatExt,
+};
+use bevy_time::Time;
+
+use crate::{
+    BackgroundColor, BorderColor, BorderRadius, Interaction, InteractionDisabled, Node, Pressed,
+    UiRect, UiSystems, UiTransform, Val, Val2,
+};
+
+/// A component that can be animated by a [`Transition`].
+pub trait Transitionable: Component<Mutability = Mutable> + Clone + PartialEq {
+    /// Returns the value part of the way from `self` to `target`, where `t` is `0.` at `self` and
+    /// `1.` at `target`.
+    fn interpolate(&self, target: &Self, t: f32) -> Self;
+}
+
+/// Animates the changes to the `C` component of a UI node.
+///
+/// When the value of `C` changes, the component is reset to the value it was showing, and then
+/// animated to the new value over [`duration`](Self::duration). If the value changes again while
+/// the transition is running, the new transition starts from the value reached so far.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_color::Color;
+/// # use bevy_math::curve::EaseFunction;
+/// # use bevy_ui::{BackgroundColor, Node, StateStyles, Transition};
+/// # use core::time::Duration;
+/// fn spawn_button(mut commands: Commands) {
+///     commands.spawn((
+///         Node::default(),
+///         BackgroundColor(Color::BLACK),
+///         StateStyles::new(BackgroundColor(Color::BLACK))
+///             .with_hovered(BackgroundColor(Color::srgb(0.2, 0.2, 0.2)))
+///             .with_pressed(BackgroundColor(Color::srgb(0.4, 0.4, 0.4))),
+///         Transition::<BackgroundColor>::new(Duration::from_millis(150))
+///             .with_easing(EaseFunction::QuadraticOut),
+///     ));
+/// }
+/// ```
+#[derive(Component, Debug, Clone)]
+pub struct Transition<C: Transitionable> {
+    /// How long it takes to transition to a new value.
+    pub duration: Duration,
+    /// The easing curve applied to the transition.
+    pub easing: EaseFunction,
+    /// The state of the running transition, or `None` before the component was first seen.
+    state: Option<TransitionState<C>>,
+}
+
+#[derive(Debug, Clone)]
+struct TransitionState<C> {
+    /// The value shown when the transition started.
+    start: C,
+    /// The value being transitioned to.
+    target: C,
+    /// The last value written to the component by the transition.
+    current: C,
+    /// Time elapsed since the transition started.
+    elapsed: Duration,
+}
+
+impl<C: Transitionable> Transition<C> {
+    /// Creates a linear transition that takes `duration`.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            easing: EaseFunction::Linear,
+            state: None,
+        }
+    }
+
+    /// Returns this transition with the given easing curve.
+    pub fn with_easing(mut self, easing: EaseFunction) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Returns `true` if the component is currently being animated.
+    pub fn is_running(&self) -> bool {
+        self.state
+            .as_ref()
+            .is_some_and(|state| state.elapsed < self.duration)
+    }
+
+    /// Returns the value being transitioned to, if the transition has started.
+    pub fn target(&self) -> Option<&C> {
+        self.state.as_ref().map(|state| &state.target)
+    }
+
+    /// Advances the transition by `delta`, taking `value` as the component's value before the
+    /// update. Returns the value the component should have, if it should change.
+    fn advance(&mut self, value: &C, delta: Duration) -> Option<C> {
+        let Some(state) = &mut self.state else {
+            // Nothing to animate from the first value.
+            self.state = Some(TransitionState {
+                start: value.clone(),
+                target: value.clone(),
+                current: value.clone(),
+                elapsed: self.duration,
+            });
+            return None;
+        };
+
+        if *value != state.current {
+            // The component was changed outside of the transition, so transition to the new value
+            // from the value that was being shown.
+            state.start = state.current.clone();
+            state.target = value.clone();
+            state.elapsed = Duration::ZERO;
+        } else if state.elapsed >= self.duration {
+            return None;
+        } else {
+            state.elapsed += delta;
+        }
+
+        let t = if self.duration.is_zero() {
+            1.
+        } else {
+            (state.elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.)
+        };
+        state.current = if t < 1. {
+            state
+                .start
+                .interpolate(&state.target, self.easing.sample_clamped(t))
+        } else {
+            state.target.clone()
+        };
+        Some(state.current.clone())
+    }
+}
+
+/// Sets the `C` component of a UI node depending on its interaction state.
+///
+/// The node needs an [`Interaction`] component, or a [`Pressed`] component added by a widget, to
+/// be styled as hovered or pressed. Add a [`Transition<C>`] to animate the changes between states.
+///
+/// The component is only set when the state changes. To style the node depending on other states,
+/// set the component from your own systems.
+#[derive(Component, Debug, Clone)]
+pub struct StateStyles<C: Transitionable> {
+    /// The value of the component when none of the other states apply.
+    pub normal: C,
+    /// The value of the component while the node is hovered.
+    pub hovered: Option<C>,
+    /// The value of the component while the node is pressed.
+    ///
+    /// Falls back to [`hovered`](Self::hovered) if it's `None`.
+    pub pressed: Option<C>,
+    /// The value of the component while the node has [`InteractionDisabled`].
+    ///
+    /// Falls back to [`normal`](Self::normal) if it's `None`.
+    pub disabled: Option<C>,
+}
+
+impl<C: Transitionable> StateStyles<C> {
+    /// Creates styles that set the component to `normal` in every state.
+    pub fn new(normal: C) -> Self {
+        Self {
+            normal,
+            hovered: None,
+            pressed: None,
+            disabled: None,
+        }
+    }
+
+    /// Returns these styles with the value for hovered nodes set to `hovered`.
+    pub fn with_hovered(mut self, hovered: C) -> Self {
+        self.hovered = Some(hovered);
+        self
+    }
+
+    /// Returns these styles with the value for pressed nodes set to `pressed`.
+    pub fn with_pressed(mut self, pressed: C) -> Self {
+        self.pressed = Some(pressed);
+        self
+    }
+
+    /// Returns these styles with the value for disabled nodes set to `disabled`.
+    pub fn with_disabled(mut self, disabled: C) -> Self {
+        self.disabled = Some(disabled);
+        self
+    }
+
+    /// Returns the value of the component for the given interaction state.
+    pub fn get(&self, interaction: Interaction, disabled: bool) -> &C {
+        if disabled {
+            return self.disabled.as_ref().unwrap_or(&self.normal);
+        }
+        match interaction {
+            Interaction::Pressed => self
+                .pressed
+                .as_ref()
+                .or(self.hovered.as_ref())
+                .unwrap_or(&self.normal),
+            Interaction::Hovered => self.hovered.as_ref().unwrap_or(&self.normal),
+            Interaction::None => &self.normal,
+        }
+    }
+}
+
+/// Sets the components styled by [`StateStyles`] when the interaction state of their node changes.
+pub fn update_state_styles<C: Transitionable>(
+    mut query: Query<(
+        Entity,
+        &mut C,
+        Ref<StateStyles<C>>,
+        Option<Ref<Interaction>>,
+        Option<Ref<Pressed>>,
+        Option<Ref<InteractionDisabled>>,
+    )>,
+    mut removed_pressed: RemovedComponents<Pressed>,
+    mut removed_disabled: RemovedComponents<InteractionDisabled>,
+) {
+    let removed: EntityHashSet = removed_pressed
+        .read()
+        .chain(removed_disabled.read())
+        .collect();
+
+    for (entity, mut component, styles, interaction, pressed, disabled) in &mut query {
+        let changed = styles.is_changed()
+            || interaction.as_ref().is_some_and(DetectChanges::is_changed)
+            || pressed.as_ref().is_some_and(DetectChanges::is_changed)
+            || disabled.as_ref().is_some_and(DetectChanges::is_changed)
+            || removed.contains(&entity);
+        if !changed {
+            continue;
+        }
+
+        let interaction = if pressed.is_some() {
+            Interaction::Pressed
+        } else {
+            interaction.map_or(Interaction::None, |interaction| *interaction)
+        };
+        let value = styles.get(interaction, disabled.is_some());
+        component.set_if_neq(value.clone());
+    }
+}
+
+/// Advances the [`Transition`]s of the `C` components.
+pub fn update_transitions<C: Transitionable>(
+    mut query: Query<(&mut C, &mut Transition<C>)>,
+    time: Res<Time>,
+) {
+    for (mut component, mut transition) in &mut query {
+        if let Some(value) = transition.advance(&component, time.delta()) {
+            component.set_if_neq(value);
+        }
+    }
+}
+
+/// Adds the systems that update [`StateStyles<C>`] and [`Transition<C>`].
+///
+/// The [`UiPlugin`](crate::UiPlugin) adds this plugin for [`Node`], [`BackgroundColor`],
+/// [`BorderColor`] and [`UiTransform`].
+pub struct UiTransitionPlugin<C: Transitionable>(PhantomData<C>);
+
+impl<C: Transitionable> Default for UiTransitionPlugin<C> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<C: Transitionable> Plugin for UiTransitionPlugin<C> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (update_state_styles::<C>, update_transitions::<C>)
+                .chain()
+                .in_set(UiSystems::Prepare),
+        );
+    }
+}
+
+/// Interpolates between two [`Val`]s with the same unit. Values with different units can't be
+/// interpolated, so they switch halfway through instead.
+pub fn interpolate_val(start: Val, end: Val, t: f32) -> Val {
+    match (start, end) {
+        (Val::Px(a), Val::Px(b)) => Val::Px(a.lerp(b, t)),
+        (Val::Percent(a), Val::Percent(b)) => Val::Percent(a.lerp(b, t)),
+        (Val::Vw(a), Val::Vw(b)) => Val::Vw(a.lerp(b, t)),
+        (Val::Vh(a), Val::Vh(b)) => Val::Vh(a.lerp(b, t)),
+        (Val::VMin(a), Val::VMin(b)) => Val::VMin(a.lerp(b, t)),
+        (Val::VMax(a), Val::VMax(b)) => Val::VMax(a.lerp(b, t)),
+        _ if t < 0.5 => start,
+        _ => end,
+    }
+}
+
+fn interpolate_rect(start: UiRect, end: UiRect, t: f32) -> UiRect {
+    UiRect {
+        left: interpolate_val(start.left, end.left, t),
+        right: interpolate_val(start.right, end.right, t),
+        top: interpolate_val(start.top, end.top, t),
+        bottom: interpolate_val(start.bottom, end.bottom, t),
+    }
+}
+
+fn interpolate_color(start: Color, end: Color, t: f32) -> Color {
+    // Mixing in Oklab avoids the muddy midpoints of mixing in sRGB.
+    Oklaba::from(start).mix(&Oklaba::from(end), t).into()
+}
+
+impl Transitionable for BackgroundColor {
+    fn interpolate(&self, target: &Self, t: f32) -> Self {
+        Self(interpolate_color(self.0, target.0, t))
+    }
+}
+
+impl Transitionable for BorderColor {
+    fn interpolate(&self, target: &Self, t: f32) -> Self {
+        Self {
+            top: interpolate_color(self.top, target.top, t),
+            right: interpolate_color(self.right, target.right, t),
+            bottom: interpolate_color(self.bottom, target.bottom, t),
+            left: interpolate_color(self.left, target.left, t),
+        }
+    }
+}
+
+impl Transitionable for UiTransform {
+    fn interpolate(&self, target: &Self, t: f32) -> Self {
+        Self {
+            translation: Val2 {
+                x: interpolate_val(self.translation.x, target.translation.x, t),
+                y: interpolate_val(self.translation.y, target.translation.y, t),
+            },
+            scale: self.scale.lerp(target.scale, t),
+            rotation: self.rotation.slerp(target.rotation, t),
+        }
+    }
+}
+
+impl Transitionable for Node {
+    /// Interpolates the sizes, positions and spacing of the node. The other properties can't be
+    /// interpolated, so they switch halfway through.
+    fn interpolate(&self, target: &Self, t: f32) -> Self {
+        let mut node = if t < 0.5 {
+            self.clone()
+        } else {
+            target.clone()
+        };
+        node.left = interpolate_val(self.left, target.left, t);
+        node.right = interpolate_val(self.right, target.right, t);
+        node.top = interpolate_val(self.top, target.top, t);
+        node.bottom = interpolate_val(self.bottom, target.bottom, t);
+        node.width = interpolate_val(self.width, target.width, t);
+        node.height = interpolate_val(self.height, target.height, t);
+        node.min_width = interpolate_val(self.min_width, target.min_width, t);
+        node.min_height = interpolate_val(self.min_height, target.min_height, t);
+        node.max_width = interpolate_val(self.max_width, target.max_width, t);
+        node.max_height = interpolate_val(self.max_height, target.max_height, t);
+        node.margin = interpolate_rect(self.margin, target.margin, t);
+        node.padding = interpolate_rect(self.padding, target.padding, t);
+        node.border = interpolate_rect(self.border, target.border, t);
+        node.border_radius = BorderRadius {
+            top_left: interpolate_val(
+                self.border_radius.top_left,
+                target.border_radius.top_left,
+                t,
+            ),
+            top_right: interpolate_val(
+                self.border_radius.top_right,
+                target.border_radius.top_right,
+                t,
+            ),
+            bottom_right: interpolate_val(
+                self.border_radius.bottom_right,
+                target.border_radius.bottom_right,
+                t,
+            ),
+            bottom_left: interpolate_val(
+                self.border_radius.bottom_left,
+                target.border_radius.bottom_left,
+                t,
+            ),
+        };
+        node.flex_grow = self.flex_grow.lerp(target.flex_grow, t);
+        node.flex_shrink = self.flex_shrink.lerp(target.flex_shrink, t);
+        node.flex_basis = interpolate_val(self.flex_basis, target.flex_basis, t);
+        node.row_gap = interpolate_val(self.row_gap, target.row_gap, t);
+        node.column_gap = interpolate_val(self.column_gap, target.column_gap, t);
+        node
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::Vec2;
+
+    #[test]
+    fn interpolate_val_with_same_unit() {
+        assert_eq!(
+            interpolate_val(Val::Px(10.), Val::Px(20.), 0.25),
+            Val::Px(12.5)
+        );
+        assert_eq!(
+            interpolate_val(Val::Percent(0.), Val::Percent(100.), 0.5),
+            Val::Percent(50.)
+        );
+    }
+
+    #[test]
+    fn interpolate_val_with_different_units_switches_halfway() {
+        assert_eq!(interpolate_val(Val::Px(10.), Val::Auto, 0.4), Val::Px(10.));
+        assert_eq!(interpolate_val(Val::Px(10.), Val::Auto, 0.6), Val::Auto);
+    }
+
+    #[test]
+    fn transition_animates_changes() {
+        let mut transition = Transition::<UiTransform>::new(Duration::from_secs(1))
+            .with_easing(EaseFunction::Linear);
+        let start = UiTransform::from_scale(Vec2::ONE);
+        let end = UiTransform::from_scale(Vec2::splat(3.));
+
+        // The first value is shown as is.
+        assert_eq!(transition.advance(&start, Duration::ZERO), None);
+        assert!(!transition.is_running());
+
+        // A new value resets the component to the shown value.
+        assert_eq!(
+            transition.advance(&end, Duration::from_millis(100)),
+            Some(start)
+        );
+        assert!(transition.is_running());
+        assert_eq!(transition.target(), Some(&end));
+
+        let value = transition
+            .advance(&start, Duration::from_millis(500))
+            .unwrap();
+        assert_eq!(value.scale, Vec2::splat(2.));
+
+        let value = transition
+            .advance(&value, Duration::from_millis(500))
+            .unwrap();
+        assert_eq!(value, end);
+        assert!(!transition.is_running());
+        assert_eq!(transition.advance(&value, Duration::from_millis(500)), None);
+    }
+
+    #[test]
+    fn interrupted_transition_starts_from_shown_value() {
+        let mut transition = Transition::<UiTransform>::new(Duration::from_secs(1));
+        let start = UiTransform::from_scale(Vec2::ONE);
+        transition.advance(&start, Duration::ZERO);
+        transition.advance(&UiTransform::from_scale(Vec2::splat(3.)), Duration::ZERO);
+        let shown = transition
+            .advance(&start, Duration::from_millis(500))
+            .unwrap();
+
+        let value = transition
+            .advance(&UiTransform::from_scale(Vec2::ZERO), Duration::ZERO)
+            .unwrap();
+        assert_eq!(value, shown);
+    }
+}
@@ -242,20 +242,7 @@ impl ComputedNode {
         else {
             return false;
         };
-        let [top, bottom] = if local_point.x < 0. {
-            [self.border_radius.top_left, self.border_radius.bottom_left]
-        } else {
-            [
-                self.border_radius.top_right,
-                self.border_radius.bottom_right,
-            ]
-        };
-        let r = if local_point.y < 0. { top } else { bottom };
-        let corner_to_point = local_point.abs() - 0.5 * self.size;
-        let q = corner_to_point + r;
-        let l = q.max(Vec2::ZERO).length();
-        let m = q.max_element().min(0.);
-        l + m - r < 0.
+        sd_rounded_box(local_point, self.size, self.border_radius) < 0.
     }
 
     /// Transform a point to normalized node space with the center of the node at the origin and the corners at [+/-0.5, +/-0.5]
@@ -298,6 +285,65 @@ impl ComputedNode {
 
         clip_rect
     }
+
+    /// Resolve the corner radii of the node's clipping rect in physical pixels.
+    ///
+    /// The clipping rect follows the node's rounded corners, shrunk by the inset of the
+    /// [`OverflowClipBox`]. If either axis is visible the clipping rect is unbounded and has no
+    /// rounded corners.
+    pub fn resolve_clip_radius(
+        &self,
+        overflow: Overflow,
+        overflow_clip_margin: OverflowClipMargin,
+    ) -> ResolvedBorderRadius {
+        if overflow.x == OverflowAxis::Visible || overflow.y == OverflowAxis::Visible {
+            return ResolvedBorderRadius::ZERO;
+        }
+
+        let clip_inset = match overflow_clip_margin.visual_box {
+            OverflowClipBox::BorderBox => BorderRect::ZERO,
+            OverflowClipBox::ContentBox => self.content_inset(),
+            OverflowClipBox::PaddingBox => self.border(),
+        };
+
+        let shrink = |radius: f32, x: f32, y: f32| (radius - x.max(y)).max(0.);
+        ResolvedBorderRadius {
+            top_left: shrink(self.border_radius.top_left, clip_inset.left, clip_inset.top),
+            top_right: shrink(
+                self.border_radius.top_right,
+                clip_inset.right,
+                clip_inset.top,
+            ),
+            bottom_right: shrink(
+                self.border_radius.bottom_right,
+                clip_inset.right,
+                clip_inset.bottom,
+            ),
+            bottom_left: shrink(
+                self.border_radius.bottom_left,
+                clip_inset.left,
+                clip_inset.bottom,
+            ),
+        }
+    }
+}
+
+/// Returns the signed distance from `point` to the edge of a rounded rectangle centered on the
+/// origin. Negative values are inside the rectangle.
+///
+/// Matches the `sd_rounded_box` function in `ui.wgsl`.
+pub(crate) fn sd_rounded_box(point: Vec2, size: Vec2, radius: ResolvedBorderRadius) -> f32 {
+    let [top, bottom] = if point.x < 0. {
+        [radius.top_left, radius.bottom_left]
+    } else {
+        [radius.top_right, radius.bottom_right]
+    };
+    let r = if point.y < 0. { top } else { bottom };
+    let corner_to_point = point.abs() - 0.5 * size;
+    let q = corner_to_point + r;
+    let l = q.max(Vec2::ZERO).length();
+    let m = q.max_element().min(0.);
+    l + m - r
 }
 
 impl ComputedNode {
@@ -2289,11 +2335,68 @@ impl Outline {
 }
 
 /// The calculated clip of the node
-#[derive(Component, Default, Copy, Clone, Debug, Reflect)]
-#[reflect(Component, Default, Debug, Clone)]
+#[derive(Component, Default, Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
 pub struct CalculatedClip {
     /// The rect of the clip
     pub clip: Rect,
+    /// The radii of the rounded corners of the clip, in physical pixels.
+    ///
+    /// Content outside of the rounded corners is clipped as well.
+    pub radius: ResolvedBorderRadius,
+}
+
+impl CalculatedClip {
+    /// Returns `true` if the point is inside the clip, taking its rounded corners into account.
+    pub fn contains(&self, point: Vec2) -> bool {
+        if !self.clip.contains(point) {
+            return false;
+        }
+        self.radius == ResolvedBorderRadius::ZERO
+            || sd_rounded_box(point - self.clip.center(), self.clip.size(), self.radius) <= 0.
+    }
+
+    /// Returns the intersection of two clips.
+    ///
+    /// Each corner of the intersection keeps the radius of the clip that it is a corner of, and
+    /// is sharp if it isn't a corner of either clip.
+    pub fn intersect(&self, other: CalculatedClip) -> CalculatedClip {
+        fn corners(rect: Rect) -> [Vec2; 4] {
+            [
+                rect.min,
+                Vec2::new(rect.max.x, rect.min.y),
+                rect.max,
+                Vec2::new(rect.min.x, rect.max.y),
+            ]
+        }
+
+        let clip = self.clip.intersect(other.clip);
+        let self_corners = corners(self.clip);
+        let other_corners = corners(other.clip);
+        let self_radius: [f32; 4] = self.radius.into();
+        let other_radius: [f32; 4] = other.radius.into();
+        let [top_left, top_right, bottom_right, bottom_left] = core::array::from_fn(|i| {
+            let corner = corners(clip)[i];
+            let mut radius = 0.;
+            if corner == self_corners[i] {
+                radius = self_radius[i];
+            }
+            if corner == other_corners[i] {
+                radius = f32::max(radius, other_radius[i]);
+            }
+            radius
+        });
+
+        CalculatedClip {
+            clip,
+            radius: ResolvedBorderRadius {
+                top_left,
+                top_right,
+                bottom_right,
+                bottom_left,
+            },
+        }
+    }
 }
 
 /// UI node entities with this component will ignore any clipping rect they inherit,
@@ -2333,6 +2436,104 @@ pub struct ZIndex(pub i32);
 #[reflect(Component, Default, Debug, PartialEq, Clone)]
 pub struct GlobalZIndex(pub i32);
 
+/// The layer a UI hierarchy is drawn on, set on root [`Node`]s.
+///
+/// Layers are ordered before [`GlobalZIndex`] and [`ZIndex`]: every node of a hierarchy on a
+/// higher layer is drawn on top of, and receives interactions before, every node of a hierarchy on
+/// a lower layer that is rendered by the same camera. This makes it possible to stack separate UI
+/// hierarchies such as a HUD, a pause menu and debug overlays in a well-defined order.
+///
+/// Layers are also used to pick the camera rendering a root node without a [`UiTargetCamera`],
+/// see [`UiLayerFilter`]. Cameras are composited in their [`Camera::order`], so a hierarchy can be
+/// drawn on top of the UI of another camera by routing its layer to a higher order camera.
+///
+/// Nodes without this component are on layer 0. Setting this component on a non-root node has no
+/// effect, nodes with a [`GlobalZIndex`] use the layer of their root node.
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct UiLayer(pub i32);
+
+/// Filters the [`UiLayer`]s rendered by a camera.
+///
+/// Root nodes without a [`UiTargetCamera`] are rendered by the default UI camera (see
+/// [`IsDefaultUiCamera`]) if it accepts their layer. Otherwise they are rendered by the highest order
+/// camera targeting the primary window that accepts their layer, or failing that by the highest
+/// order camera with a [`UiLayerFilter`] containing their layer, which can target any window.
+///
+/// Cameras without this component accept every layer. Root nodes with a [`UiTargetCamera`] are
+/// always rendered by their target camera, regardless of its filter.
+///
+/// ```
+/// # use bevy_ui::prelude::*;
+/// # use bevy_ecs::prelude::Commands;
+/// # use bevy_camera::{Camera, Camera2d, ClearColorConfig};
+/// const PAUSE_MENU_LAYER: i32 = 10;
+///
+/// fn setup(mut commands: Commands) {
+///     // Renders the game and the HUD, on the default layer.
+///     commands.spawn((Camera2d, UiLayerFilter::only(0)));
+///     // Draws the pause menu on top of everything rendered by the first camera.
+///     commands.spawn((
+///         Camera2d,
+///         Camera {
+///             order: 1,
+///             clear_color: ClearColorConfig::None,
+///             ..Default::default()
+///         },
+///         UiLayerFilter::only(PAUSE_MENU_LAYER),
+///     ));
+///
+///     commands.spawn(Node::default());
+///     commands.spawn((Node::default(), UiLayer(PAUSE_MENU_LAYER)));
+/// }
+/// ```
+#[derive(Component, Copy, Clone, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct UiLayerFilter {
+    /// The lowest layer rendered by the camera.
+    pub min: i32,
+    /// The highest layer rendered by the camera.
+    pub max: i32,
+}
+
+impl UiLayerFilter {
+    /// Accepts every layer.
+    pub const ALL: Self = Self {
+        min: i32::MIN,
+        max: i32::MAX,
+    };
+
+    /// Accepts no layer, for cameras that shouldn't render any UI.
+    pub const NONE: Self = Self {
+        min: i32::MAX,
+        max: i32::MIN,
+    };
+
+    /// Accepts only the given layer.
+    pub const fn only(layer: i32) -> Self {
+        Self {
+            min: layer,
+            max: layer,
+        }
+    }
+
+    /// Accepts the layers from `min` to `max`, inclusive.
+    pub const fn range(min: i32, max: i32) -> Self {
+        Self { min, max }
+    }
+
+    /// Returns `true` if the filter accepts the given layer.
+    pub const fn contains(&self, layer: i32) -> bool {
+        self.min <= layer && layer <= self.max
+    }
+}
+
+impl Default for UiLayerFilter {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
 /// Used to add rounded corners to a UI node. You can set a UI node to have uniformly
 /// rounded corners or specify different radii for each corner. If a given radius exceeds half
 /// the length of the smallest dimension between the node's height or width, the radius will
@@ -2686,6 +2887,8 @@ impl From<ResolvedBorderRadius> for [f32; 4] {
 /// List of shadows to draw for a [`Node`].
 ///
 /// Draw order is determined implicitly from the vector of [`ShadowStyle`]s, back-to-front.
+/// Drop shadows are drawn behind the node, and [inset](ShadowStyle::inset) shadows are drawn
+/// inside the node's border, over its background.
 pub struct BoxShadow(pub Vec<ShadowStyle>);
 
 impl BoxShadow {
@@ -2703,6 +2906,25 @@ impl BoxShadow {
             y_offset,
             spread_radius,
             blur_radius,
+            inset: false,
+        }])
+    }
+
+    /// A single inset shadow
+    pub fn inset(
+        color: Color,
+        x_offset: Val,
+        y_offset: Val,
+        spread_radius: Val,
+        blur_radius: Val,
+    ) -> Self {
+        Self(vec![ShadowStyle {
+            color,
+            x_offset,
+            y_offset,
+            spread_radius,
+            blur_radius,
+            inset: true,
         }])
     }
 }
@@ -2734,6 +2956,12 @@ pub struct ShadowStyle {
     pub spread_radius: Val,
     /// Blurriness of the shadow
     pub blur_radius: Val,
+    /// If `true`, the shadow is cast inside the node, as if the node were a hole, instead of
+    /// behind it.
+    ///
+    /// Inset shadows are drawn over the node's background, clipped to the inside of its border.
+    /// The spread radius grows the shadow inwards from the edges.
+    pub inset: bool,
 }
 
 impl Default for ShadowStyle {
@@ -2744,6 +2972,7 @@ impl Default for ShadowStyle {
             y_offset: Val::Percent(20.),
             spread_radius: Val::ZERO,
             blur_radius: Val::Percent(10.),
+            inset: false,
         }
     }
 }
@@ -2770,6 +2999,231 @@ impl Default for LayoutConfig {
     }
 }
 
+/// Overrides whether the layout of a UI node and its descendants is rounded to the physical pixel grid.
+///
+/// By default, UI layout is rounded to whole physical pixels, which keeps text and images crisp but
+/// makes nodes move in one pixel steps. Use [`LayoutRounding::Never`] on an animated subtree to let
+/// it move smoothly, and [`LayoutRounding::Always`] on descendants such as static text that should
+/// still be snapped to the pixel grid.
+///
+/// Takes precedence over [`LayoutConfig::use_rounding`].
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, PartialEq, Default, Clone)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum LayoutRounding {
+    /// Use the rounding mode of the parent node, or [`LayoutConfig`] if present.
+    /// Root nodes are rounded.
+    #[default]
+    Inherit,
+    /// Round this node and its descendants to the physical pixel grid.
+    ///
+    /// If an ancestor is not rounded, the node's top-left corner is also snapped to
+    /// the nearest physical pixel, so the whole subtree stays pixel aligned.
+    Always,
+    /// Use sub-pixel positions and sizes for this node and its descendants.
+    Never,
+}
+
+/// Lays out the children of this node by anchoring their edges to the edges of this node or of their
+/// siblings, instead of using the flexbox or grid layout selected by [`Node::display`].
+///
+/// Each child is positioned using its [`EdgeConstraints`]. Children without [`EdgeConstraints`] are
+/// placed in the top-left corner. The [`Node`] of a child still controls its size (including
+/// [`Node::aspect_ratio`] and the min and max sizes) along axes that aren't stretched by its
+/// constraints, but its position, margins and grid placement are ignored.
+///
+/// Constraints are resolved relative to the padding box of the container, like absolutely
+/// positioned nodes. This is useful for HUDs, where elements are pinned to the corners of the screen
+/// or placed next to each other:
+///
+/// ```
+/// # use bevy_ui::prelude::*;
+/// # use bevy_ecs::prelude::*;
+/// fn setup(mut commands: Commands) {
+///     commands
+///         .spawn((
+///             Node {
+///                 width: Val::Percent(100.),
+///                 height: Val::Percent(100.),
+///                 ..Default::default()
+///             },
+///             ConstraintLayout,
+///         ))
+///         .with_children(|parent| {
+///             // Minimap in the top-right corner, 10 pixels from the edges.
+///             let minimap = parent
+///                 .spawn((
+///                     Node {
+///                         width: Val::Px(200.),
+///                         height: Val::Px(200.),
+///                         ..Default::default()
+///                     },
+///                     EdgeConstraints {
+///                         right: Some(EdgeAnchor::parent(1.).with_offset(Val::Px(-10.))),
+///                         top: Some(EdgeAnchor::parent(0.).with_offset(Val::Px(10.))),
+///                         ..Default::default()
+///                     },
+///                 ))
+///                 .id();
+///             // Quest log below the minimap, stretched to its width.
+///             parent.spawn((
+///                 Node::default(),
+///                 EdgeConstraints {
+///                     left: Some(EdgeAnchor::sibling(minimap, 0.)),
+///                     right: Some(EdgeAnchor::sibling(minimap, 1.)),
+///                     top: Some(EdgeAnchor::sibling(minimap, 1.).with_offset(Val::Px(5.))),
+///                     ..Default::default()
+///                 },
+///             ));
+///             // Crosshair in the center of the screen.
+///             parent.spawn((
+///                 Node {
+///                     width: Val::Px(16.),
+///                     height: Val::Px(16.),
+///                     ..Default::default()
+///                 },
+///                 EdgeConstraints::center(),
+///             ));
+///         });
+/// }
+/// ```
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, PartialEq, Default, Clone)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+#[require(Node)]
+pub struct ConstraintLayout;
+
+/// Positions a child of a [`ConstraintLayout`] node by anchoring its edges.
+///
+/// Along each axis:
+/// - If both edges are anchored, the node is stretched between them.
+/// - If only one edge is anchored, the node keeps its size and that edge is placed on the anchor.
+/// - If neither edge is anchored, the center of the node is placed on the center anchor.
+/// - With no anchors the node is placed at the start of the container.
+///
+/// Has no effect on nodes whose parent doesn't have a [`ConstraintLayout`].
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, PartialEq, Default, Clone)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct EdgeConstraints {
+    /// The position of the left edge of the node.
+    pub left: Option<EdgeAnchor>,
+    /// The position of the right edge of the node.
+    pub right: Option<EdgeAnchor>,
+    /// The position of the horizontal center of the node, used if neither `left` nor `right` are set.
+    pub center_x: Option<EdgeAnchor>,
+    /// The position of the top edge of the node.
+    pub top: Option<EdgeAnchor>,
+    /// The position of the bottom edge of the node.
+    pub bottom: Option<EdgeAnchor>,
+    /// The position of the vertical center of the node, used if neither `top` nor `bottom` are set.
+    pub center_y: Option<EdgeAnchor>,
+}
+
+impl EdgeConstraints {
+    /// Centers the node in its container.
+    pub const fn center() -> Self {
+        Self {
+            left: None,
+            right: None,
+            center_x: Some(EdgeAnchor::parent(0.5)),
+            top: None,
+            bottom: None,
+            center_y: Some(EdgeAnchor::parent(0.5)),
+        }
+    }
+
+    /// Stretches the node to fill its container.
+    pub const fn fill() -> Self {
+        Self {
+            left: Some(EdgeAnchor::parent(0.)),
+            right: Some(EdgeAnchor::parent(1.)),
+            center_x: None,
+            top: Some(EdgeAnchor::parent(0.)),
+            bottom: Some(EdgeAnchor::parent(1.)),
+            center_y: None,
+        }
+    }
+}
+
+/// A position along one axis of a [`ConstraintLayout`], relative to the container or to a sibling.
+///
+/// The position is `start + ratio * length + offset`, where `start` and `length` are the position
+/// and length of the target along the axis. A `ratio` of `0.` is the left or top edge of the target,
+/// `0.5` its center and `1.` its right or bottom edge.
+#[derive(Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct EdgeAnchor {
+    /// The node the anchor is relative to.
+    pub target: AnchorTarget,
+    /// The normalized position along the target.
+    pub ratio: f32,
+    /// The distance from the anchor point, towards the right or bottom for positive values.
+    ///
+    /// Percentage values are resolved against the size of the container.
+    pub offset: Val,
+}
+
+impl EdgeAnchor {
+    /// An anchor at the given normalized position along the container.
+    pub const fn parent(ratio: f32) -> Self {
+        Self {
+            target: AnchorTarget::Parent,
+            ratio,
+            offset: Val::ZERO,
+        }
+    }
+
+    /// An anchor at the given normalized position along a sibling.
+    pub const fn sibling(sibling: Entity, ratio: f32) -> Self {
+        Self {
+            target: AnchorTarget::Sibling(sibling),
+            ratio,
+            offset: Val::ZERO,
+        }
+    }
+
+    /// Moves the anchor by the given offset.
+    pub const fn with_offset(mut self, offset: Val) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+/// The node an [`EdgeAnchor`] is relative to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum AnchorTarget {
+    /// The [`ConstraintLayout`] container.
+    Parent,
+    /// Another child of the same container.
+    ///
+    /// If the entity isn't a child of the container, the anchor is relative to the container instead.
+    Sibling(Entity),
+}
+
 /// Indicates that this root [`Node`] entity should be rendered to a specific camera.
 ///
 /// UI then will be laid out respecting the camera's viewport and scale factor, and
@@ -2831,6 +3285,7 @@ pub struct DefaultUiCamera<'w, 's> {
     cameras: Query<'w, 's, (Entity, &'static Camera)>,
     default_cameras: Query<'w, 's, Entity, (With<Camera>, With<IsDefaultUiCamera>)>,
     primary_window: Query<'w, 's, Entity, With<PrimaryWindow>>,
+    layer_filters: Query<'w, 's, &'static UiLayerFilter, With<Camera>>,
 }
 
 impl<'w, 's> DefaultUiCamera<'w, 's> {
@@ -2842,17 +3297,56 @@ impl<'w, 's> DefaultUiCamera<'w, 's> {
             }
             self.cameras
                 .iter()
-                .filter(|(_, c)| match c.target {
-                    RenderTarget::Window(WindowRef::Primary) => true,
-                    RenderTarget::Window(WindowRef::Entity(w)) => {
-                        self.primary_window.get(w).is_ok()
-                    }
-                    _ => false,
-                })
+                .filter(|(_, c)| self.targets_primary_window(c))
                 .max_by_key(|(e, c)| (c.order, *e))
                 .map(|(e, _)| e)
         })
     }
+
+    /// Returns the camera rendering root nodes on the given [`UiLayer`] which don't have a
+    /// [`UiTargetCamera`], taking the [`UiLayerFilter`]s of the cameras into account.
+    pub fn get_for_layer(&self, layer: i32) -> Option<Entity> {
+        if self.layer_filters.is_empty() {
+            return self.get();
+        }
+
+        let accepts_layer = |camera: Entity| {
+            self.layer_filters
+                .get(camera)
+                .ok()
+                .is_none_or(|filter| filter.contains(layer))
+        };
+
+        if let Some(camera) = self.get()
+            && accepts_layer(camera)
+        {
+            return Some(camera);
+        }
+
+        self.cameras
+            .iter()
+            .filter(|(e, c)| accepts_layer(*e) && self.targets_primary_window(c))
+            .max_by_key(|(e, c)| (c.order, *e))
+            .or_else(|| {
+                self.cameras
+                    .iter()
+                    .filter(|(e, _)| {
+                        self.layer_filters
+                            .get(*e)
+                            .is_ok_and(|filter| filter.contains(layer))
+                    })
+                    .max_by_key(|(e, c)| (c.order, *e))
+            })
+            .map(|(e, _)| e)
+    }
+
+    fn targets_primary_window(&self, camera: &Camera) -> bool {
+        match camera.target {
+            RenderTarget::Window(WindowRef::Primary) => true,
+            RenderTarget::Window(WindowRef::Entity(w)) => self.primary_window.get(w).is_ok(),
+            _ => false,
+        }
+    }
 }
 
 /// Derived information about the camera target for this UI node.
@@ -2916,7 +3410,8 @@ impl ComputedUiRenderTargetInfo {
 
 #[cfg(test)]
 mod tests {
-    use crate::GridPlacement;
+    use crate::{CalculatedClip, GridPlacement, ResolvedBorderRadius};
+    use bevy_math::{Rect, Vec2};
 
     #[test]
     fn invalid_grid_placement_values() {
@@ -2943,4 +3438,39 @@ mod tests {
         assert_eq!(GridPlacement::start_span(3, 5).get_end(), None);
         assert_eq!(GridPlacement::end_span(-4, 12).get_start(), None);
     }
+
+    #[test]
+    fn rounded_clip_intersection() {
+        let rounded = CalculatedClip {
+            clip: Rect::new(0., 0., 100., 100.),
+            radius: ResolvedBorderRadius {
+                top_left: 50.,
+                top_right: 50.,
+                bottom_right: 50.,
+                bottom_left: 50.,
+            },
+        };
+        let sharp = CalculatedClip {
+            clip: Rect::new(50., -50., 150., 150.),
+            radius: ResolvedBorderRadius::ZERO,
+        };
+
+        let clip = rounded.intersect(sharp);
+        assert_eq!(clip.clip, Rect::new(50., 0., 100., 100.));
+        assert_eq!(
+            clip.radius,
+            ResolvedBorderRadius {
+                top_left: 0.,
+                top_right: 50.,
+                bottom_right: 50.,
+                bottom_left: 0.,
+            }
+        );
+
+        assert!(rounded.contains(Vec2::new(50., 50.)));
+        assert!(rounded.contains(Vec2::new(50., 1.)));
+        assert!(!rounded.contains(Vec2::new(5., 5.)));
+        assert!(!rounded.contains(Vec2::new(95., 95.)));
+        assert!(sharp.contains(Vec2::new(51., -49.)));
+    }
 }
@@ -4,7 +4,7 @@ use crate::{
     experimental::{UiChildren, UiRootNodes},
     ui_transform::UiGlobalTransform,
     CalculatedClip, ComputedUiRenderTargetInfo, ComputedUiTargetCamera, DefaultUiCamera, Display,
-    Node, OverflowAxis, OverrideClip, UiScale, UiTargetCamera,
+    Node, OverflowAxis, OverrideClip, UiLayer, UiScale, UiTargetCamera,
 };
 
 use super::ComputedNode;
@@ -53,7 +53,7 @@ fn update_clipping(
         Has<OverrideClip>,
     )>,
     entity: Entity,
-    mut maybe_inherited_clip: Option<Rect>,
+    mut maybe_inherited_clip: Option<CalculatedClip>,
 ) {
     let Ok((node, computed_node, transform, maybe_calculated_clip, has_override_clip)) =
         node_query.get_mut(entity)
@@ -68,17 +68,15 @@ fn update_clipping(
 
     // If `display` is None, clip the entire node and all its descendants by replacing the inherited clip with a default rect (which is empty)
     if node.display == Display::None {
-        maybe_inherited_clip = Some(Rect::default());
+        maybe_inherited_clip = Some(CalculatedClip::default());
     }
 
     // Update this node's CalculatedClip component
     if let Some(mut calculated_clip) = maybe_calculated_clip {
         if let Some(inherited_clip) = maybe_inherited_clip {
             // Replace the previous calculated clip with the inherited clipping rect
-            if calculated_clip.clip != inherited_clip {
-                *calculated_clip = CalculatedClip {
-                    clip: inherited_clip,
-                };
+            if *calculated_clip != inherited_clip {
+                *calculated_clip = inherited_clip;
             }
         } else {
             // No inherited clipping rect, remove the component
@@ -86,9 +84,7 @@ fn update_clipping(
         }
     } else if let Some(inherited_clip) = maybe_inherited_clip {
         // No previous calculated clip, add a new CalculatedClip component with the inherited clipping rect
-        commands.entity(entity).try_insert(CalculatedClip {
-            clip: inherited_clip,
-        });
+        commands.entity(entity).try_insert(inherited_clip);
     }
 
     // Calculate new clip rectangle for children nodes
@@ -114,8 +110,22 @@ fn update_clipping(
         clip_rect.max.x -= clip_inset.right + computed_node.scrollbar_size.x;
         clip_rect.max.y -= clip_inset.bottom + computed_node.scrollbar_size.y;
 
-        clip_rect = clip_rect
-            .inflate(node.overflow_clip_margin.margin.max(0.) / computed_node.inverse_scale_factor);
+        let margin = node.overflow_clip_margin.margin.max(0.) / computed_node.inverse_scale_factor;
+        clip_rect = clip_rect.inflate(margin);
+
+        // The clip follows the node's rounded corners, expanded by the clip margin.
+        let mut clip_radius =
+            computed_node.resolve_clip_radius(node.overflow, node.overflow_clip_margin);
+        for radius in [
+            &mut clip_radius.top_left,
+            &mut clip_radius.top_right,
+            &mut clip_radius.bottom_right,
+            &mut clip_radius.bottom_left,
+        ] {
+            if 0. < *radius {
+                *radius += margin;
+            }
+        }
 
         if node.overflow.x == OverflowAxis::Visible {
             clip_rect.min.x = -f32::INFINITY;
@@ -125,7 +135,11 @@ fn update_clipping(
             clip_rect.min.y = -f32::INFINITY;
             clip_rect.max.y = f32::INFINITY;
         }
-        Some(maybe_inherited_clip.map_or(clip_rect, |c| c.intersect(clip_rect)))
+        let clip = CalculatedClip {
+            clip: clip_rect,
+            radius: clip_radius,
+        };
+        Some(maybe_inherited_clip.map_or(clip, |c| c.intersect(clip)))
     };
 
     for child in ui_children.iter_ui_children(entity) {
@@ -139,16 +153,24 @@ pub fn propagate_ui_target_cameras(
     ui_scale: Res<UiScale>,
     camera_query: Query<&Camera>,
     target_camera_query: Query<&UiTargetCamera>,
+    layer_query: Query<&UiLayer>,
     ui_root_nodes: UiRootNodes,
 ) {
-    let default_camera_entity = default_ui_camera.get();
+    let default_camera_entity = default_ui_camera.get_for_layer(0);
 
     for root_entity in ui_root_nodes.iter() {
+        let layer = layer_query.get(root_entity).map_or(0, |layer| layer.0);
         let camera = target_camera_query
             .get(root_entity)
             .ok()
             .map(UiTargetCamera::entity)
-            .or(default_camera_entity)
+            .or_else(|| {
+                if layer == 0 {
+                    default_camera_entity
+                } else {
+                    default_ui_camera.get_for_layer(layer)
+                }
+            })
             .unwrap_or(Entity::PLACEHOLDER);
 
         commands
@@ -211,6 +233,8 @@ mod tests {
     use crate::ComputedUiTargetCamera;
     use crate::IsDefaultUiCamera;
     use crate::Node;
+    use crate::UiLayer;
+    use crate::UiLayerFilter;
     use crate::UiScale;
     use crate::UiTargetCamera;
     use bevy_app::App;
@@ -647,4 +671,59 @@ mod tests {
             2.
         );
     }
+
+    #[test]
+    fn ui_layers_pick_target_camera() {
+        let mut app = setup_test_app();
+        let world = app.world_mut();
+
+        world.spawn((Window::default(), PrimaryWindow));
+        let window_2 = world.spawn(Window::default()).id();
+
+        let game_camera = world.spawn((Camera2d, UiLayerFilter::only(0))).id();
+        let overlay_camera = world
+            .spawn((
+                Camera2d,
+                Camera {
+                    order: 1,
+                    ..default()
+                },
+                UiLayerFilter::range(10, 20),
+            ))
+            .id();
+        let debug_camera = world
+            .spawn((
+                Camera2d,
+                Camera {
+                    target: RenderTarget::Window(WindowRef::Entity(window_2)),
+                    ..default()
+                },
+                UiLayerFilter::only(100),
+            ))
+            .id();
+
+        let hud = world.spawn(Node::default()).id();
+        let pause_menu = world.spawn((Node::default(), UiLayer(10))).id();
+        let debug_overlay = world.spawn((Node::default(), UiLayer(100))).id();
+        let targeted = world
+            .spawn((Node::default(), UiLayer(100), UiTargetCamera(game_camera)))
+            .id();
+        let hidden = world.spawn((Node::default(), UiLayer(5))).id();
+
+        app.update();
+        let world = app.world_mut();
+
+        for (uinode, camera) in [
+            (hud, Some(game_camera)),
+            (pause_menu, Some(overlay_camera)),
+            (debug_overlay, Some(debug_camera)),
+            (targeted, Some(game_camera)),
+            (hidden, None),
+        ] {
+            assert_eq!(
+                world.get::<ComputedUiTargetCamera>(uinode).unwrap().get(),
+                camera
+            );
+        }
+    }
 }
@@ -5,7 +5,7 @@ use bevy_ecs::prelude::*;
 use bevy_image::{prelude::*, TRANSPARENT_IMAGE_HANDLE};
 use bevy_math::{Rect, UVec2, Vec2};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
-use bevy_sprite::TextureSlicer;
+use bevy_sprite::{BorderRect, SliceScaleMode, TextureSlicer};
 use taffy::{MaybeMath, MaybeResolve};
 
 /// A UI Node that renders an image.
@@ -178,6 +178,77 @@ impl NodeImageMode {
     }
 }
 
+/// An image drawn over the border of a UI node, like the CSS `border-image` property.
+///
+/// The image is cut into 9 slices by the `slices` insets. The corner slices are drawn in the
+/// corners of the node's border, the side slices are scaled to the width of the border and drawn
+/// along its edges, and the center slice is only drawn if `fill` is set.
+///
+/// The image is drawn instead of the [`BorderColor`](crate::BorderColor), and is clipped to the
+/// node's rounded corners. Nothing is drawn if the node has no border.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default, Debug, Clone)]
+#[require(Node)]
+pub struct BorderImage {
+    /// The tint color used to draw the image.
+    pub color: Color,
+    /// Handle to the texture.
+    pub image: Handle<Image>,
+    /// Inset values in pixels of the four lines slicing the texture into nine sections.
+    pub slices: BorderRect,
+    /// Defines how the 4 side slices are scaled along the edges of the border.
+    pub sides_scale_mode: SliceScaleMode,
+    /// Defines how the center slice is scaled, if `fill` is set.
+    pub center_scale_mode: SliceScaleMode,
+    /// Whether the center slice is drawn over the inside of the node.
+    pub fill: bool,
+}
+
+impl Default for BorderImage {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            image: TRANSPARENT_IMAGE_HANDLE,
+            slices: BorderRect::ZERO,
+            sides_scale_mode: SliceScaleMode::Stretch,
+            center_scale_mode: SliceScaleMode::Stretch,
+            fill: false,
+        }
+    }
+}
+
+impl BorderImage {
+    /// Creates a border image from a texture, cut into slices by the given insets, in pixels.
+    pub fn new(image: Handle<Image>, slices: BorderRect) -> Self {
+        Self {
+            image,
+            slices,
+            ..Default::default()
+        }
+    }
+
+    /// Draw the center slice of the image over the inside of the node.
+    #[must_use]
+    pub const fn with_fill(mut self) -> Self {
+        self.fill = true;
+        self
+    }
+
+    /// Set the scale mode of the side slices.
+    #[must_use]
+    pub const fn with_sides_scale_mode(mut self, sides_scale_mode: SliceScaleMode) -> Self {
+        self.sides_scale_mode = sides_scale_mode;
+        self
+    }
+
+    /// Set the color tint.
+    #[must_use]
+    pub const fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
 /// The size of the image's texture
 ///
 /// This component is updated automatically by [`update_image_content_size_system`]
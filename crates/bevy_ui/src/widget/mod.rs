@@ -4,10 +4,12 @@ mod button;
 mod image;
 mod label;
 mod text;
+mod text_input;
 mod viewport;
 
 pub use button::*;
 pub use image::*;
 pub use label::*;
 pub use text::*;
+pub use text_input::*;
 pub use viewport::*;
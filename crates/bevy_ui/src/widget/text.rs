@@ -2,6 +2,8 @@ use crate::{
     ComputedNode, ComputedUiRenderTargetInfo, ContentSize, FixedMeasure, Measure, MeasureArgs,
     Node, NodeMeasure,
 };
+#[cfg(feature = "bevy_picking")]
+use crate::{UiGlobalTransform, UiScale};
 use bevy_asset::Assets;
 use bevy_color::Color;
 use bevy_derive::{Deref, DerefMut};
@@ -14,13 +16,17 @@ use bevy_ecs::{
     system::{Query, Res, ResMut},
     world::{Mut, Ref},
 };
+#[cfg(feature = "bevy_picking")]
+use bevy_ecs::{observer::On, system::Commands};
 use bevy_image::prelude::*;
 use bevy_math::Vec2;
+#[cfg(feature = "bevy_picking")]
+use bevy_picking::events::{Click, Pointer};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_text::{
-    ComputedTextBlock, CosmicFontSystem, Font, FontAtlasSet, LineBreak, LineHeight, SwashCache,
-    TextBounds, TextColor, TextError, TextFont, TextLayout, TextLayoutInfo, TextMeasureInfo,
-    TextPipeline, TextReader, TextRoot, TextSpanAccess, TextWriter,
+    ComputedTextBlock, CosmicFontSystem, Font, FontAtlasSet, FontStack, LineBreak, LineHeight,
+    SwashCache, TextBounds, TextColor, TextError, TextFont, TextLayout, TextLayoutInfo,
+    TextMeasureInfo, TextPipeline, TextReader, TextRoot, TextSpanAccess, TextWriter,
 };
 use taffy::style::AvailableSpace;
 use tracing::error;
@@ -227,6 +233,7 @@ impl Measure for TextMeasure {
 fn create_text_measure<'a>(
     entity: Entity,
     fonts: &Assets<Font>,
+    font_stacks: &Assets<FontStack>,
     scale_factor: f64,
     spans: impl Iterator<Item = (Entity, usize, &'a str, &'a TextFont, Color, LineHeight)>,
     block: Ref<TextLayout>,
@@ -239,6 +246,7 @@ fn create_text_measure<'a>(
     match text_pipeline.create_text_measure(
         entity,
         fonts,
+        font_stacks,
         spans,
         scale_factor,
         &block,
@@ -284,6 +292,7 @@ fn create_text_measure<'a>(
 ///   method should be called when only changing the `Text`'s colors.
 pub fn measure_text_system(
     fonts: Res<Assets<Font>>,
+    font_stacks: Res<Assets<FontStack>>,
     mut text_query: Query<
         (
             Entity,
@@ -314,6 +323,7 @@ pub fn measure_text_system(
             create_text_measure(
                 entity,
                 &fonts,
+                &font_stacks,
                 computed_target.scale_factor.into(),
                 text_reader.iter(entity),
                 block,
@@ -331,6 +341,7 @@ pub fn measure_text_system(
 fn queue_text(
     entity: Entity,
     fonts: &Assets<Font>,
+    font_stacks: &Assets<FontStack>,
     text_pipeline: &mut TextPipeline,
     font_atlas_set: &mut FontAtlasSet,
     texture_atlases: &mut Assets<TextureAtlasLayout>,
@@ -363,6 +374,7 @@ fn queue_text(
     match text_pipeline.queue_text(
         text_layout_info,
         fonts,
+        font_stacks,
         text_reader.iter(entity),
         scale_factor.into(),
         block,
@@ -406,6 +418,7 @@ fn queue_text(
 pub fn text_system(
     mut textures: ResMut<Assets<Image>>,
     fonts: Res<Assets<Font>>,
+    font_stacks: Res<Assets<FontStack>>,
     mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
     mut font_atlas_set: ResMut<FontAtlasSet>,
     mut text_pipeline: ResMut<TextPipeline>,
@@ -426,6 +439,7 @@ pub fn text_system(
             queue_text(
                 entity,
                 &fonts,
+                &font_stacks,
                 &mut text_pipeline,
                 &mut font_atlas_set,
                 &mut texture_atlases,
@@ -444,3 +458,48 @@ pub fn text_system(
         }
     }
 }
+
+/// Sends [`TextLinkClicked`](bevy_text::TextLinkClicked) when a text span with a
+/// [`TextLink`](bevy_text::TextLink) is clicked.
+#[cfg(feature = "bevy_picking")]
+pub(crate) fn text_link_on_click(
+    mut click: On<Pointer<Click>>,
+    text_query: Query<(
+        &ComputedTextBlock,
+        &TextLayoutInfo,
+        &ComputedNode,
+        &ComputedUiRenderTargetInfo,
+        &UiGlobalTransform,
+    )>,
+    links: Query<&bevy_text::TextLink>,
+    ui_scale: Res<UiScale>,
+    mut commands: Commands,
+) {
+    let Ok((block, text_layout_info, node, target, transform)) = text_query.get(click.entity)
+    else {
+        return;
+    };
+    let Some(inverse) = transform.try_inverse() else {
+        return;
+    };
+    // Run geometry is relative to the top left corner of the node.
+    let position = inverse
+        .transform_point2(click.pointer_location.position * target.scale_factor() / ui_scale.0)
+        + 0.5 * node.size();
+    let Some(span) = text_layout_info
+        .span_at(position)
+        .and_then(|span_index| block.entities().get(span_index))
+        .map(|text_entity| text_entity.entity)
+    else {
+        return;
+    };
+    let Ok(link) = links.get(span) else {
+        return;
+    };
+    click.propagate(false);
+    commands.trigger(bevy_text::TextLinkClicked {
+        entity: click.entity,
+        span,
+        link: link.0.clone(),
+    });
+}
@@ -0,0 +1,560 @@
+use crate::{widget::Text, ComputedNode};
+use bevy_color::Color;
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    component::Component,
+    query::{Changed, Or},
+    reflect::ReflectComponent,
+    system::Query,
+};
+use bevy_math::{Rect, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_text::{ComputedTextBlock, CosmicBuffer};
+use core::ops::Range;
+
+/// An editable text node.
+///
+/// The edited string is stored in [`TextInputValue`], and the [`Text`] of the node is kept in sync
+/// with it, along with any text being composed with an input method. The caret, selection and
+/// composition are tracked by [`TextInputState`] and laid out in [`TextInputLayout`].
+///
+/// This component only displays the text input: editing it with the keyboard, pointer, clipboard
+/// and input methods is handled by the `TextInputPlugin` in `bevy_ui_widgets`.
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+#[require(Text, TextInputValue, TextInputState, TextInputLayout)]
+pub struct TextInput {
+    /// If true, pressing enter inserts a line break rather than submitting the value.
+    pub multiline: bool,
+    /// The maximum number of characters in the value, if any.
+    pub max_length: Option<usize>,
+    /// The color of the caret.
+    pub caret_color: Color,
+    /// The width of the caret, in logical pixels.
+    pub caret_width: f32,
+    /// The color drawn behind the selected text.
+    pub selection_color: Color,
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        Self {
+            multiline: false,
+            max_length: None,
+            caret_color: Color::WHITE,
+            caret_width: 1.,
+            selection_color: Color::srgba(0.25, 0.45, 0.85, 0.6),
+        }
+    }
+}
+
+/// The value of a [`TextInput`].
+#[derive(Component, Debug, Default, Clone, Deref, DerefMut, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct TextInputValue(pub String);
+
+impl TextInputValue {
+    /// Makes a new text input value.
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+}
+
+/// The caret, selection and input method composition of a [`TextInput`].
+///
+/// Indices are byte indices into the [`TextInputValue`], and always lie on character boundaries.
+#[derive(Component, Debug, Default, Clone, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct TextInputState {
+    /// The index of the caret.
+    pub cursor: usize,
+    /// The index the selection extends from, up to the caret.
+    ///
+    /// This is equal to `cursor` when no text is selected.
+    pub anchor: usize,
+    /// The text being composed with an input method, which is shown at the caret until it's
+    /// committed.
+    pub preedit: Option<TextPreedit>,
+    /// If true, the caret is shown. This is usually set while the text input has focus.
+    pub show_caret: bool,
+}
+
+/// Text being composed with an input method, see [`TextInputState::preedit`].
+#[derive(Debug, Default, Clone, PartialEq, Reflect)]
+#[reflect(Default, Debug, PartialEq, Clone)]
+pub struct TextPreedit {
+    /// The composed text.
+    pub text: String,
+    /// The byte range of the input method's cursor in the composed text, if it should be shown.
+    pub cursor: Option<(usize, usize)>,
+}
+
+impl TextInputState {
+    /// Returns the range of the selected text.
+    pub fn selection(&self) -> Range<usize> {
+        self.cursor.min(self.anchor)..self.cursor.max(self.anchor)
+    }
+
+    /// Returns true if any text is selected.
+    pub fn has_selection(&self) -> bool {
+        self.cursor != self.anchor
+    }
+
+    /// Returns the selected text of `value`.
+    pub fn selected_text<'a>(&self, value: &'a str) -> &'a str {
+        &value[self.selection()]
+    }
+
+    /// Moves the caret and the selection anchor back into `value`, after it was changed.
+    pub fn clamp(&mut self, value: &str) {
+        self.cursor = floor_char_boundary(value, self.cursor);
+        self.anchor = floor_char_boundary(value, self.anchor);
+    }
+
+    /// Moves the caret to `index`, selecting the text from the anchor if `extend` is true.
+    pub fn move_to(&mut self, index: usize, extend: bool) {
+        self.cursor = index;
+        if !extend {
+            self.anchor = index;
+        }
+    }
+
+    /// Selects all of `value`.
+    pub fn select_all(&mut self, value: &str) {
+        self.anchor = 0;
+        self.cursor = value.len();
+    }
+
+    /// Moves the caret one character to the left.
+    ///
+    /// If text is selected and `extend` is false, the caret moves to the start of the selection
+    /// instead.
+    pub fn move_left(&mut self, value: &str, extend: bool) {
+        let index = if self.has_selection() && !extend {
+            self.selection().start
+        } else {
+            previous_char_boundary(value, self.cursor)
+        };
+        self.move_to(index, extend);
+    }
+
+    /// Moves the caret one character to the right.
+    ///
+    /// If text is selected and `extend` is false, the caret moves to the end of the selection
+    /// instead.
+    pub fn move_right(&mut self, value: &str, extend: bool) {
+        let index = if self.has_selection() && !extend {
+            self.selection().end
+        } else {
+            next_char_boundary(value, self.cursor)
+        };
+        self.move_to(index, extend);
+    }
+
+    /// Moves the caret to the start of the previous word.
+    pub fn move_word_left(&mut self, value: &str, extend: bool) {
+        self.move_to(previous_word_boundary(value, self.cursor), extend);
+    }
+
+    /// Moves the caret to the end of the next word.
+    pub fn move_word_right(&mut self, value: &str, extend: bool) {
+        self.move_to(next_word_boundary(value, self.cursor), extend);
+    }
+
+    /// Moves the caret to the start of its line.
+    pub fn move_line_start(&mut self, value: &str, extend: bool) {
+        let index = value[..self.cursor]
+            .rfind('\n')
+            .map_or(0, |index| index + 1);
+        self.move_to(index, extend);
+    }
+
+    /// Moves the caret to the end of its line.
+    pub fn move_line_end(&mut self, value: &str, extend: bool) {
+        let index = value[self.cursor..]
+            .find('\n')
+            .map_or(value.len(), |index| self.cursor + index);
+        self.move_to(index, extend);
+    }
+
+    /// Replaces the selected text of `value` with `text`, and moves the caret after it.
+    ///
+    /// `text` is truncated so that `value` has at most `max_length` characters. Returns true if
+    /// `value` changed.
+    pub fn insert(&mut self, value: &mut String, text: &str, max_length: Option<usize>) -> bool {
+        let selection = self.selection();
+        let text = match max_length {
+            Some(max_length) => {
+                let remaining = max_length.saturating_sub(
+                    value.chars().count() - value[selection.clone()].chars().count(),
+                );
+                let end = text
+                    .char_indices()
+                    .nth(remaining)
+                    .map_or(text.len(), |(index, _)| index);
+                &text[..end]
+            }
+            None => text,
+        };
+        if text.is_empty() && selection.is_empty() {
+            return false;
+        }
+        value.replace_range(selection.clone(), text);
+        self.move_to(selection.start + text.len(), false);
+        true
+    }
+
+    /// Deletes the selected text of `value`. Returns true if any text was selected.
+    pub fn delete_selection(&mut self, value: &mut String) -> bool {
+        self.insert(value, "", None)
+    }
+
+    /// Deletes the selected text of `value`, or the character before the caret if no text is
+    /// selected. Returns true if `value` changed.
+    pub fn delete_backward(&mut self, value: &mut String) -> bool {
+        if !self.has_selection() {
+            self.anchor = previous_char_boundary(value, self.cursor);
+        }
+        self.delete_selection(value)
+    }
+
+    /// Deletes the selected text of `value`, or the character after the caret if no text is
+    /// selected. Returns true if `value` changed.
+    pub fn delete_forward(&mut self, value: &mut String) -> bool {
+        if !self.has_selection() {
+            self.anchor = next_char_boundary(value, self.cursor);
+        }
+        self.delete_selection(value)
+    }
+
+    /// Returns the text shown by the text input: `value` with the [`preedit`](Self::preedit)
+    /// inserted at the caret.
+    pub fn display_text(&self, value: &str) -> String {
+        match &self.preedit {
+            Some(preedit) => {
+                let mut text = value.to_owned();
+                text.insert_str(self.cursor, &preedit.text);
+                text
+            }
+            None => value.to_owned(),
+        }
+    }
+
+    /// Converts an index into the value to an index into the [displayed text](Self::display_text).
+    pub fn display_index(&self, index: usize) -> usize {
+        match &self.preedit {
+            Some(preedit) if index > self.cursor => index + preedit.text.len(),
+            _ => index,
+        }
+    }
+
+    /// Converts an index into the [displayed text](Self::display_text) to an index into the value.
+    ///
+    /// Indices inside the [`preedit`](Self::preedit) map to the caret.
+    pub fn value_index(&self, index: usize) -> usize {
+        match &self.preedit {
+            Some(preedit) if index > self.cursor => {
+                index.saturating_sub(preedit.text.len()).max(self.cursor)
+            }
+            _ => index,
+        }
+    }
+}
+
+fn floor_char_boundary(value: &str, index: usize) -> usize {
+    let mut index = index.min(value.len());
+    while !value.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+fn previous_char_boundary(value: &str, index: usize) -> usize {
+    value[..index]
+        .char_indices()
+        .next_back()
+        .map_or(0, |(index, _)| index)
+}
+
+fn next_char_boundary(value: &str, index: usize) -> usize {
+    value[index..]
+        .chars()
+        .next()
+        .map_or(index, |c| index + c.len_utf8())
+}
+
+fn previous_word_boundary(value: &str, index: usize) -> usize {
+    let trimmed = value[..index].trim_end();
+    trimmed
+        .rfind(char::is_whitespace)
+        .map_or(0, |index| next_char_boundary(trimmed, index))
+}
+
+fn next_word_boundary(value: &str, index: usize) -> usize {
+    let rest = &value[index..];
+    let start = rest.len() - rest.trim_start().len();
+    rest[start..]
+        .find(char::is_whitespace)
+        .map_or(value.len(), |end| index + start + end)
+}
+
+/// The geometry of the caret, selection and input method composition of a [`TextInput`].
+///
+/// Rectangles are in physical pixels, relative to the top-left corner of the node, like the glyphs
+/// of its [`TextLayoutInfo`](bevy_text::TextLayoutInfo).
+///
+/// Automatically computed by [`update_text_input_layout`].
+#[derive(Component, Debug, Default, Clone, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct TextInputLayout {
+    /// The caret, if it's shown.
+    pub caret: Option<Rect>,
+    /// The selected text, with a rectangle per line.
+    pub selection: Vec<Rect>,
+    /// The underline of the text being composed with an input method, with a rectangle per line.
+    pub preedit: Vec<Rect>,
+}
+
+/// Updates the [`Text`] of [`TextInput`] nodes with their value and input method composition.
+pub fn update_text_input_text(
+    mut query: Query<
+        (&TextInputValue, &mut TextInputState, &mut Text),
+        Or<(Changed<TextInputValue>, Changed<TextInputState>)>,
+    >,
+) {
+    for (value, mut state, mut text) in &mut query {
+        state.bypass_change_detection().clamp(value);
+        text.set_if_neq(Text(state.display_text(value)));
+    }
+}
+
+/// Updates the [`TextInputLayout`] of [`TextInput`] nodes from their text layout.
+pub fn update_text_input_layout(
+    mut query: Query<(
+        &TextInput,
+        &TextInputState,
+        &Text,
+        &ComputedTextBlock,
+        &ComputedNode,
+        &mut TextInputLayout,
+    )>,
+) {
+    for (input, state, text, block, node, mut layout) in &mut query {
+        let buffer = block.buffer();
+        let scale_factor = node.inverse_scale_factor.recip();
+
+        let caret = if state.show_caret {
+            caret_position(buffer, text, state.display_index(state.cursor)).map(|(x, y, height)| {
+                let width = (input.caret_width * scale_factor).max(1.);
+                Rect::new(x, y, x + width, y + height)
+            })
+        } else {
+            None
+        };
+
+        let selection = if state.preedit.is_none() {
+            highlight_rects(buffer, text, state.selection())
+        } else {
+            Vec::new()
+        };
+
+        let preedit = state
+            .preedit
+            .as_ref()
+            .map(|preedit| {
+                let thickness = scale_factor.round().max(1.);
+                highlight_rects(
+                    buffer,
+                    text,
+                    state.cursor..state.cursor + preedit.text.len(),
+                )
+                .into_iter()
+                .map(|rect| Rect::new(rect.min.x, rect.max.y - thickness, rect.max.x, rect.max.y))
+                .collect()
+            })
+            .unwrap_or_default();
+
+        layout.set_if_neq(TextInputLayout {
+            caret,
+            selection,
+            preedit,
+        });
+    }
+}
+
+/// Returns the index in `text` of the character closest to `position`, in physical pixels
+/// relative to the top-left corner of the node displaying `text` with `block`.
+pub fn text_index_at_position(block: &ComputedTextBlock, text: &str, position: Vec2) -> usize {
+    let Some(cursor) = block.buffer().hit(position.x, position.y) else {
+        return text.len();
+    };
+    let line_start = text
+        .split_inclusive('\n')
+        .take(cursor.line)
+        .map(str::len)
+        .sum::<usize>();
+    floor_char_boundary(text, line_start + cursor.index)
+}
+
+/// Returns the line of `text` that contains `index`, and the index relative to the line's start.
+fn line_position(text: &str, index: usize) -> (usize, usize) {
+    let before = &text[..index];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    (line, index - line_start)
+}
+
+/// Returns the x position, top and height of a caret placed before `index` in `text`.
+fn caret_position(buffer: &CosmicBuffer, text: &str, index: usize) -> Option<(f32, f32, f32)> {
+    let (line, index) = line_position(text, index);
+    let mut position = None;
+    for run in buffer.layout_runs().filter(|run| run.line_i == line) {
+        for glyph in run.glyphs {
+            if (glyph.start..glyph.end).contains(&index) {
+                // Place carets inside ligatures proportionally to the bytes before them.
+                let offset =
+                    glyph.w * (index - glyph.start) as f32 / (glyph.end - glyph.start) as f32;
+                let x = if run.rtl {
+                    glyph.x + glyph.w - offset
+                } else {
+                    glyph.x + offset
+                };
+                return Some((x, run.line_top, run.line_height));
+            }
+        }
+        // The caret is past the last glyph of the run, which is the end of the line unless the line
+        // wraps into another run.
+        let x = run.glyphs.last().map_or(
+            0.,
+            |glyph| {
+                if run.rtl {
+                    glyph.x
+                } else {
+                    glyph.x + glyph.w
+                }
+            },
+        );
+        position = Some((x, run.line_top, run.line_height));
+    }
+    position
+}
+
+/// Returns a rectangle per laid out line covering the glyphs of `range` in `text`.
+fn highlight_rects(buffer: &CosmicBuffer, text: &str, range: Range<usize>) -> Vec<Rect> {
+    if range.is_empty() {
+        return Vec::new();
+    }
+    let (start_line, start_index) = line_position(text, range.start);
+    let (end_line, end_index) = line_position(text, range.end);
+    buffer
+        .layout_runs()
+        .filter(|run| (start_line..=end_line).contains(&run.line_i))
+        .filter_map(|run| {
+            let start = if run.line_i == start_line {
+                start_index
+            } else {
+                0
+            };
+            let end = if run.line_i == end_line {
+                end_index
+            } else {
+                usize::MAX
+            };
+            let (min_x, max_x) = run
+                .glyphs
+                .iter()
+                .filter(|glyph| glyph.start < end && start < glyph.end)
+                .fold(None, |bounds: Option<(f32, f32)>, glyph| {
+                    let (min_x, max_x) = bounds.unwrap_or((glyph.x, glyph.x + glyph.w));
+                    Some((min_x.min(glyph.x), max_x.max(glyph.x + glyph.w)))
+                })?;
+            Some(Rect::new(
+                min_x,
+                run.line_top,
+                max_x,
+                run.line_top + run.line_height,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextInputState;
+
+    fn state_at(cursor: usize, anchor: usize) -> TextInputState {
+        TextInputState {
+            cursor,
+            anchor,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn insert_replaces_selection() {
+        let mut value = String::from("hello world");
+        let mut state = state_at(6, 11);
+        assert!(state.insert(&mut value, "bevy", None));
+        assert_eq!(value, "hello bevy");
+        assert_eq!(state, state_at(10, 10));
+    }
+
+    #[test]
+    fn insert_respects_max_length() {
+        let mut value = String::from("abc");
+        let mut state = state_at(3, 3);
+        assert!(state.insert(&mut value, "défg", Some(5)));
+        assert_eq!(value, "abcdé");
+        assert_eq!(state.cursor, "abcdé".len());
+        assert!(!state.insert(&mut value, "h", Some(5)));
+        assert_eq!(value, "abcdé");
+    }
+
+    #[test]
+    fn delete_multibyte_characters() {
+        let mut value = String::from("añb");
+        let mut state = state_at(3, 3);
+        assert!(state.delete_backward(&mut value));
+        assert_eq!(value, "ab");
+        assert_eq!(state, state_at(1, 1));
+        assert!(state.delete_forward(&mut value));
+        assert_eq!(value, "a");
+        assert!(!state.delete_forward(&mut value));
+    }
+
+    #[test]
+    fn move_and_select() {
+        let value = "one two\nthree";
+        let mut state = state_at(0, 0);
+        state.move_word_right(value, false);
+        assert_eq!(state.cursor, 3);
+        state.move_word_right(value, true);
+        assert_eq!(state.selected_text(value), " two");
+        state.move_left(value, false);
+        assert_eq!(state, state_at(3, 3));
+        state.move_line_end(value, false);
+        assert_eq!(state.cursor, 7);
+        state.move_right(value, false);
+        state.move_line_end(value, true);
+        assert_eq!(state.selected_text(value), "three");
+        state.move_word_left(value, false);
+        assert_eq!(state.cursor, 8);
+        state.move_line_start(value, false);
+        assert_eq!(state.cursor, 8);
+    }
+
+    #[test]
+    fn display_text_with_preedit() {
+        let mut state = state_at(3, 3);
+        state.preedit = Some(super::TextPreedit {
+            text: "にほ".into(),
+            cursor: None,
+        });
+        assert_eq!(state.display_text("abcd"), "abcにほd");
+        assert_eq!(state.display_index(4), 4 + "にほ".len());
+        assert_eq!(state.value_index(4 + "にほ".len()), 4);
+        assert_eq!(state.value_index(4), 3);
+        assert_eq!(state.display_index(2), 2);
+    }
+}
@@ -0,0 +1,254 @@
+//! UI displayed on surfaces in the world, such as in-game computer screens or VR menus.
+
+use bevy_ecs::{component::Component, entity::Entity, reflect::ReflectComponent};
+use bevy_math::Vec2;
+use bevy_reflect::Reflect;
+#[cfg(feature = "bevy_picking")]
+use bevy_transform::components::GlobalTransform;
+use bevy_transform::components::Transform;
+
+#[cfg(feature = "bevy_picking")]
+use bevy_camera::Camera;
+#[cfg(feature = "bevy_picking")]
+use bevy_ecs::{
+    message::{MessageReader, MessageWriter},
+    query::With,
+    system::{Commands, Query, Res},
+};
+#[cfg(feature = "bevy_picking")]
+use bevy_math::{primitives::InfinitePlane3d, Ray3d, Vec3Swizzles};
+#[cfg(feature = "bevy_picking")]
+use bevy_picking::{
+    backend::{ray::RayMap, HitData, PointerHits},
+    events::PointerState,
+    hover::HoverMap,
+    pointer::{Location, PointerId, PointerInput, PointerLocation},
+    Pickable,
+};
+#[cfg(feature = "bevy_picking")]
+use bevy_window::PrimaryWindow;
+
+/// Component used to display the UI rendered by a [`Camera`](bevy_camera::Camera) on a rectangle
+/// in the world, and to interact with it using the pointers that hit the rectangle.
+///
+/// The surface is a rectangle of [`size`](Self::size) centered on the entity, in the entity's
+/// local XY plane, facing local +Z. To draw the UI on it, render the UI with a camera targeting an
+/// image (see [`Image::new_target_texture`](bevy_image::Image::new_target_texture)), and add a
+/// mesh of the same size, such as a `Rectangle`, with a material that samples the image.
+///
+/// With the `bevy_picking` feature, the surface is hit by picking rays, and pointer input on the
+/// surface is sent to the UI as a pointer over the camera's render target. This lets the normal
+/// UI widgets and interactions work on the surface.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component, Debug)]
+#[require(Transform)]
+#[cfg_attr(
+    feature = "bevy_picking",
+    require(PointerId::Custom(uuid::Uuid::new_v4()))
+)]
+pub struct WorldUiSurface {
+    /// The entity representing the [`Camera`](bevy_camera::Camera) that renders the UI displayed
+    /// on this surface.
+    ///
+    /// Note that removing the [`WorldUiSurface`] component will not despawn this entity.
+    pub camera: Entity,
+    /// The size of the surface, in local units.
+    pub size: Vec2,
+}
+
+impl WorldUiSurface {
+    /// Creates a new [`WorldUiSurface`] of the given `size`, displaying the UI rendered by
+    /// `camera`.
+    #[inline]
+    pub const fn new(camera: Entity, size: Vec2) -> Self {
+        Self { camera, size }
+    }
+
+    /// Returns the position on the surface where `ray` hits its plane, normalized so that the
+    /// top-left corner of the surface is `(0, 0)` and the bottom-right corner is `(1, 1)`, along
+    /// with the distance along the ray. The position lies outside this range if the ray misses
+    /// the surface.
+    ///
+    /// Returns [`None`] if the ray doesn't hit the plane, or hits it from behind, as the surface is
+    /// only displayed on its front.
+    #[cfg(feature = "bevy_picking")]
+    fn ray_intersection(&self, transform: &GlobalTransform, ray: Ray3d) -> Option<(Vec2, f32)> {
+        if ray.direction.dot(*transform.back()) >= 0.0 {
+            return None;
+        }
+        let plane = InfinitePlane3d {
+            normal: transform.back(),
+        };
+        let distance = ray.intersect_plane(transform.translation(), plane)?;
+        let local_position = transform
+            .affine()
+            .inverse()
+            .transform_point3(ray.get_point(distance))
+            .xy();
+        // The UI's y-axis points down.
+        let normalized_position = Vec2::new(local_position.x, -local_position.y) / self.size + 0.5;
+        normalized_position
+            .is_finite()
+            .then_some((normalized_position, distance))
+    }
+}
+
+#[cfg(feature = "bevy_picking")]
+/// Picking backend for [`WorldUiSurface`]s, which tests the picking rays against the surfaces.
+pub fn world_ui_surface_picking(
+    ray_map: Res<RayMap>,
+    camera_query: Query<&Camera>,
+    surface_query: Query<(Entity, &WorldUiSurface, &GlobalTransform, Option<&Pickable>)>,
+    mut pointer_hits_writer: MessageWriter<PointerHits>,
+) {
+    for (&ray_id, &ray) in ray_map.iter() {
+        let Ok(camera) = camera_query.get(ray_id.camera) else {
+            continue;
+        };
+
+        let picks = surface_query
+            .iter()
+            .filter(|(.., pickable)| pickable.is_none_or(|pickable| pickable.is_hoverable))
+            .filter_map(|(entity, surface, transform, _)| {
+                let (position, distance) = surface.ray_intersection(transform, ray)?;
+                if !(0. ..=1.).contains(&position.x) || !(0. ..=1.).contains(&position.y) {
+                    return None;
+                }
+                let hit = HitData::new(
+                    ray_id.camera,
+                    distance,
+                    Some(ray.get_point(distance)),
+                    Some(*transform.back()),
+                );
+                Some((entity, hit))
+            })
+            .collect::<Vec<_>>();
+
+        if !picks.is_empty() {
+            pointer_hits_writer.write(PointerHits::new(ray_id.pointer, picks, camera.order as f32));
+        }
+    }
+}
+
+#[cfg(feature = "bevy_picking")]
+/// Sends the pointer input on [`WorldUiSurface`]s to the UI displayed on them.
+///
+/// Surfaces that are being hovered or dragged will have all pointer inputs sent to them, mapped
+/// onto the render target of the surface's camera.
+pub fn world_ui_surface_pointer_input(
+    mut commands: Commands,
+    mut surface_query: Query<(
+        Entity,
+        &WorldUiSurface,
+        &PointerId,
+        &mut PointerLocation,
+        &GlobalTransform,
+    )>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    hover_map: Res<HoverMap>,
+    pointer_state: Res<PointerState>,
+    mut pointer_inputs: MessageReader<PointerInput>,
+) {
+    use bevy_camera::NormalizedRenderTarget;
+    use bevy_platform::collections::HashMap;
+
+    // Handle hovered surfaces.
+    let mut surface_picks: HashMap<Entity, PointerId> = hover_map
+        .iter()
+        .flat_map(|(hover_pointer_id, hits)| {
+            hits.iter()
+                .filter(|(entity, _)| surface_query.contains(**entity))
+                .map(|(entity, _)| (*entity, *hover_pointer_id))
+        })
+        .collect();
+
+    // Handle dragged surfaces, so that dragging continues when the pointer leaves the surface.
+    for ((pointer_id, _), pointer_state) in pointer_state.pointer_buttons.iter() {
+        for &target in pointer_state
+            .dragging
+            .keys()
+            .filter(|&entity| surface_query.contains(*entity))
+        {
+            surface_picks.insert(target, *pointer_id);
+        }
+    }
+
+    let inputs = pointer_inputs.read().collect::<Vec<_>>();
+    for (surface_entity, surface, &surface_pointer_id, mut surface_pointer_location, transform) in
+        &mut surface_query
+    {
+        let Some(pick_pointer_id) = surface_picks.get(&surface_entity) else {
+            // Lift the surface pointer if it's not being used.
+            surface_pointer_location.location = None;
+            continue;
+        };
+        let Ok((ui_camera, _)) = camera_query.get(surface.camera) else {
+            continue;
+        };
+        let Some(ui_size) = ui_camera.logical_viewport_size() else {
+            continue;
+        };
+        let Some(target) = ui_camera.target.as_image() else {
+            continue;
+        };
+
+        for input in inputs
+            .iter()
+            .filter(|input| &input.pointer_id == pick_pointer_id)
+        {
+            // Cast a ray from the frontmost camera the pointer is over.
+            let Some(ray) = camera_query
+                .iter()
+                .filter(|(camera, _)| {
+                    camera.is_active && input.location.is_in_viewport(camera, &primary_window)
+                })
+                .max_by_key(|(camera, _)| camera.order)
+                .and_then(|(camera, camera_transform)| {
+                    camera
+                        .viewport_to_world(camera_transform, input.location.position)
+                        .ok()
+                })
+            else {
+                continue;
+            };
+            let Some((position, _)) = surface.ray_intersection(transform, ray) else {
+                continue;
+            };
+
+            let location = Location {
+                position: position * ui_size,
+                target: NormalizedRenderTarget::Image(target.clone().into()),
+            };
+            surface_pointer_location.location = Some(location.clone());
+
+            commands.write_message(PointerInput {
+                location,
+                pointer_id: surface_pointer_id,
+                action: input.action,
+            });
+        }
+    }
+}
+
+#[cfg(all(test, feature = "bevy_picking"))]
+mod tests {
+    use super::*;
+    use bevy_math::{Dir3, Vec3};
+
+    #[test]
+    fn ray_intersection_only_hits_the_front() {
+        let surface = WorldUiSurface::new(Entity::PLACEHOLDER, Vec2::new(4., 2.));
+        let transform = GlobalTransform::from_translation(Vec3::new(0., 0., -5.));
+
+        let front = Ray3d::new(Vec3::new(1., 0.5, 0.), Dir3::NEG_Z);
+        let (position, distance) = surface.ray_intersection(&transform, front).unwrap();
+        assert!(position.abs_diff_eq(Vec2::new(0.75, 0.25), 1e-6));
+        assert!((distance - 5.).abs() < 1e-6);
+
+        let behind = Ray3d::new(Vec3::new(1., 0.5, -10.), Dir3::Z);
+        assert_eq!(surface.ray_intersection(&transform, behind), None);
+        let parallel = Ray3d::new(Vec3::new(1., 0.5, 0.), Dir3::X);
+        assert_eq!(surface.ray_intersection(&transform, parallel), None);
+    }
+}
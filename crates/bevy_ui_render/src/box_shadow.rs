@@ -38,6 +38,9 @@ use crate::{BoxShadowSamples, RenderUiSystems, TransparentUi, UiCameraMap};
 
 use super::{stack_z_offsets, UiCameraView, QUAD_INDICES, QUAD_VERTEX_POSITIONS};
 
+/// Shader flag set on the vertices of inset shadows.
+const INSET_SHADOW_FLAG: u32 = 1;
+
 /// A plugin that enables the rendering of box shadows.
 pub struct BoxShadowPlugin;
 
@@ -77,6 +80,9 @@ struct BoxShadowVertex {
     radius: [f32; 4],
     blur: f32,
     bounds: [f32; 2],
+    offset: [f32; 2],
+    outer_radius: [f32; 4],
+    flags: u32,
 }
 
 #[derive(Component)]
@@ -152,6 +158,12 @@ impl SpecializedRenderPipeline for BoxShadowPipeline {
                 VertexFormat::Float32,
                 // outer size
                 VertexFormat::Float32x2,
+                // offset of the target rect from the center of the outer rect
+                VertexFormat::Float32x2,
+                // outer corner radius values, used to clip inset shadows
+                VertexFormat::Float32x4,
+                // flags
+                VertexFormat::Uint32,
             ],
         );
         let shader_defs = vec![ShaderDefVal::UInt(
@@ -198,6 +210,13 @@ pub struct ExtractedBoxShadow {
     pub radius: ResolvedBorderRadius,
     pub blur_radius: f32,
     pub size: Vec2,
+    /// Offset of the center of the shadow rect from the center of the drawn quad. Only used by
+    /// inset shadows, drop shadows are drawn centered on their quad.
+    pub offset: Vec2,
+    /// The corner radii of the drawn quad, which inset shadows are clipped to.
+    pub outer_radius: ResolvedBorderRadius,
+    /// Draw the shadow inside the node, instead of behind it.
+    pub inset: bool,
     pub main_entity: MainEntity,
     pub render_entity: Entity,
 }
@@ -257,16 +276,54 @@ pub fn extract_shadows(
                 Val::VMax(percent) => percent / 100. * ui_physical_viewport_size.max_element(),
             };
 
-            let spread_x = resolve_val(drop_shadow.spread_radius, uinode.size().x, scale_factor);
-            let spread_ratio = (spread_x + uinode.size().x) / uinode.size().x;
-
-            let spread = vec2(spread_x, uinode.size().y * spread_ratio - uinode.size().y);
-
             let blur_radius = resolve_val(drop_shadow.blur_radius, uinode.size().x, scale_factor);
             let offset = vec2(
                 resolve_val(drop_shadow.x_offset, uinode.size().x, scale_factor),
                 resolve_val(drop_shadow.y_offset, uinode.size().y, scale_factor),
             );
+            let spread_x = resolve_val(drop_shadow.spread_radius, uinode.size().x, scale_factor);
+
+            if drop_shadow.inset {
+                // Inset shadows are drawn over the node's padding box, as if light was blocked
+                // by the edges of a hole the shape of the node, shrunk by the spread radius.
+                let border = uinode.border();
+                let inner_size =
+                    uinode.size() - vec2(border.left + border.right, border.top + border.bottom);
+                if inner_size.cmple(Vec2::ZERO).any() {
+                    continue;
+                }
+                let inner_center =
+                    0.5 * vec2(border.left - border.right, border.top - border.bottom);
+                let spread_ratio = ((inner_size.x - spread_x) / inner_size.x).max(0.);
+                let inner_radius = uinode.inner_radius();
+
+                extracted_box_shadows.box_shadows.push(ExtractedBoxShadow {
+                    render_entity: commands.spawn(TemporaryRenderEntity).id(),
+                    stack_index: uinode.stack_index,
+                    transform: Affine2::from(transform) * Affine2::from_translation(inner_center),
+                    color: drop_shadow.color.into(),
+                    bounds: inner_size,
+                    clip: clip.map(|clip| clip.clip),
+                    extracted_camera_entity,
+                    radius: ResolvedBorderRadius {
+                        top_left: inner_radius.top_left * spread_ratio,
+                        top_right: inner_radius.top_right * spread_ratio,
+                        bottom_left: inner_radius.bottom_left * spread_ratio,
+                        bottom_right: inner_radius.bottom_right * spread_ratio,
+                    },
+                    blur_radius,
+                    size: inner_size * spread_ratio,
+                    offset,
+                    outer_radius: inner_radius,
+                    inset: true,
+                    main_entity: entity.into(),
+                });
+                continue;
+            }
+
+            let spread_ratio = (spread_x + uinode.size().x) / uinode.size().x;
+
+            let spread = vec2(spread_x, uinode.size().y * spread_ratio - uinode.size().y);
 
             let shadow_size = uinode.size() + spread;
             if shadow_size.cmple(Vec2::ZERO).any() {
@@ -291,6 +348,9 @@ pub fn extract_shadows(
                 radius,
                 blur_radius,
                 size: shadow_size,
+                offset: Vec2::ZERO,
+                outer_radius: ResolvedBorderRadius::ZERO,
+                inset: false,
                 main_entity: entity.into(),
             });
         }
@@ -342,7 +402,14 @@ pub fn queue_shadows(
             draw_function,
             pipeline,
             entity: (entity, extracted_shadow.main_entity),
-            sort_key: FloatOrd(extracted_shadow.stack_index as f32 + stack_z_offsets::BOX_SHADOW),
+            sort_key: FloatOrd(
+                extracted_shadow.stack_index as f32
+                    + if extracted_shadow.inset {
+                        stack_z_offsets::BOX_SHADOW_INSET
+                    } else {
+                        stack_z_offsets::BOX_SHADOW
+                    },
+            ),
 
             batch_range: 0..0,
             extra_index: PhaseItemExtraIndex::None,
@@ -474,6 +541,13 @@ pub fn prepare_shadows(
                         radius: box_shadow.radius.into(),
                         blur: box_shadow.blur_radius,
                         bounds: rect_size.into(),
+                        offset: box_shadow.offset.into(),
+                        outer_radius: box_shadow.outer_radius.into(),
+                        flags: if box_shadow.inset {
+                            INSET_SHADOW_FLAG
+                        } else {
+                            0
+                        },
                     });
                 }
 
@@ -4,6 +4,8 @@
 const PI: f32 = 3.14159265358979323846;
 const SAMPLES: i32 = #SHADOW_SAMPLES;
 
+const INSET: u32 = 1u;
+
 @group(0) @binding(0) var<uniform> view: View;
 
 struct BoxShadowVertexOutput {
@@ -13,6 +15,10 @@ struct BoxShadowVertexOutput {
     @location(2) @interpolate(flat) size: vec2<f32>,
     @location(3) @interpolate(flat) radius: vec4<f32>,    
     @location(4) @interpolate(flat) blur: f32,
+    @location(5) @interpolate(flat) bounds: vec2<f32>,
+    @location(6) @interpolate(flat) offset: vec2<f32>,
+    @location(7) @interpolate(flat) outer_radius: vec4<f32>,
+    @location(8) @interpolate(flat) flags: u32,
 }
 
 fn gaussian(x: f32, sigma: f32) -> f32 {
@@ -34,6 +40,13 @@ fn selectCorner(p: vec2<f32>, c: vec4<f32>) -> f32 {
     return mix(mix(c.x, c.y, step(0., p.x)), mix(c.w, c.z, step(0., p.x)), step(0., p.y));
 }
 
+// signed distance from the point to the edge of a rounded rectangle centered on the origin
+fn sdRoundedBox(point: vec2<f32>, size: vec2<f32>, corners: vec4<f32>) -> f32 {
+    let radius = selectCorner(point, corners);
+    let q = abs(point) - 0.5 * size + radius;
+    return length(max(q, vec2(0.))) + min(max(q.x, q.y), 0.) - radius;
+}
+
 fn horizontalRoundedBoxShadow(x: f32, y: f32, blur: f32, corner: f32, half_size: vec2<f32>) -> f32 {
     let d = min(half_size.y - corner - abs(y), 0.);
     let c = half_size.x - corner + sqrt(max(0., corner * corner - d * d));
@@ -75,6 +88,9 @@ fn vertex(
     @location(4) radius: vec4<f32>,
     @location(5) blur: f32,
     @location(6) bounds: vec2<f32>,
+    @location(7) offset: vec2<f32>,
+    @location(8) outer_radius: vec4<f32>,
+    @location(9) flags: u32,
 ) -> BoxShadowVertexOutput {
     var out: BoxShadowVertexOutput;
     out.position = view.clip_from_world * vec4(vertex_position, 1.0);
@@ -83,6 +99,10 @@ fn vertex(
     out.size = size;
     out.radius = radius;
     out.blur = blur;
+    out.bounds = bounds;
+    out.offset = offset;
+    out.outer_radius = outer_radius;
+    out.flags = flags;
     return out;
 }
 
@@ -90,7 +110,16 @@ fn vertex(
 fn fragment(
     in: BoxShadowVertexOutput,
 ) -> @location(0) vec4<f32> {
-    let g = in.color.a * roundedBoxShadow(-0.5 * in.size, 0.5 * in.size, in.point, max(in.blur, 0.01), in.radius);
+    let blur = max(in.blur, 0.01);
+    if (in.flags & INSET) != 0u {
+        // The shadow is cast by the edges of the node onto its inside, so it covers everything
+        // except the hole left by the offset and spread shadow rect.
+        let hole = roundedBoxShadow(in.offset - 0.5 * in.size, in.offset + 0.5 * in.size, in.point, blur, in.radius);
+        // Clip the shadow to the rounded corners of the node.
+        let mask = saturate(0.5 - sdRoundedBox(in.point, in.bounds, in.outer_radius));
+        return vec4(in.color.rgb, in.color.a * (1. - hole) * mask);
+    }
+    let g = in.color.a * roundedBoxShadow(-0.5 * in.size, 0.5 * in.size, in.point, blur, in.radius);
     return vec4(in.color.rgb, g);
 }
 
@@ -94,9 +94,7 @@ pub fn extract_debug_overlay(
             render_entity: commands.spawn(TemporaryRenderEntity).id(),
             // Add a large number to the UI node's stack index so that the overlay is always drawn on top
             z_order: (ui_stack.uinodes.len() as u32 + uinode.stack_index()) as f32,
-            clip: maybe_clip
-                .filter(|_| !debug_options.show_clipped)
-                .map(|clip| clip.clip),
+            clip: maybe_clip.filter(|_| !debug_options.show_clipped).copied(),
             image: AssetId::default(),
             extracted_camera_entity,
             transform: transform.into(),
@@ -401,7 +401,7 @@ pub fn extract_gradients(
                                 NodeType::Border(_) => stack_z_offsets::BORDER_GRADIENT,
                             },
                         image: AssetId::default(),
-                        clip: clip.map(|clip| clip.clip),
+                        clip: clip.copied(),
                         extracted_camera_entity,
                         transform: transform.into(),
                         item: ExtractedUiItem::Node {
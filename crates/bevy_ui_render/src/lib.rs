@@ -25,7 +25,9 @@ use bevy_reflect::prelude::ReflectDefault;
 use bevy_reflect::Reflect;
 use bevy_shader::load_shader_library;
 use bevy_sprite_render::SpriteAssetEvents;
-use bevy_ui::widget::{ImageNode, TextShadow, ViewportNode};
+use bevy_ui::widget::{
+    BorderImage, ImageNode, TextInput, TextInputLayout, TextShadow, ViewportNode,
+};
 use bevy_ui::{
     BackgroundColor, BorderColor, CalculatedClip, ComputedNode, ComputedUiTargetCamera, Display,
     Node, Outline, ResolvedBorderRadius, UiGlobalTransform,
@@ -118,10 +120,14 @@ pub mod stack_z_offsets {
     pub const BORDER: f32 = 0.01;
     pub const GRADIENT: f32 = 0.02;
     pub const BORDER_GRADIENT: f32 = 0.03;
+    pub const BORDER_IMAGE: f32 = 0.033;
+    pub const BOX_SHADOW_INSET: f32 = 0.035;
     pub const IMAGE: f32 = 0.04;
     pub const MATERIAL: f32 = 0.05;
+    pub const TEXT_SELECTION: f32 = 0.055;
     pub const TEXT: f32 = 0.06;
     pub const TEXT_STRIKETHROUGH: f32 = 0.07;
+    pub const TEXT_CARET: f32 = 0.08;
 }
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
@@ -246,6 +252,7 @@ impl Plugin for UiRenderPlugin {
                     extract_uinode_borders.in_set(RenderUiSystems::ExtractBorders),
                     extract_viewport_nodes.in_set(RenderUiSystems::ExtractViewportNodes),
                     extract_text_decorations.in_set(RenderUiSystems::ExtractTextBackgrounds),
+                    extract_text_input_decorations.in_set(RenderUiSystems::ExtractTextBackgrounds),
                     extract_text_shadows.in_set(RenderUiSystems::ExtractTextShadows),
                     extract_text_sections.in_set(RenderUiSystems::ExtractText),
                     #[cfg(feature = "bevy_ui_debug")]
@@ -348,7 +355,7 @@ impl<'w, 's> UiCameraMapper<'w, 's> {
 pub struct ExtractedUiNode {
     pub z_order: f32,
     pub image: AssetId<Image>,
-    pub clip: Option<Rect>,
+    pub clip: Option<CalculatedClip>,
     /// Render world entity of the extracted camera corresponding to this node's target camera.
     pub extracted_camera_entity: Entity,
     pub item: ExtractedUiItem,
@@ -467,7 +474,7 @@ pub fn extract_uinode_background_colors(
         extracted_uinodes.uinodes.push(ExtractedUiNode {
             render_entity: commands.spawn(TemporaryRenderEntity).id(),
             z_order: uinode.stack_index as f32 + stack_z_offsets::BACKGROUND_COLOR,
-            clip: clip.map(|clip| clip.clip),
+            clip: clip.copied(),
             image: AssetId::default(),
             extracted_camera_entity,
             transform: transform.into(),
@@ -554,7 +561,7 @@ pub fn extract_uinode_images(
         extracted_uinodes.uinodes.push(ExtractedUiNode {
             z_order: uinode.stack_index as f32 + stack_z_offsets::IMAGE,
             render_entity: commands.spawn(TemporaryRenderEntity).id(),
-            clip: clip.map(|clip| clip.clip),
+            clip: clip.copied(),
             image: image.image.id(),
             extracted_camera_entity,
             transform: transform.into(),
@@ -586,6 +593,7 @@ pub fn extract_uinode_borders(
             Option<&CalculatedClip>,
             &ComputedUiTargetCamera,
             AnyOf<(&BorderColor, &Outline)>,
+            Has<BorderImage>,
         )>,
     >,
     camera_map: Extract<UiCameraMap>,
@@ -602,6 +610,7 @@ pub fn extract_uinode_borders(
         maybe_clip,
         camera,
         (maybe_border_color, maybe_outline),
+        has_border_image,
     ) in &uinode_query
     {
         // Skip invisible borders and removed nodes
@@ -613,8 +622,9 @@ pub fn extract_uinode_borders(
             continue;
         };
 
-        // Don't extract borders with zero width along all edges
+        // Don't extract borders with zero width along all edges, or borders drawn with an image
         if computed_node.border() != BorderRect::ZERO
+            && !has_border_image
             && let Some(border_color) = maybe_border_color
         {
             let border_colors = [
@@ -653,7 +663,7 @@ pub fn extract_uinode_borders(
                 extracted_uinodes.uinodes.push(ExtractedUiNode {
                     z_order: computed_node.stack_index as f32 + stack_z_offsets::BORDER,
                     image,
-                    clip: maybe_clip.map(|clip| clip.clip),
+                    clip: maybe_clip.copied(),
                     extracted_camera_entity,
                     transform: transform.into(),
                     item: ExtractedUiItem::Node {
@@ -686,7 +696,7 @@ pub fn extract_uinode_borders(
                 z_order: computed_node.stack_index as f32 + stack_z_offsets::BORDER,
                 render_entity: commands.spawn(TemporaryRenderEntity).id(),
                 image,
-                clip: maybe_clip.map(|clip| clip.clip),
+                clip: maybe_clip.copied(),
                 extracted_camera_entity,
                 transform: transform.into(),
                 item: ExtractedUiItem::Node {
@@ -876,7 +886,7 @@ pub fn extract_viewport_nodes(
         extracted_uinodes.uinodes.push(ExtractedUiNode {
             z_order: uinode.stack_index as f32 + stack_z_offsets::IMAGE,
             render_entity: commands.spawn(TemporaryRenderEntity).id(),
-            clip: clip.map(|clip| clip.clip),
+            clip: clip.copied(),
             image: image.id(),
             extracted_camera_entity,
             transform: transform.into(),
@@ -990,7 +1000,7 @@ pub fn extract_text_sections(
                     z_order: uinode.stack_index as f32 + stack_z_offsets::TEXT,
                     render_entity: commands.spawn(TemporaryRenderEntity).id(),
                     image: atlas_info.texture,
-                    clip: clip.map(|clip| clip.clip),
+                    clip: clip.copied(),
                     extracted_camera_entity,
                     item: ExtractedUiItem::Glyphs { range: start..end },
                     main_entity: entity.into(),
@@ -1083,7 +1093,7 @@ pub fn extract_text_shadows(
                     z_order: uinode.stack_index as f32 + stack_z_offsets::TEXT,
                     render_entity: commands.spawn(TemporaryRenderEntity).id(),
                     image: atlas_info.texture,
-                    clip: clip.map(|clip| clip.clip),
+                    clip: clip.copied(),
                     extracted_camera_entity,
                     item: ExtractedUiItem::Glyphs { range: start..end },
                     main_entity: entity.into(),
@@ -1105,7 +1115,7 @@ pub fn extract_text_shadows(
                 extracted_uinodes.uinodes.push(ExtractedUiNode {
                     z_order: uinode.stack_index as f32 + stack_z_offsets::TEXT,
                     render_entity: commands.spawn(TemporaryRenderEntity).id(),
-                    clip: clip.map(|clip| clip.clip),
+                    clip: clip.copied(),
                     image: AssetId::default(),
                     extracted_camera_entity,
                     transform: node_transform
@@ -1131,7 +1141,7 @@ pub fn extract_text_shadows(
                 extracted_uinodes.uinodes.push(ExtractedUiNode {
                     z_order: uinode.stack_index as f32 + stack_z_offsets::TEXT,
                     render_entity: commands.spawn(TemporaryRenderEntity).id(),
-                    clip: clip.map(|clip| clip.clip),
+                    clip: clip.copied(),
                     image: AssetId::default(),
                     extracted_camera_entity,
                     transform: node_transform * Affine2::from_translation(run.underline_position()),
@@ -1220,7 +1230,7 @@ pub fn extract_text_decorations(
                 extracted_uinodes.uinodes.push(ExtractedUiNode {
                     z_order: uinode.stack_index as f32 + stack_z_offsets::TEXT,
                     render_entity: commands.spawn(TemporaryRenderEntity).id(),
-                    clip: clip.map(|clip| clip.clip),
+                    clip: clip.copied(),
                     image: AssetId::default(),
                     extracted_camera_entity,
                     transform: transform * Affine2::from_translation(run.bounds.center()),
@@ -1250,7 +1260,7 @@ pub fn extract_text_decorations(
                 extracted_uinodes.uinodes.push(ExtractedUiNode {
                     z_order: uinode.stack_index as f32 + stack_z_offsets::TEXT_STRIKETHROUGH,
                     render_entity: commands.spawn(TemporaryRenderEntity).id(),
-                    clip: clip.map(|clip| clip.clip),
+                    clip: clip.copied(),
                     image: AssetId::default(),
                     extracted_camera_entity,
                     transform: transform * Affine2::from_translation(run.strikethrough_position()),
@@ -1280,7 +1290,7 @@ pub fn extract_text_decorations(
                 extracted_uinodes.uinodes.push(ExtractedUiNode {
                     z_order: uinode.stack_index as f32 + stack_z_offsets::TEXT_STRIKETHROUGH,
                     render_entity: commands.spawn(TemporaryRenderEntity).id(),
-                    clip: clip.map(|clip| clip.clip),
+                    clip: clip.copied(),
                     image: AssetId::default(),
                     extracted_camera_entity,
                     transform: transform * Affine2::from_translation(run.underline_position()),
@@ -1304,6 +1314,99 @@ pub fn extract_text_decorations(
     }
 }
 
+/// Extracts the selection, input method composition underline and caret of [`TextInput`] nodes.
+pub fn extract_text_input_decorations(
+    mut commands: Commands,
+    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    uinode_query: Extract<
+        Query<(
+            Entity,
+            &ComputedNode,
+            &UiGlobalTransform,
+            &InheritedVisibility,
+            Option<&CalculatedClip>,
+            &ComputedUiTargetCamera,
+            &TextInput,
+            &TextInputLayout,
+            &TextColor,
+        )>,
+    >,
+    camera_map: Extract<UiCameraMap>,
+) {
+    let mut camera_mapper = camera_map.get_mapper();
+    for (
+        entity,
+        uinode,
+        global_transform,
+        inherited_visibility,
+        clip,
+        camera,
+        text_input,
+        layout,
+        text_color,
+    ) in &uinode_query
+    {
+        // Skip if not visible or if size is set to zero (e.g. when a parent is set to `Display::None`)
+        if !inherited_visibility.get() || uinode.is_empty() {
+            continue;
+        }
+
+        let Some(extracted_camera_entity) = camera_mapper.map(camera) else {
+            continue;
+        };
+
+        let transform =
+            Affine2::from(global_transform) * Affine2::from_translation(-0.5 * uinode.size());
+
+        let decorations = layout
+            .selection
+            .iter()
+            .map(|rect| {
+                (
+                    *rect,
+                    text_input.selection_color,
+                    stack_z_offsets::TEXT_SELECTION,
+                )
+            })
+            .chain(
+                layout
+                    .preedit
+                    .iter()
+                    .map(|rect| (*rect, text_color.0, stack_z_offsets::TEXT_STRIKETHROUGH)),
+            )
+            .chain(
+                layout
+                    .caret
+                    .map(|rect| (rect, text_input.caret_color, stack_z_offsets::TEXT_CARET)),
+            );
+
+        for (rect, color, z_offset) in decorations {
+            extracted_uinodes.uinodes.push(ExtractedUiNode {
+                z_order: uinode.stack_index as f32 + z_offset,
+                render_entity: commands.spawn(TemporaryRenderEntity).id(),
+                clip: clip.copied(),
+                image: AssetId::default(),
+                extracted_camera_entity,
+                transform: transform * Affine2::from_translation(rect.center()),
+                item: ExtractedUiItem::Node {
+                    color: color.to_linear(),
+                    rect: Rect {
+                        min: Vec2::ZERO,
+                        max: rect.size(),
+                    },
+                    atlas_scaling: None,
+                    flip_x: false,
+                    flip_y: false,
+                    border: BorderRect::ZERO,
+                    border_radius: ResolvedBorderRadius::ZERO,
+                    node_type: NodeType::Rect,
+                },
+                main_entity: entity.into(),
+            });
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Pod, Zeroable)]
 struct UiVertex {
@@ -1323,6 +1426,25 @@ struct UiVertex {
     pub size: [f32; 2],
     /// Position relative to the center of the UI node.
     pub point: [f32; 2],
+    /// Position relative to the center of the clipping rect.
+    pub clip_point: [f32; 2],
+    /// Size of the clipping rect, zero if the clip doesn't have rounded corners.
+    pub clip_size: [f32; 2],
+    /// Radii of the rounded corners of the clipping rect.
+    /// Ordering: top left, top right, bottom right, bottom left.
+    pub clip_radius: [f32; 4],
+}
+
+/// Returns the center, size and corner radii of a rounded clip, used to clip UI nodes to rounded
+/// corners in the shader. Clips without rounded corners are handled entirely on the CPU, so
+/// zeroes are returned for them.
+fn rounded_clip(clip: Option<CalculatedClip>) -> (Vec2, Vec2, [f32; 4]) {
+    match clip {
+        Some(clip) if clip.radius != ResolvedBorderRadius::ZERO => {
+            (clip.clip.center(), clip.clip.size(), clip.radius.into())
+        }
+        _ => (Vec2::ZERO, Vec2::ZERO, [0.; 4]),
+    }
 }
 
 #[derive(Resource)]
@@ -1563,6 +1685,8 @@ pub fn prepare_uinodes(
                         continue;
                     }
                 }
+                let clip_rect = extracted_uinode.clip.map(|clip| clip.clip);
+                let (clip_center, clip_size, clip_radius) = rounded_clip(extracted_uinode.clip);
                 match &extracted_uinode.item {
                     ExtractedUiItem::Node {
                         atlas_scaling,
@@ -1593,7 +1717,7 @@ pub fn prepare_uinodes(
 
                         // Calculate the effect of clipping
                         // Note: this won't work with rotation/scaling, but that's much more complex (may need more that 2 quads)
-                        let mut positions_diff = if let Some(clip) = extracted_uinode.clip {
+                        let mut positions_diff = if let Some(clip) = clip_rect {
                             [
                                 Vec2::new(
                                     f32::max(clip.min.x - positions[0].x, 0.),
@@ -1707,6 +1831,9 @@ pub fn prepare_uinodes(
                                 border: [border.left, border.top, border.right, border.bottom],
                                 size: rect_size.into(),
                                 point: points[i].into(),
+                                clip_point: (positions_clipped[i].truncate() - clip_center).into(),
+                                clip_size: clip_size.into(),
+                                clip_radius,
                             });
                         }
 
@@ -1737,7 +1864,7 @@ pub fn prepare_uinodes(
                                     .extend(0.)
                             });
 
-                            let positions_diff = if let Some(clip) = extracted_uinode.clip {
+                            let positions_diff = if let Some(clip) = clip_rect {
                                 [
                                     Vec2::new(
                                         f32::max(clip.min.x - positions[0].x, 0.),
@@ -1808,6 +1935,10 @@ pub fn prepare_uinodes(
                                     border: [0.0; 4],
                                     size: rect_size.into(),
                                     point: [0.0; 2],
+                                    clip_point: (positions_clipped[i].truncate() - clip_center)
+                                        .into(),
+                                    clip_size: clip_size.into(),
+                                    clip_radius,
                                 });
                             }
 
@@ -75,6 +75,12 @@ impl SpecializedRenderPipeline for UiPipeline {
                 VertexFormat::Float32x2,
                 // position relative to the center
                 VertexFormat::Float32x2,
+                // position relative to the center of the clip
+                VertexFormat::Float32x2,
+                // clip size
+                VertexFormat::Float32x2,
+                // clip radius
+                VertexFormat::Float32x4,
             ],
         );
         let shader_defs = if key.anti_alias {
@@ -1,6 +1,6 @@
 use core::ops::Range;
 
-use super::{ImageNodeBindGroups, UiBatch, UiMeta, UiViewTarget};
+use super::{ImageNodeBindGroups, UiBackdropTexture, UiBatch, UiMeta, UiViewTarget};
 
 use crate::UiCameraView;
 use bevy_ecs::{
@@ -24,6 +24,7 @@ pub struct UiPassNode {
     ui_view_query: QueryState<(&'static ExtractedView, &'static UiViewTarget)>,
     ui_view_target_query: QueryState<(&'static ViewTarget, &'static ExtractedCamera)>,
     ui_camera_view_query: QueryState<&'static UiCameraView>,
+    ui_backdrop_query: QueryState<&'static UiBackdropTexture>,
 }
 
 impl UiPassNode {
@@ -32,6 +33,7 @@ impl UiPassNode {
             ui_view_query: world.query_filtered(),
             ui_view_target_query: world.query(),
             ui_camera_view_query: world.query(),
+            ui_backdrop_query: world.query(),
         }
     }
 }
@@ -41,6 +43,7 @@ impl Node for UiPassNode {
         self.ui_view_query.update_archetypes(world);
         self.ui_view_target_query.update_archetypes(world);
         self.ui_camera_view_query.update_archetypes(world);
+        self.ui_backdrop_query.update_archetypes(world);
     }
 
     fn run(
@@ -91,6 +94,16 @@ impl Node for UiPassNode {
         } else {
             input_view_entity
         };
+
+        // Copy what the camera has rendered so far for the UI materials that sample the backdrop.
+        if let Ok(backdrop) = self.ui_backdrop_query.get_manual(world, input_view_entity) {
+            render_context.command_encoder().copy_texture_to_texture(
+                target.main_texture().as_image_copy(),
+                backdrop.0.texture.as_image_copy(),
+                target.main_texture().size(),
+            );
+        }
+
         let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
             label: Some("ui"),
             color_attachments: &[Some(target.get_unsampled_color_attachment())],
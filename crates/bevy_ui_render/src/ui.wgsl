@@ -29,6 +29,11 @@ struct VertexOutput {
 
     // Position relative to the center of the rectangle.
     @location(6) point: vec2<f32>,
+
+    // Position relative to the center of the clipping rect.
+    @location(7) clip_point: vec2<f32>,
+    @location(8) @interpolate(flat) clip_size: vec2<f32>,
+    @location(9) @interpolate(flat) clip_radius: vec4<f32>,
     @builtin(position) position: vec4<f32>,
 };
 
@@ -46,6 +51,12 @@ fn vertex(
     @location(5) border: vec4<f32>,
     @location(6) size: vec2<f32>,
     @location(7) point: vec2<f32>,
+    @location(8) clip_point: vec2<f32>,
+    @location(9) clip_size: vec2<f32>,
+
+    // Radii of the rounded corners of the clipping rect, all zero if it has no rounded corners.
+    // x: top left, y: top right, z: bottom right, w: bottom left.
+    @location(10) clip_radius: vec4<f32>,
 ) -> VertexOutput {
     var out: VertexOutput;
     out.uv = vertex_uv;
@@ -56,6 +67,9 @@ fn vertex(
     out.size = size;
     out.border = border;
     out.point = point;
+    out.clip_point = clip_point;
+    out.clip_size = clip_size;
+    out.clip_radius = clip_radius;
 
     return out;
 }
@@ -218,9 +232,23 @@ fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
     // This allows us to draw both textured and untextured shapes together in the same batch.
     let color = select(in.color, in.color * texture_color, enabled(in.flags, TEXTURED));
 
+    var out: vec4<f32>;
     if enabled(in.flags, BORDER_ANY) {
-        return draw_uinode_border(color, in.point, in.size, in.radius, in.border, in.flags);
+        out = draw_uinode_border(color, in.point, in.size, in.radius, in.border, in.flags);
     } else {
-        return draw_uinode_background(color, in.point, in.size, in.radius, in.border);
+        out = draw_uinode_background(color, in.point, in.size, in.radius, in.border);
+    }
+
+    // Rectangular clipping is done on the CPU, only the rounded corners of the clip need to be 
+    // handled here.
+    if any(in.clip_radius != vec4(0.0)) {
+        let clip_distance = sd_rounded_box(in.clip_point, in.clip_size, in.clip_radius);
+#ifdef ANTI_ALIAS
+        out.a *= antialias(clip_distance);
+#else
+        out.a *= 1.0 - step(0.0, clip_distance);
+#endif
     }
+
+    return out;
 }
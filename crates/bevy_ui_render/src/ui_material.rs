@@ -99,6 +99,18 @@ use derive_more::derive::From;
 ///
 /// }
 /// ```
+///
+/// # Backdrop
+///
+/// Materials that return `true` from [`UiMaterial::reads_backdrop_texture`] can sample what has been
+/// rendered behind the UI, for example to draw frosted glass panels. The backdrop is a copy of the
+/// camera's output taken before any UI is drawn, so it doesn't include other UI nodes. It is bound
+/// to the view bind group, after the globals uniform:
+///
+/// ```wgsl
+/// @group(0) @binding(2) var backdrop_texture: texture_2d<f32>;
+/// @group(0) @binding(3) var backdrop_sampler: sampler;
+/// ```
 pub trait UiMaterial: AsBindGroup + Asset + Clone + Sized {
     /// Returns this materials vertex shader. If [`ShaderRef::Default`] is returned, the default UI
     /// vertex shader will be used.
@@ -112,6 +124,15 @@ pub trait UiMaterial: AsBindGroup + Asset + Clone + Sized {
         ShaderRef::Default
     }
 
+    /// Returns `true` if this material samples the backdrop texture, the content rendered by the
+    /// camera behind the UI.
+    ///
+    /// Copying the backdrop has a cost for every UI camera, so this should only be enabled by
+    /// materials that need it.
+    fn reads_backdrop_texture() -> bool {
+        false
+    }
+
     #[expect(
         unused_variables,
         reason = "The parameters here are intentionally unused by the default implementation; however, putting underscores here will result in the underscores being copied by rust-analyzer's tab completion."
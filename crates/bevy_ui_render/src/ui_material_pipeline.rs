@@ -12,13 +12,18 @@ use bevy_ecs::{
 use bevy_image::BevyDefault as _;
 use bevy_math::{Affine2, FloatOrd, Rect, Vec2};
 use bevy_mesh::VertexBufferLayout;
+use bevy_platform::collections::HashMap;
 use bevy_render::{
     globals::{GlobalsBuffer, GlobalsUniform},
     render_asset::{PrepareAssetError, RenderAsset, RenderAssetPlugin, RenderAssets},
     render_phase::*,
-    render_resource::{binding_types::uniform_buffer, *},
+    render_resource::{
+        binding_types::{sampler, texture_2d, uniform_buffer},
+        *,
+    },
     renderer::{RenderDevice, RenderQueue},
     sync_world::{MainEntity, TemporaryRenderEntity},
+    texture::{CachedTexture, TextureCache},
     view::*,
     Extract, ExtractSchedule, Render, RenderSystems,
 };
@@ -70,14 +75,65 @@ where
                         prepare_uimaterial_nodes::<M>.in_set(RenderSystems::PrepareBindGroups),
                     ),
                 );
+
+            if M::reads_backdrop_texture() && !render_app.world().contains_resource::<UiBackdrop>()
+            {
+                render_app.init_resource::<UiBackdrop>().add_systems(
+                    Render,
+                    prepare_ui_backdrop_textures.in_set(RenderSystems::PrepareResources),
+                );
+            }
         }
     }
 }
 
+/// Render world resource which is present if any [`UiMaterial`] reads the backdrop texture, in
+/// which case a [`UiBackdropTexture`] is prepared for every UI view.
+#[derive(Resource, Default)]
+pub struct UiBackdrop;
+
+/// Render world component on UI views holding a copy of the camera's main texture, taken before the
+/// UI is drawn. Sampled by the [`UiMaterial`]s that
+/// [read the backdrop](UiMaterial::reads_backdrop_texture).
+#[derive(Component)]
+pub struct UiBackdropTexture(pub CachedTexture);
+
+pub fn prepare_ui_backdrop_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    ui_views: Query<(Entity, &UiViewTarget)>,
+    view_targets: Query<&ViewTarget>,
+) {
+    for (ui_view, ui_view_target) in &ui_views {
+        let Ok(view_target) = view_targets.get(ui_view_target.0) else {
+            continue;
+        };
+
+        let texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("ui_backdrop_texture"),
+                size: view_target.main_texture().size(),
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: view_target.main_texture_format(),
+                usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+        );
+        commands.entity(ui_view).insert(UiBackdropTexture(texture));
+    }
+}
+
 #[derive(Resource)]
 pub struct UiMaterialMeta<M: UiMaterial> {
     vertices: RawBufferVec<UiMaterialVertex>,
     view_bind_group: Option<BindGroup>,
+    /// View bind groups including the backdrop texture of each UI view, only used if the material
+    /// reads the backdrop.
+    backdrop_view_bind_groups: HashMap<Entity, BindGroup>,
     marker: PhantomData<M>,
 }
 
@@ -86,6 +142,7 @@ impl<M: UiMaterial> Default for UiMaterialMeta<M> {
         Self {
             vertices: RawBufferVec::new(BufferUsages::VERTEX),
             view_bind_group: Default::default(),
+            backdrop_view_bind_groups: Default::default(),
             marker: PhantomData,
         }
     }
@@ -115,6 +172,8 @@ pub struct UiMaterialBatch<M: UiMaterial> {
 pub struct UiMaterialPipeline<M: UiMaterial> {
     pub ui_layout: BindGroupLayoutDescriptor,
     pub view_layout: BindGroupLayoutDescriptor,
+    /// Sampler for the backdrop texture, if the material reads the backdrop.
+    pub backdrop_sampler: Option<Sampler>,
     pub vertex_shader: Handle<Shader>,
     pub fragment_shader: Handle<Shader>,
     marker: PhantomData<M>,
@@ -184,22 +243,46 @@ pub fn init_ui_material_pipeline<M: UiMaterial>(
 ) {
     let ui_layout = M::bind_group_layout_descriptor(&render_device);
 
-    let view_layout = BindGroupLayoutDescriptor::new(
-        "ui_view_layout",
-        &BindGroupLayoutEntries::sequential(
-            ShaderStages::VERTEX_FRAGMENT,
-            (
-                uniform_buffer::<ViewUniform>(true),
-                uniform_buffer::<GlobalsUniform>(false),
+    let (view_layout, backdrop_sampler) = if M::reads_backdrop_texture() {
+        let view_layout = BindGroupLayoutDescriptor::new(
+            "ui_backdrop_view_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::VERTEX_FRAGMENT,
+                (
+                    uniform_buffer::<ViewUniform>(true),
+                    uniform_buffer::<GlobalsUniform>(false),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                ),
             ),
-        ),
-    );
+        );
+        let backdrop_sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("ui_backdrop_sampler"),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..default()
+        });
+        (view_layout, Some(backdrop_sampler))
+    } else {
+        let view_layout = BindGroupLayoutDescriptor::new(
+            "ui_view_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::VERTEX_FRAGMENT,
+                (
+                    uniform_buffer::<ViewUniform>(true),
+                    uniform_buffer::<GlobalsUniform>(false),
+                ),
+            ),
+        );
+        (view_layout, None)
+    };
 
     let load_default = || load_embedded_asset!(asset_server.as_ref(), "ui_material.wgsl");
 
     commands.insert_resource(UiMaterialPipeline::<M> {
         ui_layout,
         view_layout,
+        backdrop_sampler,
         vertex_shader: match M::vertex_shader() {
             ShaderRef::Default => load_default(),
             ShaderRef::Handle(handle) => handle,
@@ -224,21 +307,26 @@ pub type DrawUiMaterial<M> = (
 pub struct SetMatUiViewBindGroup<M: UiMaterial, const I: usize>(PhantomData<M>);
 impl<P: PhaseItem, M: UiMaterial, const I: usize> RenderCommand<P> for SetMatUiViewBindGroup<M, I> {
     type Param = SRes<UiMaterialMeta<M>>;
-    type ViewQuery = Read<ViewUniformOffset>;
+    type ViewQuery = (Entity, Read<ViewUniformOffset>);
     type ItemQuery = ();
 
     fn render<'w>(
         _item: &P,
-        view_uniform: &'w ViewUniformOffset,
+        (view_entity, view_uniform): (Entity, &'w ViewUniformOffset),
         _entity: Option<()>,
         ui_meta: SystemParamItem<'w, '_, Self::Param>,
         pass: &mut TrackedRenderPass<'w>,
     ) -> RenderCommandResult {
-        pass.set_bind_group(
-            I,
-            ui_meta.into_inner().view_bind_group.as_ref().unwrap(),
-            &[view_uniform.offset],
-        );
+        let ui_meta = ui_meta.into_inner();
+        let view_bind_group = if M::reads_backdrop_texture() {
+            let Some(view_bind_group) = ui_meta.backdrop_view_bind_groups.get(&view_entity) else {
+                return RenderCommandResult::Skip;
+            };
+            view_bind_group
+        } else {
+            ui_meta.view_bind_group.as_ref().unwrap()
+        };
+        pass.set_bind_group(I, view_bind_group, &[view_uniform.offset]);
         RenderCommandResult::Success
     }
 }
@@ -387,6 +475,7 @@ pub fn prepare_uimaterial_nodes<M: UiMaterial>(
     globals_buffer: Res<GlobalsBuffer>,
     ui_material_pipeline: Res<UiMaterialPipeline<M>>,
     mut phases: ResMut<ViewSortedRenderPhases<TransparentUi>>,
+    backdrop_textures: Query<(Entity, &UiBackdropTexture)>,
     mut previous_len: Local<usize>,
 ) {
     if let (Some(view_binding), Some(globals_binding)) = (
@@ -396,11 +485,30 @@ pub fn prepare_uimaterial_nodes<M: UiMaterial>(
         let mut batches: Vec<(Entity, UiMaterialBatch<M>)> = Vec::with_capacity(*previous_len);
 
         ui_meta.vertices.clear();
-        ui_meta.view_bind_group = Some(render_device.create_bind_group(
-            "ui_material_view_bind_group",
-            &pipeline_cache.get_bind_group_layout(&ui_material_pipeline.view_layout),
-            &BindGroupEntries::sequential((view_binding, globals_binding)),
-        ));
+        ui_meta.backdrop_view_bind_groups.clear();
+        if let Some(backdrop_sampler) = ui_material_pipeline.backdrop_sampler.as_ref() {
+            for (ui_view, backdrop_texture) in &backdrop_textures {
+                let bind_group = render_device.create_bind_group(
+                    "ui_material_backdrop_view_bind_group",
+                    &pipeline_cache.get_bind_group_layout(&ui_material_pipeline.view_layout),
+                    &BindGroupEntries::sequential((
+                        view_binding.clone(),
+                        globals_binding.clone(),
+                        &backdrop_texture.0.default_view,
+                        backdrop_sampler,
+                    )),
+                );
+                ui_meta
+                    .backdrop_view_bind_groups
+                    .insert(ui_view, bind_group);
+            }
+        } else {
+            ui_meta.view_bind_group = Some(render_device.create_bind_group(
+                "ui_material_view_bind_group",
+                &pipeline_cache.get_bind_group_layout(&ui_material_pipeline.view_layout),
+                &BindGroupEntries::sequential((view_binding, globals_binding)),
+            ));
+        }
         let mut index = 0;
 
         for ui_phase in phases.values_mut() {
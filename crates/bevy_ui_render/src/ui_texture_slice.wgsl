@@ -1,5 +1,14 @@
 #import bevy_render::view::View;
 #import bevy_render::globals::Globals;
+#import bevy_ui::ui_node::{
+    antialias,
+    sd_rounded_box,
+    sd_inset_rounded_box,
+}
+
+// must align with slice_flags from ui_texture_slice_pipeline.rs
+const BORDER_IMAGE: u32 = 1u;
+const FILL: u32 = 2u;
 
 @group(0) @binding(0)
 var<uniform> view: View;
@@ -40,6 +49,13 @@ struct UiVertexOutput {
     // x, y = top, left corner of the atlas rect
     // z, w = bottom, right corner of the atlas rect
     @location(5) @interpolate(flat) atlas_rect: vec4<f32>,
+
+    @location(6) @interpolate(flat) size: vec2<f32>,
+    // x: top left, y: top right, z: bottom right, w: bottom left.
+    @location(7) @interpolate(flat) radius: vec4<f32>,
+    // x: left, y: top, z: right, w: bottom.
+    @location(8) @interpolate(flat) border: vec4<f32>,
+    @location(9) @interpolate(flat) flags: u32,
     @builtin(position) position: vec4<f32>,
 }
 
@@ -52,6 +68,10 @@ fn vertex(
     @location(4) target_slices: vec4<f32>,
     @location(5) repeat: vec4<f32>,
     @location(6) atlas_rect: vec4<f32>,
+    @location(7) size: vec2<f32>,
+    @location(8) radius: vec4<f32>,
+    @location(9) border: vec4<f32>,
+    @location(10) flags: u32,
 ) -> UiVertexOutput {
     var out: UiVertexOutput;
     out.uv = vertex_uv;
@@ -61,6 +81,10 @@ fn vertex(
     out.target_slices = target_slices;
     out.repeat = repeat;
     out.atlas_rect = atlas_rect;
+    out.size = size;
+    out.radius = radius;
+    out.border = border;
+    out.flags = flags;
     return out;
 }
 
@@ -123,5 +147,22 @@ fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
     // map the slice coords to texture coords
     let atlas_uv = in.atlas_rect.xy + uv * (in.atlas_rect.zw - in.atlas_rect.xy);
 
-    return in.color * textureSample(sprite_texture, sprite_sampler, atlas_uv);
+    let color = in.color * textureSample(sprite_texture, sprite_sampler, atlas_uv);
+
+    // Clip to the rounded corners of the node, and for border images without fill also to the
+    // inside edge of the border.
+    let point = (in.uv - 0.5) * in.size;
+    var distance = sd_rounded_box(point, in.size, in.radius);
+    if (in.flags & (BORDER_IMAGE | FILL)) == BORDER_IMAGE {
+        let internal_distance = sd_inset_rounded_box(point, in.size, in.radius, in.border);
+        distance = max(distance, -internal_distance);
+    }
+
+#ifdef ANTI_ALIAS
+    let t = antialias(distance);
+#else
+    let t = 1.0 - step(0.0, distance);
+#endif
+
+    return vec4(color.rgb, saturate(color.a * t));
 }
@@ -27,7 +27,7 @@ use bevy_render::{sync_world::MainEntity, RenderStartup};
 use bevy_shader::Shader;
 use bevy_sprite::{SliceScaleMode, SpriteImageMode, TextureSlicer};
 use bevy_sprite_render::SpriteAssetEvents;
-use bevy_ui::widget;
+use bevy_ui::widget::{self, BorderImage};
 use bevy_utils::default;
 use binding_types::{sampler, texture_2d};
 use bytemuck::{Pod, Zeroable};
@@ -71,6 +71,19 @@ struct UiTextureSliceVertex {
     pub border: [f32; 4],
     pub repeat: [f32; 4],
     pub atlas: [f32; 4],
+    pub size: [f32; 2],
+    pub radius: [f32; 4],
+    pub node_border: [f32; 4],
+    pub flags: u32,
+}
+
+/// Flags for the texture slice shader, these should match the constants in
+/// `ui_texture_slice.wgsl`.
+pub mod slice_flags {
+    /// The slices are drawn over the border of the node, for a [`BorderImage`].
+    pub const BORDER_IMAGE: u32 = 1;
+    /// The center slice of a border image is drawn over the inside of the node.
+    pub const FILL: u32 = 2;
 }
 
 #[derive(Component)]
@@ -138,6 +151,7 @@ pub fn init_ui_texture_slice_pipeline(mut commands: Commands, asset_server: Res<
 #[derive(Clone, Copy, Hash, PartialEq, Eq)]
 pub struct UiTextureSlicePipelineKey {
     pub hdr: bool,
+    pub anti_alias: bool,
 }
 
 impl SpecializedRenderPipeline for UiTextureSlicePipeline {
@@ -161,9 +175,21 @@ impl SpecializedRenderPipeline for UiTextureSlicePipeline {
                 VertexFormat::Float32x4,
                 // normalized texture atlas rect (left, top, right, bottom)
                 VertexFormat::Float32x4,
+                // node size
+                VertexFormat::Float32x2,
+                // border radius
+                VertexFormat::Float32x4,
+                // border thickness
+                VertexFormat::Float32x4,
+                // flags
+                VertexFormat::Uint32,
             ],
         );
-        let shader_defs = Vec::new();
+        let shader_defs = if key.anti_alias {
+            vec!["ANTI_ALIAS".into()]
+        } else {
+            Vec::new()
+        };
 
         RenderPipelineDescriptor {
             vertex: VertexState {
@@ -206,6 +232,13 @@ pub struct ExtractedUiTextureSlice {
     pub flip_x: bool,
     pub flip_y: bool,
     pub inverse_scale_factor: f32,
+    /// Border radius of the UI node.
+    /// Ordering: top left, top right, bottom right, bottom left.
+    pub border_radius: ResolvedBorderRadius,
+    /// Border thickness of the UI node, only used by border images.
+    /// Ordering: left, top, right, bottom.
+    pub border: BorderRect,
+    pub flags: u32,
     pub main_entity: MainEntity,
     pub render_entity: Entity,
 }
@@ -230,6 +263,17 @@ pub fn extract_ui_texture_slices(
             &ImageNode,
         )>,
     >,
+    border_images_query: Extract<
+        Query<(
+            Entity,
+            &ComputedNode,
+            &UiGlobalTransform,
+            &InheritedVisibility,
+            Option<&CalculatedClip>,
+            &ComputedUiTargetCamera,
+            &BorderImage,
+        )>,
+    >,
     camera_map: Extract<UiCameraMap>,
 ) {
     let mut camera_mapper = camera_map.get_mapper();
@@ -297,6 +341,58 @@ pub fn extract_ui_texture_slices(
             flip_x: image.flip_x,
             flip_y: image.flip_y,
             inverse_scale_factor: uinode.inverse_scale_factor,
+            border_radius: ResolvedBorderRadius::ZERO,
+            border: BorderRect::ZERO,
+            flags: 0,
+            main_entity: entity.into(),
+        });
+    }
+
+    for (entity, uinode, transform, inherited_visibility, clip, camera, border_image) in
+        &border_images_query
+    {
+        // Skip invisible border images
+        if !inherited_visibility.get()
+            || border_image.color.is_fully_transparent()
+            || border_image.image.id() == TRANSPARENT_IMAGE_HANDLE.id()
+            || (uinode.border() == BorderRect::ZERO && !border_image.fill)
+        {
+            continue;
+        }
+
+        let Some(extracted_camera_entity) = camera_mapper.map(camera) else {
+            continue;
+        };
+
+        extracted_ui_slicers.slices.push(ExtractedUiTextureSlice {
+            render_entity: commands.spawn(TemporaryRenderEntity).id(),
+            stack_index: uinode.stack_index,
+            transform: transform.into(),
+            color: border_image.color.into(),
+            rect: Rect {
+                min: Vec2::ZERO,
+                max: uinode.size,
+            },
+            clip: clip.map(|clip| clip.clip),
+            image: border_image.image.id(),
+            extracted_camera_entity,
+            image_scale_mode: SpriteImageMode::Sliced(TextureSlicer {
+                border: border_image.slices,
+                center_scale_mode: border_image.center_scale_mode,
+                sides_scale_mode: border_image.sides_scale_mode,
+                max_corner_scale: 1.,
+            }),
+            atlas_rect: None,
+            flip_x: false,
+            flip_y: false,
+            inverse_scale_factor: uinode.inverse_scale_factor,
+            border_radius: uinode.border_radius(),
+            border: uinode.border(),
+            flags: if border_image.fill {
+                slice_flags::BORDER_IMAGE | slice_flags::FILL
+            } else {
+                slice_flags::BORDER_IMAGE
+            },
             main_entity: entity.into(),
         });
     }
@@ -311,14 +407,14 @@ pub fn queue_ui_slices(
     ui_slicer_pipeline: Res<UiTextureSlicePipeline>,
     mut pipelines: ResMut<SpecializedRenderPipelines<UiTextureSlicePipeline>>,
     mut transparent_render_phases: ResMut<ViewSortedRenderPhases<TransparentUi>>,
-    mut render_views: Query<&UiCameraView, With<ExtractedView>>,
+    mut render_views: Query<(&UiCameraView, Option<&UiAntiAlias>), With<ExtractedView>>,
     camera_views: Query<&ExtractedView>,
     pipeline_cache: Res<PipelineCache>,
     draw_functions: Res<DrawFunctions<TransparentUi>>,
 ) {
     let draw_function = draw_functions.read().id::<DrawUiTextureSlices>();
     for (index, extracted_slicer) in extracted_ui_slicers.slices.iter().enumerate() {
-        let Ok(default_camera_view) =
+        let Ok((default_camera_view, ui_anti_alias)) =
             render_views.get_mut(extracted_slicer.extracted_camera_entity)
         else {
             continue;
@@ -336,14 +432,23 @@ pub fn queue_ui_slices(
         let pipeline = pipelines.specialize(
             &pipeline_cache,
             &ui_slicer_pipeline,
-            UiTextureSlicePipelineKey { hdr: view.hdr },
+            UiTextureSlicePipelineKey {
+                hdr: view.hdr,
+                anti_alias: matches!(ui_anti_alias, None | Some(UiAntiAlias::On)),
+            },
         );
 
+        let stack_z_offset = if extracted_slicer.flags & slice_flags::BORDER_IMAGE != 0 {
+            stack_z_offsets::BORDER_IMAGE
+        } else {
+            stack_z_offsets::IMAGE
+        };
+
         transparent_phase.add(TransparentUi {
             draw_function,
             pipeline,
             entity: (extracted_slicer.render_entity, extracted_slicer.main_entity),
-            sort_key: FloatOrd(extracted_slicer.stack_index as f32 + stack_z_offsets::IMAGE),
+            sort_key: FloatOrd(extracted_slicer.stack_index as f32 + stack_z_offset),
             batch_range: 0..0,
             extra_index: PhaseItemExtraIndex::None,
             index,
@@ -589,11 +694,21 @@ pub fn prepare_ui_slices(
                         atlas.swap(1, 3);
                     }
 
-                    let [slices, border, repeat] = compute_texture_slices(
-                        image_size,
-                        uinode_rect.size() * texture_slices.inverse_scale_factor,
-                        &texture_slices.image_scale_mode,
-                    );
+                    let [slices, border, repeat] =
+                        if texture_slices.flags & slice_flags::BORDER_IMAGE != 0 {
+                            compute_border_image_slices(
+                                image_size,
+                                rect_size,
+                                texture_slices.border,
+                                &texture_slices.image_scale_mode,
+                            )
+                        } else {
+                            compute_texture_slices(
+                                image_size,
+                                uinode_rect.size() * texture_slices.inverse_scale_factor,
+                                &texture_slices.image_scale_mode,
+                            )
+                        };
 
                     for i in 0..4 {
                         ui_meta.vertices.push(UiTextureSliceVertex {
@@ -604,6 +719,15 @@ pub fn prepare_ui_slices(
                             border,
                             repeat,
                             atlas,
+                            size: rect_size.into(),
+                            radius: texture_slices.border_radius.into(),
+                            node_border: [
+                                texture_slices.border.left,
+                                texture_slices.border.top,
+                                texture_slices.border.right,
+                                texture_slices.border.bottom,
+                            ],
+                            flags: texture_slices.flags,
                         });
                     }
 
@@ -791,6 +915,62 @@ fn compute_texture_slices(
     }
 }
 
+/// Computes the slices for a [`BorderImage`], where the target slices are given by the border
+/// thickness of the node instead of the size of the image.
+fn compute_border_image_slices(
+    image_size: Vec2,
+    target_size: Vec2,
+    target_border: BorderRect,
+    image_scale_mode: &SpriteImageMode,
+) -> [[f32; 4]; 3] {
+    let SpriteImageMode::Sliced(TextureSlicer {
+        border: border_rect,
+        center_scale_mode,
+        sides_scale_mode,
+        ..
+    }) = image_scale_mode
+    else {
+        unreachable!("Border images are always sliced")
+    };
+
+    // calculate the normalized extents of the nine-patched image slices
+    let slices = [
+        border_rect.left / image_size.x,
+        border_rect.top / image_size.y,
+        1. - border_rect.right / image_size.x,
+        1. - border_rect.bottom / image_size.y,
+    ];
+
+    // the target slices are the edges of the node's border
+    let border = [
+        target_border.left / target_size.x,
+        target_border.top / target_size.y,
+        1. - target_border.right / target_size.x,
+        1. - target_border.bottom / target_size.y,
+    ];
+
+    // the side slices are scaled to the thickness of the border before they are tiled
+    let scale = |target: f32, image: f32| if 0. < image { target / image } else { 1. };
+    let scale_x = scale(target_border.top, border_rect.top);
+    let scale_y = scale(target_border.left, border_rect.left);
+
+    let image_side_width = image_size.x * (slices[2] - slices[0]) * scale_x;
+    let image_side_height = image_size.y * (slices[3] - slices[1]) * scale_y;
+    let target_side_width = target_size.x * (border[2] - border[0]);
+    let target_side_height = target_size.y * (border[3] - border[1]);
+
+    [
+        slices,
+        border,
+        [
+            compute_tiled_subaxis(image_side_width, target_side_width, sides_scale_mode),
+            compute_tiled_subaxis(image_side_height, target_side_height, sides_scale_mode),
+            compute_tiled_subaxis(image_side_width, target_side_width, center_scale_mode),
+            compute_tiled_subaxis(image_side_height, target_side_height, center_scale_mode),
+        ],
+    ]
+}
+
 fn compute_tiled_axis(tile: bool, image_extent: f32, target_extent: f32, stretch: f32) -> f32 {
     if tile {
         let s = image_extent * stretch;
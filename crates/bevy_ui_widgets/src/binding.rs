@@ -0,0 +1,477 @@
+//! Data binding between UI nodes and the values they display.
+//!
+//! Most widgets in this crate leave it to the app to keep them in sync with the data they show,
+//! which usually means writing a system that copies a value into the widget, and an observer that
+//! copies the edits back. A [`Binding`] does both: it connects a field of a resource or
+//! component, found using reflection, to the text or value of a UI node.
+
+use core::any::TypeId;
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    observer::On,
+    query::{Has, With},
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    resource::Resource,
+    schedule::IntoScheduleConfigs,
+    system::{Commands, Query},
+    world::{EntityRef, World},
+};
+use bevy_log::warn_once;
+use bevy_reflect::{ParsedPath, PartialReflect, Reflect, ReflectPath, TypeRegistry};
+use bevy_ui::{
+    widget::{Text, TextInputValue},
+    Checked, UiSystems,
+};
+
+use crate::{Checkbox, Slider, SliderValue, ValueChange};
+
+/// Keeps the text or value of a UI node in sync with a field of a resource or component.
+///
+/// The field is found using reflection, so the resource or component must be registered with
+/// `#[reflect(Resource)]` or `#[reflect(Component)]`. Whenever the field changes, its value is
+/// written to the [`BindingTarget`] of the node. For editable widgets such as sliders,
+/// checkboxes and text inputs, edits made by the user are written back to the field, unless the
+/// binding is [`one_way`](Self::one_way).
+///
+/// Values are converted between numbers, `bool`s and `String`s when the types differ, so a
+/// number can be shown by a [`Text`] or edited with a text input. Text that can't be parsed is
+/// not written back.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::Reflect;
+/// # use bevy_ui::widget::Text;
+/// # use bevy_ui_widgets::Binding;
+/// #[derive(Resource, Reflect)]
+/// #[reflect(Resource)]
+/// struct Score {
+///     points: u32,
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((Text::default(), Binding::resource::<Score>("points")));
+/// }
+/// ```
+#[derive(Component, Debug)]
+pub struct Binding {
+    source: BindingSource,
+    source_path: ParsedPath,
+    target: Option<BindingTarget>,
+    two_way: bool,
+    last_source: Option<Box<dyn PartialReflect>>,
+    last_target: Option<Box<dyn PartialReflect>>,
+}
+
+impl Binding {
+    /// Binds the node to the field at `path` in the resource `R`, such as `"volume"` or
+    /// `"players[0].name"`. An empty path binds the whole resource.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `path` is not a valid reflection path.
+    pub fn resource<R: Resource + Reflect>(path: &str) -> Self {
+        Self::new(BindingSource::Resource(TypeId::of::<R>()), path)
+    }
+
+    /// Binds the node to the field at `path` in the component `C` of `entity`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `path` is not a valid reflection path.
+    pub fn component<C: Component + Reflect>(entity: Entity, path: &str) -> Self {
+        Self::new(BindingSource::Component(entity, TypeId::of::<C>()), path)
+    }
+
+    fn new(source: BindingSource, path: &str) -> Self {
+        Self {
+            source,
+            source_path: parse_path(path),
+            target: None,
+            two_way: true,
+            last_source: None,
+            last_target: None,
+        }
+    }
+
+    /// Sets what the binding writes to. By default, this is chosen from the components of the
+    /// node: a [`Slider`], [`Checkbox`], text input, or otherwise the node's [`Text`].
+    pub fn with_target(mut self, target: BindingTarget) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Only writes the value of the field to the node, ignoring edits made to the node.
+    pub fn one_way(mut self) -> Self {
+        self.two_way = false;
+        self
+    }
+
+    /// Returns `true` if the binding writes to `target`.
+    fn targets(&self, target: &BindingTarget, inferred: bool) -> bool {
+        match &self.target {
+            Some(own_target) => own_target == target,
+            None => inferred,
+        }
+    }
+}
+
+/// Where the value of a [`Binding`] comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingSource {
+    /// A resource, with the given type.
+    Resource(TypeId),
+    /// A component of an entity, with the given type.
+    Component(Entity, TypeId),
+}
+
+impl BindingSource {
+    fn reflect<'w>(&self, world: &'w World, registry: &TypeRegistry) -> Option<&'w dyn Reflect> {
+        match *self {
+            BindingSource::Resource(type_id) => registry
+                .get_type_data::<ReflectResource>(type_id)?
+                .reflect(world)
+                .ok(),
+            BindingSource::Component(entity, type_id) => registry
+                .get_type_data::<ReflectComponent>(type_id)?
+                .reflect(world.get_entity(entity).ok()?),
+        }
+    }
+
+    fn write(
+        &self,
+        world: &mut World,
+        registry: &TypeRegistry,
+        path: &ParsedPath,
+        value: &dyn PartialReflect,
+    ) {
+        let source = match *self {
+            BindingSource::Resource(type_id) => registry
+                .get_type_data::<ReflectResource>(type_id)
+                .and_then(|reflect_resource| reflect_resource.reflect_mut(world).ok()),
+            BindingSource::Component(entity, type_id) => registry
+                .get_type_data::<ReflectComponent>(type_id)
+                .zip(world.get_entity_mut(entity).ok())
+                .and_then(|(reflect_component, entity)| reflect_component.reflect_mut(entity)),
+        };
+        if let Some(mut source) = source
+            && let Ok(field) = path.reflect_element_mut(source.as_partial_reflect_mut())
+        {
+            assign(field, value);
+        }
+    }
+}
+
+/// What a [`Binding`] writes its value to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BindingTarget {
+    /// The [`Text`] of the node. Edits to the text are not written back.
+    Text,
+    /// The [`TextInputValue`] of a text input.
+    TextInput,
+    /// The [`SliderValue`] of a [`Slider`].
+    Slider,
+    /// Whether a [`Checkbox`] is [`Checked`].
+    Checkbox,
+    /// A field of a component of the node, found using reflection.
+    Component {
+        /// The type of the component.
+        type_id: TypeId,
+        /// The path to the field in the component.
+        path: ParsedPath,
+    },
+}
+
+impl BindingTarget {
+    /// Targets the field at `path` in the component `C` of the node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `path` is not a valid reflection path.
+    pub fn component<C: Component + Reflect>(path: &str) -> Self {
+        BindingTarget::Component {
+            type_id: TypeId::of::<C>(),
+            path: parse_path(path),
+        }
+    }
+
+    fn infer(entity: EntityRef) -> Self {
+        if entity.contains::<Slider>() {
+            BindingTarget::Slider
+        } else if entity.contains::<Checkbox>() {
+            BindingTarget::Checkbox
+        } else if entity.contains::<TextInputValue>() {
+            BindingTarget::TextInput
+        } else {
+            BindingTarget::Text
+        }
+    }
+
+    fn read(&self, entity: EntityRef, registry: &TypeRegistry) -> Option<Box<dyn PartialReflect>> {
+        match self {
+            BindingTarget::Text => Some(Box::new(entity.get::<Text>()?.0.clone())),
+            BindingTarget::TextInput => Some(Box::new(entity.get::<TextInputValue>()?.0.clone())),
+            BindingTarget::Slider => Some(Box::new(entity.get::<SliderValue>()?.0)),
+            BindingTarget::Checkbox => Some(Box::new(entity.contains::<Checked>())),
+            BindingTarget::Component { type_id, path } => {
+                let component = registry
+                    .get_type_data::<ReflectComponent>(*type_id)?
+                    .reflect(entity)?;
+                read_path(component, path)
+            }
+        }
+    }
+
+    fn write(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        registry: &TypeRegistry,
+        value: &dyn PartialReflect,
+    ) {
+        let Ok(mut entity) = world.get_entity_mut(entity) else {
+            return;
+        };
+        match self {
+            BindingTarget::Text => {
+                if let Some(mut text) = entity.get_mut::<Text>() {
+                    let mut string = text.0.clone();
+                    if assign(&mut string, value) && text.0 != string {
+                        text.0 = string;
+                    }
+                }
+            }
+            BindingTarget::TextInput => {
+                if let Some(mut text_input_value) = entity.get_mut::<TextInputValue>() {
+                    let mut string = text_input_value.0.clone();
+                    if assign(&mut string, value) && text_input_value.0 != string {
+                        text_input_value.0 = string;
+                    }
+                }
+            }
+            BindingTarget::Slider => {
+                let current = entity
+                    .get::<SliderValue>()
+                    .map(|slider_value| slider_value.0);
+                let mut new_value = current.unwrap_or_default();
+                if assign(&mut new_value, value) && current != Some(new_value) {
+                    entity.insert(SliderValue(new_value));
+                }
+            }
+            BindingTarget::Checkbox => {
+                let current = entity.contains::<Checked>();
+                let mut checked = current;
+                if assign(&mut checked, value) && checked != current {
+                    if checked {
+                        entity.insert(Checked);
+                    } else {
+                        entity.remove::<Checked>();
+                    }
+                }
+            }
+            BindingTarget::Component { type_id, path } => {
+                if let Some(reflect_component) =
+                    registry.get_type_data::<ReflectComponent>(*type_id)
+                    && let Some(mut component) = reflect_component.reflect_mut(&mut entity)
+                    && let Ok(field) = path.reflect_element_mut(component.as_partial_reflect_mut())
+                {
+                    assign(field, value);
+                }
+            }
+        }
+    }
+}
+
+fn parse_path(path: &str) -> ParsedPath {
+    ParsedPath::parse(path)
+        .unwrap_or_else(|error| panic!("invalid binding path \"{path}\": {error}"))
+}
+
+/// Returns a copy of the value at `path` in `root`.
+fn read_path(root: &dyn Reflect, path: &ParsedPath) -> Option<Box<dyn PartialReflect>> {
+    match path.reflect_element(root.as_partial_reflect()) {
+        Ok(value) => Some(
+            value
+                .reflect_clone()
+                .map(|value| value.into_partial_reflect())
+                .unwrap_or_else(|_| value.to_dynamic()),
+        ),
+        Err(error) => {
+            warn_once!(
+                "Binding path \"{path}\" not found in {}: {error}",
+                root.reflect_short_type_path()
+            );
+            None
+        }
+    }
+}
+
+fn is_same(last: Option<&dyn PartialReflect>, value: &dyn PartialReflect) -> bool {
+    last.is_some_and(|last| last.reflect_partial_eq(value) == Some(true))
+}
+
+/// Writes `value` to `target`, converting it if their types differ. Returns `true` if the value
+/// could be written.
+fn assign(target: &mut dyn PartialReflect, value: &dyn PartialReflect) -> bool {
+    if target.try_apply(value).is_ok() {
+        return true;
+    }
+    if let Some(string) = target.try_downcast_mut::<String>() {
+        return match to_string(value) {
+            Some(value) => {
+                *string = value;
+                true
+            }
+            None => false,
+        };
+    }
+    if let Some(string) = value.try_downcast_ref::<String>() {
+        return parse_into(target, string.trim());
+    }
+    false
+}
+
+macro_rules! impl_conversions {
+    ($($ty:ty),*) => {
+        /// Formats `value` if it is a primitive type.
+        fn to_string(value: &dyn PartialReflect) -> Option<String> {
+            $(
+                if let Some(value) = value.try_downcast_ref::<$ty>() {
+                    return Some(value.to_string());
+                }
+            )*
+            None
+        }
+
+        /// Parses `string` into `target` if it is a primitive type.
+        fn parse_into(target: &mut dyn PartialReflect, string: &str) -> bool {
+            $(
+                if let Some(target) = target.try_downcast_mut::<$ty>() {
+                    return string.parse().map(|value| *target = value).is_ok();
+                }
+            )*
+            false
+        }
+    };
+}
+
+impl_conversions!(
+    f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, bool, char
+);
+
+/// Synchronizes every [`Binding`] with its source.
+///
+/// When the source has changed since the last update, its value is written to the target.
+/// Otherwise, if the binding is two-way and the target has changed, the target's value is
+/// written back to the source.
+pub fn update_bindings(world: &mut World) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let entities = world
+        .query_filtered::<Entity, With<Binding>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    for entity in entities {
+        update_binding(world, &registry, entity);
+    }
+}
+
+fn update_binding(world: &mut World, registry: &TypeRegistry, entity: Entity) {
+    let Ok(entity_ref) = world.get_entity(entity) else {
+        return;
+    };
+    let Some(binding) = entity_ref.get::<Binding>() else {
+        return;
+    };
+    let target = binding
+        .target
+        .clone()
+        .unwrap_or_else(|| BindingTarget::infer(entity_ref));
+    let Some(source_value) = binding
+        .source
+        .reflect(world, registry)
+        .and_then(|source| read_path(source, &binding.source_path))
+    else {
+        return;
+    };
+    let target_value = target.read(entity_ref, registry);
+
+    let source = binding.source;
+    if !is_same(binding.last_source.as_deref(), &*source_value) {
+        target.write(world, entity, registry, &*source_value);
+    } else if binding.two_way
+        && target != BindingTarget::Text
+        && let Some(target_value) = target_value
+        && !is_same(binding.last_target.as_deref(), &*target_value)
+    {
+        let path = binding.source_path.clone();
+        source.write(world, registry, &path, &*target_value);
+    } else {
+        return;
+    }
+
+    // Remember the values after the update, so that only new changes are propagated.
+    let Ok(entity_ref) = world.get_entity(entity) else {
+        return;
+    };
+    let Some(binding) = entity_ref.get::<Binding>() else {
+        return;
+    };
+    let last_source = source
+        .reflect(world, registry)
+        .and_then(|source| read_path(source, &binding.source_path));
+    let last_target = target.read(entity_ref, registry);
+    if let Some(mut binding) = world.get_mut::<Binding>(entity) {
+        binding.last_source = last_source;
+        binding.last_target = last_target;
+    }
+}
+
+/// Updates the value of bound sliders when they are dragged, so that the edit is written back to
+/// the source.
+fn binding_on_slider_change(
+    value_change: On<ValueChange<f32>>,
+    q_binding: Query<(&Binding, Has<Slider>)>,
+    mut commands: Commands,
+) {
+    if let Ok((binding, is_slider)) = q_binding.get(value_change.source)
+        && binding.two_way
+        && binding.targets(&BindingTarget::Slider, is_slider)
+    {
+        commands
+            .entity(value_change.source)
+            .insert(SliderValue(value_change.value));
+    }
+}
+
+/// Checks or unchecks bound checkboxes when they are clicked, so that the edit is written back to
+/// the source.
+fn binding_on_checkbox_change(
+    value_change: On<ValueChange<bool>>,
+    q_binding: Query<(&Binding, Has<Checkbox>)>,
+    mut commands: Commands,
+) {
+    if let Ok((binding, is_checkbox)) = q_binding.get(value_change.source)
+        && binding.two_way
+        && binding.targets(&BindingTarget::Checkbox, is_checkbox)
+    {
+        if value_change.value {
+            commands.entity(value_change.source).insert(Checked);
+        } else {
+            commands.entity(value_change.source).remove::<Checked>();
+        }
+    }
+}
+
+/// Plugin that adds the systems and observers for [`Binding`]s.
+pub struct BindingPlugin;
+
+impl Plugin for BindingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, update_bindings.before(UiSystems::Prepare))
+            .add_observer(binding_on_slider_change)
+            .add_observer(binding_on_checkbox_change);
+    }
+}
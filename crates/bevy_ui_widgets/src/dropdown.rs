@@ -0,0 +1,219 @@
+use accesskit::Role;
+use bevy_a11y::AccessibilityNode;
+use bevy_app::{App, Plugin};
+use bevy_camera::visibility::Visibility;
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    hierarchy::{ChildOf, Children},
+    lifecycle::Insert,
+    observer::On,
+    query::With,
+    system::{Commands, Query, ResMut},
+    world::DeferredWorld,
+};
+use bevy_input_focus::{tab_navigation::TabIndex, InputFocus};
+use bevy_picking::hover::Hovered;
+use bevy_ui::{
+    widget::Text, Checked, FlexDirection, GlobalZIndex, Node, OverrideClip, PositionType, Val,
+};
+
+use crate::{
+    popover::{Popover, PopoverAlign, PopoverPlacement, PopoverSide},
+    Activate, MenuAction, MenuButton, MenuEvent, MenuItem, MenuPopup, ValueChange,
+};
+
+/// Headless dropdown widget, which lets the user pick one of a list of options.
+///
+/// A dropdown is a [`MenuButton`] which opens a popup menu listing its options when pressed. The
+/// popup is spawned as a child of the dropdown, marked with [`DropdownPopup`], and each option is
+/// a [`MenuItem`] marked with [`DropdownOption`]. The option that is currently selected is marked
+/// with [`Checked`]. These entities have no styling; stylists can style them by watching for the
+/// marker components being added.
+///
+/// The index of the selected option is stored in [`DropdownValue`]. Picking an option emits a
+/// [`ValueChange<usize>`] event with the index of the option, but doesn't change the value: that
+/// is left to the app, or to the [`dropdown_self_update`] observer.
+///
+/// The dropdown does not display the selected option itself, that is the responsibility of the
+/// stylist.
+#[derive(Component, Debug, Default, Clone)]
+#[require(
+    AccessibilityNode(accesskit::Node::new(Role::ComboBox)),
+    MenuButton,
+    DropdownValue
+)]
+pub struct Dropdown {
+    /// The labels of the options that can be picked.
+    pub options: Vec<String>,
+}
+
+impl Dropdown {
+    /// Creates a dropdown with the given option labels.
+    pub fn new<S: Into<String>>(options: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            options: options.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Returns the label of the option at `index`, if there is one.
+    pub fn option(&self, index: usize) -> Option<&str> {
+        self.options.get(index).map(String::as_str)
+    }
+}
+
+/// The index of the option that is selected in a [`Dropdown`].
+#[derive(Component, Debug, Default, PartialEq, Clone, Copy)]
+#[component(immutable)]
+pub struct DropdownValue(pub usize);
+
+/// Marker component for the popup menu of a [`Dropdown`].
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct DropdownPopup;
+
+/// Component for an option in the popup menu of a [`Dropdown`], which stores the index of the
+/// option.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct DropdownOption(pub usize);
+
+fn spawn_dropdown_popup(
+    dropdown_ent: Entity,
+    dropdown: &Dropdown,
+    value: usize,
+    commands: &mut Commands,
+) {
+    commands.entity(dropdown_ent).with_children(|parent| {
+        parent
+            .spawn((
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    min_width: Val::Percent(100.),
+                    position_type: PositionType::Absolute,
+                    ..Default::default()
+                },
+                DropdownPopup,
+                MenuPopup::default(),
+                // Will be visible after positioning.
+                Visibility::Hidden,
+                GlobalZIndex(100),
+                Popover {
+                    positions: vec![
+                        PopoverPlacement {
+                            side: PopoverSide::Bottom,
+                            align: PopoverAlign::Start,
+                            gap: 2.0,
+                        },
+                        PopoverPlacement {
+                            side: PopoverSide::Top,
+                            align: PopoverAlign::Start,
+                            gap: 2.0,
+                        },
+                    ],
+                    window_margin: 10.0,
+                },
+                OverrideClip,
+            ))
+            .with_children(|popup| {
+                for (index, label) in dropdown.options.iter().enumerate() {
+                    let mut option = popup.spawn((
+                        Node::default(),
+                        MenuItem,
+                        DropdownOption(index),
+                        Hovered::default(),
+                        TabIndex(0),
+                        Text::new(label.clone()),
+                    ));
+                    if index == value {
+                        option.insert(Checked);
+                    }
+                }
+            });
+    });
+}
+
+fn dropdown_on_menu_event(
+    mut menu_event: On<MenuEvent>,
+    q_dropdown: Query<(&Dropdown, &DropdownValue, Option<&Children>)>,
+    q_popup: Query<(), With<DropdownPopup>>,
+    mut focus: ResMut<InputFocus>,
+    mut commands: Commands,
+) {
+    let dropdown_ent = menu_event.source;
+    let Ok((dropdown, value, children)) = q_dropdown.get(dropdown_ent) else {
+        return;
+    };
+    menu_event.propagate(false);
+
+    let popup =
+        children.and_then(|children| children.iter().find(|child| q_popup.contains(*child)));
+    match menu_event.action {
+        MenuAction::Open => {
+            if popup.is_none() {
+                spawn_dropdown_popup(dropdown_ent, dropdown, value.0, &mut commands);
+            }
+        }
+        MenuAction::Toggle => match popup {
+            Some(popup) => commands.entity(popup).despawn(),
+            None => spawn_dropdown_popup(dropdown_ent, dropdown, value.0, &mut commands),
+        },
+        MenuAction::Close | MenuAction::CloseAll => {
+            if let Some(popup) = popup {
+                commands.entity(popup).despawn();
+            }
+        }
+        MenuAction::FocusRoot => {
+            focus.0 = Some(dropdown_ent);
+        }
+    }
+}
+
+fn dropdown_option_on_activate(
+    activate: On<Activate>,
+    q_option: Query<(&DropdownOption, &ChildOf)>,
+    q_popup: Query<&ChildOf, With<DropdownPopup>>,
+    mut commands: Commands,
+) {
+    if let Ok((option, ChildOf(popup))) = q_option.get(activate.entity)
+        && let Ok(ChildOf(dropdown)) = q_popup.get(*popup)
+    {
+        commands.trigger(ValueChange {
+            source: *dropdown,
+            value: option.0,
+        });
+    }
+}
+
+fn dropdown_on_insert_value(insert: On<Insert, DropdownValue>, mut world: DeferredWorld) {
+    let mut entity = world.entity_mut(insert.entity);
+    let value = entity.get::<DropdownValue>().unwrap().0;
+    let label = entity
+        .get::<Dropdown>()
+        .and_then(|dropdown| dropdown.option(value))
+        .map(ToOwned::to_owned);
+    if let Some(mut accessibility) = entity.get_mut::<AccessibilityNode>() {
+        match label {
+            Some(label) => accessibility.set_value(label),
+            None => accessibility.clear_value(),
+        }
+    }
+}
+
+/// Observer function which updates the dropdown value in response to a [`ValueChange`] event.
+/// This can be used to make the dropdown automatically update its own state when an option is
+/// picked, as opposed to managing the value externally.
+pub fn dropdown_self_update(value_change: On<ValueChange<usize>>, mut commands: Commands) {
+    commands
+        .entity(value_change.source)
+        .insert(DropdownValue(value_change.value));
+}
+
+/// Plugin that adds the observers for the [`Dropdown`] widget.
+pub struct DropdownPlugin;
+
+impl Plugin for DropdownPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(dropdown_on_menu_event)
+            .add_observer(dropdown_option_on_activate)
+            .add_observer(dropdown_on_insert_value);
+    }
+}
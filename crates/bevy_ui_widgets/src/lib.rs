@@ -1,4 +1,5 @@
-//! This crate provides a set of standard widgets for Bevy UI, such as buttons, checkboxes, and sliders.
+//! This crate provides a set of standard widgets for Bevy UI, such as buttons, checkboxes, sliders
+//! and text inputs.
 //! These widgets have no inherent styling, it's the responsibility of the user to add styling
 //! appropriate for their game or application.
 //!
@@ -17,23 +18,38 @@
 //! state (as well as any other related game state) in response to a change event emitted by the
 //! widget. The primary motivation for this is to avoid two-way data binding in scenarios where the
 //! user interface is showing a live view of dynamic data coming from deeper within the game engine.
+//!
+//! For the common case of a widget that shows and edits a single field of a resource or component,
+//! a [`Binding`] can be used instead of writing the state management by hand.
 
+mod binding;
 mod button;
 mod checkbox;
+mod dropdown;
 mod menu;
 mod observe;
 pub mod popover;
+mod progress_bar;
 mod radio;
+mod scroll_view;
 mod scrollbar;
 mod slider;
+mod text_input;
+mod window_hit_test;
 
+pub use binding::*;
 pub use button::*;
 pub use checkbox::*;
+pub use dropdown::*;
 pub use menu::*;
 pub use observe::*;
+pub use progress_bar::*;
 pub use radio::*;
+pub use scroll_view::*;
 pub use scrollbar::*;
 pub use slider::*;
+pub use text_input::*;
+pub use window_hit_test::*;
 
 use bevy_app::{PluginGroup, PluginGroupBuilder};
 use bevy_ecs::{entity::Entity, event::EntityEvent};
@@ -48,12 +64,18 @@ impl PluginGroup for UiWidgetsPlugins {
     fn build(self) -> PluginGroupBuilder {
         PluginGroupBuilder::start::<Self>()
             .add(PopoverPlugin)
+            .add(BindingPlugin)
             .add(ButtonPlugin)
             .add(CheckboxPlugin)
+            .add(DropdownPlugin)
             .add(MenuPlugin)
+            .add(ProgressBarPlugin)
             .add(RadioGroupPlugin)
+            .add(ScrollViewPlugin)
             .add(ScrollbarPlugin)
             .add(SliderPlugin)
+            .add(TextInputPlugin)
+            .add(WindowHitTestPlugin)
     }
 }
 
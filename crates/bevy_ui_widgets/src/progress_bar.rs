@@ -0,0 +1,90 @@
+use accesskit::Role;
+use bevy_a11y::AccessibilityNode;
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    hierarchy::Children,
+    query::{Changed, With},
+    schedule::IntoScheduleConfigs,
+    system::Query,
+};
+use bevy_ui::{Node, UiSystems, Val};
+
+/// Headless progress bar widget, which shows how much of a task has been completed.
+///
+/// The progress is a fraction between 0 and 1, or `None` if the amount of progress is unknown, in
+/// which case the progress bar is "indeterminate". The widget sets the width of descendants marked
+/// with [`ProgressBarFill`] to the percentage of the task that is complete. Everything else is
+/// left to the stylist, including how an indeterminate progress bar is animated.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+#[require(AccessibilityNode(accesskit::Node::new(Role::ProgressIndicator)))]
+pub struct ProgressBar {
+    /// The fraction of the task that is complete, from 0 to 1, or `None` if it is unknown.
+    pub progress: Option<f32>,
+}
+
+impl ProgressBar {
+    /// Creates a progress bar showing that `progress`, from 0 to 1, of the task is complete.
+    pub fn new(progress: f32) -> Self {
+        Self {
+            progress: Some(progress),
+        }
+    }
+
+    /// Creates a progress bar for a task whose progress is unknown.
+    pub fn indeterminate() -> Self {
+        Self { progress: None }
+    }
+}
+
+/// Marker component for the descendant of a [`ProgressBar`] which shows the completed part of the
+/// task. Its width is set to the percentage of the task that is complete.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct ProgressBarFill;
+
+fn update_progress_bars(
+    mut q_progress_bars: Query<
+        (Entity, &ProgressBar, Option<&mut AccessibilityNode>),
+        Changed<ProgressBar>,
+    >,
+    q_children: Query<&Children>,
+    mut q_fills: Query<&mut Node, With<ProgressBarFill>>,
+) {
+    for (progress_bar_ent, progress_bar, accessibility) in q_progress_bars.iter_mut() {
+        let progress = progress_bar.progress.map(|progress| progress.clamp(0., 1.));
+
+        if let Some(mut accessibility) = accessibility {
+            match progress {
+                Some(progress) => {
+                    accessibility.set_min_numeric_value(0.);
+                    accessibility.set_max_numeric_value(1.);
+                    accessibility.set_numeric_value(progress.into());
+                }
+                None => accessibility.clear_numeric_value(),
+            }
+        }
+
+        // Indeterminate progress bars are animated by the stylist.
+        let Some(progress) = progress else {
+            continue;
+        };
+        let width = Val::Percent(progress * 100.);
+        for child in q_children.iter_descendants(progress_bar_ent) {
+            if let Ok(mut node) = q_fills.get_mut(child)
+                && node.width != width
+            {
+                node.width = width;
+            }
+        }
+    }
+}
+
+/// Plugin that adds the systems for the [`ProgressBar`] widget.
+pub struct ProgressBarPlugin;
+
+impl Plugin for ProgressBarPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, update_progress_bars.in_set(UiSystems::Prepare));
+    }
+}
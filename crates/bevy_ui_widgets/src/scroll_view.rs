@@ -0,0 +1,391 @@
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::{
+    change_detection::DetectChanges,
+    component::Component,
+    entity::Entity,
+    event::EntityEvent,
+    hierarchy::ChildOf,
+    observer::On,
+    query::With,
+    reflect::ReflectComponent,
+    schedule::IntoScheduleConfigs,
+    system::{Commands, Query, Res},
+};
+use bevy_input::mouse::MouseScrollUnit;
+use bevy_input_focus::{InputFocus, InputFocusVisible};
+use bevy_math::{ops, Rect, Vec2};
+use bevy_picking::events::{Cancel, Drag, DragEnd, DragStart, Pointer, Scroll};
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
+use bevy_time::Time;
+use bevy_ui::{
+    ComputedNode, Node, OverflowAxis, ScrollPosition, UiGlobalTransform, UiScale, UiSystems,
+};
+
+/// A headless scrollable container, which scrolls its content in response to the mouse wheel and
+/// touch gestures.
+///
+/// The scroll view must be a [`Node`] with [`OverflowAxis::Scroll`] on the axes that can scroll.
+/// Mouse wheel input over the view scrolls it, unless it's already scrolled all the way in that
+/// direction, in which case the input is passed on to the enclosing scroll view. Dragging the
+/// content with a touch scrolls it, and flicking it keeps it scrolling with a velocity that
+/// decays over time.
+///
+/// Scroll views don't render scrollbars of their own. To add draggable scrollbars, spawn
+/// [`Scrollbar`](crate::Scrollbar)s targeting the scroll view.
+///
+/// A descendant of a scroll view can be scrolled into view by triggering [`ScrollIntoView`] on it.
+/// If [`follow_focus`](Self::follow_focus) is set, this happens automatically when a descendant
+/// receives keyboard focus.
+#[derive(Component, Debug, Clone, Reflect)]
+#[require(ScrollPosition, ScrollViewState)]
+#[reflect(Component, Default, Clone)]
+pub struct ScrollView {
+    /// The distance to scroll for each line of mouse wheel movement, in logical pixels.
+    pub line_height: f32,
+    /// How quickly kinetic scrolling slows down after a flick. The scrolling velocity decays by a
+    /// factor of `e` every `1 / deceleration` seconds.
+    pub deceleration: f32,
+    /// Whether to scroll descendants into view when they receive keyboard focus.
+    pub follow_focus: bool,
+}
+
+impl Default for ScrollView {
+    fn default() -> Self {
+        Self {
+            line_height: 20.,
+            deceleration: 4.,
+            follow_focus: true,
+        }
+    }
+}
+
+/// Component used to manage the kinetic scrolling state of a [`ScrollView`].
+#[derive(Component, Debug, Default, Clone, Reflect)]
+#[reflect(Component, Default, Clone)]
+pub struct ScrollViewState {
+    /// The current scrolling velocity, in logical pixels per second.
+    pub velocity: Vec2,
+    /// Whether the content is currently being dragged by a touch.
+    pub dragging: bool,
+}
+
+/// Request that the enclosing [`ScrollView`]s of an entity scroll so that the entity is visible.
+///
+/// If the entity is larger than a scroll view, its top-left corner is scrolled into view.
+#[derive(Clone, Debug, PartialEq, EntityEvent)]
+pub struct ScrollIntoView {
+    /// The entity to scroll into view.
+    pub entity: Entity,
+}
+
+/// Kinetic scrolling stops once it is slower than this, in logical pixels per second.
+const MIN_KINETIC_SPEED: f32 = 5.;
+
+/// Returns the maximum scroll position of a scroll view, in logical pixels.
+fn max_scroll_position(node: &ComputedNode) -> Vec2 {
+    let visible_size = (node.size() - node.scrollbar_size) * node.inverse_scale_factor;
+    let content_size = node.content_size() * node.inverse_scale_factor;
+    (content_size - visible_size).max(Vec2::ZERO)
+}
+
+/// Scrolls along the axes of `node` that can scroll, clamping the scroll position to the content.
+///
+/// Returns the part of `delta` that wasn't consumed, because the view can't scroll on that axis
+/// or is already scrolled all the way in that direction.
+fn scroll_by(
+    scroll_position: &mut ScrollPosition,
+    node: &Node,
+    computed_node: &ComputedNode,
+    mut delta: Vec2,
+) -> Vec2 {
+    let max_offset = max_scroll_position(computed_node);
+
+    if node.overflow.x == OverflowAxis::Scroll && delta.x != 0. {
+        let x = (scroll_position.x + delta.x).clamp(0., max_offset.x);
+        if x != scroll_position.x {
+            scroll_position.x = x;
+            delta.x = 0.;
+        }
+    }
+
+    if node.overflow.y == OverflowAxis::Scroll && delta.y != 0. {
+        let y = (scroll_position.y + delta.y).clamp(0., max_offset.y);
+        if y != scroll_position.y {
+            scroll_position.y = y;
+            delta.y = 0.;
+        }
+    }
+
+    delta
+}
+
+/// Returns the distance to scroll along one axis so that the range from `target_min` to
+/// `target_max` is visible, preferring the start of the range if it doesn't fit.
+fn scroll_offset(target_min: f32, target_max: f32, visible_min: f32, visible_max: f32) -> f32 {
+    if target_min < visible_min {
+        target_min - visible_min
+    } else if target_max > visible_max {
+        (target_max - visible_max).min(target_min - visible_min)
+    } else {
+        0.
+    }
+}
+
+fn scroll_view_on_scroll(
+    mut scroll: On<Pointer<Scroll>>,
+    mut q_scroll_view: Query<(
+        &ScrollView,
+        &mut ScrollPosition,
+        &mut ScrollViewState,
+        &Node,
+        &ComputedNode,
+    )>,
+) {
+    let Ok((scroll_view, mut scroll_position, mut state, node, computed_node)) =
+        q_scroll_view.get_mut(scroll.entity)
+    else {
+        return;
+    };
+
+    let mut delta = -Vec2::new(scroll.x, scroll.y);
+    if scroll.unit == MouseScrollUnit::Line {
+        delta *= scroll_view.line_height;
+    }
+
+    // A vertical mouse wheel scrolls views that can only scroll horizontally.
+    let remaining = if node.overflow.x == OverflowAxis::Scroll
+        && node.overflow.y != OverflowAxis::Scroll
+        && delta.x == 0.
+    {
+        let remaining = scroll_by(
+            &mut scroll_position,
+            node,
+            computed_node,
+            Vec2::new(delta.y, 0.),
+        );
+        if remaining == Vec2::ZERO {
+            Vec2::ZERO
+        } else {
+            delta
+        }
+    } else {
+        scroll_by(&mut scroll_position, node, computed_node, delta)
+    };
+    if remaining != delta {
+        state.velocity = Vec2::ZERO;
+    }
+
+    // Pass the rest of the scroll on to the enclosing scroll views.
+    if remaining == Vec2::ZERO {
+        scroll.propagate(false);
+    } else {
+        let remaining = match scroll.unit {
+            MouseScrollUnit::Line => remaining / scroll_view.line_height,
+            MouseScrollUnit::Pixel => remaining,
+        };
+        scroll.event.x = -remaining.x;
+        scroll.event.y = -remaining.y;
+    }
+}
+
+fn scroll_view_on_drag_start(
+    mut drag_start: On<Pointer<DragStart>>,
+    mut q_scroll_view: Query<&mut ScrollViewState, With<ScrollView>>,
+) {
+    if !drag_start.pointer_id.is_touch() {
+        return;
+    }
+    if let Ok(mut state) = q_scroll_view.get_mut(drag_start.entity) {
+        drag_start.propagate(false);
+        state.dragging = true;
+        state.velocity = Vec2::ZERO;
+    }
+}
+
+fn scroll_view_on_drag(
+    mut drag: On<Pointer<Drag>>,
+    mut q_scroll_view: Query<(
+        &mut ScrollPosition,
+        &mut ScrollViewState,
+        &Node,
+        &ComputedNode,
+    )>,
+    ui_scale: Res<UiScale>,
+    time: Res<Time>,
+) {
+    let Ok((mut scroll_position, mut state, node, computed_node)) =
+        q_scroll_view.get_mut(drag.entity)
+    else {
+        return;
+    };
+    if !state.dragging {
+        return;
+    }
+    drag.propagate(false);
+
+    // The content follows the touch.
+    let delta = -drag.delta / ui_scale.0;
+    scroll_by(&mut scroll_position, node, computed_node, delta);
+
+    // Smooth the velocity over the last few drag events, so that the flick velocity isn't
+    // dominated by the final event.
+    let delta_secs = time.delta_secs();
+    if delta_secs > 0. {
+        state.velocity = state.velocity.lerp(delta / delta_secs, 0.5);
+    }
+}
+
+fn scroll_view_on_drag_end(
+    mut drag_end: On<Pointer<DragEnd>>,
+    mut q_scroll_view: Query<&mut ScrollViewState, With<ScrollView>>,
+) {
+    if let Ok(mut state) = q_scroll_view.get_mut(drag_end.entity)
+        && state.dragging
+    {
+        drag_end.propagate(false);
+        // Keep the velocity, so that the view continues scrolling.
+        state.dragging = false;
+    }
+}
+
+fn scroll_view_on_drag_cancel(
+    mut cancel: On<Pointer<Cancel>>,
+    mut q_scroll_view: Query<&mut ScrollViewState, With<ScrollView>>,
+) {
+    if let Ok(mut state) = q_scroll_view.get_mut(cancel.entity)
+        && state.dragging
+    {
+        cancel.propagate(false);
+        state.dragging = false;
+        state.velocity = Vec2::ZERO;
+    }
+}
+
+fn scroll_view_on_scroll_into_view(
+    scroll_into_view: On<ScrollIntoView>,
+    q_node: Query<(&ComputedNode, &UiGlobalTransform)>,
+    q_parent: Query<&ChildOf>,
+    mut q_scroll_view: Query<(&mut ScrollPosition, &mut ScrollViewState, &Node), With<ScrollView>>,
+) {
+    let Ok((node, transform)) = q_node.get(scroll_into_view.entity) else {
+        return;
+    };
+    // The target's bounds, in physical pixels.
+    let mut target = Rect::from_center_size(transform.translation, node.size());
+
+    for ancestor in q_parent.iter_ancestors(scroll_into_view.entity) {
+        let Ok((mut scroll_position, mut state, scroll_node)) = q_scroll_view.get_mut(ancestor)
+        else {
+            continue;
+        };
+        let Ok((computed_node, transform)) = q_node.get(ancestor) else {
+            continue;
+        };
+
+        // The visible region of the scroll view, inside its border and scrollbars.
+        let border = computed_node.border();
+        let mut visible = Rect::from_center_size(transform.translation, computed_node.size());
+        visible.min += Vec2::new(border.left, border.top);
+        visible.max -= Vec2::new(border.right, border.bottom) + computed_node.scrollbar_size;
+
+        // The distance to scroll on each axis, in physical pixels.
+        let offset = Vec2::new(
+            scroll_offset(target.min.x, target.max.x, visible.min.x, visible.max.x),
+            scroll_offset(target.min.y, target.max.y, visible.min.y, visible.max.y),
+        );
+
+        let max_offset = max_scroll_position(computed_node);
+        let old_position = scroll_position.0;
+        if scroll_node.overflow.x == OverflowAxis::Scroll {
+            scroll_position.x = (scroll_position.x + offset.x * computed_node.inverse_scale_factor)
+                .clamp(0., max_offset.x);
+        }
+        if scroll_node.overflow.y == OverflowAxis::Scroll {
+            scroll_position.y = (scroll_position.y + offset.y * computed_node.inverse_scale_factor)
+                .clamp(0., max_offset.y);
+        }
+        state.velocity = Vec2::ZERO;
+
+        // Move the target by the amount scrolled, so that the enclosing scroll views see where it
+        // will be, and clip it to this view.
+        let scrolled = (scroll_position.0 - old_position) / computed_node.inverse_scale_factor;
+        target.min -= scrolled;
+        target.max -= scrolled;
+        target = target.intersect(visible);
+    }
+}
+
+/// Scrolls newly focused descendants of [`ScrollView`]s into view, if the focus was moved using the
+/// keyboard.
+fn scroll_focus_into_view(
+    focus: Option<Res<InputFocus>>,
+    focus_visible: Option<Res<InputFocusVisible>>,
+    q_parent: Query<&ChildOf>,
+    q_scroll_view: Query<&ScrollView>,
+    mut commands: Commands,
+) {
+    let (Some(focus), Some(focus_visible)) = (focus, focus_visible) else {
+        return;
+    };
+    if !focus.is_changed() || !focus_visible.0 {
+        return;
+    }
+    let Some(focused) = focus.0 else {
+        return;
+    };
+    if q_parent
+        .iter_ancestors(focused)
+        .filter_map(|ancestor| q_scroll_view.get(ancestor).ok())
+        .any(|scroll_view| scroll_view.follow_focus)
+    {
+        commands.trigger(ScrollIntoView { entity: focused });
+    }
+}
+
+/// Applies the velocity of flicked [`ScrollView`]s, slowing it down over time.
+fn update_kinetic_scrolling(
+    mut q_scroll_view: Query<(
+        &ScrollView,
+        &mut ScrollPosition,
+        &mut ScrollViewState,
+        &Node,
+        &ComputedNode,
+    )>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_secs();
+    for (scroll_view, mut scroll_position, mut state, node, computed_node) in &mut q_scroll_view {
+        if state.dragging || state.velocity == Vec2::ZERO {
+            continue;
+        }
+
+        let delta = state.velocity * delta_secs;
+        let remaining = scroll_by(&mut scroll_position, node, computed_node, delta);
+
+        // Stop on the axes that reached the end of the content.
+        let velocity = state.velocity;
+        state.velocity = Vec2::select(remaining.cmpne(Vec2::ZERO), Vec2::ZERO, velocity)
+            * ops::exp(-scroll_view.deceleration * delta_secs);
+        if state.velocity.length() < MIN_KINETIC_SPEED {
+            state.velocity = Vec2::ZERO;
+        }
+    }
+}
+
+/// Plugin that adds the observers and systems for the [`ScrollView`] widget.
+pub struct ScrollViewPlugin;
+
+impl Plugin for ScrollViewPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(scroll_view_on_scroll)
+            .add_observer(scroll_view_on_drag_start)
+            .add_observer(scroll_view_on_drag)
+            .add_observer(scroll_view_on_drag_end)
+            .add_observer(scroll_view_on_drag_cancel)
+            .add_observer(scroll_view_on_scroll_into_view)
+            .add_systems(
+                PostUpdate,
+                (scroll_focus_into_view, update_kinetic_scrolling).before(UiSystems::Layout),
+            );
+    }
+}
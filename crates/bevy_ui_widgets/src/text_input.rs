@@ -0,0 +1,512 @@
+use bevy_app::{App, Plugin, PostUpdate, PreUpdate};
+use bevy_camera::{Camera, NormalizedRenderTarget};
+use bevy_ecs::{
+    change_detection::{DetectChanges, DetectChangesMut},
+    entity::{ContainsEntity, Entity},
+    event::EntityEvent,
+    observer::On,
+    query::{Has, With},
+    schedule::IntoScheduleConfigs,
+    system::{Commands, Query, Res, ResMut},
+};
+use bevy_input::{
+    keyboard::{Key, KeyCode, KeyboardInput},
+    ButtonInput, ButtonState,
+};
+use bevy_input_focus::{
+    dispatch_focused_input, FocusedInput, InputFocus, InputFocusSystems, InputFocusVisible,
+};
+use bevy_math::Vec2;
+use bevy_picking::events::{Drag, Pointer, Press};
+use bevy_text::ComputedTextBlock;
+use bevy_ui::{
+    widget::{
+        text_index_at_position, Text, TextInput, TextInputLayout, TextInputState, TextInputValue,
+        TextPreedit,
+    },
+    ComputedNode, ComputedUiRenderTargetInfo, ComputedUiTargetCamera, InteractionDisabled,
+    UiGlobalTransform, UiScale, UiSystems,
+};
+use bevy_window::{Clipboard, ClipboardContent, ClipboardPaste, Ime, PrimaryWindow, Window};
+
+use crate::ValueChange;
+
+/// Notification sent by a [`TextInput`] when enter is pressed, unless it's
+/// [`multiline`](TextInput::multiline).
+#[derive(Clone, Debug, PartialEq, EntityEvent)]
+pub struct TextInputSubmit {
+    /// The text input that was submitted.
+    pub entity: Entity,
+    /// The value of the text input.
+    pub value: String,
+}
+
+/// Notification sent by a focused [`TextInput`] when the text being composed with an input method
+/// changes, for apps which render the composition themselves.
+///
+/// The composition is also shown by the text input, see [`TextInputState::preedit`].
+#[derive(Clone, Debug, PartialEq, EntityEvent)]
+pub struct TextInputComposition {
+    /// The text input receiving the composition.
+    pub entity: Entity,
+    /// The text being composed, which is empty once the composition ends.
+    pub text: String,
+    /// The byte range of the input method's cursor in `text`, if it should be shown.
+    ///
+    /// Input methods use it to mark the segment of the composition currently being converted.
+    pub cursor: Option<(usize, usize)>,
+}
+
+impl TextInputComposition {
+    /// Splits the composed text into the segments before, under and after the input method's
+    /// cursor.
+    pub fn segments(&self) -> [&str; 3] {
+        let (start, end) = self.cursor.unwrap_or((self.text.len(), self.text.len()));
+        match (
+            self.text.get(..start),
+            self.text.get(start..end),
+            self.text.get(end..),
+        ) {
+            (Some(before), Some(under), Some(after)) => [before, under, after],
+            _ => [self.text.as_str(), "", ""],
+        }
+    }
+}
+
+fn text_input_on_key_input(
+    mut focused_input: On<FocusedInput<KeyboardInput>>,
+    mut q_text_input: Query<(
+        &TextInput,
+        &mut TextInputValue,
+        &mut TextInputState,
+        Has<InteractionDisabled>,
+    )>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut clipboard: ResMut<Clipboard>,
+    mut commands: Commands,
+) {
+    let entity = focused_input.focused_entity;
+    let Ok((text_input, mut value, mut state, disabled)) = q_text_input.get_mut(entity) else {
+        return;
+    };
+    let input_event = &focused_input.input;
+    // Keys are handled by the input method while composing.
+    if disabled || input_event.state != ButtonState::Pressed || state.preedit.is_some() {
+        return;
+    }
+
+    let shortcut = keys.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let max_length = text_input.max_length;
+
+    // Only mark the value as changed if the key edits it.
+    let edited = &mut value.bypass_change_detection().0;
+    let changed = match &input_event.logical_key {
+        Key::ArrowLeft if shortcut => {
+            state.move_word_left(edited, shift);
+            false
+        }
+        Key::ArrowLeft => {
+            state.move_left(edited, shift);
+            false
+        }
+        Key::ArrowRight if shortcut => {
+            state.move_word_right(edited, shift);
+            false
+        }
+        Key::ArrowRight => {
+            state.move_right(edited, shift);
+            false
+        }
+        Key::Home => {
+            state.move_line_start(edited, shift);
+            false
+        }
+        Key::End => {
+            state.move_line_end(edited, shift);
+            false
+        }
+        Key::Backspace => state.delete_backward(edited),
+        Key::Delete => state.delete_forward(edited),
+        Key::Enter if text_input.multiline => state.insert(edited, "\n", max_length),
+        Key::Enter => {
+            commands.trigger(TextInputSubmit {
+                entity,
+                value: edited.clone(),
+            });
+            false
+        }
+        Key::Character(character) if shortcut => match character.to_lowercase().as_str() {
+            "a" => {
+                state.select_all(edited);
+                false
+            }
+            "c" => {
+                if state.has_selection() {
+                    clipboard.set_text(state.selected_text(edited));
+                }
+                false
+            }
+            "x" => {
+                if state.has_selection() {
+                    clipboard.set_text(state.selected_text(edited));
+                }
+                state.delete_selection(edited)
+            }
+            "v" => match clipboard.get_text() {
+                Some(text) => paste(text_input, &mut state, edited, &text),
+                None => false,
+            },
+            _ => return,
+        },
+        _ => match &input_event.text {
+            Some(text) if !shortcut && !text.chars().any(char::is_control) => {
+                state.insert(edited, text, max_length)
+            }
+            _ => return,
+        },
+    };
+
+    focused_input.propagate(false);
+    if changed {
+        value.set_changed();
+        commands.trigger(ValueChange {
+            source: entity,
+            value: value.0.clone(),
+        });
+    }
+}
+
+/// Inserts pasted text, or its first line in single-line inputs.
+fn paste(
+    text_input: &TextInput,
+    state: &mut TextInputState,
+    edited: &mut String,
+    text: &str,
+) -> bool {
+    let text = if text_input.multiline {
+        text
+    } else {
+        text.lines().next().unwrap_or("")
+    };
+    state.insert(edited, text, text_input.max_length)
+}
+
+/// Inserts the text pasted on platforms that send [`ClipboardPaste`] messages rather than letting
+/// the clipboard be read when the paste shortcut is pressed.
+fn text_input_on_paste(
+    mut focused_input: On<FocusedInput<ClipboardPaste>>,
+    mut q_text_input: Query<(
+        &TextInput,
+        &mut TextInputValue,
+        &mut TextInputState,
+        Has<InteractionDisabled>,
+    )>,
+    mut commands: Commands,
+) {
+    let entity = focused_input.focused_entity;
+    let Ok((text_input, mut value, mut state, disabled)) = q_text_input.get_mut(entity) else {
+        return;
+    };
+    focused_input.propagate(false);
+    let ClipboardContent::Text(text) = &focused_input.input.content else {
+        return;
+    };
+    if disabled || state.preedit.is_some() {
+        return;
+    }
+
+    if paste(
+        text_input,
+        &mut state,
+        &mut value.bypass_change_detection().0,
+        text,
+    ) {
+        value.set_changed();
+        commands.trigger(ValueChange {
+            source: entity,
+            value: value.0.clone(),
+        });
+    }
+}
+
+fn text_input_on_ime(
+    mut focused_input: On<FocusedInput<Ime>>,
+    mut q_text_input: Query<(
+        &TextInput,
+        &mut TextInputValue,
+        &mut TextInputState,
+        Has<InteractionDisabled>,
+    )>,
+    mut commands: Commands,
+) {
+    let entity = focused_input.focused_entity;
+    let Ok((text_input, mut value, mut state, disabled)) = q_text_input.get_mut(entity) else {
+        return;
+    };
+    focused_input.propagate(false);
+    if disabled {
+        return;
+    }
+
+    let composing = state.preedit.is_some();
+    let edited = &mut value.bypass_change_detection().0;
+    let changed = match &focused_input.input {
+        Ime::Preedit {
+            value: preedit,
+            cursor,
+            ..
+        } if !preedit.is_empty() => {
+            // The composed text replaces the selection.
+            let changed = state.delete_selection(edited);
+            state.preedit = Some(TextPreedit {
+                text: preedit.clone(),
+                cursor: *cursor,
+            });
+            changed
+        }
+        Ime::Commit { value: text, .. } => {
+            state.preedit = None;
+            state.insert(edited, text, text_input.max_length)
+        }
+        Ime::Preedit { .. } | Ime::Disabled { .. } => {
+            state.preedit = None;
+            false
+        }
+        Ime::Enabled { .. } => false,
+    };
+
+    match &state.preedit {
+        Some(preedit) => commands.trigger(TextInputComposition {
+            entity,
+            text: preedit.text.clone(),
+            cursor: preedit.cursor,
+        }),
+        None if composing => commands.trigger(TextInputComposition {
+            entity,
+            text: String::new(),
+            cursor: None,
+        }),
+        None => {}
+    }
+
+    if changed {
+        value.set_changed();
+        commands.trigger(ValueChange {
+            source: entity,
+            value: value.0.clone(),
+        });
+    }
+}
+
+fn text_input_on_pointer_press(
+    mut press: On<Pointer<Press>>,
+    mut q_text_input: Query<
+        (
+            &mut TextInputState,
+            &Text,
+            &ComputedTextBlock,
+            &ComputedNode,
+            &ComputedUiRenderTargetInfo,
+            &UiGlobalTransform,
+            Has<InteractionDisabled>,
+        ),
+        With<TextInput>,
+    >,
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Option<ResMut<InputFocus>>,
+    focus_visible: Option<ResMut<InputFocusVisible>>,
+    ui_scale: Res<UiScale>,
+) {
+    let Ok((mut state, text, block, node, node_target, transform, disabled)) =
+        q_text_input.get_mut(press.entity)
+    else {
+        return;
+    };
+    press.propagate(false);
+    if disabled {
+        return;
+    }
+
+    // Clicking on a text input makes it the focused input,
+    // and hides the focus ring if it was visible.
+    if let Some(mut focus) = focus {
+        focus.0 = Some(press.entity);
+    }
+    if let Some(mut focus_visible) = focus_visible {
+        focus_visible.0 = false;
+    }
+
+    if state.preedit.is_none() {
+        let local_pos = transform.try_inverse().unwrap().transform_point2(
+            press.pointer_location.position * node_target.scale_factor() / ui_scale.0,
+        ) + 0.5 * node.size();
+        let index = state.value_index(text_index_at_position(block, text, local_pos));
+        let extend = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        state.move_to(index, extend);
+    }
+}
+
+fn text_input_on_drag(
+    mut drag: On<Pointer<Drag>>,
+    mut q_text_input: Query<
+        (
+            &mut TextInputState,
+            &Text,
+            &ComputedTextBlock,
+            &ComputedNode,
+            &ComputedUiRenderTargetInfo,
+            &UiGlobalTransform,
+            Has<InteractionDisabled>,
+        ),
+        With<TextInput>,
+    >,
+    ui_scale: Res<UiScale>,
+) {
+    let Ok((mut state, text, block, node, node_target, transform, disabled)) =
+        q_text_input.get_mut(drag.entity)
+    else {
+        return;
+    };
+    drag.propagate(false);
+    if disabled || state.preedit.is_some() {
+        return;
+    }
+
+    let local_pos =
+        transform.try_inverse().unwrap().transform_point2(
+            drag.pointer_location.position * node_target.scale_factor() / ui_scale.0,
+        ) + 0.5 * node.size();
+    let index = state.value_index(text_index_at_position(block, text, local_pos));
+    state.move_to(index, true);
+}
+
+/// Shows the caret of the focused [`TextInput`], and enables input methods on windows while a text
+/// input has focus.
+fn update_text_input_focus(
+    focus: Option<Res<InputFocus>>,
+    mut q_text_input: Query<(Entity, &mut TextInputState), With<TextInput>>,
+    mut windows: Query<&mut Window>,
+) {
+    let Some(focus) = focus else {
+        return;
+    };
+    for (entity, mut state) in &mut q_text_input {
+        let focused = focus.0 == Some(entity);
+        if state.show_caret != focused {
+            state.show_caret = focused;
+            if !focused {
+                state.preedit = None;
+            }
+        }
+    }
+
+    if focus.is_changed() {
+        let ime_enabled = focus.0.is_some_and(|entity| q_text_input.contains(entity));
+        for mut window in &mut windows {
+            window.ime_enabled = ime_enabled;
+        }
+    }
+}
+
+/// Moves the candidate window of input methods to the caret of the focused [`TextInput`], by
+/// setting [`Window::ime_position`] below it.
+fn update_ime_position(
+    focus: Option<Res<InputFocus>>,
+    q_text_input: Query<
+        (
+            &TextInputLayout,
+            &ComputedNode,
+            &UiGlobalTransform,
+            &ComputedUiTargetCamera,
+        ),
+        With<TextInput>,
+    >,
+    q_camera: Query<&Camera>,
+    q_primary_window: Query<Entity, With<PrimaryWindow>>,
+    mut q_window: Query<&mut Window>,
+) {
+    let Some((layout, node, transform, target_camera)) = focus
+        .and_then(|focus| focus.0)
+        .and_then(|entity| q_text_input.get(entity).ok())
+    else {
+        return;
+    };
+    let Some(caret) = layout.caret else {
+        return;
+    };
+    let Some(camera) = target_camera
+        .get()
+        .and_then(|camera| q_camera.get(camera).ok())
+    else {
+        return;
+    };
+    let Some(NormalizedRenderTarget::Window(window_ref)) =
+        camera.target.normalize(q_primary_window.single().ok())
+    else {
+        return;
+    };
+    let Ok(mut window) = q_window.get_mut(window_ref.entity()) else {
+        return;
+    };
+
+    // The caret is in physical pixels relative to the top-left corner of the node, and the node
+    // is positioned relative to the camera's viewport.
+    let viewport_min = camera
+        .physical_viewport_rect()
+        .map_or(Vec2::ZERO, |viewport| viewport.min.as_vec2());
+    let caret_bottom_left = Vec2::new(caret.min.x, caret.max.y) - 0.5 * node.size();
+    let ime_position =
+        (transform.transform_point2(caret_bottom_left) + viewport_min) / window.scale_factor();
+    if window.ime_position != ime_position {
+        window.ime_position = ime_position;
+    }
+}
+
+/// Plugin that adds the observers and systems for editing [`TextInput`]s.
+///
+/// Unlike most widgets in this crate, text inputs update their own [`TextInputValue`], and send
+/// [`ValueChange<String>`] after each edit.
+///
+/// While a text input has focus, input methods are enabled on all windows by setting
+/// [`Window::ime_enabled`], and their candidate window is placed below the caret with
+/// [`Window::ime_position`]. Changes to the text being composed are sent as
+/// [`TextInputComposition`] events.
+///
+/// Text is copied to and pasted from the [`Clipboard`] with the usual shortcuts, and from the
+/// [`ClipboardPaste`] messages on platforms that send them.
+pub struct TextInputPlugin;
+
+impl Plugin for TextInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Clipboard>()
+            .init_resource::<InputFocus>()
+            .add_message::<Ime>()
+            .add_message::<ClipboardPaste>()
+            .add_systems(
+                PreUpdate,
+                (
+                    dispatch_focused_input::<Ime>,
+                    dispatch_focused_input::<ClipboardPaste>,
+                )
+                    .in_set(InputFocusSystems::Dispatch),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    update_text_input_focus.before(UiSystems::Content),
+                    update_ime_position.after(UiSystems::PostLayout),
+                ),
+            )
+            .add_observer(text_input_on_key_input)
+            .add_observer(text_input_on_paste)
+            .add_observer(text_input_on_ime)
+            .add_observer(text_input_on_pointer_press)
+            .add_observer(text_input_on_drag);
+    }
+}
@@ -0,0 +1,68 @@
+use bevy_app::{App, Plugin};
+use bevy_camera::NormalizedRenderTarget;
+use bevy_ecs::{component::Component, observer::On, query::Has, system::Query};
+use bevy_math::CompassOctant;
+use bevy_picking::{
+    events::{Pointer, Press},
+    pointer::PointerButton,
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_ui::InteractionDisabled;
+use bevy_window::Window;
+
+/// Marks a UI node as a part of the chrome of its window, so that pressing the primary mouse
+/// button on it moves or resizes the window like its native titlebar and borders would.
+///
+/// This lets apps draw their own titlebar and borders for windows without
+/// [`decorations`](Window::decorations). Other UI nodes above the region, such as the close button
+/// of a custom titlebar, still receive the pointer normally.
+///
+/// Changing the cursor icon while hovering the resize borders is left to the app.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub enum WindowHitTestRegion {
+    /// The titlebar of the window, which moves the window when dragged.
+    #[default]
+    Titlebar,
+    /// A border or corner of the window, which resizes the window in the given direction when
+    /// dragged.
+    ResizeBorder(CompassOctant),
+}
+
+fn window_hit_test_on_pointer_press(
+    mut press: On<Pointer<Press>>,
+    q_region: Query<(&WindowHitTestRegion, Has<InteractionDisabled>)>,
+    mut q_window: Query<&mut Window>,
+) {
+    let Ok((region, disabled)) = q_region.get(press.entity) else {
+        return;
+    };
+    press.propagate(false);
+    // Windows can only be dragged with the mouse.
+    if disabled
+        || press.event.button != PointerButton::Primary
+        || !(press.pointer_id.is_mouse() || press.pointer_id.is_mouse_device())
+    {
+        return;
+    }
+
+    let NormalizedRenderTarget::Window(window_ref) = &press.pointer_location.target else {
+        return;
+    };
+    let Ok(mut window) = q_window.get_mut(window_ref.entity()) else {
+        return;
+    };
+    match *region {
+        WindowHitTestRegion::Titlebar => window.start_drag_move(),
+        WindowHitTestRegion::ResizeBorder(direction) => window.start_drag_resize(direction),
+    }
+}
+
+/// Plugin that adds the observers for the [`WindowHitTestRegion`] component.
+pub struct WindowHitTestPlugin;
+
+impl Plugin for WindowHitTestPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(window_hit_test_on_pointer_press);
+    }
+}
@@ -0,0 +1,32 @@
+//! Representation of assets present in a USD file
+
+#[cfg(feature = "bevy_animation")]
+use bevy_animation::AnimationClip;
+use bevy_asset::{Asset, Handle};
+use bevy_ecs::{component::Component, reflect::ReflectComponent};
+use bevy_mesh::Mesh;
+use bevy_pbr::StandardMaterial;
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{prelude::ReflectDefault, Reflect, TypePath};
+use bevy_scene::Scene;
+
+/// Representation of a loaded USD file.
+#[derive(Asset, Debug, TypePath)]
+pub struct Usd {
+    /// The stage of the USD file, with its root prims under a single root entity.
+    pub scene: Handle<Scene>,
+    /// All meshes loaded from the USD file.
+    pub meshes: Vec<Handle<Mesh>>,
+    /// All materials loaded from the USD file.
+    pub materials: Vec<Handle<StandardMaterial>>,
+    /// The materials loaded from USD `Material` prims, by prim path (such as `/World/Looks/Wood`).
+    pub named_materials: HashMap<Box<str>, Handle<StandardMaterial>>,
+    /// The animated transforms of the stage, if any.
+    #[cfg(feature = "bevy_animation")]
+    pub animation: Option<Handle<AnimationClip>>,
+}
+
+/// The path of the USD prim an entity was loaded from, such as `/World/Chair`.
+#[derive(Clone, Debug, Reflect, Default, Component)]
+#[reflect(Component, Clone, Default, Debug)]
+pub struct UsdPrimPath(pub String);
@@ -0,0 +1,64 @@
+//! Labels that can be used to load part of a USD file
+
+use bevy_asset::AssetPath;
+
+/// Labels that can be used to load part of a USD file
+///
+/// You can use [`UsdAssetLabel::from_asset`] to add it to an asset path
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_asset::prelude::*;
+/// # use bevy_scene::prelude::*;
+/// # use bevy_usd::prelude::*;
+///
+/// fn load_usd_scene(asset_server: Res<AssetServer>) {
+///     let usd_scene: Handle<Scene> = asset_server.load(UsdAssetLabel::Scene.from_asset("models/Kitchen/kitchen.usdz"));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsdAssetLabel {
+    /// `Scene`: the USD stage as a Bevy [`Scene`](bevy_scene::Scene)
+    Scene,
+    /// `Mesh{}`: a mesh of a USD `Mesh` prim as a Bevy [`Mesh`](bevy_mesh::Mesh).
+    ///
+    /// A prim results in several meshes if its faces are bound to different materials.
+    Mesh(usize),
+    /// `Material{}`: USD material as a Bevy [`StandardMaterial`](bevy_pbr::StandardMaterial)
+    Material(usize),
+    /// `Texture{}`: a texture embedded in a `.usdz` package as a Bevy [`Image`](bevy_image::prelude::Image)
+    Texture(usize),
+    /// `Animation`: the animated transforms of the USD stage as a Bevy
+    /// [`AnimationClip`](bevy_animation::AnimationClip)
+    Animation,
+}
+
+impl core::fmt::Display for UsdAssetLabel {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            UsdAssetLabel::Scene => f.write_str("Scene"),
+            UsdAssetLabel::Mesh(index) => f.write_str(&format!("Mesh{index}")),
+            UsdAssetLabel::Material(index) => f.write_str(&format!("Material{index}")),
+            UsdAssetLabel::Texture(index) => f.write_str(&format!("Texture{index}")),
+            UsdAssetLabel::Animation => f.write_str("Animation"),
+        }
+    }
+}
+
+impl UsdAssetLabel {
+    /// Add this label to an asset path
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_asset::prelude::*;
+    /// # use bevy_scene::prelude::*;
+    /// # use bevy_usd::prelude::*;
+    ///
+    /// fn load_usd_scene(asset_server: Res<AssetServer>) {
+    ///     let usd_scene: Handle<Scene> = asset_server.load(UsdAssetLabel::Scene.from_asset("models/Kitchen/kitchen.usdz"));
+    /// }
+    /// ```
+    pub fn from_asset(&self, path: impl Into<AssetPath<'static>>) -> AssetPath<'static> {
+        path.into().with_label(self.to_string())
+    }
+}
@@ -0,0 +1,100 @@
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![forbid(unsafe_code)]
+#![doc(
+    html_logo_url = "https://bevy.org/assets/icon.png",
+    html_favicon_url = "https://bevy.org/assets/icon.png"
+)]
+
+//! Plugin providing an [`AssetLoader`](bevy_asset::AssetLoader) and type definitions
+//! for loading [USD](https://openusd.org) (Universal Scene Description) files in Bevy.
+//!
+//! Text (`.usda`), binary (`.usdc`) and packaged (`.usdz`) layers are supported, as well as `.usd`
+//! files in either the text or binary format.
+//!
+//! # Quick Start
+//!
+//! Here's how to spawn a USD stage
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_asset::prelude::*;
+//! # use bevy_scene::prelude::*;
+//! # use bevy_transform::prelude::*;
+//! # use bevy_usd::prelude::*;
+//!
+//! fn spawn_usd(mut commands: Commands, asset_server: Res<AssetServer>) {
+//!     commands.spawn((
+//!         // This is equivalent to "models/Kitchen/kitchen.usdz#Scene"
+//!         SceneRoot(asset_server.load(UsdAssetLabel::Scene.from_asset("models/Kitchen/kitchen.usdz"))),
+//!         // You can use the transform to give it a position
+//!         Transform::from_xyz(2.0, 0.0, -5.0),
+//!     ));
+//! }
+//! ```
+//!
+//! # Supported features
+//!
+//! The loader converts the following parts of a stage:
+//!
+//! - `Xform` and other prims, with their transform operations, visibility and name. Each entity also
+//!   gets a [`UsdPrimPath`] component.
+//! - `Mesh` prims, including normals, texture coordinates, display colors and `GeomSubset` material
+//!   bindings. Faces are triangulated as fans.
+//! - `Material` prims using a `UsdPreviewSurface` shader, with `UsdUVTexture` textures.
+//! - Animated transforms, as an [`AnimationClip`](bevy_animation::AnimationClip) when the
+//!   `bevy_animation` feature is enabled.
+//! - The `upAxis` and `metersPerUnit` metadata of the stage, which can be disabled in
+//!   [`UsdLoaderSettings`].
+//!
+//! Composition arcs (references, payloads, sublayers, inherits and variants) aren't resolved, so
+//! stages should be flattened before being loaded. Lights, cameras, skeletons and blend shapes
+//! are ignored, and `.usdz` packages must store their files uncompressed, as the specification
+//! requires.
+
+mod assets;
+mod label;
+mod loader;
+mod stage;
+
+use tracing::warn;
+
+use bevy_app::prelude::*;
+use bevy_asset::AssetApp;
+use bevy_image::{CompressedImageFormatSupport, CompressedImageFormats};
+
+/// The USD prelude.
+///
+/// This includes the most common types in this crate, re-exported for your convenience.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{assets::Usd, label::UsdAssetLabel};
+}
+
+pub use {assets::*, label::UsdAssetLabel, loader::*, stage::UsdParseError};
+
+/// Adds support for USD file loading to the app.
+#[derive(Default)]
+pub struct UsdPlugin;
+
+impl Plugin for UsdPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Usd>()
+            .preregister_asset_loader::<UsdLoader>(&["usd", "usda", "usdc", "usdz"]);
+    }
+
+    fn finish(&self, app: &mut App) {
+        let supported_compressed_formats = if let Some(resource) =
+            app.world().get_resource::<CompressedImageFormatSupport>()
+        {
+            resource.0
+        } else {
+            warn!("CompressedImageFormatSupport resource not found. It should either be initialized in finish() of \
+            RenderPlugin, or manually if not using the RenderPlugin or the WGPU backend.");
+            CompressedImageFormats::NONE
+        };
+
+        app.register_asset_loader(UsdLoader {
+            supported_compressed_formats,
+        });
+    }
+}
@@ -0,0 +1,1211 @@
+use std::io::{Cursor, Read};
+
+#[cfg(feature = "bevy_animation")]
+use bevy_animation::{
+    animated_field,
+    animation_curves::{AnimatableCurve, AnimatedField},
+    AnimatedBy, AnimationClip, AnimationPlayer, AnimationTargetId,
+};
+use bevy_asset::{
+    io::Reader, AssetLoader, Handle, LoadContext, ParseAssetPathError, RenderAssetUsages,
+};
+use bevy_camera::visibility::Visibility;
+use bevy_color::{Alpha, Color, ColorToComponents, LinearRgba};
+use bevy_ecs::{entity::Entity, hierarchy::ChildOf, name::Name, world::World};
+use bevy_image::{
+    CompressedImageFormats, Image, ImageAddressMode, ImageLoaderSettings, ImageSampler,
+    ImageSamplerDescriptor, ImageType, TextureError,
+};
+#[cfg(feature = "bevy_animation")]
+use bevy_math::curve::UnevenSampleAutoCurve;
+use bevy_math::{Mat4, Quat, Vec3};
+use bevy_mesh::{Indices, Mesh, Mesh3d, PrimitiveTopology};
+use bevy_pbr::{MeshMaterial3d, StandardMaterial};
+use bevy_platform::collections::{HashMap, HashSet};
+use bevy_render::alpha::AlphaMode;
+use bevy_scene::Scene;
+use bevy_transform::components::Transform;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::warn;
+use zip::{result::ZipError, ZipArchive};
+
+use crate::{
+    stage::{
+        parse_usda, parse_usdc, split_property_path, Attribute, Prim, Specifier, Stage, UpAxis,
+        UsdParseError, Value,
+    },
+    Usd, UsdAssetLabel, UsdPrimPath,
+};
+
+/// An error that occurs when loading a USD file.
+#[derive(Error, Debug)]
+pub enum UsdError {
+    /// Failed to read the file.
+    #[error("failed to read USD file: {0}")]
+    Io(#[from] std::io::Error),
+    /// Failed to parse a USD layer.
+    #[error(transparent)]
+    Parse(#[from] UsdParseError),
+    /// Failed to read a `.usdz` package.
+    #[error("invalid usdz package: {0}")]
+    Zip(#[from] ZipError),
+    /// A `.usdz` package doesn't contain any USD layer.
+    #[error("usdz package doesn't contain a USD layer")]
+    MissingUsdzLayer,
+    /// Failed to decode a texture embedded in a `.usdz` package.
+    #[error("failed to load texture {0}: {1}")]
+    Texture(String, TextureError),
+    /// A texture path is invalid.
+    #[error("invalid texture path {0}: {1}")]
+    InvalidTexturePath(String, ParseAssetPathError),
+}
+
+/// Loads USD files (`.usd`, `.usda`, `.usdc` and `.usdz`) as a [`Usd`] asset.
+pub struct UsdLoader {
+    /// The compressed texture formats supported by the GPU, used to decode textures embedded in `.usdz` packages.
+    pub supported_compressed_formats: CompressedImageFormats,
+}
+
+/// Specifies optional settings for processing USD files at load time.
+///
+/// To use, load the asset with [`AssetServer::load_with_settings`](bevy_asset::AssetServer::load_with_settings):
+///
+/// ```no_run
+/// # use bevy_asset::{AssetServer, Handle};
+/// # use bevy_usd::*;
+/// # let asset_server: AssetServer = panic!();
+/// let usd_handle: Handle<Usd> = asset_server.load_with_settings(
+///     "my.usdz",
+///     |s: &mut UsdLoaderSettings| {
+///         s.load_animations = false;
+///     },
+/// );
+/// ```
+#[derive(Serialize, Deserialize)]
+pub struct UsdLoaderSettings {
+    /// If empty, the `Mesh` prims will be skipped.
+    ///
+    /// Otherwise, meshes will be loaded and retained in RAM/VRAM according to the active flags.
+    pub load_meshes: RenderAssetUsages,
+    /// If empty, the materials will be skipped.
+    ///
+    /// Otherwise, materials will be loaded and retained in RAM/VRAM according to the active flags.
+    pub load_materials: RenderAssetUsages,
+    /// If true, the loader will load the animated transforms as an `AnimationClip` asset, and add
+    /// `AnimationTarget` and `AnimationPlayer` components to the scene. Requires the `bevy_animation` feature.
+    pub load_animations: bool,
+    /// If true, stages with a `Z` up axis are rotated so that their up axis is Bevy's `Y` axis.
+    pub convert_up_axis: bool,
+    /// If true, stages with an authored `metersPerUnit` are scaled so that one unit is one meter.
+    pub convert_units: bool,
+}
+
+impl Default for UsdLoaderSettings {
+    fn default() -> Self {
+        Self {
+            load_meshes: RenderAssetUsages::default(),
+            load_materials: RenderAssetUsages::default(),
+            load_animations: true,
+            convert_up_axis: true,
+            convert_units: true,
+        }
+    }
+}
+
+impl AssetLoader for UsdLoader {
+    type Asset = Usd;
+    type Settings = UsdLoaderSettings;
+    type Error = UsdError;
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        settings: &UsdLoaderSettings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Usd, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        // `.usdz` files are zip archives whose first file is the root layer.
+        let (stage, package) = if bytes.starts_with(b"PK\x03\x04") {
+            let mut archive = ZipArchive::new(Cursor::new(bytes))?;
+            let Some(root_layer) = archive.file_names().find(|name| is_layer(name)) else {
+                return Err(UsdError::MissingUsdzLayer);
+            };
+            let root_layer = root_layer.to_string();
+            let mut layer = Vec::new();
+            archive.by_name(&root_layer)?.read_to_end(&mut layer)?;
+            (parse_layer(&layer)?, Some(archive))
+        } else {
+            (parse_layer(&bytes)?, None)
+        };
+
+        StageLoader {
+            stage: &stage,
+            prims: stage.prims_by_path(),
+            settings,
+            load_context,
+            package,
+            supported_compressed_formats: self.supported_compressed_formats,
+            meshes: Vec::new(),
+            materials: Vec::new(),
+            named_materials: HashMap::default(),
+            normal_mapped_materials: HashSet::default(),
+            display_color_materials: HashMap::default(),
+            textures: HashMap::default(),
+            texture_count: 0,
+            #[cfg(feature = "bevy_animation")]
+            animation: AnimationClip::default(),
+            #[cfg(feature = "bevy_animation")]
+            is_animated: false,
+        }
+        .load()
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["usd", "usda", "usdc", "usdz"]
+    }
+}
+
+fn is_layer(path: &str) -> bool {
+    [".usd", ".usda", ".usdc"]
+        .iter()
+        .any(|extension| path.ends_with(extension))
+}
+
+/// Parses a USD layer, which can either be in the text or binary format.
+fn parse_layer(bytes: &[u8]) -> Result<Stage, UsdParseError> {
+    if bytes.starts_with(b"PXR-USDC") {
+        parse_usdc(bytes)
+    } else {
+        parse_usda(&String::from_utf8_lossy(bytes))
+    }
+}
+
+/// How the values of a primvar are mapped to the faces of a mesh.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Interpolation {
+    /// One value for the whole mesh.
+    Constant,
+    /// One value per face.
+    Uniform,
+    /// One value per point.
+    Vertex,
+    /// One value per face corner.
+    FaceVarying,
+}
+
+/// A primvar of a mesh, with its values flattened.
+struct Primvar<'a> {
+    values: &'a [f64],
+    width: usize,
+    interpolation: Interpolation,
+    indices: Option<Vec<usize>>,
+}
+
+impl<'a> Primvar<'a> {
+    fn new(
+        prim: &'a Prim,
+        name: &str,
+        width: usize,
+        default_interpolation: Interpolation,
+    ) -> Option<Self> {
+        let attribute = prim.attributes.get(name)?;
+        let values = attribute.value()?.as_numbers()?;
+        let interpolation = match attribute.interpolation.as_deref() {
+            Some("constant") => Interpolation::Constant,
+            Some("uniform") => Interpolation::Uniform,
+            Some("vertex" | "varying") => Interpolation::Vertex,
+            Some("faceVarying") => Interpolation::FaceVarying,
+            _ => default_interpolation,
+        };
+        let indices = prim
+            .attribute(&format!("{name}:indices"))
+            .and_then(Value::as_numbers)
+            .map(|indices| indices.iter().map(|&index| index as usize).collect());
+        Some(Self {
+            values,
+            width,
+            interpolation,
+            indices,
+        })
+    }
+
+    /// Returns the number of elements the primvar has values for.
+    fn element_count(&self) -> usize {
+        match &self.indices {
+            Some(indices) => indices.len(),
+            None => self.values.len() / self.width,
+        }
+    }
+
+    /// Checks that the primvar has enough values for a mesh.
+    fn is_valid(&self, faces: usize, points: usize, corners: usize) -> bool {
+        let required = match self.interpolation {
+            Interpolation::Constant => 1,
+            Interpolation::Uniform => faces,
+            Interpolation::Vertex => points,
+            Interpolation::FaceVarying => corners,
+        };
+        let value_count = self.values.len() / self.width;
+        self.element_count() >= required
+            && self
+                .indices
+                .as_ref()
+                .is_none_or(|indices| indices.iter().all(|&index| index < value_count))
+    }
+
+    fn get(&self, face: usize, point: usize, corner: usize) -> &'a [f64] {
+        let element = match self.interpolation {
+            Interpolation::Constant => 0,
+            Interpolation::Uniform => face,
+            Interpolation::Vertex => point,
+            Interpolation::FaceVarying => corner,
+        };
+        let element = match &self.indices {
+            Some(indices) => indices[element],
+            None => element,
+        };
+        &self.values[element * self.width..(element + 1) * self.width]
+    }
+}
+
+fn vec3(values: &[f64]) -> Option<Vec3> {
+    match values {
+        [x, y, z, ..] => Some(Vec3::new(*x as f32, *y as f32, *z as f32)),
+        _ => None,
+    }
+}
+
+/// Returns the value of `attribute` at `time`, interpolating between time samples.
+fn sample(attribute: &Attribute, time: f64, is_rotation: bool) -> Option<Vec<f64>> {
+    let samples = &attribute.time_samples;
+    if samples.is_empty() {
+        return attribute
+            .default
+            .as_ref()?
+            .as_numbers()
+            .map(<[f64]>::to_vec);
+    }
+    let next = samples.partition_point(|(sample_time, _)| *sample_time <= time);
+    let numbers = |index: usize| samples[index].1.as_ref().and_then(Value::as_numbers);
+    if next == 0 {
+        return numbers(0).map(<[f64]>::to_vec);
+    }
+    let previous = numbers(next - 1)?;
+    let Some(following) = samples.get(next).and_then(|(_, value)| value.as_ref()) else {
+        return Some(previous.to_vec());
+    };
+    let Some(following) = following
+        .as_numbers()
+        .filter(|values| values.len() == previous.len())
+    else {
+        return Some(previous.to_vec());
+    };
+    let (start, end) = (samples[next - 1].0, samples[next].0);
+    let t = (time - start) / (end - start);
+    // Quaternions take the shortest path.
+    let sign = if is_rotation
+        && previous
+            .iter()
+            .zip(following)
+            .map(|(a, b)| a * b)
+            .sum::<f64>()
+            < 0.0
+    {
+        -1.0
+    } else {
+        1.0
+    };
+    Some(
+        previous
+            .iter()
+            .zip(following)
+            .map(|(a, b)| a + (sign * b - a) * t)
+            .collect(),
+    )
+}
+
+/// Returns the matrix of a transform operation, such as `xformOp:translate`, from its value.
+fn xform_op_matrix(name: &str, values: &[f64]) -> Option<Mat4> {
+    let op = name.strip_prefix("xformOp:")?;
+    let op = op.split(':').next()?;
+    let radians = |degrees: f64| (degrees as f32).to_radians();
+    Some(match op {
+        "translate" => Mat4::from_translation(vec3(values)?),
+        "scale" => Mat4::from_scale(vec3(values)?),
+        "rotateX" => Mat4::from_rotation_x(radians(*values.first()?)),
+        "rotateY" => Mat4::from_rotation_y(radians(*values.first()?)),
+        "rotateZ" => Mat4::from_rotation_z(radians(*values.first()?)),
+        "orient" => {
+            let [x, y, z, w] = *values else {
+                return None;
+            };
+            Mat4::from_quat(Quat::from_xyzw(x as f32, y as f32, z as f32, w as f32).normalize())
+        }
+        "transform" => {
+            // USD matrices are row-major and transform row vectors, so they have the same layout as column-major
+            // matrices transforming column vectors.
+            let values: [f32; 16] =
+                core::array::from_fn(|index| values.get(index).copied().unwrap_or(0.0) as f32);
+            Mat4::from_cols_array(&values)
+        }
+        _ => {
+            // Rotations around three axes, such as `rotateXYZ`, which rotates around X first.
+            let axes = op.strip_prefix("rotate").filter(|axes| axes.len() == 3)?;
+            let angles = vec3(values)?;
+            let mut rotation = Quat::IDENTITY;
+            for axis in axes.chars() {
+                let axis_rotation = match axis {
+                    'X' => Quat::from_rotation_x(angles.x.to_radians()),
+                    'Y' => Quat::from_rotation_y(angles.y.to_radians()),
+                    'Z' => Quat::from_rotation_z(angles.z.to_radians()),
+                    _ => return None,
+                };
+                rotation = axis_rotation * rotation;
+            }
+            Mat4::from_quat(rotation)
+        }
+    })
+}
+
+/// Returns the names of the transform operations of `prim`, in the order they are applied.
+fn xform_ops(prim: &Prim) -> &[String] {
+    prim.attribute("xformOpOrder")
+        .and_then(Value::as_strs)
+        .unwrap_or_default()
+}
+
+/// Returns the local transform of `prim` at `time`.
+fn local_transform(prim: &Prim, time: f64) -> Transform {
+    let mut matrix = Mat4::IDENTITY;
+    for op in xform_ops(prim) {
+        if op == "!resetXformStack!" {
+            matrix = Mat4::IDENTITY;
+            continue;
+        }
+        let (name, invert) = match op.strip_prefix("!invert!") {
+            Some(name) => (name, true),
+            None => (op.as_str(), false),
+        };
+        let Some(values) = prim
+            .attributes
+            .get(name)
+            .and_then(|attribute| sample(attribute, time, name.starts_with("xformOp:orient")))
+        else {
+            continue;
+        };
+        let Some(op_matrix) = xform_op_matrix(name, &values) else {
+            warn!("Unsupported transform operation {op} on {}", prim.path);
+            continue;
+        };
+        matrix *= if invert {
+            op_matrix.inverse()
+        } else {
+            op_matrix
+        };
+    }
+    Transform::from_matrix(matrix)
+}
+
+/// Converts a [`Stage`] to Bevy assets.
+struct StageLoader<'a, 'b, 'c> {
+    stage: &'a Stage,
+    prims: HashMap<&'a str, &'a Prim>,
+    settings: &'a UsdLoaderSettings,
+    load_context: &'b mut LoadContext<'c>,
+    package: Option<ZipArchive<Cursor<Vec<u8>>>>,
+    supported_compressed_formats: CompressedImageFormats,
+    meshes: Vec<Handle<Mesh>>,
+    materials: Vec<Handle<StandardMaterial>>,
+    named_materials: HashMap<Box<str>, Handle<StandardMaterial>>,
+    normal_mapped_materials: HashSet<Handle<StandardMaterial>>,
+    display_color_materials: HashMap<[u32; 4], Handle<StandardMaterial>>,
+    textures: HashMap<(String, bool), Handle<Image>>,
+    texture_count: usize,
+    #[cfg(feature = "bevy_animation")]
+    animation: AnimationClip,
+    #[cfg(feature = "bevy_animation")]
+    is_animated: bool,
+}
+
+/// The texture an input of a `UsdPreviewSurface` is connected to.
+struct TextureInput<'a> {
+    /// The `UsdUVTexture` shader.
+    shader: &'a Prim,
+    /// The output of the texture shader, such as `outputs:rgb`.
+    output: &'a str,
+}
+
+impl<'a> StageLoader<'a, '_, '_> {
+    fn load(mut self) -> Result<Usd, UsdError> {
+        if !self.settings.load_materials.is_empty() {
+            let mut material_prims = self
+                .prims
+                .values()
+                .filter(|prim| prim.type_name == "Material" && prim.specifier == Specifier::Def)
+                .copied()
+                .collect::<Vec<_>>();
+            material_prims.sort_by(|a, b| a.path.cmp(&b.path));
+            for prim in material_prims {
+                let (material, normal_mapped) = self.load_material(prim)?;
+                let handle = self.add_material(material);
+                if normal_mapped {
+                    self.normal_mapped_materials.insert(handle.clone());
+                }
+                self.named_materials
+                    .insert(prim.path.as_str().into(), handle);
+            }
+        }
+
+        let mut world = World::default();
+        let mut root_transform = Transform::default();
+        if self.settings.convert_up_axis && self.stage.up_axis == UpAxis::Z {
+            root_transform.rotation = Quat::from_rotation_x(-core::f32::consts::FRAC_PI_2);
+        }
+        if self.settings.convert_units
+            && let Some(meters_per_unit) = self.stage.meters_per_unit
+        {
+            root_transform.scale = Vec3::splat(meters_per_unit as f32);
+        }
+        let root = world.spawn((root_transform, Visibility::default())).id();
+        let stage = self.stage;
+        let mut path = Vec::new();
+        for prim in &stage.root_prims {
+            self.spawn_prim(&mut world, prim, root, root, None, &mut path)?;
+        }
+
+        #[cfg(feature = "bevy_animation")]
+        let animation = if self.is_animated {
+            world.entity_mut(root).insert(AnimationPlayer::default());
+            let animation = core::mem::take(&mut self.animation);
+            Some(
+                self.load_context
+                    .add_labeled_asset(UsdAssetLabel::Animation.to_string(), animation),
+            )
+        } else {
+            None
+        };
+
+        let scene = self
+            .load_context
+            .add_labeled_asset(UsdAssetLabel::Scene.to_string(), Scene::new(world));
+        Ok(Usd {
+            scene,
+            meshes: self.meshes,
+            materials: self.materials,
+            named_materials: self.named_materials,
+            #[cfg(feature = "bevy_animation")]
+            animation,
+        })
+    }
+
+    #[cfg_attr(
+        not(feature = "bevy_animation"),
+        expect(
+            clippy::only_used_in_recursion,
+            reason = "`root` is only used to animate prims"
+        )
+    )]
+    fn spawn_prim(
+        &mut self,
+        world: &mut World,
+        prim: &'a Prim,
+        root: Entity,
+        parent: Entity,
+        inherited_material: Option<&'a str>,
+        path: &mut Vec<Name>,
+    ) -> Result<(), UsdError> {
+        // Classes and overs without a definition aren't part of the scene, and shading prims are loaded as materials.
+        if prim.specifier != Specifier::Def
+            || matches!(
+                prim.type_name.as_str(),
+                "Material" | "Shader" | "NodeGraph" | "GeomSubset"
+            )
+        {
+            return Ok(());
+        }
+
+        let start_time = self.stage.start_time_code.unwrap_or(0.0);
+        let visibility = match prim.attribute("visibility").and_then(Value::as_str) {
+            Some("invisible") => Visibility::Hidden,
+            _ => Visibility::Inherited,
+        };
+        let name = Name::new(prim.name.clone());
+        let entity = world
+            .spawn((
+                name.clone(),
+                local_transform(prim, start_time),
+                visibility,
+                UsdPrimPath(prim.path.clone()),
+                ChildOf(parent),
+            ))
+            .id();
+        path.push(name);
+
+        #[cfg(feature = "bevy_animation")]
+        if self.settings.load_animations && self.load_animation(prim, path) {
+            world
+                .entity_mut(entity)
+                .insert((AnimationTargetId::from_names(path.iter()), AnimatedBy(root)));
+        }
+
+        let material = prim.target("material:binding").or(inherited_material);
+        if prim.type_name == "Mesh" && !self.settings.load_meshes.is_empty() {
+            for (mesh, material) in self.load_mesh(prim, material) {
+                let mut primitive = world.spawn((Mesh3d(mesh), ChildOf(entity)));
+                if let Some(material) = material {
+                    primitive.insert(MeshMaterial3d(material));
+                }
+            }
+        }
+
+        for child in &prim.children {
+            self.spawn_prim(world, child, root, entity, material, path)?;
+        }
+        path.pop();
+        Ok(())
+    }
+
+    /// Adds the animated transform of `prim` to the animation clip, returning whether it is animated.
+    #[cfg(feature = "bevy_animation")]
+    fn load_animation(&mut self, prim: &Prim, path: &[Name]) -> bool {
+        let mut times = xform_ops(prim)
+            .iter()
+            .map(|op| op.trim_start_matches("!invert!"))
+            .filter_map(|op| prim.attributes.get(op))
+            .flat_map(|attribute| attribute.time_samples.iter().map(|(time, _)| *time))
+            .collect::<Vec<_>>();
+        times.sort_by(f64::total_cmp);
+        times.dedup();
+        if times.len() < 2 {
+            return false;
+        }
+
+        let start_time = self.stage.start_time_code.unwrap_or(times[0]);
+        let time_codes_per_second = self.stage.time_codes_per_second();
+        let seconds = times
+            .iter()
+            .map(|time| ((time - start_time) / time_codes_per_second) as f32);
+        let transforms = times
+            .iter()
+            .map(|&time| local_transform(prim, time))
+            .collect::<Vec<_>>();
+
+        let target = AnimationTargetId::from_names(path.iter());
+        let translations = seconds
+            .clone()
+            .zip(transforms.iter().map(|transform| transform.translation));
+        let rotations = seconds
+            .clone()
+            .zip(transforms.iter().map(|transform| transform.rotation));
+        let scales = seconds.zip(transforms.iter().map(|transform| transform.scale));
+        let (Ok(translations), Ok(rotations), Ok(scales)) = (
+            UnevenSampleAutoCurve::new(translations),
+            UnevenSampleAutoCurve::new(rotations),
+            UnevenSampleAutoCurve::new(scales),
+        ) else {
+            warn!("Invalid time samples for the transform of {}", prim.path);
+            return false;
+        };
+        self.animation.add_curve_to_target(
+            target,
+            AnimatableCurve::new(animated_field!(Transform::translation), translations),
+        );
+        self.animation.add_curve_to_target(
+            target,
+            AnimatableCurve::new(animated_field!(Transform::rotation), rotations),
+        );
+        self.animation.add_curve_to_target(
+            target,
+            AnimatableCurve::new(animated_field!(Transform::scale), scales),
+        );
+        self.is_animated = true;
+        true
+    }
+
+    fn add_material(&mut self, material: StandardMaterial) -> Handle<StandardMaterial> {
+        let label = UsdAssetLabel::Material(self.materials.len());
+        let handle = self
+            .load_context
+            .add_labeled_asset(label.to_string(), material);
+        self.materials.push(handle.clone());
+        handle
+    }
+
+    /// Follows the connection of an attribute, through the inputs and outputs of node graphs, to a shader output.
+    fn resolve_connection(&self, mut attribute: &'a Attribute) -> Option<(&'a Prim, &'a str)> {
+        // Bound the number of steps, in case connections form a cycle.
+        for _ in 0..16 {
+            let (prim_path, property) = split_property_path(attribute.connections.first()?)?;
+            let prim = *self.prims.get(prim_path)?;
+            if prim.type_name == "Shader" {
+                return Some((prim, property));
+            }
+            attribute = prim.attributes.get(property)?;
+            if attribute.connections.is_empty() {
+                return None;
+            }
+        }
+        None
+    }
+
+    /// Returns the value of an input of a shader, following connections to node graph and material inputs.
+    fn input_value(&self, shader: &'a Prim, name: &str) -> Option<&'a Value> {
+        let mut attribute = shader.attributes.get(name)?;
+        for _ in 0..16 {
+            if attribute.connections.is_empty() {
+                return attribute.value();
+            }
+            let (prim_path, property) = split_property_path(&attribute.connections[0])?;
+            let prim = *self.prims.get(prim_path)?;
+            attribute = prim.attributes.get(property)?;
+        }
+        None
+    }
+
+    /// Returns the texture an input of a shader is connected to.
+    fn input_texture(&self, shader: &'a Prim, name: &str) -> Option<TextureInput<'a>> {
+        let (texture, output) = self.resolve_connection(shader.attributes.get(name)?)?;
+        (texture.attribute("info:id").and_then(Value::as_str) == Some("UsdUVTexture")).then_some(
+            TextureInput {
+                shader: texture,
+                output,
+            },
+        )
+    }
+
+    /// Loads a `Material` prim with a `UsdPreviewSurface` shader, returning whether it has a normal map.
+    fn load_material(&mut self, prim: &'a Prim) -> Result<(StandardMaterial, bool), UsdError> {
+        // The defaults of `UsdPreviewSurface`.
+        let mut material = StandardMaterial {
+            base_color: Color::linear_rgb(0.18, 0.18, 0.18),
+            perceptual_roughness: 0.5,
+            metallic: 0.0,
+            ..Default::default()
+        };
+        let surface = prim
+            .attributes
+            .get("outputs:surface")
+            .and_then(|attribute| self.resolve_connection(attribute))
+            .map(|(shader, _)| shader);
+        let Some(shader) = surface.filter(|shader| {
+            shader.attribute("info:id").and_then(Value::as_str) == Some("UsdPreviewSurface")
+        }) else {
+            warn!(
+                "Material {} doesn't have a UsdPreviewSurface shader, using the default material",
+                prim.path
+            );
+            return Ok((material, false));
+        };
+
+        let number = |name: &str| self.input_value(shader, name).and_then(Value::as_f64);
+        let color = |name: &str| {
+            self.input_value(shader, name)
+                .and_then(Value::as_numbers)
+                .and_then(vec3)
+        };
+        if let Some(color) = color("inputs:diffuseColor") {
+            material.base_color = Color::linear_rgb(color.x, color.y, color.z);
+        }
+        if let Some(color) = color("inputs:emissiveColor") {
+            material.emissive = LinearRgba::rgb(color.x, color.y, color.z);
+        }
+        if let Some(metallic) = number("inputs:metallic") {
+            material.metallic = metallic as f32;
+        }
+        if let Some(roughness) = number("inputs:roughness") {
+            material.perceptual_roughness = roughness as f32;
+        }
+        if let Some(ior) = number("inputs:ior") {
+            material.ior = ior as f32;
+        }
+        let opacity = number("inputs:opacity").unwrap_or(1.0) as f32;
+        material.base_color.set_alpha(opacity);
+        let opacity_threshold = number("inputs:opacityThreshold").unwrap_or(0.0) as f32;
+        let opacity_texture = self.input_texture(shader, "inputs:opacity");
+        if opacity_threshold > 0.0 {
+            material.alpha_mode = AlphaMode::Mask(opacity_threshold);
+        } else if opacity < 1.0 || opacity_texture.is_some() {
+            material.alpha_mode = AlphaMode::Blend;
+        }
+
+        if let Some(texture) = self.input_texture(shader, "inputs:diffuseColor") {
+            material.base_color_texture = self.load_texture(texture.shader, true)?;
+            // The texture is multiplied by its scale, which is the base color.
+            if let Some(scale) = self
+                .input_value(texture.shader, "inputs:scale")
+                .and_then(Value::as_numbers)
+            {
+                if let [r, g, b, a] = *scale {
+                    material.base_color =
+                        Color::linear_rgba(r as f32, g as f32, b as f32, a as f32);
+                }
+            } else {
+                material.base_color = Color::WHITE.with_alpha(opacity);
+            }
+            if let Some(opacity) = &opacity_texture
+                && (opacity.shader.path != texture.shader.path || opacity.output != "outputs:a")
+            {
+                warn!(
+                    "Material {} has an opacity texture that isn't the alpha channel of its diffuse texture, which isn't supported",
+                    prim.path
+                );
+            }
+        }
+        if let Some(texture) = self.input_texture(shader, "inputs:emissiveColor") {
+            material.emissive_texture = self.load_texture(texture.shader, true)?;
+            material.emissive = LinearRgba::WHITE;
+        }
+
+        let metallic = self.input_texture(shader, "inputs:metallic");
+        let roughness = self.input_texture(shader, "inputs:roughness");
+        if metallic.is_some() || roughness.is_some() {
+            // Bevy reads the roughness from the green channel and the metallic value from the blue channel of the
+            // same texture, which is how they are usually packed.
+            let shaders = [(&metallic, "outputs:b"), (&roughness, "outputs:g")]
+                .into_iter()
+                .filter_map(|(input, channel)| input.as_ref().map(|input| (input, channel)))
+                .collect::<Vec<_>>();
+            let packed = shaders.iter().all(|(input, channel)| {
+                input.output == *channel && input.shader.path == shaders[0].0.shader.path
+            });
+            if packed {
+                material.metallic_roughness_texture =
+                    self.load_texture(shaders[0].0.shader, false)?;
+                if metallic.is_some() {
+                    material.metallic = 1.0;
+                }
+                if roughness.is_some() {
+                    material.perceptual_roughness = 1.0;
+                }
+            } else {
+                warn!(
+                    "Material {} has metallic or roughness textures that aren't packed in the blue and green channels of the same texture, which isn't supported",
+                    prim.path
+                );
+            }
+        }
+
+        if let Some(texture) = self.input_texture(shader, "inputs:occlusion") {
+            material.occlusion_texture = self.load_texture(texture.shader, false)?;
+        }
+        let mut normal_mapped = false;
+        if let Some(texture) = self.input_texture(shader, "inputs:normal") {
+            material.normal_map_texture = self.load_texture(texture.shader, false)?;
+            normal_mapped = material.normal_map_texture.is_some();
+        }
+        Ok((material, normal_mapped))
+    }
+
+    /// Loads the image of a `UsdUVTexture` shader.
+    fn load_texture(
+        &mut self,
+        shader: &'a Prim,
+        is_color: bool,
+    ) -> Result<Option<Handle<Image>>, UsdError> {
+        let Some(file) = self
+            .input_value(shader, "inputs:file")
+            .and_then(Value::as_str)
+        else {
+            return Ok(None);
+        };
+        let is_srgb = match self
+            .input_value(shader, "inputs:sourceColorSpace")
+            .and_then(Value::as_str)
+        {
+            Some("raw") => false,
+            Some("sRGB") => true,
+            _ => is_color,
+        };
+        if let Some(handle) = self.textures.get(&(file.to_string(), is_srgb)) {
+            return Ok(Some(handle.clone()));
+        }
+
+        let address_mode = |name: &str| match self.input_value(shader, name).and_then(Value::as_str)
+        {
+            Some("mirror") => ImageAddressMode::MirrorRepeat,
+            Some("clamp" | "black") => ImageAddressMode::ClampToEdge,
+            _ => ImageAddressMode::Repeat,
+        };
+        let sampler = ImageSamplerDescriptor {
+            address_mode_u: address_mode("inputs:wrapS"),
+            address_mode_v: address_mode("inputs:wrapT"),
+            ..ImageSamplerDescriptor::linear()
+        };
+
+        // Textures of `.usdz` packages are stored in the package, relative to the root of the package.
+        let package_path = file.trim_start_matches("./");
+        let embedded = match &mut self.package {
+            Some(package) => match package.by_name(package_path) {
+                Ok(mut entry) => {
+                    let mut bytes = Vec::new();
+                    entry.read_to_end(&mut bytes)?;
+                    Some(bytes)
+                }
+                Err(_) => None,
+            },
+            None => None,
+        };
+        let handle = if let Some(bytes) = embedded {
+            let extension = package_path.rsplit('.').next().unwrap_or_default();
+            let image = Image::from_buffer(
+                &bytes,
+                ImageType::Extension(extension),
+                self.supported_compressed_formats,
+                is_srgb,
+                ImageSampler::Descriptor(sampler),
+                self.settings.load_materials,
+            )
+            .map_err(|err| UsdError::Texture(file.into(), err))?;
+            let label = UsdAssetLabel::Texture(self.texture_count);
+            self.texture_count += 1;
+            self.load_context
+                .add_labeled_asset(label.to_string(), image)
+        } else {
+            let path = self
+                .load_context
+                .path()
+                .resolve_embed(file)
+                .map_err(|err| UsdError::InvalidTexturePath(file.into(), err))?;
+            self.load_context
+                .loader()
+                .with_settings(move |settings: &mut ImageLoaderSettings| {
+                    settings.is_srgb = is_srgb;
+                    settings.sampler = ImageSampler::Descriptor(sampler.clone());
+                })
+                .load(path)
+        };
+        self.textures
+            .insert((file.to_string(), is_srgb), handle.clone());
+        Ok(Some(handle))
+    }
+
+    /// Returns the material for a mesh, from its bound material or its display color.
+    fn mesh_material(
+        &mut self,
+        prim: &Prim,
+        binding: Option<&str>,
+        has_vertex_colors: bool,
+    ) -> Option<Handle<StandardMaterial>> {
+        if self.settings.load_materials.is_empty() {
+            return None;
+        }
+        if let Some(binding) = binding {
+            if let Some(material) = self.named_materials.get(binding) {
+                return Some(material.clone());
+            }
+            warn!("Material {binding} bound to {} doesn't exist", prim.path);
+        }
+        // Meshes without a material are shaded with their display color, which is multiplied by vertex colors.
+        let color = if has_vertex_colors {
+            LinearRgba::WHITE
+        } else {
+            let color = prim
+                .attribute("primvars:displayColor")
+                .and_then(Value::as_numbers)
+                .and_then(vec3)
+                .unwrap_or(Vec3::splat(0.18));
+            let opacity = prim
+                .attribute("primvars:displayOpacity")
+                .and_then(Value::as_numbers)
+                .and_then(|opacity| opacity.first())
+                .copied()
+                .unwrap_or(1.0);
+            LinearRgba::new(color.x, color.y, color.z, opacity as f32)
+        };
+        let key = color.to_f32_array().map(f32::to_bits);
+        if let Some(material) = self.display_color_materials.get(&key) {
+            return Some(material.clone());
+        }
+        let material = self.add_material(StandardMaterial {
+            base_color: color.into(),
+            alpha_mode: if color.alpha < 1.0 {
+                AlphaMode::Blend
+            } else {
+                AlphaMode::Opaque
+            },
+            ..Default::default()
+        });
+        self.display_color_materials.insert(key, material.clone());
+        Some(material)
+    }
+
+    /// Loads a `Mesh` prim as a Bevy mesh for each material bound to its faces.
+    fn load_mesh(
+        &mut self,
+        prim: &'a Prim,
+        binding: Option<&'a str>,
+    ) -> Vec<(Handle<Mesh>, Option<Handle<StandardMaterial>>)> {
+        let numbers = |name: &str| prim.attribute(name).and_then(Value::as_numbers);
+        let (Some(points), Some(counts), Some(indices)) = (
+            numbers("points"),
+            numbers("faceVertexCounts"),
+            numbers("faceVertexIndices"),
+        ) else {
+            warn!("Mesh {} is missing its points or faces", prim.path);
+            return Vec::new();
+        };
+        let point_count = points.len() / 3;
+        let counts = counts
+            .iter()
+            .map(|&count| count as usize)
+            .collect::<Vec<_>>();
+        let indices = indices
+            .iter()
+            .map(|&index| index as usize)
+            .collect::<Vec<_>>();
+        if counts.iter().sum::<usize>() != indices.len()
+            || indices.iter().any(|&index| index >= point_count)
+        {
+            warn!("Mesh {} has invalid faces", prim.path);
+            return Vec::new();
+        }
+        let face_starts = counts
+            .iter()
+            .scan(0, |start, &count| {
+                let face_start = *start;
+                *start += count;
+                Some(face_start)
+            })
+            .collect::<Vec<_>>();
+        let left_handed =
+            prim.attribute("orientation").and_then(Value::as_str) == Some("leftHanded");
+
+        let valid = |primvar: Option<Primvar<'a>>, name: &str| {
+            primvar.filter(|primvar| {
+                let is_valid = primvar.is_valid(counts.len(), point_count, indices.len());
+                if !is_valid {
+                    warn!("Mesh {} has an invalid number of {name}", prim.path);
+                }
+                is_valid
+            })
+        };
+        let normals = valid(
+            Primvar::new(prim, "primvars:normals", 3, Interpolation::Vertex)
+                .or_else(|| Primvar::new(prim, "normals", 3, Interpolation::Vertex)),
+            "normals",
+        );
+        // Texture coordinates are usually named `st`, but can have any name.
+        let uv_name = ["primvars:st", "primvars:st0", "primvars:UVMap"]
+            .into_iter()
+            .find(|name| prim.attributes.contains_key(*name))
+            .map(ToString::to_string)
+            .or_else(|| {
+                let mut names = prim
+                    .attributes
+                    .iter()
+                    .filter(|(name, attribute)| {
+                        name.starts_with("primvars:")
+                            && !name.ends_with(":indices")
+                            && attribute.type_name.starts_with("texCoord2")
+                    })
+                    .map(|(name, _)| name.clone())
+                    .collect::<Vec<_>>();
+                names.sort();
+                names.into_iter().next()
+            });
+        let uvs = valid(
+            uv_name.and_then(|name| Primvar::new(prim, &name, 2, Interpolation::Constant)),
+            "texture coordinates",
+        );
+        let colors = valid(
+            Primvar::new(prim, "primvars:displayColor", 3, Interpolation::Constant),
+            "display colors",
+        )
+        .filter(|colors| colors.interpolation != Interpolation::Constant);
+        let opacities = valid(
+            Primvar::new(prim, "primvars:displayOpacity", 1, Interpolation::Constant),
+            "display opacities",
+        )
+        .filter(|opacities| opacities.interpolation != Interpolation::Constant);
+        let has_vertex_colors = colors.is_some() || opacities.is_some();
+
+        // Vertices are shared between faces unless some primvars differ between the corners of a point.
+        let primvars = [&normals, &uvs, &colors, &opacities];
+        let shared_vertices = primvars.iter().all(|primvar| {
+            primvar.as_ref().is_none_or(|primvar| {
+                matches!(
+                    primvar.interpolation,
+                    Interpolation::Constant | Interpolation::Vertex
+                )
+            })
+        });
+
+        // Faces are grouped by the material bound to them, with subsets binding materials to some of the faces.
+        let mut groups = vec![(binding, Vec::new())];
+        let mut face_groups = vec![0; counts.len()];
+        for subset in &prim.children {
+            if subset.type_name != "GeomSubset"
+                || subset
+                    .attribute("elementType")
+                    .and_then(Value::as_str)
+                    .unwrap_or("face")
+                    != "face"
+            {
+                continue;
+            }
+            let Some(faces) = subset.attribute("indices").and_then(Value::as_numbers) else {
+                continue;
+            };
+            let group = groups.len();
+            groups.push((subset.target("material:binding").or(binding), Vec::new()));
+            for &face in faces {
+                if let Some(face_group) = face_groups.get_mut(face as usize) {
+                    *face_group = group;
+                }
+            }
+        }
+        for (face, group) in face_groups.into_iter().enumerate() {
+            groups[group].1.push(face);
+        }
+
+        let mut meshes = Vec::new();
+        for (material_binding, faces) in groups {
+            if faces.is_empty() {
+                continue;
+            }
+            let mut positions = Vec::new();
+            let mut vertex_normals = Vec::new();
+            let mut vertex_uvs = Vec::new();
+            let mut vertex_colors = Vec::new();
+            let mut triangles = Vec::new();
+            // Maps points (or corners when vertices aren't shared) to vertices.
+            let mut vertices = HashMap::<usize, u32>::default();
+            for &face in &faces {
+                let start = face_starts[face];
+                let corners = start..start + counts[face];
+                let mut face_vertices = Vec::with_capacity(counts[face]);
+                for corner in corners {
+                    let point = indices[corner];
+                    let key = if shared_vertices { point } else { corner };
+                    let vertex = *vertices.entry(key).or_insert_with(|| {
+                        positions.push(core::array::from_fn::<f32, 3, _>(|index| {
+                            points[point * 3 + index] as f32
+                        }));
+                        if let Some(normals) = &normals {
+                            let normal = normals.get(face, point, corner);
+                            vertex_normals.push([
+                                normal[0] as f32,
+                                normal[1] as f32,
+                                normal[2] as f32,
+                            ]);
+                        }
+                        if let Some(uvs) = &uvs {
+                            // USD texture coordinates start at the bottom of textures.
+                            let uv = uvs.get(face, point, corner);
+                            vertex_uvs.push([uv[0] as f32, 1.0 - uv[1] as f32]);
+                        }
+                        if has_vertex_colors {
+                            let color = colors.as_ref().map_or([1.0; 3], |colors| {
+                                let color = colors.get(face, point, corner);
+                                [color[0] as f32, color[1] as f32, color[2] as f32]
+                            });
+                            let opacity = opacities.as_ref().map_or(1.0, |opacities| {
+                                opacities.get(face, point, corner)[0] as f32
+                            });
+                            vertex_colors.push([color[0], color[1], color[2], opacity]);
+                        }
+                        (positions.len() - 1) as u32
+                    });
+                    face_vertices.push(vertex);
+                }
+                // Faces are triangulated as fans.
+                for index in 1..face_vertices.len().saturating_sub(1) {
+                    let (b, c) = (face_vertices[index], face_vertices[index + 1]);
+                    if left_handed {
+                        triangles.extend([face_vertices[0], c, b]);
+                    } else {
+                        triangles.extend([face_vertices[0], b, c]);
+                    }
+                }
+            }
+
+            let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, self.settings.load_meshes);
+            mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+            if !vertex_uvs.is_empty() {
+                mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vertex_uvs);
+            }
+            if !vertex_colors.is_empty() {
+                mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, vertex_colors);
+            }
+            mesh.insert_indices(Indices::U32(triangles));
+            if !vertex_normals.is_empty() {
+                mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vertex_normals);
+            } else if prim.attribute("subdivisionScheme").and_then(Value::as_str) == Some("none") {
+                // Meshes that aren't subdivision surfaces are flat shaded.
+                mesh.duplicate_vertices();
+                mesh.compute_flat_normals();
+            } else {
+                mesh.compute_smooth_normals();
+            }
+
+            let material = self.mesh_material(prim, material_binding, has_vertex_colors);
+            if material
+                .as_ref()
+                .is_some_and(|material| self.normal_mapped_materials.contains(material))
+                && mesh.contains_attribute(Mesh::ATTRIBUTE_UV_0)
+                && let Err(err) = mesh.generate_tangents()
+            {
+                warn!(
+                    "Failed to generate vertex tangents for {}: {err}",
+                    prim.path
+                );
+            }
+
+            let label = UsdAssetLabel::Mesh(self.meshes.len());
+            let handle = self.load_context.add_labeled_asset(label.to_string(), mesh);
+            self.meshes.push(handle.clone());
+            meshes.push((handle, material));
+        }
+        meshes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::EulerRot;
+
+    #[test]
+    fn evaluate_xform_ops() {
+        let stage = parse_usda(
+            r#"#usda 1.0
+def Xform "A"
+{
+    double3 xformOp:translate = (1, 2, 3)
+    float3 xformOp:rotateXYZ = (90, 0, 0)
+    float3 xformOp:scale.timeSamples = {
+        0: (1, 1, 1),
+        10: (3, 3, 3),
+    }
+    uniform token[] xformOpOrder = ["xformOp:translate", "xformOp:rotateXYZ", "xformOp:scale"]
+}
+"#,
+        )
+        .unwrap();
+        let prim = &stage.root_prims[0];
+        let transform = local_transform(prim, 5.0);
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(1.0, 2.0, 3.0), 1e-5));
+        assert!(transform
+            .rotation
+            .abs_diff_eq(Quat::from_rotation_x(core::f32::consts::FRAC_PI_2), 1e-5));
+        assert!(transform.scale.abs_diff_eq(Vec3::splat(2.0), 1e-5));
+        // Time samples are held before the first sample and after the last one.
+        assert!(local_transform(prim, 20.0)
+            .scale
+            .abs_diff_eq(Vec3::splat(3.0), 1e-5));
+
+        // Rotations around several axes are applied in the order of their name.
+        let rotation = xform_op_matrix("xformOp:rotateZYX", &[10.0, 20.0, 30.0]).unwrap();
+        let expected = Mat4::from_quat(Quat::from_euler(
+            EulerRot::XYZ,
+            10f32.to_radians(),
+            20f32.to_radians(),
+            30f32.to_radians(),
+        ));
+        assert!(rotation.abs_diff_eq(expected, 1e-5));
+    }
+}
@@ -0,0 +1,89 @@
+//! Decompression of the LZ4 blocks used by `.usdc` files.
+
+/// Decompresses data compressed with USD's `TfFastCompression`, which splits the data into LZ4 blocks.
+///
+/// Returns [`None`] if the data is invalid or decompresses to more than `max_size` bytes.
+pub(super) fn decompress(compressed: &[u8], max_size: usize) -> Option<Vec<u8>> {
+    let (&chunk_count, mut compressed) = compressed.split_first()?;
+    let mut output = Vec::with_capacity(max_size);
+    if chunk_count == 0 {
+        decompress_block(compressed, &mut output, max_size)?;
+        return Some(output);
+    }
+    for _ in 0..chunk_count {
+        let (size, rest) = compressed.split_first_chunk::<4>()?;
+        let size = usize::try_from(i32::from_le_bytes(*size)).ok()?;
+        let block = rest.get(..size)?;
+        decompress_block(block, &mut output, max_size)?;
+        compressed = &rest[size..];
+    }
+    Some(output)
+}
+
+/// Decompresses a single LZ4 block, appending it to `output`.
+fn decompress_block(mut input: &[u8], output: &mut Vec<u8>, max_size: usize) -> Option<()> {
+    fn read_length(input: &mut &[u8], mut length: usize) -> Option<usize> {
+        if length == 15 {
+            loop {
+                let (&byte, rest) = input.split_first()?;
+                *input = rest;
+                length += byte as usize;
+                if byte != 255 {
+                    break;
+                }
+            }
+        }
+        Some(length)
+    }
+
+    let block_start = output.len();
+    while let Some((&token, rest)) = input.split_first() {
+        input = rest;
+        let literal_length = read_length(&mut input, (token >> 4) as usize)?;
+        let literals = input.get(..literal_length)?;
+        if output.len() + literal_length > max_size {
+            return None;
+        }
+        output.extend_from_slice(literals);
+        input = &input[literal_length..];
+        // The last sequence only contains literals.
+        let Some((offset, rest)) = input.split_first_chunk::<2>() else {
+            break;
+        };
+        input = rest;
+        let offset = u16::from_le_bytes(*offset) as usize;
+        let match_length = read_length(&mut input, (token & 0xF) as usize)? + 4;
+        if offset == 0
+            || offset > output.len() - block_start
+            || output.len() + match_length > max_size
+        {
+            return None;
+        }
+        // Matches can overlap the bytes they produce, so they are copied byte by byte.
+        let start = output.len() - offset;
+        for index in start..start + match_length {
+            output.push(output[index]);
+        }
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decompress_blocks() {
+        // "abc" as literals, then a match of 6 bytes at offset 3, then "d" as the last literal.
+        let block = [0x32, b'a', b'b', b'c', 3, 0, 0x10, b'd'];
+        let mut compressed = vec![0];
+        compressed.extend_from_slice(&block);
+        assert_eq!(decompress(&compressed, 100).unwrap(), b"abcabcabcd");
+        // The output can't be larger than the maximum size.
+        assert!(decompress(&compressed, 5).is_none());
+
+        // Two chunks, each with a single literal run.
+        let compressed = [2, 2, 0, 0, 0, 0x10, b'x', 3, 0, 0, 0, 0x20, b'y', b'z'];
+        assert_eq!(decompress(&compressed, 100).unwrap(), b"xyz");
+    }
+}
@@ -0,0 +1,288 @@
+//! An in-memory representation of a USD layer, parsed from `.usda` or `.usdc` files.
+//!
+//! Only the data that the loader converts is kept: the prim hierarchy, attribute values (both default
+//! values and time samples), relationship targets, attribute connections and a few pieces of layer
+//! metadata. Composition arcs (references, payloads, sublayers, inherits and variants) aren't resolved.
+
+mod lz4;
+mod usda;
+mod usdc;
+
+use bevy_platform::collections::HashMap;
+use thiserror::Error;
+
+pub(crate) use usda::parse_usda;
+pub(crate) use usdc::parse_usdc;
+
+/// An error that occurs when parsing a USD layer.
+#[derive(Error, Debug)]
+pub enum UsdParseError {
+    /// The text of a `.usda` layer is invalid.
+    #[error("invalid usda at line {line}: {message}")]
+    InvalidUsda {
+        /// The line at which the error occurred.
+        line: usize,
+        /// A description of the error.
+        message: String,
+    },
+    /// The contents of a `.usdc` layer are invalid.
+    #[error("invalid usdc: {0}")]
+    InvalidUsdc(String),
+    /// The `.usdc` layer was written with a version of the crate format that isn't supported.
+    #[error("unsupported usdc version {0}.{1}.{2}")]
+    UnsupportedUsdcVersion(u8, u8, u8),
+}
+
+/// The value of an attribute.
+///
+/// Numeric values are all stored as `f64`. Vectors, colors, quaternions and matrices are stored as
+/// their flattened components, quaternions in `x, y, z, w` order and matrices in row-major order.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Value {
+    Bool(bool),
+    Number(f64),
+    Numbers(Vec<f64>),
+    Token(String),
+    Tokens(Vec<String>),
+    AssetPath(String),
+}
+
+impl Value {
+    pub(crate) fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Bool(value) => Some(*value as u8 as f64),
+            Value::Number(value) => Some(*value),
+            Value::Numbers(values) if values.len() == 1 => Some(values[0]),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_numbers(&self) -> Option<&[f64]> {
+        match self {
+            Value::Number(value) => Some(core::slice::from_ref(value)),
+            Value::Numbers(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Token(value) | Value::AssetPath(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_strs(&self) -> Option<&[String]> {
+        match self {
+            Value::Token(value) => Some(core::slice::from_ref(value)),
+            Value::Tokens(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+/// An attribute of a [`Prim`].
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Attribute {
+    /// The declared type of the attribute, such as `point3f[]`.
+    pub type_name: String,
+    pub default: Option<Value>,
+    /// The time samples of the attribute, sorted by time. A [`None`] value blocks the attribute.
+    pub time_samples: Vec<(f64, Option<Value>)>,
+    /// The absolute paths of the properties this attribute is connected to.
+    pub connections: Vec<String>,
+    /// The `interpolation` metadata of primvars.
+    pub interpolation: Option<String>,
+}
+
+impl Attribute {
+    /// Returns the value of the attribute when it isn't animated, which is either its default value or its
+    /// first time sample.
+    pub(crate) fn value(&self) -> Option<&Value> {
+        self.default.as_ref().or_else(|| {
+            self.time_samples
+                .first()
+                .and_then(|(_, value)| value.as_ref())
+        })
+    }
+}
+
+/// How a [`Prim`] is specified in its layer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum Specifier {
+    #[default]
+    Def,
+    Over,
+    Class,
+}
+
+/// A prim of a USD layer.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Prim {
+    pub name: String,
+    /// The absolute path of the prim, such as `/World/Cube`.
+    pub path: String,
+    pub specifier: Specifier,
+    pub type_name: String,
+    pub attributes: HashMap<String, Attribute>,
+    /// The absolute target paths of each relationship.
+    pub relationships: HashMap<String, Vec<String>>,
+    pub children: Vec<Prim>,
+}
+
+impl Prim {
+    pub(crate) fn attribute(&self, name: &str) -> Option<&Value> {
+        self.attributes.get(name).and_then(Attribute::value)
+    }
+
+    /// Returns the first target of the relationship `name`.
+    pub(crate) fn target(&self, name: &str) -> Option<&str> {
+        self.relationships
+            .get(name)
+            .and_then(|targets| targets.first())
+            .map(String::as_str)
+    }
+}
+
+/// The up axis of a stage.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum UpAxis {
+    #[default]
+    Y,
+    Z,
+}
+
+/// A parsed USD layer.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Stage {
+    pub root_prims: Vec<Prim>,
+    pub default_prim: Option<String>,
+    pub up_axis: UpAxis,
+    /// The size of a unit, if authored. USD defines centimeters as the default, but scaling layers that don't
+    /// specify it would shrink most hand-written layers.
+    pub meters_per_unit: Option<f64>,
+    pub time_codes_per_second: Option<f64>,
+    pub frames_per_second: Option<f64>,
+    pub start_time_code: Option<f64>,
+}
+
+impl Stage {
+    /// Sets a piece of layer metadata, ignoring the keys the loader doesn't use.
+    fn set_metadata(&mut self, key: &str, value: &Value) {
+        match key {
+            "defaultPrim" => self.default_prim = value.as_str().map(Into::into),
+            "upAxis" => {
+                self.up_axis = match value.as_str() {
+                    Some("Z") => UpAxis::Z,
+                    _ => UpAxis::Y,
+                }
+            }
+            "metersPerUnit" => self.meters_per_unit = value.as_f64(),
+            "timeCodesPerSecond" => self.time_codes_per_second = value.as_f64(),
+            "framesPerSecond" => self.frames_per_second = value.as_f64(),
+            "startTimeCode" => self.start_time_code = value.as_f64(),
+            _ => {}
+        }
+    }
+
+    /// Returns the number of time codes per second, which is used to convert time samples to seconds.
+    #[cfg_attr(
+        not(feature = "bevy_animation"),
+        expect(dead_code, reason = "only used to load animations")
+    )]
+    pub(crate) fn time_codes_per_second(&self) -> f64 {
+        // Like USD, fall back to the frames per second before the default of 24.
+        self.time_codes_per_second
+            .or(self.frames_per_second)
+            .unwrap_or(24.0)
+    }
+
+    /// Returns every prim of the stage, indexed by path.
+    pub(crate) fn prims_by_path(&self) -> HashMap<&str, &Prim> {
+        fn add<'a>(prim: &'a Prim, prims: &mut HashMap<&'a str, &'a Prim>) {
+            prims.insert(&prim.path, prim);
+            for child in &prim.children {
+                add(child, prims);
+            }
+        }
+        let mut prims = HashMap::default();
+        for prim in &self.root_prims {
+            add(prim, &mut prims);
+        }
+        prims
+    }
+}
+
+/// Resolves a path relative to the prim at `anchor`, returning an absolute path.
+pub(crate) fn resolve_path(anchor: &str, path: &str) -> String {
+    if path.starts_with('/') {
+        return path.into();
+    }
+    let mut resolved = anchor.trim_end_matches('/').to_string();
+    let mut rest = path;
+    loop {
+        if let Some(next) = rest.strip_prefix("../") {
+            if let Some(index) = resolved.rfind('/') {
+                resolved.truncate(index);
+            }
+            rest = next;
+        } else if let Some(next) = rest.strip_prefix("./") {
+            rest = next;
+        } else {
+            break;
+        }
+    }
+    if rest == ".." {
+        if let Some(index) = resolved.rfind('/') {
+            resolved.truncate(index);
+        }
+        rest = "";
+    }
+    if rest.is_empty() {
+        if resolved.is_empty() {
+            resolved.push('/');
+        }
+        return resolved;
+    }
+    // Properties are separated from their prim with a `.` instead of a `/`.
+    if !rest.starts_with('.') {
+        resolved.push('/');
+    }
+    resolved.push_str(rest);
+    resolved
+}
+
+/// Splits a property path such as `/Looks/Material/Shader.outputs:rgb` into its prim path and property name.
+pub(crate) fn split_property_path(path: &str) -> Option<(&str, &str)> {
+    let separator = path.rfind('.')?;
+    // A `.` before the last `/` is part of a prim name (which isn't valid), not a property separator.
+    if path[separator..].contains('/') {
+        return None;
+    }
+    Some((&path[..separator], &path[separator + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_relative_paths() {
+        assert_eq!(resolve_path("/World/Cube", "/Looks/Red"), "/Looks/Red");
+        assert_eq!(
+            resolve_path("/World/Cube", "../Looks/Red"),
+            "/World/Looks/Red"
+        );
+        assert_eq!(resolve_path("/World/Cube", "Shader"), "/World/Cube/Shader");
+        assert_eq!(
+            resolve_path("/World/Material", "./Shader.outputs:rgb"),
+            "/World/Material/Shader.outputs:rgb"
+        );
+        assert_eq!(resolve_path("/World", ".."), "/");
+        assert_eq!(
+            split_property_path("/World/Shader.outputs:rgb"),
+            Some(("/World/Shader", "outputs:rgb"))
+        );
+        assert_eq!(split_property_path("/World/Shader"), None);
+    }
+}
@@ -0,0 +1,772 @@
+//! Parsing of the text (`.usda`) format of USD layers.
+
+use super::{resolve_path, Attribute, Prim, Specifier, Stage, UsdParseError, Value};
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token<'a> {
+    Punct(char),
+    Ident(&'a str),
+    Number(f64),
+    String(String),
+    AssetPath(String),
+    Path(&'a str),
+    Eof,
+}
+
+/// Splits the text of a layer into tokens, along with the line they start at.
+fn tokenize(text: &str) -> Result<Vec<(Token<'_>, usize)>, UsdParseError> {
+    let bytes = text.as_bytes();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut index = 0;
+    let error = |line, message: &str| UsdParseError::InvalidUsda {
+        line,
+        message: message.into(),
+    };
+    while index < bytes.len() {
+        let byte = bytes[index];
+        let start = index;
+        let token = match byte {
+            b'\n' => {
+                line += 1;
+                index += 1;
+                continue;
+            }
+            _ if byte.is_ascii_whitespace() => {
+                index += 1;
+                continue;
+            }
+            b'#' => {
+                while index < bytes.len() && bytes[index] != b'\n' {
+                    index += 1;
+                }
+                continue;
+            }
+            b'(' | b')' | b'[' | b']' | b'{' | b'}' | b'=' | b',' | b';' | b':' => {
+                index += 1;
+                Token::Punct(byte as char)
+            }
+            b'"' | b'\'' => {
+                let token_line = line;
+                let triple = bytes[index..].starts_with(&[byte; 3]);
+                index += if triple { 3 } else { 1 };
+                let mut string = String::new();
+                loop {
+                    let Some(&next) = bytes.get(index) else {
+                        return Err(error(token_line, "unterminated string"));
+                    };
+                    if triple && bytes[index..].starts_with(&[byte; 3]) {
+                        index += 3;
+                        break;
+                    } else if !triple && next == byte {
+                        index += 1;
+                        break;
+                    } else if next == b'\\' {
+                        let Some(&escaped) = bytes.get(index + 1) else {
+                            return Err(error(token_line, "unterminated string"));
+                        };
+                        string.push(match escaped {
+                            b'n' => '\n',
+                            b't' => '\t',
+                            b'r' => '\r',
+                            _ => escaped as char,
+                        });
+                        index += 2;
+                    } else {
+                        // Copy whole UTF-8 characters.
+                        let character = text[index..].chars().next().unwrap();
+                        if character == '\n' {
+                            line += 1;
+                        }
+                        string.push(character);
+                        index += character.len_utf8();
+                    }
+                }
+                tokens.push((Token::String(string), token_line));
+                continue;
+            }
+            b'@' => {
+                let delimiter: &[u8] = if bytes[index..].starts_with(b"@@@") {
+                    b"@@@"
+                } else {
+                    b"@"
+                };
+                index += delimiter.len();
+                let Some(length) = text[index..].find(core::str::from_utf8(delimiter).unwrap())
+                else {
+                    return Err(error(line, "unterminated asset path"));
+                };
+                let path = text[index..index + length].to_string();
+                index += length + delimiter.len();
+                Token::AssetPath(path)
+            }
+            b'<' => {
+                let Some(length) = text[index..].find('>') else {
+                    return Err(error(line, "unterminated path"));
+                };
+                index += length + 1;
+                Token::Path(&text[start + 1..index - 1])
+            }
+            b'-' | b'+' | b'.' | b'0'..=b'9' => {
+                if text[index..].starts_with("-inf") {
+                    index += 4;
+                    Token::Number(f64::NEG_INFINITY)
+                } else {
+                    index += 1;
+                    while index < bytes.len()
+                        && (bytes[index].is_ascii_alphanumeric()
+                            || bytes[index] == b'.'
+                            || ((bytes[index] == b'-' || bytes[index] == b'+')
+                                && matches!(bytes[index - 1], b'e' | b'E')))
+                    {
+                        index += 1;
+                    }
+                    let number = text[start..index]
+                        .parse()
+                        .map_err(|_| error(line, "invalid number"))?;
+                    Token::Number(number)
+                }
+            }
+            _ if byte.is_ascii_alphabetic() || byte == b'_' => {
+                while index < bytes.len()
+                    && (bytes[index].is_ascii_alphanumeric()
+                        || matches!(bytes[index], b'_' | b':' | b'.'))
+                {
+                    index += 1;
+                }
+                match &text[start..index] {
+                    "inf" => Token::Number(f64::INFINITY),
+                    "nan" => Token::Number(f64::NAN),
+                    ident => Token::Ident(ident),
+                }
+            }
+            _ => return Err(error(line, "unexpected character")),
+        };
+        tokens.push((token, line));
+    }
+    tokens.push((Token::Eof, line));
+    Ok(tokens)
+}
+
+/// A value as it is written, before its type is known.
+#[derive(Debug)]
+enum RawValue {
+    Number(f64),
+    String(String),
+    Ident(String),
+    AssetPath(String),
+    Path(String),
+    List(Vec<RawValue>),
+    /// A value the loader doesn't use, such as a dictionary.
+    Skipped,
+}
+
+impl RawValue {
+    fn as_value(&self) -> Option<Value> {
+        match self {
+            RawValue::Number(number) => Some(Value::Number(*number)),
+            RawValue::String(string) | RawValue::Ident(string) => {
+                Some(Value::Token(string.clone()))
+            }
+            RawValue::AssetPath(path) => Some(Value::AssetPath(path.clone())),
+            _ => None,
+        }
+    }
+
+    fn flatten_numbers(&self, numbers: &mut Vec<f64>) -> bool {
+        match self {
+            RawValue::Number(number) => numbers.push(*number),
+            RawValue::Ident(ident) if ident == "true" => numbers.push(1.0),
+            RawValue::Ident(ident) if ident == "false" => numbers.push(0.0),
+            RawValue::List(items) => {
+                return items.iter().all(|item| item.flatten_numbers(numbers));
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Converts the value to an attribute [`Value`] of the type `type_name`. Returns [`None`] for blocked values
+    /// and values of types that aren't supported.
+    fn to_value(&self, type_name: &str) -> Option<Value> {
+        if matches!(self, RawValue::Ident(ident) if ident == "None") {
+            return None;
+        }
+        let (base_type, is_array) = match type_name.strip_suffix("[]") {
+            Some(base_type) => (base_type, true),
+            None => (type_name, false),
+        };
+        match base_type {
+            "bool" if !is_array => match self {
+                RawValue::Ident(ident) => Some(Value::Bool(ident == "true")),
+                RawValue::Number(number) => Some(Value::Bool(*number != 0.0)),
+                _ => None,
+            },
+            "token" | "string" => match self {
+                RawValue::String(string) if !is_array => Some(Value::Token(string.clone())),
+                RawValue::List(items) if is_array => items
+                    .iter()
+                    .map(|item| match item {
+                        RawValue::String(string) => Some(string.clone()),
+                        _ => None,
+                    })
+                    .collect::<Option<_>>()
+                    .map(Value::Tokens),
+                _ => None,
+            },
+            "asset" => match self {
+                RawValue::AssetPath(path) if !is_array => Some(Value::AssetPath(path.clone())),
+                _ => None,
+            },
+            _ => {
+                let mut numbers = Vec::new();
+                if !self.flatten_numbers(&mut numbers) {
+                    return None;
+                }
+                if base_type.starts_with("quat") {
+                    // Quaternions are written with their real part first.
+                    for quat in numbers.chunks_exact_mut(4) {
+                        quat.rotate_left(1);
+                    }
+                }
+                if !is_array && numbers.len() == 1 && !matches!(self, RawValue::List(_)) {
+                    Some(Value::Number(numbers[0]))
+                } else {
+                    Some(Value::Numbers(numbers))
+                }
+            }
+        }
+    }
+}
+
+struct Parser<'a> {
+    tokens: Vec<(Token<'a>, usize)>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> &Token<'a> {
+        &self.tokens[self.position].0
+    }
+
+    fn next(&mut self) -> Token<'a> {
+        let token = self.tokens[self.position].0.clone();
+        if token != Token::Eof {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn error(&self, message: impl Into<String>) -> UsdParseError {
+        UsdParseError::InvalidUsda {
+            line: self.tokens[self.position.saturating_sub(1)].1,
+            message: message.into(),
+        }
+    }
+
+    fn eat(&mut self, punct: char) -> bool {
+        if *self.peek() == Token::Punct(punct) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, punct: char) -> Result<(), UsdParseError> {
+        match self.next() {
+            Token::Punct(next) if next == punct => Ok(()),
+            token => Err(self.error(format!("expected `{punct}`, found {token:?}"))),
+        }
+    }
+
+    fn ident(&mut self) -> Result<&'a str, UsdParseError> {
+        match self.next() {
+            Token::Ident(ident) => Ok(ident),
+            token => Err(self.error(format!("expected an identifier, found {token:?}"))),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, UsdParseError> {
+        match self.next() {
+            Token::String(string) => Ok(string),
+            token => Err(self.error(format!("expected a string, found {token:?}"))),
+        }
+    }
+
+    /// Skips tokens until the bracket matching the already consumed `open` bracket.
+    fn skip_balanced(&mut self, open: char) -> Result<(), UsdParseError> {
+        let mut stack = vec![open];
+        while let Some(&open) = stack.last() {
+            match self.next() {
+                Token::Punct(punct @ ('(' | '[' | '{')) => stack.push(punct),
+                Token::Punct(punct @ (')' | ']' | '}')) => {
+                    let expected = match open {
+                        '(' => ')',
+                        '[' => ']',
+                        _ => '}',
+                    };
+                    if punct != expected {
+                        return Err(self.error(format!("expected `{expected}`, found `{punct}`")));
+                    }
+                    stack.pop();
+                }
+                Token::Eof => return Err(self.error("unexpected end of file")),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn raw_value(&mut self) -> Result<RawValue, UsdParseError> {
+        Ok(match self.next() {
+            Token::Number(number) => RawValue::Number(number),
+            Token::String(string) => RawValue::String(string),
+            Token::Ident(ident) => RawValue::Ident(ident.into()),
+            Token::Path(path) => RawValue::Path(path.into()),
+            Token::AssetPath(path) => {
+                // References and payloads can target a prim of the asset, and have a layer offset.
+                if let Token::Path(_) = self.peek() {
+                    self.next();
+                }
+                if self.eat('(') {
+                    self.skip_balanced('(')?;
+                }
+                RawValue::AssetPath(path)
+            }
+            Token::Punct(open @ ('(' | '[')) => {
+                let close = if open == '(' { ')' } else { ']' };
+                let mut items = Vec::new();
+                while !self.eat(close) {
+                    items.push(self.raw_value()?);
+                    if !self.eat(',') {
+                        self.expect(close)?;
+                        break;
+                    }
+                }
+                RawValue::List(items)
+            }
+            Token::Punct('{') => {
+                self.skip_balanced('{')?;
+                RawValue::Skipped
+            }
+            token => return Err(self.error(format!("expected a value, found {token:?}"))),
+        })
+    }
+
+    /// Parses a metadata block, after its opening parenthesis, calling `f` with each key and value.
+    fn metadata(&mut self, mut f: impl FnMut(&str, RawValue)) -> Result<(), UsdParseError> {
+        loop {
+            match self.next() {
+                Token::Punct(')') => return Ok(()),
+                // Documentation strings don't have a key.
+                Token::Punct(';') | Token::String(_) => {}
+                Token::Ident(mut key) => {
+                    let list_op =
+                        matches!(key, "add" | "prepend" | "append" | "delete" | "reorder");
+                    if list_op {
+                        key = self.ident()?;
+                    }
+                    // Typed entries of dictionaries, such as `string name = "value"`.
+                    if let Token::Ident(name) = *self.peek() {
+                        self.next();
+                        key = name;
+                    }
+                    self.expect('=')?;
+                    let value = self.raw_value()?;
+                    if !list_op {
+                        f(key, value);
+                    }
+                }
+                token => return Err(self.error(format!("unexpected {token:?} in metadata"))),
+            }
+        }
+    }
+
+    fn paths(&self, raw: RawValue, anchor: &str) -> Vec<String> {
+        match raw {
+            RawValue::Path(path) => vec![resolve_path(anchor, &path)],
+            RawValue::List(items) => items
+                .into_iter()
+                .filter_map(|item| match item {
+                    RawValue::Path(path) => Some(resolve_path(anchor, &path)),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Parses a prim after its specifier. Returns [`None`] for inactive prims.
+    fn prim(
+        &mut self,
+        specifier: Specifier,
+        parent_path: &str,
+    ) -> Result<Option<Prim>, UsdParseError> {
+        let type_name = match *self.peek() {
+            Token::Ident(type_name) => {
+                self.next();
+                type_name
+            }
+            _ => "",
+        };
+        let name = self.string()?;
+        let mut prim = Prim {
+            path: format!("{}/{name}", parent_path.trim_end_matches('/')),
+            name,
+            specifier,
+            type_name: type_name.into(),
+            ..Default::default()
+        };
+        let mut active = true;
+        if self.eat('(') {
+            self.metadata(|key, value| {
+                if key == "active" {
+                    active = !matches!(value, RawValue::Ident(ident) if ident == "false");
+                }
+            })?;
+        }
+        self.expect('{')?;
+        while !self.eat('}') {
+            self.property_or_child(&mut prim)?;
+        }
+        Ok(active.then_some(prim))
+    }
+
+    fn property_or_child(&mut self, prim: &mut Prim) -> Result<(), UsdParseError> {
+        let mut keyword = self.ident()?;
+        match keyword {
+            "def" | "over" | "class" => {
+                let specifier = match keyword {
+                    "def" => Specifier::Def,
+                    "over" => Specifier::Over,
+                    _ => Specifier::Class,
+                };
+                if let Some(child) = self.prim(specifier, &prim.path.clone())? {
+                    prim.children.push(child);
+                }
+                return Ok(());
+            }
+            "variantSet" => {
+                self.string()?;
+                self.expect('=')?;
+                self.expect('{')?;
+                return self.skip_balanced('{');
+            }
+            "reorder" => {
+                self.ident()?;
+                self.expect('=')?;
+                self.raw_value()?;
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        let mut deleted = false;
+        if matches!(keyword, "add" | "prepend" | "append" | "delete") {
+            deleted = keyword == "delete";
+            keyword = self.ident()?;
+        }
+        if keyword == "custom" {
+            keyword = self.ident()?;
+        }
+
+        if keyword == "rel" {
+            let name = self.ident()?;
+            let targets = if self.eat('=') {
+                let raw = self.raw_value()?;
+                self.paths(raw, &prim.path)
+            } else {
+                Vec::new()
+            };
+            if self.eat('(') {
+                self.metadata(|_, _| {})?;
+            }
+            if !deleted {
+                prim.relationships
+                    .entry(name.into())
+                    .or_default()
+                    .extend(targets);
+            }
+            return Ok(());
+        }
+
+        if matches!(keyword, "uniform" | "varying" | "config") {
+            keyword = self.ident()?;
+        }
+        let mut type_name = keyword.to_string();
+        if self.eat('[') {
+            self.expect(']')?;
+            type_name.push_str("[]");
+        }
+        let name = self.ident()?;
+        let (name, suffix) = match name.rsplit_once('.') {
+            Some((name, suffix @ ("connect" | "timeSamples" | "spline"))) => (name, suffix),
+            _ => (name, ""),
+        };
+
+        let mut attribute = Attribute {
+            type_name: type_name.clone(),
+            ..Default::default()
+        };
+        if self.eat('=') {
+            match suffix {
+                "connect" => {
+                    let raw = self.raw_value()?;
+                    attribute.connections = self.paths(raw, &prim.path);
+                }
+                "timeSamples" => {
+                    self.expect('{')?;
+                    while !self.eat('}') {
+                        let Token::Number(time) = self.next() else {
+                            return Err(self.error("expected a time"));
+                        };
+                        self.expect(':')?;
+                        let value = self.raw_value()?.to_value(&type_name);
+                        attribute.time_samples.push((time, value));
+                        if !self.eat(',') {
+                            self.expect('}')?;
+                            break;
+                        }
+                    }
+                    attribute
+                        .time_samples
+                        .sort_by(|(a, _), (b, _)| a.total_cmp(b));
+                }
+                "spline" => {
+                    self.expect('{')?;
+                    self.skip_balanced('{')?;
+                }
+                _ => attribute.default = self.raw_value()?.to_value(&type_name),
+            }
+        }
+        if self.eat('(') {
+            self.metadata(|key, value| {
+                if key == "interpolation" {
+                    attribute.interpolation = value.as_value().and_then(|value| match value {
+                        Value::Token(value) => Some(value),
+                        _ => None,
+                    });
+                }
+            })?;
+        }
+        if deleted {
+            return Ok(());
+        }
+
+        // An attribute can be declared over several statements, for example one for its default value and one
+        // for its time samples.
+        let existing = prim.attributes.entry(name.into()).or_default();
+        existing.type_name = attribute.type_name;
+        if attribute.default.is_some() {
+            existing.default = attribute.default;
+        }
+        if !attribute.time_samples.is_empty() {
+            existing.time_samples = attribute.time_samples;
+        }
+        existing.connections.extend(attribute.connections);
+        if attribute.interpolation.is_some() {
+            existing.interpolation = attribute.interpolation;
+        }
+        Ok(())
+    }
+}
+
+/// Parses the text of a `.usda` layer.
+pub(crate) fn parse_usda(text: &str) -> Result<Stage, UsdParseError> {
+    if !text.starts_with("#usda") {
+        return Err(UsdParseError::InvalidUsda {
+            line: 1,
+            message: "missing `#usda` header".into(),
+        });
+    }
+    let mut parser = Parser {
+        tokens: tokenize(text)?,
+        position: 0,
+    };
+    let mut stage = Stage::default();
+    if parser.eat('(') {
+        parser.metadata(|key, value| {
+            if let Some(value) = value.as_value() {
+                stage.set_metadata(key, &value);
+            }
+        })?;
+    }
+    while *parser.peek() != Token::Eof {
+        let specifier = match parser.ident()? {
+            "def" => Specifier::Def,
+            "over" => Specifier::Over,
+            "class" => Specifier::Class,
+            keyword => return Err(parser.error(format!("unexpected `{keyword}`"))),
+        };
+        if let Some(prim) = parser.prim(specifier, "/")? {
+            stage.root_prims.push(prim);
+        }
+    }
+    Ok(stage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAYER: &str = r#"#usda 1.0
+(
+    """A test layer."""
+    defaultPrim = "World"
+    upAxis = "Z"
+    metersPerUnit = 0.01
+    customLayerData = {
+        string creator = "test"
+    }
+)
+
+def Xform "World" (
+    kind = "component"
+    prepend references = @./other.usda@</Other> (offset = 10)
+)
+{
+    double3 xformOp:translate = (1, 2, 3)
+    float xformOp:rotateY.timeSamples = {
+        0: 0,
+        24: 90,
+    }
+    quatf xformOp:orient = (1, 0, 0, 0)
+    uniform token[] xformOpOrder = ["xformOp:translate", "xformOp:rotateY", "xformOp:orient"]
+
+    def Mesh "Triangle"
+    {
+        int[] faceVertexCounts = [3]
+        int[] faceVertexIndices = [0, 1, 2]
+        point3f[] points = [(0, 0, 0), (1, 0, 0), (0, 1, 0)]
+        color3f[] primvars:displayColor = [(1, 0.5, 0)] (
+            interpolation = "constant"
+        )
+        rel material:binding = <../Looks/Red>
+    }
+
+    def Xform "Inactive" (
+        active = false
+    )
+    {
+    }
+
+    def Scope "Looks"
+    {
+        def Material "Red"
+        {
+            token outputs:surface.connect = </World/Looks/Red/Surface.outputs:surface>
+
+            def Shader "Surface"
+            {
+                uniform token info:id = "UsdPreviewSurface"
+                color3f inputs:diffuseColor = (1, 0, 0)
+                float inputs:roughness = 0.5
+                asset inputs:file = @textures/red.png@
+                token outputs:surface
+            }
+        }
+    }
+
+    variantSet "color" = {
+        "blue" {
+            def Xform "Blue" {}
+        }
+    }
+}
+"#;
+
+    #[test]
+    fn parse_layer() {
+        let stage = parse_usda(LAYER).unwrap();
+        assert_eq!(stage.default_prim.as_deref(), Some("World"));
+        assert_eq!(stage.up_axis, super::super::UpAxis::Z);
+        assert_eq!(stage.meters_per_unit, Some(0.01));
+
+        let world = &stage.root_prims[0];
+        assert_eq!(world.type_name, "Xform");
+        assert_eq!(
+            world.attribute("xformOp:translate"),
+            Some(&Value::Numbers(vec![1.0, 2.0, 3.0]))
+        );
+        assert_eq!(
+            world.attributes["xformOp:rotateY"].time_samples,
+            vec![
+                (0.0, Some(Value::Number(0.0))),
+                (24.0, Some(Value::Number(90.0)))
+            ]
+        );
+        assert_eq!(
+            world.attribute("xformOp:orient"),
+            Some(&Value::Numbers(vec![0.0, 0.0, 0.0, 1.0]))
+        );
+        assert_eq!(
+            world
+                .attribute("xformOpOrder")
+                .unwrap()
+                .as_strs()
+                .unwrap()
+                .len(),
+            3
+        );
+        // Inactive prims and variants are skipped.
+        let children = world
+            .children
+            .iter()
+            .map(|child| child.path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(children, ["/World/Triangle", "/World/Looks"]);
+
+        let triangle = &world.children[0];
+        assert_eq!(
+            triangle
+                .attribute("points")
+                .unwrap()
+                .as_numbers()
+                .unwrap()
+                .len(),
+            9
+        );
+        assert_eq!(
+            triangle.attributes["primvars:displayColor"]
+                .interpolation
+                .as_deref(),
+            Some("constant")
+        );
+        assert_eq!(
+            triangle.target("material:binding"),
+            Some("/World/Looks/Red")
+        );
+
+        let prims = stage.prims_by_path();
+        let material = prims["/World/Looks/Red"];
+        assert_eq!(
+            material.attributes["outputs:surface"].connections,
+            ["/World/Looks/Red/Surface.outputs:surface"]
+        );
+        let shader = prims["/World/Looks/Red/Surface"];
+        assert_eq!(
+            shader.attribute("info:id"),
+            Some(&Value::Token("UsdPreviewSurface".into()))
+        );
+        assert_eq!(
+            shader.attribute("inputs:roughness"),
+            Some(&Value::Number(0.5))
+        );
+        assert_eq!(
+            shader.attribute("inputs:file"),
+            Some(&Value::AssetPath("textures/red.png".into()))
+        );
+        assert!(shader.attributes["outputs:surface"].value().is_none());
+    }
+
+    #[test]
+    fn report_error_line() {
+        let Err(UsdParseError::InvalidUsda { line, .. }) =
+            parse_usda("#usda 1.0\n\ndef Xform \"World\" {\n    float a = )\n}\n")
+        else {
+            panic!("expected an error");
+        };
+        assert_eq!(line, 4);
+    }
+}
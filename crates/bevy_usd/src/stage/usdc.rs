@@ -0,0 +1,1057 @@
+//! Parsing of the binary "crate" (`.usdc`) format of USD layers.
+
+use super::{lz4, Attribute, Prim, Specifier, Stage, UsdParseError, Value};
+use bevy_platform::collections::HashMap;
+
+/// The number of elements below which arrays aren't compressed.
+const MIN_COMPRESSED_ARRAY_SIZE: usize = 16;
+
+const SPEC_TYPE_ATTRIBUTE: u32 = 1;
+const SPEC_TYPE_PRIM: u32 = 6;
+const SPEC_TYPE_PSEUDO_ROOT: u32 = 7;
+const SPEC_TYPE_RELATIONSHIP: u32 = 8;
+
+fn invalid(message: impl Into<String>) -> UsdParseError {
+    UsdParseError::InvalidUsdc(message.into())
+}
+
+/// Reads little-endian values from the bytes of a crate file.
+#[derive(Clone)]
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn at(data: &'a [u8], position: usize) -> Self {
+        Self { data, position }
+    }
+
+    fn bytes(&mut self, length: usize) -> Result<&'a [u8], UsdParseError> {
+        let bytes = self
+            .position
+            .checked_add(length)
+            .and_then(|end| self.data.get(self.position..end))
+            .ok_or_else(|| invalid("unexpected end of file"))?;
+        self.position += length;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], UsdParseError> {
+        Ok(self.bytes(N)?.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8, UsdParseError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, UsdParseError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, UsdParseError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn i64(&mut self) -> Result<i64, UsdParseError> {
+        Ok(i64::from_le_bytes(self.array()?))
+    }
+
+    /// Reads a count, checking that it isn't larger than the file so it can be used to allocate memory.
+    fn count(&mut self) -> Result<usize, UsdParseError> {
+        usize::try_from(self.u64()?)
+            .ok()
+            .filter(|&count| count <= self.data.len())
+            .ok_or_else(|| invalid("invalid count"))
+    }
+
+    /// Follows a relative offset, as written before nested values.
+    fn jump(&mut self) -> Result<(), UsdParseError> {
+        let start = self.position as i64;
+        let offset = self.i64()?;
+        self.position = usize::try_from(start + offset).map_err(|_| invalid("invalid offset"))?;
+        Ok(())
+    }
+
+    fn u32s(&mut self, count: usize) -> Result<Vec<u32>, UsdParseError> {
+        Ok(self
+            .bytes(count * 4)?
+            .chunks_exact(4)
+            .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect())
+    }
+
+    /// Reads integers compressed with USD's integer coding, followed by LZ4.
+    fn compressed_ints(&mut self, count: usize, wide: bool) -> Result<Vec<i64>, UsdParseError> {
+        let size = self.count()?;
+        let compressed = self.bytes(size)?;
+        let int_size = if wide { 8 } else { 4 };
+        let max_size = int_size + (count * 2).div_ceil(8) + count * int_size;
+        let data = lz4::decompress(compressed, max_size)
+            .ok_or_else(|| invalid("invalid compressed data"))?;
+        decode_integers(&data, count, wide).ok_or_else(|| invalid("invalid compressed integers"))
+    }
+
+    fn compressed_u32s(&mut self, count: usize) -> Result<Vec<u32>, UsdParseError> {
+        Ok(self
+            .compressed_ints(count, false)?
+            .into_iter()
+            .map(|value| value as u32)
+            .collect())
+    }
+}
+
+/// Decodes integers encoded as a common value, 2-bit codes and variable-size differences between consecutive
+/// integers.
+fn decode_integers(data: &[u8], count: usize, wide: bool) -> Option<Vec<i64>> {
+    fn read(data: &mut &[u8], size: usize) -> Option<i64> {
+        let (bytes, rest) = data.split_at_checked(size)?;
+        *data = rest;
+        Some(match size {
+            1 => bytes[0] as i8 as i64,
+            2 => i16::from_le_bytes(bytes.try_into().ok()?) as i64,
+            4 => i32::from_le_bytes(bytes.try_into().ok()?) as i64,
+            _ => i64::from_le_bytes(bytes.try_into().ok()?),
+        })
+    }
+
+    let int_size = if wide { 8 } else { 4 };
+    let mut data = data;
+    let common = read(&mut data, int_size)?;
+    let (codes, mut differences) = data.split_at_checked((count * 2).div_ceil(8))?;
+    let mut previous = 0i64;
+    let mut values = Vec::with_capacity(count);
+    for index in 0..count {
+        let difference = match (codes[index / 4] >> (index % 4 * 2)) & 3 {
+            0 => common,
+            code => read(&mut differences, int_size >> (3 - code))?,
+        };
+        previous = previous.wrapping_add(difference);
+        if !wide {
+            previous = previous as i32 as i64;
+        }
+        values.push(previous);
+    }
+    Some(values)
+}
+
+/// A value of a field, before it is converted to an attribute [`Value`].
+enum FieldValue {
+    Value(Value),
+    Specifier(Specifier),
+    Paths(Vec<u32>),
+    TimeSamples(Vec<(f64, Option<Value>)>),
+    Block,
+    Unsupported,
+}
+
+struct Spec {
+    field_set: usize,
+    spec_type: u32,
+}
+
+struct CrateFile<'a> {
+    data: &'a [u8],
+    version: (u8, u8, u8),
+    tokens: Vec<String>,
+    strings: Vec<u32>,
+    fields: Vec<(u32, u64)>,
+    field_sets: Vec<u32>,
+    paths: Vec<String>,
+    specs: HashMap<String, Spec>,
+}
+
+impl<'a> CrateFile<'a> {
+    fn read(data: &'a [u8]) -> Result<Self, UsdParseError> {
+        let mut reader = Reader::at(data, 0);
+        if reader.bytes(8)? != b"PXR-USDC" {
+            return Err(invalid("missing `PXR-USDC` header"));
+        }
+        let version = reader.array::<8>()?;
+        let version = (version[0], version[1], version[2]);
+        // Structural sections are compressed since version 0.4.0.
+        if version.0 != 0 || version.1 < 4 {
+            return Err(UsdParseError::UnsupportedUsdcVersion(
+                version.0, version.1, version.2,
+            ));
+        }
+        let toc_offset = reader.count()?;
+        let mut reader = Reader::at(data, toc_offset);
+        let mut sections = HashMap::<String, usize>::default();
+        for _ in 0..reader.count()? {
+            let name = reader.array::<16>()?;
+            let name = name.split(|&byte| byte == 0).next().unwrap_or_default();
+            let start = reader.count()?;
+            let _size = reader.u64()?;
+            sections.insert(String::from_utf8_lossy(name).into_owned(), start);
+        }
+        let section = |name: &str| {
+            sections
+                .get(name)
+                .map(|&start| Reader::at(data, start))
+                .ok_or_else(|| invalid(format!("missing {name} section")))
+        };
+
+        let mut file = CrateFile {
+            data,
+            version,
+            tokens: Vec::new(),
+            strings: Vec::new(),
+            fields: Vec::new(),
+            field_sets: Vec::new(),
+            paths: Vec::new(),
+            specs: HashMap::default(),
+        };
+
+        let mut reader = section("TOKENS")?;
+        let count = reader.count()?;
+        let uncompressed_size = reader.count()?;
+        let compressed_size = reader.count()?;
+        let tokens = lz4::decompress(reader.bytes(compressed_size)?, uncompressed_size)
+            .ok_or_else(|| invalid("invalid compressed tokens"))?;
+        file.tokens = tokens
+            .split(|&byte| byte == 0)
+            .take(count)
+            .map(|token| String::from_utf8_lossy(token).into_owned())
+            .collect();
+
+        let mut reader = section("STRINGS")?;
+        let count = reader.count()?;
+        file.strings = reader.u32s(count)?;
+
+        let mut reader = section("FIELDS")?;
+        let count = reader.count()?;
+        let field_tokens = reader.compressed_u32s(count)?;
+        let reps_size = reader.count()?;
+        let reps = lz4::decompress(reader.bytes(reps_size)?, count * 8)
+            .ok_or_else(|| invalid("invalid compressed fields"))?;
+        file.fields = field_tokens
+            .into_iter()
+            .zip(
+                reps.chunks_exact(8)
+                    .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap())),
+            )
+            .collect();
+
+        let mut reader = section("FIELDSETS")?;
+        let count = reader.count()?;
+        file.field_sets = reader.compressed_u32s(count)?;
+
+        let mut reader = section("PATHS")?;
+        file.paths = vec![String::new(); reader.count()?];
+        let count = reader.count()?;
+        let path_indices = reader.compressed_u32s(count)?;
+        let element_tokens = reader.compressed_ints(count, false)?;
+        let jumps = reader.compressed_ints(count, false)?;
+        if count > 0 {
+            file.build_paths(&path_indices, &element_tokens, &jumps)?;
+        }
+
+        let mut reader = section("SPECS")?;
+        let count = reader.count()?;
+        let spec_paths = reader.compressed_u32s(count)?;
+        let spec_field_sets = reader.compressed_u32s(count)?;
+        let spec_types = reader.compressed_u32s(count)?;
+        for ((path, field_set), spec_type) in
+            spec_paths.into_iter().zip(spec_field_sets).zip(spec_types)
+        {
+            let path = file.path(path)?.to_string();
+            file.specs.insert(
+                path,
+                Spec {
+                    field_set: field_set as usize,
+                    spec_type,
+                },
+            );
+        }
+        Ok(file)
+    }
+
+    /// Rebuilds the paths from their tree encoding, where each entry is either a child or a sibling of the
+    /// previous one.
+    fn build_paths(
+        &mut self,
+        path_indices: &[u32],
+        element_tokens: &[i64],
+        jumps: &[i64],
+    ) -> Result<(), UsdParseError> {
+        // Siblings that still need to be visited, along with their parent path.
+        let mut pending = vec![(0usize, None::<String>)];
+        while let Some((mut index, mut parent)) = pending.pop() {
+            loop {
+                let (Some(&path_index), Some(&token), Some(&jump)) = (
+                    path_indices.get(index),
+                    element_tokens.get(index),
+                    jumps.get(index),
+                ) else {
+                    return Err(invalid("invalid path tree"));
+                };
+                let path = match &parent {
+                    None => "/".to_string(),
+                    Some(parent) => {
+                        let element = self
+                            .tokens
+                            .get(token.unsigned_abs() as usize)
+                            .ok_or_else(|| invalid("invalid path token"))?;
+                        if token < 0 {
+                            format!("{parent}.{element}")
+                        } else if element.starts_with(['{', '[']) || parent == "/" {
+                            format!("{parent}{element}")
+                        } else {
+                            format!("{parent}/{element}")
+                        }
+                    }
+                };
+                *self
+                    .paths
+                    .get_mut(path_index as usize)
+                    .ok_or_else(|| invalid("invalid path index"))? = path.clone();
+                let has_child = jump > 0 || jump == -1;
+                let has_sibling = jump >= 0;
+                if has_child {
+                    if has_sibling {
+                        let sibling = usize::try_from(index as i64 + jump)
+                            .map_err(|_| invalid("invalid path tree"))?;
+                        pending.push((sibling, parent.clone()));
+                    }
+                    parent = Some(path);
+                } else if !has_sibling {
+                    break;
+                }
+                index += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn token(&self, index: u64) -> Result<&str, UsdParseError> {
+        self.tokens
+            .get(index as usize)
+            .map(String::as_str)
+            .ok_or_else(|| invalid("invalid token index"))
+    }
+
+    fn string(&self, index: u64) -> Result<&str, UsdParseError> {
+        let token = *self
+            .strings
+            .get(index as usize)
+            .ok_or_else(|| invalid("invalid string index"))?;
+        self.token(token as u64)
+    }
+
+    fn path(&self, index: u32) -> Result<&str, UsdParseError> {
+        self.paths
+            .get(index as usize)
+            .map(String::as_str)
+            .ok_or_else(|| invalid("invalid path index"))
+    }
+
+    /// Returns the fields of the spec at `path`.
+    fn fields(&self, path: &str) -> Result<HashMap<&str, u64>, UsdParseError> {
+        let mut fields = HashMap::default();
+        let Some(spec) = self.specs.get(path) else {
+            return Ok(fields);
+        };
+        for &field in self.field_sets.get(spec.field_set..).unwrap_or_default() {
+            if field == u32::MAX {
+                break;
+            }
+            let &(token, rep) = self
+                .fields
+                .get(field as usize)
+                .ok_or_else(|| invalid("invalid field index"))?;
+            fields.insert(self.token(token as u64)?, rep);
+        }
+        Ok(fields)
+    }
+
+    fn value(&self, rep: u64) -> Result<FieldValue, UsdParseError> {
+        let is_array = rep & (1 << 63) != 0;
+        let is_inlined = rep & (1 << 62) != 0;
+        let is_compressed = rep & (1 << 61) != 0;
+        let value_type = ((rep >> 48) & 0xFF) as u8;
+        let payload = rep & ((1 << 48) - 1);
+
+        // Inlined values are stored in the payload.
+        if is_inlined {
+            let bits = payload as u32;
+            let value = match value_type {
+                1 => Value::Bool(bits != 0),
+                2 | 4 | 6 => Value::Number(bits as f64),
+                3 | 5 => Value::Number(bits as i32 as f64),
+                7 => Value::Number(half::f16::from_bits(bits as u16).to_f64()),
+                // Doubles are inlined when they can be represented as floats.
+                8 | 9 | 56 => Value::Number(f32::from_bits(bits) as f64),
+                10 => Value::Token(self.string(payload)?.into()),
+                11 => Value::Token(self.token(payload)?.into()),
+                12 => Value::AssetPath(self.token(payload)?.into()),
+                // Vectors are inlined when their components fit in 8-bit integers.
+                19..=30 => {
+                    let dimension = (value_type - 19) / 4 + 2;
+                    Value::Numbers(
+                        bits.to_le_bytes()[..dimension as usize]
+                            .iter()
+                            .map(|&byte| byte as i8 as f64)
+                            .collect(),
+                    )
+                }
+                // Matrices are inlined when they are diagonal and their diagonal fits in 8-bit integers.
+                13..=15 => {
+                    let dimension = (value_type - 11) as usize;
+                    let mut matrix = vec![0.0; dimension * dimension];
+                    for (index, &byte) in bits.to_le_bytes()[..dimension].iter().enumerate() {
+                        matrix[index * dimension + index] = byte as i8 as f64;
+                    }
+                    Value::Numbers(matrix)
+                }
+                42 => {
+                    return Ok(FieldValue::Specifier(match payload {
+                        1 => Specifier::Over,
+                        2 => Specifier::Class,
+                        _ => Specifier::Def,
+                    }));
+                }
+                51 => return Ok(FieldValue::Block),
+                _ => return Ok(FieldValue::Unsupported),
+            };
+            return Ok(FieldValue::Value(value));
+        }
+
+        let mut reader = Reader::at(self.data, payload as usize);
+        if is_array {
+            // Empty arrays don't point to any data.
+            let count = if payload == 0 {
+                0
+            } else if self.version >= (0, 7, 0) {
+                reader.count()?
+            } else {
+                reader.u32()? as usize
+            };
+            return self.array(&mut reader, value_type, count, is_compressed);
+        }
+
+        Ok(match value_type {
+            // Scalars that aren't inlined.
+            5 | 6 | 9 | 56 => {
+                FieldValue::Value(Value::Number(self.numbers(&mut reader, value_type, 1)?[0]))
+            }
+            13..=30 => {
+                FieldValue::Value(Value::Numbers(self.numbers(&mut reader, value_type, 1)?))
+            }
+            41 | 50 => {
+                let count = reader.count()?;
+                let tokens = reader
+                    .u32s(count)?
+                    .into_iter()
+                    .map(|index| {
+                        if value_type == 41 {
+                            self.token(index as u64)
+                        } else {
+                            self.string(index as u64)
+                        }
+                        .map(Into::into)
+                    })
+                    .collect::<Result<_, _>>()?;
+                FieldValue::Value(Value::Tokens(tokens))
+            }
+            48 => {
+                let count = reader.count()?;
+                FieldValue::Value(Value::Numbers(self.numbers(&mut reader, 9, count)?))
+            }
+            34 => {
+                let header = reader.u8()?;
+                let mut paths = Vec::new();
+                // Explicit, added, prepended, appended, deleted and ordered items, in that order. Deleted and
+                // ordered items don't add paths.
+                for bit in [1, 2, 5, 6, 3, 4] {
+                    if header & (1 << bit) != 0 {
+                        let count = reader.count()?;
+                        let items = reader.u32s(count)?;
+                        if bit != 3 && bit != 4 {
+                            paths.extend(items);
+                        }
+                    }
+                }
+                FieldValue::Paths(paths)
+            }
+            40 => {
+                let count = reader.count()?;
+                FieldValue::Paths(reader.u32s(count)?)
+            }
+            46 => {
+                reader.jump()?;
+                let times_rep = reader.u64()?;
+                reader.jump()?;
+                let count = reader.count()?;
+                let times = match self.value(times_rep)? {
+                    FieldValue::Value(value) => value.as_numbers().map(<[f64]>::to_vec),
+                    _ => None,
+                }
+                .ok_or_else(|| invalid("invalid time samples"))?;
+                if times.len() != count {
+                    return Err(invalid("invalid time samples"));
+                }
+                let mut samples = Vec::with_capacity(count);
+                for time in times {
+                    let value = match self.value(reader.u64()?)? {
+                        FieldValue::Value(value) => Some(value),
+                        _ => None,
+                    };
+                    samples.push((time, value));
+                }
+                FieldValue::TimeSamples(samples)
+            }
+            _ => FieldValue::Unsupported,
+        })
+    }
+
+    fn array(
+        &self,
+        reader: &mut Reader,
+        value_type: u8,
+        count: usize,
+        is_compressed: bool,
+    ) -> Result<FieldValue, UsdParseError> {
+        let value = match value_type {
+            10..=12 => {
+                let tokens = reader
+                    .u32s(count)?
+                    .into_iter()
+                    .map(|index| {
+                        if value_type == 10 {
+                            self.string(index as u64)
+                        } else {
+                            self.token(index as u64)
+                        }
+                        .map(Into::into)
+                    })
+                    .collect::<Result<_, _>>()?;
+                Value::Tokens(tokens)
+            }
+            // Integer arrays can be compressed since version 0.5.0.
+            3..=6 if is_compressed && count >= MIN_COMPRESSED_ARRAY_SIZE => {
+                let values = reader.compressed_ints(count, value_type >= 5)?;
+                Value::Numbers(
+                    values
+                        .into_iter()
+                        .map(|value| match value_type {
+                            4 => value as u32 as f64,
+                            6 => value as u64 as f64,
+                            _ => value as f64,
+                        })
+                        .collect(),
+                )
+            }
+            // Floating point arrays are either stored as integers or as indices into a lookup table.
+            7..=9 if is_compressed && count >= MIN_COMPRESSED_ARRAY_SIZE => match reader.u8()? {
+                b'i' => Value::Numbers(
+                    reader
+                        .compressed_ints(count, false)?
+                        .into_iter()
+                        .map(|value| value as f64)
+                        .collect(),
+                ),
+                b't' => {
+                    let lookup_size = reader.u32()? as usize;
+                    let lookup = self.numbers(reader, value_type, lookup_size)?;
+                    let indices = reader.compressed_u32s(count)?;
+                    Value::Numbers(
+                        indices
+                            .into_iter()
+                            .map(|index| lookup.get(index as usize).copied())
+                            .collect::<Option<_>>()
+                            .ok_or_else(|| invalid("invalid lookup index"))?,
+                    )
+                }
+                _ => return Err(invalid("invalid array compression")),
+            },
+            1..=9 | 13..=30 | 56 => Value::Numbers(self.numbers(reader, value_type, count)?),
+            _ => return Ok(FieldValue::Unsupported),
+        };
+        Ok(FieldValue::Value(value))
+    }
+
+    /// Reads `count` uncompressed values of a numeric type, flattening their components.
+    fn numbers(
+        &self,
+        reader: &mut Reader,
+        value_type: u8,
+        count: usize,
+    ) -> Result<Vec<f64>, UsdParseError> {
+        let (components, component_type) = match value_type {
+            // Matrices (of doubles).
+            13..=15 => {
+                let dimension = (value_type - 11) as usize;
+                (dimension * dimension, 9)
+            }
+            // Quaternions of doubles, floats and halves.
+            16..=18 => (4, 9 - (value_type - 16)),
+            // Vectors of doubles, floats, halves and ints.
+            19..=30 => (
+                ((value_type - 19) / 4 + 2) as usize,
+                [9, 8, 7, 3][((value_type - 19) % 4) as usize],
+            ),
+            56 => (1, 9),
+            _ => (1, value_type),
+        };
+        let size = match component_type {
+            1 | 2 => 1,
+            7 => 2,
+            3 | 4 | 8 => 4,
+            _ => 8,
+        };
+        let bytes = reader.bytes(
+            count
+                .checked_mul(components * size)
+                .ok_or_else(|| invalid("invalid count"))?,
+        )?;
+        Ok(bytes
+            .chunks_exact(size)
+            .map(|bytes| match component_type {
+                1 | 2 => bytes[0] as f64,
+                3 => i32::from_le_bytes(bytes.try_into().unwrap()) as f64,
+                4 => u32::from_le_bytes(bytes.try_into().unwrap()) as f64,
+                5 => i64::from_le_bytes(bytes.try_into().unwrap()) as f64,
+                6 => u64::from_le_bytes(bytes.try_into().unwrap()) as f64,
+                7 => half::f16::from_le_bytes(bytes.try_into().unwrap()).to_f64(),
+                8 => f32::from_le_bytes(bytes.try_into().unwrap()) as f64,
+                _ => f64::from_le_bytes(bytes.try_into().unwrap()),
+            })
+            .collect())
+    }
+
+    fn paths_value(&self, value: FieldValue) -> Result<Vec<String>, UsdParseError> {
+        match value {
+            FieldValue::Paths(paths) => paths
+                .into_iter()
+                .map(|path| self.path(path).map(Into::into))
+                .collect(),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    fn prim(&self, path: String, name: &str) -> Result<Option<Prim>, UsdParseError> {
+        let fields = self.fields(&path)?;
+        let mut prim = Prim {
+            name: name.into(),
+            ..Default::default()
+        };
+        let mut children = Vec::new();
+        let mut properties = Vec::new();
+        for (&field, &rep) in &fields {
+            match (field, self.value(rep)?) {
+                ("specifier", FieldValue::Specifier(specifier)) => prim.specifier = specifier,
+                ("typeName", FieldValue::Value(Value::Token(type_name))) => {
+                    prim.type_name = type_name;
+                }
+                ("active", FieldValue::Value(Value::Bool(false))) => return Ok(None),
+                ("primChildren", FieldValue::Value(Value::Tokens(tokens))) => children = tokens,
+                ("properties", FieldValue::Value(Value::Tokens(tokens))) => properties = tokens,
+                _ => {}
+            }
+        }
+
+        for name in properties {
+            let property_path = format!("{path}.{name}");
+            let Some(spec) = self.specs.get(&property_path) else {
+                continue;
+            };
+            let fields = self.fields(&property_path)?;
+            if spec.spec_type == SPEC_TYPE_RELATIONSHIP {
+                let targets = match fields.get("targetPaths") {
+                    Some(&rep) => self.paths_value(self.value(rep)?)?,
+                    None => Vec::new(),
+                };
+                prim.relationships.insert(name, targets);
+            } else if spec.spec_type == SPEC_TYPE_ATTRIBUTE {
+                let mut attribute = Attribute::default();
+                for (&field, &rep) in &fields {
+                    match (field, self.value(rep)?) {
+                        ("typeName", FieldValue::Value(Value::Token(type_name))) => {
+                            attribute.type_name = type_name;
+                        }
+                        ("default", FieldValue::Value(value)) => attribute.default = Some(value),
+                        ("timeSamples", FieldValue::TimeSamples(samples)) => {
+                            attribute.time_samples = samples;
+                        }
+                        ("connectionPaths", value) => {
+                            attribute.connections = self.paths_value(value)?;
+                        }
+                        ("interpolation", FieldValue::Value(Value::Token(interpolation))) => {
+                            attribute.interpolation = Some(interpolation);
+                        }
+                        _ => {}
+                    }
+                }
+                // Bool attributes are stored as numbers in arrays and time samples, like other numeric types.
+                if attribute.type_name == "bool"
+                    && let Some(Value::Number(value)) = attribute.default
+                {
+                    attribute.default = Some(Value::Bool(value != 0.0));
+                }
+                attribute
+                    .time_samples
+                    .sort_by(|(a, _), (b, _)| a.total_cmp(b));
+                prim.attributes.insert(name, attribute);
+            }
+        }
+
+        for child in children {
+            let child_path = format!("{}/{child}", path.trim_end_matches('/'));
+            if self
+                .specs
+                .get(&child_path)
+                .is_some_and(|spec| spec.spec_type == SPEC_TYPE_PRIM)
+                && let Some(child) = self.prim(child_path, &child)?
+            {
+                prim.children.push(child);
+            }
+        }
+        prim.path = path;
+        Ok(Some(prim))
+    }
+}
+
+/// Parses the bytes of a `.usdc` layer.
+pub(crate) fn parse_usdc(bytes: &[u8]) -> Result<Stage, UsdParseError> {
+    let file = CrateFile::read(bytes)?;
+    let mut stage = Stage::default();
+    if file
+        .specs
+        .get("/")
+        .is_none_or(|spec| spec.spec_type != SPEC_TYPE_PSEUDO_ROOT)
+    {
+        return Err(invalid("missing pseudo-root"));
+    }
+    for (&field, &rep) in &file.fields("/")? {
+        if let FieldValue::Value(value) = file.value(rep)? {
+            stage.set_metadata(field, &value);
+        }
+    }
+    let root = file
+        .prim("/".into(), "")?
+        .ok_or_else(|| invalid("inactive pseudo-root"))?;
+    stage.root_prims = root.children;
+    Ok(stage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Compresses `data` as a single LZ4 block of literals.
+    fn compress(data: &[u8]) -> Vec<u8> {
+        let mut compressed = vec![0];
+        let length = data.len();
+        compressed.push((length.min(15) as u8) << 4);
+        if length >= 15 {
+            let mut remaining = length - 15;
+            while remaining >= 255 {
+                compressed.push(255);
+                remaining -= 255;
+            }
+            compressed.push(remaining as u8);
+        }
+        compressed.extend_from_slice(data);
+        compressed
+    }
+
+    /// Encodes integers with the integer coding, only using full-size differences.
+    fn compress_ints(values: &[i32]) -> Vec<u8> {
+        let mut data = 0i32.to_le_bytes().to_vec();
+        data.extend(core::iter::repeat_n(0xFF, (values.len() * 2).div_ceil(8)));
+        let mut previous = 0i32;
+        for &value in values {
+            data.extend_from_slice(&value.wrapping_sub(previous).to_le_bytes());
+            previous = value;
+        }
+        let compressed = compress(&data);
+        let mut bytes = (compressed.len() as u64).to_le_bytes().to_vec();
+        bytes.extend(compressed);
+        bytes
+    }
+
+    const fn rep(value_type: u64, payload: u64) -> u64 {
+        (value_type << 48) | payload
+    }
+
+    const INLINED: u64 = 1 << 62;
+    const ARRAY: u64 = 1 << 63;
+    const COMPRESSED: u64 = 1 << 61;
+
+    /// Writes a crate file with a hierarchy of a transform with animated translation and a mesh.
+    fn write_crate_file() -> Vec<u8> {
+        let tokens = [
+            "",
+            "World",
+            "Cube",
+            "xformOp:translate",
+            "xformOpOrder",
+            "points",
+            "faceVertexIndices",
+            "material:binding",
+            "upAxis",
+            "Z",
+            "primChildren",
+            "specifier",
+            "typeName",
+            "Xform",
+            "Mesh",
+            "properties",
+            "double3",
+            "timeSamples",
+            "token[]",
+            "default",
+            "point3f[]",
+            "int[]",
+            "targetPaths",
+            "metersPerUnit",
+        ];
+        let token = |name: &str| tokens.iter().position(|token| *token == name).unwrap() as u64;
+
+        let mut data = b"PXR-USDC".to_vec();
+        data.extend_from_slice(&[0, 8, 0, 0, 0, 0, 0, 0]);
+        // The table of contents offset is written at the end.
+        data.extend_from_slice(&[0; 16]);
+
+        let mut write_at = |bytes: &[u8]| {
+            let offset = data.len() as u64;
+            data.extend_from_slice(bytes);
+            offset
+        };
+        let token_vector = |names: &[&str]| {
+            let mut bytes = (names.len() as u64).to_le_bytes().to_vec();
+            for name in names {
+                bytes.extend_from_slice(&(token(name) as u32).to_le_bytes());
+            }
+            bytes
+        };
+        let world_children = write_at(&token_vector(&["Cube"]));
+        let root_children = write_at(&token_vector(&["World"]));
+        let world_properties = write_at(&token_vector(&["xformOp:translate", "xformOpOrder"]));
+        let cube_properties = write_at(&token_vector(&[
+            "points",
+            "faceVertexIndices",
+            "material:binding",
+        ]));
+
+        // Time samples: a jump to the times, the times, a jump to the values, and the values.
+        let times =
+            write_at(&[2u64.to_le_bytes(), 0f64.to_le_bytes(), 24f64.to_le_bytes()].concat());
+        let second_translation =
+            write_at(&[0f64.to_le_bytes(), 0.5f64.to_le_bytes(), 0f64.to_le_bytes()].concat());
+        let mut samples = 8i64.to_le_bytes().to_vec();
+        samples.extend_from_slice(&rep(48, times).to_le_bytes());
+        samples.extend_from_slice(&8i64.to_le_bytes());
+        samples.extend_from_slice(&2u64.to_le_bytes());
+        samples.extend_from_slice(&(INLINED | rep(23, 0x03_02_01)).to_le_bytes());
+        samples.extend_from_slice(&rep(23, second_translation).to_le_bytes());
+        let samples = write_at(&samples);
+
+        let op_order = write_at(
+            &[
+                1u64.to_le_bytes().as_slice(),
+                &(token("xformOp:translate") as u32).to_le_bytes(),
+            ]
+            .concat(),
+        );
+        let mut points = 3u64.to_le_bytes().to_vec();
+        for component in [0f32, 0., 0., 1., 0., 0., 0., 1., 0.] {
+            points.extend_from_slice(&component.to_le_bytes());
+        }
+        let points = write_at(&points);
+        let indices_values = (0..20).map(|index| index % 3).collect::<Vec<_>>();
+        let mut indices = 20u64.to_le_bytes().to_vec();
+        indices.extend(compress_ints(&indices_values));
+        let indices = write_at(&indices);
+        // An explicit list of one path, /World.
+        let mut targets = vec![0b11];
+        targets.extend_from_slice(&1u64.to_le_bytes());
+        targets.extend_from_slice(&1u32.to_le_bytes());
+        let targets = write_at(&targets);
+
+        let fields = [
+            (token("upAxis"), INLINED | rep(11, token("Z"))),
+            (token("primChildren"), rep(41, root_children)),
+            (
+                token("metersPerUnit"),
+                INLINED | rep(9, 0.5f32.to_bits() as u64),
+            ),
+            (token("specifier"), INLINED | rep(42, 0)),
+            (token("typeName"), INLINED | rep(11, token("Xform"))),
+            (token("primChildren"), rep(41, world_children)),
+            (token("properties"), rep(41, world_properties)),
+            (token("typeName"), INLINED | rep(11, token("double3"))),
+            (token("timeSamples"), rep(46, samples)),
+            (token("typeName"), INLINED | rep(11, token("token[]"))),
+            (token("default"), ARRAY | rep(11, op_order)),
+            (token("typeName"), INLINED | rep(11, token("Mesh"))),
+            (token("properties"), rep(41, cube_properties)),
+            (token("typeName"), INLINED | rep(11, token("point3f[]"))),
+            (token("default"), ARRAY | rep(24, points)),
+            (token("typeName"), INLINED | rep(11, token("int[]"))),
+            (token("default"), ARRAY | COMPRESSED | rep(3, indices)),
+            (token("targetPaths"), rep(34, targets)),
+        ];
+        let field_sets: [&[u32]; 8] = [
+            &[0, 1, 2],
+            &[3, 4, 5, 6],
+            &[7, 8],
+            &[9, 10],
+            &[3, 11, 12],
+            &[13, 14],
+            &[15, 16],
+            &[17],
+        ];
+
+        let mut sections = Vec::new();
+        let mut section = |name: &str, bytes: Vec<u8>| {
+            let start = write_at(&bytes);
+            sections.push((name.to_string(), start, bytes.len() as u64));
+        };
+
+        let token_bytes = tokens.join("\0") + "\0";
+        let compressed = compress(token_bytes.as_bytes());
+        section(
+            "TOKENS",
+            [
+                (tokens.len() as u64).to_le_bytes().as_slice(),
+                &(token_bytes.len() as u64).to_le_bytes(),
+                &(compressed.len() as u64).to_le_bytes(),
+                &compressed,
+            ]
+            .concat(),
+        );
+        section("STRINGS", 0u64.to_le_bytes().to_vec());
+
+        let mut bytes = (fields.len() as u64).to_le_bytes().to_vec();
+        bytes.extend(compress_ints(
+            &fields
+                .iter()
+                .map(|(token, _)| *token as i32)
+                .collect::<Vec<_>>(),
+        ));
+        let reps = compress(
+            &fields
+                .iter()
+                .flat_map(|(_, rep)| rep.to_le_bytes())
+                .collect::<Vec<_>>(),
+        );
+        bytes.extend_from_slice(&(reps.len() as u64).to_le_bytes());
+        bytes.extend(reps);
+        section("FIELDS", bytes);
+
+        let sets = field_sets
+            .iter()
+            .flat_map(|set| set.iter().copied().chain([u32::MAX]))
+            .map(|field| field as i32)
+            .collect::<Vec<_>>();
+        let mut bytes = (sets.len() as u64).to_le_bytes().to_vec();
+        bytes.extend(compress_ints(&sets));
+        section("FIELDSETS", bytes);
+
+        // "/", "/World", "/World.xformOp:translate", "/World.xformOpOrder", "/World/Cube", "/World/Cube.points",
+        // "/World/Cube.faceVertexIndices", "/World/Cube.material:binding"
+        let element_tokens = [
+            0,
+            token("World") as i32,
+            -(token("xformOp:translate") as i32),
+            -(token("xformOpOrder") as i32),
+            token("Cube") as i32,
+            -(token("points") as i32),
+            -(token("faceVertexIndices") as i32),
+            -(token("material:binding") as i32),
+        ];
+        let jumps = [-1, -1, 0, 0, -1, 0, 0, -2];
+        let mut bytes = 8u64.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&8u64.to_le_bytes());
+        bytes.extend(compress_ints(&(0..8).collect::<Vec<_>>()));
+        bytes.extend(compress_ints(&element_tokens));
+        bytes.extend(compress_ints(&jumps));
+        section("PATHS", bytes);
+
+        let set_starts = field_sets
+            .iter()
+            .scan(0, |start, set| {
+                let current = *start;
+                *start += set.len() as i32 + 1;
+                Some(current)
+            })
+            .collect::<Vec<_>>();
+        let mut bytes = 8u64.to_le_bytes().to_vec();
+        bytes.extend(compress_ints(&(0..8).collect::<Vec<_>>()));
+        bytes.extend(compress_ints(&set_starts));
+        bytes.extend(compress_ints(&[7, 6, 1, 1, 6, 1, 1, 8]));
+        section("SPECS", bytes);
+
+        let mut toc = (sections.len() as u64).to_le_bytes().to_vec();
+        for (name, start, size) in &sections {
+            let mut name_bytes = [0u8; 16];
+            name_bytes[..name.len()].copy_from_slice(name.as_bytes());
+            toc.extend_from_slice(&name_bytes);
+            toc.extend_from_slice(&start.to_le_bytes());
+            toc.extend_from_slice(&size.to_le_bytes());
+        }
+        let toc_offset = write_at(&toc);
+        data[16..24].copy_from_slice(&toc_offset.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn parse_crate_file() {
+        let stage = parse_usdc(&write_crate_file()).unwrap();
+        assert_eq!(stage.up_axis, super::super::UpAxis::Z);
+        assert_eq!(stage.meters_per_unit, Some(0.5));
+
+        let world = &stage.root_prims[0];
+        assert_eq!(
+            (world.path.as_str(), world.type_name.as_str()),
+            ("/World", "Xform")
+        );
+        let translation = &world.attributes["xformOp:translate"];
+        assert_eq!(translation.type_name, "double3");
+        assert_eq!(
+            translation.time_samples,
+            vec![
+                (0.0, Some(Value::Numbers(vec![1.0, 2.0, 3.0]))),
+                (24.0, Some(Value::Numbers(vec![0.0, 0.5, 0.0]))),
+            ]
+        );
+        assert_eq!(
+            world.attribute("xformOpOrder"),
+            Some(&Value::Tokens(vec!["xformOp:translate".into()]))
+        );
+
+        let cube = &world.children[0];
+        assert_eq!(
+            (cube.path.as_str(), cube.type_name.as_str()),
+            ("/World/Cube", "Mesh")
+        );
+        assert_eq!(
+            cube.attribute("points").unwrap().as_numbers().unwrap(),
+            [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0]
+        );
+        let indices = cube
+            .attribute("faceVertexIndices")
+            .unwrap()
+            .as_numbers()
+            .unwrap();
+        assert_eq!(indices.len(), 20);
+        assert_eq!(&indices[..4], [0.0, 1.0, 2.0, 0.0]);
+        assert_eq!(cube.target("material:binding"), Some("/World"));
+    }
+
+    #[test]
+    fn decode_integer_codes() {
+        // A common value of 2, then codes for: common, small (-1), medium (300), large (70000).
+        let mut data = 2i32.to_le_bytes().to_vec();
+        data.push(0b11_10_01_00);
+        data.push(-1i8 as u8);
+        data.extend_from_slice(&300i16.to_le_bytes());
+        data.extend_from_slice(&70000i32.to_le_bytes());
+        assert_eq!(
+            decode_integers(&data, 4, false).unwrap(),
+            [2, 1, 301, 70301]
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use alloc::{boxed::Box, string::String, vec::Vec};
+use bevy_ecs::{entity::Entity, message::Message, resource::Resource};
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::Reflect;
+
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// An image on the clipboard.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct ClipboardImage {
+    /// The width of the image, in pixels.
+    pub width: u32,
+    /// The height of the image, in pixels.
+    pub height: u32,
+    /// The pixels of the image, row by row from the top, as 8-bit RGBA values.
+    pub data: Vec<u8>,
+}
+
+/// The content of the clipboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum ClipboardContent {
+    /// Plain text.
+    Text(String),
+    /// An image.
+    Image(ClipboardImage),
+}
+
+/// A platform clipboard, used by [`Clipboard`].
+///
+/// Windowing backends such as `bevy_winit` provide one for the platforms they support.
+///
+/// Reading the clipboard takes `&mut self`, as platform clipboards like the ones of `arboard`
+/// need exclusive access to their connection to the system clipboard, even to read it.
+pub trait ClipboardProvider: Send + Sync + 'static {
+    /// Returns the text on the clipboard, if any.
+    fn get_text(&mut self) -> Option<String>;
+
+    /// Puts `text` on the clipboard.
+    fn set_text(&mut self, text: String);
+
+    /// Returns the image on the clipboard, if any.
+    ///
+    /// Returns `None` by default, for platforms that don't support images on the clipboard.
+    fn get_image(&mut self) -> Option<ClipboardImage> {
+        None
+    }
+
+    /// Puts `image` on the clipboard.
+    ///
+    /// Does nothing by default, for platforms that don't support images on the clipboard.
+    fn set_image(&mut self, image: ClipboardImage) {
+        let _ = image;
+    }
+}
+
+/// The clipboard of the platform, to copy and paste text and images.
+///
+/// Unless a [`ClipboardProvider`] is set, by the windowing backend or with
+/// [`Clipboard::with_provider`], content is only copied within the app. `bevy_winit` only sets
+/// one when its `clipboard` feature is enabled.
+///
+/// Reading the clipboard needs a [`ResMut<Clipboard>`](bevy_ecs::system::ResMut), as the provider
+/// needs exclusive access to the platform clipboard, see [`ClipboardProvider`].
+///
+/// Some platforms, like the web, don't let apps read the clipboard whenever they want: the content
+/// pasted by the user is sent as a [`ClipboardPaste`] message instead.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_window::Clipboard;
+/// fn copy_level_name(mut clipboard: ResMut<Clipboard>) {
+///     clipboard.set_text("Level 1");
+/// }
+/// ```
+#[derive(Resource, Default)]
+pub struct Clipboard {
+    provider: Option<Box<dyn ClipboardProvider>>,
+    content: Option<ClipboardContent>,
+}
+
+impl Clipboard {
+    /// Makes a clipboard backed by `provider`.
+    pub fn with_provider(provider: impl ClipboardProvider) -> Self {
+        Self {
+            provider: Some(Box::new(provider)),
+            content: None,
+        }
+    }
+
+    /// Returns `true` if the clipboard is backed by a [`ClipboardProvider`], rather than only
+    /// copying content within the app.
+    pub fn has_provider(&self) -> bool {
+        self.provider.is_some()
+    }
+
+    /// Returns the text on the clipboard, if any.
+    pub fn get_text(&mut self) -> Option<String> {
+        match &mut self.provider {
+            Some(provider) => provider.get_text(),
+            None => match &self.content {
+                Some(ClipboardContent::Text(text)) => Some(text.clone()),
+                _ => None,
+            },
+        }
+    }
+
+    /// Puts `text` on the clipboard.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        let text = text.into();
+        match &mut self.provider {
+            Some(provider) => provider.set_text(text),
+            None => self.content = Some(ClipboardContent::Text(text)),
+        }
+    }
+
+    /// Returns the image on the clipboard, if any.
+    pub fn get_image(&mut self) -> Option<ClipboardImage> {
+        match &mut self.provider {
+            Some(provider) => provider.get_image(),
+            None => match &self.content {
+                Some(ClipboardContent::Image(image)) => Some(image.clone()),
+                _ => None,
+            },
+        }
+    }
+
+    /// Puts `image` on the clipboard.
+    pub fn set_image(&mut self, image: ClipboardImage) {
+        match &mut self.provider {
+            Some(provider) => provider.set_image(image),
+            None => self.content = Some(ClipboardContent::Image(image)),
+        }
+    }
+}
+
+/// Content was pasted into a window by the platform, such as with the `paste` event of the
+/// browser.
+///
+/// This is only sent on platforms that don't let the app read the [`Clipboard`] at any time,
+/// like the web, where reading the clipboard when the paste shortcut is pressed returns `None`.
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct ClipboardPaste {
+    /// The window the content was pasted into.
+    pub window: Entity,
+    /// The pasted content.
+    pub content: ClipboardContent,
+}
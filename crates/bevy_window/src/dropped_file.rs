@@ -0,0 +1,133 @@
+use crate::FileDragAndDrop;
+use alloc::{sync::Arc, vec::Vec};
+use bevy_app::{App, Plugin, Update};
+use bevy_asset::{
+    Asset, AssetLoadError, AssetServer, Assets, Handle, LoadState, LoadedUntypedAsset,
+    UntypedHandle,
+};
+use bevy_ecs::prelude::*;
+use std::path::PathBuf;
+
+/// A [`Plugin`] that loads the files dropped onto windows as assets.
+///
+/// Dropped files are loaded with [`AssetServer::load_file_override`], which picks the asset loader
+/// using the file's extension. Once a file is loaded, a [`DroppedAssetLoaded`] message is written
+/// with a handle to its asset, or a [`DroppedAssetFailed`] message if it couldn't be loaded.
+///
+/// Dropped files are usually outside of the approved asset folders, so loading them requires
+/// [`AssetPlugin::unapproved_path_mode`](bevy_asset::AssetPlugin::unapproved_path_mode) to be
+/// [`Deny`](bevy_asset::UnapprovedPathMode::Deny) or [`Allow`](bevy_asset::UnapprovedPathMode::Allow).
+/// With the default [`Forbid`](bevy_asset::UnapprovedPathMode::Forbid), they fail to load with
+/// [`AssetLoadError::UnapprovedPath`].
+///
+/// ```
+/// # use bevy_asset::Asset;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::TypePath;
+/// # use bevy_window::DroppedAssetLoaded;
+/// # #[derive(Asset, TypePath)]
+/// # struct Level;
+/// fn open_dropped_levels(mut dropped: MessageReader<DroppedAssetLoaded>) {
+///     for dropped in dropped.read() {
+///         if let Some(level) = dropped.typed::<Level>() {
+///             // Open `level`.
+///         }
+///     }
+/// }
+/// ```
+pub struct DroppedFileAssetPlugin;
+
+impl Plugin for DroppedFileAssetPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<DroppedAssetLoaded>()
+            .add_message::<DroppedAssetFailed>()
+            .init_resource::<LoadingDroppedFiles>()
+            .add_systems(
+                Update,
+                (load_dropped_files, send_dropped_asset_messages).chain(),
+            );
+    }
+}
+
+/// A file dropped onto a window was loaded by the [`DroppedFileAssetPlugin`].
+#[derive(Message, Debug, Clone)]
+pub struct DroppedAssetLoaded {
+    /// The window the file was dropped onto.
+    pub window: Entity,
+    /// The path of the dropped file.
+    pub path: PathBuf,
+    /// The handle to the asset loaded from the file.
+    pub handle: UntypedHandle,
+}
+
+impl DroppedAssetLoaded {
+    /// Returns the handle to the loaded asset if it's an `A`, or `None` otherwise.
+    pub fn typed<A: Asset>(&self) -> Option<Handle<A>> {
+        self.handle.clone().try_typed().ok()
+    }
+}
+
+/// A file dropped onto a window couldn't be loaded by the [`DroppedFileAssetPlugin`].
+#[derive(Message, Debug, Clone)]
+pub struct DroppedAssetFailed {
+    /// The window the file was dropped onto.
+    pub window: Entity,
+    /// The path of the dropped file.
+    pub path: PathBuf,
+    /// The error that occurred while loading the file.
+    pub error: Arc<AssetLoadError>,
+}
+
+/// The dropped files that are still loading.
+#[derive(Resource, Default)]
+struct LoadingDroppedFiles(Vec<LoadingDroppedFile>);
+
+struct LoadingDroppedFile {
+    window: Entity,
+    path: PathBuf,
+    handle: Handle<LoadedUntypedAsset>,
+}
+
+fn load_dropped_files(
+    mut drag_and_drop: MessageReader<FileDragAndDrop>,
+    asset_server: Res<AssetServer>,
+    mut loading: ResMut<LoadingDroppedFiles>,
+) {
+    for message in drag_and_drop.read() {
+        if let FileDragAndDrop::DroppedFile { window, path_buf } = message {
+            loading.0.push(LoadingDroppedFile {
+                window: *window,
+                path: path_buf.clone(),
+                handle: asset_server.load_file_override(path_buf.clone()),
+            });
+        }
+    }
+}
+
+fn send_dropped_asset_messages(
+    asset_server: Res<AssetServer>,
+    loaded_assets: Res<Assets<LoadedUntypedAsset>>,
+    mut loading: ResMut<LoadingDroppedFiles>,
+    mut loaded: MessageWriter<DroppedAssetLoaded>,
+    mut failed: MessageWriter<DroppedAssetFailed>,
+) {
+    loading.0.retain(|file| {
+        if let Some(asset) = loaded_assets.get(&file.handle) {
+            loaded.write(DroppedAssetLoaded {
+                window: file.window,
+                path: file.path.clone(),
+                handle: asset.handle.clone(),
+            });
+            return false;
+        }
+        if let LoadState::Failed(error) = asset_server.load_state(&file.handle) {
+            failed.write(DroppedAssetFailed {
+                window: file.window,
+                path: file.path.clone(),
+                error,
+            });
+            return false;
+        }
+        true
+    });
+}
@@ -4,6 +4,7 @@ use bevy_input::{
     gestures::*,
     keyboard::{KeyboardFocusLost, KeyboardInput},
     mouse::{MouseButtonInput, MouseMotion, MouseWheel},
+    pen::PenInput,
     touch::TouchInput,
 };
 use bevy_math::{IVec2, Vec2};
@@ -20,7 +21,7 @@ use bevy_reflect::Reflect;
 #[cfg(feature = "serialize")]
 use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 
-use crate::WindowTheme;
+use crate::{WindowMode, WindowTheme};
 
 /// A window event that is sent whenever a window's logical size has changed.
 #[derive(Message, Debug, Clone, PartialEq)]
@@ -444,6 +445,29 @@ pub struct WindowThemeChanged {
     pub theme: WindowTheme,
 }
 
+/// An event sent when the windowing backend applied a new [`WindowMode`] to a window.
+///
+/// If the mode can't be applied, for instance because the monitor doesn't support the
+/// [`VideoModeSelection`](crate::VideoModeSelection), [`Window::mode`](crate::window::Window::mode)
+/// is reverted to the previous mode and this event isn't sent.
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct WindowModeChanged {
+    /// Window whose mode changed.
+    pub window: Entity,
+    /// The new mode of the window.
+    pub mode: WindowMode,
+}
+
 /// Application lifetime events
 #[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(
@@ -553,6 +577,8 @@ pub enum WindowEvent {
 
     /// A touch input state change.
     TouchInput(TouchInput),
+    /// A pen input state change.
+    PenInput(PenInput),
 
     /// A keyboard input.
     KeyboardInput(KeyboardInput),
@@ -712,6 +738,12 @@ impl From<TouchInput> for WindowEvent {
     }
 }
 
+impl From<PenInput> for WindowEvent {
+    fn from(e: PenInput) -> Self {
+        Self::PenInput(e)
+    }
+}
+
 impl From<KeyboardInput> for WindowEvent {
     fn from(e: KeyboardInput) -> Self {
         Self::KeyboardInput(e)
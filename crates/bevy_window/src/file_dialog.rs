@@ -0,0 +1,202 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use bevy_ecs::{component::Component, entity::Entity, event::EntityEvent};
+
+#[cfg(feature = "std")]
+use std::path::PathBuf;
+
+#[cfg(not(feature = "std"))]
+use alloc::string::String as PathBuf;
+
+#[cfg(feature = "bevy_reflect")]
+use {
+    bevy_ecs::prelude::ReflectComponent,
+    bevy_reflect::{std_traits::ReflectDefault, Reflect},
+};
+
+/// What a [`FileDialog`] lets the user pick.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, PartialEq, Hash, Clone)
+)]
+pub enum FileDialogKind {
+    /// A single existing file.
+    #[default]
+    OpenFile,
+    /// One or more existing files.
+    OpenFiles,
+    /// A single existing folder.
+    PickFolder,
+    /// The path of a file to save, which may not exist yet.
+    SaveFile,
+}
+
+/// A filter of the files shown in a [`FileDialog`], such as "Images" for the `png` and `jpg`
+/// extensions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, PartialEq, Clone)
+)]
+pub struct FileDialogFilter {
+    /// The name of the filter, shown to the user.
+    pub name: String,
+    /// The extensions of the files shown, without the leading dot.
+    pub extensions: Vec<String>,
+}
+
+/// A native dialog to pick files or a folder, opened when this component is added to an entity.
+///
+/// The dialog is handled by the windowing backend without blocking the app. When the user closes
+/// it, a [`FileDialogClosed`] event is triggered on the entity, and this component is removed from
+/// it. Inserting the component again opens a new dialog.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_window::{FileDialog, FileDialogClosed};
+/// fn open_level(mut commands: Commands) {
+///     commands
+///         .spawn(FileDialog::open_file().with_filter("Levels", &["level"]))
+///         .observe(|closed: On<FileDialogClosed>, mut commands: Commands| {
+///             if let Some(level) = closed.files.first() {
+///                 // Load `level`.
+///             }
+///             commands.entity(closed.entity).despawn();
+///         });
+/// }
+/// ```
+///
+/// ## Platform-specific
+///
+/// - **Web:** Only [`FileDialogKind::OpenFile`] and [`FileDialogKind::OpenFiles`] are supported,
+///   and the dialog must be opened in response to an input of the user, such as a click. The other
+///   kinds close immediately without any file.
+/// - **iOS / Android:** Unsupported.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Debug, Default, PartialEq, Clone)
+)]
+pub struct FileDialog {
+    /// What the dialog lets the user pick.
+    pub kind: FileDialogKind,
+    /// The title of the dialog. A default title is used if it's `None`.
+    pub title: Option<String>,
+    /// The folder the dialog starts in.
+    pub directory: Option<PathBuf>,
+    /// The file name the dialog is filled in with, for [`FileDialogKind::SaveFile`].
+    pub file_name: Option<String>,
+    /// The filters of the files shown in the dialog. All files are shown if it's empty.
+    pub filters: Vec<FileDialogFilter>,
+    /// The window the dialog belongs to, which it's shown above. The dialog is shown on its own if
+    /// it's `None`.
+    pub window: Option<Entity>,
+}
+
+impl FileDialog {
+    /// Makes a dialog of the given kind.
+    pub fn new(kind: FileDialogKind) -> Self {
+        Self {
+            kind,
+            ..Default::default()
+        }
+    }
+
+    /// Makes a dialog to pick a single existing file.
+    pub fn open_file() -> Self {
+        Self::new(FileDialogKind::OpenFile)
+    }
+
+    /// Makes a dialog to pick one or more existing files.
+    pub fn open_files() -> Self {
+        Self::new(FileDialogKind::OpenFiles)
+    }
+
+    /// Makes a dialog to pick a single existing folder.
+    pub fn pick_folder() -> Self {
+        Self::new(FileDialogKind::PickFolder)
+    }
+
+    /// Makes a dialog to pick the path of a file to save.
+    pub fn save_file() -> Self {
+        Self::new(FileDialogKind::SaveFile)
+    }
+
+    /// Sets the title of the dialog.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the folder the dialog starts in.
+    pub fn with_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    /// Sets the file name the dialog is filled in with.
+    pub fn with_file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    /// Adds a filter of the files shown in the dialog.
+    pub fn with_filter(mut self, name: impl Into<String>, extensions: &[&str]) -> Self {
+        self.filters.push(FileDialogFilter {
+            name: name.into(),
+            extensions: extensions.iter().map(ToString::to_string).collect(),
+        });
+        self
+    }
+
+    /// Sets the window the dialog belongs to.
+    pub fn with_window(mut self, window: Entity) -> Self {
+        self.window = Some(window);
+        self
+    }
+}
+
+/// A file or folder picked in a [`FileDialog`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+pub struct PickedFile {
+    /// The name of the file or folder.
+    pub name: String,
+    /// The path of the file or folder.
+    ///
+    /// This is `None` on the web, where pages can't access the paths of files.
+    pub path: Option<PathBuf>,
+    /// The contents of the file.
+    ///
+    /// This is only read on the web, where the file can't be opened through its path. It's `None`
+    /// on other platforms.
+    pub contents: Option<Vec<u8>>,
+}
+
+/// A [`FileDialog`] was closed by the user.
+///
+/// This is triggered on the entity of the [`FileDialog`].
+#[derive(EntityEvent, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+pub struct FileDialogClosed {
+    /// The entity of the dialog.
+    pub entity: Entity,
+    /// What the dialog let the user pick.
+    pub kind: FileDialogKind,
+    /// The picked files or folder, which is empty if the dialog was canceled.
+    pub files: Vec<PickedFile>,
+}
@@ -17,19 +17,31 @@ extern crate std;
 
 extern crate alloc;
 
+mod clipboard;
 mod cursor;
+#[cfg(feature = "dropped_file_assets")]
+mod dropped_file;
 mod event;
+mod file_dialog;
 mod monitor;
+mod notification;
 mod raw_handle;
 mod system;
+mod tray;
 mod window;
 
 pub use crate::raw_handle::*;
 
+pub use clipboard::*;
 pub use cursor::*;
+#[cfg(feature = "dropped_file_assets")]
+pub use dropped_file::*;
 pub use event::*;
+pub use file_dialog::*;
 pub use monitor::*;
+pub use notification::*;
 pub use system::*;
+pub use tray::*;
 pub use window::*;
 
 /// The windowing prelude.
@@ -38,7 +50,7 @@ pub use window::*;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, Ime, MonitorSelection,
+        Clipboard, CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, Ime, MonitorSelection,
         VideoModeSelection, Window, WindowMoved, WindowPlugin, WindowPosition,
         WindowResizeConstraints,
     };
@@ -46,6 +58,7 @@ pub mod prelude {
 
 use alloc::sync::Arc;
 use bevy_app::prelude::*;
+use bevy_ecs::schedule::{common_conditions::any_with_component, IntoScheduleConfigs};
 use bevy_platform::sync::Mutex;
 
 impl Default for WindowPlugin {
@@ -122,7 +135,11 @@ impl Plugin for WindowPlugin {
             .add_message::<FileDragAndDrop>()
             .add_message::<WindowMoved>()
             .add_message::<WindowThemeChanged>()
-            .add_message::<AppLifecycle>();
+            .add_message::<WindowModeChanged>()
+            .add_message::<AppLifecycle>()
+            .add_message::<ClipboardPaste>()
+            .init_resource::<Clipboard>()
+            .init_resource::<Notifications>();
 
         if let Some(primary_window) = &self.primary_window {
             let mut entity_commands = app.world_mut().spawn(primary_window.clone());
@@ -145,6 +162,11 @@ impl Plugin for WindowPlugin {
             ExitCondition::DontExit => {}
         }
 
+        app.add_systems(
+            PostUpdate,
+            update_window_input_regions.run_if(any_with_component::<WindowInputRegions>),
+        );
+
         if self.close_when_requested {
             // Need to run before `exit_on_*` systems
             app.add_systems(Update, close_when_requested);
@@ -1,6 +1,7 @@
 use alloc::{string::String, vec::Vec};
 use bevy_ecs::component::Component;
 use bevy_math::{IVec2, UVec2};
+use core::cmp::Reverse;
 
 #[cfg(feature = "bevy_reflect")]
 use {bevy_ecs::prelude::ReflectComponent, bevy_reflect::Reflect};
@@ -60,11 +61,53 @@ impl Monitor {
     pub fn physical_size(&self) -> UVec2 {
         UVec2::new(self.physical_width, self.physical_height)
     }
+
+    /// Returns the video mode the monitor is in, if it's one of its [`video_modes`](Self::video_modes).
+    pub fn current_video_mode(&self) -> Option<VideoMode> {
+        self.video_modes
+            .iter()
+            .filter(|mode| {
+                mode.physical_size == self.physical_size()
+                    && Some(mode.refresh_rate_millihertz) == self.refresh_rate_millihertz
+            })
+            .max_by_key(|mode| mode.bit_depth)
+            .copied()
+    }
+
+    /// Returns the distinct resolutions of the video modes of the monitor, from the largest to the
+    /// smallest, to be listed in graphics settings.
+    pub fn resolutions(&self) -> Vec<UVec2> {
+        let mut resolutions: Vec<UVec2> = self
+            .video_modes
+            .iter()
+            .map(|mode| mode.physical_size)
+            .collect();
+        resolutions.sort_by_key(|size| Reverse((size.x * size.y, size.x)));
+        resolutions.dedup();
+        resolutions
+    }
+
+    /// Returns the video modes of the monitor with the given resolution, from the highest refresh
+    /// rate and bit depth to the lowest.
+    pub fn video_modes_with_resolution(&self, physical_size: UVec2) -> Vec<VideoMode> {
+        let mut video_modes: Vec<VideoMode> = self
+            .video_modes
+            .iter()
+            .filter(|mode| mode.physical_size == physical_size)
+            .copied()
+            .collect();
+        video_modes.sort_by_key(|mode| Reverse((mode.refresh_rate_millihertz, mode.bit_depth)));
+        video_modes
+    }
 }
 
 /// Represents a video mode that a monitor supports
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[cfg_attr(feature = "bevy_reflect", derive(Reflect), reflect(Debug, Clone))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Hash, Clone)
+)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(
     all(feature = "serialize", feature = "bevy_reflect"),
@@ -78,3 +121,10 @@ pub struct VideoMode {
     /// The refresh rate in millihertz
     pub refresh_rate_millihertz: u32,
 }
+
+impl VideoMode {
+    /// Returns the refresh rate in hertz.
+    pub fn refresh_rate_hz(&self) -> f32 {
+        self.refresh_rate_millihertz as f32 / 1000.0
+    }
+}
@@ -0,0 +1,100 @@
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+};
+use bevy_ecs::resource::Resource;
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::Reflect;
+
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// A notification shown by the notification center of the platform.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct Notification {
+    /// The title of the notification.
+    pub title: String,
+    /// The text of the notification.
+    pub body: String,
+    /// The icon of the notification, see [`Notifications::send`].
+    pub icon: Option<String>,
+}
+
+/// A platform notification center, used by [`Notifications`].
+///
+/// Windowing backends such as `bevy_winit` provide one for the platforms they support.
+pub trait NotificationProvider: Send + Sync + 'static {
+    /// Shows `notification` to the user.
+    fn send(&mut self, notification: Notification);
+}
+
+/// The notification center of the platform, to alert the user even when the app is in the
+/// background, for instance when a long task completes.
+///
+/// Unless a [`NotificationProvider`] is set, by the windowing backend or with
+/// [`Notifications::with_provider`], the notifications aren't shown.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_window::Notifications;
+/// fn notify_export_done(mut notifications: ResMut<Notifications>) {
+///     notifications.send("Export done", "The level was saved to level.ron", None);
+/// }
+/// ```
+#[derive(Resource, Default)]
+pub struct Notifications {
+    provider: Option<Box<dyn NotificationProvider>>,
+}
+
+impl Notifications {
+    /// Makes a notification center backed by `provider`.
+    pub fn with_provider(provider: impl NotificationProvider) -> Self {
+        Self {
+            provider: Some(Box::new(provider)),
+        }
+    }
+
+    /// Returns `true` if the notification center is backed by a [`NotificationProvider`], rather
+    /// than ignoring the notifications.
+    pub fn has_provider(&self) -> bool {
+        self.provider.is_some()
+    }
+
+    /// Shows a notification with the given title, text and icon to the user.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The icon is the path of an image file.
+    /// - **Linux:** The icon is the path of an image file, or the name of an icon of the desktop
+    ///   environment, such as `dialog-information`.
+    /// - **macOS:** The icon is ignored, and the icon of the app is shown instead.
+    /// - **Web:** The icon is the URL of an image. The user is asked for the permission to show
+    ///   notifications the first time, and the notifications are ignored if they refuse.
+    pub fn send(&mut self, title: impl Into<String>, body: impl Into<String>, icon: Option<&str>) {
+        self.send_notification(Notification {
+            title: title.into(),
+            body: body.into(),
+            icon: icon.map(ToString::to_string),
+        });
+    }
+
+    /// Shows `notification` to the user.
+    ///
+    /// See [`Notifications::send`] for the platform-specific behavior.
+    pub fn send_notification(&mut self, notification: Notification) {
+        if let Some(provider) = &mut self.provider {
+            provider.send(notification);
+        }
+    }
+}
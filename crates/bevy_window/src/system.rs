@@ -1,7 +1,12 @@
-use crate::{ClosingWindow, PrimaryWindow, Window, WindowCloseRequested};
+use crate::{
+    ClosingWindow, CursorMoved, CursorOptions, PrimaryWindow, Window, WindowCloseRequested,
+    WindowInputRegions,
+};
 
 use bevy_app::AppExit;
 use bevy_ecs::prelude::*;
+use bevy_input::mouse::MouseMotion;
+use bevy_platform::collections::HashMap;
 
 /// Exit the application when there are no open windows.
 ///
@@ -56,3 +61,55 @@ pub fn close_when_requested(
         commands.entity(event.window).try_insert(ClosingWindow);
     }
 }
+
+/// The number of frames a window with [`WindowInputRegions`] stays hit-testable after the mouse
+/// moves, waiting for the platform to report the position of the cursor.
+const INPUT_REGIONS_REARM_FRAMES: u8 = 2;
+
+/// Toggles [`CursorOptions::hit_test`] of the windows with [`WindowInputRegions`], depending on
+/// whether the cursor is in one of their regions.
+///
+/// This system is added by the [`WindowPlugin`].
+///
+/// [`WindowPlugin`]: crate::WindowPlugin
+pub fn update_window_input_regions(
+    mut windows: Query<(Entity, &WindowInputRegions, &mut CursorOptions)>,
+    mut cursor_moved: MessageReader<CursorMoved>,
+    mut mouse_motion: MessageReader<MouseMotion>,
+    mut rearmed: Local<HashMap<Entity, u8>>,
+) {
+    let mut positions = HashMap::<Entity, _>::default();
+    for event in cursor_moved.read() {
+        positions.insert(event.window, event.position);
+    }
+    let mouse_moved = mouse_motion.read().count() > 0;
+
+    rearmed.retain(|window, _| windows.contains(*window));
+    for (window, regions, mut cursor_options) in &mut windows {
+        let hit_test = if let Some(&position) = positions.get(&window) {
+            rearmed.remove(&window);
+            regions.contains(position)
+        } else if !cursor_options.hit_test {
+            // The window doesn't receive the cursor position while the pointer passes through it.
+            if !mouse_moved {
+                continue;
+            }
+            rearmed.insert(window, INPUT_REGIONS_REARM_FRAMES);
+            true
+        } else if let Some(frames) = rearmed.get_mut(&window) {
+            *frames -= 1;
+            if *frames > 0 {
+                continue;
+            }
+            // The cursor isn't over the window.
+            rearmed.remove(&window);
+            false
+        } else {
+            continue;
+        };
+
+        if cursor_options.hit_test != hit_test {
+            cursor_options.hit_test = hit_test;
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use alloc::{string::String, vec::Vec};
+use bevy_ecs::{component::Component, entity::Entity, event::EntityEvent};
+use bevy_input::mouse::MouseButton;
+
+#[cfg(feature = "bevy_reflect")]
+use {
+    bevy_ecs::prelude::ReflectComponent,
+    bevy_reflect::{std_traits::ReflectDefault, Reflect},
+};
+
+/// An icon in the system tray, also known as the notification area, with an optional menu.
+///
+/// The icon is shown while this component exists, and is updated when it changes. This lets
+/// desktop apps keep running in the background, for instance by hiding their window with
+/// [`Window::visible`](crate::Window::visible) and showing it again when the icon is clicked.
+///
+/// A [`TrayIconClicked`] event is triggered on the entity when the icon is clicked, and a
+/// [`TrayMenuItemClicked`] event when an item of its menu is clicked. The menu is shown when the
+/// icon is right-clicked.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_window::{TrayIcon, TrayMenuItem, TrayMenuItemClicked};
+/// fn spawn_tray_icon(mut commands: Commands) {
+///     commands
+///         .spawn(
+///             TrayIcon::default()
+///                 .with_tooltip("My app")
+///                 .with_item(TrayMenuItem::item("show", "Show"))
+///                 .with_item(TrayMenuItem::Separator)
+///                 .with_item(TrayMenuItem::item("quit", "Quit")),
+///         )
+///         .observe(|clicked: On<TrayMenuItemClicked>| {
+///             if clicked.id == "quit" {
+///                 // Exit the app.
+///             }
+///         });
+/// }
+/// ```
+///
+/// ## Platform-specific
+///
+/// - **Linux / Web / iOS / Android:** Unsupported.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Debug, Default, PartialEq, Clone)
+)]
+pub struct TrayIcon {
+    /// The image of the icon. An empty icon is shown if it's `None`.
+    pub icon: Option<TrayIconImage>,
+    /// The text shown when hovering the icon.
+    pub tooltip: Option<String>,
+    /// The items of the menu of the icon. No menu is shown if it's empty.
+    pub menu: Vec<TrayMenuItem>,
+}
+
+impl TrayIcon {
+    /// Sets the image of the icon.
+    pub fn with_icon(mut self, icon: TrayIconImage) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Sets the text shown when hovering the icon.
+    pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Adds an item at the end of the menu of the icon.
+    pub fn with_item(mut self, item: TrayMenuItem) -> Self {
+        self.menu.push(item);
+        self
+    }
+}
+
+/// The image of a [`TrayIcon`], as RGBA pixels with 8 bits per channel.
+///
+/// The image is scaled to the size of the tray icons of the platform, which is usually 16 or 32
+/// pixels wide.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, PartialEq, Clone)
+)]
+pub struct TrayIconImage {
+    /// The width of the image in pixels.
+    pub width: u32,
+    /// The height of the image in pixels.
+    pub height: u32,
+    /// The pixels of the image, row by row from the top left corner.
+    pub rgba: Vec<u8>,
+}
+
+/// An item of the menu of a [`TrayIcon`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+pub enum TrayMenuItem {
+    /// An item that triggers a [`TrayMenuItemClicked`] event when clicked.
+    Item {
+        /// The identifier of the item, which is given back by [`TrayMenuItemClicked`].
+        id: String,
+        /// The text of the item.
+        label: String,
+        /// Whether the item can be clicked.
+        enabled: bool,
+    },
+    /// A line separating groups of items.
+    Separator,
+}
+
+impl TrayMenuItem {
+    /// Makes an enabled item with the given identifier and text.
+    pub fn item(id: impl Into<String>, label: impl Into<String>) -> Self {
+        Self::Item {
+            id: id.into(),
+            label: label.into(),
+            enabled: true,
+        }
+    }
+}
+
+/// A [`TrayIcon`] was clicked.
+///
+/// This is triggered on the entity of the [`TrayIcon`].
+#[derive(EntityEvent, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+pub struct TrayIconClicked {
+    /// The entity of the tray icon.
+    pub entity: Entity,
+    /// The mouse button that clicked the icon.
+    pub button: MouseButton,
+}
+
+/// An item of the menu of a [`TrayIcon`] was clicked.
+///
+/// This is triggered on the entity of the [`TrayIcon`].
+#[derive(EntityEvent, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+pub struct TrayMenuItemClicked {
+    /// The entity of the tray icon.
+    pub entity: Entity,
+    /// The identifier of the [`TrayMenuItem::Item`] that was clicked.
+    pub id: String,
+}
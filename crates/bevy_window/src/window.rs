@@ -1,13 +1,13 @@
 #[cfg(feature = "std")]
 use alloc::format;
-use alloc::{borrow::ToOwned, string::String};
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
 use core::num::NonZero;
 
 use bevy_ecs::{
     entity::{ContainsEntity, Entity},
     prelude::Component,
 };
-use bevy_math::{CompassOctant, DVec2, IVec2, UVec2, Vec2};
+use bevy_math::{CompassOctant, DVec2, IVec2, Rect, UVec2, Vec2};
 use bevy_platform::sync::LazyLock;
 use log::warn;
 
@@ -542,6 +542,24 @@ impl Window {
         self.internal.drag_resize_request = Some(direction);
     }
 
+    /// Requests the attention of the user, for instance when a long-running task is done while the
+    /// window isn't focused. Calling this with `None` cancels the request.
+    ///
+    /// The request is automatically canceled when the window gets focused.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Flashes the taskbar button of the window, until it gets focused for
+    ///   [`UserAttention::Critical`].
+    /// - **macOS:** Bounces the dock icon, until the app gets focused for
+    ///   [`UserAttention::Critical`].
+    /// - **X11:** Sets the urgency hint of the window.
+    /// - **Wayland:** Requires the `xdg_activation_v1` protocol.
+    /// - **iOS / Android / Web:** Unsupported.
+    pub fn request_user_attention(&mut self, attention: Option<UserAttention>) {
+        self.internal.attention_request = Some(attention);
+    }
+
     /// The window's client area width in logical pixels.
     ///
     /// See [`WindowResolution`] for an explanation about logical/physical sizes.
@@ -776,6 +794,79 @@ impl Default for CursorOptions {
     }
 }
 
+/// The regions of a window that receive the pointer, which passes through the rest of the window to
+/// the windows below.
+///
+/// This lets overlays and companion apps float above other windows in a
+/// [`transparent`](Window::transparent) window, without eating the clicks outside of their
+/// interactive parts.
+///
+/// The regions are applied by toggling [`CursorOptions::hit_test`] as the cursor moves, so they're
+/// only supported on the platforms that support it. The pointer passes through the window while the
+/// cursor is outside of the regions. When the mouse moves, the window is made hit-testable again for
+/// a couple of frames to find out whether the cursor entered a region, during which a click outside
+/// of the regions can still be received by the window.
+///
+/// ## Platform-specific
+///
+/// - iOS / Android / Web / X11: Unsupported.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Debug, Default, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct WindowInputRegions {
+    /// The regions receiving the pointer, in logical pixels from the top-left corner of the window.
+    pub regions: Vec<Rect>,
+}
+
+impl WindowInputRegions {
+    /// Returns `true` if the position, in logical pixels, is in one of the regions.
+    pub fn contains(&self, position: Vec2) -> bool {
+        self.regions.iter().any(|region| region.contains(position))
+    }
+}
+
+/// The progress of a long-running task, such as loading or baking, shown on the taskbar button of a
+/// window.
+///
+/// Insert this component on a window entity to show the progress, and remove it or set it to
+/// [`TaskbarProgress::None`] to hide it.
+///
+/// ## Platform-specific
+///
+/// - **macOS / Linux / iOS / Android / Web:** Unsupported.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Debug, Default, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum TaskbarProgress {
+    /// No progress is shown.
+    #[default]
+    None,
+    /// The task is in progress, but its progress is unknown.
+    Indeterminate,
+    /// The task is in progress. The progress goes from `0.0` to `1.0`.
+    Normal(f32),
+    /// The task is paused. The progress goes from `0.0` to `1.0`.
+    Paused(f32),
+    /// The task has failed. The progress goes from `0.0` to `1.0`.
+    Error(f32),
+}
+
 /// Defines where a [`Window`] should be placed on the screen.
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(
@@ -1099,6 +1190,8 @@ pub struct InternalWindowState {
     drag_move_request: bool,
     /// If this is `Some` then the next frame we will ask to drag-resize the window.
     drag_resize_request: Option<CompassOctant>,
+    /// If this is `Some` then the next frame we will request or cancel the attention of the user.
+    attention_request: Option<Option<UserAttention>>,
     /// Unscaled cursor position.
     physical_cursor_position: Option<DVec2>,
 }
@@ -1123,6 +1216,11 @@ impl InternalWindowState {
     pub fn take_resize_request(&mut self) -> Option<CompassOctant> {
         self.drag_resize_request.take()
     }
+
+    /// Consumes the current attention request, if it exists. This should only be called by window backends.
+    pub fn take_attention_request(&mut self) -> Option<Option<UserAttention>> {
+        self.attention_request.take()
+    }
 }
 
 /// References a screen monitor.
@@ -1314,6 +1412,25 @@ pub enum CompositeAlphaMode {
     Inherit = 4,
 }
 
+/// How urgently the attention of the user is requested, with [`Window::request_user_attention`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Hash, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum UserAttention {
+    /// The user is notified until the window gets focused.
+    Critical,
+    /// The user is notified briefly.
+    Informational,
+}
+
 /// Defines the way a [`Window`] is displayed.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(
@@ -0,0 +1,155 @@
+//! Platform clipboards for [`Clipboard`].
+
+use bevy_app::{App, Plugin};
+use bevy_window::Clipboard;
+
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+use {
+    bevy_platform::cell::SyncCell,
+    bevy_window::{ClipboardImage, ClipboardProvider},
+    tracing::warn,
+};
+
+#[cfg(target_arch = "wasm32")]
+use {
+    alloc::sync::Arc,
+    bevy_app::PreUpdate,
+    bevy_ecs::prelude::*,
+    bevy_input::InputSystems,
+    bevy_platform::sync::Mutex,
+    bevy_window::{ClipboardContent, ClipboardPaste, ClipboardProvider, Window},
+    wasm_bindgen::{closure::Closure, JsCast},
+};
+
+/// Backs the [`Clipboard`] with the clipboard of the platform.
+pub(crate) struct WinitClipboardPlugin;
+
+impl Plugin for WinitClipboardPlugin {
+    fn build(&self, app: &mut App) {
+        #[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+        match arboard::Clipboard::new() {
+            Ok(clipboard) => {
+                app.insert_resource(Clipboard::with_provider(ArboardClipboard(SyncCell::new(
+                    clipboard,
+                ))));
+            }
+            Err(err) => warn!("Failed to access the clipboard. {}", err),
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        app.insert_resource(Clipboard::with_provider(WebClipboard))
+            .insert_resource(listen_to_pastes())
+            .add_systems(PreUpdate, send_pastes.before(InputSystems));
+    }
+}
+
+/// The clipboard of desktop platforms.
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+struct ArboardClipboard(SyncCell<arboard::Clipboard>);
+
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+impl ClipboardProvider for ArboardClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        self.0.get().get_text().ok()
+    }
+
+    fn set_text(&mut self, text: String) {
+        if let Err(err) = self.0.get().set_text(text) {
+            warn!("Failed to copy text to the clipboard. {}", err);
+        }
+    }
+
+    fn get_image(&mut self) -> Option<ClipboardImage> {
+        let image = self.0.get().get_image().ok()?;
+        Some(ClipboardImage {
+            width: image.width as u32,
+            height: image.height as u32,
+            data: image.bytes.into_owned(),
+        })
+    }
+
+    fn set_image(&mut self, image: ClipboardImage) {
+        let image = arboard::ImageData {
+            width: image.width as usize,
+            height: image.height as usize,
+            bytes: image.data.into(),
+        };
+        if let Err(err) = self.0.get().set_image(image) {
+            warn!("Failed to copy an image to the clipboard. {}", err);
+        }
+    }
+}
+
+/// The clipboard of the browser.
+///
+/// Browsers only let pages read the clipboard asynchronously and with the permission of the user,
+/// so the text pasted by the user is sent as [`ClipboardPaste`] messages instead. Images aren't
+/// supported.
+#[cfg(target_arch = "wasm32")]
+struct WebClipboard;
+
+#[cfg(target_arch = "wasm32")]
+impl ClipboardProvider for WebClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        None
+    }
+
+    fn set_text(&mut self, text: String) {
+        if let Some(window) = web_sys::window() {
+            // The returned promise is ignored: it's rejected if the page doesn't have focus, which
+            // can't be helped.
+            window.navigator().clipboard().write_text(&text);
+        }
+    }
+}
+
+/// The text pasted in the page since the last frame.
+#[cfg(target_arch = "wasm32")]
+#[derive(Resource)]
+struct WebPastes(Arc<Mutex<Vec<String>>>);
+
+/// Listens to the `paste` events of the page.
+#[cfg(target_arch = "wasm32")]
+fn listen_to_pastes() -> WebPastes {
+    let pastes = Arc::new(Mutex::new(Vec::new()));
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return WebPastes(pastes);
+    };
+
+    let queue = pastes.clone();
+    let listener = Closure::<dyn FnMut(_)>::new(move |event: web_sys::ClipboardEvent| {
+        let text = event
+            .clipboard_data()
+            .and_then(|data| data.get_data("text/plain").ok());
+        if let Some(text) = text.filter(|text| !text.is_empty()) {
+            queue.lock().unwrap().push(text);
+        }
+    });
+    if document
+        .add_event_listener_with_callback("paste", listener.as_ref().unchecked_ref())
+        .is_ok()
+    {
+        // The listener lives as long as the page.
+        listener.forget();
+    }
+    WebPastes(pastes)
+}
+
+/// Sends the text pasted in the page as [`ClipboardPaste`] messages to the focused window.
+#[cfg(target_arch = "wasm32")]
+fn send_pastes(
+    pastes: Res<WebPastes>,
+    windows: Query<(Entity, &Window)>,
+    mut paste_messages: MessageWriter<ClipboardPaste>,
+) {
+    let pasted = core::mem::take(&mut *pastes.0.lock().unwrap());
+    let Some((window, _)) = windows.iter().find(|(_, window)| window.focused) else {
+        return;
+    };
+    for text in pasted {
+        paste_messages.write(ClipboardPaste {
+            window,
+            content: ClipboardContent::Text(text),
+        });
+    }
+}
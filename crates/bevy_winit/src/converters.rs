@@ -2,12 +2,13 @@ use bevy_ecs::entity::Entity;
 use bevy_input::{
     keyboard::{KeyCode, KeyboardInput, NativeKeyCode},
     mouse::MouseButton,
+    pen::{PenBarrelButtons, PenInput, PenPhase},
     touch::{ForceTouch, TouchInput, TouchPhase},
     ButtonState,
 };
 use bevy_math::{CompassOctant, Vec2};
 use bevy_window::SystemCursorIcon;
-use bevy_window::{EnabledButtons, WindowLevel, WindowTheme};
+use bevy_window::{EnabledButtons, UserAttention, WindowLevel, WindowTheme};
 use winit::keyboard::{Key, NamedKey, NativeKey};
 
 #[cfg(target_os = "ios")]
@@ -75,6 +76,49 @@ pub fn convert_touch_input(
     }
 }
 
+/// Converts the touch of a stylus into a [`PenInput`], or returns `None` if it's the touch of a
+/// finger.
+///
+/// `winit` doesn't report styluses separately, but only reports the altitude of touches made
+/// by a stylus, such as the Apple Pencil on iOS.
+pub fn convert_stylus_touch(
+    touch_input: &winit::event::Touch,
+    location: winit::dpi::LogicalPosition<f64>,
+    window_entity: Entity,
+) -> Option<PenInput> {
+    let Some(winit::event::Force::Calibrated {
+        force,
+        max_possible_force,
+        altitude_angle: Some(altitude_angle),
+    }) = touch_input.force
+    else {
+        return None;
+    };
+    let phase = match touch_input.phase {
+        winit::event::TouchPhase::Started => PenPhase::Down,
+        winit::event::TouchPhase::Moved => PenPhase::Moved,
+        winit::event::TouchPhase::Ended => PenPhase::Up,
+        winit::event::TouchPhase::Cancelled => PenPhase::Canceled,
+    };
+    let pressure = match phase {
+        PenPhase::Down | PenPhase::Moved if max_possible_force > 0.0 => {
+            (force / max_possible_force).clamp(0.0, 1.0) as f32
+        }
+        _ => 0.0,
+    };
+    Some(PenInput {
+        phase,
+        position: Vec2::new(location.x as f32, location.y as f32),
+        pressure,
+        altitude_angle: Some(altitude_angle as f32),
+        azimuth_angle: None,
+        barrel_buttons: PenBarrelButtons::default(),
+        eraser: false,
+        window: window_entity,
+        id: touch_input.id,
+    })
+}
+
 pub fn convert_physical_native_key_code(
     native_key_code: winit::keyboard::NativeKeyCode,
 ) -> NativeKeyCode {
@@ -681,6 +725,13 @@ pub fn convert_window_level(window_level: WindowLevel) -> winit::window::WindowL
     }
 }
 
+pub fn convert_user_attention(attention: UserAttention) -> winit::window::UserAttentionType {
+    match attention {
+        UserAttention::Critical => winit::window::UserAttentionType::Critical,
+        UserAttention::Informational => winit::window::UserAttentionType::Informational,
+    }
+}
+
 pub fn convert_winit_theme(theme: winit::window::Theme) -> WindowTheme {
     match theme {
         winit::window::Theme::Light => WindowTheme::Light,
@@ -0,0 +1,120 @@
+//! Native file dialogs for [`FileDialog`].
+
+use bevy_app::{App, Last, Plugin};
+use bevy_ecs::{prelude::*, system::NonSendMarker};
+use bevy_tasks::{futures::check_ready, IoTaskPool, Task};
+use bevy_window::{FileDialog, FileDialogClosed, FileDialogKind, PickedFile};
+use rfd::{AsyncFileDialog, FileHandle};
+
+use crate::WINIT_WINDOWS;
+
+#[cfg(target_arch = "wasm32")]
+use tracing::warn;
+
+/// Opens the [`FileDialog`]s with `rfd`.
+pub(crate) struct WinitFileDialogPlugin;
+
+impl Plugin for WinitFileDialogPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Last, (open_file_dialogs, poll_file_dialogs).chain());
+    }
+}
+
+/// The task of an open [`FileDialog`].
+#[derive(Component)]
+struct FileDialogTask(Task<Vec<PickedFile>>);
+
+/// Opens the dialogs of the [`FileDialog`]s that were added.
+fn open_file_dialogs(
+    dialogs: Query<(Entity, &FileDialog), Added<FileDialog>>,
+    mut commands: Commands,
+    _non_send_marker: NonSendMarker,
+) {
+    for (entity, dialog) in &dialogs {
+        let mut builder = AsyncFileDialog::new();
+        if let Some(title) = &dialog.title {
+            builder = builder.set_title(title);
+        }
+        if let Some(directory) = &dialog.directory {
+            builder = builder.set_directory(directory);
+        }
+        if let Some(file_name) = &dialog.file_name {
+            builder = builder.set_file_name(file_name);
+        }
+        for filter in &dialog.filters {
+            builder = builder.add_filter(&filter.name, filter.extensions.as_slice());
+        }
+        if let Some(window) = dialog.window {
+            builder =
+                WINIT_WINDOWS.with_borrow(|winit_windows| match winit_windows.get_window(window) {
+                    Some(winit_window) => builder.set_parent(&**winit_window),
+                    None => builder,
+                });
+        }
+
+        let task = IoTaskPool::get().spawn(pick(builder, dialog.kind));
+        commands.entity(entity).insert(FileDialogTask(task));
+    }
+}
+
+/// Shows the dialog and waits for the user to close it.
+async fn pick(builder: AsyncFileDialog, kind: FileDialogKind) -> Vec<PickedFile> {
+    let handles = match kind {
+        FileDialogKind::OpenFile => builder.pick_file().await.into_iter().collect(),
+        FileDialogKind::OpenFiles => builder.pick_files().await.unwrap_or_default(),
+        #[cfg(not(target_arch = "wasm32"))]
+        FileDialogKind::PickFolder => builder.pick_folder().await.into_iter().collect(),
+        #[cfg(not(target_arch = "wasm32"))]
+        FileDialogKind::SaveFile => builder.save_file().await.into_iter().collect(),
+        #[cfg(target_arch = "wasm32")]
+        FileDialogKind::PickFolder | FileDialogKind::SaveFile => {
+            warn!("{kind:?} file dialogs aren't supported on the web.");
+            Vec::new()
+        }
+    };
+
+    let mut files = Vec::with_capacity(handles.len());
+    for handle in handles {
+        files.push(picked_file(handle).await);
+    }
+    files
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn picked_file(handle: FileHandle) -> PickedFile {
+    PickedFile {
+        name: handle.file_name(),
+        path: Some(handle.path().to_path_buf()),
+        contents: None,
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn picked_file(handle: FileHandle) -> PickedFile {
+    PickedFile {
+        name: handle.file_name(),
+        path: None,
+        contents: Some(handle.read().await),
+    }
+}
+
+/// Triggers [`FileDialogClosed`] for the dialogs that were closed.
+fn poll_file_dialogs(
+    mut tasks: Query<(Entity, &FileDialog, &mut FileDialogTask)>,
+    mut commands: Commands,
+) {
+    for (entity, dialog, mut task) in &mut tasks {
+        let Some(files) = check_ready(&mut task.0) else {
+            continue;
+        };
+        // The component is removed first, so that observers can open a new dialog.
+        commands
+            .entity(entity)
+            .remove::<(FileDialog, FileDialogTask)>();
+        commands.trigger(FileDialogClosed {
+            entity,
+            kind: dialog.kind,
+            files,
+        });
+    }
+}
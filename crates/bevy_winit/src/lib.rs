@@ -1,5 +1,5 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
-#![forbid(unsafe_code)]
+#![deny(unsafe_code)]
 #![doc(
     html_logo_url = "https://bevy.org/assets/icon.png",
     html_favicon_url = "https://bevy.org/assets/icon.png"
@@ -44,10 +44,44 @@ use crate::{
 };
 
 pub mod accessibility;
+#[cfg(all(
+    feature = "clipboard",
+    any(
+        target_os = "windows",
+        target_os = "macos",
+        target_os = "linux",
+        target_arch = "wasm32"
+    )
+))]
+mod clipboard;
 mod converters;
 mod cursor;
+#[cfg(all(
+    feature = "file_dialog",
+    any(
+        target_os = "windows",
+        target_os = "macos",
+        target_os = "linux",
+        target_arch = "wasm32"
+    )
+))]
+mod file_dialog;
+#[cfg(all(
+    feature = "notifications",
+    any(
+        target_os = "windows",
+        target_os = "macos",
+        target_os = "linux",
+        target_arch = "wasm32"
+    )
+))]
+mod notification;
 mod state;
 mod system;
+#[cfg(target_os = "windows")]
+mod taskbar;
+#[cfg(all(feature = "tray_icon", any(target_os = "windows", target_os = "macos")))]
+mod tray;
 mod winit_config;
 mod winit_monitors;
 mod winit_windows;
@@ -150,6 +184,40 @@ impl<T: Message> Plugin for WinitPlugin<T> {
 
         app.add_plugins(AccessKitPlugin);
         app.add_plugins(cursor::WinitCursorPlugin);
+        #[cfg(all(
+            feature = "clipboard",
+            any(
+                target_os = "windows",
+                target_os = "macos",
+                target_os = "linux",
+                target_arch = "wasm32"
+            )
+        ))]
+        app.add_plugins(clipboard::WinitClipboardPlugin);
+        #[cfg(all(
+            feature = "file_dialog",
+            any(
+                target_os = "windows",
+                target_os = "macos",
+                target_os = "linux",
+                target_arch = "wasm32"
+            )
+        ))]
+        app.add_plugins(file_dialog::WinitFileDialogPlugin);
+        #[cfg(all(
+            feature = "notifications",
+            any(
+                target_os = "windows",
+                target_os = "macos",
+                target_os = "linux",
+                target_arch = "wasm32"
+            )
+        ))]
+        app.add_plugins(notification::WinitNotificationPlugin);
+        #[cfg(target_os = "windows")]
+        app.add_plugins(taskbar::WinitTaskbarPlugin);
+        #[cfg(all(feature = "tray_icon", any(target_os = "windows", target_os = "macos")))]
+        app.add_plugins(tray::WinitTrayIconPlugin);
     }
 }
 
@@ -0,0 +1,90 @@
+//! Platform notification centers for [`Notifications`].
+
+use bevy_app::{App, Plugin};
+use bevy_window::{Notification, NotificationProvider, Notifications};
+
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+use tracing::warn;
+
+#[cfg(target_arch = "wasm32")]
+use {
+    wasm_bindgen::{closure::Closure, JsValue},
+    web_sys::NotificationPermission,
+};
+
+/// Backs the [`Notifications`] with the notification center of the platform.
+pub(crate) struct WinitNotificationPlugin;
+
+impl Plugin for WinitNotificationPlugin {
+    fn build(&self, app: &mut App) {
+        #[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+        app.insert_resource(Notifications::with_provider(NativeNotifications));
+
+        #[cfg(target_arch = "wasm32")]
+        app.insert_resource(Notifications::with_provider(WebNotifications));
+    }
+}
+
+/// The notification center of desktop platforms.
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+struct NativeNotifications;
+
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+impl NotificationProvider for NativeNotifications {
+    fn send(&mut self, notification: Notification) {
+        let mut native = notify_rust::Notification::new();
+        native.summary(&notification.title).body(&notification.body);
+        if let Some(icon) = &notification.icon {
+            native.icon(icon);
+        }
+        // Showing a notification waits for the notification center, which can take a while, so
+        // it's done on its own thread.
+        std::thread::spawn(move || {
+            if let Err(err) = native.show() {
+                warn!("Failed to show a notification. {}", err);
+            }
+        });
+    }
+}
+
+/// The notification center of the browser.
+///
+/// Browsers only show notifications with the permission of the user, which is asked for when the
+/// first notification is sent.
+#[cfg(target_arch = "wasm32")]
+struct WebNotifications;
+
+#[cfg(target_arch = "wasm32")]
+impl NotificationProvider for WebNotifications {
+    fn send(&mut self, notification: Notification) {
+        match web_sys::Notification::permission() {
+            NotificationPermission::Granted => show_web_notification(&notification),
+            NotificationPermission::Default => {
+                let Ok(promise) = web_sys::Notification::request_permission() else {
+                    return;
+                };
+                let on_permission = Closure::<dyn FnMut(JsValue)>::once(move |permission| {
+                    if permission.as_string().as_deref() == Some("granted") {
+                        show_web_notification(&notification);
+                    }
+                });
+                let _ = promise.then(&on_permission);
+                // The closure is called once the user answers, which may be long after this.
+                on_permission.forget();
+            }
+            // The user refused to show notifications.
+            _ => {}
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn show_web_notification(notification: &Notification) {
+    let options = web_sys::NotificationOptions::new();
+    options.set_body(&notification.body);
+    if let Some(icon) = &notification.icon {
+        options.set_icon(icon);
+    }
+    // The notification is shown as long as the browser keeps it, even once the handle is dropped.
+    let _ = web_sys::Notification::new_with_options(&notification.title, &options);
+}
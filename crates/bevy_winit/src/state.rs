@@ -10,11 +10,12 @@ use bevy_ecs::{
 };
 use bevy_input::{
     gestures::*,
-    mouse::{MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
+    mouse::{MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel, PointingDeviceId},
+    pen::{PenInput, PenPhase},
 };
 use bevy_log::{trace, warn};
 use bevy_math::{ivec2, DVec2, Vec2};
-use bevy_platform::time::Instant;
+use bevy_platform::{collections::HashMap, time::Instant};
 #[cfg(not(target_arch = "wasm32"))]
 use bevy_tasks::tick_global_task_pools_on_main_thread;
 use core::marker::PhantomData;
@@ -25,15 +26,15 @@ use winit::{
     dpi::PhysicalSize,
     event,
     event::{DeviceEvent, DeviceId, StartCause, WindowEvent},
-    event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
+    event_loop::{ActiveEventLoop, ControlFlow, DeviceEvents, EventLoop},
     window::WindowId,
 };
 
 use bevy_window::{
     AppLifecycle, CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, Ime, RequestRedraw,
     Window, WindowBackendScaleFactorChanged, WindowCloseRequested, WindowDestroyed,
-    WindowEvent as BevyWindowEvent, WindowFocused, WindowMoved, WindowOccluded, WindowResized,
-    WindowScaleFactorChanged, WindowThemeChanged,
+    WindowEvent as BevyWindowEvent, WindowFocused, WindowInputRegions, WindowMoved, WindowOccluded,
+    WindowResized, WindowScaleFactorChanged, WindowThemeChanged,
 };
 #[cfg(target_os = "android")]
 use bevy_window::{CursorOptions, PrimaryWindow, RawHandleWrapper};
@@ -78,6 +79,10 @@ pub(crate) struct WinitAppRunnerState<T: Message> {
     bevy_window_events: Vec<bevy_window::WindowEvent>,
     /// Raw Winit window events to send
     raw_winit_events: Vec<RawWinitWindowEvent>,
+    /// The ids given to the pointing devices, in the order they first sent input.
+    pointing_devices: HashMap<DeviceId, PointingDeviceId>,
+    /// Is `true` if device events are received while the app isn't focused.
+    unfocused_device_events: bool,
     _marker: PhantomData<T>,
 
     message_writer_system_state: SystemState<(
@@ -125,6 +130,8 @@ impl<M: Message> WinitAppRunnerState<M> {
             startup_forced_updates: 5,
             bevy_window_events: Vec::new(),
             raw_winit_events: Vec::new(),
+            pointing_devices: HashMap::default(),
+            unfocused_device_events: false,
             _marker: PhantomData,
             message_writer_system_state,
             scheduled_tick_start: None,
@@ -303,11 +310,16 @@ impl<M: Message> ApplicationHandler<M> for WinitAppRunnerState<M> {
                         win.set_physical_cursor_position(None);
                         self.bevy_window_events.send(CursorLeft { window });
                     }
-                    WindowEvent::MouseInput { state, button, .. } => {
+                    WindowEvent::MouseInput {
+                        device_id,
+                        state,
+                        button,
+                    } => {
                         self.bevy_window_events.send(MouseButtonInput {
                             button: converters::convert_mouse_button(button),
                             state: converters::convert_element_state(state),
                             window,
+                            device: pointing_device_id(&mut self.pointing_devices, device_id),
                         });
                     }
                     WindowEvent::PinchGesture { delta, .. } => {
@@ -325,13 +337,16 @@ impl<M: Message> ApplicationHandler<M> for WinitAppRunnerState<M> {
                             y: delta.y,
                         }));
                     }
-                    WindowEvent::MouseWheel { delta, .. } => match delta {
+                    WindowEvent::MouseWheel {
+                        device_id, delta, ..
+                    } => match delta {
                         event::MouseScrollDelta::LineDelta(x, y) => {
                             self.bevy_window_events.send(MouseWheel {
                                 unit: MouseScrollUnit::Line,
                                 x,
                                 y,
                                 window,
+                                device: pointing_device_id(&mut self.pointing_devices, device_id),
                             });
                         }
                         event::MouseScrollDelta::PixelDelta(p) => {
@@ -340,6 +355,7 @@ impl<M: Message> ApplicationHandler<M> for WinitAppRunnerState<M> {
                                 x: p.x as f32,
                                 y: p.y as f32,
                                 window,
+                                device: pointing_device_id(&mut self.pointing_devices, device_id),
                             });
                         }
                     },
@@ -347,8 +363,23 @@ impl<M: Message> ApplicationHandler<M> for WinitAppRunnerState<M> {
                         let location = touch
                             .location
                             .to_logical(win.resolution.scale_factor() as f64);
-                        self.bevy_window_events
-                            .send(converters::convert_touch_input(touch, location, window));
+                        if let Some(pen) =
+                            converters::convert_stylus_touch(&touch, location, window)
+                        {
+                            self.bevy_window_events.send(pen);
+                            // Styluses reported as touches can't hover, so they leave as soon as
+                            // they're lifted
+                            if pen.phase == PenPhase::Up {
+                                self.bevy_window_events.send(PenInput {
+                                    phase: PenPhase::Left,
+                                    pressure: 0.0,
+                                    ..pen
+                                });
+                            }
+                        } else {
+                            self.bevy_window_events
+                                .send(converters::convert_touch_input(touch, location, window));
+                        }
                     }
                     WindowEvent::Focused(focused) => {
                         win.focused = focused;
@@ -437,14 +468,15 @@ impl<M: Message> ApplicationHandler<M> for WinitAppRunnerState<M> {
     fn device_event(
         &mut self,
         _event_loop: &ActiveEventLoop,
-        _device_id: DeviceId,
+        device_id: DeviceId,
         event: DeviceEvent,
     ) {
         self.device_event_received = true;
 
         if let DeviceEvent::MouseMotion { delta: (x, y) } = event {
             let delta = Vec2::new(x as f32, y as f32);
-            self.bevy_window_events.send(MouseMotion { delta });
+            let device = pointing_device_id(&mut self.pointing_devices, device_id);
+            self.bevy_window_events.send(MouseMotion { delta, device });
         }
     }
 
@@ -459,6 +491,21 @@ impl<M: Message> ApplicationHandler<M> for WinitAppRunnerState<M> {
         create_windows(event_loop, create_window.get_mut(self.world_mut()));
         create_window.apply(self.world_mut());
 
+        // Windows with input regions need the mouse motion while the pointer passes through them,
+        // which usually means the app isn't focused.
+        let mut input_regions = self
+            .world_mut()
+            .query_filtered::<(), With<WindowInputRegions>>();
+        let unfocused_device_events = input_regions.iter(self.world()).next().is_some();
+        if unfocused_device_events != self.unfocused_device_events {
+            self.unfocused_device_events = unfocused_device_events;
+            event_loop.listen_device_events(if unfocused_device_events {
+                DeviceEvents::Always
+            } else {
+                DeviceEvents::WhenFocused
+            });
+        }
+
         // TODO: This is a workaround for https://github.com/bevyengine/bevy/issues/17488
         //       while preserving the iOS fix in https://github.com/bevyengine/bevy/pull/11245
         //       The monitor sync logic likely belongs in monitor event handlers and not here.
@@ -852,6 +899,9 @@ impl<M: Message> WinitAppRunnerState<M> {
                 BevyWindowEvent::TouchInput(e) => {
                     world.write_message(e);
                 }
+                BevyWindowEvent::PenInput(e) => {
+                    world.write_message(e);
+                }
                 BevyWindowEvent::KeyboardInput(e) => {
                     world.write_message(e);
                 }
@@ -947,6 +997,16 @@ pub(crate) fn react_to_scale_factor_change(
     }
 }
 
+/// Returns the [`PointingDeviceId`] of a `winit` device, giving the next id to the devices that
+/// didn't send input before.
+fn pointing_device_id(
+    pointing_devices: &mut HashMap<DeviceId, PointingDeviceId>,
+    device_id: DeviceId,
+) -> PointingDeviceId {
+    let next_id = PointingDeviceId(pointing_devices.len() as u64 + 1);
+    *pointing_devices.entry(device_id).or_insert(next_id)
+}
+
 #[cfg(test)]
 mod tests {
     use bevy_app::Update;
@@ -14,7 +14,7 @@ use bevy_input::keyboard::{Key, KeyCode, KeyboardFocusLost, KeyboardInput};
 use bevy_window::{
     ClosingWindow, CursorOptions, Monitor, PrimaryMonitor, RawHandleWrapper, VideoMode, Window,
     WindowClosed, WindowClosing, WindowCreated, WindowEvent, WindowFocused, WindowMode,
-    WindowResized, WindowWrapper,
+    WindowModeChanged, WindowResized, WindowWrapper,
 };
 use tracing::{error, info, warn};
 
@@ -34,8 +34,8 @@ use winit::platform::web::WindowExtWebSys;
 use crate::{
     accessibility::ACCESS_KIT_ADAPTERS,
     converters::{
-        convert_enabled_buttons, convert_resize_direction, convert_window_level,
-        convert_window_theme, convert_winit_theme,
+        convert_enabled_buttons, convert_resize_direction, convert_user_attention,
+        convert_window_level, convert_window_theme, convert_winit_theme,
     },
     get_selected_videomode, select_monitor,
     state::react_to_resize,
@@ -302,6 +302,7 @@ pub(crate) fn changed_windows(
     mut changed_windows: Query<(Entity, &mut Window, &mut CachedWindow), Changed<Window>>,
     monitors: Res<WinitMonitors>,
     mut window_resized: MessageWriter<WindowResized>,
+    mut window_mode_changed: MessageWriter<WindowModeChanged>,
     _non_send_marker: NonSendMarker,
 ) {
     WINIT_WINDOWS.with_borrow(|winit_windows| {
@@ -349,10 +350,17 @@ pub(crate) fn changed_windows(
                     WindowMode::Windowed => Some(None),
                 };
 
-                if let Some(new_mode) = new_mode
-                    && winit_window.fullscreen() != new_mode {
+                if let Some(new_mode) = new_mode {
+                    if winit_window.fullscreen() != new_mode {
                         winit_window.set_fullscreen(new_mode);
                     }
+                    window_mode_changed.write(WindowModeChanged {
+                        window: entity,
+                        mode: window.mode,
+                    });
+                } else {
+                    window.mode = cache.mode;
+                }
             }
 
             if window.resolution != cache.resolution {
@@ -479,6 +487,10 @@ pub(crate) fn changed_windows(
                     warn!("Winit returned an error while attempting to drag resize the window: {e}");
                 }
 
+            if let Some(attention) = window.internal.take_attention_request() {
+                winit_window.request_user_attention(attention.map(convert_user_attention));
+            }
+
             if window.focused != cache.focused && window.focused {
                 winit_window.focus_window();
             }
@@ -0,0 +1,147 @@
+//! Shows the [`TaskbarProgress`] of windows on the taskbar of Windows.
+
+#![expect(
+    unsafe_code,
+    reason = "The taskbar of Windows is only accessible through COM interfaces."
+)]
+
+use bevy_app::{App, Last, Plugin};
+use bevy_ecs::{prelude::*, system::NonSendMarker};
+use bevy_platform::collections::HashMap;
+use bevy_window::TaskbarProgress;
+use core::cell::OnceCell;
+use tracing::warn;
+use windows::Win32::{
+    Foundation::HWND,
+    System::Com::{
+        CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED,
+    },
+    UI::Shell::{
+        ITaskbarList3, TaskbarList, TBPFLAG, TBPF_ERROR, TBPF_INDETERMINATE, TBPF_NOPROGRESS,
+        TBPF_NORMAL, TBPF_PAUSED,
+    },
+};
+use winit::raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+use crate::{system::changed_windows, WINIT_WINDOWS};
+
+/// The progress values are sent to the taskbar as a fraction of this total.
+const PROGRESS_TOTAL: u64 = 10_000;
+
+thread_local! {
+    // `ITaskbarList3` can only be used from the thread that created it.
+    static TASKBAR: OnceCell<Option<ITaskbarList3>> = const { OnceCell::new() };
+}
+
+/// Shows the [`TaskbarProgress`] of windows on their taskbar button.
+pub(crate) struct WinitTaskbarPlugin;
+
+impl Plugin for WinitTaskbarPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Last, update_taskbar_progress.after(changed_windows));
+    }
+}
+
+/// Creates the taskbar interface, or returns `None` if the taskbar isn't available.
+fn create_taskbar() -> Option<ITaskbarList3> {
+    // SAFETY: No reserved pointer is passed. The result is ignored because COM may already have
+    // been initialized on this thread by winit, possibly with another concurrency model, which
+    // doesn't prevent creating the taskbar interface.
+    let _ = unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) };
+    // SAFETY: `TaskbarList` is the class of the `ITaskbarList3` interface.
+    let taskbar: ITaskbarList3 =
+        match unsafe { CoCreateInstance(&TaskbarList, None, CLSCTX_INPROC_SERVER) } {
+            Ok(taskbar) => taskbar,
+            Err(err) => {
+                warn!("Failed to access the taskbar. {}", err);
+                return None;
+            }
+        };
+    // SAFETY: `HrInit` must be called before any other method of the interface.
+    if let Err(err) = unsafe { taskbar.HrInit() } {
+        warn!("Failed to initialize the taskbar. {}", err);
+        return None;
+    }
+    Some(taskbar)
+}
+
+/// Sets the progress shown on the taskbar button of the window.
+fn set_progress(
+    taskbar: &ITaskbarList3,
+    hwnd: HWND,
+    progress: TaskbarProgress,
+) -> windows::core::Result<()> {
+    let (state, value): (TBPFLAG, _) = match progress {
+        TaskbarProgress::None => (TBPF_NOPROGRESS, None),
+        TaskbarProgress::Indeterminate => (TBPF_INDETERMINATE, None),
+        TaskbarProgress::Normal(value) => (TBPF_NORMAL, Some(value)),
+        TaskbarProgress::Paused(value) => (TBPF_PAUSED, Some(value)),
+        TaskbarProgress::Error(value) => (TBPF_ERROR, Some(value)),
+    };
+    // SAFETY: `hwnd` is the handle of a window that's still open.
+    unsafe {
+        if let Some(value) = value {
+            let completed = (value.clamp(0.0, 1.0) * PROGRESS_TOTAL as f32) as u64;
+            taskbar.SetProgressValue(hwnd, completed, PROGRESS_TOTAL)?;
+        }
+        taskbar.SetProgressState(hwnd, state)
+    }
+}
+
+/// Applies the changes of the [`TaskbarProgress`] of the windows.
+///
+/// The progress that was last applied to each window is kept, so that the progress of windows whose
+/// taskbar button wasn't created yet is applied once it is, and the progress of windows whose
+/// component was removed is hidden.
+fn update_taskbar_progress(
+    windows: Query<(Entity, &TaskbarProgress)>,
+    mut applied: Local<HashMap<Entity, TaskbarProgress>>,
+    _non_send_marker: NonSendMarker,
+) {
+    if windows.is_empty() && applied.is_empty() {
+        return;
+    }
+
+    TASKBAR.with(|taskbar| {
+        let Some(taskbar) = taskbar.get_or_init(create_taskbar) else {
+            return;
+        };
+        WINIT_WINDOWS.with_borrow(|winit_windows| {
+            let removed_windows = applied
+                .iter()
+                .filter(|(entity, _)| !windows.contains(**entity))
+                .map(|(entity, _)| (*entity, TaskbarProgress::None));
+            let changes: Vec<_> = windows
+                .iter()
+                .map(|(entity, progress)| (entity, *progress))
+                .chain(removed_windows)
+                .filter(|(entity, progress)| {
+                    applied.get(entity).copied().unwrap_or_default() != *progress
+                })
+                .collect();
+
+            for (entity, progress) in changes {
+                let Some(winit_window) = winit_windows.get_window(entity) else {
+                    // The window was closed, or isn't created yet.
+                    if progress == TaskbarProgress::None {
+                        applied.remove(&entity);
+                    }
+                    continue;
+                };
+                let Ok(RawWindowHandle::Win32(handle)) =
+                    winit_window.window_handle().map(|handle| handle.as_raw())
+                else {
+                    continue;
+                };
+                let hwnd = HWND(handle.hwnd.get() as *mut _);
+                if set_progress(taskbar, hwnd, progress).is_ok() {
+                    if progress == TaskbarProgress::None {
+                        applied.remove(&entity);
+                    } else {
+                        applied.insert(entity, progress);
+                    }
+                }
+            }
+        });
+    });
+}
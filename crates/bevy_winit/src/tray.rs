@@ -0,0 +1,199 @@
+//! System tray icons for [`TrayIcon`], with `tray-icon`.
+
+use alloc::string::String;
+use bevy_app::{App, Last, Plugin, PreUpdate};
+use bevy_ecs::{prelude::*, system::NonSendMarker};
+use bevy_input::mouse::MouseButton;
+use bevy_platform::{collections::HashMap, sync::Mutex};
+use bevy_window::{TrayIcon, TrayIconClicked, TrayMenuItem, TrayMenuItemClicked};
+use core::cell::RefCell;
+use tracing::warn;
+use tray_icon::{
+    menu::{Menu, MenuEvent, MenuId, MenuItem, PredefinedMenuItem},
+    Icon, MouseButtonState, TrayIconBuilder, TrayIconEvent, TrayIconId,
+};
+use winit::event_loop::EventLoopProxy;
+
+use crate::{EventLoopProxyWrapper, WakeUp};
+
+/// The events of the tray icons that weren't handled yet.
+static TRAY_EVENTS: Mutex<Vec<TrayEvent>> = Mutex::new(Vec::new());
+
+/// Wakes up the event loop when an event of a tray icon is received, which is only available when
+/// the [`WinitPlugin`](crate::WinitPlugin) uses the default [`WakeUp`] event.
+static EVENT_LOOP_PROXY: Mutex<Option<EventLoopProxy<WakeUp>>> = Mutex::new(None);
+
+thread_local! {
+    // Tray icons can only be used from the thread that created them.
+    static TRAY_ICONS: RefCell<TrayIcons> = RefCell::default();
+}
+
+/// Shows the [`TrayIcon`]s in the system tray.
+pub(crate) struct WinitTrayIconPlugin;
+
+impl Plugin for WinitTrayIconPlugin {
+    fn build(&self, app: &mut App) {
+        // The events are sent from the event loop, so they are forwarded to the app and wake it up
+        // if it's waiting for events.
+        TrayIconEvent::set_event_handler(Some(|event| {
+            if let TrayIconEvent::Click {
+                id,
+                button,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                send_tray_event(TrayEvent::Icon(id, button));
+            }
+        }));
+        MenuEvent::set_event_handler(Some(|event: MenuEvent| {
+            send_tray_event(TrayEvent::MenuItem(event.id));
+        }));
+
+        app.add_systems(PreUpdate, trigger_tray_events)
+            .add_systems(Last, update_tray_icons);
+    }
+}
+
+/// An event of a tray icon or of its menu.
+enum TrayEvent {
+    Icon(TrayIconId, tray_icon::MouseButton),
+    MenuItem(MenuId),
+}
+
+fn send_tray_event(event: TrayEvent) {
+    TRAY_EVENTS.lock().unwrap().push(event);
+    if let Some(proxy) = &*EVENT_LOOP_PROXY.lock().unwrap() {
+        let _ = proxy.send_event(WakeUp);
+    }
+}
+
+/// The tray icons that are shown, with the entities of their icons and menu items.
+#[derive(Default)]
+struct TrayIcons {
+    icons: HashMap<Entity, tray_icon::TrayIcon>,
+    entities: HashMap<TrayIconId, Entity>,
+    menu_items: HashMap<MenuId, (Entity, String)>,
+}
+
+impl TrayIcons {
+    fn remove(&mut self, entity: Entity) {
+        if let Some(tray_icon) = self.icons.remove(&entity) {
+            self.entities.remove(tray_icon.id());
+        }
+        self.menu_items
+            .retain(|_, (item_entity, _)| *item_entity != entity);
+    }
+}
+
+/// Builds the menu of a [`TrayIcon`], keeping the identifiers of its items.
+fn build_menu(
+    entity: Entity,
+    tray_icon: &TrayIcon,
+    menu_items: &mut HashMap<MenuId, (Entity, String)>,
+) -> Option<Menu> {
+    if tray_icon.menu.is_empty() {
+        return None;
+    }
+
+    let menu = Menu::new();
+    for item in &tray_icon.menu {
+        let result = match item {
+            TrayMenuItem::Item { id, label, enabled } => {
+                // Menu item identifiers must be unique across all menus.
+                let menu_id = MenuId::new(format!("{}/{}", entity.to_bits(), id));
+                menu_items.insert(menu_id.clone(), (entity, id.clone()));
+                menu.append(&MenuItem::with_id(menu_id, label, *enabled, None))
+            }
+            TrayMenuItem::Separator => menu.append(&PredefinedMenuItem::separator()),
+        };
+        if let Err(err) = result {
+            warn!("Failed to add an item to the menu of a tray icon. {}", err);
+        }
+    }
+    Some(menu)
+}
+
+/// Creates, updates and removes the tray icons of the [`TrayIcon`]s that changed.
+fn update_tray_icons(
+    changed: Query<(Entity, &TrayIcon), Changed<TrayIcon>>,
+    mut removed: RemovedComponents<TrayIcon>,
+    _non_send_marker: NonSendMarker,
+) {
+    TRAY_ICONS.with_borrow_mut(|tray_icons| {
+        for entity in removed.read() {
+            tray_icons.remove(entity);
+        }
+
+        for (entity, tray_icon) in &changed {
+            tray_icons.remove(entity);
+
+            let mut builder = TrayIconBuilder::new()
+                .with_id(entity.to_bits().to_string())
+                .with_menu_on_left_click(false);
+            if let Some(image) = &tray_icon.icon {
+                match Icon::from_rgba(image.rgba.clone(), image.width, image.height) {
+                    Ok(icon) => builder = builder.with_icon(icon),
+                    Err(err) => warn!("Invalid tray icon image. {}", err),
+                }
+            }
+            if let Some(tooltip) = &tray_icon.tooltip {
+                builder = builder.with_tooltip(tooltip);
+            }
+            if let Some(menu) = build_menu(entity, tray_icon, &mut tray_icons.menu_items) {
+                builder = builder.with_menu(Box::new(menu));
+            }
+
+            match builder.build() {
+                Ok(built) => {
+                    tray_icons.entities.insert(built.id().clone(), entity);
+                    tray_icons.icons.insert(entity, built);
+                }
+                Err(err) => warn!("Failed to create a tray icon. {}", err),
+            }
+        }
+    });
+}
+
+/// Triggers the [`TrayIconClicked`] and [`TrayMenuItemClicked`] events that were received.
+fn trigger_tray_events(
+    proxy: Option<Res<EventLoopProxyWrapper<WakeUp>>>,
+    mut commands: Commands,
+    _non_send_marker: NonSendMarker,
+) {
+    if let Some(proxy) = proxy.filter(|proxy| proxy.is_added()) {
+        *EVENT_LOOP_PROXY.lock().unwrap() = Some((**proxy).clone());
+    }
+
+    let events = core::mem::take(&mut *TRAY_EVENTS.lock().unwrap());
+    if events.is_empty() {
+        return;
+    }
+
+    TRAY_ICONS.with_borrow(|tray_icons| {
+        for event in events {
+            match event {
+                TrayEvent::Icon(id, button) => {
+                    let Some(&entity) = tray_icons.entities.get(&id) else {
+                        continue;
+                    };
+                    let button = match button {
+                        tray_icon::MouseButton::Left => MouseButton::Left,
+                        tray_icon::MouseButton::Right => MouseButton::Right,
+                        tray_icon::MouseButton::Middle => MouseButton::Middle,
+                    };
+                    commands.trigger(TrayIconClicked { entity, button });
+                }
+                TrayEvent::MenuItem(menu_id) => {
+                    let Some((entity, id)) = tray_icons.menu_items.get(&menu_id) else {
+                        continue;
+                    };
+                    commands.trigger(TrayMenuItemClicked {
+                        entity: *entity,
+                        id: id.clone(),
+                    });
+                }
+            }
+        }
+    });
+}
@@ -1,7 +1,7 @@
 //! Example usage of the `https` asset source to load assets from the web.
 //!
 //! Run with the feature `https`, and optionally `web_asset_cache`
-//! for a simple caching mechanism that never invalidates.
+//! for a simple caching mechanism that revalidates assets using their `ETag`.
 //!
 use bevy::{asset::io::web::WebAssetPlugin, prelude::*};
 
@@ -9,6 +9,7 @@ fn main() {
     App::new()
         .add_plugins(DefaultPlugins.set(WebAssetPlugin {
             silence_startup_warning: true,
+            ..default()
         }))
         .add_systems(Startup, setup)
         .run();
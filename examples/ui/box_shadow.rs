@@ -177,6 +177,7 @@ fn setup(
                     y_offset: px(shadow.y_offset),
                     spread_radius: px(shadow.spread),
                     blur_radius: px(shadow.blur),
+                    inset: false,
                 }]),
                 ShadowNode,
             )
@@ -496,6 +497,7 @@ fn make_shadow(color: Color, x_offset: f32, y_offset: f32, spread: f32, blur: f3
         y_offset: px(y_offset),
         spread_radius: px(spread),
         blur_radius: px(blur),
+        inset: false,
     }
 }
 
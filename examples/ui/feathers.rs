@@ -4,10 +4,10 @@ use bevy::{
     color::palettes,
     feathers::{
         controls::{
-            button, checkbox, color_plane, color_slider, color_swatch, radio, slider,
-            toggle_switch, ButtonProps, ButtonVariant, ColorChannel, ColorPlane, ColorPlaneValue,
-            ColorSlider, ColorSliderProps, ColorSwatch, ColorSwatchValue, SliderBaseColor,
-            SliderProps,
+            button, checkbox, color_plane, color_slider, color_swatch, dropdown, progress_bar,
+            radio, slider, toggle_switch, ButtonProps, ButtonVariant, ColorChannel, ColorPlane,
+            ColorPlaneValue, ColorSlider, ColorSliderProps, ColorSwatch, ColorSwatchValue,
+            DropdownProps, ProgressBarProps, SliderBaseColor, SliderProps,
         },
         dark_theme::create_dark_theme,
         rounded_corners::RoundedCorners,
@@ -18,8 +18,8 @@ use bevy::{
     prelude::*,
     ui::{Checked, InteractionDisabled},
     ui_widgets::{
-        checkbox_self_update, observe, slider_self_update, Activate, RadioButton, RadioGroup,
-        SliderPrecision, SliderStep, SliderValue, ValueChange,
+        checkbox_self_update, dropdown_self_update, observe, slider_self_update, Activate,
+        RadioButton, RadioGroup, SliderPrecision, SliderStep, SliderValue, ValueChange,
     },
 };
 
@@ -294,6 +294,23 @@ fn demo_root() -> impl Bundle {
                     ),
                     observe(slider_self_update)
                 ),
+                (
+                    dropdown(
+                        DropdownProps {
+                            options: vec!["Low".into(), "Medium".into(), "High".into()],
+                            value: 1,
+                        },
+                        (),
+                    ),
+                    observe(dropdown_self_update)
+                ),
+                progress_bar(
+                    ProgressBarProps {
+                        progress: Some(0.4)
+                    },
+                    ()
+                ),
+                progress_bar(ProgressBarProps { progress: None }, ()),
                 (
                     Node {
                         display: Display::Flex,
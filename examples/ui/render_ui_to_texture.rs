@@ -1,36 +1,24 @@
-//! Shows how to render UI to a texture. Useful for displaying UI in 3D space.
+//! Shows how to render UI to a texture, and display it on a surface in 3D space that can be
+//! interacted with like any other UI.
 
-use std::f32::consts::PI;
-
-use bevy::picking::PickingSystems;
 use bevy::{
-    asset::{uuid::Uuid, RenderAssetUsages},
     camera::RenderTarget,
     color::palettes::css::{BLUE, GRAY, RED},
-    input::ButtonState,
-    picking::{
-        backend::ray::RayMap,
-        pointer::{Location, PointerAction, PointerId, PointerInput},
-    },
     prelude::*,
-    render::render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
-    window::{PrimaryWindow, WindowEvent},
+    render::render_resource::TextureFormat,
 };
 
-const CUBE_POINTER_ID: PointerId = PointerId::Custom(Uuid::from_u128(90870987));
-
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
         .add_systems(Startup, setup)
         .add_systems(Update, rotator_system)
-        .add_systems(First, drive_diegetic_pointer.in_set(PickingSystems::Input))
         .run();
 }
 
-// Marks the cube, to which the UI texture is applied.
+// Marks the screen, to which the UI texture is applied.
 #[derive(Component)]
-struct Cube;
+struct Screen;
 
 fn setup(
     mut commands: Commands,
@@ -38,24 +26,8 @@ fn setup(
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut images: ResMut<Assets<Image>>,
 ) {
-    let size = Extent3d {
-        width: 512,
-        height: 512,
-        ..default()
-    };
-
     // This is the texture that will be rendered to.
-    let mut image = Image::new_fill(
-        size,
-        TextureDimension::D2,
-        &[0, 0, 0, 0],
-        TextureFormat::Bgra8UnormSrgb,
-        RenderAssetUsages::default(),
-    );
-    // You need to set these texture usage flags in order to use the image as a render target
-    image.texture_descriptor.usage =
-        TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST | TextureUsages::RENDER_ATTACHMENT;
-
+    let image = Image::new_target_texture(512, 512, TextureFormat::Bgra8UnormSrgb);
     let image_handle = images.add(image);
 
     // Light
@@ -131,7 +103,7 @@ fn setup(
                 });
         });
 
-    let mesh_handle = meshes.add(Cuboid::default());
+    let mesh_handle = meshes.add(Rectangle::new(SCREEN_SIZE.x, SCREEN_SIZE.y));
 
     // This material has the texture that has been rendered.
     let material_handle = materials.add(StandardMaterial {
@@ -141,12 +113,14 @@ fn setup(
         ..default()
     });
 
-    // Cube with material containing the rendered UI texture.
+    // Screen with material containing the rendered UI texture. `WorldUiSurface` sends the pointer
+    // input on the screen to the UI rendered by the texture camera.
     commands.spawn((
         Mesh3d(mesh_handle),
         MeshMaterial3d(material_handle),
-        Transform::from_xyz(0.0, 0.0, 1.5).with_rotation(Quat::from_rotation_x(PI)),
-        Cube,
+        WorldUiSurface::new(texture_camera, SCREEN_SIZE),
+        Transform::from_xyz(0.0, 0.0, 1.5),
+        Screen,
     ));
 
     // The main pass camera.
@@ -154,96 +128,15 @@ fn setup(
         Camera3d::default(),
         Transform::from_xyz(0.0, 0.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
     ));
-
-    commands.spawn(CUBE_POINTER_ID);
-}
-
-const ROTATION_SPEED: f32 = 0.1;
-
-fn rotator_system(time: Res<Time>, mut query: Query<&mut Transform, With<Cube>>) {
-    for mut transform in &mut query {
-        transform.rotate_x(1.0 * time.delta_secs() * ROTATION_SPEED);
-        transform.rotate_y(0.7 * time.delta_secs() * ROTATION_SPEED);
-    }
 }
 
-/// Because bevy has no way to know how to map a mouse input to the UI texture, we need to write a
-/// system that tells it there is a pointer on the UI texture. We cast a ray into the scene and find
-/// the UV (2D texture) coordinates of the raycast hit. This UV coordinate is effectively the same
-/// as a pointer coordinate on a 2D UI rect.
-fn drive_diegetic_pointer(
-    mut cursor_last: Local<Vec2>,
-    mut raycast: MeshRayCast,
-    rays: Res<RayMap>,
-    cubes: Query<&Mesh3d, With<Cube>>,
-    ui_camera: Query<&Camera, With<Camera2d>>,
-    primary_window: Query<Entity, With<PrimaryWindow>>,
-    windows: Query<(Entity, &Window)>,
-    images: Res<Assets<Image>>,
-    manual_texture_views: Res<ManualTextureViews>,
-    mut window_events: MessageReader<WindowEvent>,
-    mut pointer_inputs: MessageWriter<PointerInput>,
-) -> Result {
-    // Get the size of the texture, so we can convert from dimensionless UV coordinates that span
-    // from 0 to 1, to pixel coordinates.
-    let target = ui_camera
-        .single()?
-        .target
-        .normalize(primary_window.single().ok())
-        .unwrap();
-    let target_info = target
-        .get_render_target_info(windows, &images, &manual_texture_views)
-        .unwrap();
-    let size = target_info.physical_size.as_vec2();
+const SCREEN_SIZE: Vec2 = Vec2::new(2.0, 2.0);
 
-    // Find raycast hits and update the virtual pointer.
-    let raycast_settings = MeshRayCastSettings {
-        visibility: RayCastVisibility::VisibleInView,
-        filter: &|entity| cubes.contains(entity),
-        early_exit_test: &|_| false,
-    };
-    for (_id, ray) in rays.iter() {
-        for (_cube, hit) in raycast.cast_ray(*ray, &raycast_settings) {
-            let position = size * hit.uv.unwrap();
-            if position != *cursor_last {
-                pointer_inputs.write(PointerInput::new(
-                    CUBE_POINTER_ID,
-                    Location {
-                        target: target.clone(),
-                        position,
-                    },
-                    PointerAction::Move {
-                        delta: position - *cursor_last,
-                    },
-                ));
-                *cursor_last = position;
-            }
-        }
-    }
+const ROTATION_SPEED: f32 = 0.5;
 
-    // Pipe pointer button presses to the virtual pointer on the UI texture.
-    for window_event in window_events.read() {
-        if let WindowEvent::MouseButtonInput(input) = window_event {
-            let button = match input.button {
-                MouseButton::Left => PointerButton::Primary,
-                MouseButton::Right => PointerButton::Secondary,
-                MouseButton::Middle => PointerButton::Middle,
-                _ => continue,
-            };
-            let action = match input.state {
-                ButtonState::Pressed => PointerAction::Press(button),
-                ButtonState::Released => PointerAction::Release(button),
-            };
-            pointer_inputs.write(PointerInput::new(
-                CUBE_POINTER_ID,
-                Location {
-                    target: target.clone(),
-                    position: *cursor_last,
-                },
-                action,
-            ));
-        }
+fn rotator_system(time: Res<Time>, mut query: Query<&mut Transform, With<Screen>>) {
+    for mut transform in &mut query {
+        let angle = ops::sin(time.elapsed_secs() * ROTATION_SPEED) * 0.6;
+        transform.rotation = Quat::from_rotation_y(angle);
     }
-
-    Ok(())
 }
@@ -1,5 +1,6 @@
 //! This example illustrates how to create buttons with their textures sliced
-//! and kept in proportion instead of being stretched by the button dimensions
+//! and kept in proportion instead of being stretched by the button dimensions, and how to draw
+//! a sliced texture over the border of a node.
 
 use bevy::{
     color::palettes::css::{GOLD, ORANGE},
@@ -91,5 +92,19 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                         TextColor(Color::srgb(0.9, 0.9, 0.9)),
                     ));
             }
+
+            // The same texture drawn as a border image, only covering the node's border
+            parent.spawn((
+                Node {
+                    width: px(200),
+                    height: px(200),
+                    border: UiRect::all(px(22)),
+                    border_radius: BorderRadius::all(px(40)),
+                    margin: UiRect::all(px(20)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.1, 0.1, 0.2)),
+                BorderImage::new(image.clone(), BorderRect::all(22.0)),
+            ));
         });
 }
@@ -0,0 +1,130 @@
+//! This example illustrates a titlebar and resize borders drawn with Bevy UI, for a window without
+//! decorations.
+//!
+//! UI nodes with a [`WindowHitTestRegion`] move or resize their window when the left mouse button
+//! is pressed on them, by calling `Window::start_drag_move()` or `Window::start_drag_resize()`.
+//! Other UI nodes above them, like the close button of the titlebar, still receive the pointer.
+
+use bevy::{
+    math::CompassOctant,
+    prelude::*,
+    ui_widgets::{observe, Activate, Button, UiWidgetsPlugins, WindowHitTestRegion},
+};
+
+const TITLEBAR_HEIGHT: f32 = 32.0;
+const BORDER_WIDTH: f32 = 6.0;
+const TITLEBAR_COLOR: Color = Color::srgb(0.12, 0.12, 0.16);
+const CLOSE_BUTTON_COLOR: Color = Color::srgb(0.75, 0.2, 0.2);
+
+fn main() {
+    App::new()
+        .add_plugins((
+            DefaultPlugins.set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: "Custom titlebar".into(),
+                    decorations: false,
+                    ..default()
+                }),
+                ..default()
+            }),
+            UiWidgetsPlugins,
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+
+    commands.spawn((
+        Node {
+            width: percent(100),
+            height: percent(100),
+            flex_direction: FlexDirection::Column,
+            ..default()
+        },
+        children![
+            titlebar(),
+            (
+                Node {
+                    flex_grow: 1.0,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                children![Text::new(
+                    "Drag the titlebar to move the window,\nand its borders to resize it."
+                )],
+            ),
+        ],
+    ));
+
+    // The resize borders are spawned last, to be above the titlebar in its corners.
+    use CompassOctant::*;
+    let border = px(BORDER_WIDTH);
+    let corner = px(BORDER_WIDTH * 2.0);
+    let (zero, auto) = (px(0), Val::Auto);
+    let borders = [
+        // Direction, top, bottom, left, right, width, height
+        (North, zero, auto, zero, zero, auto, border),
+        (South, auto, zero, zero, zero, auto, border),
+        (West, zero, zero, zero, auto, border, auto),
+        (East, zero, zero, auto, zero, border, auto),
+        (NorthWest, zero, auto, zero, auto, corner, corner),
+        (NorthEast, zero, auto, auto, zero, corner, corner),
+        (SouthWest, auto, zero, zero, auto, corner, corner),
+        (SouthEast, auto, zero, auto, zero, corner, corner),
+    ];
+    for (direction, top, bottom, left, right, width, height) in borders {
+        commands.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top,
+                bottom,
+                left,
+                right,
+                width,
+                height,
+                ..default()
+            },
+            WindowHitTestRegion::ResizeBorder(direction),
+        ));
+    }
+}
+
+fn titlebar() -> impl Bundle {
+    (
+        Node {
+            height: px(TITLEBAR_HEIGHT),
+            padding: UiRect::horizontal(px(10)),
+            justify_content: JustifyContent::SpaceBetween,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(TITLEBAR_COLOR),
+        WindowHitTestRegion::Titlebar,
+        children![
+            (
+                Text::new("Custom titlebar"),
+                TextFont::from_font_size(14.0),
+                // Let the presses on the title reach the titlebar.
+                Pickable::IGNORE,
+            ),
+            (
+                Node {
+                    width: px(20),
+                    height: px(20),
+                    border_radius: BorderRadius::MAX,
+                    ..default()
+                },
+                Button,
+                BackgroundColor(CLOSE_BUTTON_COLOR),
+                observe(close_window),
+            ),
+        ],
+    )
+}
+
+fn close_window(_activate: On<Activate>, mut exit: MessageWriter<AppExit>) {
+    exit.write(AppExit::Success);
+}